@@ -0,0 +1,522 @@
+//! Read-only REST API over prompts, memos, issues and workflow runs
+//!
+//! The API is served from the same storage the MCP server uses, for
+//! dashboards and tools that can't speak MCP. Only `GET` requests are
+//! accepted and every response is JSON:
+//!
+//! - `GET /health`, the same report as the MCP `health` tool
+//! - `GET /prompts?q=<query>` and `GET /prompts/<name>`, only the prompts MCP
+//!   clients see
+//! - `GET /memos?q=<query>` and `GET /memos/<id>`
+//! - `GET /issues?q=<query>&status=open|completed` and `GET /issues/<name>`
+//! - `GET /runs?workflow=<name>` and `GET /runs/<id>`
+
+use crate::exit_codes::{EXIT_SUCCESS, EXIT_WARNING};
+use serde_json::{json, Value};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use swissarmyhammer::mcp::McpServer;
+use swissarmyhammer::memoranda::MemoId;
+use swissarmyhammer::workflow::{WorkflowRun, WorkflowRunId};
+use swissarmyhammer::{Prompt, PromptLibrary, SwissArmyHammerError};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Semaphore;
+
+/// Largest request head read before the request is refused
+const MAX_REQUEST_HEAD: usize = 16 * 1024;
+
+/// How long a client has to send its request head
+const REQUEST_HEAD_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Connections answered at once; further clients wait to be accepted
+const MAX_CONNECTIONS: usize = 64;
+
+/// A request the API can answer
+#[derive(Debug, PartialEq, Eq)]
+enum Route {
+    Health,
+    Prompts {
+        query: Option<String>,
+    },
+    Prompt(String),
+    Memos {
+        query: Option<String>,
+    },
+    Memo(String),
+    Issues {
+        query: Option<String>,
+        completed: Option<bool>,
+    },
+    Issue(String),
+    Runs {
+        workflow: Option<String>,
+    },
+    Run(String),
+}
+
+/// An error response
+#[derive(Debug, PartialEq, Eq)]
+struct ApiError {
+    status: u16,
+    message: String,
+}
+
+impl ApiError {
+    fn new(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<SwissArmyHammerError> for ApiError {
+    fn from(error: SwissArmyHammerError) -> Self {
+        let status = match error {
+            SwissArmyHammerError::PromptNotFound(_)
+            | SwissArmyHammerError::MemoNotFound(_)
+            | SwissArmyHammerError::IssueNotFound(_)
+            | SwissArmyHammerError::WorkflowRunNotFound(_) => 404,
+            _ => 500,
+        };
+        Self::new(status, error.to_string())
+    }
+}
+
+/// Serve the API on `addr` until the process is stopped
+pub async fn run_api_server(addr: SocketAddr) -> i32 {
+    let library = PromptLibrary::from_config();
+    let server = match McpServer::new(library) {
+        Ok(server) => server,
+        Err(e) => {
+            tracing::error!("Failed to create API server: {}", e);
+            return EXIT_WARNING;
+        }
+    };
+    if let Err(e) = server.initialize().await {
+        tracing::error!("Failed to initialize API server: {}", e);
+        return EXIT_WARNING;
+    }
+
+    let listener = match TcpListener::bind(addr).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            tracing::error!("Failed to listen on {}: {}", addr, e);
+            return EXIT_WARNING;
+        }
+    };
+    tracing::info!("API server listening on http://{}", addr);
+
+    let connections = Arc::new(Semaphore::new(MAX_CONNECTIONS));
+    loop {
+        let permit = tokio::select! {
+            permit = connections.clone().acquire_owned() => {
+                permit.expect("the connection semaphore is never closed")
+            }
+            _ = tokio::signal::ctrl_c() => break,
+        };
+        tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok((stream, peer)) => {
+                    let server = server.clone();
+                    tokio::spawn(async move {
+                        let result = handle_connection(&server, stream, REQUEST_HEAD_TIMEOUT).await;
+                        if let Err(e) = result {
+                            tracing::debug!("API connection from {} failed: {}", peer, e);
+                        }
+                        drop(permit);
+                    });
+                }
+                Err(e) => tracing::warn!("Failed to accept API connection: {}", e),
+            },
+            _ = tokio::signal::ctrl_c() => break,
+        }
+    }
+
+    tracing::info!("API server shutting down");
+    EXIT_SUCCESS
+}
+
+/// Answer a single request, then close the connection
+///
+/// A client that doesn't send its request head within `head_timeout` gets a
+/// 408 so it can't hold the connection open.
+async fn handle_connection(
+    server: &McpServer,
+    mut stream: TcpStream,
+    head_timeout: Duration,
+) -> std::io::Result<()> {
+    let request = match tokio::time::timeout(head_timeout, read_request_line(&mut stream)).await {
+        Ok(request) => request?,
+        Err(_) => {
+            let body = json!({ "error": "Timed out reading the request" });
+            return write_response(&mut stream, 408, &body).await;
+        }
+    };
+    let (status, body) = match request {
+        Some((method, target)) => {
+            tracing::debug!("API request {} {}", method, target);
+            let result = match route(&method, &target) {
+                Ok(route) => respond(server, route).await,
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(body) => (200, body),
+                Err(e) => (e.status, json!({ "error": e.message })),
+            }
+        }
+        None => (400, json!({ "error": "Malformed request" })),
+    };
+    write_response(&mut stream, status, &body).await
+}
+
+/// Method and target of the request, or `None` for a malformed request
+async fn read_request_line(stream: &mut TcpStream) -> std::io::Result<Option<(String, String)>> {
+    let mut head = Vec::new();
+    let mut buf = [0u8; 1024];
+    while !head.windows(4).any(|window| window == b"\r\n\r\n") {
+        if head.len() > MAX_REQUEST_HEAD {
+            return Ok(None);
+        }
+        let read = stream.read(&mut buf).await?;
+        if read == 0 {
+            break;
+        }
+        head.extend_from_slice(&buf[..read]);
+    }
+
+    let head = String::from_utf8_lossy(&head);
+    let mut parts = head.lines().next().unwrap_or_default().split_whitespace();
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(method), Some(target), Some(version)) if version.starts_with("HTTP/") => {
+            Ok(Some((method.to_string(), target.to_string())))
+        }
+        _ => Ok(None),
+    }
+}
+
+async fn write_response(stream: &mut TcpStream, status: u16, body: &Value) -> std::io::Result<()> {
+    let body = serde_json::to_string_pretty(body).unwrap_or_else(|_| "{}".to_string());
+    let response = format!(
+        "HTTP/1.1 {status} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        reason_phrase(status),
+        body.len()
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        408 => "Request Timeout",
+        _ => "Internal Server Error",
+    }
+}
+
+/// Route a request target such as `/issues?status=open`
+fn route(method: &str, target: &str) -> Result<Route, ApiError> {
+    if method != "GET" {
+        return Err(ApiError::new(405, "Only GET requests are supported"));
+    }
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let params: Vec<(String, String)> = url::form_urlencoded::parse(query.as_bytes())
+        .into_owned()
+        .collect();
+    let param = |name: &str| {
+        params
+            .iter()
+            .find(|(key, value)| key == name && !value.is_empty())
+            .map(|(_, value)| value.clone())
+    };
+    let segments: Vec<String> = path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(percent_decode)
+        .collect();
+
+    let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+    match segments.as_slice() {
+        ["health"] => Ok(Route::Health),
+        ["prompts"] => Ok(Route::Prompts { query: param("q") }),
+        ["prompts", name] => Ok(Route::Prompt(name.to_string())),
+        ["memos"] => Ok(Route::Memos { query: param("q") }),
+        ["memos", id] => Ok(Route::Memo(id.to_string())),
+        ["issues"] => {
+            let completed = match param("status").as_deref() {
+                None | Some("all") => None,
+                Some("open") => Some(false),
+                Some("completed") => Some(true),
+                Some(other) => {
+                    return Err(ApiError::new(
+                        400,
+                        format!("Unknown issue status '{other}', expected open, completed or all"),
+                    ))
+                }
+            };
+            Ok(Route::Issues {
+                query: param("q"),
+                completed,
+            })
+        }
+        ["issues", name] => Ok(Route::Issue(name.to_string())),
+        ["runs"] => Ok(Route::Runs {
+            workflow: param("workflow"),
+        }),
+        ["runs", id] => Ok(Route::Run(id.to_string())),
+        _ => Err(ApiError::new(404, format!("No such endpoint: {path}"))),
+    }
+}
+
+/// Decode `%XX` escapes in a path segment, leaving invalid escapes as they are
+fn percent_decode(segment: &str) -> String {
+    let bytes = segment.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = match bytes[i] {
+            b'%' => bytes
+                .get(i + 1..i + 3)
+                .and_then(|hex| std::str::from_utf8(hex).ok())
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok()),
+            _ => None,
+        };
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Whether a prompt is served, the same prompts MCP clients can list and get
+fn is_served(prompt: &Prompt) -> bool {
+    !prompt.is_partial() && prompt.visibility().is_exposed_to_mcp()
+}
+
+async fn respond(server: &McpServer, route: Route) -> Result<Value, ApiError> {
+    let to_json = |value: serde_json::Result<Value>| {
+        value.map_err(|e| ApiError::new(500, format!("Failed to serialize response: {e}")))
+    };
+
+    match route {
+        Route::Health => to_json(serde_json::to_value(server.tool_context.health.report())),
+        Route::Prompts { query } => {
+            let library = server.library().read().await;
+            let prompts = match query {
+                Some(query) => library.search(&query)?,
+                None => library.list()?,
+            };
+            let prompts: Vec<Value> = prompts
+                .iter()
+                .filter(|prompt| is_served(prompt))
+                .map(|prompt| {
+                    json!({
+                        "name": prompt.name,
+                        "description": prompt.description,
+                        "category": prompt.category,
+                        "tags": prompt.tags,
+                        "deprecated": prompt.is_deprecated(),
+                    })
+                })
+                .collect();
+            Ok(Value::Array(prompts))
+        }
+        Route::Prompt(name) => {
+            let prompt = server.library().read().await.get(&name)?;
+            // Prompts kept from MCP look like prompts that don't exist
+            if !is_served(&prompt) {
+                return Err(SwissArmyHammerError::PromptNotFound(name).into());
+            }
+            to_json(serde_json::to_value(prompt))
+        }
+        Route::Memos { query } => {
+            let storage = server.tool_context.memo_storage.read().await;
+            let memos = match query {
+                Some(query) => storage.search_memos(&query).await?,
+                None => storage.list_memos().await?,
+            };
+            to_json(serde_json::to_value(memos))
+        }
+        Route::Memo(id) => {
+            let id = MemoId::from_string(id).map_err(|e| ApiError::new(400, e.to_string()))?;
+            let memo = server
+                .tool_context
+                .memo_storage
+                .read()
+                .await
+                .get_memo(&id)
+                .await?;
+            to_json(serde_json::to_value(memo))
+        }
+        Route::Issues { query, completed } => {
+            let query = query.map(|query| query.to_lowercase());
+            let issues: Vec<_> = server
+                .tool_context
+                .issue_storage
+                .read()
+                .await
+                .list_issues()
+                .await?
+                .into_iter()
+                .filter(|issue| completed.map_or(true, |completed| issue.completed == completed))
+                .filter(|issue| {
+                    query.as_ref().map_or(true, |query| {
+                        issue.name.to_lowercase().contains(query)
+                            || issue.content.to_lowercase().contains(query)
+                    })
+                })
+                .collect();
+            to_json(serde_json::to_value(issues))
+        }
+        Route::Issue(name) => {
+            let issue = server
+                .tool_context
+                .issue_storage
+                .read()
+                .await
+                .get_issue(&name)
+                .await?;
+            to_json(serde_json::to_value(issue))
+        }
+        Route::Runs { workflow } => {
+            let mut runs = server.workflow_storage().read().await.list_runs()?;
+            if let Some(workflow) = workflow {
+                runs.retain(|run| run.workflow.name.to_string() == workflow);
+            }
+            runs.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+            Ok(Value::Array(runs.iter().map(run_status).collect()))
+        }
+        Route::Run(id) => {
+            let id = WorkflowRunId::parse(&id).map_err(|e| ApiError::new(400, e))?;
+            let run = server.workflow_storage().read().await.get_run(&id)?;
+            Ok(run_status(&run))
+        }
+    }
+}
+
+/// Status of a run, without its workflow definition
+fn run_status(run: &WorkflowRun) -> Value {
+    json!({
+        "id": run.id.to_string(),
+        "workflow": run.workflow.name.to_string(),
+        "status": run.status,
+        "current_state": run.current_state.to_string(),
+        "started_at": run.started_at,
+        "completed_at": run.completed_at,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_route() {
+        assert_eq!(route("GET", "/health"), Ok(Route::Health));
+        assert_eq!(
+            route("GET", "/prompts?q=code+review"),
+            Ok(Route::Prompts {
+                query: Some("code review".to_string())
+            })
+        );
+        assert_eq!(
+            route("GET", "/prompts/code%2Freview/"),
+            Ok(Route::Prompt("code/review".to_string()))
+        );
+        assert_eq!(
+            route("GET", "/issues?status=open&q="),
+            Ok(Route::Issues {
+                query: None,
+                completed: Some(false)
+            })
+        );
+        assert_eq!(
+            route("GET", "/runs?workflow=deploy"),
+            Ok(Route::Runs {
+                workflow: Some("deploy".to_string())
+            })
+        );
+        assert_eq!(
+            route("GET", "/runs/01K0000000000000000000000"),
+            Ok(Route::Run("01K0000000000000000000000".to_string()))
+        );
+
+        assert_eq!(route("POST", "/issues").unwrap_err().status, 405);
+        assert_eq!(
+            route("GET", "/issues?status=closed").unwrap_err().status,
+            400
+        );
+        assert_eq!(route("GET", "/issues/a/b").unwrap_err().status, 404);
+        assert_eq!(route("GET", "/").unwrap_err().status, 404);
+    }
+
+    #[tokio::test]
+    async fn test_prompts_kept_from_mcp_are_not_served() {
+        let mut library = PromptLibrary::new();
+        library
+            .add(Prompt::new("public_prompt", "Hello {{ name }}"))
+            .unwrap();
+        let mut internal = Prompt::new("internal_prompt", "Internal notes");
+        internal
+            .metadata
+            .insert("visibility".to_string(), json!("private"));
+        library.add(internal).unwrap();
+        let server = McpServer::new(library).unwrap();
+
+        let prompts = respond(&server, Route::Prompts { query: None })
+            .await
+            .unwrap();
+        let names: Vec<&str> = prompts
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|prompt| prompt["name"].as_str())
+            .collect();
+        assert_eq!(names, vec!["public_prompt"]);
+
+        assert!(respond(&server, Route::Prompt("public_prompt".to_string()))
+            .await
+            .is_ok());
+        let error = respond(&server, Route::Prompt("internal_prompt".to_string()))
+            .await
+            .unwrap_err();
+        assert_eq!(error.status, 404);
+    }
+
+    #[tokio::test]
+    async fn test_silent_client_times_out() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let mut client = TcpStream::connect(listener.local_addr().unwrap())
+            .await
+            .unwrap();
+        let (stream, _) = listener.accept().await.unwrap();
+        let server = McpServer::new(PromptLibrary::new()).unwrap();
+
+        handle_connection(&server, stream, Duration::from_millis(50))
+            .await
+            .unwrap();
+        let mut response = String::new();
+        client.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 408 Request Timeout\r\n"));
+    }
+
+    #[test]
+    fn test_percent_decode() {
+        assert_eq!(percent_decode("fix%20login"), "fix login");
+        assert_eq!(percent_decode("a+b"), "a+b");
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("%zz"), "%zz");
+    }
+}
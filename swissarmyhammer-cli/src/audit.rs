@@ -0,0 +1,59 @@
+//! Prompt library health report
+
+use anyhow::Result;
+use colored::*;
+use swissarmyhammer::{AuditFinding, AuditFindingKind, PromptAudit};
+
+use crate::cli::OutputFormat;
+
+/// Report prompts that need cleaning up
+pub fn run_audit_command(max_length: usize, format: OutputFormat) -> Result<()> {
+    let audit = PromptAudit::load(max_length)?;
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&audit)?),
+        OutputFormat::Yaml => print!("{}", serde_yaml::to_string(&audit)?),
+        OutputFormat::Table => display_audit(&audit),
+    }
+    Ok(())
+}
+
+fn display_audit(audit: &PromptAudit) {
+    if audit.is_clean() {
+        println!(
+            "{} {} prompts audited, nothing to clean up",
+            "✓".green(),
+            audit.prompts
+        );
+        return;
+    }
+
+    for kind in AuditFindingKind::ALL {
+        let findings: Vec<&AuditFinding> = audit.of_kind(kind).collect();
+        if findings.is_empty() {
+            continue;
+        }
+        println!("{} ({})", kind.to_string().bold(), findings.len());
+        for finding in findings {
+            println!("  {}", describe(finding));
+        }
+        println!();
+    }
+
+    println!(
+        "{} findings across {} prompts",
+        audit.findings.len(),
+        audit.prompts
+    );
+}
+
+fn describe(finding: &AuditFinding) -> String {
+    let mut line = finding.prompt.clone();
+    if let Some(detail) = &finding.detail {
+        line.push_str(&format!(": {detail}"));
+    }
+    if let Some(path) = &finding.path {
+        line.push_str(&format!(" {}", path.display().to_string().dimmed()));
+    }
+    line
+}
@@ -0,0 +1,637 @@
+//! Snapshots of SwissArmyHammer state
+//!
+//! A backup is a gzipped tar archive named `sah-backup-<timestamp>.tar.gz`,
+//! kept in `.swissarmyhammer/backups` unless another directory is given. Each
+//! component of the state is stored under its own top level directory in the
+//! archive, and `manifest.json` records the SHA-256 checksum of every file so
+//! a damaged archive is refused before anything is restored:
+//!
+//! - `prompts`, `workflows` and `memos` from `.swissarmyhammer`
+//! - `issues` from `./issues`
+//! - `runs` from the workflow run storage in `~/.swissarmyhammer`
+//! - `config`, everything else in `.swissarmyhammer` except backups, issue
+//!   worktrees, logs and the search index
+
+use crate::cli::{BackupComponent, BackupSubcommand, OutputFormat};
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{DateTime, Local};
+use clap::ValueEnum;
+use colored::*;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Component, Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Directory backups are written to and listed from by default
+pub const DEFAULT_BACKUP_DIR: &str = ".swissarmyhammer/backups";
+
+/// Archive entry holding the [`Manifest`]
+const MANIFEST_NAME: &str = "manifest.json";
+
+/// File name prefix of backup archives
+const BACKUP_PREFIX: &str = "sah-backup-";
+
+/// File name suffix of backup archives
+const BACKUP_SUFFIX: &str = ".tar.gz";
+
+/// Version of the manifest format written by this release
+const MANIFEST_VERSION: u32 = 1;
+
+/// Entries of `.swissarmyhammer` that are not part of the `config` component
+const CONFIG_EXCLUDED: &[&str] = &["prompts", "workflows", "memos", "backups", "worktrees"];
+
+/// Contents of a backup, stored in the archive as `manifest.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Manifest {
+    version: u32,
+    created_at: DateTime<Local>,
+    components: Vec<String>,
+    files: Vec<ManifestFile>,
+}
+
+/// A file in a backup
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct ManifestFile {
+    path: String,
+    size: u64,
+    sha256: String,
+}
+
+/// Where the state lives, so tests can point backups at temporary directories
+#[derive(Debug, Clone)]
+struct StatePaths {
+    work_dir: PathBuf,
+    home_dir: PathBuf,
+}
+
+impl StatePaths {
+    fn current() -> Result<Self> {
+        Ok(Self {
+            work_dir: std::env::current_dir()?,
+            home_dir: dirs::home_dir().ok_or_else(|| anyhow!("Cannot find the home directory"))?,
+        })
+    }
+
+    /// Directories backed up for a component, with their prefix in the archive
+    fn sources(&self, component: BackupComponent) -> Vec<(String, PathBuf)> {
+        let local = self.work_dir.join(".swissarmyhammer");
+        let user = self.home_dir.join(".swissarmyhammer");
+        match component {
+            BackupComponent::Prompts => vec![("prompts".into(), local.join("prompts"))],
+            BackupComponent::Workflows => vec![("workflows".into(), local.join("workflows"))],
+            BackupComponent::Memos => vec![("memos".into(), local.join("memos"))],
+            BackupComponent::Issues => vec![("issues".into(), self.work_dir.join("issues"))],
+            // The CLI and the MCP server keep runs in different directories
+            BackupComponent::Runs => vec![
+                ("runs/runs".into(), user.join("runs")),
+                ("runs/workflow-runs".into(), user.join("workflow-runs")),
+            ],
+            BackupComponent::Config => vec![("config".into(), local)],
+        }
+    }
+
+    /// Where a file stored at `archive_path` is restored to
+    fn destination(&self, archive_path: &str) -> Option<PathBuf> {
+        let relative = Path::new(archive_path);
+        if relative
+            .components()
+            .any(|part| !matches!(part, Component::Normal(_)))
+        {
+            return None;
+        }
+        BackupComponent::value_variants()
+            .iter()
+            .flat_map(|component| self.sources(*component))
+            .filter_map(|(prefix, dir)| {
+                let rest = relative.strip_prefix(&prefix).ok()?;
+                (!rest.as_os_str().is_empty()).then(|| (prefix.len(), dir.join(rest)))
+            })
+            .max_by_key(|(prefix_len, _)| *prefix_len)
+            .map(|(_, path)| path)
+    }
+}
+
+fn component_name(component: BackupComponent) -> String {
+    component
+        .to_possible_value()
+        .map(|value| value.get_name().to_string())
+        .unwrap_or_default()
+}
+
+/// Component of a file stored at `archive_path`
+fn component_of(archive_path: &str) -> Option<BackupComponent> {
+    let name = archive_path.split('/').next()?;
+    BackupComponent::from_str(name, false).ok()
+}
+
+/// Components to include, all of them when none are given
+fn selected(only: &[BackupComponent]) -> Vec<BackupComponent> {
+    let mut components = if only.is_empty() {
+        BackupComponent::value_variants().to_vec()
+    } else {
+        only.to_vec()
+    };
+    components.sort();
+    components.dedup();
+    components
+}
+
+/// Whether a path below `.swissarmyhammer` belongs to the `config` component
+fn is_config_file(relative: &Path) -> bool {
+    let Some(Component::Normal(first)) = relative.components().next() else {
+        return false;
+    };
+    let first = first.to_string_lossy();
+    !CONFIG_EXCLUDED.contains(&first.as_ref())
+        && !first.starts_with("semantic.db")
+        && !first.contains(".log")
+}
+
+pub fn run_backup_command(subcommand: BackupSubcommand) -> Result<()> {
+    let paths = StatePaths::current()?;
+    match subcommand {
+        BackupSubcommand::Create { only, dir } => {
+            let dir = dir.unwrap_or_else(|| PathBuf::from(DEFAULT_BACKUP_DIR));
+            let (archive, manifest) = create_backup(&paths, &selected(&only), &dir, Local::now())?;
+            let bytes: u64 = manifest.files.iter().map(|file| file.size).sum();
+            println!(
+                "{} Backed up {} files ({}) from {} to {}",
+                "✓".green(),
+                manifest.files.len(),
+                format_size(bytes),
+                manifest.components.join(", "),
+                archive.display()
+            );
+            Ok(())
+        }
+        BackupSubcommand::List { dir, format } => {
+            let dir = dir.unwrap_or_else(|| PathBuf::from(DEFAULT_BACKUP_DIR));
+            print_backups(&list_backups(&dir)?, format)
+        }
+        BackupSubcommand::Restore {
+            archive,
+            only,
+            dry_run,
+        } => {
+            let archive = resolve_archive(&archive);
+            let restored = restore_backup(&paths, &archive, &selected(&only), dry_run)?;
+            for path in &restored {
+                println!("  {}", path.display());
+            }
+            if dry_run {
+                println!("{} files would be restored", restored.len());
+            } else {
+                println!(
+                    "{} Restored {} files from {}",
+                    "✓".green(),
+                    restored.len(),
+                    archive.display()
+                );
+            }
+            Ok(())
+        }
+    }
+}
+
+/// An archive given by file name is looked for in the default backup directory
+fn resolve_archive(archive: &Path) -> PathBuf {
+    let in_backup_dir = Path::new(DEFAULT_BACKUP_DIR).join(archive);
+    if !archive.exists() && archive.components().count() == 1 && in_backup_dir.exists() {
+        in_backup_dir
+    } else {
+        archive.to_path_buf()
+    }
+}
+
+/// Write a backup of the components to `dir`, returning its path and manifest
+fn create_backup(
+    paths: &StatePaths,
+    components: &[BackupComponent],
+    dir: &Path,
+    now: DateTime<Local>,
+) -> Result<(PathBuf, Manifest)> {
+    fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create backup directory {}", dir.display()))?;
+    let name = format!(
+        "{BACKUP_PREFIX}{}{BACKUP_SUFFIX}",
+        now.format("%Y%m%d-%H%M%S")
+    );
+    let archive = dir.join(name);
+    if archive.exists() {
+        bail!("Backup {} already exists", archive.display());
+    }
+    // Backups written inside a backed up directory must not include themselves
+    let backup_dir = dir.canonicalize()?;
+
+    let partial = archive.with_extension("partial");
+    let mut builder = tar::Builder::new(GzEncoder::new(
+        File::create(&partial)?,
+        Compression::default(),
+    ));
+    let mut files = Vec::new();
+    for component in components {
+        for (prefix, source) in paths.sources(*component) {
+            if !source.is_dir() {
+                continue;
+            }
+            let source = source.canonicalize()?;
+            for entry in WalkDir::new(&source).sort_by_file_name() {
+                let entry = entry?;
+                let path = entry.path();
+                if !entry.file_type().is_file() || path.starts_with(&backup_dir) {
+                    continue;
+                }
+                let relative = path.strip_prefix(&source)?;
+                if *component == BackupComponent::Config && !is_config_file(relative) {
+                    continue;
+                }
+                let archive_path =
+                    relative
+                        .components()
+                        .fold(prefix.clone(), |archive_path, part| {
+                            format!("{archive_path}/{}", part.as_os_str().to_string_lossy())
+                        });
+
+                let content =
+                    fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+                append(&mut builder, &archive_path, &content)?;
+                files.push(ManifestFile {
+                    path: archive_path,
+                    size: content.len() as u64,
+                    sha256: sha256(&content),
+                });
+            }
+        }
+    }
+
+    let manifest = Manifest {
+        version: MANIFEST_VERSION,
+        created_at: now,
+        components: components.iter().map(|c| component_name(*c)).collect(),
+        files,
+    };
+    append(
+        &mut builder,
+        MANIFEST_NAME,
+        &serde_json::to_vec_pretty(&manifest)?,
+    )?;
+    builder.into_inner()?.finish()?;
+    fs::rename(&partial, &archive)?;
+    Ok((archive, manifest))
+}
+
+fn append<W: io::Write>(builder: &mut tar::Builder<W>, path: &str, content: &[u8]) -> Result<()> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(content.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(Local::now().timestamp().max(0) as u64);
+    header.set_cksum();
+    builder.append_data(&mut header, path, content)?;
+    Ok(())
+}
+
+fn sha256(content: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(content))
+}
+
+fn open_archive(archive: &Path) -> Result<tar::Archive<GzDecoder<File>>> {
+    let file =
+        File::open(archive).with_context(|| format!("Failed to open {}", archive.display()))?;
+    Ok(tar::Archive::new(GzDecoder::new(file)))
+}
+
+/// The manifest of an archive and the checksums of the files actually in it
+fn read_archive(archive: &Path) -> Result<(Manifest, BTreeMap<String, ManifestFile>)> {
+    let mut manifest = None;
+    let mut found = BTreeMap::new();
+    for entry in open_archive(archive)?.entries()? {
+        let mut entry = entry?;
+        let path = entry.path()?.to_string_lossy().into_owned();
+        let mut content = Vec::new();
+        entry.read_to_end(&mut content)?;
+        if path == MANIFEST_NAME {
+            manifest = Some(
+                serde_json::from_slice::<Manifest>(&content)
+                    .with_context(|| format!("{} has an unreadable manifest", archive.display()))?,
+            );
+        } else {
+            let file = ManifestFile {
+                path: path.clone(),
+                size: content.len() as u64,
+                sha256: sha256(&content),
+            };
+            found.insert(path, file);
+        }
+    }
+    let manifest = manifest
+        .ok_or_else(|| anyhow!("{} is not a backup, it has no manifest", archive.display()))?;
+    if manifest.version > MANIFEST_VERSION {
+        bail!(
+            "{} was written by a newer release (manifest version {})",
+            archive.display(),
+            manifest.version
+        );
+    }
+    Ok((manifest, found))
+}
+
+/// Restore the components from an archive, returning the restored files
+///
+/// Every file is checked against the manifest before anything is written, so
+/// a damaged archive leaves the current state untouched. Files that exist
+/// are overwritten; files created since the backup are left alone.
+fn restore_backup(
+    paths: &StatePaths,
+    archive: &Path,
+    components: &[BackupComponent],
+    dry_run: bool,
+) -> Result<Vec<PathBuf>> {
+    let (manifest, found) = read_archive(archive)?;
+    let wanted = |path: &str| component_of(path).is_some_and(|c| components.contains(&c));
+
+    let mut problems = Vec::new();
+    let mut restore = Vec::new();
+    for expected in manifest.files.iter().filter(|file| wanted(&file.path)) {
+        match found.get(&expected.path) {
+            None => problems.push(format!("{} is missing", expected.path)),
+            Some(actual) if actual != expected => {
+                problems.push(format!("{} fails its checksum", expected.path))
+            }
+            Some(_) => match paths.destination(&expected.path) {
+                Some(destination) => restore.push((expected.path.clone(), destination)),
+                None => problems.push(format!("{} has an invalid path", expected.path)),
+            },
+        }
+    }
+    for path in found.keys().filter(|path| wanted(path)) {
+        if !manifest.files.iter().any(|file| &file.path == path) {
+            problems.push(format!("{path} is not in the manifest"));
+        }
+    }
+    if !problems.is_empty() {
+        bail!(
+            "{} is damaged, nothing was restored:\n  {}",
+            archive.display(),
+            problems.join("\n  ")
+        );
+    }
+
+    let destinations: BTreeMap<String, PathBuf> = restore.into_iter().collect();
+    if !dry_run {
+        for entry in open_archive(archive)?.entries()? {
+            let mut entry = entry?;
+            let path = entry.path()?.to_string_lossy().into_owned();
+            let Some(destination) = destinations.get(&path) else {
+                continue;
+            };
+            if let Some(parent) = destination.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            let mut file = File::create(destination)
+                .with_context(|| format!("Failed to write {}", destination.display()))?;
+            io::copy(&mut entry, &mut file)?;
+        }
+    }
+    Ok(destinations.into_values().collect())
+}
+
+/// A backup archive found in the backup directory
+#[derive(Debug, Serialize)]
+struct BackupInfo {
+    path: PathBuf,
+    size: u64,
+    created_at: Option<DateTime<Local>>,
+    components: Vec<String>,
+    files: usize,
+    /// Why the archive can't be read, if it can't
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Backups in `dir`, newest first
+fn list_backups(dir: &Path) -> Result<Vec<BackupInfo>> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut backups = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        if !name.starts_with(BACKUP_PREFIX) || !name.ends_with(BACKUP_SUFFIX) {
+            continue;
+        }
+        let size = fs::metadata(&path)?.len();
+        let info = match read_archive(&path) {
+            Ok((manifest, _)) => BackupInfo {
+                path,
+                size,
+                created_at: Some(manifest.created_at),
+                components: manifest.components,
+                files: manifest.files.len(),
+                error: None,
+            },
+            Err(e) => BackupInfo {
+                path,
+                size,
+                created_at: None,
+                components: Vec::new(),
+                files: 0,
+                error: Some(e.to_string()),
+            },
+        };
+        backups.push(info);
+    }
+    // Names embed the creation time, so they sort oldest first
+    backups.sort_by(|a, b| b.path.cmp(&a.path));
+    Ok(backups)
+}
+
+fn print_backups(backups: &[BackupInfo], format: OutputFormat) -> Result<()> {
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(backups)?),
+        OutputFormat::Yaml => print!("{}", serde_yaml::to_string(backups)?),
+        OutputFormat::Table => {
+            if backups.is_empty() {
+                println!("No backups found");
+            }
+            for backup in backups {
+                let name = backup
+                    .path
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy();
+                match &backup.error {
+                    Some(error) => println!("{}  {}", name.bold(), error.red()),
+                    None => println!(
+                        "{}  {} files, {}  {}",
+                        name.bold(),
+                        backup.files,
+                        format_size(backup.size),
+                        backup.components.join(", ").dimmed()
+                    ),
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn format_size(bytes: u64) -> String {
+    match bytes {
+        b if b >= 1024 * 1024 => format!("{:.1} MB", b as f64 / (1024.0 * 1024.0)),
+        b if b >= 1024 => format!("{:.1} KB", b as f64 / 1024.0),
+        b => format!("{b} B"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use tempfile::TempDir;
+
+    fn write(path: &Path, content: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn state(temp_dir: &TempDir) -> StatePaths {
+        let paths = StatePaths {
+            work_dir: temp_dir.path().join("project"),
+            home_dir: temp_dir.path().join("home"),
+        };
+        let local = paths.work_dir.join(".swissarmyhammer");
+        write(&local.join("prompts/review.md"), "review");
+        write(&local.join("memos/notes.md"), "notes");
+        write(&local.join("presets/review.yaml"), "strict: {}");
+        write(&local.join("mcp.log.1"), "log");
+        write(&local.join("worktrees/fix/file.rs"), "worktree");
+        write(&paths.work_dir.join("issues/000001_fix.md"), "# Fix");
+        write(
+            &paths.home_dir.join(".swissarmyhammer/runs/run.yaml"),
+            "run",
+        );
+        paths
+    }
+
+    fn time(second: u32) -> DateTime<Local> {
+        Local.with_ymd_and_hms(2026, 10, 16, 12, 0, second).unwrap()
+    }
+
+    #[test]
+    fn test_backup_and_restore() {
+        let temp_dir = TempDir::new().unwrap();
+        let paths = state(&temp_dir);
+        let backups = paths.work_dir.join(DEFAULT_BACKUP_DIR);
+        let (archive, manifest) = create_backup(&paths, &selected(&[]), &backups, time(0)).unwrap();
+
+        let stored: Vec<&str> = manifest.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(
+            stored,
+            [
+                "prompts/review.md",
+                "memos/notes.md",
+                "issues/000001_fix.md",
+                "runs/runs/run.yaml",
+                "config/presets/review.yaml",
+            ]
+        );
+        assert!(archive.ends_with("sah-backup-20261016-120000.tar.gz"));
+
+        // A second backup into the same directory leaves the first one out
+        let (_, manifest) = create_backup(&paths, &selected(&[]), &backups, time(1)).unwrap();
+        assert_eq!(manifest.files.len(), 5);
+
+        let issue = paths.work_dir.join("issues/000001_fix.md");
+        let memo = paths.work_dir.join(".swissarmyhammer/memos/notes.md");
+        fs::write(&issue, "# Broken").unwrap();
+        fs::remove_file(&memo).unwrap();
+
+        let restored = restore_backup(&paths, &archive, &[BackupComponent::Issues], true).unwrap();
+        assert_eq!(restored, vec![issue.clone()]);
+        assert_eq!(fs::read_to_string(&issue).unwrap(), "# Broken");
+
+        restore_backup(&paths, &archive, &[BackupComponent::Issues], false).unwrap();
+        assert_eq!(fs::read_to_string(&issue).unwrap(), "# Fix");
+        assert!(!memo.exists());
+
+        restore_backup(&paths, &archive, &selected(&[]), false).unwrap();
+        assert_eq!(fs::read_to_string(&memo).unwrap(), "notes");
+
+        let listed = list_backups(&backups).unwrap();
+        assert_eq!(listed.len(), 2);
+        assert!(listed[0]
+            .path
+            .ends_with("sah-backup-20261016-120001.tar.gz"));
+        assert_eq!(listed[1].files, 5);
+    }
+
+    #[test]
+    fn test_damaged_backup_is_not_restored() {
+        let temp_dir = TempDir::new().unwrap();
+        let paths = state(&temp_dir);
+        let backups = temp_dir.path().join("backups");
+        let (archive, mut manifest) = create_backup(
+            &paths,
+            &[BackupComponent::Issues, BackupComponent::Prompts],
+            &backups,
+            time(0),
+        )
+        .unwrap();
+
+        // Rewrite the archive with a checksum that no longer matches
+        manifest.files[0].sha256 = sha256(b"something else");
+        let mut builder = tar::Builder::new(GzEncoder::new(
+            File::create(&archive).unwrap(),
+            Compression::default(),
+        ));
+        append(&mut builder, "issues/000001_fix.md", b"# Fix").unwrap();
+        append(&mut builder, "prompts/review.md", b"review").unwrap();
+        append(
+            &mut builder,
+            MANIFEST_NAME,
+            &serde_json::to_vec(&manifest).unwrap(),
+        )
+        .unwrap();
+        builder.into_inner().unwrap().finish().unwrap();
+
+        let issue = paths.work_dir.join("issues/000001_fix.md");
+        fs::write(&issue, "# Changed").unwrap();
+        let error = restore_backup(&paths, &archive, &selected(&[]), false)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("fails its checksum"), "{error}");
+        assert_eq!(fs::read_to_string(&issue).unwrap(), "# Changed");
+
+        // The damaged component can be skipped
+        restore_backup(&paths, &archive, &[BackupComponent::Prompts], false).unwrap();
+    }
+
+    #[test]
+    fn test_destination_rejects_escaping_paths() {
+        let temp_dir = TempDir::new().unwrap();
+        let paths = state(&temp_dir);
+        assert_eq!(
+            paths.destination("runs/workflow-runs/runs/a.yaml"),
+            Some(
+                paths
+                    .home_dir
+                    .join(".swissarmyhammer/workflow-runs/runs/a.yaml")
+            )
+        );
+        assert_eq!(
+            paths.destination("config/presets/a.yaml"),
+            Some(paths.work_dir.join(".swissarmyhammer/presets/a.yaml"))
+        );
+        assert_eq!(paths.destination("issues/../../etc/passwd"), None);
+        assert_eq!(paths.destination("/etc/passwd"), None);
+        assert_eq!(paths.destination("issues"), None);
+        assert_eq!(paths.destination("unknown/file"), None);
+    }
+}
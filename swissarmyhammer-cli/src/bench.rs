@@ -0,0 +1,118 @@
+//! Prompt benchmark measuring parse and render latency
+
+use anyhow::{anyhow, Result};
+use colored::*;
+use std::collections::HashMap;
+use std::sync::Arc;
+use swissarmyhammer::{LatencyStats, PromptBenchmark, PromptLibrary, PromptResolver};
+
+use crate::cli::OutputFormat;
+
+/// Benchmark parsing and rendering a prompt
+pub fn run_bench_command(
+    name: String,
+    iterations: usize,
+    arguments: Vec<String>,
+    format: OutputFormat,
+) -> Result<()> {
+    let mut library = PromptLibrary::new();
+    PromptResolver::new().load_all_prompts(&mut library)?;
+    let args = parse_arguments(&arguments)?;
+
+    // Allocations are only counted in builds with the alloc-stats feature
+    #[cfg(feature = "alloc-stats")]
+    let allocator = Some(&crate::ALLOCATOR);
+    #[cfg(not(feature = "alloc-stats"))]
+    let allocator = None;
+    let benchmark = PromptBenchmark::run(Arc::new(library), &name, &args, iterations, allocator)?;
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&benchmark)?),
+        OutputFormat::Yaml => print!("{}", serde_yaml::to_string(&benchmark)?),
+        OutputFormat::Table => display_benchmark(&benchmark),
+    }
+    Ok(())
+}
+
+fn parse_arguments(arguments: &[String]) -> Result<HashMap<String, String>> {
+    arguments
+        .iter()
+        .map(|argument| {
+            argument
+                .split_once('=')
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .ok_or_else(|| {
+                    anyhow!("Invalid argument format: '{argument}'. Use key=value format")
+                })
+        })
+        .collect()
+}
+
+fn display_benchmark(benchmark: &PromptBenchmark) {
+    println!(
+        "{}",
+        format!(
+            "Benchmark of '{}' over {} iterations",
+            benchmark.prompt, benchmark.iterations
+        )
+        .bold()
+    );
+
+    println!(
+        "\n{:<8} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10}",
+        "step", "min", "p50", "p95", "p99", "max", "mean"
+    );
+    display_latency("parse", &benchmark.parse);
+    display_latency("render", &benchmark.render);
+
+    if let Some(allocations) = &benchmark.render_allocations {
+        println!(
+            "\nMemory per render: {} allocations, {}",
+            allocations.allocations,
+            format_bytes(allocations.bytes)
+        );
+    }
+}
+
+fn display_latency(step: &str, stats: &LatencyStats) {
+    let ms = |value: f64| format!("{value:.3}ms");
+    println!(
+        "{:<8} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10}",
+        step,
+        ms(stats.min_ms),
+        ms(stats.p50_ms),
+        ms(stats.p95_ms),
+        ms(stats.p99_ms),
+        ms(stats.max_ms),
+        ms(stats.mean_ms)
+    );
+}
+
+fn format_bytes(bytes: u64) -> String {
+    match bytes {
+        0..=1023 => format!("{bytes} B"),
+        1024..=1_048_575 => format!("{:.1} KiB", bytes as f64 / 1024.0),
+        _ => format!("{:.1} MiB", bytes as f64 / 1_048_576.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_arguments() {
+        let args =
+            parse_arguments(&["language=rust".to_string(), "query=a=b".to_string()]).unwrap();
+        assert_eq!(args["language"], "rust");
+        assert_eq!(args["query"], "a=b");
+        assert!(parse_arguments(&["language".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(2048), "2.0 KiB");
+        assert_eq!(format_bytes(3 * 1_048_576), "3.0 MiB");
+    }
+}
@@ -43,6 +43,93 @@ impl From<PromptSource> for PromptSourceArg {
     }
 }
 
+/// Column on the issue board, used as a target for `issue board --move`
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum BoardColumnArg {
+    Todo,
+    InProgress,
+    Done,
+}
+
+/// Order of issues for `issue list` and `issue next`
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum IssueSortArg {
+    /// By name, the order issues were numbered in
+    Name,
+    /// Highest priority first
+    Priority,
+    /// Smallest estimate first
+    Estimate,
+    /// Oldest first
+    Created,
+}
+
+impl From<IssueSortArg> for swissarmyhammer::issues::IssueSort {
+    fn from(arg: IssueSortArg) -> Self {
+        match arg {
+            IssueSortArg::Name => Self::Name,
+            IssueSortArg::Priority => Self::Priority,
+            IssueSortArg::Estimate => Self::Estimate,
+            IssueSortArg::Created => Self::Created,
+        }
+    }
+}
+
+/// Issue priority, used to filter `issue list`
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum IssuePriorityArg {
+    High,
+    Medium,
+    Low,
+}
+
+impl From<IssuePriorityArg> for swissarmyhammer::issues::IssuePriority {
+    fn from(arg: IssuePriorityArg) -> Self {
+        match arg {
+            IssuePriorityArg::High => Self::High,
+            IssuePriorityArg::Medium => Self::Medium,
+            IssuePriorityArg::Low => Self::Low,
+        }
+    }
+}
+
+/// Output format for `prompt export`
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum ExportFormat {
+    /// JSON bundle written to prompts.json
+    Json,
+    /// Static HTML catalog written to index.html
+    Html,
+}
+
+/// Source format for `prompt import`
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum ImportFormat {
+    /// LangChain prompt templates saved as JSON or YAML
+    Langchain,
+    /// OpenAI style prompts with chat messages, as JSON
+    OpenaiJson,
+    /// Text and markdown files, one prompt per file
+    Plain,
+}
+
+/// Output format for `query`
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum QueryFormat {
+    Table,
+    Json,
+    Csv,
+}
+
+/// Kind of item `pick` can open
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PickKind {
+    Prompt,
+    Issue,
+    Memo,
+    Workflow,
+}
+
 #[derive(ValueEnum, Clone, Debug)]
 pub enum ValidateFormat {
     Text,
@@ -70,6 +157,10 @@ Example usage:
   swissarmyhammer serve     # Run as MCP server
   swissarmyhammer doctor    # Check configuration and setup
   swissarmyhammer completion bash > ~/.bashrc.d/swissarmyhammer  # Generate bash completions
+
+Prompts are loaded from builtin, user and local sources. --source and
+--exclude-source skip sources entirely, such as user prompts in CI:
+  swissarmyhammer --exclude-source user validate
 ")]
 pub struct Cli {
     #[command(subcommand)]
@@ -86,6 +177,14 @@ pub struct Cli {
     /// Suppress all output except errors
     #[arg(short, long)]
     pub quiet: bool,
+
+    /// Only load prompts from these sources
+    #[arg(long = "source", value_enum, value_delimiter = ',')]
+    pub sources: Vec<PromptSourceArg>,
+
+    /// Never load prompts from these sources
+    #[arg(long = "exclude-source", value_enum, value_delimiter = ',')]
+    pub exclude_sources: Vec<PromptSourceArg>,
 }
 
 #[derive(Subcommand, Debug)]
@@ -100,22 +199,37 @@ invoked via stdio (e.g., by Claude Code). The server will:
 - Expose prompts via the MCP protocol
 - Support template substitution with {{variables}}
 
+With --read-only the server lists and accepts only tools that read state,
+such as issue_list, memo_search and search_query. Tools that create or change
+issues or memos, touch git, rebuild the search index or run workflows are
+refused, so the server can be given to agents that shouldn't change anything.
+
 Example:
   swissarmyhammer serve
+  swissarmyhammer serve --read-only
   # Or configure in Claude Code's MCP settings
 ")]
-    Serve,
+    Serve {
+        /// Only serve tools that never change issues, memos, git or the search index
+        #[arg(long)]
+        read_only: bool,
+    },
     /// Diagnose configuration and setup issues
     #[command(long_about = "
 Runs comprehensive diagnostics to help troubleshoot setup issues.
 The doctor command will check:
 
 - If swissarmyhammer is in your PATH
-- Claude Code MCP configuration
+- Claude Code and Claude Desktop MCP configuration
 - Prompt directories and permissions
 - YAML syntax in prompt files
 - File watching capabilities
 
+With --fix, doctor lists the problems it can resolve itself, such as creating
+missing directories, fixing directory permissions and adding swissarmyhammer
+to the Claude Code or Claude Desktop MCP servers, and applies them once
+confirmed. The diagnostics are then run again.
+
 Exit codes:
   0 - All checks passed
   1 - Warnings found
@@ -124,8 +238,16 @@ Exit codes:
 Example:
   swissarmyhammer doctor
   swissarmyhammer doctor --verbose  # Show detailed diagnostics
+  swissarmyhammer doctor --fix      # Resolve problems after confirmation
 ")]
-    Doctor,
+    Doctor {
+        /// Apply the fixes for problems doctor can resolve itself
+        #[arg(long)]
+        fix: bool,
+        /// Apply fixes without asking
+        #[arg(short, long, requires = "fix")]
+        yes: bool,
+    },
     /// Manage and test prompts
     #[command(long_about = "
 Manage prompts with support for listing, validating, testing, and searching.
@@ -225,6 +347,21 @@ Examples:
   swissarmyhammer validate                 # Validate all prompts and workflows
   swissarmyhammer validate --quiet         # CI/CD mode - only shows errors, hides warnings
   swissarmyhammer validate --format json   # JSON output for tooling
+  swissarmyhammer validate --impact .swissarmyhammer/prompts/header.md  # Report what an edit affects
+  swissarmyhammer validate --watch         # Validate again on every save
+  swissarmyhammer validate --jobs 4        # Validate four files at a time
+
+Files are validated in parallel, one per CPU unless --jobs says otherwise. The
+report lists them in the same order however many run at once.
+
+With --impact, each prompt and workflow that uses the changed file, directly or
+through other prompts and workflows, is reported as INFO, and missing partial,
+prompt or workflow references among them are reported as errors. The file may
+already be deleted.
+
+With --watch, everything is validated once and then, each time a prompt or
+workflow file is saved, only that file and the prompts and workflows using it
+are validated again. The results are shown as a live summary until Ctrl+C.
 ")]
     Validate {
         /// Suppress all output except errors. In quiet mode, warnings are hidden from both output and summary.
@@ -238,6 +375,18 @@ Examples:
         /// \[DEPRECATED\] This parameter is ignored. Workflows are now only loaded from standard locations.
         #[arg(long = "workflow-dir", value_name = "DIR", hide = true)]
         workflow_dirs: Vec<String>,
+
+        /// Also report every prompt and workflow affected by changes to this prompt or workflow file
+        #[arg(long, value_name = "CHANGED_FILE")]
+        impact: Option<std::path::PathBuf>,
+
+        /// Keep validating the files affected by each change until stopped
+        #[arg(long, conflicts_with = "impact")]
+        watch: bool,
+
+        /// Number of files to validate at once, 0 for one per CPU
+        #[arg(short, long, value_name = "N", default_value_t = 0)]
+        jobs: usize,
     },
     /// Issue management commands
     #[command(long_about = "
@@ -282,6 +431,9 @@ Basic usage:
   swissarmyhammer memo delete <id>              # Delete memo
   swissarmyhammer memo search <query>           # Search memos
   swissarmyhammer memo context                  # Get all context for AI
+  swissarmyhammer memo context --query <text>   # Get context relevant to a query
+  swissarmyhammer memo list --notebook <name>   # List the memos in a notebook
+  swissarmyhammer memo move <id> <notebook>     # File a memo in a notebook
 
 Content input:
   --content \"text\"                            # Specify content directly
@@ -297,6 +449,7 @@ Examples:
   swissarmyhammer memo update 01GX5Q2D1NPRZ3KXFW2H8V3A1Y --content \"Updated content\"
   swissarmyhammer memo delete 01GX5Q2D1NPRZ3KXFW2H8V3A1Y
   swissarmyhammer memo context
+  swissarmyhammer memo context --query \"deploy\" --max-tokens 2000
 ")]
     Memo {
         #[command(subcommand)]
@@ -331,6 +484,139 @@ Examples:
         #[command(subcommand)]
         subcommand: SearchCommands,
     },
+    /// Query the metadata of issues and memos with SQL-like statements
+    #[command(long_about = "
+Runs a SQL-like query over the structured metadata of issues and memos, for
+ad-hoc reports without exporting and filtering JSON:
+
+  SELECT <columns | *> FROM <issues | memos>
+    [WHERE <condition>] [ORDER BY <column> [ASC | DESC], ...] [LIMIT <n>]
+
+Conditions compare a column with a value (=, !=, <>, <, <=, >, >=), match
+LIKE patterns with % and _, test IN (...) lists and IS [NOT] NULL, and combine
+with AND, OR, NOT and parentheses. Strings are single quoted.
+
+Issue columns:
+  name, state, completed, priority, estimate, labels, assignee, reporter,
+  repo, related_memos, created_at, file_path, and content and label, which
+  are left out of *
+Memo columns:
+  id, title, created_at, updated_at, and content, which is left out of *
+
+A list column such as labels or label equals a value when any of its items
+does. Timestamps compare as RFC 3339 text, so '2026-01-01' works as a date.
+
+Examples:
+  swissarmyhammer query \"SELECT name, created_at FROM issues WHERE label='bug' ORDER BY created_at\"
+  swissarmyhammer query \"SELECT name, estimate FROM issues WHERE NOT completed = true AND assignee IS NULL\"
+  swissarmyhammer query \"SELECT title FROM memos WHERE title LIKE '%meeting%'\" --format csv
+")]
+    Query {
+        /// The query to run
+        query: String,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = QueryFormat::Table)]
+        format: QueryFormat,
+    },
+    /// Fuzzy find a prompt, issue, memo or workflow and open it
+    #[command(long_about = "
+Fuzzy find a prompt, issue, memo or workflow and open it.
+
+Type to narrow the list, then press Enter to open the highlighted item:
+prompts are tested, issues are shown, memos are printed and workflows are run.
+Press Esc to leave without opening anything.
+
+Use --kind to list only some kinds of item, and --print to print the command
+that opens the item instead of running it.
+
+Examples:
+  swissarmyhammer pick                          # Pick from everything
+  swissarmyhammer pick login                    # Start with \"login\" typed in
+  swissarmyhammer pick --kind issue --kind memo # Pick from issues and memos
+  swissarmyhammer pick --print                  # Print the command to open the item
+")]
+    Pick {
+        /// Text to start the search with
+        query: Option<String>,
+        /// Only list items of this kind (repeatable)
+        #[arg(long, value_enum)]
+        kind: Vec<PickKind>,
+        /// Print the command that opens the item instead of running it
+        #[arg(long)]
+        print: bool,
+    },
+    /// Read the MCP server log
+    #[command(long_about = "
+Read the log written by the MCP server to .swissarmyhammer/mcp.log, or the
+file named by SWISSARMYHAMMER_LOG_FILE.
+
+The log is rotated once it reaches SWISSARMYHAMMER_LOG_MAX_SIZE_MB megabytes
+(default 10) and when the date changes, unless SWISSARMYHAMMER_LOG_ROTATE_DAILY
+is false. Rotated files are kept as mcp.log.1 (newest) to mcp.log.N, where N is
+SWISSARMYHAMMER_LOG_RETENTION (default 5).
+
+Examples:
+  swissarmyhammer logs tail                     # Follow the log, starting with the last 20 lines
+  swissarmyhammer logs tail -n 100              # Start with the last 100 lines
+  swissarmyhammer logs tail --no-follow         # Print the last lines and exit
+")]
+    Logs {
+        #[command(subcommand)]
+        subcommand: LogsSubcommand,
+    },
+    /// Serve a read-only REST API over prompts, memos, issues and workflow runs
+    #[command(long_about = "
+Serves a small read-only JSON API for dashboards and tools that can't speak
+MCP. It reads the same prompts, memos, issues and workflow runs as the MCP
+server, and only answers GET requests:
+
+  GET /health
+  GET /prompts?q=<query>                       GET /prompts/<name>
+  GET /memos?q=<query>                         GET /memos/<id>
+  GET /issues?q=<query>&status=open|completed  GET /issues/<name>
+  GET /runs?workflow=<name>                    GET /runs/<id>
+
+The API has no authentication, so it listens on localhost unless told
+otherwise. Clients have 10 seconds to send a request and at most 64
+connections are answered at once.
+
+Examples:
+  swissarmyhammer api
+  swissarmyhammer api --addr 0.0.0.0:8787
+  curl http://127.0.0.1:8787/issues?status=open
+")]
+    Api {
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1:8787")]
+        addr: std::net::SocketAddr,
+    },
+    /// Back up and restore prompts, memos, issues, workflow runs and config
+    #[command(long_about = "
+Snapshots SwissArmyHammer state into a compressed archive in
+.swissarmyhammer/backups and restores it. Each archive holds a manifest with
+the SHA-256 checksum of every file, and a restore checks them all before
+writing anything.
+
+Components:
+  prompts    .swissarmyhammer/prompts
+  workflows  .swissarmyhammer/workflows
+  memos      .swissarmyhammer/memos
+  issues     ./issues
+  runs       workflow runs in ~/.swissarmyhammer
+  config     the rest of .swissarmyhammer, without logs, worktrees and the
+             search index
+
+Examples:
+  swissarmyhammer backup create
+  swissarmyhammer backup create --only issues,memos
+  swissarmyhammer backup list
+  swissarmyhammer backup restore sah-backup-20261016-120000.tar.gz --dry-run
+  swissarmyhammer backup restore sah-backup-20261016-120000.tar.gz --only issues
+")]
+    Backup {
+        #[command(subcommand)]
+        subcommand: BackupSubcommand,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -351,6 +637,7 @@ Examples:
   swissarmyhammer prompt list --verbose             # Show full details including arguments
   swissarmyhammer prompt list --source builtin      # Show only built-in prompts
   swissarmyhammer prompt list --search debug        # Search for prompts containing 'debug'
+  swissarmyhammer prompt list --include-deprecated  # Also show deprecated prompts
 ")]
     List {
         /// Output format
@@ -372,6 +659,10 @@ Examples:
         /// Search prompts by name or description
         #[arg(long)]
         search: Option<String>,
+
+        /// Include prompts marked deprecated
+        #[arg(long)]
+        include_deprecated: bool,
     },
     /// Test prompts interactively with sample arguments
     #[command(long_about = "
@@ -386,21 +677,36 @@ Usage modes:
 Interactive features:
 - Prompts for each argument with descriptions
 - Shows default values (press Enter to accept)
+- Offers a selection for arguments with choices
+- Asks for required arguments missing from --arg or --preset
 - Validates required arguments
 - Supports multi-line input
 
+Prompting happens automatically when run in a terminal. Use --interactive to
+force it, or --no-interactive to fail when required arguments are missing.
+
 Output options:
   --raw     Show rendered prompt without formatting
   --copy    Copy rendered prompt to clipboard
   --save    Save rendered prompt to file
   --debug   Show template processing details
 
+Presets:
+  --save-preset NAME   Save the provided arguments as a named preset
+  --preset NAME        Load arguments from a saved preset (--arg values override it)
+
+Inline tests:
+  --all     Run the test cases in every prompt's front matter and report
+            which fail, instead of rendering one prompt
+
 Examples:
   swissarmyhammer prompt test code-review                           # Interactive test
   swissarmyhammer prompt test -f my-prompt.md                       # Test file
   swissarmyhammer prompt test help --arg topic=git                  # Non-interactive
   swissarmyhammer prompt test plan --debug --save output.md         # Debug + save
   swissarmyhammer prompt test code-review --set author=John --set version=1.0  # With template variables
+  swissarmyhammer prompt test code-review --arg language=rust --save-preset rust-strict
+  swissarmyhammer prompt test code-review --preset rust-strict
 ")]
     Test {
         /// Prompt name to test (alternative to --file)
@@ -433,6 +739,26 @@ Examples:
         /// Show debug information (template, args, processing steps)
         #[arg(long)]
         debug: bool,
+
+        /// Load arguments from a saved preset
+        #[arg(long, value_name = "NAME")]
+        preset: Option<String>,
+
+        /// Save the provided arguments as a named preset for this prompt
+        #[arg(long, value_name = "NAME")]
+        save_preset: Option<String>,
+
+        /// Prompt for missing required arguments (default when run in a terminal)
+        #[arg(long, overrides_with = "no_interactive")]
+        interactive: bool,
+
+        /// Never prompt; fail if required arguments are missing
+        #[arg(long, overrides_with = "interactive")]
+        no_interactive: bool,
+
+        /// Run the tests in the front matter of every prompt
+        #[arg(long, conflicts_with_all = ["prompt_name", "file"])]
+        all: bool,
     },
     /// Search for prompts with advanced filtering and ranking
     #[command(long_about = "
@@ -509,6 +835,230 @@ Examples:
         #[arg(short, long)]
         limit: Option<usize>,
     },
+    /// Export the prompt library as a JSON bundle or static HTML catalog
+    #[command(long_about = "
+Export every prompt in the resolved library to a directory. Local and user
+prompts override builtin prompts with the same name, exactly as they do for
+the MCP server. Partial templates and prompts with `visibility: private` are
+not exported.
+
+Formats:
+  json  - prompts.json bundle with each prompt's MCP listing fields
+          (name, description, arguments) plus its template and metadata
+  html  - index.html catalog with descriptions and argument tables
+
+Examples:
+  swissarmyhammer prompt export ./catalog                  # Write catalog/prompts.json
+  swissarmyhammer prompt export --format html ./site      # Write site/index.html
+")]
+    Export {
+        /// Output format
+        #[arg(long, value_enum, default_value = "json")]
+        format: ExportFormat,
+
+        /// Directory to write the export into (created if missing)
+        output_dir: std::path::PathBuf,
+    },
+    /// Import prompts from LangChain, OpenAI prompt JSON or plain text files
+    #[command(long_about = "
+Convert prompts from another format into SwissArmyHammer prompt files with
+front matter. PATH is a single file or a directory searched recursively; each
+prompt is written to OUTPUT_DIR under its name, keeping subdirectories.
+
+Formats:
+  langchain    - PromptTemplate and ChatPromptTemplate files (.json, .yaml)
+                 saved by LangChain; f-string variables become Liquid variables
+                 and partial variables become argument defaults
+  openai-json  - JSON prompts with chat messages, instructions or prompt text
+                 and {{variable}} placeholders; several messages are joined
+                 under role headings
+  plain        - .txt, .md and .prompt files whose {{variable}} placeholders
+                 become arguments
+
+Features without an equivalent, such as output parsers, few-shot examples,
+model settings and image content, are reported for each prompt. Existing
+prompts are only replaced with --force.
+
+Examples:
+  swissarmyhammer prompt import --format langchain ./chains/prompts
+  swissarmyhammer prompt import --format openai-json prompts.json --output-dir ~/.swissarmyhammer/prompts
+  swissarmyhammer prompt import --format plain ./notes --force
+")]
+    Import {
+        /// Format of the prompts to import
+        #[arg(long, value_enum)]
+        format: ImportFormat,
+
+        /// File or directory of prompts to import
+        path: std::path::PathBuf,
+
+        /// Directory to write the prompt files into (created if missing)
+        #[arg(long, default_value = ".swissarmyhammer/prompts")]
+        output_dir: std::path::PathBuf,
+
+        /// Replace prompts that already exist in the output directory
+        #[arg(long)]
+        force: bool,
+    },
+    /// Show which partials a prompt uses and everything affected by changing it
+    #[command(long_about = "
+Show the dependencies of a prompt or partial template.
+
+Prompts depend on the partials they include with {% render %} or {% include %},
+and workflows depend on the prompts and workflows their states and hooks run.
+The report lists what the prompt uses, references that do not exist, what uses
+the prompt directly, and every prompt and workflow affected by changing it.
+
+When no prompt has the given name, a workflow with that name is reported on.
+
+Examples:
+  swissarmyhammer prompt deps coding_standards         # What is affected by editing a partial
+  swissarmyhammer prompt deps code --format json       # Machine readable report
+")]
+    Deps {
+        /// Name of the prompt, partial or workflow
+        name: String,
+
+        /// Output format
+        #[arg(long, value_enum, default_value = "table")]
+        format: OutputFormat,
+    },
+    /// Show how the prompts of one source differ from another source
+    #[command(long_about = "
+Compare the prompts of two sources, for example to audit how far local
+overrides have drifted from the builtin prompts they replace.
+
+Sources take precedence in the order builtin, user, local. Each prompt either
+source defines is reported as:
+  overridden  - both define it and the --to version takes precedence
+  shadowed    - both define it and the --from version takes precedence
+  added       - only --to defines it
+  removed     - only --from defines it
+  identical   - both define it with the same content
+
+Overridden and shadowed prompts include a line diff from the --from version
+to the --to version.
+
+Examples:
+  swissarmyhammer prompt diff-sources                          # builtin vs local
+  swissarmyhammer prompt diff-sources --from builtin --to user
+  swissarmyhammer prompt diff-sources --names-only             # Skip content diffs
+  swissarmyhammer prompt diff-sources --format json            # Machine readable report
+")]
+    DiffSources {
+        /// Source to compare from
+        #[arg(long, value_enum, default_value = "builtin")]
+        from: PromptSourceArg,
+
+        /// Source to compare to
+        #[arg(long, value_enum, default_value = "local")]
+        to: PromptSourceArg,
+
+        /// Output format
+        #[arg(long, value_enum, default_value = "table")]
+        format: OutputFormat,
+
+        /// List prompt names without content diffs
+        #[arg(long)]
+        names_only: bool,
+    },
+    /// Measure how long a prompt takes to parse and render
+    #[command(long_about = "
+Parse and render a prompt many times and report the latency distribution of
+both steps (min, P50, P95, P99, max and mean). Rendering is measured the way
+workflows render prompts, partials included, so slow templates show up before
+they slow down a workflow. Binaries built with the alloc-stats feature also
+report the memory each render allocates.
+
+Required arguments must be given with --arg; arguments with defaults use them.
+
+Examples:
+  swissarmyhammer prompt bench code-review --arg language=rust
+  swissarmyhammer prompt bench help --iterations 1000
+  swissarmyhammer prompt bench help --format json      # Machine readable report
+")]
+    Bench {
+        /// Name of the prompt to benchmark
+        name: String,
+
+        /// Number of times to parse and render the prompt
+        #[arg(long, default_value_t = 100)]
+        iterations: usize,
+
+        /// Prompt arguments as key=value pairs
+        #[arg(long = "arg", value_name = "KEY=VALUE")]
+        arguments: Vec<String>,
+
+        /// Output format
+        #[arg(long, value_enum, default_value = "table")]
+        format: OutputFormat,
+    },
+    /// Report prompts that need cleaning up
+    #[command(long_about = "
+Check the whole prompt library for housekeeping problems:
+
+- prompts without a description
+- arguments the template never uses
+- template variables no argument declares
+- prompts sharing the same title
+- prompts no workflow uses, directly or through another prompt
+- prompts whose template is longer than --max-length characters
+
+Partials are exempt from the description and argument checks.
+
+Examples:
+  swissarmyhammer prompt audit
+  swissarmyhammer prompt audit --max-length 4000
+  swissarmyhammer prompt audit --format json    # Machine readable report
+")]
+    Audit {
+        /// Template length, in characters, above which a prompt is reported
+        #[arg(long, default_value_t = swissarmyhammer::prompt_audit::DEFAULT_MAX_PROMPT_LENGTH)]
+        max_length: usize,
+
+        /// Output format
+        #[arg(long, value_enum, default_value = "table")]
+        format: OutputFormat,
+    },
+    /// Show the prompts MCP clients fetched
+    #[command(long_about = "
+Show the record the MCP server keeps of every prompt a client fetched, most
+recent last: when it was fetched, by which client, the names of the arguments
+it was rendered with, and hashes of the argument values and of the text the
+client received. Equal hashes mean the same arguments, or the same rendered
+text, so two runs can be compared without the values themselves being stored.
+
+Runs are recorded in .swissarmyhammer/prompt-runs in the directory the server
+runs in.
+
+Examples:
+  swissarmyhammer prompt runs code-review              # Recent runs of a prompt
+  swissarmyhammer prompt runs --limit 50               # Recent runs of every prompt
+  swissarmyhammer prompt runs code-review --format json
+")]
+    Runs {
+        /// Prompt to show runs of; every prompt when omitted
+        name: Option<String>,
+
+        /// Number of most recent runs to show
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+
+        /// Output format
+        #[arg(long, value_enum, default_value = "table")]
+        format: OutputFormat,
+    },
+    /// Print completion values for a prompt argument, used by shell completion
+    #[command(hide = true)]
+    Complete {
+        /// Prompt name
+        prompt_name: String,
+        /// Argument to complete; the prompt's argument names when omitted
+        argument: Option<String>,
+        /// Only print values starting with this prefix
+        #[arg(default_value = "")]
+        prefix: String,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -538,6 +1088,10 @@ pub enum FlowSubcommand {
         #[arg(long)]
         test: bool,
 
+        /// Record the rendered prompts as snapshots (requires --dry-run)
+        #[arg(long, requires = "dry_run")]
+        update_snapshots: bool,
+
         /// Execution timeout (e.g., 30s, 5m, 1h)
         #[arg(long)]
         timeout: Option<String>,
@@ -545,6 +1099,18 @@ pub enum FlowSubcommand {
         /// Quiet mode - only show errors
         #[arg(short, long)]
         quiet: bool,
+
+        /// Print log lines instead of the live progress display, e.g. in CI
+        #[arg(long)]
+        no_progress: bool,
+
+        /// Start the run again up to this many times after a transient failure
+        #[arg(long, value_name = "N")]
+        retries: Option<u32>,
+
+        /// Wait before the first retry, doubled for each retry after it (e.g., 30s, 5m)
+        #[arg(long, value_name = "DURATION")]
+        retry_backoff: Option<String>,
     },
     /// Resume a paused workflow run
     Resume {
@@ -562,6 +1128,10 @@ pub enum FlowSubcommand {
         /// Quiet mode - only show errors
         #[arg(short, long)]
         quiet: bool,
+
+        /// Print log lines instead of the live progress display, e.g. in CI
+        #[arg(long)]
+        no_progress: bool,
     },
     /// List available workflows
     List {
@@ -590,6 +1160,24 @@ pub enum FlowSubcommand {
         #[arg(short, long)]
         watch: bool,
     },
+    /// Stop a workflow run that is in flight
+    #[command(long_about = "
+Ask an in-flight workflow run to stop. The run notices within a moment, even
+in the middle of an action: Claude and shell processes the action started are
+killed, the run is marked cancelled and the workflow's on_cancel hook runs, so
+it can clean up branches or files it left half done.
+
+Run it from the directory the workflow runs in. The run ID is logged when the
+run starts. Pressing Ctrl+C in the terminal running the workflow cancels it
+the same way; press it again to stop without waiting.
+
+Examples:
+  swissarmyhammer flow cancel 01H8XYZ...
+")]
+    Cancel {
+        /// Run ID to cancel
+        run_id: String,
+    },
     /// View logs for a workflow run
     Logs {
         /// Run ID to view logs for
@@ -624,6 +1212,91 @@ pub enum FlowSubcommand {
         #[arg(short, long)]
         global: bool,
     },
+    /// Compare two workflow runs side by side
+    #[command(long_about = "
+Compare two persisted workflow runs side by side.
+
+The report shows, for each run, the states visited with visit counts and time
+spent, the inputs and outputs of every prompt executed, the total duration,
+and the cost reported by Claude. Rows whose values differ are marked with '*'.
+
+Examples:
+  swissarmyhammer flow compare 01H8XYZ... 01H8ABC...
+  swissarmyhammer flow compare 01H8XYZ... 01H8ABC... --format json
+")]
+    Compare {
+        /// First run ID
+        run_a: String,
+
+        /// Second run ID
+        run_b: String,
+
+        /// Output format
+        #[arg(long, value_enum, default_value = "table")]
+        format: OutputFormat,
+    },
+    /// List the files a workflow run saved as artifacts
+    #[command(long_about = "
+List the artifacts of a workflow run: the files its actions saved with
+'Save artifact \"path\" [as <kind>]'. Each artifact is copied into
+.swissarmyhammer/runs/<run_id>/artifacts, so it is kept after the working copy
+it was produced in is gone. The table shows the kind, size, saving state and
+stored path of each artifact.
+
+Kinds are patch, report, log, data and file. An artifact saved without a kind
+gets one from its file extension.
+
+Run it from the directory the workflow ran in.
+
+Examples:
+  swissarmyhammer flow artifacts 01H8XYZ...
+  swissarmyhammer flow artifacts 01H8XYZ... --format json
+")]
+    Artifacts {
+        /// Run ID to list artifacts for
+        run_id: String,
+
+        /// Output format
+        #[arg(long, value_enum, default_value = "table")]
+        format: OutputFormat,
+    },
+    /// Forecast what a workflow will cost before running it
+    #[command(long_about = "
+Estimate the cost of a workflow without running it. Every path from the
+initial state to a terminal state is walked, and the prompts along it are
+rendered with the given variables and counted at four characters per token.
+Responses are assumed to be between a low and a high multiple of the prompt,
+which gives a cost range for each path. Sub-workflows count as their cheapest
+to most expensive path, and loops are counted once.
+
+Token prices and response multiples come from the configuration:
+
+  SWISSARMYHAMMER_COST_INPUT_USD_PER_MTOK     (default 3.0)
+  SWISSARMYHAMMER_COST_OUTPUT_USD_PER_MTOK    (default 15.0)
+  SWISSARMYHAMMER_COST_OUTPUT_RATIO_LOW       (default 0.5)
+  SWISSARMYHAMMER_COST_OUTPUT_RATIO_HIGH      (default 3.0)
+
+Examples:
+  swissarmyhammer flow estimate implement
+  swissarmyhammer flow estimate code-review --var file=main.rs
+  swissarmyhammer flow estimate implement --format json
+")]
+    Estimate {
+        /// Workflow name to estimate
+        workflow: String,
+
+        /// Initial variables as key=value pairs
+        #[arg(long = "var", value_name = "KEY=VALUE")]
+        vars: Vec<String>,
+
+        /// Set template variables for liquid rendering in action strings as key=value pairs
+        #[arg(long = "set", value_name = "KEY=VALUE")]
+        set: Vec<String>,
+
+        /// Output format
+        #[arg(long, value_enum, default_value = "table")]
+        format: OutputFormat,
+    },
     /// Generate execution visualization
     Visualize {
         /// Run ID to visualize
@@ -671,6 +1344,12 @@ Examples:
   swissarmyhammer flow test greeting --set name=John --set language=Spanish  # With template variables
   swissarmyhammer flow test code-review --var file=main.rs --timeout 60s     # With vars and timeout
   swissarmyhammer flow test deploy --interactive                      # Step-by-step execution
+  swissarmyhammer flow test code-review --set file=main.rs --snapshots # Check prompt snapshots
+
+With --snapshots, every prompt the workflow executes is rendered with the given
+variables and compared to the snapshots recorded in .swissarmyhammer/snapshots
+by 'flow run <workflow> --dry-run --update-snapshots'. The test fails when a
+rendered prompt differs from its snapshot.
 
 This is equivalent to 'flow run --test' but provided as a separate command
 for better discoverability and clearer intent.
@@ -691,6 +1370,10 @@ for better discoverability and clearer intent.
         #[arg(short, long)]
         interactive: bool,
 
+        /// Fail when rendered prompts differ from their recorded snapshots
+        #[arg(long)]
+        snapshots: bool,
+
         /// Execution timeout (e.g., 30s, 5m, 1h)
         #[arg(long)]
         timeout: Option<String>,
@@ -699,23 +1382,126 @@ for better discoverability and clearer intent.
         #[arg(short, long)]
         quiet: bool,
     },
-}
+    /// Step through a workflow interactively
+    #[command(long_about = "
+Run a workflow under an interactive debugger. The run pauses before its first
+state and waits for commands:
+
+  step, s, <enter>      Run the current state and take one transition
+  continue, c           Run until a breakpoint or the end of the workflow
+  where, w              Show the current state and its transitions
+  vars, v               List the run variables
+  print, p NAME         Show one variable
+  set NAME=VALUE        Set a variable; VALUE is JSON or a plain string
+  unset NAME            Remove a variable
+  render, r             Render the current state's action without running it
+  break, b [STATE]      Break when the run enters STATE, or list breakpoints
+  delete, d STATE       Remove a breakpoint
+  quit, q               Stop debugging and save the run
+
+Actions run for real, exactly as they do with 'flow run'. Prompts are
+re-read from disk each time they are rendered, so a prompt can be edited and
+rendered again before stepping into it.
 
-#[derive(Subcommand, Debug)]
-pub enum IssueCommands {
-    /// Create a new issue
-    Create {
-        /// Issue name (optional)
-        #[arg()]
-        name: Option<String>,
-        /// Issue content (use - for stdin)
-        #[arg(short, long)]
-        content: Option<String>,
-        /// Read content from file
+Examples:
+  swissarmyhammer flow debug code-review
+  swissarmyhammer flow debug code-review --var file=main.rs --break review
+")]
+    Debug {
+        /// Workflow name to debug
+        workflow: String,
+
+        /// Initial variables as key=value pairs
+        #[arg(long = "var", value_name = "KEY=VALUE")]
+        vars: Vec<String>,
+
+        /// Set template variables for liquid rendering in action strings as key=value pairs
+        #[arg(long = "set", value_name = "KEY=VALUE")]
+        set: Vec<String>,
+
+        /// Break when the run enters this state (can be repeated)
+        #[arg(long = "break", value_name = "STATE")]
+        breakpoints: Vec<String>,
+    },
+    /// Create a workflow file from a template
+    #[command(long_about = "
+Scaffold a new workflow from a template. The template's values are asked for
+one at a time, with the default accepted by pressing Enter; values passed with
+--arg are not asked for. Without a terminal, defaults are used.
+
+The workflow is written to .swissarmyhammer/workflows/<name>.md unless --output
+is given, and is checked to parse before it is written.
+
+Builtin templates:
+  basic          Run one prompt and finish
+  review-loop    Make a change, then review and fix it until the review passes
+
+Templates are markdown files in ~/.swissarmyhammer/workflow-templates or
+.swissarmyhammer/workflow-templates, and override builtin templates of the same
+name. Their front matter declares arguments like a prompt, and their body is
+the workflow file as a Liquid template, with the workflow name as {{ name }}.
+
+Examples:
+  swissarmyhammer flow new fix-loop --template review-loop
+  swissarmyhammer flow new greet --arg prompt=say-hello --output greet.md
+")]
+    New {
+        /// Name of the new workflow
+        name: String,
+
+        /// Template to generate the workflow from
+        #[arg(short, long, default_value = swissarmyhammer::workflow::DEFAULT_WORKFLOW_TEMPLATE)]
+        template: String,
+
+        /// Template values as key=value pairs
+        #[arg(long = "arg", value_name = "KEY=VALUE")]
+        args: Vec<String>,
+
+        /// File to write, instead of .swissarmyhammer/workflows/<name>.md
+        #[arg(short, long)]
+        output: Option<std::path::PathBuf>,
+
+        /// Overwrite the file if it exists
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum IssueCommands {
+    /// Create a new issue
+    Create {
+        /// Issue name (optional)
+        #[arg()]
+        name: Option<String>,
+        /// Issue content (use - for stdin)
+        #[arg(short, long)]
+        content: Option<String>,
+        /// Read content from file
         #[arg(short, long)]
         file: Option<std::path::PathBuf>,
     },
     /// List all issues
+    #[command(long_about = "
+List issues, optionally filtered and sorted by the priority and estimate set
+in their front matter:
+
+  ---
+  priority: high
+  estimate: 3
+  ---
+  # Fix the login bug
+
+Issues without a priority count as medium:
+
+  swissarmyhammer issue list --sort priority
+  swissarmyhammer issue list --priority high --sort estimate
+
+The assignee field names who owns an issue; @me stands for the identity in
+git config:
+
+  swissarmyhammer issue list --assignee @me
+")]
     List {
         /// Show completed issues
         #[arg(short, long)]
@@ -726,6 +1512,15 @@ pub enum IssueCommands {
         /// Output format
         #[arg(short, long, value_enum, default_value = "table")]
         format: OutputFormat,
+        /// Order of the listed issues
+        #[arg(long, value_enum, default_value = "name")]
+        sort: IssueSortArg,
+        /// Only list issues with this priority
+        #[arg(long, value_enum)]
+        priority: Option<IssuePriorityArg>,
+        /// Only list issues assigned to this person (@me for yourself)
+        #[arg(long, value_name = "USER")]
+        assignee: Option<String>,
     },
     /// Show issue details
     Show {
@@ -754,6 +1549,125 @@ pub enum IssueCommands {
         /// Issue name
         name: String,
     },
+    /// Show every change made to an issue as a diff
+    #[command(long_about = "
+Show every change made to an issue's content, oldest first, each as a diff
+against the version before it. Each change is numbered with the version it
+produced; pass that number to issue restore to bring the content back:
+
+  swissarmyhammer issue history fix_login
+  swissarmyhammer issue restore fix_login 2
+
+The history is kept in .swissarmyhammer/issues/issues/history.
+")]
+    History {
+        /// Issue name
+        name: String,
+        /// Output format
+        #[arg(short, long, value_enum, default_value = "table")]
+        format: OutputFormat,
+    },
+    /// Restore an issue to a version from its history
+    Restore {
+        /// Issue name
+        name: String,
+        /// Version to restore, as numbered by issue history
+        version: usize,
+    },
+    /// Enrich an issue description with acceptance criteria and checklists
+    #[command(long_about = "
+Ask the configured model to refine an issue description. The built-in
+issue/refine prompt is rendered with the issue's content, and the model
+answers with the description rewritten to include acceptance criteria and a
+checklist of steps, keeping everything the issue already says.
+
+The changes are shown as a diff and the issue is only updated once they are
+confirmed. Without a terminal to confirm on, pass --yes to apply them:
+
+  swissarmyhammer issue refine fix_login
+  swissarmyhammer issue refine fix_login --yes
+
+The prompt can be replaced by a user or local prompt named issue/refine.
+")]
+    Refine {
+        /// Issue name
+        name: String,
+        /// Apply the refined description without asking
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Move an issue to another state
+    #[command(long_about = "
+Move an issue to another state. Every issue is open or complete, and a project
+can add states in between with SWISSARMYHAMMER_ISSUE_STATES. The state of a
+pending issue is kept in the state field of its front matter.
+
+When SWISSARMYHAMMER_ISSUE_TRANSITIONS lists from->to pairs, only those moves
+are allowed. Moving an issue to complete moves it to issues/complete, and
+moving a completed issue to any other state reopens it:
+
+  export SWISSARMYHAMMER_ISSUE_STATES=in-review,blocked
+  export SWISSARMYHAMMER_ISSUE_TRANSITIONS='open->in-review,in-review->open,in-review->complete'
+  swissarmyhammer issue move fix_login in-review
+  swissarmyhammer issue move fix_login complete
+")]
+    Move {
+        /// Issue name
+        name: String,
+        /// State to move the issue to
+        state: String,
+    },
+    /// Assign an issue to someone
+    #[command(long_about = "
+Assign an issue to the person working on it. The assignee is kept in the
+assignee field of the issue's front matter, next to the reporter recorded
+when the issue was created, so everyone sharing the issues directory sees who
+owns it. Without a user the issue is assigned to you, the identity in git
+config (user.name, or else user.email):
+
+  swissarmyhammer issue assign fix_login
+  swissarmyhammer issue assign fix_login \"Jane Doe\"
+  swissarmyhammer issue assign fix_login --unassign
+  swissarmyhammer issue list --assignee @me
+")]
+    Assign {
+        /// Issue name
+        name: String,
+        /// Who to assign the issue to (default: you, from git config)
+        #[arg(conflicts_with = "unassign")]
+        user: Option<String>,
+        /// Remove the assignee instead
+        #[arg(long)]
+        unassign: bool,
+    },
+    /// Tick a task in an issue's checklist
+    #[command(long_about = "
+Tick or untick a task in an issue's checklist. The task list items in the
+issue's body, '- [ ] task' and '- [x] task', are its checklist, numbered from
+1 in the order they appear. Name a task by its number or by words of its text;
+without --done or --undo the task is toggled. Without a task the checklist is
+shown, and `issue list` shows how much of each issue's checklist is done:
+
+  swissarmyhammer issue check fix_login
+  swissarmyhammer issue check fix_login 2
+  swissarmyhammer issue check fix_login \"write tests\" --done
+  swissarmyhammer issue check fix_login 2 --undo --format json
+")]
+    Check {
+        /// Issue name
+        name: String,
+        /// Task to tick, by its number or by words of its text
+        item: Option<String>,
+        /// Mark the task done instead of toggling it
+        #[arg(long, requires = "item", conflicts_with = "undo")]
+        done: bool,
+        /// Mark the task not done instead of toggling it
+        #[arg(long, requires = "item")]
+        undo: bool,
+        /// Output format
+        #[arg(short, long, value_enum, default_value = "table")]
+        format: OutputFormat,
+    },
     /// Start working on an issue
     Work {
         /// Issue name
@@ -767,12 +1681,302 @@ pub enum IssueCommands {
         #[arg(short, long)]
         keep_branch: bool,
     },
+    /// Bring new main branch commits into an issue branch
+    #[command(long_about = "
+Bring new commits from the main branch into an issue's issue/<name> branch,
+by merging main into it or, with --rebase, by rebasing it onto main. The
+issue branch is left checked out, and the working directory must be clean.
+
+When the sync conflicts it is rolled back, so the branch is unchanged, and
+each conflicted file is listed with a plan for resolving it by hand. Issue
+markdown edited on both branches, or completed on one and edited on the
+other, is called out. The command exits with a warning status on conflicts:
+
+  swissarmyhammer issue sync fix_login
+  swissarmyhammer issue sync fix_login --rebase
+  swissarmyhammer issue sync fix_login --format json
+")]
+    Sync {
+        /// Issue name
+        name: String,
+        /// Rebase the issue branch onto main instead of merging main into it
+        #[arg(long)]
+        rebase: bool,
+        /// Output format
+        #[arg(short, long, value_enum, default_value = "table")]
+        format: OutputFormat,
+    },
     /// Show current issue
     Current,
     /// Show project status
     Status,
     /// Show the next issue to work on
-    Next,
+    Next {
+        /// Order used to pick the issue; by default the highest priority
+        /// pending issue, first by name among equal priorities
+        #[arg(long, value_enum, default_value = "priority")]
+        sort: IssueSortArg,
+    },
+    /// Show issues as a board with todo, in progress and done columns
+    #[command(long_about = "
+Show issues as a board with todo, in progress and done columns.
+
+Pending issues with an issue/<name> branch are in progress, completed issues
+are done, and everything else is todo. Use --move with --to to move an issue
+between columns before the board is shown:
+
+  swissarmyhammer issue board
+  swissarmyhammer issue board --move fix_login --to in-progress
+  swissarmyhammer issue board --move fix_login --to done
+  swissarmyhammer issue board --move fix_login --to todo
+")]
+    Board {
+        /// Issue to move before showing the board
+        #[arg(long = "move", value_name = "NAME", requires = "to")]
+        move_issue: Option<String>,
+        /// Column to move the issue to
+        #[arg(long, value_enum, requires = "move_issue")]
+        to: Option<BoardColumnArg>,
+        /// Output format
+        #[arg(short, long, value_enum, default_value = "table")]
+        format: OutputFormat,
+    },
+    /// Show issue metrics: throughput, cycle time and open vs completed counts
+    #[command(long_about = "
+Show issue metrics computed from issue timestamps and git history.
+
+An issue is created when issues/<name>.md is first committed and completed
+when issues/complete/<name>.md is first committed. The report includes open
+and completed counts, the average cycle time, and for each recent week the
+number of issues opened, completed and still open:
+
+  swissarmyhammer issue stats
+  swissarmyhammer issue stats --weeks 13
+  swissarmyhammer issue stats --format json
+")]
+    Stats {
+        /// Number of weeks of throughput to show, ending with the current week
+        #[arg(long, default_value_t = swissarmyhammer::issues::DEFAULT_STATS_WEEKS, value_parser = clap::value_parser!(u64).range(1..).map(|weeks| weeks as usize))]
+        weeks: usize,
+        /// Output format
+        #[arg(short, long, value_enum, default_value = "table")]
+        format: OutputFormat,
+    },
+    /// List open issues that have been open too long
+    #[command(long_about = "
+List open issues older than an age, oldest first. Ages are hours, days or
+weeks, such as 12h, 30d or 2w. Without --older-than, issues past the SLA
+warning age are listed, or issues older than 30 days when no SLA is set.
+
+SLA rules are set with environment variables. Issues past them are also
+flagged in issue list and reported by doctor:
+
+  export SWISSARMYHAMMER_ISSUE_SLA_WARN_DAYS=14
+  export SWISSARMYHAMMER_ISSUE_SLA_ESCALATE_DAYS=30
+  swissarmyhammer issue stale
+  swissarmyhammer issue stale --older-than 60d --format json
+
+--escalate adds the SWISSARMYHAMMER_ISSUE_SLA_ESCALATE_LABEL label, escalated
+by default, to the listed issues past the escalation age:
+
+  swissarmyhammer issue stale --escalate
+")]
+    Stale {
+        /// Only list issues at least this old, such as 30d
+        #[arg(long, value_name = "AGE")]
+        older_than: Option<String>,
+        /// Label the listed issues that are past the escalation age
+        #[arg(long)]
+        escalate: bool,
+        /// Output format
+        #[arg(short, long, value_enum, default_value = "table")]
+        format: OutputFormat,
+    },
+    /// Complete, label or move many issues at once
+    #[command(long_about = "
+Complete, label or move many issues at once. Select issues by name or by glob
+pattern, or pipe names on stdin one per line.
+
+Every change is planned before any file is touched, and the plan is shown as
+a summary. Nothing changes until the command is run again with --yes, and then
+the changes are all or nothing: if one issue can't be changed, the issues
+already changed are restored:
+
+  swissarmyhammer issue bulk complete '*_stale_*'
+  swissarmyhammer issue bulk complete '*_stale_*' --yes
+  swissarmyhammer issue bulk label auth 000012_login 000015_signup --yes
+  swissarmyhammer issue bulk move --to ../team/issues '*_shared_*' --yes
+  swissarmyhammer issue bulk complete --yes < stale_issues.txt
+")]
+    Bulk {
+        #[command(subcommand)]
+        action: IssueBulkCommands,
+    },
+    /// Rename existing issues to the configured numbering scheme
+    #[command(long_about = "
+Rename existing issues so their names start with an identifier of the issue
+numbering scheme, and rewrite references to the old names in every issue.
+The scheme is set with SWISSARMYHAMMER_ISSUE_NUMBERING, or given with
+--scheme:
+
+  name     no identifier, the default
+  numeric  000123_fix_login
+  ulid     01J3ZQ8V5E6KX2M9T4R7B1C0DF_fix_login
+  date     2024-07-001_fix_login
+  project  PROJ-123_fix_login, with SWISSARMYHAMMER_ISSUE_PROJECT_PREFIX
+           or --prefix
+
+Issues are numbered in the order they were created, keeping the name after
+any identifier they already had. Issues already named under the scheme are
+left alone. Nothing changes until the command is run again with --yes, and
+then the changes are all or nothing:
+
+  swissarmyhammer issue renumber --scheme project --prefix PROJ
+  swissarmyhammer issue renumber --scheme project --prefix PROJ --yes
+  swissarmyhammer issue renumber --scheme name --yes
+")]
+    Renumber {
+        /// Numbering scheme; the configured scheme when omitted
+        #[arg(long, value_parser = ["name", "numeric", "ulid", "date", "project"])]
+        scheme: Option<String>,
+        /// Prefix for the project scheme, such as PROJ
+        #[arg(long)]
+        prefix: Option<String>,
+        /// Apply the changes instead of only showing them
+        #[arg(short, long)]
+        yes: bool,
+        /// Output format
+        #[arg(short, long, value_enum, default_value = "table")]
+        format: OutputFormat,
+    },
+    /// Rename existing issue branches to the configured branch template
+    #[command(long_about = "
+Rename the branches of existing issues after the issue branch template
+changes. Branches are named by SWISSARMYHAMMER_ISSUE_BRANCH_TEMPLATE, using:
+
+  {name}    the issue name, 000123_fix_login
+  {number}  the identifier the name starts with, 000123
+  {slug}    the name after the identifier, fix_login
+
+Each issue branch named by the --from template, issue/{name} by default, is
+renamed to its name under the configured template. Branches whose new name is
+taken, and issues of other repositories, are left alone. Nothing changes until
+the command is run again with --yes, and then the renames are all or nothing:
+
+  SWISSARMYHAMMER_ISSUE_BRANCH_TEMPLATE='feature/{number}-{slug}' \\
+    swissarmyhammer issue rename-branches --yes
+")]
+    RenameBranches {
+        /// Template the branches are named with now
+        #[arg(long, value_name = "TEMPLATE", default_value = "issue/{name}")]
+        from: String,
+        /// Apply the changes instead of only showing them
+        #[arg(short, long)]
+        yes: bool,
+        /// Output format
+        #[arg(short, long, value_enum, default_value = "table")]
+        format: OutputFormat,
+    },
+    /// Work several issues at once, each in its own worktree
+    #[command(long_about = "
+Work several pending issues at once. Each issue's issue/<name> branch is
+checked out in a worktree under .swissarmyhammer/worktrees and a workflow,
+work_issue by default, runs in it. Up to --jobs workflows run at the same
+time, and as each run completes its branch is merged into the main branch,
+one at a time.
+
+A branch that conflicts with the issues merged before it is left unmerged,
+with its worktree, for resolving by hand. Issues are picked by name or glob
+pattern, or all pending issues highest priority first. The working directory
+must be clean, since worktrees start from the last commit:
+
+  swissarmyhammer issue parallel --jobs 4
+  swissarmyhammer issue parallel '*_api_*' --jobs 2
+  swissarmyhammer issue parallel --workflow my_issue_flow --format json
+")]
+    Parallel {
+        /// Issue names or glob patterns; all pending issues when none are given
+        patterns: Vec<String>,
+        /// Number of issues worked at the same time
+        #[arg(short, long, default_value_t = 2, value_parser = clap::value_parser!(u64).range(1..).map(|jobs| jobs as usize))]
+        jobs: usize,
+        /// Workflow run for each issue; it must not merge the issue branch
+        #[arg(long, default_value = swissarmyhammer::workflow::DEFAULT_ISSUE_WORKFLOW)]
+        workflow: String,
+        /// Output format
+        #[arg(short, long, value_enum, default_value = "table")]
+        format: OutputFormat,
+    },
+    /// List or run the workflows queued for new issues
+    #[command(long_about = "
+List the workflow runs queued for new issues. An issue created with a
+workflow field in its front matter gets a run of that workflow queued, with
+the issue's name in the issue_name variable:
+
+  ---
+  workflow: work_issue
+  ---
+  # Fix the login bug
+
+Issues created by the MCP server queue runs too. --run runs them here, one
+after another, oldest first, the way flow run does. When a run completes, the issue moves to
+SWISSARMYHAMMER_ISSUE_WORKFLOW_COMPLETE_STATE, complete by default. A failed
+run moves it to SWISSARMYHAMMER_ISSUE_WORKFLOW_FAILED_STATE when set:
+
+  swissarmyhammer issue queue
+  swissarmyhammer issue queue --run
+  swissarmyhammer issue queue --run --format json
+")]
+    Queue {
+        /// Run the queued workflows instead of listing them
+        #[arg(long)]
+        run: bool,
+        /// Output format
+        #[arg(short, long, value_enum, default_value = "table")]
+        format: OutputFormat,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum IssueBulkCommands {
+    /// Mark the selected issues complete
+    Complete {
+        /// Issue names or glob patterns; read from stdin when omitted or -
+        patterns: Vec<String>,
+        /// Apply the changes instead of only showing them
+        #[arg(short, long)]
+        yes: bool,
+        /// Output format
+        #[arg(short, long, value_enum, default_value = "table")]
+        format: OutputFormat,
+    },
+    /// Add a label to the front matter of the selected issues
+    Label {
+        /// Label to add
+        label: String,
+        /// Issue names or glob patterns; read from stdin when omitted or -
+        patterns: Vec<String>,
+        /// Apply the changes instead of only showing them
+        #[arg(short, long)]
+        yes: bool,
+        /// Output format
+        #[arg(short, long, value_enum, default_value = "table")]
+        format: OutputFormat,
+    },
+    /// Move the selected issues to another issues directory
+    Move {
+        /// Issues directory to move the issues to
+        #[arg(long)]
+        to: std::path::PathBuf,
+        /// Issue names or glob patterns; read from stdin when omitted or -
+        patterns: Vec<String>,
+        /// Apply the changes instead of only showing them
+        #[arg(short, long)]
+        yes: bool,
+        /// Output format
+        #[arg(short, long, value_enum, default_value = "table")]
+        format: OutputFormat,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -784,13 +1988,26 @@ pub enum MemoCommands {
         /// Memo content (use - for stdin)
         #[arg(short, long)]
         content: Option<String>,
+        /// Notebook to file the memo in, created if it doesn't exist
+        #[arg(short, long)]
+        notebook: Option<String>,
     },
     /// List all memos
-    List,
+    List {
+        /// Output format
+        #[arg(short, long, value_enum, default_value = "table")]
+        format: OutputFormat,
+        /// Only list the memos filed in this notebook
+        #[arg(short, long)]
+        notebook: Option<String>,
+    },
     /// Get a specific memo by ID
     Get {
         /// Memo ID (ULID)
         id: String,
+        /// Output format
+        #[arg(short, long, value_enum, default_value = "table")]
+        format: OutputFormat,
     },
     /// Update a memo's content
     Update {
@@ -809,9 +2026,135 @@ pub enum MemoCommands {
     Search {
         /// Search query
         query: String,
+        /// Output format
+        #[arg(short, long, value_enum, default_value = "table")]
+        format: OutputFormat,
     },
     /// Get all memos as context for AI
-    Context,
+    Context {
+        /// Only include memos that mention every word of the query
+        #[arg(short, long)]
+        query: Option<String>,
+        /// Approximate token budget; older memos are left out once it is spent
+        #[arg(long)]
+        max_tokens: Option<usize>,
+    },
+    /// Check memo files for corruption and optionally restore them from backups
+    Verify {
+        /// Restore corrupted memos from their most recent backup snapshot
+        #[arg(long)]
+        restore: bool,
+    },
+    /// Remove leftover files and duplicate memos and rebuild the search index
+    Compact {
+        /// Only report what would be removed and rewritten
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Find near-duplicate memos and merge them
+    #[command(long_about = "
+Find memos that say nearly the same thing, judged by the words their titles
+share and the runs of three words their content shares, and list them as
+merge candidates. With --merge the older memo of each pair is kept, the
+paragraphs only the newer memo has are appended to it, the newer memo is
+deleted and [[Title]] links to it in other memos are pointed at the kept one:
+
+  swissarmyhammer memo dedupe
+  swissarmyhammer memo dedupe --threshold 0.6 --merge
+
+Both memos keep their history, so memo history and memo restore still reach
+the deleted one. Each memo is merged once per run; run again to merge groups
+of three or more.
+")]
+    Dedupe {
+        /// Lowest similarity, from 0 to 1, at which memos are merge candidates
+        #[arg(long, default_value_t = swissarmyhammer::memoranda::dedupe::DEFAULT_SIMILARITY_THRESHOLD)]
+        threshold: f64,
+        /// Merge the candidates instead of only listing them
+        #[arg(long)]
+        merge: bool,
+    },
+    /// Show every change made to a memo as a diff
+    #[command(long_about = "
+Show every change made to a memo's content, oldest first, each as a diff
+against the version before it. Each change is numbered with the version it
+produced; pass that number to memo restore to bring the content back:
+
+  swissarmyhammer memo history Meeting_Notes
+  swissarmyhammer memo restore Meeting_Notes 2
+
+The history is kept in .history beside the memo files, and outlives deleted
+memos.
+")]
+    History {
+        /// Memo ID
+        id: String,
+        /// Output format
+        #[arg(short, long, value_enum, default_value = "table")]
+        format: OutputFormat,
+    },
+    /// Restore a memo to a version from its history
+    Restore {
+        /// Memo ID
+        id: String,
+        /// Version to restore, as numbered by memo history
+        version: usize,
+    },
+    /// Move a memo into a notebook, keeping its ID
+    #[command(long_about = "
+Move a memo into a notebook, or back to the top level when no notebook is
+given. The memo keeps its ID, content and history, so links and commands
+that name it keep working. The notebook is created if it doesn't exist:
+
+  swissarmyhammer memo move Meeting_Notes work
+  swissarmyhammer memo move Meeting_Notes
+")]
+    Move {
+        /// Memo ID
+        id: String,
+        /// Notebook to move the memo to; leave out for the top level
+        notebook: Option<String>,
+    },
+    /// Manage the notebooks memos are filed in
+    #[command(long_about = "
+Notebooks group memos into subdirectories of the memos directory. Memo IDs are
+unique across notebooks, so a memo is found by its ID wherever it is filed:
+
+  swissarmyhammer memo notebook create work
+  swissarmyhammer memo create \"Standup\" --notebook work
+  swissarmyhammer memo list --notebook work
+  swissarmyhammer memo notebook rename work office
+  swissarmyhammer memo notebook delete office
+
+Only empty notebooks can be deleted; move or delete their memos first.
+")]
+    Notebook {
+        #[command(subcommand)]
+        subcommand: NotebookCommands,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum NotebookCommands {
+    /// List the notebooks
+    List,
+    /// Create an empty notebook
+    Create {
+        /// Notebook name
+        name: String,
+    },
+    /// Delete an empty notebook
+    Delete {
+        /// Notebook name
+        name: String,
+    },
+    /// Rename a notebook, keeping the IDs of its memos
+    Rename {
+        /// Current notebook name
+        name: String,
+        /// New notebook name
+        new_name: String,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -837,13 +2180,70 @@ pub enum SearchCommands {
     },
 }
 
-impl Cli {
-    pub fn parse_args() -> Self {
-        Self::parse()
-    }
-
-    #[allow(dead_code)]
-    pub fn try_parse_from_args<I, T>(args: I) -> Result<Self, clap::Error>
+#[derive(Subcommand, Debug)]
+pub enum LogsSubcommand {
+    /// Print the end of the log and follow it as it is written
+    Tail {
+        /// Number of lines to print before following
+        #[arg(short = 'n', long, default_value = "20")]
+        lines: usize,
+        /// Exit after printing the last lines
+        #[arg(long)]
+        no_follow: bool,
+    },
+}
+
+/// Part of the SwissArmyHammer state that is backed up
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BackupComponent {
+    Prompts,
+    Workflows,
+    Memos,
+    Issues,
+    Runs,
+    Config,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum BackupSubcommand {
+    /// Write a new backup archive
+    Create {
+        /// Components to back up, all of them by default
+        #[arg(long, value_enum, value_delimiter = ',')]
+        only: Vec<BackupComponent>,
+        /// Directory to write the archive to
+        #[arg(long)]
+        dir: Option<std::path::PathBuf>,
+    },
+    /// List backup archives, newest first
+    List {
+        /// Directory to look for archives in
+        #[arg(long)]
+        dir: Option<std::path::PathBuf>,
+        /// Output format
+        #[arg(long, value_enum, default_value = "table")]
+        format: OutputFormat,
+    },
+    /// Restore files from a backup archive
+    Restore {
+        /// Archive path, or the file name of an archive in the backup directory
+        archive: std::path::PathBuf,
+        /// Components to restore, all of them by default
+        #[arg(long, value_enum, value_delimiter = ',')]
+        only: Vec<BackupComponent>,
+        /// List the files that would be restored without writing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+impl Cli {
+    pub fn parse_args() -> Self {
+        Self::parse()
+    }
+
+    #[allow(dead_code)]
+    pub fn try_parse_from_args<I, T>(args: I) -> Result<Self, clap::Error>
     where
         I: IntoIterator<Item = T>,
         T: Into<std::ffi::OsString> + Clone,
@@ -851,6 +2251,13 @@ impl Cli {
         <Self as Parser>::try_parse_from(args)
     }
 
+    /// The sources prompts are loaded from, as set by `--source` and `--exclude-source`
+    pub fn source_filter(&self) -> swissarmyhammer::SourceFilter {
+        let sources =
+            |args: &[PromptSourceArg]| args.iter().cloned().map(PromptSource::from).collect();
+        swissarmyhammer::SourceFilter::new(sources(&self.sources), sources(&self.exclude_sources))
+    }
+
     pub fn is_tty() -> bool {
         io::stdout().is_terminal()
     }
@@ -899,7 +2306,16 @@ mod tests {
         assert!(result.is_ok());
 
         let cli = result.unwrap();
-        assert!(matches!(cli.command, Some(Commands::Serve)));
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Serve { read_only: false })
+        ));
+
+        let cli = Cli::try_parse_from_args(["swissarmyhammer", "serve", "--read-only"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Serve { read_only: true })
+        ));
     }
 
     #[test]
@@ -908,7 +2324,28 @@ mod tests {
         assert!(result.is_ok());
 
         let cli = result.unwrap();
-        assert!(matches!(cli.command, Some(Commands::Doctor)));
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Doctor {
+                fix: false,
+                yes: false
+            })
+        ));
+    }
+
+    #[test]
+    fn test_cli_doctor_fix() {
+        let cli =
+            Cli::try_parse_from_args(["swissarmyhammer", "doctor", "--fix", "--yes"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Doctor {
+                fix: true,
+                yes: true
+            })
+        ));
+
+        assert!(Cli::try_parse_from_args(["swissarmyhammer", "doctor", "--yes"]).is_err());
     }
 
     #[test]
@@ -938,7 +2375,7 @@ mod tests {
 
         let cli = result.unwrap();
         assert!(cli.verbose);
-        assert!(matches!(cli.command, Some(Commands::Serve)));
+        assert!(matches!(cli.command, Some(Commands::Serve { .. })));
     }
 
     #[test]
@@ -966,6 +2403,11 @@ mod tests {
                 copy,
                 save,
                 debug,
+                preset,
+                save_preset,
+                interactive,
+                no_interactive,
+                all,
             } = subcommand
             {
                 assert_eq!(prompt_name, Some("help".to_string()));
@@ -976,6 +2418,11 @@ mod tests {
                 assert!(!copy);
                 assert_eq!(save, None);
                 assert!(!debug);
+                assert_eq!(preset, None);
+                assert_eq!(save_preset, None);
+                assert!(!interactive);
+                assert!(!no_interactive);
+                assert!(!all);
             } else {
                 panic!("Expected Test subcommand");
             }
@@ -1001,6 +2448,11 @@ mod tests {
                 copy,
                 save,
                 debug,
+                preset,
+                save_preset,
+                interactive,
+                no_interactive,
+                all,
             } = subcommand
             {
                 assert_eq!(prompt_name, None);
@@ -1011,6 +2463,11 @@ mod tests {
                 assert!(!copy);
                 assert_eq!(save, None);
                 assert!(!debug);
+                assert_eq!(preset, None);
+                assert_eq!(save_preset, None);
+                assert!(!interactive);
+                assert!(!no_interactive);
+                assert!(!all);
             } else {
                 panic!("Expected Test subcommand");
             }
@@ -1044,6 +2501,11 @@ mod tests {
                 copy,
                 save,
                 debug,
+                preset,
+                save_preset,
+                interactive,
+                no_interactive,
+                all,
             } = subcommand
             {
                 assert_eq!(prompt_name, Some("help".to_string()));
@@ -1054,6 +2516,11 @@ mod tests {
                 assert!(!copy);
                 assert_eq!(save, None);
                 assert!(!debug);
+                assert_eq!(preset, None);
+                assert_eq!(save_preset, None);
+                assert!(!interactive);
+                assert!(!no_interactive);
+                assert!(!all);
             } else {
                 panic!("Expected Test subcommand");
             }
@@ -1088,6 +2555,11 @@ mod tests {
                 copy,
                 save,
                 debug,
+                preset,
+                save_preset,
+                interactive,
+                no_interactive,
+                all,
             } = subcommand
             {
                 assert_eq!(prompt_name, Some("help".to_string()));
@@ -1098,6 +2570,11 @@ mod tests {
                 assert!(copy);
                 assert_eq!(save, Some("output.md".to_string()));
                 assert!(debug);
+                assert_eq!(preset, None);
+                assert_eq!(save_preset, None);
+                assert!(!interactive);
+                assert!(!no_interactive);
+                assert!(!all);
             } else {
                 panic!("Expected Test subcommand");
             }
@@ -1133,6 +2610,11 @@ mod tests {
                 copy,
                 save,
                 debug,
+                preset,
+                save_preset,
+                interactive,
+                no_interactive,
+                all,
             } = subcommand
             {
                 assert_eq!(prompt_name, Some("help".to_string()));
@@ -1143,6 +2625,11 @@ mod tests {
                 assert!(!copy);
                 assert_eq!(save, None);
                 assert!(!debug);
+                assert_eq!(preset, None);
+                assert_eq!(save_preset, None);
+                assert!(!interactive);
+                assert!(!no_interactive);
+                assert!(!all);
             } else {
                 panic!("Expected Test subcommand");
             }
@@ -1151,6 +2638,88 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cli_test_subcommand_with_presets() {
+        let result = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "prompt",
+            "test",
+            "code-review",
+            "--preset",
+            "rust-strict",
+            "--save-preset",
+            "rust-lenient",
+        ]);
+        assert!(result.is_ok());
+
+        let cli = result.unwrap();
+        if let Some(Commands::Prompt {
+            subcommand:
+                PromptSubcommand::Test {
+                    preset,
+                    save_preset,
+                    ..
+                },
+        }) = cli.command
+        {
+            assert_eq!(preset, Some("rust-strict".to_string()));
+            assert_eq!(save_preset, Some("rust-lenient".to_string()));
+        } else {
+            panic!("Expected Prompt Test command");
+        }
+    }
+
+    #[test]
+    fn test_cli_test_subcommand_all() {
+        let result = Cli::try_parse_from_args(["swissarmyhammer", "prompt", "test", "--all"]);
+        let cli = result.unwrap();
+        if let Some(Commands::Prompt {
+            subcommand: PromptSubcommand::Test {
+                all, prompt_name, ..
+            },
+        }) = cli.command
+        {
+            assert!(all);
+            assert_eq!(prompt_name, None);
+        } else {
+            panic!("Expected Prompt Test command");
+        }
+
+        let result =
+            Cli::try_parse_from_args(["swissarmyhammer", "prompt", "test", "help", "--all"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_cli_test_subcommand_interactive_flags() {
+        let result = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "prompt",
+            "test",
+            "code-review",
+            "--interactive",
+            "--no-interactive",
+        ]);
+        assert!(result.is_ok());
+
+        let cli = result.unwrap();
+        if let Some(Commands::Prompt {
+            subcommand:
+                PromptSubcommand::Test {
+                    interactive,
+                    no_interactive,
+                    ..
+                },
+        }) = cli.command
+        {
+            // The last flag given wins
+            assert!(!interactive);
+            assert!(no_interactive);
+        } else {
+            panic!("Expected Prompt Test command");
+        }
+    }
+
     #[test]
     fn test_cli_search_subcommand_basic() {
         let result =
@@ -1299,6 +2868,7 @@ mod tests {
                 source,
                 category,
                 search,
+                include_deprecated,
             } = subcommand
             {
                 assert!(matches!(format, OutputFormat::Table));
@@ -1306,6 +2876,7 @@ mod tests {
                 assert_eq!(source, None);
                 assert_eq!(category, None);
                 assert_eq!(search, None);
+                assert!(!include_deprecated);
             } else {
                 panic!("Expected List subcommand");
             }
@@ -1314,6 +2885,348 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cli_prompt_export_subcommand() {
+        let result = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "prompt",
+            "export",
+            "--format",
+            "html",
+            "./site",
+        ]);
+        assert!(result.is_ok());
+
+        let cli = result.unwrap();
+        if let Some(Commands::Prompt {
+            subcommand: PromptSubcommand::Export { format, output_dir },
+        }) = cli.command
+        {
+            assert_eq!(format, ExportFormat::Html);
+            assert_eq!(output_dir, std::path::PathBuf::from("./site"));
+        } else {
+            panic!("Expected Prompt Export command");
+        }
+
+        // The output directory is required
+        let result = Cli::try_parse_from_args(["swissarmyhammer", "prompt", "export"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_cli_prompt_import_subcommand() {
+        let result = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "prompt",
+            "import",
+            "--format",
+            "openai-json",
+            "prompts.json",
+        ]);
+        assert!(result.is_ok());
+
+        let cli = result.unwrap();
+        if let Some(Commands::Prompt {
+            subcommand:
+                PromptSubcommand::Import {
+                    format,
+                    path,
+                    output_dir,
+                    force,
+                },
+        }) = cli.command
+        {
+            assert_eq!(format, ImportFormat::OpenaiJson);
+            assert_eq!(path, std::path::PathBuf::from("prompts.json"));
+            assert_eq!(
+                output_dir,
+                std::path::PathBuf::from(".swissarmyhammer/prompts")
+            );
+            assert!(!force);
+        } else {
+            panic!("Expected Prompt Import command");
+        }
+
+        // The format is required
+        let result = Cli::try_parse_from_args(["swissarmyhammer", "prompt", "import", "./prompts"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_cli_prompt_deps_subcommand() {
+        let result = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "prompt",
+            "deps",
+            "coding_standards",
+            "--format",
+            "json",
+        ]);
+        assert!(result.is_ok());
+
+        let cli = result.unwrap();
+        if let Some(Commands::Prompt {
+            subcommand: PromptSubcommand::Deps { name, format },
+        }) = cli.command
+        {
+            assert_eq!(name, "coding_standards");
+            assert!(matches!(format, OutputFormat::Json));
+        } else {
+            panic!("Expected Prompt Deps command");
+        }
+    }
+
+    #[test]
+    fn test_cli_prompt_diff_sources() {
+        let cli = Cli::try_parse_from_args(["swissarmyhammer", "prompt", "diff-sources"]).unwrap();
+        if let Some(Commands::Prompt {
+            subcommand:
+                PromptSubcommand::DiffSources {
+                    from,
+                    to,
+                    format,
+                    names_only,
+                },
+        }) = cli.command
+        {
+            assert_eq!(from, PromptSourceArg::Builtin);
+            assert_eq!(to, PromptSourceArg::Local);
+            assert!(matches!(format, OutputFormat::Table));
+            assert!(!names_only);
+        } else {
+            panic!("Expected Prompt DiffSources command");
+        }
+
+        let cli = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "prompt",
+            "diff-sources",
+            "--from",
+            "user",
+            "--to",
+            "local",
+            "--names-only",
+        ])
+        .unwrap();
+        if let Some(Commands::Prompt {
+            subcommand:
+                PromptSubcommand::DiffSources {
+                    from, names_only, ..
+                },
+        }) = cli.command
+        {
+            assert_eq!(from, PromptSourceArg::User);
+            assert!(names_only);
+        } else {
+            panic!("Expected Prompt DiffSources command");
+        }
+    }
+
+    #[test]
+    fn test_cli_prompt_bench() {
+        let cli = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "prompt",
+            "bench",
+            "code-review",
+            "--iterations",
+            "500",
+            "--arg",
+            "language=rust",
+        ])
+        .unwrap();
+        if let Some(Commands::Prompt {
+            subcommand:
+                PromptSubcommand::Bench {
+                    name,
+                    iterations,
+                    arguments,
+                    format,
+                },
+        }) = cli.command
+        {
+            assert_eq!(name, "code-review");
+            assert_eq!(iterations, 500);
+            assert_eq!(arguments, vec!["language=rust"]);
+            assert!(matches!(format, OutputFormat::Table));
+        } else {
+            panic!("Expected Prompt Bench command");
+        }
+
+        let cli = Cli::try_parse_from_args(["swissarmyhammer", "prompt", "bench", "help"]).unwrap();
+        if let Some(Commands::Prompt {
+            subcommand: PromptSubcommand::Bench { iterations, .. },
+        }) = cli.command
+        {
+            assert_eq!(iterations, 100);
+        } else {
+            panic!("Expected Prompt Bench command");
+        }
+    }
+
+    #[test]
+    fn test_cli_prompt_runs() {
+        let cli = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "prompt",
+            "runs",
+            "code-review",
+            "--limit",
+            "5",
+            "--format",
+            "json",
+        ])
+        .unwrap();
+        if let Some(Commands::Prompt {
+            subcommand:
+                PromptSubcommand::Runs {
+                    name,
+                    limit,
+                    format,
+                },
+        }) = cli.command
+        {
+            assert_eq!(name.as_deref(), Some("code-review"));
+            assert_eq!(limit, 5);
+            assert!(matches!(format, OutputFormat::Json));
+        } else {
+            panic!("Expected Prompt Runs command");
+        }
+
+        let cli = Cli::try_parse_from_args(["swissarmyhammer", "prompt", "runs"]).unwrap();
+        if let Some(Commands::Prompt {
+            subcommand: PromptSubcommand::Runs { name, limit, .. },
+        }) = cli.command
+        {
+            assert!(name.is_none());
+            assert_eq!(limit, 20);
+        } else {
+            panic!("Expected Prompt Runs command");
+        }
+    }
+
+    #[test]
+    fn test_cli_prompt_audit() {
+        let cli = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "prompt",
+            "audit",
+            "--max-length",
+            "4000",
+            "--format",
+            "json",
+        ])
+        .unwrap();
+        if let Some(Commands::Prompt {
+            subcommand: PromptSubcommand::Audit { max_length, format },
+        }) = cli.command
+        {
+            assert_eq!(max_length, 4000);
+            assert!(matches!(format, OutputFormat::Json));
+        } else {
+            panic!("Expected Prompt Audit command");
+        }
+
+        let cli = Cli::try_parse_from_args(["swissarmyhammer", "prompt", "audit"]).unwrap();
+        if let Some(Commands::Prompt {
+            subcommand: PromptSubcommand::Audit { max_length, .. },
+        }) = cli.command
+        {
+            assert_eq!(
+                max_length,
+                swissarmyhammer::prompt_audit::DEFAULT_MAX_PROMPT_LENGTH
+            );
+        } else {
+            panic!("Expected Prompt Audit command");
+        }
+    }
+
+    #[test]
+    fn test_cli_validate_impact() {
+        let result = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "validate",
+            "--impact",
+            ".swissarmyhammer/prompts/header.md",
+        ]);
+        assert!(result.is_ok());
+
+        let cli = result.unwrap();
+        if let Some(Commands::Validate { impact, .. }) = cli.command {
+            assert_eq!(
+                impact,
+                Some(std::path::PathBuf::from(
+                    ".swissarmyhammer/prompts/header.md"
+                ))
+            );
+        } else {
+            panic!("Expected Validate command");
+        }
+    }
+
+    #[test]
+    fn test_cli_validate_jobs() {
+        let cli = Cli::try_parse_from_args(["swissarmyhammer", "validate", "--jobs", "4"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Validate { jobs: 4, .. })
+        ));
+
+        let cli = Cli::try_parse_from_args(["swissarmyhammer", "validate"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Validate { jobs: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn test_cli_validate_watch() {
+        let cli = Cli::try_parse_from_args(["swissarmyhammer", "validate", "--watch"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Validate { watch: true, .. })
+        ));
+
+        // Watching reports on every change, not on one file's impact
+        let result = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "validate",
+            "--watch",
+            "--impact",
+            "prompts/header.md",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_cli_prompt_source_filter() {
+        let cli = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "--source",
+            "builtin,local",
+            "--exclude-source",
+            "local",
+            "validate",
+        ])
+        .unwrap();
+        let filter = cli.source_filter();
+        assert!(filter.allows(&PromptSource::Builtin));
+        assert!(!filter.allows(&PromptSource::User));
+        assert!(!filter.allows(&PromptSource::Local));
+
+        let cli = Cli::try_parse_from_args(["swissarmyhammer", "validate"]).unwrap();
+        assert_eq!(
+            cli.source_filter(),
+            swissarmyhammer::SourceFilter::default()
+        );
+
+        // The list command's own --source still filters the listing
+        let cli =
+            Cli::try_parse_from_args(["swissarmyhammer", "prompt", "list", "--source", "user"])
+                .unwrap();
+        assert!(cli.sources.is_empty());
+    }
+
     #[test]
     fn test_cli_validate_command() {
         let result = Cli::try_parse_from_args(["swissarmyhammer", "validate"]);
@@ -1324,11 +3237,17 @@ mod tests {
             quiet,
             format,
             workflow_dirs,
+            impact,
+            watch,
+            jobs,
         }) = cli.command
         {
             assert!(!quiet);
             assert!(matches!(format, ValidateFormat::Text));
             assert!(workflow_dirs.is_empty());
+            assert!(impact.is_none());
+            assert!(!watch);
+            assert_eq!(jobs, 0);
         } else {
             panic!("Expected Validate command");
         }
@@ -1352,6 +3271,7 @@ mod tests {
             quiet,
             format,
             workflow_dirs,
+            ..
         }) = cli.command
         {
             assert!(quiet);
@@ -1363,17 +3283,114 @@ mod tests {
     }
 
     #[test]
-    fn test_cli_flow_test_subcommand() {
-        let result = Cli::try_parse_from_args(["swissarmyhammer", "flow", "test", "my-workflow"]);
+    fn test_cli_flow_compare_subcommand() {
+        let result = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "flow",
+            "compare",
+            "01H8XYZ",
+            "01H8ABC",
+            "--format",
+            "json",
+        ]);
         assert!(result.is_ok());
 
         let cli = result.unwrap();
-        if let Some(Commands::Flow { subcommand }) = cli.command {
+        if let Some(Commands::Flow {
+            subcommand:
+                FlowSubcommand::Compare {
+                    run_a,
+                    run_b,
+                    format,
+                },
+        }) = cli.command
+        {
+            assert_eq!(run_a, "01H8XYZ");
+            assert_eq!(run_b, "01H8ABC");
+            assert!(matches!(format, OutputFormat::Json));
+        } else {
+            panic!("Expected Flow Compare command");
+        }
+    }
+
+    #[test]
+    fn test_cli_flow_artifacts_subcommand() {
+        let cli =
+            Cli::try_parse_from_args(["swissarmyhammer", "flow", "artifacts", "01H8XYZ"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Flow {
+                subcommand: FlowSubcommand::Artifacts {
+                    ref run_id,
+                    format: OutputFormat::Table,
+                },
+            }) if run_id == "01H8XYZ"
+        ));
+    }
+
+    #[test]
+    fn test_cli_flow_cancel_subcommand() {
+        let result = Cli::try_parse_from_args(["swissarmyhammer", "flow", "cancel", "01H8XYZ"]);
+        assert!(result.is_ok());
+
+        let cli = result.unwrap();
+        if let Some(Commands::Flow {
+            subcommand: FlowSubcommand::Cancel { run_id },
+        }) = cli.command
+        {
+            assert_eq!(run_id, "01H8XYZ");
+        } else {
+            panic!("Expected Flow Cancel command");
+        }
+
+        assert!(Cli::try_parse_from_args(["swissarmyhammer", "flow", "cancel"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_flow_estimate_subcommand() {
+        let cli = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "flow",
+            "estimate",
+            "implement",
+            "--var",
+            "file=main.rs",
+            "--format",
+            "json",
+        ])
+        .unwrap();
+        if let Some(Commands::Flow {
+            subcommand:
+                FlowSubcommand::Estimate {
+                    workflow,
+                    vars,
+                    set,
+                    format,
+                },
+        }) = cli.command
+        {
+            assert_eq!(workflow, "implement");
+            assert_eq!(vars, vec!["file=main.rs"]);
+            assert!(set.is_empty());
+            assert!(matches!(format, OutputFormat::Json));
+        } else {
+            panic!("Expected Flow Estimate command");
+        }
+    }
+
+    #[test]
+    fn test_cli_flow_test_subcommand() {
+        let result = Cli::try_parse_from_args(["swissarmyhammer", "flow", "test", "my-workflow"]);
+        assert!(result.is_ok());
+
+        let cli = result.unwrap();
+        if let Some(Commands::Flow { subcommand }) = cli.command {
             if let FlowSubcommand::Test {
                 workflow,
                 vars,
                 set,
                 interactive,
+                snapshots,
                 timeout,
                 quiet,
             } = subcommand
@@ -1382,6 +3399,7 @@ mod tests {
                 assert!(vars.is_empty());
                 assert!(set.is_empty());
                 assert!(!interactive);
+                assert!(!snapshots);
                 assert_eq!(timeout, None);
                 assert!(!quiet);
             } else {
@@ -1406,6 +3424,7 @@ mod tests {
             "--set",
             "version=2.0",
             "--interactive",
+            "--snapshots",
             "--timeout",
             "30s",
             "--quiet",
@@ -1419,6 +3438,7 @@ mod tests {
                 vars,
                 set,
                 interactive,
+                snapshots,
                 timeout,
                 quiet,
             } = subcommand
@@ -1427,6 +3447,7 @@ mod tests {
                 assert_eq!(vars, vec!["input=test"]);
                 assert_eq!(set, vec!["author=Jane", "version=2.0"]);
                 assert!(interactive);
+                assert!(snapshots);
                 assert_eq!(timeout, Some("30s".to_string()));
                 assert!(quiet);
             } else {
@@ -1437,6 +3458,174 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cli_flow_run_no_progress() {
+        let result = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "flow",
+            "run",
+            "my-workflow",
+            "--no-progress",
+        ]);
+        assert!(result.is_ok());
+
+        if let Some(Commands::Flow {
+            subcommand: FlowSubcommand::Run { no_progress, .. },
+        }) = result.unwrap().command
+        {
+            assert!(no_progress);
+        } else {
+            panic!("Expected Flow Run command");
+        }
+    }
+
+    #[test]
+    fn test_cli_flow_run_update_snapshots() {
+        let result = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "flow",
+            "run",
+            "my-workflow",
+            "--dry-run",
+            "--update-snapshots",
+        ]);
+        assert!(result.is_ok());
+
+        if let Some(Commands::Flow {
+            subcommand:
+                FlowSubcommand::Run {
+                    dry_run,
+                    update_snapshots,
+                    ..
+                },
+        }) = result.unwrap().command
+        {
+            assert!(dry_run);
+            assert!(update_snapshots);
+        } else {
+            panic!("Expected Flow Run command");
+        }
+
+        let result = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "flow",
+            "run",
+            "my-workflow",
+            "--update-snapshots",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_cli_flow_run_retries() {
+        let result = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "flow",
+            "run",
+            "nightly",
+            "--retries",
+            "3",
+            "--retry-backoff",
+            "1m",
+        ]);
+
+        if let Some(Commands::Flow {
+            subcommand:
+                FlowSubcommand::Run {
+                    retries,
+                    retry_backoff,
+                    ..
+                },
+        }) = result.unwrap().command
+        {
+            assert_eq!(retries, Some(3));
+            assert_eq!(retry_backoff.as_deref(), Some("1m"));
+        } else {
+            panic!("Expected Flow Run command");
+        }
+    }
+
+    #[test]
+    fn test_cli_flow_debug_subcommand() {
+        let result = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "flow",
+            "debug",
+            "my-workflow",
+            "--var",
+            "input=test",
+            "--break",
+            "review",
+            "--break",
+            "done",
+        ]);
+        assert!(result.is_ok());
+
+        let cli = result.unwrap();
+        if let Some(Commands::Flow {
+            subcommand:
+                FlowSubcommand::Debug {
+                    workflow,
+                    vars,
+                    set,
+                    breakpoints,
+                },
+        }) = cli.command
+        {
+            assert_eq!(workflow, "my-workflow");
+            assert_eq!(vars, vec!["input=test"]);
+            assert!(set.is_empty());
+            assert_eq!(breakpoints, vec!["review", "done"]);
+        } else {
+            panic!("Expected Flow Debug command");
+        }
+    }
+
+    #[test]
+    fn test_flow_new_command() {
+        let result = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "flow",
+            "new",
+            "fix-loop",
+            "--template",
+            "review-loop",
+            "--arg",
+            "review_prompt=review/code",
+        ]);
+        assert!(result.is_ok());
+
+        if let Some(Commands::Flow {
+            subcommand:
+                FlowSubcommand::New {
+                    name,
+                    template,
+                    args,
+                    output,
+                    force,
+                },
+        }) = result.unwrap().command
+        {
+            assert_eq!(name, "fix-loop");
+            assert_eq!(template, "review-loop");
+            assert_eq!(args, vec!["review_prompt=review/code"]);
+            assert!(output.is_none());
+            assert!(!force);
+        } else {
+            panic!("Expected Flow New command");
+        }
+
+        let result = Cli::try_parse_from_args(["swissarmyhammer", "flow", "new", "greet"]);
+        if let Some(Commands::Flow {
+            subcommand: FlowSubcommand::New { template, .. },
+        }) = result.unwrap().command
+        {
+            assert_eq!(template, "basic");
+        } else {
+            panic!("Expected Flow New command");
+        }
+    }
+
     #[test]
     fn test_parse_args_panics_on_error() {
         // This test verifies that parse_args would panic on invalid input
@@ -1466,84 +3655,673 @@ mod tests {
             PromptSource::Dynamic
         ));
 
-        // Test From<PromptSource> for PromptSourceArg
-        assert!(matches!(
-            PromptSourceArg::from(PromptSource::Builtin),
-            PromptSourceArg::Builtin
-        ));
-        assert!(matches!(
-            PromptSourceArg::from(PromptSource::User),
-            PromptSourceArg::User
-        ));
-        assert!(matches!(
-            PromptSourceArg::from(PromptSource::Local),
-            PromptSourceArg::Local
-        ));
-        assert!(matches!(
-            PromptSourceArg::from(PromptSource::Dynamic),
-            PromptSourceArg::Dynamic
-        ));
+        // Test From<PromptSource> for PromptSourceArg
+        assert!(matches!(
+            PromptSourceArg::from(PromptSource::Builtin),
+            PromptSourceArg::Builtin
+        ));
+        assert!(matches!(
+            PromptSourceArg::from(PromptSource::User),
+            PromptSourceArg::User
+        ));
+        assert!(matches!(
+            PromptSourceArg::from(PromptSource::Local),
+            PromptSourceArg::Local
+        ));
+        assert!(matches!(
+            PromptSourceArg::from(PromptSource::Dynamic),
+            PromptSourceArg::Dynamic
+        ));
+    }
+
+    #[test]
+    fn test_prompt_source_arg_equality() {
+        assert_eq!(PromptSourceArg::Builtin, PromptSourceArg::Builtin);
+        assert_ne!(PromptSourceArg::Builtin, PromptSourceArg::User);
+        assert_ne!(PromptSourceArg::User, PromptSourceArg::Local);
+        assert_ne!(PromptSourceArg::Local, PromptSourceArg::Dynamic);
+    }
+
+    #[test]
+    fn test_debug_flag() {
+        let result = Cli::try_parse_from_args(["swissarmyhammer", "--debug"]);
+        assert!(result.is_ok());
+
+        let cli = result.unwrap();
+        assert!(cli.debug);
+        assert!(!cli.verbose);
+        assert!(!cli.quiet);
+    }
+
+    #[test]
+    fn test_combined_flags() {
+        let result = Cli::try_parse_from_args(["swissarmyhammer", "--debug", "--verbose"]);
+        assert!(result.is_ok());
+
+        let cli = result.unwrap();
+        assert!(cli.debug);
+        assert!(cli.verbose);
+        assert!(!cli.quiet);
+    }
+
+    #[test]
+    fn test_issue_create_with_name() {
+        let result = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "issue",
+            "create",
+            "bug_fix",
+            "--content",
+            "Fix login bug",
+        ]);
+        assert!(result.is_ok());
+
+        let cli = result.unwrap();
+        if let Some(Commands::Issue { subcommand }) = cli.command {
+            if let IssueCommands::Create {
+                name,
+                content,
+                file,
+            } = subcommand
+            {
+                assert_eq!(name, Some("bug_fix".to_string()));
+                assert_eq!(content, Some("Fix login bug".to_string()));
+                assert_eq!(file, None);
+            } else {
+                panic!("Expected Create subcommand");
+            }
+        } else {
+            panic!("Expected Issue command");
+        }
+    }
+
+    #[test]
+    fn test_issue_stats_command() {
+        let result = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "issue",
+            "stats",
+            "--weeks",
+            "4",
+            "--format",
+            "json",
+        ]);
+        assert!(result.is_ok());
+
+        let cli = result.unwrap();
+        if let Some(Commands::Issue {
+            subcommand: IssueCommands::Stats { weeks, format },
+        }) = cli.command
+        {
+            assert_eq!(weeks, 4);
+            assert!(matches!(format, OutputFormat::Json));
+        } else {
+            panic!("Expected Issue Stats command");
+        }
+
+        // Zero weeks is rejected
+        let result =
+            Cli::try_parse_from_args(["swissarmyhammer", "issue", "stats", "--weeks", "0"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_issue_stale_command() {
+        let cli = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "issue",
+            "stale",
+            "--older-than",
+            "30d",
+            "--escalate",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Commands::Issue {
+                subcommand:
+                    IssueCommands::Stale {
+                        older_than,
+                        escalate,
+                        format,
+                    },
+            }) => {
+                assert_eq!(older_than.as_deref(), Some("30d"));
+                assert!(escalate);
+                assert!(matches!(format, OutputFormat::Table));
+            }
+            _ => panic!("Expected Issue Stale command"),
+        }
+
+        let cli = Cli::try_parse_from_args(["swissarmyhammer", "issue", "stale"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Issue {
+                subcommand: IssueCommands::Stale {
+                    older_than: None,
+                    escalate: false,
+                    ..
+                },
+            })
+        ));
+    }
+
+    #[test]
+    fn test_issue_list_sort_and_priority() {
+        let result = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "issue",
+            "list",
+            "--sort",
+            "priority",
+            "--priority",
+            "high",
+        ]);
+        assert!(result.is_ok());
+
+        let cli = result.unwrap();
+        if let Some(Commands::Issue {
+            subcommand: IssueCommands::List { sort, priority, .. },
+        }) = cli.command
+        {
+            assert_eq!(sort, IssueSortArg::Priority);
+            assert_eq!(priority, Some(IssuePriorityArg::High));
+        } else {
+            panic!("Expected Issue List command");
+        }
+
+        // issue next honors priority unless told otherwise
+        let cli = Cli::try_parse_from_args(["swissarmyhammer", "issue", "next"]).unwrap();
+        if let Some(Commands::Issue {
+            subcommand: IssueCommands::Next { sort },
+        }) = cli.command
+        {
+            assert_eq!(sort, IssueSortArg::Priority);
+        } else {
+            panic!("Expected Issue Next command");
+        }
+
+        let result =
+            Cli::try_parse_from_args(["swissarmyhammer", "issue", "list", "--priority", "urgent"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_issue_sync_command() {
+        let cli =
+            Cli::try_parse_from_args(["swissarmyhammer", "issue", "sync", "fix_login"]).unwrap();
+        match cli.command {
+            Some(Commands::Issue {
+                subcommand:
+                    IssueCommands::Sync {
+                        name,
+                        rebase,
+                        format,
+                    },
+            }) => {
+                assert_eq!(name, "fix_login");
+                assert!(!rebase);
+                assert!(matches!(format, OutputFormat::Table));
+            }
+            _ => panic!("Expected Issue Sync command"),
+        }
+
+        let cli = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "issue",
+            "sync",
+            "fix_login",
+            "--rebase",
+            "--format",
+            "json",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Commands::Issue {
+                subcommand: IssueCommands::Sync { rebase, format, .. },
+            }) => {
+                assert!(rebase);
+                assert!(matches!(format, OutputFormat::Json));
+            }
+            _ => panic!("Expected Issue Sync command"),
+        }
+    }
+
+    #[test]
+    fn test_issue_parallel_command() {
+        let cli = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "issue",
+            "parallel",
+            "*_api_*",
+            "--jobs",
+            "4",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Commands::Issue {
+                subcommand:
+                    IssueCommands::Parallel {
+                        patterns,
+                        jobs,
+                        workflow,
+                        format,
+                    },
+            }) => {
+                assert_eq!(patterns, vec!["*_api_*"]);
+                assert_eq!(jobs, 4);
+                assert_eq!(workflow, "work_issue");
+                assert!(matches!(format, OutputFormat::Table));
+            }
+            _ => panic!("Expected Issue Parallel command"),
+        }
+
+        // At least one job is needed
+        let result =
+            Cli::try_parse_from_args(["swissarmyhammer", "issue", "parallel", "--jobs", "0"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_issue_queue_command() {
+        let cli = Cli::try_parse_from_args(["swissarmyhammer", "issue", "queue"]).unwrap();
+        match cli.command {
+            Some(Commands::Issue {
+                subcommand: IssueCommands::Queue { run, format },
+            }) => {
+                assert!(!run);
+                assert!(matches!(format, OutputFormat::Table));
+            }
+            _ => panic!("Expected Issue Queue command"),
+        }
+
+        let cli = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "issue",
+            "queue",
+            "--run",
+            "--format",
+            "json",
+        ])
+        .unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Issue {
+                subcommand: IssueCommands::Queue {
+                    run: true,
+                    format: OutputFormat::Json
+                },
+            })
+        ));
+    }
+
+    #[test]
+    fn test_issue_move_command() {
+        let cli = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "issue",
+            "move",
+            "fix_login",
+            "in-review",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Commands::Issue {
+                subcommand: IssueCommands::Move { name, state },
+            }) => {
+                assert_eq!(name, "fix_login");
+                assert_eq!(state, "in-review");
+            }
+            _ => panic!("Expected Issue Move command"),
+        }
+
+        assert!(
+            Cli::try_parse_from_args(["swissarmyhammer", "issue", "move", "fix_login"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_issue_assign_command() {
+        let cli = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "issue",
+            "assign",
+            "fix_login",
+            "Jane Doe",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Commands::Issue {
+                subcommand:
+                    IssueCommands::Assign {
+                        name,
+                        user,
+                        unassign,
+                    },
+            }) => {
+                assert_eq!(name, "fix_login");
+                assert_eq!(user.as_deref(), Some("Jane Doe"));
+                assert!(!unassign);
+            }
+            _ => panic!("Expected Issue Assign command"),
+        }
+
+        let cli =
+            Cli::try_parse_from_args(["swissarmyhammer", "issue", "list", "--assignee", "@me"])
+                .unwrap();
+        match cli.command {
+            Some(Commands::Issue {
+                subcommand: IssueCommands::List { assignee, .. },
+            }) => assert_eq!(assignee.as_deref(), Some("@me")),
+            _ => panic!("Expected Issue List command"),
+        }
+
+        assert!(Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "issue",
+            "assign",
+            "fix_login",
+            "Jane",
+            "--unassign",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_issue_check_command() {
+        let cli = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "issue",
+            "check",
+            "fix_login",
+            "write tests",
+            "--done",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Commands::Issue {
+                subcommand:
+                    IssueCommands::Check {
+                        name,
+                        item,
+                        done,
+                        undo,
+                        ..
+                    },
+            }) => {
+                assert_eq!(name, "fix_login");
+                assert_eq!(item.as_deref(), Some("write tests"));
+                assert!(done);
+                assert!(!undo);
+            }
+            _ => panic!("Expected Issue Check command"),
+        }
+
+        assert!(
+            Cli::try_parse_from_args(["swissarmyhammer", "issue", "check", "fix_login"]).is_ok()
+        );
+        assert!(Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "issue",
+            "check",
+            "fix_login",
+            "--done"
+        ])
+        .is_err());
+        assert!(Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "issue",
+            "check",
+            "fix_login",
+            "2",
+            "--done",
+            "--undo",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_issue_bulk_commands() {
+        let cli = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "issue",
+            "bulk",
+            "complete",
+            "*_stale_*",
+            "000002_login",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Commands::Issue {
+                subcommand:
+                    IssueCommands::Bulk {
+                        action:
+                            IssueBulkCommands::Complete {
+                                patterns,
+                                yes,
+                                format,
+                            },
+                    },
+            }) => {
+                assert_eq!(patterns, vec!["*_stale_*", "000002_login"]);
+                assert!(!yes);
+                assert!(matches!(format, OutputFormat::Table));
+            }
+            _ => panic!("Expected Issue Bulk Complete command"),
+        }
+
+        let cli = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "issue",
+            "bulk",
+            "label",
+            "auth",
+            "000012_login",
+            "--yes",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Commands::Issue {
+                subcommand:
+                    IssueCommands::Bulk {
+                        action:
+                            IssueBulkCommands::Label {
+                                label,
+                                patterns,
+                                yes,
+                                ..
+                            },
+                    },
+            }) => {
+                assert_eq!(label, "auth");
+                assert_eq!(patterns, vec!["000012_login"]);
+                assert!(yes);
+            }
+            _ => panic!("Expected Issue Bulk Label command"),
+        }
+
+        let cli = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "issue",
+            "bulk",
+            "move",
+            "--to",
+            "archive",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Commands::Issue {
+                subcommand:
+                    IssueCommands::Bulk {
+                        action: IssueBulkCommands::Move { to, patterns, .. },
+                    },
+            }) => {
+                assert_eq!(to, std::path::PathBuf::from("archive"));
+                assert!(patterns.is_empty());
+            }
+            _ => panic!("Expected Issue Bulk Move command"),
+        }
+
+        // move requires a destination
+        let result = Cli::try_parse_from_args(["swissarmyhammer", "issue", "bulk", "move", "x"]);
+        assert!(result.is_err());
     }
 
     #[test]
-    fn test_prompt_source_arg_equality() {
-        assert_eq!(PromptSourceArg::Builtin, PromptSourceArg::Builtin);
-        assert_ne!(PromptSourceArg::Builtin, PromptSourceArg::User);
-        assert_ne!(PromptSourceArg::User, PromptSourceArg::Local);
-        assert_ne!(PromptSourceArg::Local, PromptSourceArg::Dynamic);
+    fn test_issue_rename_branches() {
+        let cli =
+            Cli::try_parse_from_args(["swissarmyhammer", "issue", "rename-branches"]).unwrap();
+        match cli.command {
+            Some(Commands::Issue {
+                subcommand: IssueCommands::RenameBranches { from, yes, .. },
+            }) => {
+                assert_eq!(from, "issue/{name}");
+                assert!(!yes);
+            }
+            _ => panic!("Expected Issue RenameBranches command"),
+        }
+
+        let cli = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "issue",
+            "rename-branches",
+            "--from",
+            "feature/{name}",
+            "--yes",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Commands::Issue {
+                subcommand: IssueCommands::RenameBranches { from, yes, .. },
+            }) => {
+                assert_eq!(from, "feature/{name}");
+                assert!(yes);
+            }
+            _ => panic!("Expected Issue RenameBranches command"),
+        }
     }
 
     #[test]
-    fn test_debug_flag() {
-        let result = Cli::try_parse_from_args(["swissarmyhammer", "--debug"]);
-        assert!(result.is_ok());
+    fn test_issue_renumber() {
+        let cli = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "issue",
+            "renumber",
+            "--scheme",
+            "project",
+            "--prefix",
+            "PROJ",
+            "--yes",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Commands::Issue {
+                subcommand:
+                    IssueCommands::Renumber {
+                        scheme,
+                        prefix,
+                        yes,
+                        ..
+                    },
+            }) => {
+                assert_eq!(scheme.as_deref(), Some("project"));
+                assert_eq!(prefix.as_deref(), Some("PROJ"));
+                assert!(yes);
+            }
+            _ => panic!("Expected Issue Renumber command"),
+        }
 
-        let cli = result.unwrap();
-        assert!(cli.debug);
-        assert!(!cli.verbose);
-        assert!(!cli.quiet);
+        let result = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "issue",
+            "renumber",
+            "--scheme",
+            "sequential",
+        ]);
+        assert!(result.is_err());
     }
 
     #[test]
-    fn test_combined_flags() {
-        let result = Cli::try_parse_from_args(["swissarmyhammer", "--debug", "--verbose"]);
-        assert!(result.is_ok());
+    fn test_issue_history_and_restore() {
+        let cli =
+            Cli::try_parse_from_args(["swissarmyhammer", "issue", "history", "fix_login"]).unwrap();
+        match cli.command {
+            Some(Commands::Issue {
+                subcommand: IssueCommands::History { name, format },
+            }) => {
+                assert_eq!(name, "fix_login");
+                assert!(matches!(format, OutputFormat::Table));
+            }
+            _ => panic!("Expected Issue History command"),
+        }
 
-        let cli = result.unwrap();
-        assert!(cli.debug);
-        assert!(cli.verbose);
-        assert!(!cli.quiet);
+        let cli =
+            Cli::try_parse_from_args(["swissarmyhammer", "issue", "restore", "fix_login", "3"])
+                .unwrap();
+        match cli.command {
+            Some(Commands::Issue {
+                subcommand: IssueCommands::Restore { name, version },
+            }) => {
+                assert_eq!(name, "fix_login");
+                assert_eq!(version, 3);
+            }
+            _ => panic!("Expected Issue Restore command"),
+        }
     }
 
     #[test]
-    fn test_issue_create_with_name() {
+    fn test_issue_refine() {
+        let cli =
+            Cli::try_parse_from_args(["swissarmyhammer", "issue", "refine", "fix_login", "--yes"])
+                .unwrap();
+        match cli.command {
+            Some(Commands::Issue {
+                subcommand: IssueCommands::Refine { name, yes },
+            }) => {
+                assert_eq!(name, "fix_login");
+                assert!(yes);
+            }
+            _ => panic!("Expected Issue Refine command"),
+        }
+
+        let result = Cli::try_parse_from_args(["swissarmyhammer", "issue", "refine"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_issue_board_with_move() {
         let result = Cli::try_parse_from_args([
             "swissarmyhammer",
             "issue",
-            "create",
-            "bug_fix",
-            "--content",
-            "Fix login bug",
+            "board",
+            "--move",
+            "fix_login",
+            "--to",
+            "in-progress",
         ]);
         assert!(result.is_ok());
 
         let cli = result.unwrap();
         if let Some(Commands::Issue { subcommand }) = cli.command {
-            if let IssueCommands::Create {
-                name,
-                content,
-                file,
+            if let IssueCommands::Board {
+                move_issue,
+                to,
+                format,
             } = subcommand
             {
-                assert_eq!(name, Some("bug_fix".to_string()));
-                assert_eq!(content, Some("Fix login bug".to_string()));
-                assert_eq!(file, None);
+                assert_eq!(move_issue, Some("fix_login".to_string()));
+                assert_eq!(to, Some(BoardColumnArg::InProgress));
+                assert!(matches!(format, OutputFormat::Table));
             } else {
-                panic!("Expected Create subcommand");
+                panic!("Expected Board subcommand");
             }
         } else {
             panic!("Expected Issue command");
         }
+
+        // --move without --to is rejected
+        let result =
+            Cli::try_parse_from_args(["swissarmyhammer", "issue", "board", "--move", "fix_login"]);
+        assert!(result.is_err());
     }
 
     #[test]
@@ -1632,6 +4410,201 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_prompt_complete_subcommand() {
+        let cli = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "prompt",
+            "complete",
+            "review",
+            "file",
+            "src/",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Commands::Prompt {
+                subcommand:
+                    PromptSubcommand::Complete {
+                        prompt_name,
+                        argument,
+                        prefix,
+                    },
+            }) => {
+                assert_eq!(prompt_name, "review");
+                assert_eq!(argument.as_deref(), Some("file"));
+                assert_eq!(prefix, "src/");
+            }
+            _ => panic!("Expected Prompt Complete command"),
+        }
+
+        let cli =
+            Cli::try_parse_from_args(["swissarmyhammer", "prompt", "complete", "review"]).unwrap();
+        match cli.command {
+            Some(Commands::Prompt {
+                subcommand:
+                    PromptSubcommand::Complete {
+                        argument, prefix, ..
+                    },
+            }) => {
+                assert_eq!(argument, None);
+                assert_eq!(prefix, "");
+            }
+            _ => panic!("Expected Prompt Complete command"),
+        }
+    }
+
+    #[test]
+    fn test_api_subcommand() {
+        let cli = Cli::try_parse_from_args(["swissarmyhammer", "api"]).unwrap();
+        match cli.command {
+            Some(Commands::Api { addr }) => assert_eq!(addr.to_string(), "127.0.0.1:8787"),
+            _ => panic!("Expected Api command"),
+        }
+
+        let cli =
+            Cli::try_parse_from_args(["swissarmyhammer", "api", "--addr", "0.0.0.0:9000"]).unwrap();
+        match cli.command {
+            Some(Commands::Api { addr }) => assert_eq!(addr.port(), 9000),
+            _ => panic!("Expected Api command"),
+        }
+
+        assert!(Cli::try_parse_from_args(["swissarmyhammer", "api", "--addr", "nowhere"]).is_err());
+    }
+
+    #[test]
+    fn test_query_command() {
+        let cli = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "query",
+            "SELECT name FROM issues",
+            "--format",
+            "csv",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Commands::Query { query, format }) => {
+                assert_eq!(query, "SELECT name FROM issues");
+                assert_eq!(format, QueryFormat::Csv);
+            }
+            _ => panic!("Expected Query command"),
+        }
+
+        assert!(Cli::try_parse_from_args(["swissarmyhammer", "query"]).is_err());
+    }
+
+    #[test]
+    fn test_pick_command() {
+        let cli = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "pick",
+            "login",
+            "--kind",
+            "issue",
+            "--kind",
+            "memo",
+            "--print",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Commands::Pick { query, kind, print }) => {
+                assert_eq!(query.as_deref(), Some("login"));
+                assert_eq!(kind, vec![PickKind::Issue, PickKind::Memo]);
+                assert!(print);
+            }
+            _ => panic!("Expected Pick command"),
+        }
+
+        let cli = Cli::try_parse_from_args(["swissarmyhammer", "pick"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Pick { query: None, ref kind, print: false }) if kind.is_empty()
+        ));
+    }
+
+    #[test]
+    fn test_backup_subcommands() {
+        let cli = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "backup",
+            "create",
+            "--only",
+            "issues,memos",
+        ])
+        .unwrap();
+        if let Some(Commands::Backup {
+            subcommand: BackupSubcommand::Create { only, dir },
+        }) = cli.command
+        {
+            assert_eq!(only, [BackupComponent::Issues, BackupComponent::Memos]);
+            assert!(dir.is_none());
+        } else {
+            panic!("Expected backup create command");
+        }
+
+        let cli = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "backup",
+            "restore",
+            "sah-backup-20261016-120000.tar.gz",
+            "--dry-run",
+        ])
+        .unwrap();
+        if let Some(Commands::Backup {
+            subcommand:
+                BackupSubcommand::Restore {
+                    archive,
+                    only,
+                    dry_run,
+                },
+        }) = cli.command
+        {
+            assert_eq!(
+                archive,
+                std::path::PathBuf::from("sah-backup-20261016-120000.tar.gz")
+            );
+            assert!(only.is_empty());
+            assert!(dry_run);
+        } else {
+            panic!("Expected backup restore command");
+        }
+
+        assert!(Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "backup",
+            "create",
+            "--only",
+            "cache"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_logs_tail_subcommand() {
+        let cli = Cli::try_parse_from_args(["swissarmyhammer", "logs", "tail"]).unwrap();
+        if let Some(Commands::Logs {
+            subcommand: LogsSubcommand::Tail { lines, no_follow },
+        }) = cli.command
+        {
+            assert_eq!(lines, 20);
+            assert!(!no_follow);
+        } else {
+            panic!("Expected Logs command");
+        }
+
+        let cli =
+            Cli::try_parse_from_args(["swissarmyhammer", "logs", "tail", "-n", "5", "--no-follow"])
+                .unwrap();
+        if let Some(Commands::Logs {
+            subcommand: LogsSubcommand::Tail { lines, no_follow },
+        }) = cli.command
+        {
+            assert_eq!(lines, 5);
+            assert!(no_follow);
+        } else {
+            panic!("Expected Logs command");
+        }
+    }
+
     #[test]
     fn test_memo_create_basic() {
         let result =
@@ -1640,7 +4613,7 @@ mod tests {
 
         let cli = result.unwrap();
         if let Some(Commands::Memo { subcommand }) = cli.command {
-            if let MemoCommands::Create { title, content } = subcommand {
+            if let MemoCommands::Create { title, content, .. } = subcommand {
                 assert_eq!(title, "Meeting Notes");
                 assert_eq!(content, None);
             } else {
@@ -1665,7 +4638,7 @@ mod tests {
 
         let cli = result.unwrap();
         if let Some(Commands::Memo { subcommand }) = cli.command {
-            if let MemoCommands::Create { title, content } = subcommand {
+            if let MemoCommands::Create { title, content, .. } = subcommand {
                 assert_eq!(title, "Task List");
                 assert_eq!(content, Some("1. Review code\n2. Write tests".to_string()));
             } else {
@@ -1676,6 +4649,160 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_memo_verify_command() {
+        let result = Cli::try_parse_from_args(["swissarmyhammer", "memo", "verify", "--restore"]);
+        assert!(result.is_ok());
+
+        let cli = result.unwrap();
+        if let Some(Commands::Memo { subcommand }) = cli.command {
+            if let MemoCommands::Verify { restore } = subcommand {
+                assert!(restore);
+            } else {
+                panic!("Expected Verify subcommand");
+            }
+        } else {
+            panic!("Expected Memo command");
+        }
+    }
+
+    #[test]
+    fn test_memo_dedupe_command() {
+        let cli = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "memo",
+            "dedupe",
+            "--threshold",
+            "0.6",
+            "--merge",
+        ])
+        .unwrap();
+        if let Some(Commands::Memo {
+            subcommand: MemoCommands::Dedupe { threshold, merge },
+        }) = cli.command
+        {
+            assert_eq!(threshold, 0.6);
+            assert!(merge);
+        } else {
+            panic!("Expected Memo Dedupe command");
+        }
+
+        let cli = Cli::try_parse_from_args(["swissarmyhammer", "memo", "dedupe"]).unwrap();
+        if let Some(Commands::Memo {
+            subcommand: MemoCommands::Dedupe { threshold, merge },
+        }) = cli.command
+        {
+            assert_eq!(
+                threshold,
+                swissarmyhammer::memoranda::dedupe::DEFAULT_SIMILARITY_THRESHOLD
+            );
+            assert!(!merge);
+        } else {
+            panic!("Expected Memo Dedupe command");
+        }
+    }
+
+    #[test]
+    fn test_memo_compact_command() {
+        let result = Cli::try_parse_from_args(["swissarmyhammer", "memo", "compact", "--dry-run"]);
+        assert!(result.is_ok());
+
+        let cli = result.unwrap();
+        if let Some(Commands::Memo { subcommand }) = cli.command {
+            if let MemoCommands::Compact { dry_run } = subcommand {
+                assert!(dry_run);
+            } else {
+                panic!("Expected Compact subcommand");
+            }
+        } else {
+            panic!("Expected Memo command");
+        }
+    }
+
+    #[test]
+    fn test_memo_history_and_restore_commands() {
+        let cli = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "memo",
+            "history",
+            "Plans",
+            "--format",
+            "json",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Commands::Memo {
+                subcommand: MemoCommands::History { id, format },
+            }) => {
+                assert_eq!(id, "Plans");
+                assert!(matches!(format, OutputFormat::Json));
+            }
+            _ => panic!("Expected Memo History command"),
+        }
+
+        let cli =
+            Cli::try_parse_from_args(["swissarmyhammer", "memo", "restore", "Plans", "2"]).unwrap();
+        match cli.command {
+            Some(Commands::Memo {
+                subcommand: MemoCommands::Restore { id, version },
+            }) => {
+                assert_eq!(id, "Plans");
+                assert_eq!(version, 2);
+            }
+            _ => panic!("Expected Memo Restore command"),
+        }
+
+        let result = Cli::try_parse_from_args(["swissarmyhammer", "memo", "restore", "Plans", "x"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_memo_notebook_commands() {
+        let cli =
+            Cli::try_parse_from_args(["swissarmyhammer", "memo", "list", "--notebook", "work"])
+                .unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Memo {
+                subcommand: MemoCommands::List { notebook: Some(ref notebook), .. }
+            }) if notebook == "work"
+        ));
+
+        let cli =
+            Cli::try_parse_from_args(["swissarmyhammer", "memo", "move", "Plans", "work"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Memo {
+                subcommand: MemoCommands::Move { ref id, notebook: Some(ref notebook) }
+            }) if id == "Plans" && notebook == "work"
+        ));
+        let cli = Cli::try_parse_from_args(["swissarmyhammer", "memo", "move", "Plans"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Memo {
+                subcommand: MemoCommands::Move { notebook: None, .. }
+            })
+        ));
+
+        let cli = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "memo",
+            "notebook",
+            "rename",
+            "work",
+            "office",
+        ])
+        .unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Memo {
+                subcommand: MemoCommands::Notebook {
+                    subcommand: NotebookCommands::Rename { ref name, ref new_name }
+                }
+            }) if name == "work" && new_name == "office"
+        ));
+    }
+
     #[test]
     fn test_memo_list() {
         let result = Cli::try_parse_from_args(["swissarmyhammer", "memo", "list"]);
@@ -1683,8 +4810,8 @@ mod tests {
 
         let cli = result.unwrap();
         if let Some(Commands::Memo { subcommand }) = cli.command {
-            if let MemoCommands::List = subcommand {
-                // Test passes
+            if let MemoCommands::List { format, .. } = subcommand {
+                assert!(matches!(format, OutputFormat::Table));
             } else {
                 panic!("Expected List subcommand");
             }
@@ -1705,8 +4832,9 @@ mod tests {
 
         let cli = result.unwrap();
         if let Some(Commands::Memo { subcommand }) = cli.command {
-            if let MemoCommands::Get { id } = subcommand {
+            if let MemoCommands::Get { id, format } = subcommand {
                 assert_eq!(id, "01GX5Q2D1NPRZ3KXFW2H8V3A1Y");
+                assert!(matches!(format, OutputFormat::Table));
             } else {
                 panic!("Expected Get subcommand");
             }
@@ -1770,8 +4898,9 @@ mod tests {
 
         let cli = result.unwrap();
         if let Some(Commands::Memo { subcommand }) = cli.command {
-            if let MemoCommands::Search { query } = subcommand {
+            if let MemoCommands::Search { query, format } = subcommand {
                 assert_eq!(query, "meeting notes");
+                assert!(matches!(format, OutputFormat::Table));
             } else {
                 panic!("Expected Search subcommand");
             }
@@ -1780,6 +4909,44 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_memo_output_format() {
+        let cli = Cli::try_parse_from_args(["swissarmyhammer", "memo", "list", "--format", "json"])
+            .unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Memo {
+                subcommand: MemoCommands::List {
+                    format: OutputFormat::Json,
+                    ..
+                }
+            })
+        ));
+
+        let cli =
+            Cli::try_parse_from_args(["swissarmyhammer", "memo", "search", "notes", "-f", "yaml"])
+                .unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Memo {
+                subcommand: MemoCommands::Search {
+                    format: OutputFormat::Yaml,
+                    ..
+                }
+            })
+        ));
+
+        let result = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "memo",
+            "get",
+            "01GX5",
+            "--format",
+            "xml",
+        ]);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_memo_context() {
         let result = Cli::try_parse_from_args(["swissarmyhammer", "memo", "context"]);
@@ -1787,8 +4954,9 @@ mod tests {
 
         let cli = result.unwrap();
         if let Some(Commands::Memo { subcommand }) = cli.command {
-            if let MemoCommands::Context = subcommand {
-                // Test passes
+            if let MemoCommands::Context { query, max_tokens } = subcommand {
+                assert_eq!(query, None);
+                assert_eq!(max_tokens, None);
             } else {
                 panic!("Expected Context subcommand");
             }
@@ -1797,6 +4965,31 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_memo_context_query_and_budget() {
+        let result = Cli::try_parse_from_args([
+            "swissarmyhammer",
+            "memo",
+            "context",
+            "--query",
+            "deploy staging",
+            "--max-tokens",
+            "2000",
+        ]);
+        assert!(result.is_ok());
+
+        let cli = result.unwrap();
+        if let Some(Commands::Memo {
+            subcommand: MemoCommands::Context { query, max_tokens },
+        }) = cli.command
+        {
+            assert_eq!(query.as_deref(), Some("deploy staging"));
+            assert_eq!(max_tokens, Some(2000));
+        } else {
+            panic!("Expected Memo Context subcommand");
+        }
+    }
+
     #[test]
     fn test_search_index_single_pattern() {
         let result = Cli::try_parse_from_args(["swissarmyhammer", "search", "index", "**/*.rs"]);
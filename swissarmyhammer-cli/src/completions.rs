@@ -1,9 +1,63 @@
 use crate::cli::Cli;
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use clap::CommandFactory;
 use clap_complete::{generate_to, Shell};
-use std::io;
+use std::io::{self, Write};
 use std::path::Path;
+use swissarmyhammer::prompt_completion::argument_values;
+use swissarmyhammer::{PromptLibrary, PromptResolver};
+
+/// Completes `prompt test NAME --arg KEY=VALUE` from the prompt's arguments,
+/// deferring to the generated completion everywhere else
+const BASH_ARGUMENT_COMPLETION: &str = r#"
+_swissarmyhammer_prompt_arguments() {
+    local line="${COMP_LINE:0:COMP_POINT}"
+    local -a words
+    read -ra words <<< "$line"
+    [[ "$line" == *[[:space:]] ]] && words+=("")
+    local count=${#words[@]}
+    if (( count >= 6 )) && [[ "${words[1]}" == "prompt" && "${words[2]}" == "test" && "${words[count-2]}" == "--arg" ]]; then
+        local current="${words[count-1]}" name="${words[3]}"
+        local IFS=$'
+'
+        if [[ "$current" == *=* ]]; then
+            COMPREPLY=($(compgen -W "$("${words[0]}" prompt complete "$name" "${current%%=*}" 2>/dev/null)" -- "${current#*=}"))
+        else
+            COMPREPLY=($(compgen -W "$("${words[0]}" prompt complete "$name" 2>/dev/null)" -- "$current"))
+            compopt -o nospace
+        fi
+        return 0
+    fi
+    _swissarmyhammer "$@"
+}
+complete -F _swissarmyhammer_prompt_arguments -o bashdefault -o default swissarmyhammer
+"#;
+
+/// Completes `prompt test NAME --arg KEY=VALUE` from the prompt's arguments
+const FISH_ARGUMENT_COMPLETION: &str = r#"
+function __swissarmyhammer_prompt_arguments
+    set -l words (commandline -opc)
+    set -l current (commandline -ct)
+    if string match -q -- '*=*' $current
+        set -l key (string split -m 1 = -- $current)[1]
+        for value in (swissarmyhammer prompt complete $words[4] $key 2>/dev/null)
+            echo "$key=$value"
+        end
+    else
+        swissarmyhammer prompt complete $words[4] 2>/dev/null
+    end
+end
+complete -c swissarmyhammer -n '__fish_seen_subcommand_from prompt; and __fish_seen_subcommand_from test' -l arg -x -a '(__swissarmyhammer_prompt_arguments)'
+"#;
+
+/// Script added to the generated completion to complete prompt argument values
+fn argument_completion(shell: Shell) -> Option<&'static str> {
+    match shell {
+        Shell::Bash => Some(BASH_ARGUMENT_COMPLETION),
+        Shell::Fish => Some(FISH_ARGUMENT_COMPLETION),
+        _ => None,
+    }
+}
 
 /// Generate shell completion scripts
 #[allow(dead_code)]
@@ -13,7 +67,13 @@ pub fn generate_completions<P: AsRef<Path>>(outdir: P) -> Result<()> {
     let mut cmd = Cli::command();
 
     for shell in [Shell::Bash, Shell::Zsh, Shell::Fish, Shell::PowerShell] {
-        generate_to(shell, &mut cmd, "swissarmyhammer", outdir)?;
+        let path = generate_to(shell, &mut cmd, "swissarmyhammer", outdir)?;
+        if let Some(script) = argument_completion(shell) {
+            std::fs::OpenOptions::new()
+                .append(true)
+                .open(path)?
+                .write_all(script.as_bytes())?;
+        }
     }
 
     println!("Generated shell completions in: {}", outdir.display());
@@ -25,8 +85,47 @@ pub fn generate_completions<P: AsRef<Path>>(outdir: P) -> Result<()> {
 pub fn print_completion(shell: Shell) -> Result<()> {
     let mut cmd = Cli::command();
 
-    clap_complete::generate(shell, &mut cmd, "swissarmyhammer", &mut io::stdout());
+    let mut stdout = io::stdout();
+    clap_complete::generate(shell, &mut cmd, "swissarmyhammer", &mut stdout);
+    if let Some(script) = argument_completion(shell) {
+        stdout.write_all(script.as_bytes())?;
+    }
+
+    Ok(())
+}
+
+/// Print completion candidates for a prompt argument, one per line
+///
+/// Without an argument name, the prompt's arguments are printed as `name=`
+/// so a shell can complete the key before the value.
+pub fn run_complete_command(prompt_name: &str, argument: Option<&str>, prefix: &str) -> Result<()> {
+    let mut library = PromptLibrary::new();
+    PromptResolver::new().load_all_prompts(&mut library)?;
+    let prompt = library.get(prompt_name)?;
 
+    let candidates = match argument {
+        None => prompt
+            .arguments
+            .iter()
+            .map(|arg| format!("{}=", arg.name))
+            .filter(|candidate| candidate.starts_with(prefix))
+            .collect(),
+        Some(argument) => {
+            let arg = prompt
+                .arguments
+                .iter()
+                .find(|arg| arg.name == argument)
+                .ok_or_else(|| {
+                    anyhow!("Prompt '{prompt_name}' has no argument named '{argument}'")
+                })?;
+            argument_values(arg, prefix)?
+        }
+    };
+
+    let mut stdout = io::stdout().lock();
+    for candidate in candidates {
+        writeln!(stdout, "{candidate}")?;
+    }
     Ok(())
 }
 
@@ -74,6 +173,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_argument_completion_scripts() {
+        let temp_dir = TempDir::new().unwrap();
+        generate_completions(temp_dir.path()).unwrap();
+
+        let bash = std::fs::read_to_string(temp_dir.path().join("swissarmyhammer.bash")).unwrap();
+        assert!(bash.contains("_swissarmyhammer_prompt_arguments"));
+        assert!(bash.contains("prompt complete"));
+        let fish = std::fs::read_to_string(temp_dir.path().join("swissarmyhammer.fish")).unwrap();
+        assert!(fish.contains("__swissarmyhammer_prompt_arguments"));
+        let zsh = std::fs::read_to_string(temp_dir.path().join("_swissarmyhammer")).unwrap();
+        assert!(!zsh.contains("prompt_arguments"));
+    }
+
     #[test]
     fn test_print_completion_bash() {
         // Capture stdout
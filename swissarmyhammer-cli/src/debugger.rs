@@ -0,0 +1,534 @@
+//! Interactive step-through debugger for workflows
+//!
+//! `flow debug` runs a workflow one transition at a time. Between steps the
+//! run variables can be inspected and changed, the next action can be
+//! rendered without running it, and breakpoints let `continue` run ahead to a
+//! chosen state.
+
+use colored::*;
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap};
+use std::io::{self, BufRead, Write};
+use swissarmyhammer::workflow::{
+    parse_action_from_description_with_context, HookDetails, PromptAction, StateId,
+    WorkflowExecutor, WorkflowHookEvent, WorkflowName, WorkflowRun, WorkflowRunStatus,
+    WorkflowStorage,
+};
+use swissarmyhammer::{PromptLibrary, PromptResolver, Result, SwissArmyHammerError};
+
+/// Most transitions `continue` takes before giving up, matching the executor
+const MAX_TRANSITIONS: usize = 1000;
+
+const HELP: &str = "\
+Commands:
+  step, s, <enter>      Run the current state and take one transition
+  continue, c           Run until a breakpoint or the end of the workflow
+  where, w              Show the current state and its transitions
+  vars, v               List the run variables
+  print, p NAME         Show one variable
+  set NAME=VALUE        Set a variable; VALUE is JSON or a plain string
+  unset NAME            Remove a variable
+  render, r             Render the current state's action without running it
+  break, b [STATE]      Break when the run enters STATE, or list breakpoints
+  delete, d STATE       Remove a breakpoint
+  help, h, ?            Show this help
+  quit, q               Stop debugging and save the run";
+
+/// A command entered at the debugger prompt
+#[derive(Debug, Clone, PartialEq)]
+pub enum DebugCommand {
+    /// Run the current state and take one transition
+    Step,
+    /// Run until a breakpoint or the end of the workflow
+    Continue,
+    /// Show the current state and its transitions
+    Where,
+    /// List the run variables
+    Vars,
+    /// Show one variable
+    Print(String),
+    /// Set a variable
+    Set(String, Value),
+    /// Remove a variable
+    Unset(String),
+    /// Render the current state's action without running it
+    Render,
+    /// Add a breakpoint, or list breakpoints when no state is given
+    Break(Option<String>),
+    /// Remove a breakpoint
+    Delete(String),
+    /// Show the available commands
+    Help,
+    /// Stop debugging
+    Quit,
+}
+
+impl DebugCommand {
+    /// Parse a line entered at the prompt
+    ///
+    /// An empty line steps, so holding enter walks through the workflow.
+    pub fn parse(line: &str) -> std::result::Result<Self, String> {
+        let line = line.trim();
+        let (command, rest) = match line.split_once(char::is_whitespace) {
+            Some((command, rest)) => (command, rest.trim()),
+            None => (line, ""),
+        };
+        let argument = |usage: &str| {
+            if rest.is_empty() {
+                Err(format!("Usage: {usage}"))
+            } else {
+                Ok(rest.to_string())
+            }
+        };
+
+        match command {
+            "" | "s" | "step" => Ok(DebugCommand::Step),
+            "c" | "continue" => Ok(DebugCommand::Continue),
+            "w" | "where" => Ok(DebugCommand::Where),
+            "v" | "vars" => Ok(DebugCommand::Vars),
+            "p" | "print" => argument("print NAME").map(DebugCommand::Print),
+            "set" => {
+                let (name, value) = rest
+                    .split_once('=')
+                    .or_else(|| rest.split_once(char::is_whitespace))
+                    .map(|(name, value)| (name.trim(), value.trim()))
+                    .filter(|(name, _)| !name.is_empty())
+                    .ok_or_else(|| "Usage: set NAME=VALUE".to_string())?;
+                let value = serde_json::from_str(value)
+                    .unwrap_or_else(|_| Value::String(value.to_string()));
+                Ok(DebugCommand::Set(name.to_string(), value))
+            }
+            "unset" => argument("unset NAME").map(DebugCommand::Unset),
+            "r" | "render" => Ok(DebugCommand::Render),
+            "b" | "break" => Ok(DebugCommand::Break(
+                (!rest.is_empty()).then(|| rest.to_string()),
+            )),
+            "d" | "delete" => argument("delete STATE").map(DebugCommand::Delete),
+            "h" | "help" | "?" => Ok(DebugCommand::Help),
+            "q" | "quit" | "exit" => Ok(DebugCommand::Quit),
+            other => Err(format!(
+                "Unknown command '{other}'. Type 'help' for a list of commands"
+            )),
+        }
+    }
+}
+
+/// A workflow run paused between states
+pub struct Debugger {
+    executor: WorkflowExecutor,
+    run: WorkflowRun,
+    breakpoints: BTreeSet<String>,
+}
+
+impl Debugger {
+    /// Debug a run started by `executor`
+    pub fn new(executor: WorkflowExecutor, run: WorkflowRun) -> Self {
+        Self {
+            executor,
+            run,
+            breakpoints: BTreeSet::new(),
+        }
+    }
+
+    /// The run being debugged
+    pub fn run(&self) -> &WorkflowRun {
+        &self.run
+    }
+
+    /// Whether the run can take no more steps
+    pub fn is_finished(&self) -> bool {
+        self.run.status != WorkflowRunStatus::Running
+    }
+
+    /// Break when the run enters a state
+    pub fn add_breakpoint(&mut self, state: &str) -> Result<()> {
+        if !self
+            .run
+            .workflow
+            .states
+            .keys()
+            .any(|id| id.as_str() == state)
+        {
+            return Err(SwissArmyHammerError::Other(format!(
+                "Workflow '{}' has no state '{}'",
+                self.run.workflow.name, state
+            )));
+        }
+        self.breakpoints.insert(state.to_string());
+        Ok(())
+    }
+
+    /// Remove a breakpoint, returning whether one was set
+    pub fn remove_breakpoint(&mut self, state: &str) -> bool {
+        self.breakpoints.remove(state)
+    }
+
+    /// Run the current state and take one transition
+    ///
+    /// Returns whether the run moved to another state. Hooks run as they do
+    /// when the workflow runs normally.
+    pub async fn step(&mut self) -> Result<bool> {
+        if self.is_finished() {
+            return Err(SwissArmyHammerError::Other(format!(
+                "The run is {:?} and cannot step",
+                self.run.status
+            )));
+        }
+
+        let previous_state = self.run.current_state.clone();
+        let result = self.executor.execute_single_cycle(&mut self.run).await;
+
+        if self.run.current_state != previous_state {
+            let details = HookDetails {
+                previous_state: Some(previous_state.to_string()),
+                ..Default::default()
+            };
+            self.executor
+                .run_hook(&self.run, WorkflowHookEvent::StateChange, details)
+                .await;
+        }
+
+        match result {
+            Ok(transitioned) => Ok(transitioned),
+            Err(e) => {
+                let details = HookDetails {
+                    error: Some(e.to_string()),
+                    ..Default::default()
+                };
+                self.executor
+                    .run_hook(&self.run, WorkflowHookEvent::Failure, details)
+                    .await;
+                self.run.fail();
+                Err(SwissArmyHammerError::Other(format!(
+                    "State '{previous_state}' failed: {e}"
+                )))
+            }
+        }
+    }
+
+    /// Step until the run enters a breakpoint state, stops moving or finishes
+    ///
+    /// Returns the breakpoint state the run stopped at, if any.
+    pub async fn continue_run(&mut self) -> Result<Option<StateId>> {
+        for _ in 0..MAX_TRANSITIONS {
+            if !self.step().await? || self.is_finished() {
+                return Ok(None);
+            }
+            if self.breakpoints.contains(self.run.current_state.as_str()) {
+                return Ok(Some(self.run.current_state.clone()));
+            }
+        }
+        Err(SwissArmyHammerError::Other(format!(
+            "Stopped after {MAX_TRANSITIONS} transitions without reaching a breakpoint"
+        )))
+    }
+
+    /// Describe the current state's action as it would run now
+    ///
+    /// Variables are substituted from the current context. Prompts are
+    /// rendered from the prompt files on disk, so edits to a prompt show up
+    /// the next time it is rendered.
+    pub fn render_action(&self) -> Result<String> {
+        let state = self
+            .run
+            .workflow
+            .states
+            .get(&self.run.current_state)
+            .ok_or_else(|| {
+                SwissArmyHammerError::Other(format!(
+                    "Workflow has no state '{}'",
+                    self.run.current_state
+                ))
+            })?;
+
+        let action =
+            parse_action_from_description_with_context(&state.description, &self.run.context)
+                .map_err(|e| SwissArmyHammerError::Other(e.to_string()))?;
+        let Some(action) = action else {
+            return Ok(format!("No action: {}", state.description));
+        };
+
+        let mut rendered = format!("{}: {}", action.action_type(), action.description());
+        if let Some(prompt) = action.as_any().downcast_ref::<PromptAction>() {
+            let mut library = PromptLibrary::new();
+            PromptResolver::new().load_all_prompts(&mut library)?;
+            let arguments = prompt.substitute_variables(&self.run.context);
+            let text = library.render_prompt(&prompt.prompt_name, &arguments)?;
+            rendered.push_str("\n\n");
+            rendered.push_str(&text);
+        }
+        Ok(rendered)
+    }
+
+    /// Run one command, returning `false` when debugging should stop
+    pub async fn execute(&mut self, command: DebugCommand) -> Result<bool> {
+        match command {
+            DebugCommand::Step => {
+                if !self.step().await? && !self.is_finished() {
+                    println!(
+                        "{} No transition was taken from '{}'",
+                        "⚠️".yellow(),
+                        self.run.current_state
+                    );
+                }
+                self.print_where();
+            }
+            DebugCommand::Continue => {
+                if let Some(state) = self.continue_run().await? {
+                    println!("🔴 Breakpoint at '{state}'");
+                }
+                self.print_where();
+            }
+            DebugCommand::Where => self.print_where(),
+            DebugCommand::Vars => {
+                let mut names: Vec<&String> = self.run.context.keys().collect();
+                names.sort();
+                if names.is_empty() {
+                    println!("{}", "No variables".dimmed());
+                }
+                for name in names {
+                    println!("  {} = {}", name.cyan(), self.run.context[name]);
+                }
+            }
+            DebugCommand::Print(name) => match self.run.context.get(&name) {
+                Some(value) => println!("{}", serde_json::to_string_pretty(value)?),
+                None => println!("{}", format!("'{name}' is not set").dimmed()),
+            },
+            DebugCommand::Set(name, value) => {
+                self.run.context.insert(name, value);
+            }
+            DebugCommand::Unset(name) => {
+                if self.run.context.remove(&name).is_none() {
+                    println!("{}", format!("'{name}' is not set").dimmed());
+                }
+            }
+            DebugCommand::Render => println!("{}", self.render_action()?),
+            DebugCommand::Break(Some(state)) => {
+                self.add_breakpoint(&state)?;
+                println!("🔴 Breakpoint at '{state}'");
+            }
+            DebugCommand::Break(None) => {
+                if self.breakpoints.is_empty() {
+                    println!("{}", "No breakpoints".dimmed());
+                }
+                for state in &self.breakpoints {
+                    println!("  🔴 {state}");
+                }
+            }
+            DebugCommand::Delete(state) => {
+                if !self.remove_breakpoint(&state) {
+                    println!("{}", format!("No breakpoint at '{state}'").dimmed());
+                }
+            }
+            DebugCommand::Help => println!("{HELP}"),
+            DebugCommand::Quit => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn print_where(&self) {
+        if self.is_finished() {
+            println!(
+                "🏁 Run {:?} at '{}'",
+                self.run.status, self.run.current_state
+            );
+            return;
+        }
+
+        let description = self
+            .run
+            .workflow
+            .states
+            .get(&self.run.current_state)
+            .map(|state| state.description.as_str())
+            .unwrap_or("Unknown state");
+        println!(
+            "📍 {} - {}",
+            self.run.current_state.to_string().bold(),
+            description
+        );
+        for transition in self
+            .run
+            .workflow
+            .transitions
+            .iter()
+            .filter(|transition| transition.from_state == self.run.current_state)
+        {
+            let condition = transition
+                .condition
+                .expression
+                .as_deref()
+                .unwrap_or(transition.condition.condition_type.as_str());
+            println!(
+                "   → {} {}",
+                transition.to_state,
+                format!("[{condition}]").dimmed()
+            );
+        }
+    }
+}
+
+/// Start a workflow under the debugger and read commands from stdin
+pub async fn run_debug_command(
+    workflow_name: String,
+    vars: Vec<String>,
+    set: Vec<String>,
+    breakpoints: Vec<String>,
+) -> Result<()> {
+    let mut storage = WorkflowStorage::file_system()?;
+    let workflow = storage.get_workflow(&WorkflowName::new(&workflow_name))?;
+
+    let mut executor = WorkflowExecutor::new();
+    let mut run = executor.start_workflow(workflow).map_err(|e| {
+        SwissArmyHammerError::Other(format!("Failed to start workflow '{workflow_name}': {e}"))
+    })?;
+    run.context.extend(parse_assignments(&vars, "--var")?);
+    let set_variables = parse_assignments(&set, "--set")?;
+    if !set_variables.is_empty() {
+        run.context.insert(
+            "_template_vars".to_string(),
+            serde_json::to_value(set_variables)?,
+        );
+    }
+    executor
+        .run_hook(&run, WorkflowHookEvent::Start, HookDetails::default())
+        .await;
+
+    let mut debugger = Debugger::new(executor, run);
+    for state in &breakpoints {
+        debugger.add_breakpoint(state)?;
+    }
+
+    println!(
+        "🐞 Debugging workflow '{}'. Type 'help' for commands.",
+        workflow_name
+    );
+    debugger.print_where();
+
+    let stdin = io::stdin();
+    loop {
+        print!("(debug {}) ", debugger.run().current_state);
+        io::stdout().flush()?;
+
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line)? == 0 {
+            break;
+        }
+        let keep_going = match DebugCommand::parse(&line) {
+            Ok(command) => match debugger.execute(command).await {
+                Ok(keep_going) => keep_going,
+                Err(e) => {
+                    eprintln!("{} {}", "❌".red(), e);
+                    true
+                }
+            },
+            Err(message) => {
+                eprintln!("{message}");
+                true
+            }
+        };
+        if !keep_going {
+            break;
+        }
+    }
+
+    storage.store_run(debugger.run())?;
+    println!("🆔 Run ID: {}", debugger.run().id);
+    Ok(())
+}
+
+/// Parse `key=value` arguments into string variables
+pub fn parse_assignments(values: &[String], flag: &str) -> Result<HashMap<String, Value>> {
+    values
+        .iter()
+        .map(|assignment| match assignment.split_once('=') {
+            Some((key, value)) => Ok((key.to_string(), Value::String(value.to_string()))),
+            None => Err(SwissArmyHammerError::Other(format!(
+                "Invalid variable format: '{assignment}'. Expected 'key=value' format. Example: {flag} input=test"
+            ))),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use swissarmyhammer::workflow::MermaidParser;
+
+    fn debugger() -> Debugger {
+        let workflow = MermaidParser::parse(
+            r#"```mermaid
+stateDiagram-v2
+    [*] --> greet
+    greet --> remember
+    remember --> done
+    done --> [*]
+```
+
+## Actions
+
+- greet: Log "Hello ${name}"
+- remember: Set greeted="${name}"
+- done: Log "Done"
+"#,
+            "greeting",
+        )
+        .unwrap();
+        let mut executor = WorkflowExecutor::new();
+        let run = executor.start_workflow(workflow).unwrap();
+        Debugger::new(executor, run)
+    }
+
+    #[test]
+    fn test_parse_commands() {
+        assert_eq!(DebugCommand::parse("\n"), Ok(DebugCommand::Step));
+        assert_eq!(DebugCommand::parse("c"), Ok(DebugCommand::Continue));
+        assert_eq!(
+            DebugCommand::parse("p name"),
+            Ok(DebugCommand::Print("name".to_string()))
+        );
+        assert_eq!(
+            DebugCommand::parse("set count=3"),
+            Ok(DebugCommand::Set("count".to_string(), json!(3)))
+        );
+        assert_eq!(
+            DebugCommand::parse("set name Ada Lovelace"),
+            Ok(DebugCommand::Set("name".to_string(), json!("Ada Lovelace")))
+        );
+        assert_eq!(DebugCommand::parse("b"), Ok(DebugCommand::Break(None)));
+        assert_eq!(
+            DebugCommand::parse("break done"),
+            Ok(DebugCommand::Break(Some("done".to_string())))
+        );
+        assert!(DebugCommand::parse("print").is_err());
+        assert!(DebugCommand::parse("set =3").is_err());
+        assert!(DebugCommand::parse("jump").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_step_and_continue_to_breakpoint() {
+        let mut debugger = debugger();
+        assert!(debugger.add_breakpoint("missing").is_err());
+        debugger.add_breakpoint("done").unwrap();
+        debugger
+            .execute(DebugCommand::Set("name".to_string(), json!("Ada")))
+            .await
+            .unwrap();
+
+        assert!(debugger.step().await.unwrap());
+        assert_eq!(debugger.run().current_state, StateId::new("remember"));
+        assert_eq!(
+            debugger.render_action().unwrap(),
+            "set_variable: Set variable 'greeted' to '${name}'"
+        );
+
+        let stopped = debugger.continue_run().await.unwrap();
+        assert_eq!(stopped, Some(StateId::new("done")));
+        assert_eq!(debugger.run().context["greeted"], json!("Ada"));
+
+        assert_eq!(debugger.continue_run().await.unwrap(), None);
+        assert!(debugger.is_finished());
+        assert!(debugger.step().await.is_err());
+    }
+}
@@ -0,0 +1,125 @@
+//! Prompt dependency report showing what a prompt uses and what uses it
+
+use anyhow::{anyhow, Result};
+use colored::*;
+use serde::Serialize;
+use swissarmyhammer::{DependencyGraph, DependencyNode};
+
+use crate::cli::OutputFormat;
+
+/// Dependencies of a single prompt or workflow
+#[derive(Debug, Serialize)]
+struct DependencyReport {
+    /// The prompt or workflow reported on
+    item: DependencyNode,
+    /// File the item was loaded from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    path: Option<String>,
+    /// Items the item uses directly
+    depends_on: Vec<DependencyNode>,
+    /// Items the item uses that do not exist
+    missing: Vec<DependencyNode>,
+    /// Items that use the item directly
+    used_by: Vec<DependencyNode>,
+    /// Every item affected by changes to the item
+    affected: Vec<DependencyNode>,
+}
+
+/// Report the dependencies of a prompt or partial
+///
+/// Workflows can be reported on by name too, when no prompt has that name.
+pub fn run_deps_command(name: String, format: OutputFormat) -> Result<()> {
+    let graph = DependencyGraph::load()?;
+    let report = build_report(&graph, &name)?;
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        OutputFormat::Yaml => print!("{}", serde_yaml::to_string(&report)?),
+        OutputFormat::Table => display_report(&report),
+    }
+    Ok(())
+}
+
+fn build_report(graph: &DependencyGraph, name: &str) -> Result<DependencyReport> {
+    let item = [DependencyNode::prompt(name), DependencyNode::workflow(name)]
+        .into_iter()
+        .find(|node| graph.contains(node))
+        .ok_or_else(|| anyhow!("No prompt or workflow named '{name}'"))?;
+    let owned = |nodes: Vec<&DependencyNode>| -> Vec<DependencyNode> {
+        nodes.into_iter().cloned().collect()
+    };
+
+    Ok(DependencyReport {
+        path: graph.path(&item).map(|path| path.display().to_string()),
+        depends_on: owned(graph.dependencies(&item)),
+        missing: owned(graph.missing_dependencies(&item)),
+        used_by: owned(graph.dependents(&item)),
+        affected: owned(graph.affected_by(&item)),
+        item,
+    })
+}
+
+fn display_report(report: &DependencyReport) {
+    println!("{}", report.item.to_string().bold());
+    if let Some(path) = &report.path {
+        println!("  {}", path.dimmed());
+    }
+
+    println!("\nDepends on:");
+    if report.depends_on.is_empty() {
+        println!("  {}", "nothing".dimmed());
+    }
+    for dependency in &report.depends_on {
+        if report.missing.contains(dependency) {
+            println!("  {} {}", dependency, "(missing)".red());
+        } else {
+            println!("  {dependency}");
+        }
+    }
+
+    println!("\nUsed by:");
+    if report.used_by.is_empty() {
+        println!("  {}", "nothing".dimmed());
+    }
+    for dependent in &report.used_by {
+        println!("  {dependent}");
+    }
+
+    println!("\nAffected by changes ({}):", report.affected.len());
+    for affected in &report.affected {
+        println!("  {affected}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_report_for_partial() {
+        let mut graph = DependencyGraph::new();
+        graph.add_prompt("header.md", "# Header", None);
+        graph.add_prompt(
+            "greeting",
+            "{% render \"header\" %}{% render \"footer\" %}",
+            None,
+        );
+        graph.add_prompt("welcome", "{% render \"greeting\" %}", None);
+
+        let report = build_report(&graph, "header").unwrap();
+        assert!(report.depends_on.is_empty());
+        assert_eq!(report.used_by, vec![DependencyNode::prompt("greeting")]);
+        assert_eq!(
+            report.affected,
+            vec![
+                DependencyNode::prompt("greeting"),
+                DependencyNode::prompt("welcome")
+            ]
+        );
+
+        let report = build_report(&graph, "greeting").unwrap();
+        assert_eq!(report.missing, vec![DependencyNode::prompt("footer")]);
+
+        assert!(build_report(&graph, "unknown").is_err());
+    }
+}
@@ -0,0 +1,101 @@
+//! Prompt source diff showing how one prompt source differs from another
+
+use anyhow::Result;
+use colored::*;
+use swissarmyhammer::{PromptDiffKind, PromptSource, PromptSourceDiff};
+
+use crate::cli::{OutputFormat, PromptSourceArg};
+
+/// Kinds of difference in the order they are reported
+const REPORTED_KINDS: [PromptDiffKind; 5] = [
+    PromptDiffKind::Overridden,
+    PromptDiffKind::Shadowed,
+    PromptDiffKind::Added,
+    PromptDiffKind::Removed,
+    PromptDiffKind::Identical,
+];
+
+/// Report how the prompts of the `to` source differ from the `from` source
+pub fn run_diff_sources_command(
+    from: PromptSourceArg,
+    to: PromptSourceArg,
+    format: OutputFormat,
+    names_only: bool,
+) -> Result<()> {
+    let mut diff = PromptSourceDiff::load(PromptSource::from(from), PromptSource::from(to))?;
+    if names_only && !matches!(format, OutputFormat::Table) {
+        for entry in &mut diff.entries {
+            entry.diff.clear();
+        }
+    }
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&diff)?),
+        OutputFormat::Yaml => print!("{}", serde_yaml::to_string(&diff)?),
+        OutputFormat::Table => display_diff(&diff, names_only),
+    }
+    Ok(())
+}
+
+fn display_diff(diff: &PromptSourceDiff, names_only: bool) {
+    println!(
+        "{}",
+        format!("Prompts in {} compared with {}", diff.to, diff.from).bold()
+    );
+
+    for kind in REPORTED_KINDS {
+        let entries: Vec<_> = diff.of_kind(kind).collect();
+        if entries.is_empty() {
+            continue;
+        }
+
+        println!("\n{} ({}):", heading(kind, diff), entries.len());
+        if kind == PromptDiffKind::Identical {
+            let names: Vec<&str> = entries.iter().map(|entry| entry.name.as_str()).collect();
+            println!("  {}", names.join(", ").dimmed());
+            continue;
+        }
+
+        for entry in entries {
+            let (removed, added) = entry.line_changes();
+            if entry.diff.is_empty() {
+                println!("  {}", entry.name);
+            } else {
+                println!(
+                    "  {} {}",
+                    entry.name,
+                    format!("(-{removed} +{added})").dimmed()
+                );
+            }
+            if !names_only && !entry.diff.is_empty() {
+                print_unified_diff(&entry.unified_diff());
+            }
+        }
+    }
+}
+
+fn heading(kind: PromptDiffKind, diff: &PromptSourceDiff) -> String {
+    match kind {
+        PromptDiffKind::Overridden => format!("Overridden by {}", diff.to),
+        PromptDiffKind::Shadowed => format!("Shadowed by {}", diff.from),
+        PromptDiffKind::Added => format!("Only in {}", diff.to),
+        PromptDiffKind::Removed => format!("Only in {}", diff.from),
+        PromptDiffKind::Identical => "Identical".to_string(),
+    }
+}
+
+/// Print unified diff hunks indented, colored when stdout allows it
+pub(crate) fn print_unified_diff(unified: &str) {
+    for line in unified.lines() {
+        let line = if line.starts_with("@@") {
+            line.cyan()
+        } else if line.starts_with('-') {
+            line.red()
+        } else if line.starts_with('+') {
+            line.green()
+        } else {
+            line.normal()
+        };
+        println!("    {line}");
+    }
+}
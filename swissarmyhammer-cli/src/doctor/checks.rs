@@ -6,6 +6,10 @@ use anyhow::Result;
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
+use swissarmyhammer::git::GitOperations;
+use swissarmyhammer::issues::{
+    issue_storage_for_work_dir, BranchRenamePlan, Issue, IssueBranchNaming, IssueSla, SlaStatus,
+};
 use walkdir::WalkDir;
 
 /// Minimum disk space in MB before warning
@@ -26,6 +30,7 @@ pub mod check_names {
     pub const BINARY_NAME: &str = "Binary Name";
     pub const IN_PATH: &str = "swissarmyhammer in PATH";
     pub const CLAUDE_CONFIG: &str = "Claude Code MCP configuration";
+    pub const CLAUDE_DESKTOP_CONFIG: &str = "Claude Desktop MCP configuration";
     pub const BUILTIN_PROMPTS: &str = "Built-in prompts";
     pub const USER_PROMPTS_DIR: &str = "User prompts directory";
     pub const LOCAL_PROMPTS_DIR: &str = "Local prompts directory";
@@ -36,6 +41,9 @@ pub mod check_names {
     pub const WORKFLOW_RUN_STORAGE_SPACE: &str = "Workflow run storage space";
     pub const WORKFLOW_NAME_CONFLICTS: &str = "Workflow name conflicts";
     pub const WORKFLOW_CIRCULAR_DEPS: &str = "Workflow circular dependencies";
+    pub const ISSUE_SLA: &str = "Issue SLA";
+    pub const ISSUE_BRANCH_NAMING: &str = "Issue branch naming";
+    pub const WINDOWS_ENVIRONMENT: &str = "Windows environment";
 }
 
 /// Format strings used throughout the module
@@ -56,18 +64,7 @@ pub fn check_installation(checks: &mut Vec<Check>) -> Result<()> {
     let current_exe = env::current_exe().unwrap_or_default();
     let exe_path = current_exe.to_string_lossy();
 
-    // Determine installation method
-    let installation_method = if exe_path.contains(".cargo/bin") {
-        "Cargo install"
-    } else if exe_path.contains("/usr/local/bin") || exe_path.contains("/usr/bin") {
-        "System installation"
-    } else if exe_path.contains("target/") && exe_path.contains("debug") {
-        "Development build"
-    } else if exe_path.contains("target/") && exe_path.contains("release") {
-        "Local release build"
-    } else {
-        "Unknown"
-    };
+    let installation_method = installation_method(&current_exe);
 
     // Check binary version and build info
     let version = env!("CARGO_PKG_VERSION");
@@ -82,6 +79,7 @@ pub fn check_installation(checks: &mut Vec<Check>) -> Result<()> {
         status: CheckStatus::Ok,
         message: format!("{installation_method} (v{version}, {build_info}) at {exe_path}"),
         fix: None,
+        remedy: None,
     });
 
     // Check if binary has execute permissions (Unix only)
@@ -98,6 +96,7 @@ pub fn check_installation(checks: &mut Vec<Check>) -> Result<()> {
                     status: CheckStatus::Ok,
                     message: format!("Executable permissions: {:o}", mode & 0o777),
                     fix: None,
+                    remedy: None,
                 });
             } else {
                 checks.push(Check {
@@ -105,6 +104,7 @@ pub fn check_installation(checks: &mut Vec<Check>) -> Result<()> {
                     status: CheckStatus::Error,
                     message: "Binary is not executable".to_string(),
                     fix: Some(format!("Run: chmod +x {exe_path}")),
+                    remedy: None,
                 });
             }
         }
@@ -122,6 +122,7 @@ pub fn check_installation(checks: &mut Vec<Check>) -> Result<()> {
             status: CheckStatus::Ok,
             message: format!("Running as {exe_name}"),
             fix: None,
+            remedy: None,
         });
     } else {
         checks.push(Check {
@@ -129,12 +130,53 @@ pub fn check_installation(checks: &mut Vec<Check>) -> Result<()> {
             status: CheckStatus::Warning,
             message: format!("Unexpected binary name: {exe_name}"),
             fix: Some("Consider renaming binary to 'swissarmyhammer'".to_string()),
+            remedy: None,
         });
     }
 
     Ok(())
 }
 
+/// How the binary at `exe_path` was installed, judged from the directories
+/// it is in
+///
+/// Directory names are compared without regard to case or separator, so
+/// `C:\Users\me\.cargo\bin` is recognised like `~/.cargo/bin`.
+fn installation_method(exe_path: &Path) -> &'static str {
+    let mut dirs: Vec<String> = exe_path
+        .to_string_lossy()
+        .split(['/', '\\'])
+        .filter(|dir| !dir.is_empty())
+        .map(str::to_ascii_lowercase)
+        .collect();
+    // The last part is the binary itself
+    dirs.pop();
+    let has = |dir: &str| dirs.iter().any(|d| d == dir);
+    let ends_with = |tail: &[&str]| {
+        dirs.len() >= tail.len()
+            && dirs[dirs.len() - tail.len()..]
+                .iter()
+                .zip(tail)
+                .all(|(d, t)| d == t)
+    };
+
+    if ends_with(&[".cargo", "bin"]) {
+        "Cargo install"
+    } else if ends_with(&["usr", "local", "bin"])
+        || ends_with(&["usr", "bin"])
+        || has("program files")
+        || has("program files (x86)")
+    {
+        "System installation"
+    } else if has("target") && has("debug") {
+        "Development build"
+    } else if has("target") && has("release") {
+        "Local release build"
+    } else {
+        "Unknown"
+    }
+}
+
 /// Check if swissarmyhammer is in PATH
 ///
 /// Searches the system PATH for the swissarmyhammer executable
@@ -143,12 +185,12 @@ pub fn check_in_path(checks: &mut Vec<Check>) -> Result<()> {
     let path_var = env::var("PATH").unwrap_or_default();
     let paths: Vec<std::path::PathBuf> = env::split_paths(&path_var).collect();
 
-    let exe_name = "swissarmyhammer";
+    let exe_name = format!("swissarmyhammer{}", env::consts::EXE_SUFFIX);
     let mut found = false;
     let mut found_path = None;
 
     for path in paths {
-        let exe_path = path.join(exe_name);
+        let exe_path = path.join(&exe_name);
         if exe_path.exists() {
             found = true;
             found_path = Some(exe_path);
@@ -165,6 +207,7 @@ pub fn check_in_path(checks: &mut Vec<Check>) -> Result<()> {
                 found_path.expect("found_path should be Some when found is true")
             ),
             fix: None,
+            remedy: None,
         });
     } else {
         checks.push(Check {
@@ -175,6 +218,7 @@ pub fn check_in_path(checks: &mut Vec<Check>) -> Result<()> {
                 "Add swissarmyhammer to your PATH or use the full path in Claude Code config"
                     .to_string(),
             ),
+            remedy: None,
         });
     }
 
@@ -226,6 +270,7 @@ pub fn check_claude_config(checks: &mut Vec<Check>) -> Result<()> {
                 "Install Claude Code from https://claude.ai/code or ensure the 'claude' command is in your PATH\nCurrent PATH: {}",
                 env::split_paths(&path_var).take(3).map(|p| p.display().to_string()).collect::<Vec<_>>().join(if cfg!(windows) { ";" } else { ":" }) + "..."
             )),
+            remedy: None,
         });
         return Ok(());
     }
@@ -246,6 +291,7 @@ pub fn check_claude_config(checks: &mut Vec<Check>) -> Result<()> {
                             claude_path.unwrap_or_else(|| PathBuf::from("claude"))
                         ),
                         fix: None,
+                        remedy: None,
                     });
                 } else {
                     checks.push(Check {
@@ -253,6 +299,7 @@ pub fn check_claude_config(checks: &mut Vec<Check>) -> Result<()> {
                         status: CheckStatus::Warning,
                         message: "swissarmyhammer not found in Claude Code MCP servers".to_string(),
                         fix: Some(get_claude_add_command()),
+                        remedy: Some(Remedy::AddMcpServer(McpClient::ClaudeCode)),
                     });
                 }
             } else {
@@ -265,6 +312,7 @@ pub fn check_claude_config(checks: &mut Vec<Check>) -> Result<()> {
                         "Ensure Claude Code is installed and the 'claude' command is available"
                             .to_string(),
                     ),
+                    remedy: None,
                 });
             }
         }
@@ -281,6 +329,7 @@ pub fn check_claude_config(checks: &mut Vec<Check>) -> Result<()> {
                 fix: Some(
                     "Check that Claude Code is properly installed and executable".to_string(),
                 ),
+                remedy: None,
             });
         }
     }
@@ -288,6 +337,73 @@ pub fn check_claude_config(checks: &mut Vec<Check>) -> Result<()> {
     Ok(())
 }
 
+/// Check Claude Desktop lists swissarmyhammer as an MCP server
+///
+/// Only reported when Claude Desktop is installed, which is taken to be when
+/// the directory of its configuration file exists.
+pub fn check_claude_desktop_config(checks: &mut Vec<Check>) -> Result<()> {
+    let config_path = get_claude_config_path();
+    if let Some(check) = claude_desktop_config_check(&config_path) {
+        checks.push(check);
+    }
+    Ok(())
+}
+
+fn claude_desktop_config_check(config_path: &Path) -> Option<Check> {
+    if !config_path.parent().is_some_and(Path::is_dir) {
+        return None;
+    }
+
+    let config = match fs::read_to_string(config_path) {
+        Ok(content) => match serde_json::from_str::<serde_json::Value>(&content) {
+            Ok(config) => config,
+            Err(e) => {
+                return Some(Check {
+                    name: check_names::CLAUDE_DESKTOP_CONFIG.to_string(),
+                    status: CheckStatus::Error,
+                    message: format!("Failed to parse {config_path:?}: {e}"),
+                    fix: Some(format!("Fix the JSON syntax in {config_path:?}")),
+                    remedy: None,
+                })
+            }
+        },
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => serde_json::Value::Null,
+        Err(e) => {
+            return Some(Check {
+                name: check_names::CLAUDE_DESKTOP_CONFIG.to_string(),
+                status: CheckStatus::Error,
+                message: format!("Failed to read {config_path:?}: {e}"),
+                fix: Some(format!("Check permissions on {config_path:?}")),
+                remedy: None,
+            })
+        }
+    };
+
+    if config
+        .get("mcpServers")
+        .and_then(|servers| servers.get(MCP_SERVER_NAME))
+        .is_some()
+    {
+        Some(Check {
+            name: check_names::CLAUDE_DESKTOP_CONFIG.to_string(),
+            status: CheckStatus::Ok,
+            message: format!("swissarmyhammer is configured in {config_path:?}"),
+            fix: None,
+            remedy: None,
+        })
+    } else {
+        Some(Check {
+            name: check_names::CLAUDE_DESKTOP_CONFIG.to_string(),
+            status: CheckStatus::Warning,
+            message: "swissarmyhammer not found in Claude Desktop MCP servers".to_string(),
+            fix: Some(format!(
+                "Add a \"{MCP_SERVER_NAME}\" entry running `swissarmyhammer serve` to mcpServers in {config_path:?}"
+            )),
+            remedy: Some(Remedy::AddMcpServer(McpClient::ClaudeDesktop)),
+        })
+    }
+}
+
 /// Check prompt directories
 ///
 /// Verifies the existence and accessibility of:
@@ -301,10 +417,11 @@ pub fn check_prompt_directories(checks: &mut Vec<Check>) -> Result<()> {
         status: CheckStatus::Ok,
         message: "Built-in prompts are embedded in the binary".to_string(),
         fix: None,
+        remedy: None,
     });
 
     // Check user prompts directory
-    if let Some(home) = dirs::home_dir() {
+    if let Some(home) = swissarmyhammer::directory_utils::home_dir() {
         let user_prompts = home.join(SWISSARMYHAMMER_DIR).join("prompts");
         if user_prompts.exists() {
             let count = count_markdown_files(&user_prompts);
@@ -313,6 +430,7 @@ pub fn check_prompt_directories(checks: &mut Vec<Check>) -> Result<()> {
                 status: CheckStatus::Ok,
                 message: format!("Found {count} prompts in {user_prompts:?}"),
                 fix: None,
+                remedy: None,
             });
         } else {
             checks.push(Check {
@@ -323,6 +441,7 @@ pub fn check_prompt_directories(checks: &mut Vec<Check>) -> Result<()> {
                     "User prompts", user_prompts
                 ),
                 fix: Some(format!("Create directory: mkdir -p {user_prompts:?}")),
+                remedy: None,
             });
         }
     }
@@ -336,6 +455,7 @@ pub fn check_prompt_directories(checks: &mut Vec<Check>) -> Result<()> {
             status: CheckStatus::Ok,
             message: format!("Found {count} prompts in {local_prompts:?}"),
             fix: None,
+            remedy: None,
         });
     } else {
         checks.push(Check {
@@ -346,6 +466,7 @@ pub fn check_prompt_directories(checks: &mut Vec<Check>) -> Result<()> {
                 "Local prompts", local_prompts
             ),
             fix: Some(format!("Create directory: mkdir -p {local_prompts:?}")),
+            remedy: None,
         });
     }
 
@@ -363,7 +484,7 @@ pub fn check_yaml_parsing(checks: &mut Vec<Check>) -> Result<()> {
     let mut dirs_to_check = vec![PathBuf::from(SWISSARMYHAMMER_DIR).join("prompts")];
 
     // Add user directory if it exists
-    if let Some(home) = dirs::home_dir() {
+    if let Some(home) = swissarmyhammer::directory_utils::home_dir() {
         dirs_to_check.push(home.join(SWISSARMYHAMMER_DIR).join("prompts"));
     }
 
@@ -408,6 +529,7 @@ pub fn check_yaml_parsing(checks: &mut Vec<Check>) -> Result<()> {
             status: CheckStatus::Ok,
             message: "All prompt YAML front matter is valid".to_string(),
             fix: None,
+            remedy: None,
         });
     } else {
         for (path, error) in yaml_errors {
@@ -416,6 +538,7 @@ pub fn check_yaml_parsing(checks: &mut Vec<Check>) -> Result<()> {
                 status: CheckStatus::Error,
                 message: error,
                 fix: Some(format!("Fix the YAML syntax in {path:?}")),
+                remedy: None,
             });
         }
     }
@@ -436,6 +559,7 @@ pub fn check_file_permissions(checks: &mut Vec<Check>) -> Result<()> {
                 status: CheckStatus::Ok,
                 message: format!("Can read current directory: {cwd:?}"),
                 fix: None,
+                remedy: None,
             });
         }
         Err(e) => {
@@ -444,6 +568,7 @@ pub fn check_file_permissions(checks: &mut Vec<Check>) -> Result<()> {
                 status: CheckStatus::Error,
                 message: format!("Failed to read current directory: {e}"),
                 fix: Some("Check file permissions for the current directory".to_string()),
+                remedy: None,
             });
         }
     }
@@ -451,6 +576,65 @@ pub fn check_file_permissions(checks: &mut Vec<Check>) -> Result<()> {
     Ok(())
 }
 
+/// Check the environment SwissArmyHammer relies on under Windows
+///
+/// Verifies that `%USERPROFILE%` names a directory, since user prompts and
+/// workflows live in `%USERPROFILE%\.swissarmyhammer`, and that `cmd.exe`,
+/// which shell actions run in, is on the PATH.
+pub fn check_windows_environment(checks: &mut Vec<Check>) -> Result<()> {
+    let user_profile = env::var_os("USERPROFILE").map(PathBuf::from);
+    let shell = env::var_os("PATH").and_then(|path| {
+        env::split_paths(&path)
+            .map(|dir| dir.join("cmd.exe"))
+            .find(|cmd| cmd.is_file())
+    });
+    checks.push(windows_environment_check(
+        user_profile.as_deref(),
+        shell.as_deref(),
+    ));
+    Ok(())
+}
+
+fn windows_environment_check(user_profile: Option<&Path>, shell: Option<&Path>) -> Check {
+    let profile_fix = "Set USERPROFILE to your profile directory, e.g. C:\\Users\\<name>";
+    let (status, message, fix) = match (user_profile, shell) {
+        (None, _) => (
+            CheckStatus::Error,
+            "USERPROFILE is not set, so user prompts and workflows cannot be found".to_string(),
+            Some(profile_fix.to_string()),
+        ),
+        (Some(profile), _) if !profile.is_dir() => (
+            CheckStatus::Error,
+            format!("USERPROFILE is not a directory: {}", profile.display()),
+            Some(profile_fix.to_string()),
+        ),
+        (Some(profile), None) => (
+            CheckStatus::Warning,
+            format!(
+                "Profile at {}, but cmd.exe is not on the PATH so shell actions will fail",
+                profile.display()
+            ),
+            Some("Add C:\\Windows\\System32 to your PATH".to_string()),
+        ),
+        (Some(profile), Some(shell)) => (
+            CheckStatus::Ok,
+            format!(
+                "Profile at {}, shell actions run in {}",
+                profile.display(),
+                shell.display()
+            ),
+            None,
+        ),
+    };
+    Check {
+        name: check_names::WINDOWS_ENVIRONMENT.to_string(),
+        status,
+        message,
+        fix,
+        remedy: None,
+    }
+}
+
 /// Check workflow directories exist
 ///
 /// Verifies the existence of workflow directories:
@@ -467,6 +651,7 @@ pub fn check_workflow_directories(checks: &mut Vec<Check>) -> Result<()> {
                 status: CheckStatus::Ok,
                 message: format!("Found {} workflows in {}", count, dir_info.path),
                 fix: None,
+                remedy: None,
             });
         } else {
             checks.push(Check {
@@ -477,12 +662,13 @@ pub fn check_workflow_directories(checks: &mut Vec<Check>) -> Result<()> {
                     dir_info.category, dir_info.path
                 ),
                 fix: Some(format!("Create directory: mkdir -p {}", dir_info.path)),
+                remedy: None,
             });
         }
     }
 
     // Check workflow run storage directory
-    if let Some(home) = dirs::home_dir() {
+    if let Some(home) = swissarmyhammer::directory_utils::home_dir() {
         let run_storage = home.join(SWISSARMYHAMMER_DIR).join("runs");
         if run_storage.exists() {
             checks.push(Check {
@@ -490,6 +676,7 @@ pub fn check_workflow_directories(checks: &mut Vec<Check>) -> Result<()> {
                 status: CheckStatus::Ok,
                 message: format!("Run storage directory exists: {run_storage:?}"),
                 fix: None,
+                remedy: None,
             });
         } else {
             checks.push(Check {
@@ -497,6 +684,7 @@ pub fn check_workflow_directories(checks: &mut Vec<Check>) -> Result<()> {
                 status: CheckStatus::Warning,
                 message: format!("Run storage directory not found: {run_storage:?}"),
                 fix: Some(format!("Create directory: mkdir -p {run_storage:?}")),
+                remedy: Some(Remedy::CreateDirectory(run_storage)),
             });
         }
     }
@@ -520,7 +708,7 @@ pub fn check_workflow_permissions(checks: &mut Vec<Check>) -> Result<()> {
     }
 
     // Add run storage directory if it exists
-    if let Some(home) = dirs::home_dir() {
+    if let Some(home) = swissarmyhammer::directory_utils::home_dir() {
         let run_storage = home.join(SWISSARMYHAMMER_DIR).join("runs");
         if run_storage.exists() {
             dirs_to_check.push(run_storage);
@@ -546,8 +734,10 @@ pub fn check_workflow_permissions(checks: &mut Vec<Check>) -> Result<()> {
                         status: CheckStatus::Ok,
                         message: format!("Directory has correct permissions: {:o}", mode & 0o777),
                         fix: None,
+                        remedy: None,
                     });
                 } else {
+                    let fixed_mode = (mode & 0o777) | 0o700;
                     checks.push(Check {
                         name: format!(
                             "Workflow directory permissions: {:?}",
@@ -558,7 +748,11 @@ pub fn check_workflow_permissions(checks: &mut Vec<Check>) -> Result<()> {
                             "Directory permissions may be insufficient: {:o}",
                             mode & 0o777
                         ),
-                        fix: Some(format!("Run: chmod 755 {dir:?}")),
+                        fix: Some(format!("Run: chmod {fixed_mode:o} {dir:?}")),
+                        remedy: Some(Remedy::SetPermissions {
+                            path: dir.clone(),
+                            mode: fixed_mode,
+                        }),
                     });
                 }
             } else {
@@ -570,6 +764,7 @@ pub fn check_workflow_permissions(checks: &mut Vec<Check>) -> Result<()> {
                     status: CheckStatus::Warning,
                     message: "Failed to check directory permissions".to_string(),
                     fix: None,
+                    remedy: None,
                 });
             }
         }
@@ -586,6 +781,7 @@ pub fn check_workflow_permissions(checks: &mut Vec<Check>) -> Result<()> {
                     status: CheckStatus::Ok,
                     message: "Directory is accessible".to_string(),
                     fix: None,
+                    remedy: None,
                 });
             } else {
                 checks.push(Check {
@@ -596,6 +792,7 @@ pub fn check_workflow_permissions(checks: &mut Vec<Check>) -> Result<()> {
                     status: CheckStatus::Error,
                     message: "Failed to access directory".to_string(),
                     fix: Some("Check directory permissions and ownership".to_string()),
+                    remedy: None,
                 });
             }
         }
@@ -654,6 +851,7 @@ pub fn check_workflow_parsing(checks: &mut Vec<Check>) -> Result<()> {
             status: CheckStatus::Ok,
             message: "All workflow files are readable".to_string(),
             fix: None,
+            remedy: None,
         });
     } else {
         for (path, error) in workflow_errors {
@@ -665,6 +863,7 @@ pub fn check_workflow_parsing(checks: &mut Vec<Check>) -> Result<()> {
                 status: CheckStatus::Error,
                 message: error,
                 fix: Some(format!("Fix or remove the workflow file: {path:?}")),
+                remedy: None,
             });
         }
     }
@@ -679,7 +878,7 @@ pub fn check_workflow_parsing(checks: &mut Vec<Check>) -> Result<()> {
 /// - Has write permissions
 /// - Has adequate disk space
 pub fn check_workflow_run_storage(checks: &mut Vec<Check>) -> Result<()> {
-    if let Some(home) = dirs::home_dir() {
+    if let Some(home) = swissarmyhammer::directory_utils::home_dir() {
         let run_storage = home.join(SWISSARMYHAMMER_DIR).join("runs");
 
         if run_storage.exists() {
@@ -691,6 +890,7 @@ pub fn check_workflow_run_storage(checks: &mut Vec<Check>) -> Result<()> {
                 status: CheckStatus::Warning,
                 message: "Run storage directory does not exist".to_string(),
                 fix: Some(format!("Create directory: mkdir -p {run_storage:?}")),
+                remedy: Some(Remedy::CreateDirectory(run_storage)),
             });
         }
     }
@@ -712,6 +912,7 @@ fn check_run_storage_write_access(checks: &mut Vec<Check>, run_storage: &Path) -
                 status: CheckStatus::Ok,
                 message: "Run storage is accessible and writable".to_string(),
                 fix: None,
+                remedy: None,
             });
         }
         Err(e) => {
@@ -720,6 +921,7 @@ fn check_run_storage_write_access(checks: &mut Vec<Check>, run_storage: &Path) -
                 status: CheckStatus::Error,
                 message: format!("Run storage is not writable: {e}"),
                 fix: Some(format!("Check permissions on {run_storage:?}")),
+                remedy: None,
             });
         }
     }
@@ -739,6 +941,7 @@ fn check_run_storage_disk_space(checks: &mut Vec<Check>, run_storage: &Path) ->
                     fix: Some(
                         "Consider cleaning up old workflow runs or freeing disk space".to_string(),
                     ),
+                    remedy: None,
                 });
             } else {
                 checks.push(Check {
@@ -746,6 +949,7 @@ fn check_run_storage_disk_space(checks: &mut Vec<Check>, run_storage: &Path) ->
                     status: CheckStatus::Ok,
                     message: format!("Adequate disk space: {available}"),
                     fix: None,
+                    remedy: None,
                 });
             }
         }
@@ -755,6 +959,7 @@ fn check_run_storage_disk_space(checks: &mut Vec<Check>, run_storage: &Path) ->
                 status: CheckStatus::Warning,
                 message: format!("Failed to check disk space: {e}"),
                 fix: None,
+                remedy: None,
             });
         }
     }
@@ -824,6 +1029,7 @@ fn check_name_conflicts(
                 status: CheckStatus::Warning,
                 message: format!("Workflow '{name}' exists in multiple locations: {locations}"),
                 fix: Some("Rename or remove duplicate workflows to avoid conflicts".to_string()),
+                remedy: None,
             });
         }
     }
@@ -834,6 +1040,7 @@ fn check_name_conflicts(
             status: CheckStatus::Ok,
             message: "No workflow name conflicts detected".to_string(),
             fix: None,
+            remedy: None,
         });
     }
 }
@@ -847,7 +1054,161 @@ fn check_circular_dependencies(checks: &mut Vec<Check>) {
         status: CheckStatus::Ok,
         message: "Circular dependency checking requires workflow execution".to_string(),
         fix: None,
+        remedy: None,
+    });
+}
+
+/// Check open issues against the configured SLA rules
+///
+/// Open issues past the warning or escalation age are a warning, so they
+/// don't silently rot in the issues directory.
+pub fn check_issue_sla(checks: &mut Vec<Check>) -> Result<()> {
+    let sla = IssueSla::from_config(swissarmyhammer::Config::global());
+    let work_dir = env::current_dir()?;
+    if !sla.is_configured() || !work_dir.join("issues").is_dir() {
+        checks.push(Check {
+            name: check_names::ISSUE_SLA.to_string(),
+            status: CheckStatus::Ok,
+            message: if sla.is_configured() {
+                "No issues directory".to_string()
+            } else {
+                "No issue SLA configured".to_string()
+            },
+            fix: None,
+            remedy: None,
+        });
+        return Ok(());
+    }
+
+    let issues = list_issues_blocking(work_dir)?;
+    let now = chrono::Utc::now();
+    let names_with = |status: SlaStatus| {
+        let mut names: Vec<_> = issues
+            .iter()
+            .filter(|issue| sla.status(issue, now) == status)
+            .map(|issue| issue.name.as_str())
+            .collect();
+        names.sort_unstable();
+        names
+    };
+    let escalated = names_with(SlaStatus::Escalated);
+    let warned = names_with(SlaStatus::Warning);
+
+    if escalated.is_empty() && warned.is_empty() {
+        checks.push(Check {
+            name: check_names::ISSUE_SLA.to_string(),
+            status: CheckStatus::Ok,
+            message: "All open issues are within their SLA".to_string(),
+            fix: None,
+            remedy: None,
+        });
+        return Ok(());
+    }
+
+    let mut problems = Vec::new();
+    if let (false, Some(days)) = (escalated.is_empty(), sla.escalate_after_days) {
+        problems.push(format!(
+            "{} escalated past {days} days ({})",
+            escalated.len(),
+            escalated.join(", ")
+        ));
+    }
+    if let (false, Some(days)) = (warned.is_empty(), sla.warn_after_days) {
+        problems.push(format!(
+            "{} past {days} days ({})",
+            warned.len(),
+            warned.join(", ")
+        ));
+    }
+    checks.push(Check {
+        name: check_names::ISSUE_SLA.to_string(),
+        status: CheckStatus::Warning,
+        message: format!("Open issues past their SLA: {}", problems.join("; ")),
+        fix: Some(
+            "Work, close or re-prioritize them; 'swissarmyhammer issue stale' lists them"
+                .to_string(),
+        ),
+        remedy: None,
     });
+    Ok(())
+}
+
+/// Check the issue branch template, and that existing issue branches follow it
+pub fn check_issue_branch_naming(checks: &mut Vec<Check>) -> Result<()> {
+    let template = &swissarmyhammer::Config::global().issue_branch_template;
+    checks.push(issue_branch_naming_check(template, &env::current_dir()?)?);
+    Ok(())
+}
+
+fn issue_branch_naming_check(template: &str, work_dir: &Path) -> Result<Check> {
+    let naming = match IssueBranchNaming::new(template) {
+        Ok(naming) => naming,
+        Err(e) => {
+            return Ok(Check {
+                name: check_names::ISSUE_BRANCH_NAMING.to_string(),
+                status: CheckStatus::Error,
+                message: e.to_string(),
+                fix: Some(
+                    "Set SWISSARMYHAMMER_ISSUE_BRANCH_TEMPLATE to a template such as feature/{number}-{slug}"
+                        .to_string(),
+                ),
+                remedy: None,
+            })
+        }
+    };
+
+    // Branches still named by the default template are left over from before a change
+    let default = IssueBranchNaming::default();
+    let git = GitOperations::with_work_dir(work_dir.to_path_buf())
+        .ok()
+        .map(|git| git.with_branch_naming(naming.clone()));
+    let plan = match git {
+        Some(git) if naming != default && work_dir.join("issues").is_dir() => {
+            let issues = list_issues_blocking(work_dir.to_path_buf())?;
+            Some(BranchRenamePlan::new(
+                &default,
+                &naming,
+                &issues,
+                &git.local_branches()?,
+            ))
+        }
+        _ => None,
+    };
+
+    Ok(match plan.filter(|plan| !plan.renames.is_empty()) {
+        Some(plan) => Check {
+            name: check_names::ISSUE_BRANCH_NAMING.to_string(),
+            status: CheckStatus::Warning,
+            message: format!(
+                "{} issue branches are not named by {template}, such as {}",
+                plan.renames.len(),
+                plan.renames[0].from
+            ),
+            fix: Some("Run 'swissarmyhammer issue rename-branches --yes'".to_string()),
+            remedy: None,
+        },
+        None => Check {
+            name: check_names::ISSUE_BRANCH_NAMING.to_string(),
+            status: CheckStatus::Ok,
+            message: format!("Issue branches are named {template}"),
+            fix: None,
+            remedy: None,
+        },
+    })
+}
+
+/// List the issues of a work directory from synchronous code
+///
+/// Doctor runs both inside the CLI's runtime and from plain tests, so the
+/// storage is read on its own thread with a runtime of its own.
+fn list_issues_blocking(work_dir: PathBuf) -> Result<Vec<Issue>> {
+    std::thread::spawn(move || -> Result<Vec<Issue>> {
+        let runtime = tokio::runtime::Builder::new_current_thread().build()?;
+        Ok(runtime
+            .block_on(async { issue_storage_for_work_dir(&work_dir)?.list_issues().await })?)
+    })
+    .join()
+    .map_err(|_| anyhow::anyhow!("Listing issues panicked"))?
 }
 
 #[cfg(test)]
@@ -940,4 +1301,104 @@ mod tests {
             .message
             .contains("Claude Code command not found in PATH"));
     }
+
+    #[test]
+    fn test_check_issue_sla_reports_once() {
+        let mut checks = Vec::new();
+        check_issue_sla(&mut checks).unwrap();
+
+        assert_eq!(checks.len(), 1);
+        assert_eq!(checks[0].name, check_names::ISSUE_SLA);
+        assert_ne!(checks[0].status, CheckStatus::Error);
+    }
+
+    #[test]
+    fn test_issue_branch_naming_check() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let check = issue_branch_naming_check("issue/{name}", temp_dir.path()).unwrap();
+        assert_eq!(check.name, check_names::ISSUE_BRANCH_NAMING);
+        assert_eq!(check.status, CheckStatus::Ok);
+
+        let check = issue_branch_naming_check("feature/{slug}", temp_dir.path()).unwrap();
+        assert_eq!(check.status, CheckStatus::Error);
+        assert!(check.fix.is_some());
+    }
+
+    #[test]
+    fn test_windows_environment_check() {
+        let temp_dir = TempDir::new().unwrap();
+        let shell = temp_dir.path().join("cmd.exe");
+
+        let check = windows_environment_check(Some(temp_dir.path()), Some(&shell));
+        assert_eq!(check.name, check_names::WINDOWS_ENVIRONMENT);
+        assert_eq!(check.status, CheckStatus::Ok);
+
+        let check = windows_environment_check(Some(temp_dir.path()), None);
+        assert_eq!(check.status, CheckStatus::Warning);
+
+        let missing = temp_dir.path().join("missing");
+        for profile in [None, Some(missing.as_path())] {
+            let check = windows_environment_check(profile, Some(&shell));
+            assert_eq!(check.status, CheckStatus::Error);
+            assert!(check.fix.is_some());
+        }
+    }
+
+    #[test]
+    fn test_claude_desktop_config_check() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir
+            .path()
+            .join("Claude")
+            .join("claude_desktop_config.json");
+
+        // Claude Desktop is not installed
+        assert!(claude_desktop_config_check(&config_path).is_none());
+
+        fs::create_dir(temp_dir.path().join("Claude")).unwrap();
+        let check = claude_desktop_config_check(&config_path).unwrap();
+        assert_eq!(check.status, CheckStatus::Warning);
+        assert_eq!(
+            check.remedy,
+            Some(Remedy::AddMcpServer(McpClient::ClaudeDesktop))
+        );
+
+        fs::write(
+            &config_path,
+            r#"{"mcpServers": {"swissarmyhammer": {"command": "swissarmyhammer", "args": ["serve"]}}}"#,
+        )
+        .unwrap();
+        let check = claude_desktop_config_check(&config_path).unwrap();
+        assert_eq!(check.status, CheckStatus::Ok);
+
+        fs::write(&config_path, "{ not json").unwrap();
+        let check = claude_desktop_config_check(&config_path).unwrap();
+        assert_eq!(check.status, CheckStatus::Error);
+        assert!(check.remedy.is_none());
+    }
+
+    #[test]
+    fn test_installation_method() {
+        assert_eq!(
+            installation_method(Path::new("/home/jane/.cargo/bin/swissarmyhammer")),
+            "Cargo install"
+        );
+        assert_eq!(
+            installation_method(Path::new(r"C:\Users\Jane\.Cargo\bin\swissarmyhammer.exe")),
+            "Cargo install"
+        );
+        assert_eq!(
+            installation_method(Path::new(r"C:\Program Files\swissarmyhammer.exe")),
+            "System installation"
+        );
+        assert_eq!(
+            installation_method(Path::new(r"D:\src\sah\target\debug\swissarmyhammer.exe")),
+            "Development build"
+        );
+        assert_eq!(
+            installation_method(Path::new("/opt/tools/swissarmyhammer")),
+            "Unknown"
+        );
+    }
 }
@@ -10,9 +10,15 @@
 //! - Prompt directory validation
 //! - YAML front matter parsing verification
 //! - Workflow system diagnostics
+//! - Open issues past their SLA
 //! - Disk space monitoring
 //! - File permission checks
 //!
+//! Checks that find a problem doctor can resolve itself carry a [`Remedy`],
+//! such as creating a missing directory or adding swissarmyhammer to the MCP
+//! servers of Claude Code or Claude Desktop. `doctor --fix` lists these and
+//! applies them once confirmed.
+//!
 //! # Usage
 //!
 //! ```no_run
@@ -29,11 +35,14 @@
 
 use anyhow::Result;
 use colored::*;
+use dialoguer::{theme::ColorfulTheme, Confirm};
+use std::io::{self, IsTerminal};
 
 // Re-export types from submodules
 pub use types::*;
 
 pub mod checks;
+pub mod remedies;
 pub mod types;
 pub mod utils;
 
@@ -42,6 +51,7 @@ const SYSTEM_CHECK_KEYWORDS: &[&str] = &["PATH", "permissions", "Binary", "Insta
 const CONFIG_CHECK_KEYWORDS: &[&str] = &["Claude", "config"];
 const PROMPT_CHECK_KEYWORDS: &[&str] = &["prompt", "YAML"];
 const WORKFLOW_CHECK_KEYWORDS: &[&str] = &["Workflow", "workflow"];
+const ISSUE_CHECK_KEYWORDS: &[&str] = &["Issue"];
 
 /// Main diagnostic tool for SwissArmyHammer system health checks
 ///
@@ -81,6 +91,7 @@ impl Doctor {
         self.run_configuration_checks()?;
         self.run_prompt_checks()?;
         self.run_workflow_checks()?;
+        self.run_issue_checks()?;
 
         // Print results
         self.print_results();
@@ -89,17 +100,83 @@ impl Doctor {
         Ok(self.get_exit_code())
     }
 
+    /// Run all diagnostic checks, then apply the remedies of failed checks
+    ///
+    /// Lists the remedies and asks for confirmation before applying them,
+    /// unless `yes` is set. Diagnostics are run again afterwards and their exit
+    /// code returned.
+    pub fn run_fixes(&mut self, yes: bool) -> Result<i32> {
+        let exit_code = self.run_diagnostics()?;
+        let fixes = self.remedies();
+        if fixes.is_empty() {
+            println!("No problems doctor --fix can resolve");
+            return Ok(exit_code);
+        }
+
+        println!("{}", "Fixes:".bold().yellow());
+        for remedy in &fixes {
+            println!("  • {remedy}");
+        }
+        println!();
+
+        let apply = if yes {
+            true
+        } else if io::stdin().is_terminal() {
+            Confirm::with_theme(&ColorfulTheme::default())
+                .with_prompt(format!("Apply these {} fixes?", fixes.len()))
+                .default(false)
+                .interact()?
+        } else {
+            println!("Run again with --yes to apply these fixes");
+            false
+        };
+        if !apply {
+            return Ok(exit_code);
+        }
+
+        for remedy in &fixes {
+            match remedies::apply(remedy) {
+                Ok(done) => println!("  {} {done}", "✓".green()),
+                Err(e) => println!("  {} {remedy}: {e:#}", "✗".red()),
+            }
+        }
+        println!();
+
+        self.checks.clear();
+        self.run_diagnostics()
+    }
+
+    /// Remedies of the checks that found a warning or error, without repeats
+    pub fn remedies(&self) -> Vec<Remedy> {
+        let mut remedies: Vec<Remedy> = Vec::new();
+        for check in &self.checks {
+            if check.status == CheckStatus::Ok {
+                continue;
+            }
+            if let Some(remedy) = &check.remedy {
+                if !remedies.contains(remedy) {
+                    remedies.push(remedy.clone());
+                }
+            }
+        }
+        remedies
+    }
+
     /// Run system checks
     fn run_system_checks(&mut self) -> Result<()> {
         checks::check_installation(&mut self.checks)?;
         checks::check_in_path(&mut self.checks)?;
         checks::check_file_permissions(&mut self.checks)?;
+        if cfg!(windows) {
+            checks::check_windows_environment(&mut self.checks)?;
+        }
         Ok(())
     }
 
     /// Run configuration checks
     fn run_configuration_checks(&mut self) -> Result<()> {
         checks::check_claude_config(&mut self.checks)?;
+        checks::check_claude_desktop_config(&mut self.checks)?;
         Ok(())
     }
 
@@ -120,6 +197,13 @@ impl Doctor {
         Ok(())
     }
 
+    /// Run issue checks
+    fn run_issue_checks(&mut self) -> Result<()> {
+        checks::check_issue_sla(&mut self.checks)?;
+        checks::check_issue_branch_naming(&mut self.checks)?;
+        Ok(())
+    }
+
     /// Print the results
     ///
     /// Displays all diagnostic results grouped by category:
@@ -127,6 +211,7 @@ impl Doctor {
     /// - Configuration
     /// - Prompts
     /// - Workflows
+    /// - Issues
     ///
     /// Results are color-coded based on status (OK, Warning, Error).
     pub fn print_results(&self) {
@@ -139,6 +224,7 @@ impl Doctor {
         self.print_check_category(&check_groups.config_checks, "Configuration:", use_color);
         self.print_check_category(&check_groups.prompt_checks, "Prompts:", use_color);
         self.print_check_category(&check_groups.workflow_checks, "Workflows:", use_color);
+        self.print_check_category(&check_groups.issue_checks, "Issues:", use_color);
 
         // Print summary
         self.print_summary(use_color);
@@ -188,6 +274,15 @@ impl Doctor {
                         .any(|&keyword| c.name.contains(keyword))
                 })
                 .collect(),
+            issue_checks: self
+                .checks
+                .iter()
+                .filter(|c| {
+                    ISSUE_CHECK_KEYWORDS
+                        .iter()
+                        .any(|&keyword| c.name.contains(keyword))
+                })
+                .collect(),
         }
     }
 
@@ -357,6 +452,7 @@ mod tests {
             status: CheckStatus::Ok,
             message: "Everything is fine".to_string(),
             fix: None,
+            remedy: None,
         });
         assert_eq!(doctor.get_exit_code(), 0);
 
@@ -366,6 +462,7 @@ mod tests {
             status: CheckStatus::Warning,
             message: "Something might be wrong".to_string(),
             fix: Some("Consider fixing this".to_string()),
+            remedy: None,
         });
         assert_eq!(doctor.get_exit_code(), 1);
 
@@ -375,10 +472,36 @@ mod tests {
             status: CheckStatus::Error,
             message: "Something is definitely wrong".to_string(),
             fix: Some("You must fix this".to_string()),
+            remedy: None,
         });
         assert_eq!(doctor.get_exit_code(), 2);
     }
 
+    #[test]
+    fn test_remedies_of_failed_checks() {
+        let mut doctor = Doctor::new();
+        let runs = Remedy::CreateDirectory(std::path::PathBuf::from("/test/runs"));
+        for status in [CheckStatus::Warning, CheckStatus::Error] {
+            doctor.checks.push(
+                Check::builder("Workflow run storage", status)
+                    .with_remedy(runs.clone())
+                    .build(),
+            );
+        }
+        doctor.checks.push(
+            Check::builder("Local workflows directory", CheckStatus::Ok)
+                .with_remedy(Remedy::CreateDirectory(std::path::PathBuf::from(
+                    "/test/workflows",
+                )))
+                .build(),
+        );
+        doctor
+            .checks
+            .push(Check::builder("Binary Name", CheckStatus::Warning).build());
+
+        assert_eq!(doctor.remedies(), vec![runs]);
+    }
+
     #[test]
     fn test_run_diagnostics() {
         let mut doctor = Doctor::new();
@@ -428,6 +551,7 @@ mod tests {
             status: CheckStatus::Warning,
             message: "Test warning message".to_string(),
             fix: Some("Test fix".to_string()),
+            remedy: None,
         });
 
         let check_groups = doctor.group_checks_by_category();
@@ -0,0 +1,159 @@
+//! Remediation actions applied by `doctor --fix`
+
+use super::types::{McpClient, Remedy};
+use super::utils::{get_claude_config_path, MCP_SERVER_NAME};
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+/// Apply a remedy, returning a description of what was done
+pub fn apply(remedy: &Remedy) -> Result<String> {
+    match remedy {
+        Remedy::CreateDirectory(path) => {
+            fs::create_dir_all(path).with_context(|| format!("Failed to create {path:?}"))?;
+            Ok(format!("Created directory {path:?}"))
+        }
+        Remedy::SetPermissions { path, mode } => {
+            set_permissions(path, *mode)?;
+            Ok(format!("Set permissions of {path:?} to {mode:o}"))
+        }
+        Remedy::AddMcpServer(McpClient::ClaudeCode) => {
+            let output = Command::new("claude")
+                .args(["mcp", "add", "--scope", "user", MCP_SERVER_NAME])
+                .arg(server_command())
+                .arg("serve")
+                .output()
+                .context("Failed to run 'claude mcp add'")?;
+            if !output.status.success() {
+                anyhow::bail!(
+                    "'claude mcp add' failed: {}",
+                    String::from_utf8_lossy(&output.stderr).trim()
+                );
+            }
+            Ok("Added swissarmyhammer to the Claude Code MCP servers".to_string())
+        }
+        Remedy::AddMcpServer(McpClient::ClaudeDesktop) => {
+            let config_path = get_claude_config_path();
+            add_claude_desktop_server(&config_path, &server_command())?;
+            Ok(format!("Added swissarmyhammer to {config_path:?}"))
+        }
+    }
+}
+
+/// Command MCP clients start the server with, the running binary when its path is known
+fn server_command() -> String {
+    std::env::current_exe()
+        .map(|exe| exe.display().to_string())
+        .unwrap_or_else(|_| MCP_SERVER_NAME.to_string())
+}
+
+#[cfg(unix)]
+fn set_permissions(path: &Path, mode: u32) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    fs::set_permissions(path, fs::Permissions::from_mode(mode))
+        .with_context(|| format!("Failed to set permissions of {path:?}"))
+}
+
+#[cfg(not(unix))]
+fn set_permissions(path: &Path, _mode: u32) -> Result<()> {
+    anyhow::bail!("Permission bits of {path:?} can only be set on Unix")
+}
+
+/// Add a swissarmyhammer entry to the `mcpServers` of a Claude Desktop configuration
+///
+/// Creates the file when it does not exist and leaves every other setting as it was.
+fn add_claude_desktop_server(config_path: &Path, command: &str) -> Result<()> {
+    let mut config = if config_path.exists() {
+        let content = fs::read_to_string(config_path)
+            .with_context(|| format!("Failed to read {config_path:?}"))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {config_path:?}"))?
+    } else {
+        serde_json::json!({})
+    };
+
+    let Some(settings) = config.as_object_mut() else {
+        anyhow::bail!("{config_path:?} does not hold a JSON object");
+    };
+    let servers = settings
+        .entry("mcpServers")
+        .or_insert_with(|| serde_json::json!({}));
+    let Some(servers) = servers.as_object_mut() else {
+        anyhow::bail!("mcpServers in {config_path:?} is not a JSON object");
+    };
+    servers.insert(
+        MCP_SERVER_NAME.to_string(),
+        serde_json::json!({ "command": command, "args": ["serve"] }),
+    );
+
+    if let Some(parent) = config_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(config_path, serde_json::to_string_pretty(&config)? + "\n")
+        .with_context(|| format!("Failed to write {config_path:?}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_create_directory_remedy() {
+        let temp_dir = TempDir::new().unwrap();
+        let runs = temp_dir.path().join(".swissarmyhammer").join("runs");
+
+        apply(&Remedy::CreateDirectory(runs.clone())).unwrap();
+        assert!(runs.is_dir());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_set_permissions_remedy() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join("workflows");
+        fs::create_dir(&dir).unwrap();
+        fs::set_permissions(&dir, fs::Permissions::from_mode(0o500)).unwrap();
+
+        apply(&Remedy::SetPermissions {
+            path: dir.clone(),
+            mode: 0o700,
+        })
+        .unwrap();
+        let mode = fs::metadata(&dir).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o700);
+    }
+
+    #[test]
+    fn test_add_claude_desktop_server_keeps_other_settings() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("claude_desktop_config.json");
+        fs::write(
+            &config_path,
+            r#"{"theme": "dark", "mcpServers": {"other": {"command": "other"}}}"#,
+        )
+        .unwrap();
+
+        add_claude_desktop_server(&config_path, "/usr/local/bin/swissarmyhammer").unwrap();
+
+        let config: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+        assert_eq!(config["theme"], "dark");
+        assert_eq!(config["mcpServers"]["other"]["command"], "other");
+        assert_eq!(
+            config["mcpServers"]["swissarmyhammer"],
+            serde_json::json!({ "command": "/usr/local/bin/swissarmyhammer", "args": ["serve"] })
+        );
+
+        let missing = temp_dir
+            .path()
+            .join("Claude")
+            .join("claude_desktop_config.json");
+        add_claude_desktop_server(&missing, "swissarmyhammer").unwrap();
+        assert!(missing.exists());
+    }
+}
@@ -156,6 +156,54 @@ impl From<ExitCode> for i32 {
     }
 }
 
+/// MCP client doctor can add swissarmyhammer to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum McpClient {
+    /// Claude Code, configured through `claude mcp add`
+    ClaudeCode,
+    /// Claude Desktop, configured through `claude_desktop_config.json`
+    ClaudeDesktop,
+}
+
+impl std::fmt::Display for McpClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            McpClient::ClaudeCode => write!(f, "Claude Code"),
+            McpClient::ClaudeDesktop => write!(f, "Claude Desktop"),
+        }
+    }
+}
+
+/// Action `doctor --fix` takes to resolve the problem a check found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Remedy {
+    /// Create a missing directory and its parents
+    CreateDirectory(PathBuf),
+    /// Set the Unix permission bits of a file or directory
+    SetPermissions {
+        /// File or directory to change
+        path: PathBuf,
+        /// Permission bits to set, such as 0o755
+        mode: u32,
+    },
+    /// Add swissarmyhammer to the MCP servers of a client
+    AddMcpServer(McpClient),
+}
+
+impl std::fmt::Display for Remedy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Remedy::CreateDirectory(path) => write!(f, "Create directory {path:?}"),
+            Remedy::SetPermissions { path, mode } => {
+                write!(f, "Set permissions of {path:?} to {mode:o}")
+            }
+            Remedy::AddMcpServer(client) => {
+                write!(f, "Add swissarmyhammer to the {client} MCP servers")
+            }
+        }
+    }
+}
+
 /// Result of a single diagnostic check
 #[derive(Debug, Clone)]
 pub struct Check {
@@ -167,6 +215,8 @@ pub struct Check {
     pub message: String,
     /// Optional fix suggestion for warnings or errors
     pub fix: Option<String>,
+    /// Action `doctor --fix` can take to resolve a warning or error
+    pub remedy: Option<Remedy>,
 }
 
 impl Check {
@@ -196,6 +246,7 @@ pub struct CheckBuilder {
     status: CheckStatus,
     message: String,
     fix: Option<String>,
+    remedy: Option<Remedy>,
 }
 
 #[allow(dead_code)]
@@ -207,6 +258,7 @@ impl CheckBuilder {
             status,
             message: String::new(),
             fix: None,
+            remedy: None,
         }
     }
     /// Set the message for this check
@@ -221,6 +273,12 @@ impl CheckBuilder {
         self
     }
 
+    /// Set the action `doctor --fix` takes for this check
+    pub fn with_remedy(mut self, remedy: Remedy) -> Self {
+        self.remedy = Some(remedy);
+        self
+    }
+
     /// Build the Check instance
     pub fn build(self) -> Check {
         Check {
@@ -228,6 +286,7 @@ impl CheckBuilder {
             status: self.status,
             message: self.message,
             fix: self.fix,
+            remedy: self.remedy,
         }
     }
 }
@@ -238,6 +297,7 @@ pub(crate) struct CheckGroups<'a> {
     pub config_checks: Vec<&'a Check>,
     pub prompt_checks: Vec<&'a Check>,
     pub workflow_checks: Vec<&'a Check>,
+    pub issue_checks: Vec<&'a Check>,
 }
 
 /// Count of checks by status
@@ -397,4 +457,27 @@ mod tests {
         assert_eq!(check.message, "Message");
         assert_eq!(check.fix, Some("Fix".to_string()));
     }
+
+    #[test]
+    fn test_check_builder_with_remedy() {
+        let check = Check::builder("Test", CheckStatus::Warning)
+            .with_remedy(Remedy::CreateDirectory(PathBuf::from("/test/runs")))
+            .build();
+        assert_eq!(
+            check.remedy,
+            Some(Remedy::CreateDirectory(PathBuf::from("/test/runs")))
+        );
+        assert_eq!(
+            Remedy::SetPermissions {
+                path: PathBuf::from("/test"),
+                mode: 0o755
+            }
+            .to_string(),
+            "Set permissions of \"/test\" to 755"
+        );
+        assert_eq!(
+            Remedy::AddMcpServer(McpClient::ClaudeDesktop).to_string(),
+            "Add swissarmyhammer to the Claude Desktop MCP servers"
+        );
+    }
 }
@@ -8,6 +8,9 @@ use walkdir::WalkDir;
 /// Directory name for SwissArmyHammer configuration and data
 pub const SWISSARMYHAMMER_DIR: &str = ".swissarmyhammer";
 
+/// Name swissarmyhammer is registered under in MCP client configurations
+pub const MCP_SERVER_NAME: &str = "swissarmyhammer";
+
 /// Count markdown files in a directory
 pub fn count_markdown_files(path: &Path) -> usize {
     WalkDir::new(path)
@@ -204,15 +207,13 @@ pub fn get_workflow_directories() -> Vec<WorkflowDirectoryInfo> {
     dirs
 }
 
-/// Get the Claude Code configuration file path based on the OS
+/// Get the Claude Desktop configuration file path based on the OS
 ///
-/// Note: This function is kept for backward compatibility but is no longer used.
-/// The doctor command now uses `claude mcp list` instead.
+/// Claude Code is checked with `claude mcp list` instead.
 ///
 /// # Returns
 ///
 /// Platform-specific path to claude_desktop_config.json
-#[allow(dead_code)]
 pub fn get_claude_config_path() -> PathBuf {
     #[cfg(target_os = "macos")]
     {
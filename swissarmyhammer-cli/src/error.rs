@@ -40,9 +40,11 @@ impl CliError {
     }
 
     /// Create a CLI error from a SwissArmyHammer error, with proper exit code handling for abort errors
+    ///
+    /// The message is prefixed with the error's code, e.g. `[SAH-1700] Memo not found: ...`.
     #[allow(dead_code)]
     pub fn from_swissarmyhammer_error(error: swissarmyhammer::SwissArmyHammerError) -> Self {
-        let error_msg = error.to_string();
+        let error_msg = format!("[{}] {error}", error.code());
         if Self::is_abort_error(&error_msg) {
             Self {
                 message: format!("Execution aborted: {error_msg}"),
@@ -0,0 +1,388 @@
+//! Prompt library export as a JSON bundle or a static HTML catalog
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::cli::ExportFormat;
+use swissarmyhammer::{Prompt, PromptLibrary, PromptResolver, PromptSource, PromptVisibility};
+
+/// File name of the JSON bundle inside the export directory
+pub const BUNDLE_FILE_NAME: &str = "prompts.json";
+
+/// File name of the HTML catalog inside the export directory
+pub const CATALOG_FILE_NAME: &str = "index.html";
+
+/// The resolved prompt library in a form other tools can load
+///
+/// Each prompt's `name`, `description` and `arguments` match the MCP prompt
+/// listing, with extra fields for the template and catalog metadata.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PromptBundle {
+    /// Version of swissarmyhammer that produced the bundle
+    pub version: String,
+    /// When the bundle was generated
+    pub generated_at: DateTime<Utc>,
+    /// Exported prompts, sorted by name
+    pub prompts: Vec<ExportedPrompt>,
+}
+
+/// A single prompt in the bundle
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportedPrompt {
+    /// Prompt name
+    pub name: String,
+    /// Human readable title from the front matter
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// What the prompt does
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Category used to group the prompt
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    /// Search tags
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Where the winning definition came from (builtin, user, local)
+    pub source: String,
+    /// Template arguments
+    pub arguments: Vec<ExportedArgument>,
+    /// Liquid template
+    pub template: String,
+}
+
+/// A prompt argument in the bundle
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportedArgument {
+    /// Argument name
+    pub name: String,
+    /// What the argument is for
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Whether the argument must be provided
+    pub required: bool,
+    /// Value used when the argument is omitted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+}
+
+impl ExportedPrompt {
+    fn from_prompt(prompt: &Prompt, source: Option<&PromptSource>) -> Self {
+        Self {
+            name: prompt.name.clone(),
+            title: prompt
+                .metadata
+                .get("title")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string()),
+            description: prompt.description.clone(),
+            category: prompt.category.clone(),
+            tags: prompt.tags.clone(),
+            source: source.unwrap_or(&PromptSource::Dynamic).to_string(),
+            arguments: prompt
+                .arguments
+                .iter()
+                .map(|arg| ExportedArgument {
+                    name: arg.name.clone(),
+                    description: arg.description.clone(),
+                    required: arg.required,
+                    default: arg.default.clone(),
+                })
+                .collect(),
+            template: prompt.template.clone(),
+        }
+    }
+}
+
+/// Export the resolved prompt library into `output_dir`
+///
+/// Returns the path of the file that was written.
+pub fn run_export_command(format: ExportFormat, output_dir: PathBuf) -> Result<PathBuf> {
+    let mut library = PromptLibrary::new();
+    let mut resolver = PromptResolver::new();
+    resolver.load_all_prompts(&mut library)?;
+
+    let bundle = build_bundle(&library, &resolver)?;
+    let path = write_export(&bundle, format, &output_dir)?;
+
+    println!(
+        "Exported {} prompts to {}",
+        bundle.prompts.len(),
+        path.display()
+    );
+    Ok(path)
+}
+
+/// Collect every prompt in the library except partial templates and private
+/// prompts
+fn build_bundle(library: &PromptLibrary, resolver: &PromptResolver) -> Result<PromptBundle> {
+    let mut prompts: Vec<ExportedPrompt> = library
+        .list()?
+        .iter()
+        .filter(|prompt| !prompt.is_partial() && prompt.visibility() != PromptVisibility::Private)
+        .map(|prompt| {
+            ExportedPrompt::from_prompt(prompt, resolver.prompt_sources.get(&prompt.name))
+        })
+        .collect();
+    prompts.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(PromptBundle {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        generated_at: Utc::now(),
+        prompts,
+    })
+}
+
+/// Write the bundle in the requested format, creating the directory if needed
+fn write_export(bundle: &PromptBundle, format: ExportFormat, output_dir: &Path) -> Result<PathBuf> {
+    fs::create_dir_all(output_dir)?;
+
+    let (file_name, contents) = match format {
+        ExportFormat::Json => (BUNDLE_FILE_NAME, serde_json::to_string_pretty(bundle)?),
+        ExportFormat::Html => (CATALOG_FILE_NAME, render_catalog(bundle)),
+    };
+
+    let path = output_dir.join(file_name);
+    fs::write(&path, contents)?;
+    Ok(path)
+}
+
+/// Escape text for use in HTML content and attribute values
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Render a description as HTML paragraphs with `inline code` spans
+fn render_description(text: &str) -> String {
+    text.split("\n\n")
+        .map(str::trim)
+        .filter(|paragraph| !paragraph.is_empty())
+        .map(|paragraph| {
+            let html = escape_html(paragraph)
+                .split('`')
+                .enumerate()
+                .map(|(index, part)| {
+                    // Odd parts sit between a pair of backticks
+                    if index % 2 == 1 {
+                        format!("<code>{part}</code>")
+                    } else {
+                        part.to_string()
+                    }
+                })
+                .collect::<String>();
+            format!("<p>{}</p>", html.replace('\n', "<br>\n"))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Render the arguments of a prompt as an HTML table
+fn render_arguments(arguments: &[ExportedArgument]) -> String {
+    if arguments.is_empty() {
+        return "<p class=\"muted\">No arguments.</p>".to_string();
+    }
+
+    let rows = arguments
+        .iter()
+        .map(|arg| {
+            format!(
+                "<tr><td><code>{}</code></td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                escape_html(&arg.name),
+                if arg.required { "yes" } else { "no" },
+                arg.default
+                    .as_deref()
+                    .map(|default| format!("<code>{}</code>", escape_html(default)))
+                    .unwrap_or_default(),
+                escape_html(arg.description.as_deref().unwrap_or("")),
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    format!(
+        "<table>\n<thead><tr><th>Argument</th><th>Required</th><th>Default</th><th>Description</th></tr></thead>\n<tbody>\n{rows}\n</tbody>\n</table>"
+    )
+}
+
+/// Render the whole bundle as a single page HTML catalog
+fn render_catalog(bundle: &PromptBundle) -> String {
+    let index = bundle
+        .prompts
+        .iter()
+        .map(|prompt| {
+            format!(
+                "<li><a href=\"#{name}\">{name}</a></li>",
+                name = escape_html(&prompt.name)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let sections = bundle
+        .prompts
+        .iter()
+        .map(|prompt| {
+            let name = escape_html(&prompt.name);
+            let heading = match &prompt.title {
+                Some(title) => format!("{name} <small>{}</small>", escape_html(title)),
+                None => name.clone(),
+            };
+
+            let mut labels = vec![escape_html(&prompt.source)];
+            labels.extend(prompt.category.iter().map(|c| escape_html(c)));
+            labels.extend(prompt.tags.iter().map(|t| format!("#{}", escape_html(t))));
+            let labels = labels
+                .iter()
+                .map(|label| format!("<span class=\"label\">{label}</span>"))
+                .collect::<Vec<_>>()
+                .join(" ");
+
+            let description = prompt
+                .description
+                .as_deref()
+                .map(render_description)
+                .unwrap_or_else(|| "<p class=\"muted\">No description.</p>".to_string());
+
+            format!(
+                "<section id=\"{name}\">\n<h2>{heading}</h2>\n<div>{labels}</div>\n{description}\n{arguments}\n<details><summary>Template</summary><pre>{template}</pre></details>\n</section>",
+                arguments = render_arguments(&prompt.arguments),
+                template = escape_html(&prompt.template),
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>Prompt Catalog</title>
+<style>
+body {{ font-family: system-ui, sans-serif; max-width: 60rem; margin: 2rem auto; padding: 0 1rem; line-height: 1.5; }}
+section {{ border-top: 1px solid #ddd; padding: 1rem 0; }}
+h2 small {{ color: #666; font-weight: normal; }}
+table {{ border-collapse: collapse; width: 100%; }}
+th, td {{ border: 1px solid #ddd; padding: 0.3rem 0.5rem; text-align: left; vertical-align: top; }}
+pre {{ background: #f6f6f6; padding: 0.75rem; overflow-x: auto; white-space: pre-wrap; }}
+.label {{ display: inline-block; background: #eef; border-radius: 3px; padding: 0 0.4rem; font-size: 0.85em; }}
+.muted {{ color: #666; }}
+</style>
+</head>
+<body>
+<h1>Prompt Catalog</h1>
+<p class="muted">{count} prompts, generated by swissarmyhammer {version} on {generated}</p>
+<ul>
+{index}
+</ul>
+{sections}
+</body>
+</html>
+"#,
+        count = bundle.prompts.len(),
+        version = escape_html(&bundle.version),
+        generated = bundle.generated_at.format("%Y-%m-%d %H:%M UTC"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swissarmyhammer::ArgumentSpec;
+    use tempfile::TempDir;
+
+    fn test_bundle() -> PromptBundle {
+        let mut library = PromptLibrary::new();
+        library
+            .add(
+                Prompt::new("review", "Review {{file}}")
+                    .with_description("Review `code` for <bugs>")
+                    .add_argument(ArgumentSpec {
+                        name: "file".to_string(),
+                        description: Some("File to review".to_string()),
+                        required: true,
+                        default: None,
+                        type_hint: None,
+                        choices: None,
+                        completion: None,
+                        source: None,
+                    }),
+            )
+            .unwrap();
+        library
+            .add(Prompt::new("header", "{% partial %}\n# Header"))
+            .unwrap();
+        let mut internal = Prompt::new("internal", "Internal notes");
+        internal
+            .metadata
+            .insert("visibility".to_string(), "private".into());
+        library.add(internal).unwrap();
+
+        build_bundle(&library, &PromptResolver::new()).unwrap()
+    }
+
+    #[test]
+    fn test_bundle_excludes_partials_and_private_prompts() {
+        let bundle = test_bundle();
+
+        assert_eq!(bundle.prompts.len(), 1);
+        let prompt = &bundle.prompts[0];
+        assert_eq!(prompt.name, "review");
+        assert_eq!(prompt.source, "dynamic");
+        assert_eq!(prompt.arguments.len(), 1);
+        assert!(prompt.arguments[0].required);
+    }
+
+    #[test]
+    fn test_write_json_bundle() {
+        let temp_dir = TempDir::new().unwrap();
+        let bundle = test_bundle();
+
+        let path = write_export(&bundle, ExportFormat::Json, temp_dir.path()).unwrap();
+        assert_eq!(path, temp_dir.path().join(BUNDLE_FILE_NAME));
+
+        let loaded: PromptBundle =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(loaded.prompts.len(), 1);
+        assert_eq!(loaded.prompts[0].template, "Review {{file}}");
+    }
+
+    #[test]
+    fn test_write_html_catalog() {
+        let temp_dir = TempDir::new().unwrap();
+        let output_dir = temp_dir.path().join("site");
+        let bundle = test_bundle();
+
+        let path = write_export(&bundle, ExportFormat::Html, &output_dir).unwrap();
+        assert_eq!(path, output_dir.join(CATALOG_FILE_NAME));
+
+        let html = fs::read_to_string(&path).unwrap();
+        assert!(html.contains("<section id=\"review\">"));
+        assert!(html.contains("<p>Review <code>code</code> for &lt;bugs&gt;</p>"));
+        assert!(html.contains("<td><code>file</code></td><td>yes</td>"));
+        assert!(!html.contains("id=\"header\""));
+    }
+
+    #[test]
+    fn test_escape_html() {
+        assert_eq!(
+            escape_html("<a href=\"x\">&'</a>"),
+            "&lt;a href=&quot;x&quot;&gt;&amp;&#39;&lt;/a&gt;"
+        );
+    }
+}
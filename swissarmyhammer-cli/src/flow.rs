@@ -3,18 +3,29 @@
 use crate::cli::{
     FlowSubcommand, OutputFormat, PromptSource, PromptSourceArg, VisualizationFormat,
 };
+use crate::debugger::parse_assignments;
+use crate::progress::FlowProgressDisplay;
 use colored::*;
 use is_terminal::IsTerminal;
 use std::collections::{HashMap, HashSet};
 use std::future;
 use std::io::{self, Write};
+use std::path::Path;
+use std::sync::Arc;
 use std::time::Duration;
+use swissarmyhammer::config::Config;
+use swissarmyhammer::cost::CostRates;
+use swissarmyhammer::events::{event_bus, Event};
 use swissarmyhammer::workflow::{
-    ExecutionVisualizer, MemoryWorkflowStorage, StateId, TransitionKey, Workflow, WorkflowExecutor,
-    WorkflowName, WorkflowResolver, WorkflowRunId, WorkflowRunStatus, WorkflowStorage,
-    WorkflowStorageBackend,
+    record_attempt, record_failure, recorded_failure, ExecutionVisualizer, FailureKind,
+    HookDetails, MemoryWorkflowStorage, RunArtifacts, RunCancellation, RunComparison,
+    RunProgressReporter, RunRetryPolicy, StateId, TransitionKey, Workflow, WorkflowEstimate,
+    WorkflowExecutor, WorkflowHookEvent, WorkflowName, WorkflowResolver, WorkflowRunId,
+    WorkflowRunStatus, WorkflowSnapshot, WorkflowStorage, WorkflowStorageBackend,
+    ATTEMPT_METADATA_KEY, DEFAULT_SNAPSHOT_DIR, FAILURE_MESSAGE_METADATA_KEY,
+    RETRY_OF_METADATA_KEY,
 };
-use swissarmyhammer::{Result, SwissArmyHammerError};
+use swissarmyhammer::{PromptLibrary, PromptResolver, Result, SwissArmyHammerError};
 use tokio::signal;
 use tokio::time::timeout;
 
@@ -23,6 +34,7 @@ const DEFAULT_TEST_MODE_TIMEOUT_SECS: u64 = 60;
 
 /// Main entry point for flow command
 pub async fn run_flow_command(subcommand: FlowSubcommand) -> Result<()> {
+    let progress = shows_progress(&subcommand);
     match subcommand {
         FlowSubcommand::Run {
             workflow,
@@ -31,8 +43,12 @@ pub async fn run_flow_command(subcommand: FlowSubcommand) -> Result<()> {
             interactive,
             dry_run,
             test,
+            update_snapshots,
             timeout: timeout_str,
             quiet,
+            no_progress: _,
+            retries,
+            retry_backoff,
         } => {
             run_workflow_command(WorkflowCommandConfig {
                 workflow_name: workflow,
@@ -41,8 +57,13 @@ pub async fn run_flow_command(subcommand: FlowSubcommand) -> Result<()> {
                 interactive,
                 dry_run,
                 test_mode: test,
+                update_snapshots,
+                check_snapshots: false,
                 timeout_str,
                 quiet,
+                progress,
+                retries,
+                retry_backoff,
             })
             .await
         }
@@ -51,7 +72,8 @@ pub async fn run_flow_command(subcommand: FlowSubcommand) -> Result<()> {
             interactive,
             timeout: timeout_str,
             quiet,
-        } => resume_workflow_command(run_id, interactive, timeout_str, quiet).await,
+            no_progress: _,
+        } => resume_workflow_command(run_id, interactive, timeout_str, quiet, progress).await,
         FlowSubcommand::List {
             format,
             verbose,
@@ -62,6 +84,7 @@ pub async fn run_flow_command(subcommand: FlowSubcommand) -> Result<()> {
             format,
             watch,
         } => status_workflow_command(run_id, format, watch).await,
+        FlowSubcommand::Cancel { run_id } => cancel_workflow_command(run_id),
         FlowSubcommand::Logs {
             run_id,
             follow,
@@ -74,6 +97,18 @@ pub async fn run_flow_command(subcommand: FlowSubcommand) -> Result<()> {
             format,
             global,
         } => metrics_workflow_command(run_id, workflow, format, global).await,
+        FlowSubcommand::Compare {
+            run_a,
+            run_b,
+            format,
+        } => compare_workflow_runs_command(run_a, run_b, format).await,
+        FlowSubcommand::Artifacts { run_id, format } => artifacts_workflow_command(run_id, format),
+        FlowSubcommand::Estimate {
+            workflow,
+            vars,
+            set,
+            format,
+        } => estimate_workflow_command(workflow, vars, set, format),
         FlowSubcommand::Visualize {
             run_id,
             format,
@@ -87,6 +122,7 @@ pub async fn run_flow_command(subcommand: FlowSubcommand) -> Result<()> {
             vars,
             set,
             interactive,
+            snapshots,
             timeout: timeout_str,
             quiet,
         } => {
@@ -98,11 +134,29 @@ pub async fn run_flow_command(subcommand: FlowSubcommand) -> Result<()> {
                 interactive,
                 dry_run: false,
                 test_mode: true,
+                update_snapshots: false,
+                check_snapshots: snapshots,
                 timeout_str,
                 quiet,
+                progress: false,
+                retries: None,
+                retry_backoff: None,
             })
             .await
         }
+        FlowSubcommand::Debug {
+            workflow,
+            vars,
+            set,
+            breakpoints,
+        } => crate::debugger::run_debug_command(workflow, vars, set, breakpoints).await,
+        FlowSubcommand::New {
+            name,
+            template,
+            args,
+            output,
+            force,
+        } => crate::scaffold::run_new_command(name, template, args, output, force),
     }
 }
 
@@ -114,8 +168,38 @@ struct WorkflowCommandConfig {
     interactive: bool,
     dry_run: bool,
     test_mode: bool,
+    update_snapshots: bool,
+    check_snapshots: bool,
     timeout_str: Option<String>,
     quiet: bool,
+    progress: bool,
+    /// Retries of transient failures, overriding the configuration
+    retries: Option<u32>,
+    /// Wait before the first retry, overriding the configuration
+    retry_backoff: Option<String>,
+}
+
+/// Whether a flow command draws a live progress display
+///
+/// Progress is drawn by `flow run` and `flow resume` when stderr is a
+/// terminal, unless `--no-progress` is given or the run is interactive.
+pub fn shows_progress(subcommand: &FlowSubcommand) -> bool {
+    let enabled = match subcommand {
+        FlowSubcommand::Run {
+            interactive,
+            dry_run,
+            test,
+            no_progress,
+            ..
+        } => !(*interactive || *dry_run || *test || *no_progress),
+        FlowSubcommand::Resume {
+            interactive,
+            no_progress,
+            ..
+        } => !(*interactive || *no_progress),
+        _ => false,
+    };
+    enabled && io::stderr().is_terminal()
 }
 
 /// Execute a workflow
@@ -158,6 +242,15 @@ async fn run_workflow_command(config: WorkflowCommandConfig) -> Result<()> {
         }
     }
 
+    // Fill in the defaults of the variables the workflow declares
+    if let Err(missing) = workflow.apply_variable_defaults(&mut set_variables) {
+        return Err(SwissArmyHammerError::Other(format!(
+            "Workflow '{}' requires variables that were not set: {}. Set them with --set name=value",
+            workflow.name,
+            missing.join(", ")
+        )));
+    }
+
     // Parse timeout
     let timeout_duration = if let Some(timeout_str) = config.timeout_str {
         Some(parse_duration(&timeout_str)?)
@@ -187,6 +280,16 @@ async fn run_workflow_command(config: WorkflowCommandConfig) -> Result<()> {
             );
         }
 
+        if config.update_snapshots {
+            let snapshot = render_snapshot(&workflow, &variables, &set_variables)?;
+            let directory = snapshot.save(Path::new(DEFAULT_SNAPSHOT_DIR))?;
+            println!(
+                "\n📸 Recorded {} prompt snapshot(s) in {}",
+                snapshot.prompts.len(),
+                directory.display()
+            );
+        }
+
         return Ok(());
     }
 
@@ -199,6 +302,12 @@ async fn run_workflow_command(config: WorkflowCommandConfig) -> Result<()> {
             println!("⏱️  Timeout: {timeout:?}");
         }
 
+        let snapshot = if config.check_snapshots {
+            Some(render_snapshot(&workflow, &variables, &set_variables)?)
+        } else {
+            None
+        };
+
         // Execute in test mode with coverage tracking
         let coverage =
             execute_workflow_test_mode(workflow, variables, set_variables, timeout_duration)
@@ -258,60 +367,171 @@ async fn run_workflow_command(config: WorkflowCommandConfig) -> Result<()> {
             println!("✅ Full transition coverage achieved!");
         }
 
+        if let Some(snapshot) = snapshot {
+            check_snapshot(&snapshot)?;
+        }
+
         return Ok(());
     }
 
-    tracing::info!("🚀 Starting workflow: {}", workflow.name);
-
-    // Create executor
-    let mut executor = WorkflowExecutor::new();
-
-    // Create workflow run
-    let mut run = executor.start_workflow(workflow.clone()).map_err(|e| {
-        SwissArmyHammerError::Other(format!(
-            "Failed to start workflow '{}': {}",
-            workflow.name, e
-        ))
-    })?;
-
-    // Set initial variables
-    run.context.extend(variables);
+    let mut policy = RunRetryPolicy::from_config(Config::global());
+    if let Some(retries) = config.retries {
+        policy.max_retries = retries;
+    }
+    if let Some(backoff) = &config.retry_backoff {
+        policy.backoff = parse_duration(backoff)?;
+    }
 
+    // The context every attempt starts from
+    let mut context = variables;
     // Store set variables in context for liquid template rendering
     if !set_variables.is_empty() {
-        run.context.insert(
+        context.insert(
             "_template_vars".to_string(),
             serde_json::to_value(set_variables)?,
         );
     }
-
     // Set quiet mode in context for actions to use
     if config.quiet {
-        run.context
-            .insert("_quiet".to_string(), serde_json::Value::Bool(true));
+        context.insert("_quiet".to_string(), serde_json::Value::Bool(true));
     }
-
     // Set timeout in context for actions to use
     if let Some(timeout_duration) = timeout_duration {
-        run.context.insert(
+        context.insert(
             "_timeout_secs".to_string(),
             serde_json::Value::Number(serde_json::Number::from(timeout_duration.as_secs())),
         );
     }
 
-    // Setup signal handling for graceful shutdown
-    let (shutdown_tx, mut shutdown_rx) = tokio::sync::mpsc::channel(1);
-    let shutdown_tx_clone = shutdown_tx.clone();
+    let options = RunOptions {
+        interactive: config.interactive,
+        progress: config.progress,
+        timeout: timeout_duration,
+    };
+    let mut attempt = 1;
+    let mut retry_of = None;
+    loop {
+        let run = execute_run_attempt(
+            &mut storage,
+            &workflow,
+            &context,
+            options,
+            attempt,
+            retry_of,
+        )
+        .await?;
+        let Some(kind) = recorded_failure(&run) else {
+            return Ok(());
+        };
+        if !policy.should_retry(kind, attempt - 1) {
+            if kind.is_retryable() && policy.max_retries > 0 {
+                tracing::error!(
+                    "❌ Giving up on workflow '{}' after {} attempts",
+                    workflow.name,
+                    attempt
+                );
+            }
+            return Ok(());
+        }
+
+        let backoff = policy.backoff_for(attempt);
+        tracing::warn!(
+            "🔁 Run {} failed with a {} error, retrying in {:?} (retry {} of {})",
+            workflow_run_id_to_string(&run.id),
+            kind,
+            backoff,
+            attempt,
+            policy.max_retries
+        );
+        tokio::select! {
+            _ = tokio::time::sleep(backoff) => {}
+            _ = signal::ctrl_c() => {
+                tracing::info!("Retry cancelled by user");
+                return Ok(());
+            }
+        }
+        retry_of = Some(run.id);
+        attempt += 1;
+    }
+}
+
+/// Run a workflow queued for an issue, with the issue's name in `issue_name`
+///
+/// The run goes through the same path as `flow run`, once and without a
+/// progress display.
+pub async fn run_issue_workflow(
+    storage: &mut WorkflowStorage,
+    workflow: &Workflow,
+    issue: &str,
+) -> Result<swissarmyhammer::workflow::WorkflowRun> {
+    let context = HashMap::from([(
+        "issue_name".to_string(),
+        serde_json::Value::String(issue.to_string()),
+    )]);
+    let options = RunOptions {
+        interactive: false,
+        progress: false,
+        timeout: None,
+    };
+    execute_run_attempt(storage, workflow, &context, options, 1, None).await
+}
 
-    tokio::spawn(async move {
-        signal::ctrl_c().await.expect("Failed to listen for Ctrl+C");
-        let _ = shutdown_tx_clone.send(()).await;
-    });
+/// Options shared by every attempt of a workflow run
+#[derive(Debug, Clone, Copy)]
+struct RunOptions {
+    interactive: bool,
+    progress: bool,
+    timeout: Option<Duration>,
+}
+
+/// Run a workflow once and store the run, recording why it failed if it did
+async fn execute_run_attempt(
+    storage: &mut WorkflowStorage,
+    workflow: &Workflow,
+    context: &HashMap<String, serde_json::Value>,
+    options: RunOptions,
+    attempt: u32,
+    retry_of: Option<WorkflowRunId>,
+) -> Result<swissarmyhammer::workflow::WorkflowRun> {
+    tracing::info!("🚀 Starting workflow: {}", workflow.name);
+
+    // Create executor
+    let mut executor = WorkflowExecutor::new();
+    let progress = options
+        .progress
+        .then(|| Arc::new(FlowProgressDisplay::new(workflow.name.as_str())));
+    if let Some(progress) = &progress {
+        executor.add_progress(progress.clone());
+    }
+    let reporter = Arc::new(RunProgressReporter::default());
+    executor.add_progress(reporter.clone());
+
+    // Create workflow run
+    let mut run = executor.start_workflow(workflow.clone()).map_err(|e| {
+        SwissArmyHammerError::Other(format!(
+            "Failed to start workflow '{}': {}",
+            workflow.name, e
+        ))
+    })?;
+    run.context.extend(context.clone());
+    record_attempt(&mut run, attempt, retry_of);
+    tracing::info!(
+        "🆔 Run ID: {} (stop it with flow cancel)",
+        workflow_run_id_to_string(&run.id)
+    );
+
+    // Run the start hook now that the run context is populated
+    executor
+        .run_hook(&run, WorkflowHookEvent::Start, HookDetails::default())
+        .await;
+
+    let mut shutdown_rx = watch_for_interrupt(run.id);
 
     // Execute workflow with timeout and signal handling
-    let execution_result = if let Some(timeout_duration) = timeout_duration {
+    let mut interrupted = false;
+    let execution_result = if let Some(timeout_duration) = options.timeout {
         tokio::select! {
-            result = execute_workflow_with_progress(&mut executor, &mut run, config.interactive) => result,
+            result = execute_workflow_with_progress(&mut executor, &mut run, options.interactive) => result,
             _ = timeout(timeout_duration, future::pending::<()>()) => {
                 tracing::warn!("Workflow execution timed out");
                 run.status = WorkflowRunStatus::Cancelled;
@@ -320,22 +540,54 @@ async fn run_workflow_command(config: WorkflowCommandConfig) -> Result<()> {
             _ = shutdown_rx.recv() => {
                 tracing::info!("Workflow execution interrupted by user");
                 run.status = WorkflowRunStatus::Cancelled;
+                interrupted = true;
                 Ok(())
             }
         }
     } else {
         tokio::select! {
-            result = execute_workflow_with_progress(&mut executor, &mut run, config.interactive) => result,
+            result = execute_workflow_with_progress(&mut executor, &mut run, options.interactive) => result,
             _ = shutdown_rx.recv() => {
                 tracing::info!("Workflow execution interrupted by user");
                 run.status = WorkflowRunStatus::Cancelled;
+                interrupted = true;
                 Ok(())
             }
         }
     };
 
+    if let Some(progress) = &progress {
+        progress.finish(&run);
+    }
+
+    match &execution_result {
+        Err(e) => {
+            let message = e.to_string();
+            run.fail();
+            record_failure(&mut run, FailureKind::of_message(&message), &message);
+        }
+        Ok(()) if run.status == WorkflowRunStatus::Failed => {
+            let message = run
+                .context
+                .get("result")
+                .and_then(|result| result.as_str())
+                .unwrap_or("Workflow failed")
+                .to_string();
+            record_failure(&mut run, FailureKind::of_message(&message), &message);
+        }
+        Ok(()) if interrupted => {
+            record_failure(&mut run, FailureKind::UserAbort, "Interrupted by user");
+        }
+        Ok(()) => {}
+    }
+
     // Store the run
     storage.store_run(&run)?;
+    reporter.finish(&run);
+
+    if let Some(event) = Event::run_finished(&run, run.status) {
+        event_bus().publish(event);
+    }
 
     match execution_result {
         Ok(_) => match run.status {
@@ -358,11 +610,56 @@ async fn run_workflow_command(config: WorkflowCommandConfig) -> Result<()> {
         },
         Err(e) => {
             tracing::error!("❌ Workflow execution failed: {}", e);
-            run.fail();
-            storage.store_run(&run)?;
+            tracing::info!("🆔 Run ID: {}", workflow_run_id_to_string(&run.id));
         }
     }
 
+    Ok(run)
+}
+
+/// Cancel a run cleanly on the first Ctrl+C, and stop waiting for it on the
+/// second
+///
+/// The first Ctrl+C asks the run to stop like `flow cancel`, so the executor
+/// kills the processes of the running action and runs the cancel hook. The
+/// returned receiver fires on the second Ctrl+C, for a run that does not stop.
+fn watch_for_interrupt(run_id: WorkflowRunId) -> tokio::sync::mpsc::Receiver<()> {
+    let (shutdown_tx, shutdown_rx) = tokio::sync::mpsc::channel(1);
+
+    tokio::spawn(async move {
+        signal::ctrl_c().await.expect("Failed to listen for Ctrl+C");
+        match RunCancellation::default().request(&run_id) {
+            Ok(()) => {
+                tracing::info!("Cancelling workflow run, press Ctrl+C again to stop now");
+                signal::ctrl_c().await.expect("Failed to listen for Ctrl+C");
+            }
+            Err(e) => tracing::warn!("Failed to cancel workflow run cleanly: {}", e),
+        }
+        RunCancellation::default().clear(&run_id);
+        let _ = shutdown_tx.send(()).await;
+    });
+
+    shutdown_rx
+}
+
+/// Ask an in-flight workflow run to stop
+fn cancel_workflow_command(run_id: String) -> Result<()> {
+    let run_id_typed = parse_workflow_run_id(&run_id)?;
+
+    // Runs are stored when they stop, so a stored run that finished is not running
+    if let Ok(run) = WorkflowStorage::file_system()?.get_run(&run_id_typed) {
+        if matches!(
+            run.status,
+            WorkflowRunStatus::Completed | WorkflowRunStatus::Failed
+        ) {
+            println!("❌ Run {run_id} already finished as {:?}", run.status);
+            return Ok(());
+        }
+    }
+
+    RunCancellation::default().request(&run_id_typed)?;
+    println!("🚫 Cancellation requested for run {run_id}");
+    println!("The run stops within a moment if it is running from this directory.");
     Ok(())
 }
 
@@ -372,6 +669,7 @@ async fn resume_workflow_command(
     interactive: bool,
     timeout_str: Option<String>,
     quiet: bool,
+    progress: bool,
 ) -> Result<()> {
     let mut storage = WorkflowStorage::file_system()?;
 
@@ -399,6 +697,9 @@ async fn resume_workflow_command(
         None
     };
 
+    // Resuming overrides a cancellation requested while the run was not running
+    RunCancellation::default().clear(&run.id);
+
     println!("🔄 Resuming workflow: {}", run.workflow.name);
     println!("🔄 From state: {}", run.current_state);
 
@@ -410,15 +711,14 @@ async fn resume_workflow_command(
 
     // Create executor
     let mut executor = WorkflowExecutor::new();
+    let progress = progress.then(|| Arc::new(FlowProgressDisplay::new(run.workflow.name.as_str())));
+    if let Some(progress) = &progress {
+        executor.add_progress(progress.clone());
+    }
+    let reporter = Arc::new(RunProgressReporter::default());
+    executor.add_progress(reporter.clone());
 
-    // Setup signal handling for graceful shutdown
-    let (shutdown_tx, mut shutdown_rx) = tokio::sync::mpsc::channel(1);
-    let shutdown_tx_clone = shutdown_tx.clone();
-
-    tokio::spawn(async move {
-        signal::ctrl_c().await.expect("Failed to listen for Ctrl+C");
-        let _ = shutdown_tx_clone.send(()).await;
-    });
+    let mut shutdown_rx = watch_for_interrupt(run.id);
 
     // Resume workflow execution
     let execution_result = if let Some(timeout_duration) = timeout_duration {
@@ -446,6 +746,10 @@ async fn resume_workflow_command(
         }
     };
 
+    if let Some(progress) = &progress {
+        progress.finish(&run);
+    }
+
     // Store the updated run
     storage.store_run(&run)?;
 
@@ -470,6 +774,11 @@ async fn resume_workflow_command(
             storage.store_run(&run)?;
         }
     }
+    reporter.finish(&run);
+
+    if let Some(event) = Event::run_finished(&run, run.status) {
+        event_bus().publish(event);
+    }
 
     Ok(())
 }
@@ -560,27 +869,21 @@ fn display_workflows_to_writer<W: Write>(
         let name = workflow.name.as_str();
         let description = &workflow.description;
 
-        // Extract title from metadata, or use a formatted version of the name
-        let title = workflow
-            .metadata
-            .get("title")
-            .map(|s| s.to_string())
-            .unwrap_or_else(|| {
-                // Fallback: convert workflow name to a readable title
-                name.replace(['-', '_'], " ")
-                    .split_whitespace()
-                    .map(|word| {
-                        let mut chars = word.chars();
-                        match chars.next() {
-                            None => String::new(),
-                            Some(first) => {
-                                first.to_uppercase().collect::<String>() + chars.as_str()
-                            }
-                        }
-                    })
-                    .collect::<Vec<_>>()
-                    .join(" ")
-            });
+        // Use the title from the front matter, or a formatted version of the name
+        let title = workflow.title.clone().unwrap_or_else(|| {
+            // Fallback: convert workflow name to a readable title
+            name.replace(['-', '_'], " ")
+                .split_whitespace()
+                .map(|word| {
+                    let mut chars = word.chars();
+                    match chars.next() {
+                        None => String::new(),
+                        Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        });
 
         // Color code based on source, matching prompt list
         let first_line = if is_tty {
@@ -621,6 +924,29 @@ fn display_workflows_to_writer<W: Write>(
                 terminal_count,
                 workflow.transitions.len()
             )?;
+            if !workflow.tags.is_empty() {
+                writeln!(writer, "  Tags: {}", workflow.tags.join(", "))?;
+            }
+            if !workflow.variables.is_empty() {
+                writeln!(writer, "  Variables:")?;
+            }
+            for variable in &workflow.variables {
+                let mut line = format!("    {}", variable.name);
+                if variable.required {
+                    line.push_str(" (required)");
+                }
+                if let Some(default) = &variable.default {
+                    let default = match default {
+                        serde_json::Value::String(default) => default.clone(),
+                        default => default.to_string(),
+                    };
+                    line.push_str(&format!(" = {default}"));
+                }
+                if let Some(description) = &variable.description {
+                    line.push_str(&format!(" - {description}"));
+                }
+                writeln!(writer, "{line}")?;
+            }
         }
 
         writeln!(writer)?; // Empty line between entries
@@ -731,6 +1057,108 @@ async fn logs_workflow_command(
     Ok(())
 }
 
+/// Compare two workflow runs side by side
+async fn compare_workflow_runs_command(
+    run_a: String,
+    run_b: String,
+    format: OutputFormat,
+) -> Result<()> {
+    let storage = WorkflowStorage::file_system()?;
+
+    let run_a = storage.get_run(&parse_workflow_run_id(&run_a)?)?;
+    let run_b = storage.get_run(&parse_workflow_run_id(&run_b)?)?;
+    let comparison = RunComparison::new(&run_a, &run_b);
+
+    match format {
+        OutputFormat::Table => {
+            println!("🔍 Comparing workflow runs");
+            println!();
+            println!("{comparison}");
+        }
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&comparison)?);
+        }
+        OutputFormat::Yaml => {
+            println!("{}", serde_yaml::to_string(&comparison)?);
+        }
+    }
+
+    Ok(())
+}
+
+/// List the artifacts a workflow run saved
+fn artifacts_workflow_command(run_id: String, format: OutputFormat) -> Result<()> {
+    let run_id = parse_workflow_run_id(&run_id)?;
+    let store = RunArtifacts::default();
+    let artifacts = store.list(&run_id)?;
+
+    match format {
+        OutputFormat::Table => {
+            if artifacts.is_empty() {
+                println!("Run {run_id} saved no artifacts");
+                return Ok(());
+            }
+            println!("📦 Artifacts of run {run_id}");
+            println!();
+            let dir = store.artifacts_dir(&run_id);
+            for artifact in &artifacts {
+                println!(
+                    "  {:<8} {:>10}  {:<16} {}",
+                    artifact.kind.to_string(),
+                    format!("{} B", artifact.size),
+                    artifact.state.as_deref().unwrap_or("-"),
+                    dir.join(&artifact.name).display()
+                );
+            }
+        }
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&artifacts)?);
+        }
+        OutputFormat::Yaml => {
+            println!("{}", serde_yaml::to_string(&artifacts)?);
+        }
+    }
+
+    Ok(())
+}
+
+/// Forecast the cost of a workflow from the prompts its paths render
+fn estimate_workflow_command(
+    workflow_name: String,
+    vars: Vec<String>,
+    set: Vec<String>,
+    format: OutputFormat,
+) -> Result<()> {
+    let storage = WorkflowStorage::file_system()?;
+    let workflow = storage.get_workflow(&WorkflowName::new(&workflow_name))?;
+
+    let mut context = parse_assignments(&vars, "--var")?;
+    let set_variables = parse_assignments(&set, "--set")?;
+    if !set_variables.is_empty() {
+        context.insert(
+            "_template_vars".to_string(),
+            serde_json::to_value(set_variables)?,
+        );
+    }
+
+    let mut library = PromptLibrary::new();
+    PromptResolver::new().load_all_prompts(&mut library)?;
+    let estimate = WorkflowEstimate::new(
+        &workflow,
+        &context,
+        &library,
+        &storage,
+        CostRates::from_config(Config::global()),
+    );
+
+    match format {
+        OutputFormat::Table => print!("{estimate}"),
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&estimate)?),
+        OutputFormat::Yaml => println!("{}", serde_yaml::to_string(&estimate)?),
+    }
+    Ok(())
+}
+
 /// Execute workflow with progress display
 async fn execute_workflow_with_progress(
     executor: &mut WorkflowExecutor,
@@ -805,6 +1233,22 @@ fn print_run_status(
             }
             println!("📈 History: {} transitions", run.history.len());
             println!("🔧 Variables: {} items", run.context.len());
+            if let Some(attempt) = run.metadata.get(ATTEMPT_METADATA_KEY) {
+                match run.metadata.get(RETRY_OF_METADATA_KEY) {
+                    Some(retry_of) => println!("🔁 Attempt: {attempt} (retry of {retry_of})"),
+                    None => println!("🔁 Attempt: {attempt}"),
+                }
+            }
+            if let Some(kind) = recorded_failure(run) {
+                println!(
+                    "💥 Failure: {} - {}",
+                    kind,
+                    run.metadata
+                        .get(FAILURE_MESSAGE_METADATA_KEY)
+                        .map(String::as_str)
+                        .unwrap_or_default()
+                );
+            }
         }
         OutputFormat::Json => {
             let json_output = serde_json::to_string_pretty(&run)?;
@@ -917,6 +1361,52 @@ fn workflow_run_id_to_string(id: &WorkflowRunId) -> String {
     id.to_string()
 }
 
+/// Render the prompts of a workflow with the variables given on the command line
+fn render_snapshot(
+    workflow: &Workflow,
+    variables: &HashMap<String, serde_json::Value>,
+    set_variables: &HashMap<String, serde_json::Value>,
+) -> Result<WorkflowSnapshot> {
+    let mut context = variables.clone();
+    if !set_variables.is_empty() {
+        context.insert(
+            "_template_vars".to_string(),
+            serde_json::to_value(set_variables)?,
+        );
+    }
+
+    let mut library = PromptLibrary::new();
+    PromptResolver::new().load_all_prompts(&mut library)?;
+    WorkflowSnapshot::render(workflow, &context, &library)
+}
+
+/// Fail when the rendered prompts differ from the recorded snapshot
+fn check_snapshot(snapshot: &WorkflowSnapshot) -> Result<()> {
+    let Some(stored) = WorkflowSnapshot::load(Path::new(DEFAULT_SNAPSHOT_DIR), &snapshot.workflow)?
+    else {
+        return Err(SwissArmyHammerError::Other(format!(
+            "No prompt snapshots recorded for workflow '{}'. Record them with: swissarmyhammer flow run {} --dry-run --update-snapshots",
+            snapshot.workflow, snapshot.workflow
+        )));
+    };
+
+    let mismatches = snapshot.compare(&stored);
+    if mismatches.is_empty() {
+        println!("✅ {} prompt snapshot(s) match", snapshot.prompts.len());
+        return Ok(());
+    }
+
+    println!("\n❌ Prompt snapshots differ:");
+    for mismatch in &mismatches {
+        println!("{mismatch}");
+    }
+    Err(SwissArmyHammerError::Other(format!(
+        "{} prompt snapshot(s) of workflow '{}' differ. If the changes are intended, record them again with --dry-run --update-snapshots",
+        mismatches.len(),
+        snapshot.workflow
+    )))
+}
+
 /// Display metrics for workflow runs
 async fn metrics_workflow_command(
     run_id: Option<String>,
@@ -1721,4 +2211,44 @@ mod tests {
         );
         assert_eq!(vars_map.get("name").unwrap(), &serde_json::json!("Alice"));
     }
+
+    #[test]
+    fn test_display_workflows_verbose() {
+        use swissarmyhammer::workflow::WorkflowVariable;
+
+        let mut workflow = Workflow::new(
+            WorkflowName::new("greeting"),
+            "Greets someone".to_string(),
+            StateId::new("greet"),
+        );
+        workflow.title = Some("Greeting Workflow".to_string());
+        workflow.tags = vec!["example".to_string(), "greeting".to_string()];
+        workflow.variables = vec![
+            WorkflowVariable {
+                name: "name".to_string(),
+                description: Some("Who to greet".to_string()),
+                default: None,
+                required: true,
+            },
+            WorkflowVariable {
+                name: "language".to_string(),
+                description: None,
+                default: Some(serde_json::json!("English")),
+                required: false,
+            },
+        ];
+        let infos = [(workflow, PromptSource::Builtin)];
+
+        let mut output = Vec::new();
+        display_workflows_to_writer(&infos, true, &mut output, false).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.starts_with("greeting | Greeting Workflow\n  Greets someone\n"));
+        assert!(output.contains("  Tags: example, greeting\n"));
+        assert!(output.contains("  Variables:\n    name (required) - Who to greet\n"));
+        assert!(output.contains("    language = English\n"));
+
+        let mut output = Vec::new();
+        display_workflows_to_writer(&infos, false, &mut output, false).unwrap();
+        assert!(!String::from_utf8(output).unwrap().contains("Tags:"));
+    }
 }
@@ -0,0 +1,45 @@
+//! Content history of memos and issues, shown one change at a time
+
+use colored::*;
+use swissarmyhammer::ContentChange;
+
+use crate::cli::OutputFormat;
+use crate::diff_sources::print_unified_diff;
+
+/// Print the changes recorded for a memo or issue, oldest first
+pub fn print_history(
+    what: &str,
+    changes: &[ContentChange],
+    format: OutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(changes)?),
+        OutputFormat::Yaml => print!("{}", serde_yaml::to_string(changes)?),
+        OutputFormat::Table => display_history(what, changes),
+    }
+    Ok(())
+}
+
+fn display_history(what: &str, changes: &[ContentChange]) {
+    if changes.is_empty() {
+        println!("No history recorded for {what}");
+        return;
+    }
+
+    println!("{}", format!("History of {what}").bold());
+    let mut previous: Option<&ContentChange> = None;
+    for change in changes {
+        let mut heading = format!(
+            "v{} {} {}",
+            change.version,
+            change.kind,
+            change.timestamp.format("%Y-%m-%d %H:%M:%S UTC")
+        );
+        if let Some(version) = change.restored_from {
+            heading.push_str(&format!(" (from v{version})"));
+        }
+        println!("\n  {heading}");
+        print_unified_diff(&change.unified_diff(previous));
+        previous = Some(change);
+    }
+}
@@ -0,0 +1,983 @@
+//! Prompt import from LangChain, OpenAI prompt JSON and plain text files
+//!
+//! Each external prompt becomes a markdown file with front matter in the
+//! output directory. Variables are kept as Liquid variables and declared as
+//! arguments. Features without an equivalent, such as output parsers, few-shot
+//! examples or model settings, are reported instead of being silently lost.
+
+use anyhow::{anyhow, Result};
+use regex::Regex;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use crate::cli::ImportFormat;
+
+/// A prompt converted from another format
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedPrompt {
+    /// Prompt name, with `/` between subdirectories
+    pub name: String,
+    /// What the prompt does
+    pub description: Option<String>,
+    /// Arguments the template uses
+    pub arguments: Vec<ImportedArgument>,
+    /// Liquid template
+    pub template: String,
+    /// File the prompt was read from
+    pub source: PathBuf,
+    /// Features that were not converted exactly
+    pub warnings: Vec<String>,
+}
+
+/// An argument of an imported prompt, as written to its front matter
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ImportedArgument {
+    /// Argument name
+    pub name: String,
+    /// What the argument is for
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Whether the argument must be given
+    pub required: bool,
+    /// Value used when the argument is not given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+}
+
+impl ImportedPrompt {
+    fn new(name: String, source: &Path) -> Self {
+        Self {
+            name,
+            description: None,
+            arguments: Vec::new(),
+            template: String::new(),
+            source: source.to_path_buf(),
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Declare required arguments for the variables not declared yet
+    fn add_arguments(&mut self, names: impl IntoIterator<Item = String>) {
+        for name in names {
+            if !self.arguments.iter().any(|argument| argument.name == name) {
+                self.arguments.push(ImportedArgument {
+                    name,
+                    description: None,
+                    required: true,
+                    default: None,
+                });
+            }
+        }
+    }
+
+    /// Give an argument a default, declaring it if needed, so it is optional
+    fn set_default(&mut self, name: &str, default: String) {
+        self.add_arguments([name.to_string()]);
+        if let Some(argument) = self.arguments.iter_mut().find(|a| a.name == name) {
+            argument.required = false;
+            argument.default = Some(default);
+        }
+    }
+
+    /// The prompt as a markdown file with front matter
+    pub fn to_markdown(&self) -> Result<String> {
+        #[derive(Serialize)]
+        struct FrontMatter<'a> {
+            title: String,
+            description: String,
+            #[serde(skip_serializing_if = "<[_]>::is_empty")]
+            arguments: &'a [ImportedArgument],
+        }
+
+        let file_name = self
+            .source
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let front_matter = serde_yaml::to_string(&FrontMatter {
+            title: title_of(&self.name),
+            description: self
+                .description
+                .clone()
+                .unwrap_or_else(|| format!("Imported from {file_name}")),
+            arguments: &self.arguments,
+        })?;
+        Ok(format!(
+            "---\n{front_matter}---\n\n{}\n",
+            self.template.trim_end()
+        ))
+    }
+}
+
+/// Import the prompts in `path` into `output_dir`, returning how many were
+/// written
+///
+/// Prompts that already exist in the output directory are only replaced with
+/// `force`.
+pub fn run_import_command(
+    format: ImportFormat,
+    path: PathBuf,
+    output_dir: PathBuf,
+    force: bool,
+) -> Result<usize> {
+    let (prompts, mut skipped) = import_prompts(format, &path)?;
+
+    let mut written = HashSet::new();
+    for prompt in &prompts {
+        let target = output_dir.join(format!("{}.md", prompt.name));
+        if written.contains(&prompt.name) {
+            skipped.push((
+                prompt.source.clone(),
+                format!("another imported prompt is named '{}'", prompt.name),
+            ));
+            continue;
+        }
+        if target.exists() && !force {
+            skipped.push((
+                prompt.source.clone(),
+                format!(
+                    "{} already exists; use --force to replace it",
+                    target.display()
+                ),
+            ));
+            continue;
+        }
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&target, prompt.to_markdown()?)?;
+        written.insert(prompt.name.clone());
+
+        println!("Imported {} from {}", prompt.name, prompt.source.display());
+        for warning in &prompt.warnings {
+            println!("  warning: {warning}");
+        }
+    }
+    for (source, reason) in &skipped {
+        println!("Skipped {}: {reason}", source.display());
+    }
+
+    if written.is_empty() {
+        return Err(anyhow!("No prompts were imported from {}", path.display()));
+    }
+    println!(
+        "Imported {} prompts into {}",
+        written.len(),
+        output_dir.display()
+    );
+    Ok(written.len())
+}
+
+/// Convert the prompts in a file or directory
+///
+/// Returns the converted prompts, and the files that could not be converted
+/// with the reason why.
+pub fn import_prompts(
+    format: ImportFormat,
+    path: &Path,
+) -> Result<(Vec<ImportedPrompt>, Vec<(PathBuf, String)>)> {
+    let mut prompts = Vec::new();
+    let mut skipped = Vec::new();
+
+    for file in source_files(path, extensions(format))? {
+        let name = prompt_name(path, &file);
+        let converted = fs::read_to_string(&file)
+            .map_err(|e| e.to_string())
+            .and_then(|content| match format {
+                ImportFormat::Langchain => {
+                    convert_langchain(&content, &file, name).map(|p| vec![p])
+                }
+                ImportFormat::OpenaiJson => convert_openai(&content, &file, name),
+                ImportFormat::Plain => convert_plain(&content, &file, name).map(|p| vec![p]),
+            });
+        match converted {
+            Ok(converted) => prompts.extend(converted),
+            Err(reason) => skipped.push((file, reason)),
+        }
+    }
+
+    for prompt in &mut prompts {
+        if let Err(e) = swissarmyhammer::Template::new(&prompt.template) {
+            prompt
+                .warnings
+                .push(format!("the converted template is not valid Liquid: {e}"));
+        }
+    }
+    Ok((prompts, skipped))
+}
+
+/// Extensions of the files a format is read from
+fn extensions(format: ImportFormat) -> &'static [&'static str] {
+    match format {
+        ImportFormat::Langchain => &["json", "yaml", "yml"],
+        ImportFormat::OpenaiJson => &["json"],
+        ImportFormat::Plain => &["txt", "md", "prompt"],
+    }
+}
+
+/// The file at `path`, or the files under it with one of the extensions
+fn source_files(path: &Path, extensions: &[&str]) -> Result<Vec<PathBuf>> {
+    if path.is_file() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+    if !path.is_dir() {
+        return Err(anyhow!("{} does not exist", path.display()));
+    }
+
+    let mut files: Vec<PathBuf> = WalkDir::new(path)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| entry.into_path())
+        .filter(|file| {
+            file.extension()
+                .and_then(|extension| extension.to_str())
+                .is_some_and(|extension| extensions.contains(&extension.to_lowercase().as_str()))
+        })
+        .collect();
+    files.sort();
+    if files.is_empty() {
+        return Err(anyhow!(
+            "No {} files found in {}",
+            extensions.join(", "),
+            path.display()
+        ));
+    }
+    Ok(files)
+}
+
+/// Name of the prompt imported from `file`, relative to the imported `root`
+fn prompt_name(root: &Path, file: &Path) -> String {
+    let relative = if root == file {
+        Path::new(file.file_name().unwrap_or_default())
+    } else {
+        file.strip_prefix(root).unwrap_or(file)
+    };
+    let name = relative
+        .with_extension("")
+        .components()
+        .map(|component| sanitize_name(&component.as_os_str().to_string_lossy()))
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>()
+        .join("/");
+    if name.is_empty() {
+        "imported".to_string()
+    } else {
+        name
+    }
+}
+
+/// A prompt name segment with characters other than letters, digits, `_`
+/// and `-` replaced by `-`
+fn sanitize_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect::<String>()
+        .trim_matches('-')
+        .to_string()
+}
+
+/// A title for a prompt, from the last segment of its name
+fn title_of(name: &str) -> String {
+    let words = name
+        .rsplit('/')
+        .next()
+        .unwrap_or(name)
+        .replace(['-', '_'], " ");
+    let mut chars = words.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => words,
+    }
+}
+
+/// Text that renders as itself in Liquid
+///
+/// Text that could start a Liquid tag or output, including a trailing `{`
+/// that a following variable would turn into `{{{`, is wrapped in a raw
+/// block.
+fn liquid_literal(text: &str) -> String {
+    if text.contains("{{") || text.contains("{%") || text.ends_with('{') {
+        format!("{{% raw %}}{text}{{% endraw %}}")
+    } else {
+        text.to_string()
+    }
+}
+
+/// Variables referenced as `{{ name }}`, in the order they first appear
+fn placeholder_variables(template: &str) -> Vec<String> {
+    let variable_re =
+        Regex::new(r"\{\{-?\s*([A-Za-z_]\w*)").expect("Failed to compile variable regex");
+    let mut variables: Vec<String> = Vec::new();
+    for cap in variable_re.captures_iter(template) {
+        if !variables.iter().any(|variable| variable == &cap[1]) {
+            variables.push(cap[1].to_string());
+        }
+    }
+    variables
+}
+
+/// Convert a Python format string, the syntax of LangChain's f-string
+/// templates, to Liquid
+///
+/// Returns the template and the variables it uses. Replacement fields Liquid
+/// has no equivalent for are kept as text with a warning.
+fn convert_f_string(template: &str, warnings: &mut Vec<String>) -> (String, Vec<String>) {
+    let field_re = Regex::new(r"^[A-Za-z_]\w*(?:\.[A-Za-z_]\w*|\[\d+\])*$")
+        .expect("Failed to compile field regex");
+    let mut liquid = String::with_capacity(template.len());
+    let mut literal = String::new();
+    let mut variables: Vec<String> = Vec::new();
+    let mut rest = template;
+
+    while let Some(brace) = rest.find(['{', '}']) {
+        literal.push_str(&rest[..brace]);
+        let tail = &rest[brace..];
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            literal.push_str(&tail[..1]);
+            rest = &tail[2..];
+            continue;
+        }
+        if tail.starts_with('}') {
+            literal.push('}');
+            rest = &tail[1..];
+            continue;
+        }
+        let Some(end) = tail.find('}') else {
+            warnings.push("an unmatched '{' was kept as text".to_string());
+            literal.push_str(tail);
+            rest = "";
+            break;
+        };
+
+        let field = &tail[1..end];
+        let name_end = field.find(['!', ':']).unwrap_or(field.len());
+        let name = field[..name_end].trim();
+        if field_re.is_match(name) {
+            if name_end < field.len() {
+                warnings.push(format!(
+                    "the format '{}' of '{name}' was dropped",
+                    &field[name_end..]
+                ));
+            }
+            liquid.push_str(&liquid_literal(&literal));
+            literal.clear();
+            liquid.push_str(&format!("{{{{ {name} }}}}"));
+            let root = name.split(['.', '[']).next().unwrap_or(name).to_string();
+            if !variables.contains(&root) {
+                variables.push(root);
+            }
+        } else {
+            warnings.push(format!(
+                "the replacement field '{{{field}}}' was kept as text"
+            ));
+            literal.push_str(&tail[..=end]);
+        }
+        rest = &tail[end + 1..];
+    }
+    literal.push_str(rest);
+    liquid.push_str(&liquid_literal(&literal));
+    (liquid, variables)
+}
+
+/// Join chat messages into one template, under role headings when there are
+/// several
+fn flatten_messages(sections: Vec<(String, String)>, warnings: &mut Vec<String>) -> String {
+    if sections.len() == 1 {
+        return sections
+            .into_iter()
+            .next()
+            .map(|(_, text)| text)
+            .unwrap_or_default();
+    }
+    warnings.push(format!(
+        "{} chat messages were joined into one template under role headings",
+        sections.len()
+    ));
+    sections
+        .into_iter()
+        .map(|(role, text)| format!("## {role}\n\n{}", text.trim()))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// The type of a serialized LangChain object and the fields describing it
+///
+/// Objects serialized with `dumpd` name their class last in `id` and keep
+/// their fields in `kwargs`; older files name their `_type` next to the fields.
+fn langchain_parts(value: &Value) -> (&str, &Value) {
+    match value.get("kwargs").filter(|_| value.get("lc").is_some()) {
+        Some(kwargs) => {
+            let kind = value
+                .get("id")
+                .and_then(Value::as_array)
+                .and_then(|id| id.last())
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            (kind, kwargs)
+        }
+        None => {
+            let default = if value.get("messages").is_some() {
+                "chat"
+            } else {
+                "prompt"
+            };
+            let kind = value
+                .get("_type")
+                .and_then(Value::as_str)
+                .unwrap_or(default);
+            (kind, value)
+        }
+    }
+}
+
+/// The Liquid template of a LangChain prompt template and its variables
+fn langchain_template(
+    fields: &Value,
+    source: &Path,
+    warnings: &mut Vec<String>,
+) -> std::result::Result<(String, Vec<String>), String> {
+    let template = match (
+        fields.get("template").and_then(Value::as_str),
+        fields.get("template_path").and_then(Value::as_str),
+    ) {
+        (Some(template), _) => template.to_string(),
+        (None, Some(path)) => {
+            let path = source.parent().unwrap_or(Path::new(".")).join(path);
+            fs::read_to_string(&path)
+                .map_err(|e| format!("template_path {} can't be read: {e}", path.display()))?
+        }
+        (None, None) => return Err("the prompt has no template".to_string()),
+    };
+
+    match fields
+        .get("template_format")
+        .and_then(Value::as_str)
+        .unwrap_or("f-string")
+    {
+        "f-string" => Ok(convert_f_string(&template, warnings)),
+        "jinja2" => {
+            if ["{%", "{#", "|"]
+                .iter()
+                .any(|syntax| template.contains(syntax))
+            {
+                warnings.push(
+                    "Jinja2 tags, comments and filters were kept as written; check them against Liquid"
+                        .to_string(),
+                );
+            }
+            let variables = placeholder_variables(&template);
+            Ok((template, variables))
+        }
+        "mustache" => {
+            if ["{{#", "{{^", "{{/", "{{>", "{{!", "{{{", "{{&"]
+                .iter()
+                .any(|syntax| template.contains(syntax))
+            {
+                warnings.push(
+                    "Mustache sections, partials and comments were kept as written and are not Liquid"
+                        .to_string(),
+                );
+            }
+            let variables = placeholder_variables(&template);
+            Ok((template, variables))
+        }
+        other => Err(format!("the template format '{other}' is not supported")),
+    }
+}
+
+/// Convert a LangChain prompt template or chat prompt template, in JSON or
+/// YAML
+fn convert_langchain(
+    content: &str,
+    source: &Path,
+    name: String,
+) -> std::result::Result<ImportedPrompt, String> {
+    let value: Value = serde_yaml::from_str(content)
+        .map_err(|e| format!("not a LangChain JSON or YAML file: {e}"))?;
+    let mut prompt = ImportedPrompt::new(name, source);
+    let (kind, fields) = langchain_parts(&value);
+
+    let variables = match kind {
+        "prompt" | "PromptTemplate" => {
+            let (template, variables) = langchain_template(fields, source, &mut prompt.warnings)?;
+            prompt.template = template;
+            variables
+        }
+        "chat" | "ChatPromptTemplate" => {
+            let messages = fields
+                .get("messages")
+                .and_then(Value::as_array)
+                .ok_or("the chat prompt has no messages")?;
+            let mut sections = Vec::new();
+            let mut variables = Vec::new();
+            for message in messages {
+                let (kind, message) = langchain_parts(message);
+                if kind == "MessagesPlaceholder" {
+                    let variable = message
+                        .get("variable_name")
+                        .and_then(Value::as_str)
+                        .ok_or("a messages placeholder has no variable_name")?;
+                    prompt.warnings.push(format!(
+                        "the messages placeholder '{variable}' became the text variable '{variable}'"
+                    ));
+                    sections.push(("Messages".to_string(), format!("{{{{ {variable} }}}}")));
+                    variables.push(variable.to_string());
+                    continue;
+                }
+                let role = match kind {
+                    "SystemMessagePromptTemplate" | "SystemMessage" => "System",
+                    "HumanMessagePromptTemplate" | "HumanMessage" => "User",
+                    "AIMessagePromptTemplate" | "AIMessage" => "Assistant",
+                    "ChatMessagePromptTemplate" | "ChatMessage" => message
+                        .get("role")
+                        .and_then(Value::as_str)
+                        .unwrap_or("Message"),
+                    other => {
+                        return Err(format!("the chat message type '{other}' is not supported"))
+                    }
+                };
+                // Message prompt templates hold a prompt template, messages hold text
+                let (text, message_variables) = match message.get("prompt") {
+                    Some(inner) => {
+                        langchain_template(langchain_parts(inner).1, source, &mut prompt.warnings)?
+                    }
+                    None => {
+                        let content = message
+                            .get("content")
+                            .and_then(Value::as_str)
+                            .ok_or_else(|| format!("a {kind} has no text"))?;
+                        (liquid_literal(content), Vec::new())
+                    }
+                };
+                sections.push((role.to_string(), text));
+                variables.extend(message_variables);
+            }
+            prompt.template = flatten_messages(sections, &mut prompt.warnings);
+            variables
+        }
+        "few_shot" | "FewShotPromptTemplate" | "FewShotChatMessagePromptTemplate" => {
+            return Err("few-shot prompt templates have no SwissArmyHammer equivalent".to_string())
+        }
+        other => return Err(format!("the LangChain type '{other}' is not supported")),
+    };
+
+    let declared = fields
+        .get("input_variables")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .map(str::to_string);
+    prompt.add_arguments(declared.chain(variables));
+
+    if let Some(partials) = fields.get("partial_variables").and_then(Value::as_object) {
+        for (variable, value) in partials {
+            match value.as_str() {
+                Some(default) => prompt.set_default(variable, default.to_string()),
+                None => prompt.warnings.push(format!(
+                    "the partial variable '{variable}' is not text and was not kept"
+                )),
+            }
+        }
+    }
+    if fields
+        .get("output_parser")
+        .is_some_and(|parser| !parser.is_null())
+    {
+        prompt
+            .warnings
+            .push("the output parser was not kept".to_string());
+    }
+    Ok(prompt)
+}
+
+/// Settings of OpenAI prompts that have no place in a prompt file
+const OPENAI_MODEL_SETTINGS: &[&str] = &[
+    "model",
+    "temperature",
+    "top_p",
+    "max_tokens",
+    "max_output_tokens",
+    "frequency_penalty",
+    "presence_penalty",
+    "stop",
+    "seed",
+    "tools",
+    "tool_choice",
+    "response_format",
+    "reasoning",
+];
+
+/// Convert the OpenAI prompts in a JSON file: one prompt, a list of them, or
+/// an object listing them under `prompts`
+fn convert_openai(
+    content: &str,
+    source: &Path,
+    name: String,
+) -> std::result::Result<Vec<ImportedPrompt>, String> {
+    let value: Value =
+        serde_json::from_str(content).map_err(|e| format!("not a JSON file: {e}"))?;
+    let entries: Vec<&Value> = match &value {
+        Value::Array(entries) => entries.iter().collect(),
+        Value::Object(fields) => match fields.get("prompts").and_then(Value::as_array) {
+            Some(entries) => entries.iter().collect(),
+            None => vec![&value],
+        },
+        _ => return Err("expected a prompt object or a list of them".to_string()),
+    };
+
+    entries
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            let entry_name = entry
+                .get("name")
+                .or_else(|| entry.get("id"))
+                .and_then(Value::as_str)
+                .map(sanitize_name)
+                .filter(|entry_name| !entry_name.is_empty())
+                .unwrap_or_else(|| {
+                    if entries.len() == 1 {
+                        name.clone()
+                    } else {
+                        format!("{name}-{}", index + 1)
+                    }
+                });
+            convert_openai_prompt(entry, source, entry_name)
+        })
+        .collect()
+}
+
+/// Convert one OpenAI prompt: chat `messages`, `instructions` or a `prompt`
+/// string, with `{{variable}}` placeholders
+fn convert_openai_prompt(
+    entry: &Value,
+    source: &Path,
+    name: String,
+) -> std::result::Result<ImportedPrompt, String> {
+    let mut prompt = ImportedPrompt::new(name, source);
+    prompt.description = entry
+        .get("description")
+        .and_then(Value::as_str)
+        .map(str::to_string);
+
+    let mut sections = Vec::new();
+    if let Some(instructions) = entry.get("instructions").and_then(Value::as_str) {
+        sections.push(("System".to_string(), instructions.to_string()));
+    }
+    if let Some(text) = entry.get("prompt").and_then(Value::as_str) {
+        sections.push(("User".to_string(), text.to_string()));
+    }
+    let messages = entry.get("messages").and_then(Value::as_array);
+    for (index, message) in messages.into_iter().flatten().enumerate() {
+        let role = message
+            .get("role")
+            .and_then(Value::as_str)
+            .unwrap_or("user");
+        let text = match message.get("content") {
+            Some(Value::String(text)) => text.clone(),
+            Some(Value::Array(parts)) => {
+                let mut texts = Vec::new();
+                for part in parts {
+                    match part.get("type").and_then(Value::as_str) {
+                        Some("text" | "input_text" | "output_text") => {
+                            texts.extend(part.get("text").and_then(Value::as_str));
+                        }
+                        other => prompt.warnings.push(format!(
+                            "the {} content of message {} was dropped",
+                            other.unwrap_or("untyped"),
+                            index + 1
+                        )),
+                    }
+                }
+                texts.join("\n\n")
+            }
+            _ => {
+                prompt
+                    .warnings
+                    .push(format!("message {} has no text and was dropped", index + 1));
+                continue;
+            }
+        };
+        sections.push((title_of(role), text));
+    }
+    if sections.is_empty() {
+        return Err("the prompt has no messages, instructions or prompt text".to_string());
+    }
+    prompt.template = flatten_messages(sections, &mut prompt.warnings);
+    prompt.add_arguments(placeholder_variables(&prompt.template));
+
+    match entry.get("variables") {
+        Some(Value::Object(variables)) => {
+            for (variable, value) in variables {
+                let (default, description) = match value {
+                    Value::String(default) => (Some(default.clone()), None),
+                    Value::Object(fields) => (
+                        fields
+                            .get("default")
+                            .and_then(Value::as_str)
+                            .map(str::to_string),
+                        fields
+                            .get("description")
+                            .and_then(Value::as_str)
+                            .map(str::to_string),
+                    ),
+                    _ => (None, None),
+                };
+                match default {
+                    Some(default) => prompt.set_default(variable, default),
+                    None => prompt.add_arguments([variable.clone()]),
+                }
+                if let Some(argument) = prompt.arguments.iter_mut().find(|a| &a.name == variable) {
+                    argument.description = description;
+                }
+            }
+        }
+        Some(Value::Array(variables)) => {
+            prompt.add_arguments(
+                variables
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .map(str::to_string),
+            );
+        }
+        _ => {}
+    }
+
+    let settings: Vec<&str> = OPENAI_MODEL_SETTINGS
+        .iter()
+        .copied()
+        .filter(|setting| entry.get(setting).is_some())
+        .collect();
+    if !settings.is_empty() {
+        prompt.warnings.push(format!(
+            "the model settings {} were not kept",
+            settings.join(", ")
+        ));
+    }
+    Ok(prompt)
+}
+
+/// Convert a plain text prompt, keeping its text and `{{variable}}`
+/// placeholders as they are
+fn convert_plain(
+    content: &str,
+    source: &Path,
+    name: String,
+) -> std::result::Result<ImportedPrompt, String> {
+    if content.starts_with("---\n") || content.starts_with("---\r\n") {
+        return Err(
+            "it already has front matter; copy it into a prompts directory as it is".to_string(),
+        );
+    }
+    if content.trim().is_empty() {
+        return Err("the file is empty".to_string());
+    }
+    let mut prompt = ImportedPrompt::new(name, source);
+    prompt.template = content.to_string();
+    prompt.add_arguments(placeholder_variables(content));
+    Ok(prompt)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_convert_f_string() {
+        let mut warnings = Vec::new();
+        let (template, variables) = convert_f_string(
+            "Tell me a {adjective} joke about {topic.name:>10}. Use {{braces}} and {0}.",
+            &mut warnings,
+        );
+        assert_eq!(
+            template,
+            "Tell me a {{ adjective }} joke about {{ topic.name }}. Use {braces} and {0}."
+        );
+        assert_eq!(variables, ["adjective", "topic"]);
+        assert_eq!(warnings.len(), 2, "{warnings:?}");
+
+        // Literal braces that would read as Liquid are kept in a raw block
+        let (template, _) = convert_f_string("{{{{not liquid}}}} {{{name}}}", &mut warnings);
+        assert_eq!(template, "{% raw %}{{not liquid}} {{% endraw %}{{ name }}}");
+    }
+
+    #[test]
+    fn test_convert_langchain() {
+        let legacy = r#"
+_type: prompt
+input_variables: [language, code]
+template: "Review this {language} code:\n{code}"
+partial_variables:
+  language: Rust
+output_parser: {_type: json}
+"#;
+        let prompt = convert_langchain(legacy, Path::new("review.yaml"), "review".into()).unwrap();
+        assert_eq!(
+            prompt.template,
+            "Review this {{ language }} code:\n{{ code }}"
+        );
+        assert_eq!(prompt.arguments.len(), 2);
+        assert_eq!(prompt.arguments[0].default.as_deref(), Some("Rust"));
+        assert!(!prompt.arguments[0].required);
+        assert!(prompt.arguments[1].required);
+        assert_eq!(prompt.warnings, ["the output parser was not kept"]);
+
+        let chat = serde_json::json!({
+            "lc": 1,
+            "type": "constructor",
+            "id": ["langchain", "prompts", "chat", "ChatPromptTemplate"],
+            "kwargs": {
+                "input_variables": ["question"],
+                "messages": [
+                    {
+                        "lc": 1,
+                        "type": "constructor",
+                        "id": ["langchain", "prompts", "chat", "SystemMessagePromptTemplate"],
+                        "kwargs": {"prompt": {
+                            "lc": 1,
+                            "type": "constructor",
+                            "id": ["langchain", "prompts", "prompt", "PromptTemplate"],
+                            "kwargs": {"template": "You answer briefly.", "input_variables": []}
+                        }}
+                    },
+                    {
+                        "lc": 1,
+                        "type": "constructor",
+                        "id": ["langchain_core", "prompts", "chat", "MessagesPlaceholder"],
+                        "kwargs": {"variable_name": "history"}
+                    },
+                    {
+                        "lc": 1,
+                        "type": "constructor",
+                        "id": ["langchain", "prompts", "chat", "HumanMessagePromptTemplate"],
+                        "kwargs": {"prompt": {
+                            "lc": 1,
+                            "type": "constructor",
+                            "id": ["langchain", "prompts", "prompt", "PromptTemplate"],
+                            "kwargs": {"template": "{question}", "input_variables": ["question"]}
+                        }}
+                    }
+                ]
+            }
+        });
+        let prompt =
+            convert_langchain(&chat.to_string(), Path::new("qa.json"), "qa".into()).unwrap();
+        assert_eq!(
+            prompt.template,
+            "## System\n\nYou answer briefly.\n\n## Messages\n\n{{ history }}\n\n## User\n\n{{ question }}"
+        );
+        let names: Vec<&str> = prompt.arguments.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, ["question", "history"]);
+        assert_eq!(prompt.warnings.len(), 2, "{:?}", prompt.warnings);
+
+        let few_shot = r#"{"_type": "few_shot", "examples": []}"#;
+        assert!(convert_langchain(few_shot, Path::new("f.json"), "f".into())
+            .unwrap_err()
+            .contains("few-shot"));
+    }
+
+    #[test]
+    fn test_convert_openai() {
+        let content = serde_json::json!({"prompts": [
+            {
+                "name": "Summarize Ticket",
+                "model": "gpt-4o",
+                "temperature": 0.2,
+                "variables": {"tone": {"default": "neutral", "description": "Voice of the summary"}},
+                "messages": [
+                    {"role": "system", "content": "Write in a {{tone}} tone."},
+                    {"role": "user", "content": [
+                        {"type": "text", "text": "Summarize {{ ticket }}"},
+                        {"type": "image_url", "image_url": {"url": "https://example.com/a.png"}}
+                    ]}
+                ]
+            },
+            {"prompt": "Translate {{text}}"}
+        ]});
+        let prompts = convert_openai(
+            &content.to_string(),
+            Path::new("prompts.json"),
+            "prompts".into(),
+        )
+        .unwrap();
+        assert_eq!(prompts.len(), 2);
+
+        let summarize = &prompts[0];
+        assert_eq!(summarize.name, "Summarize-Ticket");
+        assert_eq!(
+            summarize.template,
+            "## System\n\nWrite in a {{tone}} tone.\n\n## User\n\nSummarize {{ ticket }}"
+        );
+        assert_eq!(summarize.arguments[0].default.as_deref(), Some("neutral"));
+        assert_eq!(
+            summarize.arguments[0].description.as_deref(),
+            Some("Voice of the summary")
+        );
+        assert!(summarize.arguments[1].required);
+        assert!(summarize
+            .warnings
+            .contains(&"the model settings model, temperature were not kept".to_string()));
+        assert!(summarize.warnings[0].contains("image_url"));
+
+        assert_eq!(prompts[1].name, "prompts-2");
+        assert_eq!(prompts[1].template, "Translate {{text}}");
+        assert!(prompts[1].warnings.is_empty());
+    }
+
+    #[test]
+    fn test_import_plain_directory() {
+        let temp = TempDir::new().unwrap();
+        let source = temp.path().join("corpus");
+        fs::create_dir_all(source.join("review")).unwrap();
+        fs::write(
+            source.join("review/security check.txt"),
+            "Audit {{ file }}\n",
+        )
+        .unwrap();
+        fs::write(source.join("greeting.md"), "Say hello").unwrap();
+        fs::write(source.join("done.md"), "---\ntitle: Done\n---\nDone").unwrap();
+        fs::write(source.join("notes.csv"), "ignored").unwrap();
+
+        let output = temp.path().join("prompts");
+        let written =
+            run_import_command(ImportFormat::Plain, source.clone(), output.clone(), false).unwrap();
+        assert_eq!(written, 2);
+
+        let markdown = fs::read_to_string(output.join("review/security-check.md")).unwrap();
+        assert!(
+            markdown.starts_with("---\ntitle: Security check\n"),
+            "{markdown}"
+        );
+        assert!(
+            markdown.contains("- name: file\n  required: true"),
+            "{markdown}"
+        );
+        assert!(
+            markdown.ends_with("---\n\nAudit {{ file }}\n"),
+            "{markdown}"
+        );
+        assert!(!output.join("done.md").exists());
+
+        // Existing prompts are only replaced with --force
+        assert!(
+            run_import_command(ImportFormat::Plain, source.clone(), output.clone(), false).is_err()
+        );
+        assert_eq!(
+            run_import_command(ImportFormat::Plain, source, output, true).unwrap(),
+            2
+        );
+    }
+}
@@ -1,8 +1,30 @@
-use crate::cli::{IssueCommands, OutputFormat};
+use crate::cli::{
+    BoardColumnArg, IssueBulkCommands, IssueCommands, IssuePriorityArg, IssueSortArg, OutputFormat,
+};
+use crate::diff_sources::print_unified_diff;
+use crate::flow;
+use crate::history::print_history;
 use crate::mcp_integration::{response_formatting, CliToolContext};
+use colored::*;
+use dialoguer::{theme::ColorfulTheme, Confirm};
+use is_terminal::IsTerminal;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::io::{self, Read};
+use std::sync::Arc;
 use swissarmyhammer::config::Config;
+use swissarmyhammer::git::{GitOperations, SyncReport, SyncStatus, SyncStrategy};
+use swissarmyhammer::issues::{
+    issue_storage_for_work_dir, parse_age, select_issues, stale_issues, BoardColumn,
+    BranchRenamePlan, BulkAction, BulkPlan, ChecklistItem, ChecklistProgress, IssueBoard,
+    IssueBranchNaming, IssueNumbering, IssuePriority, IssueRefinement, IssueSla, IssueSort,
+    IssueStats, IssueStorage, RenumberPlan, SlaStatus, StaleIssue, CURRENT_USER,
+};
+use swissarmyhammer::workflow::{
+    IssueCoordinator, IssueRun, IssueRunListener, IssueRunStatus, IssueWorkflowBinding,
+    IssueWorkflowOutcome, WorkflowName, WorkflowRunStatus, WorkflowStorage,
+    FAILURE_MESSAGE_METADATA_KEY,
+};
 
 pub async fn handle_issue_command(
     command: IssueCommands,
@@ -21,8 +43,14 @@ pub async fn handle_issue_command(
             completed,
             active,
             format,
+            sort,
+            priority,
+            assignee,
         } => {
-            list_issues(&context, completed, active, format).await?;
+            list_issues(
+                &context, completed, active, format, sort, priority, assignee,
+            )
+            .await?;
         }
         IssueCommands::Show { name, raw } => {
             show_issue(&context, &name, raw).await?;
@@ -38,20 +66,100 @@ pub async fn handle_issue_command(
         IssueCommands::Complete { name } => {
             complete_issue(&context, &name).await?;
         }
+        IssueCommands::History { name, format } => {
+            show_issue_history(&name, format).await?;
+        }
+        IssueCommands::Restore { name, version } => {
+            restore_issue(&name, version).await?;
+        }
+        IssueCommands::Refine { name, yes } => {
+            refine_issue(&name, yes).await?;
+        }
+        IssueCommands::Move { name, state } => {
+            move_issue(&context, &name, &state).await?;
+        }
+        IssueCommands::Assign {
+            name,
+            user,
+            unassign,
+        } => {
+            assign_issue(&context, &name, user, unassign).await?;
+        }
+        IssueCommands::Check {
+            name,
+            item,
+            done,
+            undo,
+            format,
+        } => {
+            check_issue(&context, &name, item, done, undo, format).await?;
+        }
         IssueCommands::Work { name } => {
             work_issue(&context, &name).await?;
         }
         IssueCommands::Merge { name, keep_branch } => {
             merge_issue(&context, &name, keep_branch).await?;
         }
+        IssueCommands::Sync {
+            name,
+            rebase,
+            format,
+        } => {
+            sync_issue(&context, &name, rebase, format).await?;
+        }
         IssueCommands::Current => {
             show_current_issue(&context).await?;
         }
         IssueCommands::Status => {
             show_status(&context).await?;
         }
-        IssueCommands::Next => {
-            show_next_issue(&context).await?;
+        IssueCommands::Next { sort } => {
+            show_next_issue(&context, sort).await?;
+        }
+        IssueCommands::Board {
+            move_issue,
+            to,
+            format,
+        } => {
+            if let (Some(name), Some(to)) = (move_issue, to) {
+                move_issue_on_board(&context, &name, to).await?;
+            }
+            show_board(&context, format).await?;
+        }
+        IssueCommands::Stats { weeks, format } => {
+            show_stats(&context, weeks, format).await?;
+        }
+        IssueCommands::Stale {
+            older_than,
+            escalate,
+            format,
+        } => {
+            show_stale_issues(older_than.as_deref(), escalate, format).await?;
+        }
+        IssueCommands::Bulk { action } => {
+            bulk_change_issues(&context, action).await?;
+        }
+        IssueCommands::Renumber {
+            scheme,
+            prefix,
+            yes,
+            format,
+        } => {
+            renumber_issues(scheme.as_deref(), prefix.as_deref(), yes, format).await?;
+        }
+        IssueCommands::RenameBranches { from, yes, format } => {
+            rename_issue_branches(&from, yes, format).await?;
+        }
+        IssueCommands::Parallel {
+            patterns,
+            jobs,
+            workflow,
+            format,
+        } => {
+            work_issues_in_parallel(patterns, jobs, &workflow, format).await?;
+        }
+        IssueCommands::Queue { run, format } => {
+            issue_queue(run, format).await?;
         }
     }
 
@@ -92,23 +200,137 @@ async fn list_issues(
     show_completed: bool,
     show_active: bool,
     format: OutputFormat,
+    sort: IssueSortArg,
+    priority: Option<IssuePriorityArg>,
+    assignee: Option<String>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let format_str = match format {
         OutputFormat::Table => "table",
         OutputFormat::Json => "json",
         OutputFormat::Yaml => "markdown", // MCP tool uses "markdown" for YAML-like output
     };
-    let args = context.create_arguments(vec![
+    let mut args = vec![
         ("show_completed", json!(show_completed)),
         ("show_active", json!(show_active)),
         ("format", json!(format_str)),
-    ]);
+        ("sort", json!(IssueSort::from(sort).to_string())),
+    ];
+    if let Some(priority) = priority {
+        args.push(("priority", json!(IssuePriority::from(priority).to_string())));
+    }
+    if let Some(assignee) = assignee {
+        args.push(("assignee", json!(assignee)));
+    }
+    let args = context.create_arguments(args);
 
     let result = context.execute_tool("issue_list", args).await?;
-    println!("{}", response_formatting::format_success_response(&result));
+    let text = response_formatting::format_success_response(&result);
+    if matches!(format, OutputFormat::Table) && crate::cli::Cli::should_use_color() {
+        println!("{}", color_sla_lines(&text));
+    } else {
+        println!("{text}");
+    }
+    Ok(())
+}
+
+/// Color the lines of issues past their SLA by how far past it they are
+fn color_sla_lines(text: &str) -> String {
+    text.lines()
+        .map(|line| {
+            if line.contains(SlaStatus::Escalated.marker()) {
+                line.red().to_string()
+            } else if line.contains(SlaStatus::Warning.marker()) {
+                line.yellow().to_string()
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// List open issues older than an age, labeling escalated ones when asked
+async fn show_stale_issues(
+    older_than: Option<&str>,
+    escalate: bool,
+    format: OutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let sla = IssueSla::from_config(Config::global());
+    if escalate && sla.escalate_after_days.is_none() {
+        return Err(
+            "Set SWISSARMYHAMMER_ISSUE_SLA_ESCALATE_DAYS to choose which issues --escalate labels"
+                .into(),
+        );
+    }
+    let older_than = match older_than {
+        Some(age) => parse_age(age)?,
+        None => sla.stale_after(),
+    };
+
+    let issues = issue_storage_for_work_dir(&std::env::current_dir()?)?
+        .list_issues()
+        .await?;
+    let stale = stale_issues(&issues, older_than, &sla, chrono::Utc::now());
+
+    match format {
+        OutputFormat::Table => print_stale_issues(&stale, older_than),
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&stale)?),
+        OutputFormat::Yaml => print!("{}", serde_yaml::to_string(&stale)?),
+    }
+
+    if escalate {
+        let escalated: Vec<_> = issues
+            .into_iter()
+            .filter(|issue| {
+                stale
+                    .iter()
+                    .any(|s| s.sla == SlaStatus::Escalated && s.file_path == issue.file_path)
+            })
+            .collect();
+        let mut plan = BulkPlan::new(
+            BulkAction::Label {
+                label: sla.escalate_label.clone(),
+            },
+            &escalated,
+        )?;
+        plan.apply()?;
+        // Keep JSON and YAML output on stdout parseable
+        if matches!(format, OutputFormat::Table) {
+            print!("{plan}");
+        } else {
+            eprint!("{plan}");
+        }
+    }
     Ok(())
 }
 
+fn print_stale_issues(stale: &[StaleIssue], older_than: chrono::Duration) {
+    let age = if older_than.num_days() > 0 {
+        format!("{} days", older_than.num_days())
+    } else {
+        format!("{} hours", older_than.num_hours())
+    };
+    if stale.is_empty() {
+        println!("No open issues older than {age}");
+        return;
+    }
+
+    println!("{} open issues older than {age}:", stale.len());
+    let width = stale.iter().map(|s| s.name.len()).max().unwrap_or(0);
+    let use_color = crate::cli::Cli::should_use_color();
+    for issue in stale {
+        let line = format!(
+            "  {:<width$}  {:>4} days  {}",
+            issue.name, issue.age_days, issue.sla
+        );
+        match issue.sla {
+            SlaStatus::Escalated if use_color => println!("{}", line.red()),
+            SlaStatus::Warning if use_color => println!("{}", line.yellow()),
+            _ => println!("{line}"),
+        }
+    }
+}
+
 async fn show_issue(
     context: &CliToolContext,
     name: &str,
@@ -152,6 +374,97 @@ async fn complete_issue(
     Ok(())
 }
 
+async fn move_issue(
+    context: &CliToolContext,
+    name: &str,
+    state: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let args = context.create_arguments(vec![("name", json!(name)), ("state", json!(state))]);
+    let result = context.execute_tool("issue_transition", args).await?;
+
+    println!("{}", response_formatting::format_success_response(&result));
+    Ok(())
+}
+
+async fn assign_issue(
+    context: &CliToolContext,
+    name: &str,
+    user: Option<String>,
+    unassign: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let assignee = if unassign {
+        json!(null)
+    } else {
+        json!(user.unwrap_or_else(|| CURRENT_USER.to_string()))
+    };
+    let args = context.create_arguments(vec![("name", json!(name)), ("assignee", assignee)]);
+    let result = context.execute_tool("issue_assign", args).await?;
+
+    println!("{}", response_formatting::format_success_response(&result));
+    Ok(())
+}
+
+/// Checklist of an issue as reported by the issue_check tool
+#[derive(Debug, Serialize, Deserialize)]
+struct ChecklistReport {
+    issue: String,
+    checked: Option<ChecklistItem>,
+    progress: ChecklistProgress,
+    items: Vec<ChecklistItem>,
+}
+
+async fn check_issue(
+    context: &CliToolContext,
+    name: &str,
+    item: Option<String>,
+    done: bool,
+    undo: bool,
+    format: OutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let done = match (done, undo) {
+        (true, _) => json!(true),
+        (_, true) => json!(false),
+        _ => json!(null),
+    };
+    let args = context.create_arguments(vec![
+        ("name", json!(name)),
+        ("item", json!(item)),
+        ("done", done),
+    ]);
+    let result = context.execute_tool("issue_check", args).await?;
+    let text = response_formatting::extract_text_content(&result)
+        .ok_or("Issue check response was empty")?;
+    let report: ChecklistReport = serde_json::from_str(&text)?;
+
+    match format {
+        OutputFormat::Table => {
+            if let Some(checked) = &report.checked {
+                let state = if checked.done { "done" } else { "not done" };
+                println!(
+                    "✅ Marked task {} {}: {}",
+                    checked.number, state, checked.text
+                );
+            }
+            if report.items.is_empty() {
+                println!("Issue '{}' has no checklist", report.issue);
+            } else {
+                println!("{}: {}", report.issue.bold(), report.progress);
+                for item in &report.items {
+                    let mark = if item.done {
+                        "[x]".green()
+                    } else {
+                        "[ ]".normal()
+                    };
+                    println!("  {mark} {}. {}", item.number, item.text);
+                }
+            }
+        }
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        OutputFormat::Yaml => print!("{}", serde_yaml::to_string(&report)?),
+    }
+    Ok(())
+}
+
 async fn work_issue(
     context: &CliToolContext,
     name: &str,
@@ -221,10 +534,522 @@ fn get_content_from_args(
     }
 }
 
-async fn show_next_issue(context: &CliToolContext) -> Result<(), Box<dyn std::error::Error>> {
-    let args = context.create_arguments(vec![]);
+async fn show_next_issue(
+    context: &CliToolContext,
+    sort: IssueSortArg,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let args = context.create_arguments(vec![("sort", json!(IssueSort::from(sort).to_string()))]);
     let result = context.execute_tool("issue_next", args).await?;
 
     println!("{}", response_formatting::format_success_response(&result));
     Ok(())
 }
+
+async fn show_board(
+    context: &CliToolContext,
+    format: OutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    match format {
+        OutputFormat::Table => {
+            let args = context.create_arguments(vec![("format", json!("table"))]);
+            let result = context.execute_tool("issue_board", args).await?;
+            let table = response_formatting::extract_text_content(&result).unwrap_or_default();
+            println!("{table}");
+        }
+        OutputFormat::Json => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&fetch_board(context).await?)?
+            );
+        }
+        OutputFormat::Yaml => {
+            print!("{}", serde_yaml::to_string(&fetch_board(context).await?)?);
+        }
+    }
+    Ok(())
+}
+
+async fn fetch_board(context: &CliToolContext) -> Result<IssueBoard, Box<dyn std::error::Error>> {
+    let args = context.create_arguments(vec![("format", json!("json"))]);
+    let result = context.execute_tool("issue_board", args).await?;
+    let text = response_formatting::extract_text_content(&result)
+        .ok_or("Issue board response was empty")?;
+    Ok(serde_json::from_str(&text)?)
+}
+
+async fn show_stats(
+    context: &CliToolContext,
+    weeks: usize,
+    format: OutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let tool_format = match format {
+        OutputFormat::Table => "table",
+        OutputFormat::Json | OutputFormat::Yaml => "json",
+    };
+    let args = context.create_arguments(vec![
+        ("weeks", json!(weeks)),
+        ("format", json!(tool_format)),
+    ]);
+    let result = context.execute_tool("issue_stats", args).await?;
+    let text = response_formatting::extract_text_content(&result)
+        .ok_or("Issue stats response was empty")?;
+
+    match format {
+        OutputFormat::Table | OutputFormat::Json => println!("{}", text.trim_end()),
+        OutputFormat::Yaml => {
+            let stats: IssueStats = serde_json::from_str(&text)?;
+            print!("{}", serde_yaml::to_string(&stats)?);
+        }
+    }
+    Ok(())
+}
+
+async fn sync_issue(
+    context: &CliToolContext,
+    name: &str,
+    rebase: bool,
+    format: OutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let strategy = if rebase {
+        SyncStrategy::Rebase
+    } else {
+        SyncStrategy::Merge
+    };
+    let args = context.create_arguments(vec![
+        ("name", json!(name)),
+        ("strategy", json!(strategy)),
+        ("format", json!("json")),
+    ]);
+    let result = context.execute_tool("issue_sync", args).await?;
+    let text = response_formatting::extract_text_content(&result)
+        .ok_or("Issue sync response was empty")?;
+    let report: SyncReport = serde_json::from_str(&text)?;
+
+    match format {
+        OutputFormat::Table => println!("{report}"),
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        OutputFormat::Yaml => print!("{}", serde_yaml::to_string(&report)?),
+    }
+
+    if report.status == SyncStatus::Conflicted {
+        return Err(format!("Syncing issue '{name}' conflicts; see the resolution plan").into());
+    }
+    Ok(())
+}
+
+/// Move an issue between board columns using the matching issue tools
+async fn move_issue_on_board(
+    context: &CliToolContext,
+    name: &str,
+    to: BoardColumnArg,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let board = fetch_board(context).await?;
+    let from = board
+        .column_of(name)
+        .ok_or_else(|| format!("Issue '{name}' not found"))?;
+    let args = || context.create_arguments(vec![("name", json!(name))]);
+
+    match (from, to) {
+        (BoardColumn::Todo, BoardColumnArg::Todo)
+        | (BoardColumn::InProgress, BoardColumnArg::InProgress)
+        | (BoardColumn::Done, BoardColumnArg::Done) => {
+            println!("Issue '{name}' is already in {from}");
+        }
+        (_, BoardColumnArg::Done) => {
+            let result = context.execute_tool("issue_mark_complete", args()).await?;
+            println!("{}", response_formatting::format_success_response(&result));
+        }
+        (BoardColumn::Done, BoardColumnArg::Todo) => {
+            let result = context.execute_tool("issue_reopen", args()).await?;
+            println!("{}", response_formatting::format_success_response(&result));
+        }
+        (BoardColumn::Done, BoardColumnArg::InProgress) => {
+            let result = context.execute_tool("issue_reopen", args()).await?;
+            println!("{}", response_formatting::format_success_response(&result));
+            let result = context.execute_tool("issue_work", args()).await?;
+            println!("{}", response_formatting::format_success_response(&result));
+        }
+        (BoardColumn::Todo, BoardColumnArg::InProgress) => {
+            let result = context.execute_tool("issue_work", args()).await?;
+            println!("{}", response_formatting::format_success_response(&result));
+        }
+        (BoardColumn::InProgress, BoardColumnArg::Todo) => {
+            return Err(format!(
+                "Issue '{name}' has a work branch; merge or delete its branch to move it back to todo"
+            )
+            .into());
+        }
+    }
+    Ok(())
+}
+
+/// Plan a bulk change and apply it when confirmed with --yes
+async fn bulk_change_issues(
+    context: &CliToolContext,
+    command: IssueBulkCommands,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let (mut args, patterns, yes, format) = match command {
+        IssueBulkCommands::Complete {
+            patterns,
+            yes,
+            format,
+        } => (vec![("action", json!("complete"))], patterns, yes, format),
+        IssueBulkCommands::Label {
+            label,
+            patterns,
+            yes,
+            format,
+        } => (
+            vec![("action", json!("label")), ("label", json!(label))],
+            patterns,
+            yes,
+            format,
+        ),
+        IssueBulkCommands::Move {
+            to,
+            patterns,
+            yes,
+            format,
+        } => (
+            vec![
+                ("action", json!("move")),
+                ("to", json!(to.display().to_string())),
+            ],
+            patterns,
+            yes,
+            format,
+        ),
+    };
+    args.push(("patterns", json!(bulk_patterns(patterns)?)));
+    args.push(("apply", json!(yes)));
+    args.push(("format", json!("json")));
+
+    let result = context
+        .execute_tool("issue_bulk", context.create_arguments(args))
+        .await?;
+    let text = response_formatting::extract_text_content(&result)
+        .ok_or("Issue bulk response was empty")?;
+    let plan: BulkPlan = serde_json::from_str(&text)?;
+
+    match format {
+        OutputFormat::Table => {
+            print!("{plan}");
+            if !plan.applied && !plan.changes.is_empty() {
+                println!("Run again with --yes to apply these changes");
+            }
+        }
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&plan)?),
+        OutputFormat::Yaml => print!("{}", serde_yaml::to_string(&plan)?),
+    }
+    Ok(())
+}
+
+/// Plan renaming issues to a numbering scheme and apply it when confirmed with --yes
+async fn renumber_issues(
+    scheme: Option<&str>,
+    prefix: Option<&str>,
+    yes: bool,
+    format: OutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::global();
+    let numbering = IssueNumbering::parse(
+        scheme.unwrap_or(&config.issue_numbering),
+        prefix.unwrap_or(&config.issue_project_prefix),
+        config.issue_number_width,
+    )?;
+
+    let issues = issue_storage_for_work_dir(&std::env::current_dir()?)?
+        .list_issues()
+        .await?;
+    let mut plan = RenumberPlan::new(&numbering, &issues);
+    if yes {
+        plan.apply()?;
+    }
+
+    match format {
+        OutputFormat::Table => {
+            print!("{plan}");
+            if !plan.applied && !plan.changes.is_empty() {
+                println!("Run again with --yes to apply these changes");
+            }
+        }
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&plan)?),
+        OutputFormat::Yaml => print!("{}", serde_yaml::to_string(&plan)?),
+    }
+    Ok(())
+}
+
+/// Plan renaming issue branches to the configured template and apply it when
+/// confirmed with --yes
+async fn rename_issue_branches(
+    from: &str,
+    yes: bool,
+    format: OutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let from = IssueBranchNaming::new(from)?;
+    let git = GitOperations::new()?;
+    let issues = issue_storage_for_work_dir(git.work_dir())?
+        .list_issues()
+        .await?;
+    let mut plan =
+        BranchRenamePlan::new(&from, git.branch_naming(), &issues, &git.local_branches()?);
+    if yes {
+        plan.apply(&git)?;
+    }
+
+    match format {
+        OutputFormat::Table => {
+            print!("{plan}");
+            if !plan.applied && !plan.renames.is_empty() {
+                println!("Run again with --yes to apply these changes");
+            }
+        }
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&plan)?),
+        OutputFormat::Yaml => print!("{}", serde_yaml::to_string(&plan)?),
+    }
+    Ok(())
+}
+
+/// Show every change made to an issue's content
+async fn show_issue_history(
+    name: &str,
+    format: OutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let changes = issue_storage_for_work_dir(&std::env::current_dir()?)?
+        .issue_history(name)
+        .await?;
+    print_history(&format!("issue '{name}'"), &changes, format)
+}
+
+/// Restore an issue's content to a version from its history
+async fn restore_issue(name: &str, version: usize) -> Result<(), Box<dyn std::error::Error>> {
+    let issue = issue_storage_for_work_dir(&std::env::current_dir()?)?
+        .restore_issue(name, version)
+        .await?;
+    println!("Restored issue '{}' to version {version}", issue.name);
+    Ok(())
+}
+
+/// Ask the model to refine an issue, show the changes and apply them once confirmed
+async fn refine_issue(name: &str, yes: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let storage = issue_storage_for_work_dir(&std::env::current_dir()?)?;
+    let issue = storage.get_issue(name).await?;
+
+    println!("Refining issue '{}'...", issue.name);
+    let refinement = IssueRefinement::request(&issue).await?;
+    if refinement.is_unchanged() {
+        println!("The model suggested no changes to '{}'", issue.name);
+        return Ok(());
+    }
+    print_unified_diff(&refinement.unified_diff());
+
+    let apply = if yes {
+        true
+    } else if io::stdin().is_terminal() {
+        Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!("Update issue '{}' with these changes?", issue.name))
+            .default(false)
+            .interact()?
+    } else {
+        println!("Run again with --yes to apply these changes");
+        false
+    };
+    if !apply {
+        return Ok(());
+    }
+
+    storage
+        .update_issue(&issue.name, format!("{}\n", refinement.refined))
+        .await?;
+    println!("Updated issue '{}'", issue.name);
+    Ok(())
+}
+
+/// Prints each issue's status as it changes
+struct IssueRunPrinter;
+
+impl IssueRunListener for IssueRunPrinter {
+    fn on_update(&self, run: &IssueRun) {
+        match &run.message {
+            Some(message) => println!("[{}] {}: {message}", run.status, run.issue),
+            None => println!("[{}] {}", run.status, run.issue),
+        }
+    }
+}
+
+/// Work pending issues concurrently in worktrees, merging them as they complete
+async fn work_issues_in_parallel(
+    patterns: Vec<String>,
+    jobs: usize,
+    workflow: &str,
+    format: OutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let work_dir = std::env::current_dir()?;
+    let pending: Vec<_> = issue_storage_for_work_dir(&work_dir)?
+        .list_issues()
+        .await?
+        .into_iter()
+        .filter(|issue| !issue.completed)
+        .collect();
+    let mut issues = if patterns.is_empty() {
+        pending
+    } else {
+        select_issues(&pending, &patterns)?
+    };
+    IssueSort::Priority.sort(&mut issues);
+    if issues.is_empty() {
+        println!("No pending issues to work");
+        return Ok(());
+    }
+
+    let mut coordinator = IssueCoordinator::new(GitOperations::with_work_dir(work_dir)?, jobs)
+        .with_workflow(workflow);
+    if matches!(format, OutputFormat::Table) {
+        coordinator = coordinator.with_listener(Arc::new(IssueRunPrinter));
+    }
+    let mut storage = WorkflowStorage::file_system()?;
+    let runs = coordinator.run(&mut storage, &issues).await?;
+
+    match format {
+        OutputFormat::Table => {
+            println!();
+            for run in &runs {
+                let run_id = run.run_id.map(|id| id.to_string()).unwrap_or_default();
+                println!("{:<11} {:<40} {run_id}", run.status.to_string(), run.issue);
+            }
+        }
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&runs)?),
+        OutputFormat::Yaml => print!("{}", serde_yaml::to_string(&runs)?),
+    }
+
+    let unmerged = runs
+        .iter()
+        .filter(|run| run.status != IssueRunStatus::Merged)
+        .count();
+    if unmerged > 0 {
+        return Err(format!("{unmerged} of {} issues were not merged", runs.len()).into());
+    }
+    Ok(())
+}
+
+/// List the workflow runs queued for new issues, or run them
+async fn issue_queue(run: bool, format: OutputFormat) -> Result<(), Box<dyn std::error::Error>> {
+    let binding = IssueWorkflowBinding::from_config(Config::global())?;
+    if !run {
+        let queued = binding.queue().list()?;
+        match format {
+            OutputFormat::Table if queued.is_empty() => println!("No issue workflows queued"),
+            OutputFormat::Table => {
+                for entry in &queued {
+                    println!(
+                        "{:<40} {:<24} {}",
+                        entry.issue,
+                        entry.workflow,
+                        entry.queued_at.format("%Y-%m-%d %H:%M:%S")
+                    );
+                }
+            }
+            OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&queued)?),
+            OutputFormat::Yaml => print!("{}", serde_yaml::to_string(&queued)?),
+        }
+        return Ok(());
+    }
+
+    let issues = issue_storage_for_work_dir(&std::env::current_dir()?)?;
+    let mut workflows = WorkflowStorage::file_system()?;
+    let mut outcomes = Vec::new();
+    while let Some(entry) = binding.next_queued()? {
+        let mut outcome = IssueWorkflowOutcome {
+            issue: entry.issue,
+            workflow: entry.workflow,
+            run_id: None,
+            status: None,
+            state: None,
+            message: None,
+        };
+        if let Err(e) =
+            run_queued_issue(&binding, &mut outcome, issues.as_ref(), &mut workflows).await
+        {
+            tracing::warn!(
+                "Workflow '{}' for issue '{}': {}",
+                outcome.workflow,
+                outcome.issue,
+                e
+            );
+            outcome.message = Some(e.to_string());
+        }
+        outcomes.push(outcome);
+    }
+    match format {
+        OutputFormat::Table if outcomes.is_empty() => println!("No issue workflows queued"),
+        OutputFormat::Table => {
+            for outcome in &outcomes {
+                let status = outcome
+                    .status
+                    .map(|status| format!("{status:?}"))
+                    .unwrap_or_else(|| "NotStarted".to_string());
+                let mut line = format!("{:<11} {:<40} {}", status, outcome.issue, outcome.workflow);
+                if let Some(state) = &outcome.state {
+                    line.push_str(&format!(" -> {state}"));
+                }
+                if let Some(message) = &outcome.message {
+                    line.push_str(&format!(": {message}"));
+                }
+                println!("{line}");
+            }
+        }
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&outcomes)?),
+        OutputFormat::Yaml => print!("{}", serde_yaml::to_string(&outcomes)?),
+    }
+
+    let failed = outcomes
+        .iter()
+        .filter(|outcome| outcome.status != Some(WorkflowRunStatus::Completed))
+        .count();
+    if failed > 0 {
+        return Err(format!(
+            "{failed} of {} issue workflows did not complete",
+            outcomes.len()
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// Run a queued issue workflow the way `flow run` does and move its issue on
+async fn run_queued_issue(
+    binding: &IssueWorkflowBinding,
+    outcome: &mut IssueWorkflowOutcome,
+    issues: &dyn IssueStorage,
+    workflows: &mut WorkflowStorage,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if issues.get_issue(&outcome.issue).await?.completed {
+        return Err("the issue is already complete".into());
+    }
+    let workflow = workflows.get_workflow(&WorkflowName::new(&outcome.workflow))?;
+    let run = flow::run_issue_workflow(workflows, &workflow, &outcome.issue).await?;
+    outcome.run_id = Some(run.id);
+    outcome.status = Some(run.status);
+    outcome.message = run.metadata.get(FAILURE_MESSAGE_METADATA_KEY).cloned();
+    outcome.state = binding.settle(issues, &outcome.issue, run.status).await?;
+    Ok(())
+}
+
+/// Issue names and patterns from the arguments, or from stdin one per line
+fn bulk_patterns(patterns: Vec<String>) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    if !patterns.is_empty() && patterns != ["-"] {
+        return Ok(patterns);
+    }
+    if patterns.is_empty() && io::stdin().is_terminal() {
+        return Err("Give issue names or glob patterns, or pipe them on stdin one per line".into());
+    }
+
+    let mut buffer = String::new();
+    io::stdin().read_to_string(&mut buffer)?;
+    Ok(buffer
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect())
+}
@@ -5,7 +5,7 @@ use std::io::{self, Write};
 // Tabled import removed - using custom 2-line format instead
 
 use crate::cli::{OutputFormat, PromptSource, PromptSourceArg};
-use swissarmyhammer::{PromptFilter, PromptLibrary, PromptResolver};
+use swissarmyhammer::{PromptFilter, PromptLibrary, PromptResolver, ResolvedPrompt};
 
 // PromptRow struct removed - using custom 2-line format instead of table
 
@@ -17,6 +17,13 @@ struct PromptInfo {
     source: PromptSource,
     category: Option<String>,
     arguments: Vec<PromptArgument>,
+    deprecated: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    superseded_by: Option<String>,
+    /// Content hash a workflow can pin the prompt to
+    hash: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
 }
 
 #[derive(serde::Serialize)]
@@ -33,6 +40,7 @@ pub fn run_list_command(
     source_filter: Option<PromptSourceArg>,
     category_filter: Option<String>,
     search_term: Option<String>,
+    include_deprecated: bool,
 ) -> Result<()> {
     // Load all prompts from all sources
     let mut library = PromptLibrary::new();
@@ -55,6 +63,8 @@ pub fn run_list_command(
         filter = filter.with_search_term(search);
     }
 
+    filter = filter.with_hide_deprecated(!include_deprecated);
+
     // Get filtered prompts
     let filtered_prompts = library.list_filtered(&filter, &resolver.prompt_sources)?;
 
@@ -111,6 +121,7 @@ pub fn run_list_command(
                 )
             });
 
+        let resolved = ResolvedPrompt::of(prompt);
         prompt_infos.push(PromptInfo {
             name: prompt.name.clone(),
             title,
@@ -118,6 +129,10 @@ pub fn run_list_command(
             source: prompt_source,
             category: prompt.category.clone(),
             arguments,
+            deprecated: prompt.is_deprecated(),
+            superseded_by: prompt.superseded_by().map(str::to_string),
+            hash: resolved.hash,
+            version: resolved.version,
         });
     }
 
@@ -143,7 +158,7 @@ pub fn run_list_command(
 
 fn display_table_to_writer<W: Write>(
     prompt_infos: &[PromptInfo],
-    _verbose: bool,
+    verbose: bool,
     writer: &mut W,
     is_tty: bool,
 ) -> Result<()> {
@@ -182,6 +197,14 @@ fn display_table_to_writer<W: Write>(
             format!("{} | {}", info.name, title)
         };
 
+        let first_line = match (info.deprecated, &info.superseded_by) {
+            (false, _) => first_line,
+            (true, Some(replacement)) => {
+                format!("{first_line} (deprecated, use {replacement})")
+            }
+            (true, None) => format!("{first_line} (deprecated)"),
+        };
+
         // Second line: Full description (indented)
         let second_line = if !description.is_empty() {
             format!("  {description}")
@@ -191,6 +214,13 @@ fn display_table_to_writer<W: Write>(
 
         writeln!(writer, "{first_line}")?;
         writeln!(writer, "{second_line}")?;
+        if verbose {
+            // What a workflow pins the prompt to
+            match &info.version {
+                Some(version) => writeln!(writer, "  {}@{} or @{version}", info.name, info.hash)?,
+                None => writeln!(writer, "  {}@{}", info.name, info.hash)?,
+            }
+        }
         writeln!(writer)?; // Empty line between entries
     }
 
@@ -226,7 +256,7 @@ mod tests {
     #[test]
     fn test_list_command_with_no_prompts() {
         // This test will fail initially, driving the implementation
-        let result = run_list_command(OutputFormat::Table, false, None, None, None);
+        let result = run_list_command(OutputFormat::Table, false, None, None, None, false);
         assert!(result.is_ok());
     }
 
@@ -238,19 +268,20 @@ mod tests {
             None,
             None,
             Some("example".to_string()),
+            false,
         );
         assert!(result.is_ok());
     }
 
     #[test]
     fn test_list_command_json_format() {
-        let result = run_list_command(OutputFormat::Json, false, None, None, None);
+        let result = run_list_command(OutputFormat::Json, false, None, None, None, false);
         assert!(result.is_ok());
     }
 
     #[test]
     fn test_list_command_yaml_format() {
-        let result = run_list_command(OutputFormat::Yaml, false, None, None, None);
+        let result = run_list_command(OutputFormat::Yaml, false, None, None, None, false);
         assert!(result.is_ok());
     }
 
@@ -262,6 +293,7 @@ mod tests {
             Some(PromptSourceArg::Builtin),
             None,
             None,
+            false,
         );
         assert!(result.is_ok());
     }
@@ -278,6 +310,10 @@ mod tests {
                 source: PromptSource::Builtin,
                 category: Some("test".to_string()),
                 arguments: vec![],
+                deprecated: false,
+                superseded_by: None,
+                hash: String::new(),
+                version: None,
             },
             PromptInfo {
                 name: "test_user".to_string(),
@@ -286,6 +322,10 @@ mod tests {
                 source: PromptSource::User,
                 category: Some("test".to_string()),
                 arguments: vec![],
+                deprecated: false,
+                superseded_by: None,
+                hash: String::new(),
+                version: None,
             },
             PromptInfo {
                 name: "test_local".to_string(),
@@ -294,6 +334,10 @@ mod tests {
                 source: PromptSource::Local,
                 category: Some("test".to_string()),
                 arguments: vec![],
+                deprecated: false,
+                superseded_by: None,
+                hash: String::new(),
+                version: None,
             },
         ];
 
@@ -348,6 +392,33 @@ mod tests {
         control::unset_override();
     }
 
+    #[test]
+    fn test_deprecated_prompts_are_marked() {
+        let prompt_infos = vec![PromptInfo {
+            name: "old-review".to_string(),
+            title: Some("Old Review".to_string()),
+            description: None,
+            source: PromptSource::Local,
+            category: None,
+            arguments: vec![],
+            deprecated: true,
+            superseded_by: Some("review/code".to_string()),
+            hash: "sha256:abcd".to_string(),
+            version: Some("1.2".to_string()),
+        }];
+
+        let mut output = Vec::new();
+        display_table_to_writer(&prompt_infos, false, &mut output, false).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("old-review | Old Review (deprecated, use review/code)"));
+        assert!(!output.contains("sha256:abcd"));
+
+        let mut output = Vec::new();
+        display_table_to_writer(&prompt_infos, true, &mut output, false).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("  old-review@sha256:abcd or @1.2"));
+    }
+
     #[test]
     fn test_prompt_info_creation() {
         let info = PromptInfo {
@@ -357,6 +428,10 @@ mod tests {
             source: PromptSource::Builtin,
             category: None,
             arguments: vec![],
+            deprecated: false,
+            superseded_by: None,
+            hash: String::new(),
+            version: None,
         };
 
         assert_eq!(info.name, "test");
@@ -1,69 +1,235 @@
-/// A thread-safe writer wrapper that ensures immediate flushing and disk synchronization for MCP logging.
-///
-/// This struct wraps a `File` in `Arc<Mutex<>>` to provide thread-safe access while ensuring
-/// that all writes are immediately flushed to the operating system and synced to disk.
-/// This behavior is critical for MCP (Model Context Protocol) servers where log data must
-/// be immediately available for debugging purposes.
-///
-/// # Thread Safety
-///
-/// Multiple threads can safely write to the same `FileWriterGuard` instance. Each write
-/// operation acquires the mutex lock, writes the data, flushes the OS buffer, and
-/// synchronizes to disk before releasing the lock.
+use chrono::{DateTime, Local, NaiveDate};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use swissarmyhammer::config::Config;
+
+/// Directory MCP mode writes its log file to
+pub const LOG_DIR: &str = ".swissarmyhammer";
+
+/// Name of the MCP log file unless `SWISSARMYHAMMER_LOG_FILE` names another
+pub const DEFAULT_LOG_FILE: &str = "mcp.log";
+
+/// Path of the active MCP log file
+pub fn mcp_log_path() -> PathBuf {
+    let name =
+        std::env::var("SWISSARMYHAMMER_LOG_FILE").unwrap_or_else(|_| DEFAULT_LOG_FILE.to_string());
+    Path::new(LOG_DIR).join(name)
+}
+
+/// Path of the `index`th rotated log file, `mcp.log.1` being the newest
+pub fn rotated_log_path(path: &Path, index: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{index}"));
+    PathBuf::from(name)
+}
+
+/// When the log file is rotated and how many rotated files are kept
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LogRotation {
+    /// Rotate before the file grows beyond this many bytes, 0 for never
+    pub max_bytes: u64,
+    /// Rotate when the local date changes
+    pub daily: bool,
+    /// Number of rotated files kept, older ones are deleted
+    pub retention: usize,
+}
+
+impl LogRotation {
+    /// Rotation settings from the configuration
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            max_bytes: config.log_max_size_mb.saturating_mul(1024 * 1024),
+            daily: config.log_rotate_daily,
+            retention: config.log_retention,
+        }
+    }
+}
+
+/// The log file currently written to
+struct ActiveLog {
+    path: PathBuf,
+    rotation: LogRotation,
+    file: File,
+    size: u64,
+    date: NaiveDate,
+}
+
+impl ActiveLog {
+    fn open(path: PathBuf, rotation: LogRotation) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let metadata = file.metadata()?;
+        // A file last written on an earlier day is rotated on the first write
+        let date = metadata
+            .modified()
+            .map(|modified| DateTime::<Local>::from(modified).date_naive())
+            .unwrap_or_else(|_| Local::now().date_naive());
+        Ok(Self {
+            path,
+            rotation,
+            file,
+            size: metadata.len(),
+            date,
+        })
+    }
+
+    fn needs_rotation(&self, incoming: usize, today: NaiveDate) -> bool {
+        if self.size == 0 {
+            return false;
+        }
+        let too_large = self.rotation.max_bytes > 0
+            && self.size.saturating_add(incoming as u64) > self.rotation.max_bytes;
+        let new_day = self.rotation.daily && today != self.date;
+        too_large || new_day
+    }
+
+    /// Shift `mcp.log.N` to `mcp.log.N+1`, dropping the oldest, and start a new file
+    fn rotate(&mut self, today: NaiveDate) -> io::Result<()> {
+        self.date = today;
+        let retention = self.rotation.retention;
+        if retention == 0 {
+            self.file.set_len(0)?;
+        } else {
+            let oldest = rotated_log_path(&self.path, retention);
+            if oldest.exists() {
+                fs::remove_file(oldest)?;
+            }
+            for index in (1..retention).rev() {
+                let from = rotated_log_path(&self.path, index);
+                if from.exists() {
+                    fs::rename(from, rotated_log_path(&self.path, index + 1))?;
+                }
+            }
+            fs::rename(&self.path, rotated_log_path(&self.path, 1))?;
+            self.file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.path)?;
+        }
+        self.size = 0;
+        Ok(())
+    }
+}
+
+/// A thread-safe log file writer that rotates the file as it grows.
 ///
-/// # Performance Considerations
+/// Before a write would take the file past its size limit, or on the first
+/// write of a new day, the file is renamed to `<file>.1`, earlier rotations
+/// move up by one, and the ones beyond the retention count are deleted. A
+/// file that cannot be rotated keeps being written to.
 ///
-/// This implementation prioritizes data reliability over performance by calling `sync_all()`
-/// on every write operation. This ensures data is written to disk immediately but may
-/// impact performance in high-throughput scenarios.
+/// Every write is flushed and synced to disk before it returns, since MCP log
+/// data must be available for debugging even if the server dies right after.
 ///
 /// # Example
 ///
 /// ```no_run
-/// use std::sync::{Arc, Mutex};
-/// use std::fs::File;
-/// use swissarmyhammer_cli::logging::FileWriterGuard;
-///
-/// let file = File::create("log.txt").unwrap();
-/// let shared_file = Arc::new(Mutex::new(file));
-/// let mut guard = FileWriterGuard::new(shared_file);
+/// use std::io::Write;
+/// use swissarmyhammer_cli::logging::{LogRotation, RotatingFileWriter};
 ///
-/// // This write will be immediately flushed and synced to disk
-/// guard.write_all(b"Log message\n").unwrap();
+/// let rotation = LogRotation {
+///     max_bytes: 10 * 1024 * 1024,
+///     daily: true,
+///     retention: 5,
+/// };
+/// let mut writer = RotatingFileWriter::open("mcp.log", rotation).unwrap();
+/// writer.write_all(b"Log message\n").unwrap();
 /// ```
-pub struct FileWriterGuard {
-    file: std::sync::Arc<std::sync::Mutex<std::fs::File>>,
+#[derive(Clone)]
+pub struct RotatingFileWriter {
+    log: Arc<Mutex<ActiveLog>>,
 }
 
-impl FileWriterGuard {
-    /// Creates a new `FileWriterGuard` wrapping the given file.
-    ///
-    /// # Arguments
-    ///
-    /// * `file` - A thread-safe reference to a file that will be written to
-    ///
-    /// # Returns
-    ///
-    /// A new `FileWriterGuard` instance that will ensure immediate flushing for all writes
-    pub fn new(file: std::sync::Arc<std::sync::Mutex<std::fs::File>>) -> Self {
-        Self { file }
+impl RotatingFileWriter {
+    /// Opens the log file for appending, creating it if needed
+    pub fn open(path: impl Into<PathBuf>, rotation: LogRotation) -> io::Result<Self> {
+        Ok(Self {
+            log: Arc::new(Mutex::new(ActiveLog::open(path.into(), rotation)?)),
+        })
     }
 }
 
-impl std::io::Write for FileWriterGuard {
-    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
-        let mut file = self.file.lock().expect("FileWriterGuard mutex was poisoned - this indicates a panic occurred while another thread held the lock");
-        let result = file.write(buf)?;
-        file.flush()?; // Flush immediately for MCP mode
-                       // Ensure data is written to disk immediately
-        file.sync_all()?;
-        Ok(result)
+impl Write for RotatingFileWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut log = self.log.lock().expect("RotatingFileWriter mutex was poisoned - this indicates a panic occurred while another thread held the lock");
+        let today = Local::now().date_naive();
+        if log.needs_rotation(buf.len(), today) {
+            // Losing log lines is worse than an oversized file, so a failed
+            // rotation carries on with the current file
+            let _ = log.rotate(today);
+        }
+        log.file.write_all(buf)?;
+        log.size += buf.len() as u64;
+        log.file.flush()?;
+        log.file.sync_all()?;
+        Ok(buf.len())
     }
 
-    fn flush(&mut self) -> std::io::Result<()> {
-        let mut file = self.file.lock().expect("FileWriterGuard flush mutex was poisoned - this indicates a panic occurred while another thread held the lock");
-        file.flush()?;
-        file.sync_all()?; // Force sync to disk
-        Ok(())
+    fn flush(&mut self) -> io::Result<()> {
+        let mut log = self.log.lock().expect("RotatingFileWriter flush mutex was poisoned - this indicates a panic occurred while another thread held the lock");
+        log.file.flush()?;
+        log.file.sync_all()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read(path: &Path) -> String {
+        fs::read_to_string(path).unwrap()
+    }
+
+    #[test]
+    fn test_rotates_by_size_and_keeps_retention() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("mcp.log");
+        let rotation = LogRotation {
+            max_bytes: 12,
+            daily: false,
+            retention: 2,
+        };
+        let mut writer = RotatingFileWriter::open(&path, rotation).unwrap();
+        for line in ["first\n", "second\n", "third\n", "fourth\n"] {
+            writer.write_all(line.as_bytes()).unwrap();
+        }
+
+        assert_eq!(read(&path), "fourth\n");
+        assert_eq!(read(&rotated_log_path(&path, 1)), "third\n");
+        assert_eq!(read(&rotated_log_path(&path, 2)), "second\n");
+        assert!(!rotated_log_path(&path, 3).exists());
+    }
+
+    #[test]
+    fn test_rotates_on_new_day() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("mcp.log");
+        let rotation = LogRotation {
+            max_bytes: 0,
+            daily: true,
+            retention: 1,
+        };
+        let mut writer = RotatingFileWriter::open(&path, rotation).unwrap();
+        writer.write_all(b"yesterday\n").unwrap();
+        {
+            let mut log = writer.log.lock().unwrap();
+            log.date = log.date.pred_opt().unwrap();
+        }
+        writer.write_all(b"today\n").unwrap();
+
+        assert_eq!(read(&path), "today\n");
+        assert_eq!(read(&rotated_log_path(&path, 1)), "yesterday\n");
+
+        // Without retention the file is truncated in place
+        let rotation = LogRotation {
+            max_bytes: 8,
+            daily: false,
+            retention: 0,
+        };
+        let mut writer = RotatingFileWriter::open(&path, rotation).unwrap();
+        writer.write_all(b"again\n").unwrap();
+        assert_eq!(read(&path), "again\n");
+        assert!(!rotated_log_path(&path, 2).exists());
     }
 }
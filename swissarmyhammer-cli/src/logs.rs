@@ -0,0 +1,145 @@
+//! Reading the MCP server log
+
+use crate::cli::LogsSubcommand;
+use crate::logging::mcp_log_path;
+use anyhow::{bail, Result};
+use std::fs::{File, Metadata};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::time::Duration;
+
+/// How often a followed log file is checked for new lines
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Bytes read at a time while looking for the last lines of a file
+const TAIL_CHUNK_SIZE: u64 = 8 * 1024;
+
+pub fn run_logs_command(subcommand: LogsSubcommand) -> Result<()> {
+    match subcommand {
+        LogsSubcommand::Tail { lines, no_follow } => tail(&mcp_log_path(), lines, !no_follow),
+    }
+}
+
+/// Print the last lines of the log, then keep printing lines as they are
+/// written, moving on to the new file whenever the log is rotated
+fn tail(path: &Path, lines: usize, follow: bool) -> Result<()> {
+    let mut stdout = io::stdout();
+    let mut file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::NotFound && follow => wait_for(path)?,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            bail!("No log file at {}", path.display())
+        }
+        Err(e) => return Err(e.into()),
+    };
+    let mut position = last_lines_start(&mut file, lines)?;
+    position += copy_from(&mut file, position, &mut stdout)?;
+    if !follow {
+        return Ok(());
+    }
+
+    loop {
+        std::thread::sleep(POLL_INTERVAL);
+        let Ok(current) = std::fs::metadata(path) else {
+            continue;
+        };
+        if !is_same_file(&file.metadata()?, &current) || current.len() < position {
+            file = match File::open(path) {
+                Ok(file) => file,
+                Err(_) => continue,
+            };
+            position = 0;
+        }
+        position += copy_from(&mut file, position, &mut stdout)?;
+    }
+}
+
+fn wait_for(path: &Path) -> Result<File> {
+    eprintln!("Waiting for {} to be created...", path.display());
+    loop {
+        if let Ok(file) = File::open(path) {
+            return Ok(file);
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Offset at which the last `lines` lines of the file start
+///
+/// The file is read backwards a chunk at a time, so only the end of a large
+/// log is ever read.
+fn last_lines_start(file: &mut File, lines: usize) -> io::Result<u64> {
+    let len = file.metadata()?.len();
+    if lines == 0 {
+        return Ok(len);
+    }
+    let mut found = 0;
+    let mut end = len;
+    let mut chunk = Vec::new();
+    while end > 0 {
+        let start = end.saturating_sub(TAIL_CHUNK_SIZE);
+        chunk.resize((end - start) as usize, 0);
+        file.seek(SeekFrom::Start(start))?;
+        file.read_exact(&mut chunk)?;
+        for (offset, byte) in chunk.iter().enumerate().rev() {
+            let at = start + offset as u64;
+            // A trailing newline ends the last line rather than starting another
+            if *byte == b'\n' && at + 1 != len {
+                found += 1;
+                if found == lines {
+                    return Ok(at + 1);
+                }
+            }
+        }
+        end = start;
+    }
+    Ok(0)
+}
+
+/// Copy everything after `position` to the output, returning the bytes copied
+fn copy_from(file: &mut File, position: u64, output: &mut impl Write) -> io::Result<u64> {
+    file.seek(SeekFrom::Start(position))?;
+    let copied = io::copy(file, output)?;
+    output.flush()?;
+    Ok(copied)
+}
+
+#[cfg(unix)]
+fn is_same_file(a: &Metadata, b: &Metadata) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    a.dev() == b.dev() && a.ino() == b.ino()
+}
+
+/// Without file identities a rotation is noticed by the file shrinking
+#[cfg(not(unix))]
+fn is_same_file(_a: &Metadata, _b: &Metadata) -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tail_of(content: &str, lines: usize) -> String {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("mcp.log");
+        std::fs::write(&path, content).unwrap();
+        let mut file = File::open(&path).unwrap();
+        let start = last_lines_start(&mut file, lines).unwrap();
+        let mut output = Vec::new();
+        copy_from(&mut file, start, &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn test_last_lines() {
+        assert_eq!(tail_of("a\nb\nc\n", 2), "b\nc\n");
+        assert_eq!(tail_of("a\nb\nc", 2), "b\nc");
+        assert_eq!(tail_of("a\nb\n", 5), "a\nb\n");
+        assert_eq!(tail_of("a\nb\n", 0), "");
+        assert_eq!(tail_of("", 3), "");
+
+        let long: String = (0..5000).map(|i| format!("line {i}\n")).collect();
+        assert_eq!(tail_of(&long, 2), "line 4998\nline 4999\n");
+    }
+}
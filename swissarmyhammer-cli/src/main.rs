@@ -1,17 +1,33 @@
 use std::process;
+mod api;
+mod audit;
+mod backup;
+mod bench;
 mod cli;
 mod completions;
+mod debugger;
+mod deps;
+mod diff_sources;
 mod doctor;
 mod error;
 mod exit_codes;
+mod export;
 mod flow;
+mod history;
+mod import;
 mod issue;
 mod list;
 mod logging;
+mod logs;
 mod mcp_integration;
 mod memo;
+mod pick;
 // prompt_loader module removed - using SDK's PromptResolver directly
+mod progress;
 mod prompt;
+mod prompt_runs;
+mod query;
+mod scaffold;
 mod search;
 mod signal_handler;
 mod test;
@@ -20,7 +36,13 @@ mod validate;
 use clap::CommandFactory;
 use cli::{Cli, Commands};
 use exit_codes::{EXIT_ERROR, EXIT_SUCCESS, EXIT_WARNING};
-use logging::FileWriterGuard;
+use logging::{LogRotation, RotatingFileWriter};
+
+/// Counts allocations so `prompt bench` can report what a render allocates,
+/// only in builds with the `alloc-stats` feature
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static ALLOCATOR: swissarmyhammer::CountingAllocator = swissarmyhammer::CountingAllocator::new();
 
 #[tokio::main]
 async fn main() {
@@ -39,7 +61,12 @@ async fn main() {
     // Configure logging based on verbosity flags and MCP mode detection
     use is_terminal::IsTerminal;
     let is_mcp_mode =
-        matches!(cli.command, Some(Commands::Serve)) && !std::io::stdin().is_terminal();
+        matches!(cli.command, Some(Commands::Serve { .. })) && !std::io::stdin().is_terminal();
+
+    let shows_flow_progress = match &cli.command {
+        Some(Commands::Flow { subcommand }) => flow::shows_progress(subcommand),
+        _ => false,
+    };
 
     let log_level = if is_mcp_mode {
         Level::DEBUG // More verbose for MCP mode to help with debugging
@@ -49,6 +76,8 @@ async fn main() {
         Level::DEBUG
     } else if cli.verbose {
         Level::TRACE
+    } else if shows_flow_progress {
+        Level::WARN // The progress display replaces info log lines
     } else {
         Level::INFO
     };
@@ -65,40 +94,23 @@ async fn main() {
     };
 
     if is_mcp_mode {
-        // In MCP mode, write logs to .swissarmyhammer/log for debugging
-        use std::fs;
-        use std::path::PathBuf;
-
-        let log_dir = PathBuf::from(".swissarmyhammer");
+        // In MCP mode, write logs to .swissarmyhammer/mcp.log for debugging
+        let log_file = logging::mcp_log_path();
 
         // Ensure the directory exists
-        if let Err(e) = fs::create_dir_all(&log_dir) {
+        if let Err(e) = std::fs::create_dir_all(logging::LOG_DIR) {
             tracing::warn!("Failed to create log directory: {}", e);
         }
 
-        let log_filename =
-            std::env::var("SWISSARMYHAMMER_LOG_FILE").unwrap_or_else(|_| "mcp.log".to_string());
-        let log_file = log_dir.join(log_filename);
-
-        // Try to open the log file - use unbuffered writing for immediate flushing
-        match std::fs::OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(&log_file)
-        {
-            Ok(file) => {
-                // Use Arc<Mutex<File>> for thread-safe, unbuffered writing
-                use std::sync::{Arc, Mutex};
-                let shared_file = Arc::new(Mutex::new(file));
-
+        // Try to open the log file - rotated so it cannot grow without bound
+        let rotation = LogRotation::from_config(swissarmyhammer::config::Config::global());
+        match RotatingFileWriter::open(&log_file, rotation) {
+            Ok(writer) => {
                 registry()
                     .with(create_filter())
                     .with(
                         fmt::layer()
-                            .with_writer(move || {
-                                let file = shared_file.clone();
-                                Box::new(FileWriterGuard::new(file)) as Box<dyn std::io::Write>
-                            })
+                            .with_writer(move || writer.clone())
                             .with_ansi(false), // No color codes in file
                     )
                     .init();
@@ -119,14 +131,30 @@ async fn main() {
             .init();
     }
 
+    // Skip prompt sources the user excluded before anything loads prompts
+    swissarmyhammer::SourceFilter::set_global(cli.source_filter());
+
+    // Announce events to the configured webhooks while the command runs
+    let webhooks = swissarmyhammer::webhooks::WebhookSender::spawn_configured();
+
+    // Queue workflows for the issues the command creates; `issue queue --run`
+    // runs them
+    let creates_issues = matches!(
+        cli.command,
+        Some(Commands::Serve { read_only: false } | Commands::Issue { .. } | Commands::Flow { .. })
+    );
+    let issue_workflows = creates_issues
+        .then(swissarmyhammer::workflow::IssueWorkflowBinding::spawn_configured)
+        .flatten();
+
     let exit_code = match cli.command {
-        Some(Commands::Serve) => {
+        Some(Commands::Serve { read_only }) => {
             tracing::info!("Starting MCP server");
-            run_server().await
+            run_server(read_only).await
         }
-        Some(Commands::Doctor) => {
+        Some(Commands::Doctor { fix, yes }) => {
             tracing::info!("Running diagnostics");
-            run_doctor()
+            run_doctor(fix, yes)
         }
         Some(Commands::Prompt { subcommand }) => {
             tracing::info!("Running prompt command");
@@ -144,9 +172,16 @@ async fn main() {
             quiet,
             format,
             workflow_dirs,
+            impact,
+            watch,
+            jobs,
         }) => {
             tracing::info!("Running validate command");
-            run_validate(quiet, format, workflow_dirs)
+            if watch {
+                run_validate_watch(quiet, format, jobs).await
+            } else {
+                run_validate(quiet, format, workflow_dirs, impact, jobs)
+            }
         }
         Some(Commands::Issue { subcommand }) => {
             tracing::info!("Running issue command");
@@ -160,12 +195,43 @@ async fn main() {
             tracing::info!("Running search command");
             run_search(subcommand).await
         }
+        Some(Commands::Query { query, format }) => {
+            tracing::info!("Running query command");
+            run_query(query, format).await
+        }
+        Some(Commands::Pick { query, kind, print }) => {
+            tracing::info!("Running pick command");
+            run_pick(query, kind, print).await
+        }
+        Some(Commands::Logs { subcommand }) => {
+            tracing::info!("Running logs command");
+            run_logs(subcommand)
+        }
+        Some(Commands::Api { addr }) => {
+            tracing::info!("Starting API server");
+            api::run_api_server(addr).await
+        }
+        Some(Commands::Backup { subcommand }) => {
+            tracing::info!("Running backup command");
+            run_backup(subcommand)
+        }
         None => {
             // This case is handled early above for performance
             unreachable!()
         }
     };
 
+    // Queue workflows for the issues created and deliver the events the
+    // command published before exiting
+    if let Some(issue_workflows) = issue_workflows {
+        issue_workflows
+            .finish(std::time::Duration::from_secs(5))
+            .await;
+    }
+    if let Some(webhooks) = webhooks {
+        webhooks.finish(std::time::Duration::from_secs(30)).await;
+    }
+
     // Ensure all logs are flushed before process exit
     if is_mcp_mode {
         // Give tracing sufficient time to flush any pending logs
@@ -175,14 +241,18 @@ async fn main() {
     process::exit(exit_code);
 }
 
-async fn run_server() -> i32 {
+async fn run_server(read_only: bool) -> i32 {
     use rmcp::serve_server;
     use rmcp::transport::io::stdio;
     use swissarmyhammer::{mcp::McpServer, PromptLibrary};
 
     // Create library and server
-    let library = PromptLibrary::new();
+    let library = PromptLibrary::from_config();
     let server = match McpServer::new(library) {
+        Ok(server) if read_only => {
+            tracing::info!("Serving read-only tools only");
+            server.with_read_only()
+        }
         Ok(server) => server,
         Err(e) => {
             tracing::error!("Failed to create MCP server: {}", e);
@@ -231,11 +301,16 @@ async fn run_server() -> i32 {
     EXIT_SUCCESS
 }
 
-fn run_doctor() -> i32 {
+fn run_doctor(fix: bool, yes: bool) -> i32 {
     use doctor::Doctor;
 
     let mut doctor = Doctor::new();
-    match doctor.run_diagnostics() {
+    let result = if fix {
+        doctor.run_fixes(yes)
+    } else {
+        doctor.run_diagnostics()
+    };
+    match result {
         Ok(exit_code) => exit_code,
         Err(e) => {
             tracing::error!("Doctor error: {}", e);
@@ -251,6 +326,68 @@ async fn run_prompt(subcommand: cli::PromptSubcommand) -> i32 {
     handle_cli_result(prompt::run_prompt_command(subcommand).await)
 }
 
+async fn run_query(query: String, format: cli::QueryFormat) -> i32 {
+    match query::run_query_command(query, format).await {
+        Ok(_) => EXIT_SUCCESS,
+        Err(e) => {
+            tracing::error!("Query error: {}", e);
+            EXIT_WARNING
+        }
+    }
+}
+
+async fn run_pick(query: Option<String>, kinds: Vec<cli::PickKind>, print: bool) -> i32 {
+    let args = match pick::run_pick_command(query, kinds).await {
+        Ok(Some(args)) => args,
+        Ok(None) => return EXIT_SUCCESS,
+        Err(e) => {
+            tracing::error!("Pick error: {}", e);
+            return EXIT_WARNING;
+        }
+    };
+    if print {
+        println!("{}", pick::command_line(&args));
+        return EXIT_SUCCESS;
+    }
+
+    let cli = match Cli::try_parse_from_args(
+        std::iter::once("swissarmyhammer".to_string()).chain(args),
+    ) {
+        Ok(cli) => cli,
+        Err(e) => {
+            tracing::error!("Pick error: {}", e);
+            return EXIT_WARNING;
+        }
+    };
+    match cli.command {
+        Some(Commands::Prompt { subcommand }) => run_prompt(subcommand).await,
+        Some(Commands::Issue { subcommand }) => run_issue(subcommand).await,
+        Some(Commands::Memo { subcommand }) => run_memo(subcommand).await,
+        Some(Commands::Flow { subcommand }) => run_flow(subcommand).await,
+        _ => EXIT_SUCCESS,
+    }
+}
+
+fn run_logs(subcommand: cli::LogsSubcommand) -> i32 {
+    match logs::run_logs_command(subcommand) {
+        Ok(_) => EXIT_SUCCESS,
+        Err(e) => {
+            tracing::error!("Logs error: {}", e);
+            EXIT_WARNING
+        }
+    }
+}
+
+fn run_backup(subcommand: cli::BackupSubcommand) -> i32 {
+    match backup::run_backup_command(subcommand) {
+        Ok(_) => EXIT_SUCCESS,
+        Err(e) => {
+            tracing::error!("Backup error: {}", e);
+            EXIT_WARNING
+        }
+    }
+}
+
 fn run_completions(shell: clap_complete::Shell) -> i32 {
     use completions;
 
@@ -334,10 +471,27 @@ async fn run_search(subcommand: cli::SearchCommands) -> i32 {
 /// - 0: Success (no errors or warnings)
 /// - 1: Warnings found
 /// - 2: Errors found
-fn run_validate(quiet: bool, format: cli::ValidateFormat, workflow_dirs: Vec<String>) -> i32 {
+fn run_validate(
+    quiet: bool,
+    format: cli::ValidateFormat,
+    workflow_dirs: Vec<String>,
+    impact: Option<std::path::PathBuf>,
+    jobs: usize,
+) -> i32 {
     use validate;
 
-    match validate::run_validate_command_with_dirs(quiet, format, workflow_dirs) {
+    match validate::run_validate_command_with_dirs(quiet, format, workflow_dirs, impact, jobs) {
+        Ok(exit_code) => exit_code,
+        Err(e) => {
+            tracing::error!("Validate error: {}", e);
+            EXIT_ERROR
+        }
+    }
+}
+
+/// Validate prompts and workflows, then again after every change until Ctrl+C
+async fn run_validate_watch(quiet: bool, format: cli::ValidateFormat, jobs: usize) -> i32 {
+    match validate::run_validate_watch(quiet, format, jobs).await {
         Ok(exit_code) => exit_code,
         Err(e) => {
             tracing::error!("Validate error: {}", e);
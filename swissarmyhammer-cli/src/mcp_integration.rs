@@ -14,6 +14,9 @@ use tokio::sync::{Mutex, RwLock};
 /// Type alias for issue storage to reduce complexity
 type IssueStorageArc = Arc<RwLock<Box<dyn swissarmyhammer::issues::IssueStorage>>>;
 
+/// Type alias for memo storage to reduce complexity
+type MemoStorageArc = Arc<RwLock<Box<dyn swissarmyhammer::memoranda::MemoStorage>>>;
+
 /// CLI-specific tool context that can create and execute MCP tools
 pub struct CliToolContext {
     tool_registry: ToolRegistry,
@@ -57,10 +60,9 @@ impl CliToolContext {
     fn create_issue_storage(
         current_dir: &std::path::Path,
     ) -> Result<IssueStorageArc, Box<dyn std::error::Error>> {
-        let issues_dir = current_dir.join("issues");
-        Ok(Arc::new(RwLock::new(Box::new(
-            swissarmyhammer::issues::FileSystemIssueStorage::new(issues_dir)?,
-        ))))
+        Ok(Arc::new(RwLock::new(
+            swissarmyhammer::issues::issue_storage_for_work_dir(current_dir)?,
+        )))
     }
 
     /// Create git operations handler
@@ -69,15 +71,16 @@ impl CliToolContext {
     }
 
     /// Create memo storage backend
-    fn create_memo_storage(
-        _current_dir: &std::path::Path,
-    ) -> Arc<RwLock<Box<dyn swissarmyhammer::memoranda::MemoStorage>>> {
+    ///
+    /// Memos are kept in the same markdown storage the MCP server uses, so
+    /// every memo command sees the memos, notebooks and history of the others.
+    fn create_memo_storage(current_dir: &std::path::Path) -> MemoStorageArc {
         // Use new_default() to respect SWISSARMYHAMMER_MEMOS_DIR environment variable
-        let storage = swissarmyhammer::memoranda::storage::FileSystemMemoStorage::new_default()
+        let storage = swissarmyhammer::memoranda::MarkdownMemoStorage::new_default()
             .unwrap_or_else(|_| {
-                // Fallback to current directory if new_default() fails
-                swissarmyhammer::memoranda::storage::FileSystemMemoStorage::new(
-                    _current_dir.to_path_buf(),
+                // Fallback to the project's memos directory if new_default() fails
+                swissarmyhammer::memoranda::MarkdownMemoStorage::new(
+                    swissarmyhammer::memoranda::storage::memos_dir_for_work_dir(current_dir),
                 )
             });
         Arc::new(RwLock::new(Box::new(storage)))
@@ -85,7 +88,7 @@ impl CliToolContext {
 
     /// Create tool handlers for backward compatibility
     fn create_tool_handlers(
-        memo_storage: Arc<RwLock<Box<dyn swissarmyhammer::memoranda::MemoStorage>>>,
+        memo_storage: MemoStorageArc,
     ) -> Arc<swissarmyhammer::mcp::tool_handlers::ToolHandlers> {
         Arc::new(swissarmyhammer::mcp::tool_handlers::ToolHandlers::new(
             memo_storage,
@@ -122,6 +125,11 @@ impl CliToolContext {
         }
     }
 
+    /// Memo storage the memo tools read and write
+    pub fn memo_storage(&self) -> &MemoStorageArc {
+        &self.tool_context.memo_storage
+    }
+
     /// Helper to convert CLI arguments to MCP tool arguments
     pub fn create_arguments(
         &self,
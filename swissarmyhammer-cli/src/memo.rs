@@ -1,21 +1,27 @@
-use crate::cli::MemoCommands;
-use crate::mcp_integration::CliToolContext;
+use crate::cli::{MemoCommands, NotebookCommands, OutputFormat};
+use crate::history::print_history;
+use crate::mcp_integration::{response_formatting, CliToolContext};
 use rmcp::model::CallToolResult;
 use serde_json::json;
 use std::io::{self, Read};
+use swissarmyhammer::memoranda::MemoId;
 
 pub async fn handle_memo_command(command: MemoCommands) -> Result<(), Box<dyn std::error::Error>> {
     let context = CliToolContext::new().await?;
 
     match command {
-        MemoCommands::Create { title, content } => {
-            create_memo(&context, title, content).await?;
+        MemoCommands::Create {
+            title,
+            content,
+            notebook,
+        } => {
+            create_memo(&context, title, content, notebook).await?;
         }
-        MemoCommands::List => {
-            list_memos(&context).await?;
+        MemoCommands::List { format, notebook } => {
+            list_memos(&context, format, notebook).await?;
         }
-        MemoCommands::Get { id } => {
-            get_memo(&context, &id).await?;
+        MemoCommands::Get { id, format } => {
+            get_memo(&context, &id, format).await?;
         }
         MemoCommands::Update { id, content } => {
             update_memo(&context, &id, content).await?;
@@ -23,11 +29,32 @@ pub async fn handle_memo_command(command: MemoCommands) -> Result<(), Box<dyn st
         MemoCommands::Delete { id } => {
             delete_memo(&context, &id).await?;
         }
-        MemoCommands::Search { query } => {
-            search_memos(&context, &query).await?;
+        MemoCommands::Search { query, format } => {
+            search_memos(&context, &query, format).await?;
         }
-        MemoCommands::Context => {
-            get_context(&context).await?;
+        MemoCommands::Context { query, max_tokens } => {
+            get_context(&context, query, max_tokens).await?;
+        }
+        MemoCommands::Verify { restore } => {
+            verify_memos(&context, restore).await?;
+        }
+        MemoCommands::Compact { dry_run } => {
+            compact_memos(&context, dry_run).await?;
+        }
+        MemoCommands::Dedupe { threshold, merge } => {
+            dedupe_memos(&context, threshold, merge).await?;
+        }
+        MemoCommands::History { id, format } => {
+            show_memo_history(&context, &id, format).await?;
+        }
+        MemoCommands::Restore { id, version } => {
+            restore_memo(&context, &id, version).await?;
+        }
+        MemoCommands::Move { id, notebook } => {
+            move_memo(&context, &id, notebook.as_deref()).await?;
+        }
+        MemoCommands::Notebook { subcommand } => {
+            handle_notebook_command(&context, subcommand).await?;
         }
     }
 
@@ -38,10 +65,15 @@ async fn create_memo(
     context: &CliToolContext,
     title: String,
     content: Option<String>,
+    notebook: Option<String>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let content = get_content_input(content)?;
 
-    let args = context.create_arguments(vec![("title", json!(title)), ("content", json!(content))]);
+    let mut args = vec![("title", json!(title)), ("content", json!(content))];
+    if let Some(notebook) = notebook {
+        args.push(("notebook", json!(notebook)));
+    }
+    let args = context.create_arguments(args);
 
     let result = context.execute_tool("memo_create", args).await?;
 
@@ -49,21 +81,42 @@ async fn create_memo(
     Ok(())
 }
 
-async fn list_memos(context: &CliToolContext) -> Result<(), Box<dyn std::error::Error>> {
-    let args = context.create_arguments(vec![]);
+async fn list_memos(
+    context: &CliToolContext,
+    format: OutputFormat,
+    notebook: Option<String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = vec![("format", json!(tool_format(&format)))];
+    if let Some(notebook) = notebook {
+        args.push(("notebook", json!(notebook)));
+    }
+    let args = context.create_arguments(args);
     let result = context.execute_tool("memo_list", args).await?;
 
-    println!("{}", format_list_memo_response(&result));
+    match format {
+        OutputFormat::Table => println!("{}", format_list_memo_response(&result)),
+        _ => print_structured_response(&result, &format)?,
+    }
     Ok(())
 }
 
-async fn get_memo(context: &CliToolContext, id: &str) -> Result<(), Box<dyn std::error::Error>> {
-    let args = context.create_arguments(vec![("id", json!(id))]);
+async fn get_memo(
+    context: &CliToolContext,
+    id: &str,
+    format: OutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let args = context.create_arguments(vec![
+        ("id", json!(id)),
+        ("format", json!(tool_format(&format))),
+    ]);
     let result = context.execute_tool("memo_get", args).await;
 
     match result {
         Ok(result) => {
-            println!("{}", format_get_memo_response(&result));
+            match format {
+                OutputFormat::Table => println!("{}", format_get_memo_response(&result)),
+                _ => print_structured_response(&result, &format)?,
+            }
             Ok(())
         }
         Err(e) => {
@@ -136,22 +189,193 @@ async fn delete_memo(context: &CliToolContext, id: &str) -> Result<(), Box<dyn s
 async fn search_memos(
     context: &CliToolContext,
     query: &str,
+    format: OutputFormat,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let args = context.create_arguments(vec![("query", json!(query))]);
+    let args = context.create_arguments(vec![
+        ("query", json!(query)),
+        ("format", json!(tool_format(&format))),
+    ]);
     let result = context.execute_tool("memo_search", args).await?;
 
-    println!("{}", format_search_memo_response(&result, query));
+    match format {
+        OutputFormat::Table => println!("{}", format_search_memo_response(&result, query)),
+        _ => print_structured_response(&result, &format)?,
+    }
+    Ok(())
+}
+
+/// Format to request from a memo tool; YAML is converted from the tool's JSON
+fn tool_format(format: &OutputFormat) -> &'static str {
+    match format {
+        OutputFormat::Table => "table",
+        OutputFormat::Json | OutputFormat::Yaml => "json",
+    }
+}
+
+/// Print a memo tool's JSON response as JSON or YAML
+fn print_structured_response(
+    result: &CallToolResult,
+    format: &OutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let text =
+        response_formatting::extract_text_content(result).ok_or("Memo response was empty")?;
+    match format {
+        OutputFormat::Yaml => {
+            let value: serde_json::Value = serde_json::from_str(&text)?;
+            print!("{}", serde_yaml::to_string(&value)?);
+        }
+        _ => println!("{}", text.trim_end()),
+    }
     Ok(())
 }
 
-async fn get_context(context: &CliToolContext) -> Result<(), Box<dyn std::error::Error>> {
-    let args = context.create_arguments(vec![]);
+async fn get_context(
+    context: &CliToolContext,
+    query: Option<String>,
+    max_tokens: Option<usize>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = Vec::new();
+    if let Some(query) = query {
+        args.push(("query", json!(query)));
+    }
+    if let Some(max_tokens) = max_tokens {
+        args.push(("max_tokens", json!(max_tokens)));
+    }
+    let args = context.create_arguments(args);
     let result = context.execute_tool("memo_get_all_context", args).await?;
 
     println!("{}", format_context_memo_response(&result));
     Ok(())
 }
 
+async fn verify_memos(
+    context: &CliToolContext,
+    restore: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let args = context.create_arguments(vec![("restore", json!(restore))]);
+    let result = context.execute_tool("memo_verify", args).await?;
+
+    if result.is_error.unwrap_or(false) {
+        eprintln!("{}", response_formatting::format_error_response(&result));
+        std::process::exit(1);
+    }
+    println!("{}", response_formatting::format_success_response(&result));
+    Ok(())
+}
+
+async fn compact_memos(
+    context: &CliToolContext,
+    dry_run: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let args = context.create_arguments(vec![("dry_run", json!(dry_run))]);
+    let result = context.execute_tool("memo_compact", args).await?;
+    println!("{}", response_formatting::format_success_response(&result));
+    Ok(())
+}
+
+/// List near-duplicate memos, merging them when `merge` is set
+///
+/// The merge and the deletion are recorded in the memos' history.
+async fn dedupe_memos(
+    context: &CliToolContext,
+    threshold: f64,
+    merge: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if !(0.0..=1.0).contains(&threshold) {
+        return Err(format!("Threshold must be between 0 and 1, got {threshold}").into());
+    }
+    let storage = context.memo_storage().write().await;
+    let report =
+        swissarmyhammer::memoranda::dedupe::dedupe_memos(&**storage, threshold, !merge).await?;
+    println!("{report}");
+    Ok(())
+}
+
+/// Show every change made to a memo's content
+async fn show_memo_history(
+    context: &CliToolContext,
+    id: &str,
+    format: OutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let changes = context
+        .memo_storage()
+        .read()
+        .await
+        .memo_history(&MemoId::from_filename(id))
+        .await?;
+    print_history(&format!("memo '{id}'"), &changes, format)
+}
+
+/// Restore a memo's content to a version from its history
+async fn restore_memo(
+    context: &CliToolContext,
+    id: &str,
+    version: usize,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let memo = context
+        .memo_storage()
+        .write()
+        .await
+        .restore_memo(&MemoId::from_filename(id), version)
+        .await?;
+    println!("Restored memo '{}' to version {version}", memo.title);
+    Ok(())
+}
+
+/// Move a memo into a notebook, or to the top level
+///
+/// Notebooks are subdirectories of the memos directory.
+async fn move_memo(
+    context: &CliToolContext,
+    id: &str,
+    notebook: Option<&str>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let memo = context
+        .memo_storage()
+        .write()
+        .await
+        .move_memo(&MemoId::from_filename(id), notebook)
+        .await?;
+    match notebook {
+        Some(notebook) => println!("Moved memo '{}' to notebook '{notebook}'", memo.id),
+        None => println!("Moved memo '{}' to the top level", memo.id),
+    }
+    Ok(())
+}
+
+/// List, create, delete or rename notebooks
+async fn handle_notebook_command(
+    context: &CliToolContext,
+    command: NotebookCommands,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let storage = context.memo_storage().write().await;
+    match command {
+        NotebookCommands::List => {
+            let notebooks = storage.list_notebooks().await?;
+            if notebooks.is_empty() {
+                println!("No notebooks found");
+            }
+            for notebook in notebooks {
+                let count = storage.list_memos_in_notebook(&notebook).await?.len();
+                println!("{notebook} ({count} memos)");
+            }
+        }
+        NotebookCommands::Create { name } => {
+            storage.create_notebook(&name).await?;
+            println!("Created notebook '{name}'");
+        }
+        NotebookCommands::Delete { name } => {
+            storage.delete_notebook(&name).await?;
+            println!("Deleted notebook '{name}'");
+        }
+        NotebookCommands::Rename { name, new_name } => {
+            storage.rename_notebook(&name, &new_name).await?;
+            println!("Renamed notebook '{name}' to '{new_name}'");
+        }
+    }
+    Ok(())
+}
+
 /// Represents different sources of content input
 enum ContentInput {
     Direct(String),
@@ -283,6 +507,8 @@ mod memo_response_formatting {
         // Handle empty context case
         if response_text.contains("No memos available") {
             format!("{} No memos available for context", "ℹ️".blue())
+        } else if response_text.starts_with("No memos match") {
+            format!("{} {response_text}", "ℹ️".blue())
         } else {
             // Add document emoji to the context header
             let result =
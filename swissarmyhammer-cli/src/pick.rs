@@ -0,0 +1,229 @@
+//! Fuzzy finder over prompts, issues, memos and workflows
+//!
+//! `pick` lists everything that can be opened by name, lets the user narrow it
+//! down by typing, and hands back the command for the chosen item: testing a
+//! prompt, showing an issue, getting a memo or running a workflow.
+
+use anyhow::{anyhow, Result};
+use dialoguer::{theme::ColorfulTheme, FuzzySelect};
+use is_terminal::IsTerminal;
+
+use crate::cli::PickKind;
+use swissarmyhammer::issues::issue_storage_for_work_dir;
+use swissarmyhammer::memoranda::{MarkdownMemoStorage, MemoStorage};
+use swissarmyhammer::workflow::{MemoryWorkflowStorage, WorkflowResolver, WorkflowStorageBackend};
+use swissarmyhammer::{PromptLibrary, PromptResolver};
+
+/// Something the finder can open
+#[derive(Debug, Clone, PartialEq)]
+pub struct PickItem {
+    /// What kind of item it is
+    pub kind: PickKind,
+    /// Name the item is opened by
+    pub name: String,
+    /// One line describing the item
+    pub detail: String,
+}
+
+impl PickItem {
+    fn new(kind: PickKind, name: impl Into<String>, detail: &str) -> Self {
+        Self {
+            kind,
+            name: name.into(),
+            detail: detail.trim().to_string(),
+        }
+    }
+
+    /// Line shown for the item in the finder
+    pub fn label(&self) -> String {
+        let kind = match self.kind {
+            PickKind::Prompt => "prompt",
+            PickKind::Issue => "issue",
+            PickKind::Memo => "memo",
+            PickKind::Workflow => "workflow",
+        };
+        if self.detail.is_empty() {
+            format!("{kind:<8} {}", self.name)
+        } else {
+            format!("{kind:<8} {}  {}", self.name, self.detail)
+        }
+    }
+
+    /// Arguments of the command that opens the item
+    pub fn command_args(&self) -> Vec<String> {
+        let command: [&str; 2] = match self.kind {
+            PickKind::Prompt => ["prompt", "test"],
+            PickKind::Issue => ["issue", "show"],
+            PickKind::Memo => ["memo", "get"],
+            PickKind::Workflow => ["flow", "run"],
+        };
+        command
+            .iter()
+            .map(|arg| arg.to_string())
+            .chain([self.name.clone()])
+            .collect()
+    }
+}
+
+/// The command line that opens an item, quoted for a POSIX shell
+pub fn command_line(args: &[String]) -> String {
+    std::iter::once("swissarmyhammer".to_string())
+        .chain(args.iter().map(|arg| {
+            if !arg.is_empty()
+                && arg
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "-_./:@".contains(c))
+            {
+                arg.clone()
+            } else {
+                format!("'{}'", arg.replace('\'', r"'\''"))
+            }
+        }))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// First line of content worth showing, past any front matter and heading marks
+fn summary(content: &str) -> &str {
+    let body = content
+        .strip_prefix("---\n")
+        .and_then(|rest| rest.split_once("\n---\n").map(|(_, body)| body))
+        .unwrap_or(content);
+    body.lines()
+        .map(|line| line.trim_start_matches('#').trim())
+        .find(|line| !line.is_empty())
+        .unwrap_or_default()
+}
+
+/// Every item of the given kinds, or of all kinds when none are given
+pub async fn collect_items(kinds: &[PickKind]) -> Result<Vec<PickItem>> {
+    let wanted = |kind: PickKind| kinds.is_empty() || kinds.contains(&kind);
+    let mut items = Vec::new();
+
+    if wanted(PickKind::Prompt) {
+        let mut library = PromptLibrary::new();
+        PromptResolver::new().load_all_prompts(&mut library)?;
+        for prompt in library.list()? {
+            let detail = prompt.description.as_deref().unwrap_or_default();
+            items.push(PickItem::new(PickKind::Prompt, &prompt.name, detail));
+        }
+    }
+    if wanted(PickKind::Issue) {
+        let storage = issue_storage_for_work_dir(&std::env::current_dir()?)?;
+        for issue in storage.list_issues().await? {
+            let mut item = PickItem::new(PickKind::Issue, &issue.name, summary(&issue.content));
+            if issue.completed {
+                item.detail = format!("(completed) {}", item.detail);
+            }
+            items.push(item);
+        }
+    }
+    if wanted(PickKind::Memo) {
+        for memo in MarkdownMemoStorage::new_default()?.list_memos().await? {
+            items.push(PickItem::new(
+                PickKind::Memo,
+                memo.id.as_str(),
+                summary(&memo.content),
+            ));
+        }
+    }
+    if wanted(PickKind::Workflow) {
+        let mut storage = MemoryWorkflowStorage::new();
+        WorkflowResolver::new().load_all_workflows(&mut storage)?;
+        for workflow in storage.list_workflows()? {
+            items.push(PickItem::new(
+                PickKind::Workflow,
+                workflow.name.as_str(),
+                &workflow.description,
+            ));
+        }
+    }
+
+    items.sort_by(|a, b| a.kind.cmp(&b.kind).then_with(|| a.name.cmp(&b.name)));
+    Ok(items)
+}
+
+/// Let the user pick an item and return the arguments of the command that
+/// opens it, or `None` when the finder was dismissed
+pub async fn run_pick_command(
+    query: Option<String>,
+    kinds: Vec<PickKind>,
+) -> Result<Option<Vec<String>>> {
+    if !std::io::stderr().is_terminal() {
+        return Err(anyhow!("pick needs an interactive terminal"));
+    }
+    let items = collect_items(&kinds).await?;
+    if items.is_empty() {
+        return Err(anyhow!("Nothing to pick from"));
+    }
+
+    let labels: Vec<String> = items.iter().map(PickItem::label).collect();
+    let selection = FuzzySelect::with_theme(&ColorfulTheme::default())
+        .with_prompt("Open")
+        .with_initial_text(query.unwrap_or_default())
+        .items(&labels)
+        .default(0)
+        .max_length(20)
+        .interact_opt()
+        .map_err(|e| anyhow!("Failed to read selection: {}", e))?;
+    Ok(selection.map(|index| items[index].command_args()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::{Cli, Commands, IssueCommands, MemoCommands};
+
+    #[test]
+    fn test_pick_item_commands() {
+        let issue = PickItem::new(PickKind::Issue, "000001_login", " Fix the login ");
+        assert_eq!(issue.label(), "issue    000001_login  Fix the login");
+        let cli = Cli::try_parse_from_args(
+            std::iter::once("swissarmyhammer".to_string()).chain(issue.command_args()),
+        )
+        .unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Issue {
+                subcommand: IssueCommands::Show { ref name, raw: false }
+            }) if name == "000001_login"
+        ));
+
+        let memo = PickItem::new(PickKind::Memo, "Meeting Notes", "");
+        assert_eq!(memo.label(), "memo     Meeting Notes");
+        let cli = Cli::try_parse_from_args(
+            std::iter::once("swissarmyhammer".to_string()).chain(memo.command_args()),
+        )
+        .unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Memo {
+                subcommand: MemoCommands::Get { ref id, .. }
+            }) if id == "Meeting Notes"
+        ));
+        assert_eq!(
+            command_line(&memo.command_args()),
+            "swissarmyhammer memo get 'Meeting Notes'"
+        );
+
+        let workflow = PickItem::new(PickKind::Workflow, "release", "Cut a release");
+        assert_eq!(
+            command_line(&workflow.command_args()),
+            "swissarmyhammer flow run release"
+        );
+        assert_eq!(
+            command_line(&["it's".to_string()]),
+            r"swissarmyhammer 'it'\''s'"
+        );
+    }
+
+    #[test]
+    fn test_summary() {
+        assert_eq!(
+            summary("---\npriority: high\n---\n\n# Fix login\nDetails"),
+            "Fix login"
+        );
+        assert_eq!(summary("Plain text\nmore"), "Plain text");
+        assert_eq!(summary(""), "");
+    }
+}
@@ -0,0 +1,122 @@
+//! Live progress display for workflow runs
+//!
+//! Draws a line per state with a spinner and timer while the state runs, and
+//! a header with the total elapsed time and the tokens and cost of the
+//! prompts run so far.
+
+use colored::*;
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use std::sync::Mutex;
+use std::time::Duration;
+use swissarmyhammer::workflow::{
+    RunUsage, WorkflowProgress, WorkflowProgressEvent, WorkflowRun, WorkflowRunStatus,
+};
+
+/// How often spinners and timers are redrawn
+const TICK_INTERVAL: Duration = Duration::from_millis(120);
+
+/// Spinner style with a message and timer
+fn spinner_style(template: &str) -> ProgressStyle {
+    ProgressStyle::with_template(template).unwrap_or_else(|_| ProgressStyle::default_spinner())
+}
+
+/// Progress of a workflow run drawn on the terminal
+pub struct FlowProgressDisplay {
+    multi: MultiProgress,
+    header: ProgressBar,
+    current: Mutex<Option<ProgressBar>>,
+}
+
+impl FlowProgressDisplay {
+    /// Start drawing progress for a run of the named workflow
+    pub fn new(workflow_name: &str) -> Self {
+        let multi = MultiProgress::new();
+        let header = multi.add(ProgressBar::new_spinner());
+        header.set_style(spinner_style("{spinner:.cyan} {msg} [{elapsed_precise}]"));
+        header.set_message(format!(
+            "{} · {}",
+            workflow_name.bold(),
+            RunUsage::default()
+        ));
+        header.enable_steady_tick(TICK_INTERVAL);
+
+        Self {
+            multi,
+            header,
+            current: Mutex::new(None),
+        }
+    }
+
+    /// Stop drawing and leave a summary line for the finished run
+    pub fn finish(&self, run: &WorkflowRun) {
+        if let Some(bar) = self
+            .current
+            .lock()
+            .ok()
+            .and_then(|mut current| current.take())
+        {
+            bar.abandon();
+        }
+
+        let outcome = match run.status {
+            WorkflowRunStatus::Completed => "✅ completed".green(),
+            WorkflowRunStatus::Failed => "❌ failed".red(),
+            WorkflowRunStatus::Cancelled => "🚫 cancelled".yellow(),
+            _ => "⏸️  paused".yellow(),
+        };
+        self.header
+            .set_style(spinner_style("{msg} [{elapsed_precise}]"));
+        self.header.finish_with_message(format!(
+            "{} {} · {} · run {}",
+            run.workflow.name.to_string().bold(),
+            outcome,
+            RunUsage::from_context(&run.context),
+            run.id
+        ));
+    }
+}
+
+impl WorkflowProgress for FlowProgressDisplay {
+    fn on_progress(&self, run: &WorkflowRun, event: &WorkflowProgressEvent) {
+        let Ok(mut current) = self.current.lock() else {
+            return;
+        };
+
+        match event {
+            WorkflowProgressEvent::StateStarted { state, description } => {
+                let bar = self.multi.add(ProgressBar::new_spinner());
+                bar.set_style(spinner_style("  {spinner:.cyan} {wide_msg} [{elapsed}]"));
+                bar.set_message(format!(
+                    "{} {}",
+                    state.to_string().bold(),
+                    description.dimmed()
+                ));
+                bar.enable_steady_tick(TICK_INTERVAL);
+                *current = Some(bar);
+            }
+            WorkflowProgressEvent::StateFinished {
+                state,
+                elapsed,
+                succeeded,
+            } => {
+                if let Some(bar) = current.take() {
+                    let mark = if *succeeded {
+                        "✓".green()
+                    } else {
+                        "✗".red()
+                    };
+                    bar.set_style(spinner_style("  {msg}"));
+                    bar.finish_with_message(format!(
+                        "{mark} {state} ({:.1}s)",
+                        elapsed.as_secs_f64()
+                    ));
+                }
+                self.header.set_message(format!(
+                    "{} · {}",
+                    run.workflow.name.to_string().bold(),
+                    RunUsage::from_context(&run.context)
+                ));
+            }
+        }
+    }
+}
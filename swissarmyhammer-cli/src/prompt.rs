@@ -2,7 +2,9 @@
 
 use crate::cli::PromptSubcommand;
 use crate::error::{CliError, CliResult};
-use crate::{list, search, test};
+use crate::{
+    audit, bench, completions, deps, diff_sources, export, import, list, prompt_runs, search, test,
+};
 
 /// Main entry point for prompt command
 pub async fn run_prompt_command(subcommand: PromptSubcommand) -> CliResult<()> {
@@ -13,9 +15,17 @@ pub async fn run_prompt_command(subcommand: PromptSubcommand) -> CliResult<()> {
             source,
             category,
             search,
-        } => list::run_list_command(format, verbose, source, category, search)
-            .map(|_| ())
-            .map_err(|e| CliError::new(e.to_string(), 1)),
+            include_deprecated,
+        } => list::run_list_command(
+            format,
+            verbose,
+            source,
+            category,
+            search,
+            include_deprecated,
+        )
+        .map(|_| ())
+        .map_err(|e| CliError::new(e.to_string(), 1)),
         PromptSubcommand::Test {
             prompt_name,
             file,
@@ -25,6 +35,11 @@ pub async fn run_prompt_command(subcommand: PromptSubcommand) -> CliResult<()> {
             copy,
             save,
             debug,
+            preset,
+            save_preset,
+            interactive,
+            no_interactive,
+            all,
         } => {
             let mut runner = test::TestRunner::new();
             let config = test::TestConfig {
@@ -36,6 +51,16 @@ pub async fn run_prompt_command(subcommand: PromptSubcommand) -> CliResult<()> {
                 copy,
                 save,
                 debug,
+                preset,
+                save_preset,
+                interactive: if no_interactive {
+                    Some(false)
+                } else if interactive {
+                    Some(true)
+                } else {
+                    None
+                },
+                all,
             };
             runner.run(config).await.map(|_| ()).map_err(|e| {
                 let error_msg = e.to_string();
@@ -75,6 +100,52 @@ pub async fn run_prompt_command(subcommand: PromptSubcommand) -> CliResult<()> {
         )
         .map(|_| ())
         .map_err(|e| CliError::new(e.to_string(), 1)),
+        PromptSubcommand::Export { format, output_dir } => {
+            export::run_export_command(format, output_dir)
+                .map(|_| ())
+                .map_err(|e| CliError::new(e.to_string(), 1))
+        }
+        PromptSubcommand::Import {
+            format,
+            path,
+            output_dir,
+            force,
+        } => import::run_import_command(format, path, output_dir, force)
+            .map(|_| ())
+            .map_err(|e| CliError::new(e.to_string(), 1)),
+        PromptSubcommand::Deps { name, format } => {
+            deps::run_deps_command(name, format).map_err(|e| CliError::new(e.to_string(), 1))
+        }
+        PromptSubcommand::DiffSources {
+            from,
+            to,
+            format,
+            names_only,
+        } => diff_sources::run_diff_sources_command(from, to, format, names_only)
+            .map_err(|e| CliError::new(e.to_string(), 1)),
+        PromptSubcommand::Bench {
+            name,
+            iterations,
+            arguments,
+            format,
+        } => bench::run_bench_command(name, iterations, arguments, format)
+            .map_err(|e| CliError::new(e.to_string(), 1)),
+        PromptSubcommand::Audit { max_length, format } => {
+            audit::run_audit_command(max_length, format)
+                .map_err(|e| CliError::new(e.to_string(), 1))
+        }
+        PromptSubcommand::Runs {
+            name,
+            limit,
+            format,
+        } => prompt_runs::run_runs_command(name, limit, format)
+            .map_err(|e| CliError::new(e.to_string(), 1)),
+        PromptSubcommand::Complete {
+            prompt_name,
+            argument,
+            prefix,
+        } => completions::run_complete_command(&prompt_name, argument.as_deref(), &prefix)
+            .map_err(|e| CliError::new(e.to_string(), 1)),
     }
 }
 
@@ -92,6 +163,7 @@ mod tests {
             source: None,
             category: None,
             search: None,
+            include_deprecated: false,
         };
 
         // Run the command - we expect it to succeed
@@ -134,6 +206,11 @@ mod tests {
             copy: false,
             save: None,
             debug: false,
+            preset: None,
+            save_preset: None,
+            interactive: false,
+            no_interactive: true,
+            all: false,
         };
 
         // Run the command - should return an error
@@ -0,0 +1,58 @@
+//! Records of the prompts MCP clients fetched
+
+use anyhow::Result;
+use colored::*;
+use swissarmyhammer::{PromptRun, PromptRunLog};
+
+use crate::cli::OutputFormat;
+
+/// Show the most recent runs of a prompt, or of every prompt
+pub fn run_runs_command(name: Option<String>, limit: usize, format: OutputFormat) -> Result<()> {
+    let mut runs = PromptRunLog::new_default()?.runs(name.as_deref())?;
+    runs.drain(..runs.len().saturating_sub(limit));
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&runs)?),
+        OutputFormat::Yaml => print!("{}", serde_yaml::to_string(&runs)?),
+        OutputFormat::Table => display_runs(name.as_deref(), &runs),
+    }
+    Ok(())
+}
+
+fn display_runs(name: Option<&str>, runs: &[PromptRun]) {
+    if runs.is_empty() {
+        match name {
+            Some(name) => println!("No recorded runs of prompt '{name}'"),
+            None => println!("No recorded prompt runs"),
+        }
+        return;
+    }
+
+    for run in runs {
+        let mut line = format!(
+            "{}  {}",
+            run.timestamp
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M:%S"),
+            run.prompt.bold()
+        );
+        if let Some(client) = &run.client {
+            line.push_str(&format!("  {}", client.cyan()));
+        }
+        if !run.arguments.is_empty() {
+            line.push_str(&format!("  ({})", run.arguments.join(", ")));
+        }
+        println!("{line}");
+
+        let mut hashes = format!("    output {}", short_hash(&run.output_hash));
+        if let Some(arguments_hash) = &run.arguments_hash {
+            hashes.push_str(&format!("  arguments {}", short_hash(arguments_hash)));
+        }
+        println!("{}", hashes.dimmed());
+    }
+}
+
+/// The first characters of a hash, enough to tell runs apart
+fn short_hash(hash: &str) -> &str {
+    &hash[..hash.len().min(12)]
+}
@@ -0,0 +1,89 @@
+//! SQL-like reports over issues and memos
+
+use anyhow::Result;
+use is_terminal::IsTerminal;
+use tabled::{
+    builder::Builder,
+    settings::{object::Rows, Color, Modify, Style},
+};
+
+use crate::cli::QueryFormat;
+use swissarmyhammer::issues::issue_storage_for_work_dir;
+use swissarmyhammer::memoranda::{MarkdownMemoStorage, MemoStorage};
+use swissarmyhammer::query::{issue_row, memo_row, text, Query, QueryResult, QueryTable};
+
+/// Run a query against the issues or memos of the current directory and print
+/// the rows
+pub async fn run_query_command(query: String, format: QueryFormat) -> Result<()> {
+    let query: Query = query.parse()?;
+    let rows = match query.table {
+        QueryTable::Issues => issue_storage_for_work_dir(&std::env::current_dir()?)?
+            .list_issues()
+            .await?
+            .iter()
+            .map(issue_row)
+            .collect(),
+        QueryTable::Memos => MarkdownMemoStorage::new_default()?
+            .list_memos()
+            .await?
+            .iter()
+            .map(memo_row)
+            .collect(),
+    };
+    let result = query.execute(rows)?;
+
+    match format {
+        QueryFormat::Table => print!("{}", format_table(&result, std::io::stdout().is_terminal())),
+        QueryFormat::Json => println!("{}", serde_json::to_string_pretty(&result.to_json())?),
+        QueryFormat::Csv => print!("{}", result.to_csv()),
+    }
+    Ok(())
+}
+
+/// Render query results as a table, with a count of the rows
+fn format_table(result: &QueryResult, is_tty: bool) -> String {
+    if result.rows.is_empty() {
+        return "No rows found\n".to_string();
+    }
+    let mut builder = Builder::default();
+    builder.push_record(result.columns.clone());
+    for row in &result.rows {
+        builder.push_record(row.iter().map(text));
+    }
+    let mut table = builder.build();
+    table.with(Style::modern());
+    if is_tty {
+        table.with(Modify::new(Rows::one(0)).with(Color::FG_BRIGHT_CYAN));
+    }
+    let count = result.rows.len();
+    format!(
+        "{table}\n{count} row{}\n",
+        if count == 1 { "" } else { "s" }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_table() {
+        let result = QueryResult {
+            columns: vec!["name".to_string(), "labels".to_string()],
+            rows: vec![vec![
+                serde_json::json!("000001_login"),
+                serde_json::json!(["bug", "auth"]),
+            ]],
+        };
+        let table = format_table(&result, false);
+        assert!(table.contains("000001_login"));
+        assert!(table.contains("bug, auth"));
+        assert!(table.ends_with("1 row\n"));
+
+        let empty = QueryResult {
+            columns: result.columns,
+            rows: Vec::new(),
+        };
+        assert_eq!(format_table(&empty, false), "No rows found\n");
+    }
+}
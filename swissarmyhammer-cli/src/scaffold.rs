@@ -0,0 +1,155 @@
+//! Scaffolding new workflows from templates
+
+use colored::*;
+use dialoguer::{theme::ColorfulTheme, Input, Select};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use swissarmyhammer::workflow::{WorkflowTemplate, WorkflowTemplates};
+use swissarmyhammer::{Result, SwissArmyHammerError};
+
+/// Generate a workflow file from a template
+pub fn run_new_command(
+    name: String,
+    template: String,
+    args: Vec<String>,
+    output: Option<PathBuf>,
+    force: bool,
+) -> Result<()> {
+    if name
+        .split(['/', '\\'])
+        .any(|part| part.is_empty() || part == "..")
+    {
+        return Err(SwissArmyHammerError::Other(format!(
+            "Invalid workflow name '{name}'"
+        )));
+    }
+
+    let path = output.unwrap_or_else(|| {
+        Path::new(".swissarmyhammer")
+            .join("workflows")
+            .join(format!("{name}.md"))
+    });
+    if path.exists() && !force {
+        return Err(SwissArmyHammerError::Other(format!(
+            "'{}' already exists, use --force to overwrite it",
+            path.display()
+        )));
+    }
+
+    let templates = WorkflowTemplates::load()?;
+    let template = templates.get(&template)?;
+    let mut values = parse_args(&args)?;
+    if atty::is(atty::Stream::Stdin) {
+        ask_for_values(template, &mut values)?;
+    }
+
+    let content = template.render(&name, &values)?;
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, content)?;
+
+    println!(
+        "{} Created workflow '{}' from template '{}' at {}",
+        "✓".green(),
+        name,
+        template.name(),
+        path.display()
+    );
+    println!("  Try it with: swissarmyhammer flow test {name}");
+    Ok(())
+}
+
+fn parse_args(args: &[String]) -> Result<HashMap<String, String>> {
+    args.iter()
+        .map(|arg| {
+            arg.split_once('=')
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .ok_or_else(|| {
+                    SwissArmyHammerError::Other(format!(
+                        "Invalid argument format: '{arg}'. Expected 'key=value' format"
+                    ))
+                })
+        })
+        .collect()
+}
+
+/// Ask for each template value not already given
+fn ask_for_values(template: &WorkflowTemplate, values: &mut HashMap<String, String>) -> Result<()> {
+    let theme = ColorfulTheme::default();
+    let read_error =
+        |e: dialoguer::Error| SwissArmyHammerError::Other(format!("Failed to read input: {e}"));
+
+    for argument in template.arguments() {
+        if values.contains_key(&argument.name) {
+            continue;
+        }
+        let prompt = match &argument.description {
+            Some(description) => format!("{} ({description})", argument.name),
+            None => argument.name.clone(),
+        };
+
+        let value = match argument
+            .choices
+            .as_ref()
+            .filter(|choices| !choices.is_empty())
+        {
+            Some(choices) => {
+                let default = argument
+                    .default
+                    .as_ref()
+                    .and_then(|default| choices.iter().position(|choice| choice == default))
+                    .unwrap_or(0);
+                let selection = Select::with_theme(&theme)
+                    .with_prompt(&prompt)
+                    .items(choices)
+                    .default(default)
+                    .interact()
+                    .map_err(read_error)?;
+                choices[selection].clone()
+            }
+            None => {
+                let mut input = Input::<String>::with_theme(&theme)
+                    .with_prompt(&prompt)
+                    .allow_empty(!argument.required);
+                if let Some(default) = &argument.default {
+                    input = input.default(default.clone());
+                }
+                input.interact_text().map_err(read_error)?
+            }
+        };
+        values.insert(argument.name.clone(), value);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_args() {
+        let values =
+            parse_args(&["prompt=say-hello".to_string(), "title=a=b".to_string()]).unwrap();
+        assert_eq!(values["prompt"], "say-hello");
+        assert_eq!(values["title"], "a=b");
+        assert!(parse_args(&["prompt".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_invalid_workflow_names() {
+        for name in ["", "../escape", "a//b"] {
+            let result = run_new_command(
+                name.to_string(),
+                "basic".to_string(),
+                Vec::new(),
+                None,
+                false,
+            );
+            assert!(result.is_err(), "'{name}' was accepted");
+        }
+    }
+}
@@ -1,14 +1,17 @@
 use anyhow::{anyhow, Result};
 use colored::*;
-use dialoguer::{theme::ColorfulTheme, Input};
+use dialoguer::{theme::ColorfulTheme, FuzzySelect, Input, Select};
 use std::collections::HashMap;
 use std::fs;
 
-use swissarmyhammer::PromptResolver;
-use swissarmyhammer::{Prompt, PromptLibrary};
+use swissarmyhammer::{ArgumentSpec, Prompt, PromptLibrary};
+use swissarmyhammer::{PresetStorage, PromptResolver};
 
 use crate::exit_codes::EXIT_SUCCESS;
 
+/// Selection entry for typing a value that isn't among the suggestions
+const OTHER_VALUE_ITEM: &str = "(type another value)";
+
 /// Configuration for running a prompt test
 #[derive(Default)]
 pub struct TestConfig {
@@ -20,6 +23,12 @@ pub struct TestConfig {
     pub copy: bool,
     pub save: Option<String>,
     pub debug: bool,
+    pub preset: Option<String>,
+    pub save_preset: Option<String>,
+    /// Whether to prompt for missing arguments; `None` prompts only in a terminal
+    pub interactive: Option<bool>,
+    /// Run the front matter tests of every prompt instead of rendering one
+    pub all: bool,
 }
 
 pub struct TestRunner {
@@ -37,23 +46,68 @@ impl TestRunner {
         // Load all prompts first
         self.load_prompts()?;
 
+        if config.all {
+            return self.run_inline_tests();
+        }
+
         // Get the prompt to test
         let prompt = self.get_prompt(config.prompt_name.as_deref(), config.file.as_deref())?;
 
+        let interactive = config
+            .interactive
+            .unwrap_or_else(|| atty::is(atty::Stream::Stdin));
+
         // Collect arguments
-        let mut args = if config.arguments.is_empty() {
-            // Interactive mode - but only if we're in a terminal
-            if atty::is(atty::Stream::Stdin) {
-                self.collect_arguments_interactive(&prompt)?
-            } else {
-                // Non-interactive mode when not in terminal (CI/testing)
-                self.collect_arguments_non_interactive(&prompt)?
-            }
+        let mut args = if let Some(preset_name) = &config.preset {
+            // Preset mode - start from the saved preset and let --arg values override it
+            let mut args = self.collect_arguments_non_interactive(&prompt)?;
+            args.extend(PresetStorage::new_default()?.load(&prompt.name, preset_name)?);
+            args.extend(self.parse_arguments(&config.arguments)?);
+            args
+        } else if config.arguments.is_empty() && interactive {
+            self.collect_arguments_interactive(&prompt)?
         } else {
-            // Non-interactive mode
-            self.parse_arguments(&config.arguments)?
+            // Non-interactive mode, with defaults filling in arguments not given
+            let mut args = self.collect_arguments_non_interactive(&prompt)?;
+            args.extend(self.parse_arguments(&config.arguments)?);
+            args
         };
 
+        // Ask for required arguments that are still missing
+        let missing = missing_required_arguments(&prompt, &args);
+        if !missing.is_empty() {
+            if interactive {
+                println!(
+                    "{}",
+                    "📝 Please provide values for the missing required arguments:"
+                        .bold()
+                        .blue()
+                );
+                println!();
+                args.extend(self.prompt_for_arguments(&missing)?);
+                println!();
+            } else if config.interactive == Some(false) {
+                let names: Vec<&str> = missing.iter().map(|arg| arg.name.as_str()).collect();
+                return Err(anyhow!(
+                    "Missing required arguments for '{}': {}. Pass them with --arg key=value",
+                    prompt.name,
+                    names.join(", ")
+                ));
+            }
+        }
+        validate_choices(&prompt, &args)?;
+
+        // Save the collected arguments as a preset before template variables are mixed in
+        if let Some(preset_name) = &config.save_preset {
+            PresetStorage::new_default()?.save(&prompt.name, preset_name, &args)?;
+            if !config.raw {
+                println!(
+                    "{}",
+                    format!("💾 Saved preset '{}' for {}", preset_name, prompt.name).green()
+                );
+            }
+        }
+
         // Parse and add set variables for liquid template rendering
         let set_variables = self.parse_arguments(&config.set)?;
         if !set_variables.is_empty() {
@@ -83,6 +137,48 @@ impl TestRunner {
         Ok(())
     }
 
+    /// Run the test cases in every prompt's front matter
+    ///
+    /// Fails when any case fails or a prompt's tests are malformed.
+    fn run_inline_tests(&self) -> Result<i32> {
+        let mut passed = 0;
+        let mut failed = 0;
+
+        for prompt in self.library.list()? {
+            let results = match swissarmyhammer::run_prompt_tests(&prompt, &self.library) {
+                Ok(results) => results,
+                Err(e) => {
+                    println!("{} {}: {}", "✗".red(), prompt.name.bold(), e);
+                    failed += 1;
+                    continue;
+                }
+            };
+            for result in results {
+                if result.passed() {
+                    println!("{} {} › {}", "✓".green(), result.prompt.bold(), result.case);
+                    passed += 1;
+                } else {
+                    println!("{} {} › {}", "✗".red(), result.prompt.bold(), result.case);
+                    for failure in &result.failures {
+                        println!("    {}", failure.red());
+                    }
+                    failed += 1;
+                }
+            }
+        }
+
+        println!();
+        if passed + failed == 0 {
+            println!("{}", "ℹ No prompts have tests in their front matter".blue());
+            return Ok(EXIT_SUCCESS);
+        }
+        println!("{passed} passed, {failed} failed");
+        if failed > 0 {
+            return Err(anyhow!("{failed} inline prompt test(s) failed"));
+        }
+        Ok(EXIT_SUCCESS)
+    }
+
     fn get_prompt(&self, prompt_name: Option<&str>, file_path: Option<&str>) -> Result<Prompt> {
         match (prompt_name, file_path) {
             (Some(name), None) => {
@@ -124,12 +220,9 @@ impl TestRunner {
     }
 
     fn collect_arguments_interactive(&self, prompt: &Prompt) -> Result<HashMap<String, String>> {
-        let mut args = HashMap::new();
-        let theme = ColorfulTheme::default();
-
         if prompt.arguments.is_empty() {
             println!("{}", "ℹ No arguments required for this prompt".blue());
-            return Ok(args);
+            return Ok(HashMap::new());
         }
 
         println!(
@@ -140,7 +233,29 @@ impl TestRunner {
         );
         println!();
 
-        for arg in &prompt.arguments {
+        // Arguments with a source are looked up at render time, so secrets are never typed in
+        let arguments: Vec<&ArgumentSpec> = prompt
+            .arguments
+            .iter()
+            .filter(|arg| arg.source.is_none())
+            .collect();
+        let args = self.prompt_for_arguments(&arguments)?;
+
+        println!();
+        Ok(args)
+    }
+
+    /// Ask the user for a value for each argument
+    ///
+    /// Arguments with choices are offered as a selection, and arguments with a
+    /// completion source as a searchable list of suggestions that also allows
+    /// typing another value; others are read as text, with the default
+    /// accepted by pressing Enter.
+    fn prompt_for_arguments(&self, arguments: &[&ArgumentSpec]) -> Result<HashMap<String, String>> {
+        let mut args = HashMap::new();
+        let theme = ColorfulTheme::default();
+
+        for arg in arguments {
             let prompt_text = if arg.required {
                 format!(
                     "{} (required): {}",
@@ -155,6 +270,27 @@ impl TestRunner {
                 )
             };
 
+            if let Some(choices) = arg.choices.as_ref().filter(|choices| !choices.is_empty()) {
+                let default_index = arg
+                    .default
+                    .as_ref()
+                    .and_then(|default| choices.iter().position(|choice| choice == default))
+                    .unwrap_or(0);
+                let selection = Select::with_theme(&theme)
+                    .with_prompt(&prompt_text)
+                    .items(choices)
+                    .default(default_index)
+                    .interact()
+                    .map_err(|e| anyhow!("Failed to read input: {}", e))?;
+                args.insert(arg.name.clone(), choices[selection].clone());
+                continue;
+            }
+
+            if let Some(value) = self.select_suggestion(arg, &prompt_text, &theme)? {
+                args.insert(arg.name.clone(), value);
+                continue;
+            }
+
             loop {
                 let mut input = Input::<String>::with_theme(&theme).with_prompt(&prompt_text);
 
@@ -183,10 +319,48 @@ impl TestRunner {
             }
         }
 
-        println!();
         Ok(args)
     }
 
+    /// Offer the completion values of an argument, returning `None` when there
+    /// are none or the user chooses to type a value instead
+    fn select_suggestion(
+        &self,
+        arg: &ArgumentSpec,
+        prompt_text: &str,
+        theme: &ColorfulTheme,
+    ) -> Result<Option<String>> {
+        let Some(source) = &arg.completion else {
+            return Ok(None);
+        };
+        let values = match source.values("") {
+            Ok(values) if !values.is_empty() => values,
+            Ok(_) => return Ok(None),
+            Err(e) => {
+                println!(
+                    "{}",
+                    format!("⚠ No suggestions for {}: {e}", arg.name).yellow()
+                );
+                return Ok(None);
+            }
+        };
+
+        let mut items = values.clone();
+        items.push(OTHER_VALUE_ITEM.to_string());
+        let default_index = arg
+            .default
+            .as_ref()
+            .and_then(|default| values.iter().position(|value| value == default))
+            .unwrap_or(0);
+        let selection = FuzzySelect::with_theme(theme)
+            .with_prompt(prompt_text)
+            .items(&items)
+            .default(default_index)
+            .interact()
+            .map_err(|e| anyhow!("Failed to read input: {}", e))?;
+        Ok(values.get(selection).cloned())
+    }
+
     fn collect_arguments_non_interactive(
         &self,
         prompt: &Prompt,
@@ -310,6 +484,41 @@ impl TestRunner {
     }
 }
 
+/// Required arguments without a default or source that have not been given a value
+fn missing_required_arguments<'a>(
+    prompt: &'a Prompt,
+    args: &HashMap<String, String>,
+) -> Vec<&'a ArgumentSpec> {
+    prompt
+        .arguments
+        .iter()
+        .filter(|arg| {
+            arg.required
+                && arg.default.is_none()
+                && arg.source.is_none()
+                && !args.contains_key(&arg.name)
+        })
+        .collect()
+}
+
+/// Check that arguments with choices were given one of the allowed values
+fn validate_choices(prompt: &Prompt, args: &HashMap<String, String>) -> Result<()> {
+    for arg in &prompt.arguments {
+        let (Some(choices), Some(value)) = (&arg.choices, args.get(&arg.name)) else {
+            continue;
+        };
+        if !choices.is_empty() && !choices.contains(value) {
+            return Err(anyhow!(
+                "Invalid value '{}' for argument '{}': expected one of {}",
+                value,
+                arg.name,
+                choices.join(", ")
+            ));
+        }
+    }
+    Ok(())
+}
+
 #[allow(dead_code)]
 pub fn get_prompt_validation(prompt: &Prompt) -> (Vec<String>, Vec<String>) {
     let mut errors = Vec::new();
@@ -397,6 +606,9 @@ mod tests {
                 required: true,
                 default: None,
                 type_hint: None,
+                choices: None,
+                completion: None,
+                source: None,
             })
             .add_argument(ArgumentSpec {
                 name: "unused".to_string(),
@@ -404,6 +616,9 @@ mod tests {
                 required: false,
                 default: Some("default".to_string()),
                 type_hint: None,
+                choices: None,
+                completion: None,
+                source: None,
             });
 
         let (errors, warnings) = get_prompt_validation(&prompt);
@@ -415,6 +630,44 @@ mod tests {
         assert!(warnings[0].contains("Argument 'unused' is defined but not used"));
     }
 
+    #[test]
+    fn test_missing_required_arguments_and_choices() {
+        let prompt = Prompt::new("review", "Review {{ language }} for {{ focus }}")
+            .add_argument(ArgumentSpec {
+                name: "language".to_string(),
+                description: Some("Language to review".to_string()),
+                required: true,
+                default: None,
+                type_hint: None,
+                choices: Some(vec!["rust".to_string(), "python".to_string()]),
+                completion: None,
+                source: None,
+            })
+            .add_argument(ArgumentSpec {
+                name: "focus".to_string(),
+                description: None,
+                required: true,
+                default: Some("style".to_string()),
+                type_hint: None,
+                choices: None,
+                completion: None,
+                source: None,
+            });
+
+        let mut args = HashMap::new();
+        let missing = missing_required_arguments(&prompt, &args);
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].name, "language");
+
+        args.insert("language".to_string(), "go".to_string());
+        assert!(missing_required_arguments(&prompt, &args).is_empty());
+        let error = validate_choices(&prompt, &args).unwrap_err().to_string();
+        assert!(error.contains("expected one of rust, python"));
+
+        args.insert("language".to_string(), "rust".to_string());
+        assert!(validate_choices(&prompt, &args).is_ok());
+    }
+
     #[test]
     fn test_parse_arguments_with_set_variables() {
         let runner = TestRunner::new();
@@ -1,7 +1,13 @@
 use anyhow::{Context, Result};
 use colored::*;
+use is_terminal::IsTerminal;
+use rayon::prelude::*;
 use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use swissarmyhammer::file_watcher::{FileWatcher, FileWatcherCallback, WatchedContent};
+use swissarmyhammer::prompt_refs::pinned_prompts;
 use swissarmyhammer::validation::{
     Validatable, ValidationConfig, ValidationIssue, ValidationLevel, ValidationManager,
     ValidationResult,
@@ -9,6 +15,7 @@ use swissarmyhammer::validation::{
 use swissarmyhammer::workflow::{
     MemoryWorkflowStorage, MermaidParser, Workflow, WorkflowResolver, WorkflowStorageBackend,
 };
+use swissarmyhammer::{DependencyGraph, DependencyNode, Prompt, PromptLibrary, ResolvedPrompt};
 
 use crate::cli::ValidateFormat;
 use crate::exit_codes::{EXIT_ERROR, EXIT_SUCCESS, EXIT_WARNING};
@@ -35,6 +42,12 @@ pub struct Validator {
     quiet: bool,
     config: ValidationConfig,
     validation_manager: ValidationManager,
+    /// Deprecated prompts and the prompts that supersede them
+    deprecated_prompts: HashMap<String, Option<String>>,
+    /// Content hash and version of each validated prompt, to check workflow pins
+    resolved_prompts: HashMap<String, ResolvedPrompt>,
+    /// Files validated at once, 0 for one per CPU
+    jobs: usize,
 }
 
 impl Validator {
@@ -45,9 +58,29 @@ impl Validator {
             quiet,
             config,
             validation_manager,
+            deprecated_prompts: HashMap::new(),
+            resolved_prompts: HashMap::new(),
+            jobs: 0,
         }
     }
 
+    /// Validate `jobs` files at once, or one per CPU when 0
+    ///
+    /// Results are reported in the same order whatever the number of jobs.
+    pub fn with_jobs(mut self, jobs: usize) -> Self {
+        self.jobs = jobs;
+        self
+    }
+
+    /// Run work on a thread pool of the configured size
+    fn in_pool<T: Send>(&self, work: impl FnOnce() -> T + Send) -> Result<T> {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(self.jobs)
+            .build()
+            .context("Failed to start validation threads")?;
+        Ok(pool.install(work))
+    }
+
     pub fn validate_all_with_options(&mut self) -> Result<ValidationResult> {
         let mut result = ValidationResult::new();
 
@@ -58,36 +91,18 @@ impl Validator {
 
         // Validate each loaded prompt
         let prompts = library.list()?;
-        for prompt in prompts {
-            result.files_checked += 1;
-
-            // Store prompt title for error reporting
-            let content_title = prompt
-                .metadata
-                .get("title")
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_string())
-                .or_else(|| Some(prompt.name.clone()));
-
-            // Validate template syntax with partials support
-            self.validate_liquid_syntax_with_partials(
-                &prompt,
-                &library,
-                prompt.source.as_ref().unwrap_or(&PathBuf::new()),
-                &mut result,
-                content_title.clone(),
-            );
-
-            // Use the Validatable trait to validate the prompt
-            let validation_issues = prompt.validate(prompt.source.as_deref());
-            for issue in validation_issues {
-                result.add_issue(issue);
-            }
+        for prompt_result in self.validate_prompts(&prompts, &library)? {
+            result.merge(prompt_result);
         }
 
         // Validate workflows using WorkflowResolver for consistent loading
         self.validate_all_workflows(&mut result)?;
 
+        Self::validate_issue_branch_template(
+            &swissarmyhammer::Config::global().issue_branch_template,
+            &mut result,
+        );
+
         Ok(result)
     }
 
@@ -104,39 +119,213 @@ impl Validator {
 
         // Validate each loaded prompt
         let prompts = library.list()?;
-        for prompt in prompts {
-            result.files_checked += 1;
-
-            // Store prompt title for error reporting
-            let content_title = prompt
-                .metadata
-                .get("title")
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_string())
-                .or_else(|| Some(prompt.name.clone()));
-
-            // Validate template syntax with partials support
-            self.validate_liquid_syntax_with_partials(
-                &prompt,
-                &library,
-                prompt.source.as_ref().unwrap_or(&PathBuf::new()),
-                &mut result,
-                content_title.clone(),
-            );
-
-            // Use the Validatable trait to validate the prompt
-            let validation_issues = prompt.validate(prompt.source.as_deref());
-            for issue in validation_issues {
-                result.add_issue(issue);
-            }
+        for prompt_result in self.validate_prompts(&prompts, &library)? {
+            result.merge(prompt_result);
         }
 
         // Validate workflows from custom directories
         self.validate_workflows_from_dirs(&mut result, workflow_dirs)?;
 
+        Self::validate_issue_branch_template(
+            &swissarmyhammer::Config::global().issue_branch_template,
+            &mut result,
+        );
+
         Ok(result)
     }
 
+    /// Check that the configured issue branch template makes usable branch names
+    fn validate_issue_branch_template(template: &str, result: &mut ValidationResult) {
+        if let Err(e) = swissarmyhammer::issues::IssueBranchNaming::new(template) {
+            result.add_issue(ValidationIssue {
+                level: ValidationLevel::Error,
+                file_path: PathBuf::from("SWISSARMYHAMMER_ISSUE_BRANCH_TEMPLATE"),
+                content_title: Some(template.to_string()),
+                line: None,
+                column: None,
+                message: e.to_string(),
+                suggestion: Some(
+                    "Use {name}, or {number} and {slug}, e.g. issue/{number}-{slug}".to_string(),
+                ),
+            });
+        }
+    }
+
+    /// Validate prompts in parallel, returning their results in the order given
+    ///
+    /// Deprecated prompts are remembered afterwards, so workflows validated
+    /// next can report their use.
+    fn validate_prompts(
+        &mut self,
+        prompts: &[Prompt],
+        library: &PromptLibrary,
+    ) -> Result<Vec<ValidationResult>> {
+        let validator = &*self;
+        let results = self.in_pool(|| {
+            prompts
+                .par_iter()
+                .map(|prompt| validator.validate_prompt(prompt, library))
+                .collect()
+        })?;
+        for prompt in prompts {
+            self.remember_deprecation(prompt);
+            self.resolved_prompts
+                .insert(prompt.name.clone(), ResolvedPrompt::of(prompt));
+        }
+        Ok(results)
+    }
+
+    /// Validate a single prompt, rendering it with partials from `library`
+    fn validate_prompt(&self, prompt: &Prompt, library: &PromptLibrary) -> ValidationResult {
+        let mut result = ValidationResult::new();
+        result.files_checked += 1;
+
+        // Store prompt title for error reporting
+        let content_title = prompt
+            .metadata
+            .get("title")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+            .or_else(|| Some(prompt.name.clone()));
+
+        // Validate template syntax with partials support
+        self.validate_liquid_syntax_with_partials(
+            prompt,
+            library,
+            prompt.source.as_ref().unwrap_or(&PathBuf::new()),
+            &mut result,
+            content_title,
+        );
+
+        // Use the Validatable trait to validate the prompt
+        let validation_issues = prompt.validate(prompt.source.as_deref());
+        for issue in validation_issues {
+            result.add_issue(issue);
+        }
+
+        self.check_deprecation(prompt, library, &mut result);
+        self.check_inline_tests(prompt, library, &mut result);
+        result
+    }
+
+    /// Validate prompts and workflows separately, keyed by their dependency graph node
+    ///
+    /// When `only` is given just those items are validated, though every
+    /// prompt is still loaded so partials resolve. Workflows are validated
+    /// after prompts so deprecated prompts they use are known.
+    pub fn validate_items(
+        &mut self,
+        only: Option<&BTreeSet<DependencyNode>>,
+    ) -> Result<BTreeMap<DependencyNode, ValidationResult>> {
+        let selected = |node: &DependencyNode| only.map_or(true, |only| only.contains(node));
+        let mut results = BTreeMap::new();
+
+        let mut library = PromptLibrary::new();
+        let mut resolver = swissarmyhammer::PromptResolver::new();
+        resolver.load_all_prompts(&mut library)?;
+        let prompts: Vec<Prompt> = library
+            .list()?
+            .into_iter()
+            .filter(|prompt| selected(&DependencyNode::prompt(&prompt.name)))
+            .collect();
+        let prompt_results = self.validate_prompts(&prompts, &library)?;
+        for (prompt, result) in prompts.iter().zip(prompt_results) {
+            results.insert(DependencyNode::prompt(&prompt.name), result);
+        }
+
+        let mut storage = MemoryWorkflowStorage::new();
+        let mut resolver = WorkflowResolver::new();
+        // Workflow directories may be missing, as in validate_all_workflows
+        if resolver.load_all_workflows(&mut storage).is_ok() {
+            let workflows: Vec<Workflow> = storage
+                .list_workflows()
+                .context("Failed to retrieve loaded workflows from storage")?
+                .into_iter()
+                .filter(|workflow| selected(&DependencyNode::workflow(workflow.name.as_str())))
+                .collect();
+            let workflow_results = self.validate_workflows(&workflows, &resolver)?;
+            for (workflow, result) in workflows.iter().zip(workflow_results) {
+                results.insert(DependencyNode::workflow(workflow.name.as_str()), result);
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Remember a deprecated prompt so workflows still using it can be reported
+    fn remember_deprecation(&mut self, prompt: &Prompt) {
+        if prompt.is_deprecated() {
+            self.deprecated_prompts.insert(
+                prompt.name.clone(),
+                prompt.superseded_by().map(str::to_string),
+            );
+        } else {
+            self.deprecated_prompts.remove(&prompt.name);
+        }
+    }
+
+    /// Report a deprecated prompt pointing at a replacement that does not
+    /// exist, since it would send users nowhere
+    fn check_deprecation(
+        &self,
+        prompt: &Prompt,
+        library: &PromptLibrary,
+        result: &mut ValidationResult,
+    ) {
+        if !prompt.is_deprecated() {
+            return;
+        }
+        if let Some(replacement) = prompt.superseded_by() {
+            if library.get(replacement).is_err() {
+                result.add_issue(ValidationIssue {
+                    level: ValidationLevel::Warning,
+                    file_path: prompt.source.clone().unwrap_or_default(),
+                    content_title: Some(prompt.name.clone()),
+                    line: None,
+                    column: None,
+                    message: format!("Superseded by '{replacement}', which does not exist"),
+                    suggestion: Some("Point superseded_by at an existing prompt".to_string()),
+                });
+            }
+        }
+    }
+
+    /// Run the test cases in a prompt's front matter, reporting each failure
+    fn check_inline_tests(
+        &self,
+        prompt: &Prompt,
+        library: &PromptLibrary,
+        result: &mut ValidationResult,
+    ) {
+        let issue = |message: String, suggestion: &str| ValidationIssue {
+            level: ValidationLevel::Error,
+            file_path: prompt.source.clone().unwrap_or_default(),
+            content_title: Some(prompt.name.clone()),
+            line: None,
+            column: None,
+            message,
+            suggestion: Some(suggestion.to_string()),
+        };
+        match swissarmyhammer::run_prompt_tests(prompt, library) {
+            Ok(results) => {
+                for test in results.iter().filter(|test| !test.passed()) {
+                    result.add_issue(issue(
+                        format!(
+                            "Inline test '{}' failed: {}",
+                            test.case,
+                            test.failures.join("; ")
+                        ),
+                        "Update the template or the test's expectations",
+                    ));
+                }
+            }
+            Err(e) => result.add_issue(issue(
+                e.to_string(),
+                "Give each test args and expect_contains or expect_not_contains",
+            )),
+        }
+    }
+
     /// Validates all workflow files using WorkflowResolver for consistent loading
     ///
     /// This uses the same loading mechanism as `flow list` to ensure consistency:
@@ -166,38 +355,57 @@ impl Validator {
             .context("Failed to retrieve loaded workflows from storage")?;
 
         // Validate each workflow
-        for workflow in workflows {
-            result.files_checked += 1;
-
-            // Get the source location for better error reporting
-            let source_location = match resolver.workflow_sources.get(&workflow.name) {
-                Some(swissarmyhammer::FileSource::Builtin) => "builtin",
-                Some(swissarmyhammer::FileSource::User) => "user",
-                Some(swissarmyhammer::FileSource::Local) => "local",
-                Some(swissarmyhammer::FileSource::Dynamic) => "dynamic",
-                None => "unknown",
-            };
-
-            // Create a path that includes the source location for better debugging
-            let workflow_path = PathBuf::from(format!(
-                "workflow:{}:{}",
-                source_location,
-                workflow.name.as_str()
-            ));
-
-            // Validate the workflow structure directly
-            self.validate_workflow_structure(&workflow, &workflow_path, result);
+        for workflow_result in self.validate_workflows(&workflows, &resolver)? {
+            result.merge(workflow_result);
         }
 
         Ok(())
     }
 
+    /// Validate loaded workflows in parallel, returning their results in the
+    /// order given
+    fn validate_workflows(
+        &self,
+        workflows: &[Workflow],
+        resolver: &WorkflowResolver,
+    ) -> Result<Vec<ValidationResult>> {
+        self.in_pool(|| {
+            workflows
+                .par_iter()
+                .map(|workflow| {
+                    let mut result = ValidationResult::new();
+                    result.files_checked += 1;
+
+                    // Get the source location for better error reporting
+                    let source_location = match resolver.workflow_sources.get(&workflow.name) {
+                        Some(swissarmyhammer::FileSource::Builtin) => "builtin",
+                        Some(swissarmyhammer::FileSource::User) => "user",
+                        Some(swissarmyhammer::FileSource::Local) => "local",
+                        Some(swissarmyhammer::FileSource::Dynamic) => "dynamic",
+                        None => "unknown",
+                    };
+
+                    // Create a path that includes the source location for better debugging
+                    let workflow_path = PathBuf::from(format!(
+                        "workflow:{}:{}",
+                        source_location,
+                        workflow.name.as_str()
+                    ));
+
+                    // Validate the workflow structure directly
+                    self.validate_workflow_structure(workflow, &workflow_path, &mut result);
+                    result
+                })
+                .collect()
+        })
+    }
+
     /// Validates a workflow structure directly using the Validatable trait
     ///
     /// This method delegates to the workflow's own validation implementation
     /// and adds any issues to the provided ValidationResult.
     fn validate_workflow_structure(
-        &mut self,
+        &self,
         workflow: &Workflow,
         workflow_path: &Path,
         result: &mut ValidationResult,
@@ -226,6 +434,60 @@ impl Validator {
         for issue in issues {
             result.add_issue(issue);
         }
+
+        // Report prompts the workflow still uses after they were deprecated
+        let mut graph = DependencyGraph::new();
+        graph.add_workflow(workflow, None);
+        let node = DependencyNode::workflow(workflow.name.as_str());
+        for dependency in graph.dependencies(&node) {
+            let DependencyNode::Prompt(name) = dependency else {
+                continue;
+            };
+            let Some(replacement) = self.deprecated_prompts.get(name) else {
+                continue;
+            };
+            result.add_issue(ValidationIssue {
+                level: ValidationLevel::Warning,
+                file_path: workflow_path.to_path_buf(),
+                content_title: Some(workflow.name.as_str().to_string()),
+                line: None,
+                column: None,
+                message: format!("Uses deprecated prompt '{name}'"),
+                suggestion: Some(match replacement {
+                    Some(replacement) => format!("Use prompt '{replacement}' instead"),
+                    None => "Replace the prompt before it is removed".to_string(),
+                }),
+            });
+        }
+
+        // Report pinned prompts whose pinned content is missing
+        for reference in pinned_prompts(workflow) {
+            let (Some(pin), Some(resolved)) =
+                (&reference.pin, self.resolved_prompts.get(&reference.name))
+            else {
+                continue;
+            };
+            if pin.matches(resolved) {
+                continue;
+            }
+            result.add_issue(ValidationIssue {
+                level: ValidationLevel::Error,
+                file_path: workflow_path.to_path_buf(),
+                content_title: Some(workflow.name.as_str().to_string()),
+                line: None,
+                column: None,
+                message: format!("Pinned prompt '{reference}' is missing"),
+                suggestion: Some(format!(
+                    "The prompt now has {}{}; restore the pinned content or update the pin",
+                    resolved.hash,
+                    resolved
+                        .version
+                        .as_ref()
+                        .map(|version| format!(" and version {version}"))
+                        .unwrap_or_default()
+                )),
+            });
+        }
     }
 
     /// Validates a single workflow file
@@ -509,16 +771,32 @@ impl Validator {
                 }
             };
 
-            for entry in entries.filter_map(Result::ok) {
-                let path = entry.path();
-                if path.is_file() {
-                    if let Some(ext) = path.extension() {
-                        if ext == "mermaid" || ext == "md" {
-                            // Read and validate the workflow file
-                            self.validate_workflow_file(&path, result);
-                        }
-                    }
-                }
+            // Sorted, so the report is in the same order on every run
+            let mut paths: Vec<PathBuf> = entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    path.is_file()
+                        && path
+                            .extension()
+                            .is_some_and(|ext| ext == "mermaid" || ext == "md")
+                })
+                .collect();
+            paths.sort();
+
+            // Read and validate the workflow files
+            let file_results = self.in_pool(|| {
+                paths
+                    .par_iter()
+                    .map(|path| {
+                        let mut file_result = ValidationResult::new();
+                        self.validate_workflow_file(path, &mut file_result);
+                        file_result
+                    })
+                    .collect::<Vec<_>>()
+            })?;
+            for file_result in file_results {
+                result.merge(file_result);
             }
         }
 
@@ -526,7 +804,7 @@ impl Validator {
     }
 
     /// Validates a single workflow file from a custom directory
-    fn validate_workflow_file(&mut self, workflow_path: &Path, result: &mut ValidationResult) {
+    fn validate_workflow_file(&self, workflow_path: &Path, result: &mut ValidationResult) {
         result.files_checked += 1;
 
         // Read the workflow file
@@ -642,20 +920,88 @@ impl Validator {
     }
 }
 
+/// Report every prompt and workflow affected by changes to a file
+///
+/// Each affected item is reported as info, and references among them that
+/// no longer resolve are reported as errors.
+pub fn add_impact_issues(
+    graph: &DependencyGraph,
+    changed_file: &Path,
+    result: &mut ValidationResult,
+) -> Result<()> {
+    let changed = graph.node_for_file(changed_file).ok_or_else(|| {
+        anyhow::anyhow!(
+            "{} is not a prompt or workflow file",
+            changed_file.display()
+        )
+    })?;
+    let affected = graph.affected_by(&changed);
+
+    result.add_issue(ValidationIssue {
+        level: ValidationLevel::Info,
+        file_path: changed_file.to_path_buf(),
+        content_title: Some(changed.to_string()),
+        line: None,
+        column: None,
+        message: format!(
+            "Changes to {changed} affect {} prompts and workflows",
+            affected.len()
+        ),
+        suggestion: None,
+    });
+
+    for item in affected {
+        let file_path = graph.path(item).map(Path::to_path_buf).unwrap_or_default();
+        result.add_issue(ValidationIssue {
+            level: ValidationLevel::Info,
+            file_path: file_path.clone(),
+            content_title: Some(item.to_string()),
+            line: None,
+            column: None,
+            message: format!("Affected by changes to {}", changed_file.display()),
+            suggestion: None,
+        });
+
+        for missing in graph.missing_dependencies(item) {
+            result.add_issue(ValidationIssue {
+                level: ValidationLevel::Error,
+                file_path: file_path.clone(),
+                content_title: Some(item.to_string()),
+                line: None,
+                column: None,
+                message: format!("References {missing}, which does not exist"),
+                suggestion: Some(format!(
+                    "Restore the {} or update the reference",
+                    missing.kind()
+                )),
+            });
+        }
+    }
+
+    Ok(())
+}
+
 pub fn run_validate_command_with_dirs(
     quiet: bool,
     format: ValidateFormat,
     workflow_dirs: Vec<String>,
+    impact: Option<PathBuf>,
+    jobs: usize,
 ) -> Result<i32> {
-    let mut validator = Validator::new(quiet);
+    let mut validator = Validator::new(quiet).with_jobs(jobs);
 
     // Validate with custom workflow directories if provided
-    let result = if workflow_dirs.is_empty() {
+    let mut result = if workflow_dirs.is_empty() {
         validator.validate_all_with_options()?
     } else {
         validator.validate_with_custom_dirs(workflow_dirs)?
     };
 
+    if let Some(changed_file) = impact {
+        let graph = DependencyGraph::load()?;
+        add_impact_issues(&graph, &changed_file, &mut result)?;
+    }
+
     validator.print_results(&result, format)?;
 
     // Return appropriate exit code
@@ -668,6 +1014,179 @@ pub fn run_validate_command_with_dirs(
     }
 }
 
+/// Wait after a change for the rest of the events a save makes
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Items to validate again after files change: the items the files define
+/// and every item that uses them
+///
+/// Files that define no prompt or workflow are ignored.
+pub fn stale_items(graph: &DependencyGraph, changed: &[PathBuf]) -> BTreeSet<DependencyNode> {
+    let mut stale = BTreeSet::new();
+    for file in changed {
+        if let Some(node) = graph.node_for_file(file) {
+            stale.extend(graph.affected_by(&node).into_iter().cloned());
+            stale.insert(node);
+        }
+    }
+    stale
+}
+
+/// Validation results of every prompt and workflow, kept up to date as files change
+pub struct ValidationWatch {
+    validator: Validator,
+    results: BTreeMap<DependencyNode, ValidationResult>,
+}
+
+impl ValidationWatch {
+    /// Validate everything once, `jobs` files at a time
+    pub fn new(quiet: bool, jobs: usize) -> Result<Self> {
+        let mut validator = Validator::new(quiet).with_jobs(jobs);
+        let results = validator.validate_items(None)?;
+        Ok(Self { validator, results })
+    }
+
+    /// Validate again the items affected by changed files
+    ///
+    /// Returns the number of items validated again. Items whose files were
+    /// deleted are dropped.
+    pub fn update(&mut self, changed: &[PathBuf]) -> Result<usize> {
+        let graph = DependencyGraph::load()?;
+        let stale = stale_items(&graph, changed);
+        if stale.is_empty() {
+            return Ok(0);
+        }
+        let fresh = self.validator.validate_items(Some(&stale))?;
+        for node in &stale {
+            self.results.remove(node);
+        }
+        let validated = fresh.len();
+        self.results.extend(fresh);
+        Ok(validated)
+    }
+
+    /// Results of every item combined
+    pub fn result(&self) -> ValidationResult {
+        let mut combined = ValidationResult::new();
+        for result in self.results.values() {
+            combined.merge(result.clone());
+        }
+        combined
+    }
+}
+
+/// Forwards changed prompt and workflow files to the watch loop
+#[derive(Clone)]
+struct ChangeForwarder {
+    tx: tokio::sync::mpsc::UnboundedSender<Vec<PathBuf>>,
+}
+
+impl FileWatcherCallback for ChangeForwarder {
+    async fn on_file_changed(
+        &self,
+        content: WatchedContent,
+        paths: Vec<PathBuf>,
+    ) -> swissarmyhammer::Result<()> {
+        if matches!(content, WatchedContent::Prompts | WatchedContent::Workflows) {
+            // The receiver is only gone when watching stops
+            let _ = self.tx.send(paths);
+        }
+        Ok(())
+    }
+
+    async fn on_error(&self, error: String) {
+        tracing::warn!("File watcher error: {}", error);
+    }
+}
+
+/// Validate everything, then validate again whatever each saved file affects
+///
+/// Runs until Ctrl+C and returns the exit code of the last results.
+pub async fn run_validate_watch(quiet: bool, format: ValidateFormat, jobs: usize) -> Result<i32> {
+    let mut watch = ValidationWatch::new(quiet, jobs)?;
+    print_watch_results(&watch, &format, None)?;
+
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let mut watcher = FileWatcher::new();
+    watcher.start_watching(ChangeForwarder { tx }).await?;
+
+    loop {
+        tokio::select! {
+            changed = rx.recv() => {
+                let Some(mut changed) = changed else { break };
+                tokio::time::sleep(WATCH_DEBOUNCE).await;
+                while let Ok(more) = rx.try_recv() {
+                    changed.extend(more);
+                }
+                changed.sort();
+                changed.dedup();
+
+                let started = Instant::now();
+                let validated = watch.update(&changed)?;
+                if validated > 0 {
+                    print_watch_results(&watch, &format, Some((&changed, validated, started.elapsed())))?;
+                }
+            }
+            _ = tokio::signal::ctrl_c() => break,
+        }
+    }
+    watcher.stop_watching_async().await;
+
+    let result = watch.result();
+    Ok(if result.has_errors() {
+        EXIT_ERROR
+    } else if result.has_warnings() {
+        EXIT_WARNING
+    } else {
+        EXIT_SUCCESS
+    })
+}
+
+/// Print the current results, replacing the previous ones on a terminal
+fn print_watch_results(
+    watch: &ValidationWatch,
+    format: &ValidateFormat,
+    update: Option<(&[PathBuf], usize, Duration)>,
+) -> Result<()> {
+    let text = matches!(format, ValidateFormat::Text);
+    if text && std::io::stdout().is_terminal() {
+        // Clear the screen and move the cursor home
+        print!("\x1B[2J\x1B[H");
+    }
+    watch
+        .validator
+        .print_results(&watch.result(), format.clone())?;
+
+    // Keep JSON output on stdout parseable
+    let status = match update {
+        Some((changed, validated, elapsed)) => {
+            let files: Vec<String> = changed
+                .iter()
+                .map(|path| {
+                    path.file_name()
+                        .unwrap_or(path.as_os_str())
+                        .to_string_lossy()
+                        .into_owned()
+                })
+                .collect();
+            format!(
+                "Validated {validated} of {} prompts and workflows again after changes to {} in {}ms",
+                watch.results.len(),
+                files.join(", "),
+                elapsed.as_millis()
+            )
+        }
+        None => format!("Validated {} prompts and workflows", watch.results.len()),
+    };
+    let watching = "👁️  Watching for changes (Press Ctrl+C to stop)...";
+    if text {
+        println!("\n{}\n{watching}", status.dimmed());
+    } else {
+        eprintln!("{status}\n{watching}");
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -984,6 +1503,107 @@ mod tests {
         assert_eq!(result.errors, 0);
     }
 
+    #[test]
+    fn test_inline_prompt_tests_are_run() {
+        let validator = Validator::new(false);
+        let mut library = PromptLibrary::new();
+        let prompt = swissarmyhammer::PromptLoader::new()
+            .load_from_string(
+                "greet",
+                "---\ntitle: Greet\ntests:\n  - name: greets by name\n    args:\n      name: Ada\n    expect_contains: \"Hello Ada\"\n  - args:\n      name: Bob\n    expect_contains: \"Goodbye\"\n---\nHello {{ name }}!\n",
+            )
+            .unwrap();
+        library.add(prompt.clone()).unwrap();
+
+        let mut result = ValidationResult::new();
+        validator.check_inline_tests(&prompt, &library, &mut result);
+        assert_eq!(result.errors, 1);
+        assert_eq!(
+            result.issues[0].message,
+            "Inline test 'test 2' failed: expected output to contain \"Goodbye\""
+        );
+    }
+
+    #[test]
+    fn test_validate_workflow_using_deprecated_prompt() {
+        let mut validator = Validator::new(false);
+        validator
+            .deprecated_prompts
+            .insert("old-review".to_string(), Some("review/code".to_string()));
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let workflow_path = temp_dir.path().join("review.md");
+        std::fs::write(
+            &workflow_path,
+            r#"```mermaid
+stateDiagram-v2
+    [*] --> Review
+    Review --> [*]
+```
+
+## Actions
+
+- Review: Execute prompt "old-review"
+"#,
+        )
+        .unwrap();
+
+        let mut result = ValidationResult::new();
+        validator.validate_workflow(&workflow_path, &mut result);
+
+        let issue = result
+            .issues
+            .iter()
+            .find(|issue| issue.message == "Uses deprecated prompt 'old-review'")
+            .expect("deprecated prompt is reported");
+        assert_eq!(issue.level, ValidationLevel::Warning);
+        assert_eq!(
+            issue.suggestion.as_deref(),
+            Some("Use prompt 'review/code' instead")
+        );
+    }
+
+    #[test]
+    fn test_validate_workflow_with_missing_pinned_prompt() {
+        let mut validator = Validator::new(false);
+        let mut prompt = Prompt::new("review", "Review {{ file }}");
+        prompt
+            .metadata
+            .insert("version".to_string(), serde_json::json!("2"));
+        validator
+            .resolved_prompts
+            .insert("review".to_string(), ResolvedPrompt::of(&prompt));
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let workflow_path = temp_dir.path().join("review.md");
+        std::fs::write(
+            &workflow_path,
+            r#"```mermaid
+stateDiagram-v2
+    [*] --> Review
+    Review --> Check
+    Check --> [*]
+```
+
+## Actions
+
+- Review: Execute prompt "review@2"
+- Check: Execute prompt "review@1"
+"#,
+        )
+        .unwrap();
+
+        let mut result = ValidationResult::new();
+        validator.validate_workflow(&workflow_path, &mut result);
+
+        let pinned: Vec<_> = result
+            .issues
+            .iter()
+            .filter(|issue| issue.message.starts_with("Pinned prompt"))
+            .collect();
+        assert_eq!(pinned.len(), 1);
+        assert_eq!(pinned[0].message, "Pinned prompt 'review@1' is missing");
+        assert_eq!(pinned[0].level, ValidationLevel::Error);
+    }
+
     #[test]
     fn test_validate_workflow_variable_detection_removed() {
         let mut validator = Validator::new(false);
@@ -1260,11 +1880,18 @@ stateDiagram-v2
         // Using from() to bypass validation and test the validator's handling
         let workflow = Workflow {
             name: WorkflowName::from(""),
+            title: None,
             description: "Test workflow".to_string(),
+            tags: Vec::new(),
+            variables: Vec::new(),
             states: HashMap::new(),
             transitions: vec![],
             initial_state: StateId::new("start"),
             metadata: HashMap::new(),
+            hooks: Default::default(),
+            context_window: Default::default(),
+            backend: None,
+            invariants: Vec::new(),
         };
 
         let workflow_path = PathBuf::from("workflow:test:");
@@ -1288,11 +1915,18 @@ stateDiagram-v2
         // Create a workflow with special characters in name (now allowed since parsers decide validity)
         let mut workflow = Workflow {
             name: WorkflowName::from("test@workflow!"),
+            title: None,
             description: "Test workflow".to_string(),
+            tags: Vec::new(),
+            variables: Vec::new(),
             states: HashMap::new(),
             transitions: vec![],
             initial_state: StateId::new("start"),
             metadata: HashMap::new(),
+            hooks: Default::default(),
+            context_window: Default::default(),
+            backend: None,
+            invariants: Vec::new(),
         };
 
         // Add the required initial state and terminal state to make it structurally valid
@@ -1338,11 +1972,18 @@ stateDiagram-v2
 
             let mut workflow = Workflow {
                 name: WorkflowName::from(name),
+                title: None,
                 description: "Test workflow".to_string(),
+                tags: Vec::new(),
+                variables: Vec::new(),
                 states: HashMap::new(),
                 transitions: vec![],
                 initial_state: StateId::new("start"),
                 metadata: HashMap::new(),
+                hooks: Default::default(),
+                context_window: Default::default(),
+                backend: None,
+                invariants: Vec::new(),
             };
 
             // Add required states to make it structurally valid
@@ -1405,6 +2046,46 @@ stateDiagram-v2
         }
     }
 
+    #[test]
+    fn test_parallel_validation_reports_in_order() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        for i in 0..12 {
+            let content = if i % 3 == 0 {
+                "flowchart TD\n    A --> B".to_string()
+            } else {
+                format!("stateDiagram-v2\n    [*] --> State{i}\n    State{i} --> [*]")
+            };
+            std::fs::write(temp_dir.path().join(format!("flow{i:02}.mermaid")), content).unwrap();
+        }
+        let dirs = vec![temp_dir.path().display().to_string()];
+
+        let validate = |jobs| {
+            let mut validator = Validator::new(false).with_jobs(jobs);
+            let mut result = ValidationResult::new();
+            validator
+                .validate_workflows_from_dirs(&mut result, dirs.clone())
+                .unwrap();
+            result
+        };
+        let serial = validate(1);
+        let parallel = validate(4);
+
+        assert_eq!(serial.files_checked, 12);
+        assert_eq!(parallel.files_checked, 12);
+        assert_eq!(serial.errors, 4);
+        let paths = |result: &ValidationResult| -> Vec<PathBuf> {
+            result
+                .issues
+                .iter()
+                .map(|issue| issue.file_path.clone())
+                .collect()
+        };
+        assert_eq!(paths(&serial), paths(&parallel));
+        let mut sorted = paths(&serial);
+        sorted.sort();
+        assert_eq!(paths(&serial), sorted);
+    }
+
     #[test]
     fn test_validate_workflow_complex_edge_cases() {
         let mut validator = Validator::new(false);
@@ -1667,4 +2348,74 @@ Neither of these should cause validation errors."#;
         // The fix ensures that in quiet mode, when only warnings exist,
         // no summary is shown at all (tested in integration tests)
     }
+
+    #[test]
+    fn test_impact_of_deleted_partial() {
+        let mut graph = DependencyGraph::new();
+        graph.add_prompt(
+            "greeting",
+            "{% render \"header\" %}",
+            Some(PathBuf::from(".swissarmyhammer/prompts/greeting.md")),
+        );
+        graph.add_prompt("welcome", "{% render \"greeting\" %}", None);
+        graph.add_prompt("unrelated", "Hello", None);
+
+        let mut result = ValidationResult::new();
+        add_impact_issues(
+            &graph,
+            Path::new(".swissarmyhammer/prompts/header.md"),
+            &mut result,
+        )
+        .unwrap();
+
+        let affected: Vec<&str> = result
+            .issues
+            .iter()
+            .filter(|issue| issue.message.starts_with("Affected by"))
+            .filter_map(|issue| issue.content_title.as_deref())
+            .collect();
+        assert_eq!(affected, vec!["prompt:greeting", "prompt:welcome"]);
+        assert_eq!(result.errors, 1);
+
+        let mut result = ValidationResult::new();
+        assert!(add_impact_issues(&graph, Path::new("README.md"), &mut result).is_err());
+    }
+
+    #[test]
+    fn test_stale_items_after_partial_changes() {
+        let mut graph = DependencyGraph::new();
+        graph.add_prompt(
+            "header",
+            "Header",
+            Some(PathBuf::from(".swissarmyhammer/prompts/header.md")),
+        );
+        graph.add_prompt(
+            "greeting",
+            "{% render \"header\" %}",
+            Some(PathBuf::from(".swissarmyhammer/prompts/greeting.md")),
+        );
+        graph.add_prompt("unrelated", "Hello", None);
+
+        let stale = stale_items(
+            &graph,
+            &[
+                PathBuf::from(".swissarmyhammer/prompts/header.md"),
+                PathBuf::from("README.md"),
+            ],
+        );
+        let stale: Vec<String> = stale.iter().map(ToString::to_string).collect();
+        assert_eq!(stale, vec!["prompt:greeting", "prompt:header"]);
+
+        assert!(stale_items(&graph, &[PathBuf::from("notes.txt")]).is_empty());
+    }
+
+    #[test]
+    fn test_validate_issue_branch_template() {
+        let mut result = ValidationResult::new();
+        Validator::validate_issue_branch_template("issue/{number}-{slug}", &mut result);
+        assert_eq!(result.errors, 0);
+
+        Validator::validate_issue_branch_template("issue/{slug}", &mut result);
+        assert_eq!(result.errors, 1);
+    }
 }
@@ -189,6 +189,42 @@ fn test_cli_memo_get_basic() {
         .stdout(predicate::str::contains("🔄 Updated:"));
 }
 
+#[test]
+fn test_cli_memo_json_output() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let create_output = memo_cmd_with_dir(&temp_dir)
+        .args(["memo", "create", "Json Memo"])
+        .arg("--content")
+        .arg("Content for json output")
+        .output()
+        .unwrap();
+    let memo_id = extract_memo_id(&String::from_utf8(create_output.stdout).unwrap());
+
+    let list_output = memo_cmd_with_dir(&temp_dir)
+        .args(["memo", "list", "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(list_output.status.success());
+    let memos: serde_json::Value = serde_json::from_slice(&list_output.stdout).unwrap();
+    assert_eq!(memos[0]["id"], memo_id);
+    assert_eq!(memos[0]["title"], "Json Memo");
+
+    let get_output = memo_cmd_with_dir(&temp_dir)
+        .args(["memo", "get", &memo_id, "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(get_output.status.success());
+    let memo: serde_json::Value = serde_json::from_slice(&get_output.stdout).unwrap();
+    assert_eq!(memo["content"], "Content for json output");
+
+    memo_cmd_with_dir(&temp_dir)
+        .args(["memo", "search", "json", "--format", "yaml"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("title: Json Memo"));
+}
+
 #[test]
 fn test_cli_memo_get_invalid_id() {
     let temp_dir = TempDir::new().unwrap();
@@ -262,6 +298,63 @@ fn test_cli_memo_update_with_stdin() {
         .stdout(predicate::str::contains("✅ Updated memo:"));
 }
 
+#[test]
+fn test_cli_memo_update_recorded_in_history() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let create_output = memo_cmd_with_dir(&temp_dir)
+        .args(["memo", "create", "History Test"])
+        .arg("--content")
+        .arg("Original content")
+        .output()
+        .unwrap();
+    let memo_id = extract_memo_id(&String::from_utf8(create_output.stdout).unwrap());
+
+    memo_cmd_with_dir(&temp_dir)
+        .args(["memo", "update", &memo_id])
+        .arg("--content")
+        .arg("Updated content")
+        .assert()
+        .success();
+
+    // Both versions are in the history the update wrote
+    memo_cmd_with_dir(&temp_dir)
+        .args(["memo", "history", &memo_id])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("v1 created"))
+        .stdout(predicate::str::contains("v2 updated"));
+
+    let history_output = memo_cmd_with_dir(&temp_dir)
+        .args(["memo", "history", &memo_id, "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(history_output.status.success());
+    let changes: serde_json::Value = serde_json::from_slice(&history_output.stdout).unwrap();
+    let contents: Vec<&str> = changes
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|change| change["content"].as_str().unwrap())
+        .collect();
+    assert_eq!(contents, ["Original content", "Updated content"]);
+
+    // And the first version can be restored from it
+    memo_cmd_with_dir(&temp_dir)
+        .args(["memo", "restore", &memo_id, "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Restored memo 'History Test' to version 1",
+        ));
+
+    memo_cmd_with_dir(&temp_dir)
+        .args(["memo", "get", &memo_id])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Original content"));
+}
+
 #[test]
 fn test_cli_memo_update_invalid_id() {
     let temp_dir = TempDir::new().unwrap();
@@ -711,6 +804,62 @@ fn test_cli_memo_workflow() {
         .stdout(predicate::str::contains("ℹ️ No memos found"));
 }
 
+#[test]
+fn test_cli_memo_notebook_workflow() {
+    let temp_dir = TempDir::new().unwrap();
+
+    // Create a memo straight into a notebook
+    let create_output = memo_cmd_with_dir(&temp_dir)
+        .args(["memo", "create", "Standup", "--notebook", "work"])
+        .arg("--content")
+        .arg("Daily standup notes")
+        .output()
+        .unwrap();
+    assert!(create_output.status.success());
+    let memo_id = extract_memo_id(&String::from_utf8(create_output.stdout).unwrap());
+
+    // The notebook lists the memo
+    memo_cmd_with_dir(&temp_dir)
+        .args(["memo", "list", "--notebook", "work"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("📝 Found 1 memo"))
+        .stdout(predicate::str::contains("Standup"));
+
+    memo_cmd_with_dir(&temp_dir)
+        .args(["memo", "notebook", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("work (1 memos)"));
+
+    // Move it into another notebook, keeping its ID
+    memo_cmd_with_dir(&temp_dir)
+        .args(["memo", "move", &memo_id, "office"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "Moved memo '{memo_id}' to notebook 'office'"
+        )));
+
+    memo_cmd_with_dir(&temp_dir)
+        .args(["memo", "list", "--notebook", "office"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Standup"));
+
+    memo_cmd_with_dir(&temp_dir)
+        .args(["memo", "list", "--notebook", "work"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("ℹ️ No memos found"));
+
+    memo_cmd_with_dir(&temp_dir)
+        .args(["memo", "get", &memo_id])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Daily standup notes"));
+}
+
 #[test]
 fn test_cli_memo_content_with_newlines() {
     let temp_dir = TempDir::new().unwrap();
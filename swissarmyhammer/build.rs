@@ -9,6 +9,8 @@ fn main() {
     println!("cargo:rerun-if-changed=../builtin/prompts");
     // Tell Cargo to re-run this build script if the workflows directory changes
     println!("cargo:rerun-if-changed=../builtin/workflows");
+    // Tell Cargo to re-run this build script if the workflow templates change
+    println!("cargo:rerun-if-changed=../builtin/workflow-templates");
     // Tell Cargo to re-run this build script if the tool descriptions change
     println!("cargo:rerun-if-changed=src/mcp/tools");
 
@@ -20,6 +22,9 @@ fn main() {
     // Generate builtin workflows
     generate_builtin_workflows(&out_dir);
 
+    // Generate builtin workflow templates
+    generate_builtin_workflow_templates(&out_dir);
+
     // Generate tool descriptions
     generate_tool_descriptions(&out_dir);
 }
@@ -72,6 +77,30 @@ fn generate_builtin_workflows(out_dir: &str) {
     fs::write(&dest_path, code).unwrap();
 }
 
+fn generate_builtin_workflow_templates(out_dir: &str) {
+    let dest_path = Path::new(&out_dir).join("builtin_workflow_templates.rs");
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let builtin_dir = Path::new(&manifest_dir).join("../builtin/workflow-templates");
+
+    let mut code = String::new();
+    code.push_str("// Auto-generated builtin workflow templates - do not edit manually\n");
+    code.push_str("// Generated by build.rs from builtin/workflow-templates directory\n\n");
+    code.push_str("/// Get all built-in workflow templates as a vector of (name, content) tuples\n");
+    code.push_str("pub fn get_builtin_workflow_templates() -> Vec<(&'static str, &'static str)> {\n");
+    code.push_str("    vec![\n");
+
+    if builtin_dir.exists() {
+        // Templates are laid out like workflows, one markdown file each
+        collect_workflows(&builtin_dir, "", &mut code);
+    }
+
+    code.push_str("    ]\n");
+    code.push_str("}\n");
+
+    fs::write(&dest_path, code).unwrap();
+}
+
 fn collect_prompts(dir: &Path, prefix: &str, code: &mut String) {
     if let Ok(entries) = fs::read_dir(dir) {
         let mut entries: Vec<_> = entries.collect();
@@ -37,6 +37,9 @@ Focus on:
         required: true,
         default: None,
         type_hint: Some("string".to_string()),
+        choices: None,
+        completion: None,
+        source: None,
     })
     .add_argument(ArgumentSpec {
         name: "code".to_string(),
@@ -44,6 +47,9 @@ Focus on:
         required: true,
         default: None,
         type_hint: Some("string".to_string()),
+        choices: None,
+        completion: None,
+        source: None,
     });
 
     // Add the prompt to the library
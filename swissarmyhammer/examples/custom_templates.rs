@@ -84,6 +84,9 @@ Fixes: {{ issues }}
         required: true,
         default: None,
         type_hint: Some("string".to_string()),
+        choices: None,
+        completion: None,
+        source: None,
     })
     .add_argument(ArgumentSpec {
         name: "description".to_string(),
@@ -91,6 +94,9 @@ Fixes: {{ issues }}
         required: true,
         default: None,
         type_hint: Some("string".to_string()),
+        choices: None,
+        completion: None,
+        source: None,
     })
     .add_argument(ArgumentSpec {
         name: "body".to_string(),
@@ -98,6 +104,9 @@ Fixes: {{ issues }}
         required: false,
         default: None,
         type_hint: Some("string".to_string()),
+        choices: None,
+        completion: None,
+        source: None,
     })
     .add_argument(ArgumentSpec {
         name: "breaking_change".to_string(),
@@ -105,6 +114,9 @@ Fixes: {{ issues }}
         required: false,
         default: None,
         type_hint: Some("string".to_string()),
+        choices: None,
+        completion: None,
+        source: None,
     })
     .add_argument(ArgumentSpec {
         name: "issues".to_string(),
@@ -112,6 +124,9 @@ Fixes: {{ issues }}
         required: false,
         default: None,
         type_hint: Some("string".to_string()),
+        choices: None,
+        completion: None,
+        source: None,
     });
 
     library.add(prompt)?;
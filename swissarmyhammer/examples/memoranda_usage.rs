@@ -418,6 +418,7 @@ mod tests {
                 .to_string(),
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
+            notebook: None,
         };
 
         let stats = analyze_memo_content(&memo).await;
@@ -8,8 +8,10 @@ use crate::common::env_loader::EnvLoader;
 /// Configuration settings for the SwissArmyHammer application
 #[derive(Debug, Clone)]
 pub struct Config {
-    /// Prefix for issue branches (default: "issue/")
+    /// Prefix for issue branches, used when no branch template is set (default: "issue/")
     pub issue_branch_prefix: String,
+    /// Template naming issue branches with {name}, {number} and {slug} (default: "issue/{name}")
+    pub issue_branch_template: String,
     /// Maximum number of pending issues to display in summary (default: 5)
     pub max_pending_issues_in_summary: usize,
     /// Maximum content length for issue content (default: 50000)
@@ -36,12 +38,125 @@ pub struct Config {
     pub virtual_issue_number_range: u32,
     /// Default content for new issues (default: "# Issue\n\nDescribe the issue here.")
     pub default_issue_content: String,
+    /// Default Claude model for workflow prompt actions (default: Claude CLI default)
+    pub claude_model: Option<String>,
+    /// Default sampling temperature for workflow prompt actions (default: unset)
+    pub claude_temperature: Option<f32>,
+    /// Default maximum output tokens for workflow prompt actions (default: unset)
+    pub claude_max_output_tokens: Option<u32>,
+    /// Default system prompt override for workflow prompt actions (default: unset)
+    pub claude_system_prompt: Option<String>,
+    /// Backend workflow prompts are sent to: claude, sampling, anthropic or openai (default: "claude")
+    pub prompt_backend: String,
+    /// Base URL of the Anthropic API for the anthropic backend (default: "https://api.anthropic.com")
+    pub anthropic_base_url: String,
+    /// Base URL of the OpenAI-compatible endpoint for the openai backend (default: "https://api.openai.com/v1")
+    pub openai_base_url: String,
+    /// Default model for prompts sent to the openai backend (default: unset)
+    pub openai_model: Option<String>,
+    /// Allow loaded files to be symlinks resolving outside their directory (default: false)
+    pub allow_symlinks_outside_root: bool,
+    /// Maximum directory nesting below a loader's root directory (default: 10)
+    pub max_path_depth: usize,
+    /// Comma separated glob patterns of paths loaders must skip (default: none)
+    pub path_denylist: Vec<String>,
+    /// Render templates in strict mode, failing on undefined variables (default: false)
+    pub strict_templates: bool,
+    /// Minify rendered prompts, dropping comment lines, trailing whitespace and extra blank lines (default: false)
+    pub minify_prompts: bool,
+    /// Bytes of a file the `include_file` and `glob_files` template tags embed before cutting it short (default: 100000)
+    pub template_include_max_bytes: usize,
+    /// Most files a `glob_files` template tag embeds, whatever its `limit` (default: 50)
+    pub template_glob_max_files: usize,
+    /// Bytes of each text in the `git` template object, and of a `git_diff` filter's output, before cutting it short (default: 16384)
+    pub template_git_max_bytes: usize,
+    /// Comma separated issue directories merged beneath `./issues`, lowest priority first (default: none)
+    pub issue_dirs: Vec<std::path::PathBuf>,
+    /// Comma separated `name=path` repositories issues can name with `repo:` (default: none)
+    pub repos: std::collections::BTreeMap<String, std::path::PathBuf>,
+    /// Comma separated MCP tool groups to serve, all groups when empty (default: none)
+    pub mcp_tool_groups: Vec<String>,
+    /// Comma separated MCP tool groups never to serve (default: none)
+    pub mcp_disabled_tool_groups: Vec<String>,
+    /// Prompt bodies kept in memory when loading them lazily, 0 loads every body eagerly (default: 0)
+    pub prompt_body_cache_size: usize,
+    /// Comma separated front matter fields every prompt must set (default: none)
+    pub prompt_required_fields: Vec<String>,
+    /// Comma separated front matter fields prompts may set (default: none)
+    pub prompt_optional_fields: Vec<String>,
+    /// What loading does with prompt directories failing checksum or signature verification: off, warn or require (default: "warn")
+    pub prompt_integrity: String,
+    /// Minisign public key prompt directory checksums must be signed with (default: unset)
+    pub prompt_public_key: Option<String>,
+    /// US dollars workflow prompts may spend per day (default: unset)
+    pub cost_daily_budget_usd: Option<f64>,
+    /// US dollars workflow prompts may spend per issue (default: unset)
+    pub cost_issue_budget_usd: Option<f64>,
+    /// Percent of a cost budget at which a warning is raised (default: 80)
+    pub cost_budget_warning_percent: u32,
+    /// Refuse to start prompts once a cost budget is spent (default: false)
+    pub cost_budget_hard_limit: bool,
+    /// US dollars per million input tokens in cost estimates (default: 3.0)
+    pub cost_input_usd_per_mtok: f64,
+    /// US dollars per million output tokens in cost estimates (default: 15.0)
+    pub cost_output_usd_per_mtok: f64,
+    /// Output tokens per input token in low cost estimates (default: 0.5)
+    pub cost_output_ratio_low: f64,
+    /// Output tokens per input token in high cost estimates (default: 3.0)
+    pub cost_output_ratio_high: f64,
+    /// Size in megabytes at which the MCP log file is rotated, 0 for never (default: 10)
+    pub log_max_size_mb: u64,
+    /// Rotate the MCP log file when the local date changes (default: true)
+    pub log_rotate_daily: bool,
+    /// Number of rotated MCP log files kept (default: 5)
+    pub log_retention: usize,
+    /// Comma separated issue states between open and complete, such as in-review (default: none)
+    pub issue_states: Vec<String>,
+    /// Comma separated `from->to` issue state moves allowed, any move when empty (default: none)
+    pub issue_transitions: Vec<String>,
+    /// Days after which an open issue is flagged as past its SLA (default: unset)
+    pub issue_sla_warn_days: Option<u32>,
+    /// Days after which an open issue is escalated (default: unset)
+    pub issue_sla_escalate_days: Option<u32>,
+    /// Label added to escalated issues by `issue stale --escalate` (default: "escalated")
+    pub issue_sla_escalate_label: String,
+    /// State an issue moves to when the workflow its front matter names completes (default: "complete")
+    pub issue_workflow_complete_state: String,
+    /// State an issue moves to when the workflow its front matter names fails, left alone when unset (default: unset)
+    pub issue_workflow_failed_state: Option<String>,
+    /// Bytes above which JSON memo storage writes a memo body zstd compressed, 0 never compresses (default: 65536)
+    pub memo_compression_threshold_bytes: usize,
+    /// Times `flow run` starts a run again after a transient failure (default: 0)
+    pub workflow_retry_attempts: u32,
+    /// Seconds before the first retry of a failed run, doubled for each retry (default: 30)
+    pub workflow_retry_backoff_seconds: u64,
+    /// Longest wait in seconds between retries of a failed run (default: 600)
+    pub workflow_retry_max_backoff_seconds: u64,
+    /// Identifier scheme new issue names start with: name, numeric, ulid, date or project (default: "name")
+    pub issue_numbering: String,
+    /// Prefix of identifiers under the project issue numbering scheme, such as PROJ (default: "")
+    pub issue_project_prefix: String,
+    /// Queries whose advanced search results are cached, 0 disables the cache (default: 100)
+    pub search_query_cache_size: usize,
+    /// Seconds cached advanced search results stay valid (default: 300)
+    pub search_query_cache_ttl_seconds: u64,
+    /// Comma separated URLs events are POSTed to (default: none)
+    pub webhook_urls: Vec<String>,
+    /// Comma separated events sent to webhooks, all webhook events when empty (default: none)
+    pub webhook_events: Vec<String>,
+    /// Secret webhook request bodies are signed with using HMAC-SHA256 (default: unset)
+    pub webhook_secret: Option<String>,
+    /// Times a failed webhook delivery is tried again (default: 3)
+    pub webhook_max_retries: u32,
+    /// Seconds a webhook request may take (default: 10)
+    pub webhook_timeout_seconds: u64,
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
             issue_branch_prefix: "issue/".to_string(),
+            issue_branch_template: "issue/{name}".to_string(),
             max_pending_issues_in_summary: 5,
             max_content_length: 50000,
             max_line_length: 10000,
@@ -55,6 +170,62 @@ impl Default for Config {
             virtual_issue_number_base: 500_000,
             virtual_issue_number_range: 500_000,
             default_issue_content: "# Issue\n\nDescribe the issue here.".to_string(),
+            claude_model: None,
+            claude_temperature: None,
+            claude_max_output_tokens: None,
+            claude_system_prompt: None,
+            prompt_backend: "claude".to_string(),
+            anthropic_base_url: "https://api.anthropic.com".to_string(),
+            openai_base_url: "https://api.openai.com/v1".to_string(),
+            openai_model: None,
+            allow_symlinks_outside_root: false,
+            max_path_depth: crate::security::MAX_DIRECTORY_DEPTH,
+            path_denylist: Vec::new(),
+            strict_templates: false,
+            minify_prompts: false,
+            template_include_max_bytes: crate::security::MAX_TEMPLATE_SIZE,
+            template_glob_max_files: 50,
+            template_git_max_bytes: 16_384,
+            issue_dirs: Vec::new(),
+            repos: std::collections::BTreeMap::new(),
+            mcp_tool_groups: Vec::new(),
+            mcp_disabled_tool_groups: Vec::new(),
+            prompt_body_cache_size: 0,
+            prompt_required_fields: Vec::new(),
+            prompt_optional_fields: Vec::new(),
+            prompt_integrity: "warn".to_string(),
+            prompt_public_key: None,
+            cost_daily_budget_usd: None,
+            cost_issue_budget_usd: None,
+            cost_budget_warning_percent: 80,
+            cost_budget_hard_limit: false,
+            cost_input_usd_per_mtok: 3.0,
+            cost_output_usd_per_mtok: 15.0,
+            cost_output_ratio_low: 0.5,
+            cost_output_ratio_high: 3.0,
+            log_max_size_mb: 10,
+            log_rotate_daily: true,
+            log_retention: 5,
+            issue_states: Vec::new(),
+            issue_transitions: Vec::new(),
+            issue_sla_warn_days: None,
+            issue_sla_escalate_days: None,
+            issue_sla_escalate_label: "escalated".to_string(),
+            issue_workflow_complete_state: "complete".to_string(),
+            issue_workflow_failed_state: None,
+            memo_compression_threshold_bytes: 65_536,
+            workflow_retry_attempts: 0,
+            workflow_retry_backoff_seconds: 30,
+            workflow_retry_max_backoff_seconds: 600,
+            issue_numbering: "name".to_string(),
+            issue_project_prefix: String::new(),
+            search_query_cache_size: 100,
+            search_query_cache_ttl_seconds: 300,
+            webhook_urls: Vec::new(),
+            webhook_events: Vec::new(),
+            webhook_secret: None,
+            webhook_max_retries: 3,
+            webhook_timeout_seconds: 10,
         }
     }
 }
@@ -64,9 +235,14 @@ impl Config {
     /// or defaults if environment variables are not set
     pub fn new() -> Self {
         let loader = EnvLoader::new("SWISSARMYHAMMER");
+        let issue_branch_prefix = loader.load_string("ISSUE_BRANCH_PREFIX", "issue/");
 
         Self {
-            issue_branch_prefix: loader.load_string("ISSUE_BRANCH_PREFIX", "issue/"),
+            issue_branch_template: loader.load_string(
+                "ISSUE_BRANCH_TEMPLATE",
+                &format!("{issue_branch_prefix}{{name}}"),
+            ),
+            issue_branch_prefix,
             max_pending_issues_in_summary: loader.load_parsed("MAX_PENDING_ISSUES_IN_SUMMARY", 5),
             max_content_length: loader.load_parsed("MAX_CONTENT_LENGTH", 50000),
             max_line_length: loader.load_parsed("MAX_LINE_LENGTH", 10000),
@@ -83,6 +259,84 @@ impl Config {
                 "DEFAULT_ISSUE_CONTENT",
                 "# Issue\n\nDescribe the issue here.",
             ),
+            claude_model: loader.load_optional("CLAUDE_MODEL"),
+            claude_temperature: loader.load_optional("CLAUDE_TEMPERATURE"),
+            claude_max_output_tokens: loader.load_optional("CLAUDE_MAX_OUTPUT_TOKENS"),
+            claude_system_prompt: loader.load_optional("CLAUDE_SYSTEM_PROMPT"),
+            prompt_backend: loader.load_string("PROMPT_BACKEND", "claude"),
+            anthropic_base_url: loader
+                .load_string("ANTHROPIC_BASE_URL", "https://api.anthropic.com"),
+            openai_base_url: loader.load_string("OPENAI_BASE_URL", "https://api.openai.com/v1"),
+            openai_model: loader.load_optional("OPENAI_MODEL"),
+            allow_symlinks_outside_root: loader.load_parsed("ALLOW_SYMLINKS_OUTSIDE_ROOT", false),
+            max_path_depth: loader
+                .load_parsed("MAX_PATH_DEPTH", crate::security::MAX_DIRECTORY_DEPTH),
+            path_denylist: loader
+                .load_string("PATH_DENYLIST", "")
+                .split(',')
+                .map(str::trim)
+                .filter(|pattern| !pattern.is_empty())
+                .map(str::to_string)
+                .collect(),
+            strict_templates: loader.load_parsed("STRICT_TEMPLATES", false),
+            minify_prompts: loader.load_parsed("MINIFY_PROMPTS", false),
+            template_include_max_bytes: loader.load_parsed(
+                "TEMPLATE_INCLUDE_MAX_BYTES",
+                crate::security::MAX_TEMPLATE_SIZE,
+            ),
+            template_glob_max_files: loader.load_parsed("TEMPLATE_GLOB_MAX_FILES", 50),
+            template_git_max_bytes: loader.load_parsed("TEMPLATE_GIT_MAX_BYTES", 16_384),
+            issue_dirs: loader
+                .load_string("ISSUE_DIRS", "")
+                .split(',')
+                .map(str::trim)
+                .filter(|dir| !dir.is_empty())
+                .map(std::path::PathBuf::from)
+                .collect(),
+            repos: load_repos(&loader),
+            mcp_tool_groups: load_list(&loader, "MCP_TOOL_GROUPS"),
+            mcp_disabled_tool_groups: load_list(&loader, "MCP_DISABLED_TOOL_GROUPS"),
+            prompt_body_cache_size: loader.load_parsed("PROMPT_BODY_CACHE_SIZE", 0),
+            prompt_required_fields: load_list(&loader, "PROMPT_REQUIRED_FIELDS"),
+            prompt_optional_fields: load_list(&loader, "PROMPT_OPTIONAL_FIELDS"),
+            prompt_integrity: loader.load_string("PROMPT_INTEGRITY", "warn"),
+            prompt_public_key: loader.load_optional("PROMPT_PUBLIC_KEY"),
+            cost_daily_budget_usd: loader.load_optional("COST_DAILY_BUDGET_USD"),
+            cost_issue_budget_usd: loader.load_optional("COST_ISSUE_BUDGET_USD"),
+            cost_budget_warning_percent: loader.load_parsed("COST_BUDGET_WARNING_PERCENT", 80),
+            cost_budget_hard_limit: loader.load_parsed("COST_BUDGET_HARD_LIMIT", false),
+            cost_input_usd_per_mtok: loader.load_parsed("COST_INPUT_USD_PER_MTOK", 3.0),
+            cost_output_usd_per_mtok: loader.load_parsed("COST_OUTPUT_USD_PER_MTOK", 15.0),
+            cost_output_ratio_low: loader.load_parsed("COST_OUTPUT_RATIO_LOW", 0.5),
+            cost_output_ratio_high: loader.load_parsed("COST_OUTPUT_RATIO_HIGH", 3.0),
+            log_max_size_mb: loader.load_parsed("LOG_MAX_SIZE_MB", 10),
+            log_rotate_daily: loader.load_parsed("LOG_ROTATE_DAILY", true),
+            log_retention: loader.load_parsed("LOG_RETENTION", 5),
+            issue_states: load_list(&loader, "ISSUE_STATES"),
+            issue_transitions: load_list(&loader, "ISSUE_TRANSITIONS"),
+            issue_sla_warn_days: loader.load_optional("ISSUE_SLA_WARN_DAYS"),
+            issue_sla_escalate_days: loader.load_optional("ISSUE_SLA_ESCALATE_DAYS"),
+            issue_sla_escalate_label: loader.load_string("ISSUE_SLA_ESCALATE_LABEL", "escalated"),
+            issue_workflow_complete_state: loader
+                .load_string("ISSUE_WORKFLOW_COMPLETE_STATE", "complete"),
+            issue_workflow_failed_state: loader.load_optional("ISSUE_WORKFLOW_FAILED_STATE"),
+            memo_compression_threshold_bytes: loader
+                .load_parsed("MEMO_COMPRESSION_THRESHOLD_BYTES", 65_536),
+            workflow_retry_attempts: loader.load_parsed("WORKFLOW_RETRY_ATTEMPTS", 0),
+            workflow_retry_backoff_seconds: loader
+                .load_parsed("WORKFLOW_RETRY_BACKOFF_SECONDS", 30),
+            workflow_retry_max_backoff_seconds: loader
+                .load_parsed("WORKFLOW_RETRY_MAX_BACKOFF_SECONDS", 600),
+            issue_numbering: loader.load_string("ISSUE_NUMBERING", "name"),
+            issue_project_prefix: loader.load_string("ISSUE_PROJECT_PREFIX", ""),
+            search_query_cache_size: loader.load_parsed("SEARCH_QUERY_CACHE_SIZE", 100),
+            search_query_cache_ttl_seconds: loader
+                .load_parsed("SEARCH_QUERY_CACHE_TTL_SECONDS", 300),
+            webhook_urls: load_list(&loader, "WEBHOOK_URLS"),
+            webhook_events: load_list(&loader, "WEBHOOK_EVENTS"),
+            webhook_secret: loader.load_optional("WEBHOOK_SECRET"),
+            webhook_max_retries: loader.load_parsed("WEBHOOK_MAX_RETRIES", 3),
+            webhook_timeout_seconds: loader.load_parsed("WEBHOOK_TIMEOUT_SECONDS", 10),
         }
     }
 
@@ -101,6 +355,32 @@ impl Config {
     }
 }
 
+/// Load a comma separated list, skipping empty entries
+fn load_list(loader: &EnvLoader, suffix: &str) -> Vec<String> {
+    loader
+        .load_string(suffix, "")
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Load `name=path` repositories; a bare path is named after its last component
+fn load_repos(loader: &EnvLoader) -> std::collections::BTreeMap<String, std::path::PathBuf> {
+    load_list(loader, "REPOS")
+        .into_iter()
+        .filter_map(|entry| match entry.split_once('=') {
+            Some((name, path)) => Some((name.trim().to_string(), path.trim().into())),
+            None => {
+                let path = std::path::PathBuf::from(&entry);
+                let name = path.file_name()?.to_str()?.to_string();
+                Some((name, path))
+            }
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -109,6 +389,7 @@ mod tests {
     fn test_default_config() {
         let config = Config::default();
         assert_eq!(config.issue_branch_prefix, "issue/");
+        assert_eq!(config.issue_branch_template, "issue/{name}");
         assert_eq!(config.issue_number_width, 6);
         assert_eq!(config.max_pending_issues_in_summary, 5);
         assert_eq!(config.min_issue_number, 1);
@@ -123,6 +404,56 @@ mod tests {
             config.default_issue_content,
             "# Issue\n\nDescribe the issue here."
         );
+        assert!(config.claude_model.is_none());
+        assert!(config.claude_temperature.is_none());
+        assert!(config.claude_max_output_tokens.is_none());
+        assert!(config.claude_system_prompt.is_none());
+        assert_eq!(config.prompt_backend, "claude");
+        assert!(config.openai_model.is_none());
+        assert!(!config.allow_symlinks_outside_root);
+        assert_eq!(config.max_path_depth, crate::security::MAX_DIRECTORY_DEPTH);
+        assert!(config.path_denylist.is_empty());
+        assert!(!config.strict_templates);
+        assert!(!config.minify_prompts);
+        assert_eq!(config.template_include_max_bytes, 100_000);
+        assert_eq!(config.template_glob_max_files, 50);
+        assert_eq!(config.template_git_max_bytes, 16_384);
+        assert!(config.issue_dirs.is_empty());
+        assert!(config.mcp_tool_groups.is_empty());
+        assert!(config.mcp_disabled_tool_groups.is_empty());
+        assert_eq!(config.prompt_body_cache_size, 0);
+        assert!(config.prompt_required_fields.is_empty());
+        assert!(config.prompt_optional_fields.is_empty());
+        assert_eq!(config.prompt_integrity, "warn");
+        assert!(config.prompt_public_key.is_none());
+        assert!(config.cost_daily_budget_usd.is_none());
+        assert!(config.cost_issue_budget_usd.is_none());
+        assert_eq!(config.cost_budget_warning_percent, 80);
+        assert!(!config.cost_budget_hard_limit);
+        assert_eq!(config.cost_input_usd_per_mtok, 3.0);
+        assert_eq!(config.cost_output_ratio_high, 3.0);
+        assert_eq!(config.log_max_size_mb, 10);
+        assert!(config.log_rotate_daily);
+        assert_eq!(config.log_retention, 5);
+        assert!(config.issue_states.is_empty());
+        assert!(config.issue_transitions.is_empty());
+        assert!(config.issue_sla_warn_days.is_none());
+        assert!(config.issue_sla_escalate_days.is_none());
+        assert_eq!(config.issue_sla_escalate_label, "escalated");
+        assert_eq!(config.issue_workflow_complete_state, "complete");
+        assert!(config.issue_workflow_failed_state.is_none());
+        assert_eq!(config.memo_compression_threshold_bytes, 65_536);
+        assert_eq!(config.workflow_retry_attempts, 0);
+        assert_eq!(config.workflow_retry_backoff_seconds, 30);
+        assert_eq!(config.workflow_retry_max_backoff_seconds, 600);
+        assert_eq!(config.issue_numbering, "name");
+        assert!(config.issue_project_prefix.is_empty());
+        assert_eq!(config.search_query_cache_size, 100);
+        assert_eq!(config.search_query_cache_ttl_seconds, 300);
+        assert!(config.webhook_urls.is_empty());
+        assert!(config.webhook_secret.is_none());
+        assert_eq!(config.webhook_max_retries, 3);
+        assert_eq!(config.webhook_timeout_seconds, 10);
     }
 
     #[test]
@@ -187,6 +518,7 @@ mod tests {
 
         let config = Config::new();
         assert_eq!(config.issue_branch_prefix, "feature/");
+        assert_eq!(config.issue_branch_template, "feature/{name}");
         assert_eq!(config.issue_number_width, 8);
         assert_eq!(config.max_pending_issues_in_summary, 10);
         assert_eq!(config.min_issue_number, 1);
@@ -233,4 +565,64 @@ mod tests {
             None => std::env::remove_var("SWISSARMYHAMMER_DEFAULT_ISSUE_CONTENT"),
         }
     }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_config_claude_settings_from_env() {
+        let keys = [
+            ("SWISSARMYHAMMER_CLAUDE_MODEL", "sonnet"),
+            ("SWISSARMYHAMMER_CLAUDE_TEMPERATURE", "0.2"),
+            ("SWISSARMYHAMMER_CLAUDE_MAX_OUTPUT_TOKENS", "4096"),
+            ("SWISSARMYHAMMER_CLAUDE_SYSTEM_PROMPT", "Be terse."),
+            ("SWISSARMYHAMMER_PROMPT_BACKEND", "openai"),
+            (
+                "SWISSARMYHAMMER_OPENAI_BASE_URL",
+                "http://localhost:11434/v1",
+            ),
+            ("SWISSARMYHAMMER_OPENAI_MODEL", "llama3.1"),
+        ];
+        let originals: Vec<_> = keys
+            .iter()
+            .map(|(key, _)| (*key, std::env::var(key).ok()))
+            .collect();
+
+        for (key, value) in keys {
+            std::env::set_var(key, value);
+        }
+
+        let config = Config::new();
+        assert_eq!(config.claude_model.as_deref(), Some("sonnet"));
+        assert_eq!(config.claude_temperature, Some(0.2));
+        assert_eq!(config.claude_max_output_tokens, Some(4096));
+        assert_eq!(config.claude_system_prompt.as_deref(), Some("Be terse."));
+        assert_eq!(config.prompt_backend, "openai");
+        assert_eq!(config.openai_base_url, "http://localhost:11434/v1");
+        assert_eq!(config.openai_model.as_deref(), Some("llama3.1"));
+
+        for (key, original) in originals {
+            match original {
+                Some(val) => std::env::set_var(key, val),
+                None => std::env::remove_var(key),
+            }
+        }
+    }
+    #[test]
+    #[serial_test::serial]
+    fn test_config_repos_from_env() {
+        let original = std::env::var("SWISSARMYHAMMER_REPOS").ok();
+        std::env::set_var("SWISSARMYHAMMER_REPOS", "api = ../api-server, ../web,");
+
+        let config = Config::new();
+        assert_eq!(config.repos.len(), 2);
+        assert_eq!(
+            config.repos["api"],
+            std::path::PathBuf::from("../api-server")
+        );
+        assert_eq!(config.repos["web"], std::path::PathBuf::from("../web"));
+
+        match original {
+            Some(val) => std::env::set_var("SWISSARMYHAMMER_REPOS", val),
+            None => std::env::remove_var("SWISSARMYHAMMER_REPOS"),
+        }
+    }
 }
@@ -0,0 +1,652 @@
+//! Cost budgets for workflow prompts
+//!
+//! Prompt actions report what each Claude call cost. [`CostTracker`] keeps a
+//! ledger of that spend in `.swissarmyhammer/cost`, each entry tagged with the
+//! workflow run, state and prompt it was spent on. [`CostTracker::breakdown`]
+//! adds the ledger up per state or per prompt across runs, to show which step
+//! of a workflow is expensive.
+//!
+//! When a daily or per-issue budget is configured, the tracker raises a
+//! [`BudgetEvent`] when the spend crosses the warning threshold or the budget
+//! itself. With a hard limit it also refuses new calls once a budget is spent,
+//! so a runaway workflow stops instead of spending more.
+//!
+//! Budget events are logged and appended to `budget_events.jsonl` next to
+//! the ledger. The MCP server watches that file and forwards new events to
+//! its client as logging notifications. Exceeded and refused budgets are
+//! also published as [`crate::events::Event::BudgetExceeded`], which
+//! webhooks can announce.
+//!
+//! [`CostRates`] holds the token prices and typical response sizes used to
+//! forecast what a workflow will cost before it runs.
+
+use crate::config::Config;
+use crate::error::Result;
+use crate::events::{event_bus, Event};
+use crate::workflow::{StateId, WorkflowName, WorkflowRunId};
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// File in the cost directory holding one [`CostEntry`] per line
+pub const COST_LEDGER_FILE: &str = "ledger.jsonl";
+
+/// File in the cost directory holding one [`BudgetEvent`] per line
+pub const BUDGET_EVENTS_FILE: &str = "budget_events.jsonl";
+
+/// The directory cost ledgers are kept in, `.swissarmyhammer/cost` in the
+/// current directory
+pub fn default_cost_dir() -> Result<PathBuf> {
+    Ok(std::env::current_dir()?
+        .join(".swissarmyhammer")
+        .join("cost"))
+}
+
+/// Spending limits for prompts
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CostBudget {
+    /// US dollars that may be spent per day
+    pub daily_usd: Option<f64>,
+    /// US dollars that may be spent per issue
+    pub issue_usd: Option<f64>,
+    /// Percent of a budget at which a warning is raised
+    pub warning_percent: u32,
+    /// Refuse new prompts once a budget is spent
+    pub hard_limit: bool,
+}
+
+impl CostBudget {
+    /// The budget set in the configuration
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            daily_usd: config.cost_daily_budget_usd,
+            issue_usd: config.cost_issue_budget_usd,
+            warning_percent: config.cost_budget_warning_percent,
+            hard_limit: config.cost_budget_hard_limit,
+        }
+    }
+
+    /// Whether any budget is set
+    pub fn is_enabled(&self) -> bool {
+        self.daily_usd.is_some() || self.issue_usd.is_some()
+    }
+}
+
+/// Token prices and typical output sizes used to forecast prompt costs
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct CostRates {
+    /// US dollars per million input tokens
+    pub input_usd_per_mtok: f64,
+    /// US dollars per million output tokens
+    pub output_usd_per_mtok: f64,
+    /// Output tokens per input token for a terse response
+    pub output_ratio_low: f64,
+    /// Output tokens per input token for a long response
+    pub output_ratio_high: f64,
+}
+
+impl CostRates {
+    /// The rates set in the configuration
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            input_usd_per_mtok: config.cost_input_usd_per_mtok,
+            output_usd_per_mtok: config.cost_output_usd_per_mtok,
+            output_ratio_low: config.cost_output_ratio_low,
+            output_ratio_high: config.cost_output_ratio_high,
+        }
+    }
+
+    /// US dollars charged for a number of input and output tokens
+    pub fn cost_usd(&self, input_tokens: u64, output_tokens: u64) -> f64 {
+        (input_tokens as f64 * self.input_usd_per_mtok
+            + output_tokens as f64 * self.output_usd_per_mtok)
+            / 1_000_000.0
+    }
+}
+
+/// Cost of a single prompt
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CostEntry {
+    /// When the prompt finished
+    pub timestamp: DateTime<Utc>,
+    /// Issue being worked when the prompt ran
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issue: Option<String>,
+    /// Workflow run the prompt ran in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run_id: Option<WorkflowRunId>,
+    /// Workflow the run executed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workflow: Option<WorkflowName>,
+    /// State the prompt ran in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<StateId>,
+    /// Name of the prompt
+    pub prompt: String,
+    /// Cost reported for the prompt in US dollars
+    pub cost_usd: f64,
+}
+
+/// What a cost breakdown adds spending up by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CostGrouping {
+    /// Each state of each workflow
+    State,
+    /// Each prompt, whichever workflow ran it
+    Prompt,
+}
+
+/// Spending on one workflow state or prompt, across runs
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CostBreakdown {
+    /// Workflow the state belongs to, when grouped by state
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workflow: Option<WorkflowName>,
+    /// State ID or prompt name
+    pub name: String,
+    /// Number of prompts that reported a cost
+    pub calls: usize,
+    /// Number of workflow runs the prompts ran in
+    pub runs: usize,
+    /// US dollars spent in total
+    pub total_usd: f64,
+    /// US dollars spent by the most expensive prompt
+    pub max_usd: f64,
+}
+
+impl CostBreakdown {
+    /// US dollars spent per prompt on average
+    pub fn average_usd(&self) -> f64 {
+        if self.calls == 0 {
+            0.0
+        } else {
+            self.total_usd / self.calls as f64
+        }
+    }
+}
+
+/// Add up spending per state or per prompt, most expensive first
+///
+/// Grouped by state, entries recorded outside a workflow state are left out.
+pub fn cost_breakdown(entries: &[CostEntry], grouping: CostGrouping) -> Vec<CostBreakdown> {
+    let mut groups: BTreeMap<(Option<&str>, &str), (CostBreakdown, HashSet<WorkflowRunId>)> =
+        BTreeMap::new();
+    for entry in entries {
+        let key = match grouping {
+            CostGrouping::State => match &entry.state {
+                Some(state) => (
+                    entry.workflow.as_ref().map(WorkflowName::as_str),
+                    state.as_str(),
+                ),
+                None => continue,
+            },
+            CostGrouping::Prompt => (None, entry.prompt.as_str()),
+        };
+        let (breakdown, runs) = groups.entry(key).or_insert_with(|| {
+            (
+                CostBreakdown {
+                    workflow: key.0.map(WorkflowName::from),
+                    name: key.1.to_string(),
+                    calls: 0,
+                    runs: 0,
+                    total_usd: 0.0,
+                    max_usd: 0.0,
+                },
+                HashSet::new(),
+            )
+        });
+        breakdown.calls += 1;
+        breakdown.total_usd += entry.cost_usd;
+        breakdown.max_usd = breakdown.max_usd.max(entry.cost_usd);
+        runs.extend(entry.run_id);
+    }
+
+    let mut breakdowns: Vec<CostBreakdown> = groups
+        .into_values()
+        .map(|(breakdown, runs)| CostBreakdown {
+            runs: runs.len(),
+            ..breakdown
+        })
+        .collect();
+    breakdowns.sort_by(|a, b| b.total_usd.total_cmp(&a.total_usd));
+    breakdowns
+}
+
+/// What a budget limits spending on
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "scope", rename_all = "lowercase")]
+pub enum BudgetScope {
+    /// All prompts on one day, in local time
+    Daily {
+        /// The day
+        date: NaiveDate,
+    },
+    /// Prompts run while an issue is worked
+    Issue {
+        /// Issue name
+        issue: String,
+    },
+}
+
+impl BudgetScope {
+    /// Whether an entry counts against this budget
+    fn includes(&self, entry: &CostEntry) -> bool {
+        match self {
+            BudgetScope::Daily { date } => local_date(entry.timestamp) == *date,
+            BudgetScope::Issue { issue } => entry.issue.as_deref() == Some(issue.as_str()),
+        }
+    }
+}
+
+impl fmt::Display for BudgetScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BudgetScope::Daily { date } => write!(f, "daily budget for {date}"),
+            BudgetScope::Issue { issue } => write!(f, "budget for issue '{issue}'"),
+        }
+    }
+}
+
+/// How serious a budget event is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BudgetLevel {
+    /// Spending crossed the warning threshold
+    Warning,
+    /// Spending crossed the budget
+    Exceeded,
+    /// A prompt was refused because the budget is spent
+    Refused,
+}
+
+impl fmt::Display for BudgetLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            BudgetLevel::Warning => "warning",
+            BudgetLevel::Exceeded => "exceeded",
+            BudgetLevel::Refused => "refused",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// A budget crossing its warning threshold or limit
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BudgetEvent {
+    /// When the event happened
+    pub timestamp: DateTime<Utc>,
+    /// The budget concerned
+    #[serde(flatten)]
+    pub scope: BudgetScope,
+    /// How serious the event is
+    pub level: BudgetLevel,
+    /// US dollars spent against the budget
+    pub spent_usd: f64,
+    /// The budget in US dollars
+    pub budget_usd: f64,
+}
+
+impl fmt::Display for BudgetEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.level {
+            BudgetLevel::Warning => write!(
+                f,
+                "Spent ${:.4} of the ${:.2} {} ({:.0}%)",
+                self.spent_usd,
+                self.budget_usd,
+                self.scope,
+                self.spent_usd / self.budget_usd * 100.0
+            ),
+            BudgetLevel::Exceeded => write!(
+                f,
+                "Spent ${:.4}, over the ${:.2} {}",
+                self.spent_usd, self.budget_usd, self.scope
+            ),
+            BudgetLevel::Refused => write!(
+                f,
+                "Refused to start a prompt: ${:.4} already spent of the ${:.2} {}",
+                self.spent_usd, self.budget_usd, self.scope
+            ),
+        }
+    }
+}
+
+/// Ledger of prompt costs checked against a [`CostBudget`]
+#[derive(Debug, Clone)]
+pub struct CostTracker {
+    dir: PathBuf,
+    budget: CostBudget,
+}
+
+impl CostTracker {
+    /// Track costs in a directory against a budget
+    pub fn new(dir: PathBuf, budget: CostBudget) -> Self {
+        Self { dir, budget }
+    }
+
+    /// Track costs in the default directory against the configured budget
+    pub fn new_default() -> Result<Self> {
+        Ok(Self::new(
+            default_cost_dir()?,
+            CostBudget::from_config(Config::global()),
+        ))
+    }
+
+    /// The budget costs are checked against
+    pub fn budget(&self) -> &CostBudget {
+        &self.budget
+    }
+
+    /// Every recorded prompt cost, oldest first
+    pub fn entries(&self) -> Result<Vec<CostEntry>> {
+        read_lines(&self.dir.join(COST_LEDGER_FILE))
+    }
+
+    /// Spending per state or per prompt across every recorded run, most
+    /// expensive first
+    pub fn breakdown(&self, grouping: CostGrouping) -> Result<Vec<CostBreakdown>> {
+        Ok(cost_breakdown(&self.entries()?, grouping))
+    }
+
+    /// Every budget event raised, oldest first
+    pub fn events(&self) -> Result<Vec<BudgetEvent>> {
+        read_lines(&self.dir.join(BUDGET_EVENTS_FILE))
+    }
+
+    /// Check whether a new prompt may start
+    ///
+    /// Returns the refusal when the budget has a hard limit and one of the
+    /// budgets covering the prompt is already spent. The refusal is raised
+    /// like any other budget event.
+    pub fn check(&self, issue: Option<&str>, now: DateTime<Utc>) -> Result<Option<BudgetEvent>> {
+        if !self.budget.hard_limit {
+            return Ok(None);
+        }
+
+        let entries = self.entries()?;
+        for (scope, budget_usd) in self.scopes(issue, now) {
+            let spent_usd = spent(&entries, &scope);
+            if spent_usd >= budget_usd {
+                let event = BudgetEvent {
+                    timestamp: now,
+                    scope,
+                    level: BudgetLevel::Refused,
+                    spent_usd,
+                    budget_usd,
+                };
+                self.raise(&event)?;
+                return Ok(Some(event));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Record the cost of a prompt
+    ///
+    /// Returns the budget events the cost raised: a warning when spending
+    /// crosses the warning threshold, or an exceeded event when it crosses
+    /// the budget.
+    pub fn record(&self, entry: CostEntry) -> Result<Vec<BudgetEvent>> {
+        let scopes = self.scopes(entry.issue.as_deref(), entry.timestamp);
+        // Without a budget there is nothing to check the ledger against
+        let entries = if scopes.is_empty() {
+            Vec::new()
+        } else {
+            self.entries()?
+        };
+        let mut events = Vec::new();
+        for (scope, budget_usd) in scopes {
+            let before = spent(&entries, &scope);
+            let after = before + entry.cost_usd;
+            let warn_at = budget_usd * f64::from(self.budget.warning_percent) / 100.0;
+            let level = if before < budget_usd && after >= budget_usd {
+                BudgetLevel::Exceeded
+            } else if before < warn_at && after >= warn_at {
+                BudgetLevel::Warning
+            } else {
+                continue;
+            };
+            events.push(BudgetEvent {
+                timestamp: entry.timestamp,
+                scope,
+                level,
+                spent_usd: after,
+                budget_usd,
+            });
+        }
+
+        append_line(&self.dir.join(COST_LEDGER_FILE), &entry)?;
+        for event in &events {
+            self.raise(event)?;
+        }
+        Ok(events)
+    }
+
+    /// The configured budgets covering a prompt
+    fn scopes(&self, issue: Option<&str>, now: DateTime<Utc>) -> Vec<(BudgetScope, f64)> {
+        let daily = self.budget.daily_usd.map(|budget| {
+            (
+                BudgetScope::Daily {
+                    date: local_date(now),
+                },
+                budget,
+            )
+        });
+        let issue = self.budget.issue_usd.zip(issue).map(|(budget, issue)| {
+            (
+                BudgetScope::Issue {
+                    issue: issue.to_string(),
+                },
+                budget,
+            )
+        });
+        daily.into_iter().chain(issue).collect()
+    }
+
+    /// Log a budget event, append it to the events file, and publish it when
+    /// the budget is exceeded
+    fn raise(&self, event: &BudgetEvent) -> Result<()> {
+        match event.level {
+            BudgetLevel::Warning | BudgetLevel::Exceeded => tracing::warn!(
+                level = %event.level,
+                scope = %event.scope,
+                spent_usd = event.spent_usd,
+                budget_usd = event.budget_usd,
+                "{}",
+                event
+            ),
+            BudgetLevel::Refused => tracing::error!(
+                level = %event.level,
+                scope = %event.scope,
+                spent_usd = event.spent_usd,
+                budget_usd = event.budget_usd,
+                "{}",
+                event
+            ),
+        }
+        if event.level != BudgetLevel::Warning {
+            event_bus().publish(Event::BudgetExceeded(event.clone()));
+        }
+        append_line(&self.dir.join(BUDGET_EVENTS_FILE), event)
+    }
+}
+
+/// The local day a time falls on
+fn local_date(time: DateTime<Utc>) -> NaiveDate {
+    time.with_timezone(&Local).date_naive()
+}
+
+/// US dollars spent against a budget
+fn spent(entries: &[CostEntry], scope: &BudgetScope) -> f64 {
+    entries
+        .iter()
+        .filter(|entry| scope.includes(entry))
+        .map(|entry| entry.cost_usd)
+        .sum()
+}
+
+/// Read a JSON lines file, skipping lines that can't be read
+pub(crate) fn read_lines<T: DeserializeOwned>(path: &Path) -> Result<Vec<T>> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    Ok(content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| match serde_json::from_str(line) {
+            Ok(value) => Some(value),
+            Err(e) => {
+                debug!("Skipping unreadable line in {}: {}", path.display(), e);
+                None
+            }
+        })
+        .collect())
+}
+
+/// Append a value to a JSON lines file
+pub(crate) fn append_line<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(value)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+    use tempfile::TempDir;
+
+    fn entry(now: DateTime<Utc>, issue: Option<&str>, cost_usd: f64) -> CostEntry {
+        CostEntry {
+            timestamp: now,
+            issue: issue.map(str::to_string),
+            run_id: None,
+            workflow: None,
+            state: None,
+            prompt: "review".to_string(),
+            cost_usd,
+        }
+    }
+
+    fn budget(daily_usd: Option<f64>, issue_usd: Option<f64>, hard_limit: bool) -> CostBudget {
+        CostBudget {
+            daily_usd,
+            issue_usd,
+            warning_percent: 80,
+            hard_limit,
+        }
+    }
+
+    #[test]
+    fn test_record_raises_warning_then_exceeded() {
+        let temp = TempDir::new().unwrap();
+        let tracker = CostTracker::new(temp.path().to_path_buf(), budget(Some(1.0), None, false));
+        let now = Utc.with_ymd_and_hms(2026, 3, 10, 12, 0, 0).unwrap();
+
+        assert!(tracker.record(entry(now, None, 0.5)).unwrap().is_empty());
+        let events = tracker.record(entry(now, None, 0.35)).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].level, BudgetLevel::Warning);
+        assert!((events[0].spent_usd - 0.85).abs() < 1e-9);
+
+        assert!(tracker.record(entry(now, None, 0.05)).unwrap().is_empty());
+        let events = tracker.record(entry(now, None, 0.2)).unwrap();
+        assert_eq!(events[0].level, BudgetLevel::Exceeded);
+
+        // Spending on another day counts against a fresh budget
+        let tomorrow = now + Duration::days(1);
+        assert!(tracker
+            .record(entry(tomorrow, None, 0.1))
+            .unwrap()
+            .is_empty());
+
+        assert_eq!(tracker.entries().unwrap().len(), 5);
+        let levels: Vec<BudgetLevel> = tracker
+            .events()
+            .unwrap()
+            .iter()
+            .map(|event| event.level)
+            .collect();
+        assert_eq!(levels, vec![BudgetLevel::Warning, BudgetLevel::Exceeded]);
+    }
+
+    #[test]
+    fn test_hard_limit_refuses_spent_issue_budget() {
+        let temp = TempDir::new().unwrap();
+        let now = Utc.with_ymd_and_hms(2026, 3, 10, 12, 0, 0).unwrap();
+        let soft = CostTracker::new(temp.path().to_path_buf(), budget(None, Some(2.0), false));
+        soft.record(entry(now, Some("fix_login"), 2.5)).unwrap();
+        assert!(soft.check(Some("fix_login"), now).unwrap().is_none());
+
+        let hard = CostTracker::new(temp.path().to_path_buf(), budget(None, Some(2.0), true));
+        assert!(hard.check(Some("other_issue"), now).unwrap().is_none());
+        assert!(hard.check(None, now).unwrap().is_none());
+
+        let refusal = hard.check(Some("fix_login"), now).unwrap().unwrap();
+        assert_eq!(refusal.level, BudgetLevel::Refused);
+        assert_eq!(
+            refusal.scope,
+            BudgetScope::Issue {
+                issue: "fix_login".to_string()
+            }
+        );
+        assert_eq!(
+            refusal.to_string(),
+            "Refused to start a prompt: $2.5000 already spent of the $2.00 budget for issue 'fix_login'"
+        );
+        assert_eq!(hard.events().unwrap().last(), Some(&refusal));
+    }
+
+    #[test]
+    fn test_breakdown_per_state_and_prompt() {
+        let temp = TempDir::new().unwrap();
+        let tracker = CostTracker::new(temp.path().to_path_buf(), CostBudget::default());
+        let now = Utc.with_ymd_and_hms(2026, 3, 10, 12, 0, 0).unwrap();
+        let (first, second) = (WorkflowRunId::new(), WorkflowRunId::new());
+        let step = |run_id: WorkflowRunId, state: &str, prompt: &str, cost_usd: f64| CostEntry {
+            run_id: Some(run_id),
+            workflow: Some(WorkflowName::new("implement")),
+            state: Some(StateId::new(state)),
+            prompt: prompt.to_string(),
+            ..entry(now, None, cost_usd)
+        };
+        for entry in [
+            step(first, "plan", "plan", 0.5),
+            step(first, "code", "code", 1.0),
+            step(first, "code", "code", 2.0),
+            step(second, "code", "code", 1.5),
+            step(second, "review", "review", 0.25),
+            entry(now, None, 0.75),
+        ] {
+            tracker.record(entry).unwrap();
+        }
+
+        let by_state = tracker.breakdown(CostGrouping::State).unwrap();
+        let names: Vec<&str> = by_state.iter().map(|b| b.name.as_str()).collect();
+        assert_eq!(names, vec!["code", "plan", "review"]);
+        let code = &by_state[0];
+        assert_eq!(code.workflow, Some(WorkflowName::new("implement")));
+        assert_eq!((code.calls, code.runs), (3, 2));
+        assert!((code.total_usd - 4.5).abs() < 1e-9);
+        assert!((code.max_usd - 2.0).abs() < 1e-9);
+        assert!((code.average_usd() - 1.5).abs() < 1e-9);
+
+        // Prompts run outside a workflow state still count per prompt
+        let by_prompt = tracker.breakdown(CostGrouping::Prompt).unwrap();
+        let review = by_prompt.iter().find(|b| b.name == "review").unwrap();
+        assert_eq!(review.workflow, None);
+        assert_eq!((review.calls, review.runs), (2, 1));
+        assert!((review.total_usd - 1.0).abs() < 1e-9);
+    }
+}
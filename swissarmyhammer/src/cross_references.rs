@@ -0,0 +1,192 @@
+//! Cross references between issues and memos
+//!
+//! Debugging notes often live in memos while the work they support is tracked
+//! as an issue. An issue names its memos in front matter, by memo ID or title:
+//!
+//! ```markdown
+//! ---
+//! related_memos: [login-investigation]
+//! ---
+//! # Fix the login bug
+//! ```
+//!
+//! and a memo names the issue it belongs to in its own front matter:
+//!
+//! ```markdown
+//! ---
+//! related_issue: 000123_fix_login
+//! ---
+//! Session cookie is dropped after the redirect.
+//! ```
+//!
+//! References are resolved in both directions, so declaring a reference on
+//! either side links the issue and the memo.
+
+use crate::issues::priority::front_matter;
+use crate::issues::{Issue, IssueStorage};
+use crate::memoranda::{Memo, MemoStorage};
+use crate::Result;
+
+/// Memos related to an issue
+#[derive(Debug, Clone, Default)]
+pub struct RelatedMemos {
+    /// Memos the issue names or that name the issue, by title
+    pub memos: Vec<Memo>,
+    /// Memos the issue names that don't exist
+    pub missing: Vec<String>,
+}
+
+/// Issues related to a memo
+#[derive(Debug, Clone, Default)]
+pub struct RelatedIssues {
+    /// Issues the memo names or that name the memo, by name
+    pub issues: Vec<Issue>,
+    /// Issue the memo names when it doesn't exist
+    pub missing: Option<String>,
+}
+
+/// The `related_issue` field of a memo's front matter
+pub fn memo_related_issue(content: &str) -> Option<String> {
+    let yaml: serde_yaml::Value = serde_yaml::from_str(front_matter(content)?).ok()?;
+    yaml.get("related_issue")?
+        .as_str()
+        .map(str::trim)
+        .filter(|issue| !issue.is_empty())
+        .map(str::to_string)
+}
+
+/// Whether a memo reference from an issue names this memo
+pub fn memo_matches(memo: &Memo, reference: &str) -> bool {
+    let reference = reference.trim();
+    memo.id.as_str() == reference || memo.title == reference
+}
+
+/// Find the memos related to an issue
+pub fn find_related_memos(issue: &Issue, memos: &[Memo]) -> RelatedMemos {
+    let mut related: Vec<Memo> = memos
+        .iter()
+        .filter(|memo| {
+            issue
+                .related_memos
+                .iter()
+                .any(|reference| memo_matches(memo, reference))
+                || memo_related_issue(&memo.content).as_deref() == Some(issue.name.as_str())
+        })
+        .cloned()
+        .collect();
+    related.sort_by(|a, b| a.title.cmp(&b.title));
+
+    let missing = issue
+        .related_memos
+        .iter()
+        .filter(|reference| !memos.iter().any(|memo| memo_matches(memo, reference)))
+        .cloned()
+        .collect();
+
+    RelatedMemos {
+        memos: related,
+        missing,
+    }
+}
+
+/// Find the issues related to a memo
+pub fn find_related_issues(memo: &Memo, issues: &[Issue]) -> RelatedIssues {
+    let named = memo_related_issue(&memo.content);
+    let mut related: Vec<Issue> = issues
+        .iter()
+        .filter(|issue| {
+            named.as_deref() == Some(issue.name.as_str())
+                || issue
+                    .related_memos
+                    .iter()
+                    .any(|reference| memo_matches(memo, reference))
+        })
+        .cloned()
+        .collect();
+    related.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let missing = named.filter(|name| !issues.iter().any(|issue| &issue.name == name));
+
+    RelatedIssues {
+        issues: related,
+        missing,
+    }
+}
+
+/// Load the memos related to an issue from memo storage
+pub async fn related_memos(issue: &Issue, storage: &dyn MemoStorage) -> Result<RelatedMemos> {
+    let memos = storage.list_memos().await?;
+    Ok(find_related_memos(issue, &memos))
+}
+
+/// Load the issues related to a memo from issue storage
+pub async fn related_issues(memo: &Memo, storage: &dyn IssueStorage) -> Result<RelatedIssues> {
+    let issues = storage.list_issues().await?;
+    Ok(find_related_issues(memo, &issues))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::issues::IssueMetadata;
+    use chrono::Utc;
+    use std::path::PathBuf;
+
+    fn issue(name: &str, content: &str) -> Issue {
+        Issue {
+            name: name.to_string(),
+            content: content.to_string(),
+            completed: false,
+            file_path: PathBuf::from(format!("issues/{name}.md")),
+            created_at: Utc::now(),
+            source: None,
+            priority: None,
+            estimate: None,
+            related_memos: IssueMetadata::from_content(content).related_memos,
+        }
+    }
+
+    #[test]
+    fn test_memo_related_issue() {
+        assert_eq!(
+            memo_related_issue("---\nrelated_issue: 000123_login\n---\nNotes"),
+            Some("000123_login".to_string())
+        );
+        assert_eq!(memo_related_issue("Notes without front matter"), None);
+    }
+
+    #[test]
+    fn test_references_resolve_in_both_directions() {
+        let trace = Memo::new("trace".to_string(), "Stack trace".to_string());
+        let notes = Memo::new(
+            "notes".to_string(),
+            "---\nrelated_issue: 000002_crash\n---\nCrash notes".to_string(),
+        );
+        let memos = vec![trace.clone(), notes.clone()];
+        let login = issue("000001_login", "---\nrelated_memos: [trace, gone]\n---\n");
+        let crash = issue("000002_crash", "# Crash");
+        let issues = vec![login.clone(), crash.clone()];
+
+        let related = find_related_memos(&login, &memos);
+        let titles: Vec<&str> = related.memos.iter().map(|m| m.title.as_str()).collect();
+        assert_eq!(titles, vec!["trace"]);
+        assert_eq!(related.missing, vec!["gone"]);
+
+        let related = find_related_memos(&crash, &memos);
+        assert_eq!(related.memos.len(), 1);
+        assert_eq!(related.memos[0].title, "notes");
+
+        let related = find_related_issues(&trace, &issues);
+        let names: Vec<&str> = related.issues.iter().map(|i| i.name.as_str()).collect();
+        assert_eq!(names, vec!["000001_login"]);
+        assert_eq!(related.missing, None);
+
+        let orphan = Memo::new(
+            "orphan".to_string(),
+            "---\nrelated_issue: 000009_missing\n---\n".to_string(),
+        );
+        let related = find_related_issues(&orphan, &issues);
+        assert!(related.issues.is_empty());
+        assert_eq!(related.missing.as_deref(), Some("000009_missing"));
+    }
+}
@@ -0,0 +1,384 @@
+//! Dependency graph between prompts, partials and workflows
+//!
+//! Prompts depend on the partials they include with `{% render %}` or
+//! `{% include %}`, and workflows depend on the prompts they execute and the
+//! workflows they run, including from their hooks. The graph answers two
+//! questions: what does an item use, and what is affected when it changes.
+//!
+//! Partials are prompts, so partial names are compared the way the template
+//! engine resolves them: `coding_standards`, `coding_standards.md` and
+//! `coding_standards.md.liquid` all name the same node. References whose name
+//! is filled in at run time, such as `Execute prompt "${next}"`, are not
+//! tracked.
+
+use crate::workflow::{
+    parse_action_from_description, MemoryWorkflowStorage, PromptAction, SubWorkflowAction,
+    Workflow, WorkflowHookEvent, WorkflowResolver, WorkflowStorageBackend,
+};
+use crate::{PromptLibrary, PromptResolver, Result};
+use regex::Regex;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::fmt;
+use std::path::{Component, Path, PathBuf};
+
+/// Template extensions ignored when comparing prompt names
+const PROMPT_EXTENSIONS: [&str; 4] = [".md.liquid", ".markdown", ".liquid", ".md"];
+
+/// A prompt or workflow in the dependency graph
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(tag = "kind", content = "name", rename_all = "lowercase")]
+pub enum DependencyNode {
+    /// A prompt or partial template
+    Prompt(String),
+    /// A workflow
+    Workflow(String),
+}
+
+impl DependencyNode {
+    /// Node for a prompt, ignoring any template extension on the name
+    pub fn prompt(name: &str) -> Self {
+        let name = PROMPT_EXTENSIONS
+            .iter()
+            .find_map(|ext| name.strip_suffix(ext))
+            .unwrap_or(name);
+        DependencyNode::Prompt(name.to_string())
+    }
+
+    /// Node for a workflow
+    pub fn workflow(name: &str) -> Self {
+        DependencyNode::Workflow(name.to_string())
+    }
+
+    /// Name of the prompt or workflow
+    pub fn name(&self) -> &str {
+        match self {
+            DependencyNode::Prompt(name) | DependencyNode::Workflow(name) => name,
+        }
+    }
+
+    /// `prompt` or `workflow`
+    pub fn kind(&self) -> &'static str {
+        match self {
+            DependencyNode::Prompt(_) => "prompt",
+            DependencyNode::Workflow(_) => "workflow",
+        }
+    }
+}
+
+impl fmt::Display for DependencyNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.kind(), self.name())
+    }
+}
+
+/// Which prompts and workflows use which
+#[derive(Debug, Clone, Default)]
+pub struct DependencyGraph {
+    /// Items each known item uses
+    dependencies: BTreeMap<DependencyNode, BTreeSet<DependencyNode>>,
+    /// File each item was loaded from
+    paths: BTreeMap<DependencyNode, PathBuf>,
+}
+
+impl DependencyGraph {
+    /// Create an empty graph
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build the graph of every prompt and workflow
+    ///
+    /// Prompts and workflows are resolved from builtin, user and local
+    /// directories exactly as they are when used, so overridden definitions
+    /// are the ones analyzed.
+    pub fn load() -> Result<Self> {
+        let mut graph = Self::new();
+
+        let mut library = PromptLibrary::new();
+        let mut prompt_resolver = PromptResolver::new();
+        prompt_resolver.load_all_prompts(&mut library)?;
+        for prompt in library.list()? {
+            let path = prompt_resolver.prompt_paths.get(&prompt.name).cloned();
+            graph.add_prompt(&prompt.name, &prompt.template, path);
+        }
+
+        let mut storage = MemoryWorkflowStorage::new();
+        let mut workflow_resolver = WorkflowResolver::new();
+        workflow_resolver.load_all_workflows(&mut storage)?;
+        for workflow in storage.list_workflows()? {
+            let path = workflow_resolver
+                .workflow_paths
+                .get(&workflow.name)
+                .cloned();
+            graph.add_workflow(&workflow, path);
+        }
+
+        Ok(graph)
+    }
+
+    /// Add a prompt and the partials its template includes
+    pub fn add_prompt(&mut self, name: &str, template: &str, path: Option<PathBuf>) {
+        let partial_re = Regex::new(r#"\{%-?\s*(?:render|include)\s+["']([^"']+)["']"#)
+            .expect("partial pattern is valid");
+        let partials = partial_re
+            .captures_iter(template)
+            .map(|captures| DependencyNode::prompt(&captures[1]))
+            .collect();
+        self.add_node(DependencyNode::prompt(name), partials, path);
+    }
+
+    /// Add a workflow and the prompts and workflows its states and hooks run
+    pub fn add_workflow(&mut self, workflow: &Workflow, path: Option<PathBuf>) {
+        let hooks = WorkflowHookEvent::ALL
+            .into_iter()
+            .filter_map(|event| workflow.hooks.get(event));
+        let actions = workflow
+            .states
+            .values()
+            .map(|state| state.description.as_str())
+            .chain(hooks);
+
+        let mut used = BTreeSet::new();
+        for description in actions {
+            let Ok(Some(action)) = parse_action_from_description(description) else {
+                continue;
+            };
+            let action = action.as_any();
+            let node = if let Some(prompt) = action.downcast_ref::<PromptAction>() {
+                DependencyNode::prompt(&prompt.prompt_name)
+            } else if let Some(sub_workflow) = action.downcast_ref::<SubWorkflowAction>() {
+                DependencyNode::workflow(&sub_workflow.workflow_name)
+            } else {
+                continue;
+            };
+            if !is_dynamic_name(node.name()) {
+                used.insert(node);
+            }
+        }
+
+        self.add_node(DependencyNode::workflow(workflow.name.as_str()), used, path);
+    }
+
+    fn add_node(
+        &mut self,
+        node: DependencyNode,
+        dependencies: BTreeSet<DependencyNode>,
+        path: Option<PathBuf>,
+    ) {
+        if let Some(path) = path {
+            self.paths.insert(node.clone(), path);
+        }
+        self.dependencies.insert(node, dependencies);
+    }
+
+    /// Whether a prompt or workflow with this node's name was added
+    pub fn contains(&self, node: &DependencyNode) -> bool {
+        self.dependencies.contains_key(node)
+    }
+
+    /// File the item was loaded from
+    pub fn path(&self, node: &DependencyNode) -> Option<&Path> {
+        self.paths.get(node).map(PathBuf::as_path)
+    }
+
+    /// Items the node uses directly
+    pub fn dependencies(&self, node: &DependencyNode) -> Vec<&DependencyNode> {
+        self.dependencies
+            .get(node)
+            .map(|dependencies| dependencies.iter().collect())
+            .unwrap_or_default()
+    }
+
+    /// Items the node uses that do not exist
+    pub fn missing_dependencies(&self, node: &DependencyNode) -> Vec<&DependencyNode> {
+        self.dependencies(node)
+            .into_iter()
+            .filter(|dependency| !self.contains(dependency))
+            .collect()
+    }
+
+    /// Items that use the node directly
+    pub fn dependents(&self, node: &DependencyNode) -> Vec<&DependencyNode> {
+        self.dependencies
+            .iter()
+            .filter(|(_, dependencies)| dependencies.contains(node))
+            .map(|(dependent, _)| dependent)
+            .collect()
+    }
+
+    /// Every item that uses the node, directly or through other items
+    ///
+    /// These are the items whose behavior can change when the node changes.
+    pub fn affected_by(&self, node: &DependencyNode) -> Vec<&DependencyNode> {
+        let mut affected = BTreeSet::new();
+        let mut queue = VecDeque::from([node]);
+        while let Some(current) = queue.pop_front() {
+            for dependent in self.dependents(current) {
+                if dependent != node && affected.insert(dependent) {
+                    queue.push_back(dependent);
+                }
+            }
+        }
+        affected.into_iter().collect()
+    }
+
+    /// The item defined by a file
+    ///
+    /// Files the graph was loaded from are matched by path. Other files, such
+    /// as one that was just deleted, are matched by their location under a
+    /// `prompts` or `workflows` directory.
+    pub fn node_for_file(&self, file: &Path) -> Option<DependencyNode> {
+        let canonical = file.canonicalize().ok();
+        let loaded = self.paths.iter().find(|(_, path)| {
+            path.as_path() == file || (canonical.is_some() && path.canonicalize().ok() == canonical)
+        });
+        if let Some((node, _)) = loaded {
+            return Some(node.clone());
+        }
+
+        let components: Vec<&str> = file
+            .components()
+            .filter_map(|component| match component {
+                Component::Normal(part) => part.to_str(),
+                _ => None,
+            })
+            .collect();
+        let (index, kind) = components
+            .iter()
+            .enumerate()
+            .rev()
+            .find(|(_, part)| **part == "prompts" || **part == "workflows")?;
+        let name = components[index + 1..].join("/");
+        if name.is_empty() {
+            return None;
+        }
+
+        Some(if *kind == "prompts" {
+            DependencyNode::prompt(&name)
+        } else {
+            DependencyNode::workflow(name.strip_suffix(".md").unwrap_or(&name))
+        })
+    }
+}
+
+/// Whether a referenced name is only known at run time
+fn is_dynamic_name(name: &str) -> bool {
+    name.contains("${") || name.contains("{{")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workflow::MermaidParser;
+
+    fn graph() -> DependencyGraph {
+        let mut graph = DependencyGraph::new();
+        graph.add_prompt(
+            "coding_standards.md",
+            "Write good code",
+            Some(PathBuf::from(
+                "/project/.swissarmyhammer/prompts/coding_standards.md.liquid",
+            )),
+        );
+        graph.add_prompt(
+            "review/code",
+            "{% render \"coding_standards\" %}\n{%- include 'missing' %}",
+            None,
+        );
+        graph.add_prompt("standalone", "Hello {{ name }}", None);
+
+        let review = MermaidParser::parse(
+            r#"```mermaid
+stateDiagram-v2
+    [*] --> review
+    review --> dynamic
+    dynamic --> [*]
+```
+
+## Actions
+
+- review: Execute prompt "review/code"
+- dynamic: Execute prompt "${next_prompt}"
+"#,
+            "review",
+        )
+        .unwrap();
+        graph.add_workflow(&review, None);
+
+        let release = MermaidParser::parse(
+            r#"```mermaid
+stateDiagram-v2
+    [*] --> run
+    run --> [*]
+```
+
+## Actions
+
+- run: Run workflow "review"
+"#,
+            "release",
+        )
+        .unwrap();
+        graph.add_workflow(&release, None);
+        graph
+    }
+
+    #[test]
+    fn test_dependencies_and_missing_partials() {
+        let graph = graph();
+        let review = DependencyNode::prompt("review/code");
+
+        assert_eq!(
+            graph.dependencies(&review),
+            vec![
+                &DependencyNode::prompt("coding_standards"),
+                &DependencyNode::prompt("missing"),
+            ]
+        );
+        assert_eq!(
+            graph.missing_dependencies(&review),
+            vec![&DependencyNode::prompt("missing")]
+        );
+        assert_eq!(
+            graph.dependencies(&DependencyNode::workflow("review")),
+            vec![&review]
+        );
+    }
+
+    #[test]
+    fn test_changing_a_partial_affects_prompts_and_workflows() {
+        let graph = graph();
+
+        let affected = graph.affected_by(&DependencyNode::prompt("coding_standards.md.liquid"));
+        let names: Vec<String> = affected.iter().map(|node| node.to_string()).collect();
+        assert_eq!(
+            names,
+            vec!["prompt:review/code", "workflow:release", "workflow:review"]
+        );
+        assert!(graph
+            .affected_by(&DependencyNode::prompt("standalone"))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_node_for_file() {
+        let graph = graph();
+
+        assert_eq!(
+            graph.node_for_file(Path::new(
+                "/project/.swissarmyhammer/prompts/coding_standards.md.liquid"
+            )),
+            Some(DependencyNode::prompt("coding_standards"))
+        );
+        assert_eq!(
+            graph.node_for_file(Path::new(".swissarmyhammer/prompts/review/code.md")),
+            Some(DependencyNode::prompt("review/code"))
+        );
+        assert_eq!(
+            graph.node_for_file(Path::new(".swissarmyhammer/workflows/release.md")),
+            Some(DependencyNode::workflow("release"))
+        );
+        assert_eq!(graph.node_for_file(Path::new("src/main.rs")), None);
+    }
+}
@@ -2,10 +2,386 @@
 //!
 //! This module provides reusable directory traversal functionality to avoid
 //! code duplication across the codebase.
+//!
+//! Directories are scanned into a [`DirectoryIndex`] that respects
+//! `.sahignore` files and caps how deep and how many files it lists. Git
+//! ignore rules are not applied, so prompts and workflows kept out of a
+//! repository are still found. Indexes are cached per directory, so loading
+//! prompts and workflows repeatedly does not walk the same tree again. A
+//! cached index is checked for changes once [`invalidate_cached_indexes`]
+//! names a path in it, as the file watcher does for every event, and the
+//! check only lists directories whose modification time changed.
 
 use crate::security::MAX_DIRECTORY_DEPTH;
+use ignore::WalkBuilder;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fs;
 use std::path::{Path, PathBuf};
-use walkdir::WalkDir;
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
+use std::time::SystemTime;
+
+/// Ignore file applied when scanning directories
+pub const SAH_IGNORE_FILE: &str = ".sahignore";
+
+/// Most files a directory index lists
+pub const MAX_INDEXED_FILES: usize = 100_000;
+
+/// Limits on how much of a directory tree is scanned
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScanLimits {
+    /// Deepest level below the root that is listed
+    pub max_depth: usize,
+    /// Files listed before the scan stops
+    pub max_files: usize,
+}
+
+impl Default for ScanLimits {
+    fn default() -> Self {
+        Self {
+            max_depth: MAX_DIRECTORY_DEPTH,
+            max_files: MAX_INDEXED_FILES,
+        }
+    }
+}
+
+/// Files that appeared or disappeared since the previous scan
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IndexChanges {
+    /// Files that were added
+    pub added: Vec<PathBuf>,
+    /// Files that were removed
+    pub removed: Vec<PathBuf>,
+}
+
+impl IndexChanges {
+    /// Whether no files changed
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// Files directly inside one scanned directory
+#[derive(Debug, Clone, Default)]
+struct IndexedDirectory {
+    /// Modification time of the directory when it was listed
+    modified: Option<SystemTime>,
+    files: BTreeSet<PathBuf>,
+}
+
+/// The files below a directory, as of the last scan
+///
+/// Files ignored by a `.sahignore` in the scanned tree are left out; ignore
+/// files above the root and git ignore rules are not read. Hidden files are
+/// listed unless a `.sahignore` says otherwise.
+#[derive(Debug, Clone)]
+pub struct DirectoryIndex {
+    root: PathBuf,
+    limits: ScanLimits,
+    directories: BTreeMap<PathBuf, IndexedDirectory>,
+    file_count: usize,
+    truncated: bool,
+}
+
+impl DirectoryIndex {
+    /// Scan a directory tree
+    pub fn scan(root: &Path, limits: ScanLimits) -> Self {
+        let mut index = Self {
+            root: root.to_path_buf(),
+            limits,
+            directories: BTreeMap::new(),
+            file_count: 0,
+            truncated: false,
+        };
+        if root.is_dir() {
+            index.walk(root, Arc::new(HashSet::new()));
+        }
+        index
+    }
+
+    /// Update the index with files added or removed since the last scan
+    ///
+    /// Only directories whose modification time changed are listed again, and
+    /// new subdirectories are scanned in full. Changes to the ignore files
+    /// themselves are picked up by a new [`scan`](Self::scan).
+    pub fn rescan(&mut self) -> IndexChanges {
+        let mut changes = IndexChanges::default();
+        let mut changed = Vec::new();
+
+        let known: Vec<PathBuf> = self.directories.keys().cloned().collect();
+        for directory in known {
+            if !directory.is_dir() {
+                if let Some(removed) = self.directories.remove(&directory) {
+                    self.file_count -= removed.files.len();
+                    changes.removed.extend(removed.files);
+                }
+            } else if modified_time(&directory) != self.directories[&directory].modified {
+                changed.push(directory);
+            }
+        }
+        if self.directories.is_empty() && self.root.is_dir() {
+            changed.push(self.root.clone());
+        }
+
+        let known: Arc<HashSet<PathBuf>> = Arc::new(self.directories.keys().cloned().collect());
+        for directory in changed {
+            let previous = self
+                .directories
+                .remove(&directory)
+                .map(|listed| listed.files)
+                .unwrap_or_default();
+            self.file_count -= previous.len();
+
+            let added = self.walk(&directory, Arc::clone(&known));
+            if let Some(current) = self.directories.get(&directory) {
+                changes
+                    .removed
+                    .extend(previous.difference(&current.files).cloned());
+            }
+            changes
+                .added
+                .extend(added.into_iter().filter(|path| !previous.contains(path)));
+        }
+
+        changes.added.sort();
+        changes.removed.sort();
+        changes
+    }
+
+    /// The scanned directory
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Every listed file
+    pub fn files(&self) -> impl Iterator<Item = &Path> + '_ {
+        self.directories
+            .values()
+            .flat_map(|directory| directory.files.iter().map(PathBuf::as_path))
+    }
+
+    /// Listed files with one of the given extensions (without dots)
+    pub fn files_with_extensions(&self, extensions: &[&str]) -> Vec<PathBuf> {
+        self.files()
+            .filter(|path| has_extension(path, extensions))
+            .map(Path::to_path_buf)
+            .collect()
+    }
+
+    /// Number of listed files
+    pub fn len(&self) -> usize {
+        self.file_count
+    }
+
+    /// Whether no files were listed
+    pub fn is_empty(&self) -> bool {
+        self.file_count == 0
+    }
+
+    /// Whether the scan stopped at [`ScanLimits::max_files`]
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// List `start` and the directories below it, skipping the `known` ones
+    ///
+    /// The walk begins at the root, so the `.sahignore` files between the
+    /// root and `start` apply, but only descends towards `start`. Returns the
+    /// files listed.
+    fn walk(&mut self, start: &Path, known: Arc<HashSet<PathBuf>>) -> Vec<PathBuf> {
+        let target = start.to_path_buf();
+        let walker = WalkBuilder::new(&self.root)
+            .hidden(false)
+            .ignore(false)
+            .git_ignore(false)
+            .git_global(false)
+            .git_exclude(false)
+            .parents(false)
+            .add_custom_ignore_filename(SAH_IGNORE_FILE)
+            .max_depth(Some(self.limits.max_depth))
+            .filter_entry(move |entry| {
+                let path = entry.path();
+                target.starts_with(path) || (path.starts_with(&target) && !known.contains(path))
+            })
+            .build();
+
+        let mut added = Vec::new();
+        for entry in walker {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(e) => {
+                    tracing::debug!("Skipping directory entry: {}", e);
+                    continue;
+                }
+            };
+            let path = entry.path();
+            if !path.starts_with(start) {
+                continue;
+            }
+            if entry
+                .file_type()
+                .is_some_and(|file_type| file_type.is_dir())
+            {
+                self.directories.insert(
+                    path.to_path_buf(),
+                    IndexedDirectory {
+                        modified: modified_time(path),
+                        files: BTreeSet::new(),
+                    },
+                );
+            } else if path.is_file() {
+                if self.file_count >= self.limits.max_files {
+                    if !self.truncated {
+                        tracing::warn!(
+                            "Stopped scanning '{}' after {} files",
+                            self.root.display(),
+                            self.limits.max_files
+                        );
+                    }
+                    self.truncated = true;
+                    break;
+                }
+                let directory = path.parent().unwrap_or(start).to_path_buf();
+                self.directories
+                    .entry(directory)
+                    .or_default()
+                    .files
+                    .insert(path.to_path_buf());
+                self.file_count += 1;
+                added.push(path.to_path_buf());
+            }
+        }
+        added
+    }
+}
+
+/// A cached index and whether files changed in it since it was scanned
+struct CachedIndex {
+    index: DirectoryIndex,
+    /// Whether the index must be rescanned on the next lookup
+    stale: bool,
+}
+
+fn index_cache() -> &'static Mutex<HashMap<PathBuf, CachedIndex>> {
+    static CACHE: OnceLock<Mutex<HashMap<PathBuf, CachedIndex>>> = OnceLock::new();
+    CACHE.get_or_init(Default::default)
+}
+
+/// Every file below `dir`, from the cached index
+///
+/// The first lookup scans the directory. Later lookups reuse the index,
+/// rescanning it for changes after [`invalidate_cached_indexes`].
+pub fn cached_files(dir: &Path) -> Vec<PathBuf> {
+    with_cached_index(dir, |index| index.files().map(Path::to_path_buf).collect())
+}
+
+/// Files below `dir` with one of the given extensions, from the cached index
+pub fn cached_files_with_extensions(dir: &Path, extensions: &[&str]) -> Vec<PathBuf> {
+    with_cached_index(dir, |index| index.files_with_extensions(extensions))
+}
+
+fn with_cached_index<T>(dir: &Path, f: impl FnOnce(&DirectoryIndex) -> T) -> T {
+    let mut cache = index_cache().lock().unwrap_or_else(PoisonError::into_inner);
+    let cached = cache
+        .entry(dir.to_path_buf())
+        .or_insert_with(|| CachedIndex {
+            index: DirectoryIndex::scan(dir, ScanLimits::default()),
+            stale: false,
+        });
+
+    if cached.stale {
+        let changes = cached.index.rescan();
+        if !changes.is_empty() {
+            tracing::debug!(
+                "Rescanned '{}': {} added, {} removed",
+                dir.display(),
+                changes.added.len(),
+                changes.removed.len()
+            );
+        }
+        cached.stale = false;
+    }
+
+    f(&cached.index)
+}
+
+/// Make the next lookup of any cached index containing one of `paths`, or
+/// below one of them, rescan it
+///
+/// Code writing prompt or workflow files calls this, as does the file
+/// watcher for every change it sees.
+pub fn invalidate_cached_indexes(paths: &[PathBuf]) {
+    let mut cache = index_cache().lock().unwrap_or_else(PoisonError::into_inner);
+    for cached in cache.values_mut() {
+        let root = cached.index.root();
+        if paths
+            .iter()
+            .any(|path| path.starts_with(root) || root.starts_with(path))
+        {
+            cached.stale = true;
+        }
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// Whether a file name ends in one of the extensions, including the parts of
+/// compound extensions such as `.md.liquid`
+///
+/// Extensions are matched without regard to case, so `PROMPT.MD` written on
+/// Windows is found like `prompt.md`.
+fn has_extension(path: &Path, extensions: &[&str]) -> bool {
+    let Some(filename) = path.file_name().and_then(|s| s.to_str()) else {
+        return false;
+    };
+    let filename = filename.to_ascii_lowercase();
+
+    // Check for compound extensions first
+    let compound_extensions = [".md.liquid", ".markdown.liquid", ".liquid.md"];
+    for compound_ext in &compound_extensions {
+        if filename.ends_with(compound_ext) {
+            // Check if any part of the compound extension matches our filter
+            let parts: Vec<&str> = compound_ext.trim_start_matches('.').split('.').collect();
+            if parts.iter().any(|part| extensions.contains(part)) {
+                return true;
+            }
+        }
+    }
+
+    // Fallback to single extension check
+    path.extension()
+        .and_then(|s| s.to_str())
+        .is_some_and(|ext| extensions.iter().any(|e| e.eq_ignore_ascii_case(ext)))
+}
+
+/// The user's home directory
+///
+/// `%USERPROFILE%` on Windows and `$HOME` elsewhere take precedence over the
+/// platform lookup, so a profile moved with the environment variable is
+/// honoured the same way the shell honours it.
+pub fn home_dir() -> Option<PathBuf> {
+    let var = if cfg!(windows) { "USERPROFILE" } else { "HOME" };
+    std::env::var_os(var)
+        .filter(|home| !home.is_empty())
+        .map(PathBuf::from)
+        .or_else(dirs::home_dir)
+}
+
+/// Whether two paths name the same file
+///
+/// Windows paths are compared without regard to case or to the kind of
+/// separator; elsewhere paths are compared component by component.
+pub fn same_path(a: &Path, b: &Path) -> bool {
+    if cfg!(windows) {
+        let normalize = |path: &Path| path.to_string_lossy().replace('/', "\\").to_lowercase();
+        normalize(a).trim_end_matches('\\') == normalize(b).trim_end_matches('\\')
+    } else {
+        a == b
+    }
+}
 
 /// Find all `.swissarmyhammer` directories by walking up from the current directory
 ///
@@ -27,7 +403,7 @@ pub fn find_swissarmyhammer_dirs_upward(start_path: &Path, exclude_home: bool) -
     let mut depth = 0;
 
     // Get home directory for exclusion check
-    let home_swissarmyhammer = dirs::home_dir().map(|home| home.join(".swissarmyhammer"));
+    let home_swissarmyhammer = home_dir().map(|home| home.join(".swissarmyhammer"));
 
     loop {
         if depth >= MAX_DIRECTORY_DEPTH {
@@ -39,7 +415,7 @@ pub fn find_swissarmyhammer_dirs_upward(start_path: &Path, exclude_home: bool) -
             // Check if we should exclude home directory
             if exclude_home {
                 if let Some(ref home_dir) = home_swissarmyhammer {
-                    if &swissarmyhammer_dir == home_dir {
+                    if same_path(&swissarmyhammer_dir, home_dir) {
                         // Skip home directory but continue searching
                         match path.parent() {
                             Some(parent) => {
@@ -72,8 +448,8 @@ pub fn find_swissarmyhammer_dirs_upward(start_path: &Path, exclude_home: bool) -
 
 /// Walk a directory recursively to find files with specific extensions
 ///
-/// This function uses WalkDir to recursively find all files with the given
-/// extensions in a directory.
+/// Files come from the cached [`DirectoryIndex`] of the directory, so ignored
+/// files are skipped and repeated walks of the same directory are cheap.
 ///
 /// # Arguments
 ///
@@ -87,37 +463,7 @@ pub fn walk_files_with_extensions<'a>(
     dir: &Path,
     extensions: &'a [&'a str],
 ) -> impl Iterator<Item = PathBuf> + 'a {
-    let dir = dir.to_owned();
-    WalkDir::new(dir).into_iter().filter_map(move |entry| {
-        entry.ok().and_then(|e| {
-            let path = e.path();
-            if path.is_file() {
-                if let Some(filename) = path.file_name().and_then(|s| s.to_str()) {
-                    // Check for compound extensions first
-                    let compound_extensions = [".md.liquid", ".markdown.liquid", ".liquid.md"];
-
-                    for compound_ext in &compound_extensions {
-                        if filename.ends_with(compound_ext) {
-                            // Check if any part of the compound extension matches our filter
-                            let parts: Vec<&str> =
-                                compound_ext.trim_start_matches('.').split('.').collect();
-                            if parts.iter().any(|part| extensions.contains(part)) {
-                                return Some(path.to_path_buf());
-                            }
-                        }
-                    }
-
-                    // Fallback to single extension check
-                    if let Some(ext) = path.extension().and_then(|s| s.to_str()) {
-                        if extensions.contains(&ext) {
-                            return Some(path.to_path_buf());
-                        }
-                    }
-                }
-            }
-            None
-        })
-    })
+    cached_files_with_extensions(dir, extensions).into_iter()
 }
 
 #[cfg(test)]
@@ -173,4 +519,141 @@ mod tests {
         assert!(files.iter().any(|p| p.ends_with("nested.mermaid")));
         assert!(!files.iter().any(|p| p.ends_with("test.txt")));
     }
+
+    #[test]
+    fn test_extensions_match_without_case() {
+        assert!(has_extension(Path::new("prompts/REVIEW.MD"), &["md"]));
+        assert!(has_extension(
+            Path::new("prompts/Release.Md.Liquid"),
+            &["liquid"]
+        ));
+        assert!(has_extension(
+            Path::new("flows/Deploy.Mermaid"),
+            &["mermaid"]
+        ));
+        assert!(!has_extension(Path::new("notes/TODO.TXT"), &["md"]));
+
+        assert!(same_path(Path::new("/home/jane"), Path::new("/home/jane")));
+        assert_eq!(
+            same_path(Path::new("C:/Users/Jane"), Path::new(r"c:\users\jane\")),
+            cfg!(windows)
+        );
+    }
+
+    #[test]
+    fn test_directory_index_respects_ignore_files_and_limits() {
+        let temp_dir = TempDir::new().unwrap();
+        let base = temp_dir.path();
+
+        fs::write(base.join(SAH_IGNORE_FILE), "generated/\n*.tmp\n").unwrap();
+        fs::write(base.join("keep.md"), "content").unwrap();
+        fs::write(base.join("scratch.tmp"), "content").unwrap();
+        fs::create_dir_all(base.join("generated")).unwrap();
+        fs::write(base.join("generated").join("out.md"), "content").unwrap();
+        fs::create_dir_all(base.join("a").join("b")).unwrap();
+        fs::write(base.join("a").join("one.md"), "content").unwrap();
+        fs::write(base.join("a").join("b").join("two.md"), "content").unwrap();
+
+        let index = DirectoryIndex::scan(base, ScanLimits::default());
+        let files = index.files_with_extensions(&["md"]);
+        assert_eq!(
+            files,
+            vec![
+                base.join("keep.md"),
+                base.join("a").join("one.md"),
+                base.join("a").join("b").join("two.md"),
+            ]
+        );
+        assert!(!index.is_truncated());
+
+        let shallow = DirectoryIndex::scan(
+            base,
+            ScanLimits {
+                max_depth: 2,
+                ..ScanLimits::default()
+            },
+        );
+        assert!(!shallow.files().any(|path| path.ends_with("two.md")));
+
+        let capped = DirectoryIndex::scan(
+            base,
+            ScanLimits {
+                max_files: 2,
+                ..ScanLimits::default()
+            },
+        );
+        assert_eq!(capped.len(), 2);
+        assert!(capped.is_truncated());
+    }
+
+    #[test]
+    fn test_directory_index_rescan_reports_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let base = temp_dir.path();
+        fs::create_dir_all(base.join("old")).unwrap();
+        fs::write(base.join("keep.md"), "content").unwrap();
+        fs::write(base.join("old").join("gone.md"), "content").unwrap();
+
+        let mut index = DirectoryIndex::scan(base, ScanLimits::default());
+        assert_eq!(index.len(), 2);
+        assert!(index.rescan().is_empty());
+
+        // Let directory modification times move past the scan
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        fs::remove_dir_all(base.join("old")).unwrap();
+        fs::create_dir_all(base.join("new")).unwrap();
+        fs::write(base.join("new").join("added.md"), "content").unwrap();
+        fs::write(base.join("also.md"), "content").unwrap();
+
+        let changes = index.rescan();
+        assert_eq!(
+            changes.added,
+            vec![base.join("also.md"), base.join("new").join("added.md")]
+        );
+        assert_eq!(changes.removed, vec![base.join("old").join("gone.md")]);
+        assert_eq!(index.len(), 3);
+
+        // Rescanning a subdirectory applies the root's ignore file
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        fs::write(base.join(SAH_IGNORE_FILE), "*.tmp\n").unwrap();
+        let mut index = DirectoryIndex::scan(base, ScanLimits::default());
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        fs::write(base.join("new").join("scratch.tmp"), "content").unwrap();
+        fs::write(base.join("new").join("more.md"), "content").unwrap();
+        assert_eq!(index.rescan().added, vec![base.join("new").join("more.md")]);
+    }
+
+    #[test]
+    fn test_directory_index_ignores_git_ignore_rules() {
+        let temp_dir = TempDir::new().unwrap();
+        let base = temp_dir.path();
+        fs::create_dir_all(base.join(".git")).unwrap();
+        fs::write(base.join(".gitignore"), "drafts/\n").unwrap();
+        fs::create_dir_all(base.join("drafts")).unwrap();
+        fs::write(base.join("drafts").join("plan.md"), "content").unwrap();
+
+        let index = DirectoryIndex::scan(base, ScanLimits::default());
+        assert_eq!(
+            index.files_with_extensions(&["md"]),
+            vec![base.join("drafts").join("plan.md")]
+        );
+    }
+
+    #[test]
+    fn test_cached_index_rescans_only_when_invalidated() {
+        let temp_dir = TempDir::new().unwrap();
+        let base = temp_dir.path();
+        fs::write(base.join("first.md"), "content").unwrap();
+        assert_eq!(cached_files(base), vec![base.join("first.md")]);
+
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        fs::write(base.join("second.md"), "content").unwrap();
+        assert_eq!(cached_files(base), vec![base.join("first.md")]);
+
+        invalidate_cached_indexes(&[base.join("second.md")]);
+        assert_eq!(
+            cached_files(base),
+            vec![base.join("first.md"), base.join("second.md")]
+        );
+    }
 }
@@ -2,7 +2,13 @@
 //!
 //! This module provides a comprehensive error type hierarchy that replaces
 //! ad-hoc error handling throughout the codebase with typed, structured errors.
+//!
+//! Every [`SwissArmyHammerError`] has a stable [`ErrorCode`] such as `SAH-1700`
+//! so scripts and MCP clients can tell errors apart without matching messages.
+//! Codes are grouped into [`ErrorCategory`] ranges of one hundred; codes are
+//! never reused or renumbered once released.
 
+use serde::{Serialize, Serializer};
 use std::fmt;
 use std::io;
 use std::path::PathBuf;
@@ -674,6 +680,425 @@ impl SwissArmyHammerError {
     }
 }
 
+/// Area of the library an [`ErrorCode`] belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCategory {
+    /// Errors without a more specific category
+    General,
+    /// File system access and serialization
+    Io,
+    /// Configuration
+    Config,
+    /// Prompts and templates
+    Prompt,
+    /// Workflows and workflow runs
+    Workflow,
+    /// Storage backends
+    Storage,
+    /// Issues
+    Issue,
+    /// Memos
+    Memo,
+    /// Git operations
+    Git,
+    /// Semantic search
+    Search,
+}
+
+impl ErrorCategory {
+    /// All categories, in code order
+    pub const ALL: [ErrorCategory; 10] = [
+        ErrorCategory::General,
+        ErrorCategory::Io,
+        ErrorCategory::Config,
+        ErrorCategory::Prompt,
+        ErrorCategory::Workflow,
+        ErrorCategory::Storage,
+        ErrorCategory::Issue,
+        ErrorCategory::Memo,
+        ErrorCategory::Git,
+        ErrorCategory::Search,
+    ];
+
+    /// Machine-readable category name, as used in JSON output
+    pub fn name(self) -> &'static str {
+        match self {
+            ErrorCategory::General => "general",
+            ErrorCategory::Io => "io",
+            ErrorCategory::Config => "config",
+            ErrorCategory::Prompt => "prompt",
+            ErrorCategory::Workflow => "workflow",
+            ErrorCategory::Storage => "storage",
+            ErrorCategory::Issue => "issue",
+            ErrorCategory::Memo => "memo",
+            ErrorCategory::Git => "git",
+            ErrorCategory::Search => "search",
+        }
+    }
+
+    /// Human-readable category title
+    pub fn title(self) -> &'static str {
+        match self {
+            ErrorCategory::General => "General",
+            ErrorCategory::Io => "I/O and serialization",
+            ErrorCategory::Config => "Configuration",
+            ErrorCategory::Prompt => "Prompts and templates",
+            ErrorCategory::Workflow => "Workflows",
+            ErrorCategory::Storage => "Storage",
+            ErrorCategory::Issue => "Issues",
+            ErrorCategory::Memo => "Memos",
+            ErrorCategory::Git => "Git",
+            ErrorCategory::Search => "Semantic search",
+        }
+    }
+
+    /// First code of the category's range
+    fn first_code(self) -> u16 {
+        1000 + 100 * Self::ALL.iter().position(|c| *c == self).unwrap_or(0) as u16
+    }
+
+    /// Documented codes in this category
+    pub fn codes(self) -> impl Iterator<Item = &'static ErrorCodeInfo> {
+        ERROR_CODES
+            .iter()
+            .filter(move |info| info.code.category() == self)
+    }
+}
+
+impl fmt::Display for ErrorCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Stable, machine-readable error code such as `SAH-1700`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ErrorCode(u16);
+
+impl ErrorCode {
+    /// An error without a more specific code
+    pub const OTHER: ErrorCode = ErrorCode(1000);
+    /// An error wrapped with context whose cause has no code of its own
+    pub const CONTEXT: ErrorCode = ErrorCode(1001);
+    /// An ABORT ERROR
+    pub const ABORT: ErrorCode = ErrorCode(1002);
+    /// An I/O operation failed
+    pub const IO: ErrorCode = ErrorCode(1100);
+    /// YAML could not be parsed or written
+    pub const YAML: ErrorCode = ErrorCode(1101);
+    /// JSON could not be parsed or written
+    pub const JSON: ErrorCode = ErrorCode(1102);
+    /// Configuration is invalid
+    pub const CONFIG: ErrorCode = ErrorCode(1200);
+    /// A prompt was not found
+    pub const PROMPT_NOT_FOUND: ErrorCode = ErrorCode(1300);
+    /// A template failed to parse or render
+    pub const TEMPLATE: ErrorCode = ErrorCode(1301);
+    /// A workflow was not found
+    pub const WORKFLOW_NOT_FOUND: ErrorCode = ErrorCode(1400);
+    /// A workflow run was not found
+    pub const WORKFLOW_RUN_NOT_FOUND: ErrorCode = ErrorCode(1401);
+    /// A storage backend failed
+    pub const STORAGE: ErrorCode = ErrorCode(1500);
+    /// An issue was not found
+    pub const ISSUE_NOT_FOUND: ErrorCode = ErrorCode(1600);
+    /// An issue already exists
+    pub const ISSUE_ALREADY_EXISTS: ErrorCode = ErrorCode(1601);
+    /// A memo was not found
+    pub const MEMO_NOT_FOUND: ErrorCode = ErrorCode(1700);
+    /// A memo ID is malformed
+    pub const INVALID_MEMO_ID: ErrorCode = ErrorCode(1701);
+    /// A memo already exists
+    pub const MEMO_ALREADY_EXISTS: ErrorCode = ErrorCode(1702);
+    /// A memo failed validation
+    pub const MEMO_VALIDATION_FAILED: ErrorCode = ErrorCode(1703);
+    /// A git operation failed
+    pub const GIT_OPERATION_FAILED: ErrorCode = ErrorCode(1800);
+    /// A git command exited unsuccessfully
+    pub const GIT_COMMAND_FAILED: ErrorCode = ErrorCode(1801);
+    /// No git repository was found
+    pub const GIT_REPOSITORY_NOT_FOUND: ErrorCode = ErrorCode(1802);
+    /// A git branch operation failed
+    pub const GIT_BRANCH_OPERATION_FAILED: ErrorCode = ErrorCode(1803);
+    /// Semantic search failed
+    pub const SEMANTIC_SEARCH: ErrorCode = ErrorCode(1900);
+
+    /// Numeric part of the code
+    pub fn number(self) -> u16 {
+        self.0
+    }
+
+    /// Category the code belongs to
+    pub fn category(self) -> ErrorCategory {
+        ErrorCategory::ALL
+            .iter()
+            .rev()
+            .copied()
+            .find(|category| self.0 >= category.first_code())
+            .unwrap_or(ErrorCategory::General)
+    }
+
+    /// Documentation of the code
+    pub fn info(self) -> Option<&'static ErrorCodeInfo> {
+        ERROR_CODES.iter().find(|info| info.code == self)
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SAH-{:04}", self.0)
+    }
+}
+
+impl std::str::FromStr for ErrorCode {
+    type Err = SwissArmyHammerError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        s.trim()
+            .strip_prefix("SAH-")
+            .and_then(|number| number.parse().ok())
+            .map(ErrorCode)
+            .filter(|code| code.info().is_some())
+            .ok_or_else(|| SwissArmyHammerError::Other(format!("Unknown error code: {s}")))
+    }
+}
+
+impl Serialize for ErrorCode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Documentation of one [`ErrorCode`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ErrorCodeInfo {
+    /// The code
+    pub code: ErrorCode,
+    /// The [`SwissArmyHammerError`] variant that carries the code
+    pub variant: &'static str,
+    /// What the error means
+    pub summary: &'static str,
+}
+
+/// Every error code, in code order
+pub const ERROR_CODES: &[ErrorCodeInfo] = &[
+    ErrorCodeInfo {
+        code: ErrorCode::OTHER,
+        variant: "Other",
+        summary: "An error without a more specific code.",
+    },
+    ErrorCodeInfo {
+        code: ErrorCode::CONTEXT,
+        variant: "Context",
+        summary: "An error wrapped with context whose cause has no code of its own. Wrapped library errors keep their own code.",
+    },
+    ErrorCodeInfo {
+        code: ErrorCode::ABORT,
+        variant: "Other",
+        summary: "An ABORT ERROR: a rule such as the issue branching workflow was violated and the operation must stop without retrying.",
+    },
+    ErrorCodeInfo {
+        code: ErrorCode::IO,
+        variant: "Io",
+        summary: "A file system operation failed, for example a missing file or a permission error.",
+    },
+    ErrorCodeInfo {
+        code: ErrorCode::YAML,
+        variant: "Serialization",
+        summary: "YAML could not be parsed or written, for example malformed front matter or a corrupt stored file.",
+    },
+    ErrorCodeInfo {
+        code: ErrorCode::JSON,
+        variant: "Json",
+        summary: "JSON could not be parsed or written, for example a corrupt stored memo or workflow run.",
+    },
+    ErrorCodeInfo {
+        code: ErrorCode::CONFIG,
+        variant: "Config",
+        summary: "Configuration is missing or invalid.",
+    },
+    ErrorCodeInfo {
+        code: ErrorCode::PROMPT_NOT_FOUND,
+        variant: "PromptNotFound",
+        summary: "No prompt has the requested name.",
+    },
+    ErrorCodeInfo {
+        code: ErrorCode::TEMPLATE,
+        variant: "Template",
+        summary: "A template failed to parse or render, or exceeded the size and complexity limits.",
+    },
+    ErrorCodeInfo {
+        code: ErrorCode::WORKFLOW_NOT_FOUND,
+        variant: "WorkflowNotFound",
+        summary: "No workflow has the requested name.",
+    },
+    ErrorCodeInfo {
+        code: ErrorCode::WORKFLOW_RUN_NOT_FOUND,
+        variant: "WorkflowRunNotFound",
+        summary: "No stored workflow run has the requested ID.",
+    },
+    ErrorCodeInfo {
+        code: ErrorCode::STORAGE,
+        variant: "Storage",
+        summary: "A storage backend failed to read or write.",
+    },
+    ErrorCodeInfo {
+        code: ErrorCode::ISSUE_NOT_FOUND,
+        variant: "IssueNotFound",
+        summary: "No issue has the requested name.",
+    },
+    ErrorCodeInfo {
+        code: ErrorCode::ISSUE_ALREADY_EXISTS,
+        variant: "IssueAlreadyExists",
+        summary: "An issue with the requested number already exists.",
+    },
+    ErrorCodeInfo {
+        code: ErrorCode::MEMO_NOT_FOUND,
+        variant: "MemoNotFound",
+        summary: "No memo has the requested ID.",
+    },
+    ErrorCodeInfo {
+        code: ErrorCode::INVALID_MEMO_ID,
+        variant: "InvalidMemoId",
+        summary: "A memo ID is not a valid ULID.",
+    },
+    ErrorCodeInfo {
+        code: ErrorCode::MEMO_ALREADY_EXISTS,
+        variant: "MemoAlreadyExists",
+        summary: "A memo with the requested ID already exists.",
+    },
+    ErrorCodeInfo {
+        code: ErrorCode::MEMO_VALIDATION_FAILED,
+        variant: "MemoValidationFailed",
+        summary: "A memo's title or content is invalid.",
+    },
+    ErrorCodeInfo {
+        code: ErrorCode::GIT_OPERATION_FAILED,
+        variant: "GitOperationFailed",
+        summary: "A git operation failed.",
+    },
+    ErrorCodeInfo {
+        code: ErrorCode::GIT_COMMAND_FAILED,
+        variant: "GitCommandFailed",
+        summary: "A git command exited unsuccessfully; the message includes its exit code and stderr.",
+    },
+    ErrorCodeInfo {
+        code: ErrorCode::GIT_REPOSITORY_NOT_FOUND,
+        variant: "GitRepositoryNotFound",
+        summary: "The working directory is not inside a git repository.",
+    },
+    ErrorCodeInfo {
+        code: ErrorCode::GIT_BRANCH_OPERATION_FAILED,
+        variant: "GitBranchOperationFailed",
+        summary: "Creating, switching, merging or deleting a branch failed.",
+    },
+    ErrorCodeInfo {
+        code: ErrorCode::SEMANTIC_SEARCH,
+        variant: "Semantic",
+        summary: "Semantic search failed to index or query.",
+    },
+];
+
+/// Markdown reference of every error code, grouped by category
+///
+/// `doc/src/error-codes.md` is this text; a test keeps the two in sync and
+/// rewrites the file when `SAH_UPDATE_ERROR_CODES` is set.
+pub fn error_code_reference() -> String {
+    let mut reference = String::from(
+        "# Error Codes\n\n\
+         Every SwissArmyHammer error has a stable code such as `SAH-1700`. Codes \
+         appear in CLI error messages, in the `data` of MCP error responses, and \
+         in the JSON form of errors. Scripts should match on codes rather than \
+         messages, which may change.\n\n\
+         Codes are grouped by category in ranges of one hundred, and a code is never \
+         reused for a different error.\n\n\
+         This page is generated from the error definitions.\n",
+    );
+    for category in ErrorCategory::ALL {
+        reference.push_str(&format!(
+            "\n## {} (`{}`)\n\n| Code | Error | Meaning |\n| --- | --- | --- |\n",
+            category.title(),
+            category.name()
+        ));
+        for info in category.codes() {
+            reference.push_str(&format!(
+                "| `{}` | `{}` | {} |\n",
+                info.code, info.variant, info.summary
+            ));
+        }
+    }
+    reference
+}
+
+impl SwissArmyHammerError {
+    /// Stable code identifying the kind of error
+    ///
+    /// Errors wrapped with [`ErrorContext`] keep the code of the wrapped
+    /// library error.
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            SwissArmyHammerError::Io(_) => ErrorCode::IO,
+            SwissArmyHammerError::Template(_) => ErrorCode::TEMPLATE,
+            SwissArmyHammerError::PromptNotFound(_) => ErrorCode::PROMPT_NOT_FOUND,
+            SwissArmyHammerError::Config(_) => ErrorCode::CONFIG,
+            SwissArmyHammerError::Storage(_) => ErrorCode::STORAGE,
+            SwissArmyHammerError::WorkflowNotFound(_) => ErrorCode::WORKFLOW_NOT_FOUND,
+            SwissArmyHammerError::WorkflowRunNotFound(_) => ErrorCode::WORKFLOW_RUN_NOT_FOUND,
+            SwissArmyHammerError::Serialization(_) => ErrorCode::YAML,
+            SwissArmyHammerError::Json(_) => ErrorCode::JSON,
+            SwissArmyHammerError::IssueNotFound(_) => ErrorCode::ISSUE_NOT_FOUND,
+            SwissArmyHammerError::IssueAlreadyExists(_) => ErrorCode::ISSUE_ALREADY_EXISTS,
+            SwissArmyHammerError::GitOperationFailed { .. } => ErrorCode::GIT_OPERATION_FAILED,
+            SwissArmyHammerError::GitCommandFailed { .. } => ErrorCode::GIT_COMMAND_FAILED,
+            SwissArmyHammerError::GitRepositoryNotFound { .. } => {
+                ErrorCode::GIT_REPOSITORY_NOT_FOUND
+            }
+            SwissArmyHammerError::GitBranchOperationFailed { .. } => {
+                ErrorCode::GIT_BRANCH_OPERATION_FAILED
+            }
+            SwissArmyHammerError::MemoNotFound(_) => ErrorCode::MEMO_NOT_FOUND,
+            SwissArmyHammerError::InvalidMemoId(_) => ErrorCode::INVALID_MEMO_ID,
+            SwissArmyHammerError::MemoAlreadyExists(_) => ErrorCode::MEMO_ALREADY_EXISTS,
+            SwissArmyHammerError::MemoValidationFailed(_) => ErrorCode::MEMO_VALIDATION_FAILED,
+            SwissArmyHammerError::Semantic(_) => ErrorCode::SEMANTIC_SEARCH,
+            SwissArmyHammerError::Other(_) if self.is_abort_error() => ErrorCode::ABORT,
+            SwissArmyHammerError::Other(_) => ErrorCode::OTHER,
+            SwissArmyHammerError::Context { source, .. } => source
+                .downcast_ref::<SwissArmyHammerError>()
+                .map_or(ErrorCode::CONTEXT, SwissArmyHammerError::code),
+        }
+    }
+
+    /// Category of the error's code
+    pub fn category(&self) -> ErrorCategory {
+        self.code().category()
+    }
+
+    /// Machine-readable form of the error: `{"code", "category", "message"}`
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "code": self.code(),
+            "category": self.category(),
+            "message": self.to_string(),
+        })
+    }
+}
+
+/// Errors serialize to the same object as [`SwissArmyHammerError::to_json`],
+/// so JSON output that embeds an error carries its code
+impl Serialize for SwissArmyHammerError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut error = serializer.serialize_struct("SwissArmyHammerError", 3)?;
+        error.serialize_field("code", &self.code())?;
+        error.serialize_field("category", &self.category())?;
+        error.serialize_field("message", &self.to_string())?;
+        error.end()
+    }
+}
+
 /// Extension trait for error types to format the full error chain
 pub trait ErrorChainExt {
     /// Format the full error chain
@@ -712,4 +1137,95 @@ mod tests {
         assert!(chain.contains("Failed to load workflow"));
         assert!(chain.contains("file not found"));
     }
+
+    #[test]
+    fn test_error_codes() {
+        let errors = [
+            SwissArmyHammerError::Io(io::Error::new(io::ErrorKind::NotFound, "gone")),
+            SwissArmyHammerError::Template(String::new()),
+            SwissArmyHammerError::PromptNotFound(String::new()),
+            SwissArmyHammerError::Config(String::new()),
+            SwissArmyHammerError::Storage(String::new()),
+            SwissArmyHammerError::WorkflowNotFound(String::new()),
+            SwissArmyHammerError::WorkflowRunNotFound(String::new()),
+            serde_yaml::from_str::<u32>("[").unwrap_err().into(),
+            serde_json::from_str::<u32>("[").unwrap_err().into(),
+            SwissArmyHammerError::IssueNotFound(String::new()),
+            SwissArmyHammerError::IssueAlreadyExists(1),
+            SwissArmyHammerError::git_operation_failed("op", "details"),
+            SwissArmyHammerError::git_command_failed("cmd", 1, "stderr"),
+            SwissArmyHammerError::git_repository_not_found("/"),
+            SwissArmyHammerError::git_branch_operation_failed("op", "branch", "details"),
+            SwissArmyHammerError::memo_not_found("id"),
+            SwissArmyHammerError::invalid_memo_id("id"),
+            SwissArmyHammerError::memo_already_exists("id"),
+            SwissArmyHammerError::memo_validation_failed("reason"),
+            SwissArmyHammerError::Semantic(crate::search::SemanticError::Index(String::new())),
+            SwissArmyHammerError::Other(String::new()),
+            SwissArmyHammerError::cannot_switch_issue_to_issue(),
+            SwissArmyHammerError::Context {
+                message: String::new(),
+                source: Box::new(io::Error::new(io::ErrorKind::NotFound, "gone")),
+            },
+        ];
+
+        // Every error has a distinct, documented code, and every documented
+        // code is used
+        let codes: Vec<ErrorCode> = errors.iter().map(|e| e.code()).collect();
+        let documented: Vec<ErrorCode> = ERROR_CODES.iter().map(|info| info.code).collect();
+        let mut sorted = codes.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(sorted.len(), codes.len());
+        assert_eq!(sorted, documented);
+
+        for error in &errors {
+            let info = error.code().info().unwrap();
+            let variant = format!("{error:?}");
+            assert!(variant.starts_with(info.variant), "{variant}");
+        }
+    }
+
+    #[test]
+    fn test_error_code_format_and_category() {
+        let error = SwissArmyHammerError::memo_not_found("01ABC");
+        assert_eq!(error.code().to_string(), "SAH-1700");
+        assert_eq!(error.category(), ErrorCategory::Memo);
+        assert_eq!("SAH-1700".parse::<ErrorCode>().unwrap(), error.code());
+        assert!("SAH-1799".parse::<ErrorCode>().is_err());
+        assert_eq!(
+            error.to_json(),
+            serde_json::json!({
+                "code": "SAH-1700",
+                "category": "memo",
+                "message": "Memo not found: 01ABC",
+            })
+        );
+
+        assert_eq!(serde_json::to_value(&error).unwrap(), error.to_json());
+
+        // Wrapped library errors keep their code
+        let wrapped: Result<()> = Err(SwissArmyHammerError::memo_not_found("01ABC"));
+        let wrapped = wrapped.context("Failed to load memo").unwrap_err();
+        assert_eq!(wrapped.code(), ErrorCode::MEMO_NOT_FOUND);
+        assert_eq!(ErrorCode::CONTEXT.category(), ErrorCategory::General);
+        assert_eq!(ErrorCode::SEMANTIC_SEARCH.category(), ErrorCategory::Search);
+    }
+
+    #[test]
+    fn test_error_code_reference_is_current() {
+        let path =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../doc/src/error-codes.md");
+        let reference = error_code_reference();
+        if std::env::var_os("SAH_UPDATE_ERROR_CODES").is_some() {
+            std::fs::write(&path, &reference).unwrap();
+        }
+        let documented = std::fs::read_to_string(&path).unwrap_or_default();
+        assert!(
+            documented == reference,
+            "{} is out of date; regenerate it with \
+             SAH_UPDATE_ERROR_CODES=1 cargo test -p swissarmyhammer error_code_reference",
+            path.display()
+        );
+    }
 }
@@ -0,0 +1,309 @@
+//! Typed events modules publish and others subscribe to
+//!
+//! Modules announce what happened on the process-wide [`event_bus`] rather
+//! than calling whoever needs to know. Issue and memo storage publish
+//! [`Event::IssueCreated`], [`Event::IssueCompleted`] and [`Event::MemoUpdated`],
+//! the MCP server publishes [`Event::PromptsReloaded`] and
+//! [`Event::WorkflowsReloaded`], the workflow executor publishes
+//! [`Event::WorkflowStateChanged`] and [`Event::WorkflowRunFinished`], runs
+//! followed by a [`RunProgressReporter`](crate::workflow::RunProgressReporter)
+//! publish [`Event::WorkflowRunProgress`], and the cost tracker publishes
+//! [`Event::BudgetExceeded`]. Subscribers, such as the
+//! MCP server's client notifications and [`crate::webhooks`], receive every
+//! event published after they subscribed.
+//!
+//! Publishing never blocks and costs next to nothing when nobody listens. A
+//! subscriber that falls more than [`EVENT_BUS_CAPACITY`] events behind skips
+//! the oldest ones, with a warning logged.
+
+use crate::cost::BudgetEvent;
+use crate::history::ChangeKind;
+use crate::workflow::{
+    RunProgress, StateId, WorkflowName, WorkflowRun, WorkflowRunId, WorkflowRunStatus,
+};
+use serde::Serialize;
+use std::fmt;
+use std::sync::OnceLock;
+use tokio::sync::broadcast::{self, error::RecvError, error::TryRecvError};
+
+/// Events a subscriber can fall behind by before it skips the oldest
+pub const EVENT_BUS_CAPACITY: usize = 256;
+
+/// Something that happened which other modules may react to
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event {
+    /// Prompts were reloaded from disk
+    PromptsReloaded {
+        /// Number of prompts loaded
+        count: usize,
+    },
+    /// Workflows were reloaded from disk
+    WorkflowsReloaded {
+        /// Number of workflows loaded
+        count: usize,
+    },
+    /// An issue was created
+    IssueCreated {
+        /// Name of the issue
+        name: String,
+    },
+    /// An issue was marked complete
+    IssueCompleted {
+        /// Name of the issue
+        name: String,
+    },
+    /// A workflow run moved from one state to another
+    WorkflowStateChanged {
+        /// The run
+        run_id: WorkflowRunId,
+        /// Workflow the run executes
+        workflow: WorkflowName,
+        /// State the run left
+        from: StateId,
+        /// State the run entered
+        to: StateId,
+    },
+    /// A state of a followed workflow run started or finished executing
+    WorkflowRunProgress(RunProgress),
+    /// A workflow run completed, failed or was cancelled
+    WorkflowRunFinished {
+        /// The run
+        run_id: WorkflowRunId,
+        /// Workflow the run executed
+        workflow: WorkflowName,
+        /// How the run ended
+        status: WorkflowRunStatus,
+    },
+    /// A memo was created, updated, deleted or restored
+    MemoUpdated {
+        /// ID of the memo
+        id: String,
+        /// What happened to the memo
+        change: ChangeKind,
+    },
+    /// Spending crossed a cost budget, or a prompt was refused because the
+    /// budget is spent
+    BudgetExceeded(BudgetEvent),
+}
+
+impl Event {
+    /// The event announcing that a run ended with `status`, or `None` when
+    /// the run is still running or paused
+    pub fn run_finished(run: &WorkflowRun, status: WorkflowRunStatus) -> Option<Self> {
+        matches!(
+            status,
+            WorkflowRunStatus::Completed | WorkflowRunStatus::Failed | WorkflowRunStatus::Cancelled
+        )
+        .then(|| Event::WorkflowRunFinished {
+            run_id: run.id,
+            workflow: run.workflow.name.clone(),
+            status,
+        })
+    }
+
+    /// Name of the kind of event, as it is serialized
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Event::PromptsReloaded { .. } => "prompts_reloaded",
+            Event::WorkflowsReloaded { .. } => "workflows_reloaded",
+            Event::IssueCreated { .. } => "issue_created",
+            Event::IssueCompleted { .. } => "issue_completed",
+            Event::WorkflowStateChanged { .. } => "workflow_state_changed",
+            Event::WorkflowRunProgress(_) => "workflow_run_progress",
+            Event::WorkflowRunFinished { .. } => "workflow_run_finished",
+            Event::MemoUpdated { .. } => "memo_updated",
+            Event::BudgetExceeded(_) => "budget_exceeded",
+        }
+    }
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Event::PromptsReloaded { count } => write!(f, "Reloaded {count} prompts"),
+            Event::WorkflowsReloaded { count } => write!(f, "Reloaded {count} workflows"),
+            Event::IssueCreated { name } => write!(f, "Created issue {name}"),
+            Event::IssueCompleted { name } => write!(f, "Completed issue {name}"),
+            Event::WorkflowStateChanged {
+                run_id,
+                workflow,
+                from,
+                to,
+            } => write!(
+                f,
+                "Workflow {workflow} run {run_id} moved from {from} to {to}"
+            ),
+            Event::WorkflowRunProgress(progress) => write!(f, "{progress}"),
+            Event::WorkflowRunFinished {
+                run_id,
+                workflow,
+                status,
+            } => write!(f, "Workflow {workflow} run {run_id} finished: {status:?}"),
+            Event::MemoUpdated { id, change } => write!(f, "Memo {id} {change}"),
+            Event::BudgetExceeded(event) => write!(f, "{event}"),
+        }
+    }
+}
+
+/// Broadcasts events to every subscriber
+#[derive(Debug, Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<Event>,
+}
+
+impl EventBus {
+    /// Create a bus whose subscribers can fall `capacity` events behind
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity.max(1));
+        Self { sender }
+    }
+
+    /// Send an event to every current subscriber, returning how many there are
+    pub fn publish(&self, event: Event) -> usize {
+        tracing::trace!("Publishing event: {}", event);
+        self.sender.send(event).unwrap_or(0)
+    }
+
+    /// Receive the events published from now on
+    pub fn subscribe(&self) -> EventSubscriber {
+        EventSubscriber {
+            receiver: self.sender.subscribe(),
+        }
+    }
+
+    /// Number of current subscribers
+    pub fn subscriber_count(&self) -> usize {
+        self.sender.receiver_count()
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new(EVENT_BUS_CAPACITY)
+    }
+}
+
+/// Events received from an [`EventBus`]
+#[derive(Debug)]
+pub struct EventSubscriber {
+    receiver: broadcast::Receiver<Event>,
+}
+
+impl EventSubscriber {
+    /// Wait for the next event, or `None` once the bus is gone
+    pub async fn recv(&mut self) -> Option<Event> {
+        loop {
+            match self.receiver.recv().await {
+                Ok(event) => return Some(event),
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!("Event subscriber fell behind and skipped {skipped} events");
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    }
+
+    /// The next event if one is waiting
+    pub fn try_recv(&mut self) -> Option<Event> {
+        loop {
+            match self.receiver.try_recv() {
+                Ok(event) => return Some(event),
+                Err(TryRecvError::Lagged(skipped)) => {
+                    tracing::warn!("Event subscriber fell behind and skipped {skipped} events");
+                }
+                Err(TryRecvError::Empty | TryRecvError::Closed) => return None,
+            }
+        }
+    }
+}
+
+static EVENT_BUS: OnceLock<EventBus> = OnceLock::new();
+
+/// Get the process-wide event bus
+pub fn event_bus() -> &'static EventBus {
+    EVENT_BUS.get_or_init(EventBus::default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::issues::{FileSystemIssueStorage, IssueStorage};
+    use crate::memoranda::{MarkdownMemoStorage, MemoStorage};
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_publish_and_subscribe() {
+        let bus = EventBus::new(2);
+        assert_eq!(bus.publish(Event::PromptsReloaded { count: 1 }), 0);
+
+        let mut first = bus.subscribe();
+        let mut second = bus.subscribe();
+        assert_eq!(bus.subscriber_count(), 2);
+        let created = Event::IssueCreated {
+            name: "000001_login".to_string(),
+        };
+        assert_eq!(bus.publish(created.clone()), 2);
+        assert_eq!(first.recv().await, Some(created.clone()));
+        assert_eq!(second.try_recv(), Some(created));
+        assert_eq!(second.try_recv(), None);
+
+        // A subscriber that falls behind skips the oldest events
+        for count in 0..3 {
+            bus.publish(Event::PromptsReloaded { count });
+        }
+        assert_eq!(first.try_recv(), Some(Event::PromptsReloaded { count: 1 }));
+
+        let memo = Event::MemoUpdated {
+            id: "Notes".to_string(),
+            change: ChangeKind::Deleted,
+        };
+        assert_eq!(memo.kind(), "memo_updated");
+        assert_eq!(memo.to_string(), "Memo Notes deleted");
+        assert_eq!(
+            serde_json::to_value(&memo).unwrap(),
+            serde_json::json!({"event": "memo_updated", "id": "Notes", "change": "deleted"})
+        );
+
+        drop(bus);
+        assert_eq!(
+            first.recv().await,
+            Some(Event::PromptsReloaded { count: 2 })
+        );
+        assert_eq!(first.recv().await, None);
+    }
+
+    #[tokio::test]
+    async fn test_storage_publishes_events() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut events = event_bus().subscribe();
+
+        let memos = MarkdownMemoStorage::new(temp_dir.path().join("memos"));
+        let memo = memos
+            .create_memo("Event bus notes".to_string(), "Hello".to_string())
+            .await
+            .unwrap();
+        memos.delete_memo(&memo.id).await.unwrap();
+        let issues = FileSystemIssueStorage::new(temp_dir.path().join("issues")).unwrap();
+        let issue = issues
+            .create_issue("event_bus_issue".to_string(), "# Hello".to_string())
+            .await
+            .unwrap();
+        issues.mark_complete(&issue.name).await.unwrap();
+
+        let mut received = Vec::new();
+        while let Some(event) = events.try_recv() {
+            received.push(event);
+        }
+        for change in [ChangeKind::Created, ChangeKind::Deleted] {
+            assert!(received.contains(&Event::MemoUpdated {
+                id: memo.id.as_str().to_string(),
+                change,
+            }));
+        }
+        assert!(received.contains(&Event::IssueCreated {
+            name: issue.name.clone()
+        }));
+        assert!(received.contains(&Event::IssueCompleted { name: issue.name }));
+    }
+}
@@ -15,8 +15,13 @@
 //!   the error is silently ignored and loading continues with other directories.
 //!
 //! - **Security violations**: Files that fail security checks (path traversal,
-//!   file size limits) are logged and skipped, but don't cause the overall
-//!   operation to fail.
+//!   symlinks escaping the directory, the [`PathPolicy`] depth and denylist, file
+//!   size limits) are logged and skipped, but don't cause the overall operation
+//!   to fail.
+//!
+//! - **Integrity failures**: Directories failing the [`PromptIntegrity`]
+//!   verification a file system is given are logged, or fail the load under
+//!   the `require` policy.
 //!
 //! - **Critical errors**: Only errors that prevent the entire operation from
 //!   functioning (like current directory access) are propagated up.
@@ -24,7 +29,11 @@
 //! All skipped files and errors are logged using the `tracing` framework at
 //! appropriate levels (warn for security issues, debug for missing directories).
 
-use crate::directory_utils::{find_swissarmyhammer_dirs_upward, walk_files_with_extensions};
+use crate::directory_utils::{
+    find_swissarmyhammer_dirs_upward, home_dir, walk_files_with_extensions,
+};
+use crate::prompt_integrity::PromptIntegrity;
+use crate::security::{validate_path, PathPolicy};
 use crate::Result;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -32,6 +41,10 @@ use std::path::{Path, PathBuf};
 /// Maximum file size to load (10MB)
 const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
 
+/// Extensions of the files loaded from directories, including the parts of
+/// compound extensions such as `.md.liquid`
+pub const FILE_EXTENSIONS: &[&str] = &["md", "mermaid"];
+
 /// Source of a file (builtin, user, local, or dynamic)
 #[derive(Debug, Clone, PartialEq, serde::Serialize)]
 pub enum FileSource {
@@ -56,6 +69,46 @@ impl std::fmt::Display for FileSource {
     }
 }
 
+/// Sources files may be loaded from
+///
+/// An empty `include` list allows every source. Sources listed in `exclude`
+/// are never loaded, even when included. Sources that are not allowed are
+/// skipped entirely, so their directories are never read.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SourceFilter {
+    /// Sources to load from, all sources when empty
+    pub include: Vec<FileSource>,
+    /// Sources never loaded from
+    pub exclude: Vec<FileSource>,
+}
+
+static GLOBAL_SOURCE_FILTER: std::sync::OnceLock<SourceFilter> = std::sync::OnceLock::new();
+
+impl SourceFilter {
+    /// Create a filter loading `include`, or every source when empty, except `exclude`
+    pub fn new(include: Vec<FileSource>, exclude: Vec<FileSource>) -> Self {
+        Self { include, exclude }
+    }
+
+    /// Whether files from `source` are loaded
+    pub fn allows(&self, source: &FileSource) -> bool {
+        (self.include.is_empty() || self.include.contains(source)) && !self.exclude.contains(source)
+    }
+
+    /// The filter prompts are loaded with in this process, allowing every
+    /// source unless [`Self::set_global`] was called
+    pub fn global() -> Self {
+        GLOBAL_SOURCE_FILTER.get().cloned().unwrap_or_default()
+    }
+
+    /// Set the filter prompts are loaded with in this process
+    ///
+    /// Only the first call has an effect; returns whether this call set it.
+    pub fn set_global(filter: SourceFilter) -> bool {
+        GLOBAL_SOURCE_FILTER.set(filter).is_ok()
+    }
+}
+
 /// Represents a file with its metadata
 #[derive(Debug, Clone)]
 pub struct FileEntry {
@@ -90,12 +143,7 @@ impl FileEntry {
         // Extract the name from the path
         // For a path like /path/to/prompts/category/subcategory/test.md
         // We want to extract "category/subcategory/test"
-
-        // Get the stem by removing compound extensions
-        let stem = Self::remove_compound_extensions(&path);
-
-        // Extract name using proper path operations
-        let name = Self::extract_name_from_path(&path, stem);
+        let name = Self::name_from_path(&path);
 
         Self {
             name,
@@ -105,12 +153,24 @@ impl FileEntry {
         }
     }
 
+    /// The logical name of the file at `path`, as
+    /// [`from_path_and_content`](Self::from_path_and_content) names it
+    pub fn name_from_path(path: &Path) -> String {
+        Self::extract_name_from_path(path, Self::remove_compound_extensions(path))
+    }
+
     /// Remove compound extensions from a filename
+    ///
+    /// Extensions are matched without regard to case, so `Review.MD` is
+    /// named `Review`.
     fn remove_compound_extensions(path: &Path) -> &str {
         let filename = path
             .file_name()
             .and_then(|s| s.to_str())
             .unwrap_or_default();
+        // Names written with backslashes are one component on Unix
+        let filename = filename.rsplit('\\').next().unwrap_or(filename);
+        let lowercase = filename.to_ascii_lowercase();
 
         // List of supported compound extensions (sorted by length descending)
         let extensions = [
@@ -124,18 +184,23 @@ impl FileEntry {
 
         // Check for compound extensions first
         for ext in &extensions {
-            if let Some(stem) = filename.strip_suffix(ext) {
-                return stem;
+            if lowercase.ends_with(ext) {
+                return &filename[..filename.len() - ext.len()];
             }
         }
 
         // Fallback to file_stem behavior
-        path.file_stem()
-            .and_then(|s| s.to_str())
-            .unwrap_or_default()
+        match filename.rfind('.') {
+            Some(dot) if dot > 0 => &filename[..dot],
+            _ => filename,
+        }
     }
 
     /// Extract the name from a path using proper path operations
+    ///
+    /// Components are joined with `/` whichever separator the path used, so a
+    /// prompt in `prompts\review\code.md` is named `review/code` on every
+    /// platform.
     fn extract_name_from_path(path: &Path, stem: &str) -> String {
         let mut components: Vec<String> = Vec::new();
         let mut found_subdirectory = false;
@@ -143,11 +208,16 @@ impl FileEntry {
         // Convert path to components and find prompts or workflows directory
         for component in path.components() {
             if let std::path::Component::Normal(os_str) = component {
-                if let Some(s) = os_str.to_str() {
+                let Some(s) = os_str.to_str() else {
+                    continue;
+                };
+                for part in s.split('\\').filter(|part| !part.is_empty()) {
                     if found_subdirectory {
                         // Collect all components after prompts or workflows directory
-                        components.push(s.to_string());
-                    } else if s == "prompts" || s == "workflows" {
+                        components.push(part.to_string());
+                    } else if part.eq_ignore_ascii_case("prompts")
+                        || part.eq_ignore_ascii_case("workflows")
+                    {
                         found_subdirectory = true;
                     }
                 }
@@ -215,6 +285,15 @@ pub struct VirtualFileSystem {
     pub files: HashMap<String, FileEntry>,
     /// Track sources for each file
     pub file_sources: HashMap<String, FileSource>,
+    /// Policy every loaded file path is validated against
+    pub path_policy: PathPolicy,
+    /// Sources files are loaded from
+    pub source_filter: SourceFilter,
+    /// Verification of each directory loaded, none when unset
+    pub integrity: Option<PromptIntegrity>,
+    /// Read only the front matter of prompt files, for storage that loads
+    /// their templates when they are used
+    pub front_matter_only: bool,
 }
 
 impl VirtualFileSystem {
@@ -224,6 +303,37 @@ impl VirtualFileSystem {
             subdirectory: subdirectory.into(),
             files: HashMap::new(),
             file_sources: HashMap::new(),
+            path_policy: PathPolicy::global().clone(),
+            source_filter: SourceFilter::default(),
+            integrity: None,
+            front_matter_only: false,
+        }
+    }
+
+    /// Use a different path policy when loading directories
+    pub fn with_path_policy(mut self, path_policy: PathPolicy) -> Self {
+        self.path_policy = path_policy;
+        self
+    }
+
+    /// Only load files from the sources `source_filter` allows
+    pub fn with_source_filter(mut self, source_filter: SourceFilter) -> Self {
+        self.source_filter = source_filter;
+        self
+    }
+
+    /// Verify each directory loaded with `integrity` before reading its files
+    pub fn with_integrity(mut self, integrity: PromptIntegrity) -> Self {
+        self.integrity = Some(integrity);
+        self
+    }
+
+    /// Verify the `files` of directory `target_dir` when the file system
+    /// was given a [`PromptIntegrity`], failing when its policy requires
+    pub fn verify_directory(&self, target_dir: &Path, files: &[PathBuf]) -> Result<()> {
+        match &self.integrity {
+            Some(integrity) => integrity.check(target_dir, files),
+            None => Ok(()),
         }
     }
 
@@ -268,44 +378,80 @@ impl VirtualFileSystem {
             return Ok(());
         }
 
-        for path in walk_files_with_extensions(&target_dir, &["md", "mermaid"]) {
-            // Check file size before loading
-            match std::fs::metadata(&path) {
-                Ok(metadata) => {
-                    if metadata.len() > MAX_FILE_SIZE {
-                        tracing::warn!(
-                            "Skipping file '{}' - size {} bytes exceeds limit of {} bytes",
-                            path.display(),
-                            metadata.len(),
-                            MAX_FILE_SIZE
-                        );
-                        continue;
-                    }
+        let paths: Vec<PathBuf> =
+            walk_files_with_extensions(&target_dir, FILE_EXTENSIONS).collect();
+        self.verify_directory(&target_dir, &paths)?;
 
-                    // Validate path is within expected directory
-                    if !Self::is_path_safe(&path, &target_dir) {
-                        tracing::warn!(
-                            "Skipping file '{}' - path validation failed",
-                            path.display()
-                        );
-                        continue;
-                    }
+        for path in paths {
+            if let Some(file_entry) = self.read_file(path, &target_dir, source.clone()) {
+                self.add_file(file_entry);
+            }
+        }
 
-                    if let Ok(content) = std::fs::read_to_string(&path) {
-                        let file_entry =
-                            FileEntry::from_path_and_content(path, content, source.clone());
-                        self.add_file(file_entry);
-                    } else {
-                        tracing::warn!("Failed to read file '{}'", path.display());
-                    }
+        Ok(())
+    }
+
+    /// Read a file in `target_dir`, or `None` when it is too large, fails the
+    /// path policy or can't be read
+    pub fn read_file(
+        &self,
+        path: PathBuf,
+        target_dir: &Path,
+        source: FileSource,
+    ) -> Option<FileEntry> {
+        // Check file size before loading
+        match std::fs::metadata(&path) {
+            Ok(metadata) => {
+                if metadata.len() > MAX_FILE_SIZE {
+                    tracing::warn!(
+                        "Skipping file '{}' - size {} bytes exceeds limit of {} bytes",
+                        path.display(),
+                        metadata.len(),
+                        MAX_FILE_SIZE
+                    );
+                    return None;
+                }
+
+                // Validate path is within expected directory
+                if let Err(e) = validate_path(&path, target_dir, &self.path_policy) {
+                    tracing::warn!("Skipping file '{}' - {}", path.display(), e);
+                    return None;
                 }
-                Err(e) => {
-                    tracing::warn!("Failed to get metadata for '{}': {}", path.display(), e);
+
+                let content = if self.front_matter_only {
+                    crate::PromptLoader::read_front_matter(&path)
+                } else {
+                    std::fs::read_to_string(&path).map_err(Into::into)
+                };
+                if let Ok(content) = content {
+                    Some(FileEntry::from_path_and_content(path, content, source))
+                } else {
+                    tracing::warn!("Failed to read file '{}'", path.display());
+                    None
                 }
             }
+            Err(e) => {
+                tracing::warn!("Failed to get metadata for '{}': {}", path.display(), e);
+                None
+            }
         }
+    }
 
-        Ok(())
+    /// Load the files of a single source directory layer
+    ///
+    /// Only user and local files are on disk; builtin files are added with
+    /// [`Self::add_builtin`] and dynamic files with [`Self::add_file`].
+    pub fn load_source(&mut self, source: FileSource) -> Result<()> {
+        match source {
+            FileSource::User => {
+                if let Some(home) = home_dir() {
+                    self.load_directory(&home.join(".swissarmyhammer"), FileSource::User)?;
+                }
+                Ok(())
+            }
+            FileSource::Local => self.load_local_files(),
+            FileSource::Builtin | FileSource::Dynamic => Ok(()),
+        }
     }
 
     /// Load all files following the standard precedence
@@ -314,13 +460,17 @@ impl VirtualFileSystem {
         // Note: Builtin files are typically added via add_builtin method
 
         // Load user files from home directory
-        if let Some(home) = dirs::home_dir() {
-            let user_dir = home.join(".swissarmyhammer");
-            self.load_directory(&user_dir, FileSource::User)?;
+        if self.source_filter.allows(&FileSource::User) {
+            if let Some(home) = home_dir() {
+                let user_dir = home.join(".swissarmyhammer");
+                self.load_directory(&user_dir, FileSource::User)?;
+            }
         }
 
         // Load local files by walking up the directory tree
-        self.load_local_files()?;
+        if self.source_filter.allows(&FileSource::Local) {
+            self.load_local_files()?;
+        }
 
         Ok(())
     }
@@ -342,16 +492,32 @@ impl VirtualFileSystem {
 
     /// Get all directories that are being monitored
     pub fn get_directories(&self) -> Result<Vec<PathBuf>> {
+        Ok(self
+            .source_directories()?
+            .into_iter()
+            .map(|(directory, _)| directory)
+            .collect())
+    }
+
+    /// The existing directories files are loaded from with their source, from
+    /// least to most specific
+    pub fn source_directories(&self) -> Result<Vec<(PathBuf, FileSource)>> {
         let mut directories = Vec::new();
 
         // User directory
-        if let Some(home) = dirs::home_dir() {
-            let user_dir = home.join(".swissarmyhammer").join(&self.subdirectory);
-            if user_dir.exists() {
-                directories.push(user_dir);
+        if self.source_filter.allows(&FileSource::User) {
+            if let Some(home) = home_dir() {
+                let user_dir = home.join(".swissarmyhammer").join(&self.subdirectory);
+                if user_dir.exists() {
+                    directories.push((user_dir, FileSource::User));
+                }
             }
         }
 
+        if !self.source_filter.allows(&FileSource::Local) {
+            return Ok(directories);
+        }
+
         // Local directories
         let current_dir = std::env::current_dir()?;
         let swissarmyhammer_dirs = find_swissarmyhammer_dirs_upward(&current_dir, true);
@@ -360,28 +526,12 @@ impl VirtualFileSystem {
         for dir in swissarmyhammer_dirs {
             let subdir = dir.join(&self.subdirectory);
             if subdir.exists() && subdir.is_dir() {
-                directories.push(subdir);
+                directories.push((subdir, FileSource::Local));
             }
         }
 
         Ok(directories)
     }
-
-    /// Validate that a path is safe and within the expected directory
-    fn is_path_safe(path: &Path, base_dir: &Path) -> bool {
-        // Try to canonicalize both paths
-        match (path.canonicalize(), base_dir.canonicalize()) {
-            (Ok(canonical_path), Ok(canonical_base)) => {
-                // Ensure the path is within the base directory
-                canonical_path.starts_with(&canonical_base)
-            }
-            _ => {
-                // If we can't canonicalize, at least check for suspicious patterns
-                let path_str = path.to_string_lossy();
-                !path_str.contains("..") && !path_str.contains("~")
-            }
-        }
-    }
 }
 
 #[cfg(test)]
@@ -438,6 +588,23 @@ mod tests {
         assert_eq!(entry.name, "category/subcategory/test");
     }
 
+    #[test]
+    fn test_file_entry_windows_path_name() {
+        let entry = FileEntry::from_path_and_content(
+            PathBuf::from(r"C:\Users\jane\.swissarmyhammer\Prompts\review\Code.MD"),
+            "content".to_string(),
+            FileSource::User,
+        );
+        assert_eq!(entry.name, "review/Code");
+
+        let entry = FileEntry::from_path_and_content(
+            PathBuf::from("/path/to/prompts/Release.Md.Liquid"),
+            "content".to_string(),
+            FileSource::Local,
+        );
+        assert_eq!(entry.name, "Release");
+    }
+
     #[test]
     fn test_virtual_file_system_new() {
         let vfs = VirtualFileSystem::new("prompts");
@@ -479,6 +646,74 @@ mod tests {
         assert_eq!(file.source, FileSource::Local);
     }
 
+    #[test]
+    fn test_virtual_file_system_skips_paths_rejected_by_policy() {
+        use std::fs;
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let prompts_dir = temp_dir.path().join("prompts");
+        fs::create_dir_all(prompts_dir.join("drafts")).unwrap();
+        fs::write(prompts_dir.join("kept.md"), "kept").unwrap();
+        fs::write(prompts_dir.join("drafts/skipped.md"), "skipped").unwrap();
+
+        // A symlink to a file outside the prompts directory
+        let outside = TempDir::new().unwrap();
+        fs::write(outside.path().join("secret.md"), "secret").unwrap();
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(
+            outside.path().join("secret.md"),
+            prompts_dir.join("escape.md"),
+        )
+        .unwrap();
+
+        let mut vfs = VirtualFileSystem::new("prompts").with_path_policy(PathPolicy {
+            denylist: vec!["drafts".to_string()],
+            ..Default::default()
+        });
+        vfs.load_directory(temp_dir.path(), FileSource::Local)
+            .unwrap();
+
+        assert!(vfs.get("kept").is_some());
+        assert!(vfs.get("drafts/skipped").is_none());
+        assert!(vfs.get("escape").is_none());
+    }
+
+    #[test]
+    fn test_virtual_file_system_verifies_integrity() {
+        use crate::prompt_integrity::{IntegrityPolicy, CHECKSUMS_FILE};
+        use std::fs;
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let prompts_dir = temp_dir.path().join("prompts");
+        fs::create_dir_all(&prompts_dir).unwrap();
+        let prompt = prompts_dir.join("test.md");
+        fs::write(&prompt, "test content").unwrap();
+        let checksums =
+            crate::prompt_integrity::checksums_for(&prompts_dir, &[prompt.clone()]).unwrap();
+        fs::write(prompts_dir.join(CHECKSUMS_FILE), checksums).unwrap();
+
+        let integrity = PromptIntegrity::new(IntegrityPolicy::Require);
+        let mut vfs = VirtualFileSystem::new("prompts").with_integrity(integrity.clone());
+        vfs.load_directory(temp_dir.path(), FileSource::Local)
+            .unwrap();
+        assert!(vfs.get("test").is_some());
+
+        fs::write(&prompt, "tampered content").unwrap();
+        let mut vfs = VirtualFileSystem::new("prompts").with_integrity(integrity);
+        assert!(vfs
+            .load_directory(temp_dir.path(), FileSource::Local)
+            .is_err());
+        assert!(vfs.get("test").is_none());
+
+        // Without a verification the tampered file still loads
+        let mut vfs = VirtualFileSystem::new("prompts");
+        vfs.load_directory(temp_dir.path(), FileSource::Local)
+            .unwrap();
+        assert_eq!(vfs.get("test").unwrap().content, "tampered content");
+    }
+
     #[test]
     fn test_virtual_file_system_precedence() {
         let mut vfs = VirtualFileSystem::new("prompts");
@@ -524,4 +759,19 @@ mod tests {
         assert_eq!(vfs.get_source("test"), Some(&FileSource::Builtin));
         assert_eq!(vfs.get_source("nonexistent"), None);
     }
+
+    #[test]
+    fn test_source_filter() {
+        let everything = SourceFilter::default();
+        assert!(everything.allows(&FileSource::Builtin));
+        assert!(everything.allows(&FileSource::User));
+
+        let filter = SourceFilter::new(
+            vec![FileSource::Builtin, FileSource::Local],
+            vec![FileSource::Local],
+        );
+        assert!(filter.allows(&FileSource::Builtin));
+        assert!(!filter.allows(&FileSource::User));
+        assert!(!filter.allows(&FileSource::Local));
+    }
 }
@@ -1,21 +1,128 @@
-//! File watching functionality for prompt directories
+//! File watching functionality for prompt, workflow and memo directories
 //!
 //! This module provides a unified file watching system that can monitor
-//! prompt directories for changes and trigger appropriate reload actions.
+//! prompt, workflow and memo directories for changes and trigger appropriate
+//! reload actions.
+//!
+//! Editors and `git checkout` touch many files at once, often with several
+//! events per file. Events are collected until the directories have been
+//! quiet for [`FileWatcherConfig::debounce_millis`], and the changed paths
+//! are then handed to the callback in one batch per kind of content. A
+//! rename reports both the old and the new path, so the callback can tell a
+//! removal from an addition by whether the path still exists.
 
 use crate::common::file_types::is_any_prompt_file;
 #[cfg(not(test))]
 use crate::common::mcp_errors::ToSwissArmyHammerError;
+use crate::cost::{default_cost_dir, BUDGET_EVENTS_FILE};
+use crate::memoranda::default_memos_dir;
+use crate::workflow::WorkflowResolver;
 use crate::{PromptResolver, Result};
 #[cfg(not(test))]
 use notify::{
-    event::{Event, EventKind},
+    event::{Event, EventKind, ModifyKind},
     RecommendedWatcher, RecursiveMode, Watcher,
 };
 #[cfg(not(test))]
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+#[cfg(not(test))]
 use tokio::sync::mpsc;
 
-/// File watcher for monitoring prompt directories
+/// Kind of content kept in a watched directory
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WatchedContent {
+    /// Prompt templates
+    Prompts,
+    /// Workflow definitions
+    Workflows,
+    /// Memos
+    Memos,
+    /// Cost budget warnings and refusals
+    BudgetEvents,
+}
+
+/// A directory watched for changes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatchedDirectory {
+    /// Path of the directory
+    pub path: PathBuf,
+    /// Kind of content the directory holds
+    pub content: WatchedContent,
+}
+
+/// The existing prompt, workflow, memo and cost directories
+pub fn watched_directories() -> Result<Vec<WatchedDirectory>> {
+    let prompts = PromptResolver::new().get_prompt_directories()?;
+    let workflows = WorkflowResolver::new().get_workflow_directories()?;
+    let memos = Some(default_memos_dir()?).filter(|dir| dir.is_dir());
+    let cost = Some(default_cost_dir()?).filter(|dir| dir.is_dir());
+
+    let directories = prompts
+        .into_iter()
+        .map(|path| (path, WatchedContent::Prompts))
+        .chain(
+            workflows
+                .into_iter()
+                .map(|path| (path, WatchedContent::Workflows)),
+        )
+        .chain(memos.map(|path| (path, WatchedContent::Memos)))
+        .chain(cost.map(|path| (path, WatchedContent::BudgetEvents)))
+        .map(|(path, content)| WatchedDirectory { path, content })
+        .collect();
+    Ok(directories)
+}
+
+/// Group changed files by the kind of content of the directory they are in
+///
+/// Files that are not prompt, workflow or memo files (or, in the cost
+/// directory, the budget events file), or that are outside every watched
+/// directory, are left out. Directories in prompt directories are kept, since
+/// renaming or removing one changes the prompts below it; a removed path
+/// without an extension is taken to be a directory. A file in nested watched
+/// directories belongs to the innermost one.
+pub fn classify_changes(
+    paths: &[PathBuf],
+    directories: &[WatchedDirectory],
+) -> Vec<(WatchedContent, Vec<PathBuf>)> {
+    let mut changes: Vec<(WatchedContent, Vec<PathBuf>)> = Vec::new();
+    for path in paths {
+        let Some(content) = watched_content_of(path, directories) else {
+            continue;
+        };
+        let relevant = match content {
+            WatchedContent::BudgetEvents => {
+                path.file_name().and_then(|name| name.to_str()) == Some(BUDGET_EVENTS_FILE)
+            }
+            WatchedContent::Prompts => is_any_prompt_file(path) || may_be_directory(path),
+            _ => is_any_prompt_file(path),
+        };
+        if !relevant {
+            continue;
+        }
+        match changes.iter_mut().find(|(kind, _)| *kind == content) {
+            Some((_, group)) => group.push(path.clone()),
+            None => changes.push((content, vec![path.clone()])),
+        }
+    }
+    changes
+}
+
+/// Whether a path is a directory, or was removed and had no extension
+fn may_be_directory(path: &Path) -> bool {
+    path.is_dir() || (!path.exists() && path.extension().is_none())
+}
+
+/// Kind of content of the innermost watched directory containing a path
+fn watched_content_of(path: &Path, directories: &[WatchedDirectory]) -> Option<WatchedContent> {
+    directories
+        .iter()
+        .filter(|directory| path.starts_with(&directory.path))
+        .max_by_key(|directory| directory.path.components().count())
+        .map(|directory| directory.content)
+}
+
+/// File watcher for monitoring prompt, workflow and memo directories
 pub struct FileWatcher {
     /// Handle to the background watcher task
     watcher_handle: Option<tokio::task::JoinHandle<()>>,
@@ -35,6 +142,8 @@ pub struct FileWatcherConfig {
     pub shutdown_timeout_secs: u64,
     /// Timeout for abort cleanup after forced shutdown
     pub abort_cleanup_timeout_millis: u64,
+    /// Quiet time after the last event before the changes are applied together
+    pub debounce_millis: u64,
 }
 
 impl Default for FileWatcherConfig {
@@ -44,15 +153,17 @@ impl Default for FileWatcherConfig {
             recursive: true,
             shutdown_timeout_secs: 2,
             abort_cleanup_timeout_millis: 100,
+            debounce_millis: 100,
         }
     }
 }
 
 /// Callback trait for handling file system events
 pub trait FileWatcherCallback: Send + Sync + 'static {
-    /// Called when a relevant file change is detected
+    /// Called when relevant files holding one kind of content change
     fn on_file_changed(
         &self,
+        content: WatchedContent,
         paths: Vec<std::path::PathBuf>,
     ) -> impl std::future::Future<Output = Result<()>> + Send;
 
@@ -70,7 +181,7 @@ impl FileWatcher {
         }
     }
 
-    /// Start watching prompt directories for changes
+    /// Start watching prompt, workflow and memo directories for changes
     pub async fn start_watching<C>(&mut self, callback: C) -> Result<()>
     where
         C: FileWatcherCallback + Clone,
@@ -94,21 +205,20 @@ impl FileWatcher {
         // Store the config
         self.config = config;
 
-        tracing::info!("Starting file watching for prompt directories");
+        tracing::info!("Starting file watching for prompt, workflow and memo directories");
 
-        // Get the directories to watch using the same logic as PromptResolver
-        let resolver = PromptResolver::new();
-        let watch_paths = resolver.get_prompt_directories()?;
+        // Get the directories to watch using the same logic as the resolvers
+        let directories = watched_directories()?;
 
         tracing::info!(
             "Found {} directories to watch: {:?}",
-            watch_paths.len(),
-            watch_paths
+            directories.len(),
+            directories
         );
 
-        // The resolver already returns only existing paths
-        if watch_paths.is_empty() {
-            tracing::warn!("No prompt directories found to watch");
+        // The resolvers already return only existing paths
+        if directories.is_empty() {
+            tracing::warn!("No prompt, workflow or memo directories found to watch");
             return Ok(());
         }
 
@@ -153,49 +263,40 @@ impl FileWatcher {
                 RecursiveMode::NonRecursive
             };
 
-            for path in &watch_paths {
+            for directory in &directories {
+                let path = &directory.path;
                 watcher
                     .watch(path, recursive_mode)
                     .to_swiss_error_with_context(&format!("Failed to watch directory {path:?}"))?;
-                tracing::info!("Watching directory: {:?}", path);
+                tracing::info!("Watching {:?} directory: {:?}", directory.content, path);
             }
 
             // Spawn the event handler task
+            let debounce = std::time::Duration::from_millis(self.config.debounce_millis);
             let handle = tokio::spawn(async move {
                 // Keep the watcher alive for the duration of this task
                 // The watcher must be moved into the task to prevent it from being dropped
                 let _watcher = watcher;
+                // Paths changed since the last batch was applied
+                let mut pending: BTreeSet<PathBuf> = BTreeSet::new();
 
                 loop {
                     tokio::select! {
-                            // Handle file system events
-                            event = rx.recv() => {
-                                match event {
-                                    Some(event) => {
-                                        tracing::debug!("📁 File system event: {:?}", event);
-
-                                        // Check if this is a relevant event
-                                        match event.kind {
-                                            EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_) => {
-                                                // Check if it's a prompt file
-                                                let relevant_paths: Vec<std::path::PathBuf> = event
-                                                    .paths
-                                                .iter()
-                                                .filter(|p| is_any_prompt_file(p))
-                                                .cloned()
-                                                .collect();
-
-                                            if !relevant_paths.is_empty() {
-                                                tracing::info!("📄 Prompt file changed: {:?}", relevant_paths);
-
-                                                // Notify callback about the change
-                                                if let Err(e) = _callback.on_file_changed(relevant_paths).await {
-                                                    tracing::error!("❌ File watcher callback failed: {}", e);
-                                                    _callback.on_error(format!("Callback failed: {e}")).await;
-                                                }
-                                            } else {
-                                                tracing::debug!("🚫 Ignoring non-prompt file: {:?}", event.paths);
-                                            }
+                        // Handle file system events
+                        event = rx.recv() => {
+                            match event {
+                                Some(event) => {
+                                    tracing::debug!("📁 File system event: {:?}", event);
+
+                                    // Check if this is a relevant event; renames are
+                                    // modifications naming the old and new paths
+                                    match event.kind {
+                                        EventKind::Modify(ModifyKind::Metadata(_)) => {
+                                            tracing::debug!("🚫 Ignoring metadata change: {:?}", event.paths);
+                                        }
+                                        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_) => {
+                                            crate::directory_utils::invalidate_cached_indexes(&event.paths);
+                                            pending.extend(event.paths);
                                         }
                                         _ => {
                                             tracing::debug!("🚫 Ignoring event type: {:?}", event.kind);
@@ -209,6 +310,24 @@ impl FileWatcher {
                                 }
                             }
                         }
+                        // Apply the pending changes once no event came for the debounce time
+                        _ = tokio::time::sleep(debounce), if !pending.is_empty() => {
+                            let paths: Vec<PathBuf> = std::mem::take(&mut pending).into_iter().collect();
+                            let changes = classify_changes(&paths, &directories);
+                            if changes.is_empty() {
+                                tracing::debug!("🚫 Ignoring unwatched files: {:?}", paths);
+                            }
+
+                            for (content, paths) in changes {
+                                tracing::info!("📄 {:?} files changed: {:?}", content, paths);
+
+                                // Notify callback about the change
+                                if let Err(e) = _callback.on_file_changed(content, paths).await {
+                                    tracing::error!("❌ File watcher callback failed: {}", e);
+                                    _callback.on_error(format!("Callback failed: {e}")).await;
+                                }
+                            }
+                        }
                         // Handle shutdown signal
                         _ = &mut shutdown_rx => {
                             tracing::debug!("📁 Received shutdown signal, stopping file watcher");
@@ -319,7 +438,11 @@ mod tests {
     }
 
     impl FileWatcherCallback for TestCallback {
-        async fn on_file_changed(&self, paths: Vec<std::path::PathBuf>) -> Result<()> {
+        async fn on_file_changed(
+            &self,
+            _content: WatchedContent,
+            paths: Vec<std::path::PathBuf>,
+        ) -> Result<()> {
             self.changes.lock().await.push(paths);
             Ok(())
         }
@@ -384,11 +507,78 @@ mod tests {
         assert!(!is_any_prompt_file(Path::new("test")));
     }
 
+    #[test]
+    fn test_classify_changes() {
+        let root = PathBuf::from("/project/.swissarmyhammer");
+        let directories = vec![
+            WatchedDirectory {
+                path: root.join("prompts"),
+                content: WatchedContent::Prompts,
+            },
+            WatchedDirectory {
+                path: root.join("workflows"),
+                content: WatchedContent::Workflows,
+            },
+            WatchedDirectory {
+                path: root.join("prompts").join("memos"),
+                content: WatchedContent::Memos,
+            },
+            WatchedDirectory {
+                path: root.join("cost"),
+                content: WatchedContent::BudgetEvents,
+            },
+        ];
+
+        let changes = classify_changes(
+            &[
+                root.join("workflows").join("deploy.md"),
+                root.join("prompts").join("review.md"),
+                root.join("prompts").join("notes.txt"),
+                root.join("prompts").join("drafts"),
+                root.join("prompts").join("memos").join("plan.md"),
+                root.join("workflows").join("release.md"),
+                root.join("cost").join("ledger.jsonl"),
+                root.join("cost").join("budget_events.jsonl"),
+                PathBuf::from("/elsewhere/other.md"),
+            ],
+            &directories,
+        );
+
+        assert_eq!(
+            changes,
+            vec![
+                (
+                    WatchedContent::Workflows,
+                    vec![
+                        root.join("workflows").join("deploy.md"),
+                        root.join("workflows").join("release.md"),
+                    ]
+                ),
+                (
+                    WatchedContent::Prompts,
+                    vec![
+                        root.join("prompts").join("review.md"),
+                        root.join("prompts").join("drafts"),
+                    ]
+                ),
+                (
+                    WatchedContent::Memos,
+                    vec![root.join("prompts").join("memos").join("plan.md")]
+                ),
+                (
+                    WatchedContent::BudgetEvents,
+                    vec![root.join("cost").join("budget_events.jsonl")]
+                ),
+            ]
+        );
+    }
+
     #[test]
     fn test_file_watcher_config_default() {
         let config = FileWatcherConfig::default();
         assert_eq!(config.channel_buffer_size, 100);
         assert!(config.recursive);
+        assert_eq!(config.debounce_millis, 100);
     }
 
     #[test]
@@ -429,6 +619,7 @@ mod tests {
             recursive: false,
             shutdown_timeout_secs: 5,
             abort_cleanup_timeout_millis: 1000,
+            debounce_millis: 50,
         };
 
         // Start with custom config - should now succeed
@@ -544,7 +735,11 @@ mod tests {
     }
 
     impl FileWatcherCallback for ErrorCallback {
-        async fn on_file_changed(&self, paths: Vec<std::path::PathBuf>) -> Result<()> {
+        async fn on_file_changed(
+            &self,
+            _content: WatchedContent,
+            paths: Vec<std::path::PathBuf>,
+        ) -> Result<()> {
             self.calls.lock().await.push(paths.clone());
             // Return error to test error handling
             Err(crate::SwissArmyHammerError::Other("Test error".to_string()))
@@ -6,8 +6,11 @@
 
 use crate::common::error_context::IoResultExt;
 use crate::error::{Result, SwissArmyHammerError};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt;
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
 
 /// File permissions for secure file creation
 #[derive(Debug, Clone, Copy)]
@@ -86,6 +89,15 @@ pub trait FileSystem: Send + Sync {
     /// Remove a file
     fn remove_file(&self, path: &Path) -> Result<()>;
 
+    /// Move a file, replacing the destination if it exists
+    fn rename(&self, from: &Path, to: &Path) -> Result<()>;
+
+    /// Remove a directory and everything in it
+    fn remove_dir_all(&self, path: &Path) -> Result<()>;
+
+    /// Last modification time of a file, if known
+    fn modified(&self, path: &Path) -> Option<SystemTime>;
+
     /// Set file permissions
     fn set_permissions(&self, path: &Path, permissions: FilePermissions) -> Result<()>;
 }
@@ -178,7 +190,8 @@ impl FileSystem for StdFileSystem {
     }
 
     fn create_dir_all(&self, path: &Path) -> Result<()> {
-        self.create_dir_all_with_permissions(path, FilePermissions::Standard)
+        // Directories keep the default mode; the file modes would make them unsearchable
+        std::fs::create_dir_all(path).with_io_context(path, "Failed to create directory")
     }
 
     fn create_dir_all_with_permissions(
@@ -209,6 +222,18 @@ impl FileSystem for StdFileSystem {
         std::fs::remove_file(path).with_io_context(path, "Failed to remove file")
     }
 
+    fn rename(&self, from: &Path, to: &Path) -> Result<()> {
+        std::fs::rename(from, to).with_io_context(from, "Failed to rename file")
+    }
+
+    fn remove_dir_all(&self, path: &Path) -> Result<()> {
+        std::fs::remove_dir_all(path).with_io_context(path, "Failed to remove directory")
+    }
+
+    fn modified(&self, path: &Path) -> Option<SystemTime> {
+        std::fs::metadata(path).and_then(|m| m.modified()).ok()
+    }
+
     fn set_permissions(&self, path: &Path, permissions: FilePermissions) -> Result<()> {
         #[cfg(unix)]
         {
@@ -247,6 +272,298 @@ impl FileSystem for StdFileSystem {
     }
 }
 
+/// A change recorded by an [`OverlayFileSystem`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileChange {
+    /// A file was written
+    Write {
+        /// File written
+        path: PathBuf,
+        /// Content written
+        content: String,
+    },
+    /// A file was removed
+    Remove(PathBuf),
+    /// A file was moved
+    Rename {
+        /// Original location
+        from: PathBuf,
+        /// New location
+        to: PathBuf,
+    },
+    /// A directory was created
+    CreateDir(PathBuf),
+    /// A directory was removed with everything in it
+    RemoveDir(PathBuf),
+}
+
+impl fmt::Display for FileChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FileChange::Write { path, content } => {
+                write!(f, "write {} ({} bytes)", path.display(), content.len())
+            }
+            FileChange::Remove(path) => write!(f, "remove {}", path.display()),
+            FileChange::Rename { from, to } => {
+                write!(f, "move {} to {}", from.display(), to.display())
+            }
+            FileChange::CreateDir(path) => write!(f, "create directory {}", path.display()),
+            FileChange::RemoveDir(path) => write!(f, "remove directory {}", path.display()),
+        }
+    }
+}
+
+/// File content held by an [`OverlayFileSystem`]
+#[derive(Debug, Clone)]
+struct OverlayFile {
+    content: String,
+    modified: SystemTime,
+}
+
+/// Changes an [`OverlayFileSystem`] holds in memory
+#[derive(Debug, Default)]
+struct OverlayLayer {
+    /// Files written through the overlay, or `None` for files removed through it
+    files: HashMap<PathBuf, Option<OverlayFile>>,
+    /// Directories created through the overlay
+    dirs: HashSet<PathBuf>,
+    /// Directories removed through the overlay, hiding their base contents
+    removed_dirs: Vec<PathBuf>,
+    /// Every change, in the order it was made
+    changes: Vec<FileChange>,
+}
+
+impl OverlayLayer {
+    fn hides(&self, path: &Path) -> bool {
+        self.removed_dirs.iter().any(|dir| path.starts_with(dir))
+    }
+
+    fn add_dirs(&mut self, path: &Path) {
+        for dir in path.ancestors().filter(|dir| !dir.as_os_str().is_empty()) {
+            self.dirs.insert(dir.to_path_buf());
+        }
+    }
+
+    fn insert_file(&mut self, path: &Path, content: String) {
+        if let Some(parent) = path.parent() {
+            self.add_dirs(parent);
+        }
+        let file = OverlayFile {
+            content,
+            modified: SystemTime::now(),
+        };
+        self.files.insert(path.to_path_buf(), Some(file));
+    }
+}
+
+/// File system that records writes in memory instead of applying them
+///
+/// Reads see the base file system with the recorded changes applied, so code
+/// running on the overlay behaves as if its writes went through, while the
+/// base is never modified. [`OverlayFileSystem::changes`] lists what would
+/// have been changed, which is what dry runs report. Storage validates paths
+/// against its root directory, so the root must exist in the base.
+pub struct OverlayFileSystem {
+    base: Arc<dyn FileSystem>,
+    layer: Mutex<OverlayLayer>,
+}
+
+impl OverlayFileSystem {
+    /// Create an overlay on top of another file system
+    pub fn new(base: Arc<dyn FileSystem>) -> Self {
+        Self {
+            base,
+            layer: Mutex::new(OverlayLayer::default()),
+        }
+    }
+
+    /// Changes made through the overlay, in order
+    pub fn changes(&self) -> Vec<FileChange> {
+        self.layer().changes.clone()
+    }
+
+    fn layer(&self) -> std::sync::MutexGuard<'_, OverlayLayer> {
+        self.layer
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn file(&self, layer: &OverlayLayer, path: &Path) -> Option<String> {
+        match layer.files.get(path) {
+            Some(file) => file.as_ref().map(|file| file.content.clone()),
+            None if layer.hides(path) => None,
+            None => self.base.read_to_string(path).ok(),
+        }
+    }
+
+    fn is_file_in(&self, layer: &OverlayLayer, path: &Path) -> bool {
+        match layer.files.get(path) {
+            Some(file) => file.is_some(),
+            None => !layer.hides(path) && self.base.is_file(path),
+        }
+    }
+
+    fn is_dir_in(&self, layer: &OverlayLayer, path: &Path) -> bool {
+        layer.dirs.contains(path) || (!layer.hides(path) && self.base.is_dir(path))
+    }
+}
+
+impl Default for OverlayFileSystem {
+    /// An overlay on the real file system
+    fn default() -> Self {
+        Self::new(Arc::new(StdFileSystem))
+    }
+}
+
+fn not_found(path: &Path) -> SwissArmyHammerError {
+    SwissArmyHammerError::Io(std::io::Error::new(
+        std::io::ErrorKind::NotFound,
+        format!("File not found: {}", path.display()),
+    ))
+}
+
+impl FileSystem for OverlayFileSystem {
+    fn read_to_string(&self, path: &Path) -> Result<String> {
+        let layer = self.layer();
+        match layer.files.get(path) {
+            Some(Some(file)) => Ok(file.content.clone()),
+            Some(None) => Err(not_found(path)),
+            None if layer.hides(path) => Err(not_found(path)),
+            None => self.base.read_to_string(path),
+        }
+    }
+
+    fn write(&self, path: &Path, content: &str) -> Result<()> {
+        let mut layer = self.layer();
+        layer.insert_file(path, content.to_string());
+        layer.changes.push(FileChange::Write {
+            path: path.to_path_buf(),
+            content: content.to_string(),
+        });
+        Ok(())
+    }
+
+    fn write_with_permissions(
+        &self,
+        path: &Path,
+        content: &str,
+        _permissions: FilePermissions,
+    ) -> Result<()> {
+        self.write(path, content)
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        let layer = self.layer();
+        self.is_file_in(&layer, path) || self.is_dir_in(&layer, path)
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        self.is_file_in(&self.layer(), path)
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        self.is_dir_in(&self.layer(), path)
+    }
+
+    fn create_dir_all(&self, path: &Path) -> Result<()> {
+        let mut layer = self.layer();
+        if !self.is_dir_in(&layer, path) {
+            layer
+                .changes
+                .push(FileChange::CreateDir(path.to_path_buf()));
+        }
+        layer.add_dirs(path);
+        Ok(())
+    }
+
+    fn create_dir_all_with_permissions(
+        &self,
+        path: &Path,
+        _permissions: FilePermissions,
+    ) -> Result<()> {
+        self.create_dir_all(path)
+    }
+
+    fn read_dir(&self, path: &Path) -> Result<Vec<PathBuf>> {
+        let layer = self.layer();
+        if !self.is_dir_in(&layer, path) {
+            return Err(not_found(path));
+        }
+
+        let mut entries = BTreeSet::new();
+        if !layer.hides(path) && self.base.is_dir(path) {
+            entries.extend(self.base.read_dir(path)?.into_iter().filter(|entry| {
+                !layer.hides(entry) && !matches!(layer.files.get(entry), Some(None))
+            }));
+        }
+        let written = layer
+            .files
+            .iter()
+            .filter(|(_, file)| file.is_some())
+            .map(|(file, _)| file);
+        for entry in written.chain(layer.dirs.iter()) {
+            if entry.parent() == Some(path) {
+                entries.insert(entry.clone());
+            }
+        }
+        Ok(entries.into_iter().collect())
+    }
+
+    fn remove_file(&self, path: &Path) -> Result<()> {
+        let mut layer = self.layer();
+        if !self.is_file_in(&layer, path) {
+            return Err(not_found(path));
+        }
+        layer.files.insert(path.to_path_buf(), None);
+        layer.changes.push(FileChange::Remove(path.to_path_buf()));
+        Ok(())
+    }
+
+    fn rename(&self, from: &Path, to: &Path) -> Result<()> {
+        let mut layer = self.layer();
+        let content = self.file(&layer, from).ok_or_else(|| not_found(from))?;
+        layer.files.insert(from.to_path_buf(), None);
+        layer.insert_file(to, content);
+        layer.changes.push(FileChange::Rename {
+            from: from.to_path_buf(),
+            to: to.to_path_buf(),
+        });
+        Ok(())
+    }
+
+    fn remove_dir_all(&self, path: &Path) -> Result<()> {
+        let mut layer = self.layer();
+        if !self.is_dir_in(&layer, path) {
+            return Err(not_found(path));
+        }
+        layer.files.retain(|file, _| !file.starts_with(path));
+        layer.dirs.retain(|dir| !dir.starts_with(path));
+        layer.removed_dirs.push(path.to_path_buf());
+        layer
+            .changes
+            .push(FileChange::RemoveDir(path.to_path_buf()));
+        Ok(())
+    }
+
+    fn modified(&self, path: &Path) -> Option<SystemTime> {
+        let layer = self.layer();
+        match layer.files.get(path) {
+            Some(file) => file.as_ref().map(|file| file.modified),
+            None if layer.hides(path) => None,
+            None => self.base.modified(path),
+        }
+    }
+
+    fn set_permissions(&self, path: &Path, _permissions: FilePermissions) -> Result<()> {
+        // Permissions are not recorded; the file only has to exist
+        if self.exists(path) {
+            Ok(())
+        } else {
+            Err(not_found(path))
+        }
+    }
+}
+
 /// File system utility with dependency injection support
 pub struct FileSystemUtils {
     fs: Arc<dyn FileSystem>,
@@ -365,13 +682,11 @@ impl Default for FileSystemUtils {
 /// Test utilities and mock implementations for file system operations
 pub mod tests {
     use super::*;
-    use std::collections::HashMap;
-    use std::sync::Mutex;
 
     /// Mock file system for testing
     pub struct MockFileSystem {
         files: Mutex<HashMap<PathBuf, String>>,
-        dirs: Mutex<std::collections::HashSet<PathBuf>>,
+        dirs: Mutex<HashSet<PathBuf>>,
     }
 
     impl Default for MockFileSystem {
@@ -385,7 +700,7 @@ pub mod tests {
         pub fn new() -> Self {
             Self {
                 files: Mutex::new(HashMap::new()),
-                dirs: Mutex::new(std::collections::HashSet::new()),
+                dirs: Mutex::new(HashSet::new()),
             }
         }
     }
@@ -460,6 +775,30 @@ pub mod tests {
             Ok(())
         }
 
+        fn rename(&self, from: &Path, to: &Path) -> Result<()> {
+            let content = self.read_to_string(from)?;
+            let mut files = self.files.lock().unwrap();
+            files.remove(from);
+            files.insert(to.to_path_buf(), content);
+            Ok(())
+        }
+
+        fn remove_dir_all(&self, path: &Path) -> Result<()> {
+            self.files
+                .lock()
+                .unwrap()
+                .retain(|file, _| !file.starts_with(path));
+            self.dirs
+                .lock()
+                .unwrap()
+                .retain(|dir| !dir.starts_with(path));
+            Ok(())
+        }
+
+        fn modified(&self, _path: &Path) -> Option<SystemTime> {
+            None
+        }
+
         fn set_permissions(&self, _path: &Path, _permissions: FilePermissions) -> Result<()> {
             // Mock implementation does nothing - permissions are not stored
             Ok(())
@@ -543,6 +882,68 @@ pub mod tests {
         assert_eq!(content, read_content);
     }
 
+    #[test]
+    fn test_overlay_records_changes_without_touching_base() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(root.join("notes.md"), "on disk").unwrap();
+        std::fs::create_dir_all(root.join("runs/1")).unwrap();
+        std::fs::write(root.join("runs/1/run.json"), "{}").unwrap();
+
+        let overlay = OverlayFileSystem::default();
+        assert_eq!(
+            overlay.read_to_string(&root.join("notes.md")).unwrap(),
+            "on disk"
+        );
+
+        overlay.write(&root.join("notes.md"), "changed").unwrap();
+        overlay.write(&root.join("new/todo.md"), "todo").unwrap();
+        overlay
+            .rename(&root.join("new/todo.md"), &root.join("done.md"))
+            .unwrap();
+        overlay.remove_dir_all(&root.join("runs")).unwrap();
+
+        assert_eq!(
+            overlay.read_to_string(&root.join("notes.md")).unwrap(),
+            "changed"
+        );
+        assert_eq!(
+            overlay.read_to_string(&root.join("done.md")).unwrap(),
+            "todo"
+        );
+        assert!(!overlay.exists(&root.join("new/todo.md")));
+        assert!(overlay.is_dir(&root.join("new")));
+        assert!(!overlay.exists(&root.join("runs/1/run.json")));
+        assert_eq!(
+            overlay.read_dir(root).unwrap(),
+            vec![
+                root.join("done.md"),
+                root.join("new"),
+                root.join("notes.md")
+            ]
+        );
+
+        overlay.remove_file(&root.join("done.md")).unwrap();
+        assert!(overlay.remove_file(&root.join("done.md")).is_err());
+        assert!(overlay.modified(&root.join("notes.md")).is_some());
+
+        // Nothing reached the disk
+        assert_eq!(
+            std::fs::read_to_string(root.join("notes.md")).unwrap(),
+            "on disk"
+        );
+        assert!(root.join("runs/1/run.json").exists());
+        assert!(!root.join("new").exists());
+
+        let changes = overlay.changes();
+        assert_eq!(changes.len(), 5);
+        assert_eq!(
+            changes[3].to_string(),
+            format!("remove directory {}", root.join("runs").display())
+        );
+        assert_eq!(changes[4], FileChange::Remove(root.join("done.md")));
+    }
+
     #[cfg(unix)]
     #[test]
     fn test_file_permissions_mapping() {
@@ -1,17 +1,324 @@
 //! Git operations for issue management
 //!
 //! This module provides git integration for managing issue branches,
-//! including creating work branches, switching branches, and merging
-//! completed work back to the main branch.
-
+//! including creating work branches, switching branches, syncing them with
+//! the main branch, and merging completed work back to the main branch.
+//!
+//! Operations run in an explicit repository. Issues that belong to another
+//! repository name it with a `repo:` front matter key, looked up in the
+//! repositories configured by `SWISSARMYHAMMER_REPOS`, and
+//! [`GitOperations::for_issue`] switches to it.
+
+use crate::config::Config;
+use crate::issues::{Issue, IssueBranchNaming, IssueMetadata};
+use crate::security::{validate_path, PathPolicy};
 use crate::{Result, SwissArmyHammerError};
-use std::path::{Path, PathBuf};
-use std::process::Command;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Component, Path, PathBuf};
+use std::process::{Command, Output};
+use std::str::FromStr;
+
+/// How [`GitOperations::sync_issue_branch`] brings the main branch into an
+/// issue branch
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SyncStrategy {
+    /// Merge the main branch into the issue branch
+    #[default]
+    Merge,
+    /// Rebase the issue branch onto the main branch
+    Rebase,
+}
+
+impl fmt::Display for SyncStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SyncStrategy::Merge => write!(f, "merge"),
+            SyncStrategy::Rebase => write!(f, "rebase"),
+        }
+    }
+}
+
+impl FromStr for SyncStrategy {
+    type Err = SwissArmyHammerError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "merge" => Ok(SyncStrategy::Merge),
+            "rebase" => Ok(SyncStrategy::Rebase),
+            _ => Err(SwissArmyHammerError::Other(format!(
+                "Invalid sync strategy '{s}': expected merge or rebase"
+            ))),
+        }
+    }
+}
+
+/// How the two branches changed a conflicted file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictKind {
+    /// Both branches changed the file
+    BothModified,
+    /// Both branches added a file at this path
+    BothAdded,
+    /// Both branches deleted the file
+    BothDeleted,
+    /// The main branch deleted the file and the issue branch changed it
+    DeletedOnBase,
+    /// The issue branch deleted the file and the main branch changed it
+    DeletedOnIssue,
+}
+
+impl ConflictKind {
+    /// Parse the unmerged `XY` code of `git status --porcelain`
+    ///
+    /// "Us" is the issue branch when merging and the main branch when
+    /// rebasing, since a rebase replays the issue commits onto main.
+    fn from_status(code: &str, strategy: SyncStrategy) -> Option<Self> {
+        let (deleted_by_us, deleted_by_them) = match strategy {
+            SyncStrategy::Merge => (ConflictKind::DeletedOnIssue, ConflictKind::DeletedOnBase),
+            SyncStrategy::Rebase => (ConflictKind::DeletedOnBase, ConflictKind::DeletedOnIssue),
+        };
+        match code {
+            "UU" | "AU" | "UA" => Some(ConflictKind::BothModified),
+            "AA" => Some(ConflictKind::BothAdded),
+            "DD" => Some(ConflictKind::BothDeleted),
+            "DU" => Some(deleted_by_us),
+            "UD" => Some(deleted_by_them),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ConflictKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConflictKind::BothModified => write!(f, "changed on both branches"),
+            ConflictKind::BothAdded => write!(f, "added on both branches"),
+            ConflictKind::BothDeleted => write!(f, "deleted on both branches"),
+            ConflictKind::DeletedOnBase => write!(f, "deleted on the main branch"),
+            ConflictKind::DeletedOnIssue => write!(f, "deleted on the issue branch"),
+        }
+    }
+}
+
+/// A file that could not be synced automatically
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncConflict {
+    /// Path relative to the repository root
+    pub path: String,
+    /// How the two branches changed the file
+    pub kind: ConflictKind,
+    /// Whether the file is an issue's markdown
+    pub is_issue: bool,
+    /// How to resolve the conflict
+    pub resolution: String,
+}
+
+impl SyncConflict {
+    fn new(path: String, kind: ConflictKind, base: &str) -> Self {
+        let is_issue = is_issue_file(Path::new(&path));
+        let resolution = match (kind, is_issue) {
+            (ConflictKind::BothModified, true) => format!(
+                "Both branches edited this issue. Keep the edits from {base} and add this branch's notes after them"
+            ),
+            (ConflictKind::BothAdded, true) => {
+                "Both branches created an issue with this name. Keep one and create the other under a new name".to_string()
+            }
+            (ConflictKind::DeletedOnBase, true) => format!(
+                "{base} removed this issue, usually by completing it, while this branch edited it. Confirm the removal with `git rm` unless this branch still needs the issue"
+            ),
+            (ConflictKind::DeletedOnIssue, true) => format!(
+                "This branch removed this issue, usually by completing it, while {base} edited it. Copy the edits from {base} into the completed issue, then `git rm` this path"
+            ),
+            (ConflictKind::BothDeleted, _) => {
+                "Both branches removed this file. Confirm with `git rm`".to_string()
+            }
+            (ConflictKind::BothModified, false) => {
+                "Edit the file to combine both changes, then `git add` it".to_string()
+            }
+            (ConflictKind::BothAdded, false) => {
+                "Both branches added this file. Combine the two versions, then `git add` it".to_string()
+            }
+            (ConflictKind::DeletedOnBase, false) => format!(
+                "{base} deleted this file while this branch changed it. Keep it with `git add` or confirm the deletion with `git rm`"
+            ),
+            (ConflictKind::DeletedOnIssue, false) => format!(
+                "This branch deleted this file while {base} changed it. Keep it with `git add` or confirm the deletion with `git rm`"
+            ),
+        };
+        Self {
+            path,
+            kind,
+            is_issue,
+            resolution,
+        }
+    }
+}
+
+/// Result of syncing an issue branch
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncStatus {
+    /// The issue branch already contained the main branch
+    UpToDate,
+    /// The main branch was merged or rebased in without conflicts
+    Synced,
+    /// The sync stopped on conflicts and was rolled back
+    Conflicted,
+}
+
+/// What [`GitOperations::sync_issue_branch`] did, and how to finish a sync
+/// that conflicted
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncReport {
+    /// Issue branch
+    pub branch: String,
+    /// Main branch synced from
+    pub base: String,
+    /// How the branches were combined
+    pub strategy: SyncStrategy,
+    /// Result of the sync
+    pub status: SyncStatus,
+    /// Commits on the main branch that the issue branch was missing
+    pub behind: usize,
+    /// Commits on the issue branch that the main branch does not have
+    pub ahead: usize,
+    /// Files that could not be synced automatically
+    pub conflicts: Vec<SyncConflict>,
+    /// Steps that finish the sync by hand, when it conflicted
+    pub plan: Vec<String>,
+}
+
+impl SyncReport {
+    fn resolution_plan(&self) -> Vec<String> {
+        if self.conflicts.is_empty() {
+            return Vec::new();
+        }
+
+        let mut plan = vec![
+            format!("git checkout {}", self.branch),
+            match self.strategy {
+                SyncStrategy::Merge => format!("git merge {}", self.base),
+                SyncStrategy::Rebase => format!("git rebase {}", self.base),
+            },
+        ];
+        plan.extend(
+            self.conflicts
+                .iter()
+                .map(|conflict| format!("{}: {}", conflict.path, conflict.resolution)),
+        );
+        plan.push("Stage each resolved file with `git add` or `git rm`".to_string());
+        match self.strategy {
+            SyncStrategy::Merge => plan.push("git commit --no-edit".to_string()),
+            SyncStrategy::Rebase => plan.push(
+                "git rebase --continue, resolving any later commits that conflict the same way"
+                    .to_string(),
+            ),
+        }
+        plan
+    }
+}
+
+impl fmt::Display for SyncReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.status {
+            SyncStatus::UpToDate => {
+                return write!(f, "{} is up to date with {}", self.branch, self.base)
+            }
+            SyncStatus::Synced => {
+                return write!(
+                    f,
+                    "Synced {} with {} by {}: {} commit(s) brought in",
+                    self.branch, self.base, self.strategy, self.behind
+                )
+            }
+            SyncStatus::Conflicted => {}
+        }
+
+        match self.strategy {
+            SyncStrategy::Merge => write!(f, "Merging {} into {}", self.base, self.branch)?,
+            SyncStrategy::Rebase => write!(f, "Rebasing {} onto {}", self.branch, self.base)?,
+        }
+        writeln!(
+            f,
+            " conflicts in {} file(s). The {} was rolled back, so {} is unchanged.",
+            self.conflicts.len(),
+            self.strategy,
+            self.branch
+        )?;
+        writeln!(f)?;
+        writeln!(f, "Conflicts:")?;
+        for conflict in &self.conflicts {
+            let label = if conflict.is_issue { " (issue)" } else { "" };
+            writeln!(f, "  {}{} - {}", conflict.path, label, conflict.kind)?;
+        }
+        writeln!(f)?;
+        write!(f, "Resolution plan:")?;
+        for (step, action) in self.plan.iter().enumerate() {
+            write!(f, "\n  {}. {}", step + 1, action)?;
+        }
+        Ok(())
+    }
+}
+
+/// State of the working tree, as prompt templates see it in the `git` object
+///
+/// Every text is stripped of control characters and cut short at the size
+/// cap it was gathered with, so a huge diff or a commit message full of
+/// escape sequences cannot flood or garble a rendered prompt.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GitContext {
+    /// Checked out branch, empty on a detached HEAD
+    pub branch: String,
+    /// Files with staged, unstaged or untracked changes, by their new path when renamed
+    pub changed_files: Vec<String>,
+    /// Full message of the last commit
+    pub last_commit_message: String,
+    /// `git diff --stat` of the working tree against the last commit
+    pub diff_stat: String,
+    /// Whether any of the above was cut short at the size cap
+    pub truncated: bool,
+}
+
+/// `text` without control characters other than newlines and tabs, cut at
+/// the last character boundary within `max_bytes`
+///
+/// Returns whether the text was cut short.
+pub fn sanitize_git_output(text: &str, max_bytes: usize) -> (String, bool) {
+    let clean: String = text
+        .chars()
+        .filter(|c| !c.is_control() || *c == '\n' || *c == '\t')
+        .collect();
+    let clean = clean.trim_end();
+    if clean.len() <= max_bytes {
+        return (clean.to_string(), false);
+    }
+    let mut end = max_bytes;
+    while !clean.is_char_boundary(end) {
+        end -= 1;
+    }
+    (format!("{}\n[... truncated]", &clean[..end]), true)
+}
+
+/// Whether a repository path is an issue's markdown file
+fn is_issue_file(path: &Path) -> bool {
+    path.extension().and_then(|ext| ext.to_str()) == Some("md")
+        && path
+            .components()
+            .any(|component| component == Component::Normal("issues".as_ref()))
+}
 
 /// Git operations for issue management
+#[derive(Debug, Clone)]
 pub struct GitOperations {
     /// Working directory for git operations
     work_dir: PathBuf,
+    /// How issue branches are named
+    branch_naming: IssueBranchNaming,
 }
 
 impl GitOperations {
@@ -22,7 +329,10 @@ impl GitOperations {
         // Verify this is a git repository
         Self::verify_git_repo(&work_dir)?;
 
-        Ok(Self { work_dir })
+        Ok(Self {
+            work_dir,
+            branch_naming: IssueBranchNaming::from_config(Config::global())?,
+        })
     }
 
     /// Create git operations handler with explicit work directory
@@ -30,7 +340,64 @@ impl GitOperations {
         // Verify this is a git repository
         Self::verify_git_repo(&work_dir)?;
 
-        Ok(Self { work_dir })
+        Ok(Self {
+            work_dir,
+            branch_naming: IssueBranchNaming::from_config(Config::global())?,
+        })
+    }
+
+    /// Name issue branches with `naming` instead of the configured template
+    pub fn with_branch_naming(mut self, naming: IssueBranchNaming) -> Self {
+        self.branch_naming = naming;
+        self
+    }
+
+    /// How issue branches are named
+    pub fn branch_naming(&self) -> &IssueBranchNaming {
+        &self.branch_naming
+    }
+
+    /// The branch an issue is worked on
+    pub fn issue_branch(&self, issue_name: &str) -> String {
+        self.branch_naming.branch_for(issue_name)
+    }
+
+    /// Create git operations handler for a repository configured by name
+    ///
+    /// Relative repository paths are resolved against `base_dir`, normally
+    /// the project's own repository.
+    pub fn for_repo(name: &str, base_dir: &Path) -> Result<Self> {
+        Self::for_repo_in(&Config::global().repos, name, base_dir)
+    }
+
+    /// Git operations handler for the repository an issue belongs to
+    ///
+    /// Issues name their repository with the `repo:` front matter key.
+    /// Issues without one belong to this handler's repository.
+    pub fn for_issue(&self, issue: &Issue) -> Result<Self> {
+        self.for_issue_in(&Config::global().repos, issue)
+    }
+
+    fn for_issue_in(&self, repos: &BTreeMap<String, PathBuf>, issue: &Issue) -> Result<Self> {
+        match IssueMetadata::from_content(&issue.content).repo {
+            Some(repo) => Self::for_repo_in(repos, &repo, &self.work_dir),
+            None => Ok(self.clone()),
+        }
+    }
+
+    fn for_repo_in(repos: &BTreeMap<String, PathBuf>, name: &str, base_dir: &Path) -> Result<Self> {
+        let Some(path) = repos.get(name) else {
+            let configured: Vec<&str> = repos.keys().map(String::as_str).collect();
+            let configured = if configured.is_empty() {
+                "none".to_string()
+            } else {
+                configured.join(", ")
+            };
+            return Err(SwissArmyHammerError::Other(format!(
+                "Unknown repository '{name}', configure it in SWISSARMYHAMMER_REPOS (configured: {configured})"
+            )));
+        };
+        Self::with_work_dir(base_dir.join(path))
     }
 
     /// Verify directory is a git repository
@@ -118,7 +485,7 @@ impl GitOperations {
     /// 3. If creating new branch, must be on main branch
     /// 4. Returns ABORT ERROR if branching rules are violated
     pub fn create_work_branch(&self, issue_name: &str) -> Result<String> {
-        let branch_name = format!("issue/{issue_name}");
+        let branch_name = self.issue_branch(issue_name);
         let current_branch = self.current_branch()?;
         let main_branch = self.main_branch()?;
 
@@ -201,7 +568,7 @@ impl GitOperations {
 
     /// Merge issue branch to main branch
     pub fn merge_issue_branch(&self, issue_name: &str) -> Result<()> {
-        let branch_name = format!("issue/{issue_name}");
+        let branch_name = self.issue_branch(issue_name);
         let main_branch = self.main_branch()?;
 
         // Debug: List all branches before checking
@@ -283,6 +650,80 @@ impl GitOperations {
         Ok(())
     }
 
+    /// Check out an issue branch in a worktree of its own
+    ///
+    /// The branch is created from the main branch when it does not exist yet.
+    /// This checkout stays on its branch, so several issues can be worked at
+    /// once. An existing directory at `path` is taken to be the worktree of
+    /// an earlier attempt and is reused.
+    pub fn add_issue_worktree(&self, issue_name: &str, path: &Path) -> Result<String> {
+        let branch_name = self.issue_branch(issue_name);
+        if path.exists() {
+            return Ok(branch_name);
+        }
+
+        let mut cmd = Command::new("git");
+        cmd.current_dir(&self.work_dir).args(["worktree", "add"]);
+        if self.branch_exists(&branch_name)? {
+            cmd.arg(path).arg(&branch_name);
+        } else {
+            cmd.args(["-b", &branch_name])
+                .arg(path)
+                .arg(self.main_branch()?);
+        }
+        let output = cmd.output()?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(SwissArmyHammerError::git_command_failed(
+                "worktree add",
+                output.status.code().unwrap_or(-1),
+                &stderr,
+            ));
+        }
+
+        Ok(branch_name)
+    }
+
+    /// Remove a worktree, discarding any changes left in it
+    pub fn remove_worktree(&self, path: &Path) -> Result<()> {
+        let output = Command::new("git")
+            .current_dir(&self.work_dir)
+            .args(["worktree", "remove", "--force"])
+            .arg(path)
+            .output()?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(SwissArmyHammerError::git_command_failed(
+                "worktree remove",
+                output.status.code().unwrap_or(-1),
+                &stderr,
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Abort a merge that stopped on conflicts
+    pub fn abort_merge(&self) -> Result<()> {
+        let output = Command::new("git")
+            .current_dir(&self.work_dir)
+            .args(["merge", "--abort"])
+            .output()?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(SwissArmyHammerError::git_command_failed(
+                "merge --abort",
+                output.status.code().unwrap_or(-1),
+                &stderr,
+            ));
+        }
+
+        Ok(())
+    }
+
     /// Get information about the last commit
     pub fn get_last_commit_info(&self) -> Result<String> {
         let output = Command::new("git")
@@ -301,6 +742,102 @@ impl GitOperations {
         Ok(commit_info.trim().to_string())
     }
 
+    /// Gather the working tree's [`GitContext`], capping each text at `max_bytes`
+    ///
+    /// Parts git cannot report, such as the last commit of a repository
+    /// without commits, are left empty. The changed file list is capped at
+    /// `max_bytes` of paths in total.
+    pub fn context(&self, max_bytes: usize) -> Result<GitContext> {
+        let mut truncated = false;
+        let mut text = |args: &[&str]| -> Result<String> {
+            let output = self.git(args)?;
+            if !output.status.success() {
+                return Ok(String::new());
+            }
+            let (text, cut) =
+                sanitize_git_output(&String::from_utf8_lossy(&output.stdout), max_bytes);
+            truncated |= cut;
+            Ok(text)
+        };
+
+        let branch = text(&["branch", "--show-current"])?;
+        let last_commit_message = text(&["log", "-1", "--pretty=format:%B"])?;
+        let diff_stat = text(&["diff", "--stat", "--no-color", "HEAD"])?;
+
+        let mut changed_files = Vec::new();
+        let mut listed_bytes = 0;
+        for file in self.is_working_directory_clean()? {
+            // A renamed file is listed as `old -> new`
+            let file = file.rsplit(" -> ").next().unwrap_or_default();
+            let (file, _) = sanitize_git_output(file, max_bytes);
+            listed_bytes += file.len();
+            if listed_bytes > max_bytes {
+                truncated = true;
+                break;
+            }
+            changed_files.push(file);
+        }
+
+        Ok(GitContext {
+            branch,
+            changed_files,
+            last_commit_message,
+            diff_stat,
+            truncated,
+        })
+    }
+
+    /// Diff of one path in the working tree against the last commit, capped at `max_bytes`
+    ///
+    /// The path is relative to the work directory and is checked against
+    /// `policy` first, so it may not leave the work directory or name a file
+    /// on the denylist.
+    pub fn file_diff(&self, path: &Path, policy: &PathPolicy, max_bytes: usize) -> Result<String> {
+        validate_path(path, &self.work_dir, policy)?;
+
+        let path = path.to_string_lossy();
+        let output = self.git(&["diff", "--no-color", "--no-ext-diff", "HEAD", "--", &path])?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(SwissArmyHammerError::git_command_failed(
+                "diff HEAD",
+                output.status.code().unwrap_or(-1),
+                &stderr,
+            ));
+        }
+        let (diff, _) = sanitize_git_output(&String::from_utf8_lossy(&output.stdout), max_bytes);
+        Ok(diff)
+    }
+
+    /// Get when a file was first committed at the given path
+    ///
+    /// Returns the commit time of the oldest commit that added the path, or
+    /// `None` if the path has never been committed. Renames are not followed,
+    /// so for an issue moved into `complete/` this is when it was completed.
+    pub fn file_added_at(&self, path: &Path) -> Result<Option<DateTime<Utc>>> {
+        let output = Command::new("git")
+            .current_dir(&self.work_dir)
+            .args(["log", "--diff-filter=A", "--format=%cI", "--"])
+            .arg(path)
+            .output()?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(SwissArmyHammerError::Other(format!(
+                "Failed to read git history for '{}': {stderr}",
+                path.display()
+            )));
+        }
+
+        // git log lists newest first, so the last line is the oldest add
+        let history = String::from_utf8_lossy(&output.stdout);
+        Ok(history
+            .lines()
+            .filter_map(|line| DateTime::parse_from_rfc3339(line.trim()).ok())
+            .map(|time| time.with_timezone(&Utc))
+            .last())
+    }
+
     /// Check if working directory is clean (no uncommitted changes)
     pub fn is_working_directory_clean(&self) -> Result<Vec<String>> {
         let output = Command::new("git")
@@ -339,6 +876,170 @@ impl GitOperations {
     pub fn work_dir(&self) -> &std::path::Path {
         &self.work_dir
     }
+
+    /// Bring the main branch's new commits into an issue branch
+    ///
+    /// Leaves the issue branch checked out. When the merge or rebase
+    /// conflicts, it is aborted so the branch is left as it was, and the
+    /// report lists each conflicted file, including issue markdown changed on
+    /// both branches, with a plan for resolving it by hand.
+    pub fn sync_issue_branch(
+        &self,
+        issue_name: &str,
+        strategy: SyncStrategy,
+    ) -> Result<SyncReport> {
+        let branch = self.issue_branch(issue_name);
+        if !self.branch_exists(&branch)? {
+            return Err(SwissArmyHammerError::Other(format!(
+                "Issue branch '{branch}' does not exist"
+            )));
+        }
+        let base = self.main_branch()?;
+
+        let changes = self.is_working_directory_clean()?;
+        if !changes.is_empty() {
+            return Err(SwissArmyHammerError::Other(format!(
+                "Cannot sync '{branch}' with uncommitted changes: {}",
+                changes.join(", ")
+            )));
+        }
+
+        let mut report = SyncReport {
+            behind: self.count_commits(&format!("{branch}..{base}"))?,
+            ahead: self.count_commits(&format!("{base}..{branch}"))?,
+            branch,
+            base,
+            strategy,
+            status: SyncStatus::UpToDate,
+            conflicts: Vec::new(),
+            plan: Vec::new(),
+        };
+        if report.behind == 0 {
+            return Ok(report);
+        }
+
+        if self.current_branch()? != report.branch {
+            self.checkout_branch(&report.branch)?;
+        }
+
+        let output = match strategy {
+            SyncStrategy::Merge => self.git(&["merge", "--no-edit", &report.base])?,
+            SyncStrategy::Rebase => self.git(&["rebase", &report.base])?,
+        };
+        if output.status.success() {
+            report.status = SyncStatus::Synced;
+            return Ok(report);
+        }
+
+        let conflicts = self.conflicted_files(strategy, &report.base)?;
+        let abort = match strategy {
+            SyncStrategy::Merge => self.git(&["merge", "--abort"])?,
+            SyncStrategy::Rebase => self.git(&["rebase", "--abort"])?,
+        };
+        if !abort.status.success() {
+            tracing::warn!(
+                "Failed to abort {} of '{}': {}",
+                strategy,
+                report.branch,
+                String::from_utf8_lossy(&abort.stderr)
+            );
+        }
+
+        if conflicts.is_empty() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(SwissArmyHammerError::git_command_failed(
+                &strategy.to_string(),
+                output.status.code().unwrap_or(-1),
+                &stderr,
+            ));
+        }
+
+        report.status = SyncStatus::Conflicted;
+        report.conflicts = conflicts;
+        report.plan = report.resolution_plan();
+        Ok(report)
+    }
+
+    /// Names of the local branches
+    pub fn local_branches(&self) -> Result<Vec<String>> {
+        let output = self.git(&["branch", "--format=%(refname:short)"])?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(SwissArmyHammerError::git_command_failed(
+                "branch",
+                output.status.code().unwrap_or(-1),
+                &stderr,
+            ));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::trim)
+            .filter(|branch| !branch.is_empty())
+            .map(str::to_string)
+            .collect())
+    }
+
+    /// Rename a local branch
+    pub fn rename_branch(&self, from: &str, to: &str) -> Result<()> {
+        let output = self.git(&["branch", "-m", from, to])?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(SwissArmyHammerError::git_command_failed(
+                "branch -m",
+                output.status.code().unwrap_or(-1),
+                &stderr,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Count the commits in a revision range such as `a..b`
+    fn count_commits(&self, range: &str) -> Result<usize> {
+        let output = self.git(&["rev-list", "--count", range])?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(SwissArmyHammerError::git_command_failed(
+                "rev-list --count",
+                output.status.code().unwrap_or(-1),
+                &stderr,
+            ));
+        }
+
+        String::from_utf8_lossy(&output.stdout)
+            .trim()
+            .parse()
+            .map_err(|e: std::num::ParseIntError| {
+                SwissArmyHammerError::parsing_failed("git output", "commit count", &e.to_string())
+            })
+    }
+
+    /// Files left unmerged by a merge or rebase in progress
+    fn conflicted_files(&self, strategy: SyncStrategy, base: &str) -> Result<Vec<SyncConflict>> {
+        let output = self.git(&["status", "--porcelain"])?;
+        if !output.status.success() {
+            return Err(SwissArmyHammerError::Other(
+                "Failed to check git status".to_string(),
+            ));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| {
+                let kind = ConflictKind::from_status(line.get(..2)?, strategy)?;
+                let path = line.get(3..)?.to_string();
+                Some(SyncConflict::new(path, kind, base))
+            })
+            .collect())
+    }
+
+    /// Run git in the work directory
+    fn git(&self, args: &[&str]) -> Result<Output> {
+        Ok(Command::new("git")
+            .current_dir(&self.work_dir)
+            .args(args)
+            .output()?)
+    }
 }
 
 #[cfg(test)]
@@ -461,6 +1162,40 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_git_operations_for_issue_repo() {
+        let project = create_test_git_repo().unwrap();
+        let api = create_test_git_repo().unwrap();
+        let repos = BTreeMap::from([("api".to_string(), api.path().to_path_buf())]);
+        let git_ops = GitOperations::with_work_dir(project.path().to_path_buf()).unwrap();
+
+        let issue = |content: &str| Issue {
+            name: "000001_issue".to_string(),
+            content: content.to_string(),
+            completed: false,
+            file_path: project.path().join("issues/000001_issue.md"),
+            created_at: Utc::now(),
+            source: None,
+            priority: None,
+            estimate: None,
+            related_memos: Vec::new(),
+        };
+
+        let api_ops = git_ops
+            .for_issue_in(&repos, &issue("---\nrepo: api\n---\n# Fix"))
+            .unwrap();
+        assert_eq!(api_ops.work_dir(), api.path());
+
+        let local_ops = git_ops.for_issue_in(&repos, &issue("# Fix")).unwrap();
+        assert_eq!(local_ops.work_dir(), project.path());
+
+        let error = git_ops
+            .for_issue_in(&repos, &issue("---\nrepo: web\n---\n"))
+            .unwrap_err();
+        assert!(error.to_string().contains("Unknown repository 'web'"));
+        assert!(error.to_string().contains("configured: api"));
+    }
+
     #[test]
     fn test_current_branch() {
         let temp_dir = create_test_git_repo().unwrap();
@@ -497,6 +1232,31 @@ mod tests {
         assert!(!git_ops.branch_exists("non-existent-branch").unwrap());
     }
 
+    #[test]
+    fn test_issue_branches_follow_naming_template() {
+        let temp_dir = create_test_git_repo().unwrap();
+        let naming = IssueBranchNaming::new("feature/{number}-{slug}").unwrap();
+        let git_ops = GitOperations::with_work_dir(temp_dir.path().to_path_buf())
+            .unwrap()
+            .with_branch_naming(naming);
+        let main_branch = git_ops.main_branch().unwrap();
+
+        let branch_name = git_ops.create_work_branch("000007_fix_login").unwrap();
+        assert_eq!(branch_name, "feature/000007-fix_login");
+        assert_eq!(
+            git_ops.branch_naming().issue_name(&branch_name).as_deref(),
+            Some("000007_fix_login")
+        );
+
+        git_ops.checkout_branch(&main_branch).unwrap();
+        git_ops
+            .rename_branch(&branch_name, "issue/000007_fix_login")
+            .unwrap();
+        let branches = git_ops.local_branches().unwrap();
+        assert!(branches.contains(&"issue/000007_fix_login".to_string()));
+        assert!(!branches.contains(&branch_name));
+    }
+
     #[test]
     fn test_create_work_branch() {
         let temp_dir = create_test_git_repo().unwrap();
@@ -616,6 +1376,21 @@ mod tests {
         assert!(!git_ops.has_uncommitted_changes().unwrap());
     }
 
+    #[test]
+    fn test_file_added_at() {
+        let temp_dir = create_test_git_repo().unwrap();
+        let git_ops = GitOperations::with_work_dir(temp_dir.path().to_path_buf()).unwrap();
+
+        // Committed files have an add time
+        let added = git_ops.file_added_at(Path::new("README.md")).unwrap();
+        assert!(added.is_some());
+        assert!(added.unwrap() <= chrono::Utc::now());
+
+        // Uncommitted files do not
+        fs::write(temp_dir.path().join("new.txt"), "new").unwrap();
+        assert_eq!(git_ops.file_added_at(Path::new("new.txt")).unwrap(), None);
+    }
+
     #[test]
     fn test_create_work_branch_from_issue_branch_should_abort() {
         let temp_dir = create_test_git_repo().unwrap();
@@ -819,4 +1594,155 @@ mod tests {
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), "issue/issue_003");
     }
+
+    fn commit_file(repo: &Path, path: &str, content: &str, message: &str) {
+        let file = repo.join(path);
+        fs::create_dir_all(file.parent().unwrap()).unwrap();
+        fs::write(&file, content).unwrap();
+        Command::new("git")
+            .current_dir(repo)
+            .args(["add", path])
+            .output()
+            .unwrap();
+        Command::new("git")
+            .current_dir(repo)
+            .args(["commit", "-m", message])
+            .output()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_sync_issue_branch_up_to_date() {
+        let temp_dir = create_test_git_repo().unwrap();
+        let git_ops = GitOperations::with_work_dir(temp_dir.path().to_path_buf()).unwrap();
+        git_ops.create_work_branch("test_issue").unwrap();
+
+        let report = git_ops
+            .sync_issue_branch("test_issue", SyncStrategy::Merge)
+            .unwrap();
+        assert_eq!(report.status, SyncStatus::UpToDate);
+        assert_eq!(report.behind, 0);
+        assert!(report.plan.is_empty());
+
+        assert!(git_ops
+            .sync_issue_branch("missing", SyncStrategy::Merge)
+            .is_err());
+    }
+
+    #[test]
+    fn test_sync_issue_branch_brings_in_main() {
+        for strategy in [SyncStrategy::Merge, SyncStrategy::Rebase] {
+            let temp_dir = create_test_git_repo().unwrap();
+            let repo = temp_dir.path();
+            let git_ops = GitOperations::with_work_dir(repo.to_path_buf()).unwrap();
+            let main_branch = git_ops.main_branch().unwrap();
+
+            git_ops.create_work_branch("test_issue").unwrap();
+            commit_file(repo, "issue.txt", "issue work", "Issue work");
+            git_ops.checkout_branch(&main_branch).unwrap();
+            commit_file(repo, "main.txt", "main work", "Main work");
+
+            let report = git_ops.sync_issue_branch("test_issue", strategy).unwrap();
+            assert_eq!(report.status, SyncStatus::Synced);
+            assert_eq!((report.behind, report.ahead), (1, 1));
+            assert_eq!(git_ops.current_branch().unwrap(), "issue/test_issue");
+            assert!(repo.join("main.txt").exists());
+            assert!(repo.join("issue.txt").exists());
+        }
+    }
+
+    #[test]
+    fn test_sync_issue_branch_reports_issue_conflict() {
+        for strategy in [SyncStrategy::Merge, SyncStrategy::Rebase] {
+            let temp_dir = create_test_git_repo().unwrap();
+            let repo = temp_dir.path();
+            let git_ops = GitOperations::with_work_dir(repo.to_path_buf()).unwrap();
+            let main_branch = git_ops.main_branch().unwrap();
+            commit_file(repo, "issues/test_issue.md", "# Task\n", "Add issue");
+
+            git_ops.create_work_branch("test_issue").unwrap();
+            commit_file(
+                repo,
+                "issues/test_issue.md",
+                "# Task\nBranch notes\n",
+                "Notes",
+            );
+            git_ops.checkout_branch(&main_branch).unwrap();
+            commit_file(repo, "issues/test_issue.md", "# Task\nMain notes\n", "Edit");
+
+            let report = git_ops.sync_issue_branch("test_issue", strategy).unwrap();
+            assert_eq!(report.status, SyncStatus::Conflicted);
+            assert_eq!(report.conflicts.len(), 1);
+            let conflict = &report.conflicts[0];
+            assert_eq!(conflict.path, "issues/test_issue.md");
+            assert_eq!(conflict.kind, ConflictKind::BothModified);
+            assert!(conflict.is_issue);
+            assert_eq!(
+                report.plan.first().unwrap(),
+                "git checkout issue/test_issue"
+            );
+            assert!(report.to_string().contains("Resolution plan:"));
+
+            // The sync was rolled back
+            assert!(git_ops.is_working_directory_clean().unwrap().is_empty());
+            assert_eq!(
+                fs::read_to_string(repo.join("issues/test_issue.md")).unwrap(),
+                "# Task\nBranch notes\n"
+            );
+        }
+    }
+
+    #[test]
+    fn test_sync_strategy_from_str() {
+        assert_eq!(
+            "merge".parse::<SyncStrategy>().unwrap(),
+            SyncStrategy::Merge
+        );
+        assert_eq!(
+            "Rebase".parse::<SyncStrategy>().unwrap(),
+            SyncStrategy::Rebase
+        );
+        assert!("squash".parse::<SyncStrategy>().is_err());
+    }
+
+    #[test]
+    fn test_context_reports_working_tree() {
+        let temp_dir = create_test_git_repo().unwrap();
+        let git_ops = GitOperations::with_work_dir(temp_dir.path().to_path_buf()).unwrap();
+        fs::write(temp_dir.path().join("README.md"), "# Changed\n").unwrap();
+        fs::write(temp_dir.path().join("notes.txt"), "new").unwrap();
+
+        let context = git_ops.context(4096).unwrap();
+        assert_eq!(context.branch, git_ops.current_branch().unwrap());
+        assert_eq!(context.last_commit_message, "Initial commit");
+        assert!(context.changed_files.contains(&"README.md".to_string()));
+        assert!(context.changed_files.contains(&"notes.txt".to_string()));
+        assert!(context.diff_stat.contains("README.md"));
+        assert!(!context.truncated);
+
+        let policy = PathPolicy::default();
+        let diff = git_ops
+            .file_diff(Path::new("README.md"), &policy, 4096)
+            .unwrap();
+        assert!(diff.contains("+# Changed"));
+        assert!(git_ops
+            .file_diff(Path::new("../secret"), &policy, 4096)
+            .is_err());
+
+        let context = git_ops.context(4).unwrap();
+        assert!(context.truncated);
+        assert!(context.changed_files.is_empty());
+    }
+
+    #[test]
+    fn test_sanitize_git_output() {
+        assert_eq!(
+            sanitize_git_output("fix\x1b[31m bug\r\n\tdetails\n\n", 100),
+            ("fix[31m bug\n\tdetails".to_string(), false)
+        );
+        assert_eq!(
+            sanitize_git_output("héllo", 2),
+            ("h\n[... truncated]".to_string(), true)
+        );
+    }
 }
@@ -0,0 +1,298 @@
+//! Content history of memos and issues
+//!
+//! Every write to a memo or issue is recorded in a change log kept beside the
+//! stored files, one JSON line per change holding the content it left behind.
+//! Entries are only ever appended, so content overwritten by mistake, for
+//! example by an MCP tool, can still be looked up and restored. The diff of
+//! each change is computed from the content of the change before it.
+//!
+//! When the content before a change is not the last content recorded, because
+//! it was written before history was kept or edited by hand, it is recorded as
+//! a [`ChangeKind::Baseline`] entry ahead of the change.
+
+use crate::fs_utils::FileSystem;
+use crate::prompt_diff::{diff_lines, unified_diff};
+use crate::{Result, SwissArmyHammerError};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Extension of change log files
+const LOG_EXTENSION: &str = "jsonl";
+
+/// What a change did to the content
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    /// Content the history had not recorded, written before history was
+    /// kept or edited by hand
+    Baseline,
+    /// The content was created
+    Created,
+    /// The content was replaced
+    Updated,
+    /// The content was deleted
+    Deleted,
+    /// The content was restored from an earlier version
+    Restored,
+}
+
+impl fmt::Display for ChangeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ChangeKind::Baseline => "baseline",
+            ChangeKind::Created => "created",
+            ChangeKind::Updated => "updated",
+            ChangeKind::Deleted => "deleted",
+            ChangeKind::Restored => "restored",
+        })
+    }
+}
+
+/// One entry of a change log
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContentChange {
+    /// Version the change produced, counting from 1
+    pub version: usize,
+    /// When the change was made
+    pub timestamp: DateTime<Utc>,
+    /// What the change did
+    pub kind: ChangeKind,
+    /// Content after the change, empty once deleted
+    pub content: String,
+    /// Version a restore brought back
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub restored_from: Option<usize>,
+}
+
+impl ContentChange {
+    /// The change as unified diff hunks against the content before it
+    pub fn unified_diff(&self, previous: Option<&ContentChange>) -> String {
+        let before = previous.map_or("", |change| change.content.as_str());
+        unified_diff(&diff_lines(before, &self.content))
+    }
+
+    /// Content to bring back when restoring this version, refusing a
+    /// version that deleted the content
+    pub fn restorable_content(&self) -> Result<&str> {
+        if self.kind == ChangeKind::Deleted {
+            return Err(SwissArmyHammerError::Other(format!(
+                "Version {} deleted the content; restore an earlier version",
+                self.version
+            )));
+        }
+        Ok(&self.content)
+    }
+}
+
+/// Change logs of one kind of content, one log per memo or issue
+#[derive(Clone)]
+pub struct ContentHistory {
+    dir: PathBuf,
+    fs: Arc<dyn FileSystem>,
+}
+
+impl ContentHistory {
+    /// Keep change logs in `dir` on the file system `fs`
+    pub fn new(dir: PathBuf, fs: Arc<dyn FileSystem>) -> Self {
+        Self { dir, fs }
+    }
+
+    /// Directory the change logs are kept in
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Path of the change log of `key`
+    fn log_path(&self, key: &str) -> Result<PathBuf> {
+        if key.is_empty() || key.contains(['/', '\\']) || key == "." || key == ".." {
+            return Err(SwissArmyHammerError::Other(format!(
+                "Invalid history key '{key}'"
+            )));
+        }
+        Ok(self.dir.join(format!("{key}.{LOG_EXTENSION}")))
+    }
+
+    /// Changes recorded for `key`, oldest first
+    pub fn changes(&self, key: &str) -> Result<Vec<ContentChange>> {
+        let path = self.log_path(key)?;
+        if !self.fs.exists(&path) {
+            return Ok(Vec::new());
+        }
+        self.fs
+            .read_to_string(&path)?
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                serde_json::from_str(line).map_err(|e| {
+                    SwissArmyHammerError::Other(format!(
+                        "Invalid history entry in {}: {e}",
+                        path.display()
+                    ))
+                })
+            })
+            .collect()
+    }
+
+    /// The change that produced `version` of `key`
+    pub fn change(&self, key: &str, version: usize) -> Result<ContentChange> {
+        self.changes(key)?
+            .into_iter()
+            .find(|change| change.version == version)
+            .ok_or_else(|| {
+                SwissArmyHammerError::Other(format!(
+                    "No version {version} in the history of '{key}'"
+                ))
+            })
+    }
+
+    /// Record a change to `key`
+    ///
+    /// `previous` is the content before the change. It is recorded as a
+    /// baseline first when the history does not end with it, and an update
+    /// leaving the content as it was is not recorded.
+    pub fn record(
+        &self,
+        key: &str,
+        previous: Option<&str>,
+        kind: ChangeKind,
+        content: &str,
+    ) -> Result<()> {
+        self.record_change(key, previous, kind, content, None)
+    }
+
+    /// Record that `key` was restored to `version`
+    pub fn record_restore(
+        &self,
+        key: &str,
+        previous: Option<&str>,
+        version: usize,
+        content: &str,
+    ) -> Result<()> {
+        self.record_change(key, previous, ChangeKind::Restored, content, Some(version))
+    }
+
+    fn record_change(
+        &self,
+        key: &str,
+        previous: Option<&str>,
+        kind: ChangeKind,
+        content: &str,
+        restored_from: Option<usize>,
+    ) -> Result<()> {
+        let path = self.log_path(key)?;
+        let mut changes = self.changes(key)?;
+        if let Some(previous) = previous {
+            if changes.last().map(|change| change.content.as_str()) != Some(previous) {
+                changes.push(entry(
+                    changes.len() + 1,
+                    ChangeKind::Baseline,
+                    previous,
+                    None,
+                ));
+            }
+        }
+        if kind == ChangeKind::Updated
+            && changes
+                .last()
+                .is_some_and(|change| change.content == content)
+        {
+            return Ok(());
+        }
+        changes.push(entry(changes.len() + 1, kind, content, restored_from));
+
+        let mut log = String::new();
+        for change in &changes {
+            log.push_str(&serde_json::to_string(change)?);
+            log.push('\n');
+        }
+        if !self.fs.exists(&self.dir) {
+            self.fs.create_dir_all(&self.dir)?;
+        }
+        self.fs.write(&path, &log)
+    }
+}
+
+/// A change log entry made now
+fn entry(
+    version: usize,
+    kind: ChangeKind,
+    content: &str,
+    restored_from: Option<usize>,
+) -> ContentChange {
+    ContentChange {
+        version,
+        timestamp: Utc::now(),
+        kind,
+        content: content.to_string(),
+        restored_from,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fs_utils::StdFileSystem;
+    use tempfile::TempDir;
+
+    fn history() -> (ContentHistory, TempDir) {
+        let temp = TempDir::new().unwrap();
+        let history = ContentHistory::new(temp.path().join("history"), Arc::new(StdFileSystem));
+        (history, temp)
+    }
+
+    #[test]
+    fn test_record_keeps_every_change() {
+        let (history, _temp) = history();
+        assert!(history.changes("notes").unwrap().is_empty());
+
+        // Content written before history was kept becomes the baseline
+        history
+            .record("notes", Some("draft"), ChangeKind::Updated, "final")
+            .unwrap();
+        history
+            .record("notes", Some("final"), ChangeKind::Updated, "final")
+            .unwrap();
+        history
+            .record("notes", Some("final"), ChangeKind::Deleted, "")
+            .unwrap();
+        history.record_restore("notes", None, 1, "draft").unwrap();
+
+        let changes = history.changes("notes").unwrap();
+        let kinds: Vec<ChangeKind> = changes.iter().map(|change| change.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ChangeKind::Baseline,
+                ChangeKind::Updated,
+                ChangeKind::Deleted,
+                ChangeKind::Restored
+            ]
+        );
+        assert_eq!(changes[3].version, 4);
+        assert_eq!(changes[3].restored_from, Some(1));
+        assert_eq!(
+            changes[1].unified_diff(Some(&changes[0])),
+            "@@ -1,1 +1,1 @@\n-draft\n+final\n"
+        );
+        assert_eq!(changes[0].unified_diff(None), "@@ -1,0 +1,1 @@\n+draft\n");
+
+        assert_eq!(history.change("notes", 1).unwrap().content, "draft");
+        assert!(history.change("notes", 9).is_err());
+        assert!(changes[2].restorable_content().is_err());
+        assert_eq!(changes[0].restorable_content().unwrap(), "draft");
+        assert!(history.changes("../escape").is_err());
+
+        // Content edited by hand since the last change is kept too
+        history
+            .record("notes", Some("draft, edited"), ChangeKind::Updated, "done")
+            .unwrap();
+        let changes = history.changes("notes").unwrap();
+        assert_eq!(changes.len(), 6);
+        assert_eq!(changes[4].kind, ChangeKind::Baseline);
+        assert_eq!(changes[4].content, "draft, edited");
+        assert_eq!(changes[5].version, 6);
+    }
+}
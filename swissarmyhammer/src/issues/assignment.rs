@@ -0,0 +1,140 @@
+//! Who owns an issue and who raised it
+//!
+//! When several people share one issues directory, an issue names its owner in
+//! the `assignee` field of its front matter and the person who raised it in
+//! `reporter`:
+//!
+//! ```markdown
+//! ---
+//! assignee: Jane Doe
+//! reporter: John Smith
+//! ---
+//! # Fix the login bug
+//! ```
+//!
+//! Identities are free text compared without regard to case. `@me` stands for
+//! the identity in git config, `user.name` or else `user.email`, which is also
+//! recorded as the reporter of new issues.
+
+use crate::error::{Result, SwissArmyHammerError};
+use crate::issues::priority::set_front_matter_field;
+use crate::issues::{Issue, IssueFields, IssueMetadata, IssueStorage};
+use std::process::Command;
+
+/// Stands for the identity in git config
+pub const CURRENT_USER: &str = "@me";
+
+/// The identity in git config, `user.name` or else `user.email`
+pub fn git_identity() -> Option<String> {
+    ["user.name", "user.email"].into_iter().find_map(|key| {
+        let output = Command::new("git")
+            .args(["config", "--get", key])
+            .output()
+            .ok()?;
+        let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (output.status.success() && !value.is_empty()).then_some(value)
+    })
+}
+
+/// The identity a user argument names, resolving [`CURRENT_USER`]
+pub fn resolve_user(user: &str) -> Result<String> {
+    let user = user.trim();
+    if user.is_empty() {
+        return Err(SwissArmyHammerError::Other(
+            "User name cannot be empty".to_string(),
+        ));
+    }
+    if user != CURRENT_USER {
+        return Ok(user.to_string());
+    }
+    git_identity().ok_or_else(|| {
+        SwissArmyHammerError::Other(format!(
+            "{CURRENT_USER} needs user.name or user.email in git config"
+        ))
+    })
+}
+
+/// Whether an issue is assigned to `user`
+pub fn is_assigned_to<T: IssueFields>(issue: &T, user: &str) -> bool {
+    issue
+        .assignee()
+        .is_some_and(|assignee| assignee.trim().eq_ignore_ascii_case(user.trim()))
+}
+
+/// Issue content with the `assignee` field set, or removed for `None`
+pub fn set_issue_assignee(content: &str, assignee: Option<&str>) -> Result<String> {
+    set_front_matter_field(content, "assignee", assignee)
+}
+
+/// Issue content with `reporter` recorded, unless it names one already
+pub fn with_reporter(content: &str, reporter: &str) -> Result<String> {
+    if IssueMetadata::from_content(content).reporter.is_some() {
+        return Ok(content.to_string());
+    }
+    set_front_matter_field(content, "reporter", Some(reporter))
+}
+
+/// Assign an issue to `assignee`, or unassign it for `None`, returning the
+/// updated issue
+pub async fn assign_issue(
+    storage: &dyn IssueStorage,
+    name: &str,
+    assignee: Option<&str>,
+) -> Result<Issue> {
+    let issue = storage.get_issue(name).await?;
+    let content = set_issue_assignee(&issue.content, assignee)?;
+    if content == issue.content {
+        return Ok(issue);
+    }
+    storage.update_issue(name, content).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::issues::FileSystemIssueStorage;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_assignee_and_reporter_fields() {
+        let content =
+            set_issue_assignee("---\npriority: high\n---\n# Fix it\n", Some("Jane")).unwrap();
+        let metadata = IssueMetadata::from_content(&content);
+        assert_eq!(metadata.assignee.as_deref(), Some("Jane"));
+        assert!(content.contains("priority: high"));
+        assert_eq!(
+            set_issue_assignee("# Fix it\n", None).unwrap(),
+            "# Fix it\n"
+        );
+
+        let reported = with_reporter("# Fix it\n", "John").unwrap();
+        assert_eq!(reported, "---\nreporter: John\n---\n# Fix it\n");
+        assert_eq!(with_reporter(&reported, "Jane").unwrap(), reported);
+
+        assert_eq!(resolve_user(" Jane ").unwrap(), "Jane");
+        assert!(resolve_user("  ").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_assign_issue() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = FileSystemIssueStorage::new(temp_dir.path().join("issues")).unwrap();
+        storage
+            .create_issue("fix_it".to_string(), "# Fix it\n".to_string())
+            .await
+            .unwrap();
+
+        let issue = assign_issue(&storage, "fix_it", Some("Jane Doe"))
+            .await
+            .unwrap();
+        assert!(is_assigned_to(&issue, "jane doe"));
+        assert!(!is_assigned_to(&issue, "John"));
+
+        let issue = assign_issue(&storage, "fix_it", None).await.unwrap();
+        assert_eq!(issue.content, "# Fix it\n");
+        assert!(!is_assigned_to(&issue, "Jane Doe"));
+        assert!(assign_issue(&storage, "missing", Some("Jane"))
+            .await
+            .is_err());
+    }
+}
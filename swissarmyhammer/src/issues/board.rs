@@ -0,0 +1,265 @@
+//! Kanban-style board view of issues
+//!
+//! Issues are grouped into three columns derived from their state on disk and
+//! the branches in the repository:
+//!
+//! - **Todo**: pending issues with no work branch
+//! - **In Progress**: pending issues that are checked out or have an `issue/<name>` branch
+//! - **Done**: issues in the `complete` directory
+
+use crate::error::{Result, SwissArmyHammerError};
+use crate::issues::Issue;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Minimum width of a rendered board column
+const MIN_COLUMN_WIDTH: usize = 12;
+
+/// A column on the issue board
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BoardColumn {
+    /// Pending issues nobody has started
+    Todo,
+    /// Pending issues with an active work branch
+    InProgress,
+    /// Completed issues
+    Done,
+}
+
+impl BoardColumn {
+    /// All columns in display order
+    pub const ALL: [BoardColumn; 3] = [
+        BoardColumn::Todo,
+        BoardColumn::InProgress,
+        BoardColumn::Done,
+    ];
+
+    /// Heading shown above the column
+    pub fn title(&self) -> &'static str {
+        match self {
+            BoardColumn::Todo => "TODO",
+            BoardColumn::InProgress => "IN PROGRESS",
+            BoardColumn::Done => "DONE",
+        }
+    }
+}
+
+impl fmt::Display for BoardColumn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            BoardColumn::Todo => "todo",
+            BoardColumn::InProgress => "in_progress",
+            BoardColumn::Done => "done",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for BoardColumn {
+    type Err = SwissArmyHammerError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().replace('-', "_").as_str() {
+            "todo" => Ok(BoardColumn::Todo),
+            "in_progress" => Ok(BoardColumn::InProgress),
+            "done" => Ok(BoardColumn::Done),
+            _ => Err(SwissArmyHammerError::Other(format!(
+                "Invalid board column '{s}': expected todo, in_progress or done"
+            ))),
+        }
+    }
+}
+
+/// Issue names grouped into board columns
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IssueBoard {
+    /// Pending issues nobody has started
+    pub todo: Vec<String>,
+    /// Pending issues with an active work branch
+    pub in_progress: Vec<String>,
+    /// Completed issues
+    pub done: Vec<String>,
+}
+
+impl IssueBoard {
+    /// Build a board from issues
+    ///
+    /// `is_in_progress` is asked about each pending issue and should return true
+    /// when work on it has started, typically because its branch exists.
+    pub fn from_issues<F>(issues: &[Issue], is_in_progress: F) -> Self
+    where
+        F: Fn(&str) -> bool,
+    {
+        let mut board = Self::default();
+        for issue in issues {
+            let column = if issue.completed {
+                &mut board.done
+            } else if is_in_progress(&issue.name) {
+                &mut board.in_progress
+            } else {
+                &mut board.todo
+            };
+            column.push(issue.name.clone());
+        }
+
+        for column in [&mut board.todo, &mut board.in_progress, &mut board.done] {
+            column.sort();
+            column.dedup();
+        }
+        board
+    }
+
+    /// Issue names in a column
+    pub fn column(&self, column: BoardColumn) -> &[String] {
+        match column {
+            BoardColumn::Todo => &self.todo,
+            BoardColumn::InProgress => &self.in_progress,
+            BoardColumn::Done => &self.done,
+        }
+    }
+
+    /// Find the column an issue is in
+    pub fn column_of(&self, name: &str) -> Option<BoardColumn> {
+        BoardColumn::ALL
+            .into_iter()
+            .find(|column| self.column(*column).iter().any(|issue| issue == name))
+    }
+
+    /// Render the board as side-by-side text columns for a terminal
+    pub fn render_table(&self) -> String {
+        let widths: Vec<usize> = BoardColumn::ALL
+            .iter()
+            .map(|column| {
+                let heading = format!("{} ({})", column.title(), self.column(*column).len());
+                self.column(*column)
+                    .iter()
+                    .map(|name| name.chars().count())
+                    .chain([heading.chars().count(), MIN_COLUMN_WIDTH])
+                    .max()
+                    .unwrap_or(MIN_COLUMN_WIDTH)
+            })
+            .collect();
+
+        let row = |cells: Vec<String>| -> String {
+            cells
+                .iter()
+                .zip(&widths)
+                .map(|(cell, &width)| format!("{cell:<width$}"))
+                .collect::<Vec<_>>()
+                .join(" | ")
+                .trim_end()
+                .to_string()
+        };
+
+        let mut lines = vec![
+            row(BoardColumn::ALL
+                .iter()
+                .map(|column| format!("{} ({})", column.title(), self.column(*column).len()))
+                .collect()),
+            widths
+                .iter()
+                .map(|width| "-".repeat(*width))
+                .collect::<Vec<_>>()
+                .join("-+-"),
+        ];
+
+        let height = BoardColumn::ALL
+            .iter()
+            .map(|column| self.column(*column).len())
+            .max()
+            .unwrap_or(0);
+        for index in 0..height {
+            lines.push(row(BoardColumn::ALL
+                .iter()
+                .map(|column| self.column(*column).get(index).cloned().unwrap_or_default())
+                .collect()));
+        }
+
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::path::PathBuf;
+
+    fn issue(name: &str, completed: bool) -> Issue {
+        Issue {
+            name: name.to_string(),
+            content: String::new(),
+            completed,
+            file_path: PathBuf::from(format!("{name}.md")),
+            created_at: Utc::now(),
+            source: None,
+            priority: None,
+            estimate: None,
+            related_memos: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_board_groups_issues_by_state() {
+        let issues = vec![
+            issue("b_todo", false),
+            issue("a_todo", false),
+            issue("active", false),
+            issue("finished", true),
+        ];
+
+        let board = IssueBoard::from_issues(&issues, |name| name == "active");
+
+        assert_eq!(board.todo, vec!["a_todo", "b_todo"]);
+        assert_eq!(board.in_progress, vec!["active"]);
+        assert_eq!(board.done, vec!["finished"]);
+        assert_eq!(board.column_of("active"), Some(BoardColumn::InProgress));
+        assert_eq!(board.column_of("missing"), None);
+    }
+
+    #[test]
+    fn test_completed_issues_are_done_even_with_branch() {
+        let issues = vec![issue("finished", true)];
+        let board = IssueBoard::from_issues(&issues, |_| true);
+
+        assert!(board.in_progress.is_empty());
+        assert_eq!(board.done, vec!["finished"]);
+    }
+
+    #[test]
+    fn test_board_column_parsing() {
+        assert_eq!(
+            "in-progress".parse::<BoardColumn>().unwrap(),
+            BoardColumn::InProgress
+        );
+        assert_eq!(
+            "IN_PROGRESS".parse::<BoardColumn>().unwrap(),
+            BoardColumn::InProgress
+        );
+        assert_eq!("todo".parse::<BoardColumn>().unwrap(), BoardColumn::Todo);
+        assert_eq!("done".parse::<BoardColumn>().unwrap(), BoardColumn::Done);
+        assert!("blocked".parse::<BoardColumn>().is_err());
+    }
+
+    #[test]
+    fn test_render_table() {
+        let issues = vec![
+            issue("first", false),
+            issue("second", false),
+            issue("finished", true),
+        ];
+        let board = IssueBoard::from_issues(&issues, |name| name == "second");
+        let rendered = board.render_table();
+        let lines: Vec<&str> = rendered.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("TODO (1)"));
+        assert!(lines[0].contains("IN PROGRESS (1)"));
+        assert!(lines[0].contains("DONE (1)"));
+        assert!(lines[2].contains("first"));
+        assert!(lines[2].contains("second"));
+        assert!(lines[2].contains("finished"));
+    }
+}
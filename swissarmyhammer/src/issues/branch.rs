@@ -0,0 +1,581 @@
+//! Names of the git branches issues are worked on
+//!
+//! An issue is worked on a branch named by a template, `issue/{name}` unless
+//! `SWISSARMYHAMMER_ISSUE_BRANCH_TEMPLATE` sets another, so projects whose
+//! branch naming policy forbids the `issue/` prefix can follow their own:
+//!
+//! - `{name}`: the whole issue name, `000123_fix_login`
+//! - `{number}`: the identifier the issue name starts with, `000123`, see
+//!   [`IssueNumbering`]
+//! - `{slug}`: the issue name after its identifier, `fix_login`
+//!
+//! `feature/{number}-{slug}` names the branch of `000123_fix_login`
+//! `feature/000123-fix_login`. An issue without an identifier has an empty
+//! `{number}`, and the separator next to it is dropped, giving
+//! `feature/fix_login`. A template needs `{name}`, or both `{number}` and
+//! `{slug}`, so the issue a branch belongs to can be told from its name.
+//!
+//! A [`BranchRenamePlan`] renames the branches of existing issues when the
+//! template changes. Like renumbering issues, the plan can be shown before any
+//! branch is touched, and applying it is all or nothing.
+
+use crate::config::Config;
+use crate::error::{Result, SwissArmyHammerError};
+use crate::git::GitOperations;
+use crate::issues::{strip_identifier, BulkSkip, Issue, IssueMetadata, IssueNumbering};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use tracing::warn;
+
+/// Template issue branches are named with unless configured otherwise
+pub const DEFAULT_ISSUE_BRANCH_TEMPLATE: &str = "issue/{name}";
+
+/// Characters dropped next to a placeholder that renders empty
+const SEPARATORS: [char; 3] = ['-', '_', '.'];
+
+/// One piece of a branch template
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Text(String),
+    Name,
+    Number,
+    Slug,
+}
+
+/// Values captured from a branch name while matching it against a template
+#[derive(Debug, Clone, Default)]
+struct Captures {
+    name: Option<String>,
+    number: Option<String>,
+    slug: Option<String>,
+}
+
+impl Captures {
+    fn set(&mut self, part: &Part, value: &str) {
+        let slot = match part {
+            Part::Name => &mut self.name,
+            Part::Number => &mut self.number,
+            Part::Slug => &mut self.slug,
+            Part::Text(_) => return,
+        };
+        *slot = Some(value.to_string());
+    }
+
+    /// The issue name the captured values make up
+    fn issue_name(self) -> Option<String> {
+        if let Some(name) = self.name {
+            return Some(name);
+        }
+        let slug = self.slug?;
+        match self.number.filter(|number| !number.is_empty()) {
+            None => Some(slug),
+            Some(number) if number == slug => Some(number),
+            Some(number) => Some(IssueNumbering::issue_name(Some(&number), &slug)),
+        }
+    }
+}
+
+/// How the branches of issues are named
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IssueBranchNaming {
+    template: String,
+    parts: Vec<Part>,
+}
+
+impl IssueBranchNaming {
+    /// Naming with a template, checking it names valid and distinct branches
+    pub fn new(template: &str) -> Result<Self> {
+        let parts = parse_template(template)?;
+        let has = |wanted: Part| parts.contains(&wanted);
+        if !has(Part::Name) && !(has(Part::Number) && has(Part::Slug)) {
+            return Err(SwissArmyHammerError::Config(format!(
+                "Issue branch template '{template}' needs {{name}}, or both {{number}} and {{slug}}, to tell which issue a branch belongs to"
+            )));
+        }
+
+        let naming = Self {
+            template: template.to_string(),
+            parts,
+        };
+        for sample in ["000123_fix_login", "fix_login", "000123"] {
+            let branch = naming.branch_for(sample);
+            validate_branch_name(&branch).map_err(|e| {
+                SwissArmyHammerError::Config(format!(
+                    "Issue branch template '{template}' names issue '{sample}' '{branch}': {e}"
+                ))
+            })?;
+        }
+        Ok(naming)
+    }
+
+    /// The naming set in the configuration
+    pub fn from_config(config: &Config) -> Result<Self> {
+        Self::new(&config.issue_branch_template)
+    }
+
+    /// The template branches are named with
+    pub fn template(&self) -> &str {
+        &self.template
+    }
+
+    /// The branch an issue is worked on
+    pub fn branch_for(&self, issue_name: &str) -> String {
+        let (number, slug) = split_issue_name(issue_name);
+        let mut branch = String::new();
+        let mut drop_separator = false;
+        for part in &self.parts {
+            let value = match part {
+                Part::Text(text) => {
+                    let text = if drop_separator {
+                        text.strip_prefix(SEPARATORS).unwrap_or(text)
+                    } else {
+                        text
+                    };
+                    branch.push_str(text);
+                    drop_separator = false;
+                    continue;
+                }
+                Part::Name => issue_name,
+                Part::Number => number,
+                Part::Slug => slug,
+            };
+            if value.is_empty() {
+                // Drop the separator before the empty value, or else the one after it
+                if branch.ends_with(SEPARATORS) {
+                    branch.pop();
+                } else {
+                    drop_separator = true;
+                }
+            }
+            branch.push_str(value);
+        }
+        branch
+    }
+
+    /// The name of the issue a branch is worked on, if it is an issue branch
+    ///
+    /// When identifiers contain the separator after `{number}`, a branch can
+    /// split more than one way. The split whose `{number}` is the longest
+    /// identifier wins, so under `{number}-{slug}` the branch
+    /// `2024-07-001-fix` is issue `2024-07-001_fix`.
+    pub fn issue_name(&self, branch: &str) -> Option<String> {
+        let mut variants = vec![self.parts.clone()];
+        if self.parts.contains(&Part::Number) {
+            variants.push(without_number(&self.parts));
+        }
+
+        let mut best: Option<(usize, String)> = None;
+        for parts in &variants {
+            match_parts(parts, branch, Captures::default(), &mut |captures| {
+                let number = captures.number.clone().unwrap_or_default();
+                let by_name = captures.name.is_some();
+                let Some(name) = captures.issue_name() else {
+                    return false;
+                };
+                let consistent = by_name || number.is_empty() || {
+                    let (identifier, _) = split_issue_name(&name);
+                    identifier == number
+                };
+                if consistent
+                    && self.branch_for(&name) == branch
+                    && !matches!(&best, Some((length, _)) if number.len() <= *length)
+                {
+                    best = Some((number.len(), name));
+                }
+                // Keep looking for splits with longer identifiers
+                false
+            });
+        }
+        best.map(|(_, name)| name)
+    }
+}
+
+impl Default for IssueBranchNaming {
+    fn default() -> Self {
+        Self::new(DEFAULT_ISSUE_BRANCH_TEMPLATE).expect("default branch template is valid")
+    }
+}
+
+impl fmt::Display for IssueBranchNaming {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.template)
+    }
+}
+
+/// Split an issue name into its identifier, empty without one, and the rest
+///
+/// A nameless issue, named by its identifier alone, uses the identifier for
+/// both.
+fn split_issue_name(name: &str) -> (&str, &str) {
+    let slug = strip_identifier(name);
+    if slug.is_empty() {
+        return (name, name);
+    }
+    let number = name[..name.len() - slug.len()].trim_end_matches('_');
+    (number, slug)
+}
+
+fn parse_template(template: &str) -> Result<Vec<Part>> {
+    let mut parts = Vec::new();
+    let mut rest = template;
+    while !rest.is_empty() {
+        let Some(start) = rest.find('{') else {
+            parts.push(Part::Text(rest.to_string()));
+            break;
+        };
+        if start > 0 {
+            parts.push(Part::Text(rest[..start].to_string()));
+        }
+        let Some(end) = rest[start..].find('}') else {
+            return Err(SwissArmyHammerError::Config(format!(
+                "Issue branch template '{template}' has an unclosed '{{'"
+            )));
+        };
+        let part = match &rest[start + 1..start + end] {
+            "name" => Part::Name,
+            "number" => Part::Number,
+            "slug" => Part::Slug,
+            other => {
+                return Err(SwissArmyHammerError::Config(format!(
+                    "Unknown placeholder '{{{other}}}' in issue branch template '{template}': expected {{name}}, {{number}} or {{slug}}"
+                )))
+            }
+        };
+        if matches!(parts.last(), Some(Part::Name | Part::Number | Part::Slug)) {
+            return Err(SwissArmyHammerError::Config(format!(
+                "Issue branch template '{template}' needs text between placeholders"
+            )));
+        }
+        parts.push(part);
+        rest = &rest[start + end + 1..];
+    }
+    Ok(parts)
+}
+
+/// The template as rendered for an issue without an identifier
+fn without_number(parts: &[Part]) -> Vec<Part> {
+    let mut result: Vec<Part> = Vec::new();
+    let mut drop_separator = false;
+    for part in parts {
+        match part {
+            Part::Number => {
+                if let Some(Part::Text(text)) = result.last_mut() {
+                    if text.ends_with(SEPARATORS) {
+                        text.pop();
+                        continue;
+                    }
+                }
+                drop_separator = true;
+            }
+            Part::Text(text) if drop_separator => {
+                let text = text.strip_prefix(SEPARATORS).unwrap_or(text);
+                result.push(Part::Text(text.to_string()));
+                drop_separator = false;
+            }
+            other => result.push(other.clone()),
+        }
+    }
+    result
+}
+
+/// Match a branch name against template parts, calling `found` with the values
+/// of each way it matches until `found` accepts one
+fn match_parts(
+    parts: &[Part],
+    rest: &str,
+    captures: Captures,
+    found: &mut dyn FnMut(Captures) -> bool,
+) -> bool {
+    match parts.split_first() {
+        None => rest.is_empty() && found(captures),
+        Some((Part::Text(text), tail)) => rest
+            .strip_prefix(text.as_str())
+            .is_some_and(|rest| match_parts(tail, rest, captures, found)),
+        Some((part, tail)) => rest
+            .char_indices()
+            .skip(1)
+            .map(|(index, _)| index)
+            .chain([rest.len()])
+            .filter(|&end| end > 0)
+            .any(|end| {
+                let mut captures = captures.clone();
+                captures.set(part, &rest[..end]);
+                match_parts(tail, &rest[end..], captures, found)
+            }),
+    }
+}
+
+/// Check a branch name follows the rules of `git check-ref-format`
+pub fn validate_branch_name(branch: &str) -> std::result::Result<(), String> {
+    let problem = if branch.is_empty() {
+        Some("is empty")
+    } else if branch.starts_with('/') || branch.ends_with('/') || branch.contains("//") {
+        Some("has an empty path component")
+    } else if branch.ends_with('.') {
+        Some("ends with '.'")
+    } else if branch.contains("..") {
+        Some("contains '..'")
+    } else if branch.contains("@{") || branch == "@" {
+        Some("contains '@{'")
+    } else if branch
+        .chars()
+        .any(|c| c.is_ascii_control() || " ~^:?*[\\".contains(c))
+    {
+        Some("contains a space or one of ~ ^ : ? * [ \\")
+    } else if branch
+        .split('/')
+        .any(|component| component.starts_with('.') || component.ends_with(".lock"))
+    {
+        Some("has a component starting with '.' or ending with '.lock'")
+    } else {
+        None
+    };
+    match problem {
+        Some(problem) => Err(format!("branch name '{branch}' {problem}")),
+        None => Ok(()),
+    }
+}
+
+/// Planned rename of one issue branch
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BranchRename {
+    /// Issue the branch is worked on
+    pub issue: String,
+    /// Branch name under the old template
+    pub from: String,
+    /// Branch name under the new template
+    pub to: String,
+}
+
+/// Branch renames moving existing issue branches to a new naming template
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BranchRenamePlan {
+    /// Template the branches are moved to
+    pub template: String,
+    /// Branches that are renamed
+    pub renames: Vec<BranchRename>,
+    /// Issues whose branches are left as they are, with the reason
+    pub skipped: Vec<BulkSkip>,
+    /// Whether the renames have been applied
+    pub applied: bool,
+}
+
+impl BranchRenamePlan {
+    /// Plan renaming the `branches` of a repository that belong to `issues`
+    /// under `from` to their names under `to`
+    ///
+    /// Issues without a branch under `from` are left out. Issues that belong
+    /// to another repository, and branches whose new name is taken, are
+    /// skipped.
+    pub fn new(
+        from: &IssueBranchNaming,
+        to: &IssueBranchNaming,
+        issues: &[Issue],
+        branches: &[String],
+    ) -> Self {
+        let mut renames = Vec::new();
+        let mut skipped = Vec::new();
+        for issue in issues {
+            let old = from.branch_for(&issue.name);
+            let new = to.branch_for(&issue.name);
+            if old == new || !branches.contains(&old) {
+                continue;
+            }
+            let reason = if let Some(repo) = IssueMetadata::from_content(&issue.content).repo {
+                Some(format!("belongs to repository '{repo}'"))
+            } else if branches.contains(&new) {
+                Some(format!("branch {new} already exists"))
+            } else {
+                None
+            };
+            match reason {
+                Some(reason) => skipped.push(BulkSkip {
+                    name: issue.name.clone(),
+                    reason,
+                }),
+                None => renames.push(BranchRename {
+                    issue: issue.name.clone(),
+                    from: old,
+                    to: new,
+                }),
+            }
+        }
+        Self {
+            template: to.template().to_string(),
+            renames,
+            skipped,
+            applied: false,
+        }
+    }
+
+    /// Rename every branch, or none of them
+    pub fn apply(&mut self, git: &GitOperations) -> Result<()> {
+        for (index, rename) in self.renames.iter().enumerate() {
+            if let Err(e) = git.rename_branch(&rename.from, &rename.to) {
+                for applied in self.renames[..index].iter().rev() {
+                    if let Err(undo_error) = git.rename_branch(&applied.to, &applied.from) {
+                        warn!(
+                            "Failed to rename branch {} back to {}: {}",
+                            applied.to, applied.from, undo_error
+                        );
+                    }
+                }
+                return Err(SwissArmyHammerError::Other(format!(
+                    "Failed to rename branch {}, no branches were renamed: {}",
+                    rename.from, e
+                )));
+            }
+        }
+        self.applied = true;
+        Ok(())
+    }
+}
+
+impl fmt::Display for BranchRenamePlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let verb = if self.applied {
+            "Renamed"
+        } else {
+            "Would rename"
+        };
+        let count = self.renames.len();
+        writeln!(
+            f,
+            "{verb} {count} issue {} to {}",
+            if count == 1 { "branch" } else { "branches" },
+            self.template
+        )?;
+
+        let width = self
+            .renames
+            .iter()
+            .map(|rename| rename.from.len())
+            .max()
+            .unwrap_or(0);
+        for rename in &self.renames {
+            writeln!(f, "  {:<width$}  -> {}", rename.from, rename.to)?;
+        }
+
+        if !self.skipped.is_empty() {
+            let width = self
+                .skipped
+                .iter()
+                .map(|skip| skip.name.len())
+                .max()
+                .unwrap_or(0);
+            writeln!(f, "Skipped {}:", self.skipped.len())?;
+            for skip in &self.skipped {
+                writeln!(f, "  {:<width$}  {}", skip.name, skip.reason)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn issue(name: &str, content: &str) -> Issue {
+        Issue {
+            name: name.to_string(),
+            content: content.to_string(),
+            completed: false,
+            file_path: PathBuf::from(format!("issues/{name}.md")),
+            created_at: chrono::Utc::now(),
+            source: None,
+            priority: None,
+            estimate: None,
+            related_memos: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_branch_names_round_trip() {
+        let default = IssueBranchNaming::default();
+        assert_eq!(
+            default.branch_for("000123_fix_login"),
+            "issue/000123_fix_login"
+        );
+        assert_eq!(
+            default.issue_name("issue/000123_fix_login").as_deref(),
+            Some("000123_fix_login")
+        );
+        assert_eq!(default.issue_name("main"), None);
+
+        let naming = IssueBranchNaming::new("feature/{number}-{slug}").unwrap();
+        for (name, branch) in [
+            ("000123_fix_login", "feature/000123-fix_login"),
+            ("fix_login", "feature/fix_login"),
+            ("000123", "feature/000123-000123"),
+            ("2024-07-001_fix_login", "feature/2024-07-001-fix_login"),
+            ("PROJ-12_fix-login", "feature/PROJ-12-fix-login"),
+        ] {
+            assert_eq!(naming.branch_for(name), branch);
+            assert_eq!(naming.issue_name(branch).as_deref(), Some(name), "{branch}");
+        }
+        assert_eq!(naming.issue_name("issue/000123_fix_login"), None);
+
+        let suffixed = IssueBranchNaming::new("{slug}_{number}").unwrap();
+        assert_eq!(suffixed.branch_for("fix_login"), "fix_login");
+        assert_eq!(suffixed.branch_for("000123_fix"), "fix_000123");
+        assert_eq!(
+            suffixed.issue_name("fix_000123").as_deref(),
+            Some("000123_fix")
+        );
+    }
+
+    #[test]
+    fn test_invalid_templates() {
+        for template in [
+            "feature/{slug}",
+            "issue/{title}",
+            "issue/{name",
+            "issue/{number}{slug}",
+            "issue {name}",
+            "issue/.{name}",
+            "issue//{name}",
+        ] {
+            assert!(IssueBranchNaming::new(template).is_err(), "{template}");
+        }
+        assert!(validate_branch_name("feature/PROJ-12-fix").is_ok());
+        assert!(validate_branch_name("feature/fix.lock").is_err());
+        assert!(validate_branch_name("feature/fix..login").is_err());
+    }
+
+    #[test]
+    fn test_branch_rename_plan() {
+        let from = IssueBranchNaming::default();
+        let to = IssueBranchNaming::new("feature/{number}-{slug}").unwrap();
+        let issues = [
+            issue("000001_fix_login", "# Fix login"),
+            issue("000002_no_branch", "# No branch yet"),
+            issue("000003_taken", "# Taken"),
+            issue("000004_elsewhere", "---\nrepo: api\n---\n# Elsewhere"),
+        ];
+        let branches: Vec<String> = [
+            "main",
+            "issue/000001_fix_login",
+            "issue/000003_taken",
+            "feature/000003-taken",
+            "issue/000004_elsewhere",
+        ]
+        .iter()
+        .map(|branch| branch.to_string())
+        .collect();
+
+        let plan = BranchRenamePlan::new(&from, &to, &issues, &branches);
+        assert_eq!(
+            plan.renames,
+            vec![BranchRename {
+                issue: "000001_fix_login".to_string(),
+                from: "issue/000001_fix_login".to_string(),
+                to: "feature/000001-fix_login".to_string(),
+            }]
+        );
+        let skipped: Vec<&str> = plan.skipped.iter().map(|skip| skip.name.as_str()).collect();
+        assert_eq!(skipped, ["000003_taken", "000004_elsewhere"]);
+        assert!(plan.to_string().starts_with("Would rename 1 issue branch"));
+    }
+}
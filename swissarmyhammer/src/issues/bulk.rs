@@ -0,0 +1,490 @@
+//! Changes applied to many issues at once
+//!
+//! A bulk change selects issues by name or by glob pattern, such as
+//! `*_stale_*`, and plans the change to each selected issue before any file
+//! is touched. The plan doubles as a pre-flight summary, and applying it is
+//! all or nothing: when an issue file can't be written, the files already
+//! changed are restored.
+//!
+//! - **complete** moves pending issues to their `complete` directory
+//! - **label** adds a label to the `labels` field of the front matter
+//! - **move** moves issues to another issues directory, keeping completed
+//!   issues completed
+
+use crate::error::{Result, SwissArmyHammerError};
+use crate::issues::priority::{split_front_matter, string_list};
+use crate::issues::Issue;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+/// Change applied to every selected issue
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "lowercase")]
+pub enum BulkAction {
+    /// Mark pending issues complete
+    Complete,
+    /// Add a label to the issues' front matter
+    Label {
+        /// Label to add
+        label: String,
+    },
+    /// Move issues to another issues directory
+    Move {
+        /// Issues directory to move the issues to
+        to: PathBuf,
+    },
+}
+
+impl fmt::Display for BulkAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BulkAction::Complete => write!(f, "complete"),
+            BulkAction::Label { label } => write!(f, "label '{label}'"),
+            BulkAction::Move { to } => write!(f, "move to {}", to.display()),
+        }
+    }
+}
+
+/// Planned change to a single issue file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BulkChange {
+    /// Issue name
+    pub name: String,
+    /// Current issue file
+    pub from: PathBuf,
+    /// Issue file after the change, the same as `from` when only the content changes
+    pub to: PathBuf,
+    /// Content before the change, restored when the plan is rolled back
+    #[serde(skip)]
+    original: String,
+    /// Content after the change
+    #[serde(skip)]
+    updated: String,
+}
+
+impl BulkChange {
+    /// Write the changed issue file and remove the old one
+    fn apply(&self) -> Result<()> {
+        if self.to != self.from && self.to.exists() {
+            return Err(SwissArmyHammerError::Other(format!(
+                "{} already exists",
+                self.to.display()
+            )));
+        }
+        if let Some(parent) = self.to.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&self.to, &self.updated)?;
+        if self.to != self.from {
+            if let Err(e) = fs::remove_file(&self.from) {
+                // Leave the issue only at its old path
+                let _ = fs::remove_file(&self.to);
+                return Err(e.into());
+            }
+        }
+        Ok(())
+    }
+
+    /// Put the issue file back the way it was before [`BulkChange::apply`]
+    fn undo(&self) -> Result<()> {
+        fs::write(&self.from, &self.original)?;
+        if self.to != self.from && self.to.exists() {
+            fs::remove_file(&self.to)?;
+        }
+        Ok(())
+    }
+}
+
+/// Selected issue left unchanged, with the reason
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BulkSkip {
+    /// Issue name
+    pub name: String,
+    /// Why the issue is left unchanged
+    pub reason: String,
+}
+
+/// Changes a bulk action makes to the selected issues
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BulkPlan {
+    /// Change applied to every selected issue
+    #[serde(flatten)]
+    pub action: BulkAction,
+    /// Issue files that change, ordered by issue name
+    pub changes: Vec<BulkChange>,
+    /// Selected issues that stay as they are
+    pub skipped: Vec<BulkSkip>,
+    /// Whether the changes have been applied
+    pub applied: bool,
+}
+
+impl BulkPlan {
+    /// Plan an action for the selected issues
+    ///
+    /// Issues the action doesn't apply to, such as completed issues for
+    /// [`BulkAction::Complete`], are skipped rather than failing the plan.
+    pub fn new(action: BulkAction, issues: &[Issue]) -> Result<Self> {
+        if let BulkAction::Label { label } = &action {
+            if label.trim().is_empty() {
+                return Err(SwissArmyHammerError::Other(
+                    "Label cannot be empty".to_string(),
+                ));
+            }
+        }
+
+        let mut changes = Vec::new();
+        let mut skipped = Vec::new();
+        for issue in issues {
+            match plan_change(&action, issue) {
+                Ok(change) => changes.push(change),
+                Err(reason) => skipped.push(BulkSkip {
+                    name: issue.name.clone(),
+                    reason,
+                }),
+            }
+        }
+
+        Ok(Self {
+            action,
+            changes,
+            skipped,
+            applied: false,
+        })
+    }
+
+    /// Apply every change, or none of them
+    ///
+    /// When a change fails, the changes already applied are undone and the
+    /// error names the issue that failed.
+    pub fn apply(&mut self) -> Result<()> {
+        for (index, change) in self.changes.iter().enumerate() {
+            if let Err(e) = change.apply() {
+                for applied in self.changes[..index].iter().rev() {
+                    if let Err(undo_error) = applied.undo() {
+                        warn!(
+                            "Failed to restore issue '{}' at {}: {}",
+                            applied.name,
+                            applied.from.display(),
+                            undo_error
+                        );
+                    }
+                }
+                return Err(SwissArmyHammerError::Other(format!(
+                    "Failed to {} issue '{}', no issues were changed: {}",
+                    self.action, change.name, e
+                )));
+            }
+        }
+        self.applied = true;
+        Ok(())
+    }
+}
+
+impl fmt::Display for BulkPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let verb = if self.applied { "Applied" } else { "Would" };
+        let plural = |count: usize| if count == 1 { "issue" } else { "issues" };
+        writeln!(
+            f,
+            "{verb} {}: {} {}",
+            self.action,
+            self.changes.len(),
+            plural(self.changes.len())
+        )?;
+
+        let width = self
+            .changes
+            .iter()
+            .map(|change| change.name.len())
+            .chain(self.skipped.iter().map(|skip| skip.name.len()))
+            .max()
+            .unwrap_or(0);
+        for change in &self.changes {
+            if change.to == change.from {
+                writeln!(f, "  {:<width$}  {}", change.name, change.from.display())?;
+            } else {
+                writeln!(
+                    f,
+                    "  {:<width$}  {} -> {}",
+                    change.name,
+                    change.from.display(),
+                    change.to.display()
+                )?;
+            }
+        }
+
+        if !self.skipped.is_empty() {
+            writeln!(
+                f,
+                "Skipped {} {}:",
+                self.skipped.len(),
+                plural(self.skipped.len())
+            )?;
+            for skip in &self.skipped {
+                writeln!(f, "  {:<width$}  {}", skip.name, skip.reason)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Plan the change to one issue, or the reason it is skipped
+fn plan_change(action: &BulkAction, issue: &Issue) -> std::result::Result<BulkChange, String> {
+    let file_name = issue
+        .file_path
+        .file_name()
+        .ok_or_else(|| format!("invalid issue path {}", issue.file_path.display()))?;
+
+    let (to, updated) = match action {
+        BulkAction::Complete => {
+            if issue.completed {
+                return Err("already complete".to_string());
+            }
+            let issues_dir = issue.file_path.parent().unwrap_or(Path::new(""));
+            (
+                issues_dir.join("complete").join(file_name),
+                issue.content.clone(),
+            )
+        }
+        BulkAction::Label { label } => (
+            issue.file_path.clone(),
+            add_label(&issue.content, label.trim())?,
+        ),
+        BulkAction::Move { to } => {
+            let to = if issue.completed {
+                to.join("complete").join(file_name)
+            } else {
+                to.join(file_name)
+            };
+            if to == issue.file_path {
+                return Err("already in that directory".to_string());
+            }
+            (to, issue.content.clone())
+        }
+    };
+
+    if to != issue.file_path && to.exists() {
+        return Err(format!("{} already exists", to.display()));
+    }
+    Ok(BulkChange {
+        name: issue.name.clone(),
+        from: issue.file_path.clone(),
+        to,
+        original: issue.content.clone(),
+        updated,
+    })
+}
+
+/// Issue content with a label added to the `labels` front matter field
+fn add_label(content: &str, label: &str) -> std::result::Result<String, String> {
+    let (yaml, body) = split_front_matter(content).unwrap_or(("", content));
+    let mut front_matter = if yaml.trim().is_empty() {
+        serde_yaml::Mapping::new()
+    } else {
+        serde_yaml::from_str::<serde_yaml::Mapping>(yaml)
+            .map_err(|e| format!("front matter can't be read: {e}"))?
+    };
+
+    let mut labels = string_list(front_matter.get("labels"));
+    if labels.iter().any(|existing| existing == label) {
+        return Err(format!("already labeled '{label}'"));
+    }
+    labels.push(label.to_string());
+    front_matter.insert(
+        serde_yaml::Value::from("labels"),
+        serde_yaml::Value::Sequence(labels.into_iter().map(serde_yaml::Value::from).collect()),
+    );
+
+    let yaml = serde_yaml::to_string(&front_matter)
+        .map_err(|e| format!("front matter can't be written: {e}"))?;
+    Ok(format!("---\n{yaml}---\n{body}"))
+}
+
+/// Issues whose names match any of the patterns, ordered by name
+///
+/// A pattern is an issue name or a glob such as `*_stale_*`. A plain name
+/// that matches no issue is an error, so a typo is caught before anything
+/// changes; a glob may match nothing.
+pub fn select_issues(issues: &[Issue], patterns: &[String]) -> Result<Vec<Issue>> {
+    let mut selected: BTreeMap<(String, PathBuf), Issue> = BTreeMap::new();
+    for pattern in patterns {
+        let pattern = pattern.trim();
+        if pattern.is_empty() {
+            continue;
+        }
+
+        let matches: Vec<&Issue> = if pattern.contains(['*', '?', '[']) {
+            let glob = glob::Pattern::new(pattern).map_err(|e| {
+                SwissArmyHammerError::Other(format!("Invalid issue pattern '{pattern}': {e}"))
+            })?;
+            issues
+                .iter()
+                .filter(|issue| glob.matches(&issue.name))
+                .collect()
+        } else {
+            let matches: Vec<&Issue> = issues
+                .iter()
+                .filter(|issue| issue.name == pattern)
+                .collect();
+            if matches.is_empty() {
+                return Err(SwissArmyHammerError::IssueNotFound(pattern.to_string()));
+            }
+            matches
+        };
+
+        for issue in matches {
+            selected.insert((issue.name.clone(), issue.file_path.clone()), issue.clone());
+        }
+    }
+    Ok(selected.into_values().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::issues::IssueMetadata;
+    use chrono::Utc;
+    use tempfile::TempDir;
+
+    fn write_issue(dir: &Path, name: &str, content: &str, completed: bool) -> Issue {
+        let dir = if completed {
+            dir.join("complete")
+        } else {
+            dir.to_path_buf()
+        };
+        fs::create_dir_all(&dir).unwrap();
+        let file_path = dir.join(format!("{name}.md"));
+        fs::write(&file_path, content).unwrap();
+        Issue {
+            name: name.to_string(),
+            content: content.to_string(),
+            completed,
+            file_path,
+            created_at: Utc::now(),
+            source: None,
+            priority: None,
+            estimate: None,
+            related_memos: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_select_issues_by_name_and_glob() {
+        let temp = TempDir::new().unwrap();
+        let issues = vec![
+            write_issue(temp.path(), "000001_stale_docs", "docs", false),
+            write_issue(temp.path(), "000002_login", "login", false),
+            write_issue(temp.path(), "000003_stale_ci", "ci", true),
+        ];
+
+        let selected = select_issues(
+            &issues,
+            &["*_stale_*".to_string(), "000002_login".to_string()],
+        )
+        .unwrap();
+        let names: Vec<&str> = selected.iter().map(|issue| issue.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["000001_stale_docs", "000002_login", "000003_stale_ci"]
+        );
+
+        assert!(select_issues(&issues, &["*_nothing_*".to_string()])
+            .unwrap()
+            .is_empty());
+        assert!(select_issues(&issues, &["000009_typo".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_complete_plan_skips_completed_issues() {
+        let temp = TempDir::new().unwrap();
+        let issues = vec![
+            write_issue(temp.path(), "000001_stale", "stale", false),
+            write_issue(temp.path(), "000002_done", "done", true),
+        ];
+
+        let mut plan = BulkPlan::new(BulkAction::Complete, &issues).unwrap();
+        assert_eq!(plan.changes.len(), 1);
+        assert_eq!(plan.skipped[0].reason, "already complete");
+        assert!(plan.to_string().starts_with("Would complete: 1 issue"));
+
+        plan.apply().unwrap();
+        assert!(plan.applied);
+        assert!(!temp.path().join("000001_stale.md").exists());
+        assert_eq!(
+            fs::read_to_string(temp.path().join("complete/000001_stale.md")).unwrap(),
+            "stale"
+        );
+    }
+
+    #[test]
+    fn test_label_plan_adds_to_front_matter() {
+        let temp = TempDir::new().unwrap();
+        let issues = vec![
+            write_issue(temp.path(), "000001_plain", "# Plain\n", false),
+            write_issue(
+                temp.path(),
+                "000002_planned",
+                "---\npriority: high\nlabels: backend\n---\n# Planned\n",
+                false,
+            ),
+            write_issue(
+                temp.path(),
+                "000003_labeled",
+                "---\nlabels: [stale]\n---\n",
+                false,
+            ),
+        ];
+        let action = BulkAction::Label {
+            label: "stale".to_string(),
+        };
+
+        let mut plan = BulkPlan::new(action, &issues).unwrap();
+        assert_eq!(plan.skipped[0].name, "000003_labeled");
+        plan.apply().unwrap();
+
+        let plain = fs::read_to_string(temp.path().join("000001_plain.md")).unwrap();
+        assert_eq!(plain, "---\nlabels:\n- stale\n---\n# Plain\n");
+        let planned = fs::read_to_string(temp.path().join("000002_planned.md")).unwrap();
+        let metadata = IssueMetadata::from_content(&planned);
+        assert_eq!(metadata.labels, vec!["backend", "stale"]);
+        assert!(metadata.priority.is_some());
+        assert!(planned.ends_with("---\n# Planned\n"));
+    }
+
+    #[test]
+    fn test_move_plan_rolls_back_on_failure() {
+        let temp = TempDir::new().unwrap();
+        let source = temp.path().join("issues");
+        let target = temp.path().join("archive");
+        let issues = vec![
+            write_issue(&source, "000001_first", "first", false),
+            write_issue(&source, "000002_done", "done", true),
+        ];
+        let action = BulkAction::Move { to: target.clone() };
+
+        let mut plan = BulkPlan::new(action.clone(), &issues).unwrap();
+        assert_eq!(plan.changes[1].to, target.join("complete/000002_done.md"));
+
+        // A file appearing after planning makes the second move fail
+        fs::create_dir_all(target.join("complete")).unwrap();
+        fs::write(target.join("complete/000002_done.md"), "other").unwrap();
+        assert!(plan.apply().is_err());
+        assert!(!plan.applied);
+        assert_eq!(
+            fs::read_to_string(source.join("000001_first.md")).unwrap(),
+            "first"
+        );
+        assert!(!target.join("000001_first.md").exists());
+
+        fs::remove_file(target.join("complete/000002_done.md")).unwrap();
+        let mut plan = BulkPlan::new(action, &issues).unwrap();
+        plan.apply().unwrap();
+        assert!(target.join("000001_first.md").exists());
+        assert!(target.join("complete/000002_done.md").exists());
+        assert!(!source.join("complete/000002_done.md").exists());
+    }
+}
@@ -0,0 +1,308 @@
+//! Checklists of tasks in issue bodies
+//!
+//! The task list items in an issue's markdown are its checklist:
+//!
+//! ```markdown
+//! # Add login
+//!
+//! - [x] Design the form
+//! - [ ] Validate the password
+//! - [ ] Write tests
+//! ```
+//!
+//! Items are numbered from 1 in the order they appear, skipping any in code
+//! blocks. `issue check <name> <item>` ticks or unticks an item, named by its
+//! number or by words of its text, and `issue list` shows how much of each
+//! issue's checklist is done, so a workflow working an issue can record and
+//! read its progress task by task.
+
+use crate::error::{Result, SwissArmyHammerError};
+use crate::issues::priority::split_front_matter;
+use crate::issues::{Issue, IssueStorage};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A task list item in an issue body
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChecklistItem {
+    /// Position of the item in the checklist, from 1
+    pub number: usize,
+    /// Text of the item after its checkbox
+    pub text: String,
+    /// Whether the item is ticked
+    pub done: bool,
+    /// Byte offset of the checkbox mark in the content
+    #[serde(skip)]
+    mark: usize,
+}
+
+/// How many of the items of a checklist are done
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChecklistProgress {
+    /// Items ticked
+    pub done: usize,
+    /// Items in the checklist
+    pub total: usize,
+}
+
+impl ChecklistProgress {
+    /// Whether there is no checklist
+    pub fn is_empty(&self) -> bool {
+        self.total == 0
+    }
+
+    /// Percent of the items done, rounded down, or `None` without a checklist
+    pub fn percent(&self) -> Option<usize> {
+        (self.total > 0).then(|| self.done * 100 / self.total)
+    }
+}
+
+impl fmt::Display for ChecklistProgress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}/{} tasks ({}%)",
+            self.done,
+            self.total,
+            self.percent().unwrap_or(0)
+        )
+    }
+}
+
+/// The task list items of an issue
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Checklist {
+    /// The items in the order they appear
+    pub items: Vec<ChecklistItem>,
+}
+
+impl Checklist {
+    /// The checklist of issue content, ignoring front matter and code blocks
+    pub fn parse(content: &str) -> Self {
+        let body = split_front_matter(content).map_or(content, |(_, body)| body);
+        let mut offset = content.len() - body.len();
+        let mut fence: Option<&str> = None;
+        let mut items = Vec::new();
+
+        for line in body.split_inclusive('\n') {
+            let start = offset;
+            offset += line.len();
+            let trimmed = line.trim_start();
+            let indent = line.len() - trimmed.len();
+
+            if let Some(marker) = ["```", "~~~"]
+                .into_iter()
+                .find(|marker| trimmed.starts_with(marker))
+            {
+                match fence {
+                    Some(open) if open == marker => fence = None,
+                    Some(_) => {}
+                    None => fence = Some(marker),
+                }
+                continue;
+            }
+            if fence.is_some() {
+                continue;
+            }
+
+            let Some(after_bullet) = strip_bullet(trimmed) else {
+                continue;
+            };
+            let rest = after_bullet.trim_start();
+            let done = match rest.get(..3) {
+                Some("[ ]") => false,
+                Some("[x]") | Some("[X]") => true,
+                _ => continue,
+            };
+            let text = rest[3..].trim();
+            if !rest[3..].starts_with([' ', '\t']) || text.is_empty() {
+                continue;
+            }
+            let mark = start + indent + (trimmed.len() - rest.len()) + 1;
+            items.push(ChecklistItem {
+                number: items.len() + 1,
+                text: text.to_string(),
+                done,
+                mark,
+            });
+        }
+
+        Self { items }
+    }
+
+    /// How many of the items are done
+    pub fn progress(&self) -> ChecklistProgress {
+        ChecklistProgress {
+            done: self.items.iter().filter(|item| item.done).count(),
+            total: self.items.len(),
+        }
+    }
+
+    /// The item named by its number or by words of its text
+    ///
+    /// Text matches ignore case. When several items contain the text, only an
+    /// item whose whole text it is can be named by it.
+    pub fn find(&self, item: &str) -> Result<&ChecklistItem> {
+        let item = item.trim();
+        if let Ok(number) = item.parse::<usize>() {
+            return self
+                .items
+                .iter()
+                .find(|candidate| candidate.number == number)
+                .ok_or_else(|| {
+                    SwissArmyHammerError::Other(format!(
+                        "No task {number}: the checklist has {} tasks",
+                        self.items.len()
+                    ))
+                });
+        }
+
+        let wanted = item.to_lowercase();
+        let matches: Vec<&ChecklistItem> = self
+            .items
+            .iter()
+            .filter(|candidate| candidate.text.to_lowercase().contains(&wanted))
+            .collect();
+        match matches.as_slice() {
+            [] => Err(SwissArmyHammerError::Other(format!(
+                "No task matches '{item}'"
+            ))),
+            [only] => Ok(only),
+            several => several
+                .iter()
+                .find(|candidate| candidate.text.to_lowercase() == wanted)
+                .copied()
+                .ok_or_else(|| {
+                    SwissArmyHammerError::Other(format!(
+                        "'{item}' matches several tasks: {}",
+                        several
+                            .iter()
+                            .map(|candidate| format!("{}. {}", candidate.number, candidate.text))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ))
+                }),
+        }
+    }
+}
+
+/// The rest of a line after a list bullet, `-`, `*`, `+` or `1.`
+fn strip_bullet(line: &str) -> Option<&str> {
+    let rest = match line.strip_prefix(['-', '*', '+']) {
+        Some(rest) => rest,
+        None => {
+            let digits = line.len() - line.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+            if digits == 0 {
+                return None;
+            }
+            line[digits..].strip_prefix(['.', ')'])?
+        }
+    };
+    rest.starts_with([' ', '\t']).then_some(rest)
+}
+
+/// Issue content with a checklist item ticked, unticked, or toggled when
+/// `done` is `None`, and the item as it now is
+pub fn set_checklist_item(
+    content: &str,
+    item: &str,
+    done: Option<bool>,
+) -> Result<(String, ChecklistItem)> {
+    let checklist = Checklist::parse(content);
+    if checklist.items.is_empty() {
+        return Err(SwissArmyHammerError::Other(
+            "The issue has no checklist; add tasks as '- [ ] task' lines".to_string(),
+        ));
+    }
+    let mut item = checklist.find(item)?.clone();
+    item.done = done.unwrap_or(!item.done);
+
+    let mut content = content.to_string();
+    content.replace_range(item.mark..item.mark + 1, if item.done { "x" } else { " " });
+    Ok((content, item))
+}
+
+/// Tick, untick or toggle an item of an issue's checklist, returning the
+/// updated issue and the item
+pub async fn check_issue_item(
+    storage: &dyn IssueStorage,
+    name: &str,
+    item: &str,
+    done: Option<bool>,
+) -> Result<(Issue, ChecklistItem)> {
+    let issue = storage.get_issue(name).await?;
+    let (content, item) = set_checklist_item(&issue.content, item, done)?;
+    if content == issue.content {
+        return Ok((issue, item));
+    }
+    Ok((storage.update_issue(name, content).await?, item))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::issues::FileSystemIssueStorage;
+    use tempfile::TempDir;
+
+    const CONTENT: &str = "---\npriority: high\n---\n# Add login\n\n- [x] Design the form\n- [ ] Validate the password\n  * [ ] Write tests\n1. [X] Review the design\n\n```markdown\n- [ ] Not a task\n```\n- [] Not a task either\n";
+
+    #[test]
+    fn test_parse_checklist() {
+        let checklist = Checklist::parse(CONTENT);
+        let texts: Vec<&str> = checklist.items.iter().map(|i| i.text.as_str()).collect();
+        assert_eq!(
+            texts,
+            [
+                "Design the form",
+                "Validate the password",
+                "Write tests",
+                "Review the design"
+            ]
+        );
+        assert_eq!(checklist.items[3].number, 4);
+        let progress = checklist.progress();
+        assert_eq!(progress, ChecklistProgress { done: 2, total: 4 });
+        assert_eq!(progress.to_string(), "2/4 tasks (50%)");
+        assert_eq!(Checklist::parse("# No tasks").progress().percent(), None);
+    }
+
+    #[test]
+    fn test_set_checklist_item() {
+        let (content, item) = set_checklist_item(CONTENT, "password", None).unwrap();
+        assert!(item.done);
+        assert!(content.contains("- [x] Validate the password"));
+        assert!(content.contains("```markdown\n- [ ] Not a task"));
+
+        let (content, item) = set_checklist_item(&content, "4", Some(false)).unwrap();
+        assert_eq!(item.text, "Review the design");
+        assert!(content.contains("1. [ ] Review the design"));
+        assert_eq!(Checklist::parse(&content).progress().done, 2);
+
+        // Text matching several tasks has to name one exactly
+        let error = set_checklist_item(CONTENT, "the", None).unwrap_err();
+        assert!(error.to_string().contains("matches several tasks"));
+        assert!(set_checklist_item(CONTENT, "Write tests", Some(true)).is_ok());
+        assert!(set_checklist_item(CONTENT, "9", None).is_err());
+        assert!(set_checklist_item("# No tasks", "1", None).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_check_issue_item() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = FileSystemIssueStorage::new(temp_dir.path().join("issues")).unwrap();
+        storage
+            .create_issue("login".to_string(), CONTENT.to_string())
+            .await
+            .unwrap();
+
+        let (issue, item) = check_issue_item(&storage, "login", "write tests", None)
+            .await
+            .unwrap();
+        assert_eq!(item.number, 3);
+        assert!(issue.content.contains("  * [x] Write tests"));
+        assert_eq!(
+            Checklist::parse(&storage.get_issue("login").await.unwrap().content).progress(),
+            ChecklistProgress { done: 3, total: 4 }
+        );
+    }
+}
@@ -1,15 +1,25 @@
 use crate::common::generate_monotonic_ulid_string;
 use crate::config::Config;
 use crate::error::{Result, SwissArmyHammerError};
+use crate::events::{event_bus, Event};
+use crate::fs_utils::{FileSystem, StdFileSystem};
+use crate::history::{ChangeKind, ContentChange, ContentHistory};
+use crate::issues::index::{IssueIndex, IssueSummary, ISSUE_INDEX_FILE};
+use crate::issues::numbering::IssueNumbering;
+use crate::issues::priority::{next_issue, IssueMetadata, IssuePriority, IssueSort};
 use crate::mcp::types::IssueName;
+use crate::security::{validate_path, PathPolicy};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::fs;
-use std::io;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use tokio::sync::Mutex;
 use tracing::debug;
 
+/// Directory, relative to the parent of an issues directory, the index and
+/// content history of the issues are kept in
+pub const ISSUE_STATE_DIR: &str = ".swissarmyhammer/issues";
+
 // IssueNumber type eliminated - we now use issue names (filename without .md) as the primary identifier
 
 /// Represents an issue in the tracking system
@@ -25,6 +35,18 @@ pub struct Issue {
     pub file_path: PathBuf,
     /// When the issue was created
     pub created_at: DateTime<Utc>,
+    /// Issues directory the issue was loaded from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<PathBuf>,
+    /// Priority from the issue's front matter
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<IssuePriority>,
+    /// Estimate from the issue's front matter
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimate: Option<u32>,
+    /// Memos named by the issue's front matter
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub related_memos: Vec<String>,
 }
 
 /// Represents the current state of the issue system
@@ -42,6 +64,10 @@ pub trait IssueStorage: Send + Sync {
     /// List all issues (both pending and completed)
     async fn list_issues(&self) -> Result<Vec<Issue>>;
 
+    /// List the name, state and front matter fields of all issues, without
+    /// reading the content of issues that are already indexed
+    async fn list_issue_summaries(&self) -> Result<Vec<IssueSummary>>;
+
     /// Get a specific issue by name
     async fn get_issue(&self, name: &str) -> Result<Issue>;
 
@@ -54,6 +80,9 @@ pub trait IssueStorage: Send + Sync {
     /// Mark an issue as complete (move to complete directory) by name
     async fn mark_complete(&self, name: &str) -> Result<Issue>;
 
+    /// Reopen a completed issue (move back to pending directory) by name
+    async fn reopen_issue(&self, name: &str) -> Result<Issue>;
+
     /// Batch operations for better performance
     /// Create multiple issues at once
     async fn create_issues_batch(&self, issues: Vec<(String, String)>) -> Result<Vec<Issue>>;
@@ -67,8 +96,10 @@ pub trait IssueStorage: Send + Sync {
     /// Mark multiple issues as complete by name
     async fn mark_complete_batch(&self, names: Vec<&str>) -> Result<Vec<Issue>>;
 
-    /// Get the next pending issue (first alphabetically)
-    /// Returns None if no pending issues exist
+    /// Get the next pending issue to work on
+    ///
+    /// Returns the highest priority pending issue, first by name among equal
+    /// priorities, or None if no pending issues exist
     async fn get_next_issue(&self) -> Result<Option<Issue>>;
 
     // Type-safe methods using IssueName
@@ -96,6 +127,12 @@ pub trait IssueStorage: Send + Sync {
 
     /// Mark multiple issues as complete by IssueName
     async fn mark_complete_batch_by_name(&self, names: Vec<&IssueName>) -> Result<Vec<Issue>>;
+
+    /// Get the changes made to an issue's content, oldest first
+    async fn issue_history(&self, name: &str) -> Result<Vec<ContentChange>>;
+
+    /// Restore an issue's content to a version from its history
+    async fn restore_issue(&self, name: &str, version: usize) -> Result<Issue>;
 }
 
 /// File system implementation of issue storage
@@ -105,24 +142,173 @@ pub struct FileSystemIssueStorage {
     /// Mutex to ensure thread-safe issue creation and prevent race conditions
     /// when multiple threads attempt to create issues simultaneously
     creation_lock: Mutex<()>,
+    /// File system issue files are read from and written to
+    fs: Arc<dyn FileSystem>,
+    /// Identifier scheme for new issues, read from the configuration when unset
+    numbering: Option<IssueNumbering>,
 }
 
 impl FileSystemIssueStorage {
     /// Create a new FileSystemIssueStorage instance
     pub fn new(issues_dir: PathBuf) -> Result<Self> {
+        Self::with_fs(issues_dir, Arc::new(StdFileSystem))
+    }
+
+    /// Create a FileSystemIssueStorage instance on a specific file system
+    ///
+    /// Pass an [`OverlayFileSystem`](crate::fs_utils::OverlayFileSystem) to
+    /// simulate changes to the issues without writing them to disk.
+    pub fn with_fs(issues_dir: PathBuf, fs: Arc<dyn FileSystem>) -> Result<Self> {
         let completed_dir = issues_dir.join("complete");
 
         // Create directories if they don't exist
-        fs::create_dir_all(&issues_dir).map_err(SwissArmyHammerError::Io)?;
-        fs::create_dir_all(&completed_dir).map_err(SwissArmyHammerError::Io)?;
+        fs.create_dir_all(&issues_dir)?;
+        fs.create_dir_all(&completed_dir)?;
 
-        Ok(Self {
+        let storage = Self {
             state: IssueState {
                 issues_dir,
                 completed_dir,
             },
             creation_lock: Mutex::new(()),
-        })
+            fs,
+            numbering: None,
+        };
+        storage.ignore_state_dirs();
+        Ok(storage)
+    }
+
+    /// Have git ignore the state directories of issue storages, so the index
+    /// and history never show up as untracked files
+    fn ignore_state_dirs(&self) {
+        let state_dir = self.state_dir();
+        let Some(root) = state_dir.parent() else {
+            return;
+        };
+        let gitignore = root.join(".gitignore");
+        if self.fs.exists(&gitignore) {
+            return;
+        }
+        let written = self
+            .fs
+            .create_dir_all(root)
+            .and_then(|()| self.fs.write(&gitignore, "*\n"));
+        if let Err(e) = written {
+            debug!("Failed to write {}: {}", gitignore.display(), e);
+        }
+    }
+
+    /// Name new issues under `numbering` instead of the configured scheme
+    pub fn with_numbering(mut self, numbering: IssueNumbering) -> Self {
+        self.numbering = Some(numbering);
+        self
+    }
+
+    /// Directory pending issues are stored in
+    pub fn issues_dir(&self) -> &Path {
+        &self.state.issues_dir
+    }
+
+    /// Directory holding the index and content history of this storage
+    ///
+    /// `.swissarmyhammer/issues/<name>` beside the issues directory, so only
+    /// issue files are written to the tracked issues directory and git sees
+    /// no changes from listing issues.
+    pub fn state_dir(&self) -> PathBuf {
+        let issues_dir = &self.state.issues_dir;
+        let name = issues_dir
+            .file_name()
+            .unwrap_or_else(|| std::ffi::OsStr::new("issues"));
+        issues_dir
+            .parent()
+            .unwrap_or_else(|| Path::new(""))
+            .join(ISSUE_STATE_DIR)
+            .join(name)
+    }
+
+    /// Directory holding the content history of each issue
+    pub fn history_dir(&self) -> PathBuf {
+        self.state_dir().join("history")
+    }
+
+    /// Change logs of the issues in this storage
+    fn history(&self) -> ContentHistory {
+        ContentHistory::new(self.history_dir(), self.fs.clone())
+    }
+
+    /// File holding the index of the issues in this storage
+    pub fn index_path(&self) -> PathBuf {
+        self.state_dir().join(ISSUE_INDEX_FILE)
+    }
+
+    /// Summaries of every issue file, read through the index
+    ///
+    /// Files the index has no current entry for are parsed and indexed, and
+    /// entries of files that are gone are dropped. The index is only written
+    /// when it changed.
+    fn indexed_summaries(&self) -> Result<Vec<IssueSummary>> {
+        let index_path = self.index_path();
+        let mut index = IssueIndex::load(self.fs.as_ref(), &index_path);
+        let files = self.issue_files(&self.state.issues_dir)?;
+        let mut changed = index.retain(&files);
+
+        let mut summaries = Vec::with_capacity(files.len());
+        for path in &files {
+            let modified = self.fs.modified(path);
+            if let Some(summary) = modified.and_then(|modified| index.fresh(path, modified)) {
+                summaries.push(summary.clone());
+                continue;
+            }
+            match self.parse_issue_from_file(path) {
+                Ok(issue) => {
+                    let summary = IssueSummary::from(&issue);
+                    if let Some(modified) = modified {
+                        index.insert(summary.clone(), modified);
+                        changed = true;
+                    }
+                    summaries.push(summary);
+                }
+                Err(e) => {
+                    debug!("Failed to parse issue from {}: {}", path.display(), e);
+                }
+            }
+        }
+
+        if changed {
+            if let Err(e) = index.save(self.fs.as_ref(), &index_path) {
+                debug!(
+                    "Failed to write issue index {}: {}",
+                    index_path.display(),
+                    e
+                );
+            }
+        }
+        summaries.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(summaries)
+    }
+
+    /// Update the index entry of an issue that was just written, dropping
+    /// the entry of the path it was moved from
+    ///
+    /// Failing to update the index never fails the change; the next listing
+    /// notices the file changed and reads it again.
+    fn reindex_issue(&self, issue: &Issue, moved_from: Option<&Path>) {
+        let index_path = self.index_path();
+        let mut index = IssueIndex::load(self.fs.as_ref(), &index_path);
+        if let Some(from) = moved_from {
+            index.remove(from);
+        }
+        match self.fs.modified(&issue.file_path) {
+            Some(modified) => index.insert(IssueSummary::from(issue), modified),
+            None => index.remove(&issue.file_path),
+        }
+        if let Err(e) = index.save(self.fs.as_ref(), &index_path) {
+            debug!(
+                "Failed to write issue index {}: {}",
+                index_path.display(),
+                e
+            );
+        }
     }
 
     /// Create a new FileSystemIssueStorage instance with default directory
@@ -158,6 +344,8 @@ impl FileSystemIssueStorage {
     /// assert_eq!(issue.name.as_str(), "000123_bug_fix");
     /// ```
     fn parse_issue_from_file(&self, path: &Path) -> Result<Issue> {
+        self.validate_issue_path(path)?;
+
         let filename = path
             .file_stem()
             .ok_or_else(|| {
@@ -181,7 +369,7 @@ impl FileSystemIssueStorage {
         let name = filename.to_string();
 
         // Read file content
-        let content = fs::read_to_string(path).map_err(SwissArmyHammerError::Io)?;
+        let content = self.fs.read_to_string(path)?;
 
         // Determine if completed based on path - only mark as completed if directly in the
         // specific completed directory, not just any directory named "complete"
@@ -194,19 +382,33 @@ impl FileSystemIssueStorage {
         let created_at = path
             .metadata()
             .and_then(|m| m.created())
-            .or_else(|_| path.metadata().and_then(|m| m.modified()))
+            .ok()
+            .or_else(|| self.fs.modified(path))
             .map(DateTime::<Utc>::from)
-            .unwrap_or_else(|_| Utc::now());
+            .unwrap_or_else(Utc::now);
 
+        let metadata = IssueMetadata::from_content(&content);
         Ok(Issue {
             name,
             content,
             completed,
             file_path: path.to_path_buf(),
             created_at,
+            source: Some(self.state.issues_dir.clone()),
+            priority: metadata.priority,
+            estimate: metadata.estimate,
+            related_memos: metadata.related_memos,
         })
     }
 
+    /// Check that a path is allowed by the global [`PathPolicy`]
+    ///
+    /// Every issue file read or written goes through this check, so a symlink or
+    /// traversal can't reach files outside the issues directory.
+    fn validate_issue_path(&self, path: &Path) -> Result<PathBuf> {
+        validate_path(path, &self.state.issues_dir, PathPolicy::global())
+    }
+
     /// List issues in a directory
     ///
     /// Scans a directory for issue files and returns a vector of parsed Issues.
@@ -234,29 +436,48 @@ impl FileSystemIssueStorage {
     /// }
     /// ```
     fn list_issues_in_dir(&self, dir: &Path) -> Result<Vec<Issue>> {
-        if !dir.exists() {
-            return Ok(vec![]);
+        let mut issues = Vec::new();
+
+        for path in self.issue_files(dir)? {
+            match self.parse_issue_from_file(&path) {
+                Ok(issue) => issues.push(issue),
+                Err(e) => {
+                    debug!("Failed to parse issue from {}: {}", path.display(), e);
+                }
+            }
         }
 
-        let mut issues = Vec::new();
+        // Sort by name
+        issues.sort_by(|a, b| a.name.as_str().cmp(b.name.as_str()));
 
-        let entries = fs::read_dir(dir).map_err(SwissArmyHammerError::Io)?;
+        Ok(issues)
+    }
 
-        for entry in entries {
-            let entry = entry.map_err(SwissArmyHammerError::Io)?;
+    /// Paths of the `.md` files in a directory and its subdirectories
+    ///
+    /// Returns an empty vector if the directory doesn't exist. Subdirectories
+    /// that can't be read are logged and skipped.
+    fn issue_files(&self, dir: &Path) -> Result<Vec<PathBuf>> {
+        if !self.fs.exists(dir) {
+            return Ok(vec![]);
+        }
 
-            let path = entry.path();
-            if path.is_file() && path.extension() == Some(std::ffi::OsStr::new("md")) {
-                match self.parse_issue_from_file(&path) {
-                    Ok(issue) => issues.push(issue),
-                    Err(e) => {
-                        debug!("Failed to parse issue from {}: {}", path.display(), e);
-                    }
+        let mut files = Vec::new();
+
+        for path in self.fs.read_dir(dir)? {
+            if self.fs.is_file(&path) && path.extension() == Some(std::ffi::OsStr::new("md")) {
+                files.push(path);
+            } else if self.fs.is_dir(&path) {
+                // Never follow directories the path policy rejects, such as
+                // symlinks out of the issues directory
+                if let Err(e) = self.validate_issue_path(&path) {
+                    debug!("Skipping subdirectory {}: {}", path.display(), e);
+                    continue;
                 }
-            } else if path.is_dir() {
+
                 // Recursively scan subdirectories
-                match self.list_issues_in_dir(&path) {
-                    Ok(sub_issues) => issues.extend(sub_issues),
+                match self.issue_files(&path) {
+                    Ok(sub_files) => files.extend(sub_files),
                     Err(e) => {
                         debug!("Failed to scan subdirectory {}: {}", path.display(), e);
                     }
@@ -264,10 +485,7 @@ impl FileSystemIssueStorage {
             }
         }
 
-        // Sort by name
-        issues.sort_by(|a, b| a.name.as_str().cmp(b.name.as_str()));
-
-        Ok(issues)
+        Ok(files)
     }
 
     /// Create issue file
@@ -313,7 +531,7 @@ impl FileSystemIssueStorage {
         let file_path = self.state.issues_dir.join(&filename);
 
         // Write content to file
-        fs::write(&file_path, content).map_err(SwissArmyHammerError::Io)?;
+        self.fs.write(&file_path, content)?;
 
         Ok(file_path)
     }
@@ -324,19 +542,39 @@ impl FileSystemIssueStorage {
 
         // Find the issue by name
         let issue = self.get_issue(name).await?;
+
+        // Record the change first so the old content is kept even if the write fails
+        self.history().record(
+            &issue.name,
+            Some(&issue.content),
+            ChangeKind::Updated,
+            &content,
+        )?;
+        self.write_issue_content(issue, content)
+    }
+
+    /// Replace the content of an issue's file
+    fn write_issue_content(&self, issue: Issue, content: String) -> Result<Issue> {
         let path = &issue.file_path;
 
-        // Atomic write using temp file and rename
-        let temp_path = path.with_extension("tmp");
-        std::fs::write(&temp_path, &content).map_err(SwissArmyHammerError::Io)?;
-        std::fs::rename(&temp_path, path).map_err(SwissArmyHammerError::Io)?;
+        // The file system writes atomically through a temp file and rename
+        self.fs.write(path, &content)?;
 
         debug!(
             "Successfully updated issue {} at path {}",
-            name,
+            issue.name,
             path.display()
         );
-        Ok(Issue { content, ..issue })
+        let metadata = IssueMetadata::from_content(&content);
+        let issue = Issue {
+            content,
+            priority: metadata.priority,
+            estimate: metadata.estimate,
+            related_memos: metadata.related_memos,
+            ..issue
+        };
+        self.reindex_issue(&issue, None);
+        Ok(issue)
     }
 
     /// Cleanup duplicate files that may exist in the source directory
@@ -347,9 +585,7 @@ impl FileSystemIssueStorage {
     ///
     /// Behavior:
     /// - Removes duplicate files if they exist and are different from the current file
-    /// - Handles common I/O errors gracefully (NotFound, PermissionDenied, Interrupted)
-    /// - Logs errors but doesn't fail the main operation for non-critical cleanup issues
-    /// - Retries once for interrupted operations
+    /// - Logs errors but doesn't fail the main operation, since duplicates are not critical
     fn cleanup_duplicate_if_exists(&self, issue: &Issue, source_dir: &Path) -> Result<()> {
         let filename = issue.file_path.file_name().ok_or_else(|| {
             SwissArmyHammerError::Other(format!(
@@ -359,51 +595,17 @@ impl FileSystemIssueStorage {
         })?;
         let potential_duplicate = source_dir.join(filename);
 
-        if potential_duplicate.exists() && potential_duplicate != issue.file_path {
+        if self.fs.exists(&potential_duplicate) && potential_duplicate != issue.file_path {
             debug!(
                 "Found duplicate file at {}, removing it",
                 potential_duplicate.display()
             );
-            if let Err(e) = std::fs::remove_file(&potential_duplicate) {
-                match e.kind() {
-                    io::ErrorKind::NotFound => {
-                        // File was already deleted by another process - this is fine
-                        debug!(
-                            "Duplicate file {} was already removed",
-                            potential_duplicate.display()
-                        );
-                    }
-                    io::ErrorKind::PermissionDenied => {
-                        // Log the issue but don't fail the operation - duplicates are not critical
-                        debug!(
-                            "Permission denied removing duplicate file {}: {}",
-                            potential_duplicate.display(),
-                            e
-                        );
-                    }
-                    io::ErrorKind::Interrupted => {
-                        // Retry once for interrupted operations
-                        debug!(
-                            "Retrying removal of duplicate file {}",
-                            potential_duplicate.display()
-                        );
-                        if let Err(retry_err) = std::fs::remove_file(&potential_duplicate) {
-                            debug!(
-                                "Failed to remove duplicate file {} after retry: {}",
-                                potential_duplicate.display(),
-                                retry_err
-                            );
-                        }
-                    }
-                    _ => {
-                        // For other errors, log but don't fail the main operation
-                        debug!(
-                            "Failed to remove duplicate file {}: {}",
-                            potential_duplicate.display(),
-                            e
-                        );
-                    }
-                }
+            if let Err(e) = self.fs.remove_file(&potential_duplicate) {
+                debug!(
+                    "Failed to remove duplicate file {}: {}",
+                    potential_duplicate.display(),
+                    e
+                );
             }
         }
         Ok(())
@@ -460,11 +662,13 @@ impl FileSystemIssueStorage {
 
             if let Some(existing_completed) = matching_issues.iter().find(|i| i.completed) {
                 // Compare file modification times to determine which file was created first
-                let pending_mtime = std::fs::metadata(&issue.file_path)
-                    .and_then(|m| m.modified())
+                let pending_mtime = self
+                    .fs
+                    .modified(&issue.file_path)
                     .unwrap_or(std::time::SystemTime::UNIX_EPOCH);
-                let completed_mtime = std::fs::metadata(&existing_completed.file_path)
-                    .and_then(|m| m.modified())
+                let completed_mtime = self
+                    .fs
+                    .modified(&existing_completed.file_path)
                     .unwrap_or(std::time::SystemTime::UNIX_EPOCH);
 
                 // If the completed file is newer than the pending file, it likely means:
@@ -476,7 +680,7 @@ impl FileSystemIssueStorage {
                 // created after legitimate completion, so we should keep the completed file
                 if pending_mtime > completed_mtime {
                     // Clean up the pending duplicate
-                    let _ = std::fs::remove_file(&issue.file_path); // Ignore errors for cleanup
+                    let _ = self.fs.remove_file(&issue.file_path); // Ignore errors for cleanup
 
                     return Ok(existing_completed.clone());
                 }
@@ -499,8 +703,8 @@ impl FileSystemIssueStorage {
                 .ok_or_else(|| SwissArmyHammerError::Other("Invalid file path".to_string()))?;
             let potential_duplicate = opposite_dir.join(filename);
 
-            if potential_duplicate.exists() && potential_duplicate != issue.file_path {
-                let _ = std::fs::remove_file(&potential_duplicate); // Ignore errors for cleanup
+            if self.fs.exists(&potential_duplicate) && potential_duplicate != issue.file_path {
+                let _ = self.fs.remove_file(&potential_duplicate); // Ignore errors for cleanup
             }
 
             return Ok(issue);
@@ -521,7 +725,8 @@ impl FileSystemIssueStorage {
         let target_path = target_dir.join(filename);
 
         // Move file atomically
-        std::fs::rename(&issue.file_path, &target_path).map_err(SwissArmyHammerError::Io)?;
+        self.fs.rename(&issue.file_path, &target_path)?;
+        let source_path = issue.file_path.clone();
 
         // Clean up any duplicate files in the source directory
         let source_dir = if to_completed {
@@ -538,6 +743,12 @@ impl FileSystemIssueStorage {
         // Update issue struct
         issue.file_path = target_path.clone();
         issue.completed = to_completed;
+        self.reindex_issue(&issue, Some(&source_path));
+        if to_completed {
+            event_bus().publish(Event::IssueCompleted {
+                name: issue.name.clone(),
+            });
+        }
 
         Ok(issue)
     }
@@ -553,42 +764,78 @@ impl IssueStorage for FileSystemIssueStorage {
         Ok(all_issues)
     }
 
+    async fn list_issue_summaries(&self) -> Result<Vec<IssueSummary>> {
+        self.indexed_summaries()
+    }
+
     async fn get_issue(&self, name: &str) -> Result<Issue> {
-        // Use existing list_issues() method to avoid duplicating search logic
-        let all_issues = self.list_issues().await?;
-        all_issues
+        // Find the file through the index so only that issue is read
+        let summary = self
+            .indexed_summaries()?
             .into_iter()
-            .find(|issue| issue.name == name)
-            .ok_or_else(|| SwissArmyHammerError::IssueNotFound(name.to_string()))
+            .find(|summary| summary.name == name)
+            .ok_or_else(|| SwissArmyHammerError::IssueNotFound(name.to_string()))?;
+        self.parse_issue_from_file(&summary.file_path)
     }
 
     async fn create_issue(&self, name: String, content: String) -> Result<Issue> {
         // Lock to ensure atomic issue creation (prevents race conditions)
         let _lock = self.creation_lock.lock().await;
 
-        // If name is empty, generate a ULID
-        let issue_name = if name.trim().is_empty() {
-            generate_monotonic_ulid_string()
+        let numbering = match &self.numbering {
+            Some(numbering) => numbering.clone(),
+            None => IssueNumbering::from_config(Config::global())?,
+        };
+        let issue_name = if numbering == IssueNumbering::Name {
+            // If name is empty, generate a ULID
+            if name.trim().is_empty() {
+                generate_monotonic_ulid_string()
+            } else {
+                sanitize_issue_name(&name)
+            }
         } else {
-            sanitize_issue_name(&name)
+            // Start the name with the next identifier, never reusing one
+            // held by a pending or completed issue
+            let existing = self.list_issues_in_dir(&self.state.issues_dir)?;
+            let identifier = numbering
+                .next_identifier(existing.iter().map(|issue| issue.name.as_str()), Utc::now());
+            let name = if name.trim().is_empty() {
+                String::new()
+            } else {
+                create_safe_filename(&sanitize_issue_name(&name))
+            };
+            IssueNumbering::issue_name(identifier.as_deref(), &name)
         };
 
         // Create the filename and file path
         let filename = create_safe_filename(&issue_name);
-        let file_path = self.state.issues_dir.join(format!("{filename}.md"));
+        let file_path =
+            self.validate_issue_path(&self.state.issues_dir.join(format!("{filename}.md")))?;
 
         // Write the content to the file
-        fs::write(&file_path, &content).map_err(SwissArmyHammerError::Io)?;
+        self.history()
+            .record(&issue_name, None, ChangeKind::Created, &content)?;
+        self.fs.write(&file_path, &content)?;
+        event_bus().publish(Event::IssueCreated {
+            name: issue_name.clone(),
+        });
 
         let created_at = Utc::now();
 
-        Ok(Issue {
+        let metadata = IssueMetadata::from_content(&content);
+        let issue = Issue {
             name: issue_name,
             content,
             completed: false,
             file_path,
             created_at,
-        })
+            source: Some(self.state.issues_dir.clone()),
+            priority: metadata.priority,
+            estimate: metadata.estimate,
+            related_memos: metadata.related_memos,
+        };
+        self.reindex_issue(&issue, None);
+        Ok(issue)
     }
 
     async fn update_issue(&self, name: &str, content: String) -> Result<Issue> {
@@ -603,6 +850,18 @@ impl IssueStorage for FileSystemIssueStorage {
         self.move_issue_with_issue(issue, true).await
     }
 
+    async fn reopen_issue(&self, name: &str) -> Result<Issue> {
+        // Prefer the completed copy so a stale pending duplicate is cleaned up on the move
+        let all_issues = self.list_issues().await?;
+        let issue = all_issues
+            .iter()
+            .find(|issue| issue.name == name && issue.completed)
+            .or_else(|| all_issues.iter().find(|issue| issue.name == name))
+            .cloned()
+            .ok_or_else(|| SwissArmyHammerError::IssueNotFound(name.to_string()))?;
+        self.move_issue_with_issue(issue, false).await
+    }
+
     async fn create_issues_batch(&self, issues: Vec<(String, String)>) -> Result<Vec<Issue>> {
         let mut created_issues = Vec::new();
 
@@ -663,9 +922,10 @@ impl IssueStorage for FileSystemIssueStorage {
     }
 
     async fn get_next_issue(&self) -> Result<Option<Issue>> {
-        let all_issues = self.list_issues().await?;
-        let next_issue = all_issues.into_iter().find(|issue| !issue.completed);
-        Ok(next_issue)
+        match next_issue(self.indexed_summaries()?, IssueSort::Priority) {
+            Some(next) => self.parse_issue_from_file(&next.file_path).map(Some),
+            None => Ok(None),
+        }
     }
 
     // Type-safe implementations using IssueName
@@ -706,6 +966,20 @@ impl IssueStorage for FileSystemIssueStorage {
         let str_names: Vec<&str> = names.iter().map(|n| n.as_str()).collect();
         self.mark_complete_batch(str_names).await
     }
+
+    async fn issue_history(&self, name: &str) -> Result<Vec<ContentChange>> {
+        let issue = self.get_issue(name).await?;
+        self.history().changes(&issue.name)
+    }
+
+    async fn restore_issue(&self, name: &str, version: usize) -> Result<Issue> {
+        let issue = self.get_issue(name).await?;
+        let history = self.history();
+        let change = history.change(&issue.name, version)?;
+        let content = change.restorable_content()?.to_string();
+        history.record_restore(&issue.name, Some(&issue.content), version, &content)?;
+        self.write_issue_content(issue, content)
+    }
 }
 
 /// Format issue name as 6-digit string with leading zeros
@@ -1321,6 +1595,7 @@ pub fn get_issue_name_from_filename(filename: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
     use tempfile::TempDir;
 
     /// Create a test issue storage with temporary directory
@@ -1332,6 +1607,85 @@ mod tests {
         (storage, temp_dir)
     }
 
+    #[tokio::test]
+    async fn test_storage_on_overlay_leaves_disk_untouched() {
+        let temp_dir = TempDir::new().unwrap();
+        let issues_dir = temp_dir.path().join("issues");
+        fs::create_dir_all(&issues_dir).unwrap();
+        fs::write(issues_dir.join("000001_existing.md"), "# Existing").unwrap();
+        let overlay = Arc::new(crate::fs_utils::OverlayFileSystem::default());
+        let storage = FileSystemIssueStorage::with_fs(issues_dir.clone(), overlay.clone()).unwrap();
+
+        storage
+            .create_issue("new_issue".to_string(), "# New".to_string())
+            .await
+            .unwrap();
+        storage
+            .update_issue("000001_existing", "# Updated".to_string())
+            .await
+            .unwrap();
+        let completed = storage.mark_complete("000001_existing").await.unwrap();
+        assert!(completed.completed);
+
+        let issues = storage.list_issues().await.unwrap();
+        assert_eq!(issues.len(), 2);
+        assert_eq!(
+            storage.get_issue("000001_existing").await.unwrap().content,
+            "# Updated"
+        );
+
+        // Only the issue written before the overlay is on disk
+        assert!(!issues_dir.join("complete").exists());
+        assert!(!issues_dir.join("new_issue.md").exists());
+        assert_eq!(
+            fs::read_to_string(issues_dir.join("000001_existing.md")).unwrap(),
+            "# Existing"
+        );
+        assert!(overlay
+            .changes()
+            .contains(&crate::fs_utils::FileChange::Rename {
+                from: issues_dir.join("000001_existing.md"),
+                to: issues_dir.join("complete").join("000001_existing.md"),
+            }));
+    }
+
+    #[tokio::test]
+    async fn test_issue_history_and_restore() {
+        let (storage, _temp_dir) = create_test_storage();
+        let issue = storage
+            .create_issue("login".to_string(), "# Login".to_string())
+            .await
+            .unwrap();
+        storage
+            .update_issue(&issue.name, "# Login\n\nOverwritten".to_string())
+            .await
+            .unwrap();
+        storage.mark_complete(&issue.name).await.unwrap();
+
+        let history = storage.issue_history(&issue.name).await.unwrap();
+        let kinds: Vec<ChangeKind> = history.iter().map(|change| change.kind).collect();
+        assert_eq!(kinds, vec![ChangeKind::Created, ChangeKind::Updated]);
+        assert_eq!(
+            history[1].unified_diff(Some(&history[0])),
+            "@@ -1,1 +1,3 @@\n # Login\n+\n+Overwritten\n"
+        );
+
+        // Completed issues are restored in place
+        let restored = storage.restore_issue(&issue.name, 1).await.unwrap();
+        assert_eq!(restored.content, "# Login");
+        assert!(restored.completed);
+        assert_eq!(
+            storage.get_issue(&issue.name).await.unwrap().content,
+            "# Login"
+        );
+        let history = storage.issue_history(&issue.name).await.unwrap();
+        assert_eq!(history[2].restored_from, Some(1));
+        assert!(storage.restore_issue(&issue.name, 7).await.is_err());
+
+        // The history directory holds no issues
+        assert_eq!(storage.list_issues().await.unwrap().len(), 1);
+    }
+
     #[test]
     fn test_issue_serialization() {
         let created_at = Utc::now();
@@ -1341,6 +1695,10 @@ mod tests {
             completed: false,
             file_path: PathBuf::from("/tmp/issues/000123_test_issue.md"),
             created_at,
+            source: Some(PathBuf::from("/tmp/issues")),
+            priority: Some(IssuePriority::High),
+            estimate: Some(3),
+            related_memos: Vec::new(),
         };
 
         // Test serialization
@@ -1522,6 +1880,33 @@ mod tests {
         assert_eq!(issue.file_path, expected_path);
     }
 
+    #[tokio::test]
+    async fn test_create_issue_with_numbering() {
+        let (storage, _temp) = create_test_storage();
+        let storage = storage.with_numbering(IssueNumbering::Project {
+            prefix: "PROJ".to_string(),
+        });
+
+        let first = storage
+            .create_issue("fix login".to_string(), "# Login".to_string())
+            .await
+            .unwrap();
+        assert_eq!(first.name, "PROJ-1_fix-login");
+        assert!(first.file_path.ends_with("PROJ-1_fix-login.md"));
+
+        // Completed issues keep their numbers taken
+        storage.mark_complete(&first.name).await.unwrap();
+        let nameless = storage
+            .create_issue(String::new(), "# Untitled".to_string())
+            .await
+            .unwrap();
+        assert_eq!(nameless.name, "PROJ-2");
+        assert_eq!(
+            storage.get_issue("PROJ-2").await.unwrap().content,
+            "# Untitled"
+        );
+    }
+
     #[tokio::test]
     async fn test_create_issue_with_special_characters() {
         let temp_dir = TempDir::new().unwrap();
@@ -1594,6 +1979,31 @@ mod tests {
         assert!(issues[3].completed);
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_list_issues_skips_symlinks_outside_issues_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let issues_dir = temp_dir.path().join("issues");
+        let storage = FileSystemIssueStorage::new(issues_dir.clone()).unwrap();
+        fs::write(issues_dir.join("000001_inside.md"), "inside").unwrap();
+
+        let outside = TempDir::new().unwrap();
+        fs::create_dir_all(outside.path().join("nested")).unwrap();
+        fs::write(outside.path().join("secret.md"), "secret").unwrap();
+        fs::write(outside.path().join("nested/other.md"), "other").unwrap();
+        std::os::unix::fs::symlink(
+            outside.path().join("secret.md"),
+            issues_dir.join("000002_escape.md"),
+        )
+        .unwrap();
+        std::os::unix::fs::symlink(outside.path().join("nested"), issues_dir.join("linked"))
+            .unwrap();
+
+        let issues = storage.list_issues().await.unwrap();
+        let names: Vec<&str> = issues.iter().map(|issue| issue.name.as_str()).collect();
+        assert_eq!(names, vec!["000001_inside"]);
+    }
+
     #[tokio::test]
     async fn test_get_issue_found() {
         let temp_dir = TempDir::new().unwrap();
@@ -2006,6 +2416,31 @@ mod tests {
         assert!(!issue.file_path.exists());
     }
 
+    #[tokio::test]
+    async fn test_reopen_issue() {
+        let temp_dir = TempDir::new().unwrap();
+        let issues_dir = temp_dir.path().to_path_buf();
+        let storage = FileSystemIssueStorage::new(issues_dir.clone()).unwrap();
+
+        storage
+            .create_issue("test_issue".to_string(), "Test content".to_string())
+            .await
+            .unwrap();
+        let completed_issue = storage.mark_complete("test_issue").await.unwrap();
+
+        let reopened = storage.reopen_issue("test_issue").await.unwrap();
+        assert!(!reopened.completed);
+        assert_eq!(reopened.file_path, issues_dir.join("test_issue.md"));
+        assert!(reopened.file_path.exists());
+        assert!(!completed_issue.file_path.exists());
+
+        // Reopening a pending issue is a no-op
+        let again = storage.reopen_issue("test_issue").await.unwrap();
+        assert_eq!(again.file_path, reopened.file_path);
+
+        assert!(storage.reopen_issue("missing").await.is_err());
+    }
+
     #[tokio::test]
     async fn test_mark_complete_already_completed() {
         let temp_dir = TempDir::new().unwrap();
@@ -3282,6 +3717,113 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_next_issue_honors_front_matter_priority() {
+        let temp_dir = TempDir::new().unwrap();
+        let issues_dir = temp_dir.path().to_path_buf();
+        let storage = FileSystemIssueStorage::new(issues_dir.clone()).unwrap();
+
+        std::fs::write(issues_dir.join("000001_routine.md"), "# Routine").unwrap();
+        std::fs::write(
+            issues_dir.join("000002_outage.md"),
+            "---\npriority: high\nestimate: 2\n---\n# Outage",
+        )
+        .unwrap();
+
+        let next = storage.get_next_issue().await.unwrap().unwrap();
+        assert_eq!(next.name, "000002_outage");
+        assert_eq!(next.priority, Some(IssuePriority::High));
+        assert_eq!(next.estimate, Some(2));
+
+        let updated = storage
+            .update_issue("000002_outage", "# Outage resolved".to_string())
+            .await
+            .unwrap();
+        assert_eq!(updated.priority, None);
+        let next = storage.get_next_issue().await.unwrap().unwrap();
+        assert_eq!(next.name, "000001_routine");
+    }
+
+    #[tokio::test]
+    async fn test_issue_summaries_come_from_the_index() {
+        let (storage, temp_dir) = create_test_storage();
+        storage
+            .create_issue(
+                "000001_login".to_string(),
+                "---\nlabels: [backend]\n---\n# Login".to_string(),
+            )
+            .await
+            .unwrap();
+        storage
+            .create_issue("000002_logout".to_string(), "# Logout".to_string())
+            .await
+            .unwrap();
+        storage.mark_complete("000002_logout").await.unwrap();
+
+        // Mutations keep the index current
+        let index = IssueIndex::load(storage.fs.as_ref(), &storage.index_path());
+        assert_eq!(index.len(), 2);
+        let summaries = storage.list_issue_summaries().await.unwrap();
+        assert_eq!(summaries.len(), 2);
+        assert_eq!(summaries[0].labels, vec!["backend"]);
+        assert!(summaries[1].completed);
+        assert!(summaries[1]
+            .file_path
+            .starts_with(&storage.state.completed_dir));
+
+        // An entry matching the file's modification time is used without
+        // reading the file
+        let login = summaries[0].clone();
+        let modified = storage.fs.modified(&login.file_path).unwrap();
+        let mut index = IssueIndex::load(storage.fs.as_ref(), &storage.index_path());
+        index.insert(
+            IssueSummary {
+                priority: Some(IssuePriority::High),
+                ..login.clone()
+            },
+            modified,
+        );
+        index
+            .save(storage.fs.as_ref(), &storage.index_path())
+            .unwrap();
+        let summaries = storage.list_issue_summaries().await.unwrap();
+        assert_eq!(summaries[0].priority, Some(IssuePriority::High));
+
+        // Files added or removed by hand are noticed
+        std::fs::write(storage.issues_dir().join("000003_manual.md"), "# Manual").unwrap();
+        std::fs::remove_file(&login.file_path).unwrap();
+        let names: Vec<String> = storage
+            .list_issue_summaries()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|summary| summary.name)
+            .collect();
+        assert_eq!(names, vec!["000002_logout", "000003_manual"]);
+        assert_eq!(
+            IssueIndex::load(storage.fs.as_ref(), &storage.index_path()).len(),
+            2
+        );
+        assert_eq!(
+            storage.get_next_issue().await.unwrap().unwrap().content,
+            "# Manual"
+        );
+
+        // The index and history stay out of the tracked issues directory
+        let state_root = temp_dir.path().join(ISSUE_STATE_DIR);
+        assert!(storage.index_path().starts_with(&state_root));
+        assert_eq!(
+            std::fs::read_to_string(state_root.join(".gitignore")).unwrap(),
+            "*\n"
+        );
+        let mut files: Vec<String> = std::fs::read_dir(storage.issues_dir())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        files.sort();
+        assert_eq!(files, vec!["000003_manual.md", "complete"]);
+    }
+
     #[test]
     fn test_get_issue_name_from_filename_simple() {
         // Test the new simplified approach - filename without .md extension is the issue name
@@ -0,0 +1,279 @@
+//! Index of the issues in an issues directory
+//!
+//! Listing issues would otherwise read and parse every markdown file in the
+//! issues directory, which gets slow once a project has thousands of them.
+//! Each issues directory keeps an index in [`ISSUE_INDEX_FILE`] recording the
+//! name, state, front matter fields, checklist progress and timestamps of
+//! every issue file. The index lives in the storage's
+//! [`state_dir`](crate::issues::FileSystemIssueStorage::state_dir) under
+//! `.swissarmyhammer`, so listing issues never leaves a file in the tracked
+//! issues directory.
+//!
+//! A listing still walks the directory, but only reads the files the index
+//! has no entry for or whose modification time changed since it was indexed,
+//! such as files edited by hand or pulled from git. Issue storage updates the
+//! entries of the issues it writes, so a listing after `issue create` or
+//! `issue complete` reads nothing. The index is only a cache: deleting it costs
+//! one full read of the directory, and an unreadable index is rebuilt.
+
+use crate::error::{Result, SwissArmyHammerError};
+use crate::fs_utils::FileSystem;
+use crate::issues::checklist::{Checklist, ChecklistProgress};
+use crate::issues::priority::{IssueFields, IssueMetadata, IssuePriority};
+use crate::issues::Issue;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use tracing::debug;
+
+/// File in the issue storage's state directory holding the index
+pub const ISSUE_INDEX_FILE: &str = "index.json";
+
+/// Format of the index file; an index in another format is rebuilt
+const ISSUE_INDEX_VERSION: u32 = 2;
+
+/// What the index records about an issue, without its content
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IssueSummary {
+    /// Issue name, the filename without `.md`
+    pub name: String,
+    /// Whether the issue is completed
+    pub completed: bool,
+    /// The file path of the issue
+    pub file_path: PathBuf,
+    /// When the issue was created
+    pub created_at: DateTime<Utc>,
+    /// Priority from the issue's front matter
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<IssuePriority>,
+    /// Estimate from the issue's front matter
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimate: Option<u32>,
+    /// Labels from the issue's front matter
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+    /// Assignee from the issue's front matter
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<String>,
+    /// Custom state from the issue's front matter
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    /// How much of the checklist in the issue's body is done
+    #[serde(default, skip_serializing_if = "ChecklistProgress::is_empty")]
+    pub tasks: ChecklistProgress,
+}
+
+impl From<&Issue> for IssueSummary {
+    fn from(issue: &Issue) -> Self {
+        let metadata = IssueMetadata::from_content(&issue.content);
+        Self {
+            name: issue.name.clone(),
+            completed: issue.completed,
+            file_path: issue.file_path.clone(),
+            created_at: issue.created_at,
+            priority: issue.priority,
+            estimate: issue.estimate,
+            labels: metadata.labels,
+            assignee: metadata.assignee,
+            state: metadata.state,
+            tasks: Checklist::parse(&issue.content).progress(),
+        }
+    }
+}
+
+impl IssueFields for IssueSummary {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn completed(&self) -> bool {
+        self.completed
+    }
+
+    fn priority(&self) -> Option<IssuePriority> {
+        self.priority
+    }
+
+    fn estimate(&self) -> Option<u32> {
+        self.estimate
+    }
+
+    fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    fn assignee(&self) -> Option<String> {
+        self.assignee.clone()
+    }
+
+    fn tasks(&self) -> ChecklistProgress {
+        self.tasks
+    }
+}
+
+/// An indexed issue file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct IndexEntry {
+    /// Modification time of the file when it was indexed
+    modified: SystemTime,
+    /// What was read from the file
+    summary: IssueSummary,
+}
+
+/// Summaries of the issue files in an issues directory, by file path
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IssueIndex {
+    version: u32,
+    entries: BTreeMap<PathBuf, IndexEntry>,
+}
+
+impl Default for IssueIndex {
+    fn default() -> Self {
+        Self {
+            version: ISSUE_INDEX_VERSION,
+            entries: BTreeMap::new(),
+        }
+    }
+}
+
+impl IssueIndex {
+    /// Read the index at `path`, or start an empty one when there is none or
+    /// it can't be read
+    pub fn load(fs: &dyn FileSystem, path: &Path) -> Self {
+        if !fs.exists(path) {
+            return Self::default();
+        }
+        let index = fs.read_to_string(path).and_then(|json| {
+            serde_json::from_str::<Self>(&json)
+                .map_err(|e| SwissArmyHammerError::Other(e.to_string()))
+        });
+        match index {
+            Ok(index) if index.version == ISSUE_INDEX_VERSION => index,
+            Ok(index) => {
+                debug!(
+                    "Rebuilding issue index {} from version {}",
+                    path.display(),
+                    index.version
+                );
+                Self::default()
+            }
+            Err(e) => {
+                debug!(
+                    "Rebuilding unreadable issue index {}: {}",
+                    path.display(),
+                    e
+                );
+                Self::default()
+            }
+        }
+    }
+
+    /// Write the index to `path`
+    pub fn save(&self, fs: &dyn FileSystem, path: &Path) -> Result<()> {
+        let json = serde_json::to_string(self).map_err(|e| {
+            SwissArmyHammerError::Other(format!("Failed to write issue index: {e}"))
+        })?;
+        if let Some(parent) = path.parent() {
+            fs.create_dir_all(parent)?;
+        }
+        fs.write(path, &json)
+    }
+
+    /// The summary of the file at `path`, if it was indexed when the file had
+    /// its current modification time
+    pub fn fresh(&self, path: &Path, modified: SystemTime) -> Option<&IssueSummary> {
+        self.entries
+            .get(path)
+            .filter(|entry| entry.modified == modified)
+            .map(|entry| &entry.summary)
+    }
+
+    /// Record the summary of a file with the given modification time
+    pub fn insert(&mut self, summary: IssueSummary, modified: SystemTime) {
+        self.entries
+            .insert(summary.file_path.clone(), IndexEntry { modified, summary });
+    }
+
+    /// Forget the file at `path`
+    pub fn remove(&mut self, path: &Path) {
+        self.entries.remove(path);
+    }
+
+    /// Forget every file not in `paths`, returning whether any were forgotten
+    pub fn retain(&mut self, paths: &[PathBuf]) -> bool {
+        let paths: HashSet<&PathBuf> = paths.iter().collect();
+        let before = self.entries.len();
+        self.entries.retain(|path, _| paths.contains(path));
+        self.entries.len() != before
+    }
+
+    /// Number of indexed files
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether no files are indexed
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fs_utils::StdFileSystem;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    fn summary(name: &str) -> IssueSummary {
+        IssueSummary {
+            name: name.to_string(),
+            completed: false,
+            file_path: PathBuf::from(format!("issues/{name}.md")),
+            created_at: Utc::now(),
+            priority: Some(IssuePriority::High),
+            estimate: None,
+            labels: vec!["backend".to_string()],
+            assignee: None,
+            state: None,
+            tasks: ChecklistProgress { done: 1, total: 3 },
+        }
+    }
+
+    #[test]
+    fn test_index_round_trip_and_freshness() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(ISSUE_INDEX_FILE);
+        let fs = StdFileSystem;
+        assert!(IssueIndex::load(&fs, &path).is_empty());
+
+        let modified = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let mut index = IssueIndex::default();
+        index.insert(summary("000001_login"), modified);
+        index.insert(summary("000002_logout"), modified);
+        index.save(&fs, &path).unwrap();
+
+        let loaded = IssueIndex::load(&fs, &path);
+        assert_eq!(loaded, index);
+        let login = Path::new("issues/000001_login.md");
+        assert_eq!(
+            loaded.fresh(login, modified).unwrap().labels,
+            vec!["backend"]
+        );
+        // A file changed since it was indexed has to be read again
+        assert!(loaded
+            .fresh(login, modified + Duration::from_secs(1))
+            .is_none());
+
+        index.remove(login);
+        assert!(!index.retain(&[PathBuf::from("issues/000002_logout.md")]));
+        assert!(index.retain(&[]));
+        assert!(index.is_empty());
+
+        // An unreadable index starts over
+        std::fs::write(&path, "not json").unwrap();
+        assert!(IssueIndex::load(&fs, &path).is_empty());
+    }
+}
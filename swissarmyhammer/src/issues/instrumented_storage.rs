@@ -1,6 +1,8 @@
 use super::filesystem::{Issue, IssueStorage};
+use super::index::IssueSummary;
 use super::metrics::{MetricsSnapshot, Operation, PerformanceMetrics};
 use crate::error::Result;
+use crate::history::ContentChange;
 use crate::mcp::types::IssueName;
 use async_trait::async_trait;
 use tokio::time::Instant;
@@ -74,6 +76,15 @@ impl IssueStorage for InstrumentedIssueStorage {
         result
     }
 
+    async fn reopen_issue(&self, name: &str) -> Result<Issue> {
+        let start = Instant::now();
+        let result = self.storage.reopen_issue(name).await;
+        let duration = start.elapsed();
+
+        self.metrics.record_operation(Operation::Update, duration);
+        result
+    }
+
     async fn list_issues(&self) -> Result<Vec<Issue>> {
         let start = Instant::now();
         let result = self.storage.list_issues().await;
@@ -83,6 +94,15 @@ impl IssueStorage for InstrumentedIssueStorage {
         result
     }
 
+    async fn list_issue_summaries(&self) -> Result<Vec<IssueSummary>> {
+        let start = Instant::now();
+        let result = self.storage.list_issue_summaries().await;
+        let duration = start.elapsed();
+
+        self.metrics.record_operation(Operation::List, duration);
+        result
+    }
+
     async fn create_issues_batch(&self, issues: Vec<(String, String)>) -> Result<Vec<Issue>> {
         let start = Instant::now();
         let result = self.storage.create_issues_batch(issues).await;
@@ -234,6 +254,23 @@ impl IssueStorage for InstrumentedIssueStorage {
         let result = self.storage.mark_complete_batch_by_name(names).await;
         let duration = start.elapsed();
 
+        self.metrics.record_operation(Operation::Update, duration);
+        result
+    }
+    async fn issue_history(&self, name: &str) -> Result<Vec<ContentChange>> {
+        let start = Instant::now();
+        let result = self.storage.issue_history(name).await;
+        let duration = start.elapsed();
+
+        self.metrics.record_operation(Operation::Read, duration);
+        result
+    }
+
+    async fn restore_issue(&self, name: &str, version: usize) -> Result<Issue> {
+        let start = Instant::now();
+        let result = self.storage.restore_issue(name, version).await;
+        let duration = start.elapsed();
+
         self.metrics.record_operation(Operation::Update, duration);
         result
     }
@@ -68,12 +68,38 @@
 //! # }
 //! ```
 
+/// Issue assignees and reporters, with identities from git config
+pub mod assignment;
+/// Board view grouping issues into todo / in progress / done columns
+pub mod board;
+/// Names of issue branches, and renaming them to a new naming template
+pub mod branch;
+/// All or nothing changes to many issues at once
+pub mod bulk;
+/// Checklists of tasks in issue bodies, and ticking their items
+pub mod checklist;
 /// Filesystem-based issue storage implementation
 pub mod filesystem;
+/// Index of issue names, states and front matter fields for fast listing
+pub mod index;
 /// Storage wrapper that collects performance metrics for all operations
 pub mod instrumented_storage;
 /// Performance metrics collection and analysis
 pub mod metrics;
+/// Identifier schemes for issue names, and renumbering existing issues
+pub mod numbering;
+/// Priority and estimate from issue front matter, and issue ordering
+pub mod priority;
+/// AI-assisted refinement of issue descriptions
+pub mod refine;
+/// Service level rules for open issues, and stale issue detection
+pub mod sla;
+/// Storage merging issues from several directories by priority
+pub mod sources;
+/// Custom issue states and the moves allowed between them
+pub mod states;
+/// Throughput, cycle time, and open vs completed analytics
+pub mod stats;
 /// Shared utilities for issue management
 pub mod utils;
 
@@ -81,12 +107,55 @@ pub mod utils;
 pub use filesystem::{
     create_safe_filename, extract_issue_name_from_filename, get_issue_name_from_filename,
     is_issue_file, parse_any_issue_filename, parse_issue_filename, sanitize_issue_name,
-    validate_issue_name, FileSystemIssueStorage, Issue, IssueState, IssueStorage,
+    validate_issue_name, FileSystemIssueStorage, Issue, IssueState, IssueStorage, ISSUE_STATE_DIR,
+};
+
+// Export assignment types
+pub use assignment::{assign_issue, git_identity, is_assigned_to, resolve_user, CURRENT_USER};
+
+// Export board types
+pub use board::{BoardColumn, IssueBoard};
+
+// Export branch naming types
+pub use branch::{
+    validate_branch_name, BranchRename, BranchRenamePlan, IssueBranchNaming,
+    DEFAULT_ISSUE_BRANCH_TEMPLATE,
+};
+
+// Export bulk change types
+pub use bulk::{select_issues, BulkAction, BulkChange, BulkPlan, BulkSkip};
+
+// Export checklist types
+pub use checklist::{
+    check_issue_item, set_checklist_item, Checklist, ChecklistItem, ChecklistProgress,
 };
 
 // Export metrics types
+pub use index::{IssueIndex, IssueSummary, ISSUE_INDEX_FILE};
+
 pub use metrics::{MetricsSnapshot, Operation, PerformanceMetrics};
 
+// Export numbering types
+pub use numbering::{strip_identifier, IssueNumbering, RenumberChange, RenumberPlan};
+
+// Export priority and ordering types
+pub use priority::{next_issue, IssueFields, IssueMetadata, IssuePriority, IssueSort};
+
+// Export refinement types
+pub use refine::{IssueRefinement, REFINE_PROMPT};
+
+// Export SLA and stale issue types
+pub use sla::{parse_age, stale_issues, IssueSla, SlaStatus, StaleIssue};
+
+// Export multi-directory storage
+pub use sources::{issue_storage_for_work_dir, MultiSourceIssueStorage};
+
+// Export issue state types
+pub use states::{move_issue, IssueStateMachine, COMPLETE_STATE, OPEN_STATE};
+
+// Export analytics types
+pub use stats::{IssueStats, IssueTimeline, WeeklyIssueStats, DEFAULT_STATS_WEEKS};
+
 // Export instrumented storage types
 pub use instrumented_storage::InstrumentedIssueStorage;
 
@@ -0,0 +1,621 @@
+//! Issue identifier schemes, and moving existing issues to another scheme
+//!
+//! By default an issue file is named after the issue alone, and a nameless
+//! issue gets a ULID. A project can instead start every new issue's name with
+//! an identifier, chosen with `SWISSARMYHAMMER_ISSUE_NUMBERING`:
+//!
+//! - `name`: no identifier, the default
+//! - `numeric`: a zero-padded number, `000123_fix_login`
+//! - `ulid`: a ULID, `01J3ZQ8V5E6KX2M9T4R7B1C0DF_fix_login`
+//! - `date`: the month and a number within it, `2024-07-001_fix_login`
+//! - `project`: `SWISSARMYHAMMER_ISSUE_PROJECT_PREFIX` and a number,
+//!   `PROJ-123_fix_login`
+//!
+//! Numbers continue from the highest one used by any pending or completed
+//! issue. A nameless issue is named by its identifier alone.
+//!
+//! A [`RenumberPlan`] moves existing issues to a scheme. Issue files are
+//! renamed, keeping the name after any identifier they already had, and
+//! references to the old names in every issue are rewritten. Like a bulk
+//! change, the plan can be shown before any file is touched, and applying it
+//! is all or nothing.
+
+use crate::common::generate_monotonic_ulid_string;
+use crate::config::Config;
+use crate::error::{Result, SwissArmyHammerError};
+use crate::issues::{BulkSkip, Issue};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+use tracing::warn;
+
+/// How new issue names are prefixed with an identifier
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum IssueNumbering {
+    /// No identifier; nameless issues get a ULID
+    #[default]
+    Name,
+    /// A zero-padded number, such as `000123`
+    Numeric {
+        /// Digits the number is padded to
+        width: usize,
+    },
+    /// A ULID, sortable by creation time
+    Ulid,
+    /// The month and a number within it, such as `2024-07-001`
+    Date,
+    /// A project prefix and a number, such as `PROJ-123`
+    Project {
+        /// Prefix before the number, such as `PROJ`
+        prefix: String,
+    },
+}
+
+impl IssueNumbering {
+    /// The scheme set in the configuration
+    pub fn from_config(config: &Config) -> Result<Self> {
+        Self::parse(
+            &config.issue_numbering,
+            &config.issue_project_prefix,
+            config.issue_number_width,
+        )
+    }
+
+    /// Parse a scheme name; `prefix` is only used by `project`
+    pub fn parse(scheme: &str, prefix: &str, width: usize) -> Result<Self> {
+        match scheme.trim().to_lowercase().as_str() {
+            "" | "name" => Ok(IssueNumbering::Name),
+            "numeric" => Ok(IssueNumbering::Numeric {
+                width: width.max(1),
+            }),
+            "ulid" => Ok(IssueNumbering::Ulid),
+            "date" => Ok(IssueNumbering::Date),
+            "project" => {
+                let prefix = prefix.trim();
+                if prefix.is_empty()
+                    || !prefix.chars().all(|c| c.is_ascii_alphanumeric())
+                    || !prefix.starts_with(|c: char| c.is_ascii_alphabetic())
+                {
+                    return Err(SwissArmyHammerError::Config(format!(
+                        "Issue numbering 'project' needs a project prefix of letters and digits, such as PROJ, got '{prefix}'"
+                    )));
+                }
+                Ok(IssueNumbering::Project {
+                    prefix: prefix.to_string(),
+                })
+            }
+            other => Err(SwissArmyHammerError::Config(format!(
+                "Unknown issue numbering '{other}': expected name, numeric, ulid, date or project"
+            ))),
+        }
+    }
+
+    /// The identifier `name` starts with under this scheme
+    pub fn identifier<'a>(&self, name: &'a str) -> Option<&'a str> {
+        let id = leading_segment(name);
+        let matches = match self {
+            IssueNumbering::Name => false,
+            IssueNumbering::Numeric { width } => is_number(id) && id.len() >= *width,
+            IssueNumbering::Ulid => is_ulid(id),
+            IssueNumbering::Date => date_number(id).is_some(),
+            IssueNumbering::Project { prefix } => project_number(id, prefix).is_some(),
+        };
+        matches.then_some(id)
+    }
+
+    /// The identifier for a new issue created at `now`, none for [`IssueNumbering::Name`]
+    ///
+    /// `existing` are the names of every issue, pending or completed, so
+    /// numbers are never reused.
+    pub fn next_identifier<'a>(
+        &self,
+        existing: impl IntoIterator<Item = &'a str>,
+        now: DateTime<Utc>,
+    ) -> Option<String> {
+        let highest = |number: &dyn Fn(&str) -> Option<u64>| {
+            existing
+                .into_iter()
+                .filter_map(|name| number(leading_segment(name)))
+                .max()
+                .unwrap_or(0)
+        };
+        match self {
+            IssueNumbering::Name => None,
+            IssueNumbering::Numeric { width } => {
+                let next =
+                    highest(&|id: &str| is_number(id).then(|| id.parse().ok()).flatten()) + 1;
+                Some(format!("{next:0width$}"))
+            }
+            IssueNumbering::Ulid => Some(generate_monotonic_ulid_string()),
+            IssueNumbering::Date => {
+                let month = now.format("%Y-%m").to_string();
+                let next =
+                    highest(&|id: &str| date_number(id).filter(|_| id.starts_with(month.as_str())))
+                        + 1;
+                Some(format!("{month}-{next:03}"))
+            }
+            IssueNumbering::Project { prefix } => {
+                let next = highest(&|id: &str| project_number(id, prefix)) + 1;
+                Some(format!("{prefix}-{next}"))
+            }
+        }
+    }
+
+    /// An issue name made of an identifier and the name given to the issue
+    pub fn issue_name(identifier: Option<&str>, name: &str) -> String {
+        match identifier {
+            Some(id) if name.is_empty() => id.to_string(),
+            Some(id) => format!("{id}_{name}"),
+            None => name.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for IssueNumbering {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IssueNumbering::Name => write!(f, "name"),
+            IssueNumbering::Numeric { .. } => write!(f, "numeric"),
+            IssueNumbering::Ulid => write!(f, "ulid"),
+            IssueNumbering::Date => write!(f, "date"),
+            IssueNumbering::Project { prefix } => write!(f, "project {prefix}"),
+        }
+    }
+}
+
+/// The name of an issue after any identifier, of whatever scheme, it starts with
+///
+/// Nameless issues, named by an identifier alone, give an empty name.
+pub fn strip_identifier(name: &str) -> &str {
+    let id = leading_segment(name);
+    let is_identifier = is_number(id)
+        || is_ulid(id)
+        || date_number(id).is_some()
+        || id
+            .split_once('-')
+            .is_some_and(|(prefix, _)| project_number(id, prefix).is_some());
+    if !is_identifier {
+        return name;
+    }
+    name[id.len()..].trim_start_matches('_')
+}
+
+/// Part of a name before the first underscore
+fn leading_segment(name: &str) -> &str {
+    name.split('_').next().unwrap_or(name)
+}
+
+fn is_number(s: &str) -> bool {
+    !s.is_empty() && s.chars().all(|c| c.is_ascii_digit())
+}
+
+fn is_ulid(s: &str) -> bool {
+    s.len() == 26
+        && !s.chars().any(|c| c.is_ascii_lowercase())
+        && ulid::Ulid::from_string(s).is_ok()
+}
+
+/// The number of a `YYYY-MM-NNN` identifier
+fn date_number(s: &str) -> Option<u64> {
+    let mut parts = s.splitn(3, '-');
+    let (year, month, number) = (parts.next()?, parts.next()?, parts.next()?);
+    let valid = year.len() == 4
+        && is_number(year)
+        && month.len() == 2
+        && matches!(month.parse::<u32>(), Ok(1..=12))
+        && number.len() >= 3
+        && is_number(number);
+    valid.then(|| number.parse().ok()).flatten()
+}
+
+/// The number of a `PREFIX-N` identifier
+fn project_number(s: &str, prefix: &str) -> Option<u64> {
+    let number = s.strip_prefix(prefix)?.strip_prefix('-')?;
+    let valid = !prefix.is_empty()
+        && prefix.starts_with(|c: char| c.is_ascii_alphabetic())
+        && prefix.chars().all(|c| c.is_ascii_alphanumeric())
+        && is_number(number);
+    valid.then(|| number.parse().ok()).flatten()
+}
+
+/// `content` with every whole-word mention of `old` replaced by `new`
+///
+/// Returns the new content and the number of mentions replaced. A mention is
+/// whole when it isn't part of a longer name, so renaming `fix` leaves
+/// `fix_login` alone.
+pub fn replace_issue_references(content: &str, old: &str, new: &str) -> (String, usize) {
+    let is_name_char = |c: char| c.is_alphanumeric() || c == '_' || c == '-';
+    let mut result = String::with_capacity(content.len());
+    let mut replaced = 0;
+    let mut rest = content;
+    while let Some(index) = rest.find(old) {
+        let before = rest[..index]
+            .chars()
+            .last()
+            .or_else(|| result.chars().last());
+        let after = rest[index + old.len()..].chars().next();
+        result.push_str(&rest[..index]);
+        if !before.is_some_and(is_name_char) && !after.is_some_and(is_name_char) {
+            result.push_str(new);
+            replaced += 1;
+        } else {
+            result.push_str(old);
+        }
+        rest = &rest[index + old.len()..];
+    }
+    result.push_str(rest);
+    (result, replaced)
+}
+
+/// Planned change to one issue file when renumbering
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RenumberChange {
+    /// Issue name before renumbering
+    pub name: String,
+    /// Issue name after renumbering, the same as `name` when only references change
+    pub new_name: String,
+    /// Current issue file
+    pub from: PathBuf,
+    /// Issue file after renumbering
+    pub to: PathBuf,
+    /// References to renamed issues rewritten in the content
+    pub references: usize,
+    /// Content before the change, restored when the plan is rolled back
+    #[serde(skip)]
+    original: String,
+    /// Content after the change
+    #[serde(skip)]
+    updated: String,
+}
+
+impl RenumberChange {
+    /// Write the changed issue file and remove the old one
+    fn apply(&self) -> Result<()> {
+        if self.to != self.from && self.to.exists() {
+            return Err(SwissArmyHammerError::Other(format!(
+                "{} already exists",
+                self.to.display()
+            )));
+        }
+        fs::write(&self.to, &self.updated)?;
+        if self.to != self.from {
+            if let Err(e) = fs::remove_file(&self.from) {
+                let _ = fs::remove_file(&self.to);
+                return Err(e.into());
+            }
+        }
+        Ok(())
+    }
+
+    /// Put the issue file back the way it was before [`RenumberChange::apply`]
+    fn undo(&self) -> Result<()> {
+        fs::write(&self.from, &self.original)?;
+        if self.to != self.from && self.to.exists() {
+            fs::remove_file(&self.to)?;
+        }
+        Ok(())
+    }
+}
+
+/// Renames and reference rewrites moving issues to a numbering scheme
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RenumberPlan {
+    /// Scheme the issues are moved to
+    pub scheme: String,
+    /// Issue files that change, in the order the issues were created
+    pub changes: Vec<RenumberChange>,
+    /// Issues left as they are, with the reason
+    pub skipped: Vec<BulkSkip>,
+    /// Whether the changes have been applied
+    pub applied: bool,
+}
+
+impl RenumberPlan {
+    /// Plan moving `issues` to `numbering`
+    ///
+    /// Issues already named under the scheme keep their names. The others
+    /// are numbered in the order they were created; under the `date` scheme
+    /// each is numbered within the month it was created. An issue whose new
+    /// name is already taken is skipped.
+    pub fn new(numbering: &IssueNumbering, issues: &[Issue]) -> Self {
+        let mut ordered: Vec<&Issue> = issues.iter().collect();
+        ordered.sort_by(|a, b| {
+            a.created_at
+                .cmp(&b.created_at)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+
+        let mut taken: HashSet<String> = issues.iter().map(|issue| issue.name.clone()).collect();
+        let mut renames: BTreeMap<String, String> = BTreeMap::new();
+        let mut skipped = Vec::new();
+        for issue in &ordered {
+            if numbering.identifier(&issue.name).is_some() {
+                continue;
+            }
+            let base = strip_identifier(&issue.name);
+            let new_name = match numbering {
+                IssueNumbering::Name if base.is_empty() || base == issue.name => continue,
+                IssueNumbering::Name => base.to_string(),
+                IssueNumbering::Ulid => IssueNumbering::issue_name(
+                    Some(&ulid::Ulid::from_datetime(issue.created_at.into()).to_string()),
+                    base,
+                ),
+                _ => {
+                    let id = numbering
+                        .next_identifier(taken.iter().map(String::as_str), issue.created_at);
+                    IssueNumbering::issue_name(id.as_deref(), base)
+                }
+            };
+            if taken.contains(&new_name) {
+                skipped.push(BulkSkip {
+                    name: issue.name.clone(),
+                    reason: format!("'{new_name}' already exists"),
+                });
+                continue;
+            }
+            taken.insert(new_name.clone());
+            renames.insert(issue.name.clone(), new_name);
+        }
+
+        let mut changes = Vec::new();
+        for issue in ordered {
+            let new_name = renames.get(&issue.name).unwrap_or(&issue.name);
+            let mut updated = issue.content.clone();
+            let mut references = 0;
+            for (old, new) in &renames {
+                let (content, count) = replace_issue_references(&updated, old, new);
+                updated = content;
+                references += count;
+            }
+            if *new_name == issue.name && references == 0 {
+                continue;
+            }
+            changes.push(RenumberChange {
+                name: issue.name.clone(),
+                new_name: new_name.clone(),
+                from: issue.file_path.clone(),
+                to: issue.file_path.with_file_name(format!("{new_name}.md")),
+                references,
+                original: issue.content.clone(),
+                updated,
+            });
+        }
+
+        Self {
+            scheme: numbering.to_string(),
+            changes,
+            skipped,
+            applied: false,
+        }
+    }
+
+    /// Apply every change, or none of them
+    pub fn apply(&mut self) -> Result<()> {
+        for (index, change) in self.changes.iter().enumerate() {
+            if let Err(e) = change.apply() {
+                for applied in self.changes[..index].iter().rev() {
+                    if let Err(undo_error) = applied.undo() {
+                        warn!(
+                            "Failed to restore issue '{}' at {}: {}",
+                            applied.name,
+                            applied.from.display(),
+                            undo_error
+                        );
+                    }
+                }
+                return Err(SwissArmyHammerError::Other(format!(
+                    "Failed to renumber issue '{}', no issues were changed: {}",
+                    change.name, e
+                )));
+            }
+        }
+        self.applied = true;
+        Ok(())
+    }
+}
+
+impl fmt::Display for RenumberPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let verb = if self.applied {
+            "Renumbered"
+        } else {
+            "Would renumber"
+        };
+        let renamed = self
+            .changes
+            .iter()
+            .filter(|change| change.new_name != change.name)
+            .count();
+        writeln!(
+            f,
+            "{verb} {renamed} {} to the {} scheme",
+            if renamed == 1 { "issue" } else { "issues" },
+            self.scheme
+        )?;
+
+        let width = self
+            .changes
+            .iter()
+            .map(|change| change.name.len())
+            .chain(self.skipped.iter().map(|skip| skip.name.len()))
+            .max()
+            .unwrap_or(0);
+        for change in &self.changes {
+            let references = match change.references {
+                0 => String::new(),
+                1 => " (1 reference)".to_string(),
+                n => format!(" ({n} references)"),
+            };
+            if change.new_name == change.name {
+                writeln!(f, "  {:<width$}  unchanged{references}", change.name)?;
+            } else {
+                writeln!(
+                    f,
+                    "  {:<width$}  -> {}{references}",
+                    change.name, change.new_name
+                )?;
+            }
+        }
+
+        if !self.skipped.is_empty() {
+            writeln!(f, "Skipped {}:", self.skipped.len())?;
+            for skip in &self.skipped {
+                writeln!(f, "  {:<width$}  {}", skip.name, skip.reason)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_next_identifier() {
+        let now = Utc.with_ymd_and_hms(2024, 7, 15, 12, 0, 0).unwrap();
+        let existing = [
+            "000007_fix_login",
+            "2024-07-004_cleanup",
+            "2024-06-009_old",
+            "PROJ-41_api",
+            "OTHER-99",
+            "readme_typo",
+        ];
+
+        let numeric = IssueNumbering::parse("numeric", "", 6).unwrap();
+        assert_eq!(
+            numeric.next_identifier(existing, now).as_deref(),
+            Some("000008")
+        );
+        assert_eq!(
+            IssueNumbering::Date
+                .next_identifier(existing, now)
+                .as_deref(),
+            Some("2024-07-005")
+        );
+        assert_eq!(
+            IssueNumbering::Date
+                .next_identifier(existing, now + Duration::days(20))
+                .as_deref(),
+            Some("2024-08-001")
+        );
+        let project = IssueNumbering::parse("project", "PROJ", 6).unwrap();
+        assert_eq!(
+            project.next_identifier(existing, now).as_deref(),
+            Some("PROJ-42")
+        );
+        let ulid = IssueNumbering::Ulid.next_identifier(existing, now).unwrap();
+        assert_eq!(IssueNumbering::Ulid.identifier(&ulid), Some(ulid.as_str()));
+        assert_eq!(IssueNumbering::Name.next_identifier(existing, now), None);
+
+        assert_eq!(project.identifier("PROJ-41_api"), Some("PROJ-41"));
+        assert_eq!(project.identifier("OTHER-99"), None);
+        assert_eq!(
+            IssueNumbering::issue_name(Some("PROJ-42"), "fix_login"),
+            "PROJ-42_fix_login"
+        );
+        assert_eq!(IssueNumbering::issue_name(Some("PROJ-42"), ""), "PROJ-42");
+
+        assert!(IssueNumbering::parse("project", "", 6).is_err());
+        assert!(IssueNumbering::parse("project", "PR OJ", 6).is_err());
+        assert!(IssueNumbering::parse("sequential", "", 6).is_err());
+        assert_eq!(
+            IssueNumbering::parse("", "", 6).unwrap(),
+            IssueNumbering::Name
+        );
+    }
+
+    #[test]
+    fn test_strip_identifier_and_replace_references() {
+        assert_eq!(strip_identifier("000123_fix_login"), "fix_login");
+        assert_eq!(strip_identifier("2024-07-001_fix_login"), "fix_login");
+        assert_eq!(strip_identifier("PROJ-12_fix_login"), "fix_login");
+        assert_eq!(strip_identifier("01J3ZQ8V5E6KX2M9T4R7B1C0DF"), "");
+        assert_eq!(strip_identifier("fix_login"), "fix_login");
+        assert_eq!(strip_identifier("2fa_setup"), "2fa_setup");
+
+        let (content, count) = replace_issue_references(
+            "See fix, fix_login and issue/fix.\nfix",
+            "fix",
+            "PROJ-1_fix",
+        );
+        assert_eq!(
+            content,
+            "See PROJ-1_fix, fix_login and issue/PROJ-1_fix.\nPROJ-1_fix"
+        );
+        assert_eq!(count, 3);
+    }
+
+    fn write_issue(dir: &std::path::Path, name: &str, content: &str, age_days: i64) -> Issue {
+        let file_path = dir.join(format!("{name}.md"));
+        fs::write(&file_path, content).unwrap();
+        Issue {
+            name: name.to_string(),
+            content: content.to_string(),
+            completed: false,
+            file_path,
+            created_at: Utc::now() - Duration::days(age_days),
+            source: None,
+            priority: None,
+            estimate: None,
+            related_memos: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_renumber_plan_renames_and_rewrites_references() {
+        let temp = TempDir::new().unwrap();
+        let issues = vec![
+            write_issue(
+                temp.path(),
+                "login",
+                "# Login\n\nBlocked by 000002_schema.",
+                1,
+            ),
+            write_issue(temp.path(), "000002_schema", "# Schema", 3),
+            write_issue(temp.path(), "PROJ-5_docs", "# Docs", 2),
+        ];
+        let project = IssueNumbering::parse("project", "PROJ", 6).unwrap();
+
+        let mut plan = RenumberPlan::new(&project, &issues);
+        let renames: Vec<(&str, &str)> = plan
+            .changes
+            .iter()
+            .map(|change| (change.name.as_str(), change.new_name.as_str()))
+            .collect();
+        assert_eq!(
+            renames,
+            vec![
+                ("000002_schema", "PROJ-6_schema"),
+                ("login", "PROJ-7_login")
+            ]
+        );
+        assert_eq!(plan.changes[1].references, 1);
+        assert!(plan.to_string().starts_with("Would renumber 2 issues"));
+
+        plan.apply().unwrap();
+        assert!(plan.applied);
+        assert!(!temp.path().join("login.md").exists());
+        assert_eq!(
+            fs::read_to_string(temp.path().join("PROJ-7_login.md")).unwrap(),
+            "# Login\n\nBlocked by PROJ-6_schema."
+        );
+        assert!(temp.path().join("PROJ-6_schema.md").exists());
+        assert!(temp.path().join("PROJ-5_docs.md").exists());
+
+        // Back to plain names; the second `docs` would collide and is skipped
+        let issues = vec![
+            write_issue(temp.path(), "PROJ-5_docs", "# Docs", 2),
+            write_issue(temp.path(), "docs", "# Docs again", 1),
+        ];
+        let plan = RenumberPlan::new(&IssueNumbering::Name, &issues);
+        assert!(plan.changes.is_empty());
+        assert_eq!(plan.skipped[0].name, "PROJ-5_docs");
+    }
+}
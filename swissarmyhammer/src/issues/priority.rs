@@ -0,0 +1,508 @@
+//! Issue priority and estimate read from front matter, and issue ordering
+//!
+//! An issue file may start with YAML front matter:
+//!
+//! ```markdown
+//! ---
+//! priority: high
+//! estimate: 3
+//! related_memos: [login-investigation]
+//! labels: [backend, stale]
+//! repo: api
+//! state: in-review
+//! assignee: Jane Doe
+//! reporter: John Smith
+//! workflow: work_issue
+//! ---
+//! # Fix the login bug
+//! ```
+//!
+//! `priority` is one of `high`, `medium` or `low`, and `estimate` is a whole
+//! number in whatever unit the project uses. Both are optional; issues
+//! without a priority are ordered as `medium`. `related_memos` names memos,
+//! by ID or title, that hold notes about the issue, and `labels` tags it for
+//! bulk changes. `repo` names the configured repository the issue's branch
+//! lives in, when it isn't the project's own. `state` is the custom state of a
+//! pending issue, see [`crate::issues::states`]. `assignee` and `reporter`
+//! name who owns the issue and who raised it, see
+//! [`crate::issues::assignment`]. `workflow` names a workflow run for the
+//! issue as soon as it is created, see
+//! [`IssueWorkflowBinding`](crate::workflow::IssueWorkflowBinding). Front
+//! matter that can't be read is ignored
+//! so a typo never hides an issue.
+
+use crate::error::{Result, SwissArmyHammerError};
+use crate::issues::checklist::{Checklist, ChecklistProgress};
+use crate::issues::Issue;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+use tracing::debug;
+
+/// How urgent an issue is
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum IssuePriority {
+    /// Work on this first
+    High,
+    /// The priority of issues that don't set one
+    #[default]
+    Medium,
+    /// Work on this when nothing else is pending
+    Low,
+}
+
+impl fmt::Display for IssuePriority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            IssuePriority::High => "high",
+            IssuePriority::Medium => "medium",
+            IssuePriority::Low => "low",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for IssuePriority {
+    type Err = SwissArmyHammerError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "high" => Ok(IssuePriority::High),
+            "medium" => Ok(IssuePriority::Medium),
+            "low" => Ok(IssuePriority::Low),
+            _ => Err(SwissArmyHammerError::Other(format!(
+                "Invalid issue priority '{s}': expected high, medium or low"
+            ))),
+        }
+    }
+}
+
+/// Fields read from an issue's front matter
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IssueMetadata {
+    /// Value of the `priority` field
+    pub priority: Option<IssuePriority>,
+    /// Value of the `estimate` field
+    pub estimate: Option<u32>,
+    /// Value of the `related_memos` field, a single memo or a list
+    pub related_memos: Vec<String>,
+    /// Value of the `labels` field, a single label or a list
+    pub labels: Vec<String>,
+    /// Value of the `repo` field, the repository the issue's branch lives in
+    pub repo: Option<String>,
+    /// Value of the `state` field, the custom state of a pending issue
+    pub state: Option<String>,
+    /// Value of the `assignee` field, who works on the issue
+    pub assignee: Option<String>,
+    /// Value of the `reporter` field, who raised the issue
+    pub reporter: Option<String>,
+    /// Value of the `workflow` field, the workflow run when the issue is created
+    pub workflow: Option<String>,
+}
+
+impl IssueMetadata {
+    /// Read the planning fields from issue content
+    pub fn from_content(content: &str) -> Self {
+        let Some(front_matter) = front_matter(content) else {
+            return Self::default();
+        };
+        let yaml: serde_yaml::Value = match serde_yaml::from_str(front_matter) {
+            Ok(yaml) => yaml,
+            Err(e) => {
+                debug!("Ignoring unreadable issue front matter: {}", e);
+                return Self::default();
+            }
+        };
+
+        let priority = yaml.get("priority").and_then(|value| {
+            let priority = value.as_str()?.parse();
+            if let Err(e) = &priority {
+                debug!("Ignoring issue priority: {}", e);
+            }
+            priority.ok()
+        });
+        let estimate = yaml
+            .get("estimate")
+            .and_then(serde_yaml::Value::as_u64)
+            .and_then(|estimate| u32::try_from(estimate).ok());
+
+        Self {
+            priority,
+            estimate,
+            related_memos: string_list(yaml.get("related_memos")),
+            labels: string_list(yaml.get("labels")),
+            repo: yaml
+                .get("repo")
+                .and_then(serde_yaml::Value::as_str)
+                .map(str::to_string),
+            state: yaml
+                .get("state")
+                .and_then(serde_yaml::Value::as_str)
+                .map(str::to_string),
+            assignee: yaml
+                .get("assignee")
+                .and_then(serde_yaml::Value::as_str)
+                .map(str::to_string),
+            reporter: yaml
+                .get("reporter")
+                .and_then(serde_yaml::Value::as_str)
+                .map(str::to_string),
+            workflow: yaml
+                .get("workflow")
+                .and_then(serde_yaml::Value::as_str)
+                .map(str::trim)
+                .filter(|workflow| !workflow.is_empty())
+                .map(str::to_string),
+        }
+    }
+}
+
+/// Strings in a front matter field holding a single string or a list
+pub(crate) fn string_list(value: Option<&serde_yaml::Value>) -> Vec<String> {
+    match value {
+        Some(serde_yaml::Value::String(item)) => vec![item.clone()],
+        Some(serde_yaml::Value::Sequence(items)) => items
+            .iter()
+            .filter_map(|item| item.as_str().map(str::to_string))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// YAML between the leading `---` lines of the content, if any
+pub(crate) fn front_matter(content: &str) -> Option<&str> {
+    split_front_matter(content).map(|(yaml, _)| yaml)
+}
+
+/// Content split into the YAML between the leading `---` lines and the body
+/// after them, if it starts with front matter
+pub(crate) fn split_front_matter(content: &str) -> Option<(&str, &str)> {
+    let rest = content
+        .strip_prefix("---\n")
+        .or_else(|| content.strip_prefix("---\r\n"))?;
+    let end = rest
+        .match_indices("---")
+        .find(|(index, _)| *index == 0 || rest[..*index].ends_with('\n'))?
+        .0;
+    let body = &rest[end + 3..];
+    let body = body
+        .strip_prefix("\n")
+        .or_else(|| body.strip_prefix("\r\n"))
+        .unwrap_or(body);
+    Some((&rest[..end], body))
+}
+
+/// Content with a front matter field set to `value`, or removed when it is
+/// `None`, dropping a front matter block left empty
+pub(crate) fn set_front_matter_field(
+    content: &str,
+    key: &str,
+    value: Option<&str>,
+) -> Result<String> {
+    let (yaml, body) = split_front_matter(content).unwrap_or(("", content));
+    let mut front_matter = if yaml.trim().is_empty() {
+        serde_yaml::Mapping::new()
+    } else {
+        serde_yaml::from_str::<serde_yaml::Mapping>(yaml).map_err(|e| {
+            SwissArmyHammerError::Other(format!("Issue front matter can't be read: {e}"))
+        })?
+    };
+
+    let key = serde_yaml::Value::from(key);
+    match value {
+        Some(value) => front_matter.insert(key, serde_yaml::Value::from(value)),
+        None => front_matter.remove(&key),
+    };
+    if front_matter.is_empty() {
+        return Ok(body.to_string());
+    }
+
+    let yaml = serde_yaml::to_string(&front_matter).map_err(|e| {
+        SwissArmyHammerError::Other(format!("Issue front matter can't be written: {e}"))
+    })?;
+    Ok(format!("---\n{yaml}---\n{body}"))
+}
+
+/// Fields issues are ordered and filtered by
+///
+/// Implemented by [`Issue`] and by the content-free
+/// [`IssueSummary`](crate::issues::IssueSummary) the issue index holds, so
+/// listings can be sorted without reading every issue file.
+pub trait IssueFields {
+    /// Name of the issue
+    fn name(&self) -> &str;
+    /// Whether the issue is completed
+    fn completed(&self) -> bool;
+    /// Priority from the issue's front matter
+    fn priority(&self) -> Option<IssuePriority>;
+    /// Estimate from the issue's front matter
+    fn estimate(&self) -> Option<u32>;
+    /// When the issue was created
+    fn created_at(&self) -> DateTime<Utc>;
+    /// Assignee from the issue's front matter
+    fn assignee(&self) -> Option<String>;
+    /// How much of the checklist in the issue's body is done
+    fn tasks(&self) -> ChecklistProgress;
+}
+
+impl IssueFields for Issue {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn completed(&self) -> bool {
+        self.completed
+    }
+
+    fn priority(&self) -> Option<IssuePriority> {
+        self.priority
+    }
+
+    fn estimate(&self) -> Option<u32> {
+        self.estimate
+    }
+
+    fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    fn assignee(&self) -> Option<String> {
+        IssueMetadata::from_content(&self.content).assignee
+    }
+
+    fn tasks(&self) -> ChecklistProgress {
+        Checklist::parse(&self.content).progress()
+    }
+}
+
+impl<T: IssueFields> IssueFields for &T {
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+
+    fn completed(&self) -> bool {
+        (**self).completed()
+    }
+
+    fn priority(&self) -> Option<IssuePriority> {
+        (**self).priority()
+    }
+
+    fn estimate(&self) -> Option<u32> {
+        (**self).estimate()
+    }
+
+    fn created_at(&self) -> DateTime<Utc> {
+        (**self).created_at()
+    }
+
+    fn assignee(&self) -> Option<String> {
+        (**self).assignee()
+    }
+
+    fn tasks(&self) -> ChecklistProgress {
+        (**self).tasks()
+    }
+}
+
+/// Order in which issues are listed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IssueSort {
+    /// By name, which is the order issues were numbered in
+    #[default]
+    Name,
+    /// Highest priority first, then by name
+    Priority,
+    /// Smallest estimate first, issues without an estimate last, then by name
+    Estimate,
+    /// Oldest first, then by name
+    Created,
+}
+
+impl IssueSort {
+    /// Compare two issues in this order
+    pub fn compare<T: IssueFields>(&self, a: &T, b: &T) -> Ordering {
+        let ordering = match self {
+            IssueSort::Name => Ordering::Equal,
+            IssueSort::Priority => a
+                .priority()
+                .unwrap_or_default()
+                .cmp(&b.priority().unwrap_or_default()),
+            IssueSort::Estimate => match (a.estimate(), b.estimate()) {
+                (Some(a), Some(b)) => a.cmp(&b),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            },
+            IssueSort::Created => a.created_at().cmp(&b.created_at()),
+        };
+        ordering.then_with(|| a.name().cmp(b.name()))
+    }
+
+    /// Sort issues in this order
+    pub fn sort<T: IssueFields>(&self, issues: &mut [T]) {
+        issues.sort_by(|a, b| self.compare(a, b));
+    }
+}
+
+impl fmt::Display for IssueSort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            IssueSort::Name => "name",
+            IssueSort::Priority => "priority",
+            IssueSort::Estimate => "estimate",
+            IssueSort::Created => "created",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for IssueSort {
+    type Err = SwissArmyHammerError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "name" => Ok(IssueSort::Name),
+            "priority" => Ok(IssueSort::Priority),
+            "estimate" => Ok(IssueSort::Estimate),
+            "created" => Ok(IssueSort::Created),
+            _ => Err(SwissArmyHammerError::Other(format!(
+                "Invalid issue sort '{s}': expected name, priority, estimate or created"
+            ))),
+        }
+    }
+}
+
+/// The pending issue that comes first in the given order
+///
+/// Issue storage picks the next issue with [`IssueSort::Priority`].
+pub fn next_issue<T: IssueFields>(issues: Vec<T>, sort: IssueSort) -> Option<T> {
+    issues
+        .into_iter()
+        .filter(|issue| !issue.completed())
+        .min_by(|a, b| sort.compare(a, b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::path::PathBuf;
+
+    fn issue(name: &str, content: &str) -> Issue {
+        let metadata = IssueMetadata::from_content(content);
+        Issue {
+            name: name.to_string(),
+            content: content.to_string(),
+            completed: false,
+            file_path: PathBuf::from(format!("issues/{name}.md")),
+            created_at: Utc::now(),
+            source: None,
+            priority: metadata.priority,
+            estimate: metadata.estimate,
+            related_memos: metadata.related_memos,
+        }
+    }
+
+    #[test]
+    fn test_metadata_from_front_matter() {
+        let metadata =
+            IssueMetadata::from_content("---\npriority: High\nestimate: 3\n---\n# Title\n");
+        assert_eq!(metadata.priority, Some(IssuePriority::High));
+        assert_eq!(metadata.estimate, Some(3));
+        assert!(metadata.related_memos.is_empty());
+        assert!(metadata.labels.is_empty());
+
+        let metadata =
+            IssueMetadata::from_content("---\nrelated_memos: [notes, trace]\n---\n# Title\n");
+        assert_eq!(metadata.related_memos, vec!["notes", "trace"]);
+        let metadata = IssueMetadata::from_content("---\nrelated_memos: notes\n---\n");
+        assert_eq!(metadata.related_memos, vec!["notes"]);
+        let metadata = IssueMetadata::from_content("---\nlabels: [backend, stale]\n---\n");
+        assert_eq!(metadata.labels, vec!["backend", "stale"]);
+        let metadata = IssueMetadata::from_content("---\nrepo: api\n---\n");
+        assert_eq!(metadata.repo.as_deref(), Some("api"));
+        assert_eq!(IssueMetadata::from_content("plain issue").repo, None);
+        let metadata = IssueMetadata::from_content("---\nstate: in-review\n---\n");
+        assert_eq!(metadata.state.as_deref(), Some("in-review"));
+        let metadata = IssueMetadata::from_content("---\nworkflow: work_issue\n---\n");
+        assert_eq!(metadata.workflow.as_deref(), Some("work_issue"));
+
+        assert_eq!(
+            IssueMetadata::from_content("# No front matter\n---\npriority: high\n"),
+            IssueMetadata::default()
+        );
+        assert_eq!(
+            IssueMetadata::from_content("---\npriority: urgent\nestimate: lots\n---\n"),
+            IssueMetadata::default()
+        );
+        assert_eq!(
+            IssueMetadata::from_content("---\npriority: [\n---\n"),
+            IssueMetadata::default()
+        );
+    }
+
+    #[test]
+    fn test_sort_by_priority_and_estimate() {
+        let mut issues = vec![
+            issue("000001_low", "---\npriority: low\nestimate: 1\n---\n"),
+            issue("000002_unset", "plain issue"),
+            issue("000003_high", "---\npriority: high\nestimate: 5\n---\n"),
+            issue("000004_medium", "---\npriority: medium\n---\n"),
+        ];
+
+        IssueSort::Priority.sort(&mut issues);
+        let names: Vec<&str> = issues.iter().map(|issue| issue.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["000003_high", "000002_unset", "000004_medium", "000001_low"]
+        );
+
+        IssueSort::Estimate.sort(&mut issues);
+        let names: Vec<&str> = issues.iter().map(|issue| issue.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["000001_low", "000003_high", "000002_unset", "000004_medium"]
+        );
+    }
+
+    #[test]
+    fn test_next_issue_prefers_priority_over_name() {
+        let mut done = issue("000001_done", "---\npriority: high\n---\n");
+        done.completed = true;
+        let issues = vec![
+            done,
+            issue("000002_first", "first"),
+            issue("000003_urgent", "---\npriority: high\n---\n"),
+        ];
+
+        assert_eq!(
+            next_issue(issues.clone(), IssueSort::Priority)
+                .unwrap()
+                .name,
+            "000003_urgent"
+        );
+        assert_eq!(
+            next_issue(issues, IssueSort::Name).unwrap().name,
+            "000002_first"
+        );
+        assert!(next_issue(Vec::new(), IssueSort::Priority).is_none());
+    }
+
+    #[test]
+    fn test_parse_priority_and_sort() {
+        assert_eq!("LOW".parse::<IssuePriority>().unwrap(), IssuePriority::Low);
+        assert!("urgent".parse::<IssuePriority>().is_err());
+        assert_eq!(
+            "estimate".parse::<IssueSort>().unwrap(),
+            IssueSort::Estimate
+        );
+        assert_eq!(IssueSort::Created.to_string(), "created");
+    }
+}
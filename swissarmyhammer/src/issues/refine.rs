@@ -0,0 +1,153 @@
+//! AI-assisted refinement of issue descriptions
+//!
+//! The built-in `issue/refine` prompt is rendered with an issue's content and
+//! sent to the configured model, which answers with the issue rewritten to
+//! include acceptance criteria and checklists. The answer is kept next to the
+//! original as an [`IssueRefinement`] so it can be previewed as a diff before
+//! it replaces the issue.
+
+use crate::issues::Issue;
+use crate::prompt_diff::{diff_lines, unified_diff, DiffLine};
+use crate::workflow::{Action, PromptAction};
+use crate::{Result, SwissArmyHammerError};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Name of the built-in prompt refining an issue
+pub const REFINE_PROMPT: &str = "issue/refine";
+
+/// An issue description next to the refined description the model proposed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IssueRefinement {
+    /// Name of the refined issue
+    pub name: String,
+    /// The issue content before refinement
+    pub original: String,
+    /// The content the model proposed
+    pub refined: String,
+}
+
+impl IssueRefinement {
+    /// Pair an issue with the model's response, dropping a code fence the
+    /// model wrapped the description in
+    pub fn new(issue: &Issue, response: &str) -> Self {
+        Self {
+            name: issue.name.clone(),
+            original: issue.content.clone(),
+            refined: strip_code_fence(response.trim()).trim().to_string(),
+        }
+    }
+
+    /// The prompt action asking the model to refine `issue`
+    pub fn prompt_action(issue: &Issue) -> PromptAction {
+        PromptAction::new(REFINE_PROMPT.to_string())
+            .with_argument("issue_name".to_string(), issue.name.clone())
+            .with_argument("issue_content".to_string(), issue.content.clone())
+            .with_quiet(true)
+    }
+
+    /// Ask the configured model to refine `issue`
+    pub async fn request(issue: &Issue) -> Result<Self> {
+        let mut context: HashMap<String, Value> = HashMap::new();
+        let response = Self::prompt_action(issue)
+            .execute(&mut context)
+            .await
+            .map_err(|e| {
+                SwissArmyHammerError::Other(format!("Failed to refine issue '{}': {e}", issue.name))
+            })?;
+        let response = response.as_str().unwrap_or_default();
+        if response.trim().is_empty() {
+            return Err(SwissArmyHammerError::Other(format!(
+                "The model returned no description for issue '{}'",
+                issue.name
+            )));
+        }
+        Ok(Self::new(issue, response))
+    }
+
+    /// Line diff from the original to the refined content
+    pub fn diff(&self) -> Vec<DiffLine> {
+        diff_lines(&self.original, &self.refined)
+    }
+
+    /// The changes as unified diff hunks
+    pub fn unified_diff(&self) -> String {
+        unified_diff(&self.diff())
+    }
+
+    /// Whether the model left the content as it was
+    pub fn is_unchanged(&self) -> bool {
+        self.original.trim() == self.refined
+    }
+}
+
+/// The body of a response wrapped in a single fenced code block, or the
+/// response itself
+fn strip_code_fence(response: &str) -> &str {
+    let Some(rest) = response.strip_prefix("```") else {
+        return response;
+    };
+    let Some(body) = rest.strip_suffix("```") else {
+        return response;
+    };
+    // Drop the info string, such as `markdown`, on the opening fence
+    match body.split_once('\n') {
+        Some((_, body)) => body,
+        None => response,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::path::PathBuf;
+
+    fn issue(content: &str) -> Issue {
+        Issue {
+            name: "fix_login".to_string(),
+            content: content.to_string(),
+            completed: false,
+            file_path: PathBuf::from("issues/fix_login.md"),
+            created_at: Utc::now(),
+            source: None,
+            priority: None,
+            estimate: None,
+            related_memos: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_refinement_strips_code_fence_and_diffs() {
+        let issue = issue("# Fix login\n\nUsers cannot log in.\n");
+        let response = "```markdown\n# Fix login\n\nUsers cannot log in.\n\n## Acceptance Criteria\n\n- [ ] Login works\n```\n";
+        let refinement = IssueRefinement::new(&issue, response);
+        assert_eq!(
+            refinement.refined,
+            "# Fix login\n\nUsers cannot log in.\n\n## Acceptance Criteria\n\n- [ ] Login works"
+        );
+        assert!(!refinement.is_unchanged());
+        let unified = refinement.unified_diff();
+        assert!(unified.starts_with("@@ -1,3 +1,7 @@\n"));
+        assert!(unified.contains("+## Acceptance Criteria\n"));
+        assert!(unified.contains("+- [ ] Login works\n"));
+
+        let same = IssueRefinement::new(&issue, "# Fix login\n\nUsers cannot log in.");
+        assert!(same.is_unchanged());
+        assert!(same.unified_diff().is_empty());
+
+        // A fence inside the description is kept
+        let inline = IssueRefinement::new(&issue, "Run:\n```\ncargo test\n```");
+        assert_eq!(inline.refined, "Run:\n```\ncargo test\n```");
+    }
+
+    #[test]
+    fn test_prompt_action_passes_issue() {
+        let issue = issue("# Fix login");
+        let action = IssueRefinement::prompt_action(&issue);
+        assert_eq!(action.prompt_name, REFINE_PROMPT);
+        assert_eq!(action.arguments["issue_name"], "fix_login");
+        assert_eq!(action.arguments["issue_content"], "# Fix login");
+        assert!(action.quiet);
+    }
+}
@@ -0,0 +1,280 @@
+//! Service level rules for open issues, and stale issue detection
+//!
+//! Issues that nobody works on sit in the issues directory indefinitely. A
+//! project can set how long an issue may stay open:
+//!
+//! - `SWISSARMYHAMMER_ISSUE_SLA_WARN_DAYS` flags open issues older than this
+//! - `SWISSARMYHAMMER_ISSUE_SLA_ESCALATE_DAYS` escalates open issues older
+//!   than this, and `issue stale --escalate` adds the
+//!   `SWISSARMYHAMMER_ISSUE_SLA_ESCALATE_LABEL` label (`escalated` by default)
+//!   to them
+//!
+//! An issue's age is measured from when its file was created. Completed
+//! issues are never stale.
+
+use crate::config::Config;
+use crate::error::{Result, SwissArmyHammerError};
+use crate::issues::priority::IssueFields;
+use crate::issues::Issue;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::PathBuf;
+
+/// Age after which `issue stale` lists an issue when no SLA is configured
+pub const DEFAULT_STALE_DAYS: u32 = 30;
+
+/// Label added to escalated issues when none is configured
+pub const DEFAULT_ESCALATE_LABEL: &str = "escalated";
+
+/// Where an open issue stands against the SLA rules
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum SlaStatus {
+    /// Younger than every configured limit
+    #[default]
+    OnTrack,
+    /// Older than the warning limit
+    Warning,
+    /// Older than the escalation limit
+    Escalated,
+}
+
+impl SlaStatus {
+    /// Marker shown after an issue in lists, empty when on track
+    pub fn marker(&self) -> &'static str {
+        match self {
+            SlaStatus::OnTrack => "",
+            SlaStatus::Warning => "⚠️",
+            SlaStatus::Escalated => "🚨",
+        }
+    }
+}
+
+impl fmt::Display for SlaStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            SlaStatus::OnTrack => "on track",
+            SlaStatus::Warning => "warning",
+            SlaStatus::Escalated => "escalated",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// How long issues may stay open
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IssueSla {
+    /// Days after which an open issue is flagged
+    pub warn_after_days: Option<u32>,
+    /// Days after which an open issue is escalated
+    pub escalate_after_days: Option<u32>,
+    /// Label `issue stale --escalate` adds to escalated issues
+    pub escalate_label: String,
+}
+
+impl Default for IssueSla {
+    fn default() -> Self {
+        Self {
+            warn_after_days: None,
+            escalate_after_days: None,
+            escalate_label: DEFAULT_ESCALATE_LABEL.to_string(),
+        }
+    }
+}
+
+impl IssueSla {
+    /// The rules set in the configuration
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            warn_after_days: config.issue_sla_warn_days,
+            escalate_after_days: config.issue_sla_escalate_days,
+            escalate_label: config.issue_sla_escalate_label.clone(),
+        }
+    }
+
+    /// Whether any limit is set
+    pub fn is_configured(&self) -> bool {
+        self.warn_after_days.is_some() || self.escalate_after_days.is_some()
+    }
+
+    /// Where an issue stands at `now`
+    pub fn status<T: IssueFields>(&self, issue: &T, now: DateTime<Utc>) -> SlaStatus {
+        if issue.completed() {
+            return SlaStatus::OnTrack;
+        }
+        let age = age_days(issue, now);
+        let exceeds = |days: Option<u32>| days.is_some_and(|days| age >= i64::from(days));
+        if exceeds(self.escalate_after_days) {
+            SlaStatus::Escalated
+        } else if exceeds(self.warn_after_days) {
+            SlaStatus::Warning
+        } else {
+            SlaStatus::OnTrack
+        }
+    }
+
+    /// Age at which `issue stale` lists issues when not given one
+    pub fn stale_after(&self) -> Duration {
+        let days = self
+            .warn_after_days
+            .or(self.escalate_after_days)
+            .unwrap_or(DEFAULT_STALE_DAYS);
+        Duration::days(i64::from(days))
+    }
+}
+
+/// An open issue older than the stale age
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StaleIssue {
+    /// Issue name
+    pub name: String,
+    /// Issue file
+    pub file_path: PathBuf,
+    /// When the issue was created
+    pub created_at: DateTime<Utc>,
+    /// Whole days the issue has been open
+    pub age_days: i64,
+    /// Where the issue stands against the SLA rules
+    pub sla: SlaStatus,
+}
+
+/// Open issues at least `older_than` old at `now`, oldest first
+pub fn stale_issues(
+    issues: &[Issue],
+    older_than: Duration,
+    sla: &IssueSla,
+    now: DateTime<Utc>,
+) -> Vec<StaleIssue> {
+    let mut stale: Vec<StaleIssue> = issues
+        .iter()
+        .filter(|issue| !issue.completed && now - issue.created_at >= older_than)
+        .map(|issue| StaleIssue {
+            name: issue.name.clone(),
+            file_path: issue.file_path.clone(),
+            created_at: issue.created_at,
+            age_days: age_days(issue, now),
+            sla: sla.status(issue, now),
+        })
+        .collect();
+    stale.sort_by(|a, b| {
+        a.created_at
+            .cmp(&b.created_at)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    stale
+}
+
+/// Whole days an issue has existed at `now`
+pub fn age_days<T: IssueFields>(issue: &T, now: DateTime<Utc>) -> i64 {
+    (now - issue.created_at()).num_days().max(0)
+}
+
+/// Parse an age such as `30d`, `2w` or `12h`; a bare number is days
+pub fn parse_age(age: &str) -> Result<Duration> {
+    let age = age.trim();
+    let invalid = || {
+        SwissArmyHammerError::Other(format!(
+            "Invalid age '{age}': expected a number of hours, days or weeks, such as 12h, 30d or 2w"
+        ))
+    };
+    let (value, unit) = match age.find(|c: char| !c.is_ascii_digit()) {
+        Some(index) => age.split_at(index),
+        None => (age, "d"),
+    };
+    let value: i64 = value.parse().map_err(|_| invalid())?;
+    match unit {
+        "h" => Ok(Duration::hours(value)),
+        "d" => Ok(Duration::days(value)),
+        "w" => Ok(Duration::weeks(value)),
+        _ => Err(invalid()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(name: &str, days_old: i64, completed: bool, now: DateTime<Utc>) -> Issue {
+        Issue {
+            name: name.to_string(),
+            content: String::new(),
+            completed,
+            file_path: PathBuf::from(format!("issues/{name}.md")),
+            created_at: now - Duration::days(days_old) - Duration::minutes(1),
+            source: None,
+            priority: None,
+            estimate: None,
+            related_memos: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_sla_status() {
+        let now = Utc::now();
+        let sla = IssueSla {
+            warn_after_days: Some(14),
+            escalate_after_days: Some(30),
+            ..IssueSla::default()
+        };
+        assert_eq!(
+            sla.status(&issue("new", 3, false, now), now),
+            SlaStatus::OnTrack
+        );
+        assert_eq!(
+            sla.status(&issue("aging", 14, false, now), now),
+            SlaStatus::Warning
+        );
+        assert_eq!(
+            sla.status(&issue("old", 45, false, now), now),
+            SlaStatus::Escalated
+        );
+        assert_eq!(
+            sla.status(&issue("done", 45, true, now), now),
+            SlaStatus::OnTrack
+        );
+
+        assert!(!IssueSla::default().is_configured());
+        assert_eq!(
+            IssueSla::default().status(&issue("old", 400, false, now), now),
+            SlaStatus::OnTrack
+        );
+        assert_eq!(sla.stale_after(), Duration::days(14));
+        assert_eq!(IssueSla::default().stale_after(), Duration::days(30));
+    }
+
+    #[test]
+    fn test_stale_issues_oldest_first() {
+        let now = Utc::now();
+        let issues = vec![
+            issue("recent", 5, false, now),
+            issue("older", 40, false, now),
+            issue("oldest", 90, false, now),
+            issue("finished", 120, true, now),
+        ];
+        let sla = IssueSla {
+            escalate_after_days: Some(60),
+            ..IssueSla::default()
+        };
+
+        let stale = stale_issues(&issues, Duration::days(30), &sla, now);
+        let names: Vec<_> = stale.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["oldest", "older"]);
+        assert_eq!(stale[0].age_days, 90);
+        assert_eq!(stale[0].sla, SlaStatus::Escalated);
+        assert_eq!(stale[1].sla, SlaStatus::OnTrack);
+    }
+
+    #[test]
+    fn test_parse_age() {
+        assert_eq!(parse_age("30d").unwrap(), Duration::days(30));
+        assert_eq!(parse_age(" 2w ").unwrap(), Duration::weeks(2));
+        assert_eq!(parse_age("12h").unwrap(), Duration::hours(12));
+        assert_eq!(parse_age("7").unwrap(), Duration::days(7));
+        for age in ["", "d", "30m", "-3d", "3.5d"] {
+            assert!(parse_age(age).is_err(), "{age}");
+        }
+    }
+}
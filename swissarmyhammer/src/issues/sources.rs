@@ -0,0 +1,331 @@
+//! Issues loaded from several directories
+//!
+//! A project can combine its own `./issues` directory with other issue
+//! directories, such as a shared team repository or a subproject in a
+//! monorepo. Sources are ordered by priority in the same way prompt sources
+//! are: when two sources contain an issue with the same name, the issue from
+//! the higher priority source is used and the other is hidden.
+//!
+//! The project directory always has the highest priority, and new issues are
+//! created there. Additional directories come from the
+//! `SWISSARMYHAMMER_ISSUE_DIRS` environment variable, a comma separated list
+//! ordered from lowest to highest priority.
+
+use super::filesystem::{FileSystemIssueStorage, Issue, IssueStorage};
+use super::index::IssueSummary;
+use super::priority::{next_issue, IssueSort};
+use crate::config::Config;
+use crate::error::{Result, SwissArmyHammerError};
+use crate::history::ContentChange;
+use crate::mcp::types::IssueName;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// Issue storage merging several issue directories
+pub struct MultiSourceIssueStorage {
+    /// Sources ordered from lowest to highest priority
+    sources: Vec<FileSystemIssueStorage>,
+}
+
+impl MultiSourceIssueStorage {
+    /// Create storage over issue directories ordered from lowest to highest priority
+    ///
+    /// New issues are created in the last directory.
+    pub fn new(issue_dirs: Vec<PathBuf>) -> Result<Self> {
+        if issue_dirs.is_empty() {
+            return Err(SwissArmyHammerError::Other(
+                "At least one issues directory is required".to_string(),
+            ));
+        }
+
+        let mut sources: Vec<FileSystemIssueStorage> = Vec::new();
+        for dir in issue_dirs {
+            // Listing the same directory twice would only duplicate work
+            if sources
+                .iter()
+                .any(|source| source.issues_dir() == dir.as_path())
+            {
+                continue;
+            }
+            sources.push(FileSystemIssueStorage::new(dir)?);
+        }
+
+        Ok(Self { sources })
+    }
+
+    /// Issue directories ordered from lowest to highest priority
+    pub fn issue_dirs(&self) -> Vec<&Path> {
+        self.sources
+            .iter()
+            .map(|source| source.issues_dir())
+            .collect()
+    }
+
+    /// Source new issues are created in
+    fn primary(&self) -> &FileSystemIssueStorage {
+        self.sources
+            .last()
+            .expect("storage always has at least one source")
+    }
+
+    /// Source holding the visible copy of an issue
+    async fn source_of(&self, name: &str) -> Result<&FileSystemIssueStorage> {
+        for source in self.sources.iter().rev() {
+            if source.get_issue(name).await.is_ok() {
+                return Ok(source);
+            }
+        }
+        Err(SwissArmyHammerError::IssueNotFound(name.to_string()))
+    }
+}
+
+/// Create the issue storage for a working directory
+///
+/// Uses `<work_dir>/issues` alone unless additional issue directories are
+/// configured, in which case they are merged beneath it. Relative configured
+/// directories are resolved against `work_dir`.
+pub fn issue_storage_for_work_dir(work_dir: &Path) -> Result<Box<dyn IssueStorage>> {
+    let local_dir = work_dir.join("issues");
+    let configured = &Config::global().issue_dirs;
+    if configured.is_empty() {
+        return Ok(Box::new(FileSystemIssueStorage::new(local_dir)?));
+    }
+
+    let mut issue_dirs: Vec<PathBuf> = configured.iter().map(|dir| work_dir.join(dir)).collect();
+    issue_dirs.push(local_dir);
+    Ok(Box::new(MultiSourceIssueStorage::new(issue_dirs)?))
+}
+
+#[async_trait::async_trait]
+impl IssueStorage for MultiSourceIssueStorage {
+    async fn list_issues(&self) -> Result<Vec<Issue>> {
+        let mut merged: BTreeMap<String, Vec<Issue>> = BTreeMap::new();
+        for source in &self.sources {
+            let mut by_name: BTreeMap<String, Vec<Issue>> = BTreeMap::new();
+            for issue in source.list_issues().await? {
+                by_name.entry(issue.name.clone()).or_default().push(issue);
+            }
+            for (name, issues) in by_name {
+                if let Some(hidden) = merged.insert(name.clone(), issues) {
+                    for issue in hidden {
+                        debug!(
+                            "Issue '{}' at {} is overridden by a higher priority source",
+                            name,
+                            issue.file_path.display()
+                        );
+                    }
+                }
+            }
+        }
+
+        Ok(merged.into_values().flatten().collect())
+    }
+
+    async fn list_issue_summaries(&self) -> Result<Vec<IssueSummary>> {
+        let mut merged: BTreeMap<String, Vec<IssueSummary>> = BTreeMap::new();
+        for source in &self.sources {
+            let mut by_name: BTreeMap<String, Vec<IssueSummary>> = BTreeMap::new();
+            for summary in source.list_issue_summaries().await? {
+                by_name
+                    .entry(summary.name.clone())
+                    .or_default()
+                    .push(summary);
+            }
+            // Issues in higher priority sources hide those with the same name
+            merged.extend(by_name);
+        }
+
+        Ok(merged.into_values().flatten().collect())
+    }
+
+    async fn get_issue(&self, name: &str) -> Result<Issue> {
+        self.source_of(name).await?.get_issue(name).await
+    }
+
+    async fn create_issue(&self, name: String, content: String) -> Result<Issue> {
+        self.primary().create_issue(name, content).await
+    }
+
+    async fn update_issue(&self, name: &str, content: String) -> Result<Issue> {
+        self.source_of(name)
+            .await?
+            .update_issue(name, content)
+            .await
+    }
+
+    async fn mark_complete(&self, name: &str) -> Result<Issue> {
+        self.source_of(name).await?.mark_complete(name).await
+    }
+
+    async fn reopen_issue(&self, name: &str) -> Result<Issue> {
+        self.source_of(name).await?.reopen_issue(name).await
+    }
+
+    async fn create_issues_batch(&self, issues: Vec<(String, String)>) -> Result<Vec<Issue>> {
+        self.primary().create_issues_batch(issues).await
+    }
+
+    async fn get_issues_batch(&self, names: Vec<&str>) -> Result<Vec<Issue>> {
+        let mut issues = Vec::new();
+        for name in names {
+            issues.push(self.get_issue(name).await?);
+        }
+        Ok(issues)
+    }
+
+    async fn update_issues_batch(&self, updates: Vec<(&str, String)>) -> Result<Vec<Issue>> {
+        // First, verify all issues exist before updating any
+        for (name, _) in &updates {
+            self.source_of(name).await?;
+        }
+
+        let mut updated_issues = Vec::new();
+        for (name, content) in updates {
+            updated_issues.push(self.update_issue(name, content).await?);
+        }
+        Ok(updated_issues)
+    }
+
+    async fn mark_complete_batch(&self, names: Vec<&str>) -> Result<Vec<Issue>> {
+        // First, verify all issues exist before marking any complete
+        for name in &names {
+            self.source_of(name).await?;
+        }
+
+        let mut completed_issues = Vec::new();
+        for name in names {
+            completed_issues.push(self.mark_complete(name).await?);
+        }
+        Ok(completed_issues)
+    }
+
+    async fn get_next_issue(&self) -> Result<Option<Issue>> {
+        match next_issue(self.list_issue_summaries().await?, IssueSort::Priority) {
+            Some(next) => self.get_issue(&next.name).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    async fn get_issue_by_name(&self, name: &IssueName) -> Result<Issue> {
+        self.get_issue(name.as_str()).await
+    }
+
+    async fn create_issue_with_name(&self, name: IssueName, content: String) -> Result<Issue> {
+        self.create_issue(name.get().to_string(), content).await
+    }
+
+    async fn update_issue_by_name(&self, name: &IssueName, content: String) -> Result<Issue> {
+        self.update_issue(name.as_str(), content).await
+    }
+
+    async fn mark_complete_by_name(&self, name: &IssueName) -> Result<Issue> {
+        self.mark_complete(name.as_str()).await
+    }
+
+    async fn get_issues_batch_by_name(&self, names: Vec<&IssueName>) -> Result<Vec<Issue>> {
+        let str_names: Vec<&str> = names.iter().map(|n| n.as_str()).collect();
+        self.get_issues_batch(str_names).await
+    }
+
+    async fn update_issues_batch_by_name(
+        &self,
+        updates: Vec<(&IssueName, String)>,
+    ) -> Result<Vec<Issue>> {
+        let str_updates: Vec<(&str, String)> = updates
+            .iter()
+            .map(|(name, content)| (name.as_str(), content.clone()))
+            .collect();
+        self.update_issues_batch(str_updates).await
+    }
+
+    async fn mark_complete_batch_by_name(&self, names: Vec<&IssueName>) -> Result<Vec<Issue>> {
+        let str_names: Vec<&str> = names.iter().map(|n| n.as_str()).collect();
+        self.mark_complete_batch(str_names).await
+    }
+
+    async fn issue_history(&self, name: &str) -> Result<Vec<ContentChange>> {
+        self.source_of(name).await?.issue_history(name).await
+    }
+
+    async fn restore_issue(&self, name: &str, version: usize) -> Result<Issue> {
+        self.source_of(name)
+            .await?
+            .restore_issue(name, version)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn create_sources() -> (MultiSourceIssueStorage, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let shared = temp_dir.path().join("shared");
+        let local = temp_dir.path().join("local");
+        fs::create_dir_all(shared.join("complete")).unwrap();
+        fs::create_dir_all(&local).unwrap();
+
+        fs::write(shared.join("000001_shared.md"), "shared only").unwrap();
+        fs::write(shared.join("000002_both.md"), "shared copy").unwrap();
+        fs::write(shared.join("complete").join("000003_done.md"), "done").unwrap();
+        fs::write(local.join("000002_both.md"), "local copy").unwrap();
+
+        let storage = MultiSourceIssueStorage::new(vec![shared, local]).unwrap();
+        (storage, temp_dir)
+    }
+
+    #[tokio::test]
+    async fn test_list_merges_sources_with_priority() {
+        let (storage, temp_dir) = create_sources();
+
+        let issues = storage.list_issues().await.unwrap();
+        let names: Vec<&str> = issues.iter().map(|issue| issue.name.as_str()).collect();
+        assert_eq!(names, vec!["000001_shared", "000002_both", "000003_done"]);
+
+        let both = storage.get_issue("000002_both").await.unwrap();
+        assert_eq!(both.content, "local copy");
+        assert_eq!(both.source, Some(temp_dir.path().join("local")));
+
+        let shared = storage.get_issue("000001_shared").await.unwrap();
+        assert_eq!(shared.source, Some(temp_dir.path().join("shared")));
+        assert!(storage.get_issue("000003_done").await.unwrap().completed);
+    }
+
+    #[tokio::test]
+    async fn test_changes_go_to_the_owning_source() {
+        let (storage, temp_dir) = create_sources();
+        let shared = temp_dir.path().join("shared");
+        let local = temp_dir.path().join("local");
+
+        let created = storage
+            .create_issue("new_work".to_string(), "new".to_string())
+            .await
+            .unwrap();
+        assert_eq!(created.file_path, local.join("new_work.md"));
+
+        storage
+            .update_issue("000001_shared", "updated".to_string())
+            .await
+            .unwrap();
+        assert_eq!(
+            fs::read_to_string(shared.join("000001_shared.md")).unwrap(),
+            "updated"
+        );
+
+        let completed = storage.mark_complete("000002_both").await.unwrap();
+        assert_eq!(
+            completed.file_path,
+            local.join("complete").join("000002_both.md")
+        );
+        assert!(shared.join("000002_both.md").exists());
+    }
+
+    #[test]
+    fn test_requires_a_directory() {
+        assert!(MultiSourceIssueStorage::new(Vec::new()).is_err());
+    }
+}
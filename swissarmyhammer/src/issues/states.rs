@@ -0,0 +1,294 @@
+//! Custom issue states and the moves allowed between them
+//!
+//! Every issue is `open` or `complete`. A project can add states in between,
+//! such as `in-review` or `blocked`, with `SWISSARMYHAMMER_ISSUE_STATES`, and
+//! limit the moves between states with `from->to` pairs in
+//! `SWISSARMYHAMMER_ISSUE_TRANSITIONS`. Without transitions any move is
+//! allowed.
+//!
+//! A pending issue's state is kept in the `state` field of its front matter,
+//! an issue without one being `open`. Moving an issue to `complete` moves it
+//! to the `complete` directory as [`IssueStorage::mark_complete`] does, and
+//! moving it out of `complete` reopens it.
+//!
+//! ```
+//! use swissarmyhammer::issues::states::IssueStateMachine;
+//!
+//! let machine = IssueStateMachine::new(
+//!     &["in-review".to_string()],
+//!     &["open->in-review".to_string(), "in-review->complete".to_string()],
+//! )
+//! .unwrap();
+//! assert!(machine.check_transition("open", "in-review").is_ok());
+//! assert!(machine.check_transition("open", "complete").is_err());
+//! ```
+
+use crate::config::Config;
+use crate::error::{Result, SwissArmyHammerError};
+use crate::issues::priority::set_front_matter_field;
+use crate::issues::{Issue, IssueMetadata, IssueStorage};
+use std::collections::BTreeSet;
+
+/// State of a pending issue without a `state` field
+pub const OPEN_STATE: &str = "open";
+
+/// State of a completed issue
+pub const COMPLETE_STATE: &str = "complete";
+
+/// Issue states and the moves allowed between them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IssueStateMachine {
+    states: Vec<String>,
+    transitions: BTreeSet<(String, String)>,
+}
+
+impl Default for IssueStateMachine {
+    fn default() -> Self {
+        Self {
+            states: vec![OPEN_STATE.to_string(), COMPLETE_STATE.to_string()],
+            transitions: BTreeSet::new(),
+        }
+    }
+}
+
+impl IssueStateMachine {
+    /// States between `open` and `complete`, and `from->to` transitions
+    ///
+    /// Transitions naming a state that isn't known are an error, so a typo in
+    /// the configuration is caught rather than silently blocking moves.
+    pub fn new(custom_states: &[String], transitions: &[String]) -> Result<Self> {
+        let mut machine = Self::default();
+        for state in custom_states {
+            let state = state.trim();
+            if state.is_empty() || state.contains(char::is_whitespace) {
+                return Err(SwissArmyHammerError::Other(format!(
+                    "Invalid issue state '{state}'"
+                )));
+            }
+            if !machine.is_known(state) {
+                // Custom states sit between open and complete
+                let at = machine.states.len() - 1;
+                machine.states.insert(at, state.to_string());
+            }
+        }
+
+        for transition in transitions {
+            let (from, to) = transition
+                .split_once("->")
+                .map(|(from, to)| (from.trim(), to.trim()))
+                .ok_or_else(|| {
+                    SwissArmyHammerError::Other(format!(
+                        "Invalid issue transition '{transition}', expected 'from->to'"
+                    ))
+                })?;
+            for state in [from, to] {
+                if !machine.is_known(state) {
+                    return Err(SwissArmyHammerError::Other(format!(
+                        "Issue transition '{transition}' names unknown state '{state}'"
+                    )));
+                }
+            }
+            machine
+                .transitions
+                .insert((from.to_string(), to.to_string()));
+        }
+        Ok(machine)
+    }
+
+    /// States and transitions from the configuration
+    pub fn from_config(config: &Config) -> Result<Self> {
+        Self::new(&config.issue_states, &config.issue_transitions)
+    }
+
+    /// All states, `open` first and `complete` last
+    pub fn states(&self) -> &[String] {
+        &self.states
+    }
+
+    /// Whether the state is `open`, `complete`, or a custom state
+    pub fn is_known(&self, state: &str) -> bool {
+        self.states.iter().any(|known| known == state)
+    }
+
+    /// Current state of an issue
+    pub fn state_of(&self, issue: &Issue) -> String {
+        if issue.completed {
+            return COMPLETE_STATE.to_string();
+        }
+        IssueMetadata::from_content(&issue.content)
+            .state
+            .unwrap_or_else(|| OPEN_STATE.to_string())
+    }
+
+    /// States an issue in `from` may move to
+    pub fn allowed_from(&self, from: &str) -> Vec<&str> {
+        self.states
+            .iter()
+            .filter(|to| *to != from && self.allows(from, to))
+            .map(String::as_str)
+            .collect()
+    }
+
+    fn allows(&self, from: &str, to: &str) -> bool {
+        // An issue left in a state that is no longer configured may move anywhere
+        self.transitions.is_empty()
+            || !self.is_known(from)
+            || self
+                .transitions
+                .contains(&(from.to_string(), to.to_string()))
+    }
+
+    /// Check that an issue may move from one state to another
+    pub fn check_transition(&self, from: &str, to: &str) -> Result<()> {
+        if !self.is_known(to) {
+            return Err(SwissArmyHammerError::Other(format!(
+                "Unknown issue state '{to}', expected one of: {}",
+                self.states.join(", ")
+            )));
+        }
+        if self.allows(from, to) {
+            return Ok(());
+        }
+        let allowed = self.allowed_from(from);
+        let allowed = if allowed.is_empty() {
+            "none".to_string()
+        } else {
+            allowed.join(", ")
+        };
+        Err(SwissArmyHammerError::Other(format!(
+            "Issues can't move from '{from}' to '{to}', allowed: {allowed}"
+        )))
+    }
+}
+
+/// Issue content with the `state` front matter field set
+///
+/// `open` is the default state, so it removes the field, along with a front
+/// matter block left empty.
+pub fn set_issue_state(content: &str, state: &str) -> Result<String> {
+    let value = (state != OPEN_STATE).then_some(state);
+    set_front_matter_field(content, "state", value)
+}
+
+/// Move an issue to another state, returning the updated issue
+pub async fn move_issue(
+    storage: &dyn IssueStorage,
+    machine: &IssueStateMachine,
+    name: &str,
+    to: &str,
+) -> Result<Issue> {
+    let issue = storage.get_issue(name).await?;
+    let from = machine.state_of(&issue);
+    if from == to {
+        return Err(SwissArmyHammerError::Other(format!(
+            "Issue '{name}' is already {to}"
+        )));
+    }
+    machine.check_transition(&from, to)?;
+
+    let content = set_issue_state(&issue.content, to)?;
+    if to == COMPLETE_STATE {
+        // Completion is recorded by the directory, not the front matter
+        let content = set_issue_state(&content, OPEN_STATE)?;
+        if content != issue.content {
+            storage.update_issue(name, content).await?;
+        }
+        return storage.mark_complete(name).await;
+    }
+
+    if issue.completed {
+        storage.reopen_issue(name).await?;
+    }
+    storage.update_issue(name, content).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::issues::FileSystemIssueStorage;
+    use tempfile::TempDir;
+
+    fn review_machine() -> IssueStateMachine {
+        IssueStateMachine::new(
+            &["in-review".to_string(), "blocked".to_string()],
+            &[
+                "open->in-review".to_string(),
+                "in-review->open".to_string(),
+                "in-review->complete".to_string(),
+                "complete->open".to_string(),
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_state_machine_config() {
+        let machine = review_machine();
+        assert_eq!(
+            machine.states(),
+            ["open", "in-review", "blocked", "complete"]
+        );
+        assert!(machine.check_transition("open", "in-review").is_ok());
+        assert!(machine.check_transition("open", "complete").is_err());
+        assert!(machine.check_transition("open", "merged").is_err());
+        assert_eq!(machine.allowed_from("in-review"), ["open", "complete"]);
+        assert!(machine.allowed_from("blocked").is_empty());
+        // A state dropped from the configuration doesn't strand its issues
+        assert!(machine.check_transition("retired", "open").is_ok());
+
+        let open = IssueStateMachine::default();
+        assert!(open.check_transition("open", "complete").is_ok());
+        assert!(open.check_transition("complete", "open").is_ok());
+
+        assert!(IssueStateMachine::new(&[], &["open->merged".to_string()]).is_err());
+        assert!(IssueStateMachine::new(&[], &["open".to_string()]).is_err());
+        assert!(IssueStateMachine::new(&["in review".to_string()], &[]).is_err());
+    }
+
+    #[test]
+    fn test_set_issue_state() {
+        let content = set_issue_state("# Fix it\n", "in-review").unwrap();
+        assert_eq!(content, "---\nstate: in-review\n---\n# Fix it\n");
+        assert_eq!(set_issue_state(&content, "open").unwrap(), "# Fix it\n");
+
+        let content = set_issue_state("---\npriority: high\n---\n# Fix it\n", "blocked").unwrap();
+        let metadata = IssueMetadata::from_content(&content);
+        assert_eq!(metadata.state.as_deref(), Some("blocked"));
+        assert!(content.contains("priority: high"));
+        assert!(content.ends_with("---\n# Fix it\n"));
+    }
+
+    #[tokio::test]
+    async fn test_move_issue() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = FileSystemIssueStorage::new(temp_dir.path().join("issues")).unwrap();
+        storage
+            .create_issue("fix_it".to_string(), "# Fix it\n".to_string())
+            .await
+            .unwrap();
+        let machine = review_machine();
+
+        let issue = move_issue(&storage, &machine, "fix_it", "in-review")
+            .await
+            .unwrap();
+        assert_eq!(machine.state_of(&issue), "in-review");
+        assert!(move_issue(&storage, &machine, "fix_it", "in-review")
+            .await
+            .is_err());
+        assert!(move_issue(&storage, &machine, "fix_it", "blocked")
+            .await
+            .is_err());
+
+        let issue = move_issue(&storage, &machine, "fix_it", "complete")
+            .await
+            .unwrap();
+        assert!(issue.completed);
+        assert_eq!(issue.content, "# Fix it\n");
+
+        let issue = move_issue(&storage, &machine, "fix_it", "open")
+            .await
+            .unwrap();
+        assert!(!issue.completed);
+        assert_eq!(machine.state_of(&issue), "open");
+    }
+}
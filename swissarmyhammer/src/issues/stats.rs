@@ -0,0 +1,320 @@
+//! Issue analytics: throughput, cycle time, and open vs completed counts
+//!
+//! Each issue gets a timeline with the time it was created and, once done, the
+//! time it was completed. Times come from git history when the issue files are
+//! committed, falling back to file system timestamps otherwise:
+//!
+//! - **Created**: first commit adding `issues/<name>.md`
+//! - **Completed**: first commit adding `issues/complete/<name>.md`
+
+use crate::git::GitOperations;
+use crate::issues::Issue;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Number of weeks of throughput reported by default
+pub const DEFAULT_STATS_WEEKS: usize = 8;
+
+/// When an issue was created and completed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IssueTimeline {
+    /// Issue name
+    pub name: String,
+    /// When the issue was created
+    pub created_at: DateTime<Utc>,
+    /// When the issue was completed, if it has been
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl IssueTimeline {
+    /// Build the timeline of an issue, preferring git history over file times
+    pub fn from_issue(issue: &Issue, git_ops: Option<&GitOperations>) -> Self {
+        let added_at = |path: &Path| {
+            git_ops
+                .and_then(|ops| ops.file_added_at(path).ok())
+                .flatten()
+        };
+
+        let pending_path = if issue.completed {
+            pending_path_of(&issue.file_path)
+        } else {
+            Some(issue.file_path.clone())
+        };
+        let mut created_at = pending_path
+            .as_deref()
+            .and_then(added_at)
+            .unwrap_or(issue.created_at);
+
+        let completed_at = issue.completed.then(|| {
+            added_at(&issue.file_path)
+                .or_else(|| {
+                    issue
+                        .file_path
+                        .metadata()
+                        .and_then(|m| m.modified())
+                        .map(DateTime::<Utc>::from)
+                        .ok()
+                })
+                .unwrap_or(issue.created_at)
+        });
+
+        // Issues created and completed in the same commit have no separate
+        // creation record, so never report completion before creation
+        if let Some(completed_at) = completed_at {
+            created_at = created_at.min(completed_at);
+        }
+
+        Self {
+            name: issue.name.clone(),
+            created_at,
+            completed_at,
+        }
+    }
+
+    /// Time from creation to completion
+    pub fn cycle_time(&self) -> Option<Duration> {
+        self.completed_at
+            .map(|completed_at| completed_at - self.created_at)
+    }
+
+    /// Whether the issue was open at the given time
+    fn is_open_at(&self, time: DateTime<Utc>) -> bool {
+        self.created_at <= time && !self.completed_at.is_some_and(|done| done <= time)
+    }
+}
+
+/// Path an issue had before it was moved into the `complete` directory
+fn pending_path_of(completed_path: &Path) -> Option<PathBuf> {
+    let file_name = completed_path.file_name()?;
+    let issues_dir = completed_path.parent()?.parent()?;
+    Some(issues_dir.join(file_name))
+}
+
+/// Issue activity during one week
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WeeklyIssueStats {
+    /// Monday the week starts on
+    pub week_start: NaiveDate,
+    /// Issues created during the week
+    pub opened: usize,
+    /// Issues completed during the week
+    pub completed: usize,
+    /// Issues still open at the end of the week
+    pub open_at_end: usize,
+}
+
+/// Summary metrics over all issues
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IssueStats {
+    /// Number of issues
+    pub total: usize,
+    /// Number of pending issues
+    pub open: usize,
+    /// Number of completed issues
+    pub completed: usize,
+    /// Mean time from creation to completion, in hours
+    pub average_cycle_time_hours: Option<f64>,
+    /// Activity per week, oldest first, ending with the current week
+    pub weekly: Vec<WeeklyIssueStats>,
+}
+
+impl IssueStats {
+    /// Compute statistics from issue timelines
+    ///
+    /// `weeks` is the number of calendar weeks (Monday to Sunday) of activity to
+    /// report, ending with the week containing `now`.
+    pub fn from_timelines(timelines: &[IssueTimeline], weeks: usize, now: DateTime<Utc>) -> Self {
+        let completed = timelines
+            .iter()
+            .filter(|timeline| timeline.completed_at.is_some())
+            .count();
+
+        let cycle_times: Vec<Duration> = timelines
+            .iter()
+            .filter_map(IssueTimeline::cycle_time)
+            .collect();
+        let average_cycle_time_hours = (!cycle_times.is_empty()).then(|| {
+            let total_seconds: i64 = cycle_times.iter().map(Duration::num_seconds).sum();
+            total_seconds as f64 / cycle_times.len() as f64 / 3600.0
+        });
+
+        let today = now.date_naive();
+        let current_week_start =
+            today - Duration::days(today.weekday().num_days_from_monday() as i64);
+        let weekly = (0..weeks)
+            .rev()
+            .map(|weeks_ago| {
+                let week_start = current_week_start - Duration::weeks(weeks_ago as i64);
+                let start = week_start
+                    .and_hms_opt(0, 0, 0)
+                    .expect("midnight is a valid time")
+                    .and_utc();
+                let end = start + Duration::weeks(1);
+                let in_week = |time: DateTime<Utc>| time >= start && time < end;
+
+                WeeklyIssueStats {
+                    week_start,
+                    opened: timelines
+                        .iter()
+                        .filter(|timeline| in_week(timeline.created_at))
+                        .count(),
+                    completed: timelines
+                        .iter()
+                        .filter(|timeline| timeline.completed_at.is_some_and(in_week))
+                        .count(),
+                    open_at_end: timelines
+                        .iter()
+                        .filter(|timeline| timeline.is_open_at(end.min(now)))
+                        .count(),
+                }
+            })
+            .collect();
+
+        Self {
+            total: timelines.len(),
+            open: timelines.len() - completed,
+            completed,
+            average_cycle_time_hours,
+            weekly,
+        }
+    }
+
+    /// Mean number of issues completed per reported week
+    pub fn average_throughput(&self) -> f64 {
+        if self.weekly.is_empty() {
+            return 0.0;
+        }
+        let completed: usize = self.weekly.iter().map(|week| week.completed).sum();
+        completed as f64 / self.weekly.len() as f64
+    }
+}
+
+/// Format a number of hours as hours or days
+fn format_hours(hours: f64) -> String {
+    if hours < 48.0 {
+        format!("{hours:.1} hours")
+    } else {
+        format!("{:.1} days", hours / 24.0)
+    }
+}
+
+impl fmt::Display for IssueStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Issues: {} total, {} open, {} completed",
+            self.total, self.open, self.completed
+        )?;
+        match self.average_cycle_time_hours {
+            Some(hours) => writeln!(f, "Average cycle time: {}", format_hours(hours))?,
+            None => writeln!(f, "Average cycle time: n/a (no completed issues)")?,
+        }
+        writeln!(
+            f,
+            "Throughput: {:.1} completed per week over {} weeks",
+            self.average_throughput(),
+            self.weekly.len()
+        )?;
+
+        if !self.weekly.is_empty() {
+            writeln!(f)?;
+            writeln!(f, "Week of      Opened  Completed  Open at end")?;
+            for week in &self.weekly {
+                writeln!(
+                    f,
+                    "{}  {:>6}  {:>9}  {:>11}",
+                    week.week_start, week.opened, week.completed, week.open_at_end
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(day: u32, hour: u32) -> DateTime<Utc> {
+        // June 2024 starts on a Saturday; Monday the 3rd begins the first full week
+        Utc.with_ymd_and_hms(2024, 6, day, hour, 0, 0).unwrap()
+    }
+
+    fn timeline(name: &str, created: DateTime<Utc>, done: Option<DateTime<Utc>>) -> IssueTimeline {
+        IssueTimeline {
+            name: name.to_string(),
+            created_at: created,
+            completed_at: done,
+        }
+    }
+
+    #[test]
+    fn test_stats_counts_and_cycle_time() {
+        let timelines = vec![
+            timeline("a", at(3, 0), Some(at(4, 0))),
+            timeline("b", at(3, 0), Some(at(6, 0))),
+            timeline("c", at(5, 0), None),
+        ];
+
+        let stats = IssueStats::from_timelines(&timelines, 2, at(12, 12));
+
+        assert_eq!(stats.total, 3);
+        assert_eq!(stats.open, 1);
+        assert_eq!(stats.completed, 2);
+        assert_eq!(stats.average_cycle_time_hours, Some(48.0));
+    }
+
+    #[test]
+    fn test_weekly_throughput() {
+        let timelines = vec![
+            timeline("a", at(3, 0), Some(at(4, 0))),
+            timeline("b", at(3, 0), Some(at(11, 0))),
+            timeline("c", at(10, 0), None),
+        ];
+
+        let stats = IssueStats::from_timelines(&timelines, 2, at(12, 12));
+
+        assert_eq!(stats.weekly.len(), 2);
+        let first = &stats.weekly[0];
+        assert_eq!(
+            first.week_start,
+            NaiveDate::from_ymd_opt(2024, 6, 3).unwrap()
+        );
+        assert_eq!(
+            (first.opened, first.completed, first.open_at_end),
+            (2, 1, 1)
+        );
+
+        let second = &stats.weekly[1];
+        assert_eq!(
+            second.week_start,
+            NaiveDate::from_ymd_opt(2024, 6, 10).unwrap()
+        );
+        assert_eq!(
+            (second.opened, second.completed, second.open_at_end),
+            (1, 1, 1)
+        );
+
+        assert_eq!(stats.average_throughput(), 1.0);
+    }
+
+    #[test]
+    fn test_stats_without_completed_issues() {
+        let timelines = vec![timeline("a", at(3, 0), None)];
+        let stats = IssueStats::from_timelines(&timelines, 1, at(4, 0));
+
+        assert_eq!(stats.average_cycle_time_hours, None);
+        assert!(stats.to_string().contains("n/a"));
+    }
+
+    #[test]
+    fn test_pending_path_of_completed_issue() {
+        assert_eq!(
+            pending_path_of(Path::new("issues/complete/000001_fix.md")),
+            Some(PathBuf::from("issues/000001_fix.md"))
+        );
+    }
+}
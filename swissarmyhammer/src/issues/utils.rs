@@ -96,7 +96,8 @@ pub struct IssueMergeResult {
 ///
 /// This function encapsulates the business logic for issue branch management:
 /// - Validates the issue exists
-/// - Creates or switches to the appropriate issue branch
+/// - Creates or switches to the appropriate issue branch, in the repository
+///   named by the issue's `repo:` front matter key if it has one
 /// - Handles the git operations consistently
 pub async fn work_on_issue<S: IssueStorage>(
     issue_name: &str,
@@ -105,9 +106,10 @@ pub async fn work_on_issue<S: IssueStorage>(
 ) -> Result<IssueBranchResult> {
     // Get the issue to ensure it exists
     let issue = storage.get_issue(issue_name).await?;
+    let git_ops = &git_ops.for_issue(&issue)?;
 
-    // Create work branch with format: issue/{issue_name}
-    let branch_name = format!("issue/{}", issue.name);
+    // Create work branch named by the configured template
+    let branch_name = git_ops.issue_branch(&issue.name);
     let current_branch = git_ops.current_branch()?;
     let created_new_branch = current_branch != branch_name;
 
@@ -125,7 +127,7 @@ pub async fn work_on_issue<S: IssueStorage>(
 ///
 /// This function encapsulates the business logic for merging issue branches:
 /// - Validates the issue exists and is completed
-/// - Merges the issue branch to main
+/// - Merges the issue branch to main in the issue's repository
 /// - Optionally deletes the branch after merge
 pub async fn merge_issue_branch<S: IssueStorage>(
     issue_name: &str,
@@ -143,7 +145,8 @@ pub async fn merge_issue_branch<S: IssueStorage>(
         )));
     }
 
-    let branch_name = format!("issue/{}", issue.name);
+    let git_ops = &git_ops.for_issue(&issue)?;
+    let branch_name = git_ops.issue_branch(&issue.name);
 
     // Merge the issue branch
     git_ops.merge_issue_branch(&issue.name)?;
@@ -172,16 +175,10 @@ pub async fn merge_issue_branch<S: IssueStorage>(
 /// Get the current issue being worked on based on git branch
 ///
 /// This function determines the current issue by parsing the git branch name
-/// to extract the issue name from branches following the "issue/{name}" pattern.
+/// with the configured branch template, `issue/{name}` by default.
 pub fn get_current_issue_from_branch(git_ops: &GitOperations) -> Result<Option<String>> {
     let current_branch = git_ops.current_branch()?;
-
-    // Parse issue name from branch name pattern: issue/{issue_name}
-    if let Some(stripped) = current_branch.strip_prefix("issue/") {
-        Ok(Some(stripped.to_string()))
-    } else {
-        Ok(None)
-    }
+    Ok(git_ops.branch_naming().issue_name(&current_branch))
 }
 
 /// Project status and progress statistics
@@ -459,6 +456,10 @@ mod tests {
             completed: false,
             file_path: PathBuf::from("/test/active1.md"),
             created_at: Utc::now(),
+            source: None,
+            priority: None,
+            estimate: None,
+            related_memos: Vec::new(),
         };
 
         let active_issue2 = Issue {
@@ -467,6 +468,10 @@ mod tests {
             completed: false,
             file_path: PathBuf::from("/test/active2.md"),
             created_at: Utc::now(),
+            source: None,
+            priority: None,
+            estimate: None,
+            related_memos: Vec::new(),
         };
 
         let completed_issue = Issue {
@@ -475,6 +480,10 @@ mod tests {
             completed: true,
             file_path: PathBuf::from("/test/completed/completed1.md"),
             created_at: Utc::now(),
+            source: None,
+            priority: None,
+            estimate: None,
+            related_memos: Vec::new(),
         };
 
         let issues = vec![
@@ -48,9 +48,48 @@ pub mod prompt_filter;
 /// Prompt loading and resolution
 pub mod prompt_resolver;
 
+/// Differences between the prompts of two sources
+pub mod prompt_diff;
+
+/// Named argument presets for prompts
+pub mod prompt_presets;
+
+/// Records of prompts served over MCP
+pub mod prompt_runs;
+
+/// Render benchmarks for prompts
+pub mod prompt_bench;
+
+/// Health report across the prompt library
+pub mod prompt_audit;
+
+/// Project specific front matter fields for prompts
+pub mod prompt_schema;
+
+/// Checksum and signature verification of prompt directories
+pub mod prompt_integrity;
+
+/// Value completion for prompt arguments
+pub mod prompt_completion;
+
+/// Test cases embedded in prompt front matter
+pub mod prompt_tests;
+
+/// Prompt references pinned to a content hash or version
+pub mod prompt_refs;
+
+/// Argument values from the environment, dotenv files and commands
+pub mod value_providers;
+
 /// Template engine and rendering
 pub mod template;
 
+/// Template tags embedding project files
+pub mod template_files;
+
+/// Git context object and filters for templates
+pub mod template_git;
+
 /// Model Context Protocol (MCP) server support
 pub mod mcp;
 
@@ -78,9 +117,27 @@ pub mod issues;
 /// Memoranda management and storage system
 pub mod memoranda;
 
+/// Cross references between issues and memos
+pub mod cross_references;
+
+/// Change history of memo and issue content
+pub mod history;
+
+/// Typed events published between modules
+pub mod events;
+
+/// Webhooks announcing events to other services
+pub mod webhooks;
+
+/// SQL-like queries over the metadata of issues and memos
+pub mod query;
+
 /// Git operations for issue management
 pub mod git;
 
+/// Cost budgets for workflow prompts
+pub mod cost;
+
 /// Security utilities for path validation and resource limits
 pub mod security;
 
@@ -99,13 +156,28 @@ pub mod fs_utils;
 /// Validation framework for checking content integrity
 pub mod validation;
 
+/// Dependency graph between prompts, partials and workflows
+pub mod dependencies;
+
+/// Versioned package manifests for shared prompt and workflow collections
+pub mod packages;
+
 // Re-export core types
 
 /// File source for loading prompts from various sources
 pub use file_loader::FileSource;
 
+/// Dependency graph types for impact analysis
+pub use dependencies::{DependencyGraph, DependencyNode};
+
+/// Content history types for memos and issues
+pub use history::{ChangeKind, ContentChange, ContentHistory};
+
+/// Package manifest types for shared collections
+pub use packages::{Package, PackageManifest, PackageSet};
+
 /// File system utilities and abstractions
-pub use fs_utils::{FilePermissions, FileSystem, FileSystemUtils};
+pub use fs_utils::{FileChange, FilePermissions, FileSystem, FileSystemUtils, OverlayFileSystem};
 
 /// Plugin system types for extending functionality
 pub use plugins::{CustomLiquidFilter, PluginRegistry, SwissArmyHammerPlugin};
@@ -114,13 +186,49 @@ pub use plugins::{CustomLiquidFilter, PluginRegistry, SwissArmyHammerPlugin};
 pub use prompt_filter::PromptFilter;
 
 /// Advanced prompt loading and resolution
-pub use prompt_resolver::PromptResolver;
+pub use prompt_resolver::{PromptChanges, PromptResolver};
+
+/// Prompt source diff types
+pub use prompt_diff::{PromptDiffEntry, PromptDiffKind, PromptSourceDiff};
+
+/// Named argument presets for prompts
+pub use prompt_presets::PresetStorage;
+
+/// Prompt run record types
+pub use prompt_runs::{PromptRun, PromptRunLog};
+
+/// Prompt render benchmark types
+pub use prompt_bench::{CountingAllocator, LatencyStats, PromptBenchmark};
+
+/// Prompt library health report types
+pub use prompt_audit::{AuditFinding, AuditFindingKind, PromptAudit};
+
+/// Project specific front matter schema for prompts
+pub use prompt_schema::FrontMatterSchema;
+
+/// Prompt argument completion types
+pub use prompt_completion::CompletionSource;
+
+/// Prompt directory verification types
+pub use prompt_integrity::{IntegrityPolicy, IntegrityProblem, PromptIntegrity};
+
+/// Prompt front matter test types
+pub use prompt_tests::{run_prompt_tests, PromptTestCase, PromptTestResult};
+
+/// Prompt references pinned to a content hash or version
+pub use prompt_refs::{PromptPin, PromptRef, ResolvedPrompt};
+
+/// Argument value provider types
+pub use value_providers::{register_value_provider, ValueProvider, ValueSource};
 
 /// Backward compatibility alias for FileSource
 pub use file_loader::FileSource as PromptSource;
 
+/// Filter for the sources prompts are loaded from
+pub use file_loader::SourceFilter;
+
 /// Core prompt management types and functionality
-pub use prompts::{ArgumentSpec, Prompt, PromptLibrary, PromptLoader};
+pub use prompts::{ArgumentSpec, Prompt, PromptLibrary, PromptLoader, PromptVisibility};
 
 /// Storage backends and abstractions
 pub use storage::{PromptStorage, StorageBackend};
@@ -153,7 +261,9 @@ pub mod error;
 pub mod config;
 
 pub use config::Config;
-pub use error::{ErrorChainExt, ErrorContext, Result, SwissArmyHammerError};
+pub use error::{
+    ErrorCategory, ErrorChainExt, ErrorCode, ErrorContext, Result, SwissArmyHammerError,
+};
 
 /// Prelude module for convenient imports
 pub mod prelude {
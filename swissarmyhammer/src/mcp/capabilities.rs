@@ -0,0 +1,432 @@
+//! Tool groups that can be enabled per server and per client
+//!
+//! Tools are grouped by the domain prefix of their `{domain}_{action}` name.
+//! Issue tools that change issues or git state form their own group, so a
+//! client can be given read-only access to issues.
+//!
+//! The server configuration decides which groups are served at all:
+//!
+//! - `SWISSARMYHAMMER_MCP_TOOL_GROUPS`: comma separated groups to serve, all
+//!   groups when unset
+//! - `SWISSARMYHAMMER_MCP_DISABLED_TOOL_GROUPS`: comma separated groups never
+//!   to serve
+//!
+//! A client can narrow that further in its `initialize` request by sending
+//! experimental capabilities under the `swissarmyhammer` key:
+//!
+//! ```json
+//! {
+//!   "capabilities": {
+//!     "experimental": {
+//!       "swissarmyhammer": {
+//!         "toolGroups": ["issues", "memos"],
+//!         "disabledToolGroups": ["issue_mutation"]
+//!       }
+//!     }
+//!   }
+//! }
+//! ```
+//!
+//! A client can never enable a group the server configuration disables. The
+//! groups in effect are returned under the same key in the server's
+//! experimental capabilities.
+//!
+//! `serve --read-only` goes further and serves only the tools in
+//! [`READ_ONLY_TOOLS`], which never change issues, memos, git state or the
+//! search index, whatever groups are enabled. A client can ask for the same
+//! with `"readOnly": true`, but can't turn it off on a read-only server.
+
+use crate::config::Config;
+use crate::error::{Result, SwissArmyHammerError};
+use rmcp::model::{ClientCapabilities, ExperimentalCapabilities, JsonObject};
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::str::FromStr;
+
+/// Key of the SwissArmyHammer entry in experimental capabilities
+pub const CAPABILITY_KEY: &str = "swissarmyhammer";
+
+/// Field listing the groups to enable
+const TOOL_GROUPS_FIELD: &str = "toolGroups";
+
+/// Field listing the groups to disable
+const DISABLED_TOOL_GROUPS_FIELD: &str = "disabledToolGroups";
+
+/// Field set when only read-only tools are served
+const READ_ONLY_FIELD: &str = "readOnly";
+
+/// Tools that only read state, the only tools served in read-only mode
+///
+/// New tools are left out of read-only mode until they are listed here.
+pub const READ_ONLY_TOOLS: [&str; 14] = [
+    "health",
+    "issue_all_complete",
+    "issue_board",
+    "issue_current",
+    "issue_list",
+    "issue_next",
+    "issue_show",
+    "issue_stats",
+    "memo_get",
+    "memo_get_all_context",
+    "memo_list",
+    "memo_search",
+    "search_query",
+    "workflow_status",
+];
+
+/// Issue tools that create, change or merge issues
+const ISSUE_MUTATION_TOOLS: [&str; 11] = [
+    "issue_create",
+    "issue_update",
+    "issue_mark_complete",
+    "issue_reopen",
+    "issue_work",
+    "issue_merge",
+    "issue_sync",
+    "issue_bulk",
+    "issue_transition",
+    "issue_assign",
+    "issue_check",
+];
+
+/// A group of related MCP tools
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ToolGroup {
+    /// Issue tools that only read issues
+    Issues,
+    /// Issue tools that create, change or merge issues
+    IssueMutation,
+    /// Memo tools
+    Memos,
+    /// Semantic search tools
+    Search,
+    /// Workflow tools
+    Workflows,
+    /// Cost reporting tools
+    Cost,
+}
+
+impl ToolGroup {
+    /// Every tool group
+    pub const ALL: [ToolGroup; 6] = [
+        ToolGroup::Issues,
+        ToolGroup::IssueMutation,
+        ToolGroup::Memos,
+        ToolGroup::Search,
+        ToolGroup::Workflows,
+        ToolGroup::Cost,
+    ];
+
+    /// The group a tool belongs to, if any
+    pub fn for_tool(name: &str) -> Option<Self> {
+        if ISSUE_MUTATION_TOOLS.contains(&name) {
+            return Some(ToolGroup::IssueMutation);
+        }
+        match name.split('_').next()? {
+            "issue" => Some(ToolGroup::Issues),
+            "memo" => Some(ToolGroup::Memos),
+            "search" => Some(ToolGroup::Search),
+            "workflow" | "flow" => Some(ToolGroup::Workflows),
+            "cost" => Some(ToolGroup::Cost),
+            _ => None,
+        }
+    }
+
+    /// Name used in configuration and capabilities
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ToolGroup::Issues => "issues",
+            ToolGroup::IssueMutation => "issue_mutation",
+            ToolGroup::Memos => "memos",
+            ToolGroup::Search => "search",
+            ToolGroup::Workflows => "workflows",
+            ToolGroup::Cost => "cost",
+        }
+    }
+}
+
+impl fmt::Display for ToolGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ToolGroup {
+    type Err = SwissArmyHammerError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let name = s.trim().to_lowercase().replace('-', "_");
+        ToolGroup::ALL
+            .into_iter()
+            .find(|group| group.as_str() == name)
+            .ok_or_else(|| {
+                SwissArmyHammerError::Config(format!(
+                    "Unknown MCP tool group '{s}': expected one of {}",
+                    ToolGroup::ALL.map(|group| group.as_str()).join(", ")
+                ))
+            })
+    }
+}
+
+/// The tool groups a server or client has enabled
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolGroups {
+    enabled: BTreeSet<ToolGroup>,
+    /// Whether only [`READ_ONLY_TOOLS`] are served
+    read_only: bool,
+}
+
+impl Default for ToolGroups {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+impl ToolGroups {
+    /// Every group enabled
+    pub fn all() -> Self {
+        Self {
+            enabled: ToolGroup::ALL.into_iter().collect(),
+            read_only: false,
+        }
+    }
+
+    /// Groups enabled by name, all groups when `enabled` is empty, minus the
+    /// `disabled` groups
+    ///
+    /// Unknown names are logged and ignored.
+    pub fn from_names<S: AsRef<str>>(enabled: &[S], disabled: &[S]) -> Self {
+        let mut groups = if enabled.is_empty() {
+            Self::all()
+        } else {
+            Self {
+                enabled: parse_groups(enabled).collect(),
+                read_only: false,
+            }
+        };
+        for group in parse_groups(disabled) {
+            groups.enabled.remove(&group);
+        }
+        groups
+    }
+
+    /// Groups enabled by the server configuration
+    pub fn from_config(config: &Config) -> Self {
+        Self::from_names(&config.mcp_tool_groups, &config.mcp_disabled_tool_groups)
+    }
+
+    /// The same groups, serving only tools that never change state
+    pub fn read_only(mut self) -> Self {
+        self.read_only = true;
+        self
+    }
+
+    /// Whether only read-only tools are served
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Whether a group is enabled
+    pub fn is_enabled(&self, group: ToolGroup) -> bool {
+        self.enabled.contains(&group)
+    }
+
+    /// Whether a tool may be listed and called
+    ///
+    /// Tools that belong to no group are always allowed, unless only
+    /// read-only tools are served.
+    pub fn allows_tool(&self, name: &str) -> bool {
+        if self.read_only && !READ_ONLY_TOOLS.contains(&name) {
+            return false;
+        }
+        ToolGroup::for_tool(name).map_or(true, |group| self.is_enabled(group))
+    }
+
+    /// Enabled groups in a stable order
+    pub fn iter(&self) -> impl Iterator<Item = ToolGroup> + '_ {
+        self.enabled.iter().copied()
+    }
+
+    /// The groups a client gets, narrowed by its initialize capabilities
+    pub fn negotiate(&self, client: &ClientCapabilities) -> Self {
+        let Some(requested) = client
+            .experimental
+            .as_ref()
+            .and_then(|experimental| experimental.get(CAPABILITY_KEY))
+        else {
+            return self.clone();
+        };
+
+        let names = |field: &str| -> Vec<String> {
+            match requested.get(field) {
+                Some(Value::Array(names)) => names
+                    .iter()
+                    .filter_map(|name| name.as_str().map(str::to_string))
+                    .collect(),
+                Some(other) => {
+                    tracing::warn!(
+                        "Ignoring MCP client '{}': expected a list, got {}",
+                        field,
+                        other
+                    );
+                    Vec::new()
+                }
+                None => Vec::new(),
+            }
+        };
+        let client_groups = Self::from_names(
+            &names(TOOL_GROUPS_FIELD),
+            &names(DISABLED_TOOL_GROUPS_FIELD),
+        );
+
+        Self {
+            enabled: self
+                .enabled
+                .intersection(&client_groups.enabled)
+                .copied()
+                .collect(),
+            read_only: self.read_only || requested.get(READ_ONLY_FIELD) == Some(&Value::Bool(true)),
+        }
+    }
+
+    /// Experimental server capabilities describing the enabled groups
+    pub fn to_capabilities(&self) -> ExperimentalCapabilities {
+        let groups = self
+            .iter()
+            .map(|group| Value::String(group.as_str().to_string()))
+            .collect();
+        let mut entry = JsonObject::new();
+        entry.insert(TOOL_GROUPS_FIELD.to_string(), Value::Array(groups));
+        if self.read_only {
+            entry.insert(READ_ONLY_FIELD.to_string(), Value::Bool(true));
+        }
+
+        let mut capabilities = ExperimentalCapabilities::new();
+        capabilities.insert(CAPABILITY_KEY.to_string(), entry);
+        capabilities
+    }
+}
+
+/// Parse group names, logging and skipping unknown ones
+fn parse_groups<S: AsRef<str>>(names: &[S]) -> impl Iterator<Item = ToolGroup> + '_ {
+    names
+        .iter()
+        .filter_map(|name| match name.as_ref().parse::<ToolGroup>() {
+            Ok(group) => Some(group),
+            Err(e) => {
+                tracing::warn!("{}", e);
+                None
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn client(experimental: Value) -> ClientCapabilities {
+        serde_json::from_value(json!({ "experimental": experimental })).unwrap()
+    }
+
+    #[test]
+    fn test_tool_groups_by_name() {
+        assert_eq!(ToolGroup::for_tool("issue_list"), Some(ToolGroup::Issues));
+        assert_eq!(
+            ToolGroup::for_tool("issue_merge"),
+            Some(ToolGroup::IssueMutation)
+        );
+        assert_eq!(ToolGroup::for_tool("memo_create"), Some(ToolGroup::Memos));
+        assert_eq!(ToolGroup::for_tool("search_query"), Some(ToolGroup::Search));
+        assert_eq!(ToolGroup::for_tool("list_prompts"), None);
+        assert_eq!(
+            "Issue-Mutation".parse::<ToolGroup>().unwrap(),
+            ToolGroup::IssueMutation
+        );
+        assert!("everything".parse::<ToolGroup>().is_err());
+    }
+
+    #[test]
+    fn test_from_names() {
+        let none: [&str; 0] = [];
+        assert_eq!(ToolGroups::from_names(&none, &none), ToolGroups::all());
+
+        let groups = ToolGroups::from_names(&["issues", "memos", "bogus"], &none);
+        assert_eq!(
+            groups.iter().collect::<Vec<_>>(),
+            vec![ToolGroup::Issues, ToolGroup::Memos]
+        );
+
+        let groups = ToolGroups::from_names(&none, &["issue_mutation"]);
+        assert!(groups.allows_tool("issue_list"));
+        assert!(!groups.allows_tool("issue_create"));
+        assert!(groups.allows_tool("some_other_tool"));
+    }
+
+    #[test]
+    fn test_client_can_only_narrow_groups() {
+        let server = ToolGroups::from_names(&["issues", "issue_mutation", "memos"], &[]);
+
+        let read_only = server.negotiate(&client(json!({
+            "swissarmyhammer": { "disabledToolGroups": ["issue_mutation"] }
+        })));
+        assert_eq!(
+            read_only.iter().collect::<Vec<_>>(),
+            vec![ToolGroup::Issues, ToolGroup::Memos]
+        );
+
+        let widened = server.negotiate(&client(json!({
+            "swissarmyhammer": { "toolGroups": ["issues", "search"] }
+        })));
+        assert_eq!(widened.iter().collect::<Vec<_>>(), vec![ToolGroup::Issues]);
+
+        assert_eq!(server.negotiate(&ClientCapabilities::default()), server);
+    }
+
+    #[test]
+    fn test_read_only_serves_only_read_only_tools() {
+        let groups = ToolGroups::all().read_only();
+        assert!(groups.allows_tool("issue_list"));
+        assert!(groups.allows_tool("memo_search"));
+        assert!(groups.allows_tool("health"));
+        for tool in ["issue_create", "memo_delete", "issue_merge", "workflow_run"] {
+            assert!(!groups.allows_tool(tool), "{tool} should be hidden");
+        }
+        // Tools outside every group are only served when known to be safe
+        assert!(!groups.allows_tool("some_other_tool"));
+
+        // A client can ask for read-only tools but can't leave read-only mode
+        let server = ToolGroups::all();
+        let client_read_only = server.negotiate(&client(json!({
+            "swissarmyhammer": { "readOnly": true }
+        })));
+        assert!(client_read_only.is_read_only());
+        let still_read_only = groups.negotiate(&client(json!({
+            "swissarmyhammer": { "readOnly": false }
+        })));
+        assert!(still_read_only.is_read_only());
+
+        let capabilities = serde_json::to_value(
+            ToolGroups::from_names(&["memos"], &[])
+                .read_only()
+                .to_capabilities(),
+        )
+        .unwrap();
+        assert_eq!(
+            capabilities,
+            json!({ "swissarmyhammer": { "toolGroups": ["memos"], "readOnly": true } })
+        );
+    }
+
+    #[test]
+    fn test_to_capabilities() {
+        let groups = ToolGroups::from_names(&["memos", "issues"], &[]);
+        let capabilities = serde_json::to_value(groups.to_capabilities()).unwrap();
+        assert_eq!(
+            capabilities,
+            json!({ "swissarmyhammer": { "toolGroups": ["issues", "memos"] } })
+        );
+    }
+}
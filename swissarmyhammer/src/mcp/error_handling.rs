@@ -93,7 +93,7 @@ impl ErrorHandler {
         let before_count = library.list().map(|p| p.len()).unwrap_or(0);
 
         // Clear existing prompts and reload
-        *library = PromptLibrary::new();
+        *library = PromptLibrary::from_config();
         resolver.load_all_prompts(&mut library)?;
 
         let after_count = library.list()?.len();
@@ -1,49 +1,103 @@
 //! File watching functionality for MCP server
 
-use crate::file_watcher::{FileWatcher, FileWatcherCallback};
+use crate::cost::{BudgetLevel, CostTracker};
+use crate::events::{event_bus, Event};
+use crate::file_watcher::{FileWatcher, FileWatcherCallback, WatchedContent};
+use crate::history::ChangeKind;
 use crate::{Result, SwissArmyHammerError};
+use rmcp::model::{LoggingLevel, LoggingMessageNotificationParam};
 use rmcp::RoleServer;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
 
-/// Callback implementation for file watcher that handles prompt reloading
+/// Logger name of budget event notifications
+const BUDGET_LOGGER: &str = "swissarmyhammer.budget";
+
+/// Logger name of event bus notifications
+const EVENTS_LOGGER: &str = "swissarmyhammer.events";
+
+/// Callback implementation for file watcher that reloads changed content
+///
+/// Reloads and memo changes are published on the event bus, and reach the
+/// client through [`spawn_event_notifications`].
 #[derive(Clone)]
 pub struct McpFileWatcherCallback {
     server: super::McpServer,
     peer: rmcp::Peer<RoleServer>,
+    /// Number of budget events already sent, or there before the server started
+    budget_events_seen: Arc<AtomicUsize>,
 }
 
 impl McpFileWatcherCallback {
     /// Create a new file watcher callback with the given server and peer
     pub fn new(server: super::McpServer, peer: rmcp::Peer<RoleServer>) -> Self {
-        Self { server, peer }
+        let budget_events = CostTracker::new_default()
+            .and_then(|tracker| tracker.events())
+            .map(|events| events.len())
+            .unwrap_or(0);
+        Self {
+            server,
+            peer,
+            budget_events_seen: Arc::new(AtomicUsize::new(budget_events)),
+        }
+    }
+
+    /// Send budget events raised since the last call as logging messages
+    async fn notify_budget_events(&self) -> Result<()> {
+        let events = CostTracker::new_default()?.events()?;
+        let seen = self.budget_events_seen.swap(events.len(), Ordering::SeqCst);
+        for event in events.into_iter().skip(seen) {
+            let level = match event.level {
+                BudgetLevel::Warning | BudgetLevel::Exceeded => LoggingLevel::Warning,
+                BudgetLevel::Refused => LoggingLevel::Error,
+            };
+            let data = serde_json::to_value(&event)?;
+            let notification = LoggingMessageNotificationParam {
+                level,
+                logger: Some(BUDGET_LOGGER.to_string()),
+                data,
+            };
+            match self.peer.notify_logging_message(notification).await {
+                Ok(_) => tracing::info!("📢 Sent budget notification to client: {}", event),
+                Err(e) => tracing::error!("❌ Failed to send budget notification: {}", e),
+            }
+        }
+        Ok(())
     }
 }
 
 impl FileWatcherCallback for McpFileWatcherCallback {
-    async fn on_file_changed(&self, paths: Vec<std::path::PathBuf>) -> Result<()> {
-        tracing::info!("📄 Prompt file changed: {:?}", paths);
+    async fn on_file_changed(
+        &self,
+        content: WatchedContent,
+        paths: Vec<std::path::PathBuf>,
+    ) -> Result<()> {
+        tracing::info!("📄 {:?} file changed: {:?}", content, paths);
 
-        // Reload the library
-        if let Err(e) = self.server.reload_prompts().await {
-            tracing::error!("❌ Failed to reload prompts: {}", e);
-            return Err(e);
+        if content == WatchedContent::BudgetEvents {
+            return self.notify_budget_events().await;
         }
-        tracing::info!("✅ Prompts reloaded successfully");
 
-        // Send notification to client about prompt list change
-        let peer_clone = self.peer.clone();
-        tokio::spawn(async move {
-            match peer_clone.notify_prompt_list_changed().await {
-                Ok(_) => {
-                    tracing::info!("📢 Sent prompts/listChanged notification to client");
-                }
-                Err(e) => {
-                    tracing::error!("❌ Failed to send notification: {}", e);
-                }
+        // Reload what the server caches, which publishes the reload; only the
+        // changed prompts are loaded again, and memos are read from disk on
+        // every request, so their changes are only published
+        let reloaded = match content {
+            WatchedContent::Prompts => self.server.apply_prompt_changes(&paths).await,
+            WatchedContent::Workflows => self.server.reload_workflows().await,
+            WatchedContent::Memos => {
+                publish_memo_changes(&paths);
+                Ok(())
             }
-        });
-
+            WatchedContent::BudgetEvents => Ok(()),
+        };
+        if let Err(e) = reloaded {
+            tracing::error!("❌ Failed to reload {:?}: {}", content, e);
+            return Err(e);
+        }
+        tracing::info!("✅ {:?} reloaded successfully", content);
         Ok(())
     }
 
@@ -52,6 +106,80 @@ impl FileWatcherCallback for McpFileWatcherCallback {
     }
 }
 
+/// Publish memo files changed on disk as memo events
+fn publish_memo_changes(paths: &[PathBuf]) {
+    for path in paths {
+        let Some(id) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        let change = if path.exists() {
+            ChangeKind::Updated
+        } else {
+            ChangeKind::Deleted
+        };
+        event_bus().publish(Event::MemoUpdated {
+            id: id.to_string(),
+            change,
+        });
+    }
+}
+
+/// Forward events published on the event bus to the client
+///
+/// Prompt reloads are sent as prompt list changes, workflow reloads and memo
+/// changes as resource list changes, and other events as logging messages,
+/// except budget events, which are forwarded from the budget events file.
+/// The task runs until it is aborted.
+pub fn spawn_event_notifications(peer: rmcp::Peer<RoleServer>) -> JoinHandle<()> {
+    let mut events = event_bus().subscribe();
+    tokio::spawn(async move {
+        while let Some(event) = events.recv().await {
+            let (notification, result) = match &event {
+                Event::PromptsReloaded { .. } => (
+                    "prompts/listChanged",
+                    peer.notify_prompt_list_changed().await,
+                ),
+                Event::WorkflowsReloaded { .. } | Event::MemoUpdated { .. } => (
+                    "resources/listChanged",
+                    peer.notify_resource_list_changed().await,
+                ),
+                // Budget events are forwarded from the budget events file
+                Event::BudgetExceeded(_) => continue,
+                Event::IssueCreated { .. }
+                | Event::IssueCompleted { .. }
+                | Event::WorkflowStateChanged { .. }
+                | Event::WorkflowRunProgress(_)
+                | Event::WorkflowRunFinished { .. } => {
+                    let data = match serde_json::to_value(&event) {
+                        Ok(data) => data,
+                        Err(e) => {
+                            tracing::error!("❌ Failed to serialize event {}: {}", event, e);
+                            continue;
+                        }
+                    };
+                    let notification = LoggingMessageNotificationParam {
+                        level: LoggingLevel::Info,
+                        logger: Some(EVENTS_LOGGER.to_string()),
+                        data,
+                    };
+                    (
+                        "notifications/message",
+                        peer.notify_logging_message(notification).await,
+                    )
+                }
+            };
+            match result {
+                Ok(_) => {
+                    tracing::info!("📢 Sent {} notification to client: {}", notification, event);
+                }
+                Err(e) => {
+                    tracing::error!("❌ Failed to send {} notification: {}", notification, e);
+                }
+            }
+        }
+    })
+}
+
 /// File watcher operations for MCP server
 pub struct McpFileWatcher {
     file_watcher: Arc<Mutex<FileWatcher>>,
@@ -63,7 +191,7 @@ impl McpFileWatcher {
         Self { file_watcher }
     }
 
-    /// Start watching prompt directories for file changes.
+    /// Start watching prompt, workflow and memo directories for file changes.
     ///
     /// When files change, the server will automatically reload prompts and
     /// send notifications to the MCP client.
@@ -136,7 +264,7 @@ impl McpFileWatcher {
         Err(last_error.unwrap())
     }
 
-    /// Stop watching prompt directories for file changes.
+    /// Stop watching prompt, workflow and memo directories for file changes.
     ///
     /// This should be called when the MCP server is shutting down.
     pub async fn stop_file_watching(&self) {
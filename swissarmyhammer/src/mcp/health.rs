@@ -0,0 +1,296 @@
+//! Health of a running MCP server
+//!
+//! The server records when it started, how many prompts and workflows it has
+//! loaded, whether its file watcher runs, how its last reload went and how
+//! long reloads take. The
+//! `health` tool reports this together with the memory the process uses and
+//! a few self-diagnostics, so operators can check an embedded server without
+//! restarting it.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Outcome of a health check, ordered from best to worst
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    /// Working as expected
+    Ok,
+    /// Working, but in a degraded way
+    Warning,
+    /// Not working
+    Error,
+}
+
+/// One self-diagnostic of the server
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HealthCheck {
+    /// What was checked
+    pub name: String,
+    /// How the check went
+    pub status: HealthStatus,
+    /// What was found
+    pub message: String,
+}
+
+impl HealthCheck {
+    fn new(name: &str, status: HealthStatus, message: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+/// How content was reloaded after a change
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReloadKind {
+    /// Everything was loaded again
+    Full,
+    /// Only the changed files were loaded again
+    Incremental,
+}
+
+/// How long the reloads after changes took
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct ReloadMetrics {
+    /// Reloads since the server started
+    pub reloads: u64,
+    /// Reloads that only loaded the changed files again
+    pub incremental: u64,
+    /// Milliseconds the last reload took
+    pub last_millis: u64,
+    /// Milliseconds the slowest reload took
+    pub max_millis: u64,
+    /// Average milliseconds of the reloads
+    pub average_millis: u64,
+    #[serde(skip)]
+    total_millis: u64,
+}
+
+impl ReloadMetrics {
+    fn record(&mut self, kind: ReloadKind, elapsed: Duration) {
+        let millis = u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX);
+        self.reloads += 1;
+        if kind == ReloadKind::Incremental {
+            self.incremental += 1;
+        }
+        self.last_millis = millis;
+        self.max_millis = self.max_millis.max(millis);
+        self.total_millis = self.total_millis.saturating_add(millis);
+        self.average_millis = self.total_millis / self.reloads;
+    }
+}
+
+/// Health of the server at one point in time
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthReport {
+    /// The worst status of the checks
+    pub status: HealthStatus,
+    /// Version of the server
+    pub version: String,
+    /// When the server started
+    pub started_at: DateTime<Utc>,
+    /// Seconds since the server started
+    pub uptime_seconds: u64,
+    /// Prompts loaded, including partials
+    pub prompts: usize,
+    /// Workflows loaded
+    pub workflows: usize,
+    /// Whether the file watcher is running
+    pub file_watcher: bool,
+    /// When prompts or workflows were last reloaded after a change
+    pub last_reload: Option<DateTime<Utc>>,
+    /// How long reloads after changes took
+    pub reload_latency: ReloadMetrics,
+    /// Resident memory of the process in bytes, where the platform reports it
+    pub memory_bytes: Option<u64>,
+    /// Self-diagnostics
+    pub checks: Vec<HealthCheck>,
+}
+
+#[derive(Debug, Default)]
+struct HealthState {
+    prompts: usize,
+    workflows: usize,
+    watching: bool,
+    last_reload: Option<DateTime<Utc>>,
+    last_reload_error: Option<String>,
+    reload_latency: ReloadMetrics,
+}
+
+/// Health the server records as it runs
+#[derive(Debug)]
+pub struct ServerHealth {
+    started: Instant,
+    started_at: DateTime<Utc>,
+    state: Mutex<HealthState>,
+}
+
+impl Default for ServerHealth {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ServerHealth {
+    /// Start recording the health of a server starting now
+    pub fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            started_at: Utc::now(),
+            state: Mutex::new(HealthState::default()),
+        }
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, HealthState> {
+        self.state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Record the number of prompts loaded
+    pub fn set_prompts(&self, count: usize) {
+        self.state().prompts = count;
+    }
+
+    /// Record the number of workflows loaded
+    pub fn set_workflows(&self, count: usize) {
+        self.state().workflows = count;
+    }
+
+    /// Record whether the file watcher is running
+    pub fn set_watching(&self, watching: bool) {
+        self.state().watching = watching;
+    }
+
+    /// Record a reload after a change, how long it took and the error it
+    /// failed with
+    pub fn record_reload(&self, kind: ReloadKind, elapsed: Duration, error: Option<String>) {
+        let mut state = self.state();
+        state.last_reload = Some(Utc::now());
+        state.last_reload_error = error;
+        state.reload_latency.record(kind, elapsed);
+    }
+
+    /// Report the health of the server now
+    pub fn report(&self) -> HealthReport {
+        let state = self.state();
+        let prompts = if state.prompts > 0 {
+            HealthCheck::new(
+                "Prompts",
+                HealthStatus::Ok,
+                format!("{} prompts loaded", state.prompts),
+            )
+        } else {
+            HealthCheck::new("Prompts", HealthStatus::Warning, "No prompts are loaded")
+        };
+        let file_watcher = if state.watching {
+            HealthCheck::new("File watcher", HealthStatus::Ok, "Watching for changes")
+        } else {
+            HealthCheck::new(
+                "File watcher",
+                HealthStatus::Warning,
+                "Not running; changed files are not reloaded",
+            )
+        };
+        let last_reload = match &state.last_reload_error {
+            Some(error) => HealthCheck::new(
+                "Last reload",
+                HealthStatus::Error,
+                format!("Failed: {error}"),
+            ),
+            None => HealthCheck::new(
+                "Last reload",
+                HealthStatus::Ok,
+                match state.last_reload {
+                    Some(_) => "Succeeded",
+                    None => "No reload yet",
+                },
+            ),
+        };
+        let checks = vec![prompts, file_watcher, last_reload];
+
+        HealthReport {
+            status: checks
+                .iter()
+                .map(|check| check.status)
+                .max()
+                .unwrap_or(HealthStatus::Ok),
+            version: crate::VERSION.to_string(),
+            started_at: self.started_at,
+            uptime_seconds: self.started.elapsed().as_secs(),
+            prompts: state.prompts,
+            workflows: state.workflows,
+            file_watcher: state.watching,
+            last_reload: state.last_reload,
+            reload_latency: state.reload_latency,
+            memory_bytes: resident_memory_bytes(),
+            checks,
+        }
+    }
+}
+
+/// Resident memory of this process, read from `/proc` on Linux
+#[cfg(target_os = "linux")]
+fn resident_memory_bytes() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmRSS:"))?;
+    let kilobytes: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kilobytes * 1024)
+}
+
+/// Resident memory of this process, not reported on this platform
+#[cfg(not(target_os = "linux"))]
+fn resident_memory_bytes() -> Option<u64> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_reflects_recorded_health() {
+        let health = ServerHealth::new();
+        let report = health.report();
+        assert_eq!(report.status, HealthStatus::Warning);
+        assert_eq!(report.prompts, 0);
+        assert!(report.last_reload.is_none());
+
+        health.set_prompts(12);
+        health.set_workflows(3);
+        health.set_watching(true);
+        health.record_reload(ReloadKind::Full, Duration::from_millis(90), None);
+        health.record_reload(ReloadKind::Incremental, Duration::from_millis(10), None);
+        let report = health.report();
+        assert_eq!(report.status, HealthStatus::Ok);
+        assert_eq!(report.workflows, 3);
+        assert!(report.file_watcher);
+        assert!(report.last_reload.is_some());
+        assert_eq!(report.reload_latency.reloads, 2);
+        assert_eq!(report.reload_latency.incremental, 1);
+        assert_eq!(report.reload_latency.last_millis, 10);
+        assert_eq!(report.reload_latency.max_millis, 90);
+        assert_eq!(report.reload_latency.average_millis, 50);
+        #[cfg(target_os = "linux")]
+        assert!(report.memory_bytes.is_some_and(|bytes| bytes > 0));
+
+        health.record_reload(
+            ReloadKind::Full,
+            Duration::from_millis(5),
+            Some("permission denied".to_string()),
+        );
+        let report = health.report();
+        assert_eq!(report.status, HealthStatus::Error);
+        let reload = report
+            .checks
+            .iter()
+            .find(|check| check.name == "Last reload")
+            .unwrap();
+        assert_eq!(reload.message, "Failed: permission denied");
+    }
+}
@@ -19,6 +19,9 @@ pub struct CreateMemoRequest {
     pub title: String,
     /// Markdown content of the memo
     pub content: String,
+    /// Notebook to file the memo in, created if it doesn't exist
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notebook: Option<String>,
 }
 
 /// Request to get a memo by ID
@@ -29,12 +32,15 @@ pub struct CreateMemoRequest {
 /// ```ignore
 /// GetMemoRequest {
 ///     id: "01ARZ3NDEKTSV4RRFFQ69G5FAV".to_string(),
+///     format: None,
 /// }
 /// ```
 #[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
 pub struct GetMemoRequest {
     /// ULID identifier of the memo to retrieve
     pub id: String,
+    /// Output format (table, json)
+    pub format: Option<String>,
 }
 
 /// Request to update a memo's content
@@ -80,42 +86,111 @@ pub struct DeleteMemoRequest {
 /// ```ignore
 /// SearchMemosRequest {
 ///     query: "meeting notes project".to_string(),
+///     format: None,
 /// }
 /// ```
 #[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
 pub struct SearchMemosRequest {
     /// Search query string to match against memo titles and content
     pub query: String,
+    /// Output format (table, json)
+    pub format: Option<String>,
 }
 
 /// Request to list all memos
 ///
 /// # Examples
 ///
-/// List all available memos:
+/// List all available memos as JSON:
 /// ```ignore
 /// ListMemosRequest {
-///     // No parameters needed
+///     format: Some("json".to_string()),
 /// }
 /// ```
 #[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
 pub struct ListMemosRequest {
-    // No parameters needed for listing all memos
+    /// Output format (table, json)
+    pub format: Option<String>,
+    /// Only list the memos filed in this notebook
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notebook: Option<String>,
 }
 
 /// Request to get all memos as context
 ///
 /// # Examples
 ///
-/// Get all memo content for AI context:
+/// Get the newest memos about deployment that fit in 2000 tokens:
 /// ```ignore
 /// GetAllContextRequest {
-///     // No parameters needed
+///     query: Some("deploy".to_string()),
+///     max_tokens: Some(2000),
+///     include_metadata: None,
 /// }
 /// ```
-#[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
+#[derive(Debug, Default, Deserialize, Serialize, schemars::JsonSchema)]
 pub struct GetAllContextRequest {
-    // No parameters needed - returns all memo content formatted for AI consumption
+    /// Only include memos whose title or content mention every word of the query
+    pub query: Option<String>,
+    /// Approximate token budget for the returned context
+    pub max_tokens: Option<usize>,
+    /// Include memo titles, IDs and dates (default: true)
+    pub include_metadata: Option<bool>,
+}
+
+impl GetAllContextRequest {
+    /// Delimiter placed between memos in the returned context
+    pub const DELIMITER: &'static str =
+        "\n\n================================================================================\n\n";
+
+    /// Context options for this request
+    pub fn context_options(&self) -> crate::memoranda::ContextOptions {
+        crate::memoranda::ContextOptions {
+            include_metadata: self.include_metadata.unwrap_or(true),
+            max_tokens: self.max_tokens,
+            delimiter: Self::DELIMITER.to_string(),
+            query: self
+                .query
+                .as_deref()
+                .map(str::trim)
+                .filter(|query| !query.is_empty())
+                .map(str::to_string),
+        }
+    }
+}
+
+/// Request to verify memo integrity
+///
+/// # Examples
+///
+/// Check memos and restore any corrupted ones from backups:
+/// ```ignore
+/// VerifyMemosRequest {
+///     restore: true,
+/// }
+/// ```
+#[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct VerifyMemosRequest {
+    /// Restore corrupted memos from their most recent backup snapshot
+    #[serde(default)]
+    pub restore: bool,
+}
+
+/// Request to compact memo storage
+///
+/// # Examples
+///
+/// Preview what compaction would remove:
+/// ```ignore
+/// CompactMemosRequest {
+///     dry_run: true,
+/// }
+/// ```
+#[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct CompactMemosRequest {
+    /// Only report what would be removed and rewritten
+    #[serde(default)]
+    pub dry_run: bool,
 }
 
 #[cfg(test)]
@@ -127,6 +202,7 @@ mod tests {
         let request = CreateMemoRequest {
             title: "Test Title".to_string(),
             content: "Test Content".to_string(),
+            notebook: None,
         };
 
         let json = serde_json::to_string(&request).unwrap();
@@ -140,6 +216,7 @@ mod tests {
     fn test_get_memo_request_serialization() {
         let request = GetMemoRequest {
             id: "01ARZ3NDEKTSV4RRFFQ69G5FAV".to_string(),
+            format: None,
         };
 
         let json = serde_json::to_string(&request).unwrap();
@@ -178,39 +255,59 @@ mod tests {
     fn test_search_memos_request_serialization() {
         let request = SearchMemosRequest {
             query: "test search".to_string(),
+            format: Some("json".to_string()),
         };
 
         let json = serde_json::to_string(&request).unwrap();
         let deserialized: SearchMemosRequest = serde_json::from_str(&json).unwrap();
 
         assert_eq!(request.query, deserialized.query);
+        assert_eq!(request.format, deserialized.format);
     }
 
     #[test]
     fn test_list_memos_request_serialization() {
-        let request = ListMemosRequest {};
+        let request = ListMemosRequest {
+            format: None,
+            notebook: Some("work".to_string()),
+        };
 
         let json = serde_json::to_string(&request).unwrap();
         let deserialized: ListMemosRequest = serde_json::from_str(&json).unwrap();
+        assert_eq!(request.format, deserialized.format);
+        assert_eq!(request.notebook, deserialized.notebook);
 
-        // No fields to compare for empty struct
-        assert_eq!(
-            std::mem::size_of_val(&request),
-            std::mem::size_of_val(&deserialized)
-        );
+        // The format is optional
+        let deserialized: ListMemosRequest = serde_json::from_str("{}").unwrap();
+        assert_eq!(deserialized.format, None);
     }
 
     #[test]
     fn test_get_all_context_request_serialization() {
-        let request = GetAllContextRequest {};
+        let request = GetAllContextRequest {
+            query: Some("deploy".to_string()),
+            max_tokens: Some(2000),
+            include_metadata: Some(false),
+        };
 
         let json = serde_json::to_string(&request).unwrap();
         let deserialized: GetAllContextRequest = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.query.as_deref(), Some("deploy"));
+        assert_eq!(deserialized.max_tokens, Some(2000));
+        assert_eq!(deserialized.include_metadata, Some(false));
+
+        // Every parameter is optional
+        let deserialized: GetAllContextRequest = serde_json::from_str("{}").unwrap();
+        let options = deserialized.context_options();
+        assert!(options.include_metadata);
+        assert_eq!(options.max_tokens, None);
+        assert_eq!(options.query, None);
 
-        // No fields to compare for empty struct
-        assert_eq!(
-            std::mem::size_of_val(&request),
-            std::mem::size_of_val(&deserialized)
-        );
+        // A blank query matches every memo
+        let blank = GetAllContextRequest {
+            query: Some("  ".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(blank.context_options().query, None);
     }
 }
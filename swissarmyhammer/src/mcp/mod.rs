@@ -4,10 +4,13 @@
 //! and various tools through the Model Context Protocol.
 
 // Module declarations
+pub mod capabilities;
 pub mod error_handling;
 pub mod file_watcher;
+pub mod health;
 pub mod memo_types;
 pub mod responses;
+pub mod sampling;
 pub mod search_types;
 pub mod server;
 pub mod shared_utils;
@@ -22,10 +25,15 @@ pub mod utils;
 mod tests;
 
 // Re-export commonly used items from submodules
+pub use capabilities::{ToolGroup, ToolGroups};
+pub use health::{
+    HealthCheck, HealthReport, HealthStatus, ReloadKind, ReloadMetrics, ServerHealth,
+};
 pub use server::McpServer;
 pub use tool_handlers::ToolHandlers;
 pub use tool_registry::{
-    register_issue_tools, register_memo_tools, register_search_tools, ToolContext, ToolRegistry,
+    register_issue_tools, register_memo_tools, register_search_tools, register_server_tools,
+    register_workflow_tools, ToolContext, ToolRegistry,
 };
 pub use types::{GetPromptRequest, ListPromptsRequest};
 
@@ -0,0 +1,113 @@
+//! Prompt sampling through the connected MCP client
+//!
+//! When a client that supports sampling connects, the server registers an
+//! [`McpSampler`] so workflow prompt actions written with `backend="sampling"`
+//! are answered by the client's model instead of the local Claude CLI.
+
+use crate::workflow::{PromptSampler, SamplingRequest, SamplingResponse};
+use crate::{Result, SwissArmyHammerError};
+use async_trait::async_trait;
+use rmcp::model::{
+    Content, ContextInclusion, CreateMessageRequestParam, ModelHint, ModelPreferences, Role,
+    SamplingMessage,
+};
+use rmcp::{Peer, RoleServer};
+
+/// Sends rendered prompts to the client with `sampling/createMessage` requests
+#[derive(Clone)]
+pub struct McpSampler {
+    peer: Peer<RoleServer>,
+}
+
+impl McpSampler {
+    /// Create a sampler for a connected client
+    pub fn new(peer: Peer<RoleServer>) -> Self {
+        Self { peer }
+    }
+}
+
+#[async_trait]
+impl PromptSampler for McpSampler {
+    async fn create_message(&self, request: SamplingRequest) -> Result<SamplingResponse> {
+        let result = self
+            .peer
+            .create_message(create_message_param(request))
+            .await
+            .map_err(|e| SwissArmyHammerError::Other(format!("Sampling request failed: {e}")))?;
+
+        let text = result
+            .message
+            .content
+            .as_text()
+            .map(|content| content.text.clone())
+            .ok_or_else(|| {
+                SwissArmyHammerError::Other(format!(
+                    "Sampling response from {} contained no text",
+                    result.model
+                ))
+            })?;
+        Ok(SamplingResponse {
+            text,
+            model: result.model,
+        })
+    }
+}
+
+/// The MCP request for a rendered prompt
+fn create_message_param(request: SamplingRequest) -> CreateMessageRequestParam {
+    CreateMessageRequestParam {
+        messages: vec![SamplingMessage {
+            role: Role::User,
+            content: Content::text(request.prompt),
+        }],
+        model_preferences: request.model.map(|model| ModelPreferences {
+            hints: Some(vec![ModelHint { name: Some(model) }]),
+            cost_priority: None,
+            speed_priority: None,
+            intelligence_priority: None,
+        }),
+        system_prompt: request.system_prompt,
+        include_context: Some(ContextInclusion::None),
+        temperature: request.temperature,
+        max_tokens: request.max_tokens,
+        stop_sequences: None,
+        metadata: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_message_param() {
+        let param = create_message_param(SamplingRequest {
+            prompt: "Summarize notes.md".to_string(),
+            system_prompt: Some("Be brief".to_string()),
+            model: Some("claude-sonnet".to_string()),
+            temperature: Some(0.2),
+            max_tokens: 512,
+        });
+
+        assert_eq!(param.messages.len(), 1);
+        assert_eq!(param.messages[0].role, Role::User);
+        assert_eq!(
+            param.messages[0].content.as_text().unwrap().text,
+            "Summarize notes.md"
+        );
+        let hints = param.model_preferences.unwrap().hints.unwrap();
+        assert_eq!(hints[0].name.as_deref(), Some("claude-sonnet"));
+        assert_eq!(param.system_prompt.as_deref(), Some("Be brief"));
+        assert_eq!(param.temperature, Some(0.2));
+        assert_eq!(param.max_tokens, 512);
+
+        let param = create_message_param(SamplingRequest {
+            prompt: "Hi".to_string(),
+            system_prompt: None,
+            model: None,
+            temperature: None,
+            max_tokens: 64,
+        });
+        assert!(param.model_preferences.is_none());
+    }
+}
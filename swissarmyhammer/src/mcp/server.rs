@@ -1,15 +1,19 @@
 //! MCP server implementation for serving prompts and workflows
 
 use crate::common::rate_limiter::get_rate_limiter;
-use crate::file_watcher::{FileWatcher, FileWatcherCallback};
+use crate::events::{event_bus, Event};
+use crate::file_watcher::FileWatcher;
 use crate::git::GitOperations;
-use crate::issues::{FileSystemIssueStorage, IssueStorage};
-use crate::memoranda::{MarkdownMemoStorage, MemoStorage};
+use crate::issues::issue_storage_for_work_dir;
+use crate::memoranda::{MarkdownMemoStorage, MemoId, MemoStorage};
+use crate::prompt_presets::{PresetStorage, PRESET_ARGUMENT_KEY};
+use crate::prompt_runs::{PromptRun, PromptRunLog};
 use crate::workflow::{
-    FileSystemWorkflowRunStorage, FileSystemWorkflowStorage, WorkflowRunStorageBackend,
-    WorkflowStorage, WorkflowStorageBackend,
+    set_prompt_sampler, FileSystemWorkflowRunStorage, FileSystemWorkflowStorage, RunArtifacts,
+    WorkflowRunId, WorkflowRunStorageBackend, WorkflowStorage, WorkflowStorageBackend,
 };
 use crate::{PromptLibrary, PromptResolver, Result, SwissArmyHammerError};
+use base64::{engine::general_purpose, Engine as _};
 use rmcp::model::*;
 use rmcp::service::RequestContext;
 use rmcp::{Error as McpError, RoleServer, ServerHandler};
@@ -17,20 +21,51 @@ use serde_json::Value;
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Instant;
 use tokio::sync::{Mutex, RwLock};
+use tokio::task::JoinHandle;
 
+use super::capabilities::ToolGroups;
+use super::file_watcher::{spawn_event_notifications, McpFileWatcherCallback};
+use super::health::ReloadKind;
+use super::sampling::McpSampler;
 use super::tool_handlers::ToolHandlers;
 use super::tool_registry::{
-    register_issue_tools, register_memo_tools, register_search_tools, ToolContext, ToolRegistry,
+    register_issue_tools, register_memo_tools, register_search_tools, register_server_tools,
+    register_workflow_tools, ToolContext, ToolRegistry,
 };
 
+/// URI prefix of memos served as resources
+const MEMO_RESOURCE_PREFIX: &str = "memo://";
+
+/// URI prefix of workflows served as resources
+const WORKFLOW_RESOURCE_PREFIX: &str = "workflow://";
+
+/// URI prefix of workflow run artifacts served as resources
+const ARTIFACT_RESOURCE_PREFIX: &str = "artifact://";
+
 /// MCP server for serving prompts and workflows
 #[derive(Clone)]
 pub struct McpServer {
     library: Arc<RwLock<PromptLibrary>>,
+    /// Where the loaded prompts came from, to apply changed prompt files
+    prompt_resolver: Arc<Mutex<PromptResolver>>,
     workflow_storage: Arc<RwLock<WorkflowStorage>>,
     file_watcher: Arc<Mutex<FileWatcher>>,
+    /// Task forwarding events to the connected client
+    event_notifications: Arc<Mutex<Option<JoinHandle<()>>>>,
     tool_registry: Arc<ToolRegistry>,
+    presets: Arc<PresetStorage>,
+    /// Log of the prompts clients fetched
+    prompt_runs: Arc<PromptRunLog>,
+    /// Name of the connected client
+    client_name: Arc<RwLock<Option<String>>>,
+    /// Tool groups the server configuration enables
+    tool_groups: ToolGroups,
+    /// Tool groups enabled for the connected client
+    client_tool_groups: Arc<RwLock<ToolGroups>>,
+    /// Artifacts of workflow runs, served as resources
+    artifacts: RunArtifacts,
     /// Tool context containing shared state for tool execution
     pub tool_context: Arc<ToolContext>,
 }
@@ -87,13 +122,18 @@ impl McpServer {
 
         let workflow_storage = WorkflowStorage::new(workflow_backend, run_backend);
 
-        // Initialize issue storage with issues directory in work_dir
-        let issues_dir = work_dir.join("issues");
+        // Prompt argument presets live alongside the other local state
+        let presets = Arc::new(PresetStorage::new(
+            work_dir.join(".swissarmyhammer").join("presets"),
+        ));
+        let prompt_runs = Arc::new(PromptRunLog::for_work_dir(&work_dir));
 
-        let issue_storage = Box::new(FileSystemIssueStorage::new(issues_dir).map_err(|e| {
+        // Initialize issue storage with issues directory in work_dir, merged with any
+        // configured issue directories
+        let issue_storage = issue_storage_for_work_dir(&work_dir).map_err(|e| {
             tracing::error!("Failed to create issue storage: {}", e);
             SwissArmyHammerError::Other(format!("Failed to create issue storage: {e}"))
-        })?) as Box<dyn IssueStorage>;
+        })?;
 
         // Initialize memo storage with default location
         let memo_storage = Box::new(MarkdownMemoStorage::new_default().map_err(|e| {
@@ -132,16 +172,71 @@ impl McpServer {
         register_issue_tools(&mut tool_registry);
         register_memo_tools(&mut tool_registry);
         register_search_tools(&mut tool_registry);
+        register_workflow_tools(&mut tool_registry);
+        register_server_tools(&mut tool_registry);
+
+        let tool_groups = ToolGroups::from_config(crate::Config::global());
 
         Ok(Self {
             library: Arc::new(RwLock::new(library)),
+            prompt_resolver: Arc::new(Mutex::new(PromptResolver::new())),
             workflow_storage: Arc::new(RwLock::new(workflow_storage)),
             file_watcher: Arc::new(Mutex::new(FileWatcher::new())),
+            event_notifications: Arc::new(Mutex::new(None)),
             tool_registry: Arc::new(tool_registry),
+            presets,
+            prompt_runs,
+            client_name: Arc::new(RwLock::new(None)),
+            client_tool_groups: Arc::new(RwLock::new(tool_groups.clone())),
+            tool_groups,
+            artifacts: RunArtifacts::default(),
             tool_context,
         })
     }
 
+    /// Serve only the given tool groups, instead of the configured ones.
+    ///
+    /// Clients can narrow these groups further when they connect.
+    pub fn with_tool_groups(mut self, tool_groups: ToolGroups) -> Self {
+        self.client_tool_groups = Arc::new(RwLock::new(tool_groups.clone()));
+        self.tool_groups = tool_groups;
+        self
+    }
+
+    /// Serve the workflow run artifacts kept in `dir` instead of the
+    /// project's `.swissarmyhammer/runs`.
+    pub fn with_artifacts_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.artifacts = RunArtifacts::new(dir);
+        self
+    }
+
+    /// Serve only tools that never change issues, memos, git state or the
+    /// search index.
+    ///
+    /// Clients can't leave read-only mode when they connect.
+    pub fn with_read_only(self) -> Self {
+        let tool_groups = self.tool_groups.clone().read_only();
+        self.with_tool_groups(tool_groups)
+    }
+
+    /// Tool groups enabled for the connected client.
+    pub async fn enabled_tool_groups(&self) -> ToolGroups {
+        self.client_tool_groups.read().await.clone()
+    }
+
+    /// List the tools the connected client may call, sorted by name.
+    pub async fn list_enabled_tools(&self) -> Vec<Tool> {
+        let tool_groups = self.client_tool_groups.read().await;
+        let mut tools: Vec<Tool> = self
+            .tool_registry
+            .list_tools()
+            .into_iter()
+            .filter(|tool| tool_groups.allows_tool(&tool.name))
+            .collect();
+        tools.sort_by(|a, b| a.name.cmp(&b.name));
+        tools
+    }
+
     /// Get a reference to the underlying prompt library.
     ///
     /// # Returns
@@ -151,6 +246,11 @@ impl McpServer {
         &self.library
     }
 
+    /// Get a reference to the workflow and workflow run storage.
+    pub fn workflow_storage(&self) -> &Arc<RwLock<WorkflowStorage>> {
+        &self.workflow_storage
+    }
+
     /// Initialize the server by loading prompts and workflows from disk.
     ///
     /// This method loads all prompts using the PromptResolver and initializes
@@ -165,19 +265,24 @@ impl McpServer {
     /// Returns an error if prompt loading or workflow initialization fails.
     pub async fn initialize(&self) -> Result<()> {
         let mut library = self.library.write().await;
-        let mut resolver = PromptResolver::new();
+        let mut resolver = self.prompt_resolver.lock().await;
 
         // Use the same loading logic as CLI
         resolver.load_all_prompts(&mut library)?;
 
         let total = library.list()?.len();
         tracing::info!("Loaded {} prompts total", total);
+        self.tool_context.health.set_prompts(total);
 
         // Initialize workflows - workflows are loaded automatically by FileSystemWorkflowStorage
         // so we just need to check how many are available
         let workflow_storage = self.workflow_storage.read().await;
         let workflow_count = workflow_storage.list_workflows()?.len();
         tracing::info!("Loaded {} workflows total", workflow_count);
+        self.tool_context.health.set_workflows(workflow_count);
+        event_bus().publish(Event::WorkflowsReloaded {
+            count: workflow_count,
+        });
 
         Ok(())
     }
@@ -185,7 +290,8 @@ impl McpServer {
     /// List all available prompts, excluding partial templates.
     ///
     /// Partial templates are filtered out as they are meant for internal use
-    /// and should not be exposed via the MCP interface.
+    /// and should not be exposed via the MCP interface, as are prompts whose
+    /// visibility keeps them to the CLI.
     ///
     /// # Returns
     ///
@@ -195,7 +301,7 @@ impl McpServer {
         let prompts = library.list()?;
         Ok(prompts
             .iter()
-            .filter(|p| !Self::is_partial_template(p))
+            .filter(|p| !p.is_partial() && p.visibility().is_exposed_to_mcp())
             .map(|p| p.name.clone())
             .collect())
     }
@@ -211,35 +317,6 @@ impl McpServer {
         Ok(workflows.iter().map(|w| w.name.to_string()).collect())
     }
 
-    /// Check if a prompt is a partial template that should not be exposed over MCP.
-    ///
-    /// Partial templates are identified by either:
-    /// 1. Starting with the `{% partial %}` marker
-    /// 2. Having a description containing "Partial template for reuse"
-    ///
-    /// # Arguments
-    ///
-    /// * `prompt` - The prompt to check
-    ///
-    /// # Returns
-    ///
-    /// * `bool` - True if the prompt is a partial template
-    fn is_partial_template(prompt: &crate::prompts::Prompt) -> bool {
-        // Check if the template starts with the partial marker
-        if prompt.template.trim().starts_with("{% partial %}") {
-            return true;
-        }
-
-        // Check if the description indicates it's a partial template
-        if let Some(description) = &prompt.description {
-            if description.contains("Partial template for reuse in other prompts") {
-                return true;
-            }
-        }
-
-        false
-    }
-
     /// Get a specific prompt by name, with optional template argument rendering.
     ///
     /// # Arguments
@@ -253,8 +330,8 @@ impl McpServer {
     ///
     /// # Errors
     ///
-    /// Returns an error if the prompt is not found, is a partial template,
-    /// or if template rendering fails.
+    /// Returns an error if the prompt is not found, is a partial template, is
+    /// not exposed to MCP, or if template rendering fails.
     pub async fn get_prompt(
         &self,
         name: &str,
@@ -263,16 +340,23 @@ impl McpServer {
         let library = self.library.read().await;
         let prompt = library.get(name)?;
 
+        // Prompts kept from MCP are reported as missing so clients can't probe
+        // for them
+        if !prompt.visibility().is_exposed_to_mcp() {
+            return Err(SwissArmyHammerError::PromptNotFound(name.to_string()));
+        }
+
         // Check if this is a partial template
-        if Self::is_partial_template(&prompt) {
+        if prompt.is_partial() {
             return Err(SwissArmyHammerError::Other(format!(
                 "Cannot access partial template '{name}' via MCP. Partial templates are for internal use only."
             )));
         }
 
-        // Handle arguments if provided
+        // Handle arguments if provided, expanding any selected preset
         let content = if let Some(args) = arguments {
-            library.render_prompt(name, args)?
+            let args = self.presets.apply(&prompt, args)?;
+            library.render_prompt(name, &args)?
         } else {
             prompt.template.clone()
         };
@@ -280,6 +364,21 @@ impl McpServer {
         Ok(content)
     }
 
+    /// Record a prompt a client fetched, logging instead of failing when the
+    /// record can't be written
+    async fn record_prompt_run(
+        &self,
+        name: &str,
+        arguments: Option<&HashMap<String, String>>,
+        content: &str,
+    ) {
+        let client = self.client_name.read().await.clone();
+        let run = PromptRun::new(name, client.as_deref(), arguments, content);
+        if let Err(e) = self.prompt_runs.record(&run) {
+            tracing::warn!("Failed to record run of prompt '{}': {}", name, e);
+        }
+    }
+
     /// Get the workflow runs directory path
     fn get_workflow_runs_path() -> std::path::PathBuf {
         dirs::home_dir()
@@ -308,13 +407,44 @@ impl McpServer {
                     .map(|arg| PromptArgument {
                         name: arg.name.clone(),
                         description: arg.description.clone(),
-                        required: Some(arg.required),
+                        // Arguments with a source are looked up when not given
+                        required: Some(arg.required && arg.source.is_none()),
                     })
                     .collect(),
             )
         }
     }
 
+    /// Build the MCP argument list for a prompt, advertising saved presets.
+    ///
+    /// When presets exist for the prompt, an optional `preset` argument is added
+    /// so clients can select a saved argument set by name.
+    fn prompt_arguments_with_presets(
+        &self,
+        prompt: &crate::prompts::Prompt,
+    ) -> Option<Vec<PromptArgument>> {
+        let mut arguments = Self::convert_prompt_arguments(&prompt.arguments);
+
+        let preset_names = self.presets.list(&prompt.name).unwrap_or_default();
+        if !preset_names.is_empty()
+            && !prompt
+                .arguments
+                .iter()
+                .any(|a| a.name == PRESET_ARGUMENT_KEY)
+        {
+            arguments.get_or_insert_with(Vec::new).push(PromptArgument {
+                name: PRESET_ARGUMENT_KEY.to_string(),
+                description: Some(format!(
+                    "Name of a saved argument preset ({})",
+                    preset_names.join(", ")
+                )),
+                required: Some(false),
+            });
+        }
+
+        arguments
+    }
+
     /// Convert serde_json::Map to HashMap<String, String> for template rendering.
     ///
     /// This helper method converts MCP tool arguments from JSON format to
@@ -348,7 +478,72 @@ impl McpServer {
     ///
     /// * `Result<()>` - Ok if reload succeeds, error otherwise
     pub async fn reload_prompts(&self) -> Result<()> {
-        self.reload_prompts_with_retry().await
+        let started = Instant::now();
+        let result = self.reload_prompts_with_retry().await;
+        self.tool_context.health.record_reload(
+            ReloadKind::Full,
+            started.elapsed(),
+            result.as_ref().err().map(ToString::to_string),
+        );
+        result
+    }
+
+    /// Apply changed prompt files to the loaded prompts.
+    ///
+    /// Only the prompts of the changed, added, removed or renamed files are
+    /// loaded again, which keeps reloads fast in large prompt libraries. When
+    /// the changes can't be applied, all prompts are reloaded as
+    /// [`reload_prompts`](Self::reload_prompts) does.
+    ///
+    /// # Arguments
+    ///
+    /// * `paths` - The changed files and directories
+    ///
+    /// # Returns
+    ///
+    /// * `Result<()>` - Ok if the changes or the full reload succeed, error otherwise
+    pub async fn apply_prompt_changes(&self, paths: &[PathBuf]) -> Result<()> {
+        let started = Instant::now();
+        let (kind, result) = match self.apply_prompt_changes_internal(paths).await {
+            Ok(()) => (ReloadKind::Incremental, Ok(())),
+            Err(e) => {
+                tracing::warn!(
+                    "⚠️ Failed to apply prompt changes, reloading all prompts: {}",
+                    e
+                );
+                (ReloadKind::Full, self.reload_prompts_with_retry().await)
+            }
+        };
+        self.tool_context.health.record_reload(
+            kind,
+            started.elapsed(),
+            result.as_ref().err().map(ToString::to_string),
+        );
+        result
+    }
+
+    /// Internal method that loads only the prompts of the changed files again
+    async fn apply_prompt_changes_internal(&self, paths: &[PathBuf]) -> Result<()> {
+        let mut library = self.library.write().await;
+        let mut resolver = self.prompt_resolver.lock().await;
+
+        let changes = resolver.apply_changes(&mut library, paths)?;
+        if changes.is_empty() {
+            tracing::debug!("No prompts changed by {:?}", paths);
+            return Ok(());
+        }
+
+        let count = library.list()?.len();
+        tracing::info!(
+            "🔄 Applied prompt changes: {} updated, {} removed, {} prompts",
+            changes.updated.len(),
+            changes.removed.len(),
+            count
+        );
+        self.tool_context.health.set_prompts(count);
+        event_bus().publish(Event::PromptsReloaded { count });
+
+        Ok(())
     }
 
     /// Reload prompts with retry logic for transient file system errors
@@ -414,13 +609,14 @@ impl McpServer {
     /// Internal reload method that performs the actual reload
     async fn reload_prompts_internal(&self) -> Result<()> {
         let mut library = self.library.write().await;
-        let mut resolver = PromptResolver::new();
+        let mut resolver = self.prompt_resolver.lock().await;
 
         // Get count before reload (default to 0 if library.list() fails)
         let before_count = library.list().map(|p| p.len()).unwrap_or(0);
 
         // Clear existing prompts and reload
-        *library = PromptLibrary::new();
+        *library = PromptLibrary::from_config();
+        *resolver = PromptResolver::new();
         resolver.load_all_prompts(&mut library)?;
 
         let after_count = library.list()?.len();
@@ -429,14 +625,176 @@ impl McpServer {
             before_count,
             after_count
         );
+        self.tool_context.health.set_prompts(after_count);
+        event_bus().publish(Event::PromptsReloaded { count: after_count });
+
+        Ok(())
+    }
+
+    /// Reload workflows from disk.
+    ///
+    /// Workflows are cached when the server starts, so changes to workflow
+    /// files are only served after a reload.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<()>` - Ok if reload succeeds, error otherwise
+    pub async fn reload_workflows(&self) -> Result<()> {
+        let started = Instant::now();
+        let result = self.reload_workflows_internal().await;
+        self.tool_context.health.record_reload(
+            ReloadKind::Full,
+            started.elapsed(),
+            result.as_ref().err().map(ToString::to_string),
+        );
+        result
+    }
+
+    /// Internal reload method that swaps in a freshly loaded workflow backend
+    async fn reload_workflows_internal(&self) -> Result<()> {
+        let workflow_backend = FileSystemWorkflowStorage::new()?;
+        let workflow_count = workflow_backend.list_workflows()?.len();
+
+        self.workflow_storage
+            .write()
+            .await
+            .set_workflow_backend(Arc::new(workflow_backend));
+        tracing::info!("🔄 Reloaded workflows: {} workflows", workflow_count);
+        self.tool_context.health.set_workflows(workflow_count);
 
         Ok(())
     }
 
-    /// Start watching prompt directories for file changes.
+    /// List memos, workflows and workflow run artifacts as MCP resources.
+    ///
+    /// Memos are served as `memo://<id>`, workflows as `workflow://<name>` and
+    /// the artifacts of runs as `artifact://<run_id>/<name>`.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<Resource>>` - The resources or an error if storage fails
+    pub async fn list_resources(&self) -> Result<Vec<Resource>> {
+        let memos = self
+            .tool_context
+            .memo_storage
+            .read()
+            .await
+            .list_memos()
+            .await?;
+        let workflows = self.workflow_storage.read().await.list_workflows()?;
+
+        let memo_resources = memos.into_iter().map(|memo| RawResource {
+            uri: format!("{MEMO_RESOURCE_PREFIX}{}", memo.id),
+            name: memo.title,
+            description: None,
+            mime_type: Some("text/markdown".to_string()),
+            size: None,
+        });
+        let workflow_resources = workflows.into_iter().map(|workflow| RawResource {
+            uri: format!("{WORKFLOW_RESOURCE_PREFIX}{}", workflow.name),
+            name: workflow.name.to_string(),
+            description: Some(workflow.description),
+            mime_type: Some("application/json".to_string()),
+            size: None,
+        });
+
+        let mut artifact_resources = Vec::new();
+        for run_id in self.artifacts.runs()? {
+            for artifact in self.artifacts.list(&run_id)? {
+                artifact_resources.push(RawResource {
+                    uri: format!("{ARTIFACT_RESOURCE_PREFIX}{run_id}/{}", artifact.name),
+                    description: Some(format!(
+                        "{} artifact of workflow run {run_id}",
+                        artifact.kind
+                    )),
+                    name: artifact.name,
+                    mime_type: None,
+                    size: u32::try_from(artifact.size).ok(),
+                });
+            }
+        }
+
+        Ok(memo_resources
+            .chain(workflow_resources)
+            .chain(artifact_resources)
+            .map(|resource| resource.no_annotation())
+            .collect())
+    }
+
+    /// Read a memo, workflow or artifact resource by URI.
+    ///
+    /// Memos are read as their markdown content and workflows as their JSON
+    /// definition. Artifacts are read as text, or base64 encoded when they are
+    /// not UTF-8.
+    ///
+    /// # Arguments
+    ///
+    /// * `uri` - The URI of a resource returned by `list_resources`
+    ///
+    /// # Returns
+    ///
+    /// * `Result<ResourceContents>` - The contents or an error if the resource doesn't exist
+    pub async fn read_resource(&self, uri: &str) -> Result<ResourceContents> {
+        let (mime_type, text) = if let Some(id) = uri.strip_prefix(MEMO_RESOURCE_PREFIX) {
+            let memo_id = MemoId::from_string(id.to_string())?;
+            let memo = self
+                .tool_context
+                .memo_storage
+                .read()
+                .await
+                .get_memo(&memo_id)
+                .await?;
+            ("text/markdown", memo.content)
+        } else if let Some(name) = uri.strip_prefix(WORKFLOW_RESOURCE_PREFIX) {
+            let workflow = self
+                .workflow_storage
+                .read()
+                .await
+                .get_workflow(&name.into())?;
+            let definition = serde_json::to_string_pretty(&workflow)?;
+            ("application/json", definition)
+        } else if let Some(path) = uri.strip_prefix(ARTIFACT_RESOURCE_PREFIX) {
+            return self.read_artifact(uri, path);
+        } else {
+            return Err(SwissArmyHammerError::Other(format!(
+                "Unknown resource URI: {uri}"
+            )));
+        };
+
+        Ok(ResourceContents::TextResourceContents {
+            uri: uri.to_string(),
+            mime_type: Some(mime_type.to_string()),
+            text,
+        })
+    }
+
+    /// Read the artifact at `<run_id>/<name>`
+    fn read_artifact(&self, uri: &str, path: &str) -> Result<ResourceContents> {
+        let (run_id, name) = path.split_once('/').ok_or_else(|| {
+            SwissArmyHammerError::Other(format!("Artifact URI {uri} has no artifact name"))
+        })?;
+        let run_id = WorkflowRunId::parse(run_id).map_err(SwissArmyHammerError::Other)?;
+        let (_, file) = self.artifacts.get(&run_id, name)?;
+        let bytes = std::fs::read(file)?;
+
+        Ok(match String::from_utf8(bytes) {
+            Ok(text) => ResourceContents::TextResourceContents {
+                uri: uri.to_string(),
+                mime_type: Some("text/plain".to_string()),
+                text,
+            },
+            Err(e) => ResourceContents::BlobResourceContents {
+                uri: uri.to_string(),
+                mime_type: Some("application/octet-stream".to_string()),
+                blob: general_purpose::STANDARD.encode(e.into_bytes()),
+            },
+        })
+    }
+
+    /// Start watching prompt, workflow and memo directories for file changes.
     ///
     /// When files change, the server will automatically reload prompts and
-    /// send notifications to the MCP client.
+    /// workflows and send list changed notifications to the MCP client.
     ///
     /// # Arguments
     ///
@@ -453,6 +811,12 @@ impl McpServer {
         const MAX_RETRIES: u32 = 3;
         const INITIAL_BACKOFF_MS: u64 = 100;
 
+        // Forward reloads and other events to the client
+        let notifications = spawn_event_notifications(peer.clone());
+        if let Some(previous) = self.event_notifications.lock().await.replace(notifications) {
+            previous.abort();
+        }
+
         // Create callback that handles file changes and notifications
         let callback = McpFileWatcherCallback::new(self.clone(), peer);
 
@@ -468,6 +832,7 @@ impl McpServer {
 
             match result {
                 Ok(()) => {
+                    self.tool_context.health.set_watching(true);
                     if attempt > 1 {
                         tracing::info!(
                             "✅ File watcher started successfully on attempt {}",
@@ -503,57 +868,16 @@ impl McpServer {
         }))
     }
 
-    /// Stop watching prompt directories for file changes.
+    /// Stop watching prompt, workflow and memo directories for file changes.
     ///
     /// This should be called when the MCP server is shutting down.
     pub async fn stop_file_watching(&self) {
         let mut watcher = self.file_watcher.lock().await;
         watcher.stop_watching();
-    }
-}
-
-/// Callback implementation for file watcher that handles prompt reloading
-#[derive(Clone)]
-struct McpFileWatcherCallback {
-    server: McpServer,
-    peer: rmcp::Peer<RoleServer>,
-}
-
-impl McpFileWatcherCallback {
-    fn new(server: McpServer, peer: rmcp::Peer<RoleServer>) -> Self {
-        Self { server, peer }
-    }
-}
-
-impl FileWatcherCallback for McpFileWatcherCallback {
-    async fn on_file_changed(&self, paths: Vec<std::path::PathBuf>) -> Result<()> {
-        tracing::info!("📄 Prompt file changed: {:?}", paths);
-
-        // Reload the library
-        if let Err(e) = self.server.reload_prompts().await {
-            tracing::error!("❌ Failed to reload prompts: {}", e);
-            return Err(e);
+        if let Some(notifications) = self.event_notifications.lock().await.take() {
+            notifications.abort();
         }
-        tracing::info!("✅ Prompts reloaded successfully");
-
-        // Send notification to client about prompt list change
-        let peer_clone = self.peer.clone();
-        tokio::spawn(async move {
-            match peer_clone.notify_prompt_list_changed().await {
-                Ok(_) => {
-                    tracing::info!("📢 Sent prompts/listChanged notification to client");
-                }
-                Err(e) => {
-                    tracing::error!("❌ Failed to send notification: {}", e);
-                }
-            }
-        });
-
-        Ok(())
-    }
-
-    async fn on_error(&self, error: String) {
-        tracing::error!("❌ File watcher error: {}", error);
+        self.tool_context.health.set_watching(false);
     }
 }
 
@@ -568,6 +892,29 @@ impl ServerHandler for McpServer {
             request.client_info.name,
             request.client_info.version
         );
+        *self.client_name.write().await = Some(request.client_info.name.clone());
+
+        // Serve only the tool groups both the configuration and the client enable
+        let tool_groups = self.tool_groups.negotiate(&request.capabilities);
+        tracing::info!(
+            "🧰 Tool groups for {}: {}",
+            request.client_info.name,
+            tool_groups
+                .iter()
+                .map(|group| group.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        *self.client_tool_groups.write().await = tool_groups.clone();
+
+        // Let sampling prompt actions use the client's model when it offers one
+        if request.capabilities.sampling.is_some() {
+            set_prompt_sampler(Some(Arc::new(McpSampler::new(context.peer.clone()))));
+            tracing::info!(
+                "🧠 Sampling prompt actions will use {}",
+                request.client_info.name
+            );
+        }
 
         // Start file watching when MCP client connects
         match self.start_file_watching(context.peer).await {
@@ -589,10 +936,13 @@ impl ServerHandler for McpServer {
                 tools: Some(ToolsCapability {
                     list_changed: Some(true),
                 }),
-                resources: None,
-                logging: None,
+                resources: Some(ResourcesCapability {
+                    subscribe: None,
+                    list_changed: Some(true),
+                }),
+                logging: Some(JsonObject::new()),
                 completions: None,
-                experimental: None,
+                experimental: Some(tool_groups.to_capabilities()),
             },instructions: Some("A flexible prompt and workflow management server with integrated issue tracking. Use list_prompts to see available prompts and get_prompt to retrieve and render them. Use workflow tools to execute and manage workflows. Use issue_* tools to create and manage work items tracked as markdown files in your repository.".into()),
             server_info: Implementation {
                 name: "SwissArmyHammer".into(),
@@ -611,9 +961,10 @@ impl ServerHandler for McpServer {
             Ok(prompts) => {
                 let prompt_list: Vec<Prompt> = prompts
                     .iter()
-                    .filter(|p| !Self::is_partial_template(p)) // Filter out partial templates
+                    // Filter out partial templates and prompts kept from MCP
+                    .filter(|p| !p.is_partial() && p.visibility().is_exposed_to_mcp())
                     .map(|p| {
-                        let arguments = Self::convert_prompt_arguments(&p.arguments);
+                        let arguments = self.prompt_arguments_with_presets(p);
 
                         Prompt {
                             name: p.name.clone(),
@@ -638,10 +989,18 @@ impl ServerHandler for McpServer {
         _context: RequestContext<RoleServer>,
     ) -> std::result::Result<GetPromptResult, McpError> {
         let library = self.library.read().await;
-        match library.get(&request.name) {
+        let prompt = library.get(&request.name).and_then(|prompt| {
+            // Prompts kept from MCP are reported as missing, like in get_prompt
+            if prompt.visibility().is_exposed_to_mcp() {
+                Ok(prompt)
+            } else {
+                Err(SwissArmyHammerError::PromptNotFound(request.name.clone()))
+            }
+        });
+        match prompt {
             Ok(prompt) => {
                 // Check if this is a partial template
-                if Self::is_partial_template(&prompt) {
+                if prompt.is_partial() {
                     return Err(McpError::invalid_request(
                         format!(
                             "Cannot access partial template '{}' via MCP. Partial templates are for internal use only.",
@@ -652,11 +1011,15 @@ impl ServerHandler for McpServer {
                 }
 
                 // Handle arguments if provided
-                let content = if let Some(args) = &request.arguments {
+                let (content, template_args) = if let Some(args) = &request.arguments {
                     let template_args = Self::json_map_to_string_map(args);
+                    let template_args = match self.presets.apply(&prompt, &template_args) {
+                        Ok(resolved) => resolved,
+                        Err(e) => return Err(McpError::invalid_params(e.to_string(), None)),
+                    };
 
                     match library.render_prompt(&request.name, &template_args) {
-                        Ok(rendered) => rendered,
+                        Ok(rendered) => (rendered, Some(template_args)),
                         Err(e) => {
                             return Err(McpError::internal_error(
                                 format!("Template rendering error: {e}"),
@@ -665,8 +1028,10 @@ impl ServerHandler for McpServer {
                         }
                     }
                 } else {
-                    prompt.template.clone()
+                    (prompt.template.clone(), None)
                 };
+                self.record_prompt_run(&request.name, template_args.as_ref(), &content)
+                    .await;
 
                 Ok(GetPromptResult {
                     description: prompt.description,
@@ -689,13 +1054,56 @@ impl ServerHandler for McpServer {
         }
     }
 
+    async fn list_resources(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> std::result::Result<ListResourcesResult, McpError> {
+        match McpServer::list_resources(self).await {
+            Ok(resources) => Ok(ListResourcesResult {
+                resources,
+                next_cursor: None,
+            }),
+            Err(e) => Err(McpError::internal_error(e.to_string(), None)),
+        }
+    }
+
+    async fn read_resource(
+        &self,
+        request: ReadResourceRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> std::result::Result<ReadResourceResult, McpError> {
+        match McpServer::read_resource(self, &request.uri).await {
+            Ok(contents) => Ok(ReadResourceResult {
+                contents: vec![contents],
+            }),
+            Err(e) => {
+                tracing::warn!("Resource '{}' not readable: {}", request.uri, e);
+                Err(McpError::resource_not_found(
+                    format!("Resource '{}' is not available: {e}", request.uri),
+                    None,
+                ))
+            }
+        }
+    }
+
+    async fn set_level(
+        &self,
+        request: SetLevelRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> std::result::Result<(), McpError> {
+        // Budget warnings and refusals are the only log messages sent to the client
+        tracing::debug!("Client requested log level {:?}", request.level);
+        Ok(())
+    }
+
     async fn list_tools(
         &self,
         _request: Option<PaginatedRequestParam>,
         _context: RequestContext<RoleServer>,
     ) -> std::result::Result<ListToolsResult, McpError> {
         Ok(ListToolsResult {
-            tools: self.tool_registry.list_tools(),
+            tools: self.list_enabled_tools().await,
             next_cursor: None,
         })
     }
@@ -705,6 +1113,19 @@ impl ServerHandler for McpServer {
         request: CallToolRequestParam,
         _context: RequestContext<RoleServer>,
     ) -> std::result::Result<CallToolResult, McpError> {
+        let tool_groups = self.client_tool_groups.read().await.clone();
+        if !tool_groups.allows_tool(&request.name) {
+            let reason = if tool_groups.is_read_only() {
+                "is not available in read-only mode"
+            } else {
+                "is not enabled for this client"
+            };
+            return Err(McpError::invalid_request(
+                format!("Tool '{}' {reason}", request.name),
+                None,
+            ));
+        }
+
         if let Some(tool) = self.tool_registry.get_tool(&request.name) {
             tool.execute(request.arguments.unwrap_or_default(), &self.tool_context)
                 .await
@@ -726,10 +1147,13 @@ impl ServerHandler for McpServer {
                 tools: Some(ToolsCapability {
                     list_changed: Some(true),
                 }),
-                resources: None,
-                logging: None,
+                resources: Some(ResourcesCapability {
+                    subscribe: None,
+                    list_changed: Some(true),
+                }),
+                logging: Some(JsonObject::new()),
                 completions: None,
-                experimental: None,
+                experimental: Some(self.tool_groups.to_capabilities()),
             },server_info: Implementation {
                 name: "SwissArmyHammer".into(),
                 version: crate::VERSION.into(),
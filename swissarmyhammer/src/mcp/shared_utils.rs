@@ -56,50 +56,57 @@ pub struct McpErrorHandler;
 impl McpErrorHandler {
     /// Convert SwissArmyHammerError to appropriate MCP error response
     ///
-    /// This provides consistent error mapping across all MCP operations:
+    /// This provides consistent error mapping across all MCP operations, with
+    /// the error's code and category as the error data:
     /// - User input errors -> invalid_params
     /// - System errors -> internal_error
     /// - Security/validation errors -> invalid_params
     pub fn handle_error(error: SwissArmyHammerError, operation: &str) -> McpError {
         tracing::error!("MCP operation '{}' failed: {}", operation, error);
 
+        // The error code travels in the error data so clients can match on it
+        let data = Some(serde_json::json!({
+            "code": error.code(),
+            "category": error.category(),
+        }));
+
         match error {
             // User input validation errors
             SwissArmyHammerError::IssueNotFound(name) => {
-                McpError::invalid_params(format!("Issue not found: {name}"), None)
+                McpError::invalid_params(format!("Issue not found: {name}"), data)
             }
             SwissArmyHammerError::IssueAlreadyExists(num) => {
-                McpError::invalid_params(format!("Issue already exists: #{num:06}"), None)
+                McpError::invalid_params(format!("Issue already exists: #{num:06}"), data)
             }
             SwissArmyHammerError::MemoNotFound(id) => {
-                McpError::invalid_params(format!("Memo not found: {id}"), None)
+                McpError::invalid_params(format!("Memo not found: {id}"), data)
             }
             SwissArmyHammerError::InvalidMemoId(id) => {
-                McpError::invalid_params(format!("Invalid memo ID format: {id}"), None)
+                McpError::invalid_params(format!("Invalid memo ID format: {id}"), data)
             }
             SwissArmyHammerError::MemoAlreadyExists(id) => {
-                McpError::invalid_params(format!("Memo already exists: {id}"), None)
+                McpError::invalid_params(format!("Memo already exists: {id}"), data)
             }
             SwissArmyHammerError::MemoValidationFailed(reason) => {
-                McpError::invalid_params(format!("Memo validation failed: {reason}"), None)
+                McpError::invalid_params(format!("Memo validation failed: {reason}"), data)
             }
             SwissArmyHammerError::PromptNotFound(name) => {
-                McpError::invalid_params(format!("Prompt not found: {name}"), None)
+                McpError::invalid_params(format!("Prompt not found: {name}"), data)
             }
             SwissArmyHammerError::WorkflowNotFound(name) => {
-                McpError::invalid_params(format!("Workflow not found: {name}"), None)
+                McpError::invalid_params(format!("Workflow not found: {name}"), data)
             }
             SwissArmyHammerError::WorkflowRunNotFound(id) => {
-                McpError::invalid_params(format!("Workflow run not found: {id}"), None)
+                McpError::invalid_params(format!("Workflow run not found: {id}"), data)
             }
             SwissArmyHammerError::Config(msg) => {
-                McpError::invalid_params(format!("Configuration error: {msg}"), None)
+                McpError::invalid_params(format!("Configuration error: {msg}"), data)
             }
             // Security and validation errors
             SwissArmyHammerError::Template(msg)
                 if msg.contains("too large") || msg.contains("too complex") =>
             {
-                McpError::invalid_params(format!("Template validation failed: {msg}"), None)
+                McpError::invalid_params(format!("Template validation failed: {msg}"), data)
             }
             // Git operation errors (could be user error or system error)
             SwissArmyHammerError::GitOperationFailed {
@@ -107,9 +114,9 @@ impl McpErrorHandler {
                 details,
             } => {
                 if details.contains("not a git repository") || details.contains("branch") {
-                    McpError::invalid_params(format!("Git {git_op} failed: {details}"), None)
+                    McpError::invalid_params(format!("Git {git_op} failed: {details}"), data)
                 } else {
-                    McpError::internal_error(format!("Git {git_op} failed: {details}"), None)
+                    McpError::internal_error(format!("Git {git_op} failed: {details}"), data)
                 }
             }
             SwissArmyHammerError::GitCommandFailed {
@@ -118,10 +125,10 @@ impl McpErrorHandler {
                 stderr,
             } => McpError::internal_error(
                 format!("Git command '{command}' failed with exit code {exit_code}: {stderr}"),
-                None,
+                data,
             ),
             SwissArmyHammerError::GitRepositoryNotFound { path } => {
-                McpError::invalid_params(format!("Git repository not found at: {path}"), None)
+                McpError::invalid_params(format!("Git repository not found at: {path}"), data)
             }
             SwissArmyHammerError::GitBranchOperationFailed {
                 operation: git_op,
@@ -129,38 +136,38 @@ impl McpErrorHandler {
                 details,
             } => McpError::invalid_params(
                 format!("Git {git_op} failed for branch '{branch}': {details}"),
-                None,
+                data,
             ),
             // System errors
             SwissArmyHammerError::Io(err) => {
-                McpError::internal_error(format!("IO error: {err}"), None)
+                McpError::internal_error(format!("IO error: {err}"), data)
             }
             SwissArmyHammerError::Serialization(err) => {
-                McpError::internal_error(format!("Serialization error: {err}"), None)
+                McpError::internal_error(format!("Serialization error: {err}"), data)
             }
             SwissArmyHammerError::Json(err) => {
-                McpError::internal_error(format!("JSON error: {err}"), None)
+                McpError::internal_error(format!("JSON error: {err}"), data)
             }
             SwissArmyHammerError::Storage(msg) => {
-                McpError::internal_error(format!("Storage error: {msg}"), None)
+                McpError::internal_error(format!("Storage error: {msg}"), data)
             }
             // Generic errors
             SwissArmyHammerError::Template(msg) => {
-                McpError::internal_error(format!("Template error: {msg}"), None)
+                McpError::internal_error(format!("Template error: {msg}"), data)
             }
             SwissArmyHammerError::Other(msg) => {
                 // Check if this is a rate limiting error
                 if msg.contains("rate limit") || msg.contains("Rate limit") {
-                    McpError::invalid_params(msg, None)
+                    McpError::invalid_params(msg, data)
                 } else {
-                    McpError::internal_error(msg, None)
+                    McpError::internal_error(msg, data)
                 }
             }
             SwissArmyHammerError::Context { message, .. } => {
-                McpError::internal_error(message, None)
+                McpError::internal_error(message, data)
             }
             SwissArmyHammerError::Semantic(err) => {
-                McpError::internal_error(format!("Semantic search error: {err}"), None)
+                McpError::internal_error(format!("Semantic search error: {err}"), data)
             }
         }
     }
@@ -313,8 +320,13 @@ impl McpFormatter {
     /// This provides standardized formatting for memo displays across all tools,
     /// ensuring consistent presentation in list, search, and other operations.
     pub fn format_memo_preview(memo: &crate::memoranda::Memo, preview_length: usize) -> String {
+        let notebook = memo
+            .notebook
+            .as_ref()
+            .map(|notebook| format!("\n  Notebook: {notebook}"))
+            .unwrap_or_default();
         format!(
-            "• {} ({})\n  Created: {}\n  Updated: {}\n  Preview: {}",
+            "• {} ({}){notebook}\n  Created: {}\n  Updated: {}\n  Preview: {}",
             memo.title,
             memo.id,
             Self::format_timestamp(memo.created_at),
@@ -340,6 +352,19 @@ mod tests {
         assert_eq!(error.message, "Operation failed");
     }
 
+    #[test]
+    fn test_handle_error_includes_code() {
+        let error = McpErrorHandler::handle_error(
+            SwissArmyHammerError::memo_not_found("01ABC"),
+            "get memo",
+        );
+        assert_eq!(error.message, "Memo not found: 01ABC");
+        assert_eq!(
+            error.data,
+            Some(serde_json::json!({"code": "SAH-1700", "category": "memo"}))
+        );
+    }
+
     #[test]
     fn test_validation_string_length() {
         assert!(McpValidation::validate_string_length("short", "field", 10).is_ok());
@@ -407,6 +432,7 @@ mod tests {
             content: "This is a long piece of content that should be truncated in the preview to show only the first part".to_string(),
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            notebook: None,
         };
 
         let preview = McpFormatter::format_memo_preview(&memo, 50);
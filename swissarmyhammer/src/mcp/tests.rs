@@ -1,5 +1,6 @@
 //! Tests for MCP server functionality
 
+use super::capabilities::{ToolGroups, READ_ONLY_TOOLS};
 use super::server::McpServer;
 use super::types::{
     AllCompleteRequest, CreateIssueRequest, CurrentIssueRequest, MarkCompleteRequest,
@@ -7,7 +8,7 @@ use super::types::{
 };
 use super::utils::validate_issue_name;
 use crate::prompts::Prompt;
-use crate::PromptLibrary;
+use crate::{PromptLibrary, SwissArmyHammerError};
 use rmcp::ServerHandler;
 use std::collections::HashMap;
 
@@ -25,6 +26,55 @@ async fn test_mcp_server_creation() {
     println!("Server capabilities: {:?}", info.capabilities);
 }
 
+#[tokio::test]
+async fn test_mcp_server_lists_only_enabled_tool_groups() {
+    let server = McpServer::new(PromptLibrary::new())
+        .unwrap()
+        .with_tool_groups(ToolGroups::from_names(&["issues", "memos"], &[]));
+
+    let names: Vec<String> = server
+        .list_enabled_tools()
+        .await
+        .into_iter()
+        .map(|tool| tool.name.to_string())
+        .collect();
+    assert!(names.contains(&"issue_list".to_string()));
+    assert!(names.contains(&"memo_create".to_string()));
+    assert!(!names.contains(&"issue_create".to_string()));
+    assert!(!names.iter().any(|name| name.starts_with("search_")));
+
+    let info = server.get_info();
+    let experimental = info.capabilities.experimental.unwrap();
+    assert_eq!(
+        experimental["swissarmyhammer"]["toolGroups"],
+        serde_json::json!(["issues", "memos"])
+    );
+}
+
+#[tokio::test]
+async fn test_mcp_server_read_only_lists_only_read_only_tools() {
+    let server = McpServer::new(PromptLibrary::new())
+        .unwrap()
+        .with_read_only();
+
+    let mut names: Vec<String> = server
+        .list_enabled_tools()
+        .await
+        .into_iter()
+        .map(|tool| tool.name.to_string())
+        .collect();
+    names.sort();
+    // Every read-only tool is registered, and nothing else is served
+    assert_eq!(names, READ_ONLY_TOOLS.map(str::to_string).to_vec());
+
+    let info = server.get_info();
+    let experimental = info.capabilities.experimental.unwrap();
+    assert_eq!(
+        experimental["swissarmyhammer"]["readOnly"],
+        serde_json::json!(true)
+    );
+}
+
 #[tokio::test]
 async fn test_mcp_server_list_prompts() {
     let mut library = PromptLibrary::new();
@@ -82,6 +132,96 @@ async fn test_mcp_server_exposes_prompt_capabilities() {
         .contains("prompt and workflow management"));
 }
 
+#[tokio::test]
+async fn test_mcp_server_serves_memos_and_workflows_as_resources() {
+    use crate::memoranda::MarkdownMemoStorage;
+
+    let server = McpServer::new(PromptLibrary::new()).unwrap();
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    *server.tool_context.memo_storage.write().await =
+        Box::new(MarkdownMemoStorage::new(temp_dir.path().to_path_buf()));
+    let memo = server
+        .tool_context
+        .memo_storage
+        .read()
+        .await
+        .create_memo("Release plan".to_string(), "# Release plan".to_string())
+        .await
+        .unwrap();
+
+    let resources_cap = server.get_info().capabilities.resources.unwrap();
+    assert_eq!(resources_cap.list_changed, Some(true));
+
+    let resources = server.list_resources().await.unwrap();
+    let memo_uri = format!("memo://{}", memo.id);
+    assert!(resources
+        .iter()
+        .any(|resource| resource.uri == memo_uri && resource.name == "Release plan"));
+    let workflow_uri = "workflow://hello-world";
+    assert!(resources
+        .iter()
+        .any(|resource| resource.uri == workflow_uri));
+
+    let contents = server.read_resource(&memo_uri).await.unwrap();
+    assert!(matches!(
+        contents,
+        rmcp::model::ResourceContents::TextResourceContents { ref text, .. } if text == "# Release plan"
+    ));
+    let contents = server.read_resource(workflow_uri).await.unwrap();
+    assert!(matches!(
+        contents,
+        rmcp::model::ResourceContents::TextResourceContents { ref text, .. } if text.contains("hello-world")
+    ));
+
+    assert!(server.read_resource("prompt://hello").await.is_err());
+    assert!(server.read_resource("workflow://missing").await.is_err());
+}
+
+#[tokio::test]
+async fn test_mcp_server_serves_run_artifacts_as_resources() {
+    use crate::workflow::{RunArtifacts, WorkflowRunId};
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let runs_dir = temp_dir.path().join("runs");
+    let server = McpServer::new(PromptLibrary::new())
+        .unwrap()
+        .with_artifacts_dir(&runs_dir);
+    let run_id = WorkflowRunId::new();
+    let artifacts = RunArtifacts::new(&runs_dir);
+    let patch = temp_dir.path().join("fix.patch");
+    std::fs::write(&patch, "+fixed\n").unwrap();
+    artifacts.save(&run_id, &patch, None, None).unwrap();
+    let binary = temp_dir.path().join("logo.bin");
+    std::fs::write(&binary, [0xff, 0xfe, 0x00]).unwrap();
+    artifacts.save(&run_id, &binary, None, None).unwrap();
+
+    let resources = server.list_resources().await.unwrap();
+    let patch_uri = format!("artifact://{run_id}/fix.patch");
+    assert!(resources
+        .iter()
+        .any(|resource| resource.uri == patch_uri && resource.size == Some(7)));
+
+    let contents = server.read_resource(&patch_uri).await.unwrap();
+    assert!(matches!(
+        contents,
+        rmcp::model::ResourceContents::TextResourceContents { ref text, .. } if text == "+fixed\n"
+    ));
+    let contents = server
+        .read_resource(&format!("artifact://{run_id}/logo.bin"))
+        .await
+        .unwrap();
+    assert!(matches!(
+        contents,
+        rmcp::model::ResourceContents::BlobResourceContents { ref blob, .. } if blob == "//4A"
+    ));
+
+    assert!(server
+        .read_resource(&format!("artifact://{run_id}/missing.txt"))
+        .await
+        .is_err());
+    assert!(server.read_resource("artifact://not-a-run").await.is_err());
+}
+
 #[tokio::test]
 async fn test_mcp_server_uses_same_prompt_paths_as_cli() {
     // This test verifies the fix for issue 000054.md
@@ -265,6 +405,33 @@ async fn test_mcp_server_does_not_expose_partial_templates() {
     assert!(error_msg.contains("partial template"));
 }
 
+#[tokio::test]
+async fn test_mcp_server_does_not_expose_cli_only_prompts() {
+    let mut library = PromptLibrary::new();
+    library
+        .add(Prompt::new("public_prompt", "Hello {{ name }}"))
+        .unwrap();
+    for (name, key, value) in [
+        ("cli_prompt", "visibility", serde_json::json!("cli")),
+        ("private_prompt", "visibility", serde_json::json!("private")),
+        ("hidden_prompt", "expose", serde_json::json!(false)),
+    ] {
+        let mut prompt = Prompt::new(name, "Internal notes");
+        prompt.metadata.insert(key.to_string(), value);
+        library.add(prompt).unwrap();
+    }
+
+    let server = McpServer::new(library).unwrap();
+    assert_eq!(server.list_prompts().await.unwrap(), ["public_prompt"]);
+    assert!(server.get_prompt("public_prompt", None).await.is_ok());
+
+    // Prompts kept from MCP look like prompts that don't exist
+    for name in ["cli_prompt", "private_prompt", "hidden_prompt"] {
+        let error = server.get_prompt(name, None).await.unwrap_err();
+        assert!(matches!(error, SwissArmyHammerError::PromptNotFound(_)));
+    }
+}
+
 #[tokio::test]
 async fn test_mcp_server_exposes_issue_tools() {
     // Create a test library and server
@@ -321,50 +321,29 @@ impl ToolHandlers {
 
     /// Handle the memo_get_all_context tool operation.
     ///
-    /// Gets all memo content formatted for AI context consumption.
+    /// Gets memo content formatted for AI context consumption, filtered by
+    /// the request's query and limited to its token budget.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The context request with optional query and token budget
     ///
     /// # Returns
     ///
     /// * `Result<CallToolResult, McpError>` - The tool call result
     pub async fn handle_memo_get_all_context(
         &self,
-        _request: GetAllContextRequest,
+        request: GetAllContextRequest,
     ) -> std::result::Result<CallToolResult, McpError> {
-        tracing::debug!("Getting all memo context");
+        tracing::debug!("Getting memo context: {:?}", request);
 
         let memo_storage = self.memo_storage.read().await;
         match memo_storage.list_memos().await {
-            Ok(memos) => {
-                tracing::info!("Retrieved {} memos for context", memos.len());
-                if memos.is_empty() {
-                    Ok(create_success_response("No memos available".to_string()))
-                } else {
-                    // Sort memos by updated_at descending (most recent first)
-                    let mut sorted_memos = memos;
-                    sorted_memos.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
-
-                    let context = sorted_memos
-                        .iter()
-                        .map(|memo| {
-                            format!(
-                                "=== {} (ID: {}) ===\nCreated: {}\nUpdated: {}\n\n{}",
-                                memo.title,
-                                memo.id,
-                                McpFormatter::format_timestamp(memo.created_at),
-                                McpFormatter::format_timestamp(memo.updated_at),
-                                memo.content
-                            )
-                        })
-                        .collect::<Vec<_>>()
-                        .join(&format!("\n\n{}\n\n", "=".repeat(80)));
-
-                    let memo_count = sorted_memos.len();
-                    let plural_suffix = if memo_count == 1 { "" } else { "s" };
-                    Ok(create_success_response(format!(
-                        "All memo context ({memo_count} memo{plural_suffix}):\n\n{context}"
-                    )))
-                }
-            }
+            Ok(memos) => Ok(create_success_response(
+                crate::mcp::tools::memoranda::get_all_context::format_context_response(
+                    memos, &request,
+                ),
+            )),
             Err(e) => Err(McpErrorHandler::handle_error(e, "get memo context")),
         }
     }
@@ -71,6 +71,7 @@
 //! }
 //! ```
 
+use super::health::ServerHealth;
 use super::tool_handlers::ToolHandlers;
 use crate::common::rate_limiter::RateLimitChecker;
 use crate::git::GitOperations;
@@ -144,6 +145,9 @@ pub struct ToolContext {
     /// Provides configurable rate limiting for MCP operations. The trait-based
     /// design allows for easy testing with mock implementations.
     pub rate_limiter: Arc<dyn RateLimitChecker>,
+
+    /// Health the server records as it runs, reported by the `health` tool
+    pub health: Arc<ServerHealth>,
 }
 
 impl ToolContext {
@@ -161,6 +165,7 @@ impl ToolContext {
             git_ops,
             memo_storage,
             rate_limiter,
+            health: Arc::new(ServerHealth::new()),
         }
     }
 }
@@ -473,6 +478,18 @@ pub fn register_search_tools(registry: &mut ToolRegistry) {
     search::register_search_tools(registry);
 }
 
+/// Register all workflow-related tools with the registry
+pub fn register_workflow_tools(registry: &mut ToolRegistry) {
+    use crate::mcp::tools::workflows;
+    workflows::register_workflow_tools(registry);
+}
+
+/// Register all server tools with the registry
+pub fn register_server_tools(registry: &mut ToolRegistry) {
+    use crate::mcp::tools::server;
+    server::register_server_tools(registry);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,84 @@
+//! Issue assignment tool for MCP operations
+//!
+//! This module provides the AssignIssueTool for setting who owns an issue.
+
+use crate::issues::{assign_issue, resolve_user};
+use crate::mcp::responses::create_success_response;
+use crate::mcp::shared_utils::{McpErrorHandler, McpValidation};
+use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
+use crate::mcp::types::AssignIssueRequest;
+use async_trait::async_trait;
+use rmcp::model::CallToolResult;
+use rmcp::Error as McpError;
+
+/// Tool for assigning issues to people
+#[derive(Default)]
+pub struct AssignIssueTool;
+
+impl AssignIssueTool {
+    /// Creates a new instance of the AssignIssueTool
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl McpTool for AssignIssueTool {
+    fn name(&self) -> &'static str {
+        "issue_assign"
+    }
+
+    fn description(&self) -> &'static str {
+        crate::mcp::tool_descriptions::get_tool_description("issues", "assign")
+            .expect("Tool description should be available")
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "name": {
+                    "type": "string",
+                    "description": "Issue name to assign"
+                },
+                "assignee": {
+                    "type": ["string", "null"],
+                    "description": "Who to assign the issue to, or @me for the git config identity; omit to unassign the issue"
+                }
+            },
+            "required": ["name"]
+        })
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Map<String, serde_json::Value>,
+        context: &ToolContext,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let request: AssignIssueRequest = BaseToolImpl::parse_arguments(arguments)?;
+
+        McpValidation::validate_not_empty(request.name.as_str(), "issue name")
+            .map_err(|e| McpErrorHandler::handle_error(e, "validate issue name"))?;
+        let assignee = request
+            .assignee
+            .as_deref()
+            .map(resolve_user)
+            .transpose()
+            .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+
+        let issue_storage = context.issue_storage.write().await;
+        match assign_issue(
+            issue_storage.as_ref(),
+            request.name.as_str(),
+            assignee.as_deref(),
+        )
+        .await
+        {
+            Ok(issue) => Ok(create_success_response(match &assignee {
+                Some(assignee) => format!("Assigned issue {} to {assignee}", issue.name),
+                None => format!("Unassigned issue {}", issue.name),
+            })),
+            Err(e) => Err(McpErrorHandler::handle_error(e, "assign issue")),
+        }
+    }
+}
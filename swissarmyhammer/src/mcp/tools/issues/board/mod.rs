@@ -0,0 +1,92 @@
+//! Issue board tool for MCP operations
+//!
+//! This module provides the BoardIssuesTool for viewing issues grouped into
+//! todo / in progress / done columns.
+
+use crate::issues::IssueBoard;
+use crate::mcp::shared_utils::McpErrorHandler;
+use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
+use async_trait::async_trait;
+use rmcp::model::CallToolResult;
+use rmcp::Error as McpError;
+use serde::{Deserialize, Serialize};
+
+/// Request structure for the issue board
+#[derive(Debug, Deserialize, Serialize)]
+pub struct BoardIssuesRequest {
+    /// Output format (table, json)
+    pub format: Option<String>,
+}
+
+/// Tool for showing issues as a board
+#[derive(Default)]
+pub struct BoardIssuesTool;
+
+impl BoardIssuesTool {
+    /// Creates a new instance of the BoardIssuesTool
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl McpTool for BoardIssuesTool {
+    fn name(&self) -> &'static str {
+        "issue_board"
+    }
+
+    fn description(&self) -> &'static str {
+        crate::mcp::tool_descriptions::get_tool_description("issues", "board")
+            .expect("Tool description should be available")
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "format": {
+                    "type": "string",
+                    "description": "Output format - table or json",
+                    "default": "table",
+                    "enum": ["table", "json"]
+                }
+            },
+            "required": []
+        })
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Map<String, serde_json::Value>,
+        context: &ToolContext,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let request: BoardIssuesRequest = BaseToolImpl::parse_arguments(arguments)?;
+
+        let issue_storage = context.issue_storage.read().await;
+        let issues = issue_storage
+            .list_issues()
+            .await
+            .map_err(|e| McpErrorHandler::handle_error(e, "list issues"))?;
+
+        // An issue is in progress when its work branch is checked out or exists
+        let git_ops = context.git_ops.lock().await;
+        let current_branch = git_ops.as_ref().and_then(|ops| ops.current_branch().ok());
+        let board = IssueBoard::from_issues(&issues, |name| {
+            let Some(ops) = git_ops.as_ref() else {
+                return false;
+            };
+            let branch = ops.issue_branch(name);
+            current_branch.as_deref() == Some(branch.as_str())
+                || ops.branch_exists(&branch).unwrap_or(false)
+        });
+
+        let response = match request.format.as_deref() {
+            Some("json") => serde_json::to_string_pretty(&board).map_err(|e| {
+                McpError::internal_error(format!("Failed to serialize board: {e}"), None)
+            })?,
+            _ => board.render_table(),
+        };
+
+        Ok(BaseToolImpl::create_success_response(&response))
+    }
+}
@@ -0,0 +1,150 @@
+//! Issue bulk change tool for MCP operations
+//!
+//! This module provides the BulkIssuesTool for completing, labeling or moving
+//! many issues at once.
+
+use crate::issues::{select_issues, BulkAction, BulkPlan};
+use crate::mcp::shared_utils::McpErrorHandler;
+use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
+use async_trait::async_trait;
+use rmcp::model::CallToolResult;
+use rmcp::Error as McpError;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Request structure for bulk issue changes
+#[derive(Debug, Deserialize, Serialize)]
+pub struct BulkIssuesRequest {
+    /// Change to make (complete, label, move)
+    pub action: String,
+    /// Issue names or glob patterns selecting the issues
+    pub patterns: Vec<String>,
+    /// Label to add, for the label action
+    pub label: Option<String>,
+    /// Issues directory to move to, for the move action
+    pub to: Option<String>,
+    /// Apply the changes instead of only planning them
+    pub apply: Option<bool>,
+    /// Output format (table, json)
+    pub format: Option<String>,
+}
+
+/// Tool for changing many issues at once
+#[derive(Default)]
+pub struct BulkIssuesTool;
+
+impl BulkIssuesTool {
+    /// Creates a new instance of the BulkIssuesTool
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// The action described by a request
+    fn action(request: &BulkIssuesRequest) -> std::result::Result<BulkAction, McpError> {
+        match request.action.as_str() {
+            "complete" => Ok(BulkAction::Complete),
+            "label" => {
+                let label = request.label.clone().ok_or_else(|| {
+                    McpError::invalid_params("label is required to label issues".to_string(), None)
+                })?;
+                Ok(BulkAction::Label { label })
+            }
+            "move" => {
+                let to = request.to.as_ref().ok_or_else(|| {
+                    McpError::invalid_params("to is required to move issues".to_string(), None)
+                })?;
+                Ok(BulkAction::Move {
+                    to: PathBuf::from(to),
+                })
+            }
+            other => Err(McpError::invalid_params(
+                format!("Invalid action '{other}': expected complete, label or move"),
+                None,
+            )),
+        }
+    }
+}
+
+#[async_trait]
+impl McpTool for BulkIssuesTool {
+    fn name(&self) -> &'static str {
+        "issue_bulk"
+    }
+
+    fn description(&self) -> &'static str {
+        crate::mcp::tool_descriptions::get_tool_description("issues", "bulk")
+            .expect("Tool description should be available")
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "description": "Change to make to every selected issue",
+                    "enum": ["complete", "label", "move"]
+                },
+                "patterns": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Issue names or glob patterns such as *_stale_*"
+                },
+                "label": {
+                    "type": "string",
+                    "description": "Label to add to the issues' front matter, for the label action"
+                },
+                "to": {
+                    "type": "string",
+                    "description": "Issues directory to move the issues to, for the move action"
+                },
+                "apply": {
+                    "type": "boolean",
+                    "description": "Apply the changes; when false only the planned changes are returned",
+                    "default": false
+                },
+                "format": {
+                    "type": "string",
+                    "description": "Output format - table or json",
+                    "default": "table",
+                    "enum": ["table", "json"]
+                }
+            },
+            "required": ["action", "patterns"]
+        })
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Map<String, serde_json::Value>,
+        context: &ToolContext,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let request: BulkIssuesRequest = BaseToolImpl::parse_arguments(arguments)?;
+        let action = Self::action(&request)?;
+
+        // Hold the write lock so no other issue change lands between planning and applying
+        let issue_storage = context.issue_storage.write().await;
+        let issues = issue_storage
+            .list_issues()
+            .await
+            .map_err(|e| McpErrorHandler::handle_error(e, "list issues"))?;
+        let selected = select_issues(&issues, &request.patterns)
+            .map_err(|e| McpErrorHandler::handle_error(e, "select issues"))?;
+
+        let mut plan = BulkPlan::new(action, &selected)
+            .map_err(|e| McpErrorHandler::handle_error(e, "plan bulk change"))?;
+        if request.apply.unwrap_or(false) {
+            plan.apply()
+                .map_err(|e| McpErrorHandler::handle_error(e, "apply bulk change"))?;
+        }
+
+        let response = match request.format.as_deref() {
+            Some("json") => serde_json::to_string_pretty(&plan).map_err(|e| {
+                McpError::internal_error(format!("Failed to serialize bulk change: {e}"), None)
+            })?,
+            _ => plan.to_string(),
+        };
+
+        Ok(BaseToolImpl::create_success_response(&response))
+    }
+}
@@ -0,0 +1,101 @@
+//! Issue checklist tool for MCP operations
+//!
+//! This module provides the CheckIssueTool for ticking the task list items in
+//! an issue's body and reporting the issue's checklist.
+
+use crate::issues::{check_issue_item, Checklist};
+use crate::mcp::shared_utils::{McpErrorHandler, McpValidation};
+use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
+use crate::mcp::types::CheckIssueRequest;
+use async_trait::async_trait;
+use rmcp::model::CallToolResult;
+use rmcp::Error as McpError;
+
+/// Tool for ticking checklist items of issues
+#[derive(Default)]
+pub struct CheckIssueTool;
+
+impl CheckIssueTool {
+    /// Creates a new instance of the CheckIssueTool
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl McpTool for CheckIssueTool {
+    fn name(&self) -> &'static str {
+        "issue_check"
+    }
+
+    fn description(&self) -> &'static str {
+        crate::mcp::tool_descriptions::get_tool_description("issues", "check")
+            .expect("Tool description should be available")
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "name": {
+                    "type": "string",
+                    "description": "Issue name whose checklist to read or change"
+                },
+                "item": {
+                    "type": ["string", "null"],
+                    "description": "Task to tick, by its number from 1 or by words of its text; omit to only report the checklist"
+                },
+                "done": {
+                    "type": ["boolean", "null"],
+                    "description": "Whether the task is done; omit to toggle it"
+                }
+            },
+            "required": ["name"]
+        })
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Map<String, serde_json::Value>,
+        context: &ToolContext,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let request: CheckIssueRequest = BaseToolImpl::parse_arguments(arguments)?;
+
+        McpValidation::validate_not_empty(request.name.as_str(), "issue name")
+            .map_err(|e| McpErrorHandler::handle_error(e, "validate issue name"))?;
+
+        let issue_storage = context.issue_storage.write().await;
+        let (issue, checked) = match &request.item {
+            Some(item) => {
+                let (issue, item) = check_issue_item(
+                    issue_storage.as_ref(),
+                    request.name.as_str(),
+                    item,
+                    request.done,
+                )
+                .await
+                .map_err(|e| McpErrorHandler::handle_error(e, "check issue task"))?;
+                (issue, Some(item))
+            }
+            None => (
+                issue_storage
+                    .get_issue(request.name.as_str())
+                    .await
+                    .map_err(|e| McpErrorHandler::handle_error(e, "get issue"))?,
+                None,
+            ),
+        };
+
+        let checklist = Checklist::parse(&issue.content);
+        let response = serde_json::json!({
+            "issue": issue.name,
+            "checked": checked,
+            "progress": checklist.progress(),
+            "items": checklist.items,
+        });
+        let json = serde_json::to_string_pretty(&response).map_err(|e| {
+            McpError::internal_error(format!("Failed to serialize checklist: {e}"), None)
+        })?;
+        Ok(BaseToolImpl::create_success_response(json))
+    }
+}
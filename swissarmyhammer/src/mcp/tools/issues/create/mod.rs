@@ -2,6 +2,8 @@
 //!
 //! This module provides the CreateIssueTool for creating new issues through the MCP protocol.
 
+use crate::issues::assignment::with_reporter;
+use crate::issues::git_identity;
 use crate::mcp::responses::create_issue_response;
 use crate::mcp::shared_utils::{McpErrorHandler, McpValidation};
 use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
@@ -82,11 +84,15 @@ impl McpTool for CreateIssueTool {
         McpValidation::validate_not_empty(&request.content, "issue content")
             .map_err(|e| McpErrorHandler::handle_error(e, "validate issue content"))?;
 
+        // Record who raised the issue, when git config says who that is
+        let content = match git_identity() {
+            Some(reporter) => with_reporter(&request.content, &reporter)
+                .map_err(|e| McpErrorHandler::handle_error(e, "record issue reporter"))?,
+            None => request.content,
+        };
+
         let issue_storage = context.issue_storage.write().await;
-        match issue_storage
-            .create_issue(validated_name, request.content)
-            .await
-        {
+        match issue_storage.create_issue(validated_name, content).await {
             Ok(issue) => {
                 tracing::info!("Created issue {}", issue.name);
                 Ok(create_issue_response(&issue))
@@ -2,7 +2,6 @@
 //!
 //! This module provides the CurrentIssueTool for getting the current issue being worked on.
 
-use crate::config::Config;
 use crate::mcp::responses::{create_error_response, create_success_response};
 use crate::mcp::shared_utils::McpErrorHandler;
 use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
@@ -57,8 +56,7 @@ impl McpTool for CurrentIssueTool {
         match git_ops.as_ref() {
             Some(ops) => match ops.current_branch() {
                 Ok(branch) => {
-                    let config = Config::global();
-                    if let Some(issue_name) = branch.strip_prefix(&config.issue_branch_prefix) {
+                    if let Some(issue_name) = ops.branch_naming().issue_name(&branch) {
                         Ok(create_success_response(format!(
                             "Currently working on issue: {issue_name}"
                         )))
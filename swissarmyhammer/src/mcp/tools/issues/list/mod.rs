@@ -2,10 +2,16 @@
 //!
 //! This module provides the ListIssuesTool for listing existing issues through the MCP protocol.
 
-use crate::issues::Issue;
+use crate::config::Config;
+use crate::issues::sla::age_days;
+use crate::issues::{
+    is_assigned_to, resolve_user, Issue, IssueFields, IssueMetadata, IssuePriority, IssueSla,
+    IssueSort, SlaStatus,
+};
 use crate::mcp::shared_utils::McpErrorHandler;
 use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use rmcp::model::CallToolResult;
 use rmcp::Error as McpError;
 use serde::{Deserialize, Serialize};
@@ -19,6 +25,12 @@ pub struct ListIssuesRequest {
     pub show_active: Option<bool>,
     /// Output format (table, json, markdown)
     pub format: Option<String>,
+    /// Order of the listed issues (name, priority, estimate, created)
+    pub sort: Option<String>,
+    /// Only include issues with this priority (high, medium, low)
+    pub priority: Option<String>,
+    /// Only include issues assigned to this person, `@me` for the git config identity
+    pub assignee: Option<String>,
 }
 
 /// Tool for listing issues
@@ -31,14 +43,90 @@ impl ListIssuesTool {
         Self
     }
 
+    /// Issues in the requested states, with the requested priority and
+    /// assignee, in the requested order
+    fn select<T: IssueFields>(
+        issues: Vec<T>,
+        request: &ListIssuesRequest,
+        priority: Option<IssuePriority>,
+        assignee: Option<&str>,
+        sort: IssueSort,
+    ) -> Vec<T> {
+        let show_completed = request.show_completed.unwrap_or(false);
+        let show_active = request.show_active.unwrap_or(true);
+        let mut selected: Vec<_> = issues
+            .into_iter()
+            .filter(|issue| {
+                if show_completed && show_active {
+                    true // show all
+                } else if show_completed {
+                    issue.completed()
+                } else if show_active {
+                    !issue.completed()
+                } else {
+                    true // default: show all
+                }
+            })
+            .filter(|issue| {
+                priority.map_or(true, |priority| {
+                    issue.priority().unwrap_or_default() == priority
+                })
+            })
+            .filter(|issue| assignee.map_or(true, |assignee| is_assigned_to(issue, assignee)))
+            .collect();
+        sort.sort(&mut selected);
+        selected
+    }
+
+    /// Priority, estimate and assignee of an issue, as shown after its name
+    fn planning_label<T: IssueFields>(issue: &T) -> String {
+        let mut details = Vec::new();
+        if let Some(priority) = issue.priority() {
+            details.push(priority.to_string());
+        }
+        if let Some(estimate) = issue.estimate() {
+            details.push(format!("estimate {estimate}"));
+        }
+        if let Some(assignee) = issue.assignee() {
+            details.push(format!("@{assignee}"));
+        }
+        if details.is_empty() {
+            String::new()
+        } else {
+            format!(" [{}]", details.join(", "))
+        }
+    }
+
+    /// Checklist progress of an issue, as shown after its name
+    fn tasks_label<T: IssueFields>(issue: &T) -> String {
+        let tasks = issue.tasks();
+        if tasks.is_empty() {
+            String::new()
+        } else {
+            format!(" ☑ {tasks}")
+        }
+    }
+
+    /// Age of an issue past its SLA, as shown after its name
+    fn sla_label<T: IssueFields>(issue: &T, sla: &IssueSla, now: DateTime<Utc>) -> String {
+        match sla.status(issue, now) {
+            SlaStatus::OnTrack => String::new(),
+            status => format!(
+                " {} open {} days, {status}",
+                status.marker(),
+                age_days(issue, now)
+            ),
+        }
+    }
+
     /// Format issues as a table
-    fn format_as_table(issues: &[Issue]) -> String {
+    fn format_as_table<T: IssueFields>(issues: &[T], sla: &IssueSla, now: DateTime<Utc>) -> String {
         if issues.is_empty() {
             return "No issues found.".to_string();
         }
 
-        let active_issues: Vec<_> = issues.iter().filter(|i| !i.completed).collect();
-        let completed_issues: Vec<_> = issues.iter().filter(|i| i.completed).collect();
+        let active_issues: Vec<_> = issues.iter().filter(|i| !i.completed()).collect();
+        let completed_issues: Vec<_> = issues.iter().filter(|i| i.completed()).collect();
 
         let total_issues = issues.len();
         let completed_count = completed_issues.len();
@@ -60,7 +148,13 @@ impl ListIssuesTool {
             result.push('\n');
             result.push_str("Active Issues:\n");
             for issue in active_issues {
-                result.push_str(&format!("  🔄 {}\n", issue.name));
+                result.push_str(&format!(
+                    "  🔄 {}{}{}{}\n",
+                    issue.name(),
+                    Self::planning_label(issue),
+                    Self::tasks_label(issue),
+                    Self::sla_label(issue, sla, now)
+                ));
             }
         }
 
@@ -68,10 +162,10 @@ impl ListIssuesTool {
             result.push('\n');
             result.push_str("Recently Completed:\n");
             let mut sorted_completed = completed_issues;
-            sorted_completed.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+            sorted_completed.sort_by_key(|issue| std::cmp::Reverse(issue.created_at()));
 
             for issue in sorted_completed.iter().take(5) {
-                result.push_str(&format!("  ✅ {}\n", issue.name));
+                result.push_str(&format!("  ✅ {}\n", issue.name()));
             }
         }
 
@@ -79,7 +173,7 @@ impl ListIssuesTool {
     }
 
     /// Format issues as markdown
-    fn format_as_markdown(issues: &[Issue]) -> String {
+    fn format_as_markdown(issues: &[Issue], sla: &IssueSla, now: DateTime<Utc>) -> String {
         let mut result = String::from("# Issues\n\n");
 
         if issues.is_empty() {
@@ -98,10 +192,34 @@ impl ListIssuesTool {
                     "Active"
                 }
             ));
+            if let Some(priority) = issue.priority {
+                result.push_str(&format!("- **Priority**: {priority}\n"));
+            }
+            if let Some(estimate) = issue.estimate {
+                result.push_str(&format!("- **Estimate**: {estimate}\n"));
+            }
+            let metadata = IssueMetadata::from_content(&issue.content);
+            if let Some(assignee) = &metadata.assignee {
+                result.push_str(&format!("- **Assignee**: {assignee}\n"));
+            }
+            if let Some(reporter) = &metadata.reporter {
+                result.push_str(&format!("- **Reporter**: {reporter}\n"));
+            }
+            let tasks = issue.tasks();
+            if !tasks.is_empty() {
+                result.push_str(&format!("- **Tasks**: {tasks}\n"));
+            }
             result.push_str(&format!(
                 "- **Created**: {}\n",
                 issue.created_at.format("%Y-%m-%d")
             ));
+            let status = sla.status(issue, now);
+            if status != SlaStatus::OnTrack {
+                result.push_str(&format!(
+                    "- **SLA**: {status}, open {} days\n",
+                    age_days(issue, now)
+                ));
+            }
             result.push_str(&format!("- **File**: {}\n\n", issue.file_path.display()));
 
             if !issue.content.is_empty() {
@@ -146,6 +264,21 @@ impl McpTool for ListIssuesTool {
                     "description": "Output format - table, json, or markdown",
                     "default": "table",
                     "enum": ["table", "json", "markdown"]
+                },
+                "sort": {
+                    "type": "string",
+                    "description": "Order of the listed issues",
+                    "default": "name",
+                    "enum": ["name", "priority", "estimate", "created"]
+                },
+                "priority": {
+                    "type": "string",
+                    "description": "Only include issues with this priority; issues without a priority count as medium",
+                    "enum": ["high", "medium", "low"]
+                },
+                "assignee": {
+                    "type": "string",
+                    "description": "Only include issues assigned to this person, or @me for the git config identity"
                 }
             },
             "required": []
@@ -158,6 +291,25 @@ impl McpTool for ListIssuesTool {
         context: &ToolContext,
     ) -> std::result::Result<CallToolResult, McpError> {
         let request: ListIssuesRequest = BaseToolImpl::parse_arguments(arguments)?;
+        let sort = request
+            .sort
+            .as_deref()
+            .map(str::parse::<IssueSort>)
+            .transpose()
+            .map_err(|e| McpError::invalid_params(e.to_string(), None))?
+            .unwrap_or_default();
+        let priority = request
+            .priority
+            .as_deref()
+            .map(str::parse::<IssuePriority>)
+            .transpose()
+            .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+        let assignee = request
+            .assignee
+            .as_deref()
+            .map(resolve_user)
+            .transpose()
+            .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
 
         // Apply rate limiting for issue listing
         context
@@ -169,47 +321,49 @@ impl McpTool for ListIssuesTool {
             })?;
 
         tracing::debug!(
-            "Listing issues with filters: show_completed={:?}, show_active={:?}, format={:?}",
+            "Listing issues with filters: show_completed={:?}, show_active={:?}, priority={:?}, assignee={:?}, sort={}, format={:?}",
             request.show_completed,
             request.show_active,
+            priority,
+            assignee,
+            sort,
             request.format
         );
 
         let issue_storage = context.issue_storage.read().await;
-        let all_issues = issue_storage
-            .list_issues()
-            .await
-            .map_err(|e| McpErrorHandler::handle_error(e, "list issues"))?;
+        let format = request
+            .format
+            .clone()
+            .unwrap_or_else(|| "table".to_string());
+        let sla = IssueSla::from_config(Config::global());
+        let now = Utc::now();
 
-        let show_completed = request.show_completed.unwrap_or(false);
-        let show_active = request.show_active.unwrap_or(true);
-        let format = request.format.unwrap_or_else(|| "table".to_string());
-
-        // Filter issues based on criteria
-        let filtered_issues: Vec<_> = all_issues
-            .into_iter()
-            .filter(|issue| {
-                if show_completed && show_active {
-                    true // show all
-                } else if show_completed {
-                    issue.completed
-                } else if show_active {
-                    !issue.completed
-                } else {
-                    true // default: show all
-                }
-            })
-            .collect();
-
-        let response = match format.as_str() {
-            "json" => serde_json::to_string_pretty(&filtered_issues).map_err(|e| {
-                McpError::internal_error(format!("Failed to serialize issues: {e}"), None)
-            })?,
-            "markdown" => Self::format_as_markdown(&filtered_issues),
-            _ => Self::format_as_table(&filtered_issues),
+        // The table only needs what the issue index holds, so it never reads
+        // the issue files; the other formats include the content
+        let (response, count) = if matches!(format.as_str(), "json" | "markdown") {
+            let all_issues = issue_storage
+                .list_issues()
+                .await
+                .map_err(|e| McpErrorHandler::handle_error(e, "list issues"))?;
+            let issues = Self::select(all_issues, &request, priority, assignee.as_deref(), sort);
+            let response = if format == "json" {
+                serde_json::to_string_pretty(&issues).map_err(|e| {
+                    McpError::internal_error(format!("Failed to serialize issues: {e}"), None)
+                })?
+            } else {
+                Self::format_as_markdown(&issues, &sla, now)
+            };
+            (response, issues.len())
+        } else {
+            let summaries = issue_storage
+                .list_issue_summaries()
+                .await
+                .map_err(|e| McpErrorHandler::handle_error(e, "list issues"))?;
+            let issues = Self::select(summaries, &request, priority, assignee.as_deref(), sort);
+            (Self::format_as_table(&issues, &sla, now), issues.len())
         };
 
-        tracing::info!("Listed {} issues", filtered_issues.len());
+        tracing::info!("Listed {} issues", count);
         Ok(BaseToolImpl::create_success_response(&response))
     }
 }
@@ -19,11 +19,6 @@ impl MergeIssueTool {
     pub fn new() -> Self {
         Self
     }
-
-    /// Format the issue branch name with the standard prefix
-    fn format_issue_branch_name(issue_name: &str) -> String {
-        format!("issue/{issue_name}")
-    }
 }
 
 #[async_trait]
@@ -86,6 +81,14 @@ impl McpTool for MergeIssueTool {
 
         match git_ops.as_mut() {
             Some(ops) => {
+                // The branch lives in the repository named by the issue's `repo:` key
+                let ops = match ops.for_issue(&issue) {
+                    Ok(ops) => ops,
+                    Err(e) => {
+                        return Err(McpErrorHandler::handle_error(e, "open issue repository"))
+                    }
+                };
+
                 // First merge the branch
                 match ops.merge_issue_branch(&issue_name) {
                     Ok(_) => {
@@ -113,7 +116,7 @@ impl McpTool for MergeIssueTool {
 
                         // If delete_branch is true, delete the branch after successful merge
                         if request.delete_branch {
-                            let branch_name = Self::format_issue_branch_name(&issue_name);
+                            let branch_name = ops.issue_branch(&issue_name);
                             match ops.delete_branch(&branch_name) {
                                 Ok(_) => {
                                     success_message
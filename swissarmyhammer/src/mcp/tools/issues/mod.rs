@@ -14,6 +14,9 @@
 //! 4. **Completion**: `mark_complete` tool moves issues to `./issues/complete/`
 //! 5. **Integration**: `merge` tool integrates completed work back to main branch
 //!
+//! While an issue is being worked, `sync` brings new main branch commits into its
+//! work branch and reports a resolution plan when they conflict.
+//!
 //! ## Tool Implementation Pattern
 //!
 //! Each tool follows the standard MCP pattern:
@@ -65,16 +68,32 @@
 //! - **current**: Get the currently active issue based on git branch
 //! - **work**: Switch to or create a work branch for an issue
 //! - **merge**: Merge completed issue work back to main branch
+//! - **sync**: Merge or rebase the main branch into an issue work branch
 //! - **next**: Get the next pending issue to work on
+//! - **reopen**: Move a completed issue back to pending
+//! - **board**: Show issues grouped into todo / in progress / done columns
+//! - **stats**: Report throughput, cycle time, and open vs completed counts
+//! - **bulk**: Complete, label or move many issues at once
+//! - **transition**: Move an issue to another configured state
+//! - **assign**: Assign an issue to someone, or unassign it
+//! - **check**: Tick the checklist items of an issue and report its progress
 
 pub mod all_complete;
+pub mod assign;
+pub mod board;
+pub mod bulk;
+pub mod check;
 pub mod create;
 pub mod current;
 pub mod list;
 pub mod mark_complete;
 pub mod merge;
 pub mod next;
+pub mod reopen;
 pub mod show;
+pub mod stats;
+pub mod sync;
+pub mod transition;
 pub mod update;
 pub mod work;
 
@@ -91,5 +110,13 @@ pub fn register_issue_tools(registry: &mut ToolRegistry) {
     registry.register(current::CurrentIssueTool::new());
     registry.register(work::WorkIssueTool::new());
     registry.register(merge::MergeIssueTool::new());
+    registry.register(sync::SyncIssueTool::new());
     registry.register(next::NextIssueTool::new());
+    registry.register(reopen::ReopenIssueTool::new());
+    registry.register(board::BoardIssuesTool::new());
+    registry.register(stats::StatsIssuesTool::new());
+    registry.register(bulk::BulkIssuesTool::new());
+    registry.register(transition::TransitionIssueTool::new());
+    registry.register(assign::AssignIssueTool::new());
+    registry.register(check::CheckIssueTool::new());
 }
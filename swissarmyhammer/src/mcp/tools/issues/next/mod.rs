@@ -2,6 +2,7 @@
 //!
 //! This module provides the NextIssueTool for getting the next issue to work on.
 
+use crate::issues::{next_issue, IssueSort};
 use crate::mcp::responses::create_success_response;
 use crate::mcp::shared_utils::McpErrorHandler;
 use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
@@ -35,7 +36,14 @@ impl McpTool for NextIssueTool {
     fn schema(&self) -> serde_json::Value {
         serde_json::json!({
             "type": "object",
-            "properties": {},
+            "properties": {
+                "sort": {
+                    "type": "string",
+                    "description": "Order used to pick the issue",
+                    "default": "priority",
+                    "enum": ["name", "priority", "estimate", "created"]
+                }
+            },
             "required": []
         })
     }
@@ -45,12 +53,23 @@ impl McpTool for NextIssueTool {
         arguments: serde_json::Map<String, serde_json::Value>,
         context: &ToolContext,
     ) -> std::result::Result<CallToolResult, McpError> {
-        let _request: NextIssueRequest = BaseToolImpl::parse_arguments(arguments)?;
+        let request: NextIssueRequest = BaseToolImpl::parse_arguments(arguments)?;
+        let sort = request
+            .sort
+            .as_deref()
+            .map(str::parse::<IssueSort>)
+            .transpose()
+            .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
 
         let issue_storage = context.issue_storage.read().await;
 
-        // Use the new get_next_issue method from storage
-        match issue_storage.get_next_issue().await {
+        // Storage picks by priority; any other order is applied to the full list
+        // Only the name is shown, so the issue index is enough to pick it
+        let next = issue_storage
+            .list_issue_summaries()
+            .await
+            .map(|summaries| next_issue(summaries, sort.unwrap_or(IssueSort::Priority)));
+        match next {
             Ok(Some(next_issue)) => Ok(create_success_response(format!(
                 "Next issue: {}",
                 next_issue.name.as_str()
@@ -0,0 +1,68 @@
+//! Issue reopen tool for MCP operations
+//!
+//! This module provides the ReopenIssueTool for moving completed issues back to pending.
+
+use crate::mcp::responses::create_success_response;
+use crate::mcp::shared_utils::{McpErrorHandler, McpValidation};
+use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
+use crate::mcp::types::ReopenIssueRequest;
+use async_trait::async_trait;
+use rmcp::model::CallToolResult;
+use rmcp::Error as McpError;
+
+/// Tool for reopening completed issues
+#[derive(Default)]
+pub struct ReopenIssueTool;
+
+impl ReopenIssueTool {
+    /// Creates a new instance of the ReopenIssueTool
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl McpTool for ReopenIssueTool {
+    fn name(&self) -> &'static str {
+        "issue_reopen"
+    }
+
+    fn description(&self) -> &'static str {
+        crate::mcp::tool_descriptions::get_tool_description("issues", "reopen")
+            .expect("Tool description should be available")
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "name": {
+                    "type": "string",
+                    "description": "Issue name to reopen"
+                }
+            },
+            "required": ["name"]
+        })
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Map<String, serde_json::Value>,
+        context: &ToolContext,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let request: ReopenIssueRequest = BaseToolImpl::parse_arguments(arguments)?;
+
+        McpValidation::validate_not_empty(request.name.as_str(), "issue name")
+            .map_err(|e| McpErrorHandler::handle_error(e, "validate issue name"))?;
+
+        let issue_storage = context.issue_storage.write().await;
+        match issue_storage.reopen_issue(request.name.as_str()).await {
+            Ok(issue) => Ok(create_success_response(format!(
+                "Reopened issue {} and moved to: {}",
+                issue.name,
+                issue.file_path.display()
+            ))),
+            Err(e) => Err(McpErrorHandler::handle_error(e, "reopen issue")),
+        }
+    }
+}
@@ -2,6 +2,7 @@
 //!
 //! This module provides the ShowIssueTool for displaying specific issues through the MCP protocol.
 
+use crate::cross_references::{related_memos, RelatedMemos};
 use crate::issues::Issue;
 use crate::mcp::shared_utils::{McpErrorHandler, McpValidation};
 use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
@@ -39,11 +40,34 @@ impl ShowIssueTool {
     }
 
     /// Format issue for display
-    fn format_issue_display(issue: &Issue) -> String {
+    fn format_issue_display(issue: &Issue, related: &RelatedMemos) -> String {
         let status = Self::format_issue_status(issue.completed);
 
         let mut result = format!("{} Issue: {}\n", status, issue.name);
         result.push_str(&format!("📁 File: {}\n", issue.file_path.display()));
+        if let Some(source) = &issue.source {
+            result.push_str(&format!("🗂️ Source: {}\n", source.display()));
+        }
+        if let Some(priority) = issue.priority {
+            result.push_str(&format!("🚩 Priority: {priority}\n"));
+        }
+        if let Some(estimate) = issue.estimate {
+            result.push_str(&format!("⏱️ Estimate: {estimate}\n"));
+        }
+        if !related.memos.is_empty() {
+            let memos: Vec<String> = related
+                .memos
+                .iter()
+                .map(|memo| format!("{} ({})", memo.title, memo.id))
+                .collect();
+            result.push_str(&format!("🧠 Related memos: {}\n", memos.join(", ")));
+        }
+        if !related.missing.is_empty() {
+            result.push_str(&format!(
+                "⚠️ Missing memos: {}\n",
+                related.missing.join(", ")
+            ));
+        }
         result.push_str(&format!(
             "📅 Created: {}\n\n",
             issue.created_at.format("%Y-%m-%d %H:%M:%S")
@@ -121,7 +145,14 @@ impl McpTool for ShowIssueTool {
         let response = if request.raw.unwrap_or(false) {
             issue.content
         } else {
-            Self::format_issue_display(&issue)
+            let memo_storage = context.memo_storage.read().await;
+            let related = related_memos(&issue, memo_storage.as_ref())
+                .await
+                .unwrap_or_else(|e| {
+                    tracing::warn!("Failed to resolve memos of issue {}: {}", issue.name, e);
+                    RelatedMemos::default()
+                });
+            Self::format_issue_display(&issue, &related)
         };
 
         tracing::info!("Showed issue {}", request.name);
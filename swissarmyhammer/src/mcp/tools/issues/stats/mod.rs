@@ -0,0 +1,103 @@
+//! Issue analytics tool for MCP operations
+//!
+//! This module provides the StatsIssuesTool for reporting throughput, cycle
+//! time, and open vs completed counts.
+
+use crate::issues::{IssueStats, IssueTimeline, DEFAULT_STATS_WEEKS};
+use crate::mcp::shared_utils::McpErrorHandler;
+use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
+use async_trait::async_trait;
+use rmcp::model::CallToolResult;
+use rmcp::Error as McpError;
+use serde::{Deserialize, Serialize};
+
+/// Request structure for issue analytics
+#[derive(Debug, Deserialize, Serialize)]
+pub struct StatsIssuesRequest {
+    /// Number of weeks of throughput to report
+    pub weeks: Option<usize>,
+    /// Output format (table, json)
+    pub format: Option<String>,
+}
+
+/// Tool for computing issue analytics
+#[derive(Default)]
+pub struct StatsIssuesTool;
+
+impl StatsIssuesTool {
+    /// Creates a new instance of the StatsIssuesTool
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl McpTool for StatsIssuesTool {
+    fn name(&self) -> &'static str {
+        "issue_stats"
+    }
+
+    fn description(&self) -> &'static str {
+        crate::mcp::tool_descriptions::get_tool_description("issues", "stats")
+            .expect("Tool description should be available")
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "weeks": {
+                    "type": "integer",
+                    "description": "Number of weeks of throughput to report, ending with the current week",
+                    "default": DEFAULT_STATS_WEEKS,
+                    "minimum": 1
+                },
+                "format": {
+                    "type": "string",
+                    "description": "Output format - table or json",
+                    "default": "table",
+                    "enum": ["table", "json"]
+                }
+            },
+            "required": []
+        })
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Map<String, serde_json::Value>,
+        context: &ToolContext,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let request: StatsIssuesRequest = BaseToolImpl::parse_arguments(arguments)?;
+
+        let weeks = request.weeks.unwrap_or(DEFAULT_STATS_WEEKS);
+        if weeks == 0 {
+            return Err(McpError::invalid_params(
+                "weeks must be at least 1".to_string(),
+                None,
+            ));
+        }
+
+        let issue_storage = context.issue_storage.read().await;
+        let issues = issue_storage
+            .list_issues()
+            .await
+            .map_err(|e| McpErrorHandler::handle_error(e, "list issues"))?;
+
+        let git_ops = context.git_ops.lock().await;
+        let timelines: Vec<IssueTimeline> = issues
+            .iter()
+            .map(|issue| IssueTimeline::from_issue(issue, git_ops.as_ref()))
+            .collect();
+        let stats = IssueStats::from_timelines(&timelines, weeks, chrono::Utc::now());
+
+        let response = match request.format.as_deref() {
+            Some("json") => serde_json::to_string_pretty(&stats).map_err(|e| {
+                McpError::internal_error(format!("Failed to serialize issue stats: {e}"), None)
+            })?,
+            _ => stats.to_string(),
+        };
+
+        Ok(BaseToolImpl::create_success_response(&response))
+    }
+}
@@ -0,0 +1,110 @@
+//! Issue branch sync tool for MCP operations
+//!
+//! This module provides the SyncIssueTool for bringing the main branch's new
+//! commits into an issue work branch.
+
+use crate::git::SyncStrategy;
+use crate::mcp::shared_utils::McpErrorHandler;
+use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
+use crate::mcp::types::IssueName;
+use async_trait::async_trait;
+use rmcp::model::CallToolResult;
+use rmcp::Error as McpError;
+use serde::{Deserialize, Serialize};
+
+/// Request structure for syncing an issue branch
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SyncIssueRequest {
+    /// Issue name to sync
+    pub name: IssueName,
+    /// How to bring in the main branch (merge, rebase)
+    pub strategy: Option<SyncStrategy>,
+    /// Output format (table, json)
+    pub format: Option<String>,
+}
+
+/// Tool for syncing an issue work branch with the main branch
+#[derive(Default)]
+pub struct SyncIssueTool;
+
+impl SyncIssueTool {
+    /// Creates a new instance of the SyncIssueTool
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl McpTool for SyncIssueTool {
+    fn name(&self) -> &'static str {
+        "issue_sync"
+    }
+
+    fn description(&self) -> &'static str {
+        crate::mcp::tool_descriptions::get_tool_description("issues", "sync")
+            .expect("Tool description should be available")
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "name": {
+                    "type": "string",
+                    "description": "Issue name to sync"
+                },
+                "strategy": {
+                    "type": "string",
+                    "description": "How to bring in the main branch - merge it or rebase onto it",
+                    "default": "merge",
+                    "enum": ["merge", "rebase"]
+                },
+                "format": {
+                    "type": "string",
+                    "description": "Output format - table or json",
+                    "default": "table",
+                    "enum": ["table", "json"]
+                }
+            },
+            "required": ["name"]
+        })
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Map<String, serde_json::Value>,
+        context: &ToolContext,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let request: SyncIssueRequest = BaseToolImpl::parse_arguments(arguments)?;
+
+        let issue_storage = context.issue_storage.read().await;
+        let issue = issue_storage
+            .get_issue(request.name.as_str())
+            .await
+            .map_err(|e| McpErrorHandler::handle_error(e, "get issue for sync"))?;
+
+        let git_ops = context.git_ops.lock().await;
+        let Some(ops) = git_ops.as_ref() else {
+            return Ok(BaseToolImpl::create_error_response(
+                "Git operations not available",
+                None,
+            ));
+        };
+
+        let report = ops
+            .for_issue(&issue)
+            .and_then(|ops| {
+                ops.sync_issue_branch(&issue.name, request.strategy.unwrap_or_default())
+            })
+            .map_err(|e| McpErrorHandler::handle_error(e, "sync issue branch"))?;
+
+        let response = match request.format.as_deref() {
+            Some("json") => serde_json::to_string_pretty(&report).map_err(|e| {
+                McpError::internal_error(format!("Failed to serialize sync report: {e}"), None)
+            })?,
+            _ => report.to_string(),
+        };
+
+        Ok(BaseToolImpl::create_success_response(&response))
+    }
+}
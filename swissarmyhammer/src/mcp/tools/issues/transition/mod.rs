@@ -0,0 +1,86 @@
+//! Issue state transition tool for MCP operations
+//!
+//! This module provides the TransitionIssueTool for moving issues between the
+//! configured issue states.
+
+use crate::config::Config;
+use crate::issues::states::{move_issue, IssueStateMachine};
+use crate::mcp::responses::create_success_response;
+use crate::mcp::shared_utils::{McpErrorHandler, McpValidation};
+use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
+use crate::mcp::types::TransitionIssueRequest;
+use async_trait::async_trait;
+use rmcp::model::CallToolResult;
+use rmcp::Error as McpError;
+
+/// Tool for moving issues to another state
+#[derive(Default)]
+pub struct TransitionIssueTool;
+
+impl TransitionIssueTool {
+    /// Creates a new instance of the TransitionIssueTool
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl McpTool for TransitionIssueTool {
+    fn name(&self) -> &'static str {
+        "issue_transition"
+    }
+
+    fn description(&self) -> &'static str {
+        crate::mcp::tool_descriptions::get_tool_description("issues", "transition")
+            .expect("Tool description should be available")
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "name": {
+                    "type": "string",
+                    "description": "Issue name to move"
+                },
+                "state": {
+                    "type": "string",
+                    "description": "State to move the issue to, such as open, complete, or a configured state"
+                }
+            },
+            "required": ["name", "state"]
+        })
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Map<String, serde_json::Value>,
+        context: &ToolContext,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let request: TransitionIssueRequest = BaseToolImpl::parse_arguments(arguments)?;
+
+        McpValidation::validate_not_empty(request.name.as_str(), "issue name")
+            .map_err(|e| McpErrorHandler::handle_error(e, "validate issue name"))?;
+        let state = request.state.trim();
+        McpValidation::validate_not_empty(state, "issue state")
+            .map_err(|e| McpErrorHandler::handle_error(e, "validate issue state"))?;
+
+        let machine = IssueStateMachine::from_config(Config::global())
+            .map_err(|e| McpErrorHandler::handle_error(e, "load issue states"))?;
+        let issue_storage = context.issue_storage.write().await;
+        match move_issue(
+            issue_storage.as_ref(),
+            &machine,
+            request.name.as_str(),
+            state,
+        )
+        .await
+        {
+            Ok(issue) => Ok(create_success_response(format!(
+                "Moved issue {} to {}",
+                issue.name, state
+            ))),
+            Err(e) => Err(McpErrorHandler::handle_error(e, "move issue")),
+        }
+    }
+}
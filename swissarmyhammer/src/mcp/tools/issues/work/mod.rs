@@ -64,7 +64,11 @@ impl McpTool for WorkIssueTool {
         let branch_name = issue.name.clone();
 
         match git_ops.as_mut() {
-            Some(ops) => match ops.create_work_branch(&branch_name) {
+            // The branch lives in the repository named by the issue's `repo:` key
+            Some(ops) => match ops
+                .for_issue(&issue)
+                .and_then(|ops| ops.create_work_branch(&branch_name))
+            {
                 Ok(branch_name) => Ok(create_success_response(format!(
                     "Switched to work branch: {branch_name}"
                 ))),
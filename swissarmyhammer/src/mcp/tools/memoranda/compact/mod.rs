@@ -0,0 +1,110 @@
+//! Memo compaction tool for MCP operations
+//!
+//! This module provides the CompactMemoTool for removing leftover files and
+//! duplicate memos from memo storage through the MCP protocol.
+
+use crate::mcp::memo_types::CompactMemosRequest;
+use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
+use async_trait::async_trait;
+use rmcp::model::CallToolResult;
+use rmcp::Error as McpError;
+
+/// Tool for compacting memo storage
+#[derive(Default)]
+pub struct CompactMemoTool;
+
+impl CompactMemoTool {
+    /// Creates a new instance of the CompactMemoTool
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl McpTool for CompactMemoTool {
+    fn name(&self) -> &'static str {
+        "memo_compact"
+    }
+
+    fn description(&self) -> &'static str {
+        crate::mcp::tool_descriptions::get_tool_description("memoranda", "compact")
+            .expect("Tool description should be available")
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "dry_run": {
+                    "type": "boolean",
+                    "description": "Only report what would be removed and rewritten",
+                    "default": false
+                }
+            },
+            "required": []
+        })
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Map<String, serde_json::Value>,
+        context: &ToolContext,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let request: CompactMemosRequest = BaseToolImpl::parse_arguments(arguments)?;
+
+        tracing::debug!("Compacting memos (dry run: {})", request.dry_run);
+
+        // Compaction removes and rewrites memo files, so hold the write lock
+        let memo_storage = context.memo_storage.write().await;
+        match memo_storage.compact(request.dry_run).await {
+            Ok(report) => {
+                tracing::info!(
+                    "Compacted {} memos, reclaimed {} bytes",
+                    report.memos,
+                    report.reclaimed_bytes
+                );
+                Ok(BaseToolImpl::create_success_response(report.to_string()))
+            }
+            Err(e) => Err(crate::mcp::shared_utils::McpErrorHandler::handle_error(
+                e,
+                "compact memos",
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_context;
+
+    #[test]
+    fn test_compact_memo_tool_new() {
+        let tool = CompactMemoTool::new();
+        assert_eq!(tool.name(), "memo_compact");
+        assert!(!tool.description().is_empty());
+    }
+
+    #[test]
+    fn test_compact_memo_tool_schema() {
+        let tool = CompactMemoTool::new();
+        let schema = tool.schema();
+
+        assert_eq!(schema["type"], "object");
+        assert!(schema["properties"]["dry_run"].is_object());
+        assert_eq!(schema["required"], serde_json::json!([]));
+    }
+
+    #[tokio::test]
+    async fn test_compact_memo_tool_execute() {
+        let tool = CompactMemoTool::new();
+        let context = create_test_context().await;
+
+        let mut arguments = serde_json::Map::new();
+        arguments.insert("dry_run".to_string(), serde_json::Value::Bool(true));
+
+        let result = tool.execute(arguments, &context).await;
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().is_error, Some(false));
+    }
+}
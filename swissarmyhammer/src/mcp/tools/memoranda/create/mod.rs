@@ -42,6 +42,10 @@ impl McpTool for CreateMemoTool {
                 "content": {
                     "type": "string",
                     "description": "Markdown content of the memo"
+                },
+                "notebook": {
+                    "type": "string",
+                    "description": "Notebook to file the memo in, created if it doesn't exist"
                 }
             },
             "required": ["title", "content"]
@@ -61,13 +65,18 @@ impl McpTool for CreateMemoTool {
 
         let memo_storage = context.memo_storage.write().await;
         match memo_storage
-            .create_memo(request.title, request.content)
+            .create_memo_in_notebook(request.title, request.content, request.notebook.as_deref())
             .await
         {
             Ok(memo) => {
                 tracing::info!("Created memo {}", memo.id);
+                let notebook = memo
+                    .notebook
+                    .as_ref()
+                    .map(|notebook| format!("\nNotebook: {notebook}"))
+                    .unwrap_or_default();
                 Ok(BaseToolImpl::create_success_response(format!(
-                    "Successfully created memo '{}' with ID: {}\n\nTitle: {}\nContent: {}",
+                    "Successfully created memo '{}' with ID: {}\n\nTitle: {}{notebook}\nContent: {}",
                     memo.title, memo.id, memo.title, memo.content
                 )))
             }
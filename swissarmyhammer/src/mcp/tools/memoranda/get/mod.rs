@@ -2,6 +2,7 @@
 //!
 //! This module provides the GetMemoTool for retrieving a memo by its unique ID through the MCP protocol.
 
+use crate::cross_references::{related_issues, RelatedIssues};
 use crate::mcp::memo_types::GetMemoRequest;
 use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
 use async_trait::async_trait;
@@ -17,6 +18,23 @@ impl GetMemoTool {
     pub fn new() -> Self {
         Self
     }
+
+    /// Lines listing the issues related to a memo, if any
+    fn format_related_issues(related: &RelatedIssues) -> String {
+        let mut lines = String::new();
+        if !related.issues.is_empty() {
+            let names: Vec<&str> = related
+                .issues
+                .iter()
+                .map(|issue| issue.name.as_str())
+                .collect();
+            lines.push_str(&format!("Related issues: {}\n", names.join(", ")));
+        }
+        if let Some(missing) = &related.missing {
+            lines.push_str(&format!("Missing issue: {missing}\n"));
+        }
+        lines
+    }
 }
 
 #[async_trait]
@@ -37,6 +55,12 @@ impl McpTool for GetMemoTool {
                 "id": {
                     "type": "string",
                     "description": "ULID identifier of the memo to retrieve"
+                },
+                "format": {
+                    "type": "string",
+                    "description": "Output format - table or json",
+                    "default": "table",
+                    "enum": ["table", "json"]
                 }
             },
             "required": ["id"]
@@ -66,12 +90,26 @@ impl McpTool for GetMemoTool {
         match memo_storage.get_memo(&memo_id).await {
             Ok(memo) => {
                 tracing::info!("Retrieved memo {}", memo.id);
+                if request.format.as_deref() == Some("json") {
+                    let json = serde_json::to_string_pretty(&memo).map_err(|e| {
+                        McpError::internal_error(format!("Failed to serialize memo: {e}"), None)
+                    })?;
+                    return Ok(BaseToolImpl::create_success_response(json));
+                }
+                let issue_storage = context.issue_storage.read().await;
+                let related = related_issues(&memo, issue_storage.as_ref())
+                    .await
+                    .unwrap_or_else(|e| {
+                        tracing::warn!("Failed to resolve issues of memo {}: {}", memo.id, e);
+                        RelatedIssues::default()
+                    });
                 Ok(BaseToolImpl::create_success_response(format!(
-                    "Memo found:\n\nID: {}\nTitle: {}\nCreated: {}\nUpdated: {}\n\nContent:\n{}",
+                    "Memo found:\n\nID: {}\nTitle: {}\nCreated: {}\nUpdated: {}\n{}\nContent:\n{}",
                     memo.id,
                     memo.title,
                     crate::mcp::shared_utils::McpFormatter::format_timestamp(memo.created_at),
                     crate::mcp::shared_utils::McpFormatter::format_timestamp(memo.updated_at),
+                    Self::format_related_issues(&related),
                     memo.content
                 )))
             }
@@ -131,6 +169,32 @@ mod tests {
         assert!(!call_result.content.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_get_memo_tool_execute_json_format() {
+        let tool = GetMemoTool::new();
+        let context = create_test_context().await;
+
+        let memo_storage = context.memo_storage.write().await;
+        let memo = memo_storage
+            .create_memo("Json Memo".to_string(), "Json content".to_string())
+            .await
+            .unwrap();
+        drop(memo_storage);
+
+        let mut arguments = serde_json::Map::new();
+        arguments.insert("id".to_string(), serde_json::json!(memo.id.to_string()));
+        arguments.insert("format".to_string(), serde_json::json!("json"));
+
+        let result = tool.execute(arguments, &context).await.unwrap();
+        let text = if let rmcp::model::RawContent::Text(text) = &result.content[0].raw {
+            &text.text
+        } else {
+            panic!("Expected text content");
+        };
+        let retrieved: crate::memoranda::Memo = serde_json::from_str(text).unwrap();
+        assert_eq!(retrieved, memo);
+    }
+
     #[tokio::test]
     async fn test_get_memo_tool_execute_invalid_id_format() {
         let tool = GetMemoTool::new();
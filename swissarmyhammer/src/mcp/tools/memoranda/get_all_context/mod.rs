@@ -3,7 +3,9 @@
 //! This module provides the GetAllContextMemoTool for retrieving all memo content formatted for AI context consumption.
 
 use crate::mcp::memo_types::GetAllContextRequest;
+use crate::mcp::shared_utils::McpFormatter;
 use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
+use crate::memoranda::MemoContext;
 use async_trait::async_trait;
 use rmcp::model::CallToolResult;
 use rmcp::Error as McpError;
@@ -33,7 +35,22 @@ impl McpTool for GetAllContextMemoTool {
     fn schema(&self) -> serde_json::Value {
         serde_json::json!({
             "type": "object",
-            "properties": {},
+            "properties": {
+                "query": {
+                    "type": "string",
+                    "description": "Only include memos whose title or content mention every word of the query"
+                },
+                "max_tokens": {
+                    "type": "integer",
+                    "description": "Approximate token budget; older memos are left out once it is spent",
+                    "minimum": 1
+                },
+                "include_metadata": {
+                    "type": "boolean",
+                    "description": "Include memo titles, IDs and dates",
+                    "default": true
+                }
+            },
             "required": []
         })
     }
@@ -43,49 +60,15 @@ impl McpTool for GetAllContextMemoTool {
         arguments: serde_json::Map<String, serde_json::Value>,
         context: &ToolContext,
     ) -> std::result::Result<CallToolResult, McpError> {
-        let _request: GetAllContextRequest = BaseToolImpl::parse_arguments(arguments)?;
+        let request: GetAllContextRequest = BaseToolImpl::parse_arguments(arguments)?;
 
-        tracing::debug!("Getting all memo context");
+        tracing::debug!("Getting memo context: {:?}", request);
 
         let memo_storage = context.memo_storage.read().await;
         match memo_storage.list_memos().await {
-            Ok(memos) => {
-                tracing::info!("Retrieved {} memos for context", memos.len());
-                if memos.is_empty() {
-                    Ok(BaseToolImpl::create_success_response(
-                        "No memos available".to_string(),
-                    ))
-                } else {
-                    // Sort memos by updated_at descending (most recent first)
-                    let mut sorted_memos = memos;
-                    sorted_memos.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
-
-                    let context = sorted_memos
-                        .iter()
-                        .map(|memo| {
-                            format!(
-                                "=== {} (ID: {}) ===\nCreated: {}\nUpdated: {}\n\n{}",
-                                memo.title,
-                                memo.id,
-                                crate::mcp::shared_utils::McpFormatter::format_timestamp(
-                                    memo.created_at
-                                ),
-                                crate::mcp::shared_utils::McpFormatter::format_timestamp(
-                                    memo.updated_at
-                                ),
-                                memo.content
-                            )
-                        })
-                        .collect::<Vec<_>>()
-                        .join(&format!("\n\n{}\n\n", "=".repeat(80)));
-
-                    let memo_count = sorted_memos.len();
-                    let plural_suffix = if memo_count == 1 { "" } else { "s" };
-                    Ok(BaseToolImpl::create_success_response(format!(
-                        "All memo context ({memo_count} memo{plural_suffix}):\n\n{context}"
-                    )))
-                }
-            }
+            Ok(memos) => Ok(BaseToolImpl::create_success_response(
+                format_context_response(memos, &request),
+            )),
             Err(e) => Err(crate::mcp::shared_utils::McpErrorHandler::handle_error(
                 e,
                 "get memo context",
@@ -94,11 +77,68 @@ impl McpTool for GetAllContextMemoTool {
     }
 }
 
+/// Build the context for a request and describe it for the caller
+pub(crate) fn format_context_response(
+    memos: Vec<crate::memoranda::Memo>,
+    request: &GetAllContextRequest,
+) -> String {
+    if memos.is_empty() {
+        return "No memos available".to_string();
+    }
+
+    let options = request.context_options();
+    let memo_context = MemoContext::build_with_header(memos, &options, |memo| {
+        format!(
+            "=== {} (ID: {}) ===\nCreated: {}\nUpdated: {}\n\n",
+            memo.title,
+            memo.id.as_str(),
+            McpFormatter::format_timestamp(memo.created_at),
+            McpFormatter::format_timestamp(memo.updated_at)
+        )
+    });
+    tracing::info!(
+        "Built memo context from {} of {} matching memos (~{} tokens)",
+        memo_context.included,
+        memo_context.matched,
+        memo_context.estimated_tokens()
+    );
+
+    if let (Some(query), 0) = (&options.query, memo_context.matched) {
+        return format!("No memos match '{query}'");
+    }
+
+    let memo_count = memo_context.included;
+    let plural_suffix = if memo_count == 1 { "" } else { "s" };
+    let mut response = match &options.query {
+        Some(query) => {
+            format!("Memo context matching '{query}' ({memo_count} memo{plural_suffix}):\n\n")
+        }
+        None => format!("All memo context ({memo_count} memo{plural_suffix}):\n\n"),
+    };
+    response.push_str(&memo_context.text);
+
+    if let (true, Some(max_tokens)) = (memo_context.truncated, options.max_tokens) {
+        let omitted = memo_context.omitted();
+        let plural_suffix = if omitted == 1 { "" } else { "s" };
+        response.push_str(&format!(
+            "\n\n[Context cut short at ~{max_tokens} tokens; {omitted} older memo{plural_suffix} left out]"
+        ));
+    }
+    response
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::test_utils::create_test_context;
 
+    fn response_text(result: &CallToolResult) -> String {
+        match &result.content[0].raw {
+            rmcp::model::RawContent::Text(text) => text.text.clone(),
+            _ => panic!("Expected text content"),
+        }
+    }
+
     #[test]
     fn test_get_all_context_memo_tool_new() {
         let tool = GetAllContextMemoTool::new();
@@ -112,7 +152,9 @@ mod tests {
         let schema = tool.schema();
 
         assert_eq!(schema["type"], "object");
-        assert_eq!(schema["properties"], serde_json::json!({}));
+        assert_eq!(schema["properties"]["query"]["type"], "string");
+        assert_eq!(schema["properties"]["max_tokens"]["type"], "integer");
+        assert_eq!(schema["properties"]["include_metadata"]["type"], "boolean");
         assert_eq!(schema["required"], serde_json::json!([]));
     }
 
@@ -216,6 +258,56 @@ mod tests {
         // Should use singular form "memo" not "memos" for single result
     }
 
+    #[tokio::test]
+    async fn test_get_all_context_memo_tool_query_and_budget() {
+        let tool = GetAllContextMemoTool::new();
+        let context = create_test_context().await;
+
+        let memo_storage = context.memo_storage.write().await;
+        memo_storage
+            .create_memo(
+                "Deploy".to_string(),
+                "Deploy staging on Fridays".to_string(),
+            )
+            .await
+            .unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+        memo_storage
+            .create_memo("Lunch".to_string(), "Tacos on Tuesday".to_string())
+            .await
+            .unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+        memo_storage
+            .create_memo(
+                "Release".to_string(),
+                "Tag after the staging deploy".to_string(),
+            )
+            .await
+            .unwrap();
+        drop(memo_storage);
+
+        let mut arguments = serde_json::Map::new();
+        arguments.insert("query".to_string(), serde_json::json!("staging deploy"));
+        let response = response_text(&tool.execute(arguments, &context).await.unwrap());
+        assert!(response.starts_with("Memo context matching 'staging deploy' (2 memos)"));
+        assert!(!response.contains("Tacos"));
+        assert!(response.find("Tag after").unwrap() < response.find("Deploy staging").unwrap());
+        assert!(response.contains("=== Release (ID: "));
+
+        let mut arguments = serde_json::Map::new();
+        arguments.insert("max_tokens".to_string(), serde_json::json!(4));
+        arguments.insert("include_metadata".to_string(), serde_json::json!(false));
+        let response = response_text(&tool.execute(arguments, &context).await.unwrap());
+        assert!(response.contains("Tag after the st"));
+        assert!(!response.contains("Tacos"));
+        assert!(response.ends_with("[Context cut short at ~4 tokens; 2 older memos left out]"));
+
+        let mut arguments = serde_json::Map::new();
+        arguments.insert("query".to_string(), serde_json::json!("kubernetes"));
+        let response = response_text(&tool.execute(arguments, &context).await.unwrap());
+        assert_eq!(response, "No memos match 'kubernetes'");
+    }
+
     #[tokio::test]
     async fn test_get_all_context_memo_tool_execute_with_invalid_arguments() {
         let tool = GetAllContextMemoTool::new();
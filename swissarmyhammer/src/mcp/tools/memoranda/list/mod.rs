@@ -36,7 +36,18 @@ impl McpTool for ListMemoTool {
     fn schema(&self) -> serde_json::Value {
         serde_json::json!({
             "type": "object",
-            "properties": {},
+            "properties": {
+                "format": {
+                    "type": "string",
+                    "description": "Output format - table or json",
+                    "default": "table",
+                    "enum": ["table", "json"]
+                },
+                "notebook": {
+                    "type": "string",
+                    "description": "Only list the memos filed in this notebook"
+                }
+            },
             "required": []
         })
     }
@@ -46,15 +57,24 @@ impl McpTool for ListMemoTool {
         arguments: serde_json::Map<String, serde_json::Value>,
         context: &ToolContext,
     ) -> std::result::Result<CallToolResult, McpError> {
-        let _request: ListMemosRequest = BaseToolImpl::parse_arguments(arguments)?;
+        let request: ListMemosRequest = BaseToolImpl::parse_arguments(arguments)?;
 
-        tracing::debug!("Listing all memos");
+        tracing::debug!("Listing memos in notebook {:?}", request.notebook);
 
         let memo_storage = context.memo_storage.read().await;
-        match memo_storage.list_memos().await {
+        let memos = match &request.notebook {
+            Some(notebook) => memo_storage.list_memos_in_notebook(notebook).await,
+            None => memo_storage.list_memos().await,
+        };
+        match memos {
             Ok(memos) => {
                 tracing::info!("Retrieved {} memos", memos.len());
-                if memos.is_empty() {
+                if request.format.as_deref() == Some("json") {
+                    let json = serde_json::to_string_pretty(&memos).map_err(|e| {
+                        McpError::internal_error(format!("Failed to serialize memos: {e}"), None)
+                    })?;
+                    Ok(BaseToolImpl::create_success_response(json))
+                } else if memos.is_empty() {
                     Ok(BaseToolImpl::create_success_response(
                         "No memos found".to_string(),
                     ))
@@ -106,7 +126,7 @@ mod tests {
         let schema = tool.schema();
 
         assert_eq!(schema["type"], "object");
-        assert_eq!(schema["properties"], serde_json::json!({}));
+        assert_eq!(schema["properties"]["format"]["default"], "table");
         assert_eq!(schema["required"], serde_json::json!([]));
     }
 
@@ -121,6 +141,7 @@ mod tests {
             content: "This is a long piece of content that should be truncated in the preview to show only the first part".to_string(),
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            notebook: None,
         };
 
         let preview = crate::mcp::shared_utils::McpFormatter::format_memo_preview(&memo, 50);
@@ -172,6 +193,65 @@ mod tests {
         assert!(!call_result.content.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_list_memo_tool_execute_json_format() {
+        let tool = ListMemoTool::new();
+        let context = create_test_context().await;
+
+        let memo_storage = context.memo_storage.write().await;
+        let memo = memo_storage
+            .create_memo("Json Memo".to_string(), "Json content".to_string())
+            .await
+            .unwrap();
+        drop(memo_storage);
+
+        let mut arguments = serde_json::Map::new();
+        arguments.insert("format".to_string(), serde_json::json!("json"));
+
+        let result = tool.execute(arguments, &context).await.unwrap();
+        let text = if let rmcp::model::RawContent::Text(text) = &result.content[0].raw {
+            &text.text
+        } else {
+            panic!("Expected text content");
+        };
+        let memos: Vec<crate::memoranda::Memo> = serde_json::from_str(text).unwrap();
+        assert_eq!(memos, vec![memo]);
+    }
+
+    #[tokio::test]
+    async fn test_list_memo_tool_execute_in_notebook() {
+        let tool = ListMemoTool::new();
+        let context = create_test_context().await;
+
+        let memo_storage = context.memo_storage.write().await;
+        let work = memo_storage
+            .create_memo_in_notebook(
+                "Work Memo".to_string(),
+                "Work content".to_string(),
+                Some("work"),
+            )
+            .await
+            .unwrap();
+        memo_storage
+            .create_memo("Home Memo".to_string(), "Home content".to_string())
+            .await
+            .unwrap();
+        drop(memo_storage);
+
+        let mut arguments = serde_json::Map::new();
+        arguments.insert("format".to_string(), serde_json::json!("json"));
+        arguments.insert("notebook".to_string(), serde_json::json!("work"));
+
+        let result = tool.execute(arguments, &context).await.unwrap();
+        let text = if let rmcp::model::RawContent::Text(text) = &result.content[0].raw {
+            &text.text
+        } else {
+            panic!("Expected text content");
+        };
+        let memos: Vec<crate::memoranda::Memo> = serde_json::from_str(text).unwrap();
+        assert_eq!(memos, vec![work]);
+    }
+
     #[tokio::test]
     async fn test_list_memo_tool_execute_with_invalid_arguments() {
         let tool = ListMemoTool::new();
@@ -30,6 +30,10 @@
 //! - **search**: Full-text search across titles and content
 //! - **get_all_context**: Retrieve all memo content for AI context consumption
 //!
+//! ### Maintenance
+//! - **verify**: Detect corrupted memo files and restore them from backup snapshots
+//! - **compact**: Remove leftover files and duplicate memos and rebuild the search index
+//!
 //! ## MCP Integration Patterns
 //!
 //! All memoranda tools follow consistent patterns:
@@ -45,6 +49,7 @@
 //! - ULID-based chronological access patterns
 //! - Full-text search indexing for efficient queries
 
+pub mod compact;
 pub mod create;
 pub mod delete;
 pub mod get;
@@ -52,6 +57,7 @@ pub mod get_all_context;
 pub mod list;
 pub mod search;
 pub mod update;
+pub mod verify;
 
 use crate::mcp::tool_registry::ToolRegistry;
 
@@ -64,4 +70,6 @@ pub fn register_memoranda_tools(registry: &mut ToolRegistry) {
     registry.register(update::UpdateMemoTool::new());
     registry.register(delete::DeleteMemoTool::new());
     registry.register(search::SearchMemoTool::new());
+    registry.register(verify::VerifyMemoTool::new());
+    registry.register(compact::CompactMemoTool::new());
 }
@@ -40,6 +40,12 @@ impl McpTool for SearchMemoTool {
                 "query": {
                     "type": "string",
                     "description": "Search query string to match against memo titles and content"
+                },
+                "format": {
+                    "type": "string",
+                    "description": "Output format - table or json",
+                    "default": "table",
+                    "enum": ["table", "json"]
                 }
             },
             "required": ["query"]
@@ -65,7 +71,12 @@ impl McpTool for SearchMemoTool {
         match memo_storage.search_memos(&request.query).await {
             Ok(memos) => {
                 tracing::info!("Search returned {} memos", memos.len());
-                if memos.is_empty() {
+                if request.format.as_deref() == Some("json") {
+                    let json = serde_json::to_string_pretty(&memos).map_err(|e| {
+                        McpError::internal_error(format!("Failed to serialize memos: {e}"), None)
+                    })?;
+                    Ok(BaseToolImpl::create_success_response(json))
+                } else if memos.is_empty() {
                     Ok(BaseToolImpl::create_success_response(format!(
                         "No memos found matching query: '{}'",
                         request.query
@@ -132,6 +143,7 @@ mod tests {
             content: "This is a long piece of content that should be truncated in the preview to show only the first part".to_string(),
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            notebook: None,
         };
 
         let preview = crate::mcp::shared_utils::McpFormatter::format_memo_preview(&memo, 50);
@@ -0,0 +1,123 @@
+//! Memo verification tool for MCP operations
+//!
+//! This module provides the VerifyMemoTool for detecting corrupted memo files
+//! and restoring them from backup snapshots through the MCP protocol.
+
+use crate::mcp::memo_types::VerifyMemosRequest;
+use crate::mcp::responses::create_error_response;
+use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
+use async_trait::async_trait;
+use rmcp::model::CallToolResult;
+use rmcp::Error as McpError;
+
+/// Tool for verifying the integrity of stored memos
+#[derive(Default)]
+pub struct VerifyMemoTool;
+
+impl VerifyMemoTool {
+    /// Creates a new instance of the VerifyMemoTool
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl McpTool for VerifyMemoTool {
+    fn name(&self) -> &'static str {
+        "memo_verify"
+    }
+
+    fn description(&self) -> &'static str {
+        crate::mcp::tool_descriptions::get_tool_description("memoranda", "verify")
+            .expect("Tool description should be available")
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "restore": {
+                    "type": "boolean",
+                    "description": "Restore corrupted memos from their most recent backup snapshot",
+                    "default": false
+                }
+            },
+            "required": []
+        })
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Map<String, serde_json::Value>,
+        context: &ToolContext,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let request: VerifyMemosRequest = BaseToolImpl::parse_arguments(arguments)?;
+
+        tracing::debug!("Verifying memos (restore: {})", request.restore);
+
+        // Restoring rewrites memo files, so hold the write lock
+        let memo_storage = context.memo_storage.write().await;
+        match memo_storage.verify_integrity(request.restore).await {
+            Ok(report) => {
+                tracing::info!("Verified {} memos", report.entries.len());
+                if report.is_healthy() {
+                    Ok(BaseToolImpl::create_success_response(report.to_string()))
+                } else {
+                    Ok(create_error_response(report.to_string()))
+                }
+            }
+            Err(e) => Err(crate::mcp::shared_utils::McpErrorHandler::handle_error(
+                e,
+                "verify memos",
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_context;
+
+    #[test]
+    fn test_verify_memo_tool_new() {
+        let tool = VerifyMemoTool::new();
+        assert_eq!(tool.name(), "memo_verify");
+        assert!(!tool.description().is_empty());
+    }
+
+    #[test]
+    fn test_verify_memo_tool_schema() {
+        let tool = VerifyMemoTool::new();
+        let schema = tool.schema();
+
+        assert_eq!(schema["type"], "object");
+        assert!(schema["properties"]["restore"].is_object());
+        assert_eq!(schema["required"], serde_json::json!([]));
+    }
+
+    #[tokio::test]
+    async fn test_verify_memo_tool_execute() {
+        let tool = VerifyMemoTool::new();
+        let context = create_test_context().await;
+
+        let result = tool.execute(serde_json::Map::new(), &context).await;
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().is_error, Some(false));
+    }
+
+    #[tokio::test]
+    async fn test_verify_memo_tool_execute_invalid_argument_type() {
+        let tool = VerifyMemoTool::new();
+        let context = create_test_context().await;
+
+        let mut arguments = serde_json::Map::new();
+        arguments.insert(
+            "restore".to_string(),
+            serde_json::Value::String("yes".to_string()),
+        );
+
+        let result = tool.execute(arguments, &context).await;
+        assert!(result.is_err());
+    }
+}
@@ -38,3 +38,5 @@
 pub mod issues;
 pub mod memoranda;
 pub mod search;
+pub mod server;
+pub mod workflows;
@@ -0,0 +1,52 @@
+//! Health tool for MCP operations
+//!
+//! This module provides the HealthTool for checking a running server without
+//! restarting it.
+
+use crate::mcp::tool_registry::{McpTool, ToolContext};
+use async_trait::async_trait;
+use rmcp::model::CallToolResult;
+use rmcp::Error as McpError;
+
+/// Tool for reporting the health of the server
+#[derive(Default)]
+pub struct HealthTool;
+
+impl HealthTool {
+    /// Creates a new instance of the HealthTool
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl McpTool for HealthTool {
+    fn name(&self) -> &'static str {
+        "health"
+    }
+
+    fn description(&self) -> &'static str {
+        crate::mcp::tool_descriptions::get_tool_description("server", "health")
+            .expect("Tool description should be available")
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {},
+            "required": []
+        })
+    }
+
+    async fn execute(
+        &self,
+        _arguments: serde_json::Map<String, serde_json::Value>,
+        context: &ToolContext,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let report = context.health.report();
+        let json = serde_json::to_string_pretty(&report).map_err(|e| {
+            McpError::internal_error(format!("Failed to serialize health: {e}"), None)
+        })?;
+        Ok(crate::mcp::responses::create_success_response(json))
+    }
+}
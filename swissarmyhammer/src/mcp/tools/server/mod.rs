@@ -0,0 +1,41 @@
+//! Server tools for MCP operations
+//!
+//! This module provides tools that report on the MCP server itself.
+
+pub mod health;
+
+use crate::mcp::tool_registry::ToolRegistry;
+
+/// Register all server tools with the registry
+pub fn register_server_tools(registry: &mut ToolRegistry) {
+    registry.register(health::HealthTool::new());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_context;
+
+    #[tokio::test]
+    async fn test_health_tool_reports_server_health() {
+        let mut registry = ToolRegistry::new();
+        register_server_tools(&mut registry);
+        let tool = registry.get_tool("health").unwrap();
+        assert!(tool.description().contains("uptime"));
+
+        let context = create_test_context().await;
+        context.health.set_prompts(4);
+        let result = tool
+            .execute(serde_json::Map::new(), &context)
+            .await
+            .unwrap();
+        let text = match &result.content[0].raw {
+            rmcp::model::RawContent::Text(text) => text.text.clone(),
+            _ => panic!("expected text content"),
+        };
+        let report: serde_json::Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(report["prompts"], 4);
+        assert_eq!(report["file_watcher"], false);
+        assert_eq!(report["status"], "warning");
+    }
+}
@@ -0,0 +1,35 @@
+//! Workflow tools for MCP operations
+//!
+//! This module provides tools that run workflows inside the MCP server. Prompt
+//! actions in these runs can use `backend="sampling"` to have the connected
+//! client's model generate their responses instead of the local Claude CLI.
+//! Runs report their progress as they go, which `workflow_status` reads.
+
+pub mod run;
+pub mod status;
+
+use crate::mcp::tool_registry::ToolRegistry;
+
+/// Register all workflow-related tools with the registry
+pub fn register_workflow_tools(registry: &mut ToolRegistry) {
+    registry.register(run::RunWorkflowTool::new());
+    registry.register(status::WorkflowStatusTool::new());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register_workflow_tools() {
+        let mut registry = ToolRegistry::new();
+        register_workflow_tools(&mut registry);
+
+        let tool = registry.get_tool("workflow_run").unwrap();
+        assert!(tool.description().contains("sampling"));
+        assert_eq!(tool.schema()["required"][0], "name");
+
+        let tool = registry.get_tool("workflow_status").unwrap();
+        assert!(tool.description().contains("run dashboard"));
+    }
+}
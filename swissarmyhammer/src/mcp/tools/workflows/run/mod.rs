@@ -0,0 +1,114 @@
+//! Workflow run tool for MCP operations
+//!
+//! This module provides the RunWorkflowTool for running a workflow to
+//! completion inside the MCP server.
+
+use crate::mcp::responses::create_success_response;
+use crate::mcp::shared_utils::{McpErrorHandler, McpValidation};
+use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
+use crate::mcp::types::RunWorkflowRequest;
+use crate::workflow::{
+    HookDetails, RunProgressReporter, WorkflowExecutor, WorkflowHookEvent, WorkflowName,
+    WorkflowStorage,
+};
+use crate::SwissArmyHammerError;
+use async_trait::async_trait;
+use rmcp::model::CallToolResult;
+use rmcp::Error as McpError;
+use serde_json::Value;
+use std::sync::Arc;
+
+/// Tool for running a workflow in the server
+#[derive(Default)]
+pub struct RunWorkflowTool;
+
+impl RunWorkflowTool {
+    /// Creates a new instance of the RunWorkflowTool
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl McpTool for RunWorkflowTool {
+    fn name(&self) -> &'static str {
+        "workflow_run"
+    }
+
+    fn description(&self) -> &'static str {
+        crate::mcp::tool_descriptions::get_tool_description("workflows", "run")
+            .expect("Tool description should be available")
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "name": {
+                    "type": "string",
+                    "description": "Name of the workflow to run"
+                },
+                "vars": {
+                    "type": "object",
+                    "description": "Initial workflow variables",
+                    "additionalProperties": { "type": "string" }
+                }
+            },
+            "required": ["name"]
+        })
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Map<String, serde_json::Value>,
+        _context: &ToolContext,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let request: RunWorkflowRequest = BaseToolImpl::parse_arguments(arguments)?;
+        McpValidation::validate_not_empty(&request.name, "workflow name")
+            .map_err(|e| McpErrorHandler::handle_error(e, "validate workflow name"))?;
+
+        let mut storage = WorkflowStorage::file_system()
+            .map_err(|e| McpErrorHandler::handle_error(e, "open workflow storage"))?;
+        let workflow = storage
+            .get_workflow(&WorkflowName::new(&request.name))
+            .map_err(|e| McpErrorHandler::handle_error(e, "load workflow"))?;
+
+        let mut executor = WorkflowExecutor::new();
+        let reporter = Arc::new(RunProgressReporter::default());
+        executor.add_progress(reporter.clone());
+        let mut run = executor.start_workflow(workflow).map_err(|e| {
+            McpErrorHandler::handle_error(
+                SwissArmyHammerError::Other(e.to_string()),
+                "start workflow",
+            )
+        })?;
+        for (key, value) in request.vars {
+            run.context.insert(key, Value::String(value));
+        }
+        // Standard output carries the MCP protocol, so actions only log
+        run.context.insert("_quiet".to_string(), Value::Bool(true));
+
+        executor
+            .run_hook(&run, WorkflowHookEvent::Start, HookDetails::default())
+            .await;
+        let result = executor.execute_state(&mut run).await;
+        if result.is_err() {
+            run.fail();
+        }
+        storage
+            .store_run(&run)
+            .map_err(|e| McpErrorHandler::handle_error(e, "store workflow run"))?;
+        reporter.finish(&run);
+
+        let mut message = format!(
+            "Workflow '{}' run {} ended {:?}",
+            request.name, run.id, run.status
+        );
+        if let Err(e) = result {
+            message.push_str(&format!(": {e}"));
+        } else if let Some(Value::String(output)) = run.context.get("result") {
+            message.push_str(&format!("\n\n{output}"));
+        }
+        Ok(create_success_response(message))
+    }
+}
@@ -0,0 +1,97 @@
+//! Workflow status tool for MCP operations
+//!
+//! This module provides the WorkflowStatusTool for polling the progress of
+//! workflow runs while they execute.
+
+use crate::mcp::shared_utils::McpErrorHandler;
+use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
+use crate::mcp::types::WorkflowStatusRequest;
+use crate::workflow::{RunProgress, RunProgressBoard, WorkflowRunId, WorkflowStorage};
+use async_trait::async_trait;
+use rmcp::model::CallToolResult;
+use rmcp::Error as McpError;
+
+/// Tool for reporting the progress of workflow runs
+#[derive(Default)]
+pub struct WorkflowStatusTool;
+
+impl WorkflowStatusTool {
+    /// Creates a new instance of the WorkflowStatusTool
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Progress of one run, from its snapshot or, for a run that stopped before
+/// snapshots were kept, from workflow run storage
+fn run_progress(
+    board: &RunProgressBoard,
+    run_id: &WorkflowRunId,
+) -> std::result::Result<RunProgress, McpError> {
+    if let Some(progress) = board
+        .get(run_id)
+        .map_err(|e| McpErrorHandler::handle_error(e, "read run progress"))?
+    {
+        return Ok(progress);
+    }
+
+    let run = WorkflowStorage::file_system()
+        .and_then(|storage| storage.get_run(run_id))
+        .map_err(|_| McpError::invalid_params(format!("Workflow run not found: {run_id}"), None))?;
+    let mut progress = RunProgress::of(&run);
+    progress.updated_at = run.completed_at.unwrap_or(run.started_at);
+    Ok(progress)
+}
+
+#[async_trait]
+impl McpTool for WorkflowStatusTool {
+    fn name(&self) -> &'static str {
+        "workflow_status"
+    }
+
+    fn description(&self) -> &'static str {
+        crate::mcp::tool_descriptions::get_tool_description("workflows", "status")
+            .expect("Tool description should be available")
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "run_id": {
+                    "type": "string",
+                    "description": "ID of the run to report on, every run when omitted"
+                }
+            },
+            "required": []
+        })
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Map<String, serde_json::Value>,
+        _context: &ToolContext,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let request: WorkflowStatusRequest = BaseToolImpl::parse_arguments(arguments)?;
+        let board = RunProgressBoard::default();
+
+        let response = match request.run_id.as_deref() {
+            Some(run_id) => {
+                let run_id = WorkflowRunId::parse(run_id.trim())
+                    .map_err(|e| McpError::invalid_params(e, None))?;
+                serde_json::to_string_pretty(&run_progress(&board, &run_id)?)
+            }
+            None => {
+                let runs = board
+                    .list()
+                    .map_err(|e| McpErrorHandler::handle_error(e, "list run progress"))?;
+                serde_json::to_string_pretty(&runs)
+            }
+        }
+        .map_err(|e| {
+            McpError::internal_error(format!("Failed to serialize run progress: {e}"), None)
+        })?;
+
+        Ok(BaseToolImpl::create_success_response(&response))
+    }
+}
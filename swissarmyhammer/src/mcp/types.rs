@@ -118,6 +118,24 @@ pub struct GetPromptRequest {
     pub arguments: HashMap<String, String>,
 }
 
+/// Request to run a workflow in the server
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct RunWorkflowRequest {
+    /// Name of the workflow to run
+    pub name: String,
+    /// Initial workflow variables
+    #[serde(default)]
+    pub vars: HashMap<String, String>,
+}
+
+/// Request to report the progress of workflow runs
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct WorkflowStatusRequest {
+    /// ID of the run to report on, every run when omitted
+    #[serde(default)]
+    pub run_id: Option<String>,
+}
+
 /// Request structure for listing prompts
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
 pub struct ListPromptsRequest {
@@ -161,6 +179,44 @@ pub struct MarkCompleteRequest {
     pub name: IssueName,
 }
 
+/// Request to reopen a completed issue
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct ReopenIssueRequest {
+    /// Issue name to reopen
+    pub name: IssueName,
+}
+
+/// Request to move an issue to another state
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct TransitionIssueRequest {
+    /// Issue name to move
+    pub name: IssueName,
+    /// State to move the issue to
+    pub state: String,
+}
+
+/// Request to assign an issue to someone
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct AssignIssueRequest {
+    /// Issue name to assign
+    pub name: IssueName,
+    /// Who to assign the issue to, `@me` for the git config identity, or
+    /// none to unassign it
+    pub assignee: Option<String>,
+}
+
+/// Request to read or tick the checklist of an issue
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct CheckIssueRequest {
+    /// Issue name whose checklist to read or change
+    pub name: IssueName,
+    /// Task to tick, by its number or by words of its text, or none to only
+    /// report the checklist
+    pub item: Option<String>,
+    /// Whether the task is done, or none to toggle it
+    pub done: Option<bool>,
+}
+
 /// Request to check if all issues are complete
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
 pub struct AllCompleteRequest {
@@ -206,7 +262,10 @@ pub struct MergeIssueRequest {
 /// Request to get the next issue to work on
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
 pub struct NextIssueRequest {
-    // No parameters needed - returns first pending issue alphabetically
+    /// Order used to pick the issue: name, priority, estimate or created
+    /// (default: priority)
+    #[serde(default)]
+    pub sort: Option<String>,
 }
 
 #[cfg(test)]
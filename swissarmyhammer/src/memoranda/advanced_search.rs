@@ -282,6 +282,51 @@ impl AdvancedMemoSearchEngine {
         Ok(())
     }
 
+    /// Replace the whole index with the given memos
+    ///
+    /// Unlike [`index_memos`](Self::index_memos), this drops index entries
+    /// left behind for memos that no longer exist.
+    ///
+    /// # Arguments
+    ///
+    /// * `memos` - Every memo the index should contain
+    ///
+    /// # Returns
+    ///
+    /// * `Result<()>` - Success or error if rebuilding fails
+    pub async fn rebuild_index(&self, memos: &[Memo]) -> Result<()> {
+        {
+            let mut writer = self.writer.write().await;
+            writer
+                .delete_all_documents()
+                .map_err(|e| Self::map_tantivy_error("Failed to clear index", e))?;
+
+            for memo in memos {
+                let doc = doc!(
+                    self.title_field => memo.title.clone(),
+                    self.content_field => memo.content.clone(),
+                    self.id_field => memo.id.as_str(),
+                    self.created_at_field => memo.created_at.to_rfc3339(),
+                    self.updated_at_field => memo.updated_at.to_rfc3339(),
+                );
+                writer
+                    .add_document(doc)
+                    .map_err(|e| Self::map_tantivy_error("Failed to add document", e))?;
+            }
+
+            writer
+                .commit()
+                .map_err(|e| Self::map_commit_error("rebuild", e))?;
+        }
+
+        self.reader
+            .reload()
+            .map_err(|e| Self::map_reload_error("after rebuild", e))?;
+
+        info!("Rebuilt index with {} memos", memos.len());
+        Ok(())
+    }
+
     /// Remove a memo from the search index
     ///
     /// # Arguments
@@ -749,6 +794,22 @@ mod tests {
         assert_eq!(results_after.len(), 1);
     }
 
+    #[tokio::test]
+    async fn test_rebuild_index_drops_stale_entries() {
+        let engine = AdvancedMemoSearchEngine::new_in_memory().await.unwrap();
+        let memos = create_test_memos();
+        engine.index_memos(&memos).await.unwrap();
+        assert_eq!(engine.reader.searcher().num_docs(), 3);
+
+        engine.rebuild_index(&memos[1..]).await.unwrap();
+        assert_eq!(engine.reader.searcher().num_docs(), 2);
+
+        let options = SearchOptions::default();
+        let results = engine.search("rust", &options, &memos).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].memo.title, "Project Meeting");
+    }
+
     #[tokio::test]
     async fn test_empty_and_invalid_queries() {
         let engine = AdvancedMemoSearchEngine::new_in_memory().await.unwrap();
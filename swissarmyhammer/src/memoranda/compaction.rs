@@ -0,0 +1,124 @@
+//! Compaction of stored memos
+//!
+//! Long-lived memo stores accumulate cruft: temporary files left behind by
+//! interrupted writes, backup snapshots of memos that no longer exist, and
+//! duplicate memos created by retried requests. Compaction removes them,
+//! rewrites the remaining memo files in canonical form and rebuilds the
+//! search index.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::PathBuf;
+
+/// A memo removed because another memo has the same title and content
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoDuplicate {
+    /// ID of the removed memo
+    pub id: String,
+    /// ID of the older memo that was kept
+    pub duplicate_of: String,
+}
+
+/// Result of compacting memo storage
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoCompactionReport {
+    /// Whether this report describes changes without making them
+    pub dry_run: bool,
+    /// Number of memos kept
+    pub memos: usize,
+    /// Temporary files and orphaned backups removed
+    pub removed_files: Vec<PathBuf>,
+    /// Duplicate memos removed
+    pub duplicates: Vec<MemoDuplicate>,
+    /// IDs of memos whose files were rewritten in canonical form
+    pub rewritten: Vec<String>,
+    /// Whether the search index was rebuilt
+    pub index_rebuilt: bool,
+    /// Bytes of disk space freed
+    pub reclaimed_bytes: u64,
+}
+
+impl MemoCompactionReport {
+    /// Whether compaction found nothing to change
+    pub fn is_clean(&self) -> bool {
+        self.removed_files.is_empty() && self.duplicates.is_empty() && self.rewritten.is_empty()
+    }
+}
+
+impl fmt::Display for MemoCompactionReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let verb = if self.dry_run {
+            "Would remove"
+        } else {
+            "Removed"
+        };
+        write!(
+            f,
+            "Compacted {} memos: {} leftover files, {} duplicate memos, {} rewritten, {} bytes reclaimed",
+            self.memos,
+            self.removed_files.len(),
+            self.duplicates.len(),
+            self.rewritten.len(),
+            self.reclaimed_bytes
+        )?;
+
+        for path in &self.removed_files {
+            write!(f, "\n✗ {verb} {}", path.display())?;
+        }
+        for duplicate in &self.duplicates {
+            write!(
+                f,
+                "\n✗ {verb} {} (duplicate of {})",
+                duplicate.id, duplicate.duplicate_of
+            )?;
+        }
+        for id in &self.rewritten {
+            let verb = if self.dry_run {
+                "Would rewrite"
+            } else {
+                "Rewrote"
+            };
+            write!(f, "\n✓ {verb} {id}")?;
+        }
+        if self.index_rebuilt {
+            write!(f, "\n✓ Rebuilt search index")?;
+        }
+
+        if self.dry_run && !self.is_clean() {
+            write!(f, "\n\nRun without dry run to apply these changes")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_summary() {
+        let mut report = MemoCompactionReport {
+            dry_run: true,
+            memos: 2,
+            removed_files: vec![PathBuf::from("A.json.tmp")],
+            duplicates: vec![MemoDuplicate {
+                id: "C".to_string(),
+                duplicate_of: "B".to_string(),
+            }],
+            rewritten: Vec::new(),
+            index_rebuilt: false,
+            reclaimed_bytes: 120,
+        };
+        assert!(!report.is_clean());
+        let summary = report.to_string();
+        assert!(summary.starts_with(
+            "Compacted 2 memos: 1 leftover files, 1 duplicate memos, 0 rewritten, 120 bytes reclaimed"
+        ));
+        assert!(summary.contains("Would remove C (duplicate of B)"));
+        assert!(summary.contains("without dry run"));
+
+        report = MemoCompactionReport::default();
+        assert!(report.is_clean());
+        assert!(!report.to_string().contains('\n'));
+    }
+}
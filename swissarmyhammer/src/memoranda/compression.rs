@@ -0,0 +1,103 @@
+//! Compression of large memo bodies in JSON memo storage
+//!
+//! Some memos hold megabytes of pasted logs. JSON memo storage writes the body
+//! of a memo larger than a threshold zstd compressed and base64 encoded in a
+//! `content_zstd` field in place of `content`, so the title, timestamps and
+//! notebook stay readable in the file. Bodies are decompressed when the memo is
+//! read, and memo files holding a plain `content` field load unchanged.
+
+use crate::error::{Result, SwissArmyHammerError};
+use crate::memoranda::Memo;
+use base64::{engine::general_purpose, Engine as _};
+
+/// Field holding a compressed memo body
+pub const COMPRESSED_CONTENT_FIELD: &str = "content_zstd";
+
+/// zstd level memo bodies are compressed with
+const COMPRESSION_LEVEL: i32 = 3;
+
+/// Serialize a memo as the JSON written to its memo file
+///
+/// The body is compressed when it is longer than `threshold` bytes and
+/// compressing it makes it smaller. A `threshold` of 0 never compresses.
+pub fn encode_memo(memo: &Memo, threshold: usize) -> Result<String> {
+    if threshold == 0 || memo.content.len() <= threshold {
+        return Ok(serde_json::to_string_pretty(memo)?);
+    }
+
+    let compressed = zstd::encode_all(memo.content.as_bytes(), COMPRESSION_LEVEL)?;
+    let encoded = general_purpose::STANDARD.encode(compressed);
+    if encoded.len() >= memo.content.len() {
+        return Ok(serde_json::to_string_pretty(memo)?);
+    }
+
+    let mut value = serde_json::to_value(memo)?;
+    if let Some(fields) = value.as_object_mut() {
+        fields.remove("content");
+        fields.insert(
+            COMPRESSED_CONTENT_FIELD.to_string(),
+            serde_json::Value::String(encoded),
+        );
+    }
+    Ok(serde_json::to_string_pretty(&value)?)
+}
+
+/// Parse memo file contents, decompressing the body if it was compressed
+pub fn decode_memo(contents: &[u8]) -> Result<Memo> {
+    let mut value: serde_json::Value = serde_json::from_slice(contents)?;
+    if let Some(fields) = value.as_object_mut() {
+        if let Some(encoded) = fields.remove(COMPRESSED_CONTENT_FIELD) {
+            let content = decompress_content(&encoded)?;
+            fields.insert("content".to_string(), serde_json::Value::String(content));
+        }
+    }
+    Ok(serde_json::from_value(value)?)
+}
+
+/// Decode and decompress the value of a `content_zstd` field
+fn decompress_content(encoded: &serde_json::Value) -> Result<String> {
+    let encoded = encoded.as_str().ok_or_else(|| {
+        SwissArmyHammerError::Other(format!("{COMPRESSED_CONTENT_FIELD} must be a string"))
+    })?;
+    let compressed = general_purpose::STANDARD
+        .decode(encoded)
+        .map_err(|e| SwissArmyHammerError::Other(format!("invalid compressed memo body: {e}")))?;
+    let content = zstd::decode_all(compressed.as_slice())?;
+    String::from_utf8(content)
+        .map_err(|e| SwissArmyHammerError::Other(format!("compressed memo body is not UTF-8: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_large_memo_round_trips_compressed() {
+        let memo = Memo::new("Build log".to_string(), "error: retrying\n".repeat(10_000));
+
+        let encoded = encode_memo(&memo, 1024).unwrap();
+        assert!(encoded.contains(COMPRESSED_CONTENT_FIELD));
+        assert!(!encoded.contains("\"content\""));
+        assert!(encoded.len() < memo.content.len() / 10);
+        assert!(encoded.contains("Build log"));
+
+        let decoded = decode_memo(encoded.as_bytes()).unwrap();
+        assert_eq!(decoded, memo);
+    }
+
+    #[test]
+    fn test_small_and_plain_memos_are_not_compressed() {
+        let memo = Memo::new("Note".to_string(), "short body".to_string());
+
+        let encoded = encode_memo(&memo, 1024).unwrap();
+        assert_eq!(encoded, serde_json::to_string_pretty(&memo).unwrap());
+        assert_eq!(decode_memo(encoded.as_bytes()).unwrap(), memo);
+
+        let large = Memo::new("Log".to_string(), "x".repeat(4096));
+        let encoded = encode_memo(&large, 0).unwrap();
+        assert!(!encoded.contains(COMPRESSED_CONTENT_FIELD));
+
+        let corrupted = br#"{"id":"01ARZ3NDEKTSV4RRFFQ69G5FAV","title":"t","content_zstd":"not base64!","created_at":"2024-01-01T00:00:00Z","updated_at":"2024-01-01T00:00:00Z"}"#;
+        assert!(decode_memo(corrupted).is_err());
+    }
+}
@@ -0,0 +1,207 @@
+//! Memo context for AI consumption
+//!
+//! Builds the text handed to an assistant from the stored memos according to
+//! [`ContextOptions`]: memos are filtered by the query, ordered by last update,
+//! most recent first, and concatenated until the token budget is spent. The
+//! memo that crosses the budget is cut short and staler memos are left out, so
+//! the freshest knowledge always makes it in.
+
+use crate::memoranda::{ContextOptions, Memo};
+
+/// Characters counted as one token when estimating context size
+pub const CHARS_PER_TOKEN: usize = 4;
+
+/// Memo context built from a set of memos
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemoContext {
+    /// The concatenated memos
+    pub text: String,
+    /// Memos matching the query, or all memos without one
+    pub matched: usize,
+    /// Matching memos included, in full or cut short
+    pub included: usize,
+    /// Whether the token budget cut the context short
+    pub truncated: bool,
+}
+
+impl MemoContext {
+    /// Build the context for `memos`, introducing each memo with a
+    /// `# title (id)` heading and its dates when metadata is included
+    pub fn build(memos: Vec<Memo>, options: &ContextOptions) -> Self {
+        Self::build_with_header(memos, options, |memo| {
+            format!(
+                "# {} ({})\nCreated: {} | Updated: {}\n\n",
+                memo.title,
+                memo.id.as_str(),
+                memo.created_at.format("%Y-%m-%d %H:%M:%S UTC"),
+                memo.updated_at.format("%Y-%m-%d %H:%M:%S UTC")
+            )
+        })
+    }
+
+    /// Build the context for `memos`, introducing each memo with `header`
+    /// when metadata is included
+    pub fn build_with_header(
+        memos: Vec<Memo>,
+        options: &ContextOptions,
+        header: impl Fn(&Memo) -> String,
+    ) -> Self {
+        let terms = query_terms(options.query.as_deref());
+        let mut memos: Vec<Memo> = memos
+            .into_iter()
+            .filter(|memo| is_relevant(memo, &terms))
+            .collect();
+        memos.sort_by_key(|memo| std::cmp::Reverse(memo.updated_at));
+
+        let budget = options
+            .max_tokens
+            .map(|tokens| tokens.saturating_mul(CHARS_PER_TOKEN));
+        let mut context = Self {
+            matched: memos.len(),
+            ..Self::default()
+        };
+
+        for memo in &memos {
+            let mut section = String::new();
+            if context.included > 0 {
+                section.push_str(&options.delimiter);
+            }
+            let separator_len = section.len();
+            if options.include_metadata {
+                section.push_str(&header(memo));
+            }
+            section.push_str(&memo.content);
+
+            if let Some(budget) = budget {
+                let remaining = budget.saturating_sub(context.text.len());
+                if section.len() > remaining {
+                    context.truncated = true;
+                    let mut end = remaining;
+                    while !section.is_char_boundary(end) {
+                        end -= 1;
+                    }
+                    if end > separator_len {
+                        context.text.push_str(&section[..end]);
+                        context.included += 1;
+                    }
+                    break;
+                }
+            }
+
+            context.text.push_str(&section);
+            context.included += 1;
+        }
+
+        context
+    }
+
+    /// Estimated number of tokens in the context
+    pub fn estimated_tokens(&self) -> usize {
+        (self.text.len() + CHARS_PER_TOKEN - 1) / CHARS_PER_TOKEN
+    }
+
+    /// Matching memos left out to stay within the token budget
+    pub fn omitted(&self) -> usize {
+        self.matched - self.included
+    }
+}
+
+/// Lowercased words of a query, empty without one
+fn query_terms(query: Option<&str>) -> Vec<String> {
+    query
+        .map(|query| query.split_whitespace().map(str::to_lowercase).collect())
+        .unwrap_or_default()
+}
+
+/// Whether a memo's title or content mentions every query term
+fn is_relevant(memo: &Memo, terms: &[String]) -> bool {
+    if terms.is_empty() {
+        return true;
+    }
+    let title = memo.title.to_lowercase();
+    let content = memo.content.to_lowercase();
+    terms
+        .iter()
+        .all(|term| title.contains(term.as_str()) || content.contains(term.as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+
+    fn memo(title: &str, content: &str, hours_ago: i64) -> Memo {
+        let mut memo = Memo::new(title.to_string(), content.to_string());
+        memo.created_at = Utc::now() - Duration::hours(hours_ago);
+        memo.updated_at = memo.created_at;
+        memo
+    }
+
+    fn memos() -> Vec<Memo> {
+        vec![
+            memo("Deploy notes", "Staging deploys run on Fridays", 30),
+            memo("Release", "Tag the release after the staging deploy", 2),
+            memo("Lunch", "Tacos on Tuesday", 1),
+        ]
+    }
+
+    #[test]
+    fn test_build_filters_by_query_newest_first() {
+        let options = ContextOptions {
+            query: Some("STAGING deploy".to_string()),
+            ..Default::default()
+        };
+        let context = MemoContext::build(memos(), &options);
+        assert_eq!(context.matched, 2);
+        assert_eq!(context.included, 2);
+        assert!(!context.truncated);
+        assert!(!context.text.contains("Tacos"));
+        let release = context.text.find("# Release").unwrap();
+        let deploy = context.text.find("# Deploy notes").unwrap();
+        assert!(release < deploy);
+
+        let everything = MemoContext::build(memos(), &ContextOptions::default());
+        assert_eq!(everything.included, 3);
+        assert!(everything.text.starts_with("# Lunch"));
+    }
+
+    #[test]
+    fn test_build_puts_recently_updated_memos_first() {
+        let mut memos = memos();
+        memos[0].updated_at = Utc::now();
+        let context = MemoContext::build(memos, &ContextOptions::default());
+        assert!(context.text.starts_with("# Deploy notes"));
+
+        let context = MemoContext::build_with_header(
+            vec![memo("Lunch", "Tacos on Tuesday", 1)],
+            &ContextOptions::default(),
+            |memo| format!("=== {} ===\n", memo.title),
+        );
+        assert_eq!(context.text, "=== Lunch ===\nTacos on Tuesday");
+    }
+
+    #[test]
+    fn test_build_enforces_token_budget() {
+        let options = ContextOptions {
+            include_metadata: false,
+            max_tokens: Some(10),
+            ..Default::default()
+        };
+        let context = MemoContext::build(memos(), &options);
+        assert!(context.text.len() <= 10 * CHARS_PER_TOKEN);
+        assert!(context.estimated_tokens() <= 10);
+        assert!(context.truncated);
+        assert_eq!(context.text, "Tacos on Tuesday\n---\nTag the release aft");
+        assert_eq!(context.included, 2);
+        assert_eq!(context.omitted(), 1);
+
+        // Nothing is cut inside a multi-byte character
+        let options = ContextOptions {
+            include_metadata: false,
+            max_tokens: Some(1),
+            ..Default::default()
+        };
+        let context = MemoContext::build(vec![memo("Accents", "ééééé", 1)], &options);
+        assert_eq!(context.text, "éé");
+    }
+}
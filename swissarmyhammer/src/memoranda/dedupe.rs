@@ -0,0 +1,379 @@
+//! Finding and merging near-duplicate memos
+//!
+//! Importing notes tends to leave several memos saying the same thing under
+//! slightly different titles. [`find_merge_candidates`] pairs up memos whose
+//! titles share most of their words and whose content shares most of its
+//! shingles, runs of [`SHINGLE_SIZE`] consecutive words. Unlike compaction,
+//! which only removes exact duplicates, these memos differ, so they are merged
+//! rather than removed.
+//!
+//! Merging keeps the older memo and appends the paragraphs of the newer one it
+//! does not already contain. Both histories survive: the merge is recorded as
+//! an update of the kept memo and the removed memo's change log stays behind,
+//! so `memo history` and `memo restore` still reach it. `[[Title]]` links to the
+//! removed memo in other memos are pointed at the kept one.
+
+use crate::error::Result;
+use crate::memoranda::{Memo, MemoId, MemoStorage};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashSet};
+use std::fmt;
+
+/// Lowest similarity score at which two memos are offered for merging
+pub const DEFAULT_SIMILARITY_THRESHOLD: f64 = 0.7;
+
+/// Number of consecutive words in a content shingle
+pub const SHINGLE_SIZE: usize = 3;
+
+/// Weight of title similarity in the score; content makes up the rest
+const TITLE_WEIGHT: f64 = 0.3;
+
+/// Two memos similar enough to merge
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MergeCandidate {
+    /// ID of the older memo, which is kept
+    pub keep: String,
+    /// Title of the kept memo
+    pub keep_title: String,
+    /// ID of the newer memo, which is merged into the kept one
+    pub duplicate: String,
+    /// Title of the merged memo
+    pub duplicate_title: String,
+    /// Share of title words the memos have in common, from 0 to 1
+    pub title_similarity: f64,
+    /// Share of content shingles the memos have in common, from 0 to 1
+    pub content_similarity: f64,
+    /// Weighted similarity the candidate was chosen by
+    pub score: f64,
+}
+
+/// Result of looking for, and optionally merging, near-duplicate memos
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MemoDedupeReport {
+    /// Whether this report describes merges without making them
+    pub dry_run: bool,
+    /// Pairs of memos found similar enough to merge, most similar first
+    pub candidates: Vec<MergeCandidate>,
+    /// IDs of memos whose links were pointed at a kept memo
+    pub relinked: Vec<String>,
+}
+
+impl fmt::Display for MemoDedupeReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.candidates.is_empty() {
+            return write!(f, "No near-duplicate memos found");
+        }
+        let verb = if self.dry_run {
+            "Would merge"
+        } else {
+            "Merged"
+        };
+        write!(f, "Found {} near-duplicate memos", self.candidates.len())?;
+        for candidate in &self.candidates {
+            write!(
+                f,
+                "\n{verb} '{}' into '{}' ({:.0}% similar: title {:.0}%, content {:.0}%)",
+                candidate.duplicate_title,
+                candidate.keep_title,
+                candidate.score * 100.0,
+                candidate.title_similarity * 100.0,
+                candidate.content_similarity * 100.0
+            )?;
+        }
+        for id in &self.relinked {
+            write!(f, "\n✓ Updated links in {id}")?;
+        }
+        if self.dry_run {
+            write!(f, "\n\nRun with --merge to merge these memos")?;
+        }
+        Ok(())
+    }
+}
+
+/// Lowercase words of a text, without punctuation
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Share of the items of two sets found in both
+fn jaccard<T: Ord>(a: &BTreeSet<T>, b: &BTreeSet<T>) -> f64 {
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    let shared = a.intersection(b).count();
+    shared as f64 / (a.len() + b.len() - shared) as f64
+}
+
+/// Share of title words two titles have in common
+pub fn title_similarity(a: &str, b: &str) -> f64 {
+    let a: BTreeSet<String> = words(a).into_iter().collect();
+    let b: BTreeSet<String> = words(b).into_iter().collect();
+    jaccard(&a, &b)
+}
+
+/// Runs of [`SHINGLE_SIZE`] consecutive words of a text
+fn shingles(text: &str) -> BTreeSet<String> {
+    let words = words(text);
+    if words.len() < SHINGLE_SIZE {
+        return (!words.is_empty())
+            .then(|| words.join(" "))
+            .into_iter()
+            .collect();
+    }
+    words
+        .windows(SHINGLE_SIZE)
+        .map(|window| window.join(" "))
+        .collect()
+}
+
+/// Share of content shingles two texts have in common
+pub fn content_similarity(a: &str, b: &str) -> f64 {
+    jaccard(&shingles(a), &shingles(b))
+}
+
+/// Pairs of memos whose similarity reaches `threshold`, most similar first
+///
+/// Each memo appears in at most one pair, and the older memo of a pair is the
+/// one kept.
+pub fn find_merge_candidates(memos: &[Memo], threshold: f64) -> Vec<MergeCandidate> {
+    let mut memos: Vec<&Memo> = memos.iter().collect();
+    memos.sort_by(|a, b| {
+        a.created_at
+            .cmp(&b.created_at)
+            .then_with(|| a.id.as_str().cmp(b.id.as_str()))
+    });
+    let shingled: Vec<BTreeSet<String>> =
+        memos.iter().map(|memo| shingles(&memo.content)).collect();
+
+    let mut pairs = Vec::new();
+    for (i, keep) in memos.iter().enumerate() {
+        for (j, duplicate) in memos.iter().enumerate().skip(i + 1) {
+            let title = title_similarity(&keep.title, &duplicate.title);
+            let content = jaccard(&shingled[i], &shingled[j]);
+            let score = TITLE_WEIGHT * title + (1.0 - TITLE_WEIGHT) * content;
+            if score >= threshold {
+                pairs.push(MergeCandidate {
+                    keep: keep.id.as_str().to_string(),
+                    keep_title: keep.title.clone(),
+                    duplicate: duplicate.id.as_str().to_string(),
+                    duplicate_title: duplicate.title.clone(),
+                    title_similarity: title,
+                    content_similarity: content,
+                    score,
+                });
+            }
+        }
+    }
+    pairs.sort_by(|a, b| b.score.total_cmp(&a.score));
+
+    let mut paired = HashSet::new();
+    pairs
+        .into_iter()
+        .filter(|pair| {
+            if paired.contains(&pair.keep) || paired.contains(&pair.duplicate) {
+                return false;
+            }
+            paired.insert(pair.keep.clone());
+            paired.insert(pair.duplicate.clone());
+            true
+        })
+        .collect()
+}
+
+/// Content of `keep` followed by the paragraphs of `duplicate` it lacks
+pub fn merge_content(keep: &str, duplicate: &str) -> String {
+    let known: HashSet<String> = paragraphs(keep).map(|p| words(p).join(" ")).collect();
+    let missing: Vec<&str> = paragraphs(duplicate)
+        .filter(|p| !known.contains(&words(p).join(" ")))
+        .collect();
+    if missing.is_empty() {
+        return keep.to_string();
+    }
+    format!("{}\n\n{}\n", keep.trim_end(), missing.join("\n\n"))
+}
+
+fn paragraphs(content: &str) -> impl Iterator<Item = &str> {
+    content
+        .split("\n\n")
+        .map(str::trim)
+        .filter(|paragraph| !paragraph.is_empty())
+}
+
+/// Content with `[[from]]` links, including ones with an alias or heading,
+/// pointed at `to`
+///
+/// Link targets are compared without regard to case, as note-taking apps
+/// resolve them.
+pub fn relink(content: &str, from: &[&str], to: &str) -> String {
+    let mut result = String::with_capacity(content.len());
+    let mut rest = content;
+    while let Some(start) = rest.find("[[") {
+        let Some(end) = rest[start..].find("]]") else {
+            break;
+        };
+        let link = &rest[start + 2..start + end];
+        let target_end = link.find(['|', '#']).unwrap_or(link.len());
+        result.push_str(&rest[..start + 2]);
+        let target = link[..target_end].trim().to_lowercase();
+        if from.iter().any(|name| name.trim().to_lowercase() == target) {
+            result.push_str(to);
+            result.push_str(&link[target_end..]);
+        } else {
+            result.push_str(link);
+        }
+        result.push_str("]]");
+        rest = &rest[start + end + 2..];
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Merge the memos of each candidate and update links to the merged memos
+pub async fn merge_candidates(
+    storage: &dyn MemoStorage,
+    candidates: &[MergeCandidate],
+) -> Result<Vec<String>> {
+    for candidate in candidates {
+        let keep = storage
+            .get_memo(&MemoId::from_filename(&candidate.keep))
+            .await?;
+        let duplicate_id = MemoId::from_filename(&candidate.duplicate);
+        let duplicate = storage.get_memo(&duplicate_id).await?;
+
+        let merged = merge_content(&keep.content, &duplicate.content);
+        if merged != keep.content {
+            storage.update_memo(&keep.id, merged).await?;
+        }
+        storage.delete_memo(&duplicate_id).await?;
+    }
+
+    let mut relinked = Vec::new();
+    for memo in storage.list_memos().await? {
+        let mut content = memo.content.clone();
+        for candidate in candidates {
+            content = relink(
+                &content,
+                &[&candidate.duplicate_title, &candidate.duplicate],
+                &candidate.keep_title,
+            );
+        }
+        if content != memo.content {
+            storage.update_memo(&memo.id, content).await?;
+            relinked.push(memo.id.as_str().to_string());
+        }
+    }
+    Ok(relinked)
+}
+
+/// Look for near-duplicate memos, merging them unless `dry_run` is set
+pub async fn dedupe_memos(
+    storage: &dyn MemoStorage,
+    threshold: f64,
+    dry_run: bool,
+) -> Result<MemoDedupeReport> {
+    let candidates = find_merge_candidates(&storage.list_memos().await?, threshold);
+    let relinked = if dry_run {
+        Vec::new()
+    } else {
+        merge_candidates(storage, &candidates).await?
+    };
+    Ok(MemoDedupeReport {
+        dry_run,
+        candidates,
+        relinked,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::ChangeKind;
+    use crate::memoranda::MarkdownMemoStorage;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_similarity_and_merge_content() {
+        assert_eq!(title_similarity("Meeting Notes", "meeting notes!"), 1.0);
+        assert!(title_similarity("Meeting Notes", "Release Plan") < 0.1);
+        assert!(
+            content_similarity(
+                "We ship the release on Friday after the review",
+                "We ship the release on Friday after review"
+            ) > 0.5
+        );
+
+        let merged = merge_content("Intro\n\nShip on Friday", "Ship on  friday\n\nCall Jane");
+        assert_eq!(merged, "Intro\n\nShip on Friday\n\nCall Jane\n");
+        assert_eq!(merge_content("Same", "same"), "Same");
+
+        assert_eq!(
+            relink(
+                "See [[Old Notes]], [[old_notes#Plan|plan]] and [[Other]]",
+                &["Old Notes", "old_notes"],
+                "Notes"
+            ),
+            "See [[Notes]], [[Notes#Plan|plan]] and [[Other]]"
+        );
+        assert_eq!(relink("Broken [[link", &["link"], "x"), "Broken [[link");
+    }
+
+    #[tokio::test]
+    async fn test_dedupe_memos() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = MarkdownMemoStorage::new(temp_dir.path().join("memos"));
+        let body = "The team agreed to ship the release on Friday after the final review \
+                    and the sign off from QA";
+        let keep = storage
+            .create_memo("Release Plan".to_string(), body.to_string())
+            .await
+            .unwrap();
+        let duplicate = storage
+            .create_memo(
+                "Release plan imported".to_string(),
+                format!("{body}\n\nJane owns the changelog"),
+            )
+            .await
+            .unwrap();
+        storage
+            .create_memo(
+                "Index".to_string(),
+                "Start at [[release plan imported]]".to_string(),
+            )
+            .await
+            .unwrap();
+
+        let report = dedupe_memos(&storage, DEFAULT_SIMILARITY_THRESHOLD, true)
+            .await
+            .unwrap();
+        assert_eq!(report.candidates.len(), 1);
+        assert_eq!(report.candidates[0].keep_title, "Release Plan");
+        assert!(report.to_string().contains("Would merge"));
+        assert_eq!(storage.list_memos().await.unwrap().len(), 3);
+
+        let report = dedupe_memos(&storage, DEFAULT_SIMILARITY_THRESHOLD, false)
+            .await
+            .unwrap();
+        assert_eq!(report.relinked.len(), 1);
+        let memos = storage.list_memos().await.unwrap();
+        assert_eq!(memos.len(), 2);
+        let merged = storage.get_memo(&keep.id).await.unwrap();
+        assert!(merged.content.ends_with("Jane owns the changelog\n"));
+        assert!(memos
+            .iter()
+            .any(|memo| memo.content == "Start at [[Release Plan]]"));
+
+        // Both histories are kept
+        let kinds: Vec<ChangeKind> = storage
+            .memo_history(&duplicate.id)
+            .await
+            .unwrap()
+            .iter()
+            .map(|change| change.kind)
+            .collect();
+        assert_eq!(kinds, vec![ChangeKind::Created, ChangeKind::Deleted]);
+        assert_eq!(storage.memo_history(&keep.id).await.unwrap().len(), 2);
+    }
+}
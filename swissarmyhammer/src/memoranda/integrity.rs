@@ -0,0 +1,185 @@
+//! Integrity checking for stored memos
+//!
+//! File-backed memo storage records a SHA-256 checksum for every memo file it
+//! writes and keeps a few backup snapshots of each memo. The checksum is kept in
+//! [`CHECKSUM_FILE`] beside the memo's snapshots, so a write only touches the
+//! files of the memo written. This lets a crash that leaves a
+//! truncated or partially written file be detected and repaired from the most
+//! recent good snapshot.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::path::PathBuf;
+
+/// Number of backup snapshots kept per memo
+pub const MAX_BACKUP_SNAPSHOTS: usize = 5;
+
+/// File in a memo's backup directory holding the checksum of its last write
+pub const CHECKSUM_FILE: &str = "checksum";
+
+/// Compute the checksum recorded for memo file contents
+pub fn checksum(contents: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(contents))
+}
+
+/// Integrity state of a single memo file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum MemoIntegrityStatus {
+    /// The file parses and matches its recorded checksum
+    Valid,
+    /// The file is missing, truncated, unparseable, or does not match its checksum
+    Corrupted {
+        /// What is wrong with the file
+        reason: String,
+    },
+    /// The file was corrupted and has been restored from a backup snapshot
+    Restored {
+        /// What was wrong with the file
+        reason: String,
+        /// Snapshot the memo was restored from
+        snapshot: PathBuf,
+    },
+    /// The file was corrupted and no usable backup snapshot exists
+    Unrecoverable {
+        /// What is wrong with the file
+        reason: String,
+    },
+}
+
+/// Integrity check result for one memo
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoIntegrityEntry {
+    /// ID of the memo, taken from the file name
+    pub id: String,
+    /// Path of the memo file
+    pub path: PathBuf,
+    /// Integrity state of the file
+    #[serde(flatten)]
+    pub status: MemoIntegrityStatus,
+}
+
+/// Result of verifying every memo in storage
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoIntegrityReport {
+    /// One entry per memo checked
+    pub entries: Vec<MemoIntegrityEntry>,
+}
+
+impl MemoIntegrityReport {
+    /// Whether every memo is valid or was restored
+    pub fn is_healthy(&self) -> bool {
+        self.entries.iter().all(|entry| {
+            matches!(
+                entry.status,
+                MemoIntegrityStatus::Valid | MemoIntegrityStatus::Restored { .. }
+            )
+        })
+    }
+
+    /// Number of entries with the given kind of status
+    fn count(&self, predicate: impl Fn(&MemoIntegrityStatus) -> bool) -> usize {
+        self.entries
+            .iter()
+            .filter(|entry| predicate(&entry.status))
+            .count()
+    }
+}
+
+impl fmt::Display for MemoIntegrityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let valid = self.count(|s| matches!(s, MemoIntegrityStatus::Valid));
+        let corrupted = self.count(|s| matches!(s, MemoIntegrityStatus::Corrupted { .. }));
+        let restored = self.count(|s| matches!(s, MemoIntegrityStatus::Restored { .. }));
+        let unrecoverable = self.count(|s| matches!(s, MemoIntegrityStatus::Unrecoverable { .. }));
+
+        write!(
+            f,
+            "Checked {} memos: {valid} valid, {corrupted} corrupted, {restored} restored, {unrecoverable} unrecoverable",
+            self.entries.len()
+        )?;
+
+        for entry in &self.entries {
+            match &entry.status {
+                MemoIntegrityStatus::Valid => {}
+                MemoIntegrityStatus::Corrupted { reason } => {
+                    write!(f, "\n✗ {} corrupted: {reason}", entry.id)?;
+                }
+                MemoIntegrityStatus::Restored { reason, snapshot } => {
+                    write!(
+                        f,
+                        "\n✓ {} restored from {} ({reason})",
+                        entry.id,
+                        snapshot.display()
+                    )?;
+                }
+                MemoIntegrityStatus::Unrecoverable { reason } => {
+                    write!(f, "\n✗ {} unrecoverable: {reason}", entry.id)?;
+                }
+            }
+        }
+
+        if corrupted > 0 {
+            write!(
+                f,
+                "\n\nRun with restore enabled to recover corrupted memos from backups"
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: &str, status: MemoIntegrityStatus) -> MemoIntegrityEntry {
+        MemoIntegrityEntry {
+            id: id.to_string(),
+            path: PathBuf::from(format!("{id}.json")),
+            status,
+        }
+    }
+
+    #[test]
+    fn test_checksum_is_stable() {
+        assert_eq!(checksum(b"memo"), checksum(b"memo"));
+        assert_ne!(checksum(b"memo"), checksum(b"memo "));
+        assert_eq!(
+            checksum(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    fn test_report_health_and_summary() {
+        let mut report = MemoIntegrityReport {
+            entries: vec![
+                entry("A", MemoIntegrityStatus::Valid),
+                entry(
+                    "B",
+                    MemoIntegrityStatus::Restored {
+                        reason: "invalid JSON".to_string(),
+                        snapshot: PathBuf::from("snap.json"),
+                    },
+                ),
+            ],
+        };
+        assert!(report.is_healthy());
+        assert!(report
+            .to_string()
+            .starts_with("Checked 2 memos: 1 valid, 0 corrupted, 1 restored"));
+
+        report.entries.push(entry(
+            "C",
+            MemoIntegrityStatus::Corrupted {
+                reason: "checksum mismatch".to_string(),
+            },
+        ));
+        assert!(!report.is_healthy());
+        let summary = report.to_string();
+        assert!(summary.contains("C corrupted: checksum mismatch"));
+        assert!(summary.contains("restore enabled"));
+    }
+}
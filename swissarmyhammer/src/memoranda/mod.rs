@@ -11,6 +11,7 @@
 //! - **Pure Markdown Storage**: Files stored as `.md` with content only, no metadata wrapper
 //! - **Automatic Timestamps**: Creation and update times derived from filesystem metadata
 //! - **Full-text Search**: Search across memo titles and content
+//! - **Notebooks**: File memos in subdirectories and move them without changing their IDs
 //! - **Type-safe API**: Strong typing for memo identifiers and validation
 //!
 //! ## Basic Usage
@@ -438,7 +439,29 @@ use ulid::Ulid;
 
 /// Storage backends for memo persistence and retrieval
 pub mod storage;
-pub use storage::{FileSystemMemoStorage, MarkdownMemoStorage, MemoState, MemoStorage};
+pub use storage::{
+    default_memos_dir, memos_dir_for_work_dir, validate_notebook_name, FileSystemMemoStorage,
+    MarkdownMemoStorage, MemoState, MemoStorage,
+};
+
+/// Checksums and backup snapshots for detecting and repairing corrupted memos
+pub mod integrity;
+pub use integrity::{MemoIntegrityEntry, MemoIntegrityReport, MemoIntegrityStatus};
+
+/// zstd compression of large memo bodies in JSON memo storage
+pub mod compression;
+
+/// Compaction of leftover files and duplicate memos
+pub mod compaction;
+pub use compaction::{MemoCompactionReport, MemoDuplicate};
+
+/// Finding and merging near-duplicate memos
+pub mod dedupe;
+pub use dedupe::{MemoDedupeReport, MergeCandidate};
+
+/// Memo context built for AI consumption
+pub mod context;
+pub use context::MemoContext;
 
 /// Mock storage implementation for testing
 #[cfg(test)]
@@ -665,6 +688,9 @@ pub struct Memo {
     pub created_at: DateTime<Utc>,
     /// When this memo was last modified
     pub updated_at: DateTime<Utc>,
+    /// Notebook the memo is filed in, or `None` for the top level
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notebook: Option<String>,
 }
 
 impl Memo {
@@ -704,6 +730,7 @@ impl Memo {
             content,
             created_at: now,
             updated_at: now,
+            notebook: None,
         }
     }
 
@@ -1002,6 +1029,7 @@ pub struct SearchResult {
 ///     include_metadata: true,
 ///     max_tokens: Some(8000),
 ///     delimiter: "\n---\n".to_string(),
+///     query: Some("deploy".to_string()),
 /// };
 /// ```
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -1012,6 +1040,9 @@ pub struct ContextOptions {
     pub max_tokens: Option<usize>,
     /// Delimiter to use between memos (default: "\n---\n")
     pub delimiter: String,
+    /// Only include memos whose title or content mention every word (default: None)
+    #[serde(default)]
+    pub query: Option<String>,
 }
 
 impl Default for ContextOptions {
@@ -1020,6 +1051,7 @@ impl Default for ContextOptions {
             include_metadata: true,
             max_tokens: None,
             delimiter: "\n---\n".to_string(),
+            query: None,
         }
     }
 }
@@ -1193,6 +1225,7 @@ mod tests {
 
         assert!(options.include_metadata);
         assert!(options.max_tokens.is_none());
+        assert!(options.query.is_none());
         assert_eq!(options.delimiter, "\n---\n");
     }
 
@@ -1202,6 +1235,7 @@ mod tests {
             include_metadata: false,
             max_tokens: Some(5000),
             delimiter: "\n\n===\n\n".to_string(),
+            query: Some("deploy".to_string()),
         };
 
         let json = serde_json::to_string(&options).unwrap();
@@ -1442,6 +1476,7 @@ mod tests {
             include_metadata: false,
             max_tokens: Some(0),       // Edge case: zero tokens
             delimiter: "".to_string(), // Empty delimiter
+            query: Some(String::new()),
         };
         let json = serde_json::to_string(&options).unwrap();
         let deserialized: ContextOptions = serde_json::from_str(&json).unwrap();
@@ -1657,6 +1692,7 @@ mod property_tests {
                 include_metadata,
                 max_tokens,
                 delimiter,
+                query: None,
             };
 
             let json = serde_json::to_string(&options)?;
@@ -13,6 +13,7 @@
 //! - **File Format**: `{title}.md` containing pure markdown content
 //! - **ID System**: Filename-based IDs (sanitized title without extension)
 //! - **Timestamps**: Derived from filesystem metadata
+//! - **Notebooks**: Subdirectories of the memos directory, `{notebook}/{title}.md`
 //! - **Benefits**: Human-readable, portable, no metadata wrapper
 //!
 //! ```rust
@@ -38,6 +39,8 @@
 //! - **File Format**: `{ulid}.json` containing JSON-wrapped memo data
 //! - **ID System**: ULID-based identifiers (26-character strings)
 //! - **Timestamps**: Stored in JSON metadata
+//! - **Compression**: Bodies larger than `memo_compression_threshold_bytes` are
+//!   stored zstd compressed, see [`crate::memoranda::compression`]
 //! - **Status**: Legacy implementation, maintained for backward compatibility
 //!
 //! # Thread Safety
@@ -45,11 +48,24 @@
 //! All storage implementations are thread-safe and support concurrent access
 //! through internal locking mechanisms and atomic file operations.
 
+use crate::common::generate_monotonic_ulid;
 use crate::error::{Result, SwissArmyHammerError};
+use crate::events::{event_bus, Event};
+use crate::fs_utils::{FileSystem, StdFileSystem};
+use crate::history::{ChangeKind, ContentChange, ContentHistory};
+use crate::memoranda::compaction::{MemoCompactionReport, MemoDuplicate};
+use crate::memoranda::compression::{decode_memo, encode_memo};
+use crate::memoranda::integrity::{
+    checksum, MemoIntegrityEntry, MemoIntegrityReport, MemoIntegrityStatus, CHECKSUM_FILE,
+    MAX_BACKUP_SNAPSHOTS,
+};
 use crate::memoranda::{AdvancedMemoSearchEngine, Memo, MemoId, SearchOptions};
+use crate::security::{validate_path, PathPolicy};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use tokio::fs::OpenOptions;
 use tokio::io::AsyncWriteExt;
 use tokio::sync::Mutex;
@@ -75,6 +91,35 @@ pub struct MemoState {
     pub memos_dir: PathBuf,
 }
 
+impl MemoState {
+    /// Directory holding the persistent search index
+    pub fn search_index_path(&self) -> PathBuf {
+        self.memos_dir.join(".search_index")
+    }
+
+    /// Directory holding backup snapshots of memo files, one subdirectory per memo
+    pub fn backups_dir(&self) -> PathBuf {
+        self.memos_dir.join(".backups")
+    }
+
+    /// File recording the expected checksum of a memo file, beside its snapshots
+    pub fn checksum_path(&self, id: &str) -> PathBuf {
+        self.backups_dir().join(id).join(CHECKSUM_FILE)
+    }
+
+    /// Directory holding the content history of each memo
+    pub fn history_dir(&self) -> PathBuf {
+        self.memos_dir.join(".history")
+    }
+
+    /// Check that a memo file path is allowed by the global [`PathPolicy`]
+    ///
+    /// Rejects paths that escape the memos directory, including through symlinks.
+    pub fn validate_memo_path(&self, path: &Path) -> Result<PathBuf> {
+        validate_path(path, &self.memos_dir, PathPolicy::global())
+    }
+}
+
 /// Trait for memo storage operations
 ///
 /// Defines the interface for memo storage backends, allowing different
@@ -268,7 +313,9 @@ pub trait MemoStorage: Send + Sync {
     ///
     /// Concatenates all memos with metadata and delimiters optimized for
     /// AI context consumption. Useful for providing comprehensive context
-    /// to language models or other automated processing.
+    /// to language models or other automated processing. Memos are filtered
+    /// by the options' query and ordered newest first, and the context is
+    /// cut short at the token budget; see [`crate::memoranda::MemoContext`].
     ///
     /// # Arguments
     ///
@@ -296,6 +343,222 @@ pub trait MemoStorage: Send + Sync {
     /// # }
     /// ```
     async fn get_all_context(&self, options: &crate::memoranda::ContextOptions) -> Result<String>;
+
+    /// Verify that stored memos are intact
+    ///
+    /// Detects missing, truncated or corrupted memo files. When `restore` is true,
+    /// corrupted memos are restored from their most recent backup snapshot.
+    /// Storage backends that keep no integrity metadata return an error rather
+    /// than report memos they never checked as healthy.
+    ///
+    /// # Arguments
+    ///
+    /// * `restore` - Whether to restore corrupted memos from backups
+    ///
+    /// # Returns
+    ///
+    /// * `Result<MemoIntegrityReport>` - The state of every memo checked
+    async fn verify_integrity(&self, restore: bool) -> Result<MemoIntegrityReport> {
+        let _ = restore;
+        Err(SwissArmyHammerError::Other(
+            "Integrity verification is not supported by this memo storage".to_string(),
+        ))
+    }
+
+    /// Compact stored memos
+    ///
+    /// Removes temporary files left by interrupted writes and backups of
+    /// memos that no longer exist, removes memos duplicating the title and
+    /// content of an older memo, rewrites memo files in canonical form and
+    /// rebuilds the search index. When `dry_run` is true, nothing is changed.
+    /// Storage backends that can't be compacted return an error.
+    ///
+    /// # Arguments
+    ///
+    /// * `dry_run` - Whether to only report what would be changed
+    ///
+    /// # Returns
+    ///
+    /// * `Result<MemoCompactionReport>` - What was removed and rewritten
+    async fn compact(&self, dry_run: bool) -> Result<MemoCompactionReport> {
+        let _ = dry_run;
+        Err(SwissArmyHammerError::Other(
+            "Compaction is not supported by this memo storage".to_string(),
+        ))
+    }
+
+    /// Get the changes made to a memo's content, oldest first
+    ///
+    /// Deleted memos keep their history. Storage backends that keep no
+    /// history report no changes.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The memo ID
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<ContentChange>>` - Every recorded change to the memo
+    async fn memo_history(&self, id: &MemoId) -> Result<Vec<ContentChange>> {
+        let _ = id;
+        Ok(Vec::new())
+    }
+
+    /// Restore a memo's content to a version from its history
+    ///
+    /// Recreates the memo when it was deleted. The restore is recorded in the
+    /// history as a new version.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The memo ID
+    /// * `version` - The version to restore
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Memo>` - The restored memo
+    async fn restore_memo(&self, id: &MemoId, version: usize) -> Result<Memo> {
+        let _ = version;
+        Err(SwissArmyHammerError::Other(format!(
+            "Memo storage keeps no history to restore '{}' from",
+            id.as_str()
+        )))
+    }
+
+    /// Create a new memo filed in a notebook
+    ///
+    /// The notebook is created if it doesn't exist. With no notebook this is
+    /// [`create_memo`](MemoStorage::create_memo). Storage backends without
+    /// notebooks only create memos at the top level.
+    ///
+    /// # Arguments
+    ///
+    /// * `title` - The title or subject of the memo
+    /// * `content` - The main content/body of the memo
+    /// * `notebook` - The notebook to file the memo in
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Memo>` - The created memo
+    async fn create_memo_in_notebook(
+        &self,
+        title: String,
+        content: String,
+        notebook: Option<&str>,
+    ) -> Result<Memo> {
+        match notebook {
+            None => self.create_memo(title, content).await,
+            Some(_) => Err(no_notebooks()),
+        }
+    }
+
+    /// List the memos filed in a notebook
+    ///
+    /// # Arguments
+    ///
+    /// * `notebook` - The notebook to list
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<Memo>>` - The memos in the notebook
+    async fn list_memos_in_notebook(&self, notebook: &str) -> Result<Vec<Memo>> {
+        let memos = self.list_memos().await?;
+        Ok(memos
+            .into_iter()
+            .filter(|memo| memo.notebook.as_deref() == Some(notebook))
+            .collect())
+    }
+
+    /// List the names of the notebooks, sorted
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<String>>` - The notebook names
+    async fn list_notebooks(&self) -> Result<Vec<String>> {
+        let mut notebooks: Vec<String> = self
+            .list_memos()
+            .await?
+            .into_iter()
+            .filter_map(|memo| memo.notebook)
+            .collect();
+        notebooks.sort();
+        notebooks.dedup();
+        Ok(notebooks)
+    }
+
+    /// Create an empty notebook
+    ///
+    /// # Arguments
+    ///
+    /// * `notebook` - The name of the notebook
+    async fn create_notebook(&self, notebook: &str) -> Result<()> {
+        let _ = notebook;
+        Err(no_notebooks())
+    }
+
+    /// Delete a notebook that holds no memos
+    ///
+    /// # Arguments
+    ///
+    /// * `notebook` - The name of the notebook
+    async fn delete_notebook(&self, notebook: &str) -> Result<()> {
+        let _ = notebook;
+        Err(no_notebooks())
+    }
+
+    /// Rename a notebook, keeping the IDs of the memos filed in it
+    ///
+    /// # Arguments
+    ///
+    /// * `notebook` - The current name of the notebook
+    /// * `new_name` - The name to give it
+    async fn rename_notebook(&self, notebook: &str, new_name: &str) -> Result<()> {
+        let _ = (notebook, new_name);
+        Err(no_notebooks())
+    }
+
+    /// Move a memo into a notebook, or to the top level with `None`
+    ///
+    /// The memo keeps its ID, content and history. The target notebook is
+    /// created if it doesn't exist.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The memo ID
+    /// * `notebook` - The notebook to move the memo to
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Memo>` - The moved memo
+    async fn move_memo(&self, id: &MemoId, notebook: Option<&str>) -> Result<Memo> {
+        let _ = (id, notebook);
+        Err(no_notebooks())
+    }
+}
+
+/// Error for a notebook operation on storage without notebooks
+fn no_notebooks() -> SwissArmyHammerError {
+    SwissArmyHammerError::Other("Memo storage has no notebooks".to_string())
+}
+
+/// Check that a notebook name can be used as a directory in the memos directory
+///
+/// Notebook names may not be empty, contain path separators or start with a
+/// dot, which is reserved for the storage's own directories such as `.history`.
+pub fn validate_notebook_name(notebook: &str) -> Result<()> {
+    let invalid = notebook.trim().is_empty()
+        || notebook != notebook.trim()
+        || notebook.starts_with('.')
+        || notebook.len() > 200
+        || notebook.chars().any(|c| {
+            c.is_control() || matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|')
+        });
+    if invalid {
+        return Err(SwissArmyHammerError::Other(format!(
+            "Invalid notebook name '{notebook}': names cannot be empty, start with '.' or contain path separators"
+        )));
+    }
+    Ok(())
 }
 
 /// Filesystem-based implementation of memo storage
@@ -333,8 +596,31 @@ pub struct FileSystemMemoStorage {
     state: MemoState,
     /// Mutex to ensure thread-safe memo creation and prevent race conditions
     creation_lock: Mutex<()>,
+    /// Mutex serializing updates to memo checksums and backup snapshots
+    integrity_lock: Mutex<()>,
     /// Advanced search engine for full-text search capabilities
     search_engine: Option<AdvancedMemoSearchEngine>,
+    /// Bytes above which memo bodies are written compressed, 0 never compresses
+    compression_threshold: usize,
+}
+
+/// The directory memos are stored in by default
+///
+/// Uses the `SWISSARMYHAMMER_MEMOS_DIR` environment variable if set,
+/// otherwise `.swissarmyhammer/memos` in the current directory.
+pub fn default_memos_dir() -> Result<PathBuf> {
+    Ok(memos_dir_for_work_dir(&std::env::current_dir()?))
+}
+
+/// The directory memos are stored in for a project rooted at `work_dir`
+///
+/// Uses the `SWISSARMYHAMMER_MEMOS_DIR` environment variable if set,
+/// otherwise `.swissarmyhammer/memos` in `work_dir`.
+pub fn memos_dir_for_work_dir(work_dir: &Path) -> PathBuf {
+    if let Ok(custom_path) = std::env::var("SWISSARMYHAMMER_MEMOS_DIR") {
+        return PathBuf::from(custom_path);
+    }
+    work_dir.join(".swissarmyhammer").join("memos")
 }
 
 /// Generate highlighted text snippets showing where search matches were found
@@ -528,14 +814,7 @@ impl FileSystemMemoStorage {
     /// # Ok::<(), swissarmyhammer::error::SwissArmyHammerError>(())
     /// ```
     pub fn new_default() -> Result<Self> {
-        let memos_dir = if let Ok(custom_path) = std::env::var("SWISSARMYHAMMER_MEMOS_DIR") {
-            PathBuf::from(custom_path)
-        } else {
-            std::env::current_dir()?
-                .join(".swissarmyhammer")
-                .join("memos")
-        };
-        Ok(Self::new(memos_dir))
+        Ok(Self::new(default_memos_dir()?))
     }
 
     /// Create a new filesystem storage with a specific memo directory
@@ -560,10 +839,21 @@ impl FileSystemMemoStorage {
         Self {
             state: MemoState { memos_dir },
             creation_lock: Mutex::new(()),
+            integrity_lock: Mutex::new(()),
             search_engine: None,
+            compression_threshold: crate::config::Config::global().memo_compression_threshold_bytes,
         }
     }
 
+    /// Set the size in bytes above which memo bodies are written compressed
+    ///
+    /// A threshold of 0 writes every memo as plain JSON. Memos already written
+    /// compressed are still read either way.
+    pub fn with_compression_threshold(mut self, bytes: usize) -> Self {
+        self.compression_threshold = bytes;
+        self
+    }
+
     /// Create a new filesystem storage with advanced search enabled
     ///
     /// # Arguments
@@ -574,14 +864,16 @@ impl FileSystemMemoStorage {
     ///
     /// * `Result<Self>` - New storage instance with search engine initialized
     pub async fn new_with_search(memos_dir: PathBuf) -> Result<Self> {
-        // Create index path in the memos directory
-        let index_path = memos_dir.join(".search_index");
-        let search_engine = AdvancedMemoSearchEngine::new_persistent(index_path).await?;
+        let state = MemoState { memos_dir };
+        let search_engine =
+            AdvancedMemoSearchEngine::new_persistent(state.search_index_path()).await?;
 
         Ok(Self {
-            state: MemoState { memos_dir },
+            state,
             creation_lock: Mutex::new(()),
+            integrity_lock: Mutex::new(()),
             search_engine: Some(search_engine),
+            compression_threshold: crate::config::Config::global().memo_compression_threshold_bytes,
         })
     }
 
@@ -611,8 +903,8 @@ impl FileSystemMemoStorage {
     /// * `Result<()>` - Success or error if search engine initialization fails
     pub async fn initialize_search_engine(&mut self) -> Result<()> {
         if self.search_engine.is_none() {
-            let index_path = self.state.memos_dir.join(".search_index");
-            let search_engine = AdvancedMemoSearchEngine::new_persistent(index_path).await?;
+            let search_engine =
+                AdvancedMemoSearchEngine::new_persistent(self.state.search_index_path()).await?;
 
             // Index all existing memos
             let all_memos = self.list_memos().await?;
@@ -669,7 +961,7 @@ impl FileSystemMemoStorage {
         self.state.memos_dir.join(format!("{}.json", id.as_str()))
     }
 
-    /// Load and deserialize a memo from a JSON file
+    /// Load and deserialize a memo from a JSON file, decompressing its body if needed
     ///
     /// # Arguments
     ///
@@ -679,15 +971,16 @@ impl FileSystemMemoStorage {
     ///
     /// * `Result<Memo>` - The deserialized memo or error if file cannot be read/parsed
     async fn load_memo_from_file(&self, path: &PathBuf) -> Result<Memo> {
-        let content = tokio::fs::read_to_string(path).await?;
-        let memo: Memo = serde_json::from_str(&content)?;
-        Ok(memo)
+        let path = self.state.validate_memo_path(path)?;
+        let content = tokio::fs::read(path).await?;
+        decode_memo(&content)
     }
 
     /// Serialize and save a memo to a JSON file
     ///
     /// Creates the directory if it doesn't exist, then writes the memo
-    /// as pretty-printed JSON to the appropriate file.
+    /// as pretty-printed JSON to the appropriate file, compressing a body
+    /// over the compression threshold.
     ///
     /// # Arguments
     ///
@@ -699,12 +992,115 @@ impl FileSystemMemoStorage {
     async fn save_memo_to_file(&self, memo: &Memo) -> Result<()> {
         self.ensure_directory_exists().await?;
 
-        let path = self.get_memo_path(&memo.id);
-        let content = serde_json::to_string_pretty(memo)?;
-        tokio::fs::write(path, content).await?;
+        let path = self
+            .state
+            .validate_memo_path(&self.get_memo_path(&memo.id))?;
+        let content = encode_memo(memo, self.compression_threshold)?;
+        write_file_atomically(&path, content.as_bytes()).await?;
+        self.record_snapshot(memo.id.as_str(), content.as_bytes())
+            .await
+    }
+
+    /// Record a backup snapshot and checksum for freshly written memo contents
+    ///
+    /// Keeps the newest [`MAX_BACKUP_SNAPSHOTS`] snapshots per memo.
+    async fn record_snapshot(&self, id: &str, contents: &[u8]) -> Result<()> {
+        let _lock = self.integrity_lock.lock().await;
+
+        let snapshot_dir = self.state.backups_dir().join(id);
+        tokio::fs::create_dir_all(&snapshot_dir).await?;
+        let snapshot_path = snapshot_dir.join(format!("{}.json", generate_monotonic_ulid()));
+        write_file_atomically(&snapshot_path, contents).await?;
+
+        let snapshots = list_snapshots(&snapshot_dir).await?;
+        for stale in snapshots.iter().skip(MAX_BACKUP_SNAPSHOTS) {
+            tokio::fs::remove_file(stale).await?;
+        }
+
+        write_file_atomically(&self.state.checksum_path(id), checksum(contents).as_bytes()).await
+    }
+
+    /// Forget the checksum and backup snapshots of a deleted memo
+    async fn remove_integrity_metadata(&self, id: &str) -> Result<()> {
+        let _lock = self.integrity_lock.lock().await;
+
+        let snapshot_dir = self.state.backups_dir().join(id);
+        if snapshot_dir.exists() {
+            tokio::fs::remove_dir_all(&snapshot_dir).await?;
+        }
         Ok(())
     }
 
+    /// Checksum recorded for a memo's last write, if any
+    async fn recorded_checksum(&self, id: &str) -> Option<String> {
+        let path = self.state.checksum_path(id);
+        match tokio::fs::read_to_string(&path).await {
+            Ok(recorded) => Some(recorded.trim().to_string()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => {
+                tracing::warn!(
+                    path = %path.display(),
+                    error = %e,
+                    "Cannot read memo checksum, checking the memo without it"
+                );
+                None
+            }
+        }
+    }
+
+    /// Check a single memo file against its recorded checksum
+    ///
+    /// Returns the reason the file is corrupted, or `None` if it is valid.
+    async fn check_memo_file(&self, path: &Path, expected: Option<&str>) -> Option<String> {
+        if let Err(e) = self.state.validate_memo_path(path) {
+            return Some(e.to_string());
+        }
+
+        let contents = match tokio::fs::read(path).await {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Some("memo file is missing".to_string())
+            }
+            Err(e) => return Some(format!("cannot read memo file: {e}")),
+        };
+
+        if let Err(e) = decode_memo(&contents) {
+            return Some(format!("invalid memo JSON: {e}"));
+        }
+
+        match expected {
+            Some(expected) if expected != checksum(&contents) => {
+                Some("checksum does not match the last write".to_string())
+            }
+            _ => None,
+        }
+    }
+
+    /// Restore a memo from its most recent snapshot that parses as a memo
+    async fn restore_from_snapshot(&self, id: &str, path: &Path) -> Result<Option<PathBuf>> {
+        let snapshot_dir = self.state.backups_dir().join(id);
+        if !snapshot_dir.exists() {
+            return Ok(None);
+        }
+
+        for snapshot in list_snapshots(&snapshot_dir).await? {
+            let contents = tokio::fs::read(&snapshot).await?;
+            if decode_memo(&contents).is_err() {
+                continue;
+            }
+
+            write_file_atomically(path, &contents).await?;
+            let _lock = self.integrity_lock.lock().await;
+            write_file_atomically(
+                &self.state.checksum_path(id),
+                checksum(&contents).as_bytes(),
+            )
+            .await?;
+            return Ok(Some(snapshot));
+        }
+        Ok(None)
+    }
+
     /// Create a new memo file atomically to prevent race conditions
     ///
     /// Uses `create_new(true)` to ensure the file doesn't already exist,
@@ -726,8 +1122,10 @@ impl FileSystemMemoStorage {
     async fn create_memo_file_atomically(&self, memo: &Memo) -> Result<()> {
         self.ensure_directory_exists().await?;
 
-        let path = self.get_memo_path(&memo.id);
-        let content = serde_json::to_string_pretty(memo)?;
+        let path = self
+            .state
+            .validate_memo_path(&self.get_memo_path(&memo.id))?;
+        let content = encode_memo(memo, self.compression_threshold)?;
 
         let mut file = OpenOptions::new()
             .create_new(true)
@@ -744,8 +1142,88 @@ impl FileSystemMemoStorage {
 
         file.write_all(content.as_bytes()).await?;
         file.flush().await?;
-        Ok(())
+        self.record_snapshot(memo.id.as_str(), content.as_bytes())
+            .await
+    }
+}
+
+/// Write a file by writing a temporary sibling and renaming it into place
+///
+/// A crash mid-write leaves the previous contents intact instead of a truncated file.
+async fn write_file_atomically(path: &Path, contents: &[u8]) -> Result<()> {
+    let mut temp_name = path.as_os_str().to_owned();
+    temp_name.push(".tmp");
+    let temp_path = PathBuf::from(temp_name);
+
+    let mut file = tokio::fs::File::create(&temp_path).await?;
+    file.write_all(contents).await?;
+    file.sync_all().await?;
+    drop(file);
+
+    tokio::fs::rename(&temp_path, path).await?;
+    Ok(())
+}
+
+/// List the snapshot files in a memo's backup directory, newest first
+async fn list_snapshots(snapshot_dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut snapshots = Vec::new();
+    let mut entries = tokio::fs::read_dir(snapshot_dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        if path.extension().is_some_and(|ext| ext == "json") {
+            snapshots.push(path);
+        }
     }
+    // Snapshot names are ULIDs, so lexical order is chronological
+    snapshots.sort();
+    snapshots.reverse();
+    Ok(snapshots)
+}
+
+/// Whether a path in the memos directory is a memo JSON file rather than storage metadata
+fn is_memo_json_file(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "json")
+        && !path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with('.'))
+}
+
+/// Whether a path is a temporary file left by an interrupted [`write_file_atomically`]
+fn is_temp_file(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "tmp")
+}
+
+/// Total size of a file, or of every file under a directory
+async fn disk_usage(path: &Path) -> Result<u64> {
+    let mut total = 0;
+    let mut pending = vec![path.to_path_buf()];
+    while let Some(path) = pending.pop() {
+        let metadata = tokio::fs::symlink_metadata(&path).await?;
+        if metadata.is_dir() {
+            let mut entries = tokio::fs::read_dir(&path).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                pending.push(entry.path());
+            }
+        } else {
+            total += metadata.len();
+        }
+    }
+    Ok(total)
+}
+
+/// Remove a leftover file or directory found during compaction and record it
+async fn remove_leftover(path: PathBuf, report: &mut MemoCompactionReport) -> Result<()> {
+    report.reclaimed_bytes += disk_usage(&path).await?;
+    if !report.dry_run {
+        if path.is_dir() {
+            tokio::fs::remove_dir_all(&path).await?;
+        } else {
+            tokio::fs::remove_file(&path).await?;
+        }
+    }
+    report.removed_files.push(path);
+    Ok(())
 }
 
 #[async_trait]
@@ -789,6 +1267,7 @@ impl MemoStorage for FileSystemMemoStorage {
         }
 
         tokio::fs::remove_file(path).await?;
+        self.remove_integrity_metadata(id.as_str()).await?;
 
         // Remove the memo from the search engine if available
         self.remove_memo_from_index_if_available(id).await?;
@@ -806,7 +1285,7 @@ impl MemoStorage for FileSystemMemoStorage {
 
         while let Some(entry) = dir_entries.next_entry().await? {
             let path = entry.path();
-            if path.extension().is_some_and(|ext| ext == "json") {
+            if is_memo_json_file(&path) {
                 match self.load_memo_from_file(&path).await {
                     Ok(memo) => memos.push(memo),
                     Err(e) => {
@@ -914,45 +1393,188 @@ impl MemoStorage for FileSystemMemoStorage {
     }
 
     async fn get_all_context(&self, options: &crate::memoranda::ContextOptions) -> Result<String> {
-        let all_memos = self.list_memos().await?;
+        let memos = self.list_memos().await?;
+        Ok(crate::memoranda::MemoContext::build(memos, options).text)
+    }
 
-        if all_memos.is_empty() {
-            return Ok(String::new());
+    async fn verify_integrity(&self, restore: bool) -> Result<MemoIntegrityReport> {
+        if !self.state.memos_dir.exists() {
+            return Ok(MemoIntegrityReport::default());
         }
 
-        let mut context = String::new();
+        // Check every memo on disk plus any recorded memo whose file has gone missing
+        let mut ids = std::collections::BTreeSet::new();
+        let backups_dir = self.state.backups_dir();
+        if backups_dir.exists() {
+            let mut backup_entries = tokio::fs::read_dir(&backups_dir).await?;
+            while let Some(entry) = backup_entries.next_entry().await? {
+                let id = entry.file_name().to_string_lossy().into_owned();
+                if self.state.checksum_path(&id).exists() {
+                    ids.insert(id);
+                }
+            }
+        }
+        let mut dir_entries = tokio::fs::read_dir(&self.state.memos_dir).await?;
+        while let Some(entry) = dir_entries.next_entry().await? {
+            let path = entry.path();
+            if is_memo_json_file(&path) {
+                if let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) {
+                    ids.insert(stem.to_string());
+                }
+            }
+        }
 
-        // Sort memos by creation date (newest first)
-        let mut sorted_memos = all_memos;
-        sorted_memos.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        let mut report = MemoIntegrityReport::default();
+        for id in ids {
+            let path = self.state.memos_dir.join(format!("{id}.json"));
+            let expected = self.recorded_checksum(&id).await;
+            let status = match self.check_memo_file(&path, expected.as_deref()).await {
+                None => MemoIntegrityStatus::Valid,
+                Some(reason) if !restore => MemoIntegrityStatus::Corrupted { reason },
+                Some(reason) => match self.restore_from_snapshot(&id, &path).await? {
+                    Some(snapshot) => {
+                        tracing::info!("Restored memo {} from {}", id, snapshot.display());
+                        MemoIntegrityStatus::Restored { reason, snapshot }
+                    }
+                    None => MemoIntegrityStatus::Unrecoverable { reason },
+                },
+            };
+            report.entries.push(MemoIntegrityEntry { id, path, status });
+        }
+
+        Ok(report)
+    }
 
-        for (i, memo) in sorted_memos.iter().enumerate() {
-            if i > 0 {
-                context.push_str(&options.delimiter);
+    async fn compact(&self, dry_run: bool) -> Result<MemoCompactionReport> {
+        let mut report = MemoCompactionReport {
+            dry_run,
+            ..Default::default()
+        };
+        if !self.state.memos_dir.exists() {
+            return Ok(report);
+        }
+
+        // Hold the creation lock so no memo is created while files are removed
+        let _lock = self.creation_lock.lock().await;
+
+        let mut memos = Vec::new();
+        let mut dir_entries = tokio::fs::read_dir(&self.state.memos_dir).await?;
+        while let Some(entry) = dir_entries.next_entry().await? {
+            let path = entry.path();
+            if is_temp_file(&path) {
+                remove_leftover(path, &mut report).await?;
+            } else if is_memo_json_file(&path) {
+                let contents = tokio::fs::read(&path).await?;
+                let memo = match decode_memo(&contents) {
+                    Ok(memo) => memo,
+                    Err(e) => {
+                        tracing::warn!(
+                            path = %path.display(),
+                            error = %e,
+                            "Skipping unreadable memo file, run memo verify to repair it"
+                        );
+                        continue;
+                    }
+                };
+                if self
+                    .recorded_checksum(memo.id.as_str())
+                    .await
+                    .is_some_and(|expected| expected != checksum(&contents))
+                {
+                    tracing::warn!(
+                        path = %path.display(),
+                        "Skipping memo file that does not match its checksum, run memo verify to repair it"
+                    );
+                    continue;
+                }
+                memos.push((path, memo, contents));
             }
+        }
 
-            if options.include_metadata {
-                context.push_str(&format!(
-                    "# {} ({})\nCreated: {} | Updated: {}\n\n",
-                    memo.title,
-                    memo.id.as_str(),
-                    memo.created_at.format("%Y-%m-%d %H:%M:%S UTC"),
-                    memo.updated_at.format("%Y-%m-%d %H:%M:%S UTC")
-                ));
+        // Backups of memos that are gone and can no longer be restored
+        let backups_dir = self.state.backups_dir();
+        if backups_dir.exists() {
+            let mut backup_entries = tokio::fs::read_dir(&backups_dir).await?;
+            while let Some(entry) = backup_entries.next_entry().await? {
+                let snapshot_dir = entry.path();
+                let id = entry.file_name().to_string_lossy().into_owned();
+                let memo_path = self.state.memos_dir.join(format!("{id}.json"));
+                if !memo_path.exists() && !self.state.checksum_path(&id).exists() {
+                    remove_leftover(snapshot_dir, &mut report).await?;
+                } else if snapshot_dir.is_dir() {
+                    let mut snapshots = tokio::fs::read_dir(&snapshot_dir).await?;
+                    while let Some(snapshot) = snapshots.next_entry().await? {
+                        if is_temp_file(&snapshot.path()) {
+                            remove_leftover(snapshot.path(), &mut report).await?;
+                        }
+                    }
+                }
             }
+        }
 
-            context.push_str(&memo.content);
+        // Keep the oldest of each set of memos with the same title and content
+        memos.sort_by(|a, b| {
+            (a.1.created_at, a.1.id.as_str()).cmp(&(b.1.created_at, b.1.id.as_str()))
+        });
+        let mut originals: HashMap<(String, String), String> = HashMap::new();
+        let mut kept = Vec::new();
+        for (path, memo, contents) in memos {
+            let key = (memo.title.clone(), memo.content.clone());
+            if let Some(original) = originals.get(&key) {
+                report.reclaimed_bytes += contents.len() as u64;
+                let snapshot_dir = backups_dir.join(memo.id.as_str());
+                if snapshot_dir.exists() {
+                    report.reclaimed_bytes += disk_usage(&snapshot_dir).await?;
+                }
+                if !dry_run {
+                    tokio::fs::remove_file(&path).await?;
+                    self.remove_integrity_metadata(memo.id.as_str()).await?;
+                }
+                report.duplicates.push(MemoDuplicate {
+                    id: memo.id.as_str().to_string(),
+                    duplicate_of: original.clone(),
+                });
+            } else {
+                originals.insert(key, memo.id.as_str().to_string());
+                kept.push((memo, contents));
+            }
+        }
 
-            // Rough token estimation (4 characters per token)
-            if let Some(max_tokens) = options.max_tokens {
-                let estimated_tokens = context.len() / 4;
-                if estimated_tokens >= max_tokens {
-                    break;
+        // Rewrite memo files that are not in the form memo writes produce
+        for (memo, contents) in &kept {
+            let canonical = encode_memo(memo, self.compression_threshold)?;
+            if canonical.as_bytes() != contents.as_slice() {
+                report.reclaimed_bytes += contents.len().saturating_sub(canonical.len()) as u64;
+                if !dry_run {
+                    self.save_memo_to_file(memo).await?;
                 }
+                report.rewritten.push(memo.id.as_str().to_string());
+            }
+        }
+
+        let kept: Vec<Memo> = kept.into_iter().map(|(memo, _)| memo).collect();
+        if !dry_run {
+            if let Some(search_engine) = &self.search_engine {
+                search_engine.rebuild_index(&kept).await?;
+                report.index_rebuilt = true;
+            } else if self.state.search_index_path().exists() {
+                // Storage opened without search still rebuilds an index left on disk
+                let search_engine =
+                    AdvancedMemoSearchEngine::new_persistent(self.state.search_index_path())
+                        .await?;
+                search_engine.rebuild_index(&kept).await?;
+                report.index_rebuilt = true;
             }
         }
+        report.memos = kept.len();
 
-        Ok(context)
+        tracing::info!(
+            "Compacted memos: {} files removed, {} duplicates, {} rewritten",
+            report.removed_files.len(),
+            report.duplicates.len(),
+            report.rewritten.len()
+        );
+        Ok(report)
     }
 }
 
@@ -968,6 +1590,8 @@ impl MemoStorage for FileSystemMemoStorage {
 /// - Files contain pure markdown content without metadata
 /// - Timestamps are read from filesystem created/modified times
 /// - ID is computed from the filename (without .md extension)
+/// - Memos filed in a notebook are stored in a subdirectory named after it;
+///   IDs stay unique across notebooks, so moving a memo keeps its ID
 ///
 /// # Examples
 ///
@@ -987,8 +1611,12 @@ pub struct MarkdownMemoStorage {
     state: MemoState,
     /// Mutex to ensure thread-safe memo creation and prevent race conditions
     creation_lock: Mutex<()>,
+    /// Mutex serializing updates to memo checksums and backup snapshots
+    integrity_lock: Mutex<()>,
     /// Advanced search engine for full-text search capabilities
     search_engine: Option<AdvancedMemoSearchEngine>,
+    /// File system memo files are read from and written to
+    fs: Arc<dyn FileSystem>,
 }
 
 impl MarkdownMemoStorage {
@@ -1005,14 +1633,7 @@ impl MarkdownMemoStorage {
     ///
     /// * `SWISSARMYHAMMER_MEMOS_DIR` - Custom directory for memo storage
     pub fn new_default() -> Result<Self> {
-        let memos_dir = if let Ok(custom_path) = std::env::var("SWISSARMYHAMMER_MEMOS_DIR") {
-            PathBuf::from(custom_path)
-        } else {
-            std::env::current_dir()?
-                .join(".swissarmyhammer")
-                .join("memos")
-        };
-        Ok(Self::new(memos_dir))
+        Ok(Self::new(default_memos_dir()?))
     }
 
     /// Create a new markdown storage with a specific memo directory
@@ -1025,10 +1646,29 @@ impl MarkdownMemoStorage {
     ///
     /// * `Self` - New storage instance
     pub fn new(memos_dir: PathBuf) -> Self {
+        Self::with_fs(memos_dir, Arc::new(StdFileSystem))
+    }
+
+    /// Create a new markdown storage on a specific file system
+    ///
+    /// Pass an [`OverlayFileSystem`](crate::fs_utils::OverlayFileSystem) to
+    /// simulate memo changes without writing them to disk.
+    ///
+    /// # Arguments
+    ///
+    /// * `memos_dir` - The directory path where memo files will be stored
+    /// * `fs` - The file system memo files are read from and written to
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - New storage instance
+    pub fn with_fs(memos_dir: PathBuf, fs: Arc<dyn FileSystem>) -> Self {
         Self {
             state: MemoState { memos_dir },
             creation_lock: Mutex::new(()),
+            integrity_lock: Mutex::new(()),
             search_engine: None,
+            fs,
         }
     }
 
@@ -1084,8 +1724,91 @@ impl MarkdownMemoStorage {
     ///
     /// * `PathBuf` - The full path where the memo file should be stored
     fn get_memo_path_from_title(&self, title: &str) -> PathBuf {
+        self.memo_path(title, None)
+    }
+
+    /// Get the filesystem path for a memo with the given title in a notebook
+    fn memo_path(&self, title: &str, notebook: Option<&str>) -> PathBuf {
         let sanitized_title = Self::sanitize_title_for_filename(title);
-        self.state.memos_dir.join(format!("{sanitized_title}.md"))
+        let dir = match notebook {
+            Some(notebook) => self.state.memos_dir.join(notebook),
+            None => self.state.memos_dir.clone(),
+        };
+        dir.join(format!("{sanitized_title}.md"))
+    }
+
+    /// Get the directory of a notebook, checking its name
+    fn notebook_dir(&self, notebook: &str) -> Result<PathBuf> {
+        validate_notebook_name(notebook)?;
+        Ok(self.state.memos_dir.join(notebook))
+    }
+
+    /// Get the directory of a notebook that exists
+    fn existing_notebook_dir(&self, notebook: &str) -> Result<PathBuf> {
+        let dir = self.notebook_dir(notebook)?;
+        if !self.fs.is_dir(&dir) {
+            return Err(SwissArmyHammerError::Other(format!(
+                "Notebook '{notebook}' does not exist"
+            )));
+        }
+        Ok(dir)
+    }
+
+    /// Directories of the notebooks, skipping the storage's own dot directories
+    fn notebook_dirs(&self) -> Result<Vec<PathBuf>> {
+        if !self.fs.exists(&self.state.memos_dir) {
+            return Ok(Vec::new());
+        }
+        let mut dirs: Vec<PathBuf> = self
+            .fs
+            .read_dir(&self.state.memos_dir)?
+            .into_iter()
+            .filter(|path| {
+                self.fs.is_dir(path)
+                    && path
+                        .file_name()
+                        .is_some_and(|name| !name.to_string_lossy().starts_with('.'))
+            })
+            .collect();
+        dirs.sort();
+        Ok(dirs)
+    }
+
+    /// Find the file of a memo, at the top level or in any notebook
+    ///
+    /// IDs are unique across notebooks, so a memo keeps its ID when it moves.
+    fn find_memo_path(&self, id: &MemoId) -> Result<Option<PathBuf>> {
+        let path = self.get_memo_path_from_title(id.as_str());
+        if self.fs.exists(&path) {
+            return Ok(Some(path));
+        }
+        let filename = path.file_name().unwrap_or_default().to_os_string();
+        Ok(self
+            .notebook_dirs()?
+            .into_iter()
+            .map(|dir| dir.join(&filename))
+            .find(|path| self.fs.exists(path)))
+    }
+
+    /// Load the memos in one directory, skipping files that can't be read
+    async fn load_memos_in(&self, dir: &Path) -> Result<Vec<Memo>> {
+        let mut memos = Vec::new();
+        for path in self.fs.read_dir(dir)? {
+            if path.extension().is_some_and(|ext| ext == "md") {
+                match self.load_memo_from_markdown_file(&path).await {
+                    Ok(memo) => memos.push(memo),
+                    Err(e) => {
+                        tracing::warn!(
+                            path = %path.display(),
+                            error = %e,
+                            "Failed to load markdown memo file, skipping"
+                        );
+                        continue;
+                    }
+                }
+            }
+        }
+        Ok(memos)
     }
 
     /// Load and create a memo from a markdown file
@@ -1101,8 +1824,8 @@ impl MarkdownMemoStorage {
     ///
     /// * `Result<Memo>` - The memo object with content and metadata
     async fn load_memo_from_markdown_file(&self, path: &PathBuf) -> Result<Memo> {
-        let content = tokio::fs::read_to_string(path).await?;
-        let metadata = tokio::fs::metadata(path).await?;
+        self.state.validate_memo_path(path)?;
+        let content = self.fs.read_to_string(path)?;
 
         // Extract title from filename (remove .md extension)
         let filename = path
@@ -1118,16 +1841,23 @@ impl MarkdownMemoStorage {
         let title = filename;
 
         // Get timestamps from filesystem
-        let created_at = metadata
-            .created()
-            .or_else(|_| metadata.modified()) // Fall back to modified if created not available
+        let modified = self.fs.modified(path);
+        let created_at = path
+            .metadata()
+            .and_then(|metadata| metadata.created())
+            .ok()
+            .or(modified) // Fall back to modified if created not available
             .map(DateTime::<Utc>::from)
-            .unwrap_or_else(|_| Utc::now());
+            .unwrap_or_else(Utc::now);
 
-        let updated_at = metadata
-            .modified()
-            .map(DateTime::<Utc>::from)
-            .unwrap_or_else(|_| created_at);
+        let updated_at = modified.map(DateTime::<Utc>::from).unwrap_or(created_at);
+
+        // Memos in a subdirectory are filed in the notebook it names
+        let notebook = path
+            .parent()
+            .filter(|parent| *parent != self.state.memos_dir)
+            .and_then(Path::file_name)
+            .map(|name| name.to_string_lossy().to_string());
 
         Ok(Memo {
             id,
@@ -1135,6 +1865,7 @@ impl MarkdownMemoStorage {
             content,
             created_at,
             updated_at,
+            notebook,
         })
     }
 
@@ -1152,33 +1883,198 @@ impl MarkdownMemoStorage {
     /// * `Result<()>` - Success or error if file cannot be written
     async fn save_memo_to_markdown_file(&self, memo: &Memo) -> Result<()> {
         self.ensure_directory_exists().await?;
+        if let Some(notebook) = &memo.notebook {
+            self.fs.create_dir_all(&self.notebook_dir(notebook)?)?;
+        }
 
-        let path = self.get_memo_path_from_title(&memo.title);
-        tokio::fs::write(path, &memo.content).await?;
-        Ok(())
+        let path = self
+            .state
+            .validate_memo_path(&self.memo_path(&memo.title, memo.notebook.as_deref()))?;
+        self.fs.write(&path, &memo.content)
     }
 
     /// Ensure the memo directory exists, creating it if necessary
     async fn ensure_directory_exists(&self) -> Result<()> {
-        if !self.state.memos_dir.exists() {
-            tokio::fs::create_dir_all(&self.state.memos_dir).await?;
+        if !self.fs.exists(&self.state.memos_dir) {
+            self.fs.create_dir_all(&self.state.memos_dir)?;
         }
         Ok(())
     }
 
+    /// Change logs of the memos in this storage
+    fn history(&self) -> ContentHistory {
+        ContentHistory::new(self.state.history_dir(), self.fs.clone())
+    }
+
+    /// Tell subscribers a memo changed
+    fn publish_change(id: &MemoId, change: ChangeKind) {
+        event_bus().publish(Event::MemoUpdated {
+            id: id.as_str().to_string(),
+            change,
+        });
+    }
+
     /// Index a memo in the search engine if available
     async fn index_memo_if_available(&self, memo: &Memo) -> Result<()> {
         if let Some(search_engine) = &self.search_engine {
             search_engine.index_memo(memo).await?;
         }
-        Ok(())
+        Ok(())
+    }
+
+    /// Remove a memo from the search engine index if available
+    async fn remove_memo_from_index_if_available(&self, memo_id: &MemoId) -> Result<()> {
+        if let Some(search_engine) = &self.search_engine {
+            search_engine.remove_memo(memo_id).await?;
+        }
+        Ok(())
+    }
+
+    /// Record a backup snapshot and checksum for freshly written memo content
+    ///
+    /// Keeps the newest [`MAX_BACKUP_SNAPSHOTS`] snapshots per memo.
+    async fn record_snapshot(&self, id: &MemoId, content: &str) -> Result<()> {
+        let _lock = self.integrity_lock.lock().await;
+
+        let snapshot_dir = self.state.backups_dir().join(id.as_str());
+        self.fs.create_dir_all(&snapshot_dir)?;
+        let snapshot_path = snapshot_dir.join(format!("{}.md", generate_monotonic_ulid()));
+        self.fs.write(&snapshot_path, content)?;
+
+        for stale in self
+            .snapshots(&snapshot_dir)?
+            .iter()
+            .skip(MAX_BACKUP_SNAPSHOTS)
+        {
+            self.fs.remove_file(stale)?;
+        }
+
+        self.fs.write(
+            &self.state.checksum_path(id.as_str()),
+            &checksum(content.as_bytes()),
+        )
+    }
+
+    /// Forget the checksum and backup snapshots of a deleted memo
+    async fn remove_integrity_metadata(&self, id: &MemoId) -> Result<()> {
+        let _lock = self.integrity_lock.lock().await;
+
+        let snapshot_dir = self.state.backups_dir().join(id.as_str());
+        if self.fs.exists(&snapshot_dir) {
+            self.fs.remove_dir_all(&snapshot_dir)?;
+        }
+        Ok(())
+    }
+
+    /// The snapshot files in a memo's backup directory, newest first
+    fn snapshots(&self, snapshot_dir: &Path) -> Result<Vec<PathBuf>> {
+        let mut snapshots: Vec<PathBuf> = self
+            .fs
+            .read_dir(snapshot_dir)?
+            .into_iter()
+            .filter(|path| path.extension().is_some_and(|ext| ext == "md"))
+            .collect();
+        // Snapshot names are ULIDs, so lexical order is chronological
+        snapshots.sort();
+        snapshots.reverse();
+        Ok(snapshots)
+    }
+
+    /// Checksum recorded for a memo's last write, if any
+    fn recorded_checksum(&self, id: &str) -> Option<String> {
+        let path = self.state.checksum_path(id);
+        if !self.fs.exists(&path) {
+            return None;
+        }
+        match self.fs.read_to_string(&path) {
+            Ok(recorded) => Some(recorded.trim().to_string()),
+            Err(e) => {
+                tracing::warn!(
+                    path = %path.display(),
+                    error = %e,
+                    "Cannot read memo checksum, checking the memo without it"
+                );
+                None
+            }
+        }
+    }
+
+    /// Check a single memo file against its recorded checksum
+    ///
+    /// Returns the reason the file is corrupted, or `None` if it is valid.
+    fn check_memo_file(&self, path: &Path, expected: Option<&str>) -> Option<String> {
+        if let Err(e) = self.state.validate_memo_path(path) {
+            return Some(e.to_string());
+        }
+        if !self.fs.exists(path) {
+            return Some("memo file is missing".to_string());
+        }
+
+        let content = match self.fs.read_to_string(path) {
+            Ok(content) => content,
+            Err(e) => return Some(format!("cannot read memo file: {e}")),
+        };
+
+        match expected {
+            Some(expected) if expected != checksum(content.as_bytes()) => {
+                Some("checksum does not match the last write".to_string())
+            }
+            _ => None,
+        }
+    }
+
+    /// Restore a memo from its most recent snapshot
+    ///
+    /// The content being replaced is kept in the memo's history, so a memo
+    /// edited by hand can still be brought back with `restore_memo`.
+    async fn restore_from_snapshot(&self, id: &str, path: &Path) -> Result<Option<PathBuf>> {
+        let snapshot_dir = self.state.backups_dir().join(id);
+        if !self.fs.exists(&snapshot_dir) {
+            return Ok(None);
+        }
+
+        for snapshot in self.snapshots(&snapshot_dir)? {
+            let Ok(content) = self.fs.read_to_string(&snapshot) else {
+                continue;
+            };
+
+            let previous = self.fs.read_to_string(path).ok();
+            self.history()
+                .record(id, previous.as_deref(), ChangeKind::Restored, &content)?;
+            self.fs.write(path, &content)?;
+            let _lock = self.integrity_lock.lock().await;
+            self.fs
+                .write(&self.state.checksum_path(id), &checksum(content.as_bytes()))?;
+            return Ok(Some(snapshot));
+        }
+        Ok(None)
+    }
+
+    /// Total size of a file, or of every file under a directory
+    fn disk_usage(&self, path: &Path) -> u64 {
+        if self.fs.is_dir(path) {
+            self.fs
+                .read_dir(path)
+                .unwrap_or_default()
+                .iter()
+                .map(|entry| self.disk_usage(entry))
+                .sum()
+        } else {
+            std::fs::metadata(path).map_or(0, |metadata| metadata.len())
+        }
     }
 
-    /// Remove a memo from the search engine index if available
-    async fn remove_memo_from_index_if_available(&self, memo_id: &MemoId) -> Result<()> {
-        if let Some(search_engine) = &self.search_engine {
-            search_engine.remove_memo(memo_id).await?;
+    /// Remove a leftover file or directory found during compaction and record it
+    fn remove_leftover(&self, path: PathBuf, report: &mut MemoCompactionReport) -> Result<()> {
+        report.reclaimed_bytes += self.disk_usage(&path);
+        if !report.dry_run {
+            if self.fs.is_dir(&path) {
+                self.fs.remove_dir_all(&path)?;
+            } else {
+                self.fs.remove_file(&path)?;
+            }
         }
+        report.removed_files.push(path);
         Ok(())
     }
 }
@@ -1186,103 +2082,183 @@ impl MarkdownMemoStorage {
 #[async_trait]
 impl MemoStorage for MarkdownMemoStorage {
     async fn create_memo(&self, title: String, content: String) -> Result<Memo> {
-        let _lock = self.creation_lock.lock().await;
+        self.create_memo_in_notebook(title, content, None).await
+    }
 
-        // Check if a file with this title already exists
-        let path = self.get_memo_path_from_title(&title);
-        if path.exists() {
-            return Err(SwissArmyHammerError::MemoAlreadyExists(title));
+    async fn create_memo_in_notebook(
+        &self,
+        title: String,
+        content: String,
+        notebook: Option<&str>,
+    ) -> Result<Memo> {
+        let _lock = self.creation_lock.lock().await;
+        if let Some(notebook) = notebook {
+            validate_notebook_name(notebook)?;
         }
 
         // Create memo with filename-based ID (as specified in issue requirements)
         let sanitized_title = Self::sanitize_title_for_filename(&title);
         let id = MemoId::from_filename(&sanitized_title);
-        let now = Utc::now();
 
+        // Check if a memo with this ID already exists in any notebook
+        if self.find_memo_path(&id)?.is_some() {
+            return Err(SwissArmyHammerError::MemoAlreadyExists(title));
+        }
+
+        let now = Utc::now();
         let memo = Memo {
             id,
             title,
             content,
             created_at: now,
             updated_at: now,
+            notebook: notebook.map(str::to_string),
         };
 
+        self.history()
+            .record(memo.id.as_str(), None, ChangeKind::Created, &memo.content)?;
         self.save_memo_to_markdown_file(&memo).await?;
+        self.record_snapshot(&memo.id, &memo.content).await?;
 
         // Index the memo in the search engine if available
         self.index_memo_if_available(&memo).await?;
+        Self::publish_change(&memo.id, ChangeKind::Created);
 
         Ok(memo)
     }
 
     async fn get_memo(&self, id: &MemoId) -> Result<Memo> {
-        // Since ID is now the filename, we can directly construct the path
-        let path = self.get_memo_path_from_title(id.as_str());
-
-        if !path.exists() {
-            return Err(SwissArmyHammerError::MemoNotFound(id.as_str().to_string()));
-        }
+        // Since ID is now the filename, only the notebook has to be found
+        let path = self
+            .find_memo_path(id)?
+            .ok_or_else(|| SwissArmyHammerError::MemoNotFound(id.as_str().to_string()))?;
 
         self.load_memo_from_markdown_file(&path).await
     }
 
     async fn update_memo(&self, id: &MemoId, content: String) -> Result<Memo> {
         let mut memo = self.get_memo(id).await?;
+        // Record the change first so the old content is kept even if the write fails
+        self.history().record(
+            id.as_str(),
+            Some(&memo.content),
+            ChangeKind::Updated,
+            &content,
+        )?;
         memo.update_content(content);
 
         // Since we're updating content only, the filename stays the same
         self.save_memo_to_markdown_file(&memo).await?;
+        self.record_snapshot(&memo.id, &memo.content).await?;
 
         // Update the memo in the search engine if available
         self.index_memo_if_available(&memo).await?;
+        Self::publish_change(&memo.id, ChangeKind::Updated);
 
         Ok(memo)
     }
 
     async fn delete_memo(&self, id: &MemoId) -> Result<()> {
         let memo = self.get_memo(id).await?;
-        let path = self.get_memo_path_from_title(&memo.title);
+        let path = self.memo_path(&memo.title, memo.notebook.as_deref());
 
-        if !path.exists() {
+        if !self.fs.exists(&path) {
             return Err(SwissArmyHammerError::MemoNotFound(id.as_str().to_string()));
         }
 
-        tokio::fs::remove_file(path).await?;
+        self.history()
+            .record(id.as_str(), Some(&memo.content), ChangeKind::Deleted, "")?;
+        self.fs.remove_file(&path)?;
+        self.remove_integrity_metadata(id).await?;
 
         // Remove the memo from the search engine if available
         self.remove_memo_from_index_if_available(id).await?;
+        Self::publish_change(id, ChangeKind::Deleted);
 
         Ok(())
     }
 
     async fn list_memos(&self) -> Result<Vec<Memo>> {
-        if !self.state.memos_dir.exists() {
+        if !self.fs.exists(&self.state.memos_dir) {
             return Ok(Vec::new());
         }
 
-        let mut memos = Vec::new();
-        let mut dir_entries = tokio::fs::read_dir(&self.state.memos_dir).await?;
-
-        while let Some(entry) = dir_entries.next_entry().await? {
-            let path = entry.path();
-            if path.extension().is_some_and(|ext| ext == "md") {
-                match self.load_memo_from_markdown_file(&path).await {
-                    Ok(memo) => memos.push(memo),
-                    Err(e) => {
-                        tracing::warn!(
-                            path = %path.display(),
-                            error = %e,
-                            "Failed to load markdown memo file, skipping"
-                        );
-                        continue;
-                    }
-                }
-            }
+        let mut memos = self.load_memos_in(&self.state.memos_dir).await?;
+        for dir in self.notebook_dirs()? {
+            memos.extend(self.load_memos_in(&dir).await?);
         }
 
         Ok(memos)
     }
 
+    async fn list_memos_in_notebook(&self, notebook: &str) -> Result<Vec<Memo>> {
+        let dir = self.existing_notebook_dir(notebook)?;
+        self.load_memos_in(&dir).await
+    }
+
+    async fn list_notebooks(&self) -> Result<Vec<String>> {
+        Ok(self
+            .notebook_dirs()?
+            .iter()
+            .filter_map(|dir| dir.file_name())
+            .map(|name| name.to_string_lossy().to_string())
+            .collect())
+    }
+
+    async fn create_notebook(&self, notebook: &str) -> Result<()> {
+        let dir = self.notebook_dir(notebook)?;
+        if self.fs.exists(&dir) {
+            return Err(SwissArmyHammerError::Other(format!(
+                "Notebook '{notebook}' already exists"
+            )));
+        }
+        self.fs.create_dir_all(&dir)
+    }
+
+    async fn delete_notebook(&self, notebook: &str) -> Result<()> {
+        let dir = self.existing_notebook_dir(notebook)?;
+        let memos = self.load_memos_in(&dir).await?;
+        if !memos.is_empty() {
+            return Err(SwissArmyHammerError::Other(format!(
+                "Notebook '{notebook}' still has {} memos; move or delete them first",
+                memos.len()
+            )));
+        }
+        self.fs.remove_dir_all(&dir)
+    }
+
+    async fn rename_notebook(&self, notebook: &str, new_name: &str) -> Result<()> {
+        let dir = self.existing_notebook_dir(notebook)?;
+        let new_dir = self.notebook_dir(new_name)?;
+        if self.fs.exists(&new_dir) {
+            return Err(SwissArmyHammerError::Other(format!(
+                "Notebook '{new_name}' already exists"
+            )));
+        }
+        self.fs.rename(&dir, &new_dir)
+    }
+
+    async fn move_memo(&self, id: &MemoId, notebook: Option<&str>) -> Result<Memo> {
+        let mut memo = self.get_memo(id).await?;
+        if memo.notebook.as_deref() == notebook {
+            return Ok(memo);
+        }
+
+        let from = self.memo_path(&memo.title, memo.notebook.as_deref());
+        if let Some(notebook) = notebook {
+            self.fs.create_dir_all(&self.notebook_dir(notebook)?)?;
+        }
+        let to = self
+            .state
+            .validate_memo_path(&self.memo_path(&memo.title, notebook))?;
+        self.fs.rename(&from, &to)?;
+        memo.notebook = notebook.map(str::to_string);
+
+        self.index_memo_if_available(&memo).await?;
+        Self::publish_change(&memo.id, ChangeKind::Updated);
+        Ok(memo)
+    }
+
     async fn search_memos(&self, query: &str) -> Result<Vec<Memo>> {
         let all_memos = self.list_memos().await?;
         let query_lower = query.to_lowercase();
@@ -1373,45 +2349,158 @@ impl MemoStorage for MarkdownMemoStorage {
     }
 
     async fn get_all_context(&self, options: &crate::memoranda::ContextOptions) -> Result<String> {
-        let all_memos = self.list_memos().await?;
+        let memos = self.list_memos().await?;
+        Ok(crate::memoranda::MemoContext::build(memos, options).text)
+    }
 
-        if all_memos.is_empty() {
-            return Ok(String::new());
-        }
+    async fn memo_history(&self, id: &MemoId) -> Result<Vec<ContentChange>> {
+        self.history().changes(id.as_str())
+    }
 
-        let mut context = String::new();
+    async fn restore_memo(&self, id: &MemoId, version: usize) -> Result<Memo> {
+        let history = self.history();
+        let change = history.change(id.as_str(), version)?;
+        let content = change.restorable_content()?;
 
-        // Sort memos by creation date (newest first)
-        let mut sorted_memos = all_memos;
-        sorted_memos.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        // A deleted memo is restored at the top level
+        let existing = self.find_memo_path(id)?;
+        let previous = match &existing {
+            Some(path) => Some(self.load_memo_from_markdown_file(path).await?.content),
+            None => None,
+        };
+        let path = existing.unwrap_or_else(|| self.get_memo_path_from_title(id.as_str()));
+        history.record_restore(id.as_str(), previous.as_deref(), version, content)?;
 
-        for (i, memo) in sorted_memos.iter().enumerate() {
-            if i > 0 {
-                context.push_str(&options.delimiter);
-            }
+        self.ensure_directory_exists().await?;
+        let path = self.state.validate_memo_path(&path)?;
+        self.fs.write(&path, content)?;
+        self.record_snapshot(id, content).await?;
+
+        let memo = self.load_memo_from_markdown_file(&path).await?;
+        self.index_memo_if_available(&memo).await?;
+        Self::publish_change(&memo.id, ChangeKind::Restored);
+        Ok(memo)
+    }
 
-            if options.include_metadata {
-                context.push_str(&format!(
-                    "# {} ({})\nCreated: {} | Updated: {}\n\n",
-                    memo.title,
-                    memo.id.as_str(),
-                    memo.created_at.format("%Y-%m-%d %H:%M:%S UTC"),
-                    memo.updated_at.format("%Y-%m-%d %H:%M:%S UTC")
-                ));
+    async fn verify_integrity(&self, restore: bool) -> Result<MemoIntegrityReport> {
+        if !self.fs.exists(&self.state.memos_dir) {
+            return Ok(MemoIntegrityReport::default());
+        }
+
+        // Check every memo on disk plus any recorded memo whose file has gone
+        // missing, which is restored at the top level
+        let mut paths = std::collections::BTreeMap::new();
+        let backups_dir = self.state.backups_dir();
+        if self.fs.is_dir(&backups_dir) {
+            for snapshot_dir in self.fs.read_dir(&backups_dir)? {
+                let Some(id) = snapshot_dir.file_name() else {
+                    continue;
+                };
+                let id = id.to_string_lossy().into_owned();
+                if self.fs.exists(&self.state.checksum_path(&id)) {
+                    let path = self.get_memo_path_from_title(&id);
+                    paths.insert(id, path);
+                }
+            }
+        }
+        let mut dirs = vec![self.state.memos_dir.clone()];
+        dirs.extend(self.notebook_dirs()?);
+        for dir in dirs {
+            for path in self.fs.read_dir(&dir)? {
+                if path.extension().is_some_and(|ext| ext == "md") {
+                    if let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) {
+                        paths.insert(stem.to_string(), path);
+                    }
+                }
             }
+        }
+
+        let mut report = MemoIntegrityReport::default();
+        for (id, path) in paths {
+            let expected = self.recorded_checksum(&id);
+            let status = match self.check_memo_file(&path, expected.as_deref()) {
+                None => MemoIntegrityStatus::Valid,
+                Some(reason) if !restore => MemoIntegrityStatus::Corrupted { reason },
+                Some(reason) => match self.restore_from_snapshot(&id, &path).await? {
+                    Some(snapshot) => {
+                        tracing::info!("Restored memo {} from {}", id, snapshot.display());
+                        MemoIntegrityStatus::Restored { reason, snapshot }
+                    }
+                    None => MemoIntegrityStatus::Unrecoverable { reason },
+                },
+            };
+            report.entries.push(MemoIntegrityEntry { id, path, status });
+        }
+
+        Ok(report)
+    }
+
+    async fn compact(&self, dry_run: bool) -> Result<MemoCompactionReport> {
+        let mut report = MemoCompactionReport {
+            dry_run,
+            ..Default::default()
+        };
+        if !self.fs.exists(&self.state.memos_dir) {
+            return Ok(report);
+        }
+
+        // Hold the creation lock so no memo is created while files are removed
+        let _lock = self.creation_lock.lock().await;
 
-            context.push_str(&memo.content);
+        let mut dirs = vec![self.state.memos_dir.clone()];
+        dirs.extend(self.notebook_dirs()?);
+        for dir in dirs {
+            for path in self.fs.read_dir(&dir)? {
+                if is_temp_file(&path) {
+                    self.remove_leftover(path, &mut report)?;
+                }
+            }
+        }
 
-            // Rough token estimation (4 characters per token)
-            if let Some(max_tokens) = options.max_tokens {
-                let estimated_tokens = context.len() / 4;
-                if estimated_tokens >= max_tokens {
-                    break;
+        // Backups of memos that are gone and can no longer be restored
+        let backups_dir = self.state.backups_dir();
+        if self.fs.is_dir(&backups_dir) {
+            for snapshot_dir in self.fs.read_dir(&backups_dir)? {
+                let Some(id) = snapshot_dir.file_name() else {
+                    continue;
+                };
+                let id = id.to_string_lossy().into_owned();
+                let memo_exists = self.find_memo_path(&MemoId::from_filename(&id))?.is_some();
+                if !memo_exists && !self.fs.exists(&self.state.checksum_path(&id)) {
+                    self.remove_leftover(snapshot_dir, &mut report)?;
+                } else if self.fs.is_dir(&snapshot_dir) {
+                    for snapshot in self.fs.read_dir(&snapshot_dir)? {
+                        if is_temp_file(&snapshot) {
+                            self.remove_leftover(snapshot, &mut report)?;
+                        }
+                    }
                 }
             }
         }
 
-        Ok(context)
+        // Memo IDs are file names, so markdown memos have no duplicates to
+        // remove and no other form to be rewritten in
+        let memos = self.list_memos().await?;
+        if !dry_run {
+            if let Some(search_engine) = &self.search_engine {
+                search_engine.rebuild_index(&memos).await?;
+                report.index_rebuilt = true;
+            } else if self.fs.exists(&self.state.search_index_path()) {
+                // Storage opened without search still rebuilds an index left on disk
+                let search_engine =
+                    AdvancedMemoSearchEngine::new_persistent(self.state.search_index_path())
+                        .await?;
+                search_engine.rebuild_index(&memos).await?;
+                report.index_rebuilt = true;
+            }
+        }
+        report.memos = memos.len();
+
+        tracing::info!(
+            "Compacted memos: {} files removed",
+            report.removed_files.len()
+        );
+        Ok(report)
     }
 }
 
@@ -1440,6 +2529,186 @@ mod tests {
         assert!(!memo.id.as_str().is_empty());
     }
 
+    #[tokio::test]
+    async fn test_verify_integrity_reports_valid_memos() {
+        let (storage, _temp_dir) = create_test_storage();
+        let memo = storage
+            .create_memo("Title".to_string(), "Content".to_string())
+            .await
+            .unwrap();
+        storage
+            .update_memo(&memo.id, "Updated".to_string())
+            .await
+            .unwrap();
+
+        let report = storage.verify_integrity(false).await.unwrap();
+        assert_eq!(report.entries.len(), 1);
+        assert_eq!(report.entries[0].status, MemoIntegrityStatus::Valid);
+        assert!(report.is_healthy());
+
+        // The checksum of the last write is kept beside the memo's snapshots
+        let recorded =
+            std::fs::read_to_string(storage.state.checksum_path(memo.id.as_str())).unwrap();
+        let contents = std::fs::read(storage.get_memo_path(&memo.id)).unwrap();
+        assert_eq!(recorded, checksum(&contents));
+
+        // Storage metadata is not listed as a memo
+        assert_eq!(storage.list_memos().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_verify_integrity_detects_and_restores_truncated_memo() {
+        let (storage, _temp_dir) = create_test_storage();
+        let memo = storage
+            .create_memo("Title".to_string(), "Content".to_string())
+            .await
+            .unwrap();
+        let path = storage.get_memo_path(&memo.id);
+
+        // Simulate a partial write
+        let contents = std::fs::read_to_string(&path).unwrap();
+        std::fs::write(&path, &contents[..contents.len() / 2]).unwrap();
+
+        let report = storage.verify_integrity(false).await.unwrap();
+        assert!(matches!(
+            report.entries[0].status,
+            MemoIntegrityStatus::Corrupted { .. }
+        ));
+        assert!(!report.is_healthy());
+
+        let report = storage.verify_integrity(true).await.unwrap();
+        assert!(matches!(
+            status(&report),
+            MemoIntegrityStatus::Restored { .. }
+        ));
+        let restored = storage.get_memo(&memo.id).await.unwrap();
+        assert_eq!(restored.content, "Content");
+
+        let report = storage.verify_integrity(false).await.unwrap();
+        assert_eq!(report.entries[0].status, MemoIntegrityStatus::Valid);
+    }
+
+    #[tokio::test]
+    async fn test_verify_integrity_detects_checksum_mismatch_and_missing_file() {
+        let (storage, _temp_dir) = create_test_storage();
+        let memo = storage
+            .create_memo("Title".to_string(), "Content".to_string())
+            .await
+            .unwrap();
+        let path = storage.get_memo_path(&memo.id);
+
+        // Valid JSON that was not written by the storage
+        let mut tampered = memo.clone();
+        tampered.content = "Tampered".to_string();
+        std::fs::write(&path, serde_json::to_string(&tampered).unwrap()).unwrap();
+        let report = storage.verify_integrity(false).await.unwrap();
+        assert!(matches!(
+            report.entries[0].status,
+            MemoIntegrityStatus::Corrupted { .. }
+        ));
+
+        std::fs::remove_file(&path).unwrap();
+        let snapshot_dir = storage.state.backups_dir().join(memo.id.as_str());
+        for snapshot in list_snapshots(&snapshot_dir).await.unwrap() {
+            std::fs::remove_file(snapshot).unwrap();
+        }
+        let report = storage.verify_integrity(true).await.unwrap();
+        assert!(matches!(
+            report.entries[0].status,
+            MemoIntegrityStatus::Unrecoverable { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_backup_snapshots_are_pruned_and_removed_on_delete() {
+        let (storage, _temp_dir) = create_test_storage();
+        let memo = storage
+            .create_memo("Title".to_string(), "v0".to_string())
+            .await
+            .unwrap();
+        for i in 1..=MAX_BACKUP_SNAPSHOTS + 2 {
+            storage
+                .update_memo(&memo.id, format!("v{i}"))
+                .await
+                .unwrap();
+        }
+
+        let snapshot_dir = storage.state.backups_dir().join(memo.id.as_str());
+        assert_eq!(
+            list_snapshots(&snapshot_dir).await.unwrap().len(),
+            MAX_BACKUP_SNAPSHOTS
+        );
+
+        storage.delete_memo(&memo.id).await.unwrap();
+        assert!(!snapshot_dir.exists());
+        assert!(storage
+            .verify_integrity(false)
+            .await
+            .unwrap()
+            .entries
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_compact_removes_cruft_and_duplicates() {
+        let (storage, _temp_dir) = create_test_storage();
+        let original = storage
+            .create_memo("Title".to_string(), "Body".to_string())
+            .await
+            .unwrap();
+        let duplicate = storage
+            .create_memo("Title".to_string(), "Body".to_string())
+            .await
+            .unwrap();
+        storage
+            .create_memo("Other".to_string(), "Body".to_string())
+            .await
+            .unwrap();
+
+        // A memo written before checksums were recorded, in compact JSON
+        let legacy = Memo::new("Legacy".to_string(), "Old".to_string());
+        let legacy_path = storage.get_memo_path(&legacy.id);
+        tokio::fs::write(&legacy_path, serde_json::to_string(&legacy).unwrap())
+            .await
+            .unwrap();
+
+        let memos_dir = &storage.state.memos_dir;
+        let temp_file = memos_dir.join("interrupted.json.tmp");
+        tokio::fs::write(&temp_file, "{\"id\":").await.unwrap();
+        let orphan_backups = storage.state.backups_dir().join("01ORPHANEDMEMO");
+        tokio::fs::create_dir_all(&orphan_backups).await.unwrap();
+        tokio::fs::write(orphan_backups.join("snapshot.json"), "{}")
+            .await
+            .unwrap();
+
+        let report = storage.compact(true).await.unwrap();
+        assert_eq!(report.memos, 3);
+        assert_eq!(report.removed_files.len(), 2);
+        assert_eq!(
+            report.duplicates,
+            vec![MemoDuplicate {
+                id: duplicate.id.as_str().to_string(),
+                duplicate_of: original.id.as_str().to_string(),
+            }]
+        );
+        assert_eq!(report.rewritten, vec![legacy.id.as_str().to_string()]);
+        assert!(report.reclaimed_bytes > 0);
+        assert!(temp_file.exists());
+        assert_eq!(storage.list_memos().await.unwrap().len(), 4);
+
+        let report = storage.compact(false).await.unwrap();
+        assert_eq!(report.duplicates.len(), 1);
+        assert!(!temp_file.exists());
+        assert!(!orphan_backups.exists());
+        assert!(storage.get_memo(&duplicate.id).await.is_err());
+        assert_eq!(
+            tokio::fs::read_to_string(&legacy_path).await.unwrap(),
+            serde_json::to_string_pretty(&legacy).unwrap()
+        );
+        assert!(storage.verify_integrity(false).await.unwrap().is_healthy());
+        assert!(storage.compact(false).await.unwrap().is_clean());
+    }
+
     #[tokio::test]
     async fn test_get_memo() {
         let (storage, _temp_dir) = create_test_storage();
@@ -1542,6 +2811,32 @@ mod tests {
         assert_eq!(memos.len(), 0);
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_list_memos_skips_symlinks_outside_memos_dir() {
+        let (storage, _temp_dir) = create_test_storage();
+        let memo = storage
+            .create_memo("Inside".to_string(), "Content".to_string())
+            .await
+            .unwrap();
+
+        // A memo file elsewhere on disk, linked into the memos directory
+        let outside = TempDir::new().unwrap();
+        let outside_memo = outside.path().join("outside.json");
+        let foreign = Memo::new("Outside".to_string(), "Secret".to_string());
+        std::fs::write(&outside_memo, serde_json::to_string(&foreign).unwrap()).unwrap();
+        let link = storage
+            .state
+            .memos_dir
+            .join(format!("{}.json", foreign.id.as_str()));
+        std::os::unix::fs::symlink(&outside_memo, &link).unwrap();
+
+        let memos = storage.list_memos().await.unwrap();
+        assert_eq!(memos.len(), 1);
+        assert_eq!(memos[0].id, memo.id);
+        assert!(storage.get_memo(&foreign.id).await.is_err());
+    }
+
     #[tokio::test]
     async fn test_search_memos() {
         let (storage, _temp_dir) = create_test_storage();
@@ -1866,6 +3161,58 @@ mod tests {
         assert_eq!(retrieved.content, large_content);
     }
 
+    #[tokio::test]
+    async fn test_large_memo_body_is_compressed_on_disk() {
+        let temp_dir = TempDir::new().unwrap();
+        let memos_dir = temp_dir.path().join("memos");
+        let log = "2024-01-01 12:00:00 ERROR connection refused, retrying\n".repeat(20_000);
+
+        // A memo written before compression is plain JSON
+        let plain = FileSystemMemoStorage::new(memos_dir.clone()).with_compression_threshold(0);
+        let legacy = plain
+            .create_memo("Legacy log".to_string(), log.clone())
+            .await
+            .unwrap();
+        let legacy_path = memos_dir.join(format!("{}.json", legacy.id.as_str()));
+        let plain_size = std::fs::metadata(&legacy_path).unwrap().len();
+
+        let storage =
+            FileSystemMemoStorage::new(memos_dir.clone()).with_compression_threshold(1024);
+        assert_eq!(storage.get_memo(&legacy.id).await.unwrap().content, log);
+
+        let memo = storage
+            .create_memo("Pasted log".to_string(), log.clone())
+            .await
+            .unwrap();
+        let path = memos_dir.join(format!("{}.json", memo.id.as_str()));
+        let on_disk = std::fs::read_to_string(&path).unwrap();
+        assert!(on_disk.contains("\"content_zstd\""));
+        assert!(on_disk.contains("Pasted log"));
+        assert!((on_disk.len() as u64) < plain_size / 10);
+
+        let retrieved = storage.get_memo(&memo.id).await.unwrap();
+        assert_eq!(retrieved.content, log);
+        assert_eq!(storage.list_memos().await.unwrap().len(), 2);
+        assert_eq!(
+            storage
+                .search_memos("connection refused")
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+
+        let report = storage.verify_integrity(false).await.unwrap();
+        assert!(report.is_healthy());
+
+        // Updating the legacy memo writes it compressed
+        storage
+            .update_memo(&legacy.id, format!("{log}done\n"))
+            .await
+            .unwrap();
+        assert!(std::fs::metadata(&legacy_path).unwrap().len() < plain_size / 10);
+    }
+
     #[tokio::test]
     async fn test_unicode_content() {
         let (storage, _temp_dir) = create_test_storage();
@@ -2664,4 +4011,201 @@ mod tests {
         assert_eq!(results.len(), 1);
         assert!(!results[0].highlights.is_empty());
     }
+
+    #[tokio::test]
+    async fn test_markdown_history_and_restore() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = MarkdownMemoStorage::new(temp_dir.path().join("memos"));
+
+        let memo = storage
+            .create_memo("Plans".to_string(), "Ship on Friday".to_string())
+            .await
+            .unwrap();
+        storage
+            .update_memo(&memo.id, "Ship on Monday".to_string())
+            .await
+            .unwrap();
+        storage.delete_memo(&memo.id).await.unwrap();
+
+        let history = storage.memo_history(&memo.id).await.unwrap();
+        let kinds: Vec<ChangeKind> = history.iter().map(|change| change.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ChangeKind::Created,
+                ChangeKind::Updated,
+                ChangeKind::Deleted
+            ]
+        );
+        assert!(storage.restore_memo(&memo.id, 3).await.is_err());
+
+        // A deleted memo comes back from its history
+        let restored = storage.restore_memo(&memo.id, 1).await.unwrap();
+        assert_eq!(restored.content, "Ship on Friday");
+        let history = storage.memo_history(&memo.id).await.unwrap();
+        assert_eq!(history.len(), 4);
+        assert_eq!(history[3].kind, ChangeKind::Restored);
+        assert_eq!(history[3].restored_from, Some(1));
+
+        // The history directory is not listed as a memo
+        assert_eq!(storage.list_memos().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_markdown_verify_integrity_detects_and_restores() {
+        let temp_dir = TempDir::new().unwrap();
+        let memos_dir = temp_dir.path().join("memos");
+        let storage = MarkdownMemoStorage::new(memos_dir.clone());
+
+        let memo = storage
+            .create_memo("Plans".to_string(), "Ship on Friday".to_string())
+            .await
+            .unwrap();
+        storage
+            .create_memo_in_notebook("Lunch".to_string(), "Tacos".to_string(), Some("food"))
+            .await
+            .unwrap();
+        let report = storage.verify_integrity(false).await.unwrap();
+        assert_eq!(report.entries.len(), 2);
+        assert!(report.is_healthy());
+        let status = |report: &MemoIntegrityReport| {
+            report
+                .entries
+                .iter()
+                .find(|entry| entry.id == "Plans")
+                .map(|entry| entry.status.clone())
+                .unwrap()
+        };
+
+        // A truncated write is reported, then restored from the last snapshot
+        let path = memos_dir.join("Plans.md");
+        std::fs::write(&path, "Ship on").unwrap();
+        let report = storage.verify_integrity(false).await.unwrap();
+        assert!(!report.is_healthy());
+        assert!(matches!(
+            status(&report),
+            MemoIntegrityStatus::Corrupted { reason } if reason.contains("checksum")
+        ));
+
+        let report = storage.verify_integrity(true).await.unwrap();
+        assert!(report.is_healthy());
+        assert!(matches!(
+            status(&report),
+            MemoIntegrityStatus::Restored { .. }
+        ));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "Ship on Friday");
+
+        // The replaced content is kept in the history
+        let history = storage.memo_history(&memo.id).await.unwrap();
+        assert!(history.iter().any(|change| change.content == "Ship on"));
+
+        // A memo file that disappeared is reported missing
+        std::fs::remove_file(&path).unwrap();
+        let report = storage.verify_integrity(false).await.unwrap();
+        assert!(matches!(
+            status(&report),
+            MemoIntegrityStatus::Corrupted { reason } if reason == "memo file is missing"
+        ));
+
+        // Deleted memos are no longer checked
+        storage.verify_integrity(true).await.unwrap();
+        storage.delete_memo(&memo.id).await.unwrap();
+        assert_eq!(
+            storage.verify_integrity(false).await.unwrap().entries.len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_markdown_compact_removes_leftovers() {
+        let temp_dir = TempDir::new().unwrap();
+        let memos_dir = temp_dir.path().join("memos");
+        let storage = MarkdownMemoStorage::new(memos_dir.clone());
+        storage
+            .create_memo("Plans".to_string(), "Ship on Friday".to_string())
+            .await
+            .unwrap();
+
+        let temp_file = memos_dir.join("Plans.123.tmp");
+        std::fs::write(&temp_file, "Ship on").unwrap();
+        let orphan = memos_dir.join(".backups").join("Gone");
+        std::fs::create_dir_all(&orphan).unwrap();
+        std::fs::write(orphan.join("01K0000000000000000000000.md"), "Old").unwrap();
+
+        let report = storage.compact(true).await.unwrap();
+        assert_eq!(report.memos, 1);
+        assert_eq!(report.removed_files.len(), 2);
+        assert!(temp_file.exists());
+
+        let report = storage.compact(false).await.unwrap();
+        assert_eq!(report.removed_files.len(), 2);
+        assert!(!temp_file.exists());
+        assert!(!orphan.exists());
+        assert!(storage.verify_integrity(false).await.unwrap().is_healthy());
+        assert!(storage.compact(false).await.unwrap().is_clean());
+    }
+
+    #[tokio::test]
+    async fn test_markdown_notebooks_keep_memo_ids() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = MarkdownMemoStorage::new(temp_dir.path().join("memos"));
+
+        let plan = storage
+            .create_memo_in_notebook("Plan".to_string(), "Ship it".to_string(), Some("work"))
+            .await
+            .unwrap();
+        assert_eq!(plan.notebook.as_deref(), Some("work"));
+        assert!(temp_dir.path().join("memos/work/Plan.md").exists());
+        storage
+            .create_memo("Groceries".to_string(), "Milk".to_string())
+            .await
+            .unwrap();
+
+        // IDs are unique across notebooks
+        assert!(matches!(
+            storage
+                .create_memo("Plan".to_string(), "Again".to_string())
+                .await,
+            Err(SwissArmyHammerError::MemoAlreadyExists(_))
+        ));
+
+        // The history directory is not a notebook
+        storage
+            .update_memo(&plan.id, "Shipped".to_string())
+            .await
+            .unwrap();
+        assert_eq!(storage.list_notebooks().await.unwrap(), vec!["work"]);
+        assert_eq!(storage.list_memos().await.unwrap().len(), 2);
+        let work = storage.list_memos_in_notebook("work").await.unwrap();
+        assert_eq!(work.len(), 1);
+        assert_eq!(work[0].content, "Shipped");
+
+        // Moving keeps the ID, content and history
+        let moved = storage.move_memo(&plan.id, Some("archive")).await.unwrap();
+        assert_eq!(moved.id, plan.id);
+        let fetched = storage.get_memo(&plan.id).await.unwrap();
+        assert_eq!(fetched.notebook.as_deref(), Some("archive"));
+        assert_eq!(fetched.content, "Shipped");
+        assert_eq!(storage.memo_history(&plan.id).await.unwrap().len(), 2);
+        let moved = storage.move_memo(&plan.id, None).await.unwrap();
+        assert_eq!(moved.notebook, None);
+        assert!(temp_dir.path().join("memos/Plan.md").exists());
+
+        storage.create_notebook("ideas").await.unwrap();
+        assert!(storage.create_notebook("ideas").await.is_err());
+        storage.rename_notebook("work", "office").await.unwrap();
+        assert_eq!(
+            storage.list_notebooks().await.unwrap(),
+            vec!["archive", "ideas", "office"]
+        );
+        storage.move_memo(&plan.id, Some("ideas")).await.unwrap();
+        let error = storage.delete_notebook("ideas").await.unwrap_err();
+        assert!(error.to_string().contains("still has 1 memos"));
+        storage.delete_notebook("office").await.unwrap();
+        assert!(storage.list_memos_in_notebook("office").await.is_err());
+
+        assert!(storage.create_notebook(".history").await.is_err());
+        assert!(storage.create_notebook("a/b").await.is_err());
+        assert!(storage.move_memo(&plan.id, Some("..")).await.is_err());
+    }
 }
@@ -434,3 +434,32 @@ async fn test_markdown_list_empty_directory() {
     let memos = storage.list_memos().await.unwrap();
     assert_eq!(memos.len(), 0);
 }
+
+#[tokio::test]
+async fn test_markdown_storage_on_overlay_leaves_disk_untouched() {
+    let temp_dir = TempDir::new().unwrap();
+    let memos_dir = temp_dir.path().join("memos");
+    std::fs::create_dir_all(&memos_dir).unwrap();
+    let overlay = Arc::new(crate::fs_utils::OverlayFileSystem::default());
+    let storage = MarkdownMemoStorage::with_fs(memos_dir.clone(), overlay.clone());
+
+    let memo = storage
+        .create_memo("Dry Run".to_string(), "Draft".to_string())
+        .await
+        .unwrap();
+    storage
+        .update_memo(&memo.id, "Final".to_string())
+        .await
+        .unwrap();
+
+    let memos = storage.list_memos().await.unwrap();
+    assert_eq!(memos.len(), 1);
+    assert_eq!(memos[0].content, "Final");
+    assert_eq!(std::fs::read_dir(&memos_dir).unwrap().count(), 0);
+
+    storage.delete_memo(&memo.id).await.unwrap();
+    assert!(storage.list_memos().await.unwrap().is_empty());
+    assert!(overlay
+        .changes()
+        .contains(&crate::fs_utils::FileChange::Remove(memos_dir.join("Dry Run.md"))));
+}
@@ -0,0 +1,340 @@
+//! Package manifests for shared prompt and workflow collections
+//!
+//! A directory of prompts or workflows shared between teams can describe
+//! itself with a `sah-package.yaml` manifest naming the package, its version
+//! and the versions of other packages it needs:
+//!
+//! ```yaml
+//! name: acme-review
+//! version: 1.2.0
+//! description: Review prompts and workflows used across Acme
+//! dependencies:
+//!   acme-common: "^1.4"
+//! ```
+//!
+//! Manifests are found in `.swissarmyhammer` directories and anywhere below
+//! their `prompts` and `workflows` directories. Like prompts, a package in a
+//! more specific directory overrides one with the same name from a less
+//! specific directory. Version constraints use Cargo's syntax and are checked
+//! whenever prompts or workflows are loaded, so a collection copied in without
+//! the packages it depends on fails to load instead of failing at run time.
+
+use crate::directory_utils::{find_swissarmyhammer_dirs_upward, walk_files_with_extensions};
+use crate::file_loader::FileSource;
+use crate::{Result, SwissArmyHammerError};
+use semver::{Version, VersionReq};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// File name of a package manifest
+pub const PACKAGE_MANIFEST: &str = "sah-package.yaml";
+
+/// Subdirectories of a `.swissarmyhammer` directory searched for manifests
+const PACKAGE_SUBDIRECTORIES: [&str; 2] = ["prompts", "workflows"];
+
+/// The contents of a `sah-package.yaml` manifest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PackageManifest {
+    /// Package name other packages depend on it by
+    pub name: String,
+    /// Semantic version of the package
+    pub version: Version,
+    /// What the package contains
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Version requirements on other packages, by package name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub dependencies: BTreeMap<String, VersionReq>,
+}
+
+impl PackageManifest {
+    /// Parse a manifest, naming `path` in any error
+    pub fn parse(content: &str, path: &Path) -> Result<Self> {
+        let manifest: Self = serde_yaml::from_str(content).map_err(|e| {
+            SwissArmyHammerError::Config(format!(
+                "Invalid package manifest '{}': {e}",
+                path.display()
+            ))
+        })?;
+        if manifest.name.trim().is_empty() || manifest.name.contains(char::is_whitespace) {
+            return Err(SwissArmyHammerError::Config(format!(
+                "Invalid package manifest '{}': package name '{}' must be a single word",
+                path.display(),
+                manifest.name
+            )));
+        }
+        Ok(manifest)
+    }
+}
+
+/// An installed package
+#[derive(Debug, Clone, PartialEq)]
+pub struct Package {
+    /// The package's manifest
+    pub manifest: PackageManifest,
+    /// Path of the manifest file
+    pub path: PathBuf,
+    /// Where the package was loaded from
+    pub source: FileSource,
+}
+
+/// The packages installed in the user and local directories
+#[derive(Debug, Clone, Default)]
+pub struct PackageSet {
+    packages: BTreeMap<String, Package>,
+}
+
+impl PackageSet {
+    /// Create an empty package set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load the packages of the user directory, then the local directories
+    pub fn load_all() -> Result<Self> {
+        let mut packages = Self::new();
+        if let Some(home) = crate::directory_utils::home_dir() {
+            packages.load_directory(&home.join(".swissarmyhammer"), FileSource::User)?;
+        }
+        let current_dir = std::env::current_dir()?;
+        for dir in find_swissarmyhammer_dirs_upward(&current_dir, true) {
+            packages.load_directory(&dir, FileSource::Local)?;
+        }
+        Ok(packages)
+    }
+
+    /// Load the manifests in a `.swissarmyhammer` directory
+    ///
+    /// Packages override earlier packages with the same name, but two
+    /// manifests in one directory naming the same package are a conflict.
+    pub fn load_directory(&mut self, base_path: &Path, source: FileSource) -> Result<()> {
+        let mut manifests = vec![base_path.join(PACKAGE_MANIFEST)];
+        for subdirectory in PACKAGE_SUBDIRECTORIES {
+            let dir = base_path.join(subdirectory);
+            if dir.is_dir() {
+                manifests.extend(walk_files_with_extensions(&dir, &["yaml"]).filter(|path| {
+                    path.file_name()
+                        .is_some_and(|name| name == PACKAGE_MANIFEST)
+                }));
+            }
+        }
+
+        let mut loaded: BTreeMap<String, Package> = BTreeMap::new();
+        for path in manifests.into_iter().filter(|path| path.is_file()) {
+            let content = std::fs::read_to_string(&path)?;
+            let manifest = PackageManifest::parse(&content, &path)?;
+            if let Some(other) = loaded.get(&manifest.name) {
+                return Err(SwissArmyHammerError::Config(format!(
+                    "Package '{}' is installed twice: {} at '{}' and {} at '{}'",
+                    manifest.name,
+                    other.manifest.version,
+                    other.path.display(),
+                    manifest.version,
+                    path.display()
+                )));
+            }
+            loaded.insert(
+                manifest.name.clone(),
+                Package {
+                    manifest,
+                    path,
+                    source: source.clone(),
+                },
+            );
+        }
+
+        for (name, package) in loaded {
+            if let Some(overridden) = self.packages.get(&name) {
+                tracing::debug!(
+                    "Package '{}' {} at '{}' overrides {} at '{}'",
+                    name,
+                    package.manifest.version,
+                    package.path.display(),
+                    overridden.manifest.version,
+                    overridden.path.display()
+                );
+            }
+            self.add(package);
+        }
+        Ok(())
+    }
+
+    /// Add a package, replacing any package with the same name
+    pub fn add(&mut self, package: Package) {
+        self.packages.insert(package.manifest.name.clone(), package);
+    }
+
+    /// Get a package by name
+    pub fn get(&self, name: &str) -> Option<&Package> {
+        self.packages.get(name)
+    }
+
+    /// All packages, by name
+    pub fn iter(&self) -> impl Iterator<Item = &Package> {
+        self.packages.values()
+    }
+
+    /// Check that every dependency is installed in a version it accepts
+    ///
+    /// All unmet dependencies are reported in one error, one per line.
+    pub fn check(&self) -> Result<()> {
+        let mut problems = Vec::new();
+        for package in self.packages.values() {
+            let manifest = &package.manifest;
+            for (name, requirement) in &manifest.dependencies {
+                match self.packages.get(name) {
+                    None => problems.push(format!(
+                        "Package '{}' {} requires '{name}' {requirement}, which is not installed",
+                        manifest.name, manifest.version
+                    )),
+                    Some(dependency) if !requirement.matches(&dependency.manifest.version) => {
+                        problems.push(format!(
+                            "Package '{}' {} requires '{name}' {requirement}, but {} is installed at '{}'",
+                            manifest.name,
+                            manifest.version,
+                            dependency.manifest.version,
+                            dependency.path.display()
+                        ))
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(SwissArmyHammerError::Config(problems.join("\n")))
+        }
+    }
+}
+
+/// Load the installed packages and check their dependencies
+pub fn check_installed_packages() -> Result<PackageSet> {
+    let packages = PackageSet::load_all()?;
+    packages.check()?;
+    Ok(packages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::directory_utils::invalidate_cached_indexes;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn write_manifest(dir: &Path, content: &str) {
+        fs::create_dir_all(dir).unwrap();
+        fs::write(dir.join(PACKAGE_MANIFEST), content).unwrap();
+    }
+
+    #[test]
+    fn test_parse_manifest() {
+        let manifest = PackageManifest::parse(
+            "name: acme-review\nversion: 1.2.0\ndependencies:\n  acme-common: \"^1.4\"\n",
+            Path::new(PACKAGE_MANIFEST),
+        )
+        .unwrap();
+        assert_eq!(manifest.name, "acme-review");
+        assert_eq!(manifest.version, Version::new(1, 2, 0));
+        assert!(manifest.dependencies["acme-common"].matches(&Version::new(1, 9, 0)));
+
+        for content in [
+            "name: acme\nversion: 1.2\n",
+            "name: acme review\nversion: 1.2.0\n",
+            "name: acme\nversion: 1.2.0\ndependencies:\n  other: \"not a version\"\n",
+            "name: acme\nversion: 1.2.0\nauthor: someone\n",
+        ] {
+            let error = PackageManifest::parse(content, Path::new("a/sah-package.yaml"))
+                .unwrap_err()
+                .to_string();
+            assert!(error.contains("a/sah-package.yaml"), "{error}");
+        }
+    }
+
+    #[test]
+    fn test_load_directory_finds_nested_manifests() {
+        let temp_dir = TempDir::new().unwrap();
+        let base = temp_dir.path().join(".swissarmyhammer");
+        write_manifest(&base, "name: team\nversion: 0.3.0\n");
+        write_manifest(
+            &base.join("prompts").join("acme-review"),
+            "name: acme-review\nversion: 1.2.0\n",
+        );
+        write_manifest(
+            &base.join("workflows").join("acme-flows"),
+            "name: acme-flows\nversion: 2.0.0\n",
+        );
+
+        let mut packages = PackageSet::new();
+        packages.load_directory(&base, FileSource::Local).unwrap();
+        let names: Vec<_> = packages.iter().map(|p| p.manifest.name.as_str()).collect();
+        assert_eq!(names, vec!["acme-flows", "acme-review", "team"]);
+        assert_eq!(packages.get("team").unwrap().source, FileSource::Local);
+
+        write_manifest(
+            &base.join("prompts").join("copy"),
+            "name: acme-review\nversion: 1.3.0\n",
+        );
+        invalidate_cached_indexes(&[base.join("prompts")]);
+        let error = PackageSet::new()
+            .load_directory(&base, FileSource::Local)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            error.contains("Package 'acme-review' is installed twice"),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn test_check_dependencies() {
+        let user_dir = TempDir::new().unwrap();
+        let local_dir = TempDir::new().unwrap();
+        write_manifest(user_dir.path(), "name: acme-common\nversion: 1.4.2\n");
+        write_manifest(
+            local_dir.path(),
+            "name: acme-review\nversion: 1.2.0\ndependencies:\n  acme-common: \"^1.4\"\n",
+        );
+
+        let mut packages = PackageSet::new();
+        packages
+            .load_directory(user_dir.path(), FileSource::User)
+            .unwrap();
+        packages
+            .load_directory(local_dir.path(), FileSource::Local)
+            .unwrap();
+        packages.check().unwrap();
+
+        // A local package overrides the user one with the same name
+        let override_dir = TempDir::new().unwrap();
+        write_manifest(override_dir.path(), "name: acme-common\nversion: 2.0.0\n");
+        packages
+            .load_directory(override_dir.path(), FileSource::Local)
+            .unwrap();
+        let error = packages.check().unwrap_err().to_string();
+        assert!(
+            error.contains(
+                "Package 'acme-review' 1.2.0 requires 'acme-common' ^1.4, but 2.0.0 is installed"
+            ),
+            "{error}"
+        );
+
+        let missing_dir = TempDir::new().unwrap();
+        write_manifest(
+            missing_dir.path(),
+            "name: acme-extra\nversion: 0.1.0\ndependencies:\n  acme-lint: \">=0.2, <0.4\"\n",
+        );
+        packages
+            .load_directory(missing_dir.path(), FileSource::Local)
+            .unwrap();
+        let error = packages.check().unwrap_err().to_string();
+        assert_eq!(error.lines().count(), 2, "{error}");
+        assert!(
+            error.contains("requires 'acme-lint' >=0.2, <0.4, which is not installed"),
+            "{error}"
+        );
+    }
+}
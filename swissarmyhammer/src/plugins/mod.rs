@@ -0,0 +1,452 @@
+//! Plugin system for extending SwissArmyHammer functionality
+//!
+//! This module provides a plugin architecture that allows users to extend
+//! SwissArmyHammer with custom functionality, including Liquid filters,
+//! prompt sources, and output formatters.
+//!
+//! Plugins are either compiled into the crate as Rust types implementing
+//! [`SwissArmyHammerPlugin`], or, with the `wasm-plugins` feature, loaded as
+//! sandboxed WebAssembly modules from `.swissarmyhammer/plugins/*.wasm`. See
+//! the `wasm` module for the filter ABI those modules implement.
+
+use crate::{Result, SwissArmyHammerError};
+use liquid_core::parser::{FilterArguments, ParameterReflection};
+use liquid_core::{Filter, FilterReflection, ParseFilter, Runtime, ValueView};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, OnceLock};
+
+#[cfg(feature = "wasm-plugins")]
+use crate::security::{validate_path, PathPolicy};
+#[cfg(feature = "wasm-plugins")]
+use std::path::{Path, PathBuf};
+#[cfg(feature = "wasm-plugins")]
+use tracing::warn;
+
+#[cfg(feature = "wasm-plugins")]
+mod wasm;
+
+#[cfg(feature = "wasm-plugins")]
+pub use wasm::{WasmPlugin, WASM_FUEL_LIMIT, WASM_MEMORY_LIMIT};
+
+/// Core plugin trait that all plugins must implement
+pub trait SwissArmyHammerPlugin: Send + Sync {
+    /// Plugin name (must be unique)
+    fn name(&self) -> &str;
+
+    /// Plugin version
+    fn version(&self) -> &str;
+
+    /// Plugin description
+    fn description(&self) -> &str;
+
+    /// Get custom Liquid filters provided by this plugin
+    fn filters(&self) -> Vec<Box<dyn CustomLiquidFilter>>;
+
+    /// Initialize the plugin (called when plugin is loaded)
+    fn initialize(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Cleanup when plugin is unloaded
+    fn cleanup(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Trait for custom Liquid filters
+pub trait CustomLiquidFilter: Send + Sync {
+    /// Filter name (will be used in templates)
+    fn name(&self) -> &str;
+
+    /// Filter description for documentation
+    fn description(&self) -> &str;
+
+    /// Apply the filter to input value
+    fn apply(&self, input: &liquid::model::Value) -> Result<liquid::model::Value>;
+}
+
+/// Plugin registry for managing loaded plugins
+pub struct PluginRegistry {
+    plugins: HashMap<String, Arc<dyn SwissArmyHammerPlugin>>,
+    filters: HashMap<String, Arc<dyn CustomLiquidFilter>>,
+}
+
+impl PluginRegistry {
+    /// Create a new empty plugin registry
+    pub fn new() -> Self {
+        Self {
+            plugins: HashMap::new(),
+            filters: HashMap::new(),
+        }
+    }
+
+    /// Register a plugin
+    pub fn register_plugin(&mut self, mut plugin: Box<dyn SwissArmyHammerPlugin>) -> Result<()> {
+        let name = plugin.name().to_string();
+
+        // Check if plugin already exists
+        if self.plugins.contains_key(&name) {
+            return Err(SwissArmyHammerError::Config(format!(
+                "Plugin '{name}' is already registered"
+            )));
+        }
+
+        // Initialize the plugin
+        plugin.initialize()?;
+
+        // Check every filter before registering any, so a rejected plugin
+        // leaves the registry unchanged
+        let filters = plugin.filters();
+        for (index, filter) in filters.iter().enumerate() {
+            let filter_name = filter.name();
+            if self.filters.contains_key(filter_name)
+                || filters[..index].iter().any(|f| f.name() == filter_name)
+            {
+                return Err(SwissArmyHammerError::Config(format!(
+                    "Filter '{filter_name}' is already registered"
+                )));
+            }
+        }
+
+        // Register all filters from the plugin
+        for filter in filters {
+            self.filters
+                .insert(filter.name().to_string(), Arc::from(filter));
+        }
+
+        // Store the plugin
+        self.plugins.insert(name, Arc::from(plugin));
+
+        Ok(())
+    }
+
+    /// Get a registered plugin by name
+    pub fn get_plugin(&self, name: &str) -> Option<Arc<dyn SwissArmyHammerPlugin>> {
+        self.plugins.get(name).cloned()
+    }
+
+    /// Get all registered plugin names
+    pub fn plugin_names(&self) -> Vec<String> {
+        self.plugins.keys().cloned().collect()
+    }
+
+    /// Get a custom filter by name
+    pub fn get_filter(&self, name: &str) -> Option<Arc<dyn CustomLiquidFilter>> {
+        self.filters.get(name).cloned()
+    }
+
+    /// Get all registered filter names
+    pub fn filter_names(&self) -> Vec<String> {
+        self.filters.keys().cloned().collect()
+    }
+
+    /// Add every registered filter to a liquid parser builder
+    ///
+    /// Custom filters take no arguments and replace standard filters of the
+    /// same name.
+    pub fn add_filters<P>(&self, mut builder: liquid::ParserBuilder<P>) -> liquid::ParserBuilder<P>
+    where
+        P: liquid::partials::PartialCompiler,
+    {
+        for (name, filter) in &self.filters {
+            builder = builder.filter(PluginFilterParser {
+                name: name.clone(),
+                filter: filter.clone(),
+            });
+        }
+        builder
+    }
+
+    /// Create a liquid parser with standard filters and all registered filters
+    pub fn create_parser(&self) -> liquid::Parser {
+        self.add_filters(liquid::ParserBuilder::with_stdlib())
+            .build()
+            .expect("Failed to build Liquid parser")
+    }
+
+    /// Load every `*.wasm` plugin in a directory
+    ///
+    /// Plugins are loaded in file name order. A plugin that fails to load or
+    /// clashes with an already registered plugin or filter is skipped with a
+    /// warning. Returns the names of the plugins that were registered.
+    #[cfg(feature = "wasm-plugins")]
+    pub fn load_wasm_plugins(&mut self, plugins_dir: &Path) -> Result<Vec<String>> {
+        if !plugins_dir.is_dir() {
+            return Ok(Vec::new());
+        }
+
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(plugins_dir)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "wasm"))
+            .collect();
+        paths.sort();
+
+        let mut loaded = Vec::new();
+        for path in paths {
+            let registered = validate_path(&path, plugins_dir, PathPolicy::global())
+                .and_then(|path| WasmPlugin::load(&path))
+                .and_then(|plugin| {
+                    let name = plugin.name().to_string();
+                    self.register_plugin(Box::new(plugin))?;
+                    Ok(name)
+                });
+            match registered {
+                Ok(name) => loaded.push(name),
+                Err(e) => warn!("Skipping WASM plugin {}: {}", path.display(), e),
+            }
+        }
+        Ok(loaded)
+    }
+
+    /// Plugins available to every template
+    ///
+    /// Loaded once per process from `.swissarmyhammer/plugins` in the current
+    /// directory.
+    pub fn global() -> &'static PluginRegistry {
+        static REGISTRY: OnceLock<PluginRegistry> = OnceLock::new();
+        REGISTRY.get_or_init(|| {
+            #[allow(unused_mut)]
+            let mut registry = PluginRegistry::new();
+            #[cfg(feature = "wasm-plugins")]
+            if let Ok(current_dir) = std::env::current_dir() {
+                let plugins_dir = current_dir.join(".swissarmyhammer").join("plugins");
+                if let Err(e) = registry.load_wasm_plugins(&plugins_dir) {
+                    warn!(
+                        "Failed to load plugins from {}: {}",
+                        plugins_dir.display(),
+                        e
+                    );
+                }
+            }
+            registry
+        })
+    }
+}
+
+impl Default for PluginRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Makes a registered filter available to the liquid parser
+#[derive(Clone)]
+struct PluginFilterParser {
+    name: String,
+    filter: Arc<dyn CustomLiquidFilter>,
+}
+
+impl FilterReflection for PluginFilterParser {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        self.filter.description()
+    }
+
+    fn positional_parameters(&self) -> &'static [ParameterReflection] {
+        &[]
+    }
+
+    fn keyword_parameters(&self) -> &'static [ParameterReflection] {
+        &[]
+    }
+}
+
+impl ParseFilter for PluginFilterParser {
+    fn parse(&self, mut arguments: FilterArguments) -> liquid_core::Result<Box<dyn Filter>> {
+        if arguments.positional.next().is_some() || arguments.keyword.next().is_some() {
+            return Err(liquid_core::Error::with_msg(format!(
+                "Filter '{}' does not take arguments",
+                self.name
+            )));
+        }
+        Ok(Box::new(PluginFilter {
+            name: self.name.clone(),
+            filter: self.filter.clone(),
+        }))
+    }
+
+    fn reflection(&self) -> &dyn FilterReflection {
+        self
+    }
+}
+
+/// A parsed use of a registered filter
+struct PluginFilter {
+    name: String,
+    filter: Arc<dyn CustomLiquidFilter>,
+}
+
+impl fmt::Debug for PluginFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PluginFilter")
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+impl fmt::Display for PluginFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl Filter for PluginFilter {
+    fn evaluate(
+        &self,
+        input: &dyn ValueView,
+        _runtime: &dyn Runtime,
+    ) -> liquid_core::Result<liquid_core::Value> {
+        self.filter
+            .apply(&input.to_value())
+            .map_err(|e| liquid_core::Error::with_msg(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use liquid::model::Value;
+    use liquid::ValueView;
+
+    // Test plugin implementation
+    struct TestPlugin {
+        name: String,
+        version: String,
+    }
+
+    impl TestPlugin {
+        fn new() -> Self {
+            Self {
+                name: "test-plugin".to_string(),
+                version: "1.0.0".to_string(),
+            }
+        }
+    }
+
+    impl SwissArmyHammerPlugin for TestPlugin {
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        fn version(&self) -> &str {
+            &self.version
+        }
+
+        fn description(&self) -> &str {
+            "A test plugin for unit testing"
+        }
+
+        fn filters(&self) -> Vec<Box<dyn CustomLiquidFilter>> {
+            vec![Box::new(TestFilter::new())]
+        }
+    }
+
+    // Test filter implementation
+    struct TestFilter {
+        name: String,
+    }
+
+    impl TestFilter {
+        fn new() -> Self {
+            Self {
+                name: "test_filter".to_string(),
+            }
+        }
+    }
+
+    impl CustomLiquidFilter for TestFilter {
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        fn description(&self) -> &str {
+            "A test filter that reverses strings"
+        }
+
+        fn apply(&self, input: &Value) -> Result<Value> {
+            // Extract string value properly from liquid Value
+            let str_val = input.render().to_string();
+
+            let reversed: String = str_val.chars().rev().collect();
+            Ok(Value::scalar(reversed))
+        }
+    }
+
+    #[test]
+    fn test_plugin_registry_creation() {
+        let registry = PluginRegistry::new();
+        assert_eq!(registry.plugin_names().len(), 0);
+        assert_eq!(registry.filter_names().len(), 0);
+    }
+
+    #[test]
+    fn test_plugin_registration() {
+        let mut registry = PluginRegistry::new();
+        let plugin = TestPlugin::new();
+
+        assert!(registry.register_plugin(Box::new(plugin)).is_ok());
+        assert_eq!(registry.plugin_names().len(), 1);
+        assert_eq!(registry.filter_names().len(), 1);
+        assert!(registry.get_plugin("test-plugin").is_some());
+        assert!(registry.get_filter("test_filter").is_some());
+    }
+
+    #[test]
+    fn test_duplicate_plugin_registration() {
+        let mut registry = PluginRegistry::new();
+        let plugin1 = TestPlugin::new();
+        let plugin2 = TestPlugin::new();
+
+        assert!(registry.register_plugin(Box::new(plugin1)).is_ok());
+        assert!(registry.register_plugin(Box::new(plugin2)).is_err());
+    }
+
+    #[test]
+    fn test_filter_application() {
+        let filter = TestFilter::new();
+        let input = Value::scalar("hello");
+        let result = filter.apply(&input).unwrap();
+
+        // Check that the result is a scalar with the expected value
+        match result {
+            Value::Scalar(_) => {
+                let result_str = result.render().to_string();
+                assert_eq!(result_str, "olleh");
+            }
+            _ => panic!("Expected scalar result"),
+        }
+    }
+
+    #[test]
+    fn test_registered_filters_render_in_templates() {
+        let mut registry = PluginRegistry::new();
+        registry
+            .register_plugin(Box::new(TestPlugin::new()))
+            .unwrap();
+
+        let parser = registry.create_parser();
+        let template = parser.parse("{{ word | test_filter }}").unwrap();
+        let mut globals = liquid::Object::new();
+        globals.insert("word".into(), Value::scalar("hello"));
+        assert_eq!(template.render(&globals).unwrap(), "olleh");
+
+        assert!(parser.parse("{{ word | test_filter: 1 }}").is_err());
+    }
+
+    #[cfg(feature = "wasm-plugins")]
+    #[test]
+    fn test_load_wasm_plugins_skips_invalid_modules() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("broken.wasm"), b"not wasm").unwrap();
+        std::fs::write(temp_dir.path().join("notes.txt"), b"ignored").unwrap();
+
+        let mut registry = PluginRegistry::new();
+        let loaded = registry.load_wasm_plugins(temp_dir.path()).unwrap();
+        assert!(loaded.is_empty());
+        assert!(registry.plugin_names().is_empty());
+    }
+}
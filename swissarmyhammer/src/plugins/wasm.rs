@@ -0,0 +1,347 @@
+//! Sandboxed WebAssembly plugins providing custom Liquid filters
+//!
+//! A WASM plugin is a single `.wasm` module placed in
+//! `.swissarmyhammer/plugins/`. The plugin is named after the file stem, and
+//! its filters become available in every template.
+//!
+//! # Filter ABI
+//!
+//! Strings cross the module boundary as UTF-8 bytes in the module's linear
+//! memory. A *packed slice* is an `i64` holding `(ptr << 32) | len`. The module
+//! must export:
+//!
+//! - `memory`: the linear memory
+//! - `sah_alloc(len: i32) -> i32`: reserve `len` bytes and return their offset
+//! - `sah_filters() -> i64`: packed slice of filter names, one per line
+//! - `sah_apply(name_ptr: i32, name_len: i32, input_ptr: i32, input_len: i32) -> i64`:
+//!   apply the named filter to the JSON encoded input value and return a packed
+//!   slice of the JSON encoded output value. A negative return value signals an
+//!   error, and its absolute value is a packed slice of the error message.
+//!
+//! # Sandbox
+//!
+//! Modules may not import anything, so a plugin has no access to the file
+//! system, network, clock, or environment. Each filter call runs in a fresh
+//! instance with a fuel budget and a memory limit, so a misbehaving plugin
+//! fails the render instead of hanging it or exhausting memory.
+
+use super::{CustomLiquidFilter, SwissArmyHammerPlugin};
+use crate::{Result, SwissArmyHammerError};
+use std::path::Path;
+use std::sync::Arc;
+use wasmi::{Engine, Instance, Linker, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+/// Fuel available to a single plugin call, roughly the number of instructions
+pub const WASM_FUEL_LIMIT: u64 = 10_000_000;
+
+/// Largest linear memory a plugin instance may grow to, in bytes
+pub const WASM_MEMORY_LIMIT: usize = 16 * 1024 * 1024;
+
+/// A compiled plugin module
+struct WasmModule {
+    /// Plugin name used in error messages
+    name: String,
+    engine: Engine,
+    module: Module,
+}
+
+/// A running plugin instance together with its store
+struct WasmInstance<'a> {
+    plugin: &'a str,
+    store: Store<StoreLimits>,
+    instance: Instance,
+}
+
+impl WasmModule {
+    /// Compile a module, rejecting modules that import anything
+    fn compile(name: &str, wasm: &[u8]) -> Result<Self> {
+        let mut config = wasmi::Config::default();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config);
+        let module = Module::new(&engine, wasm).map_err(|e| {
+            SwissArmyHammerError::Other(format!("Invalid WASM plugin '{name}': {e}"))
+        })?;
+
+        if let Some(import) = module.imports().next() {
+            return Err(SwissArmyHammerError::Other(format!(
+                "WASM plugin '{name}' imports '{}::{}', but plugins may not import anything",
+                import.module(),
+                import.name()
+            )));
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            engine,
+            module,
+        })
+    }
+
+    /// Start a fresh sandboxed instance
+    fn instantiate(&self) -> Result<WasmInstance<'_>> {
+        let limits = StoreLimitsBuilder::new()
+            .memory_size(WASM_MEMORY_LIMIT)
+            .build();
+        let mut store = Store::new(&self.engine, limits);
+        store.limiter(|limits| limits);
+        store.set_fuel(WASM_FUEL_LIMIT).map_err(|e| self.error(e))?;
+
+        let instance = Linker::<StoreLimits>::new(&self.engine)
+            .instantiate(&mut store, &self.module)
+            .and_then(|pre| pre.start(&mut store))
+            .map_err(|e| self.error(e))?;
+
+        Ok(WasmInstance {
+            plugin: &self.name,
+            store,
+            instance,
+        })
+    }
+
+    fn error(&self, error: impl std::fmt::Display) -> SwissArmyHammerError {
+        SwissArmyHammerError::Other(format!("WASM plugin '{}' failed: {error}", self.name))
+    }
+}
+
+impl WasmInstance<'_> {
+    fn error(&self, error: impl std::fmt::Display) -> SwissArmyHammerError {
+        SwissArmyHammerError::Other(format!("WASM plugin '{}' failed: {error}", self.plugin))
+    }
+
+    /// Copy bytes into memory allocated by the plugin, returning their offset
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<i32> {
+        let len = i32::try_from(bytes.len()).map_err(|e| self.error(e))?;
+        let alloc = self
+            .instance
+            .get_typed_func::<i32, i32>(&self.store, "sah_alloc")
+            .map_err(|e| self.error(e))?;
+        let ptr = alloc
+            .call(&mut self.store, len)
+            .map_err(|e| self.error(e))?;
+
+        let memory = self.memory()?;
+        memory
+            .write(&mut self.store, ptr as u32 as usize, bytes)
+            .map_err(|e| self.error(e))?;
+        Ok(ptr)
+    }
+
+    /// Read a packed slice out of plugin memory as a string
+    fn read_string(&self, packed: i64) -> Result<String> {
+        let ptr = (packed as u64 >> 32) as usize;
+        let len = (packed as u64 & 0xffff_ffff) as usize;
+        if len > WASM_MEMORY_LIMIT {
+            return Err(self.error(format!("returned a {len} byte string")));
+        }
+
+        let mut buffer = vec![0; len];
+        self.memory()?
+            .read(&self.store, ptr, &mut buffer)
+            .map_err(|e| self.error(e))?;
+        String::from_utf8(buffer).map_err(|e| self.error(e))
+    }
+
+    fn memory(&self) -> Result<wasmi::Memory> {
+        self.instance
+            .get_memory(&self.store, "memory")
+            .ok_or_else(|| self.error("module does not export 'memory'"))
+    }
+
+    /// Names of the filters the plugin provides
+    fn filter_names(&mut self) -> Result<Vec<String>> {
+        let filters = self
+            .instance
+            .get_typed_func::<(), i64>(&self.store, "sah_filters")
+            .map_err(|e| self.error(e))?;
+        let packed = filters
+            .call(&mut self.store, ())
+            .map_err(|e| self.error(e))?;
+
+        Ok(self
+            .read_string(packed)?
+            .lines()
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(str::to_string)
+            .collect())
+    }
+
+    /// Apply a filter to a JSON encoded value
+    fn apply(&mut self, filter: &str, input: &str) -> Result<String> {
+        let apply = self
+            .instance
+            .get_typed_func::<(i32, i32, i32, i32), i64>(&self.store, "sah_apply")
+            .map_err(|e| self.error(e))?;
+        let name_ptr = self.write_bytes(filter.as_bytes())?;
+        let input_ptr = self.write_bytes(input.as_bytes())?;
+        let packed = apply
+            .call(
+                &mut self.store,
+                (name_ptr, filter.len() as i32, input_ptr, input.len() as i32),
+            )
+            .map_err(|e| self.error(e))?;
+
+        if packed < 0 {
+            let message = self.read_string(packed.wrapping_neg())?;
+            return Err(self.error(format!("filter '{filter}': {message}")));
+        }
+        self.read_string(packed)
+    }
+}
+
+/// Plugin loaded from a `.wasm` file
+pub struct WasmPlugin {
+    module: Arc<WasmModule>,
+    filters: Vec<String>,
+}
+
+impl WasmPlugin {
+    /// Load a plugin from a `.wasm` file, named after the file stem
+    pub fn load(path: &Path) -> Result<Self> {
+        let name = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .ok_or_else(|| {
+                SwissArmyHammerError::Other(format!(
+                    "Invalid WASM plugin file name: {}",
+                    path.display()
+                ))
+            })?;
+        let wasm = std::fs::read(path)?;
+        Self::from_bytes(name, &wasm)
+    }
+
+    /// Create a plugin from the bytes of a WASM module
+    pub fn from_bytes(name: &str, wasm: &[u8]) -> Result<Self> {
+        let module = WasmModule::compile(name, wasm)?;
+        let filters = module.instantiate()?.filter_names()?;
+        Ok(Self {
+            module: Arc::new(module),
+            filters,
+        })
+    }
+}
+
+impl SwissArmyHammerPlugin for WasmPlugin {
+    fn name(&self) -> &str {
+        &self.module.name
+    }
+
+    fn version(&self) -> &str {
+        "wasm"
+    }
+
+    fn description(&self) -> &str {
+        "WebAssembly filter plugin"
+    }
+
+    fn filters(&self) -> Vec<Box<dyn CustomLiquidFilter>> {
+        self.filters
+            .iter()
+            .map(|name| {
+                Box::new(WasmFilter {
+                    module: self.module.clone(),
+                    name: name.clone(),
+                    description: format!("Provided by WASM plugin '{}'", self.module.name),
+                }) as Box<dyn CustomLiquidFilter>
+            })
+            .collect()
+    }
+}
+
+/// Filter implemented by a WASM plugin
+struct WasmFilter {
+    module: Arc<WasmModule>,
+    name: String,
+    description: String,
+}
+
+impl CustomLiquidFilter for WasmFilter {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn apply(&self, input: &liquid::model::Value) -> Result<liquid::model::Value> {
+        let input = serde_json::to_string(input)?;
+        let output = self.module.instantiate()?.apply(&self.name, &input)?;
+        serde_json::from_str(&output).map_err(|e| {
+            SwissArmyHammerError::Other(format!(
+                "WASM filter '{}' returned invalid JSON: {e}",
+                self.name
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use liquid::model::Value;
+
+    /// Plugin with an `echo` filter returning its input and a `fail` filter
+    const ECHO_PLUGIN: &str = r#"
+        (module
+          (memory (export "memory") 1)
+          (global $next (mut i32) (i32.const 1024))
+          (data (i32.const 0) "echo\nfail\n")
+          (data (i32.const 16) "broken")
+          (func (export "sah_alloc") (param $len i32) (result i32)
+            (local $ptr i32)
+            (local.set $ptr (global.get $next))
+            (global.set $next (i32.add (global.get $next) (local.get $len)))
+            (local.get $ptr))
+          (func (export "sah_filters") (result i64)
+            (i64.const 10))
+          (func (export "sah_apply")
+            (param $name i32) (param $name_len i32) (param $input i32) (param $input_len i32)
+            (result i64)
+            (if (result i64) (i32.eq (i32.load8_u (local.get $name)) (i32.const 102))
+              (then (i64.sub (i64.const 0) (i64.or (i64.shl (i64.const 16) (i64.const 32)) (i64.const 6))))
+              (else (i64.or
+                (i64.shl (i64.extend_i32_u (local.get $input)) (i64.const 32))
+                (i64.extend_i32_u (local.get $input_len)))))))
+    "#;
+
+    fn echo_plugin() -> WasmPlugin {
+        WasmPlugin::from_bytes("echo", &wat::parse_str(ECHO_PLUGIN).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_wasm_plugin_provides_filters() {
+        let plugin = echo_plugin();
+        let filters = plugin.filters();
+        let names: Vec<&str> = filters.iter().map(|filter| filter.name()).collect();
+        assert_eq!(plugin.name(), "echo");
+        assert_eq!(names, vec!["echo", "fail"]);
+
+        let output = filters[0].apply(&Value::scalar("hello")).unwrap();
+        assert_eq!(output, Value::scalar("hello"));
+
+        let error = filters[1].apply(&Value::scalar("hello")).unwrap_err();
+        assert!(error.to_string().contains("broken"));
+    }
+
+    #[test]
+    fn test_wasm_plugin_rejects_imports() {
+        let wasm = wat::parse_str(r#"(module (import "env" "read_file" (func)))"#).unwrap();
+        let error = WasmPlugin::from_bytes("sneaky", &wasm).err().unwrap();
+        assert!(error.to_string().contains("may not import"));
+    }
+
+    #[test]
+    fn test_wasm_plugin_runs_out_of_fuel() {
+        let wasm = wat::parse_str(
+            r#"(module
+                 (memory (export "memory") 1)
+                 (func (export "sah_filters") (result i64)
+                   (loop $forever (br $forever))
+                   (i64.const 0)))"#,
+        )
+        .unwrap();
+        assert!(WasmPlugin::from_bytes("spin", &wasm).is_err());
+    }
+}
@@ -0,0 +1,310 @@
+//! Housekeeping report across the whole prompt library
+//!
+//! Validation checks one file at a time. The audit looks at every prompt
+//! together and reports what tends to pile up in large libraries: prompts
+//! without descriptions, arguments the template never uses, template
+//! variables no argument declares, titles shared by several prompts, prompts
+//! no workflow uses, and prompts that have grown too long.
+
+use crate::dependencies::{DependencyGraph, DependencyNode};
+use crate::{Prompt, PromptLibrary, PromptResolver, Result};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::PathBuf;
+
+/// Template length, in characters, above which a prompt is reported as long
+pub const DEFAULT_MAX_PROMPT_LENGTH: usize = 8000;
+
+/// Kind of problem the audit found
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditFindingKind {
+    /// The prompt has no description
+    MissingDescription,
+    /// An argument is declared but the template never uses it
+    UnusedArgument,
+    /// The template uses a variable no argument declares
+    UndeclaredVariable,
+    /// Another prompt has the same title
+    DuplicateTitle,
+    /// No workflow uses the prompt, directly or through another prompt
+    UnusedInWorkflows,
+    /// The template is longer than the configured maximum
+    TooLong,
+}
+
+impl AuditFindingKind {
+    /// Every kind, in the order findings are reported
+    pub const ALL: [AuditFindingKind; 6] = [
+        AuditFindingKind::MissingDescription,
+        AuditFindingKind::UnusedArgument,
+        AuditFindingKind::UndeclaredVariable,
+        AuditFindingKind::DuplicateTitle,
+        AuditFindingKind::UnusedInWorkflows,
+        AuditFindingKind::TooLong,
+    ];
+}
+
+impl fmt::Display for AuditFindingKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let heading = match self {
+            AuditFindingKind::MissingDescription => "Missing descriptions",
+            AuditFindingKind::UnusedArgument => "Unused arguments",
+            AuditFindingKind::UndeclaredVariable => "Undeclared variables",
+            AuditFindingKind::DuplicateTitle => "Duplicate titles",
+            AuditFindingKind::UnusedInWorkflows => "Not used by any workflow",
+            AuditFindingKind::TooLong => "Overly long prompts",
+        };
+        write!(f, "{heading}")
+    }
+}
+
+/// One problem with one prompt
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AuditFinding {
+    /// Kind of problem
+    pub kind: AuditFindingKind,
+    /// Name of the prompt
+    pub prompt: String,
+    /// What exactly is wrong, such as the argument or title concerned
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// File the prompt was loaded from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+}
+
+/// Health report of a prompt library
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct PromptAudit {
+    /// Number of prompts audited, partials included
+    pub prompts: usize,
+    /// Problems found, ordered by kind and prompt name
+    pub findings: Vec<AuditFinding>,
+}
+
+impl PromptAudit {
+    /// Audit every prompt resolved from builtin, user and local directories
+    pub fn load(max_length: usize) -> Result<Self> {
+        let mut library = PromptLibrary::new();
+        let mut resolver = PromptResolver::new();
+        resolver.load_all_prompts(&mut library)?;
+        let prompts = library.list()?;
+        let graph = DependencyGraph::load()?;
+        Ok(Self::new(&prompts, &graph, max_length)
+            .with_paths(|name| resolver.prompt_paths.get(name).cloned()))
+    }
+
+    /// Audit prompts, using a dependency graph to find the ones workflows use
+    ///
+    /// Partials are exempt from the description and argument checks, since
+    /// they take their variables from the prompts that include them.
+    pub fn new(prompts: &[Prompt], graph: &DependencyGraph, max_length: usize) -> Self {
+        let mut findings = Vec::new();
+        let mut finding = |kind, prompt: &Prompt, detail: Option<String>| {
+            findings.push(AuditFinding {
+                kind,
+                prompt: prompt.name.clone(),
+                detail,
+                path: prompt.source.clone(),
+            });
+        };
+
+        let mut titles: BTreeMap<String, Vec<&Prompt>> = BTreeMap::new();
+        for prompt in prompts {
+            if !prompt.is_partial() {
+                let described = prompt
+                    .description
+                    .as_deref()
+                    .is_some_and(|description| !description.trim().is_empty());
+                if !described {
+                    finding(AuditFindingKind::MissingDescription, prompt, None);
+                }
+                for argument in prompt.unused_arguments() {
+                    finding(AuditFindingKind::UnusedArgument, prompt, Some(argument));
+                }
+                for variable in prompt.undeclared_variables() {
+                    finding(AuditFindingKind::UndeclaredVariable, prompt, Some(variable));
+                }
+            }
+
+            if let Some(title) = prompt.metadata.get("title").and_then(|t| t.as_str()) {
+                titles
+                    .entry(title.trim().to_lowercase())
+                    .or_default()
+                    .push(prompt);
+            }
+
+            let node = DependencyNode::prompt(&prompt.name);
+            let used_by_workflow = graph
+                .affected_by(&node)
+                .iter()
+                .any(|affected| matches!(affected, DependencyNode::Workflow(_)));
+            if !used_by_workflow {
+                finding(AuditFindingKind::UnusedInWorkflows, prompt, None);
+            }
+
+            let length = prompt.template.chars().count();
+            if length > max_length {
+                finding(
+                    AuditFindingKind::TooLong,
+                    prompt,
+                    Some(format!("{length} characters")),
+                );
+            }
+        }
+
+        for same_title in titles.values().filter(|prompts| prompts.len() > 1) {
+            for prompt in same_title {
+                let others: Vec<&str> = same_title
+                    .iter()
+                    .filter(|other| other.name != prompt.name)
+                    .map(|other| other.name.as_str())
+                    .collect();
+                finding(
+                    AuditFindingKind::DuplicateTitle,
+                    prompt,
+                    Some(format!("also used by {}", others.join(", "))),
+                );
+            }
+        }
+
+        findings.sort_by(|a, b| (a.kind, &a.prompt).cmp(&(b.kind, &b.prompt)));
+        Self {
+            prompts: prompts.len(),
+            findings,
+        }
+    }
+
+    /// Fill in missing finding paths
+    fn with_paths(mut self, path_of: impl Fn(&str) -> Option<PathBuf>) -> Self {
+        for finding in &mut self.findings {
+            if finding.path.is_none() {
+                finding.path = path_of(&finding.prompt);
+            }
+        }
+        self
+    }
+
+    /// Findings of one kind
+    pub fn of_kind(&self, kind: AuditFindingKind) -> impl Iterator<Item = &AuditFinding> {
+        self.findings
+            .iter()
+            .filter(move |finding| finding.kind == kind)
+    }
+
+    /// Whether the audit found nothing to clean up
+    pub fn is_clean(&self) -> bool {
+        self.findings.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workflow::MermaidParser;
+    use crate::ArgumentSpec;
+
+    fn prompt(name: &str, title: &str, template: &str) -> Prompt {
+        let mut prompt = Prompt::new(name, template).with_description(format!("{title} prompt"));
+        prompt
+            .metadata
+            .insert("title".to_string(), serde_json::json!(title));
+        prompt
+    }
+
+    fn kinds(audit: &PromptAudit, name: &str) -> Vec<(AuditFindingKind, Option<String>)> {
+        audit
+            .findings
+            .iter()
+            .filter(|finding| finding.prompt == name)
+            .map(|finding| (finding.kind, finding.detail.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_prompt_audit() {
+        let mut review = prompt("review", "Review", "Review {{ file }} in {{ language }}");
+        review.arguments.push(ArgumentSpec {
+            name: "file".to_string(),
+            description: None,
+            required: true,
+            default: None,
+            type_hint: None,
+            choices: None,
+            completion: None,
+            source: None,
+        });
+        review.arguments.push(ArgumentSpec {
+            name: "depth".to_string(),
+            description: None,
+            required: false,
+            default: None,
+            type_hint: None,
+            choices: None,
+            completion: None,
+            source: None,
+        });
+        let mut bare = Prompt::new("bare", "{% render \"header\" %}".repeat(10));
+        bare.metadata
+            .insert("title".to_string(), serde_json::json!("review "));
+        let header = Prompt::new("header", "{% partial %}\n# {{ title }}");
+        let prompts = vec![review, bare, header];
+
+        let workflow = MermaidParser::parse(
+            r#"```mermaid
+stateDiagram-v2
+    [*] --> run
+    run --> [*]
+```
+
+## Actions
+
+- run: Execute prompt "bare"
+"#,
+            "flow",
+        )
+        .unwrap();
+        let mut graph = DependencyGraph::new();
+        for prompt in &prompts {
+            graph.add_prompt(&prompt.name, &prompt.template, None);
+        }
+        graph.add_workflow(&workflow, None);
+
+        let audit = PromptAudit::new(&prompts, &graph, 100);
+        assert_eq!(audit.prompts, 3);
+        assert_eq!(
+            kinds(&audit, "review"),
+            vec![
+                (AuditFindingKind::UnusedArgument, Some("depth".to_string())),
+                (
+                    AuditFindingKind::UndeclaredVariable,
+                    Some("language".to_string())
+                ),
+                (
+                    AuditFindingKind::DuplicateTitle,
+                    Some("also used by bare".to_string())
+                ),
+                (AuditFindingKind::UnusedInWorkflows, None),
+            ]
+        );
+        assert_eq!(
+            kinds(&audit, "bare"),
+            vec![
+                (AuditFindingKind::MissingDescription, None),
+                (
+                    AuditFindingKind::DuplicateTitle,
+                    Some("also used by review".to_string())
+                ),
+                (
+                    AuditFindingKind::TooLong,
+                    Some("210 characters".to_string())
+                ),
+            ]
+        );
+        // The partial is used by a workflow through "bare"
+        assert!(kinds(&audit, "header").is_empty());
+        assert!(!audit.is_clean());
+    }
+}
@@ -0,0 +1,275 @@
+//! Render benchmarks for prompts
+//!
+//! Slow templates usually show up only once a workflow renders them many
+//! times. [`PromptBenchmark`] parses and renders a prompt repeatedly and
+//! reports the latency distribution of both steps, along with the memory each
+//! render allocates when a [`CountingAllocator`] is installed as the global
+//! allocator. The CLI installs it only when built with the `alloc-stats`
+//! feature.
+
+use crate::{PromptLibrary, Result, SwissArmyHammerError, Template};
+use serde::Serialize;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Global allocator wrapping [`System`] that counts allocations
+///
+/// Binaries built for measuring install it with `#[global_allocator]` so
+/// benchmarks can report how much memory a render allocates. Each thread
+/// counts its own allocations, so a snapshot only sees the allocations of the
+/// thread taking it and not those of runtime worker threads.
+#[derive(Debug, Default)]
+pub struct CountingAllocator;
+
+thread_local! {
+    /// Allocations counted on this thread
+    static THREAD_ALLOCATIONS: Cell<AllocationSnapshot> = const {
+        Cell::new(AllocationSnapshot {
+            allocations: 0,
+            bytes: 0,
+        })
+    };
+}
+
+/// Allocation counters of a thread at one point in time
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocationSnapshot {
+    /// Number of allocations made so far
+    pub allocations: u64,
+    /// Bytes allocated so far
+    pub bytes: u64,
+}
+
+impl CountingAllocator {
+    /// Create an allocator
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Allocation counters of the calling thread
+    pub fn snapshot(&self) -> AllocationSnapshot {
+        THREAD_ALLOCATIONS.try_with(Cell::get).unwrap_or_default()
+    }
+
+    fn count(&self, size: usize) {
+        // Allocations made while the thread is being torn down go uncounted
+        let _ = THREAD_ALLOCATIONS.try_with(|counts| {
+            let mut snapshot = counts.get();
+            snapshot.allocations += 1;
+            snapshot.bytes += size as u64;
+            counts.set(snapshot);
+        });
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        self.count(layout.size());
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        self.count(layout.size());
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        self.count(new_size);
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+/// Latency distribution of one benchmarked step, in milliseconds
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct LatencyStats {
+    /// Fastest sample
+    pub min_ms: f64,
+    /// Median sample
+    pub p50_ms: f64,
+    /// 95th percentile
+    pub p95_ms: f64,
+    /// 99th percentile
+    pub p99_ms: f64,
+    /// Slowest sample
+    pub max_ms: f64,
+    /// Average of all samples
+    pub mean_ms: f64,
+}
+
+impl LatencyStats {
+    /// Summarize samples using nearest-rank percentiles
+    ///
+    /// Returns `None` when there are no samples.
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let (first, last) = (sorted.first()?, sorted.last()?);
+
+        let ms = |duration: &Duration| duration.as_secs_f64() * 1000.0;
+        let percentile = |percent: usize| {
+            let rank = ((percent * sorted.len() + 99) / 100).max(1);
+            ms(&sorted[rank - 1])
+        };
+        let total: Duration = sorted.iter().sum();
+
+        Some(Self {
+            min_ms: ms(first),
+            p50_ms: percentile(50),
+            p95_ms: percentile(95),
+            p99_ms: percentile(99),
+            max_ms: ms(last),
+            mean_ms: ms(&total) / sorted.len() as f64,
+        })
+    }
+}
+
+/// Memory a single render allocates, averaged over all iterations
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct AllocationStats {
+    /// Allocations per render
+    pub allocations: u64,
+    /// Bytes allocated per render
+    pub bytes: u64,
+}
+
+/// Parse and render latency of a prompt over many iterations
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PromptBenchmark {
+    /// Name of the benchmarked prompt
+    pub prompt: String,
+    /// Number of times the prompt was parsed and rendered
+    pub iterations: usize,
+    /// Time to parse the template, including its partials
+    pub parse: LatencyStats,
+    /// Time to render the prompt the way workflows do, parsing included
+    pub render: LatencyStats,
+    /// Memory allocated per render, when allocations are counted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub render_allocations: Option<AllocationStats>,
+}
+
+impl PromptBenchmark {
+    /// Parse and render a prompt `iterations` times
+    ///
+    /// One untimed render runs first so errors such as missing required
+    /// arguments are reported before measuring. Pass the installed global
+    /// allocator as `allocator` to measure allocations too; renders run on the
+    /// calling thread, so only their allocations are counted.
+    pub fn run(
+        library: Arc<PromptLibrary>,
+        name: &str,
+        args: &HashMap<String, String>,
+        iterations: usize,
+        allocator: Option<&CountingAllocator>,
+    ) -> Result<Self> {
+        if iterations == 0 {
+            return Err(SwissArmyHammerError::Other(
+                "Benchmark needs at least one iteration".to_string(),
+            ));
+        }
+
+        let prompt = library.get(name)?;
+        prompt.render_with_partials(args, library.clone())?;
+
+        let mut parse_samples = Vec::with_capacity(iterations);
+        for _ in 0..iterations {
+            let start = Instant::now();
+            Template::with_partials(&prompt.template, library.clone())?;
+            parse_samples.push(start.elapsed());
+        }
+
+        let mut render_samples = Vec::with_capacity(iterations);
+        let before = allocator.map(CountingAllocator::snapshot);
+        for _ in 0..iterations {
+            let start = Instant::now();
+            prompt.render_with_partials(args, library.clone())?;
+            render_samples.push(start.elapsed());
+        }
+        let render_allocations = allocator.zip(before).map(|(allocator, before)| {
+            let after = allocator.snapshot();
+            AllocationStats {
+                allocations: (after.allocations - before.allocations) / iterations as u64,
+                bytes: (after.bytes - before.bytes) / iterations as u64,
+            }
+        });
+
+        let stats = |samples: &[Duration]| {
+            LatencyStats::from_samples(samples).expect("at least one iteration was measured")
+        };
+        Ok(Self {
+            prompt: name.to_string(),
+            iterations,
+            parse: stats(&parse_samples),
+            render: stats(&render_samples),
+            render_allocations,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Prompt;
+
+    #[test]
+    fn test_latency_stats_percentiles() {
+        let samples: Vec<Duration> = (1..=100).rev().map(Duration::from_millis).collect();
+        let stats = LatencyStats::from_samples(&samples).unwrap();
+        let close = |actual: f64, expected: f64| (actual - expected).abs() < 1e-9;
+        assert!(close(stats.min_ms, 1.0));
+        assert!(close(stats.p50_ms, 50.0));
+        assert!(close(stats.p95_ms, 95.0));
+        assert!(close(stats.p99_ms, 99.0));
+        assert!(close(stats.max_ms, 100.0));
+        assert!(close(stats.mean_ms, 50.5));
+
+        let single = LatencyStats::from_samples(&[Duration::from_millis(4)]).unwrap();
+        assert!(close(single.p50_ms, 4.0));
+        assert!(close(single.p99_ms, 4.0));
+        assert!(LatencyStats::from_samples(&[]).is_none());
+    }
+
+    #[test]
+    fn test_benchmark_prompt() {
+        let mut library = PromptLibrary::new();
+        library.add(Prompt::new("header", "# {{ title }}")).unwrap();
+        library
+            .add(Prompt::new(
+                "report",
+                "{% render \"header\" %}\n{% for i in (1..3) %}{{ name }} {% endfor %}",
+            ))
+            .unwrap();
+        let library = Arc::new(library);
+        let args = HashMap::from([("name".to_string(), "bench".to_string())]);
+
+        let benchmark = PromptBenchmark::run(library.clone(), "report", &args, 5, None).unwrap();
+        assert_eq!(benchmark.prompt, "report");
+        assert_eq!(benchmark.iterations, 5);
+        assert!(benchmark.render.p50_ms <= benchmark.render.p99_ms);
+        assert!(benchmark.render.p99_ms <= benchmark.render.max_ms);
+        assert!(benchmark.render_allocations.is_none());
+
+        assert!(PromptBenchmark::run(library.clone(), "report", &args, 0, None).is_err());
+        assert!(PromptBenchmark::run(library, "missing", &args, 5, None).is_err());
+    }
+
+    #[test]
+    fn test_counting_allocator_counts_the_calling_thread() {
+        let allocator = CountingAllocator::new();
+        let before = allocator.snapshot();
+        allocator.count(64);
+        std::thread::spawn(|| CountingAllocator::new().count(1024))
+            .join()
+            .unwrap();
+        let after = allocator.snapshot();
+        assert_eq!(after.allocations - before.allocations, 1);
+        assert_eq!(after.bytes - before.bytes, 64);
+    }
+}
@@ -0,0 +1,178 @@
+//! Value completion for prompt arguments
+//!
+//! An argument can name where its values come from with a `completion` field
+//! in front matter, so interactive tools and shell completion can offer real
+//! file paths and branch names instead of free text:
+//!
+//! ```yaml
+//! arguments:
+//!   - name: file
+//!     completion:
+//!       glob: "src/**/*.rs"
+//!   - name: branch
+//!     completion: git_branches
+//!   - name: ticket
+//!     completion:
+//!       command: "gh issue list --json number --jq '.[].number'"
+//! ```
+//!
+//! A fixed set of values is given with `choices`, which also restricts the
+//! argument to those values. Completion values are only suggestions.
+
+use crate::error::{Result, SwissArmyHammerError};
+use crate::ArgumentSpec;
+use serde::{Deserialize, Serialize};
+use std::process::Command;
+
+/// Where the completion values of an argument come from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompletionSource {
+    /// Lines printed by a shell command run in the current directory
+    Command(String),
+    /// Paths matching a glob pattern, relative to the current directory
+    Glob(String),
+    /// Local git branch names
+    GitBranches,
+}
+
+impl CompletionSource {
+    /// Values starting with `prefix`, sorted and without duplicates
+    pub fn values(&self, prefix: &str) -> Result<Vec<String>> {
+        let mut values = match self {
+            Self::Command(command) => command_lines(command)?,
+            Self::Glob(pattern) => glob_paths(pattern)?,
+            Self::GitBranches => git_branches()?,
+        };
+        values.retain(|value| value.starts_with(prefix));
+        values.sort();
+        values.dedup();
+        Ok(values)
+    }
+}
+
+/// Values offered for an argument: its choices, or else its completion values
+pub fn argument_values(arg: &ArgumentSpec, prefix: &str) -> Result<Vec<String>> {
+    if let Some(choices) = arg.choices.as_ref().filter(|choices| !choices.is_empty()) {
+        return Ok(choices
+            .iter()
+            .filter(|choice| choice.starts_with(prefix))
+            .cloned()
+            .collect());
+    }
+    match &arg.completion {
+        Some(source) => source.values(prefix),
+        None => Ok(Vec::new()),
+    }
+}
+
+fn command_lines(command: &str) -> Result<Vec<String>> {
+    let output = if cfg!(windows) {
+        Command::new("cmd").args(["/C", command]).output()
+    } else {
+        Command::new("sh").args(["-c", command]).output()
+    }
+    .map_err(|e| {
+        SwissArmyHammerError::Other(format!("Failed to run completion command '{command}': {e}"))
+    })?;
+
+    if !output.status.success() {
+        return Err(SwissArmyHammerError::Other(format!(
+            "Completion command '{command}' failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(lines(&output.stdout))
+}
+
+fn glob_paths(pattern: &str) -> Result<Vec<String>> {
+    let paths = glob::glob(pattern).map_err(|e| {
+        SwissArmyHammerError::Other(format!("Invalid completion glob '{pattern}': {e}"))
+    })?;
+    Ok(paths
+        .filter_map(|path| path.ok())
+        .map(|path| path.to_string_lossy().into_owned())
+        .collect())
+}
+
+fn git_branches() -> Result<Vec<String>> {
+    let output = Command::new("git")
+        .args(["for-each-ref", "--format=%(refname:short)", "refs/heads"])
+        .output()?;
+    if !output.status.success() {
+        return Err(SwissArmyHammerError::git_command_failed(
+            "for-each-ref refs/heads",
+            output.status.code().unwrap_or(-1),
+            &String::from_utf8_lossy(&output.stderr),
+        ));
+    }
+    Ok(lines(&output.stdout))
+}
+
+fn lines(output: &[u8]) -> Vec<String> {
+    String::from_utf8_lossy(output)
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_completion_source_from_front_matter() {
+        let source: CompletionSource = serde_json::from_value(json!("git_branches")).unwrap();
+        assert_eq!(source, CompletionSource::GitBranches);
+        let source: CompletionSource =
+            serde_json::from_value(json!({ "glob": "src/*.rs" })).unwrap();
+        assert_eq!(source, CompletionSource::Glob("src/*.rs".to_string()));
+        assert!(serde_json::from_value::<CompletionSource>(json!("branches")).is_err());
+    }
+
+    #[test]
+    fn test_glob_values() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        for name in ["lib.rs", "main.rs", "notes.md"] {
+            std::fs::write(temp_dir.path().join(name), "").unwrap();
+        }
+        let pattern = format!("{}/*.rs", temp_dir.path().display());
+        let values = CompletionSource::Glob(pattern).values("").unwrap();
+        assert_eq!(values.len(), 2);
+        assert!(values[0].ends_with("lib.rs"));
+        assert!(values[1].ends_with("main.rs"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_command_values() {
+        let source = CompletionSource::Command("printf 'beta\\nalpha\\n\\nalpha\\n'".to_string());
+        assert_eq!(source.values("").unwrap(), vec!["alpha", "beta"]);
+        assert_eq!(source.values("b").unwrap(), vec!["beta"]);
+        assert!(CompletionSource::Command("exit 3".to_string())
+            .values("")
+            .is_err());
+    }
+
+    #[test]
+    fn test_argument_values_prefer_choices() {
+        let mut arg = ArgumentSpec {
+            name: "language".to_string(),
+            description: None,
+            required: true,
+            default: None,
+            type_hint: None,
+            choices: Some(vec!["rust".to_string(), "python".to_string()]),
+            completion: Some(CompletionSource::Command("exit 1".to_string())),
+            source: None,
+        };
+        assert_eq!(argument_values(&arg, "r").unwrap(), vec!["rust"]);
+
+        arg.choices = None;
+        arg.completion = None;
+        assert!(argument_values(&arg, "").unwrap().is_empty());
+    }
+}
@@ -0,0 +1,361 @@
+//! Differences between the prompts of two sources
+//!
+//! Prompts from later sources override prompts with the same name from
+//! earlier ones: builtin, then user, then local, then dynamic. Diffing two
+//! sources shows which prompts one of them adds or replaces, and how far
+//! each replacement has drifted from the prompt it overrides.
+
+use crate::file_loader::{FileEntry, FileSource};
+use crate::{PromptResolver, Result, SwissArmyHammerError};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::PathBuf;
+
+/// Unchanged lines shown around each change in a unified diff
+const DIFF_CONTEXT_LINES: usize = 3;
+
+/// How a prompt differs between the two sources
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PromptDiffKind {
+    /// Only the `to` source has the prompt
+    Added,
+    /// Only the `from` source has the prompt
+    Removed,
+    /// Both sources have the prompt and the `to` version takes precedence
+    Overridden,
+    /// Both sources have the prompt and the `from` version takes precedence,
+    /// hiding the `to` version
+    Shadowed,
+    /// Both sources have the same prompt content
+    Identical,
+}
+
+impl fmt::Display for PromptDiffKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PromptDiffKind::Added => write!(f, "added"),
+            PromptDiffKind::Removed => write!(f, "removed"),
+            PromptDiffKind::Overridden => write!(f, "overridden"),
+            PromptDiffKind::Shadowed => write!(f, "shadowed"),
+            PromptDiffKind::Identical => write!(f, "identical"),
+        }
+    }
+}
+
+/// One line of a content diff
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "op", content = "line", rename_all = "snake_case")]
+pub enum DiffLine {
+    /// A line both versions have
+    Same(String),
+    /// A line only the `from` version has
+    Removed(String),
+    /// A line only the `to` version has
+    Added(String),
+}
+
+/// A prompt that one or both sources define
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PromptDiffEntry {
+    /// Prompt name
+    pub name: String,
+    /// How the prompt differs
+    pub kind: PromptDiffKind,
+    /// File of the `from` version
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_path: Option<PathBuf>,
+    /// File of the `to` version
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_path: Option<PathBuf>,
+    /// Line diff from the `from` version to the `to` version, when both
+    /// sources have the prompt with different content
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub diff: Vec<DiffLine>,
+}
+
+impl PromptDiffEntry {
+    /// Number of lines removed and added
+    pub fn line_changes(&self) -> (usize, usize) {
+        self.diff
+            .iter()
+            .fold((0, 0), |(removed, added), line| match line {
+                DiffLine::Same(_) => (removed, added),
+                DiffLine::Removed(_) => (removed + 1, added),
+                DiffLine::Added(_) => (removed, added + 1),
+            })
+    }
+
+    /// The content diff as unified diff hunks, or an empty string when
+    /// there is no content diff
+    pub fn unified_diff(&self) -> String {
+        unified_diff(&self.diff)
+    }
+}
+
+/// The differences between the prompts of two sources
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PromptSourceDiff {
+    /// Source compared from
+    pub from: FileSource,
+    /// Source compared to
+    pub to: FileSource,
+    /// Every prompt either source defines, by name
+    pub entries: Vec<PromptDiffEntry>,
+}
+
+impl PromptSourceDiff {
+    /// Load the prompts of both sources and compare them
+    pub fn load(from: FileSource, to: FileSource) -> Result<Self> {
+        if from == to {
+            return Err(SwissArmyHammerError::Other(format!(
+                "Cannot diff the {from} source with itself"
+            )));
+        }
+        let from_files = PromptResolver::load_source_files(from.clone())?;
+        let to_files = PromptResolver::load_source_files(to.clone())?;
+        Ok(Self::compare(from, &from_files, to, &to_files))
+    }
+
+    /// Compare the prompt files of two sources
+    pub fn compare(
+        from: FileSource,
+        from_files: &[FileEntry],
+        to: FileSource,
+        to_files: &[FileEntry],
+    ) -> Self {
+        let mut files: BTreeMap<&str, (Option<&FileEntry>, Option<&FileEntry>)> = BTreeMap::new();
+        for file in from_files {
+            files.entry(&file.name).or_default().0 = Some(file);
+        }
+        for file in to_files {
+            files.entry(&file.name).or_default().1 = Some(file);
+        }
+
+        let to_wins = precedence(&to) > precedence(&from);
+        let entries = files
+            .into_iter()
+            .map(|(name, (from_file, to_file))| {
+                let (kind, diff) = match (from_file, to_file) {
+                    (Some(_), None) => (PromptDiffKind::Removed, Vec::new()),
+                    (None, _) => (PromptDiffKind::Added, Vec::new()),
+                    (Some(old), Some(new)) if old.content == new.content => {
+                        (PromptDiffKind::Identical, Vec::new())
+                    }
+                    (Some(old), Some(new)) => {
+                        let kind = if to_wins {
+                            PromptDiffKind::Overridden
+                        } else {
+                            PromptDiffKind::Shadowed
+                        };
+                        (kind, diff_lines(&old.content, &new.content))
+                    }
+                };
+                PromptDiffEntry {
+                    name: name.to_string(),
+                    kind,
+                    from_path: from_file.map(|file| file.path.clone()),
+                    to_path: to_file.map(|file| file.path.clone()),
+                    diff,
+                }
+            })
+            .collect();
+
+        Self { from, to, entries }
+    }
+
+    /// Entries of one kind
+    pub fn of_kind(&self, kind: PromptDiffKind) -> impl Iterator<Item = &PromptDiffEntry> {
+        self.entries.iter().filter(move |entry| entry.kind == kind)
+    }
+}
+
+/// Rank of a source; prompts from higher ranked sources override lower ones
+fn precedence(source: &FileSource) -> u8 {
+    match source {
+        FileSource::Builtin => 0,
+        FileSource::User => 1,
+        FileSource::Local => 2,
+        FileSource::Dynamic => 3,
+    }
+}
+
+/// A line diff as unified diff hunks with three lines of context, or an
+/// empty string when nothing changed
+pub fn unified_diff(diff: &[DiffLine]) -> String {
+    let changed: Vec<usize> = diff
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, DiffLine::Same(_)))
+        .map(|(index, _)| index)
+        .collect();
+
+    // Group changes whose context overlaps into hunks of diff indexes
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for index in changed {
+        let start = index.saturating_sub(DIFF_CONTEXT_LINES);
+        let end = (index + DIFF_CONTEXT_LINES + 1).min(diff.len());
+        match hunks.last_mut() {
+            Some(hunk) if start <= hunk.1 => hunk.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    let mut output = String::new();
+    for (start, end) in hunks {
+        let (from_line, to_line) = line_numbers_at(diff, start);
+        let lines = &diff[start..end];
+        let from_count = lines
+            .iter()
+            .filter(|line| !matches!(line, DiffLine::Added(_)))
+            .count();
+        let to_count = lines
+            .iter()
+            .filter(|line| !matches!(line, DiffLine::Removed(_)))
+            .count();
+        output.push_str(&format!(
+            "@@ -{from_line},{from_count} +{to_line},{to_count} @@\n"
+        ));
+        for line in lines {
+            let (marker, text) = match line {
+                DiffLine::Same(text) => (' ', text),
+                DiffLine::Removed(text) => ('-', text),
+                DiffLine::Added(text) => ('+', text),
+            };
+            output.push(marker);
+            output.push_str(text);
+            output.push('\n');
+        }
+    }
+    output
+}
+
+/// One-based line numbers in each version at a diff index
+fn line_numbers_at(diff: &[DiffLine], index: usize) -> (usize, usize) {
+    diff[..index]
+        .iter()
+        .fold((1, 1), |(from, to), line| match line {
+            DiffLine::Same(_) => (from + 1, to + 1),
+            DiffLine::Removed(_) => (from + 1, to),
+            DiffLine::Added(_) => (from, to + 1),
+        })
+}
+
+/// Line diff between two texts using their longest common subsequence
+pub fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // common[i][j] is the length of the longest common subsequence of
+    // old[i..] and new[j..]
+    let mut common = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut lines = Vec::with_capacity(old.len().max(new.len()));
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            lines.push(DiffLine::Same(old[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if common[i + 1][j] >= common[i][j + 1] {
+            lines.push(DiffLine::Removed(old[i].to_string()));
+            i += 1;
+        } else {
+            lines.push(DiffLine::Added(new[j].to_string()));
+            j += 1;
+        }
+    }
+    lines.extend(
+        old[i..]
+            .iter()
+            .map(|line| DiffLine::Removed(line.to_string())),
+    );
+    lines.extend(
+        new[j..]
+            .iter()
+            .map(|line| DiffLine::Added(line.to_string())),
+    );
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(name: &str, content: &str, source: FileSource) -> FileEntry {
+        FileEntry::new(
+            name,
+            PathBuf::from(format!("{source}/{name}.md")),
+            content.to_string(),
+            source,
+        )
+    }
+
+    #[test]
+    fn test_diff_lines() {
+        let diff = diff_lines("a\nb\nc\n", "a\nx\nc\nd\n");
+        assert_eq!(
+            diff,
+            vec![
+                DiffLine::Same("a".to_string()),
+                DiffLine::Removed("b".to_string()),
+                DiffLine::Added("x".to_string()),
+                DiffLine::Same("c".to_string()),
+                DiffLine::Added("d".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compare_sources() {
+        let builtin = [
+            file("same", "Hello", FileSource::Builtin),
+            file("changed", "one\ntwo\nthree", FileSource::Builtin),
+            file("upstream_only", "Builtin", FileSource::Builtin),
+        ];
+        let local = [
+            file("same", "Hello", FileSource::Local),
+            file("changed", "one\n2\nthree", FileSource::Local),
+            file("local_only", "Local", FileSource::Local),
+        ];
+
+        let diff =
+            PromptSourceDiff::compare(FileSource::Builtin, &builtin, FileSource::Local, &local);
+        let kinds: Vec<(&str, PromptDiffKind)> = diff
+            .entries
+            .iter()
+            .map(|entry| (entry.name.as_str(), entry.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("changed", PromptDiffKind::Overridden),
+                ("local_only", PromptDiffKind::Added),
+                ("same", PromptDiffKind::Identical),
+                ("upstream_only", PromptDiffKind::Removed),
+            ]
+        );
+
+        let changed = &diff.entries[0];
+        assert_eq!(changed.line_changes(), (1, 1));
+        assert_eq!(
+            changed.unified_diff(),
+            "@@ -1,3 +1,3 @@\n one\n-two\n+2\n three\n"
+        );
+
+        // Diffing the other way round, the local prompt hides the builtin one
+        let reverse =
+            PromptSourceDiff::compare(FileSource::Local, &local, FileSource::Builtin, &builtin);
+        assert_eq!(reverse.of_kind(PromptDiffKind::Shadowed).count(), 1);
+    }
+}
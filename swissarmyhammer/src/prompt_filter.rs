@@ -19,6 +19,8 @@ pub struct PromptFilter {
     pub has_arg: Option<String>,
     /// Filter to only show prompts with no arguments
     pub no_args: bool,
+    /// Filter out prompts marked deprecated
+    pub hide_deprecated: bool,
 }
 
 impl PromptFilter {
@@ -57,6 +59,12 @@ impl PromptFilter {
         self
     }
 
+    /// Sets the hide_deprecated filter
+    pub fn with_hide_deprecated(mut self, hide_deprecated: bool) -> Self {
+        self.hide_deprecated = hide_deprecated;
+        self
+    }
+
     /// Applies the filter to a list of prompts
     pub fn apply(
         &self,
@@ -122,6 +130,11 @@ impl PromptFilter {
             return false;
         }
 
+        // Check hide_deprecated filter
+        if self.hide_deprecated && prompt.is_deprecated() {
+            return false;
+        }
+
         true
     }
 }
@@ -225,6 +238,9 @@ mod tests {
             required: true,
             default: None,
             type_hint: None,
+            choices: None,
+            completion: None,
+            source: None,
         });
 
         let prompt_no_args = create_test_prompt("no_args", Some("dev"), vec![]);
@@ -245,6 +261,27 @@ mod tests {
         assert_eq!(filtered[0].name, "no_args");
     }
 
+    #[test]
+    fn test_filter_deprecated() {
+        let mut deprecated = create_test_prompt("old_review", Some("dev"), vec![]);
+        deprecated
+            .metadata
+            .insert("deprecated".to_string(), serde_json::Value::Bool(true));
+        let prompts = vec![
+            deprecated,
+            create_test_prompt("review", Some("dev"), vec![]),
+        ];
+        let sources = HashMap::new();
+
+        let filtered = PromptFilter::new().apply(prompts.clone(), &sources);
+        assert_eq!(filtered.len(), 2);
+
+        let filter = PromptFilter::new().with_hide_deprecated(true);
+        let filtered = filter.apply(prompts, &sources);
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].name, "review");
+    }
+
     #[test]
     fn test_combined_filters() {
         let prompts = vec![
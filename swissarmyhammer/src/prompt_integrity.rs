@@ -0,0 +1,429 @@
+//! Integrity verification of prompt directories
+//!
+//! A prompt directory distributed to other machines can carry a
+//! `checksums.txt` listing the SHA-256 of each of its files, in the format
+//! `sha256sum` writes, and a `checksums.txt.minisig` signing that list with
+//! [minisign](https://jedisct1.github.io/minisign/). When a directory is
+//! loaded, every prompt file must be listed with a matching checksum, and when
+//! a public key is configured the signature must verify with it.
+//!
+//! What happens to a directory that fails is up to the [`IntegrityPolicy`]:
+//! - `off` never verifies anything
+//! - `warn` (the default) logs each problem and loads the prompts anyway. Only
+//!   directories with a `checksums.txt` are verified, unless a public key is
+//!   configured, in which case unsigned directories are reported too
+//! - `require` refuses to load a directory that is unsigned or does not verify
+//!
+//! Signatures are checked with the `minisign` executable, which must be on
+//! the `PATH` when a public key is configured.
+
+use crate::config::Config;
+use crate::security::{validate_path, PathPolicy};
+use crate::{Result, SwissArmyHammerError};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// File in a prompt directory listing the SHA-256 of each of its files
+pub const CHECKSUMS_FILE: &str = "checksums.txt";
+
+/// File in a prompt directory holding the minisign signature of [`CHECKSUMS_FILE`]
+pub const SIGNATURE_FILE: &str = "checksums.txt.minisig";
+
+/// What loading does with a prompt directory that fails verification
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IntegrityPolicy {
+    /// Never verify prompt directories
+    Off,
+    /// Log problems and load the prompts anyway
+    #[default]
+    Warn,
+    /// Refuse to load directories that are unsigned or fail verification
+    Require,
+}
+
+impl FromStr for IntegrityPolicy {
+    type Err = SwissArmyHammerError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "off" => Ok(Self::Off),
+            "warn" => Ok(Self::Warn),
+            "require" => Ok(Self::Require),
+            other => Err(SwissArmyHammerError::Other(format!(
+                "Unknown prompt integrity policy '{other}', expected off, warn or require"
+            ))),
+        }
+    }
+}
+
+impl std::fmt::Display for IntegrityPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Off => write!(f, "off"),
+            Self::Warn => write!(f, "warn"),
+            Self::Require => write!(f, "require"),
+        }
+    }
+}
+
+/// A reason a prompt directory failed verification
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IntegrityProblem {
+    /// The directory has no [`CHECKSUMS_FILE`]
+    Unsigned,
+    /// A public key is configured but the directory has no [`SIGNATURE_FILE`]
+    MissingSignature,
+    /// The signature did not verify with the configured public key
+    BadSignature(String),
+    /// A line of the checksums file is not `<sha256>  <path>`
+    Malformed(usize),
+    /// A listed file's content does not match its checksum
+    Modified(PathBuf),
+    /// A listed file does not exist
+    Missing(PathBuf),
+    /// A prompt file is not listed in the checksums file
+    Unlisted(PathBuf),
+}
+
+impl std::fmt::Display for IntegrityProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unsigned => write!(f, "no {CHECKSUMS_FILE}"),
+            Self::MissingSignature => write!(f, "no {SIGNATURE_FILE}"),
+            Self::BadSignature(reason) => write!(f, "signature does not verify: {reason}"),
+            Self::Malformed(line) => write!(f, "{CHECKSUMS_FILE} line {line} is malformed"),
+            Self::Modified(path) => write!(f, "'{}' was modified", path.display()),
+            Self::Missing(path) => write!(f, "'{}' is missing", path.display()),
+            Self::Unlisted(path) => write!(f, "'{}' is not listed", path.display()),
+        }
+    }
+}
+
+/// Verifies prompt directories against their checksums and signature
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PromptIntegrity {
+    /// What loading does with a directory that fails verification
+    pub policy: IntegrityPolicy,
+    /// Minisign public key, the base64 line of a `.pub` file, checksums must be signed with
+    pub public_key: Option<String>,
+}
+
+impl PromptIntegrity {
+    /// Verify prompt directories under `policy`, without checking signatures
+    pub fn new(policy: IntegrityPolicy) -> Self {
+        Self {
+            policy,
+            public_key: None,
+        }
+    }
+
+    /// Also require checksums to be signed with the minisign `public_key`
+    pub fn with_public_key(mut self, public_key: impl Into<String>) -> Self {
+        self.public_key = Some(public_key.into());
+        self
+    }
+
+    /// Build the verification from configuration settings
+    ///
+    /// An unknown policy is treated as `require`, so a typo never weakens
+    /// verification.
+    pub fn from_config(config: &Config) -> Self {
+        let policy = config.prompt_integrity.parse().unwrap_or_else(|e| {
+            tracing::warn!("{e}; verifying prompt directories with 'require'");
+            IntegrityPolicy::Require
+        });
+        Self {
+            policy,
+            public_key: config
+                .prompt_public_key
+                .as_deref()
+                .map(str::trim)
+                .filter(|key| !key.is_empty())
+                .map(str::to_string),
+        }
+    }
+
+    /// Get the verification built from the global configuration
+    pub fn global() -> &'static Self {
+        static INTEGRITY: std::sync::OnceLock<PromptIntegrity> = std::sync::OnceLock::new();
+        INTEGRITY.get_or_init(|| PromptIntegrity::from_config(Config::global()))
+    }
+
+    /// Find the problems with prompt directory `dir` and the prompt `files` in it
+    ///
+    /// A directory without a checksums file is only a problem under the
+    /// `require` policy or when a public key is configured.
+    pub fn verify(&self, dir: &Path, files: &[PathBuf]) -> Result<Vec<IntegrityProblem>> {
+        if self.policy == IntegrityPolicy::Off {
+            return Ok(Vec::new());
+        }
+
+        let checksums_path = dir.join(CHECKSUMS_FILE);
+        if !checksums_path.is_file() {
+            let unsigned = !files.is_empty()
+                && (self.policy == IntegrityPolicy::Require || self.public_key.is_some());
+            return Ok(if unsigned {
+                vec![IntegrityProblem::Unsigned]
+            } else {
+                Vec::new()
+            });
+        }
+
+        let mut problems = Vec::new();
+        if let Some(public_key) = &self.public_key {
+            if let Some(problem) = verify_signature(dir, public_key) {
+                problems.push(problem);
+            }
+        }
+
+        let (listed, malformed) = parse_checksums(&std::fs::read_to_string(&checksums_path)?);
+        problems.extend(malformed.into_iter().map(IntegrityProblem::Malformed));
+
+        for (relative, expected) in &listed {
+            let relative = PathBuf::from(relative);
+            let Ok(path) = validate_path(&relative, dir, &PathPolicy::default()) else {
+                problems.push(IntegrityProblem::Missing(relative));
+                continue;
+            };
+            match std::fs::read(&path) {
+                Ok(content) => {
+                    if format!("{:x}", Sha256::digest(&content)) != *expected {
+                        problems.push(IntegrityProblem::Modified(relative));
+                    }
+                }
+                Err(_) => problems.push(IntegrityProblem::Missing(relative)),
+            }
+        }
+
+        for file in files {
+            let relative = relative_name(dir, file);
+            if !listed.contains_key(&relative) {
+                problems.push(IntegrityProblem::Unlisted(PathBuf::from(relative)));
+            }
+        }
+
+        Ok(problems)
+    }
+
+    /// Verify prompt directory `dir`, logging its problems under the `warn`
+    /// policy and failing on them under `require`
+    pub fn check(&self, dir: &Path, files: &[PathBuf]) -> Result<()> {
+        let problems = self.verify(dir, files)?;
+        if problems.is_empty() {
+            return Ok(());
+        }
+
+        if self.policy == IntegrityPolicy::Require {
+            let problems: Vec<String> = problems.iter().map(ToString::to_string).collect();
+            return Err(SwissArmyHammerError::Other(format!(
+                "Prompt directory '{}' failed integrity verification: {}",
+                dir.display(),
+                problems.join("; ")
+            )));
+        }
+        for problem in &problems {
+            tracing::warn!(
+                "Prompt directory '{}' failed integrity verification: {}",
+                dir.display(),
+                problem
+            );
+        }
+        Ok(())
+    }
+}
+
+/// The content of a checksums file listing `files` in `dir`, in the format
+/// `sha256sum` writes
+pub fn checksums_for(dir: &Path, files: &[PathBuf]) -> Result<String> {
+    let mut lines = BTreeMap::new();
+    for file in files {
+        let digest = Sha256::digest(std::fs::read(file)?);
+        lines.insert(relative_name(dir, file), format!("{digest:x}"));
+    }
+    Ok(lines
+        .into_iter()
+        .map(|(name, digest)| format!("{digest}  {name}\n"))
+        .collect())
+}
+
+/// The path of `file` below `dir`, with `/` separators as checksums list it
+fn relative_name(dir: &Path, file: &Path) -> String {
+    let relative = file.strip_prefix(dir).unwrap_or(file);
+    let parts: Vec<String> = relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    parts.join("/")
+}
+
+/// The checksums of a checksums file by path, and the numbers of its
+/// malformed lines
+///
+/// Lines are `<sha256>  <path>`, optionally with `*` before the path as
+/// `sha256sum --binary` writes; blank lines and `#` comments are skipped.
+fn parse_checksums(content: &str) -> (BTreeMap<String, String>, Vec<usize>) {
+    let mut listed = BTreeMap::new();
+    let mut malformed = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let Some((digest, path)) = line.split_once(char::is_whitespace) else {
+            malformed.push(index + 1);
+            continue;
+        };
+        let path = path.trim_start();
+        let path = path.strip_prefix('*').unwrap_or(path);
+        let path = path.strip_prefix("./").unwrap_or(path);
+        if digest.len() != 64 || !digest.chars().all(|c| c.is_ascii_hexdigit()) || path.is_empty() {
+            malformed.push(index + 1);
+            continue;
+        }
+        listed.insert(path.to_string(), digest.to_ascii_lowercase());
+    }
+    (listed, malformed)
+}
+
+/// Check the signature of the checksums file in `dir` with the `minisign`
+/// executable, returning the problem when it does not verify
+fn verify_signature(dir: &Path, public_key: &str) -> Option<IntegrityProblem> {
+    let signature = dir.join(SIGNATURE_FILE);
+    if !signature.is_file() {
+        return Some(IntegrityProblem::MissingSignature);
+    }
+    let Ok(minisign) = which::which("minisign") else {
+        return Some(IntegrityProblem::BadSignature(
+            "minisign is not installed".to_string(),
+        ));
+    };
+
+    match std::process::Command::new(minisign)
+        .arg("-V")
+        .arg("-q")
+        .arg("-P")
+        .arg(public_key)
+        .arg("-m")
+        .arg(dir.join(CHECKSUMS_FILE))
+        .arg("-x")
+        .arg(signature)
+        .output()
+    {
+        Ok(output) if output.status.success() => None,
+        Ok(output) => Some(IntegrityProblem::BadSignature(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        )),
+        Err(e) => Some(IntegrityProblem::BadSignature(e.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn prompt_dir() -> (TempDir, Vec<PathBuf>) {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("review")).unwrap();
+        let files = vec![
+            temp_dir.path().join("greet.md"),
+            temp_dir.path().join("review/code.md"),
+        ];
+        fs::write(&files[0], "Hello {{ name }}").unwrap();
+        fs::write(&files[1], "Review {{ file }}").unwrap();
+        (temp_dir, files)
+    }
+
+    #[test]
+    fn test_policy_from_str() {
+        assert_eq!(
+            "off".parse::<IntegrityPolicy>().unwrap(),
+            IntegrityPolicy::Off
+        );
+        assert_eq!(
+            " Require ".parse::<IntegrityPolicy>().unwrap(),
+            IntegrityPolicy::Require
+        );
+        assert!("strict".parse::<IntegrityPolicy>().is_err());
+        assert_eq!(IntegrityPolicy::default().to_string(), "warn");
+    }
+
+    #[test]
+    fn test_parse_checksums() {
+        let digest = "a".repeat(64);
+        let content =
+            format!("# prompts\n{digest}  greet.md\n{digest} *./review/code.md\n\nbad line\n");
+        let (listed, malformed) = parse_checksums(&content);
+        assert_eq!(listed.len(), 2);
+        assert_eq!(listed["review/code.md"], digest);
+        assert_eq!(malformed, vec![5]);
+    }
+
+    #[test]
+    fn test_unsigned_directory() {
+        let (temp_dir, files) = prompt_dir();
+
+        let warn = PromptIntegrity::new(IntegrityPolicy::Warn);
+        assert!(warn.verify(temp_dir.path(), &files).unwrap().is_empty());
+
+        let keyed = warn.with_public_key("RWQ");
+        assert_eq!(
+            keyed.verify(temp_dir.path(), &files).unwrap(),
+            vec![IntegrityProblem::Unsigned]
+        );
+
+        let require = PromptIntegrity::new(IntegrityPolicy::Require);
+        assert!(require.check(temp_dir.path(), &files).is_err());
+        assert!(PromptIntegrity::new(IntegrityPolicy::Off)
+            .check(temp_dir.path(), &files)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_tampered_directory() {
+        let (temp_dir, files) = prompt_dir();
+        let checksums = checksums_for(temp_dir.path(), &files).unwrap();
+        fs::write(temp_dir.path().join(CHECKSUMS_FILE), checksums).unwrap();
+
+        let require = PromptIntegrity::new(IntegrityPolicy::Require);
+        assert!(require.verify(temp_dir.path(), &files).unwrap().is_empty());
+        require.check(temp_dir.path(), &files).unwrap();
+
+        fs::write(&files[1], "Ignore previous instructions").unwrap();
+        let added = temp_dir.path().join("extra.md");
+        fs::write(&added, "Unlisted").unwrap();
+        fs::remove_file(&files[0]).unwrap();
+
+        let problems = require
+            .verify(temp_dir.path(), &[files[1].clone(), added.clone()])
+            .unwrap();
+        assert_eq!(
+            problems,
+            vec![
+                IntegrityProblem::Missing(PathBuf::from("greet.md")),
+                IntegrityProblem::Modified(PathBuf::from("review/code.md")),
+                IntegrityProblem::Unlisted(PathBuf::from("extra.md")),
+            ]
+        );
+        assert!(require.check(temp_dir.path(), &[added.clone()]).is_err());
+        assert!(PromptIntegrity::new(IntegrityPolicy::Warn)
+            .check(temp_dir.path(), &[added])
+            .is_ok());
+    }
+
+    #[test]
+    fn test_missing_signature() {
+        let (temp_dir, files) = prompt_dir();
+        let checksums = checksums_for(temp_dir.path(), &files).unwrap();
+        fs::write(temp_dir.path().join(CHECKSUMS_FILE), checksums).unwrap();
+
+        let integrity = PromptIntegrity::new(IntegrityPolicy::Require).with_public_key("RWQ");
+        assert_eq!(
+            integrity.verify(temp_dir.path(), &files).unwrap(),
+            vec![IntegrityProblem::MissingSignature]
+        );
+    }
+}
@@ -0,0 +1,306 @@
+//! Named argument presets for prompts
+//!
+//! Presets let users save a set of prompt arguments under a name and reuse it
+//! later instead of retyping every argument. Presets are stored as YAML files
+//! under `.swissarmyhammer/presets/`, one file per prompt, mapping preset names
+//! to argument sets:
+//!
+//! ```yaml
+//! rust-strict:
+//!   language: rust
+//!   strictness: high
+//! ```
+
+use crate::error::{Result, SwissArmyHammerError};
+use crate::fs_utils::FileSystemUtils;
+use crate::prompts::Prompt;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Component, Path, PathBuf};
+
+/// Argument key that selects a preset when passed alongside prompt arguments
+pub const PRESET_ARGUMENT_KEY: &str = "preset";
+
+/// All presets stored for a single prompt, keyed by preset name
+type PresetFile = BTreeMap<String, BTreeMap<String, String>>;
+
+/// File-backed storage for prompt argument presets
+pub struct PresetStorage {
+    presets_dir: PathBuf,
+    fs_utils: FileSystemUtils,
+}
+
+impl PresetStorage {
+    /// Create a preset storage rooted at the given directory
+    pub fn new(presets_dir: PathBuf) -> Self {
+        Self {
+            presets_dir,
+            fs_utils: FileSystemUtils::new(),
+        }
+    }
+
+    /// Create a preset storage with the default presets directory
+    ///
+    /// Uses the `SWISSARMYHAMMER_PRESETS_DIR` environment variable if set,
+    /// otherwise defaults to `.swissarmyhammer/presets` in the current directory.
+    pub fn new_default() -> Result<Self> {
+        let presets_dir = if let Ok(custom_path) = std::env::var("SWISSARMYHAMMER_PRESETS_DIR") {
+            PathBuf::from(custom_path)
+        } else {
+            std::env::current_dir()?
+                .join(".swissarmyhammer")
+                .join("presets")
+        };
+        Ok(Self::new(presets_dir))
+    }
+
+    /// Get the directory presets are stored in
+    pub fn presets_dir(&self) -> &Path {
+        &self.presets_dir
+    }
+
+    /// Save a named preset for a prompt, replacing any existing preset with the same name
+    pub fn save(
+        &self,
+        prompt_name: &str,
+        preset_name: &str,
+        arguments: &HashMap<String, String>,
+    ) -> Result<()> {
+        validate_preset_name(preset_name)?;
+        let path = self.preset_file_path(prompt_name)?;
+
+        let mut presets = self.read_presets(&path)?;
+        presets.insert(
+            preset_name.to_string(),
+            arguments
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+        );
+
+        self.fs_utils.write_yaml(&path, &presets)
+    }
+
+    /// Load the arguments stored in a named preset
+    pub fn load(&self, prompt_name: &str, preset_name: &str) -> Result<HashMap<String, String>> {
+        validate_preset_name(preset_name)?;
+        let path = self.preset_file_path(prompt_name)?;
+
+        self.read_presets(&path)?
+            .remove(preset_name)
+            .map(|args| args.into_iter().collect())
+            .ok_or_else(|| {
+                SwissArmyHammerError::Other(format!(
+                    "Preset '{preset_name}' not found for prompt '{prompt_name}'"
+                ))
+            })
+    }
+
+    /// List the preset names saved for a prompt, in sorted order
+    pub fn list(&self, prompt_name: &str) -> Result<Vec<String>> {
+        let path = self.preset_file_path(prompt_name)?;
+        Ok(self.read_presets(&path)?.into_keys().collect())
+    }
+
+    /// Delete a named preset, returning whether it existed
+    pub fn delete(&self, prompt_name: &str, preset_name: &str) -> Result<bool> {
+        validate_preset_name(preset_name)?;
+        let path = self.preset_file_path(prompt_name)?;
+
+        let mut presets = self.read_presets(&path)?;
+        if presets.remove(preset_name).is_none() {
+            return Ok(false);
+        }
+
+        if presets.is_empty() {
+            self.fs_utils.fs().remove_file(&path)?;
+        } else {
+            self.fs_utils.write_yaml(&path, &presets)?;
+        }
+        Ok(true)
+    }
+
+    /// Resolve the arguments for a prompt, applying a preset if one is selected
+    ///
+    /// If `arguments` contains the [`PRESET_ARGUMENT_KEY`] key, the named preset is
+    /// loaded and the remaining arguments are layered on top of it, so explicitly
+    /// provided values always win over preset values. A prompt that declares its
+    /// own `preset` argument keeps it, and no preset is applied.
+    pub fn apply(
+        &self,
+        prompt: &Prompt,
+        arguments: &HashMap<String, String>,
+    ) -> Result<HashMap<String, String>> {
+        if prompt
+            .arguments
+            .iter()
+            .any(|arg| arg.name == PRESET_ARGUMENT_KEY)
+        {
+            return Ok(arguments.clone());
+        }
+        let Some(preset_name) = arguments.get(PRESET_ARGUMENT_KEY) else {
+            return Ok(arguments.clone());
+        };
+
+        let mut resolved = self.load(&prompt.name, preset_name)?;
+        resolved.extend(
+            arguments
+                .iter()
+                .filter(|(key, _)| key.as_str() != PRESET_ARGUMENT_KEY)
+                .map(|(k, v)| (k.clone(), v.clone())),
+        );
+        Ok(resolved)
+    }
+
+    fn read_presets(&self, path: &Path) -> Result<PresetFile> {
+        if !self.fs_utils.fs().exists(path) {
+            return Ok(PresetFile::new());
+        }
+        self.fs_utils.read_yaml(path)
+    }
+
+    fn preset_file_path(&self, prompt_name: &str) -> Result<PathBuf> {
+        let relative = Path::new(prompt_name);
+        let is_safe = !prompt_name.is_empty()
+            && relative
+                .components()
+                .all(|component| matches!(component, Component::Normal(_)));
+
+        if !is_safe {
+            return Err(SwissArmyHammerError::Other(format!(
+                "Invalid prompt name for presets: '{prompt_name}'"
+            )));
+        }
+
+        Ok(self.presets_dir.join(format!("{prompt_name}.yaml")))
+    }
+}
+
+/// Validate that a preset name is safe to use as a key and on the command line
+fn validate_preset_name(name: &str) -> Result<()> {
+    if name.is_empty() {
+        return Err(SwissArmyHammerError::Other(
+            "Preset name cannot be empty".to_string(),
+        ));
+    }
+
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+    {
+        return Err(SwissArmyHammerError::Other(format!(
+            "Invalid preset name '{name}': use letters, digits, '-', '_' or '.'"
+        )));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn args(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_save_and_load_preset() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = PresetStorage::new(temp_dir.path().to_path_buf());
+
+        let saved = args(&[("language", "rust"), ("strictness", "high")]);
+        storage.save("code-review", "rust-strict", &saved).unwrap();
+
+        let loaded = storage.load("code-review", "rust-strict").unwrap();
+        assert_eq!(loaded, saved);
+        assert!(temp_dir.path().join("code-review.yaml").exists());
+    }
+
+    #[test]
+    fn test_list_and_delete_presets() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = PresetStorage::new(temp_dir.path().to_path_buf());
+
+        storage.save("review/code", "b", &args(&[])).unwrap();
+        storage.save("review/code", "a", &args(&[])).unwrap();
+        assert_eq!(storage.list("review/code").unwrap(), vec!["a", "b"]);
+
+        assert!(storage.delete("review/code", "a").unwrap());
+        assert!(!storage.delete("review/code", "a").unwrap());
+        assert_eq!(storage.list("review/code").unwrap(), vec!["b"]);
+
+        assert!(storage.delete("review/code", "b").unwrap());
+        assert!(!temp_dir.path().join("review/code.yaml").exists());
+    }
+
+    #[test]
+    fn test_apply_preset_with_overrides() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = PresetStorage::new(temp_dir.path().to_path_buf());
+        storage
+            .save(
+                "code-review",
+                "rust-strict",
+                &args(&[("language", "rust"), ("strictness", "high")]),
+            )
+            .unwrap();
+
+        let resolved = storage
+            .apply(
+                &Prompt::new("code-review", "{{ language }}"),
+                &args(&[("preset", "rust-strict"), ("strictness", "low")]),
+            )
+            .unwrap();
+
+        assert_eq!(resolved.get("language").unwrap(), "rust");
+        assert_eq!(resolved.get("strictness").unwrap(), "low");
+        assert!(!resolved.contains_key(PRESET_ARGUMENT_KEY));
+    }
+
+    #[test]
+    fn test_apply_keeps_a_declared_preset_argument() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = PresetStorage::new(temp_dir.path().to_path_buf());
+        storage
+            .save("slides", "rust-strict", &args(&[("language", "rust")]))
+            .unwrap();
+
+        let prompt = Prompt::new("slides", "Theme: {{ preset }}").add_argument(
+            crate::prompts::ArgumentSpec {
+                name: "preset".to_string(),
+                description: None,
+                required: true,
+                default: None,
+                type_hint: None,
+                choices: None,
+                completion: None,
+                source: None,
+            },
+        );
+        let arguments = args(&[("preset", "rust-strict")]);
+        assert_eq!(storage.apply(&prompt, &arguments).unwrap(), arguments);
+    }
+
+    #[test]
+    fn test_load_missing_preset_fails() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = PresetStorage::new(temp_dir.path().to_path_buf());
+
+        assert!(storage.load("code-review", "missing").is_err());
+    }
+
+    #[test]
+    fn test_rejects_unsafe_names() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = PresetStorage::new(temp_dir.path().to_path_buf());
+
+        assert!(storage.save("../escape", "ok", &args(&[])).is_err());
+        assert!(storage.save("/absolute", "ok", &args(&[])).is_err());
+        assert!(storage.save("code-review", "bad/name", &args(&[])).is_err());
+        assert!(storage.save("code-review", "", &args(&[])).is_err());
+    }
+}
@@ -0,0 +1,250 @@
+//! Prompt references pinned to a content hash or version
+//!
+//! A workflow names the prompt a state executes, `code-review`, and the state
+//! renders whatever the prompt's file holds when it runs. Runs that must be
+//! reproducible can pin the prompt instead:
+//!
+//! - `code-review@sha256:3f2a9c41`: the prompt whose template hashes to a
+//!   SHA-256 digest starting with these hex digits
+//! - `code-review@1.2`: the prompt whose `version` front matter field is `1.2`
+//!
+//! When the pinned content is missing, because the prompt was edited or its
+//! version bumped, the state fails and `validate` reports the workflow, rather
+//! than sending Claude something the run was not pinned to. `prompt list
+//! --verbose` shows the hash and version to pin.
+//!
+//! Every prompt a run executes, pinned or not, records the hash it resolved to
+//! in the run's prompt history, see [`PromptRecord`](crate::workflow::PromptRecord).
+
+use crate::workflow::{parse_action_from_description, PromptAction, Workflow, WorkflowHookEvent};
+use crate::{Prompt, Result, SwissArmyHammerError};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::str::FromStr;
+
+/// Prefix of a content hash in a prompt reference
+pub const HASH_PREFIX: &str = "sha256:";
+
+/// Fewest hex digits a pinned hash may be shortened to
+const MIN_HASH_DIGITS: usize = 8;
+
+/// Content hash of a prompt template, `sha256:` and its hex digest
+pub fn content_hash(template: &str) -> String {
+    format!("{HASH_PREFIX}{:x}", Sha256::digest(template.as_bytes()))
+}
+
+/// The content a prompt reference resolved to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResolvedPrompt {
+    /// Content hash of the prompt template
+    pub hash: String,
+    /// The prompt's `version` front matter field, if it sets one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+impl ResolvedPrompt {
+    /// The hash and version of a loaded prompt
+    pub fn of(prompt: &Prompt) -> Self {
+        Self {
+            hash: content_hash(&prompt.template),
+            version: prompt.version(),
+        }
+    }
+}
+
+/// What a prompt reference is pinned to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PromptPin {
+    /// Lowercase hex digits the template's SHA-256 digest starts with
+    Hash(String),
+    /// Value of the prompt's `version` front matter field
+    Version(String),
+}
+
+impl PromptPin {
+    /// Whether resolved prompt content is the pinned content
+    pub fn matches(&self, resolved: &ResolvedPrompt) -> bool {
+        match self {
+            Self::Hash(digits) => resolved
+                .hash
+                .strip_prefix(HASH_PREFIX)
+                .is_some_and(|hex| hex.starts_with(digits.as_str())),
+            Self::Version(version) => resolved.version.as_deref() == Some(version.as_str()),
+        }
+    }
+}
+
+impl fmt::Display for PromptPin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Hash(digits) => write!(f, "{HASH_PREFIX}{digits}"),
+            Self::Version(version) => write!(f, "version {version}"),
+        }
+    }
+}
+
+/// A prompt named by a workflow, optionally pinned to its content
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PromptRef {
+    /// Name of the prompt
+    pub name: String,
+    /// Content the prompt must resolve to, if pinned
+    pub pin: Option<PromptPin>,
+}
+
+impl PromptRef {
+    /// Resolve a loaded prompt against this reference, failing when the
+    /// pinned content is missing
+    pub fn resolve(&self, prompt: &Prompt) -> Result<ResolvedPrompt> {
+        let resolved = ResolvedPrompt::of(prompt);
+        let Some(pin) = &self.pin else {
+            return Ok(resolved);
+        };
+        if pin.matches(&resolved) {
+            return Ok(resolved);
+        }
+        let found = match (pin, &resolved.version) {
+            (PromptPin::Version(_), Some(version)) => format!("version {version}"),
+            (PromptPin::Version(_), None) => "no version".to_string(),
+            (PromptPin::Hash(_), _) => resolved.hash.clone(),
+        };
+        Err(SwissArmyHammerError::Other(format!(
+            "Prompt '{}' is pinned to {pin}, but the loaded prompt has {found}",
+            self.name
+        )))
+    }
+}
+
+impl FromStr for PromptRef {
+    type Err = SwissArmyHammerError;
+
+    fn from_str(reference: &str) -> Result<Self> {
+        let Some((name, pin)) = reference.rsplit_once('@') else {
+            return Ok(Self {
+                name: reference.to_string(),
+                pin: None,
+            });
+        };
+        let invalid = |reason: &str| {
+            SwissArmyHammerError::Other(format!("Invalid prompt reference '{reference}': {reason}"))
+        };
+        if name.is_empty() {
+            return Err(invalid("missing the prompt name"));
+        }
+        let pin = match pin.strip_prefix(HASH_PREFIX) {
+            Some(digits) => {
+                if digits.len() < MIN_HASH_DIGITS || !digits.chars().all(|c| c.is_ascii_hexdigit())
+                {
+                    return Err(invalid(&format!(
+                        "a hash needs at least {MIN_HASH_DIGITS} hex digits"
+                    )));
+                }
+                PromptPin::Hash(digits.to_ascii_lowercase())
+            }
+            None if pin.trim().is_empty() => return Err(invalid("missing the version")),
+            None => PromptPin::Version(pin.to_string()),
+        };
+        Ok(Self {
+            name: name.to_string(),
+            pin: Some(pin),
+        })
+    }
+}
+
+impl fmt::Display for PromptRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.pin {
+            Some(PromptPin::Hash(digits)) => write!(f, "{}@{HASH_PREFIX}{digits}", self.name),
+            Some(PromptPin::Version(version)) => write!(f, "{}@{version}", self.name),
+            None => write!(f, "{}", self.name),
+        }
+    }
+}
+
+/// The pinned prompts a workflow's states and hooks execute
+pub fn pinned_prompts(workflow: &Workflow) -> Vec<PromptRef> {
+    let hooks = WorkflowHookEvent::ALL
+        .into_iter()
+        .filter_map(|event| workflow.hooks.get(event));
+    workflow
+        .states
+        .values()
+        .map(|state| state.description.as_str())
+        .chain(hooks)
+        .filter_map(|description| parse_action_from_description(description).ok().flatten())
+        .filter_map(|action| {
+            action
+                .as_any()
+                .downcast_ref::<PromptAction>()
+                .filter(|prompt| prompt.pin.is_some())
+                .map(PromptAction::prompt_ref)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_prompt_refs() {
+        let plain: PromptRef = "code-review".parse().unwrap();
+        assert_eq!(plain.name, "code-review");
+        assert!(plain.pin.is_none());
+
+        let hashed: PromptRef = "code-review@sha256:ABCDEF0123".parse().unwrap();
+        assert_eq!(hashed.pin, Some(PromptPin::Hash("abcdef0123".to_string())));
+        assert_eq!(hashed.to_string(), "code-review@sha256:abcdef0123");
+
+        let versioned: PromptRef = "review/code@1.2".parse().unwrap();
+        assert_eq!(versioned.name, "review/code");
+        assert_eq!(versioned.pin, Some(PromptPin::Version("1.2".to_string())));
+
+        assert!("code-review@sha256:abc".parse::<PromptRef>().is_err());
+        assert!("code-review@sha256:nothexdigits"
+            .parse::<PromptRef>()
+            .is_err());
+        assert!("code-review@".parse::<PromptRef>().is_err());
+        assert!("@1.2".parse::<PromptRef>().is_err());
+    }
+
+    #[test]
+    fn test_resolve_pinned_prompt() {
+        let mut prompt = Prompt::new("code-review", "Review {{ file }}");
+        prompt
+            .metadata
+            .insert("version".to_string(), serde_json::json!("1.2"));
+        let hash = content_hash(&prompt.template);
+        assert_eq!(hash.len(), HASH_PREFIX.len() + 64);
+
+        let resolved = PromptRef::from_str("code-review").unwrap().resolve(&prompt);
+        assert_eq!(resolved.unwrap().hash, hash);
+        let pinned = format!("code-review@{}", &hash[..HASH_PREFIX.len() + 12]);
+        assert!(pinned
+            .parse::<PromptRef>()
+            .unwrap()
+            .resolve(&prompt)
+            .is_ok());
+        assert!("code-review@1.2"
+            .parse::<PromptRef>()
+            .unwrap()
+            .resolve(&prompt)
+            .is_ok());
+
+        prompt.template = "Review {{ file }} carefully".to_string();
+        let error = pinned
+            .parse::<PromptRef>()
+            .unwrap()
+            .resolve(&prompt)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains(&content_hash(&prompt.template)));
+        assert!("code-review@1.3"
+            .parse::<PromptRef>()
+            .unwrap()
+            .resolve(&prompt)
+            .is_err());
+    }
+}
@@ -1,27 +1,71 @@
-use crate::file_loader::{FileSource, VirtualFileSystem};
+use crate::directory_utils::walk_files_with_extensions;
+use crate::file_loader::{FileEntry, FileSource, SourceFilter, VirtualFileSystem, FILE_EXTENSIONS};
+use crate::packages::check_installed_packages;
+use crate::prompt_integrity::PromptIntegrity;
 use crate::{PromptLibrary, PromptLoader, Result};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
+use std::path::PathBuf;
 
 // Include the generated builtin prompts
 include!(concat!(env!("OUT_DIR"), "/builtin_prompts.rs"));
 
+/// Prompts a change to prompt files updated or removed
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PromptChanges {
+    /// Prompts loaded again, including ones now loaded from another file
+    pub updated: Vec<String>,
+    /// Prompts no file provides any more
+    pub removed: Vec<String>,
+}
+
+impl PromptChanges {
+    /// Whether no prompt changed
+    pub fn is_empty(&self) -> bool {
+        self.updated.is_empty() && self.removed.is_empty()
+    }
+}
+
 /// Handles loading prompts from various sources with proper precedence
 pub struct PromptResolver {
     /// Track the source of each prompt by name
     pub prompt_sources: HashMap<String, FileSource>,
+    /// Track the file each prompt was loaded from by name
+    pub prompt_paths: HashMap<String, PathBuf>,
     /// Virtual file system for managing prompts
     vfs: VirtualFileSystem,
+    /// Directory of prompts a workflow run pinned, loaded instead of the
+    /// prompt directories
+    pinned_dir: Option<PathBuf>,
 }
 
 impl PromptResolver {
-    /// Create a new PromptResolver
+    /// Create a new PromptResolver loading from the sources the global
+    /// [`SourceFilter`] allows, verifying prompt directories with the global
+    /// [`PromptIntegrity`]
     pub fn new() -> Self {
         Self {
             prompt_sources: HashMap::new(),
-            vfs: VirtualFileSystem::new("prompts"),
+            prompt_paths: HashMap::new(),
+            vfs: VirtualFileSystem::new("prompts")
+                .with_source_filter(SourceFilter::global())
+                .with_integrity(PromptIntegrity::global().clone()),
+            pinned_dir: crate::workflow::pinned_prompts_dir(),
         }
     }
 
+    /// Only load prompts from the sources `source_filter` allows
+    pub fn with_source_filter(mut self, source_filter: SourceFilter) -> Self {
+        self.vfs.source_filter = source_filter;
+        self
+    }
+
+    /// Load the prompts a workflow run pinned in `dir` instead of the prompt
+    /// directories
+    pub fn with_pinned_dir(mut self, dir: PathBuf) -> Self {
+        self.pinned_dir = Some(dir);
+        self
+    }
+
     /// Get all directories that prompts are loaded from
     /// Returns paths in the same order as loading precedence
     pub fn get_prompt_directories(&self) -> Result<Vec<std::path::PathBuf>> {
@@ -32,12 +76,27 @@ impl PromptResolver {
     /// 1. Builtin prompts (least specific, embedded in binary)
     /// 2. User prompts from ~/.swissarmyhammer/prompts
     /// 3. Local prompts from .swissarmyhammer directories (most specific)
+    ///
+    /// Fails when an installed package's dependencies are not met.
     pub fn load_all_prompts(&mut self, library: &mut PromptLibrary) -> Result<()> {
+        check_installed_packages()?;
+
         // Load builtin prompts first (least precedence)
         self.load_builtin_prompts()?;
 
-        // Load all files from directories using VFS
-        self.vfs.load_all()?;
+        // Storage that loads templates when they are used only needs metadata
+        self.vfs.front_matter_only = library.loads_templates_lazily();
+
+        // Load all files from directories using VFS, or the copies a workflow
+        // run pinned so files edited during the run do not change it. The
+        // copies were verified when they were pinned and carry no checksums.
+        match &self.pinned_dir {
+            Some(pinned) => {
+                self.vfs.integrity = None;
+                self.vfs.load_directory(pinned, FileSource::Local)?
+            }
+            None => self.vfs.load_all()?,
+        }
 
         // Process all loaded files into prompts
         let loader = PromptLoader::new();
@@ -48,16 +107,157 @@ impl PromptResolver {
             // Track the source
             self.prompt_sources
                 .insert(prompt.name.clone(), file.source.clone());
+            self.prompt_paths
+                .insert(prompt.name.clone(), file.path.clone());
 
-            // Add to library
-            library.add(prompt)?;
+            // Add to library, letting lazy storage reload file backed templates
+            if file.source == FileSource::Builtin {
+                library.add(prompt)?;
+            } else {
+                library.add_from_file(prompt, &file.path)?;
+            }
         }
 
+        // The prompts are in the library now; only where they came from is kept
+        self.vfs.files.clear();
+        self.vfs.file_sources.clear();
+
         Ok(())
     }
 
+    /// Apply changed prompt files to a library loaded by [`Self::load_all_prompts`]
+    ///
+    /// Only the prompts named by the changed paths are loaded again, with the
+    /// same precedence as a full load: a changed file is parsed again, and the
+    /// prompt of a removed file is removed or loaded from the file it was
+    /// overriding. A directory stands for the files below it, so a renamed
+    /// file or directory is a removal and an addition. Prompts pinned by a
+    /// workflow run are not changed.
+    pub fn apply_changes(
+        &mut self,
+        library: &mut PromptLibrary,
+        paths: &[PathBuf],
+    ) -> Result<PromptChanges> {
+        let mut changes = PromptChanges::default();
+        if self.pinned_dir.is_some() {
+            return Ok(changes);
+        }
+        self.vfs.front_matter_only = library.loads_templates_lazily();
+
+        let directories: Vec<(PathBuf, FileSource, Vec<PathBuf>)> = self
+            .vfs
+            .source_directories()?
+            .into_iter()
+            .map(|(directory, source)| {
+                let files: Vec<PathBuf> =
+                    walk_files_with_extensions(&directory, FILE_EXTENSIONS).collect();
+                self.vfs.verify_directory(&directory, &files)?;
+                Ok((directory, source, files))
+            })
+            .collect::<Result<_>>()?;
+
+        // Prompts of the files now at the paths, and of the files loaded from them
+        let mut names = BTreeSet::new();
+        for path in paths {
+            for (_, _, files) in &directories {
+                names.extend(
+                    files
+                        .iter()
+                        .filter(|file| file.starts_with(path))
+                        .map(|file| FileEntry::name_from_path(file)),
+                );
+            }
+            names.extend(
+                self.prompt_paths
+                    .iter()
+                    .filter(|(_, loaded)| loaded.starts_with(path))
+                    .map(|(name, _)| name.clone()),
+            );
+        }
+
+        let loader = PromptLoader::new();
+        for name in names {
+            match self.find_file(&name, &directories) {
+                Some(file) => {
+                    let prompt = loader.load_from_string(&file.name, &file.content)?;
+                    if file.source == FileSource::Builtin {
+                        library.add(prompt)?;
+                    } else {
+                        library.add_from_file(prompt, &file.path)?;
+                    }
+                    self.prompt_sources.insert(name.clone(), file.source);
+                    self.prompt_paths.insert(name.clone(), file.path);
+                    changes.updated.push(name);
+                }
+                None => {
+                    self.prompt_sources.remove(&name);
+                    if self.prompt_paths.remove(&name).is_some() {
+                        library.remove(&name)?;
+                        changes.removed.push(name);
+                    }
+                }
+            }
+        }
+
+        Ok(changes)
+    }
+
+    /// The file a full load would load the prompt `name` from, looking
+    /// through the files of `directories` from most to least specific and
+    /// then the builtin prompts
+    fn find_file(
+        &self,
+        name: &str,
+        directories: &[(PathBuf, FileSource, Vec<PathBuf>)],
+    ) -> Option<FileEntry> {
+        for (directory, source, files) in directories.iter().rev() {
+            // A full load keeps the last of several files naming the same prompt
+            let file = files
+                .iter()
+                .filter(|file| FileEntry::name_from_path(file) == name)
+                .rev()
+                .find_map(|file| self.vfs.read_file(file.clone(), directory, source.clone()));
+            if file.is_some() {
+                return file;
+            }
+        }
+
+        if !self.vfs.source_filter.allows(&FileSource::Builtin) {
+            return None;
+        }
+        get_builtin_prompts()
+            .into_iter()
+            .find(|(builtin, _)| *builtin == name)
+            .map(|(builtin, content)| {
+                FileEntry::new(
+                    builtin,
+                    PathBuf::from(format!("builtin:/prompts/{builtin}")),
+                    content.to_string(),
+                    FileSource::Builtin,
+                )
+            })
+    }
+
+    /// Load the prompt files of a single source, without the other sources
+    /// that would override or be overridden by them
+    pub fn load_source_files(source: FileSource) -> Result<Vec<FileEntry>> {
+        let mut vfs = VirtualFileSystem::new("prompts");
+        if source == FileSource::Builtin {
+            for (name, content) in get_builtin_prompts() {
+                vfs.add_builtin(name, content);
+            }
+        } else {
+            vfs.load_source(source)?;
+        }
+        Ok(vfs.files.into_values().collect())
+    }
+
     /// Load builtin prompts from embedded binary data
     fn load_builtin_prompts(&mut self) -> Result<()> {
+        if !self.vfs.source_filter.allows(&FileSource::Builtin) {
+            return Ok(());
+        }
+
         let builtin_prompts = get_builtin_prompts();
 
         // Add builtin prompts to VFS
@@ -259,4 +459,84 @@ This is a user-defined debug/error prompt that should override the builtin one.
             );
         }
     }
+
+    #[test]
+    fn test_prompt_resolver_source_filter() {
+        let mut resolver = PromptResolver::new()
+            .with_source_filter(SourceFilter::new(vec![FileSource::Builtin], vec![]));
+        let mut library = PromptLibrary::new();
+        resolver.load_all_prompts(&mut library).unwrap();
+        assert!(!resolver.prompt_sources.is_empty());
+        assert!(resolver
+            .prompt_sources
+            .values()
+            .all(|source| *source == FileSource::Builtin));
+
+        let mut resolver = PromptResolver::new()
+            .with_source_filter(SourceFilter::new(vec![], vec![FileSource::Builtin]));
+        let mut library = PromptLibrary::new();
+        resolver.load_all_prompts(&mut library).unwrap();
+        assert!(!resolver
+            .prompt_sources
+            .values()
+            .any(|source| *source == FileSource::Builtin));
+    }
+
+    #[test]
+    fn test_apply_changes_reloads_only_changed_prompts() {
+        let temp_dir = TempDir::new().unwrap();
+        let prompts_dir = temp_dir.path().join(".swissarmyhammer").join("prompts");
+        fs::create_dir_all(prompts_dir.join("debug")).unwrap();
+        let greet = prompts_dir.join("greet.md");
+        fs::write(&greet, "Hello").unwrap();
+        let error = prompts_dir.join("debug").join("error.md");
+        fs::write(&error, "User error prompt").unwrap();
+
+        let original_home = std::env::var("HOME").ok();
+        std::env::set_var("HOME", temp_dir.path());
+        let mut resolver = PromptResolver::new().with_source_filter(SourceFilter::new(
+            vec![FileSource::User, FileSource::Builtin],
+            vec![],
+        ));
+        let mut library = PromptLibrary::new();
+        resolver.load_all_prompts(&mut library).unwrap();
+        let mut apply = |paths: &[PathBuf]| {
+            crate::directory_utils::invalidate_cached_indexes(paths);
+            resolver.apply_changes(&mut library, paths).unwrap()
+        };
+
+        fs::write(&greet, "Hello again").unwrap();
+        let changes = apply(&[greet.clone()]);
+        assert_eq!(changes.updated, ["greet"]);
+
+        // A renamed directory removes its prompts and adds them under the new name
+        fs::create_dir_all(prompts_dir.join("review")).unwrap();
+        fs::write(prompts_dir.join("review").join("code.md"), "Review").unwrap();
+        assert_eq!(
+            apply(&[prompts_dir.join("review")]).updated,
+            ["review/code"]
+        );
+        fs::rename(prompts_dir.join("review"), prompts_dir.join("checks")).unwrap();
+        let changes = apply(&[prompts_dir.join("review"), prompts_dir.join("checks")]);
+        assert_eq!(changes.updated, ["checks/code"]);
+        assert_eq!(changes.removed, ["review/code"]);
+
+        // Removing a file that overrode a builtin prompt brings the builtin back
+        fs::remove_file(&error).unwrap();
+        assert_eq!(apply(&[error]).updated, ["debug/error"]);
+        fs::remove_file(&greet).unwrap();
+        assert_eq!(apply(&[greet]).removed, ["greet"]);
+        assert!(apply(&[prompts_dir.join("notes.txt")]).is_empty());
+
+        match original_home {
+            Some(home) => std::env::set_var("HOME", home),
+            None => std::env::remove_var("HOME"),
+        }
+        assert_eq!(
+            resolver.prompt_sources.get("debug/error"),
+            Some(&FileSource::Builtin)
+        );
+        assert!(library.get("greet").is_err());
+        assert_eq!(library.get("checks/code").unwrap().template, "Review");
+    }
 }
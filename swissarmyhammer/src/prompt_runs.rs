@@ -0,0 +1,143 @@
+//! Records of prompts served over MCP
+//!
+//! Each time an MCP client fetches a prompt, the server appends a
+//! [`PromptRun`] to `.swissarmyhammer/prompt-runs/runs.jsonl`: the prompt,
+//! when it was fetched, by which client, and hashes of the arguments and of
+//! the text the client received. Argument values are only kept as a hash, so
+//! secrets passed to a prompt never land on disk, while two runs can still be
+//! told apart or matched up.
+
+use crate::cost::{append_line, read_lines};
+use crate::error::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+/// File in the prompt runs directory holding one [`PromptRun`] per line
+pub const PROMPT_RUNS_FILE: &str = "runs.jsonl";
+
+/// A prompt fetched by an MCP client
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PromptRun {
+    /// When the prompt was fetched
+    pub timestamp: DateTime<Utc>,
+    /// Name of the prompt
+    pub prompt: String,
+    /// Name of the client that fetched it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client: Option<String>,
+    /// Names of the arguments it was rendered with
+    #[serde(default)]
+    pub arguments: Vec<String>,
+    /// MD5 hash of the argument values, absent when fetched without arguments
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arguments_hash: Option<String>,
+    /// MD5 hash of the text sent to the client
+    pub output_hash: String,
+}
+
+impl PromptRun {
+    /// Record of a prompt fetched now
+    pub fn new(
+        prompt: &str,
+        client: Option<&str>,
+        arguments: Option<&HashMap<String, String>>,
+        output: &str,
+    ) -> Self {
+        let arguments: Option<BTreeMap<&String, &String>> =
+            arguments.map(|arguments| arguments.iter().collect());
+        Self {
+            timestamp: Utc::now(),
+            prompt: prompt.to_string(),
+            client: client.map(str::to_string),
+            arguments: arguments
+                .iter()
+                .flat_map(|arguments| arguments.keys().map(|name| name.to_string()))
+                .collect(),
+            arguments_hash: arguments.map(|arguments| {
+                let encoded = serde_json::to_string(&arguments).unwrap_or_default();
+                format!("{:x}", md5::compute(encoded))
+            }),
+            output_hash: format!("{:x}", md5::compute(output)),
+        }
+    }
+}
+
+/// Log of the prompts MCP clients fetched
+#[derive(Debug, Clone)]
+pub struct PromptRunLog {
+    dir: PathBuf,
+}
+
+impl PromptRunLog {
+    /// Keep the log in a directory
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Keep the log in `.swissarmyhammer/prompt-runs` under a directory
+    pub fn for_work_dir(work_dir: &Path) -> Self {
+        Self::new(work_dir.join(".swissarmyhammer").join("prompt-runs"))
+    }
+
+    /// Keep the log in `.swissarmyhammer/prompt-runs` in the current directory
+    pub fn new_default() -> Result<Self> {
+        Ok(Self::for_work_dir(&std::env::current_dir()?))
+    }
+
+    /// Directory the log is kept in
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Append a run to the log
+    pub fn record(&self, run: &PromptRun) -> Result<()> {
+        append_line(&self.dir.join(PROMPT_RUNS_FILE), run)
+    }
+
+    /// Recorded runs of a prompt, or of every prompt, oldest first
+    pub fn runs(&self, prompt: Option<&str>) -> Result<Vec<PromptRun>> {
+        let runs: Vec<PromptRun> = read_lines(&self.dir.join(PROMPT_RUNS_FILE))?;
+        Ok(runs
+            .into_iter()
+            .filter(|run| prompt.map_or(true, |prompt| run.prompt == prompt))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_record_and_read_runs() {
+        let temp = TempDir::new().unwrap();
+        let log = PromptRunLog::for_work_dir(temp.path());
+        assert!(log.runs(None).unwrap().is_empty());
+
+        let arguments = HashMap::from([
+            ("language".to_string(), "rust".to_string()),
+            ("code".to_string(), "fn main() {}".to_string()),
+        ]);
+        let run = PromptRun::new("review", Some("claude-code"), Some(&arguments), "Review it");
+        log.record(&run).unwrap();
+        log.record(&PromptRun::new("help", None, None, "Help"))
+            .unwrap();
+
+        let runs = log.runs(Some("review")).unwrap();
+        assert_eq!(runs, vec![run.clone()]);
+        assert_eq!(runs[0].arguments, vec!["code", "language"]);
+        assert_eq!(log.runs(None).unwrap().len(), 2);
+        assert!(log.runs(Some("help")).unwrap()[0].arguments_hash.is_none());
+
+        // Equal argument values hash alike, whatever their order
+        let same = PromptRun::new("review", None, Some(&arguments.clone()), "Review it");
+        assert_eq!(same.arguments_hash, run.arguments_hash);
+        let other = HashMap::from([("language".to_string(), "python".to_string())]);
+        let different = PromptRun::new("review", None, Some(&other), "Review it");
+        assert_ne!(different.arguments_hash, run.arguments_hash);
+        assert!(!serde_json::to_string(&run).unwrap().contains("rust"));
+    }
+}
@@ -0,0 +1,174 @@
+//! Project specific front matter fields for prompts
+//!
+//! Besides the standard front matter fields, projects can declare fields of
+//! their own, such as `owner` or `review-date`, with the
+//! `SWISSARMYHAMMER_PROMPT_REQUIRED_FIELDS` and
+//! `SWISSARMYHAMMER_PROMPT_OPTIONAL_FIELDS` settings. The loader keeps every
+//! non-standard field in [`Prompt::metadata`](crate::Prompt), and validation
+//! reports prompts missing a required field and, once any field is declared,
+//! fields that are not declared.
+
+use crate::config::Config;
+use crate::validation::{ValidationIssue, ValidationLevel};
+use crate::Prompt;
+use std::path::Path;
+
+/// Front matter fields the loader parses into [`Prompt`] fields
+pub const STANDARD_FRONT_MATTER_FIELDS: &[&str] = &[
+    "name",
+    "title",
+    "description",
+    "category",
+    "tags",
+    "arguments",
+    "strict",
+    "minify",
+    "visibility",
+    "expose",
+    "layout",
+    "deprecated",
+    "superseded_by",
+    "tests",
+    "version",
+];
+
+/// Front matter fields a project declares beyond the standard ones
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FrontMatterSchema {
+    /// Fields every prompt must set
+    pub required: Vec<String>,
+    /// Fields prompts may set
+    pub optional: Vec<String>,
+}
+
+impl FrontMatterSchema {
+    /// Create a schema from required and optional field names
+    pub fn new(required: Vec<String>, optional: Vec<String>) -> Self {
+        Self { required, optional }
+    }
+
+    /// The schema declared in the configuration
+    pub fn from_config(config: &Config) -> Self {
+        Self::new(
+            config.prompt_required_fields.clone(),
+            config.prompt_optional_fields.clone(),
+        )
+    }
+
+    /// Whether the schema declares any field
+    pub fn is_empty(&self) -> bool {
+        self.required.is_empty() && self.optional.is_empty()
+    }
+
+    /// Whether a front matter field is standard or declared by the schema
+    pub fn is_known(&self, field: &str) -> bool {
+        STANDARD_FRONT_MATTER_FIELDS.contains(&field)
+            || self.required.iter().any(|required| required == field)
+            || self.optional.iter().any(|optional| optional == field)
+    }
+
+    /// Check the front matter of a prompt against the schema
+    pub fn validate(&self, prompt: &Prompt, file_path: &Path) -> Vec<ValidationIssue> {
+        let issue = |level, message: String, suggestion: String| ValidationIssue {
+            level,
+            file_path: file_path.to_path_buf(),
+            content_title: Some(prompt.name.clone()),
+            line: None,
+            column: None,
+            message,
+            suggestion: Some(suggestion),
+        };
+
+        let mut issues = Vec::new();
+        for field in &self.required {
+            let is_set = match prompt.metadata.get(field) {
+                None | Some(serde_json::Value::Null) => false,
+                Some(serde_json::Value::String(value)) => !value.trim().is_empty(),
+                Some(_) => true,
+            };
+            if !is_set {
+                issues.push(issue(
+                    ValidationLevel::Error,
+                    format!("Missing required field: {field}"),
+                    format!("Add a {field} field to the YAML front matter"),
+                ));
+            }
+        }
+
+        if !self.is_empty() {
+            let mut unknown: Vec<&String> = prompt
+                .metadata
+                .keys()
+                .filter(|field| !self.is_known(field))
+                .collect();
+            unknown.sort();
+            for field in unknown {
+                issues.push(issue(
+                    ValidationLevel::Warning,
+                    format!("Undeclared front matter field: {field}"),
+                    format!(
+                        "Declare {field} in SWISSARMYHAMMER_PROMPT_OPTIONAL_FIELDS or remove it"
+                    ),
+                ));
+            }
+        }
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PromptLoader;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_schema_validation() {
+        let content = r"---
+title: Review
+description: Review code
+owner: platform-team
+review-date: ''
+audience: internal
+---
+Review the code";
+        let prompt = PromptLoader::new()
+            .load_from_string("review", content)
+            .unwrap();
+        assert_eq!(prompt.metadata["owner"], "platform-team");
+        let path = PathBuf::from("review.md");
+
+        assert!(FrontMatterSchema::default()
+            .validate(&prompt, &path)
+            .is_empty());
+
+        let schema = FrontMatterSchema::new(
+            vec![
+                "owner".to_string(),
+                "review-date".to_string(),
+                "team".to_string(),
+            ],
+            vec!["audience".to_string()],
+        );
+        let messages: Vec<String> = schema
+            .validate(&prompt, &path)
+            .into_iter()
+            .map(|issue| issue.message)
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Missing required field: review-date",
+                "Missing required field: team",
+            ]
+        );
+
+        let schema = FrontMatterSchema::new(vec!["owner".to_string()], Vec::new());
+        let issues = schema.validate(&prompt, &path);
+        assert_eq!(issues.len(), 2);
+        assert!(issues
+            .iter()
+            .all(|issue| issue.level == ValidationLevel::Warning));
+        assert_eq!(issues[0].message, "Undeclared front matter field: audience");
+    }
+}
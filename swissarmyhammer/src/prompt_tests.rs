@@ -0,0 +1,238 @@
+//! Test cases embedded in prompt front matter
+//!
+//! A prompt can carry its own tests so they change together with the
+//! template:
+//!
+//! ```yaml
+//! tests:
+//!   - name: mentions the language
+//!     args:
+//!       language: rust
+//!     expect_contains: "Rust code"
+//!     expect_not_contains: "{{"
+//! ```
+//!
+//! Each case renders the prompt with its arguments, applying argument defaults
+//! but no environment variables, and checks the output. `expect_contains` and
+//! `expect_not_contains` take a single string or a list of strings. The cases
+//! run in `sah validate` and `sah prompt test --all`.
+
+use crate::{Prompt, PromptLibrary, Result, SwissArmyHammerError};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::HashMap;
+
+/// Front matter field holding a prompt's test cases
+pub const TESTS_FRONT_MATTER_FIELD: &str = "tests";
+
+/// One test case from a prompt's front matter
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PromptTestCase {
+    /// Name shown in test output
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Arguments the prompt is rendered with
+    #[serde(default, deserialize_with = "deserialize_args")]
+    pub args: HashMap<String, String>,
+    /// Text the rendered prompt must contain
+    #[serde(default, deserialize_with = "deserialize_one_or_many")]
+    pub expect_contains: Vec<String>,
+    /// Text the rendered prompt must not contain
+    #[serde(default, deserialize_with = "deserialize_one_or_many")]
+    pub expect_not_contains: Vec<String>,
+}
+
+impl PromptTestCase {
+    /// Parse the test cases of a prompt's `tests` front matter field
+    pub fn parse_all(value: &serde_json::Value) -> Result<Vec<Self>> {
+        let cases: Vec<Self> = serde_json::from_value(value.clone())
+            .map_err(|e| SwissArmyHammerError::Other(format!("Invalid prompt tests: {e}")))?;
+        if let Some(index) = cases
+            .iter()
+            .position(|case| case.expect_contains.is_empty() && case.expect_not_contains.is_empty())
+        {
+            return Err(SwissArmyHammerError::Other(format!(
+                "Invalid prompt tests: {} has no expect_contains or expect_not_contains",
+                cases[index].label(index)
+            )));
+        }
+        Ok(cases)
+    }
+
+    /// The case's name, or its position when it has none
+    pub fn label(&self, index: usize) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("test {}", index + 1),
+        }
+    }
+
+    /// Everything the rendered output gets wrong, empty when the case passes
+    pub fn check(&self, rendered: &str) -> Vec<String> {
+        let missing = self
+            .expect_contains
+            .iter()
+            .filter(|expected| !rendered.contains(expected.as_str()))
+            .map(|expected| format!("expected output to contain {expected:?}"));
+        let unwanted = self
+            .expect_not_contains
+            .iter()
+            .filter(|unexpected| rendered.contains(unexpected.as_str()))
+            .map(|unexpected| format!("expected output not to contain {unexpected:?}"));
+        missing.chain(unwanted).collect()
+    }
+}
+
+/// The outcome of one test case
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PromptTestResult {
+    /// Prompt the case belongs to
+    pub prompt: String,
+    /// The case's label
+    pub case: String,
+    /// Why the case failed, empty when it passed
+    pub failures: Vec<String>,
+}
+
+impl PromptTestResult {
+    /// Whether every expectation held
+    pub fn passed(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+/// Run the test cases of a prompt, rendering it with partials from `library`
+///
+/// A prompt without tests gives no results. Malformed tests are an error.
+pub fn run_prompt_tests(prompt: &Prompt, library: &PromptLibrary) -> Result<Vec<PromptTestResult>> {
+    let cases = prompt.test_cases()?;
+    Ok(cases
+        .iter()
+        .enumerate()
+        .map(|(index, case)| {
+            let failures = match library.render_prompt(&prompt.name, &case.args) {
+                Ok(rendered) => case.check(&rendered),
+                Err(e) => vec![format!("failed to render: {e}")],
+            };
+            PromptTestResult {
+                prompt: prompt.name.clone(),
+                case: case.label(index),
+                failures,
+            }
+        })
+        .collect())
+}
+
+/// Accept argument values of any scalar type, as template arguments are strings
+fn deserialize_args<'de, D>(
+    deserializer: D,
+) -> std::result::Result<HashMap<String, String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let values = HashMap::<String, serde_json::Value>::deserialize(deserializer)?;
+    Ok(values
+        .into_iter()
+        .map(|(name, value)| {
+            let value = match value {
+                serde_json::Value::String(value) => value,
+                serde_json::Value::Null => String::new(),
+                other => other.to_string(),
+            };
+            (name, value)
+        })
+        .collect())
+}
+
+/// Accept either a single string or a list of strings
+fn deserialize_one_or_many<'de, D>(deserializer: D) -> std::result::Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(value) => vec![value],
+        OneOrMany::Many(values) => values,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PromptLoader;
+
+    const REVIEW_PROMPT: &str = r#"---
+title: Review
+arguments:
+  - name: language
+    required: true
+  - name: strictness
+    default: "3"
+tests:
+  - name: mentions the language
+    args:
+      language: Rust
+    expect_contains: "Review this Rust code"
+  - args:
+      language: Go
+      strictness: 5
+    expect_contains:
+      - "Go code"
+      - "level 5"
+    expect_not_contains: "Rust"
+  - name: wrong expectation
+    args:
+      language: Python
+    expect_contains: "Rust"
+---
+Review this {{ language }} code at level {{ strictness }}.
+"#;
+
+    #[test]
+    fn test_parse_test_cases() {
+        let prompt = PromptLoader::new()
+            .load_from_string("review", REVIEW_PROMPT)
+            .unwrap();
+        let cases = prompt.test_cases().unwrap();
+        assert_eq!(cases.len(), 3);
+        assert_eq!(cases[0].label(0), "mentions the language");
+        assert_eq!(cases[1].label(1), "test 2");
+        assert_eq!(cases[1].args["strictness"], "5");
+        assert_eq!(cases[1].expect_contains, vec!["Go code", "level 5"]);
+        assert_eq!(cases[1].expect_not_contains, vec!["Rust"]);
+
+        assert!(Prompt::new("plain", "Hi").test_cases().unwrap().is_empty());
+
+        for tests in [
+            serde_json::json!({"args": {}}),
+            serde_json::json!([{"args": {"a": "b"}, "expect": "x"}]),
+            serde_json::json!([{"args": {"a": "b"}}]),
+        ] {
+            assert!(PromptTestCase::parse_all(&tests).is_err(), "{tests}");
+        }
+    }
+
+    #[test]
+    fn test_run_prompt_tests() {
+        let mut library = PromptLibrary::new();
+        let prompt = PromptLoader::new()
+            .load_from_string("review", REVIEW_PROMPT)
+            .unwrap();
+        library.add(prompt.clone()).unwrap();
+
+        let results = run_prompt_tests(&prompt, &library).unwrap();
+        let passed: Vec<bool> = results.iter().map(PromptTestResult::passed).collect();
+        assert_eq!(passed, vec![true, true, false]);
+        assert_eq!(results[2].case, "wrong expectation");
+        assert_eq!(
+            results[2].failures,
+            vec!["expected output to contain \"Rust\"".to_string()]
+        );
+    }
+}
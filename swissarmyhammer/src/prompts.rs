@@ -19,6 +19,8 @@
 //!         required: true,
 //!         default: None,
 //!         type_hint: Some("string".to_string()),
+//!         choices: None,
+//!         completion: None,
 //!     });
 //!
 //! let mut args = HashMap::new();
@@ -27,13 +29,60 @@
 //! assert_eq!(result, "Hello World!");
 //! ```
 
+use crate::prompt_completion::CompletionSource;
+use crate::prompt_schema::{FrontMatterSchema, STANDARD_FRONT_MATTER_FIELDS};
+use crate::template::{source_snippet, TemplateErrorLocation};
 use crate::validation::{Validatable, ValidationIssue, ValidationLevel};
+use crate::value_providers::ValueSource;
 use crate::{Result, SwissArmyHammerError, Template};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+/// Template lines a prompt without headers may have and still be taken for a
+/// partial
+const PARTIAL_MAX_LINES: usize = 5;
+
+/// Where a prompt may be served, from its `visibility` front matter field.
+///
+/// Internal prompts can be kept away from connected agents while still being
+/// rendered by the CLI, workflows and the prompts that include them.
+/// `expose: false` in the front matter is the same as `visibility: cli`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PromptVisibility {
+    /// Listed and served through MCP as well as the CLI
+    #[default]
+    Mcp,
+    /// Rendered by the CLI and workflows, never listed or served through MCP
+    Cli,
+    /// Like `cli`, and also left out of prompt exports
+    Private,
+}
+
+impl PromptVisibility {
+    /// Whether MCP clients may list and get the prompt
+    pub fn is_exposed_to_mcp(self) -> bool {
+        self == Self::Mcp
+    }
+}
+
+impl std::str::FromStr for PromptVisibility {
+    type Err = SwissArmyHammerError;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value {
+            "mcp" => Ok(Self::Mcp),
+            "cli" => Ok(Self::Cli),
+            "private" => Ok(Self::Private),
+            other => Err(SwissArmyHammerError::Other(format!(
+                "Invalid prompt visibility '{other}', expected private, mcp or cli"
+            ))),
+        }
+    }
+}
+
 /// Represents a single prompt with metadata and template content.
 ///
 /// A [`Prompt`] encapsulates all the information needed to use a template, including
@@ -83,6 +132,9 @@ use std::sync::Arc;
 ///         required: true,
 ///         default: None,
 ///         type_hint: Some("string".to_string()),
+///         choices: None,
+///         completion: None,
+///         source: None,
 ///     })
 ///     .add_argument(ArgumentSpec {
 ///         name: "language".to_string(),
@@ -90,6 +142,9 @@ use std::sync::Arc;
 ///         required: false,
 ///         default: Some("unknown".to_string()),
 ///         type_hint: Some("string".to_string()),
+///         choices: None,
+///         completion: None,
+///         source: None,
 ///     });
 ///
 /// // Render with arguments
@@ -179,6 +234,9 @@ pub struct Prompt {
 ///     required: true,
 ///     default: None,
 ///     type_hint: Some("path".to_string()),
+///     choices: None,
+///     completion: None,
+///     source: None,
 /// };
 ///
 /// // Optional argument with default value
@@ -188,6 +246,9 @@ pub struct Prompt {
 ///     required: false,
 ///     default: Some("markdown".to_string()),
 ///     type_hint: Some("string".to_string()),
+///     choices: None,
+///     completion: None,
+///     source: None,
 /// };
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -222,6 +283,28 @@ pub struct ArgumentSpec {
     /// Common values: "string", "number", "boolean", "path", "url", "json".
     /// This is primarily for documentation and tooling support.
     pub type_hint: Option<String>,
+
+    /// Allowed values for the argument.
+    ///
+    /// Set from the `choices` list in front matter. Interactive tools offer
+    /// these values as a selection instead of free text.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub choices: Option<Vec<String>>,
+
+    /// Where suggested values for the argument come from.
+    ///
+    /// Set from the `completion` field in front matter. Interactive tools and
+    /// shell completion offer these values, but any value is accepted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completion: Option<CompletionSource>,
+
+    /// Where the value comes from when none is given.
+    ///
+    /// Set from the `source` field in front matter, such as `env:API_TOKEN`.
+    /// The value is looked up at render time, before the default is used.
+    /// See [`crate::value_providers`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<ValueSource>,
 }
 
 impl Prompt {
@@ -258,6 +341,302 @@ impl Prompt {
         }
     }
 
+    /// Returns true if this prompt is a partial template.
+    ///
+    /// Partial templates are included by other prompts and are not meant to be
+    /// used directly, so they are hidden from MCP clients and exports. A prompt
+    /// is a partial if its template starts with the `{% partial %}` marker or its
+    /// description says it is a partial template for reuse in other prompts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use swissarmyhammer::Prompt;
+    ///
+    /// assert!(Prompt::new("header", "{% partial %}\n# Header").is_partial());
+    /// assert!(!Prompt::new("hello", "Hello {{name}}!").is_partial());
+    /// ```
+    pub fn is_partial(&self) -> bool {
+        if self.template.trim().starts_with("{% partial %}") {
+            return true;
+        }
+
+        self.description.as_deref().is_some_and(|description| {
+            description.contains("Partial template for reuse in other prompts")
+        })
+    }
+
+    /// Returns true if this prompt renders in strict mode.
+    ///
+    /// Strict prompts fail to render when they reach an undefined variable
+    /// instead of rendering it as an empty string. The `strict` front matter
+    /// field takes precedence over the global `strict_templates` setting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use swissarmyhammer::Prompt;
+    ///
+    /// let mut prompt = Prompt::new("greet", "Hello {{name}}!");
+    /// prompt
+    ///     .metadata
+    ///     .insert("strict".to_string(), serde_json::Value::Bool(true));
+    /// assert!(prompt.is_strict());
+    /// ```
+    pub fn is_strict(&self) -> bool {
+        self.metadata
+            .get("strict")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(crate::config::Config::global().strict_templates)
+    }
+
+    /// Returns true if this prompt's rendered output is minified.
+    ///
+    /// A minified prompt renders without the lines its `{% comment %}` blocks
+    /// stood on, without trailing whitespace, and with runs of blank lines
+    /// collapsed to one, saving tokens on large prompts. The `minify` front
+    /// matter field takes precedence over the global `minify_prompts` setting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use swissarmyhammer::Prompt;
+    /// use std::collections::HashMap;
+    ///
+    /// let mut prompt = Prompt::new("review", "Review   \n\n\n\nthe code\n");
+    /// prompt
+    ///     .metadata
+    ///     .insert("minify".to_string(), serde_json::Value::Bool(true));
+    /// assert!(prompt.is_minified());
+    /// assert_eq!(prompt.render(&HashMap::new()).unwrap(), "Review\n\nthe code");
+    /// ```
+    pub fn is_minified(&self) -> bool {
+        self.metadata
+            .get("minify")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(crate::config::Config::global().minify_prompts)
+    }
+
+    /// Returns where this prompt may be served.
+    ///
+    /// The `visibility` front matter field takes precedence over `expose`, and
+    /// prompts setting neither are served everywhere. An unknown visibility is
+    /// treated as `private`, so a typo never exposes an internal prompt;
+    /// validation reports it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use swissarmyhammer::{Prompt, PromptVisibility};
+    ///
+    /// let mut prompt = Prompt::new("release-notes", "Draft the release notes");
+    /// assert!(prompt.visibility().is_exposed_to_mcp());
+    /// prompt
+    ///     .metadata
+    ///     .insert("expose".to_string(), serde_json::Value::Bool(false));
+    /// assert_eq!(prompt.visibility(), PromptVisibility::Cli);
+    /// ```
+    pub fn visibility(&self) -> PromptVisibility {
+        if let Some(visibility) = self.metadata.get("visibility") {
+            return visibility
+                .as_str()
+                .and_then(|visibility| visibility.parse().ok())
+                .unwrap_or(PromptVisibility::Private);
+        }
+        match self
+            .metadata
+            .get("expose")
+            .and_then(serde_json::Value::as_bool)
+        {
+            Some(false) => PromptVisibility::Cli,
+            _ => PromptVisibility::Mcp,
+        }
+    }
+
+    /// Returns the name of the layout this prompt fills, if it declares one.
+    ///
+    /// A prompt with `layout: base-review` in its front matter renders as the
+    /// `base-review` prompt, with each `{% block %}` the prompt defines
+    /// replacing the block of the same name in the layout.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use swissarmyhammer::Prompt;
+    ///
+    /// let mut prompt = Prompt::new("review", "{% block body %}Review{% endblock %}");
+    /// prompt
+    ///     .metadata
+    ///     .insert("layout".to_string(), serde_json::json!("base-review"));
+    /// assert_eq!(prompt.layout(), Some("base-review"));
+    /// ```
+    pub fn layout(&self) -> Option<&str> {
+        self.metadata
+            .get("layout")
+            .and_then(serde_json::Value::as_str)
+    }
+
+    /// Returns true if the prompt is marked `deprecated: true` in its front matter.
+    ///
+    /// Deprecated prompts still render, with a warning, so workflows keep
+    /// working while they are migrated to the prompt that replaces them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use swissarmyhammer::Prompt;
+    ///
+    /// let mut prompt = Prompt::new("old-review", "Review the code");
+    /// prompt
+    ///     .metadata
+    ///     .insert("deprecated".to_string(), serde_json::Value::Bool(true));
+    /// assert!(prompt.is_deprecated());
+    /// ```
+    pub fn is_deprecated(&self) -> bool {
+        self.metadata
+            .get("deprecated")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false)
+    }
+
+    /// Returns the name of the prompt that replaces this one, if it names one.
+    pub fn superseded_by(&self) -> Option<&str> {
+        self.metadata
+            .get("superseded_by")
+            .and_then(serde_json::Value::as_str)
+    }
+
+    /// Returns the prompt's `version` front matter field, if it sets one.
+    ///
+    /// Workflows can pin a prompt to a version, see [`crate::prompt_refs`].
+    pub fn version(&self) -> Option<String> {
+        match self.metadata.get("version")? {
+            serde_json::Value::String(version) => Some(version.clone()),
+            serde_json::Value::Number(version) => Some(version.to_string()),
+            _ => None,
+        }
+    }
+
+    /// Returns the test cases in the prompt's `tests` front matter field.
+    ///
+    /// A prompt without the field has no test cases. See [`crate::prompt_tests`].
+    pub fn test_cases(&self) -> Result<Vec<crate::prompt_tests::PromptTestCase>> {
+        match self
+            .metadata
+            .get(crate::prompt_tests::TESTS_FRONT_MATTER_FIELD)
+        {
+            None | Some(serde_json::Value::Null) => Ok(Vec::new()),
+            Some(tests) => crate::prompt_tests::PromptTestCase::parse_all(tests),
+        }
+    }
+
+    /// Returns the warning shown when a deprecated prompt is used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use swissarmyhammer::Prompt;
+    ///
+    /// let mut prompt = Prompt::new("old-review", "Review the code");
+    /// prompt
+    ///     .metadata
+    ///     .insert("deprecated".to_string(), serde_json::Value::Bool(true));
+    /// prompt
+    ///     .metadata
+    ///     .insert("superseded_by".to_string(), serde_json::json!("review/code"));
+    /// assert_eq!(
+    ///     prompt.deprecation_warning().as_deref(),
+    ///     Some("Prompt 'old-review' is deprecated, use 'review/code' instead")
+    /// );
+    /// ```
+    pub fn deprecation_warning(&self) -> Option<String> {
+        if !self.is_deprecated() {
+            return None;
+        }
+        Some(match self.superseded_by() {
+            Some(replacement) => format!(
+                "Prompt '{}' is deprecated, use '{replacement}' instead",
+                self.name
+            ),
+            None => format!("Prompt '{}' is deprecated", self.name),
+        })
+    }
+
+    fn warn_if_deprecated(&self) {
+        if let Some(warning) = self.deprecation_warning() {
+            tracing::warn!("{warning}");
+        }
+    }
+
+    /// Layouts are looked up in a prompt library, so rendering without one fails
+    fn check_no_layout(&self) -> Result<()> {
+        match self.layout() {
+            Some(layout) => Err(SwissArmyHammerError::Template(format!(
+                "Prompt '{}' uses layout '{layout}' and must be rendered with a prompt library",
+                self.name
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// The template source to render, merged into its layout if it has one
+    fn source_with_layout(&self, library: &PromptLibrary) -> Result<String> {
+        match self.layout() {
+            Some(layout) => crate::template::apply_layout(&self.template, layout, library),
+            None => Ok(self.template.clone()),
+        }
+    }
+
+    /// Point a template error at the line of the prompt file it came from
+    ///
+    /// Errors with a known position in `source`, the template as rendered, are
+    /// reported as `path:line:column: message` followed by the offending line
+    /// with a caret. Lines count from the top of the file, front matter
+    /// included. Prompts without a file are named instead, and errors in a
+    /// layout rather than the prompt itself are returned unchanged.
+    fn locate_error(&self, error: SwissArmyHammerError, source: &str) -> SwissArmyHammerError {
+        let Some(location) = TemplateErrorLocation::of(&error) else {
+            return error;
+        };
+        let Some((line, column)) =
+            position_in_template(source, &self.template, location.line, location.column)
+        else {
+            return error;
+        };
+
+        let file = self
+            .source
+            .as_ref()
+            .and_then(|path| Some((path, std::fs::read_to_string(path).ok()?)))
+            .and_then(|(path, content)| {
+                let start = content.rfind(self.template.as_str())?;
+                Some((path, content, start))
+            });
+        let (label, text, line, column) = match &file {
+            Some((path, content, start)) => {
+                let (start_line, start_column) = crate::template::line_column(content, *start);
+                let column = if line == 1 {
+                    column + start_column - 1
+                } else {
+                    column
+                };
+                (
+                    path.display().to_string(),
+                    content.as_str(),
+                    start_line + line - 1,
+                    column,
+                )
+            }
+            None => (self.name.clone(), self.template.as_str(), line, column),
+        };
+
+        SwissArmyHammerError::Template(format!(
+            "{label}:{line}:{column}: {}\n{}",
+            location.message,
+            source_snippet(text, line, column)
+        ))
+    }
+
     /// Renders the prompt template with the provided arguments.
     ///
     /// This method validates that all required arguments are provided, applies
@@ -292,6 +671,9 @@ impl Prompt {
     ///         required: true,
     ///         default: None,
     ///         type_hint: None,
+    ///         choices: None,
+    ///         completion: None,
+    ///         source: None,
     ///     });
     ///
     /// let mut args = HashMap::new();
@@ -301,31 +683,18 @@ impl Prompt {
     /// assert_eq!(result, "Hello Alice!");
     /// ```
     pub fn render(&self, args: &HashMap<String, String>) -> Result<String> {
-        let template = Template::new(&self.template)?;
-
-        // Validate required arguments
-        for arg in &self.arguments {
-            if arg.required && !args.contains_key(&arg.name) {
-                return Err(SwissArmyHammerError::Template(format!(
-                    "Required argument '{}' not provided",
-                    arg.name
-                )));
-            }
-        }
-
-        // Start with all provided arguments
-        let mut render_args = args.clone();
-
-        // Add defaults for missing arguments
-        for arg in &self.arguments {
-            if !render_args.contains_key(&arg.name) {
-                if let Some(default) = &arg.default {
-                    render_args.insert(arg.name.clone(), default.clone());
-                }
-            }
-        }
-
-        template.render(&render_args)
+        self.check_no_layout()?;
+        let template = Template::new(&self.template)
+            .map_err(|e| self.locate_error(e, &self.template))?
+            .with_strict(self.is_strict())
+            .with_minify(self.is_minified());
+        self.warn_if_deprecated();
+
+        let render_args = self.render_arguments(args)?;
+
+        template
+            .render(&render_args)
+            .map_err(|e| self.locate_error(e, &self.template))
     }
 
     /// Renders the prompt template with environment variables included
@@ -353,31 +722,18 @@ impl Prompt {
     /// let result = prompt.render_with_env(&args).unwrap();
     /// ```
     pub fn render_with_env(&self, args: &HashMap<String, String>) -> Result<String> {
-        let template = Template::new(&self.template)?;
-
-        // Validate required arguments
-        for arg in &self.arguments {
-            if arg.required && !args.contains_key(&arg.name) {
-                return Err(SwissArmyHammerError::Template(format!(
-                    "Required argument '{}' not provided",
-                    arg.name
-                )));
-            }
-        }
-
-        // Start with all provided arguments
-        let mut render_args = args.clone();
-
-        // Add defaults for missing arguments
-        for arg in &self.arguments {
-            if !render_args.contains_key(&arg.name) {
-                if let Some(default) = &arg.default {
-                    render_args.insert(arg.name.clone(), default.clone());
-                }
-            }
-        }
-
-        template.render_with_env(&render_args)
+        self.check_no_layout()?;
+        let template = Template::new(&self.template)
+            .map_err(|e| self.locate_error(e, &self.template))?
+            .with_strict(self.is_strict())
+            .with_minify(self.is_minified());
+        self.warn_if_deprecated();
+
+        let render_args = self.render_arguments(args)?;
+
+        template
+            .render_with_env(&render_args)
+            .map_err(|e| self.locate_error(e, &self.template))
     }
 
     /// Renders the prompt template with partial support
@@ -419,31 +775,29 @@ impl Prompt {
         args: &HashMap<String, String>,
         library: Arc<PromptLibrary>,
     ) -> Result<String> {
-        let template = crate::Template::with_partials(&self.template, library)?;
-
-        // Validate required arguments
-        for arg in &self.arguments {
-            if arg.required && !args.contains_key(&arg.name) {
-                return Err(SwissArmyHammerError::Template(format!(
-                    "Required argument '{}' not provided",
-                    arg.name
-                )));
-            }
-        }
-
-        // Start with all provided arguments
-        let mut render_args = args.clone();
-
-        // Add defaults for missing arguments
-        for arg in &self.arguments {
-            if !render_args.contains_key(&arg.name) {
-                if let Some(default) = &arg.default {
-                    render_args.insert(arg.name.clone(), default.clone());
-                }
-            }
-        }
+        self.render_in_chain(args, library, vec![self.name.clone()])
+    }
 
-        template.render(&render_args)
+    /// Renders the prompt with partial support as the last of `chain`, the
+    /// prompts being rendered through `{% prompt %}` tags, outermost first
+    pub(crate) fn render_in_chain(
+        &self,
+        args: &HashMap<String, String>,
+        library: Arc<PromptLibrary>,
+        chain: Vec<String>,
+    ) -> Result<String> {
+        let source = self.source_with_layout(&library)?;
+        let template = crate::Template::calling_prompts(&source, library, chain)
+            .map_err(|e| self.locate_error(e, &source))?
+            .with_strict(self.is_strict())
+            .with_minify(self.is_minified());
+        self.warn_if_deprecated();
+
+        let render_args = self.render_arguments(args)?;
+
+        template
+            .render(&render_args)
+            .map_err(|e| self.locate_error(e, &source))
     }
 
     /// Renders the prompt template with partial support and environment variables
@@ -488,31 +842,63 @@ impl Prompt {
         args: &HashMap<String, String>,
         library: Arc<PromptLibrary>,
     ) -> Result<String> {
-        let template = crate::Template::with_partials(&self.template, library)?;
+        let source = self.source_with_layout(&library)?;
+        let template = crate::Template::calling_prompts(&source, library, vec![self.name.clone()])
+            .map_err(|e| self.locate_error(e, &source))?
+            .with_strict(self.is_strict())
+            .with_minify(self.is_minified());
+        self.warn_if_deprecated();
+
+        let render_args = self.render_arguments(args)?;
+
+        template
+            .render_with_env(&render_args)
+            .map_err(|e| self.locate_error(e, &source))
+    }
 
-        // Validate required arguments
+    /// The arguments to render with: those given, then values looked up from
+    /// the `source` of arguments not given, then defaults
+    ///
+    /// A source that cannot be resolved falls back to the default, and is an
+    /// error for an argument without one.
+    fn render_arguments(&self, args: &HashMap<String, String>) -> Result<HashMap<String, String>> {
+        let mut render_args = args.clone();
         for arg in &self.arguments {
-            if arg.required && !args.contains_key(&arg.name) {
+            if render_args.contains_key(&arg.name) {
+                continue;
+            }
+            if let Some(source) = &arg.source {
+                match source.resolve() {
+                    Ok(value) => {
+                        render_args.insert(arg.name.clone(), value);
+                        continue;
+                    }
+                    Err(e) if arg.default.is_some() && !arg.required => {
+                        tracing::debug!(
+                            "Using the default for argument '{}': {} from {source}",
+                            arg.name,
+                            e
+                        );
+                    }
+                    Err(e) => {
+                        return Err(SwissArmyHammerError::Template(format!(
+                            "Argument '{}' could not be read from {source}: {e}",
+                            arg.name
+                        )));
+                    }
+                }
+            }
+            if arg.required {
                 return Err(SwissArmyHammerError::Template(format!(
                     "Required argument '{}' not provided",
                     arg.name
                 )));
             }
-        }
-
-        // Start with all provided arguments
-        let mut render_args = args.clone();
-
-        // Add defaults for missing arguments
-        for arg in &self.arguments {
-            if !render_args.contains_key(&arg.name) {
-                if let Some(default) = &arg.default {
-                    render_args.insert(arg.name.clone(), default.clone());
-                }
+            if let Some(default) = &arg.default {
+                render_args.insert(arg.name.clone(), default.clone());
             }
         }
-
-        template.render_with_env(&render_args)
+        Ok(render_args)
     }
 
     /// Adds an argument specification to the prompt.
@@ -536,6 +922,9 @@ impl Prompt {
     ///         required: true,
     ///         default: None,
     ///         type_hint: Some("path".to_string()),
+    ///         choices: None,
+    ///         completion: None,
+    ///         source: None,
     ///     });
     ///
     /// assert_eq!(prompt.arguments.len(), 1);
@@ -680,9 +1069,32 @@ impl Validatable for Prompt {
             }
         }
 
-        // Skip variable validation for partial templates
+        if let Some(visibility) = self.metadata.get("visibility") {
+            let valid = visibility
+                .as_str()
+                .is_some_and(|visibility| visibility.parse::<PromptVisibility>().is_ok());
+            if !valid {
+                issues.push(ValidationIssue {
+                    level: ValidationLevel::Error,
+                    file_path: file_path.to_path_buf(),
+                    content_title: Some(self.name.clone()),
+                    line: None,
+                    column: None,
+                    message: format!(
+                        "Invalid visibility {visibility}; the prompt is treated as private"
+                    ),
+                    suggestion: Some("Set visibility to private, mcp or cli".to_string()),
+                });
+            }
+        }
+
+        // Skip variable and project schema validation for partial templates
         if !is_partial {
             issues.extend(self.validate_template_variables(&file_path));
+            issues.extend(
+                FrontMatterSchema::from_config(crate::config::Config::global())
+                    .validate(self, &file_path),
+            );
         }
 
         issues
@@ -690,11 +1102,39 @@ impl Validatable for Prompt {
 }
 
 impl Prompt {
-    /// Validate template variables against defined arguments
-    fn validate_template_variables(&self, file_path: &Path) -> Vec<ValidationIssue> {
-        use regex::Regex;
+    /// Template variables that are neither arguments nor defined by the template
+    pub(crate) fn undeclared_variables(&self) -> Vec<String> {
+        let (used_variables, assigned_variables) = self.template_variable_usage();
+        let mut undeclared: Vec<String> = used_variables
+            .into_iter()
+            .filter(|used_var| {
+                !assigned_variables.contains(used_var)
+                    && !self.arguments.iter().any(|arg| &arg.name == used_var)
+            })
+            .collect();
+        undeclared.sort();
+        undeclared
+    }
 
-        let mut issues = Vec::new();
+    /// Arguments the template never uses
+    pub(crate) fn unused_arguments(&self) -> Vec<String> {
+        let (used_variables, _) = self.template_variable_usage();
+        self.arguments
+            .iter()
+            .filter(|arg| !used_variables.contains(&arg.name))
+            .map(|arg| arg.name.clone())
+            .collect()
+    }
+
+    /// Variables the template uses, and the variables it defines itself with
+    /// `assign`, `capture` or `for`
+    fn template_variable_usage(
+        &self,
+    ) -> (
+        std::collections::HashSet<String>,
+        std::collections::HashSet<String>,
+    ) {
+        use regex::Regex;
 
         // Remove {% raw %} blocks from content before validation
         let raw_regex = Regex::new(r"(?s)\{%\s*raw\s*%\}.*?\{%\s*endraw\s*%\}").unwrap();
@@ -776,6 +1216,14 @@ impl Prompt {
             }
         }
 
+        (used_variables, assigned_variables)
+    }
+
+    /// Validate template variables against defined arguments
+    fn validate_template_variables(&self, file_path: &Path) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+        let (used_variables, assigned_variables) = self.template_variable_usage();
+
         // Check if all used variables are defined in arguments
         let defined_args: std::collections::HashSet<String> =
             self.arguments.iter().map(|arg| arg.name.clone()).collect();
@@ -910,6 +1358,36 @@ impl PromptLibrary {
         Self { storage }
     }
 
+    /// Creates a prompt library with the storage the configuration selects.
+    ///
+    /// When `prompt_body_cache_size` is set, prompts loaded from files keep
+    /// only their metadata in memory and load their templates when first
+    /// used, keeping at most that many templates loaded. Otherwise this is
+    /// the same as [`new`](Self::new).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use swissarmyhammer::PromptLibrary;
+    ///
+    /// let library = PromptLibrary::from_config();
+    /// ```
+    #[must_use]
+    pub fn from_config() -> Self {
+        match crate::config::Config::global().prompt_body_cache_size {
+            0 => Self::new(),
+            capacity => {
+                Self::with_storage(Box::new(crate::storage::LazyPromptStorage::new(capacity)))
+            }
+        }
+    }
+
+    /// Whether prompts added from files keep only their metadata in memory,
+    /// loading their templates when first used
+    pub fn loads_templates_lazily(&self) -> bool {
+        self.storage.loads_templates_lazily()
+    }
+
     /// Loads all prompts from a directory and adds them to the library.
     ///
     /// Recursively scans the directory for markdown files (`.md` and `.markdown`)
@@ -941,17 +1419,59 @@ impl PromptLibrary {
     /// println!("Loaded {} prompts from directory", count);
     /// ```
     pub fn add_directory(&mut self, path: impl AsRef<Path>) -> Result<usize> {
-        let loader = PromptLoader::new();
+        let loader = PromptLoader::new().front_matter_only(self.loads_templates_lazily());
         let prompts = loader.load_directory(path)?;
         let count = prompts.len();
 
         for prompt in prompts {
-            self.storage.store(prompt)?;
+            match prompt.source.clone() {
+                Some(path) => self.storage.store_from_file(prompt, &path)?,
+                None => self.storage.store(prompt)?,
+            }
         }
 
         Ok(count)
     }
 
+    /// Loads the prompts of a directory holding prompts from `source`, unless
+    /// `filter` excludes that source.
+    ///
+    /// Skipped directories are not read at all, which keeps startup fast and
+    /// stops prompts from an unwanted source overriding others.
+    ///
+    /// # Returns
+    ///
+    /// The number of prompts loaded, zero when the source is filtered out.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`add_directory`](Self::add_directory).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use swissarmyhammer::{PromptLibrary, PromptSource, SourceFilter};
+    ///
+    /// let mut library = PromptLibrary::new();
+    /// let filter = SourceFilter::new(vec![], vec![PromptSource::User]);
+    /// let count = library
+    ///     .add_directory_filtered("./.swissarmyhammer/prompts", PromptSource::Local, &filter)
+    ///     .unwrap();
+    /// println!("Loaded {} prompts from directory", count);
+    /// ```
+    pub fn add_directory_filtered(
+        &mut self,
+        path: impl AsRef<Path>,
+        source: crate::PromptSource,
+        filter: &crate::SourceFilter,
+    ) -> Result<usize> {
+        if !filter.allows(&source) {
+            tracing::debug!("Skipping {} prompts in {}", source, path.as_ref().display());
+            return Ok(0);
+        }
+        self.add_directory(path)
+    }
+
     /// Retrieves a prompt by its name.
     ///
     /// # Arguments
@@ -991,7 +1511,9 @@ impl PromptLibrary {
     ///
     /// # Returns
     ///
-    /// A vector of all prompts currently stored in the library.
+    /// A vector of all prompts currently stored in the library. When the
+    /// storage loads templates lazily, prompts loaded from files are listed
+    /// with an empty template; [`get`](Self::get) loads it.
     ///
     /// # Errors
     ///
@@ -1196,6 +1718,15 @@ impl PromptLibrary {
         self.storage.store(prompt)
     }
 
+    /// Adds a prompt loaded from a file to the library.
+    ///
+    /// Storage backends that load templates lazily drop the prompt's template
+    /// and reload it from `path` when the prompt is used. Other backends store
+    /// the prompt as [`add`](Self::add) does.
+    pub fn add_from_file(&mut self, prompt: Prompt, path: &Path) -> Result<()> {
+        self.storage.store_from_file(prompt, path)
+    }
+
     /// Removes a prompt from the library.
     ///
     /// # Arguments
@@ -1232,6 +1763,8 @@ impl Default for PromptLibrary {
 pub struct PromptLoader {
     /// File extensions to consider
     extensions: Vec<String>,
+    /// Read only the front matter of prompt files
+    front_matter_only: bool,
 }
 
 impl PromptLoader {
@@ -1248,9 +1781,20 @@ impl PromptLoader {
                 "liquid.md".to_string(),
                 "liquid.markdown".to_string(),
             ],
+            front_matter_only: false,
         }
     }
 
+    /// Read only the front matter of prompt files, leaving their templates
+    /// to storage that loads them when the prompt is used
+    ///
+    /// See [`read_front_matter`](Self::read_front_matter).
+    #[must_use]
+    pub fn front_matter_only(mut self, front_matter_only: bool) -> Self {
+        self.front_matter_only = front_matter_only;
+        self
+    }
+
     /// Load prompts from a directory
     pub fn load_directory(&self, path: impl AsRef<Path>) -> Result<Vec<Prompt>> {
         let path = path.as_ref();
@@ -1263,12 +1807,17 @@ impl PromptLoader {
             )));
         }
 
-        for entry in walkdir::WalkDir::new(path)
-            .into_iter()
-            .filter_map(std::result::Result::ok)
-        {
-            let entry_path = entry.path();
-            if entry_path.is_file() && self.is_prompt_file(entry_path) {
+        for file in crate::directory_utils::cached_files(path) {
+            let entry_path = file.as_path();
+            if self.is_prompt_file(entry_path) {
+                if let Err(e) = crate::security::validate_path(
+                    entry_path,
+                    path,
+                    crate::security::PathPolicy::global(),
+                ) {
+                    tracing::warn!("Skipping prompt '{}' - {}", entry_path.display(), e);
+                    continue;
+                }
                 if let Ok(prompt) = self.load_file_with_base(entry_path, path) {
                     prompts.push(prompt);
                 }
@@ -1286,9 +1835,49 @@ impl PromptLoader {
         )
     }
 
+    /// Load only the template of a prompt file, without its front matter
+    pub fn load_template(&self, path: impl AsRef<Path>) -> Result<String> {
+        let content = std::fs::read_to_string(path)?;
+        Ok(Self::parse_front_matter(&content)?.1)
+    }
+
+    /// Read the front matter of a prompt file and the first lines after it
+    ///
+    /// The prompt loaded from the result has the file's metadata, and is
+    /// recognised as a partial as it would be from the whole file, but its
+    /// template is cut short. The rest of the file is not read.
+    pub fn read_front_matter(path: impl AsRef<Path>) -> Result<String> {
+        use std::io::BufRead;
+
+        let mut reader = std::io::BufReader::new(std::fs::File::open(path)?);
+        let mut content = String::new();
+        let mut line = String::new();
+        let mut in_front_matter = false;
+        let mut template_lines = 0;
+        while template_lines <= PARTIAL_MAX_LINES {
+            line.clear();
+            if reader.read_line(&mut line)? == 0 {
+                break;
+            }
+            if content.is_empty() && line == "---\n" {
+                in_front_matter = true;
+            } else if in_front_matter {
+                in_front_matter = !line.ends_with("---\n");
+            } else {
+                template_lines += 1;
+            }
+            content.push_str(&line);
+        }
+        Ok(content)
+    }
+
     /// Load a single prompt file with base path for relative naming
     fn load_file_with_base(&self, path: &Path, base_path: &Path) -> Result<Prompt> {
-        let content = std::fs::read_to_string(path)?;
+        let content = if self.front_matter_only {
+            Self::read_front_matter(path)?
+        } else {
+            std::fs::read_to_string(path)?
+        };
 
         let (metadata, template) = Self::parse_front_matter(&content)?;
 
@@ -1302,6 +1891,7 @@ impl PromptLoader {
 
         // Parse metadata
         if let Some(ref metadata_value) = metadata {
+            Self::insert_custom_front_matter(&mut prompt, metadata_value);
             if let Some(title) = metadata_value
                 .get("title")
                 .and_then(serde_json::Value::as_str)
@@ -1311,6 +1901,68 @@ impl PromptLoader {
                     serde_json::Value::String(title.to_string()),
                 );
             }
+            if let Some(strict) = metadata_value
+                .get("strict")
+                .and_then(serde_json::Value::as_bool)
+            {
+                prompt
+                    .metadata
+                    .insert("strict".to_string(), serde_json::Value::Bool(strict));
+            }
+            if let Some(minify) = metadata_value
+                .get("minify")
+                .and_then(serde_json::Value::as_bool)
+            {
+                prompt
+                    .metadata
+                    .insert("minify".to_string(), serde_json::Value::Bool(minify));
+            }
+            if let Some(visibility) = metadata_value.get("visibility") {
+                prompt
+                    .metadata
+                    .insert("visibility".to_string(), visibility.clone());
+            }
+            if let Some(expose) = metadata_value
+                .get("expose")
+                .and_then(serde_json::Value::as_bool)
+            {
+                prompt
+                    .metadata
+                    .insert("expose".to_string(), serde_json::Value::Bool(expose));
+            }
+            if let Some(layout) = metadata_value
+                .get("layout")
+                .and_then(serde_json::Value::as_str)
+            {
+                prompt.metadata.insert(
+                    "layout".to_string(),
+                    serde_json::Value::String(layout.to_string()),
+                );
+            }
+            if let Some(deprecated) = metadata_value
+                .get("deprecated")
+                .and_then(serde_json::Value::as_bool)
+            {
+                prompt.metadata.insert(
+                    "deprecated".to_string(),
+                    serde_json::Value::Bool(deprecated),
+                );
+            }
+            if let Some(replacement) = metadata_value
+                .get("superseded_by")
+                .and_then(serde_json::Value::as_str)
+            {
+                prompt.metadata.insert(
+                    "superseded_by".to_string(),
+                    serde_json::Value::String(replacement.to_string()),
+                );
+            }
+            if let Some(tests) = metadata_value.get(crate::prompt_tests::TESTS_FRONT_MATTER_FIELD) {
+                prompt.metadata.insert(
+                    crate::prompt_tests::TESTS_FRONT_MATTER_FIELD.to_string(),
+                    tests.clone(),
+                );
+            }
             if let Some(desc) = metadata_value
                 .get("description")
                 .and_then(serde_json::Value::as_str)
@@ -1357,6 +2009,18 @@ impl PromptLoader {
                                 .get("type")
                                 .and_then(serde_json::Value::as_str)
                                 .map(String::from),
+                            choices: arg_obj
+                                .get("choices")
+                                .and_then(serde_json::Value::as_array)
+                                .map(|choices| {
+                                    choices
+                                        .iter()
+                                        .filter_map(serde_json::Value::as_str)
+                                        .map(String::from)
+                                        .collect()
+                                }),
+                            completion: Self::parse_completion(arg_obj),
+                            source: Self::parse_source(arg_obj),
                         };
 
                         prompt.arguments.push(arg_spec);
@@ -1375,6 +2039,46 @@ impl PromptLoader {
         Ok(prompt)
     }
 
+    /// The `completion` field of an argument, ignored with a warning when invalid
+    fn parse_completion(
+        arg_obj: &serde_json::Map<String, serde_json::Value>,
+    ) -> Option<CompletionSource> {
+        let value = arg_obj.get("completion")?;
+        match serde_json::from_value(value.clone()) {
+            Ok(source) => Some(source),
+            Err(e) => {
+                tracing::warn!(
+                    "Ignoring invalid completion for argument '{}': {}",
+                    arg_obj
+                        .get("name")
+                        .and_then(serde_json::Value::as_str)
+                        .unwrap_or_default(),
+                    e
+                );
+                None
+            }
+        }
+    }
+
+    /// The `source` field of an argument, ignored with a warning when invalid
+    fn parse_source(arg_obj: &serde_json::Map<String, serde_json::Value>) -> Option<ValueSource> {
+        let value = arg_obj.get("source")?;
+        match serde_json::from_value(value.clone()) {
+            Ok(source) => Some(source),
+            Err(e) => {
+                tracing::warn!(
+                    "Ignoring invalid source for argument '{}': {}",
+                    arg_obj
+                        .get("name")
+                        .and_then(serde_json::Value::as_str)
+                        .unwrap_or_default(),
+                    e
+                );
+                None
+            }
+        }
+    }
+
     /// Determine if a prompt is likely a partial template
     fn is_likely_partial(name: &str, content: &str) -> bool {
         // Check if the name suggests it's a partial (common naming patterns)
@@ -1409,7 +2113,9 @@ impl PromptLoader {
         };
 
         // If it's very short and has no headers, it might be a partial
-        if content_lines.len() <= 5 && !content_lines.iter().any(|line| line.starts_with('#')) {
+        if content_lines.len() <= PARTIAL_MAX_LINES
+            && !content_lines.iter().any(|line| line.starts_with('#'))
+        {
             return true;
         }
 
@@ -1427,6 +2133,7 @@ impl PromptLoader {
 
         // Parse metadata
         if let Some(ref metadata_value) = metadata {
+            Self::insert_custom_front_matter(&mut prompt, metadata_value);
             if let Some(title) = metadata_value
                 .get("title")
                 .and_then(serde_json::Value::as_str)
@@ -1436,6 +2143,68 @@ impl PromptLoader {
                     serde_json::Value::String(title.to_string()),
                 );
             }
+            if let Some(strict) = metadata_value
+                .get("strict")
+                .and_then(serde_json::Value::as_bool)
+            {
+                prompt
+                    .metadata
+                    .insert("strict".to_string(), serde_json::Value::Bool(strict));
+            }
+            if let Some(minify) = metadata_value
+                .get("minify")
+                .and_then(serde_json::Value::as_bool)
+            {
+                prompt
+                    .metadata
+                    .insert("minify".to_string(), serde_json::Value::Bool(minify));
+            }
+            if let Some(visibility) = metadata_value.get("visibility") {
+                prompt
+                    .metadata
+                    .insert("visibility".to_string(), visibility.clone());
+            }
+            if let Some(expose) = metadata_value
+                .get("expose")
+                .and_then(serde_json::Value::as_bool)
+            {
+                prompt
+                    .metadata
+                    .insert("expose".to_string(), serde_json::Value::Bool(expose));
+            }
+            if let Some(layout) = metadata_value
+                .get("layout")
+                .and_then(serde_json::Value::as_str)
+            {
+                prompt.metadata.insert(
+                    "layout".to_string(),
+                    serde_json::Value::String(layout.to_string()),
+                );
+            }
+            if let Some(deprecated) = metadata_value
+                .get("deprecated")
+                .and_then(serde_json::Value::as_bool)
+            {
+                prompt.metadata.insert(
+                    "deprecated".to_string(),
+                    serde_json::Value::Bool(deprecated),
+                );
+            }
+            if let Some(replacement) = metadata_value
+                .get("superseded_by")
+                .and_then(serde_json::Value::as_str)
+            {
+                prompt.metadata.insert(
+                    "superseded_by".to_string(),
+                    serde_json::Value::String(replacement.to_string()),
+                );
+            }
+            if let Some(tests) = metadata_value.get(crate::prompt_tests::TESTS_FRONT_MATTER_FIELD) {
+                prompt.metadata.insert(
+                    crate::prompt_tests::TESTS_FRONT_MATTER_FIELD.to_string(),
+                    tests.clone(),
+                );
+            }
             if let Some(desc) = metadata_value
                 .get("description")
                 .and_then(serde_json::Value::as_str)
@@ -1481,6 +2250,18 @@ impl PromptLoader {
                                 .get("type")
                                 .and_then(serde_json::Value::as_str)
                                 .map(String::from),
+                            choices: arg_obj
+                                .get("choices")
+                                .and_then(serde_json::Value::as_array)
+                                .map(|choices| {
+                                    choices
+                                        .iter()
+                                        .filter_map(serde_json::Value::as_str)
+                                        .map(String::from)
+                                        .collect()
+                                }),
+                            completion: Self::parse_completion(arg_obj),
+                            source: Self::parse_source(arg_obj),
                         };
 
                         prompt.arguments.push(arg_spec);
@@ -1499,6 +2280,17 @@ impl PromptLoader {
         Ok(prompt)
     }
 
+    /// Keep the front matter fields that have no [`Prompt`] field in its metadata
+    fn insert_custom_front_matter(prompt: &mut Prompt, front_matter: &serde_json::Value) {
+        if let Some(fields) = front_matter.as_object() {
+            for (field, value) in fields {
+                if !STANDARD_FRONT_MATTER_FIELDS.contains(&field.as_str()) {
+                    prompt.metadata.insert(field.clone(), value.clone());
+                }
+            }
+        }
+    }
+
     /// Check if a path is a prompt file
     fn is_prompt_file(&self, path: &Path) -> bool {
         let path_str = path.to_string_lossy().to_lowercase();
@@ -1586,6 +2378,44 @@ impl Default for PromptLoader {
     }
 }
 
+/// Map a 1-based position in the rendered `source` to one in `template`
+///
+/// The source is the template itself, or the template merged into a layout.
+/// A template found whole in the source is mapped by offset; otherwise the
+/// erroneous line is looked up in the template, which finds it in blocks the
+/// prompt overrides. Positions in the layout's own text give `None`.
+fn position_in_template(
+    source: &str,
+    template: &str,
+    line: usize,
+    column: usize,
+) -> Option<(usize, usize)> {
+    if source == template {
+        return Some((line, column));
+    }
+    if let Some(start) = source.find(template).filter(|_| !template.is_empty()) {
+        let (start_line, start_column) = crate::template::line_column(source, start);
+        let end_line = start_line + template.matches('\n').count();
+        if line < start_line || line > end_line || (line == start_line && column < start_column) {
+            return None;
+        }
+        let column = if line == start_line {
+            column - start_column + 1
+        } else {
+            column
+        };
+        return Some((line - start_line + 1, column));
+    }
+    let text = source.lines().nth(line.checked_sub(1)?)?;
+    if text.trim().is_empty() {
+        return None;
+    }
+    template
+        .lines()
+        .position(|template_line| template_line == text)
+        .map(|index| (index + 1, column))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1605,6 +2435,9 @@ mod tests {
             required: true,
             default: None,
             type_hint: None,
+            choices: None,
+            completion: None,
+            source: None,
         });
 
         let mut args = HashMap::new();
@@ -1614,6 +2447,187 @@ mod tests {
         assert_eq!(result, "Hello World!");
     }
 
+    #[test]
+    fn test_strict_front_matter_makes_undefined_variables_errors() {
+        let loader = PromptLoader::new();
+        let content = r"---
+title: Strict Prompt
+strict: true
+---
+Review {{ language }} code";
+        let prompt = loader.load_from_string("strict", content).unwrap();
+        assert!(prompt.is_strict());
+
+        let error = prompt.render(&HashMap::new()).unwrap_err().to_string();
+        assert!(error.contains("Undefined variable 'language'"), "{error}");
+
+        let mut args = HashMap::new();
+        args.insert("language".to_string(), "Rust".to_string());
+        assert_eq!(prompt.render(&args).unwrap(), "Review Rust code");
+    }
+
+    #[test]
+    fn test_minify_front_matter() {
+        let loader = PromptLoader::new();
+        let content = "---\ntitle: Minified\nminify: true\n---\n{% comment %}\nNotes for maintainers\n{% endcomment %}\nReview {{ language }}   \n\n\n\n{% comment %}Checklist{% endcomment %}\nBe brief\n";
+        let prompt = loader.load_from_string("minified", content).unwrap();
+        assert!(prompt.is_minified());
+
+        let mut args = HashMap::new();
+        args.insert("language".to_string(), "Rust".to_string());
+        assert_eq!(prompt.render(&args).unwrap(), "Review Rust\n\nBe brief");
+
+        let mut prompt = prompt;
+        prompt
+            .metadata
+            .insert("minify".to_string(), serde_json::Value::Bool(false));
+        assert!(prompt.render(&args).unwrap().contains("Rust   \n\n\n\n"));
+    }
+
+    #[test]
+    fn test_visibility_front_matter() {
+        let loader = PromptLoader::new();
+        let load = |front_matter: &str| {
+            let content = format!("---\ntitle: Internal\n{front_matter}---\nInternal notes");
+            loader.load_from_string("internal", &content).unwrap()
+        };
+
+        assert_eq!(load("").visibility(), PromptVisibility::Mcp);
+        assert_eq!(load("expose: false\n").visibility(), PromptVisibility::Cli);
+        assert_eq!(
+            load("visibility: private\nexpose: true\n").visibility(),
+            PromptVisibility::Private
+        );
+        assert_eq!(
+            load("visibility: mcp\n").visibility(),
+            PromptVisibility::Mcp
+        );
+
+        // A misspelled visibility hides the prompt and fails validation
+        let prompt = load("visibility: cil\n");
+        assert_eq!(prompt.visibility(), PromptVisibility::Private);
+        assert!(prompt
+            .validate(None)
+            .iter()
+            .any(|issue| issue.message.contains("Invalid visibility")));
+    }
+
+    #[test]
+    fn test_deprecated_front_matter() {
+        let loader = PromptLoader::new();
+        let content = r"---
+title: Old Review
+deprecated: true
+superseded_by: review/code
+---
+Review the code";
+        let prompt = loader.load_from_string("old-review", content).unwrap();
+        assert!(prompt.is_deprecated());
+        assert_eq!(prompt.superseded_by(), Some("review/code"));
+        // Deprecated prompts keep rendering so existing workflows still run
+        assert_eq!(prompt.render(&HashMap::new()).unwrap(), "Review the code");
+
+        let current = loader
+            .load_from_string("review", "---\ntitle: Review\n---\nReview")
+            .unwrap();
+        assert!(!current.is_deprecated());
+        assert_eq!(current.deprecation_warning(), None);
+    }
+
+    #[test]
+    fn test_argument_choices_from_front_matter() {
+        let loader = PromptLoader::new();
+        let content = r"---
+title: Review
+arguments:
+  - name: language
+    required: true
+    choices: [rust, python]
+  - name: focus
+---
+Review {{ language }} code";
+        let prompt = loader.load_from_string("review", content).unwrap();
+
+        assert_eq!(
+            prompt.arguments[0].choices,
+            Some(vec!["rust".to_string(), "python".to_string()])
+        );
+        assert_eq!(prompt.arguments[1].choices, None);
+    }
+
+    #[test]
+    fn test_argument_completion_from_front_matter() {
+        let loader = PromptLoader::new();
+        let content = r"---
+title: Review
+arguments:
+  - name: file
+    completion:
+      glob: 'src/**/*.rs'
+  - name: branch
+    completion: git_branches
+  - name: focus
+    completion: everything
+---
+Review {{ file }} on {{ branch }}";
+        let prompt = loader.load_from_string("review", content).unwrap();
+
+        assert_eq!(
+            prompt.arguments[0].completion,
+            Some(CompletionSource::Glob("src/**/*.rs".to_string()))
+        );
+        assert_eq!(
+            prompt.arguments[1].completion,
+            Some(CompletionSource::GitBranches)
+        );
+        assert_eq!(prompt.arguments[2].completion, None);
+    }
+
+    #[test]
+    fn test_argument_source_resolved_at_render_time() {
+        std::env::set_var("SAH_PROMPT_SOURCE_TEST_TOKEN", "t0ken");
+        let loader = PromptLoader::new();
+        let content = r"---
+title: Deploy
+arguments:
+  - name: token
+    required: true
+    source: env:SAH_PROMPT_SOURCE_TEST_TOKEN
+  - name: region
+    default: eu-west-1
+    source: env:SAH_PROMPT_SOURCE_TEST_UNSET
+  - name: secret
+    source: secrets
+---
+Deploy to {{ region }} with {{ token }}";
+        let prompt = loader.load_from_string("deploy", content).unwrap();
+        assert_eq!(
+            prompt.arguments[0].source.as_ref().map(ToString::to_string),
+            Some("env:SAH_PROMPT_SOURCE_TEST_TOKEN".to_string())
+        );
+        assert_eq!(prompt.arguments[2].source, None);
+
+        let rendered = prompt.render(&HashMap::new()).unwrap();
+        assert_eq!(rendered, "Deploy to eu-west-1 with t0ken");
+
+        // Given values win over sources
+        let args = HashMap::from([("token".to_string(), "given".to_string())]);
+        assert_eq!(
+            prompt.render(&args).unwrap(),
+            "Deploy to eu-west-1 with given"
+        );
+
+        let mut prompt = prompt;
+        prompt.arguments[0].source = Some("env:SAH_PROMPT_SOURCE_TEST_UNSET".parse().unwrap());
+        let error = prompt.render(&HashMap::new()).unwrap_err().to_string();
+        assert!(
+            error.contains(
+                "Argument 'token' could not be read from env:SAH_PROMPT_SOURCE_TEST_UNSET"
+            ),
+            "{error}"
+        );
+    }
+
     #[test]
     fn test_extension_stripping() {
         let loader = PromptLoader::new();
@@ -1788,4 +2802,95 @@ This is another prompt.
             );
         }
     }
+
+    #[test]
+    fn test_lazy_library_reads_only_front_matter() {
+        use std::fs;
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let body: String = (1..=50).map(|n| format!("Step {n}\n")).collect();
+        fs::write(
+            temp_dir.path().join("plan.md"),
+            format!("---\ndescription: Plan the work\ntags: [planning]\n---\n# Plan\n{body}"),
+        )
+        .unwrap();
+        fs::write(temp_dir.path().join("_note.md"), "A short note\n").unwrap();
+
+        let content = PromptLoader::read_front_matter(temp_dir.path().join("plan.md")).unwrap();
+        assert!(content.ends_with("Step 5\n"));
+
+        let mut library =
+            PromptLibrary::with_storage(Box::new(crate::storage::LazyPromptStorage::new(8)));
+        assert!(library.loads_templates_lazily());
+        assert_eq!(library.add_directory(temp_dir.path()).unwrap(), 2);
+
+        let listed = library.search("planning").unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].description.as_deref(), Some("Plan the work"));
+        assert!(listed[0].template.is_empty());
+        assert!(library.get("_note").unwrap().is_partial());
+
+        let plan = library.get("plan").unwrap();
+        assert!(plan.template.starts_with("# Plan\n"));
+        assert!(plan.template.ends_with("Step 50\n"));
+    }
+
+    #[test]
+    fn test_render_errors_point_into_prompt_file() {
+        use std::fs;
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("review.md");
+        fs::write(
+            &path,
+            "---\ntitle: Review\nstrict: true\n---\n\nIntro\nReview {{ langauge }} code\n",
+        )
+        .unwrap();
+        let prompt = PromptLoader::new().load_file(&path).unwrap();
+
+        let error = prompt.render(&HashMap::new()).unwrap_err().to_string();
+        let expected = format!(
+            "{}:7:11: Undefined variable 'langauge'\n  |\n7 | Review {{{{ langauge }}}} code\n  |           ^",
+            path.display()
+        );
+        assert!(error.ends_with(&expected), "{error}");
+
+        // Syntax errors are mapped too; prompts without a file are named instead
+        let inline = Prompt::new("inline", "Hello\n{{ name | }}");
+        let error = inline.render(&HashMap::new()).unwrap_err().to_string();
+        assert!(
+            error.contains("inline:2:10: expected Identifier\n  |\n2 | {{ name | }}"),
+            "{error}"
+        );
+
+        // Errors in a prompt's layout block are found in the prompt
+        let mut library = PromptLibrary::new();
+        library
+            .add(Prompt::new(
+                "base",
+                "Intro\n{% block checks %}Bugs{% endblock %}",
+            ))
+            .unwrap();
+        let mut child = Prompt::new(
+            "child",
+            "{% block checks %}\nCheck {{ missing }}\n{% endblock %}",
+        );
+        child
+            .metadata
+            .insert("layout".to_string(), serde_json::json!("base"));
+        child
+            .metadata
+            .insert("strict".to_string(), serde_json::json!(true));
+        library.add(child).unwrap();
+        let error = library
+            .render_prompt("child", &HashMap::new())
+            .unwrap_err()
+            .to_string();
+        assert!(
+            error.contains("child:2:10: Undefined variable 'missing'"),
+            "{error}"
+        );
+    }
 }
@@ -0,0 +1,934 @@
+//! SQL-like queries over the metadata of issues and memos
+//!
+//! Ad-hoc reports over issues and memos are written as a small subset of SQL
+//! instead of piping exports through `jq`:
+//!
+//! ```text
+//! SELECT name, created_at FROM issues WHERE label = 'bug' ORDER BY created_at
+//! SELECT title FROM memos WHERE title LIKE '%meeting%' ORDER BY updated_at DESC LIMIT 5
+//! ```
+//!
+//! A query selects columns, or `*`, from `issues` or `memos`, optionally
+//! filtered by a `WHERE` clause of comparisons (`=`, `!=`, `<>`, `<`, `<=`,
+//! `>`, `>=`), `LIKE` patterns with `%` and `_`, `IN (...)` lists and
+//! `IS [NOT] NULL`, combined with `AND`, `OR`, `NOT` and parentheses, then
+//! ordered by `ORDER BY` and cut short by `LIMIT`. Keywords are case
+//! insensitive, strings are single quoted and `LIKE` ignores case.
+//!
+//! List columns such as `labels` equal a value when any of their items does,
+//! so `label = 'bug'` finds issues labelled `bug`. Timestamps are RFC 3339
+//! strings, which compare and sort in time order, so `created_at >=
+//! '2026-01-01'` works as expected. A comparison with a missing value is
+//! false, and missing values sort last.
+
+use crate::error::{Result, SwissArmyHammerError};
+use crate::issues::{Issue, IssueMetadata, COMPLETE_STATE, OPEN_STATE};
+use crate::memoranda::Memo;
+use chrono::{DateTime, SecondsFormat, Utc};
+use regex::RegexBuilder;
+use serde_json::Value;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Values of one issue or memo, by column name
+pub type Row = BTreeMap<String, Value>;
+
+/// What a query can select from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueryTable {
+    /// Pending and completed issues
+    Issues,
+    /// Memos
+    Memos,
+}
+
+impl QueryTable {
+    /// Columns of the table, in the order `SELECT *` returns them
+    pub fn columns(&self) -> &'static [&'static str] {
+        match self {
+            Self::Issues => &[
+                "name",
+                "state",
+                "completed",
+                "priority",
+                "estimate",
+                "labels",
+                "assignee",
+                "reporter",
+                "repo",
+                "related_memos",
+                "created_at",
+                "file_path",
+            ],
+            Self::Memos => &["id", "title", "created_at", "updated_at"],
+        }
+    }
+
+    /// Columns that can be queried but are left out of `SELECT *`, because
+    /// they hold whole documents or repeat another column
+    fn hidden_columns(&self) -> &'static [&'static str] {
+        match self {
+            Self::Issues => &["content", "label"],
+            Self::Memos => &["content"],
+        }
+    }
+
+    fn has_column(&self, column: &str) -> bool {
+        self.columns().contains(&column) || self.hidden_columns().contains(&column)
+    }
+}
+
+impl FromStr for QueryTable {
+    type Err = SwissArmyHammerError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "issues" => Ok(Self::Issues),
+            "memos" => Ok(Self::Memos),
+            _ => Err(invalid(format!(
+                "unknown table '{s}', expected issues or memos"
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for QueryTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Issues => write!(f, "issues"),
+            Self::Memos => write!(f, "memos"),
+        }
+    }
+}
+
+fn timestamp(time: &DateTime<Utc>) -> Value {
+    Value::String(time.to_rfc3339_opts(SecondsFormat::Secs, true))
+}
+
+fn optional(value: Option<String>) -> Value {
+    value.map(Value::String).unwrap_or(Value::Null)
+}
+
+fn list(items: Vec<String>) -> Value {
+    Value::Array(items.into_iter().map(Value::String).collect())
+}
+
+/// The row an issue is queried as
+pub fn issue_row(issue: &Issue) -> Row {
+    let metadata = IssueMetadata::from_content(&issue.content);
+    let state = if issue.completed {
+        COMPLETE_STATE.to_string()
+    } else {
+        metadata.state.unwrap_or_else(|| OPEN_STATE.to_string())
+    };
+    Row::from([
+        ("name".to_string(), Value::String(issue.name.clone())),
+        ("state".to_string(), Value::String(state)),
+        ("completed".to_string(), Value::Bool(issue.completed)),
+        (
+            "priority".to_string(),
+            optional(metadata.priority.map(|priority| priority.to_string())),
+        ),
+        (
+            "estimate".to_string(),
+            metadata.estimate.map(Value::from).unwrap_or(Value::Null),
+        ),
+        ("labels".to_string(), list(metadata.labels.clone())),
+        ("label".to_string(), list(metadata.labels)),
+        ("assignee".to_string(), optional(metadata.assignee)),
+        ("reporter".to_string(), optional(metadata.reporter)),
+        ("repo".to_string(), optional(metadata.repo)),
+        ("related_memos".to_string(), list(metadata.related_memos)),
+        ("created_at".to_string(), timestamp(&issue.created_at)),
+        (
+            "file_path".to_string(),
+            Value::String(issue.file_path.display().to_string()),
+        ),
+        ("content".to_string(), Value::String(issue.content.clone())),
+    ])
+}
+
+/// The row a memo is queried as
+pub fn memo_row(memo: &Memo) -> Row {
+    Row::from([
+        ("id".to_string(), Value::String(memo.id.to_string())),
+        ("title".to_string(), Value::String(memo.title.clone())),
+        ("created_at".to_string(), timestamp(&memo.created_at)),
+        ("updated_at".to_string(), timestamp(&memo.updated_at)),
+        ("content".to_string(), Value::String(memo.content.clone())),
+    ])
+}
+
+fn invalid(message: impl fmt::Display) -> SwissArmyHammerError {
+    SwissArmyHammerError::Other(format!("Invalid query: {message}"))
+}
+
+/// Comparison operators of a `WHERE` clause
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareOp {
+    /// `=`
+    Eq,
+    /// `!=` or `<>`
+    Ne,
+    /// `<`
+    Lt,
+    /// `<=`
+    Le,
+    /// `>`
+    Gt,
+    /// `>=`
+    Ge,
+}
+
+/// A `WHERE` clause
+#[derive(Debug, Clone, PartialEq)]
+pub enum Condition {
+    /// `column <op> value`
+    Compare(String, CompareOp, Value),
+    /// `column [NOT] LIKE 'pattern'`
+    Like(String, String, bool),
+    /// `column [NOT] IN (values)`
+    In(String, Vec<Value>, bool),
+    /// `column IS [NOT] NULL`
+    IsNull(String, bool),
+    /// Both conditions hold
+    And(Box<Condition>, Box<Condition>),
+    /// Either condition holds
+    Or(Box<Condition>, Box<Condition>),
+    /// The condition does not hold
+    Not(Box<Condition>),
+}
+
+/// Order of rows by one column
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrderBy {
+    /// Column to sort by
+    pub column: String,
+    /// Whether the largest values come first
+    pub descending: bool,
+}
+
+/// A parsed query
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+    /// Columns to return, empty for `*`
+    pub columns: Vec<String>,
+    /// Table the rows come from
+    pub table: QueryTable,
+    /// Condition rows must meet
+    pub filter: Option<Condition>,
+    /// Sort order, first column first
+    pub order_by: Vec<OrderBy>,
+    /// Most rows to return
+    pub limit: Option<usize>,
+}
+
+/// Rows a query returned
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryResult {
+    /// Names of the returned columns
+    pub columns: Vec<String>,
+    /// Values of each row, in column order
+    pub rows: Vec<Vec<Value>>,
+}
+
+impl Query {
+    /// Columns the query returns
+    pub fn output_columns(&self) -> Vec<String> {
+        if self.columns.is_empty() {
+            self.table
+                .columns()
+                .iter()
+                .map(|column| column.to_string())
+                .collect()
+        } else {
+            self.columns.clone()
+        }
+    }
+
+    /// Filter, order and project the rows of the query's table
+    pub fn execute(&self, rows: Vec<Row>) -> Result<QueryResult> {
+        let mut rows = match &self.filter {
+            Some(filter) => {
+                let mut matching = Vec::new();
+                for row in rows {
+                    if filter.matches(&row)? {
+                        matching.push(row);
+                    }
+                }
+                matching
+            }
+            None => rows,
+        };
+        rows.sort_by(|a, b| {
+            self.order_by
+                .iter()
+                .map(|order| {
+                    let ordering = sort_values(a.get(&order.column), b.get(&order.column));
+                    match (order.descending, a.get(&order.column), b.get(&order.column)) {
+                        // Missing values stay last whichever way the column sorts
+                        (true, Some(x), Some(y)) if !x.is_null() && !y.is_null() => {
+                            ordering.reverse()
+                        }
+                        _ => ordering,
+                    }
+                })
+                .find(|ordering| *ordering != Ordering::Equal)
+                .unwrap_or(Ordering::Equal)
+        });
+        if let Some(limit) = self.limit {
+            rows.truncate(limit);
+        }
+
+        let columns = self.output_columns();
+        let rows = rows
+            .into_iter()
+            .map(|row| {
+                columns
+                    .iter()
+                    .map(|column| row.get(column).cloned().unwrap_or(Value::Null))
+                    .collect()
+            })
+            .collect();
+        Ok(QueryResult { columns, rows })
+    }
+
+    fn check_columns(&self) -> Result<()> {
+        let mut columns: Vec<&String> = self.columns.iter().collect();
+        columns.extend(self.order_by.iter().map(|order| &order.column));
+        if let Some(filter) = &self.filter {
+            filter.columns(&mut columns);
+        }
+        match columns
+            .into_iter()
+            .find(|column| !self.table.has_column(column))
+        {
+            Some(column) => Err(invalid(format!(
+                "unknown column '{column}' in {}, expected one of {}",
+                self.table,
+                self.table.columns().join(", ")
+            ))),
+            None => Ok(()),
+        }
+    }
+}
+
+impl FromStr for Query {
+    type Err = SwissArmyHammerError;
+
+    fn from_str(query: &str) -> Result<Self> {
+        let mut parser = Parser {
+            tokens: tokenize(query)?,
+            position: 0,
+        };
+        let query = parser.query()?;
+        query.check_columns()?;
+        Ok(query)
+    }
+}
+
+impl Condition {
+    fn columns<'a>(&'a self, columns: &mut Vec<&'a String>) {
+        match self {
+            Self::Compare(column, _, _)
+            | Self::Like(column, _, _)
+            | Self::In(column, _, _)
+            | Self::IsNull(column, _) => columns.push(column),
+            Self::And(a, b) | Self::Or(a, b) => {
+                a.columns(columns);
+                b.columns(columns);
+            }
+            Self::Not(condition) => condition.columns(columns),
+        }
+    }
+
+    /// Whether a row meets the condition
+    pub fn matches(&self, row: &Row) -> Result<bool> {
+        let value = |column: &String| row.get(column).unwrap_or(&Value::Null);
+        Ok(match self {
+            Self::Compare(column, op, expected) => compare(value(column), *op, expected),
+            Self::Like(column, pattern, negated) => {
+                let regex = like_regex(pattern)?;
+                let matched = match value(column) {
+                    Value::Null => return Ok(false),
+                    Value::Array(items) => items.iter().any(|item| regex.is_match(&text(item))),
+                    found => regex.is_match(&text(found)),
+                };
+                matched != *negated
+            }
+            Self::In(column, values, negated) => {
+                if value(column).is_null() {
+                    return Ok(false);
+                }
+                let found = values
+                    .iter()
+                    .any(|expected| compare(value(column), CompareOp::Eq, expected));
+                found != *negated
+            }
+            Self::IsNull(column, negated) => {
+                let missing = match value(column) {
+                    Value::Null => true,
+                    Value::Array(items) => items.is_empty(),
+                    _ => false,
+                };
+                missing != *negated
+            }
+            Self::And(a, b) => a.matches(row)? && b.matches(row)?,
+            Self::Or(a, b) => a.matches(row)? || b.matches(row)?,
+            Self::Not(condition) => !condition.matches(row)?,
+        })
+    }
+}
+
+/// A value as text, for `LIKE` and output
+pub fn text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        Value::Array(items) => items.iter().map(text).collect::<Vec<_>>().join(", "),
+        other => other.to_string(),
+    }
+}
+
+fn like_regex(pattern: &str) -> Result<regex::Regex> {
+    let mut regex = String::from("^");
+    for c in pattern.chars() {
+        match c {
+            '%' => regex.push_str(".*"),
+            '_' => regex.push('.'),
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+    RegexBuilder::new(&regex)
+        .case_insensitive(true)
+        .dot_matches_new_line(true)
+        .build()
+        .map_err(|e| invalid(format!("bad LIKE pattern '{pattern}': {e}")))
+}
+
+/// Order of two values of the same column, missing values last
+fn sort_values(a: Option<&Value>, b: Option<&Value>) -> Ordering {
+    let a = a.unwrap_or(&Value::Null);
+    let b = b.unwrap_or(&Value::Null);
+    match (a.is_null(), b.is_null()) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Greater,
+        (false, true) => Ordering::Less,
+        (false, false) => order(a, b).unwrap_or_else(|| text(a).cmp(&text(b))),
+    }
+}
+
+fn order(a: &Value, b: &Value) -> Option<Ordering> {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => a.as_f64()?.partial_cmp(&b.as_f64()?),
+        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+        (Value::Bool(a), Value::Bool(b)) => Some(a.cmp(b)),
+        _ => None,
+    }
+}
+
+fn compare(found: &Value, op: CompareOp, expected: &Value) -> bool {
+    if let Value::Array(items) = found {
+        return match op {
+            CompareOp::Eq => items.iter().any(|item| compare(item, op, expected)),
+            CompareOp::Ne => !items
+                .iter()
+                .any(|item| compare(item, CompareOp::Eq, expected)),
+            _ => false,
+        };
+    }
+    let Some(ordering) = order(found, expected) else {
+        return false;
+    };
+    match op {
+        CompareOp::Eq => ordering == Ordering::Equal,
+        CompareOp::Ne => ordering != Ordering::Equal,
+        CompareOp::Lt => ordering == Ordering::Less,
+        CompareOp::Le => ordering != Ordering::Greater,
+        CompareOp::Gt => ordering == Ordering::Greater,
+        CompareOp::Ge => ordering != Ordering::Less,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Text(String),
+    Number(f64),
+    Symbol(&'static str),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Word(word) => write!(f, "{word}"),
+            Self::Text(text) => write!(f, "'{text}'"),
+            Self::Number(number) => write!(f, "{number}"),
+            Self::Symbol(symbol) => write!(f, "{symbol}"),
+        }
+    }
+}
+
+const SYMBOLS: [&str; 11] = ["<=", ">=", "!=", "<>", "=", "<", ">", ",", "(", ")", "*"];
+
+fn tokenize(query: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = query.char_indices().peekable();
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '\'' {
+            chars.next();
+            let mut text = String::new();
+            loop {
+                match chars.next() {
+                    // A doubled quote stands for one quote
+                    Some((_, '\'')) if matches!(chars.peek(), Some((_, '\''))) => {
+                        chars.next();
+                        text.push('\'');
+                    }
+                    Some((_, '\'')) => break,
+                    Some((_, c)) => text.push(c),
+                    None => return Err(invalid("unterminated string")),
+                }
+            }
+            tokens.push(Token::Text(text));
+        } else if c.is_ascii_digit()
+            || (c == '-' && query[start + 1..].starts_with(|c: char| c.is_ascii_digit()))
+        {
+            let mut end = start + c.len_utf8();
+            chars.next();
+            while let Some(&(i, c)) = chars.peek() {
+                if !(c.is_ascii_digit() || c == '.') {
+                    break;
+                }
+                end = i + c.len_utf8();
+                chars.next();
+            }
+            let number = &query[start..end];
+            tokens.push(Token::Number(
+                number
+                    .parse()
+                    .map_err(|_| invalid(format!("bad number '{number}'")))?,
+            ));
+        } else if c.is_alphabetic() || c == '_' {
+            let mut word = String::new();
+            while let Some(&(_, c)) = chars.peek() {
+                if !(c.is_alphanumeric() || c == '_') {
+                    break;
+                }
+                word.push(c);
+                chars.next();
+            }
+            tokens.push(Token::Word(word));
+        } else {
+            let symbol = SYMBOLS
+                .into_iter()
+                .find(|symbol| query[start..].starts_with(symbol))
+                .ok_or_else(|| invalid(format!("unexpected '{c}'")))?;
+            for _ in 0..symbol.len() {
+                chars.next();
+            }
+            tokens.push(Token::Symbol(symbol));
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn found(&self) -> String {
+        match self.peek() {
+            Some(token) => format!("'{token}'"),
+            None => "the end of the query".to_string(),
+        }
+    }
+
+    /// Consume a keyword if it comes next
+    fn keyword(&mut self, keyword: &str) -> bool {
+        match self.peek() {
+            Some(Token::Word(word)) if word.eq_ignore_ascii_case(keyword) => {
+                self.position += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<()> {
+        if self.keyword(keyword) {
+            return Ok(());
+        }
+        Err(invalid(format!(
+            "expected {keyword}, found {}",
+            self.found()
+        )))
+    }
+
+    fn symbol(&mut self, symbol: &'static str) -> bool {
+        if self.peek() == Some(&Token::Symbol(symbol)) {
+            self.position += 1;
+            return true;
+        }
+        false
+    }
+
+    fn expect_symbol(&mut self, symbol: &'static str) -> Result<()> {
+        if self.symbol(symbol) {
+            return Ok(());
+        }
+        Err(invalid(format!(
+            "expected '{symbol}', found {}",
+            self.found()
+        )))
+    }
+
+    fn column(&mut self) -> Result<String> {
+        match self.peek() {
+            Some(Token::Word(word)) if !is_keyword(word) => {
+                let column = word.to_ascii_lowercase();
+                self.position += 1;
+                Ok(column)
+            }
+            _ => Err(invalid(format!(
+                "expected a column, found {}",
+                self.found()
+            ))),
+        }
+    }
+
+    fn query(&mut self) -> Result<Query> {
+        self.expect_keyword("SELECT")?;
+        let mut columns = Vec::new();
+        if !self.symbol("*") {
+            loop {
+                columns.push(self.column()?);
+                if !self.symbol(",") {
+                    break;
+                }
+            }
+        }
+
+        self.expect_keyword("FROM")?;
+        let table = match self.peek() {
+            Some(Token::Word(table)) => table.parse()?,
+            _ => {
+                return Err(invalid(format!(
+                    "expected issues or memos after FROM, found {}",
+                    self.found()
+                )))
+            }
+        };
+        self.position += 1;
+
+        let filter = if self.keyword("WHERE") {
+            Some(self.or()?)
+        } else {
+            None
+        };
+
+        let mut order_by = Vec::new();
+        if self.keyword("ORDER") {
+            self.expect_keyword("BY")?;
+            loop {
+                let column = self.column()?;
+                let descending = if self.keyword("DESC") {
+                    true
+                } else {
+                    self.keyword("ASC");
+                    false
+                };
+                order_by.push(OrderBy { column, descending });
+                if !self.symbol(",") {
+                    break;
+                }
+            }
+        }
+
+        let limit = if self.keyword("LIMIT") {
+            match self.next() {
+                Some(Token::Number(limit)) if limit >= 0.0 && limit.fract() == 0.0 => {
+                    Some(limit as usize)
+                }
+                _ => return Err(invalid("LIMIT needs a whole number")),
+            }
+        } else {
+            None
+        };
+
+        if self.peek().is_some() {
+            return Err(invalid(format!("unexpected {}", self.found())));
+        }
+        Ok(Query {
+            columns,
+            table,
+            filter,
+            order_by,
+            limit,
+        })
+    }
+
+    fn or(&mut self) -> Result<Condition> {
+        let mut condition = self.and()?;
+        while self.keyword("OR") {
+            condition = Condition::Or(Box::new(condition), Box::new(self.and()?));
+        }
+        Ok(condition)
+    }
+
+    fn and(&mut self) -> Result<Condition> {
+        let mut condition = self.not()?;
+        while self.keyword("AND") {
+            condition = Condition::And(Box::new(condition), Box::new(self.not()?));
+        }
+        Ok(condition)
+    }
+
+    fn not(&mut self) -> Result<Condition> {
+        if self.keyword("NOT") {
+            return Ok(Condition::Not(Box::new(self.not()?)));
+        }
+        if self.symbol("(") {
+            let condition = self.or()?;
+            self.expect_symbol(")")?;
+            return Ok(condition);
+        }
+        self.comparison()
+    }
+
+    fn comparison(&mut self) -> Result<Condition> {
+        let column = self.column()?;
+        if self.keyword("IS") {
+            let negated = self.keyword("NOT");
+            self.expect_keyword("NULL")?;
+            return Ok(Condition::IsNull(column, negated));
+        }
+        let negated = self.keyword("NOT");
+        if self.keyword("LIKE") {
+            return match self.next() {
+                Some(Token::Text(pattern)) => Ok(Condition::Like(column, pattern, negated)),
+                _ => Err(invalid("LIKE needs a quoted pattern")),
+            };
+        }
+        if self.keyword("IN") {
+            self.expect_symbol("(")?;
+            let mut values = vec![self.value()?];
+            while self.symbol(",") {
+                values.push(self.value()?);
+            }
+            self.expect_symbol(")")?;
+            return Ok(Condition::In(column, values, negated));
+        }
+        if negated {
+            return Err(invalid(format!(
+                "expected LIKE or IN after NOT, found {}",
+                self.found()
+            )));
+        }
+
+        let op = match self.next() {
+            Some(Token::Symbol("=")) => CompareOp::Eq,
+            Some(Token::Symbol("!=")) | Some(Token::Symbol("<>")) => CompareOp::Ne,
+            Some(Token::Symbol("<")) => CompareOp::Lt,
+            Some(Token::Symbol("<=")) => CompareOp::Le,
+            Some(Token::Symbol(">")) => CompareOp::Gt,
+            Some(Token::Symbol(">=")) => CompareOp::Ge,
+            _ => {
+                self.position -= 1;
+                return Err(invalid(format!(
+                    "expected a comparison after '{column}', found {}",
+                    self.found()
+                )));
+            }
+        };
+        Ok(Condition::Compare(column, op, self.value()?))
+    }
+
+    fn value(&mut self) -> Result<Value> {
+        match self.next() {
+            Some(Token::Text(text)) => Ok(Value::String(text)),
+            Some(Token::Number(number)) => Ok(serde_json::Number::from_f64(number)
+                .map(Value::Number)
+                .unwrap_or(Value::Null)),
+            Some(Token::Word(word)) if word.eq_ignore_ascii_case("TRUE") => Ok(Value::Bool(true)),
+            Some(Token::Word(word)) if word.eq_ignore_ascii_case("FALSE") => Ok(Value::Bool(false)),
+            _ => {
+                self.position -= 1;
+                Err(invalid(format!("expected a value, found {}", self.found())))
+            }
+        }
+    }
+}
+
+fn is_keyword(word: &str) -> bool {
+    [
+        "SELECT", "FROM", "WHERE", "AND", "OR", "NOT", "LIKE", "IN", "IS", "NULL", "ORDER", "BY",
+        "ASC", "DESC", "LIMIT", "TRUE", "FALSE",
+    ]
+    .iter()
+    .any(|keyword| word.eq_ignore_ascii_case(keyword))
+}
+
+impl QueryResult {
+    /// The rows as JSON objects keyed by column
+    pub fn to_json(&self) -> Value {
+        Value::Array(
+            self.rows
+                .iter()
+                .map(|row| {
+                    Value::Object(
+                        self.columns
+                            .iter()
+                            .cloned()
+                            .zip(row.iter().cloned())
+                            .collect(),
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    /// The rows as CSV with a header line
+    pub fn to_csv(&self) -> String {
+        let line = |fields: Vec<String>| {
+            fields
+                .iter()
+                .map(|field| {
+                    if field.contains([',', '"', '\n', '\r']) {
+                        format!("\"{}\"", field.replace('"', "\"\""))
+                    } else {
+                        field.clone()
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        let mut csv = line(self.columns.clone());
+        csv.push('\n');
+        for row in &self.rows {
+            csv.push_str(&line(row.iter().map(text).collect()));
+            csv.push('\n');
+        }
+        csv
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn rows() -> Vec<Row> {
+        let issue = |name: &str, labels: &[&str], estimate: Option<u32>, created: &str| {
+            Row::from([
+                ("name".to_string(), json!(name)),
+                ("labels".to_string(), json!(labels)),
+                ("label".to_string(), json!(labels)),
+                ("estimate".to_string(), json!(estimate)),
+                ("created_at".to_string(), json!(created)),
+            ])
+        };
+        vec![
+            issue("000003_crash", &["bug"], Some(3), "2026-03-01T00:00:00Z"),
+            issue(
+                "000001_login",
+                &["bug", "auth"],
+                None,
+                "2026-01-01T00:00:00Z",
+            ),
+            issue("000002_docs", &[], Some(1), "2026-02-01T00:00:00Z"),
+        ]
+    }
+
+    fn names(result: &QueryResult) -> Vec<String> {
+        result.rows.iter().map(|row| text(&row[0])).collect()
+    }
+
+    #[test]
+    fn test_parse_query() {
+        let query: Query =
+            "select name, created_at from ISSUES where label='bug' order by created_at desc limit 2"
+                .parse()
+                .unwrap();
+        assert_eq!(query.columns, vec!["name", "created_at"]);
+        assert_eq!(query.table, QueryTable::Issues);
+        assert_eq!(
+            query.filter,
+            Some(Condition::Compare(
+                "label".to_string(),
+                CompareOp::Eq,
+                json!("bug")
+            ))
+        );
+        assert!(query.order_by[0].descending);
+        assert_eq!(query.limit, Some(2));
+
+        let query: Query = "SELECT * FROM memos".parse().unwrap();
+        assert_eq!(query.output_columns()[1], "title");
+
+        for bad in [
+            "SELECT name issues",
+            "SELECT name FROM tasks",
+            "SELECT nope FROM issues",
+            "SELECT name FROM issues WHERE name = 'open",
+            "SELECT name FROM issues WHERE name",
+            "SELECT name FROM issues LIMIT -1",
+            "SELECT name FROM issues extra",
+        ] {
+            assert!(bad.parse::<Query>().is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn test_execute_query() {
+        let run = |query: &str| query.parse::<Query>().unwrap().execute(rows()).unwrap();
+
+        let result = run("SELECT name FROM issues WHERE label = 'bug' ORDER BY created_at");
+        assert_eq!(names(&result), vec!["000001_login", "000003_crash"]);
+
+        let result = run("SELECT name FROM issues WHERE estimate >= 1 AND NOT label = 'bug'");
+        assert_eq!(names(&result), vec!["000002_docs"]);
+
+        let result = run("SELECT name FROM issues ORDER BY estimate DESC");
+        assert_eq!(
+            names(&result),
+            vec!["000003_crash", "000002_docs", "000001_login"]
+        );
+
+        let result = run(
+            "SELECT name FROM issues WHERE (name LIKE '%LOGIN' OR estimate IS NULL) OR name IN ('000002_docs')",
+        );
+        assert_eq!(result.rows.len(), 2);
+
+        let result =
+            run("SELECT name FROM issues WHERE created_at < '2026-02-15' ORDER BY name LIMIT 1");
+        assert_eq!(names(&result), vec!["000001_login"]);
+
+        let result = run("SELECT name, labels FROM issues WHERE labels IS NOT NULL ORDER BY name");
+        assert_eq!(
+            result.to_csv(),
+            "name,labels\n000001_login,\"bug, auth\"\n000003_crash,bug\n"
+        );
+        assert_eq!(
+            result.to_json()[0],
+            json!({"name": "000001_login", "labels": ["bug", "auth"]})
+        );
+    }
+}
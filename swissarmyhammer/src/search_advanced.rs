@@ -2,11 +2,24 @@
 //!
 //! This module extends the basic search functionality with additional features
 //! like regex search, case sensitivity options, excerpt generation, and more.
+//!
+//! Results are kept in a least recently used cache, so a client repeating a
+//! query does not pay for the matching and scoring passes again. Entries are
+//! keyed by the query, the options that change which prompts match, the
+//! filter, and a fingerprint of the prompt library, so any change to the
+//! library misses the cache. Entries also expire after a time to live.
 
+use crate::config::Config;
 use crate::prompt_search::{SearchEngine, SearchResult};
 use crate::{Prompt, PromptFilter, Result};
+use lru::LruCache;
 use regex::Regex;
-use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{Hash, Hasher};
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
 /// Advanced search options
 #[derive(Debug, Clone, Default)]
@@ -44,19 +57,121 @@ impl From<SearchResult> for AdvancedSearchResult {
     }
 }
 
+/// What makes two searches return the same results before the limit and
+/// excerpts are applied
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct QueryKey {
+    query: String,
+    regex: bool,
+    fuzzy: bool,
+    case_sensitive: bool,
+    filter: String,
+    library: u64,
+}
+
+impl QueryKey {
+    fn new(
+        query: &str,
+        prompts: &[Prompt],
+        options: &AdvancedSearchOptions,
+        filter: Option<&PromptFilter>,
+        sources: &HashMap<String, crate::PromptSource>,
+    ) -> Result<Self> {
+        // Regex search ignores fuzzy, and fuzzy search ignores case sensitivity
+        let fuzzy = options.fuzzy && !options.regex;
+        Ok(Self {
+            query: query.to_string(),
+            regex: options.regex,
+            fuzzy,
+            case_sensitive: options.case_sensitive && !fuzzy,
+            filter: filter.map(|f| format!("{f:?}")).unwrap_or_default(),
+            library: library_fingerprint(prompts, sources)?,
+        })
+    }
+}
+
+/// Hash of every prompt and source, changing whenever the library does
+fn library_fingerprint(
+    prompts: &[Prompt],
+    sources: &HashMap<String, crate::PromptSource>,
+) -> Result<u64> {
+    let mut hasher = DefaultHasher::new();
+    serde_json::to_vec(prompts)?.hash(&mut hasher);
+    let sources: BTreeMap<_, _> = sources.iter().collect();
+    serde_json::to_vec(&sources)?.hash(&mut hasher);
+    Ok(hasher.finish())
+}
+
+/// Least recently used query results that expire after a time to live
+struct QueryCache {
+    entries: LruCache<QueryKey, (Instant, Vec<AdvancedSearchResult>)>,
+    ttl: Duration,
+}
+
+impl QueryCache {
+    fn get(&mut self, key: &QueryKey) -> Option<Vec<AdvancedSearchResult>> {
+        let (cached_at, results) = self.entries.get(key)?;
+        if cached_at.elapsed() < self.ttl {
+            return Some(results.clone());
+        }
+        self.entries.pop(key);
+        None
+    }
+
+    fn put(&mut self, key: QueryKey, results: Vec<AdvancedSearchResult>) {
+        self.entries.put(key, (Instant::now(), results));
+    }
+}
+
 /// Enhanced search engine with advanced features
 pub struct AdvancedSearchEngine {
     base_engine: SearchEngine,
+    cache: Option<Mutex<QueryCache>>,
 }
 
 impl AdvancedSearchEngine {
-    /// Create a new advanced search engine
+    /// Create a new advanced search engine, caching query results as
+    /// configured by `search_query_cache_size` and
+    /// `search_query_cache_ttl_seconds`
     pub fn new() -> Result<Self> {
+        let config = Config::global();
+        Self::with_cache(
+            config.search_query_cache_size,
+            Duration::from_secs(config.search_query_cache_ttl_seconds),
+        )
+    }
+
+    /// Create a new advanced search engine caching the results of at most
+    /// `capacity` queries for `ttl`, or none when `capacity` is 0
+    pub fn with_cache(capacity: usize, ttl: Duration) -> Result<Self> {
+        let cache = NonZeroUsize::new(capacity)
+            .filter(|_| !ttl.is_zero())
+            .map(|capacity| {
+                Mutex::new(QueryCache {
+                    entries: LruCache::new(capacity),
+                    ttl,
+                })
+            });
         Ok(Self {
             base_engine: SearchEngine::new()?,
+            cache,
         })
     }
 
+    /// Number of queries whose results are cached
+    pub fn cached_queries(&self) -> usize {
+        self.cache
+            .as_ref()
+            .map_or(0, |cache| cache.lock().unwrap().entries.len())
+    }
+
+    /// Forget every cached result
+    pub fn clear_cache(&self) {
+        if let Some(cache) = &self.cache {
+            cache.lock().unwrap().entries.clear();
+        }
+    }
+
     /// Search with advanced options
     pub fn search(
         &self,
@@ -65,6 +180,30 @@ impl AdvancedSearchEngine {
         options: &AdvancedSearchOptions,
         filter: Option<&PromptFilter>,
         sources: &HashMap<String, crate::PromptSource>,
+    ) -> Result<Vec<AdvancedSearchResult>> {
+        let Some(cache) = &self.cache else {
+            let results = self.matching(query, prompts, options, filter, sources)?;
+            return Ok(finish_results(results, query, options));
+        };
+
+        let key = QueryKey::new(query, prompts, options, filter, sources)?;
+        if let Some(results) = cache.lock().unwrap().get(&key) {
+            tracing::debug!("Search cache hit for '{query}'");
+            return Ok(finish_results(results, query, options));
+        }
+        let results = self.matching(query, prompts, options, filter, sources)?;
+        cache.lock().unwrap().put(key, results.clone());
+        Ok(finish_results(results, query, options))
+    }
+
+    /// Every prompt matching a query, best first
+    fn matching(
+        &self,
+        query: &str,
+        prompts: &[Prompt],
+        options: &AdvancedSearchOptions,
+        filter: Option<&PromptFilter>,
+        sources: &HashMap<String, crate::PromptSource>,
     ) -> Result<Vec<AdvancedSearchResult>> {
         // Apply filter first if provided
         let filtered_prompts = if let Some(f) = filter {
@@ -87,18 +226,6 @@ impl AdvancedSearchEngine {
         // Sort by score
         results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap());
 
-        // Apply limit
-        if let Some(limit) = options.limit {
-            results.truncate(limit);
-        }
-
-        // Generate excerpts if requested
-        if options.highlight {
-            for result in &mut results {
-                result.excerpt = generate_excerpt(&result.prompt.template, query, true);
-            }
-        }
-
         Ok(results)
     }
 
@@ -195,6 +322,27 @@ impl AdvancedSearchEngine {
     }
 }
 
+/// Apply the limit and excerpts to matching prompts
+fn finish_results(
+    mut results: Vec<AdvancedSearchResult>,
+    query: &str,
+    options: &AdvancedSearchOptions,
+) -> Vec<AdvancedSearchResult> {
+    // Apply limit
+    if let Some(limit) = options.limit {
+        results.truncate(limit);
+    }
+
+    // Generate excerpts if requested
+    if options.highlight {
+        for result in &mut results {
+            result.excerpt = generate_excerpt(&result.prompt.template, query, true);
+        }
+    }
+
+    results
+}
+
 /// Generate an excerpt with optional highlighting
 pub fn generate_excerpt(content: &str, query: &str, highlight: bool) -> Option<String> {
     let query_lower = query.to_lowercase();
@@ -308,6 +456,9 @@ mod tests {
             required: true,
             default: None,
             type_hint: None,
+            choices: None,
+            completion: None,
+            source: None,
         });
         prompts.push(prompt_with_arg);
 
@@ -340,6 +491,68 @@ mod tests {
         assert!(results.len() <= 2);
     }
 
+    #[test]
+    fn test_query_cache() {
+        let engine = AdvancedSearchEngine::with_cache(10, Duration::from_secs(60)).unwrap();
+        let mut prompts = create_test_prompts();
+        let search = |prompts: &[Prompt], options: &AdvancedSearchOptions| {
+            engine
+                .search("e", prompts, options, None, &HashMap::new())
+                .unwrap()
+        };
+
+        let all = search(&prompts, &AdvancedSearchOptions::default());
+        assert_eq!(engine.cached_queries(), 1);
+
+        // The limit and excerpts are applied to the cached results
+        let limited = search(
+            &prompts,
+            &AdvancedSearchOptions {
+                limit: Some(1),
+                highlight: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(engine.cached_queries(), 1);
+        assert_eq!(limited.len(), 1);
+        assert_eq!(limited[0].prompt.name, all[0].prompt.name);
+
+        // A changed library misses the cache
+        prompts.push(Prompt::new("new_one", "Another prompt"));
+        let updated = search(&prompts, &AdvancedSearchOptions::default());
+        assert_eq!(updated.len(), all.len() + 1);
+        assert_eq!(engine.cached_queries(), 2);
+
+        engine.clear_cache();
+        assert_eq!(engine.cached_queries(), 0);
+
+        let uncached = AdvancedSearchEngine::with_cache(0, Duration::from_secs(60)).unwrap();
+        uncached
+            .search(
+                "e",
+                &prompts,
+                &AdvancedSearchOptions::default(),
+                None,
+                &HashMap::new(),
+            )
+            .unwrap();
+        assert_eq!(uncached.cached_queries(), 0);
+    }
+
+    #[test]
+    fn test_query_cache_expiry() {
+        let engine = AdvancedSearchEngine::with_cache(10, Duration::from_millis(1)).unwrap();
+        let prompts = create_test_prompts();
+        let options = AdvancedSearchOptions::default();
+        engine
+            .search("debug", &prompts, &options, None, &HashMap::new())
+            .unwrap();
+        std::thread::sleep(Duration::from_millis(5));
+        let key = QueryKey::new("debug", &prompts, &options, None, &HashMap::new()).unwrap();
+        let cache = engine.cache.as_ref().unwrap();
+        assert!(cache.lock().unwrap().get(&key).is_none());
+    }
+
     #[test]
     fn test_excerpt_generation() {
         let content = "This is a long text with the keyword somewhere in the middle of it";
@@ -4,8 +4,9 @@
 //! potential security vulnerabilities like path traversal attacks and denial
 //! of service through excessive resource consumption.
 
+use crate::config::Config;
 use crate::{Result, SwissArmyHammerError};
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 
 /// Maximum allowed depth for directory traversal operations
 pub const MAX_DIRECTORY_DEPTH: usize = 10;
@@ -25,6 +26,185 @@ pub const MAX_TEMPLATE_VARIABLES: usize = 1000;
 /// Maximum allowed template render time in milliseconds
 pub const MAX_TEMPLATE_RENDER_TIME_MS: u64 = 5000;
 
+/// Policy applied by [`validate_path`] to every file loaded from disk
+///
+/// The global policy is built from [`Config`], so it can be tuned with the
+/// `SWISSARMYHAMMER_ALLOW_SYMLINKS_OUTSIDE_ROOT`, `SWISSARMYHAMMER_MAX_PATH_DEPTH`
+/// and `SWISSARMYHAMMER_PATH_DENYLIST` environment variables.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathPolicy {
+    /// Whether symlinks may resolve to locations outside the root directory
+    pub allow_symlinks_outside_root: bool,
+    /// Maximum number of directories between the root and a file
+    pub max_depth: usize,
+    /// Glob patterns, relative to the root, of paths that must never be loaded
+    ///
+    /// A pattern that matches a directory denies everything below it.
+    pub denylist: Vec<String>,
+}
+
+impl Default for PathPolicy {
+    fn default() -> Self {
+        Self {
+            allow_symlinks_outside_root: false,
+            max_depth: MAX_DIRECTORY_DEPTH,
+            denylist: Vec::new(),
+        }
+    }
+}
+
+impl PathPolicy {
+    /// Build a policy from configuration settings
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            allow_symlinks_outside_root: config.allow_symlinks_outside_root,
+            max_depth: config.max_path_depth,
+            denylist: config.path_denylist.clone(),
+        }
+    }
+
+    /// Get the policy built from the global configuration
+    pub fn global() -> &'static Self {
+        static POLICY: std::sync::OnceLock<PathPolicy> = std::sync::OnceLock::new();
+        POLICY.get_or_init(|| PathPolicy::from_config(Config::global()))
+    }
+
+    /// Find the denylist pattern matching a path relative to the root, if any
+    fn denied_by(&self, relative: &Path) -> Result<Option<&str>> {
+        for pattern in &self.denylist {
+            let glob = glob::Pattern::new(pattern).map_err(|e| {
+                SwissArmyHammerError::Other(format!(
+                    "Invalid path denylist pattern '{pattern}': {e}"
+                ))
+            })?;
+            if relative
+                .ancestors()
+                .filter(|ancestor| !ancestor.as_os_str().is_empty())
+                .any(|ancestor| glob.matches_path(ancestor))
+            {
+                return Ok(Some(pattern));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// Validates a path against a root directory and a [`PathPolicy`]
+///
+/// This is the single check every loader runs before reading or writing a file
+/// from a directory it does not fully trust, such as a cloned prompt repository.
+/// The path may be relative to `root` or already joined onto it. It is rejected
+/// when:
+/// - It contains `..` components or is absolute outside `root`
+/// - It is nested more than `policy.max_depth` directories below `root`
+/// - It matches one of the `policy.denylist` globs
+/// - It, or a directory above it, is a symlink resolving outside `root`, unless
+///   the policy allows that
+///
+/// Paths that do not exist yet are checked through their nearest existing
+/// ancestor, so the function also guards files about to be created.
+///
+/// # Returns
+///
+/// The path joined onto `root`, without resolving symlinks, so callers keep the
+/// paths they display and store
+pub fn validate_path(path: &Path, root: &Path, policy: &PathPolicy) -> Result<PathBuf> {
+    let mut relative = PathBuf::new();
+    for component in relative_to_root(path, root)?.components() {
+        match component {
+            Component::Normal(part) => relative.push(part),
+            Component::CurDir => {}
+            Component::ParentDir => {
+                return Err(SwissArmyHammerError::Other(format!(
+                    "Path '{}' contains parent directory references (..)",
+                    path.display()
+                )));
+            }
+            Component::RootDir | Component::Prefix(_) => {
+                return Err(SwissArmyHammerError::Other(format!(
+                    "Path '{}' is outside allowed directory",
+                    path.display()
+                )));
+            }
+        }
+    }
+
+    // The last component is the file itself, everything before it a directory
+    let directory_depth = relative.components().count().saturating_sub(1);
+    if directory_depth > policy.max_depth {
+        return Err(SwissArmyHammerError::Other(format!(
+            "Path '{}' is nested {directory_depth} directories deep (max allowed: {})",
+            path.display(),
+            policy.max_depth
+        )));
+    }
+
+    if let Some(pattern) = policy.denied_by(&relative)? {
+        return Err(SwissArmyHammerError::Other(format!(
+            "Path '{}' matches denylist pattern '{pattern}'",
+            path.display()
+        )));
+    }
+
+    let full_path = root.join(&relative);
+    if !policy.allow_symlinks_outside_root {
+        let canonical_root = root.canonicalize().map_err(|e| {
+            SwissArmyHammerError::Other(format!("Failed to canonicalize root path: {e}"))
+        })?;
+
+        // symlink_metadata succeeds for dangling symlinks, which then fail to resolve
+        if let Some(existing) = full_path
+            .ancestors()
+            .find(|ancestor| ancestor.symlink_metadata().is_ok())
+        {
+            let resolved = existing.canonicalize().map_err(|e| {
+                SwissArmyHammerError::Other(format!(
+                    "Failed to resolve path '{}': {e}",
+                    path.display()
+                ))
+            })?;
+            if !resolved.starts_with(&canonical_root) {
+                return Err(SwissArmyHammerError::Other(format!(
+                    "Path '{}' resolves outside allowed directory through a symlink",
+                    path.display()
+                )));
+            }
+        }
+    }
+
+    Ok(full_path)
+}
+
+/// Express a path relative to a root directory
+///
+/// Relative paths that do not start with the root are taken to be relative to it.
+fn relative_to_root(path: &Path, root: &Path) -> Result<PathBuf> {
+    if let Ok(relative) = path.strip_prefix(root) {
+        return Ok(relative.to_path_buf());
+    }
+
+    if !path.is_absolute() {
+        return Ok(path.to_path_buf());
+    }
+
+    // The root may be relative to the current directory or reached through a symlink
+    let absolute_roots = [
+        std::env::current_dir().ok().map(|cwd| cwd.join(root)),
+        root.canonicalize().ok(),
+    ];
+    absolute_roots
+        .iter()
+        .flatten()
+        .find_map(|absolute_root| path.strip_prefix(absolute_root).ok())
+        .map(Path::to_path_buf)
+        .ok_or_else(|| {
+            SwissArmyHammerError::Other(format!(
+                "Path '{}' is outside allowed directory",
+                path.display()
+            ))
+        })
+}
+
 /// Checks if a path is safe to access within a given root directory
 ///
 /// This function validates that:
@@ -211,10 +391,11 @@ pub fn validate_template_security(template_content: &str, is_trusted: bool) -> R
 
     // Check for dangerous patterns that could indicate code injection attempts
     let dangerous_patterns = [
-        "include",  // File inclusion
-        "capture",  // Variable capture (potential data exfiltration)
-        "tablerow", // Complex loops that could cause DoS
-        "cycle",    // Another potential DoS vector
+        "include",    // File inclusion, including include_file
+        "glob_files", // Inclusion of every file matching a pattern
+        "capture",    // Variable capture (potential data exfiltration)
+        "tablerow",   // Complex loops that could cause DoS
+        "cycle",      // Another potential DoS vector
     ];
 
     for pattern in &dangerous_patterns {
@@ -225,6 +406,16 @@ pub fn validate_template_security(template_content: &str, is_trusted: bool) -> R
         }
     }
 
+    // Diffs of project files are as sensitive as the files themselves
+    if regex::Regex::new(r"\|\s*git_diff\b")
+        .unwrap()
+        .is_match(template_content)
+    {
+        return Err(SwissArmyHammerError::Other(
+            "Template contains potentially dangerous pattern: git_diff".to_string(),
+        ));
+    }
+
     // Check for excessive nesting that could cause stack overflow
     let max_nesting = check_template_nesting_depth(template_content);
     if max_nesting > MAX_TEMPLATE_RECURSION_DEPTH {
@@ -356,6 +547,83 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_validate_path_accepts_paths_inside_root() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("category")).unwrap();
+        fs::write(root.join("category/prompt.md"), "test").unwrap();
+        let policy = PathPolicy::default();
+
+        let joined = root.join("category/prompt.md");
+        assert_eq!(validate_path(&joined, root, &policy).unwrap(), joined);
+        assert_eq!(
+            validate_path(Path::new("category/prompt.md"), root, &policy).unwrap(),
+            joined
+        );
+        // Files that don't exist yet are checked through their parent
+        assert!(validate_path(&root.join("category/new.md"), root, &policy).is_ok());
+    }
+
+    #[test]
+    fn test_validate_path_rejects_traversal() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let other_dir = TempDir::new().unwrap();
+        let policy = PathPolicy::default();
+
+        let result = validate_path(Path::new("../outside.md"), root, &policy);
+        assert!(result.unwrap_err().to_string().contains("parent directory"));
+
+        let result = validate_path(&other_dir.path().join("outside.md"), root, &policy);
+        assert!(result.unwrap_err().to_string().contains("outside allowed"));
+    }
+
+    #[test]
+    fn test_validate_path_enforces_depth_and_denylist() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let policy = PathPolicy {
+            max_depth: 1,
+            denylist: vec!["private".to_string(), "**/*.secret.md".to_string()],
+            ..Default::default()
+        };
+
+        assert!(validate_path(Path::new("a/prompt.md"), root, &policy).is_ok());
+        let result = validate_path(Path::new("a/b/prompt.md"), root, &policy);
+        assert!(result.unwrap_err().to_string().contains("directories deep"));
+
+        let result = validate_path(Path::new("private/prompt.md"), root, &policy);
+        assert!(result.unwrap_err().to_string().contains("denylist"));
+        assert!(validate_path(Path::new("a/keys.secret.md"), root, &policy).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_validate_path_symlink_policy() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let outside = TempDir::new().unwrap();
+        fs::write(outside.path().join("secret.md"), "secret").unwrap();
+        fs::write(root.join("inside.md"), "inside").unwrap();
+        std::os::unix::fs::symlink(outside.path().join("secret.md"), root.join("escape.md"))
+            .unwrap();
+        std::os::unix::fs::symlink(outside.path(), root.join("linked")).unwrap();
+        std::os::unix::fs::symlink(root.join("inside.md"), root.join("alias.md")).unwrap();
+
+        let policy = PathPolicy::default();
+        let result = validate_path(&root.join("escape.md"), root, &policy);
+        assert!(result.unwrap_err().to_string().contains("symlink"));
+        assert!(validate_path(&root.join("linked/new.md"), root, &policy).is_err());
+        assert!(validate_path(&root.join("alias.md"), root, &policy).is_ok());
+
+        let permissive = PathPolicy {
+            allow_symlinks_outside_root: true,
+            ..Default::default()
+        };
+        assert!(validate_path(&root.join("escape.md"), root, &permissive).is_ok());
+    }
+
     #[test]
     fn test_calculate_path_depth() {
         let temp_dir = TempDir::new().unwrap();
@@ -410,6 +678,9 @@ mod tests {
     fn test_validate_template_security_dangerous_patterns() {
         let dangerous_templates = [
             "{% include 'dangerous.liquid' %}",
+            "{% include_file \".env\" %}",
+            "{% glob_files \"**/*\" %}",
+            "{{ \".env\" | git_diff }}",
             "{% capture secret %}{{ sensitive_data }}{% endcapture %}",
             "{% tablerow item in items %}{{ item }}{% endtablerow %}",
             "{% cycle 'red', 'blue' %}",
@@ -1,16 +1,29 @@
 //! Storage abstractions and implementations
 
 use crate::fs_utils::FileSystemUtils;
-use crate::{Prompt, Result, SwissArmyHammerError};
+use crate::{Prompt, PromptLoader, Result, SwissArmyHammerError};
+use lru::LruCache;
 use std::collections::HashMap;
-use std::path::Path;
-use std::sync::Arc;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// Prompt templates a [`LazyPromptStorage`] keeps in memory by default
+pub const DEFAULT_BODY_CACHE_CAPACITY: usize = 256;
 
 /// Trait for prompt storage backends
 pub trait StorageBackend: Send + Sync {
     /// Store a prompt
     fn store(&mut self, prompt: Prompt) -> Result<()>;
 
+    /// Store a prompt loaded from a file
+    ///
+    /// Backends may drop the template and reload it from `path` when the
+    /// prompt is requested. By default the prompt is stored as is.
+    fn store_from_file(&mut self, prompt: Prompt, _path: &Path) -> Result<()> {
+        self.store(prompt)
+    }
+
     /// Get a prompt by name
     fn get(&self, name: &str) -> Result<Prompt>;
 
@@ -36,6 +49,12 @@ pub trait StorageBackend: Send + Sync {
         self.list().map(|prompts| prompts.len())
     }
 
+    /// Whether prompts stored with [`Self::store_from_file`] keep only their
+    /// metadata, so loaders need not read their templates
+    fn loads_templates_lazily(&self) -> bool {
+        false
+    }
+
     /// Clone the storage backend in a box
     fn clone_box(&self) -> Box<dyn StorageBackend>;
 }
@@ -89,23 +108,7 @@ impl StorageBackend for MemoryStorage {
         Ok(self
             .prompts
             .values()
-            .filter(|prompt| {
-                prompt.name.to_lowercase().contains(&query_lower)
-                    || prompt
-                        .description
-                        .as_ref()
-                        .map(|d| d.to_lowercase().contains(&query_lower))
-                        .unwrap_or(false)
-                    || prompt
-                        .tags
-                        .iter()
-                        .any(|tag| tag.to_lowercase().contains(&query_lower))
-                    || prompt
-                        .category
-                        .as_ref()
-                        .map(|c| c.to_lowercase().contains(&query_lower))
-                        .unwrap_or(false)
-            })
+            .filter(|prompt| matches_query(prompt, &query_lower))
             .cloned()
             .collect())
     }
@@ -117,6 +120,166 @@ impl StorageBackend for MemoryStorage {
     }
 }
 
+/// Whether a prompt's name, description, tags or category contain a
+/// lowercase query
+fn matches_query(prompt: &Prompt, query_lower: &str) -> bool {
+    prompt.name.to_lowercase().contains(query_lower)
+        || prompt
+            .description
+            .as_ref()
+            .map(|d| d.to_lowercase().contains(query_lower))
+            .unwrap_or(false)
+        || prompt
+            .tags
+            .iter()
+            .any(|tag| tag.to_lowercase().contains(query_lower))
+        || prompt
+            .category
+            .as_ref()
+            .map(|c| c.to_lowercase().contains(query_lower))
+            .unwrap_or(false)
+}
+
+/// In-memory storage that loads the templates of file backed prompts lazily
+///
+/// Prompts stored with [`StorageBackend::store_from_file`] keep only their
+/// metadata in memory. Their templates are read from disk the first time the
+/// prompt is requested with [`StorageBackend::get`] and kept in a least
+/// recently used cache shared by all clones of the storage, so memory stays
+/// bounded however many large prompts the library holds. Listing and
+/// searching only look at metadata and return file backed prompts with an
+/// empty template. Prompts stored with [`StorageBackend::store`] keep their
+/// template in memory.
+pub struct LazyPromptStorage {
+    prompts: HashMap<String, Prompt>,
+    paths: HashMap<String, PathBuf>,
+    bodies: Arc<Mutex<LruCache<PathBuf, String>>>,
+}
+
+impl LazyPromptStorage {
+    /// Create a lazy storage keeping at most `capacity` loaded templates
+    pub fn new(capacity: usize) -> Self {
+        let capacity = NonZeroUsize::new(capacity)
+            .unwrap_or(NonZeroUsize::new(DEFAULT_BODY_CACHE_CAPACITY).unwrap());
+        Self {
+            prompts: HashMap::new(),
+            paths: HashMap::new(),
+            bodies: Arc::new(Mutex::new(LruCache::new(capacity))),
+        }
+    }
+
+    /// Number of loaded templates currently cached
+    pub fn cached_bodies(&self) -> usize {
+        self.bodies.lock().unwrap().len()
+    }
+
+    /// Fill in the template of a prompt whose template was dropped
+    fn with_template(&self, mut prompt: Prompt) -> Result<Prompt> {
+        if let Some(path) = self.paths.get(&prompt.name) {
+            prompt.template = self.load_body(&prompt.name, path)?;
+        }
+        Ok(prompt)
+    }
+
+    fn load_body(&self, name: &str, path: &Path) -> Result<String> {
+        if let Some(body) = self.bodies.lock().unwrap().get(path) {
+            return Ok(body.clone());
+        }
+
+        let body = PromptLoader::new().load_template(path).map_err(|e| {
+            SwissArmyHammerError::Storage(format!(
+                "Failed to load template of prompt '{name}' from {}: {e}",
+                path.display()
+            ))
+        })?;
+        self.bodies
+            .lock()
+            .unwrap()
+            .put(path.to_path_buf(), body.clone());
+        Ok(body)
+    }
+
+    /// Forget a prompt's file and any template cached from it
+    fn forget_path(&mut self, name: &str) {
+        if let Some(path) = self.paths.remove(name) {
+            self.bodies.lock().unwrap().pop(&path);
+        }
+    }
+}
+
+impl Default for LazyPromptStorage {
+    fn default() -> Self {
+        Self::new(DEFAULT_BODY_CACHE_CAPACITY)
+    }
+}
+
+impl StorageBackend for LazyPromptStorage {
+    fn store(&mut self, prompt: Prompt) -> Result<()> {
+        self.forget_path(&prompt.name);
+        self.prompts.insert(prompt.name.clone(), prompt);
+        Ok(())
+    }
+
+    fn store_from_file(&mut self, mut prompt: Prompt, path: &Path) -> Result<()> {
+        self.forget_path(&prompt.name);
+        prompt.template = String::new();
+        self.paths.insert(prompt.name.clone(), path.to_path_buf());
+        self.prompts.insert(prompt.name.clone(), prompt);
+        Ok(())
+    }
+
+    fn get(&self, name: &str) -> Result<Prompt> {
+        let prompt = self
+            .prompts
+            .get(name)
+            .cloned()
+            .ok_or_else(|| SwissArmyHammerError::PromptNotFound(name.to_string()))?;
+        self.with_template(prompt)
+    }
+
+    fn list(&self) -> Result<Vec<Prompt>> {
+        Ok(self.prompts.values().cloned().collect())
+    }
+
+    fn remove(&mut self, name: &str) -> Result<()> {
+        self.prompts
+            .remove(name)
+            .ok_or_else(|| SwissArmyHammerError::PromptNotFound(name.to_string()))?;
+        self.forget_path(name);
+        Ok(())
+    }
+
+    fn search(&self, query: &str) -> Result<Vec<Prompt>> {
+        let query_lower = query.to_lowercase();
+        Ok(self
+            .prompts
+            .values()
+            .filter(|prompt| matches_query(prompt, &query_lower))
+            .cloned()
+            .collect())
+    }
+
+    fn exists(&self, name: &str) -> Result<bool> {
+        Ok(self.prompts.contains_key(name))
+    }
+
+    fn count(&self) -> Result<usize> {
+        Ok(self.prompts.len())
+    }
+
+    fn loads_templates_lazily(&self) -> bool {
+        true
+    }
+
+    fn clone_box(&self) -> Box<dyn StorageBackend> {
+        Box::new(LazyPromptStorage {
+            prompts: self.prompts.clone(),
+            paths: self.paths.clone(),
+            bodies: Arc::clone(&self.bodies),
+        })
+    }
+}
+
 /// File system storage implementation
 pub struct FileSystemStorage {
     base_path: std::path::PathBuf,
@@ -244,24 +407,7 @@ impl StorageBackend for FileSystemStorage {
         Ok(self
             .cache
             .iter()
-            .filter(|entry| {
-                let prompt = entry.value();
-                prompt.name.to_lowercase().contains(&query_lower)
-                    || prompt
-                        .description
-                        .as_ref()
-                        .map(|d| d.to_lowercase().contains(&query_lower))
-                        .unwrap_or(false)
-                    || prompt
-                        .tags
-                        .iter()
-                        .any(|tag| tag.to_lowercase().contains(&query_lower))
-                    || prompt
-                        .category
-                        .as_ref()
-                        .map(|c| c.to_lowercase().contains(&query_lower))
-                        .unwrap_or(false)
-            })
+            .filter(|entry| matches_query(entry.value(), &query_lower))
             .map(|entry| entry.value().clone())
             .collect())
     }
@@ -722,6 +868,70 @@ mod tests {
         assert!(storage.exists("test").unwrap());
     }
 
+    #[test]
+    fn test_lazy_storage_loads_templates_on_demand() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut storage = LazyPromptStorage::new(1);
+        for name in ["first", "second"] {
+            let path = temp_dir.path().join(format!("{name}.md"));
+            std::fs::write(
+                &path,
+                format!("---\ndescription: The {name} prompt\n---\nHello from {name}"),
+            )
+            .unwrap();
+            let prompt = PromptLoader::new().load_file(&path).unwrap();
+            storage.store_from_file(prompt, &path).unwrap();
+        }
+
+        assert_eq!(storage.count().unwrap(), 2);
+        assert_eq!(storage.cached_bodies(), 0);
+        assert_eq!(storage.get("first").unwrap().template, "Hello from first");
+        assert_eq!(storage.cached_bodies(), 1);
+
+        // Loading a second template evicts the first from the cache
+        let cloned = storage.clone_box();
+        assert_eq!(cloned.get("second").unwrap().template, "Hello from second");
+        assert_eq!(storage.cached_bodies(), 1);
+
+        // Listing and searching leave templates on disk
+        let results = storage.search("second prompt").unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].description.as_deref(), Some("The second prompt"));
+        assert!(results[0].template.is_empty());
+        assert!(storage
+            .list()
+            .unwrap()
+            .iter()
+            .all(|prompt| prompt.template.is_empty()));
+        std::fs::remove_file(temp_dir.path().join("first.md")).unwrap();
+        assert_eq!(storage.list().unwrap().len(), 2);
+        assert_eq!(storage.cached_bodies(), 1);
+
+        // Storing a prompt in memory replaces the file backed one
+        storage
+            .store(Prompt::new("first", "Hello from memory"))
+            .unwrap();
+        assert_eq!(storage.get("first").unwrap().template, "Hello from memory");
+    }
+
+    #[test]
+    fn test_lazy_storage_missing_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("gone.md");
+        let mut storage = LazyPromptStorage::default();
+        storage
+            .store_from_file(Prompt::new("gone", "Template"), &path)
+            .unwrap();
+
+        assert!(storage.exists("gone").unwrap());
+        assert!(matches!(
+            storage.get("gone").unwrap_err(),
+            SwissArmyHammerError::Storage(_)
+        ));
+        storage.remove("gone").unwrap();
+        assert!(!storage.exists("gone").unwrap());
+    }
+
     #[test]
     fn test_prompt_storage_memory() {
         let mut storage = PromptStorage::memory();
@@ -8,6 +8,10 @@
 //! - **Liquid Template Engine**: Full support for Liquid syntax with variables, conditionals, loops
 //! - **Custom Filters**: Extensible filter system for domain-specific transformations
 //! - **Partial Templates**: Support for template composition with the `{% partial %}` tag
+//! - **Layouts**: Prompts can fill the named `{% block %}`s of a shared layout prompt
+//! - **Prompt Calls**: `{% prompt %}` renders another prompt of the library inline
+//! - **File Inclusion**: `{% include_file %}` and `{% glob_files %}` embed project files
+//! - **Git Context**: the `git` object and `git_diff` filter describe the working tree
 //! - **Plugin Integration**: Seamless integration with the plugin system for custom functionality
 //!
 //! ## Basic Usage
@@ -55,24 +59,93 @@
 //! # Ok::<(), Box<dyn std::error::Error>>(())
 //! ```
 //!
+//! ## Strict Mode
+//!
+//! By default a variable with no value renders as an empty string. Strict
+//! templates instead fail to render when an output, loop or comparison reaches
+//! an undefined variable, and report the line and column of the offending
+//! expression. Presence checks such as `{% if name %}` and values guarded by a
+//! `default` filter keep working. Strict mode is enabled per prompt with
+//! `strict: true` in the front matter, or globally with
+//! `SWISSARMYHAMMER_STRICT_TEMPLATES=true`.
+//!
+//! ```rust
+//! use swissarmyhammer::template::Template;
+//! use std::collections::HashMap;
+//!
+//! let template = Template::new("Hello {{ name }}!")?.with_strict(true);
+//! let error = template.render(&HashMap::new()).unwrap_err();
+//! assert!(error.to_string().contains("line 1, column 10"));
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! ## Layouts
+//!
+//! Prompts that share a preamble and epilogue can declare `layout: base-review`
+//! in their front matter instead of repeating them. The layout is an ordinary
+//! prompt, resolved through the prompt sources like a partial, that marks the
+//! parts prompts may replace with named blocks:
+//!
+//! ```text
+//! You are reviewing {{ language }} code.
+//! {% block checks %}Look for bugs.{% endblock %}
+//! Reply with a list of findings.
+//! ```
+//!
+//! A prompt using the layout defines the blocks it replaces; anything outside
+//! its blocks is ignored, and blocks it leaves out keep the layout's content.
+//! Layouts can declare a layout of their own. Blocks do not nest.
+//!
+//! ```text
+//! {% block checks %}Look for injection and unchecked input.{% endblock %}
+//! ```
+//!
+//! ## Calling Prompts
+//!
+//! Where a partial pastes in another prompt's template, which then sees the
+//! caller's variables, `{% prompt %}` renders another prompt on its own, with
+//! the arguments the tag passes and the called prompt's defaults for the rest.
+//! The prompt is looked up in the same library, so a user or local prompt
+//! overriding a builtin one is the one called. No model is involved: the
+//! called prompt's rendered text is inserted where the tag is.
+//!
+//! ```text
+//! {% prompt "summarize" text: diff, audience: "reviewers" %}
+//! ```
+//!
+//! Called prompts can call prompts themselves, at most ten levels deep, and a
+//! prompt that would end up calling itself fails to render.
+//!
 //! ## Custom Filters
 //!
-//! The template engine supports custom filters through the plugin system:
+//! The template engine supports custom filters through the plugin system.
+//! Filters from WASM plugins in `.swissarmyhammer/plugins/` are available in
+//! every template; compiled-in plugins are registered with a
+//! [`PluginRegistry`]:
 //!
 //! ```rust
-//! use swissarmyhammer::{template::Template, plugins::PluginRegistry};
+//! use swissarmyhammer::{template::TemplateEngine, plugins::PluginRegistry};
 //!
-//! // Register custom filters with the plugin registry
 //! let mut registry = PluginRegistry::new();
-//! // registry.register_filter("capitalize", my_capitalize_filter);
+//! // registry.register_plugin(Box::new(MyPlugin))?;
 //!
-//! // Use in templates: {{ text | capitalize }}
+//! // Use in templates: {{ text | my_filter }}
+//! let engine = TemplateEngine::with_plugins(registry);
 //! # Ok::<(), Box<dyn std::error::Error>>(())
 //! ```
 
-use crate::{plugins::PluginRegistry, security, PromptLibrary, Result, SwissArmyHammerError};
+use crate::config::Config;
+use crate::template_files::{project_root, GlobFilesTag, IncludeFileTag};
+use crate::template_git::{git_context_value, GitDiffFilterParser, GIT_VARIABLE};
+use crate::{
+    plugins::PluginRegistry, security, Prompt, PromptLibrary, Result, SwissArmyHammerError,
+};
 use liquid::{Object, Parser};
-use liquid_core::{Language, ParseTag, Renderable, Runtime, TagReflection, TagTokenIter};
+use liquid_core::error::ResultLiquidReplaceExt;
+use liquid_core::{
+    BlockReflection, Expression, Language, ParseBlock, ParseTag, Renderable, Runtime, TagBlock,
+    TagReflection, TagTokenIter, ValueView,
+};
 use std::borrow::Cow;
 use std::collections::HashMap;
 use std::io::Write;
@@ -132,6 +205,316 @@ impl Renderable for PartialRenderable {
     }
 }
 
+/// Block tag naming a part of a layout that prompts can replace
+///
+/// Layouts are merged by [`apply_layout`] before parsing, so the tag itself
+/// only renders its content. This also lets a layout render on its own.
+#[derive(Clone, Debug, Default)]
+struct BlockTag;
+
+impl BlockReflection for BlockTag {
+    fn start_tag(&self) -> &str {
+        "block"
+    }
+
+    fn end_tag(&self) -> &str {
+        "endblock"
+    }
+
+    fn description(&self) -> &str {
+        "Names a part of a layout that prompts can replace"
+    }
+}
+
+impl ParseBlock for BlockTag {
+    fn parse(
+        &self,
+        mut arguments: TagTokenIter<'_>,
+        mut tokens: TagBlock<'_, '_>,
+        options: &Language,
+    ) -> liquid_core::Result<Box<dyn Renderable>> {
+        arguments
+            .expect_next("Block name expected")?
+            .expect_identifier()
+            .into_result()?;
+        arguments.expect_nothing()?;
+
+        let content = liquid_core::Template::new(tokens.parse_all(options)?);
+        tokens.assert_empty();
+        Ok(Box::new(BlockRenderable { content }))
+    }
+
+    fn reflection(&self) -> &dyn BlockReflection {
+        self
+    }
+}
+
+/// Renderable for the block tag, which renders its content
+#[derive(Debug)]
+struct BlockRenderable {
+    content: liquid_core::Template,
+}
+
+impl Renderable for BlockRenderable {
+    fn render_to(&self, output: &mut dyn Write, runtime: &dyn Runtime) -> liquid_core::Result<()> {
+        self.content.render_to(output, runtime)
+    }
+}
+
+/// Tag rendering another prompt from the library in place
+///
+/// `{% prompt "summarize" text: notes, length: "short" %}` renders the named
+/// prompt with the given arguments, and the prompt's defaults for the rest,
+/// as if it were rendered on its own. Prompts can call prompts in turn, up to
+/// [`security::MAX_TEMPLATE_RECURSION_DEPTH`] levels; a prompt that ends up
+/// calling itself is an error.
+#[derive(Clone)]
+struct PromptTag {
+    library: Arc<PromptLibrary>,
+    /// Prompts being rendered, outermost first
+    chain: Vec<String>,
+}
+
+impl std::fmt::Debug for PromptTag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PromptTag")
+            .field("library", &"<PromptLibrary>")
+            .field("chain", &self.chain)
+            .finish()
+    }
+}
+
+impl TagReflection for PromptTag {
+    fn tag(&self) -> &'static str {
+        "prompt"
+    }
+
+    fn description(&self) -> &'static str {
+        "Renders another prompt from the library with the given arguments"
+    }
+}
+
+impl ParseTag for PromptTag {
+    fn parse(
+        &self,
+        mut arguments: TagTokenIter<'_>,
+        _options: &Language,
+    ) -> liquid_core::Result<Box<dyn Renderable>> {
+        let name = arguments
+            .expect_next("Prompt name expected")?
+            .expect_value()
+            .into_result()?;
+
+        // Arguments are `key: value` pairs, optionally separated by commas
+        let mut args = Vec::new();
+        while let Some(mut token) = arguments.next() {
+            if token.as_str() == "," {
+                token = arguments.expect_next("Argument name expected")?;
+            }
+            let key = token.expect_identifier().into_result()?.to_string();
+            arguments
+                .expect_next("\":\" expected")?
+                .expect_str(":")
+                .into_result_custom_msg("\":\" expected")?;
+            let value = arguments
+                .expect_next("Argument value expected")?
+                .expect_value()
+                .into_result()?;
+            args.push((key, value));
+        }
+
+        Ok(Box::new(PromptCall {
+            library: self.library.clone(),
+            chain: self.chain.clone(),
+            name,
+            args,
+        }))
+    }
+
+    fn reflection(&self) -> &dyn TagReflection {
+        self
+    }
+}
+
+/// Renderable for the prompt tag, which renders the called prompt
+struct PromptCall {
+    library: Arc<PromptLibrary>,
+    chain: Vec<String>,
+    name: Expression,
+    args: Vec<(String, Expression)>,
+}
+
+impl std::fmt::Debug for PromptCall {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PromptCall")
+            .field("chain", &self.chain)
+            .field("name", &self.name)
+            .field("args", &self.args)
+            .finish()
+    }
+}
+
+impl Renderable for PromptCall {
+    fn render_to(&self, output: &mut dyn Write, runtime: &dyn Runtime) -> liquid_core::Result<()> {
+        let fail = |message: String| liquid_core::Error::with_msg(message).context("tag", "prompt");
+        let name = self.name.evaluate(runtime)?.to_kstr().into_string();
+        let prompt = find_prompt(&self.library, &name)
+            .ok_or_else(|| fail(format!("Prompt '{name}' not found")))?;
+
+        let mut chain = self.chain.clone();
+        let cycle = chain.contains(&prompt.name);
+        chain.push(prompt.name.clone());
+        if cycle {
+            return Err(fail(format!("Prompt cycle: {}", chain.join(" -> "))));
+        }
+        if chain.len() > security::MAX_TEMPLATE_RECURSION_DEPTH {
+            return Err(fail(format!(
+                "Prompts nest deeper than {} levels: {}",
+                security::MAX_TEMPLATE_RECURSION_DEPTH,
+                chain.join(" -> ")
+            )));
+        }
+
+        // Arguments without a value are left out so the prompt's defaults apply
+        let mut args = HashMap::new();
+        for (key, value) in &self.args {
+            let value = value.evaluate(runtime)?;
+            if !value.is_nil() {
+                args.insert(key.clone(), value.to_kstr().into_string());
+            }
+        }
+
+        let content = prompt
+            .render_in_chain(&args, self.library.clone(), chain)
+            .map_err(|e| fail(e.to_string()))?;
+        write!(output, "{content}").replace("Failed to render")?;
+        Ok(())
+    }
+}
+
+/// Look up a prompt by name, with or without a prompt file extension
+fn find_prompt(library: &PromptLibrary, name: &str) -> Option<Prompt> {
+    std::iter::once(name.to_string())
+        .chain(
+            [".md", ".markdown", ".liquid", ".md.liquid"]
+                .iter()
+                .map(|ext| format!("{name}{ext}")),
+        )
+        .find_map(|candidate| library.get(&candidate).ok())
+}
+
+/// Merge a template's blocks into a layout from the prompt library
+///
+/// Each block in the layout is replaced by the template's block of the same
+/// name, if it has one. When the layout declares a layout of its own, the
+/// result is merged into that one in turn.
+pub fn apply_layout(template_str: &str, layout: &str, library: &PromptLibrary) -> Result<String> {
+    let mut merged = template_str.to_string();
+    let mut chain: Vec<String> = Vec::new();
+    let mut next = Some(layout.to_string());
+
+    while let Some(name) = next {
+        if chain.contains(&name) {
+            chain.push(name);
+            return Err(SwissArmyHammerError::Template(format!(
+                "Layout cycle: {}",
+                chain.join(" -> ")
+            )));
+        }
+        let base = library
+            .get(&name)
+            .map_err(|_| SwissArmyHammerError::Template(format!("Layout '{name}' not found")))?;
+        merged = fill_blocks(&base.template, &merged);
+        next = base.layout().map(str::to_string);
+        chain.push(name);
+    }
+    Ok(merged)
+}
+
+/// Replace the blocks of a layout with the blocks a template defines
+fn fill_blocks(layout: &str, template: &str) -> String {
+    thread_local! {
+        static BLOCK_RE: regex::Regex = regex::Regex::new(
+            r"(?s)\{%-?\s*block\s+(\w+)\s*-?%\}(.*?)\{%-?\s*endblock\s*-?%\}"
+        )
+        .expect("Failed to compile block regex");
+    }
+
+    BLOCK_RE.with(|re| {
+        let blocks: HashMap<&str, &str> = re
+            .captures_iter(template)
+            .filter_map(|cap| Some((cap.get(1)?.as_str(), cap.get(2)?.as_str())))
+            .collect();
+        re.replace_all(layout, |cap: &regex::Captures| match blocks.get(&cap[1]) {
+            Some(content) => format!("{{% block {} %}}{content}{{% endblock %}}", &cap[1]),
+            None => cap[0].to_string(),
+        })
+        .into_owned()
+    })
+}
+
+/// Move the line break ending the line before each `{% comment %}` block that
+/// stands on lines of its own into the comment, so the block renders no line
+///
+/// The line break stays in the source rather than being removed so that
+/// errors still point at the lines of the prompt file.
+fn detach_comment_lines(template: &str) -> String {
+    thread_local! {
+        static COMMENT_RE: regex::Regex = regex::Regex::new(
+            r"(?s)\n([ \t]*)(\{%-?\s*comment\s*-?%\})(.*?\{%-?\s*endcomment\s*-?%\})[ \t]*"
+        )
+        .expect("Failed to compile comment regex");
+    }
+
+    COMMENT_RE.with(|re| {
+        let mut detached = String::with_capacity(template.len());
+        let mut copied = 0;
+        let mut from = 0;
+        while let Some(cap) = re.captures(&template[from..]) {
+            let (Some(block), Some(indent), Some(open), Some(rest)) =
+                (cap.get(0), cap.get(1), cap.get(2), cap.get(3))
+            else {
+                break;
+            };
+            let start = from + block.start();
+            let end = from + block.end();
+            if !matches!(template[end..].chars().next(), None | Some('\n')) {
+                // Text follows the comment on its line, so the line stays
+                from = start + 1;
+                continue;
+            }
+            detached.push_str(&template[copied..start]);
+            detached.push_str(open.as_str());
+            detached.push('\n');
+            detached.push_str(indent.as_str());
+            detached.push_str(rest.as_str());
+            copied = end;
+            from = end;
+        }
+        detached.push_str(&template[copied..]);
+        detached
+    })
+}
+
+/// Trim trailing whitespace from every line, collapse runs of blank lines to
+/// one, and drop blank lines at the start and end
+fn minify_output(output: &str) -> String {
+    let mut lines: Vec<&str> = Vec::new();
+    let mut blank = false;
+    for line in output.lines().map(str::trim_end) {
+        if line.is_empty() {
+            blank = !lines.is_empty();
+            continue;
+        }
+        if blank {
+            lines.push("");
+            blank = false;
+        }
+        lines.push(line);
+    }
+    lines.join("\n")
+}
+
 /// Custom partial source that loads partials from the prompt library
 pub struct PromptPartialSource {
     library: Arc<PromptLibrary>,
@@ -294,6 +677,199 @@ fn extract_template_variables(template: &str) -> Vec<String> {
     EXTRACTOR.with(|extractor| extractor.extract(template))
 }
 
+/// Extract the variables a template guards with a `default` filter
+fn extract_defaulted_variables(template: &str) -> Vec<String> {
+    thread_local! {
+        static DEFAULTED_RE: regex::Regex =
+            regex::Regex::new(r"\{\{-?\s*(\w+)(?:\.\w+)*\s*\|[^\}]*?\bdefault\b")
+                .expect("Failed to compile default filter regex");
+    }
+
+    DEFAULTED_RE.with(|re| {
+        let mut variables: Vec<String> = re
+            .captures_iter(template)
+            .map(|cap| cap[1].to_string())
+            .collect();
+        variables.sort();
+        variables.dedup();
+        variables
+    })
+}
+
+/// Convert a byte offset in a template to a 1-based line and column
+pub(crate) fn line_column(template: &str, offset: usize) -> (usize, usize) {
+    let before = &template[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+    let column = before[line_start..].chars().count() + 1;
+    (line, column)
+}
+
+/// Find the byte offset of a variable reference inside a Liquid tag or output
+///
+/// Outputs are searched before tags, because presence checks in `{% if %}` tags
+/// never fail on an undefined variable.
+fn find_variable_reference(template: &str, name: &str) -> Option<usize> {
+    let name_re = regex::Regex::new(&format!(r"(^|[^\w.]){}\b", regex::escape(name))).ok()?;
+    ["{{", "{%"].into_iter().find_map(|open| {
+        let close = if open == "{{" { "}}" } else { "%}" };
+        let mut search_from = 0;
+        while let Some(start) = template[search_from..].find(open) {
+            let start = search_from + start;
+            let end = template[start..]
+                .find(close)
+                .map_or(template.len(), |end| start + end);
+            let block = &template[start + open.len()..end];
+            if let Some(cap) = name_re.captures(block) {
+                let prefix = cap.get(1).map_or(0, |m| m.len());
+                return Some(start + open.len() + cap.get(0)?.start() + prefix);
+            }
+            search_from = end;
+        }
+        None
+    })
+}
+
+/// Rewrite a Liquid error to point at the line and column it came from
+///
+/// Undefined variable and unknown filter errors from Liquid name the culprit but
+/// not where it is in the template. Syntax errors carry their position in a
+/// snippet, which is reduced to the same `at line L, column C` form. Other
+/// errors are returned unchanged.
+fn locate_template_error(template: &str, error: &liquid::Error) -> SwissArmyHammerError {
+    let message = error.to_string();
+    let requested = |what: &str| {
+        regex::Regex::new(&format!(r"requested {what}=(\S+)"))
+            .ok()
+            .and_then(|re| re.captures(&message).map(|cap| cap[1].to_string()))
+    };
+
+    if let Some(location) = syntax_error_location(&message) {
+        return SwissArmyHammerError::Template(location.to_string());
+    }
+
+    if let Some(name) = requested("variable") {
+        if let Some(offset) = find_variable_reference(template, &name) {
+            let (line, column) = line_column(template, offset);
+            return SwissArmyHammerError::Template(format!(
+                "Undefined variable '{name}' at line {line}, column {column}"
+            ));
+        }
+    } else if let Some(name) = requested("filter") {
+        let filter_re = regex::Regex::new(&format!(r"\|\s*{}\b", regex::escape(&name)));
+        if let Some(found) = filter_re.ok().and_then(|re| re.find(template)) {
+            let offset = found.start() + found.as_str().len() - name.len();
+            let (line, column) = line_column(template, offset);
+            return SwissArmyHammerError::Template(format!(
+                "Unknown filter '{name}' at line {line}, column {column}"
+            ));
+        }
+    }
+
+    SwissArmyHammerError::Template(message)
+}
+
+/// The position and description of a Liquid syntax error
+///
+/// Liquid reports syntax errors as ` --> L:C`, a snippet, and `= description`,
+/// with the name of an unknown tag in a `requested=` line.
+fn syntax_error_location(message: &str) -> Option<TemplateErrorLocation> {
+    thread_local! {
+        static POSITION_RE: regex::Regex =
+            regex::Regex::new(r"-->\s*(\d+):(\d+)").expect("Failed to compile position regex");
+    }
+
+    let (line, column) = POSITION_RE.with(|re| {
+        let cap = re.captures(message)?;
+        Some((cap[1].parse().ok()?, cap[2].parse().ok()?))
+    })?;
+    let mut description = message
+        .lines()
+        .find_map(|line| line.trim_start().strip_prefix("= "))
+        .unwrap_or("Syntax error")
+        .trim_end_matches('.')
+        .to_string();
+    if let Some(name) = message
+        .lines()
+        .find_map(|line| line.trim_start().strip_prefix("requested="))
+    {
+        description = format!("{description} '{name}'");
+    }
+    Some(TemplateErrorLocation {
+        message: description,
+        line,
+        column,
+    })
+}
+
+/// Where in a template an error occurred
+///
+/// Template errors with a known position end their first line with
+/// `at line L, column C`; this splits them back into their parts so callers can
+/// map the position into the file the template came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateErrorLocation {
+    /// What went wrong
+    pub message: String,
+    /// 1-based line in the template
+    pub line: usize,
+    /// 1-based column in the template
+    pub column: usize,
+}
+
+impl TemplateErrorLocation {
+    /// The location of a template error, if its position is known
+    pub fn of(error: &SwissArmyHammerError) -> Option<Self> {
+        thread_local! {
+            static LOCATION_RE: regex::Regex =
+                regex::Regex::new(r"^(.*) at line (\d+), column (\d+)$")
+                    .expect("Failed to compile location regex");
+        }
+
+        let SwissArmyHammerError::Template(message) = error else {
+            return None;
+        };
+        let first_line = message.lines().next()?;
+        LOCATION_RE.with(|re| {
+            let cap = re.captures(first_line)?;
+            Some(Self {
+                message: cap[1].to_string(),
+                line: cap[2].parse().ok()?,
+                column: cap[3].parse().ok()?,
+            })
+        })
+    }
+}
+
+impl std::fmt::Display for TemplateErrorLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} at line {}, column {}",
+            self.message, self.line, self.column
+        )
+    }
+}
+
+/// The given 1-based line of `source` with a caret under `column`
+///
+/// ```text
+///    |
+/// 12 | Review {{ langauge }} code
+///    |           ^
+/// ```
+pub fn source_snippet(source: &str, line: usize, column: usize) -> String {
+    let text = source.lines().nth(line.saturating_sub(1)).unwrap_or("");
+    let gutter = " ".repeat(line.to_string().len());
+    // Keep tabs so the caret lines up with the text above it
+    let indent: String = text
+        .chars()
+        .take(column.saturating_sub(1))
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect();
+    format!("{gutter} |\n{line} | {text}\n{gutter} | {indent}^")
+}
+
 /// Template wrapper for Liquid templates
 ///
 /// # Security
@@ -321,9 +897,14 @@ fn extract_template_variables(template: &str) -> Vec<String> {
 ///
 /// Use `new_trusted()` for templates from trusted sources (builtin, user-created)
 /// or `new_untrusted()` for external templates with strict validation.
+///
+/// Templates start in strict rendering mode when the global configuration
+/// enables it; use [`with_strict`](Self::with_strict) to override that.
 pub struct Template {
     parser: Parser,
     template_str: String,
+    strict: bool,
+    minify: bool,
 }
 
 impl Template {
@@ -344,11 +925,13 @@ impl Template {
         // Validate the template by trying to parse it
         parser
             .parse(template_str)
-            .map_err(|e| SwissArmyHammerError::Template(e.to_string()))?;
+            .map_err(|e| locate_template_error(template_str, &e))?;
 
         Ok(Self {
             parser,
             template_str: template_str.to_string(),
+            strict: Config::global().strict_templates,
+            minify: false,
         })
     }
 
@@ -364,29 +947,110 @@ impl Template {
         // Validate the template by trying to parse it
         parser
             .parse(template_str)
-            .map_err(|e| SwissArmyHammerError::Template(e.to_string()))?;
+            .map_err(|e| locate_template_error(template_str, &e))?;
 
         Ok(Self {
             parser,
             template_str: template_str.to_string(),
+            strict: Config::global().strict_templates,
+            minify: false,
         })
     }
 
     /// Create a new template with partial support
+    ///
+    /// The template can also render other prompts of the library with the
+    /// `{% prompt %}` tag.
     pub fn with_partials(template_str: &str, library: Arc<PromptLibrary>) -> Result<Self> {
+        Self::calling_prompts(template_str, library, Vec::new())
+    }
+
+    /// Create a template with partial support for rendering the prompts in
+    /// `chain`, outermost first, so `{% prompt %}` tags can detect cycles
+    pub(crate) fn calling_prompts(
+        template_str: &str,
+        library: Arc<PromptLibrary>,
+        chain: Vec<String>,
+    ) -> Result<Self> {
         let partial_source = PromptPartialSource::new(library);
-        let parser = TemplateEngine::parser_with_partials(partial_source);
+        let parser = TemplateEngine::parser_calling_prompts(partial_source, chain);
         // Validate the template by trying to parse it
         parser
             .parse(template_str)
-            .map_err(|e| SwissArmyHammerError::Template(e.to_string()))?;
+            .map_err(|e| locate_template_error(template_str, &e))?;
 
         Ok(Self {
             parser,
             template_str: template_str.to_string(),
+            strict: Config::global().strict_templates,
+            minify: false,
         })
     }
 
+    /// Set whether undefined variables are errors instead of empty strings
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Whether undefined variables are errors instead of empty strings
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Set whether the rendered output is minified
+    ///
+    /// A minified template renders without the lines its `{% comment %}`
+    /// blocks stood on, without trailing whitespace, and with runs of blank
+    /// lines collapsed to one.
+    pub fn with_minify(mut self, minify: bool) -> Self {
+        if minify && !self.minify {
+            self.template_str = detach_comment_lines(&self.template_str);
+        }
+        self.minify = minify;
+        self
+    }
+
+    /// Whether the rendered output is minified
+    pub fn is_minified(&self) -> bool {
+        self.minify
+    }
+
+    /// The rendered output, minified if the template minifies
+    fn finish(&self, output: String) -> String {
+        if self.minify {
+            minify_output(&output)
+        } else {
+            output
+        }
+    }
+
+    /// Variables initialized as nil before rendering
+    ///
+    /// This lets filters like `| default` work on missing values. Strict
+    /// templates only initialize variables guarded by a `default` filter, so
+    /// any other undefined variable fails to render.
+    fn nil_variables(&self) -> Vec<String> {
+        if self.strict {
+            extract_defaulted_variables(&self.template_str)
+        } else {
+            extract_template_variables(&self.template_str)
+        }
+    }
+
+    /// Provide the `git` object to a template that uses it
+    fn insert_git_context(&self, object: &mut Object) {
+        if !extract_template_variables(&self.template_str)
+            .iter()
+            .any(|var| var == GIT_VARIABLE)
+        {
+            return;
+        }
+        if let Some(git) = git_context_value(&project_root()) {
+            object.insert(GIT_VARIABLE.into(), git);
+        }
+    }
+
     /// Render the template with given arguments
     pub fn render(&self, args: &HashMap<String, String>) -> Result<String> {
         // Create timeout for template rendering
@@ -403,15 +1067,15 @@ impl Template {
         let template = self
             .parser
             .parse(&self.template_str)
-            .map_err(|e| SwissArmyHammerError::Template(e.to_string()))?;
+            .map_err(|e| locate_template_error(&self.template_str, &e))?;
 
         let mut object = Object::new();
 
-        // First, initialize all template variables as nil so filters like | default work
-        let variables = extract_template_variables(&self.template_str);
-        for var in variables {
+        // First, initialize template variables as nil so filters like | default work
+        for var in self.nil_variables() {
             object.insert(var.into(), liquid::model::Value::Nil);
         }
+        self.insert_git_context(&mut object);
 
         // Then override with provided values
         for (key, value) in args {
@@ -426,7 +1090,7 @@ impl Template {
             let handle = s.spawn(|| template.render(&object));
 
             match handle.join() {
-                Ok(result) => result.map_err(|e| SwissArmyHammerError::Template(e.to_string())),
+                Ok(result) => result.map_err(|e| locate_template_error(&self.template_str, &e)),
                 Err(_) => Err(SwissArmyHammerError::Template(
                     "Template rendering panicked".to_string(),
                 )),
@@ -436,7 +1100,7 @@ impl Template {
         // Note: We can't easily implement actual timeout without async context
         // In a real implementation, you'd want to use tokio::time::timeout
         // For now, we rely on the security validation to prevent complex templates
-        render_result
+        render_result.map(|output| self.finish(output))
     }
 
     /// Render the template with given arguments and environment variables
@@ -447,15 +1111,15 @@ impl Template {
         let template = self
             .parser
             .parse(&self.template_str)
-            .map_err(|e| SwissArmyHammerError::Template(e.to_string()))?;
+            .map_err(|e| locate_template_error(&self.template_str, &e))?;
 
         let mut object = Object::new();
 
-        // First, initialize all template variables as nil so filters like | default work
-        let variables = extract_template_variables(&self.template_str);
-        for var in variables {
+        // First, initialize template variables as nil so filters like | default work
+        for var in self.nil_variables() {
             object.insert(var.into(), liquid::model::Value::Nil);
         }
+        self.insert_git_context(&mut object);
 
         // Add environment variables as template variables
         for (key, value) in std::env::vars() {
@@ -472,7 +1136,8 @@ impl Template {
 
         template
             .render(&object)
-            .map_err(|e| SwissArmyHammerError::Template(e.to_string()))
+            .map(|output| self.finish(output))
+            .map_err(|e| locate_template_error(&self.template_str, &e))
     }
 
     /// Get the raw template string
@@ -485,6 +1150,7 @@ impl Template {
 pub struct TemplateEngine {
     parser: liquid::Parser,
     plugin_registry: Option<PluginRegistry>,
+    strict: bool,
 }
 
 impl TemplateEngine {
@@ -493,6 +1159,7 @@ impl TemplateEngine {
         Self {
             parser: Self::default_parser(),
             plugin_registry: None,
+            strict: Config::global().strict_templates,
         }
     }
 
@@ -501,6 +1168,7 @@ impl TemplateEngine {
         Self {
             parser,
             plugin_registry: None,
+            strict: Config::global().strict_templates,
         }
     }
 
@@ -510,23 +1178,59 @@ impl TemplateEngine {
         Self {
             parser,
             plugin_registry: Some(plugin_registry),
+            strict: Config::global().strict_templates,
         }
     }
 
+    /// Set whether templates parsed by this engine render in strict mode
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
     /// Create a default parser
+    ///
+    /// Includes the filters of plugins in [`PluginRegistry::global`].
     pub fn default_parser() -> liquid::Parser {
-        liquid::ParserBuilder::with_stdlib()
+        PluginRegistry::global()
+            .add_filters(liquid::ParserBuilder::with_stdlib())
+            .filter(GitDiffFilterParser::new())
             .tag(PartialTag::new())
+            .tag(IncludeFileTag::new())
+            .tag(GlobFilesTag::new())
+            .block(BlockTag)
             .build()
             .expect("Failed to build Liquid parser")
     }
 
     /// Create a parser with custom partial loader
+    ///
+    /// Besides partials, templates can render the prompts of the partial
+    /// source's library with the `{% prompt %}` tag.
     pub fn parser_with_partials(partial_source: PromptPartialSource) -> liquid::Parser {
+        Self::parser_calling_prompts(partial_source, Vec::new())
+    }
+
+    /// Create a parser with custom partial loader for rendering the prompts in
+    /// `chain`, outermost first
+    fn parser_calling_prompts(
+        partial_source: PromptPartialSource,
+        chain: Vec<String>,
+    ) -> liquid::Parser {
+        let prompt_tag = PromptTag {
+            library: partial_source.library.clone(),
+            chain,
+        };
         let partial_compiler = liquid::partials::EagerCompiler::new(partial_source);
-        liquid::ParserBuilder::with_stdlib()
+        PluginRegistry::global()
+            .add_filters(liquid::ParserBuilder::with_stdlib())
+            .filter(GitDiffFilterParser::new())
             .partials(partial_compiler)
             .tag(PartialTag::new())
+            .tag(IncludeFileTag::new())
+            .tag(GlobFilesTag::new())
+            .tag(prompt_tag)
+            .block(BlockTag)
             .build()
             .expect("Failed to build Liquid parser with partials")
     }
@@ -536,11 +1240,13 @@ impl TemplateEngine {
         // Validate the template by trying to parse it
         self.parser
             .parse(template_str)
-            .map_err(|e| SwissArmyHammerError::Template(e.to_string()))?;
+            .map_err(|e| locate_template_error(template_str, &e))?;
 
         Ok(Template {
             parser: self.parser.clone(),
             template_str: template_str.to_string(),
+            strict: self.strict,
+            minify: false,
         })
     }
 
@@ -696,6 +1402,78 @@ mod tests {
         assert_eq!(result, "Hello World!");
     }
 
+    #[test]
+    fn test_strict_mode_reports_undefined_variable_location() {
+        let template = Template::new("Title\n\nReview {{ language }} code in {{ file }}")
+            .unwrap()
+            .with_strict(true);
+        let mut args = HashMap::new();
+        args.insert("language".to_string(), "Rust".to_string());
+
+        let error = template.render(&args).unwrap_err().to_string();
+        assert!(error.contains("Undefined variable 'file'"), "{error}");
+        assert!(error.contains("line 3, column 34"), "{error}");
+
+        args.insert("file".to_string(), "main.rs".to_string());
+        assert_eq!(
+            template.render(&args).unwrap(),
+            "Title\n\nReview Rust code in main.rs"
+        );
+    }
+
+    #[test]
+    fn test_strict_mode_allows_defaults_and_presence_checks() {
+        let template = Template::new(
+            "{% if context %}Context: {{ context }}{% endif %}Hi {{ name | default: 'there' }}",
+        )
+        .unwrap()
+        .with_strict(true);
+
+        assert_eq!(template.render(&HashMap::new()).unwrap(), "Hi there");
+        // Without strict mode the same missing variable silently renders empty
+        let lenient = Template::new("Hi {{ name }}").unwrap().with_strict(false);
+        assert_eq!(lenient.render(&HashMap::new()).unwrap(), "Hi ");
+    }
+
+    #[test]
+    fn test_unknown_filter_reports_location() {
+        let error = Template::new("Line one\n{{ name | shout }}")
+            .err()
+            .expect("unknown filter should fail to parse")
+            .to_string();
+        assert!(error.contains("Unknown filter 'shout'"), "{error}");
+        assert!(error.contains("line 2, column 11"), "{error}");
+    }
+
+    #[test]
+    fn test_syntax_error_reports_location() {
+        let error = Template::new("Hello\n{{ name | }}")
+            .err()
+            .expect("dangling filter should fail to parse");
+        let location = TemplateErrorLocation::of(&error).unwrap();
+        assert_eq!(location.message, "expected Identifier");
+        assert_eq!((location.line, location.column), (2, 10));
+
+        let error = Template::new("{% bogus %}").err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "Template error: Unknown tag 'bogus' at line 1, column 4"
+        );
+
+        // Errors without a position are left alone
+        let error = SwissArmyHammerError::Template("Layout 'base' not found".to_string());
+        assert_eq!(TemplateErrorLocation::of(&error), None);
+    }
+
+    #[test]
+    fn test_source_snippet() {
+        assert_eq!(
+            source_snippet("one\n\tReview {{ x }}\nthree", 2, 11),
+            "  |\n2 | \tReview {{ x }}\n  | \t         ^"
+        );
+        assert_eq!(source_snippet("a", 12, 1), "   |\n12 | \n   | ^");
+    }
+
     #[test]
     fn test_liquid_default_filter_with_provided_variable() {
         // Test that the | default filter is ignored when variable is provided
@@ -707,6 +1485,15 @@ mod tests {
         assert_eq!(result, "Hello Alice!");
     }
 
+    #[test]
+    fn test_git_argument_takes_precedence_over_git_context() {
+        let template = Template::new("On {{ git }}").unwrap();
+        let mut args = HashMap::new();
+        args.insert("git".to_string(), "feature/login".to_string());
+
+        assert_eq!(template.render(&args).unwrap(), "On feature/login");
+    }
+
     #[test]
     fn test_liquid_default_filter_multiple_variables() {
         // Test multiple variables with default filters
@@ -864,4 +1651,172 @@ mod tests {
         // Clean up
         env::remove_var("TEST_OVERRIDE");
     }
+
+    #[test]
+    fn test_minified_rendering() {
+        let source = "Intro  \n{% comment %}\nNotes for maintainers\n{% endcomment %}\n\n\n\nReview {{ language }}\n  {% comment %}Kept{% endcomment %} inline\n\n";
+        let mut args = HashMap::new();
+        args.insert("language".to_string(), "Rust".to_string());
+
+        let template = Template::new(source).unwrap();
+        assert!(!template.is_minified());
+        assert!(template.render(&args).unwrap().contains("\n\n\n\n"));
+
+        let template = Template::new(source).unwrap().with_minify(true);
+        assert_eq!(
+            template.render(&args).unwrap(),
+            "Intro\n\nReview Rust\n   inline"
+        );
+
+        // Errors still point at the line in the original source
+        let error = Template::new(source)
+            .unwrap()
+            .with_strict(true)
+            .with_minify(true)
+            .render(&HashMap::new())
+            .unwrap_err();
+        assert_eq!(TemplateErrorLocation::of(&error).unwrap().line, 8);
+    }
+
+    #[test]
+    fn test_layout_blocks() {
+        use crate::Prompt;
+
+        let with_layout = |name: &str, template: &str, layout: &str| {
+            let mut prompt = Prompt::new(name, template);
+            prompt
+                .metadata
+                .insert("layout".to_string(), serde_json::json!(layout));
+            prompt
+        };
+
+        let mut library = PromptLibrary::new();
+        library
+            .add(Prompt::new(
+                "base",
+                "Intro\n{% block checks %}Bugs{% endblock %}\n{% block outro %}Bye{% endblock %}",
+            ))
+            .unwrap();
+        library
+            .add(with_layout(
+                "base-review",
+                "{% block checks %}Review {{ language }}{% endblock %}",
+                "base",
+            ))
+            .unwrap();
+        library
+            .add(with_layout(
+                "loop-a",
+                "{% block x %}{% endblock %}",
+                "loop-b",
+            ))
+            .unwrap();
+        library
+            .add(with_layout(
+                "loop-b",
+                "{% block x %}{% endblock %}",
+                "loop-a",
+            ))
+            .unwrap();
+
+        let security = with_layout(
+            "security",
+            "ignored {%- block checks -%}Injection in {{ language }}{%- endblock -%}",
+            "base-review",
+        );
+        library.add(security.clone()).unwrap();
+        let library = Arc::new(library);
+        let args = HashMap::from([("language".to_string(), "rust".to_string())]);
+
+        // Blocks the prompt leaves out keep the content of the layout chain
+        assert_eq!(
+            security
+                .render_with_partials(&args, library.clone())
+                .unwrap(),
+            "Intro\nInjection in rust\nBye"
+        );
+        assert_eq!(
+            library.render_prompt("base-review", &args).unwrap(),
+            "Intro\nReview rust\nBye"
+        );
+        assert_eq!(
+            library.render_prompt("base", &args).unwrap(),
+            "Intro\nBugs\nBye"
+        );
+        assert!(security.render(&args).is_err());
+
+        let error = apply_layout("", "missing", &library)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("Layout 'missing' not found"));
+        let error = apply_layout("", "loop-a", &library)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("loop-a -> loop-b -> loop-a"));
+    }
+
+    #[test]
+    fn test_prompt_tag_renders_other_prompts() {
+        use crate::{ArgumentSpec, Prompt};
+
+        let mut library = PromptLibrary::new();
+        library
+            .add(
+                Prompt::new("summarize.md", "Summary of {{ text }} for {{ audience }}")
+                    .add_argument(ArgumentSpec {
+                        name: "audience".to_string(),
+                        description: None,
+                        required: false,
+                        default: Some("everyone".to_string()),
+                        type_hint: None,
+                        choices: None,
+                        completion: None,
+                        source: None,
+                    }),
+            )
+            .unwrap();
+        library
+            .add(Prompt::new(
+                "review",
+                "Review: {% prompt \"summarize\" text: notes %}",
+            ))
+            .unwrap();
+        library
+            .add(Prompt::new("ping", "{% prompt \"pong\" %}"))
+            .unwrap();
+        library
+            .add(Prompt::new("pong", "{% prompt \"ping\" %}"))
+            .unwrap();
+        let library = Arc::new(library);
+        let args = HashMap::from([("notes".to_string(), "the diff".to_string())]);
+
+        // The called prompt sees only the arguments passed, and its defaults
+        assert_eq!(
+            library.render_prompt("review", &args).unwrap(),
+            "Review: Summary of the diff for everyone"
+        );
+        let template = Template::with_partials(
+            "{% prompt \"summarize\", text: \"logs\", audience: \"ops\" %}",
+            library.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            template.render(&HashMap::new()).unwrap(),
+            "Summary of logs for ops"
+        );
+
+        let error = library
+            .render_prompt("ping", &HashMap::new())
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("Prompt cycle: ping -> pong -> ping"));
+        let error = Template::with_partials("{% prompt \"missing\" %}", library.clone())
+            .unwrap()
+            .render(&HashMap::new())
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("Prompt 'missing' not found"));
+        // Without a library there is nothing to call
+        assert!(Template::new("{% prompt \"summarize\" %}").is_err());
+    }
 }
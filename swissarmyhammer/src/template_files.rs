@@ -0,0 +1,398 @@
+//! Liquid tags embedding project files in templates
+//!
+//! `{% include_file "src/main.rs" %}` renders the content of one file, and
+//! `{% glob_files "src/**/*.rs" limit: 20 %}` renders each file matching a
+//! pattern under a heading with its path, in a fenced code block. Prompts can
+//! so declare the code they need instead of users pasting it into arguments.
+//!
+//! Paths are relative to the project root, the nearest directory at or above
+//! the current one that holds a `.git` entry, or the current directory outside
+//! a repository. Every file is checked against the global [`PathPolicy`], so a
+//! template cannot read outside the project, through a symlink or from a path
+//! on the denylist. Files longer than
+//! `SWISSARMYHAMMER_TEMPLATE_INCLUDE_MAX_BYTES` are cut short with a note, and
+//! a glob embeds at most `SWISSARMYHAMMER_TEMPLATE_GLOB_MAX_FILES` files.
+
+use crate::config::Config;
+use crate::security::{validate_path, PathPolicy};
+use liquid_core::error::ResultLiquidReplaceExt;
+use liquid_core::{
+    Expression, Language, ParseTag, Renderable, Runtime, TagReflection, TagTokenIter, ValueView,
+};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// The project root file tags resolve paths against
+///
+/// This is the nearest directory at or above the current one holding a `.git`
+/// entry, or the current directory when there is none.
+pub fn project_root() -> PathBuf {
+    let current = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    current
+        .ancestors()
+        .find(|dir| dir.join(".git").exists())
+        .map(Path::to_path_buf)
+        .unwrap_or(current)
+}
+
+/// Tag rendering the content of one project file
+///
+/// `{% include_file "src/main.rs" %}`; the path may be any expression.
+#[derive(Clone, Debug, Default)]
+pub struct IncludeFileTag {
+    root: Option<PathBuf>,
+}
+
+impl IncludeFileTag {
+    /// Resolve paths against the [`project_root`] at render time
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resolve paths against `root` instead of the project root
+    pub fn with_root(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: Some(root.into()),
+        }
+    }
+}
+
+impl TagReflection for IncludeFileTag {
+    fn tag(&self) -> &'static str {
+        "include_file"
+    }
+
+    fn description(&self) -> &'static str {
+        "Renders the content of a file relative to the project root"
+    }
+}
+
+impl ParseTag for IncludeFileTag {
+    fn parse(
+        &self,
+        mut arguments: TagTokenIter<'_>,
+        _options: &Language,
+    ) -> liquid_core::Result<Box<dyn Renderable>> {
+        let path = arguments
+            .expect_next("File path expected")?
+            .expect_value()
+            .into_result()?;
+        arguments.expect_nothing()?;
+
+        Ok(Box::new(IncludeFile {
+            root: self.root.clone(),
+            path,
+        }))
+    }
+
+    fn reflection(&self) -> &dyn TagReflection {
+        self
+    }
+}
+
+#[derive(Debug)]
+struct IncludeFile {
+    root: Option<PathBuf>,
+    path: Expression,
+}
+
+impl Renderable for IncludeFile {
+    fn render_to(&self, output: &mut dyn Write, runtime: &dyn Runtime) -> liquid_core::Result<()> {
+        let path = self.path.evaluate(runtime)?.to_kstr().into_string();
+        let root = self.root.clone().unwrap_or_else(project_root);
+        let content = read_project_file(&root, &path, Config::global().template_include_max_bytes)
+            .map_err(|e| {
+                liquid_core::Error::with_msg(e.to_string()).context("tag", "include_file")
+            })?;
+        write!(output, "{content}").replace("Failed to render")?;
+        Ok(())
+    }
+}
+
+/// Tag rendering every project file matching a glob pattern
+///
+/// `{% glob_files "src/**/*.rs" limit: 20 %}` renders each file, in path order,
+/// under a `### path` heading in a code block fenced with its extension.
+#[derive(Clone, Debug, Default)]
+pub struct GlobFilesTag {
+    root: Option<PathBuf>,
+}
+
+impl GlobFilesTag {
+    /// Resolve patterns against the [`project_root`] at render time
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resolve patterns against `root` instead of the project root
+    pub fn with_root(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: Some(root.into()),
+        }
+    }
+}
+
+impl TagReflection for GlobFilesTag {
+    fn tag(&self) -> &'static str {
+        "glob_files"
+    }
+
+    fn description(&self) -> &'static str {
+        "Renders the files matching a glob pattern relative to the project root"
+    }
+}
+
+impl ParseTag for GlobFilesTag {
+    fn parse(
+        &self,
+        mut arguments: TagTokenIter<'_>,
+        _options: &Language,
+    ) -> liquid_core::Result<Box<dyn Renderable>> {
+        let pattern = arguments
+            .expect_next("Glob pattern expected")?
+            .expect_value()
+            .into_result()?;
+
+        let mut limit = None;
+        if let Some(next) = arguments.next() {
+            let key = next.expect_identifier().into_result()?;
+            if key != "limit" {
+                return liquid_core::Error::with_msg(format!("Unknown glob_files option '{key}'"))
+                    .context("tag", "glob_files")
+                    .into_err();
+            }
+            arguments
+                .expect_next("\":\" expected")?
+                .expect_str(":")
+                .into_result_custom_msg("\":\" expected")?;
+            limit = Some(
+                arguments
+                    .expect_next("Limit expected")?
+                    .expect_value()
+                    .into_result()?,
+            );
+        }
+        arguments.expect_nothing()?;
+
+        Ok(Box::new(GlobFiles {
+            root: self.root.clone(),
+            pattern,
+            limit,
+        }))
+    }
+
+    fn reflection(&self) -> &dyn TagReflection {
+        self
+    }
+}
+
+#[derive(Debug)]
+struct GlobFiles {
+    root: Option<PathBuf>,
+    pattern: Expression,
+    limit: Option<Expression>,
+}
+
+impl GlobFiles {
+    fn limit(&self, runtime: &dyn Runtime) -> liquid_core::Result<usize> {
+        let max_files = Config::global().template_glob_max_files;
+        let Some(limit) = &self.limit else {
+            return Ok(max_files);
+        };
+        let value = limit.evaluate(runtime)?;
+        let limit = value
+            .as_scalar()
+            .and_then(|scalar| scalar.to_integer())
+            .filter(|limit| *limit > 0)
+            .ok_or_else(|| {
+                liquid_core::Error::with_msg("Limit must be a positive integer")
+                    .context("tag", "glob_files")
+                    .context("limit", value.to_kstr().into_string())
+            })?;
+        Ok((limit as usize).min(max_files))
+    }
+}
+
+impl Renderable for GlobFiles {
+    fn render_to(&self, output: &mut dyn Write, runtime: &dyn Runtime) -> liquid_core::Result<()> {
+        let pattern = self.pattern.evaluate(runtime)?.to_kstr().into_string();
+        let limit = self.limit(runtime)?;
+        let root = self.root.clone().unwrap_or_else(project_root);
+        let max_bytes = Config::global().template_include_max_bytes;
+        let to_error = |e: crate::SwissArmyHammerError| {
+            liquid_core::Error::with_msg(e.to_string())
+                .context("tag", "glob_files")
+                .context("pattern", pattern.clone())
+        };
+
+        let paths = matching_files(&root, &pattern).map_err(to_error)?;
+        let mut sections = Vec::new();
+        for path in paths.iter().take(limit) {
+            let content = read_project_file(&root, path, max_bytes).map_err(to_error)?;
+            sections.push(file_section(path, &content));
+        }
+        if paths.len() > limit {
+            sections.push(format!(
+                "({} more files matching '{pattern}' not included)\n",
+                paths.len() - limit
+            ));
+        }
+        write!(output, "{}", sections.join("\n")).replace("Failed to render")?;
+        Ok(())
+    }
+}
+
+/// Paths relative to `root` of the files matching `pattern`, sorted, leaving
+/// out files the path policy denies
+fn matching_files(root: &Path, pattern: &str) -> crate::Result<Vec<String>> {
+    let relative = Path::new(pattern);
+    if relative.is_absolute()
+        || relative
+            .components()
+            .any(|component| component == std::path::Component::ParentDir)
+    {
+        return Err(crate::SwissArmyHammerError::Other(format!(
+            "Glob pattern '{pattern}' must stay inside the project root"
+        )));
+    }
+
+    let full_pattern = root.join(pattern);
+    let entries = glob::glob(&full_pattern.to_string_lossy()).map_err(|e| {
+        crate::SwissArmyHammerError::Other(format!("Invalid glob pattern '{pattern}': {e}"))
+    })?;
+    let mut paths: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|path| path.is_file())
+        .filter_map(|path| {
+            let relative = path.strip_prefix(root).ok()?;
+            match validate_path(relative, root, PathPolicy::global()) {
+                Ok(_) => Some(relative.to_string_lossy().into_owned()),
+                Err(e) => {
+                    tracing::debug!("Skipping {} in glob_files: {e}", path.display());
+                    None
+                }
+            }
+        })
+        .collect();
+    paths.sort();
+    Ok(paths)
+}
+
+/// Read a file relative to `root` after checking it against the path policy,
+/// cutting it short after `max_bytes`
+fn read_project_file(root: &Path, path: &str, max_bytes: usize) -> crate::Result<String> {
+    let full_path = validate_path(Path::new(path), root, PathPolicy::global())?;
+    let bytes = std::fs::read(&full_path).map_err(|e| {
+        crate::SwissArmyHammerError::Other(format!("Failed to read file '{path}': {e}"))
+    })?;
+    let content = String::from_utf8_lossy(&bytes);
+    Ok(truncate(&content, max_bytes))
+}
+
+/// `content` cut at the last character boundary within `max_bytes`, with a
+/// note saying so
+fn truncate(content: &str, max_bytes: usize) -> String {
+    if content.len() <= max_bytes {
+        return content.to_string();
+    }
+    let mut end = max_bytes;
+    while !content.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!(
+        "{}\n[... truncated after {max_bytes} of {} bytes]\n",
+        &content[..end],
+        content.len()
+    )
+}
+
+/// A file under a heading with its path, fenced with its extension
+fn file_section(path: &str, content: &str) -> String {
+    let language = Path::new(path)
+        .extension()
+        .map(|extension| extension.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let newline = if content.ends_with('\n') { "" } else { "\n" };
+    format!("### {path}\n\n```{language}\n{content}{newline}```\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn render(root: &Path, template: &str) -> liquid_core::Result<String> {
+        let parser = liquid::ParserBuilder::with_stdlib()
+            .tag(IncludeFileTag::with_root(root))
+            .tag(GlobFilesTag::with_root(root))
+            .build()
+            .unwrap();
+        parser
+            .parse(template)?
+            .render(&liquid::object!({ "file": "src/main.rs" }))
+    }
+
+    fn project() -> TempDir {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir_all(temp.path().join("src/bin")).unwrap();
+        fs::write(temp.path().join("src/main.rs"), "fn main() {}\n").unwrap();
+        fs::write(temp.path().join("src/lib.rs"), "pub mod bin;").unwrap();
+        fs::write(temp.path().join("src/bin/tool.rs"), "// tool\n").unwrap();
+        fs::write(temp.path().join("README.md"), "# Readme\n").unwrap();
+        temp
+    }
+
+    #[test]
+    fn test_include_file_renders_content() {
+        let temp = project();
+        let root = temp.path();
+        assert_eq!(
+            render(root, "{% include_file \"src/main.rs\" %}").unwrap(),
+            "fn main() {}\n"
+        );
+        assert_eq!(
+            render(root, "{% include_file file %}").unwrap(),
+            "fn main() {}\n"
+        );
+
+        assert!(render(root, "{% include_file \"../secret\" %}").is_err());
+        assert!(render(root, "{% include_file \"/etc/passwd\" %}").is_err());
+        assert!(render(root, "{% include_file \"missing.rs\" %}").is_err());
+        assert!(render(root, "{% include_file %}").is_err());
+    }
+
+    #[test]
+    fn test_glob_files_renders_matches_in_order() {
+        let temp = project();
+        let root = temp.path();
+        let output = render(root, "{% glob_files \"src/**/*.rs\" %}").unwrap();
+        assert_eq!(
+            output,
+            "### src/bin/tool.rs\n\n```rs\n// tool\n```\n\n\
+             ### src/lib.rs\n\n```rs\npub mod bin;\n```\n\n\
+             ### src/main.rs\n\n```rs\nfn main() {}\n```\n"
+        );
+
+        let limited = render(root, "{% glob_files \"src/**/*.rs\" limit: 1 %}").unwrap();
+        assert!(limited.starts_with("### src/bin/tool.rs\n"));
+        assert!(!limited.contains("src/lib.rs"));
+        assert!(limited.ends_with("(2 more files matching 'src/**/*.rs' not included)\n"));
+
+        assert!(render(root, "{% glob_files \"*.txt\" %}")
+            .unwrap()
+            .is_empty());
+        assert!(render(root, "{% glob_files \"../**/*.rs\" %}").is_err());
+        assert!(render(root, "{% glob_files \"*.md\" limit: 0 %}").is_err());
+        assert!(render(root, "{% glob_files \"*.md\" count: 2 %}").is_err());
+    }
+
+    #[test]
+    fn test_truncate_respects_char_boundaries() {
+        assert_eq!(truncate("short", 10), "short");
+        assert_eq!(
+            truncate("héllo", 2),
+            "h\n[... truncated after 2 of 6 bytes]\n"
+        );
+    }
+}
@@ -0,0 +1,231 @@
+//! Git context for prompt templates
+//!
+//! A template that uses a `git` variable gets an object describing the working
+//! tree of the project, so code review prompts no longer need it passed in:
+//!
+//! ```text
+//! Reviewing {{ git.branch }}, last commit: {{ git.last_commit_message }}
+//! {% for file in git.changed_files %}- {{ file }}
+//! {% endfor %}
+//! {{ git.diff_stat }}
+//! ```
+//!
+//! The `git_diff` filter renders the diff of one file against the last commit:
+//! `{{ "src/lib.rs" | git_diff }}`.
+//!
+//! The path given to `git_diff` is checked against the global [`PathPolicy`]
+//! like the files `include_file` reads, so a file on the denylist can't be
+//! read through its diff. Git output is stripped of control characters, and
+//! each text is cut short
+//! at `SWISSARMYHAMMER_TEMPLATE_GIT_MAX_BYTES`; `git.truncated` says whether
+//! anything was. The repository is the one holding the [`project_root`], and
+//! outside a repository `git` is left undefined. An argument named `git` takes
+//! precedence over the object.
+
+use crate::config::Config;
+use crate::git::GitOperations;
+use crate::security::PathPolicy;
+use crate::template_files::project_root;
+use liquid_core::parser::{FilterArguments, ParameterReflection};
+use liquid_core::{Filter, FilterReflection, ParseFilter, Runtime, Value, ValueView};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Name of the template variable holding the git context
+pub const GIT_VARIABLE: &str = "git";
+
+/// The `git` object for the repository at `root`, or `None` outside a repository
+pub fn git_context_value(root: &Path) -> Option<Value> {
+    let git = GitOperations::with_work_dir(root.to_path_buf()).ok()?;
+    match git.context(Config::global().template_git_max_bytes) {
+        Ok(context) => liquid::model::to_value(&context).ok(),
+        Err(e) => {
+            tracing::debug!("Leaving the git template variable undefined: {e}");
+            None
+        }
+    }
+}
+
+/// Filter rendering the diff of a file against the last commit
+///
+/// `{{ "src/lib.rs" | git_diff }}`; the path is relative to the repository
+/// root and is checked against the path policy.
+#[derive(Clone, Debug, Default)]
+pub struct GitDiffFilterParser {
+    root: Option<PathBuf>,
+    policy: Option<PathPolicy>,
+}
+
+impl GitDiffFilterParser {
+    /// Diff files of the repository at the [`project_root`] at render time
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Diff files of the repository at `root` instead of the project root
+    pub fn with_root(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: Some(root.into()),
+            policy: None,
+        }
+    }
+
+    /// Check paths against `policy` instead of the global path policy
+    pub fn with_policy(mut self, policy: PathPolicy) -> Self {
+        self.policy = Some(policy);
+        self
+    }
+}
+
+impl FilterReflection for GitDiffFilterParser {
+    fn name(&self) -> &str {
+        "git_diff"
+    }
+
+    fn description(&self) -> &str {
+        "Renders the diff of a file against the last commit"
+    }
+
+    fn positional_parameters(&self) -> &'static [ParameterReflection] {
+        &[]
+    }
+
+    fn keyword_parameters(&self) -> &'static [ParameterReflection] {
+        &[]
+    }
+}
+
+impl ParseFilter for GitDiffFilterParser {
+    fn parse(&self, mut arguments: FilterArguments) -> liquid_core::Result<Box<dyn Filter>> {
+        if arguments.positional.next().is_some() || arguments.keyword.next().is_some() {
+            return Err(liquid_core::Error::with_msg(
+                "Filter 'git_diff' does not take arguments",
+            ));
+        }
+        Ok(Box::new(GitDiffFilter {
+            root: self.root.clone(),
+            policy: self.policy.clone(),
+        }))
+    }
+
+    fn reflection(&self) -> &dyn FilterReflection {
+        self
+    }
+}
+
+#[derive(Debug)]
+struct GitDiffFilter {
+    root: Option<PathBuf>,
+    policy: Option<PathPolicy>,
+}
+
+impl fmt::Display for GitDiffFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "git_diff")
+    }
+}
+
+impl Filter for GitDiffFilter {
+    fn evaluate(
+        &self,
+        input: &dyn ValueView,
+        _runtime: &dyn Runtime,
+    ) -> liquid_core::Result<Value> {
+        let path = input.to_kstr().into_string();
+        let root = self.root.clone().unwrap_or_else(project_root);
+        let policy = match &self.policy {
+            Some(policy) => policy,
+            None => PathPolicy::global(),
+        };
+        GitOperations::with_work_dir(root)
+            .and_then(|git| {
+                git.file_diff(
+                    Path::new(&path),
+                    policy,
+                    Config::global().template_git_max_bytes,
+                )
+            })
+            .map(Value::scalar)
+            .map_err(|e| liquid_core::Error::with_msg(e.to_string()).context("filter", "git_diff"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn repository() -> TempDir {
+        let temp = TempDir::new().unwrap();
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .current_dir(temp.path())
+                .args(args)
+                .output()
+                .unwrap();
+        };
+        git(&["init"]);
+        git(&["config", "user.name", "Test User"]);
+        git(&["config", "user.email", "test@example.com"]);
+        fs::write(temp.path().join("lib.rs"), "fn old() {}\n").unwrap();
+        git(&["add", "lib.rs"]);
+        git(&["commit", "-m", "Add lib"]);
+        fs::write(temp.path().join("lib.rs"), "fn new() {}\n").unwrap();
+        temp
+    }
+
+    #[test]
+    fn test_git_context_value() {
+        let temp = repository();
+        let parser = liquid::ParserBuilder::with_stdlib().build().unwrap();
+        let template = parser
+            .parse("{{ git.last_commit_message }}: {% for file in git.changed_files %}{{ file }}{% endfor %}")
+            .unwrap();
+        let mut globals = liquid::Object::new();
+        globals.insert(GIT_VARIABLE.into(), git_context_value(temp.path()).unwrap());
+        assert_eq!(template.render(&globals).unwrap(), "Add lib: lib.rs");
+
+        let outside = TempDir::new().unwrap();
+        assert!(git_context_value(outside.path()).is_none());
+    }
+
+    #[test]
+    fn test_git_diff_filter() {
+        let temp = repository();
+        let parser = liquid::ParserBuilder::with_stdlib()
+            .filter(GitDiffFilterParser::with_root(temp.path()))
+            .build()
+            .unwrap();
+
+        let diff = parser
+            .parse("{{ \"lib.rs\" | git_diff }}")
+            .unwrap()
+            .render(&liquid::Object::new())
+            .unwrap();
+        assert!(diff.contains("-fn old() {}"));
+        assert!(diff.contains("+fn new() {}"));
+
+        let escape = parser.parse("{{ \"../lib.rs\" | git_diff }}").unwrap();
+        assert!(escape.render(&liquid::Object::new()).is_err());
+        assert!(parser.parse("{{ \"lib.rs\" | git_diff: 3 }}").is_err());
+    }
+
+    #[test]
+    fn test_git_diff_filter_respects_the_denylist() {
+        let temp = repository();
+        let policy = PathPolicy {
+            denylist: vec!["*.rs".to_string()],
+            ..PathPolicy::default()
+        };
+        let parser = liquid::ParserBuilder::with_stdlib()
+            .filter(GitDiffFilterParser::with_root(temp.path()).with_policy(policy))
+            .build()
+            .unwrap();
+
+        let denied = parser.parse("{{ \"lib.rs\" | git_diff }}").unwrap();
+        let error = denied.render(&liquid::Object::new()).unwrap_err();
+        assert!(error.to_string().contains("denylist"));
+    }
+}
@@ -0,0 +1,392 @@
+//! Values for prompt arguments from the environment, dotenv files and commands
+//!
+//! Passing a secret with `--arg` or `--var` leaves it in shell history. An
+//! argument can instead name where its value comes from with a `source` field
+//! in front matter, and the value is looked up when the prompt is rendered
+//! without one:
+//!
+//! ```yaml
+//! arguments:
+//!   - name: api_token
+//!     required: true
+//!     source: env:API_TOKEN
+//!   - name: db_password
+//!     source: dotenv:DB_PASSWORD
+//!   - name: deploy_key
+//!     source: "command:op read op://deploy/key/credential"
+//! ```
+//!
+//! A source is a provider name and a reference, separated by the first `:`.
+//! The built in providers are:
+//!
+//! - `env:NAME` reads an environment variable
+//! - `dotenv:NAME` reads a variable from `.env` in the current directory, and
+//!   `dotenv:path/to/file.env#NAME` from another file
+//! - `command:COMMAND` runs a shell command and uses its output, without the
+//!   trailing newline
+//!
+//! Other providers can be added with [`register_value_provider`]. Values from
+//! providers other than `env` are cached for the life of the process, so a
+//! password manager is asked once however many prompts use the value.
+
+use crate::error::{Result, SwissArmyHammerError};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::process::Command;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
+
+/// Dotenv file read when a `dotenv` source names no file
+pub const DEFAULT_DOTENV_FILE: &str = ".env";
+
+/// Where the value of an argument comes from, such as `env:API_TOKEN`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ValueSource {
+    /// Name of the provider that resolves the reference
+    pub provider: String,
+    /// What to look up, in the provider's own syntax
+    pub reference: String,
+}
+
+impl ValueSource {
+    /// Look up the value with the registered provider
+    pub fn resolve(&self) -> Result<String> {
+        registry().resolve(self)
+    }
+}
+
+impl fmt::Display for ValueSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.provider, self.reference)
+    }
+}
+
+impl FromStr for ValueSource {
+    type Err = SwissArmyHammerError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || {
+            SwissArmyHammerError::Other(format!(
+                "Invalid value source '{s}': expected provider:reference, such as env:API_TOKEN"
+            ))
+        };
+        let (provider, reference) = s.trim().split_once(':').ok_or_else(invalid)?;
+        let provider = provider.trim();
+        let reference = reference.trim();
+        if provider.is_empty()
+            || reference.is_empty()
+            || !provider
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        {
+            return Err(invalid());
+        }
+        Ok(Self {
+            provider: provider.to_lowercase(),
+            reference: reference.to_string(),
+        })
+    }
+}
+
+impl TryFrom<String> for ValueSource {
+    type Error = SwissArmyHammerError;
+
+    fn try_from(value: String) -> Result<Self> {
+        value.parse()
+    }
+}
+
+impl From<ValueSource> for String {
+    fn from(source: ValueSource) -> Self {
+        source.to_string()
+    }
+}
+
+/// Looks up values for one kind of [`ValueSource`]
+pub trait ValueProvider: Send + Sync {
+    /// Provider name used before the `:` in sources
+    fn name(&self) -> &str;
+
+    /// Look up the value a reference names
+    fn resolve(&self, reference: &str) -> Result<String>;
+
+    /// Whether resolved values may be reused for the rest of the process
+    fn cacheable(&self) -> bool {
+        true
+    }
+}
+
+/// Reads environment variables
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EnvProvider;
+
+impl ValueProvider for EnvProvider {
+    fn name(&self) -> &str {
+        "env"
+    }
+
+    fn resolve(&self, reference: &str) -> Result<String> {
+        std::env::var(reference).map_err(|_| {
+            SwissArmyHammerError::Other(format!("Environment variable '{reference}' is not set"))
+        })
+    }
+
+    fn cacheable(&self) -> bool {
+        false
+    }
+}
+
+/// Reads variables from dotenv files
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DotenvProvider;
+
+impl ValueProvider for DotenvProvider {
+    fn name(&self) -> &str {
+        "dotenv"
+    }
+
+    fn resolve(&self, reference: &str) -> Result<String> {
+        let (path, key) = match reference.rsplit_once('#') {
+            Some((path, key)) => (path, key),
+            None => (DEFAULT_DOTENV_FILE, reference),
+        };
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            SwissArmyHammerError::Other(format!("Failed to read dotenv file '{path}': {e}"))
+        })?;
+        parse_dotenv(&content).remove(key).ok_or_else(|| {
+            SwissArmyHammerError::Other(format!("'{key}' is not set in dotenv file '{path}'"))
+        })
+    }
+}
+
+/// Runs a shell command and uses its output
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CommandProvider;
+
+impl ValueProvider for CommandProvider {
+    fn name(&self) -> &str {
+        "command"
+    }
+
+    fn resolve(&self, reference: &str) -> Result<String> {
+        let output = if cfg!(windows) {
+            Command::new("cmd").args(["/C", reference]).output()
+        } else {
+            Command::new("sh").args(["-c", reference]).output()
+        }
+        .map_err(|e| {
+            SwissArmyHammerError::Other(format!("Failed to run value command '{reference}': {e}"))
+        })?;
+
+        if !output.status.success() {
+            return Err(SwissArmyHammerError::Other(format!(
+                "Value command '{reference}' failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        let value = String::from_utf8_lossy(&output.stdout);
+        Ok(value.trim_end_matches(['\r', '\n']).to_string())
+    }
+}
+
+/// The registered providers and the values resolved so far
+struct ValueProviders {
+    providers: Mutex<BTreeMap<String, Arc<dyn ValueProvider>>>,
+    cache: Mutex<HashMap<ValueSource, String>>,
+}
+
+impl ValueProviders {
+    fn with_builtin_providers() -> Self {
+        let providers: [Arc<dyn ValueProvider>; 3] = [
+            Arc::new(EnvProvider),
+            Arc::new(DotenvProvider),
+            Arc::new(CommandProvider),
+        ];
+        Self {
+            providers: Mutex::new(
+                providers
+                    .into_iter()
+                    .map(|provider| (provider.name().to_string(), provider))
+                    .collect(),
+            ),
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn resolve(&self, source: &ValueSource) -> Result<String> {
+        if let Some(value) = self
+            .cache
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(source)
+        {
+            return Ok(value.clone());
+        }
+
+        let provider = {
+            let providers = self
+                .providers
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            providers.get(&source.provider).cloned().ok_or_else(|| {
+                SwissArmyHammerError::Other(format!(
+                    "Unknown value provider '{}': expected one of {}",
+                    source.provider,
+                    providers.keys().cloned().collect::<Vec<_>>().join(", ")
+                ))
+            })?
+        };
+        let value = provider.resolve(&source.reference)?;
+        if provider.cacheable() {
+            self.cache
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .insert(source.clone(), value.clone());
+        }
+        Ok(value)
+    }
+}
+
+fn registry() -> &'static ValueProviders {
+    static PROVIDERS: OnceLock<ValueProviders> = OnceLock::new();
+    PROVIDERS.get_or_init(ValueProviders::with_builtin_providers)
+}
+
+/// Add a provider, replacing any provider with the same name
+///
+/// Cached values from a replaced provider are dropped.
+pub fn register_value_provider(provider: Arc<dyn ValueProvider>) {
+    let registry = registry();
+    let name = provider.name().to_string();
+    registry
+        .cache
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .retain(|source, _| source.provider != name);
+    registry
+        .providers
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(name, provider);
+}
+
+/// Forget every cached value, so the next render looks values up again
+pub fn clear_value_cache() {
+    registry()
+        .cache
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clear();
+}
+
+/// Parse `KEY=VALUE` lines, skipping blank lines, comments and `export`
+fn parse_dotenv(content: &str) -> HashMap<String, String> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let line = line.strip_prefix("export ").unwrap_or(line);
+            let (key, value) = line.split_once('=')?;
+            let value = value.trim();
+            let value = match value.as_bytes().first() {
+                Some(quote @ (b'"' | b'\''))
+                    if value.len() > 1 && value.as_bytes()[value.len() - 1] == *quote =>
+                {
+                    &value[1..value.len() - 1]
+                }
+                // An unquoted value ends at a comment
+                _ => value.split(" #").next().unwrap_or_default().trim_end(),
+            };
+            Some((key.trim().to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_value_source() {
+        let source: ValueSource = "env:API_TOKEN".parse().unwrap();
+        assert_eq!(source.provider, "env");
+        assert_eq!(source.reference, "API_TOKEN");
+        let source: ValueSource = "command:op read op://deploy/key".parse().unwrap();
+        assert_eq!(source.reference, "op read op://deploy/key");
+        assert_eq!(source.to_string(), "command:op read op://deploy/key");
+
+        let source: ValueSource = serde_json::from_value(serde_json::json!("Dotenv:KEY")).unwrap();
+        assert_eq!(source.provider, "dotenv");
+        for invalid in ["API_TOKEN", ":API_TOKEN", "env:", "my env:X"] {
+            assert!(invalid.parse::<ValueSource>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_parse_dotenv() {
+        let values = parse_dotenv(
+            "# credentials\nexport DB_USER=admin\nDB_PASSWORD=\"s3cret #1\"\nREGION = eu-west-1 # primary\nEMPTY=\n",
+        );
+        assert_eq!(values["DB_USER"], "admin");
+        assert_eq!(values["DB_PASSWORD"], "s3cret #1");
+        assert_eq!(values["REGION"], "eu-west-1");
+        assert_eq!(values["EMPTY"], "");
+    }
+
+    #[test]
+    fn test_resolve_builtin_providers() {
+        std::env::set_var("SAH_VALUE_PROVIDER_TEST", "from-env");
+        let source: ValueSource = "env:SAH_VALUE_PROVIDER_TEST".parse().unwrap();
+        assert_eq!(source.resolve().unwrap(), "from-env");
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let dotenv = temp_dir.path().join("secrets.env");
+        std::fs::write(&dotenv, "TOKEN=from-dotenv\n").unwrap();
+        let source: ValueSource = format!("dotenv:{}#TOKEN", dotenv.display())
+            .parse()
+            .unwrap();
+        assert_eq!(source.resolve().unwrap(), "from-dotenv");
+
+        // Values are cached until the cache is cleared
+        std::fs::write(&dotenv, "TOKEN=changed\n").unwrap();
+        assert_eq!(source.resolve().unwrap(), "from-dotenv");
+        clear_value_cache();
+        assert_eq!(source.resolve().unwrap(), "changed");
+
+        let missing: ValueSource = format!("dotenv:{}#OTHER", dotenv.display())
+            .parse()
+            .unwrap();
+        assert!(missing.resolve().is_err());
+        let unknown: ValueSource = "vault:secret/db".parse().unwrap();
+        let error = unknown.resolve().unwrap_err().to_string();
+        assert!(error.contains("Unknown value provider 'vault'"), "{error}");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_command_provider() {
+        let source: ValueSource = "command:printf 'hunter2\\n'".parse().unwrap();
+        assert_eq!(source.resolve().unwrap(), "hunter2");
+        let failing: ValueSource = "command:exit 3".parse().unwrap();
+        assert!(failing.resolve().is_err());
+    }
+
+    #[test]
+    fn test_register_value_provider() {
+        struct Upper;
+        impl ValueProvider for Upper {
+            fn name(&self) -> &str {
+                "upper"
+            }
+            fn resolve(&self, reference: &str) -> Result<String> {
+                Ok(reference.to_uppercase())
+            }
+        }
+
+        register_value_provider(Arc::new(Upper));
+        let source: ValueSource = "upper:quiet".parse().unwrap();
+        assert_eq!(source.resolve().unwrap(), "QUIET");
+    }
+}
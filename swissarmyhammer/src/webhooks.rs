@@ -0,0 +1,387 @@
+//! Webhooks announcing events to other services
+//!
+//! Instead of polling issues and runs, a chat channel or CI system can be told
+//! what happened. With `SWISSARMYHAMMER_WEBHOOK_URLS` set, the CLI sends the
+//! events it publishes on the [`crate::events`] bus to each URL as a JSON
+//! `POST`:
+//!
+//! - `issue_created` and `issue_completed`
+//! - `workflow_run_finished`, when a run completes, fails or is cancelled
+//! - `budget_exceeded`, when spending crosses a cost budget or a prompt is
+//!   refused because the budget is spent
+//!
+//! `SWISSARMYHAMMER_WEBHOOK_EVENTS` narrows the events sent. The body is the
+//! event as the bus serializes it, with the time it was sent:
+//!
+//! ```json
+//! {"event": "issue_completed", "name": "000042_login", "sent_at": "2025-01-01T12:00:00Z"}
+//! ```
+//!
+//! With `SWISSARMYHAMMER_WEBHOOK_SECRET` set, each request carries the
+//! HMAC-SHA256 of its body keyed with the secret in the [`SIGNATURE_HEADER`]
+//! header, as `sha256=<hex>`, so receivers can check it came from here.
+//! Requests that fail with a network error, a 429 or a 5xx response are tried
+//! again up to `SWISSARMYHAMMER_WEBHOOK_MAX_RETRIES` times, waiting twice as
+//! long before each retry. Failed deliveries are logged and never fail the
+//! command that published the event.
+
+use crate::config::Config;
+use crate::error::{Result, SwissArmyHammerError};
+use crate::events::{event_bus, Event, EventSubscriber};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+
+/// Events sent to webhooks when no event list is configured
+pub const WEBHOOK_EVENTS: &[&str] = &[
+    "issue_created",
+    "issue_completed",
+    "workflow_run_finished",
+    "budget_exceeded",
+];
+
+/// Header carrying the `sha256=<hex>` signature of the request body
+pub const SIGNATURE_HEADER: &str = "X-SwissArmyHammer-Signature";
+
+/// Header naming the kind of event the request announces
+pub const EVENT_HEADER: &str = "X-SwissArmyHammer-Event";
+
+/// Wait before the first retry of a failed delivery
+const DEFAULT_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// Where events are sent and how
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebhookConfig {
+    /// URLs every event is sent to
+    pub urls: Vec<String>,
+    /// Kinds of events sent, every webhook event when empty
+    pub events: Vec<String>,
+    /// Secret request bodies are signed with
+    pub secret: Option<String>,
+    /// Times a failed delivery is tried again
+    pub max_retries: u32,
+    /// Wait before the first retry, doubled for each one after it
+    pub retry_delay: Duration,
+    /// Longest a single request may take
+    pub timeout: Duration,
+}
+
+impl WebhookConfig {
+    /// The webhooks set in the configuration
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            urls: config.webhook_urls.clone(),
+            events: config.webhook_events.clone(),
+            secret: config.webhook_secret.clone(),
+            max_retries: config.webhook_max_retries,
+            retry_delay: DEFAULT_RETRY_DELAY,
+            timeout: Duration::from_secs(config.webhook_timeout_seconds),
+        }
+    }
+
+    /// Whether any webhook is set
+    pub fn is_enabled(&self) -> bool {
+        !self.urls.is_empty()
+    }
+
+    /// Whether the event is sent to the webhooks
+    pub fn sends(&self, event: &Event) -> bool {
+        let kind = event.kind();
+        if self.events.is_empty() {
+            WEBHOOK_EVENTS.contains(&kind)
+        } else {
+            self.events.iter().any(|wanted| wanted == kind)
+        }
+    }
+}
+
+/// The `sha256=<hex>` HMAC-SHA256 signature of a request body
+pub fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(body);
+    format!("sha256={:x}", mac.finalize().into_bytes())
+}
+
+/// The JSON body announcing an event
+pub fn payload(event: &Event) -> Result<String> {
+    let mut body = serde_json::to_value(event)?;
+    if let Some(fields) = body.as_object_mut() {
+        fields.insert(
+            "sent_at".to_string(),
+            serde_json::Value::String(chrono::Utc::now().to_rfc3339()),
+        );
+    }
+    Ok(serde_json::to_string(&body)?)
+}
+
+/// Sends events to the configured webhooks
+#[derive(Debug, Clone)]
+pub struct WebhookSender {
+    config: WebhookConfig,
+    http: reqwest::Client,
+}
+
+impl WebhookSender {
+    /// Create a sender for the given webhooks
+    pub fn new(config: WebhookConfig) -> Self {
+        Self {
+            config,
+            http: reqwest::Client::new(),
+        }
+    }
+
+    /// The webhooks events are sent to
+    pub fn config(&self) -> &WebhookConfig {
+        &self.config
+    }
+
+    /// Send an event to every webhook, if it is one they are sent
+    ///
+    /// Each webhook is tried with retries; the error names every webhook the
+    /// event could not be delivered to.
+    pub async fn send(&self, event: &Event) -> Result<()> {
+        if !self.config.sends(event) {
+            return Ok(());
+        }
+        let body = payload(event)?;
+        let mut failures = Vec::new();
+        for url in &self.config.urls {
+            if let Err(e) = self.deliver(url, event.kind(), &body).await {
+                failures.push(format!("{url}: {e}"));
+            }
+        }
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(SwissArmyHammerError::Other(format!(
+                "Failed to deliver {} webhook: {}",
+                event.kind(),
+                failures.join("; ")
+            )))
+        }
+    }
+
+    /// POST a body to one webhook, retrying failures that may pass
+    async fn deliver(&self, url: &str, kind: &str, body: &str) -> Result<()> {
+        let mut delay = self.config.retry_delay;
+        let mut attempt = 0;
+        loop {
+            let mut request = self
+                .http
+                .post(url)
+                .timeout(self.config.timeout)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .header(EVENT_HEADER, kind)
+                .body(body.to_string());
+            if let Some(secret) = &self.config.secret {
+                request = request.header(SIGNATURE_HEADER, sign(secret, body.as_bytes()));
+            }
+
+            let error = match request.send().await {
+                Ok(response) if response.status().is_success() => return Ok(()),
+                Ok(response) => {
+                    let status = response.status();
+                    let error = format!("responded {status}");
+                    let retryable = status == reqwest::StatusCode::TOO_MANY_REQUESTS
+                        || status.is_server_error();
+                    if !retryable {
+                        return Err(SwissArmyHammerError::Other(error));
+                    }
+                    error
+                }
+                Err(e) => e.to_string(),
+            };
+
+            if attempt >= self.config.max_retries {
+                return Err(SwissArmyHammerError::Other(error));
+            }
+            attempt += 1;
+            tracing::debug!(
+                "Retrying {} webhook in {:?} ({}/{}): {}",
+                kind,
+                delay,
+                attempt,
+                self.config.max_retries,
+                error
+            );
+            tokio::time::sleep(delay).await;
+            delay *= 2;
+        }
+    }
+
+    /// Send the events published on the event bus from now on, until the
+    /// returned task is finished
+    pub fn spawn(self) -> WebhookTask {
+        let events = event_bus().subscribe();
+        let stop = Arc::new(Notify::new());
+        let handle = tokio::spawn(self.run(events, Arc::clone(&stop)));
+        WebhookTask { handle, stop }
+    }
+
+    /// Send the events published on the event bus to the webhooks set in the
+    /// configuration, or `None` when no webhook is set
+    pub fn spawn_configured() -> Option<WebhookTask> {
+        let config = WebhookConfig::from_config(Config::global());
+        config.is_enabled().then(|| Self::new(config).spawn())
+    }
+
+    async fn run(self, mut events: EventSubscriber, stop: Arc<Notify>) {
+        loop {
+            tokio::select! {
+                event = events.recv() => match event {
+                    Some(event) => self.send_logged(&event).await,
+                    None => return,
+                },
+                _ = stop.notified() => {
+                    while let Some(event) = events.try_recv() {
+                        self.send_logged(&event).await;
+                    }
+                    return;
+                }
+            }
+        }
+    }
+
+    async fn send_logged(&self, event: &Event) {
+        if let Err(e) = self.send(event).await {
+            tracing::warn!("{}", e);
+        }
+    }
+}
+
+/// Events being sent to webhooks in the background
+#[derive(Debug)]
+pub struct WebhookTask {
+    handle: JoinHandle<()>,
+    stop: Arc<Notify>,
+}
+
+impl WebhookTask {
+    /// Send the events already published and stop, waiting at most `wait`
+    /// for their deliveries
+    pub async fn finish(self, wait: Duration) {
+        self.stop.notify_one();
+        let abort = self.handle.abort_handle();
+        if tokio::time::timeout(wait, self.handle).await.is_err() {
+            tracing::warn!("Gave up delivering webhooks after {:?}", wait);
+            abort.abort();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    fn config(urls: Vec<String>) -> WebhookConfig {
+        WebhookConfig {
+            urls,
+            events: Vec::new(),
+            secret: Some("Jefe".to_string()),
+            max_retries: 2,
+            retry_delay: Duration::from_millis(10),
+            timeout: Duration::from_secs(5),
+        }
+    }
+
+    #[test]
+    fn test_sign_and_select_events() {
+        // RFC 4231 test case 2
+        assert_eq!(
+            sign("Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+
+        let completed = Event::IssueCompleted {
+            name: "000042_login".to_string(),
+        };
+        let mut config = config(vec!["http://localhost/hook".to_string()]);
+        assert!(config.sends(&completed));
+        assert!(!config.sends(&Event::PromptsReloaded { count: 1 }));
+        config.events = vec!["budget_exceeded".to_string()];
+        assert!(!config.sends(&completed));
+
+        let body: serde_json::Value = serde_json::from_str(&payload(&completed).unwrap()).unwrap();
+        assert_eq!(body["event"], "issue_completed");
+        assert_eq!(body["name"], "000042_login");
+        assert!(body["sent_at"].is_string());
+    }
+
+    /// Answer each connection with the next status, returning the requests
+    async fn serve(statuses: Vec<u16>) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let handle = tokio::spawn(async move {
+            let mut requests = Vec::new();
+            for status in statuses {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut request = Vec::new();
+                let mut buffer = [0u8; 4096];
+                loop {
+                    let read = stream.read(&mut buffer).await.unwrap();
+                    request.extend_from_slice(&buffer[..read]);
+                    let text = String::from_utf8_lossy(&request);
+                    if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                        let length = head
+                            .lines()
+                            .find_map(|line| {
+                                line.to_ascii_lowercase()
+                                    .strip_prefix("content-length:")
+                                    .map(|value| value.trim().parse::<usize>().unwrap())
+                            })
+                            .unwrap_or(0);
+                        if body.len() >= length {
+                            break;
+                        }
+                    }
+                    if read == 0 {
+                        break;
+                    }
+                }
+                requests.push(String::from_utf8_lossy(&request).into_owned());
+                let response = format!(
+                    "HTTP/1.1 {status} Status\r\ncontent-length: 0\r\nconnection: close\r\n\r\n"
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+            requests
+        });
+        (url, handle)
+    }
+
+    #[tokio::test]
+    async fn test_send_signs_and_retries() {
+        let (url, server) = serve(vec![503, 200]).await;
+        let sender = WebhookSender::new(config(vec![url]));
+        let event = Event::IssueCreated {
+            name: "000042_login".to_string(),
+        };
+        sender.send(&event).await.unwrap();
+
+        let requests = server.await.unwrap();
+        assert_eq!(requests.len(), 2);
+        let request = &requests[1];
+        let (head, body) = request.split_once("\r\n\r\n").unwrap();
+        let head = head.to_ascii_lowercase();
+        assert!(head.starts_with("post /hook"));
+        assert!(head.contains("x-swissarmyhammer-event: issue_created"));
+        let signature = format!(
+            "x-swissarmyhammer-signature: {}",
+            sign("Jefe", body.as_bytes())
+        );
+        assert!(head.contains(&signature), "{head}");
+
+        // Client errors are not retried
+        let (url, server) = serve(vec![404]).await;
+        let sender = WebhookSender::new(config(vec![url]));
+        let error = sender.send(&event).await.unwrap_err().to_string();
+        assert!(error.contains("404"), "{error}");
+        assert_eq!(server.await.unwrap().len(), 1);
+    }
+}
@@ -1,9 +1,14 @@
 //! Action parsing utilities for workflow state descriptions
 
+use crate::prompt_refs::PromptRef;
 use crate::workflow::actions::{
-    AbortAction, ActionError, ActionResult, LogAction, LogLevel, PromptAction, SetVariableAction,
-    ShellAction, SubWorkflowAction, WaitAction,
+    AbortAction, ActionError, ActionResult, CreateIssueAction, CreateMemoAction, LogAction,
+    LogLevel, PromptAction, SaveArtifactAction, SetVariableAction, ShellAction, SubWorkflowAction,
+    UpdateIssueAction, WaitAction,
 };
+use crate::workflow::artifacts::ArtifactKind;
+use crate::workflow::extractors::OutputExtractor;
+use crate::workflow::providers::PromptBackend;
 use chumsky::prelude::*;
 use regex::Regex;
 use serde_json::Value;
@@ -87,8 +92,61 @@ impl ActionParser {
             .map(|(first, rest)| format!("{first}{rest}"))
     }
 
+    /// Parse a Claude temperature value, which must be between 0.0 and 1.0
+    fn parse_temperature(value: &str) -> ActionResult<f32> {
+        value
+            .parse::<f32>()
+            .ok()
+            .filter(|t| (0.0..=1.0).contains(t))
+            .ok_or_else(|| {
+                ActionError::ParseError(format!(
+                    "Invalid temperature '{value}': must be a number between 0.0 and 1.0"
+                ))
+            })
+    }
+
+    /// Parse a Claude max output token count, which must be a positive integer
+    fn parse_max_tokens(value: &str) -> ActionResult<u32> {
+        value
+            .parse::<u32>()
+            .ok()
+            .filter(|tokens| *tokens > 0)
+            .ok_or_else(|| {
+                ActionError::ParseError(format!(
+                    "Invalid max_tokens '{value}': must be a positive integer"
+                ))
+            })
+    }
+
+    /// Parse whether a prompt continues the run's Claude conversation
+    fn parse_session(value: &str) -> ActionResult<bool> {
+        match value {
+            "continue" => Ok(true),
+            "new" => Ok(false),
+            _ => Err(ActionError::ParseError(format!(
+                "Invalid session '{value}': must be 'continue' or 'new'"
+            ))),
+        }
+    }
+
+    /// Parse where a prompt is sent, such as `claude` or `openai`
+    fn parse_backend(value: &str) -> ActionResult<PromptBackend> {
+        value
+            .parse()
+            .map_err(|e: crate::error::SwissArmyHammerError| ActionError::ParseError(e.to_string()))
+    }
+
     /// Parse a prompt action from description
     /// Format: Execute prompt "prompt-name" with arg1="value1" arg2="value2"
+    ///
+    /// The prompt name may pin the prompt's content, `"prompt-name@sha256:<hash>"`
+    /// or `"prompt-name@<version>"`, see [`crate::prompt_refs`].
+    ///
+    /// The reserved keys `result`, `model`, `temperature`, `max_tokens`,
+    /// `system_prompt`, `session`, `backend` and `on_extract_failure`, and keys
+    /// starting with `extract_`, configure the action instead of being passed
+    /// to the prompt. `extract_<variable>="<extractor>"` stores a value parsed from
+    /// the response in `<variable>`.
     pub fn parse_prompt_action(&self, description: &str) -> ActionResult<Option<PromptAction>> {
         let parser = Self::case_insensitive("execute")
             .then_ignore(Self::whitespace())
@@ -112,12 +170,40 @@ impl ActionParser {
 
         match parser.parse(description.trim()).into_result() {
             Ok((prompt_name, args)) => {
-                let mut action = PromptAction::new(prompt_name);
+                let reference: PromptRef =
+                    prompt_name
+                        .parse()
+                        .map_err(|e: crate::error::SwissArmyHammerError| {
+                            ActionError::ParseError(e.to_string())
+                        })?;
+                let mut action = PromptAction::new(reference.name).with_pin(reference.pin);
 
                 if let Some(arguments) = args {
                     for (key, value) in arguments {
                         if key == "result" {
                             action = action.with_result_variable(value);
+                        } else if key == "model" {
+                            action = action.with_model(value);
+                        } else if key == "temperature" {
+                            action = action.with_temperature(Self::parse_temperature(&value)?);
+                        } else if key == "max_tokens" {
+                            action = action.with_max_output_tokens(Self::parse_max_tokens(&value)?);
+                        } else if key == "system_prompt" {
+                            action = action.with_system_prompt(value);
+                        } else if key == "session" {
+                            action = action.with_continue_session(Self::parse_session(&value)?);
+                        } else if key == "backend" {
+                            action = action.with_backend(Self::parse_backend(&value)?);
+                        } else if key == "on_extract_failure" {
+                            action = action.with_extract_fallback_state(value);
+                        } else if let Some(variable) = key.strip_prefix("extract_") {
+                            if !self.is_valid_variable_name(variable) {
+                                return Err(ActionError::ParseError(format!(
+                                    "Invalid variable name '{variable}' in '{key}'"
+                                )));
+                            }
+                            action =
+                                action.with_extractor(OutputExtractor::parse(variable, &value)?);
                         } else {
                             if !self.is_valid_argument_key(&key) {
                                 return Err(ActionError::ParseError(
@@ -354,6 +440,158 @@ impl ActionParser {
         }
     }
 
+    /// Parse `<verb> <noun> "name" with key="value" ...` as used by the issue
+    /// and memo actions, returning the name and the arguments by key
+    fn parse_storage_action(
+        verb: &'static str,
+        noun: &'static str,
+        description: &str,
+    ) -> ActionResult<Option<(String, HashMap<String, String>)>> {
+        let parser = Self::case_insensitive(verb)
+            .then_ignore(Self::whitespace())
+            .then_ignore(Self::case_insensitive(noun))
+            .then_ignore(Self::whitespace())
+            .ignore_then(Self::quoted_string())
+            .then(
+                Self::whitespace()
+                    .ignore_then(Self::case_insensitive("with"))
+                    .ignore_then(Self::whitespace())
+                    .ignore_then(
+                        Self::argument_key()
+                            .then_ignore(just('='))
+                            .then(Self::quoted_string())
+                            .separated_by(Self::whitespace())
+                            .collect::<Vec<(String, String)>>(),
+                    )
+                    .or_not(),
+            );
+
+        let Ok((name, args)) = parser.parse(description.trim()).into_result() else {
+            return Ok(None);
+        };
+        let mut arguments = HashMap::new();
+        for (key, value) in args.unwrap_or_default() {
+            if !matches!(key.as_str(), "content" | "result" | "append") {
+                return Err(ActionError::ParseError(format!(
+                    "Unknown argument '{key}' for {verb} {noun}: expected content, result or append"
+                )));
+            }
+            arguments.insert(key, value);
+        }
+        if !arguments.contains_key("content") {
+            return Err(ActionError::ParseError(format!(
+                "{verb} {noun} requires content=\"...\""
+            )));
+        }
+        Ok(Some((name, arguments)))
+    }
+
+    /// Parse a create issue action from description
+    /// Format: Create issue "issue-name" with content="..." [result="variable"]
+    pub fn parse_create_issue_action(
+        &self,
+        description: &str,
+    ) -> ActionResult<Option<CreateIssueAction>> {
+        let Some((name, mut args)) = Self::parse_storage_action("create", "issue", description)?
+        else {
+            return Ok(None);
+        };
+        if args.contains_key("append") {
+            return Err(ActionError::ParseError(
+                "append is only supported when updating an issue".to_string(),
+            ));
+        }
+        let mut action = CreateIssueAction::new(name, args.remove("content").unwrap_or_default());
+        if let Some(variable) = args.remove("result") {
+            action = action.with_result_variable(variable);
+        }
+        Ok(Some(action))
+    }
+
+    /// Parse an update issue action from description
+    /// Format: Update issue "issue-name" with content="..." [append="true"] [result="variable"]
+    pub fn parse_update_issue_action(
+        &self,
+        description: &str,
+    ) -> ActionResult<Option<UpdateIssueAction>> {
+        let Some((name, mut args)) = Self::parse_storage_action("update", "issue", description)?
+        else {
+            return Ok(None);
+        };
+        let mut action = UpdateIssueAction::new(name, args.remove("content").unwrap_or_default());
+        if let Some(append) = args.remove("append") {
+            let append = append.parse::<bool>().map_err(|_| {
+                ActionError::ParseError(format!(
+                    "Invalid append '{append}': must be 'true' or 'false'"
+                ))
+            })?;
+            action = action.with_append(append);
+        }
+        if let Some(variable) = args.remove("result") {
+            action = action.with_result_variable(variable);
+        }
+        Ok(Some(action))
+    }
+
+    /// Parse a create memo action from description
+    /// Format: Create memo "title" with content="..." [result="variable"]
+    pub fn parse_create_memo_action(
+        &self,
+        description: &str,
+    ) -> ActionResult<Option<CreateMemoAction>> {
+        let Some((title, mut args)) = Self::parse_storage_action("create", "memo", description)?
+        else {
+            return Ok(None);
+        };
+        if args.contains_key("append") {
+            return Err(ActionError::ParseError(
+                "append is only supported when updating an issue".to_string(),
+            ));
+        }
+        let mut action = CreateMemoAction::new(title, args.remove("content").unwrap_or_default());
+        if let Some(variable) = args.remove("result") {
+            action = action.with_result_variable(variable);
+        }
+        Ok(Some(action))
+    }
+
+    /// Parse a save artifact action from description
+    /// Format: Save artifact "path" [as patch|report|log|data|file]
+    pub fn parse_save_artifact_action(
+        &self,
+        description: &str,
+    ) -> ActionResult<Option<SaveArtifactAction>> {
+        let kind = Self::whitespace()
+            .ignore_then(Self::case_insensitive("as"))
+            .ignore_then(Self::whitespace())
+            .ignore_then(
+                any()
+                    .filter(|c: &char| c.is_alphabetic())
+                    .repeated()
+                    .at_least(1)
+                    .collect::<String>(),
+            )
+            .or_not();
+        let parser = Self::case_insensitive("save")
+            .then_ignore(Self::whitespace())
+            .then_ignore(Self::case_insensitive("artifact"))
+            .then_ignore(Self::whitespace())
+            .ignore_then(Self::quoted_string())
+            .then(kind);
+
+        let Ok((path, kind)) = parser.parse(description.trim()).into_result() else {
+            return Ok(None);
+        };
+        let mut action = SaveArtifactAction::new(path);
+        if let Some(kind) = kind {
+            action = action.with_kind(
+                kind.parse::<ArtifactKind>()
+                    .map_err(ActionError::ParseError)?,
+            );
+        }
+        Ok(Some(action))
+    }
+
     /// Parse a shell action from description
     /// Format: Shell "command" [with timeout=N] [result="variable"] [working_dir="path"] [env={"KEY": "value"}]
     pub fn parse_shell_action(&self, description: &str) -> ActionResult<Option<ShellAction>> {
@@ -619,6 +857,7 @@ impl Default for ActionParser {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::prompt_refs::PromptPin;
 
     #[test]
     fn test_parse_prompt_action() {
@@ -648,6 +887,145 @@ mod tests {
         assert!(result.unwrap().is_none());
     }
 
+    #[test]
+    fn test_parse_prompt_action_with_claude_settings() {
+        let parser = ActionParser::new().unwrap();
+
+        let action = parser
+            .parse_prompt_action(
+                "Execute prompt \"review\" with file=\"main.rs\" model=\"opus\" temperature=\"0.3\" max_tokens=\"8000\" system_prompt=\"You are a reviewer\"",
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(action.arguments.len(), 1);
+        assert_eq!(action.arguments.get("file"), Some(&"main.rs".to_string()));
+        assert_eq!(action.claude.model.as_deref(), Some("opus"));
+        assert_eq!(action.claude.temperature, Some(0.3));
+        assert_eq!(action.claude.max_output_tokens, Some(8000));
+        assert_eq!(
+            action.claude.system_prompt.as_deref(),
+            Some("You are a reviewer")
+        );
+
+        assert!(parser
+            .parse_prompt_action("Execute prompt \"review\" with temperature=\"1.5\"")
+            .is_err());
+        assert!(parser
+            .parse_prompt_action("Execute prompt \"review\" with max_tokens=\"0\"")
+            .is_err());
+        assert!(parser
+            .parse_prompt_action("Execute prompt \"review\" with max_tokens=\"lots\"")
+            .is_err());
+    }
+
+    #[test]
+    fn test_parse_prompt_action_extractors() {
+        let parser = ActionParser::new().unwrap();
+
+        let action = parser
+            .parse_prompt_action(
+                "Execute prompt \"review\" with file=\"main.rs\" extract_verdict=\"json:$.verdict\" extract_count=\"regex:Found (\\d+) issues as integer\" on_extract_failure=\"retry\"",
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(action.arguments.len(), 1);
+        assert_eq!(action.extractors.len(), 2);
+        assert_eq!(action.extractors[0].variable, "verdict");
+        assert_eq!(action.extractors[1].variable, "count");
+        assert_eq!(action.extract_fallback_state.as_deref(), Some("retry"));
+
+        assert!(parser
+            .parse_prompt_action("Execute prompt \"review\" with extract_count=\"xml:/count\"")
+            .is_err());
+        assert!(parser
+            .parse_prompt_action("Execute prompt \"review\" with extract_2x=\"code\"")
+            .is_err());
+    }
+
+    #[test]
+    fn test_parse_pinned_prompt_action() {
+        let parser = ActionParser::new().unwrap();
+
+        let action = parser
+            .parse_prompt_action(
+                "Execute prompt \"code-review@sha256:abcd1234\" with file=\"a.rs\"",
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(action.prompt_name, "code-review");
+        assert_eq!(action.pin, Some(PromptPin::Hash("abcd1234".to_string())));
+        assert_eq!(
+            action.prompt_ref().to_string(),
+            "code-review@sha256:abcd1234"
+        );
+
+        let action = parser
+            .parse_prompt_action("Execute prompt \"code-review@1.2\"")
+            .unwrap()
+            .unwrap();
+        assert_eq!(action.pin, Some(PromptPin::Version("1.2".to_string())));
+
+        assert!(parser
+            .parse_prompt_action("Execute prompt \"code-review@sha256:xyz\"")
+            .is_err());
+    }
+
+    #[test]
+    fn test_parse_prompt_action_session() {
+        let parser = ActionParser::new().unwrap();
+
+        let action = parser
+            .parse_prompt_action("Execute prompt \"review\"")
+            .unwrap()
+            .unwrap();
+        assert!(action.continue_session);
+
+        let action = parser
+            .parse_prompt_action("Execute prompt \"review\" with session=\"new\"")
+            .unwrap()
+            .unwrap();
+        assert!(!action.continue_session);
+        assert!(action.arguments.is_empty());
+
+        let action = parser
+            .parse_prompt_action("Execute prompt \"review\" with session=\"continue\"")
+            .unwrap()
+            .unwrap();
+        assert!(action.continue_session);
+
+        assert!(parser
+            .parse_prompt_action("Execute prompt \"review\" with session=\"forever\"")
+            .is_err());
+    }
+
+    #[test]
+    fn test_parse_prompt_action_backend() {
+        let parser = ActionParser::new().unwrap();
+
+        let action = parser
+            .parse_prompt_action("Execute prompt \"review\"")
+            .unwrap()
+            .unwrap();
+        assert_eq!(action.backend, None);
+
+        let action = parser
+            .parse_prompt_action("Execute prompt \"review\" with backend=\"sampling\"")
+            .unwrap()
+            .unwrap();
+        assert_eq!(action.backend, Some(PromptBackend::Sampling));
+        assert!(action.arguments.is_empty());
+
+        let action = parser
+            .parse_prompt_action("Execute prompt \"review\" with backend=\"openai\"")
+            .unwrap()
+            .unwrap();
+        assert_eq!(action.backend, Some(PromptBackend::OpenAi));
+
+        assert!(parser
+            .parse_prompt_action("Execute prompt \"review\" with backend=\"gemini\"")
+            .is_err());
+    }
+
     #[test]
     fn test_parse_wait_action() {
         let parser = ActionParser::new().unwrap();
@@ -3,8 +3,24 @@
 //! This module provides the action execution infrastructure for workflows,
 //! including Claude integration, variable operations, and control flow actions.
 
+use crate::config::Config;
+use crate::cost::{CostEntry, CostTracker};
+use crate::git::GitOperations;
+use crate::issues::{get_current_issue_from_branch, issue_storage_for_work_dir};
+use crate::prompt_refs::{PromptPin, PromptRef, ResolvedPrompt};
+use crate::security::{validate_path, PathPolicy};
 use crate::workflow::action_parser::ActionParser;
-use crate::workflow::{WorkflowExecutor, WorkflowName, WorkflowRunStatus, WorkflowStorage};
+use crate::workflow::context_window::{
+    carry_over, conversation_tokens, estimate_tokens, set_conversation_tokens,
+};
+use crate::workflow::extractors::OutputExtractor;
+use crate::workflow::providers::{ModelRequest, ModelResponse, PromptBackend};
+use crate::workflow::{
+    ArtifactKind, CompactionStrategy, ContextWindowSettings, PromptRecord, RunArtifacts, RunPins,
+    StateId, WorkflowExecutor, WorkflowName, WorkflowRunId, WorkflowRunStatus, WorkflowStorage,
+    PINNED_PROMPTS_ENV, PIN_DIR_KEY,
+};
+use crate::{PromptLibrary, PromptResolver};
 use serde_json::Value;
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
@@ -84,6 +100,14 @@ pub enum ActionError {
     /// Abort error that should exit workflow immediately
     #[error("ABORT ERROR: {0}")]
     AbortError(String),
+    /// A value could not be extracted from Claude output
+    #[error("Output extraction failed: {message}")]
+    ExtractionError {
+        /// Why the value could not be extracted
+        message: String,
+        /// State the workflow moves to instead of failing, if any
+        fallback_state: Option<String>,
+    },
 }
 
 /// Result type for action operations
@@ -125,6 +149,46 @@ impl Default for ActionTimeouts {
     }
 }
 
+/// Model settings for a prompt action
+///
+/// Unset values fall back to the defaults in [`Config`], and anything still
+/// unset is left to the model provider's own defaults.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ClaudeSettings {
+    /// Model name or alias, such as the one passed to `claude --model`
+    pub model: Option<String>,
+    /// Sampling temperature between 0.0 and 1.0
+    pub temperature: Option<f32>,
+    /// Maximum number of output tokens
+    pub max_output_tokens: Option<u32>,
+    /// System prompt that replaces Claude's default system prompt
+    pub system_prompt: Option<String>,
+}
+
+impl ClaudeSettings {
+    /// Default settings from the application configuration
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            model: config.claude_model.clone(),
+            temperature: config.claude_temperature,
+            max_output_tokens: config.claude_max_output_tokens,
+            system_prompt: config.claude_system_prompt.clone(),
+        }
+    }
+
+    /// Fill any unset values from `defaults`
+    pub fn with_defaults(self, defaults: &ClaudeSettings) -> Self {
+        Self {
+            model: self.model.or_else(|| defaults.model.clone()),
+            temperature: self.temperature.or(defaults.temperature),
+            max_output_tokens: self.max_output_tokens.or(defaults.max_output_tokens),
+            system_prompt: self
+                .system_prompt
+                .or_else(|| defaults.system_prompt.clone()),
+        }
+    }
+}
+
 /// Type-safe context keys for workflow execution
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ContextKey {
@@ -176,6 +240,24 @@ const LAST_ACTION_RESULT_KEY: &str = "last_action_result";
 /// Context key for workflow execution stack (for circular dependency detection)
 const WORKFLOW_STACK_KEY: &str = "_workflow_stack";
 
+/// Context key for the Claude session of the most recent prompt in a run
+pub const CLAUDE_SESSION_ID_KEY: &str = "_claude_session_id";
+
+/// Context key for the directory Claude and shell commands run in
+///
+/// Runs that work in a separate checkout, such as an issue worktree, set it
+/// so every action and sub-workflow runs there instead of the current directory.
+pub const WORKING_DIR_KEY: &str = "_working_dir";
+
+/// Context key for the ID of the workflow run actions execute in
+pub const RUN_ID_KEY: &str = "_run_id";
+
+/// Context key for the name of the workflow actions execute in
+pub const WORKFLOW_NAME_KEY: &str = "_workflow_name";
+
+/// Context key for the state whose action is executing
+pub const STATE_ID_KEY: &str = "_state_id";
+
 /// Trait for all workflow actions
 #[async_trait::async_trait]
 pub trait Action: Send + Sync {
@@ -233,6 +315,23 @@ pub struct PromptAction {
     ///
     /// The quiet mode can also be controlled via the `_quiet` context variable in workflows.
     pub quiet: bool,
+    /// Claude model settings, falling back to [`Config`] defaults when unset
+    pub claude: ClaudeSettings,
+    /// Whether to continue the Claude conversation of the previous prompt in
+    /// the run instead of starting a new one
+    ///
+    /// Continuing resumes the session stored under [`CLAUDE_SESSION_ID_KEY`],
+    /// so Claude already has the context earlier states sent.
+    pub continue_session: bool,
+    /// Extractors storing typed values from the response in variables
+    pub extractors: Vec<OutputExtractor>,
+    /// State to move to when an extractor finds no value, instead of failing
+    pub extract_fallback_state: Option<String>,
+    /// Where the rendered prompt is sent, falling back to the workflow's
+    /// backend and then the configured one when unset
+    pub backend: Option<PromptBackend>,
+    /// Content the prompt must resolve to, when the workflow pins it
+    pub pin: Option<PromptPin>,
 }
 
 impl PromptAction {
@@ -245,6 +344,12 @@ impl PromptAction {
             result_variable: None,
             timeout: timeouts.prompt_timeout,
             quiet: false, // Default to showing output
+            claude: ClaudeSettings::default(),
+            continue_session: true,
+            extractors: Vec::new(),
+            extract_fallback_state: None,
+            backend: None,
+            pin: None,
         }
     }
 
@@ -272,8 +377,233 @@ impl PromptAction {
         self
     }
 
-    /// Substitute variables in arguments using the context
-    fn substitute_variables(&self, context: &HashMap<String, Value>) -> HashMap<String, String> {
+    /// Set the Claude model
+    pub fn with_model(mut self, model: String) -> Self {
+        self.claude.model = Some(model);
+        self
+    }
+
+    /// Set the sampling temperature
+    pub fn with_temperature(mut self, temperature: f32) -> Self {
+        self.claude.temperature = Some(temperature);
+        self
+    }
+
+    /// Set the maximum number of output tokens
+    pub fn with_max_output_tokens(mut self, max_output_tokens: u32) -> Self {
+        self.claude.max_output_tokens = Some(max_output_tokens);
+        self
+    }
+
+    /// Set the system prompt override
+    pub fn with_system_prompt(mut self, system_prompt: String) -> Self {
+        self.claude.system_prompt = Some(system_prompt);
+        self
+    }
+
+    /// Set whether to continue the Claude conversation of the previous prompt
+    pub fn with_continue_session(mut self, continue_session: bool) -> Self {
+        self.continue_session = continue_session;
+        self
+    }
+
+    /// Add an extractor storing a value from the response in a variable
+    pub fn with_extractor(mut self, extractor: OutputExtractor) -> Self {
+        self.extractors.push(extractor);
+        self
+    }
+
+    /// Set the state to move to when an extractor finds no value
+    pub fn with_extract_fallback_state(mut self, state: String) -> Self {
+        self.extract_fallback_state = Some(state);
+        self
+    }
+
+    /// Set where the rendered prompt is sent
+    pub fn with_backend(mut self, backend: PromptBackend) -> Self {
+        self.backend = Some(backend);
+        self
+    }
+
+    /// Pin the prompt to a content hash or version
+    pub fn with_pin(mut self, pin: Option<PromptPin>) -> Self {
+        self.pin = pin;
+        self
+    }
+
+    /// The reference the workflow names the prompt by, with its pin
+    pub fn prompt_ref(&self) -> PromptRef {
+        PromptRef {
+            name: self.prompt_name.clone(),
+            pin: self.pin.clone(),
+        }
+    }
+
+    /// Run the extractors on a response and store their values in the context
+    ///
+    /// Every extractor runs before an error is returned, so the context holds
+    /// all the values that could be extracted.
+    pub fn apply_extractors(
+        &self,
+        response: &str,
+        context: &mut HashMap<String, Value>,
+    ) -> ActionResult<()> {
+        let mut failures = Vec::new();
+        for extractor in &self.extractors {
+            match extractor.extract(response) {
+                Ok(value) => {
+                    context.insert(extractor.variable.clone(), value);
+                }
+                Err(message) => failures.push(message),
+            }
+        }
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(ActionError::ExtractionError {
+                message: failures.join("; "),
+                fallback_state: self.extract_fallback_state.clone(),
+            })
+        }
+    }
+
+    /// Claude session to resume, if this action continues the run's conversation
+    pub fn session_to_resume(&self, context: &HashMap<String, Value>) -> Option<String> {
+        if !self.continue_session {
+            return None;
+        }
+        context
+            .get(CLAUDE_SESSION_ID_KEY)
+            .and_then(|session_id| session_id.as_str())
+            .map(str::to_string)
+    }
+
+    /// Compact the run's Claude conversation if sending `rendered_prompt` would
+    /// take it past the context window limits, returning the prompt to send
+    ///
+    /// A compacted conversation is not resumed: the prompt starts a new one,
+    /// carrying a summary of the old conversation or its latest responses. When
+    /// summarizing fails, the latest responses are carried instead.
+    async fn compact_conversation(
+        &self,
+        rendered_prompt: String,
+        context: &mut HashMap<String, Value>,
+        quiet: bool,
+        cost_tracker: Option<&PromptCostTracker>,
+    ) -> String {
+        let window = ContextWindowSettings::from_context(context);
+        if !matches!(self.resolve_backend(context), Ok(PromptBackend::Claude))
+            || self.session_to_resume(context).is_none()
+            || !window.needs_compaction(context, &rendered_prompt)
+        {
+            return rendered_prompt;
+        }
+        tracing::info!(
+            "Compacting the Claude conversation before '{}': {} of {} tokens used",
+            self.prompt_name,
+            conversation_tokens(context),
+            window.max_tokens
+        );
+
+        let kept = match window.strategy {
+            CompactionStrategy::Summarize => {
+                match self
+                    .summarize_conversation(&window, context, quiet, cost_tracker)
+                    .await
+                {
+                    Ok(summary) => summary,
+                    Err(e) => {
+                        tracing::warn!("Failed to summarize the conversation: {}", e);
+                        window.latest_responses(context)
+                    }
+                }
+            }
+            CompactionStrategy::Truncate => window.latest_responses(context),
+        };
+        context.remove(CLAUDE_SESSION_ID_KEY);
+        set_conversation_tokens(context, 0);
+        carry_over(&kept, &rendered_prompt)
+    }
+
+    /// Ask Claude to summarize the run's conversation with the workflow's
+    /// summary prompt
+    ///
+    /// The summary is a prompt like any other: it is refused once the cost
+    /// budget is spent, and its cost is recorded against the summary prompt.
+    async fn summarize_conversation(
+        &self,
+        window: &ContextWindowSettings,
+        context: &HashMap<String, Value>,
+        quiet: bool,
+        cost_tracker: Option<&PromptCostTracker>,
+    ) -> ActionResult<String> {
+        check_cost_budget(cost_tracker)?;
+
+        let summarizer = PromptAction {
+            prompt_name: window.summary_prompt.clone(),
+            arguments: HashMap::new(),
+            result_variable: None,
+            extractors: Vec::new(),
+            extract_fallback_state: None,
+            pin: None,
+            ..self.clone()
+        };
+        let prompt = summarizer
+            .render_prompt_with_swissarmyhammer(context)
+            .await?;
+        let summary = summarizer
+            .execute_with_provider(&prompt, context, quiet)
+            .await?;
+        summarizer.record_cost(cost_tracker, summary.cost_usd, context);
+        Ok(summary.text.trim().to_string())
+    }
+
+    /// The backend the prompt is sent to: the action's own, else the one its
+    /// workflow chose, else the configured one
+    pub fn resolve_backend(&self, context: &HashMap<String, Value>) -> ActionResult<PromptBackend> {
+        match self
+            .backend
+            .or_else(|| PromptBackend::from_context(context))
+        {
+            Some(backend) => Ok(backend),
+            None => PromptBackend::from_config(Config::global())
+                .map_err(|e| ActionError::ParseError(e.to_string())),
+        }
+    }
+
+    /// Resolve the model settings for execution, substituting variables and
+    /// applying the defaults of the backend
+    fn resolve_claude_settings(
+        &self,
+        context: &HashMap<String, Value>,
+        backend: PromptBackend,
+    ) -> ClaudeSettings {
+        let settings = ClaudeSettings {
+            model: self
+                .claude
+                .model
+                .as_ref()
+                .map(|model| self.substitute_string(model, context)),
+            system_prompt: self
+                .claude
+                .system_prompt
+                .as_ref()
+                .map(|prompt| self.substitute_string(prompt, context)),
+            ..self.claude.clone()
+        };
+        let config = Config::global();
+        let defaults = ClaudeSettings {
+            model: backend.default_model(config),
+            ..ClaudeSettings::from_config(config)
+        };
+        settings.with_defaults(&defaults)
+    }
+
+    /// Prompt arguments with `${variable}` references substituted from the context
+    pub fn substitute_variables(
+        &self,
+        context: &HashMap<String, Value>,
+    ) -> HashMap<String, String> {
         self.substitute_map(&self.arguments, context)
     }
 }
@@ -289,7 +619,8 @@ impl Action for PromptAction {
     fn description(&self) -> String {
         format!(
             "Execute prompt '{}' with arguments: {:?}",
-            self.prompt_name, self.arguments
+            self.prompt_ref(),
+            self.arguments
         )
     }
 
@@ -347,6 +678,30 @@ fn resolve_swissarmyhammer_binary() -> std::path::PathBuf {
     }
 }
 
+/// Cost tracker with the issue whose budget prompts count against
+type PromptCostTracker = (CostTracker, Option<String>);
+
+/// Cost tracker and the issue being worked
+fn prompt_cost_tracker() -> Option<PromptCostTracker> {
+    let tracker = CostTracker::new_default().ok()?;
+    let issue = GitOperations::new()
+        .ok()
+        .and_then(|git_ops| get_current_issue_from_branch(&git_ops).ok().flatten());
+    Some((tracker, issue))
+}
+
+/// Refuse to start a prompt once a cost budget with a hard limit is spent
+fn check_cost_budget(cost_tracker: Option<&PromptCostTracker>) -> ActionResult<()> {
+    if let Some((tracker, issue)) = cost_tracker {
+        match tracker.check(issue.as_deref(), chrono::Utc::now()) {
+            Ok(Some(refusal)) => return Err(ActionError::AbortError(refusal.to_string())),
+            Ok(None) => {}
+            Err(e) => tracing::warn!("Failed to check cost budget: {}", e),
+        }
+    }
+    Ok(())
+}
+
 impl PromptAction {
     /// Render the prompt using swissarmyhammer prompt test
     async fn render_prompt_with_swissarmyhammer(
@@ -360,11 +715,24 @@ impl PromptAction {
         let cmd_binary = resolve_swissarmyhammer_binary();
 
         let mut cmd = Command::new(&cmd_binary);
+        if let Some(working_dir) = context.get(WORKING_DIR_KEY).and_then(Value::as_str) {
+            cmd.current_dir(working_dir);
+        }
         cmd.arg("prompt")
             .arg("test")
             .arg(&self.prompt_name)
             .arg("--raw"); // Get raw output without formatting
 
+        // Render the prompts this run pinned, not files edited since it started
+        if let Some(pins) = RunPins::from_context(context) {
+            match pins.pin_prompts() {
+                Ok(_) => {
+                    cmd.env(PINNED_PROMPTS_ENV, pins.dir());
+                }
+                Err(e) => tracing::warn!("Failed to pin prompts, rendering current files: {e}"),
+            }
+        }
+
         // Add arguments
         for (key, value) in &args {
             if !is_valid_argument_key(key) {
@@ -379,7 +747,8 @@ impl PromptAction {
         // Set up process pipes
         cmd.stdout(std::process::Stdio::piped())
             .stderr(std::process::Stdio::piped())
-            .stdin(std::process::Stdio::null());
+            .stdin(std::process::Stdio::null())
+            .kill_on_drop(true);
 
         // Execute the command
         let output = cmd.output().await.map_err(|e| {
@@ -400,6 +769,79 @@ impl PromptAction {
         Ok(stdout.to_string())
     }
 
+    /// Resolve the prompt this action renders, failing when the content it
+    /// is pinned to is missing
+    ///
+    /// Prompts a run pinned are loaded from its pin directory, so the hash is
+    /// that of the template just rendered.
+    fn resolve_prompt(&self, context: &HashMap<String, Value>) -> ActionResult<ResolvedPrompt> {
+        let mut resolver = PromptResolver::new();
+        if let Some(pins) = RunPins::from_context(context) {
+            resolver = resolver.with_pinned_dir(pins.dir().to_path_buf());
+        }
+        let mut library = PromptLibrary::new();
+        let prompt = resolver
+            .load_all_prompts(&mut library)
+            .and_then(|()| library.get(&self.prompt_name))
+            .map_err(|e| {
+                ActionError::ExecutionError(format!(
+                    "Failed to load prompt '{}': {e}",
+                    self.prompt_name
+                ))
+            })?;
+        let resolved = self
+            .prompt_ref()
+            .resolve(&prompt)
+            .map_err(|e| ActionError::ExecutionError(e.to_string()))?;
+        tracing::info!(
+            "Prompt '{}' resolved to {}",
+            self.prompt_name,
+            resolved.hash
+        );
+        Ok(resolved)
+    }
+
+    /// Send a rendered prompt to the model provider of the action's backend
+    async fn execute_with_provider(
+        &self,
+        rendered_prompt: &str,
+        context: &HashMap<String, Value>,
+        quiet: bool,
+    ) -> ActionResult<ModelResponse> {
+        let backend = self.resolve_backend(context)?;
+        let provider = backend
+            .provider(Config::global())
+            .map_err(|e| ActionError::ClaudeError(e.to_string()))?;
+        let settings = self.resolve_claude_settings(context, backend);
+        let request = ModelRequest {
+            prompt: rendered_prompt.to_string(),
+            system_prompt: settings.system_prompt,
+            model: settings.model,
+            temperature: settings.temperature,
+            max_tokens: settings.max_output_tokens,
+            session_id: self.session_to_resume(context),
+            working_dir: context
+                .get(WORKING_DIR_KEY)
+                .and_then(Value::as_str)
+                .map(std::path::PathBuf::from),
+            // Get timeout from context or use default
+            timeout: context
+                .get("_timeout_secs")
+                .and_then(|v| v.as_u64())
+                .map(Duration::from_secs)
+                .unwrap_or_else(|| Duration::from_secs(60 * 60)),
+        };
+
+        if !quiet {
+            tracing::info!(
+                "Executing prompt '{}' with the {}",
+                self.prompt_name,
+                provider.name()
+            );
+        }
+        provider.generate(request).await
+    }
+
     /// Execute the command once without retry logic
     ///
     /// This method performs a single execution attempt of the Claude command.
@@ -415,8 +857,16 @@ impl PromptAction {
         &self,
         context: &mut HashMap<String, Value>,
     ) -> ActionResult<Value> {
+        // Arguments as substituted before this prompt changes the context
+        let arguments = self.substitute_variables(context);
+
+        // Refuse to start once a cost budget with a hard limit is spent
+        let cost_tracker = prompt_cost_tracker();
+        check_cost_budget(cost_tracker.as_ref())?;
+
         // First, render the prompt using swissarmyhammer
         let rendered_prompt = self.render_prompt_with_swissarmyhammer(context).await?;
+        let resolved = self.resolve_prompt(context)?;
 
         // Log the actual prompt being sent to Claude
         tracing::debug!("Piping prompt to Claude:\n{}", rendered_prompt);
@@ -428,184 +878,21 @@ impl PromptAction {
                 .and_then(|v| v.as_bool())
                 .unwrap_or(false);
 
-        // Execute the rendered prompt with Claude
-        // Find claude in PATH or use common locations
-        let claude_path = which::which("claude")
-            .or_else(|_| {
-                // Check common installation paths
-                let home = std::env::var("HOME").unwrap_or_default();
-                let possible_paths = vec![
-                    format!("{}/.claude/local/claude", home),
-                    "/usr/local/bin/claude".to_string(),
-                    "/opt/claude/claude".to_string(),
-                ];
-
-                for path in possible_paths {
-                    if std::path::Path::new(&path).exists() {
-                        return Ok(std::path::PathBuf::from(path));
-                    }
-                }
-
-                Err(which::Error::CannotFindBinaryPath)
-            })
-            .map_err(|e| {
-                ActionError::ClaudeError(format!(
-                    "Claude CLI not found. Make sure 'claude' is installed and available in your PATH. Error: {e}"
-                ))
-            })?;
-        let mut cmd = Command::new(&claude_path);
-
-        // Claude CLI arguments
-        cmd.arg("--dangerously-skip-permissions")
-            .arg("--print")
-            .arg("--output-format")
-            .arg("stream-json")
-            .arg("--verbose");
-
-        // Set up the command to pipe prompt via stdin
-        cmd.stdin(std::process::Stdio::piped())
-            .stdout(std::process::Stdio::piped())
-            .stderr(std::process::Stdio::piped());
-
-        if !quiet {
-            tracing::info!(
-                "Executing prompt '{}' with Claude at: {}",
-                self.prompt_name,
-                claude_path.display()
-            );
-        }
-
-        // Spawn the Claude process
-        let mut child = cmd.spawn().map_err(|e| {
-            ActionError::ClaudeError(format!("Failed to spawn Claude command: {e}"))
-        })?;
-
-        // Write the prompt to Claude's stdin
-        if let Some(mut stdin) = child.stdin.take() {
-            use tokio::io::AsyncWriteExt;
-            stdin
-                .write_all(rendered_prompt.as_bytes())
-                .await
-                .map_err(|e| {
-                    ActionError::ClaudeError(format!("Failed to write prompt to Claude: {e}"))
-                })?;
-            stdin.shutdown().await.map_err(|e| {
-                ActionError::ClaudeError(format!("Failed to close Claude stdin: {e}"))
-            })?;
-        }
-
-        // Get stdout for streaming
-        let stdout = child.stdout.take().ok_or_else(|| {
-            ActionError::ClaudeError("Failed to capture Claude stdout".to_string())
-        })?;
-
-        // Read stdout line by line for streaming JSON
-        use tokio::io::{AsyncBufReadExt, BufReader};
-        let reader = BufReader::new(stdout);
-        let mut lines = reader.lines();
-
-        let mut response_text = String::new();
-        let mut _got_result = false;
-
-        // Get timeout from context or use default
-        let line_timeout = context
-            .get("_timeout_secs")
-            .and_then(|v| v.as_u64())
-            .map(Duration::from_secs)
-            .unwrap_or_else(|| Duration::from_secs(60 * 60));
-
-        tracing::debug!("Using line timeout: {:?}", line_timeout);
-
-        // Read lines with timeout
-        loop {
-            match timeout(line_timeout, lines.next_line()).await {
-                Ok(Ok(Some(line))) => {
-                    // Format JSON as YAML for better readability
-                    let formatted_output = format_claude_output_as_yaml(&line);
-                    if formatted_output != line {
-                        // If it was formatted as YAML, log it with proper structure
-                        tracing::debug!("Claude output line:\n{}", formatted_output);
-                    } else {
-                        // If not JSON, log as is
-                        tracing::debug!("Claude output line: {}", line);
-                    }
-
-                    if line.trim().is_empty() {
-                        continue;
-                    }
-
-                    if let Ok(json) = serde_json::from_str::<Value>(&line) {
-                        // Look for the final result
-                        if let Some(result) = json.get("result").and_then(|r| r.as_str()) {
-                            response_text = result.to_string();
-                            _got_result = true;
-                            break;
-                        }
-                        // Also check for assistant messages with text content
-                        if json.get("type").and_then(|t| t.as_str()) == Some("assistant") {
-                            if let Some(message) = json.get("message").and_then(|m| m.as_object()) {
-                                if let Some(content_array) =
-                                    message.get("content").and_then(|c| c.as_array())
-                                {
-                                    for content_item in content_array {
-                                        if let Some(text) =
-                                            content_item.get("text").and_then(|t| t.as_str())
-                                        {
-                                            response_text.push_str(text);
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-                Ok(Ok(None)) => {
-                    // End of stream
-                    break;
-                }
-                Ok(Err(e)) => {
-                    tracing::error!("Error reading Claude output: {}", e);
-                    break;
-                }
-                Err(_) => {
-                    // Timeout - kill the process and return error
-                    tracing::error!("Timeout reading Claude output after {:?}", line_timeout);
-                    tracing::error!("Response so far: {} characters", response_text.len());
-                    let _ = child.kill().await;
-                    // If we have some response, use it rather than erroring
-                    if !response_text.is_empty() {
-                        break;
-                    }
-                    return Err(ActionError::Timeout {
-                        timeout: line_timeout,
-                    });
-                }
-            }
-        }
-
-        // Wait for process to complete with a short timeout
-        let wait_result = timeout(Duration::from_secs(5), child.wait()).await;
-        let status = match wait_result {
-            Ok(Ok(status)) => status,
-            Ok(Err(e)) => {
-                return Err(ActionError::ClaudeError(format!(
-                    "Failed to wait for Claude: {e}"
-                )))
-            }
-            Err(_) => {
-                // Process didn't exit cleanly, kill it
-                let _ = child.kill().await;
-                return Err(ActionError::ClaudeError(
-                    "Claude process failed to exit cleanly".to_string(),
-                ));
-            }
-        };
-
-        if !status.success() {
-            return Err(ActionError::ClaudeError(
-                "Claude execution failed".to_string(),
-            ));
-        }
+        // Start a new conversation when continuing would overflow the context window
+        let rendered_prompt = self
+            .compact_conversation(rendered_prompt, context, quiet, cost_tracker.as_ref())
+            .await;
+        let continued = self.session_to_resume(context).is_some();
+        let prompt_tokens = estimate_tokens(&rendered_prompt);
+
+        let ModelResponse {
+            text: response_text,
+            cost_usd,
+            tokens,
+            session_id,
+        } = self
+            .execute_with_provider(&rendered_prompt, context, quiet)
+            .await?;
 
         let response_text = response_text.trim();
 
@@ -649,8 +936,68 @@ impl PromptAction {
         context.insert(LAST_ACTION_RESULT_KEY.to_string(), Value::Bool(true));
         context.insert(CLAUDE_RESPONSE_KEY.to_string(), response.clone());
 
+        // Later prompts in the run continue this conversation
+        if let Some(session_id) = session_id {
+            context.insert(CLAUDE_SESSION_ID_KEY.to_string(), Value::String(session_id));
+            let turn_tokens =
+                tokens.unwrap_or_else(|| prompt_tokens + estimate_tokens(response_text));
+            let earlier_tokens = if continued {
+                conversation_tokens(context)
+            } else {
+                0
+            };
+            set_conversation_tokens(context, earlier_tokens + turn_tokens);
+        }
+
+        // Keep a record of the prompt so runs can be compared later
+        PromptRecord {
+            prompt: self.prompt_name.clone(),
+            arguments: arguments.into_iter().collect(),
+            response: response_text.to_string(),
+            cost_usd,
+            tokens,
+            hash: Some(resolved.hash),
+        }
+        .record(context);
+
+        self.record_cost(cost_tracker.as_ref(), cost_usd, context);
+
+        self.apply_extractors(response_text, context)?;
+
         Ok(response)
     }
+
+    /// Record what sending the prompt cost in the cost ledger
+    fn record_cost(
+        &self,
+        cost_tracker: Option<&PromptCostTracker>,
+        cost_usd: Option<f64>,
+        context: &HashMap<String, Value>,
+    ) {
+        if let (Some((tracker, issue)), Some(cost_usd)) = (cost_tracker, cost_usd) {
+            let entry = CostEntry {
+                timestamp: chrono::Utc::now(),
+                issue: issue.clone(),
+                run_id: context
+                    .get(RUN_ID_KEY)
+                    .and_then(Value::as_str)
+                    .and_then(|id| WorkflowRunId::parse(id).ok()),
+                workflow: context
+                    .get(WORKFLOW_NAME_KEY)
+                    .and_then(Value::as_str)
+                    .map(WorkflowName::from),
+                state: context
+                    .get(STATE_ID_KEY)
+                    .and_then(Value::as_str)
+                    .map(StateId::new),
+                prompt: self.prompt_name.clone(),
+                cost_usd,
+            };
+            if let Err(e) = tracker.record(entry) {
+                tracing::warn!("Failed to record prompt cost: {}", e);
+            }
+        }
+    }
 }
 
 /// Action that pauses execution for a specified duration or waits for user input
@@ -922,6 +1269,330 @@ impl Action for AbortAction {
     impl_as_any!();
 }
 
+/// Action that creates an issue with rendered content
+#[derive(Debug, Clone)]
+pub struct CreateIssueAction {
+    /// Name of the issue to create
+    pub name: String,
+    /// Issue content, rendered as a Liquid template
+    pub content: String,
+    /// Variable name to store the name of the created issue
+    pub result_variable: Option<String>,
+}
+
+/// Action that replaces or appends to the content of an issue
+#[derive(Debug, Clone)]
+pub struct UpdateIssueAction {
+    /// Name of the issue to update
+    pub name: String,
+    /// New issue content, rendered as a Liquid template
+    pub content: String,
+    /// Whether the content is appended instead of replacing the issue's content
+    pub append: bool,
+    /// Variable name to store the name of the updated issue
+    pub result_variable: Option<String>,
+}
+
+/// Action that creates a memo with rendered content
+#[derive(Debug, Clone)]
+pub struct CreateMemoAction {
+    /// Title of the memo to create
+    pub title: String,
+    /// Memo content, rendered as a Liquid template
+    pub content: String,
+    /// Variable name to store the ID of the created memo
+    pub result_variable: Option<String>,
+}
+
+/// Directory the workflow runs in, where its issues and memos are stored
+fn action_work_dir(context: &HashMap<String, Value>) -> ActionResult<std::path::PathBuf> {
+    match context.get(WORKING_DIR_KEY).and_then(Value::as_str) {
+        Some(working_dir) => Ok(std::path::PathBuf::from(working_dir)),
+        None => Ok(std::env::current_dir()?),
+    }
+}
+
+/// Render the content of an issue or memo action
+///
+/// `${variable}` references are substituted first, then the content is
+/// rendered as a Liquid template with the workflow variables and the
+/// template variables the run was started with.
+fn render_content(content: &str, context: &HashMap<String, Value>) -> ActionResult<String> {
+    let content = substitute_variables_in_string(content, context);
+
+    let template_vars = context.get("_template_vars").and_then(Value::as_object);
+    let variables = context.iter().filter(|(key, _)| !key.starts_with('_'));
+    let mut liquid_vars = liquid::Object::new();
+    for (key, value) in template_vars.into_iter().flatten().chain(variables) {
+        liquid_vars.insert(
+            key.clone().into(),
+            liquid::model::to_value(value).unwrap_or(liquid::model::Value::Nil),
+        );
+    }
+
+    crate::plugins::PluginRegistry::global()
+        .add_filters(liquid::ParserBuilder::with_stdlib())
+        .build()
+        .and_then(|parser| parser.parse(&content))
+        .and_then(|template| template.render(&liquid_vars))
+        .map_err(|e| ActionError::ExecutionError(format!("Failed to render content: {e}")))
+}
+
+/// Store the result of an issue or memo action and mark the action successful
+fn finish_storage_action(
+    result: String,
+    result_variable: Option<&String>,
+    context: &mut HashMap<String, Value>,
+) -> Value {
+    let value = Value::String(result);
+    if let Some(variable) = result_variable {
+        context.insert(variable.clone(), value.clone());
+    }
+    context.insert(LAST_ACTION_RESULT_KEY.to_string(), Value::Bool(true));
+    value
+}
+
+impl CreateIssueAction {
+    /// Create a new create issue action
+    pub fn new(name: String, content: String) -> Self {
+        Self {
+            name,
+            content,
+            result_variable: None,
+        }
+    }
+
+    /// Set the result variable name
+    pub fn with_result_variable(mut self, variable: String) -> Self {
+        self.result_variable = Some(variable);
+        self
+    }
+}
+
+impl VariableSubstitution for CreateIssueAction {}
+
+#[async_trait::async_trait]
+impl Action for CreateIssueAction {
+    async fn execute(&self, context: &mut HashMap<String, Value>) -> ActionResult<Value> {
+        let name = self.substitute_string(&self.name, context);
+        let content = render_content(&self.content, context)?;
+
+        let storage = issue_storage_for_work_dir(&action_work_dir(context)?)
+            .map_err(|e| ActionError::ExecutionError(e.to_string()))?;
+        let issue = storage
+            .create_issue(name, content)
+            .await
+            .map_err(|e| ActionError::ExecutionError(format!("Failed to create issue: {e}")))?;
+        tracing::info!("Created issue {}", issue.name);
+
+        Ok(finish_storage_action(
+            issue.name,
+            self.result_variable.as_ref(),
+            context,
+        ))
+    }
+
+    fn description(&self) -> String {
+        format!("Create issue '{}'", self.name)
+    }
+
+    fn action_type(&self) -> &'static str {
+        "create_issue"
+    }
+
+    impl_as_any!();
+}
+
+impl UpdateIssueAction {
+    /// Create a new update issue action that replaces the issue's content
+    pub fn new(name: String, content: String) -> Self {
+        Self {
+            name,
+            content,
+            append: false,
+            result_variable: None,
+        }
+    }
+
+    /// Set whether the content is appended to the issue
+    pub fn with_append(mut self, append: bool) -> Self {
+        self.append = append;
+        self
+    }
+
+    /// Set the result variable name
+    pub fn with_result_variable(mut self, variable: String) -> Self {
+        self.result_variable = Some(variable);
+        self
+    }
+}
+
+impl VariableSubstitution for UpdateIssueAction {}
+
+#[async_trait::async_trait]
+impl Action for UpdateIssueAction {
+    async fn execute(&self, context: &mut HashMap<String, Value>) -> ActionResult<Value> {
+        let name = self.substitute_string(&self.name, context);
+        let mut content = render_content(&self.content, context)?;
+
+        let storage = issue_storage_for_work_dir(&action_work_dir(context)?)
+            .map_err(|e| ActionError::ExecutionError(e.to_string()))?;
+        if self.append {
+            let existing = storage.get_issue(&name).await.map_err(|e| {
+                ActionError::ExecutionError(format!("Failed to read issue '{name}': {e}"))
+            })?;
+            content = format!("{}\n{}", existing.content, content);
+        }
+        let issue = storage
+            .update_issue(&name, content)
+            .await
+            .map_err(|e| ActionError::ExecutionError(format!("Failed to update issue: {e}")))?;
+        tracing::info!("Updated issue {}", issue.name);
+
+        Ok(finish_storage_action(
+            issue.name,
+            self.result_variable.as_ref(),
+            context,
+        ))
+    }
+
+    fn description(&self) -> String {
+        if self.append {
+            format!("Append to issue '{}'", self.name)
+        } else {
+            format!("Update issue '{}'", self.name)
+        }
+    }
+
+    fn action_type(&self) -> &'static str {
+        "update_issue"
+    }
+
+    impl_as_any!();
+}
+
+impl CreateMemoAction {
+    /// Create a new create memo action
+    pub fn new(title: String, content: String) -> Self {
+        Self {
+            title,
+            content,
+            result_variable: None,
+        }
+    }
+
+    /// Set the result variable name
+    pub fn with_result_variable(mut self, variable: String) -> Self {
+        self.result_variable = Some(variable);
+        self
+    }
+}
+
+impl VariableSubstitution for CreateMemoAction {}
+
+#[async_trait::async_trait]
+impl Action for CreateMemoAction {
+    async fn execute(&self, context: &mut HashMap<String, Value>) -> ActionResult<Value> {
+        use crate::memoranda::{memos_dir_for_work_dir, MarkdownMemoStorage, MemoStorage};
+
+        let title = self.substitute_string(&self.title, context);
+        let content = render_content(&self.content, context)?;
+
+        let storage = MarkdownMemoStorage::new(memos_dir_for_work_dir(&action_work_dir(context)?));
+        let memo = storage
+            .create_memo(title, content)
+            .await
+            .map_err(|e| ActionError::ExecutionError(format!("Failed to create memo: {e}")))?;
+        tracing::info!("Created memo '{}' ({})", memo.title, memo.id.as_str());
+
+        Ok(finish_storage_action(
+            memo.id.as_str().to_string(),
+            self.result_variable.as_ref(),
+            context,
+        ))
+    }
+
+    fn description(&self) -> String {
+        format!("Create memo '{}'", self.title)
+    }
+
+    fn action_type(&self) -> &'static str {
+        "create_memo"
+    }
+
+    impl_as_any!();
+}
+
+/// Action that saves a file the run produced as an artifact of the run
+#[derive(Debug, Clone)]
+pub struct SaveArtifactAction {
+    /// Path of the file, relative to the directory the run works in
+    pub path: String,
+    /// Kind of the artifact, inferred from the file's extension when not given
+    pub kind: Option<ArtifactKind>,
+}
+
+impl SaveArtifactAction {
+    /// Create a new save artifact action
+    pub fn new(path: String) -> Self {
+        Self { path, kind: None }
+    }
+
+    /// Set the kind of the artifact
+    pub fn with_kind(mut self, kind: ArtifactKind) -> Self {
+        self.kind = Some(kind);
+        self
+    }
+}
+
+impl VariableSubstitution for SaveArtifactAction {}
+
+#[async_trait::async_trait]
+impl Action for SaveArtifactAction {
+    async fn execute(&self, context: &mut HashMap<String, Value>) -> ActionResult<Value> {
+        let path = self.substitute_string(&self.path, context);
+        let run_id = context
+            .get(RUN_ID_KEY)
+            .and_then(Value::as_str)
+            .and_then(|run_id| WorkflowRunId::parse(run_id).ok())
+            .ok_or_else(|| {
+                ActionError::ExecutionError(
+                    "Artifacts can only be saved by a workflow run".to_string(),
+                )
+            })?;
+        let work_dir = action_work_dir(context)?;
+        let source = validate_path(std::path::Path::new(&path), &work_dir, PathPolicy::global())
+            .map_err(|e| ActionError::ExecutionError(e.to_string()))?;
+        let state = context.get(STATE_ID_KEY).and_then(Value::as_str);
+
+        let artifact = RunArtifacts::default()
+            .save(&run_id, &source, self.kind, state)
+            .map_err(|e| ActionError::ExecutionError(format!("Failed to save artifact: {e}")))?;
+        tracing::info!(
+            "Saved {} artifact '{}' of run {}",
+            artifact.kind,
+            artifact.name,
+            run_id
+        );
+
+        context.insert(LAST_ACTION_RESULT_KEY.to_string(), Value::Bool(true));
+        Ok(Value::String(artifact.name))
+    }
+
+    fn description(&self) -> String {
+        match self.kind {
+            Some(kind) => format!("Save {kind} artifact '{}'", self.path),
+            None => format!("Save artifact '{}'", self.path),
+        }
+    }
+
+    fn action_type(&self) -> &'static str {
+        "save_artifact"
+    }
+
+    impl_as_any!();
+}
+
 /// Validate that an argument key is safe for command-line use
 fn is_valid_argument_key(key: &str) -> bool {
     !key.is_empty()
@@ -1519,6 +2190,8 @@ impl Action for ShellAction {
 
             cmd.current_dir(path);
             tracing::debug!("Set working directory to: {}", substituted_dir);
+        } else if let Some(working_dir) = context.get(WORKING_DIR_KEY).and_then(Value::as_str) {
+            cmd.current_dir(working_dir);
         }
 
         // Set environment variables if specified
@@ -1543,7 +2216,8 @@ impl Action for ShellAction {
 
         // Configure output capture
         cmd.stdout(std::process::Stdio::piped())
-            .stderr(std::process::Stdio::piped());
+            .stderr(std::process::Stdio::piped())
+            .kill_on_drop(true);
 
         // Spawn the child process
         let mut child = cmd
@@ -1686,7 +2360,13 @@ impl Action for SubWorkflowAction {
         };
 
         let workflow_name_typed = WorkflowName::new(&self.workflow_name);
-        let workflow = storage.get_workflow(&workflow_name_typed).map_err(|e| {
+        let loaded = storage.get_workflow(&workflow_name_typed);
+        let pins = RunPins::from_context(context);
+        let workflow = match &pins {
+            Some(pins) => pins.pin_workflow(&self.workflow_name, loaded),
+            None => loaded,
+        }
+        .map_err(|e| {
             ActionError::ExecutionError(format!(
                 "Failed to load sub-workflow '{}': {}",
                 self.workflow_name, e
@@ -1734,6 +2414,16 @@ impl Action for SubWorkflowAction {
             );
         }
 
+        if let Some(working_dir) = context.get(WORKING_DIR_KEY) {
+            run.context
+                .insert(WORKING_DIR_KEY.to_string(), working_dir.clone());
+        }
+
+        // Share the pins of the parent run
+        if let Some(pin_dir) = context.get(PIN_DIR_KEY) {
+            run.context.insert(PIN_DIR_KEY.to_string(), pin_dir.clone());
+        }
+
         // Execute the workflow with timeout
         let execution_future = executor.execute_state(&mut run);
 
@@ -2101,7 +2791,8 @@ pub fn parse_action_from_description_with_context(
             }
 
             // Parse and render the template
-            match liquid::ParserBuilder::with_stdlib()
+            match crate::plugins::PluginRegistry::global()
+                .add_filters(liquid::ParserBuilder::with_stdlib())
                 .build()
                 .and_then(|parser| parser.parse(description))
             {
@@ -2163,6 +2854,22 @@ pub fn parse_action_from_description(description: &str) -> ActionResult<Option<B
         return Ok(Some(Box::new(abort_action)));
     }
 
+    if let Some(issue_action) = parser.parse_create_issue_action(description)? {
+        return Ok(Some(Box::new(issue_action)));
+    }
+
+    if let Some(issue_action) = parser.parse_update_issue_action(description)? {
+        return Ok(Some(Box::new(issue_action)));
+    }
+
+    if let Some(memo_action) = parser.parse_create_memo_action(description)? {
+        return Ok(Some(Box::new(memo_action)));
+    }
+
+    if let Some(artifact_action) = parser.parse_save_artifact_action(description)? {
+        return Ok(Some(Box::new(artifact_action)));
+    }
+
     if let Some(shell_action) = parser.parse_shell_action(description)? {
         return Ok(Some(Box::new(shell_action)));
     }
@@ -3035,4 +3742,175 @@ mod tests {
         let stdout = context.get("stdout").unwrap().as_str().unwrap();
         assert!(stdout.contains("unix test"));
     }
+
+    #[tokio::test]
+    async fn test_prompt_action_sampling() {
+        struct EchoSampler(std::sync::Mutex<Option<crate::workflow::SamplingRequest>>);
+
+        #[async_trait::async_trait]
+        impl crate::workflow::PromptSampler for EchoSampler {
+            async fn create_message(
+                &self,
+                request: crate::workflow::SamplingRequest,
+            ) -> crate::error::Result<crate::workflow::SamplingResponse> {
+                let text = format!("echo: {}", request.prompt);
+                *self.0.lock().unwrap() = Some(request);
+                Ok(crate::workflow::SamplingResponse {
+                    text,
+                    model: "test-model".to_string(),
+                })
+            }
+        }
+
+        let action = PromptAction::new("review".to_string())
+            .with_backend(PromptBackend::Sampling)
+            .with_model("sonnet".to_string())
+            .with_quiet(true);
+        let context = HashMap::new();
+
+        crate::workflow::set_prompt_sampler(None);
+        assert!(action
+            .execute_with_provider("hello", &context, true)
+            .await
+            .is_err());
+
+        let sampler = Arc::new(EchoSampler(std::sync::Mutex::new(None)));
+        crate::workflow::set_prompt_sampler(Some(sampler.clone()));
+        let response = action.execute_with_provider("hello", &context, true).await;
+        crate::workflow::set_prompt_sampler(None);
+
+        let response = response.unwrap();
+        assert_eq!(response.text, "echo: hello");
+        assert!(response.session_id.is_none());
+        let request = sampler.0.lock().unwrap().take().unwrap();
+        assert_eq!(request.model.as_deref(), Some("sonnet"));
+        assert!(request.max_tokens > 0);
+    }
+
+    #[tokio::test]
+    async fn test_compact_conversation_truncates_near_the_limit() {
+        let action = PromptAction::new("code".to_string());
+        let window = ContextWindowSettings {
+            max_tokens: 1000,
+            strategy: CompactionStrategy::Truncate,
+            ..Default::default()
+        };
+        let mut context = HashMap::new();
+        window.store(&mut context);
+        context.insert(
+            CLAUDE_SESSION_ID_KEY.to_string(),
+            Value::String("session".to_string()),
+        );
+        PromptRecord {
+            prompt: "plan".to_string(),
+            arguments: Default::default(),
+            response: "Plan: fix the login".to_string(),
+            cost_usd: None,
+            tokens: None,
+            hash: None,
+        }
+        .record(&mut context);
+
+        // Below the threshold the conversation continues unchanged
+        set_conversation_tokens(&mut context, 100);
+        let prompt = action
+            .compact_conversation("Write the code".to_string(), &mut context, true, None)
+            .await;
+        assert_eq!(prompt, "Write the code");
+        assert!(action.session_to_resume(&context).is_some());
+
+        set_conversation_tokens(&mut context, 900);
+        let prompt = action
+            .compact_conversation("Write the code".to_string(), &mut context, true, None)
+            .await;
+        assert!(prompt.contains("plan:\nPlan: fix the login"));
+        assert!(prompt.ends_with("Write the code"));
+        assert!(action.session_to_resume(&context).is_none());
+        assert_eq!(conversation_tokens(&context), 0);
+    }
+
+    #[tokio::test]
+    async fn test_summarize_conversation_respects_cost_budget() {
+        use crate::cost::{BudgetLevel, CostBudget};
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let budget = CostBudget {
+            daily_usd: Some(1.0),
+            issue_usd: None,
+            warning_percent: 80,
+            hard_limit: true,
+        };
+        let tracker = CostTracker::new(temp.path().to_path_buf(), budget);
+        tracker
+            .record(CostEntry {
+                timestamp: chrono::Utc::now(),
+                issue: None,
+                run_id: None,
+                workflow: None,
+                state: None,
+                prompt: "plan".to_string(),
+                cost_usd: 1.5,
+            })
+            .unwrap();
+        let cost_tracker = (tracker, None);
+
+        let action = PromptAction::new("code".to_string()).with_backend(PromptBackend::Claude);
+        let window = ContextWindowSettings {
+            max_tokens: 1000,
+            strategy: CompactionStrategy::Summarize,
+            ..Default::default()
+        };
+        let mut context = HashMap::new();
+        window.store(&mut context);
+        context.insert(
+            CLAUDE_SESSION_ID_KEY.to_string(),
+            Value::String("session".to_string()),
+        );
+        PromptRecord {
+            prompt: "plan".to_string(),
+            arguments: Default::default(),
+            response: "Plan: fix the login".to_string(),
+            cost_usd: None,
+            tokens: None,
+            hash: None,
+        }
+        .record(&mut context);
+        set_conversation_tokens(&mut context, 900);
+
+        // The summary is refused like any other prompt once the budget is spent
+        let result = action
+            .summarize_conversation(&window, &context, true, Some(&cost_tracker))
+            .await;
+        assert!(matches!(result, Err(ActionError::AbortError(ref refusal))
+            if refusal.starts_with("Refused to start a prompt")));
+
+        // Compaction then carries the latest responses instead
+        let prompt = action
+            .compact_conversation(
+                "Write the code".to_string(),
+                &mut context,
+                true,
+                Some(&cost_tracker),
+            )
+            .await;
+        assert!(prompt.contains("plan:\nPlan: fix the login"));
+        assert!(prompt.ends_with("Write the code"));
+
+        let levels: Vec<BudgetLevel> = cost_tracker
+            .0
+            .events()
+            .unwrap()
+            .iter()
+            .map(|event| event.level)
+            .collect();
+        assert_eq!(
+            levels,
+            [
+                BudgetLevel::Exceeded,
+                BudgetLevel::Refused,
+                BudgetLevel::Refused
+            ]
+        );
+        assert_eq!(cost_tracker.0.entries().unwrap().len(), 1);
+    }
 }
@@ -8,6 +8,7 @@
 //! - `error_handling_tests` - Tests for error handling in actions
 //! - `integration_tests` - Integration tests for action execution
 //! - `resource_cleanup_tests` - Tests for resource cleanup and error recovery
+//! - `storage_action_tests` - Tests for the issue and memo actions
 
 // Common test utilities module
 #[cfg(test)]
@@ -86,3 +87,6 @@ mod shell_action_tests;
 
 #[cfg(test)]
 mod shell_action_integration_tests;
+
+#[cfg(test)]
+mod storage_action_tests;
@@ -122,3 +122,105 @@ fn test_abort_error_detection_in_response() {
         }
     }
 }
+
+#[test]
+fn test_prompt_action_with_claude_settings() {
+    let action = PromptAction::new("test-prompt".to_string())
+        .with_model("opus".to_string())
+        .with_temperature(0.5)
+        .with_max_output_tokens(2048)
+        .with_system_prompt("Be concise".to_string());
+
+    assert_eq!(action.claude.model.as_deref(), Some("opus"));
+    assert_eq!(action.claude.temperature, Some(0.5));
+    assert_eq!(action.claude.max_output_tokens, Some(2048));
+    assert_eq!(action.claude.system_prompt.as_deref(), Some("Be concise"));
+
+    // Default has no overrides
+    let action = PromptAction::new("test-prompt".to_string());
+    assert_eq!(action.claude, ClaudeSettings::default());
+}
+
+#[test]
+fn test_prompt_action_session_to_resume() {
+    let mut context = HashMap::new();
+    let action = PromptAction::new("test-prompt".to_string());
+    assert!(action.continue_session);
+    assert_eq!(action.session_to_resume(&context), None);
+
+    context.insert(
+        CLAUDE_SESSION_ID_KEY.to_string(),
+        serde_json::json!("session-123"),
+    );
+    assert_eq!(
+        action.session_to_resume(&context).as_deref(),
+        Some("session-123")
+    );
+
+    let action = action.with_continue_session(false);
+    assert_eq!(action.session_to_resume(&context), None);
+}
+
+#[test]
+fn test_prompt_action_apply_extractors() {
+    use crate::workflow::OutputExtractor;
+
+    let verdict = OutputExtractor::parse("verdict", "regex:Verdict: (\\w+)").unwrap();
+    let score = OutputExtractor::parse("score", "regex:Score: (\\d+) as integer").unwrap();
+    let action = PromptAction::new("test-prompt".to_string())
+        .with_extractor(verdict)
+        .with_extractor(score);
+
+    let mut context = HashMap::new();
+    action
+        .apply_extractors("Verdict: approve\nScore: 9", &mut context)
+        .unwrap();
+    assert_eq!(context["verdict"], serde_json::json!("approve"));
+    assert_eq!(context["score"], serde_json::json!(9));
+
+    let mut context = HashMap::new();
+    let error = action
+        .apply_extractors("Verdict: reject", &mut context)
+        .unwrap_err();
+    assert_eq!(context["verdict"], serde_json::json!("reject"));
+    assert!(!context.contains_key("score"));
+    match error {
+        ActionError::ExtractionError {
+            message,
+            fallback_state,
+        } => {
+            assert!(message.contains("'score'"));
+            assert_eq!(fallback_state, None);
+        }
+        _ => panic!("Expected ExtractionError, got {error:?}"),
+    }
+
+    let action = action.with_extract_fallback_state("ask_again".to_string());
+    match action.apply_extractors("nothing", &mut HashMap::new()) {
+        Err(ActionError::ExtractionError { fallback_state, .. }) => {
+            assert_eq!(fallback_state.as_deref(), Some("ask_again"));
+        }
+        other => panic!("Expected ExtractionError, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_claude_settings_with_defaults() {
+    let defaults = ClaudeSettings {
+        model: Some("sonnet".to_string()),
+        temperature: Some(0.7),
+        max_output_tokens: Some(1000),
+        system_prompt: Some("Default".to_string()),
+    };
+    let settings = ClaudeSettings {
+        model: Some("opus".to_string()),
+        max_output_tokens: Some(4000),
+        ..Default::default()
+    };
+
+    let resolved = settings.with_defaults(&defaults);
+    assert_eq!(resolved.model.as_deref(), Some("opus"));
+    assert_eq!(resolved.temperature, Some(0.7));
+    assert_eq!(resolved.max_output_tokens, Some(4000));
+    assert_eq!(resolved.system_prompt.as_deref(), Some("Default"));
+}
@@ -0,0 +1,156 @@
+//! Tests for the issue, memo and artifact actions
+
+use crate::memoranda::{memos_dir_for_work_dir, MarkdownMemoStorage, MemoStorage};
+use crate::workflow::action_parser::ActionParser;
+use crate::workflow::actions::*;
+use crate::workflow::ArtifactKind;
+use serde_json::Value;
+use std::collections::HashMap;
+use tempfile::TempDir;
+
+fn context_in(dir: &TempDir) -> HashMap<String, Value> {
+    let mut context = HashMap::new();
+    context.insert(
+        WORKING_DIR_KEY.to_string(),
+        Value::String(dir.path().display().to_string()),
+    );
+    context
+}
+
+#[test]
+fn test_parse_storage_actions() {
+    let parser = ActionParser::new().unwrap();
+
+    let action = parser
+        .parse_create_issue_action(
+            r#"Create issue "fix-login" with content="Login fails for ${user}" result="issue""#,
+        )
+        .unwrap()
+        .unwrap();
+    assert_eq!(action.name, "fix-login");
+    assert_eq!(action.content, "Login fails for ${user}");
+    assert_eq!(action.result_variable.as_deref(), Some("issue"));
+
+    let action = parser
+        .parse_update_issue_action(r#"update issue "fix-login" with content="More" append="true""#)
+        .unwrap()
+        .unwrap();
+    assert!(action.append);
+    assert_eq!(action.description(), "Append to issue 'fix-login'");
+
+    let action = parser
+        .parse_create_memo_action(r#"Create memo "Findings" with content="{{ notes }}""#)
+        .unwrap()
+        .unwrap();
+    assert_eq!(action.title, "Findings");
+    assert_eq!(action.action_type(), "create_memo");
+
+    assert!(parser
+        .parse_create_issue_action(r#"Create issue "fix-login""#)
+        .is_err());
+    assert!(parser
+        .parse_create_memo_action(r#"Create memo "x" with content="y" append="true""#)
+        .is_err());
+    assert!(parser
+        .parse_update_issue_action(r#"Update issue "x" with content="y" append="maybe""#)
+        .is_err());
+    assert!(parser
+        .parse_create_issue_action("Create issue for the bug")
+        .unwrap()
+        .is_none());
+
+    let action = parse_action_from_description(r#"Create memo "x" with content="y""#)
+        .unwrap()
+        .unwrap();
+    assert_eq!(action.action_type(), "create_memo");
+}
+
+#[tokio::test]
+async fn test_create_and_update_issue_with_rendered_content() {
+    let dir = TempDir::new().unwrap();
+    let mut context = context_in(&dir);
+    context.insert("user".to_string(), Value::String("alice".to_string()));
+    context.insert(
+        "steps".to_string(),
+        serde_json::json!(["open the page", "sign in"]),
+    );
+
+    let action = CreateIssueAction::new(
+        "fix-login".to_string(),
+        "Login fails for ${user}\n{% for step in steps %}- {{ step }}\n{% endfor %}".to_string(),
+    )
+    .with_result_variable("issue".to_string());
+    let result = action.execute(&mut context).await.unwrap();
+    assert_eq!(result, Value::String("fix-login".to_string()));
+    assert_eq!(context.get("issue"), Some(&result));
+    assert_eq!(context.get("last_action_result"), Some(&Value::Bool(true)));
+
+    let path = dir.path().join("issues").join("fix-login.md");
+    assert_eq!(
+        std::fs::read_to_string(&path).unwrap(),
+        "Login fails for alice\n- open the page\n- sign in\n"
+    );
+
+    let action = UpdateIssueAction::new("fix-login".to_string(), "Fixed by {{ user }}".to_string())
+        .with_append(true);
+    action.execute(&mut context).await.unwrap();
+    let content = std::fs::read_to_string(&path).unwrap();
+    assert!(content.starts_with("Login fails for alice"));
+    assert!(content.ends_with("Fixed by alice"));
+
+    let missing = UpdateIssueAction::new("missing".to_string(), "x".to_string());
+    assert!(missing.execute(&mut context).await.is_err());
+}
+
+#[tokio::test]
+async fn test_create_memo() {
+    let dir = TempDir::new().unwrap();
+    let mut context = context_in(&dir);
+    context.insert(
+        "_template_vars".to_string(),
+        serde_json::json!({ "project": "hammer" }),
+    );
+
+    let action = CreateMemoAction::new(
+        "Release notes".to_string(),
+        "Released {{ project }}".to_string(),
+    )
+    .with_result_variable("memo".to_string());
+    let id = action.execute(&mut context).await.unwrap();
+    assert_eq!(context.get("memo"), Some(&id));
+
+    let storage = MarkdownMemoStorage::new(memos_dir_for_work_dir(dir.path()));
+    let memos = storage.list_memos().await.unwrap();
+    assert_eq!(memos.len(), 1);
+    assert_eq!(memos[0].id.as_str(), id.as_str().unwrap());
+    assert_eq!(memos[0].content, "Released hammer");
+}
+
+#[tokio::test]
+async fn test_save_artifact_action() {
+    let parser = ActionParser::new().unwrap();
+    let action = parser
+        .parse_save_artifact_action(r#"Save artifact "target/${name}.diff" as Report"#)
+        .unwrap()
+        .unwrap();
+    assert_eq!(action.path, "target/${name}.diff");
+    assert_eq!(action.kind, Some(ArtifactKind::Report));
+    assert_eq!(
+        action.description(),
+        "Save report artifact 'target/${name}.diff'"
+    );
+    assert!(parser
+        .parse_save_artifact_action(r#"Save artifact "out.png" as image"#)
+        .is_err());
+    let action = parse_action_from_description(r#"save artifact "review.md""#)
+        .unwrap()
+        .unwrap();
+    assert_eq!(action.action_type(), "save_artifact");
+
+    // Outside a workflow run there is no run to save to
+    let dir = TempDir::new().unwrap();
+    std::fs::write(dir.path().join("review.md"), "Looks good").unwrap();
+    let action = SaveArtifactAction::new("review.md".to_string());
+    let error = action.execute(&mut context_in(&dir)).await.unwrap_err();
+    assert!(error.to_string().contains("workflow run"));
+}
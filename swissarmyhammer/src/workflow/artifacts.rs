@@ -0,0 +1,314 @@
+//! Files workflow runs produce, kept with the run
+//!
+//! A workflow's useful output is often a file: a patch, a report, a build log.
+//! The `Save artifact "path" [as <kind>]` action copies such a file into the
+//! run's artifacts directory, `.swissarmyhammer/runs/<run_id>/artifacts`, and
+//! records it with its kind in the run's `artifacts.json` manifest. The files
+//! outlive the working copy they were produced in, `flow artifacts <run_id>`
+//! lists them, and the MCP server serves them as `artifact://<run_id>/<name>`
+//! resources.
+//!
+//! Artifacts are named after the file they were saved from. Saving another
+//! file with the same name in the same run replaces the earlier one, so a
+//! state that runs in a loop keeps its latest output.
+
+use crate::workflow::WorkflowRunId;
+use crate::{Result, SwissArmyHammerError};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Directory run artifacts are kept in, relative to the project root
+pub const DEFAULT_RUNS_DIR: &str = ".swissarmyhammer/runs";
+
+/// Directory of a run holding its artifact files
+const ARTIFACTS_DIR: &str = "artifacts";
+
+/// File of a run recording its artifacts
+const MANIFEST_FILE: &str = "artifacts.json";
+
+/// What an artifact is
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ArtifactKind {
+    /// Changes to apply, such as a `.patch` or `.diff` file
+    Patch,
+    /// A document for people to read, such as a review or summary
+    Report,
+    /// Output logged by a command
+    Log,
+    /// Structured data, such as JSON, YAML or CSV
+    Data,
+    /// Any other file
+    #[default]
+    File,
+}
+
+impl ArtifactKind {
+    /// Every kind, in the order they are documented
+    pub const ALL: [ArtifactKind; 5] = [
+        ArtifactKind::Patch,
+        ArtifactKind::Report,
+        ArtifactKind::Log,
+        ArtifactKind::Data,
+        ArtifactKind::File,
+    ];
+
+    /// Name of the kind, as written in actions and manifests
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ArtifactKind::Patch => "patch",
+            ArtifactKind::Report => "report",
+            ArtifactKind::Log => "log",
+            ArtifactKind::Data => "data",
+            ArtifactKind::File => "file",
+        }
+    }
+
+    /// The kind of a file, going by its extension
+    pub fn from_path(path: &Path) -> Self {
+        let extension = path
+            .extension()
+            .map(|extension| extension.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        match extension.as_str() {
+            "patch" | "diff" => ArtifactKind::Patch,
+            "md" | "markdown" | "html" | "txt" => ArtifactKind::Report,
+            "log" => ArtifactKind::Log,
+            "json" | "yaml" | "yml" | "csv" | "toml" => ArtifactKind::Data,
+            _ => ArtifactKind::File,
+        }
+    }
+}
+
+impl fmt::Display for ArtifactKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ArtifactKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        ArtifactKind::ALL
+            .into_iter()
+            .find(|kind| kind.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                format!(
+                    "Unknown artifact kind '{s}': expected one of {}",
+                    ArtifactKind::ALL.map(|kind| kind.as_str()).join(", ")
+                )
+            })
+    }
+}
+
+/// A file saved by a workflow run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Artifact {
+    /// Name of the file in the run's artifacts directory
+    pub name: String,
+    /// What the file is
+    pub kind: ArtifactKind,
+    /// Path the file was saved from
+    pub source: PathBuf,
+    /// Size of the file in bytes
+    pub size: u64,
+    /// State whose action saved the file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    /// When the file was saved
+    pub saved_at: DateTime<Utc>,
+}
+
+/// The artifacts of workflow runs, kept under one directory
+#[derive(Debug, Clone)]
+pub struct RunArtifacts {
+    dir: PathBuf,
+}
+
+impl RunArtifacts {
+    /// Create artifacts kept in `dir`, one directory per run
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Directory the runs' artifacts are kept in
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Directory holding the artifact files of a run
+    pub fn artifacts_dir(&self, run_id: &WorkflowRunId) -> PathBuf {
+        self.dir.join(run_id.to_string()).join(ARTIFACTS_DIR)
+    }
+
+    fn manifest_path(&self, run_id: &WorkflowRunId) -> PathBuf {
+        self.dir.join(run_id.to_string()).join(MANIFEST_FILE)
+    }
+
+    /// Copy a file into a run's artifacts, as `kind` or the kind its extension
+    /// suggests
+    pub fn save(
+        &self,
+        run_id: &WorkflowRunId,
+        source: &Path,
+        kind: Option<ArtifactKind>,
+        state: Option<&str>,
+    ) -> Result<Artifact> {
+        let name = source
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .ok_or_else(|| {
+                SwissArmyHammerError::Other(format!(
+                    "Artifact path '{}' does not name a file",
+                    source.display()
+                ))
+            })?;
+        if !source.is_file() {
+            return Err(SwissArmyHammerError::Other(format!(
+                "Artifact '{}' is not a file",
+                source.display()
+            )));
+        }
+
+        let dir = self.artifacts_dir(run_id);
+        fs::create_dir_all(&dir)?;
+        let size = fs::copy(source, dir.join(&name))?;
+        let artifact = Artifact {
+            name,
+            kind: kind.unwrap_or_else(|| ArtifactKind::from_path(source)),
+            source: source.to_path_buf(),
+            size,
+            state: state.map(str::to_string),
+            saved_at: Utc::now(),
+        };
+
+        let mut artifacts = self.list(run_id)?;
+        artifacts.retain(|saved| saved.name != artifact.name);
+        artifacts.push(artifact.clone());
+        fs::write(
+            self.manifest_path(run_id),
+            serde_json::to_string_pretty(&artifacts)?,
+        )?;
+        Ok(artifact)
+    }
+
+    /// The artifacts of a run, in the order they were saved
+    pub fn list(&self, run_id: &WorkflowRunId) -> Result<Vec<Artifact>> {
+        let path = self.manifest_path(run_id);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    /// The runs that saved artifacts
+    pub fn runs(&self) -> Result<Vec<WorkflowRunId>> {
+        if !self.dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut runs = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let entry = entry?;
+            let Ok(run_id) = WorkflowRunId::parse(&entry.file_name().to_string_lossy()) else {
+                continue;
+            };
+            if entry.path().join(MANIFEST_FILE).exists() {
+                runs.push(run_id);
+            }
+        }
+        runs.sort();
+        Ok(runs)
+    }
+
+    /// An artifact of a run and the path of its file
+    pub fn get(&self, run_id: &WorkflowRunId, name: &str) -> Result<(Artifact, PathBuf)> {
+        let artifact = self
+            .list(run_id)?
+            .into_iter()
+            .find(|artifact| artifact.name == name)
+            .ok_or_else(|| {
+                SwissArmyHammerError::Other(format!("Run {run_id} has no artifact named '{name}'"))
+            })?;
+        let path = self.artifacts_dir(run_id).join(&artifact.name);
+        Ok((artifact, path))
+    }
+}
+
+impl Default for RunArtifacts {
+    fn default() -> Self {
+        Self::new(DEFAULT_RUNS_DIR)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_save_list_and_get() {
+        let temp = TempDir::new().unwrap();
+        let artifacts = RunArtifacts::new(temp.path().join("runs"));
+        let run_id = WorkflowRunId::new();
+        assert!(artifacts.list(&run_id).unwrap().is_empty());
+        assert!(artifacts.runs().unwrap().is_empty());
+
+        let patch = temp.path().join("changes.patch");
+        fs::write(&patch, "--- a\n+++ b\n").unwrap();
+        let saved = artifacts
+            .save(&run_id, &patch, None, Some("implement"))
+            .unwrap();
+        assert_eq!(saved.kind, ArtifactKind::Patch);
+        assert_eq!(saved.size, 12);
+        let report = temp.path().join("review.md");
+        fs::write(&report, "Looks good").unwrap();
+        artifacts
+            .save(&run_id, &report, Some(ArtifactKind::Log), None)
+            .unwrap();
+
+        // Saving a file with the same name again replaces the artifact
+        fs::write(&patch, "--- a\n+++ c\n").unwrap();
+        artifacts.save(&run_id, &patch, None, Some("fix")).unwrap();
+        let listed = artifacts.list(&run_id).unwrap();
+        let names: Vec<&str> = listed.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, ["review.md", "changes.patch"]);
+        assert_eq!(listed[0].kind, ArtifactKind::Log);
+        assert_eq!(listed[1].state.as_deref(), Some("fix"));
+
+        let (artifact, path) = artifacts.get(&run_id, "changes.patch").unwrap();
+        assert_eq!(artifact.source, patch);
+        assert_eq!(fs::read_to_string(path).unwrap(), "--- a\n+++ c\n");
+        assert!(artifacts.get(&run_id, "missing.txt").is_err());
+        assert_eq!(artifacts.runs().unwrap(), vec![run_id]);
+
+        assert!(artifacts
+            .save(&run_id, &temp.path().join("missing.txt"), None, None)
+            .is_err());
+    }
+
+    #[test]
+    fn test_artifact_kinds() {
+        assert_eq!(
+            ArtifactKind::from_path(Path::new("out/fix.DIFF")),
+            ArtifactKind::Patch
+        );
+        assert_eq!(
+            ArtifactKind::from_path(Path::new("results.json")),
+            ArtifactKind::Data
+        );
+        assert_eq!(
+            ArtifactKind::from_path(Path::new("binary")),
+            ArtifactKind::File
+        );
+        assert_eq!("Report".parse::<ArtifactKind>(), Ok(ArtifactKind::Report));
+        assert!("image"
+            .parse::<ArtifactKind>()
+            .unwrap_err()
+            .contains("patch, report, log, data, file"));
+    }
+}
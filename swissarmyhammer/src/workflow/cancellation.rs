@@ -0,0 +1,118 @@
+//! Cancelling workflow runs that are in flight
+//!
+//! `flow cancel <run_id>` writes a marker file for the run in
+//! `.swissarmyhammer/cancel`. The executor checks for the marker before each
+//! state and keeps watching for it while a state's action runs. When it
+//! appears the action is abandoned, which kills any Claude or shell process it
+//! started, the run is marked [`Cancelled`](crate::workflow::WorkflowRunStatus::Cancelled)
+//! and the workflow's `on_cancel` hook runs so it can clean up after itself.
+//!
+//! Markers are files rather than signals so a run can be cancelled from
+//! another terminal, or by a script, without knowing which process runs it.
+
+use crate::workflow::WorkflowRunId;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Directory cancellation markers are written to, relative to the project root
+pub const DEFAULT_CANCEL_DIR: &str = ".swissarmyhammer/cancel";
+
+/// How often a running action checks whether its run was cancelled
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Cancellation markers for workflow runs
+#[derive(Debug, Clone)]
+pub struct RunCancellation {
+    dir: PathBuf,
+}
+
+impl RunCancellation {
+    /// Create cancellation markers kept in `dir`
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Directory the markers are kept in
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Marker file of a run
+    pub fn marker_path(&self, run_id: &WorkflowRunId) -> PathBuf {
+        self.dir.join(format!("{run_id}.cancel"))
+    }
+
+    /// Ask a run to stop
+    pub fn request(&self, run_id: &WorkflowRunId) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        fs::write(
+            self.marker_path(run_id),
+            format!("{}\n", std::process::id()),
+        )
+    }
+
+    /// Whether a run was asked to stop
+    pub fn is_requested(&self, run_id: &WorkflowRunId) -> bool {
+        self.marker_path(run_id).exists()
+    }
+
+    /// Remove the marker of a run that stopped
+    pub fn clear(&self, run_id: &WorkflowRunId) {
+        let path = self.marker_path(run_id);
+        if let Err(e) = fs::remove_file(&path) {
+            if e.kind() != io::ErrorKind::NotFound {
+                tracing::warn!(
+                    "Failed to remove cancellation marker {}: {}",
+                    path.display(),
+                    e
+                );
+            }
+        }
+    }
+
+    /// Wait until a run is asked to stop
+    pub async fn cancelled(&self, run_id: &WorkflowRunId) {
+        while !self.is_requested(run_id) {
+            tokio::time::sleep(CANCEL_POLL_INTERVAL).await;
+        }
+    }
+}
+
+impl Default for RunCancellation {
+    fn default() -> Self {
+        Self::new(DEFAULT_CANCEL_DIR)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_request_and_clear() {
+        let temp = TempDir::new().unwrap();
+        let cancellation = RunCancellation::new(temp.path().join("cancel"));
+        let run_id = WorkflowRunId::new();
+        let other = WorkflowRunId::new();
+        assert!(!cancellation.is_requested(&run_id));
+        cancellation.clear(&run_id);
+
+        cancellation.request(&run_id).unwrap();
+        assert!(cancellation.is_requested(&run_id));
+        assert!(!cancellation.is_requested(&other));
+        tokio::time::timeout(Duration::from_secs(1), cancellation.cancelled(&run_id))
+            .await
+            .unwrap();
+        assert!(
+            tokio::time::timeout(Duration::from_millis(300), cancellation.cancelled(&other))
+                .await
+                .is_err()
+        );
+
+        cancellation.clear(&run_id);
+        assert!(!cancellation.is_requested(&run_id));
+    }
+}
@@ -0,0 +1,473 @@
+//! Side-by-side comparison of two workflow runs
+//!
+//! Comparing runs helps explain why the same workflow behaved differently
+//! between two executions, for example after editing a prompt. A comparison
+//! covers the states each run visited and how long it spent in them, the
+//! inputs and outputs of every prompt executed, and the reported cost.
+//!
+//! Prompt details come from the `_prompt_history` context variable that
+//! [`PromptAction`](crate::workflow::PromptAction) appends to, so they are only
+//! available for runs recorded after it was introduced.
+
+use crate::workflow::{WorkflowRun, WorkflowRunStatus};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+/// Context key holding the prompts executed during a run
+pub const PROMPT_HISTORY_KEY: &str = "_prompt_history";
+
+/// Longest prompt response shown in a rendered comparison
+const MAX_RESPONSE_PREVIEW: usize = 60;
+
+/// A prompt executed during a workflow run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PromptRecord {
+    /// Name of the prompt
+    pub prompt: String,
+    /// Arguments the prompt was rendered with, after variable substitution
+    pub arguments: BTreeMap<String, String>,
+    /// Response returned by Claude
+    pub response: String,
+    /// Cost reported by Claude in US dollars, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost_usd: Option<f64>,
+    /// Input and output tokens reported by Claude, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tokens: Option<u64>,
+    /// Content hash of the prompt template that was rendered, see
+    /// [`crate::prompt_refs`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
+}
+
+impl PromptRecord {
+    /// Append this record to the prompt history of a run context
+    pub fn record(self, context: &mut HashMap<String, Value>) {
+        let Ok(record) = serde_json::to_value(self) else {
+            return;
+        };
+        match context.get_mut(PROMPT_HISTORY_KEY) {
+            Some(Value::Array(history)) => history.push(record),
+            _ => {
+                context.insert(PROMPT_HISTORY_KEY.to_string(), Value::Array(vec![record]));
+            }
+        }
+    }
+
+    /// Read the prompt history recorded in a run context
+    pub fn history(context: &HashMap<String, Value>) -> Vec<PromptRecord> {
+        context
+            .get(PROMPT_HISTORY_KEY)
+            .and_then(|history| serde_json::from_value(history.clone()).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Time spent in one state of a run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateVisits {
+    /// State ID
+    pub state: String,
+    /// Number of times the state was entered
+    pub visits: usize,
+    /// Total time spent in the state, in milliseconds
+    pub duration_ms: i64,
+}
+
+/// The parts of a run that are compared
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunSummary {
+    /// Run ID
+    pub run_id: String,
+    /// Workflow name
+    pub workflow: String,
+    /// Final or current status
+    pub status: WorkflowRunStatus,
+    /// Wall clock duration in milliseconds, if the run has finished
+    pub duration_ms: Option<i64>,
+    /// States in the order they were first visited
+    pub states: Vec<StateVisits>,
+    /// Prompts in the order they were executed
+    pub prompts: Vec<PromptRecord>,
+    /// Sum of the reported prompt costs, if any were reported
+    pub total_cost_usd: Option<f64>,
+}
+
+impl RunSummary {
+    /// Summarize a run
+    ///
+    /// Time in a state runs from entering it until entering the next state, or
+    /// until the run completed for the last state.
+    pub fn from_run(run: &WorkflowRun) -> Self {
+        let mut states: Vec<StateVisits> = Vec::new();
+        for (index, (state, entered_at)) in run.history.iter().enumerate() {
+            let left_at: Option<DateTime<Utc>> = run
+                .history
+                .get(index + 1)
+                .map(|(_, next)| *next)
+                .or(run.completed_at);
+            let duration_ms = left_at
+                .map(|left_at| (left_at - *entered_at).num_milliseconds().max(0))
+                .unwrap_or(0);
+
+            match states
+                .iter_mut()
+                .find(|visits| visits.state == state.as_str())
+            {
+                Some(visits) => {
+                    visits.visits += 1;
+                    visits.duration_ms += duration_ms;
+                }
+                None => states.push(StateVisits {
+                    state: state.to_string(),
+                    visits: 1,
+                    duration_ms,
+                }),
+            }
+        }
+
+        let prompts = PromptRecord::history(&run.context);
+        let costs: Vec<f64> = prompts
+            .iter()
+            .filter_map(|prompt| prompt.cost_usd)
+            .collect();
+
+        Self {
+            run_id: run.id.to_string(),
+            workflow: run.workflow.name.to_string(),
+            status: run.status,
+            duration_ms: run
+                .completed_at
+                .map(|completed_at| (completed_at - run.started_at).num_milliseconds()),
+            states,
+            prompts,
+            total_cost_usd: (!costs.is_empty()).then(|| costs.iter().sum()),
+        }
+    }
+
+    /// Visits to a state, if the run entered it
+    pub fn state(&self, state: &str) -> Option<&StateVisits> {
+        self.states.iter().find(|visits| visits.state == state)
+    }
+}
+
+/// Comparison of two workflow runs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunComparison {
+    /// The first run
+    pub run_a: RunSummary,
+    /// The second run
+    pub run_b: RunSummary,
+}
+
+impl RunComparison {
+    /// Compare two runs
+    pub fn new(run_a: &WorkflowRun, run_b: &WorkflowRun) -> Self {
+        Self {
+            run_a: RunSummary::from_run(run_a),
+            run_b: RunSummary::from_run(run_b),
+        }
+    }
+
+    /// Names of every state visited by either run, in order of first visit
+    pub fn state_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = Vec::new();
+        for visits in self.run_a.states.iter().chain(&self.run_b.states) {
+            if !names.contains(&visits.state.as_str()) {
+                names.push(&visits.state);
+            }
+        }
+        names
+    }
+
+    /// Whether the runs visited different states or executed different prompts
+    pub fn has_differences(&self) -> bool {
+        fn visits(summary: &RunSummary) -> BTreeMap<&str, usize> {
+            summary
+                .states
+                .iter()
+                .map(|visits| (visits.state.as_str(), visits.visits))
+                .collect()
+        }
+        fn prompt_io(summary: &RunSummary) -> Vec<(&str, &BTreeMap<String, String>, &str)> {
+            summary
+                .prompts
+                .iter()
+                .map(|prompt| {
+                    (
+                        prompt.prompt.as_str(),
+                        &prompt.arguments,
+                        prompt.response.as_str(),
+                    )
+                })
+                .collect()
+        }
+
+        self.run_a.status != self.run_b.status
+            || visits(&self.run_a) != visits(&self.run_b)
+            || prompt_io(&self.run_a) != prompt_io(&self.run_b)
+    }
+}
+
+/// Format milliseconds for display
+fn format_duration_ms(ms: i64) -> String {
+    if ms < 1000 {
+        format!("{ms}ms")
+    } else if ms < 60_000 {
+        format!("{:.1}s", ms as f64 / 1000.0)
+    } else {
+        format!("{}m {}s", ms / 60_000, (ms % 60_000) / 1000)
+    }
+}
+
+/// Format a cost for display
+fn format_cost(cost: Option<f64>) -> String {
+    cost.map(|cost| format!("${cost:.4}"))
+        .unwrap_or_else(|| "n/a".to_string())
+}
+
+/// Shorten a prompt response to its first line
+fn preview(response: &str) -> String {
+    let first_line = response.lines().next().unwrap_or_default().trim();
+    let truncated: String = first_line.chars().take(MAX_RESPONSE_PREVIEW).collect();
+    if truncated.len() < first_line.len() || response.trim().lines().nth(1).is_some() {
+        format!("{truncated}…")
+    } else {
+        truncated
+    }
+}
+
+/// Format prompt arguments for display
+fn format_arguments(arguments: &BTreeMap<String, String>) -> String {
+    if arguments.is_empty() {
+        return "-".to_string();
+    }
+    arguments
+        .iter()
+        .map(|(key, value)| format!("{key}={value}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Write rows of `[label, run A, run B]`, marking rows whose values differ
+fn write_rows(f: &mut fmt::Formatter<'_>, heading: &str, rows: &[[String; 3]]) -> fmt::Result {
+    let label_width = rows
+        .iter()
+        .map(|row| row[0].chars().count())
+        .chain([heading.chars().count()])
+        .max()
+        .unwrap_or(0);
+    let a_width = rows
+        .iter()
+        .map(|row| row[1].chars().count())
+        .chain(["Run A".len()])
+        .max()
+        .unwrap_or(0);
+
+    writeln!(f, "{heading:<label_width$}  {:<a_width$}  Run B", "Run A")?;
+    for [label, a, b] in rows {
+        let marker = if a != b { "*" } else { " " };
+        writeln!(f, "{marker}{label:<label_width$} {a:<a_width$}  {b}")?;
+    }
+    Ok(())
+}
+
+impl fmt::Display for RunComparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (a, b) = (&self.run_a, &self.run_b);
+        let duration = |summary: &RunSummary| {
+            summary
+                .duration_ms
+                .map(format_duration_ms)
+                .unwrap_or_else(|| "n/a".to_string())
+        };
+
+        write_rows(
+            f,
+            "Run",
+            &[
+                ["Run ID".to_string(), a.run_id.clone(), b.run_id.clone()],
+                [
+                    "Workflow".to_string(),
+                    a.workflow.clone(),
+                    b.workflow.clone(),
+                ],
+                [
+                    "Status".to_string(),
+                    format!("{:?}", a.status),
+                    format!("{:?}", b.status),
+                ],
+                ["Duration".to_string(), duration(a), duration(b)],
+                [
+                    "Cost".to_string(),
+                    format_cost(a.total_cost_usd),
+                    format_cost(b.total_cost_usd),
+                ],
+            ],
+        )?;
+
+        let visits = |summary: &RunSummary, state: &str| {
+            summary
+                .state(state)
+                .map(|visits| {
+                    format!(
+                        "{}x {}",
+                        visits.visits,
+                        format_duration_ms(visits.duration_ms)
+                    )
+                })
+                .unwrap_or_else(|| "-".to_string())
+        };
+        let state_rows: Vec<[String; 3]> = self
+            .state_names()
+            .into_iter()
+            .map(|state| [state.to_string(), visits(a, state), visits(b, state)])
+            .collect();
+        writeln!(f)?;
+        write_rows(f, "States", &state_rows)?;
+
+        let prompt_count = a.prompts.len().max(b.prompts.len());
+        if prompt_count > 0 {
+            let fields: [(&str, fn(&PromptRecord) -> String); 4] = [
+                ("prompt", |p| p.prompt.clone()),
+                ("arguments", |p| format_arguments(&p.arguments)),
+                ("response", |p| preview(&p.response)),
+                ("cost", |p| format_cost(p.cost_usd)),
+            ];
+            let field = |summary: &RunSummary, index: usize, get: fn(&PromptRecord) -> String| {
+                summary
+                    .prompts
+                    .get(index)
+                    .map(get)
+                    .unwrap_or_else(|| "-".to_string())
+            };
+
+            let mut prompt_rows = Vec::new();
+            for index in 0..prompt_count {
+                for (name, get) in fields {
+                    prompt_rows.push([
+                        format!("#{} {name}", index + 1),
+                        field(a, index, get),
+                        field(b, index, get),
+                    ]);
+                }
+            }
+            writeln!(f)?;
+            write_rows(f, "Prompts", &prompt_rows)?;
+        }
+
+        writeln!(f)?;
+        if self.has_differences() {
+            write!(f, "* marks values that differ between the runs")
+        } else {
+            write!(
+                f,
+                "Runs visited the same states with the same prompt inputs and outputs"
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workflow::test_helpers::*;
+    use crate::workflow::StateId;
+    use chrono::Duration;
+
+    fn prompt(name: &str, response: &str, cost_usd: Option<f64>) -> PromptRecord {
+        PromptRecord {
+            prompt: name.to_string(),
+            arguments: BTreeMap::from([("file".to_string(), "main.rs".to_string())]),
+            response: response.to_string(),
+            cost_usd,
+            tokens: None,
+            hash: None,
+        }
+    }
+
+    fn run_with_history(states: &[(&str, i64)], finished_after_ms: i64) -> WorkflowRun {
+        let mut run = WorkflowRun::new(create_basic_workflow());
+        let start = run.started_at;
+        run.history = states
+            .iter()
+            .map(|(state, at_ms)| (StateId::new(*state), start + Duration::milliseconds(*at_ms)))
+            .collect();
+        run.status = WorkflowRunStatus::Completed;
+        run.completed_at = Some(start + Duration::milliseconds(finished_after_ms));
+        run
+    }
+
+    #[test]
+    fn test_prompt_history_round_trip() {
+        let mut context = HashMap::new();
+        prompt("review", "looks good", Some(0.01)).record(&mut context);
+        prompt("summarize", "done", None).record(&mut context);
+
+        let history = PromptRecord::history(&context);
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].prompt, "review");
+        assert_eq!(history[1].cost_usd, None);
+    }
+
+    #[test]
+    fn test_summary_durations_and_visits() {
+        let run = run_with_history(
+            &[("start", 0), ("work", 100), ("start", 400), ("end", 500)],
+            800,
+        );
+        let summary = RunSummary::from_run(&run);
+
+        assert_eq!(summary.duration_ms, Some(800));
+        assert_eq!(summary.states.len(), 3);
+        let start = summary.state("start").unwrap();
+        assert_eq!((start.visits, start.duration_ms), (2, 200));
+        assert_eq!(summary.state("work").unwrap().duration_ms, 300);
+        assert_eq!(summary.state("end").unwrap().duration_ms, 300);
+        assert_eq!(summary.total_cost_usd, None);
+    }
+
+    #[test]
+    fn test_comparison_reports_differences() {
+        let mut run_a = run_with_history(&[("start", 0), ("end", 100)], 200);
+        prompt("review", "looks good", Some(0.01)).record(&mut run_a.context);
+        prompt("review", "ok", Some(0.02)).record(&mut run_a.context);
+
+        let mut run_b = run_with_history(&[("start", 0), ("retry", 100), ("end", 300)], 400);
+        prompt("review", "needs work", Some(0.03)).record(&mut run_b.context);
+
+        let comparison = RunComparison::new(&run_a, &run_b);
+        assert!(comparison.has_differences());
+        assert_eq!(comparison.state_names(), vec!["start", "end", "retry"]);
+        assert!((comparison.run_a.total_cost_usd.unwrap() - 0.03).abs() < 1e-9);
+
+        let report = comparison.to_string();
+        assert!(report.contains("$0.0300"));
+        assert!(report.contains("*retry"));
+        assert!(report.contains("*#1 response"));
+        assert!(report.contains(" #1 arguments"));
+        assert!(report.contains("file=main.rs"));
+    }
+
+    #[test]
+    fn test_identical_runs_have_no_differences() {
+        let run = run_with_history(&[("start", 0), ("end", 100)], 200);
+        let comparison = RunComparison::new(&run, &run);
+
+        assert!(!comparison.has_differences());
+        assert!(comparison.to_string().contains("same states"));
+    }
+
+    #[test]
+    fn test_preview_truncates_long_responses() {
+        assert_eq!(preview("short"), "short");
+        assert_eq!(preview("first\nsecond"), "first…");
+        assert_eq!(
+            preview(&"x".repeat(100)).chars().count(),
+            MAX_RESPONSE_PREVIEW + 1
+        );
+    }
+}
@@ -0,0 +1,267 @@
+//! Keeping a run's Claude conversation inside the model's context window
+//!
+//! Prompt actions continue the run's Claude conversation by default, so a long
+//! run keeps adding to one conversation until Claude rejects it as too long.
+//! Each prompt's tokens are added up under [`CONTEXT_TOKENS_KEY`], and before a
+//! prompt would take the conversation past the compaction threshold the
+//! conversation is compacted: Claude summarizes it with a summarization prompt,
+//! or the older turns are dropped, and the prompt starts a new conversation
+//! carrying the summary or the most recent responses.
+//!
+//! The limits are set per workflow in the `context_window` key of the front
+//! matter:
+//!
+//! ```yaml
+//! context_window:
+//!   max_tokens: 200000        # Size of the model's context window
+//!   compact_at_percent: 80    # Compact once the conversation fills this much of it
+//!   strategy: summarize       # Or truncate, to keep only the latest responses
+//!   summary_prompt: summarize_conversation
+//! ```
+
+use crate::workflow::actions::CLAUDE_SESSION_ID_KEY;
+use crate::workflow::PromptRecord;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Context key holding the tokens the run's Claude conversation has accumulated
+pub const CONTEXT_TOKENS_KEY: &str = "_context_tokens";
+
+/// Context key holding the run's context window settings
+pub const CONTEXT_WINDOW_KEY: &str = "_context_window";
+
+/// Context window of the models the Claude CLI uses, in tokens
+pub const DEFAULT_CONTEXT_WINDOW_TOKENS: u64 = 200_000;
+
+/// Share of the context window at which the conversation is compacted
+pub const DEFAULT_COMPACT_AT_PERCENT: u32 = 80;
+
+/// Builtin prompt that summarizes the conversation so far
+pub const DEFAULT_SUMMARY_PROMPT: &str = "summarize_conversation";
+
+/// Share of the context window the latest responses may fill when truncating
+const TRUNCATED_CARRY_PERCENT: u64 = 10;
+
+/// Characters per token used to estimate prompts Claude did not count
+const CHARS_PER_TOKEN: u64 = 4;
+
+/// How a conversation nearing the context limit is compacted
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CompactionStrategy {
+    /// Ask Claude to summarize the conversation and carry the summary over
+    #[default]
+    Summarize,
+    /// Drop the older turns and carry over only the latest responses
+    Truncate,
+}
+
+/// Context window limits for a workflow's Claude conversation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ContextWindowSettings {
+    /// Size of the model's context window in tokens
+    pub max_tokens: u64,
+    /// Percent of the window at which the conversation is compacted
+    pub compact_at_percent: u32,
+    /// How the conversation is compacted
+    pub strategy: CompactionStrategy,
+    /// Prompt that summarizes the conversation, for the summarize strategy
+    pub summary_prompt: String,
+}
+
+impl Default for ContextWindowSettings {
+    fn default() -> Self {
+        Self {
+            max_tokens: DEFAULT_CONTEXT_WINDOW_TOKENS,
+            compact_at_percent: DEFAULT_COMPACT_AT_PERCENT,
+            strategy: CompactionStrategy::default(),
+            summary_prompt: DEFAULT_SUMMARY_PROMPT.to_string(),
+        }
+    }
+}
+
+impl ContextWindowSettings {
+    /// The settings of the run a context belongs to
+    pub fn from_context(context: &HashMap<String, Value>) -> Self {
+        context
+            .get(CONTEXT_WINDOW_KEY)
+            .and_then(|settings| serde_json::from_value(settings.clone()).ok())
+            .unwrap_or_default()
+    }
+
+    /// Store the settings in a run context for its prompt actions
+    pub fn store(&self, context: &mut HashMap<String, Value>) {
+        if let Ok(settings) = serde_json::to_value(self) {
+            context.insert(CONTEXT_WINDOW_KEY.to_string(), settings);
+        }
+    }
+
+    /// Check that the limits make sense
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_tokens == 0 {
+            return Err("context_window.max_tokens must be greater than 0".to_string());
+        }
+        if !(1..=100).contains(&self.compact_at_percent) {
+            return Err(format!(
+                "context_window.compact_at_percent must be between 1 and 100, not {}",
+                self.compact_at_percent
+            ));
+        }
+        if self.strategy == CompactionStrategy::Summarize && self.summary_prompt.trim().is_empty() {
+            return Err("context_window.summary_prompt cannot be empty".to_string());
+        }
+        Ok(())
+    }
+
+    /// Tokens at which the conversation is compacted
+    pub fn compact_at_tokens(&self) -> u64 {
+        self.max_tokens * u64::from(self.compact_at_percent) / 100
+    }
+
+    /// Whether sending `prompt` would take the run's conversation past the
+    /// compaction threshold
+    ///
+    /// Only a conversation that is being continued can overflow.
+    pub fn needs_compaction(&self, context: &HashMap<String, Value>, prompt: &str) -> bool {
+        context.contains_key(CLAUDE_SESSION_ID_KEY)
+            && conversation_tokens(context) + estimate_tokens(prompt) >= self.compact_at_tokens()
+    }
+
+    /// The latest prompt responses of the run, oldest first, that fit in the
+    /// share of the window a truncated conversation carries over
+    pub fn latest_responses(&self, context: &HashMap<String, Value>) -> String {
+        let budget = self.max_tokens * TRUNCATED_CARRY_PERCENT / 100;
+        let mut used = 0;
+        let mut responses = Vec::new();
+        for record in PromptRecord::history(context).into_iter().rev() {
+            used += estimate_tokens(&record.response);
+            if used > budget {
+                break;
+            }
+            responses.push(format!("{}:\n{}", record.prompt, record.response));
+        }
+        responses.reverse();
+        responses.join("\n\n")
+    }
+}
+
+/// Rough number of tokens in a text
+pub fn estimate_tokens(text: &str) -> u64 {
+    (text.chars().count() as u64 + CHARS_PER_TOKEN - 1) / CHARS_PER_TOKEN
+}
+
+/// Tokens the run's Claude conversation has accumulated
+pub fn conversation_tokens(context: &HashMap<String, Value>) -> u64 {
+    context
+        .get(CONTEXT_TOKENS_KEY)
+        .and_then(Value::as_u64)
+        .unwrap_or(0)
+}
+
+/// Record the size of the run's Claude conversation
+pub fn set_conversation_tokens(context: &mut HashMap<String, Value>, tokens: u64) {
+    context.insert(CONTEXT_TOKENS_KEY.to_string(), Value::from(tokens));
+}
+
+/// A prompt that starts a new conversation carrying what was kept of the
+/// old one
+pub fn carry_over(kept: &str, prompt: &str) -> String {
+    if kept.trim().is_empty() {
+        return prompt.to_string();
+    }
+    format!(
+        "The earlier conversation was compacted to stay within the context window. What it covered:\n\n{}\n\n---\n\n{prompt}",
+        kept.trim()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn record(prompt: &str, response: &str) -> PromptRecord {
+        PromptRecord {
+            prompt: prompt.to_string(),
+            arguments: BTreeMap::new(),
+            response: response.to_string(),
+            cost_usd: None,
+            tokens: None,
+            hash: None,
+        }
+    }
+
+    #[test]
+    fn test_needs_compaction() {
+        let settings = ContextWindowSettings {
+            max_tokens: 1000,
+            compact_at_percent: 80,
+            ..Default::default()
+        };
+        assert_eq!(settings.compact_at_tokens(), 800);
+
+        let mut context = HashMap::new();
+        set_conversation_tokens(&mut context, 790);
+        let prompt = "x".repeat(40);
+        // A new conversation never needs compacting
+        assert!(!settings.needs_compaction(&context, &prompt));
+
+        context.insert(CLAUDE_SESSION_ID_KEY.to_string(), Value::from("session"));
+        assert!(!settings.needs_compaction(&context, "short"));
+        assert!(settings.needs_compaction(&context, &prompt));
+
+        // The settings travel with the run context
+        settings.store(&mut context);
+        assert_eq!(ContextWindowSettings::from_context(&context), settings);
+        assert_eq!(
+            ContextWindowSettings::from_context(&HashMap::new()),
+            ContextWindowSettings::default()
+        );
+    }
+
+    #[test]
+    fn test_latest_responses_fit_the_carry_budget() {
+        let settings = ContextWindowSettings {
+            max_tokens: 1000,
+            strategy: CompactionStrategy::Truncate,
+            ..Default::default()
+        };
+        let mut context = HashMap::new();
+        record("plan", &"p".repeat(400)).record(&mut context);
+        record("code", &"c".repeat(200)).record(&mut context);
+        record("review", "Looks good").record(&mut context);
+
+        // 100 tokens fit: the review and the code, but not the plan
+        let kept = settings.latest_responses(&context);
+        assert!(kept.starts_with("code:\n"));
+        assert!(kept.ends_with("review:\nLooks good"));
+        assert!(!kept.contains("plan:"));
+
+        let prompt = carry_over(&kept, "Next step");
+        assert!(prompt.contains("compacted"));
+        assert!(prompt.ends_with("---\n\nNext step"));
+        assert_eq!(carry_over("  ", "Next step"), "Next step");
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(ContextWindowSettings::default().validate().is_ok());
+        let settings: ContextWindowSettings =
+            serde_yaml::from_str("max_tokens: 100000\nstrategy: truncate").unwrap();
+        assert_eq!(settings.strategy, CompactionStrategy::Truncate);
+        assert_eq!(settings.compact_at_percent, DEFAULT_COMPACT_AT_PERCENT);
+        assert!(settings.validate().is_ok());
+
+        let settings = ContextWindowSettings {
+            compact_at_percent: 120,
+            ..Default::default()
+        };
+        assert!(settings
+            .validate()
+            .unwrap_err()
+            .contains("between 1 and 100"));
+        assert!(serde_yaml::from_str::<ContextWindowSettings>("limit: 5").is_err());
+    }
+}
@@ -1,7 +1,10 @@
 //! Main workflow type and validation
 
 use crate::validation::{Validatable, ValidationIssue, ValidationLevel};
-use crate::workflow::{State, StateId, Transition};
+use crate::workflow::{
+    ContextWindowSettings, OutputGuard, PromptBackend, State, StateId, Transition, WorkflowHooks,
+    WorkflowInvariant,
+};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::Path;
@@ -65,13 +68,38 @@ impl std::fmt::Display for WorkflowName {
     }
 }
 
+/// A template variable a workflow declares in its front matter
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkflowVariable {
+    /// Name the variable is set by with `--set` and used by in templates
+    pub name: String,
+    /// What the variable is for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Value used when the variable is not set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<serde_json::Value>,
+    /// Whether the workflow refuses to run without the variable
+    #[serde(default)]
+    pub required: bool,
+}
+
 /// Main workflow representation
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Workflow {
     /// Workflow name
     pub name: WorkflowName,
+    /// Human-readable title from the front matter
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
     /// Workflow description
     pub description: String,
+    /// Tags from the front matter
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Template variables declared in the front matter
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variables: Vec<WorkflowVariable>,
     /// All states in the workflow
     pub states: HashMap<StateId, State>,
     /// All transitions in the workflow
@@ -80,6 +108,18 @@ pub struct Workflow {
     pub initial_state: StateId,
     /// Metadata for debugging and monitoring
     pub metadata: HashMap<String, String>,
+    /// Lifecycle hooks run when a run starts, changes state, or fails
+    #[serde(default)]
+    pub hooks: WorkflowHooks,
+    /// Limits that keep the run's Claude conversation inside the context window
+    #[serde(default)]
+    pub context_window: ContextWindowSettings,
+    /// Backend the workflow's prompts are sent to unless an action names one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backend: Option<PromptBackend>,
+    /// Conditions checked every time the run enters a state
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub invariants: Vec<WorkflowInvariant>,
 }
 
 impl Workflow {
@@ -87,11 +127,18 @@ impl Workflow {
     pub fn new(name: WorkflowName, description: String, initial_state: StateId) -> Self {
         Self {
             name,
+            title: None,
             description,
+            tags: Vec::new(),
+            variables: Vec::new(),
             states: Default::default(),
             transitions: Vec::new(),
             initial_state,
             metadata: Default::default(),
+            hooks: Default::default(),
+            context_window: Default::default(),
+            backend: None,
+            invariants: Vec::new(),
         }
     }
 
@@ -135,6 +182,54 @@ impl Workflow {
                     transition.to_state
                 ));
             }
+            if let Some(expression) = transition
+                .condition
+                .expression
+                .as_deref()
+                .filter(|expression| OutputGuard::is_guard(expression))
+            {
+                if let Err(e) = OutputGuard::parse(expression) {
+                    errors.push(format!(
+                        "Transition '{}' -> '{}': {e}",
+                        transition.from_state, transition.to_state
+                    ));
+                }
+            }
+        }
+
+        // Check the declared variables
+        let mut seen = std::collections::HashSet::new();
+        for variable in &self.variables {
+            let name = variable.name.as_str();
+            if !is_variable_name(name) {
+                errors.push(format!(
+                    "Variable '{name}' must start with a letter or underscore and contain only letters, digits, '_' and '.'"
+                ));
+            }
+            if !seen.insert(name) {
+                errors.push(format!("Variable '{name}' is declared more than once"));
+            }
+            if variable.required && variable.default.is_some() {
+                errors.push(format!(
+                    "Variable '{name}' is required but has a default. Remove one of them"
+                ));
+            }
+        }
+        for invariant in &self.invariants {
+            if let Err(e) = invariant.validate() {
+                errors.push(e);
+            }
+            for state in &invariant.states {
+                if !self.states.contains_key(&StateId::new(state)) {
+                    errors.push(format!(
+                        "Invariant '{}' names non-existent state: '{state}'",
+                        invariant.describe()
+                    ));
+                }
+            }
+        }
+        if self.tags.iter().any(|tag| tag.trim().is_empty()) {
+            errors.push("Tags cannot be empty".to_string());
         }
 
         // Check for at least one terminal state
@@ -150,6 +245,33 @@ impl Workflow {
         }
     }
 
+    /// Fill in the defaults of declared variables missing from `values`
+    ///
+    /// Returns the names of required variables that have no value.
+    pub fn apply_variable_defaults(
+        &self,
+        values: &mut HashMap<String, serde_json::Value>,
+    ) -> Result<(), Vec<String>> {
+        let mut missing = Vec::new();
+        for variable in &self.variables {
+            if values.contains_key(&variable.name) {
+                continue;
+            }
+            match &variable.default {
+                Some(default) => {
+                    values.insert(variable.name.clone(), default.clone());
+                }
+                None if variable.required => missing.push(variable.name.clone()),
+                None => {}
+            }
+        }
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(missing)
+        }
+    }
+
     /// Add a state to the workflow
     pub fn add_state(&mut self, state: State) {
         self.states.insert(state.id.clone(), state);
@@ -161,6 +283,15 @@ impl Workflow {
     }
 }
 
+/// Whether a name can be used as a liquid template variable
+fn is_variable_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+}
+
 impl Validatable for Workflow {
     fn validate(&self, source_path: Option<&Path>) -> Vec<ValidationIssue> {
         match self.validate_structure() {
@@ -191,6 +322,7 @@ impl Validatable for Workflow {
 mod tests {
     use super::*;
     use crate::workflow::test_helpers::*;
+    use crate::workflow::ConditionType;
 
     #[test]
     fn test_workflow_validation_success() {
@@ -227,4 +359,81 @@ mod tests {
         let errors = result.unwrap_err();
         assert!(errors.iter().any(|e| e.contains("terminal state")));
     }
+
+    #[test]
+    fn test_workflow_validation_output_guards() {
+        let mut workflow = create_basic_workflow();
+        let mut transition = create_transition("start", "end", ConditionType::Custom);
+        transition.condition.expression = Some(r#"output.matches("(DONE")"#.to_string());
+        workflow.add_transition(transition);
+
+        let errors = workflow.validate_structure().unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("Invalid output guard"), "{}", errors[0]);
+
+        workflow
+            .transitions
+            .last_mut()
+            .unwrap()
+            .condition
+            .expression = Some(r#"output.contains("DONE")"#.to_string());
+        assert!(workflow.validate_structure().is_ok());
+    }
+
+    #[test]
+    fn test_workflow_variables() {
+        let mut workflow = create_basic_workflow();
+        workflow.variables = vec![
+            WorkflowVariable {
+                name: "name".to_string(),
+                description: None,
+                default: None,
+                required: true,
+            },
+            WorkflowVariable {
+                name: "language".to_string(),
+                description: Some("Language to greet in".to_string()),
+                default: Some(serde_json::json!("English")),
+                required: false,
+            },
+            WorkflowVariable {
+                name: "style".to_string(),
+                description: None,
+                default: None,
+                required: false,
+            },
+        ];
+        assert!(workflow.validate_structure().is_ok());
+
+        let mut values = HashMap::new();
+        assert_eq!(
+            workflow.apply_variable_defaults(&mut values),
+            Err(vec!["name".to_string()])
+        );
+        values.insert("name".to_string(), serde_json::json!("Ada"));
+        values.insert("language".to_string(), serde_json::json!("French"));
+        assert_eq!(workflow.apply_variable_defaults(&mut values), Ok(()));
+        assert_eq!(values["language"], "French");
+        assert!(!values.contains_key("style"));
+
+        values.remove("language");
+        workflow.apply_variable_defaults(&mut values).unwrap();
+        assert_eq!(values["language"], "English");
+
+        workflow.variables[0].default = Some(serde_json::json!("World"));
+        workflow.variables[2].name = "language".to_string();
+        workflow.tags = vec![" ".to_string()];
+        let errors = workflow.validate_structure().unwrap_err();
+        assert_eq!(errors.len(), 3, "{errors:?}");
+        assert!(errors[0].contains("required but has a default"));
+        assert!(errors[1].contains("declared more than once"));
+        assert!(errors[2].contains("Tags cannot be empty"));
+
+        workflow.variables.truncate(1);
+        workflow.variables[0].required = false;
+        workflow.variables[0].name = "1st".to_string();
+        workflow.tags.clear();
+        let errors = workflow.validate_structure().unwrap_err();
+        assert!(errors[0].contains("must start with a letter"), "{errors:?}");
+    }
 }
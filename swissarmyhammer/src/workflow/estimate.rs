@@ -0,0 +1,499 @@
+//! Cost forecasts for workflows
+//!
+//! [`WorkflowEstimate`] walks a workflow's state machine from its initial
+//! state to each terminal state and totals the prompts every path executes.
+//! Prompts are rendered with the given variables and counted at four
+//! characters per token. Their responses are assumed to be between the low
+//! and high output ratios of [`CostRates`] times the prompt, which gives a
+//! cost range per path. A state that runs a sub-workflow costs between the
+//! sub-workflow's cheapest and most expensive paths.
+//!
+//! Loops are followed once, so every extra pass a run makes costs more than
+//! the forecast. A prompt that can't be rendered with the given variables is
+//! estimated from its unrendered template.
+
+use super::actions::VariableSubstitution;
+use crate::cost::CostRates;
+use crate::workflow::{
+    parse_action_from_description_with_context, PromptAction, StateId, SubWorkflowAction, Workflow,
+    WorkflowGraphAnalyzer, WorkflowName, WorkflowStorage,
+};
+use crate::PromptLibrary;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::ops::Add;
+
+/// Characters counted as one token
+pub const CHARS_PER_TOKEN: usize = 4;
+
+/// Tokens and cost of part of a workflow
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct CostEstimate {
+    /// Prompts executed
+    pub prompts: usize,
+    /// Tokens sent to Claude
+    pub input_tokens: u64,
+    /// Tokens Claude responds with
+    pub output_tokens: u64,
+    /// US dollars charged
+    pub cost_usd: f64,
+}
+
+impl Add for CostEstimate {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            prompts: self.prompts + other.prompts,
+            input_tokens: self.input_tokens + other.input_tokens,
+            output_tokens: self.output_tokens + other.output_tokens,
+            cost_usd: self.cost_usd + other.cost_usd,
+        }
+    }
+}
+
+/// Low and high estimate of part of a workflow
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct CostRange {
+    /// Estimate with terse responses and the cheapest sub-workflow paths
+    pub low: CostEstimate,
+    /// Estimate with long responses and the most expensive sub-workflow paths
+    pub high: CostEstimate,
+}
+
+impl Add for CostRange {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            low: self.low + other.low,
+            high: self.high + other.high,
+        }
+    }
+}
+
+impl fmt::Display for CostRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "${:.4} - ${:.4}", self.low.cost_usd, self.high.cost_usd)
+    }
+}
+
+/// Estimate of a state that runs a prompt or a sub-workflow
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StateEstimate {
+    /// State ID
+    pub state: String,
+    /// What the state runs, such as `prompt review/code`
+    pub action: String,
+    /// Cost of running the state once
+    pub cost: CostRange,
+    /// Why the estimate is rougher than usual
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+/// Estimate of one path from the initial state to a terminal state
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PathEstimate {
+    /// States along the path
+    pub states: Vec<String>,
+    /// Cost of the path
+    pub cost: CostRange,
+}
+
+/// Cost forecast of a workflow
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WorkflowEstimate {
+    /// Workflow name
+    pub workflow: String,
+    /// Prices and output ratios the estimate uses
+    pub rates: CostRates,
+    /// States that run prompts or sub-workflows, ordered by state ID
+    pub states: Vec<StateEstimate>,
+    /// Paths through the workflow, cheapest first
+    pub paths: Vec<PathEstimate>,
+    /// Whether runs may loop, costing more than any path
+    pub has_loops: bool,
+}
+
+impl WorkflowEstimate {
+    /// Estimate a workflow, reading sub-workflows from storage
+    ///
+    /// Action descriptions and prompt arguments are resolved against
+    /// `context` the same way they are when the workflow runs.
+    pub fn new(
+        workflow: &Workflow,
+        context: &HashMap<String, Value>,
+        library: &PromptLibrary,
+        storage: &WorkflowStorage,
+        rates: CostRates,
+    ) -> Self {
+        let mut estimator = Estimator {
+            library,
+            storage,
+            rates,
+            stack: Vec::new(),
+        };
+        estimator.estimate(workflow, context)
+    }
+
+    /// Range from the cheapest path's low to the most expensive path's high estimate
+    pub fn range(&self) -> CostRange {
+        let low = self
+            .paths
+            .iter()
+            .map(|path| path.cost.low)
+            .min_by(|a, b| a.cost_usd.total_cmp(&b.cost_usd));
+        let high = self
+            .paths
+            .iter()
+            .map(|path| path.cost.high)
+            .max_by(|a, b| a.cost_usd.total_cmp(&b.cost_usd));
+        CostRange {
+            low: low.unwrap_or_default(),
+            high: high.unwrap_or_default(),
+        }
+    }
+}
+
+impl fmt::Display for WorkflowEstimate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Cost estimate for workflow '{}'", self.workflow)?;
+        writeln!(
+            f,
+            "  ${:.2} per million input tokens, ${:.2} per million output tokens",
+            self.rates.input_usd_per_mtok, self.rates.output_usd_per_mtok
+        )?;
+        writeln!(
+            f,
+            "  responses {}x to {}x the prompt",
+            self.rates.output_ratio_low, self.rates.output_ratio_high
+        )?;
+
+        if !self.states.is_empty() {
+            writeln!(f)?;
+            writeln!(f, "States")?;
+            let width = self.states.iter().map(|s| s.state.len()).max().unwrap_or(0);
+            for state in &self.states {
+                write!(
+                    f,
+                    "  {:<width$}  {}  ~{} input tokens  {}",
+                    state.state, state.action, state.cost.high.input_tokens, state.cost
+                )?;
+                match &state.note {
+                    Some(note) => writeln!(f, " ({note})")?,
+                    None => writeln!(f)?,
+                }
+            }
+        }
+
+        writeln!(f)?;
+        writeln!(f, "Paths")?;
+        if self.paths.is_empty() {
+            writeln!(f, "  no path reaches a terminal state")?;
+        }
+        for path in &self.paths {
+            writeln!(
+                f,
+                "  {}: {} prompts, {}",
+                path.states.join(" -> "),
+                path.cost.high.prompts,
+                path.cost
+            )?;
+        }
+
+        writeln!(f)?;
+        write!(f, "Estimated cost: {}", self.range())?;
+        if self.has_loops {
+            write!(f, " per pass; loops cost more for every repeat")?;
+        }
+        writeln!(f)
+    }
+}
+
+/// Walks a workflow and the sub-workflows it runs
+struct Estimator<'a> {
+    library: &'a PromptLibrary,
+    storage: &'a WorkflowStorage,
+    rates: CostRates,
+    /// Workflows being estimated, to catch workflows that run themselves
+    stack: Vec<String>,
+}
+
+impl Estimator<'_> {
+    fn estimate(
+        &mut self,
+        workflow: &Workflow,
+        context: &HashMap<String, Value>,
+    ) -> WorkflowEstimate {
+        self.stack.push(workflow.name.to_string());
+
+        let mut state_ids: Vec<&StateId> = workflow.states.keys().collect();
+        state_ids.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+        let mut states = Vec::new();
+        for id in state_ids {
+            if let Some(estimate) =
+                self.estimate_state(id, &workflow.states[id].description, context)
+            {
+                states.push(estimate);
+            }
+        }
+
+        let analyzer = WorkflowGraphAnalyzer::new(workflow);
+        let mut terminals: Vec<&StateId> = workflow
+            .states
+            .values()
+            .filter(|state| state.is_terminal)
+            .map(|state| &state.id)
+            .collect();
+        terminals.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+
+        let mut seen = HashSet::new();
+        let mut paths = Vec::new();
+        for terminal in terminals {
+            for path in analyzer.find_paths(&workflow.initial_state, terminal) {
+                let names: Vec<String> = path.iter().map(|id| id.to_string()).collect();
+                if !seen.insert(names.clone()) {
+                    continue;
+                }
+                let cost = states
+                    .iter()
+                    .filter(|state| names.contains(&state.state))
+                    .fold(CostRange::default(), |total, state| total + state.cost);
+                paths.push(PathEstimate {
+                    states: names,
+                    cost,
+                });
+            }
+        }
+        paths.sort_by(|a, b| a.cost.high.cost_usd.total_cmp(&b.cost.high.cost_usd));
+
+        self.stack.pop();
+        WorkflowEstimate {
+            workflow: workflow.name.to_string(),
+            rates: self.rates,
+            states,
+            paths,
+            has_loops: !analyzer.detect_all_cycles().is_empty(),
+        }
+    }
+
+    /// Estimate a state's action, if it runs a prompt or a sub-workflow
+    fn estimate_state(
+        &mut self,
+        id: &StateId,
+        description: &str,
+        context: &HashMap<String, Value>,
+    ) -> Option<StateEstimate> {
+        let Ok(Some(action)) = parse_action_from_description_with_context(description, context)
+        else {
+            return None;
+        };
+        let action = action.as_any();
+
+        let estimate = if let Some(prompt) = action.downcast_ref::<PromptAction>() {
+            let (cost, note) = self.estimate_prompt(prompt, context);
+            StateEstimate {
+                state: id.to_string(),
+                action: format!("prompt {}", prompt.prompt_name),
+                cost,
+                note,
+            }
+        } else if let Some(sub_workflow) = action.downcast_ref::<SubWorkflowAction>() {
+            let (cost, note) = self.estimate_sub_workflow(sub_workflow, context);
+            StateEstimate {
+                state: id.to_string(),
+                action: format!("workflow {}", sub_workflow.workflow_name),
+                cost,
+                note,
+            }
+        } else {
+            return None;
+        };
+        Some(estimate)
+    }
+
+    fn estimate_prompt(
+        &self,
+        prompt: &PromptAction,
+        context: &HashMap<String, Value>,
+    ) -> (CostRange, Option<String>) {
+        let arguments = prompt.substitute_variables(context);
+        let (text, note) = match self.library.render_prompt(&prompt.prompt_name, &arguments) {
+            Ok(text) => (text, None),
+            Err(render_error) => match self.library.get(&prompt.prompt_name) {
+                Ok(unrendered) => (
+                    unrendered.template,
+                    Some(format!(
+                        "estimated from the unrendered template: {render_error}"
+                    )),
+                ),
+                Err(e) => return (CostRange::default(), Some(e.to_string())),
+            },
+        };
+
+        let input_tokens =
+            text.chars().count().saturating_add(CHARS_PER_TOKEN - 1) / CHARS_PER_TOKEN;
+        let input_tokens = input_tokens as u64;
+        let estimate = |ratio: f64| {
+            let output_tokens = (input_tokens as f64 * ratio).round() as u64;
+            CostEstimate {
+                prompts: 1,
+                input_tokens,
+                output_tokens,
+                cost_usd: self.rates.cost_usd(input_tokens, output_tokens),
+            }
+        };
+        let cost = CostRange {
+            low: estimate(self.rates.output_ratio_low),
+            high: estimate(self.rates.output_ratio_high),
+        };
+        (cost, note)
+    }
+
+    fn estimate_sub_workflow(
+        &mut self,
+        sub_workflow: &SubWorkflowAction,
+        context: &HashMap<String, Value>,
+    ) -> (CostRange, Option<String>) {
+        let name = &sub_workflow.workflow_name;
+        if self.stack.contains(name) {
+            return (
+                CostRange::default(),
+                Some(format!("'{name}' runs itself and is counted once")),
+            );
+        }
+        let workflow = match self.storage.get_workflow(&WorkflowName::new(name)) {
+            Ok(workflow) => workflow,
+            Err(e) => return (CostRange::default(), Some(e.to_string())),
+        };
+
+        let mut sub_context = context.clone();
+        for (key, value) in sub_workflow.substitute_map(&sub_workflow.input_variables, context) {
+            sub_context.insert(key, Value::String(value));
+        }
+        let estimate = self.estimate(&workflow, &sub_context);
+        let note = estimate
+            .has_loops
+            .then(|| format!("'{name}' loops, each pass counted once"));
+        (estimate.range(), note)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workflow::MermaidParser;
+    use crate::Prompt;
+
+    const RATES: CostRates = CostRates {
+        input_usd_per_mtok: 3.0,
+        output_usd_per_mtok: 15.0,
+        output_ratio_low: 0.5,
+        output_ratio_high: 2.0,
+    };
+
+    fn workflow(name: &str, content: &str) -> Workflow {
+        MermaidParser::parse(content, name).unwrap()
+    }
+
+    #[test]
+    fn test_estimate_paths_and_sub_workflows() {
+        let mut library = PromptLibrary::new();
+        library
+            .add(Prompt::new("plan", "Plan {{ task }}".repeat(10)))
+            .unwrap();
+        library.add(Prompt::new("code", "x".repeat(400))).unwrap();
+        library
+            .add(Prompt::new("review", "Review {{ missing | upcase }} {% if"))
+            .unwrap();
+
+        let mut storage = WorkflowStorage::memory();
+        storage
+            .store_workflow(workflow(
+                "implement",
+                r#"```mermaid
+stateDiagram-v2
+    [*] --> code
+    code --> check
+    check --> code
+    check --> [*]
+```
+
+## Actions
+
+- code: Execute prompt "code"
+- check: Log "checking"
+"#,
+            ))
+            .unwrap();
+
+        let main = workflow(
+            "main",
+            r#"```mermaid
+stateDiagram-v2
+    [*] --> plan
+    plan --> quick
+    plan --> implement
+    quick --> [*]
+    implement --> review
+    review --> [*]
+```
+
+## Actions
+
+- plan: Execute prompt "plan" with task="${task}"
+- quick: Log "nothing to do"
+- implement: run workflow "implement"
+- review: Execute prompt "review"
+"#,
+        );
+        let context = HashMap::from([("task".to_string(), Value::String("it".to_string()))]);
+        let estimate = WorkflowEstimate::new(&main, &context, &library, &storage, RATES);
+
+        let states: Vec<(&str, &str)> = estimate
+            .states
+            .iter()
+            .map(|s| (s.state.as_str(), s.action.as_str()))
+            .collect();
+        assert_eq!(
+            states,
+            vec![
+                ("implement", "workflow implement"),
+                ("plan", "prompt plan"),
+                ("review", "prompt review"),
+            ]
+        );
+
+        // "Plan it" ten times is 70 characters, 18 tokens
+        let plan = &estimate.states[1];
+        assert_eq!(plan.cost.low.input_tokens, 18);
+        assert_eq!(plan.cost.low.output_tokens, 9);
+        assert_eq!(plan.cost.high.output_tokens, 36);
+        assert!((plan.cost.high.cost_usd - (18.0 * 3.0 + 36.0 * 15.0) / 1e6).abs() < 1e-12);
+
+        // The sub-workflow loops, and its prompt is counted once
+        let implement = &estimate.states[0];
+        assert_eq!(implement.cost.high.input_tokens, 100);
+        assert!(implement.note.as_deref().unwrap().contains("loops"));
+
+        // The broken template is estimated unrendered
+        assert!(estimate.states[2].note.is_some());
+
+        let paths: Vec<String> = estimate
+            .paths
+            .iter()
+            .map(|path| path.states.join(" -> "))
+            .collect();
+        assert_eq!(paths, vec!["plan -> quick", "plan -> implement -> review"]);
+        assert_eq!(estimate.paths[1].cost.high.prompts, 3);
+        assert_eq!(estimate.range().low, estimate.paths[0].cost.low);
+        assert_eq!(estimate.range().high, estimate.paths[1].cost.high);
+        assert!(!estimate.has_loops);
+        assert!(estimate
+            .to_string()
+            .contains("plan -> implement -> review: 3 prompts"));
+    }
+}
@@ -4,11 +4,15 @@ use super::{
     ExecutionEvent, ExecutionEventType, ExecutorError, ExecutorResult, DEFAULT_MAX_HISTORY_SIZE,
     LAST_ACTION_RESULT_KEY, MAX_TRANSITIONS,
 };
+use crate::events::{event_bus, Event};
 use crate::workflow::{
+    check_invariants, hook_context,
     metrics::{MemoryMetrics, WorkflowMetrics},
-    parse_action_from_description_with_context, ActionError, CompensationKey, ErrorContext,
-    StateId, TransitionKey, TransitionPath, Workflow, WorkflowCacheManager, WorkflowRun,
-    WorkflowRunStatus,
+    parse_action_from_description_with_context, pin_dir_for, record_failure, ActionError,
+    CompensationKey, ErrorContext, FailureKind, HookDetails, RunCancellation, RunPins, StateId,
+    StateLockGuard, StateLocks, TransitionKey, TransitionPath, Workflow, WorkflowCacheManager,
+    WorkflowHookEvent, WorkflowProgress, WorkflowProgressEvent, WorkflowRun, WorkflowRunStatus,
+    PIN_DIR_KEY, RUN_ID_KEY, STATE_ID_KEY, WORKFLOW_NAME_KEY,
 };
 use cel_interpreter::Program;
 use serde_json::Value;
@@ -27,6 +31,12 @@ pub struct WorkflowExecutor {
     cache_manager: WorkflowCacheManager,
     /// Optional workflow storage for test mode
     test_storage: Option<Arc<crate::workflow::storage::WorkflowStorage>>,
+    /// Named locks held while states that declare them run
+    state_locks: StateLocks,
+    /// Listeners notified as states start and finish
+    progress: Vec<Arc<dyn WorkflowProgress>>,
+    /// Markers asking in-flight runs to stop
+    cancellation: RunCancellation,
 }
 
 impl WorkflowExecutor {
@@ -38,6 +48,9 @@ impl WorkflowExecutor {
             metrics: WorkflowMetrics::new(),
             cache_manager: WorkflowCacheManager::new(),
             test_storage: None,
+            state_locks: StateLocks::default(),
+            progress: Vec::new(),
+            cancellation: RunCancellation::default(),
         }
     }
 
@@ -49,9 +62,54 @@ impl WorkflowExecutor {
             metrics: WorkflowMetrics::new(),
             cache_manager: WorkflowCacheManager::new(),
             test_storage: Some(storage),
+            state_locks: StateLocks::default(),
+            progress: Vec::new(),
+            cancellation: RunCancellation::default(),
         }
     }
 
+    /// Report the progress of runs to a listener, as well as to the
+    /// listeners added before
+    pub fn add_progress(&mut self, progress: Arc<dyn WorkflowProgress>) {
+        self.progress.push(progress);
+    }
+
+    /// Use a different directory of cancellation markers
+    pub fn set_cancellation(&mut self, cancellation: RunCancellation) {
+        self.cancellation = cancellation;
+    }
+
+    /// Notify the progress listeners
+    fn report_progress(&self, run: &WorkflowRun, event: WorkflowProgressEvent) {
+        for progress in &self.progress {
+            progress.on_progress(run, &event);
+        }
+    }
+
+    /// Set where the named locks of states are kept
+    pub fn set_state_locks(&mut self, state_locks: StateLocks) {
+        self.state_locks = state_locks;
+    }
+
+    /// Acquire the locks a state declares, waiting while other runs hold them
+    pub async fn acquire_state_locks(
+        &self,
+        workflow: &Workflow,
+        state_id: &StateId,
+        holder: &str,
+    ) -> ExecutorResult<StateLockGuard> {
+        let locks = workflow
+            .states
+            .get(state_id)
+            .map(|state| state.locks())
+            .unwrap_or_default();
+        self.state_locks.acquire(&locks, holder).await.map_err(|e| {
+            ExecutorError::ExecutionFailed(format!(
+                "Failed to acquire locks of state '{state_id}': {e}"
+            ))
+        })
+    }
+
     /// Get the workflow storage (test storage if available, otherwise create file system storage)
     pub fn get_storage(&self) -> crate::Result<Arc<crate::workflow::storage::WorkflowStorage>> {
         if let Some(storage) = &self.test_storage {
@@ -70,7 +128,29 @@ impl WorkflowExecutor {
             .validate_structure()
             .map_err(|errors| ExecutorError::ValidationFailed(errors.join("; ")))?;
 
-        let run = WorkflowRun::new(workflow);
+        let mut run = WorkflowRun::new(workflow);
+
+        // Prompts and sub-workflows are pinned here the first time the run loads them
+        run.context.insert(
+            PIN_DIR_KEY.to_string(),
+            Value::String(pin_dir_for(&run.id).display().to_string()),
+        );
+
+        // Prompt costs are attributed to the run they are spent in
+        run.context
+            .insert(RUN_ID_KEY.to_string(), Value::String(run.id.to_string()));
+        run.context.insert(
+            WORKFLOW_NAME_KEY.to_string(),
+            Value::String(run.workflow.name.to_string()),
+        );
+
+        // Prompts keep the run's Claude conversation within these limits
+        run.workflow.context_window.store(&mut run.context);
+
+        // Prompts without a backend of their own use the workflow's
+        if let Some(backend) = run.workflow.backend {
+            backend.store(&mut run.context);
+        }
 
         // Start metrics tracking for this run
         self.metrics.start_run(run.id, run.workflow.name.clone());
@@ -89,23 +169,15 @@ impl WorkflowExecutor {
         workflow: Workflow,
     ) -> ExecutorResult<WorkflowRun> {
         let mut run = self.start_workflow(workflow)?;
+        self.run_hook(&run, WorkflowHookEvent::Start, HookDetails::default())
+            .await;
 
         // Execute the initial state with transition limit
         let result = self
             .execute_state_with_limit(&mut run, MAX_TRANSITIONS)
             .await;
 
-        // Complete metrics tracking
-        match &result {
-            Ok(_) => {
-                self.metrics.complete_run(&run.id, run.status, None);
-            }
-            Err(e) => {
-                self.metrics
-                    .complete_run(&run.id, WorkflowRunStatus::Failed, Some(e.to_string()));
-            }
-        }
-
+        self.finish_run(&run, &result);
         result.map(|_| run)
     }
 
@@ -131,23 +203,35 @@ impl WorkflowExecutor {
             .execute_state_with_limit(&mut run, MAX_TRANSITIONS)
             .await;
 
-        // Complete metrics tracking
-        match &result {
-            Ok(_) => {
+        self.finish_run(&run, &result);
+        result.map(|_| run)
+    }
+
+    /// Complete metrics tracking for a run that stopped executing, and
+    /// announce it if it finished
+    fn finish_run(&mut self, run: &WorkflowRun, result: &ExecutorResult<()>) {
+        let status = match result {
+            Ok(()) => {
                 self.metrics.complete_run(&run.id, run.status, None);
+                run.status
             }
             Err(e) => {
                 self.metrics
                     .complete_run(&run.id, WorkflowRunStatus::Failed, Some(e.to_string()));
+                WorkflowRunStatus::Failed
             }
+        };
+        if let Some(event) = Event::run_finished(run, status) {
+            event_bus().publish(event);
         }
-
-        result.map(|_| run)
     }
 
     /// Check if workflow execution should stop
     pub fn is_workflow_finished(&self, run: &WorkflowRun) -> bool {
-        run.status == WorkflowRunStatus::Completed || run.status == WorkflowRunStatus::Failed
+        matches!(
+            run.status,
+            WorkflowRunStatus::Completed | WorkflowRunStatus::Failed | WorkflowRunStatus::Cancelled
+        )
     }
 
     /// Execute a single execution cycle: state execution and potential transition
@@ -159,6 +243,11 @@ impl WorkflowExecutor {
 
         // Check if workflow is complete after state execution
         if self.is_workflow_finished(run) {
+            if let Some(error) = &state_error {
+                if run.status == WorkflowRunStatus::Failed {
+                    self.run_failure_hook(run, error).await;
+                }
+            }
             return Ok(false); // No transition needed, workflow finished
         }
 
@@ -198,6 +287,21 @@ impl WorkflowExecutor {
             return Ok(false);
         }
 
+        // Move to the fallback state of an action whose output could not be parsed
+        if let Some(ExecutorError::ActionError(ActionError::ExtractionError {
+            fallback_state: Some(fallback_state),
+            ..
+        })) = &state_error
+        {
+            let fallback_state = StateId::new(fallback_state);
+            self.log_event(
+                ExecutionEventType::StateTransition,
+                format!("Transitioning to extraction fallback state: {fallback_state}"),
+            );
+            self.perform_transition(run, fallback_state)?;
+            return Ok(true);
+        }
+
         // Evaluate and perform transition
         if let Some(next_state) = self.evaluate_transitions(run)? {
             self.perform_transition(run, next_state)?;
@@ -230,25 +334,151 @@ impl WorkflowExecutor {
                 "Workflow execution loop - current state: {}",
                 run.current_state
             );
-            let transition_performed = self.execute_single_cycle(run).await?;
+            if self.cancellation.is_requested(&run.id) {
+                self.cancel_run(run).await;
+                break;
+            }
+
+            let previous_state = run.current_state.clone();
+            // Dropping the cycle abandons its action and kills the processes it started
+            let cancellation = self.cancellation.clone();
+            let run_id = run.id;
+            let cycle_result = tokio::select! {
+                result = self.execute_single_cycle(run) => result,
+                _ = cancellation.cancelled(&run_id) => {
+                    self.cancel_run(run).await;
+                    break;
+                }
+            };
+
+            if run.current_state != previous_state {
+                let details = HookDetails {
+                    previous_state: Some(previous_state.to_string()),
+                    ..Default::default()
+                };
+                self.run_hook(run, WorkflowHookEvent::StateChange, details)
+                    .await;
+            }
+
+            let transition_performed = match cycle_result {
+                Ok(transition_performed) => transition_performed,
+                Err(error) => {
+                    self.run_failure_hook(run, &error).await;
+                    return Err(error);
+                }
+            };
+
+            // Stop before the next state runs when the repository drifted
+            // from what the workflow expects of it
+            if run.current_state != previous_state {
+                if let Err(message) =
+                    check_invariants(&run.workflow.invariants, &run.current_state, &run.context)
+                {
+                    let error = ExecutorError::InvariantViolated(message);
+                    self.log_event(ExecutionEventType::Failed, error.to_string());
+                    self.run_failure_hook(run, &error).await;
+                    return Err(error);
+                }
+            }
 
             if !transition_performed {
                 // Either workflow finished or no transitions available
                 tracing::debug!("No transition performed, exiting loop");
+                if self.is_workflow_finished(run) {
+                    RunPins::release(run);
+                    self.cancellation.clear(&run.id);
+                }
                 break;
             }
 
             current_remaining -= 1;
             if current_remaining == 0 {
-                return Err(ExecutorError::TransitionLimitExceeded {
+                let error = ExecutorError::TransitionLimitExceeded {
                     limit: MAX_TRANSITIONS,
-                });
+                };
+                self.run_failure_hook(run, &error).await;
+                return Err(error);
             }
         }
 
         Ok(())
     }
 
+    /// Run the hook configured for a lifecycle event, if any
+    ///
+    /// Hooks run with a copy of the run context, so they cannot change the run.
+    /// A hook that fails is logged and otherwise ignored.
+    pub async fn run_hook(
+        &mut self,
+        run: &WorkflowRun,
+        event: WorkflowHookEvent,
+        details: HookDetails,
+    ) {
+        let Some(hook) = run.workflow.hooks.get(event) else {
+            return;
+        };
+
+        let mut context = hook_context(run, event, &details);
+        let result = match parse_action_from_description_with_context(hook, &context) {
+            Ok(Some(action)) => action.execute(&mut context).await.map(|_| ()),
+            Ok(None) => Err(ActionError::ParseError(format!(
+                "Hook is not a valid action: {hook}"
+            ))),
+            Err(e) => Err(e),
+        };
+
+        match result {
+            Ok(()) => self.log_event(
+                ExecutionEventType::StateExecution,
+                format!("Ran {event} hook for workflow {}", run.workflow.name),
+            ),
+            Err(e) => {
+                tracing::warn!(
+                    "{} hook failed for workflow {}: {}",
+                    event,
+                    run.workflow.name,
+                    e
+                );
+                self.log_event(
+                    ExecutionEventType::StateExecution,
+                    format!(
+                        "{event} hook failed for workflow {}: {e}",
+                        run.workflow.name
+                    ),
+                );
+            }
+        }
+    }
+
+    /// Stop a run that was asked to stop and run its cancel hook
+    async fn cancel_run(&mut self, run: &mut WorkflowRun) {
+        tracing::info!(
+            "Cancelled workflow {} in state {}",
+            run.workflow.name,
+            run.current_state
+        );
+        self.log_event(
+            ExecutionEventType::Failed,
+            format!("Cancelled workflow in state: {}", run.current_state),
+        );
+        run.cancel();
+        record_failure(run, FailureKind::UserAbort, "Run cancelled");
+        self.run_hook(run, WorkflowHookEvent::Cancel, HookDetails::default())
+            .await;
+        RunPins::release(run);
+        self.cancellation.clear(&run.id);
+    }
+
+    /// Run the failure hook for an error that stopped the run
+    async fn run_failure_hook(&mut self, run: &WorkflowRun, error: &ExecutorError) {
+        let details = HookDetails {
+            error: Some(error.to_string()),
+            ..Default::default()
+        };
+        self.run_hook(run, WorkflowHookEvent::Failure, details)
+            .await;
+    }
+
     /// Execute the current state and evaluate transitions
     pub async fn execute_state(&mut self, run: &mut WorkflowRun) -> ExecutorResult<()> {
         self.execute_state_with_limit(run, MAX_TRANSITIONS).await
@@ -315,10 +545,36 @@ impl WorkflowExecutor {
             current_state_id,
             state_description
         );
-        let action_executed = self.execute_state_action(run, &state_description).await?;
+        self.report_progress(
+            run,
+            WorkflowProgressEvent::StateStarted {
+                state: current_state_id.clone(),
+                description: state_description.clone(),
+            },
+        );
+        let holder = format!(
+            "{} run {} state {}",
+            run.workflow.name, run.id, current_state_id
+        );
+        let action_result = match self
+            .acquire_state_locks(&run.workflow, &current_state_id, &holder)
+            .await
+        {
+            Ok(_locks) => self.execute_state_action(run, &state_description).await,
+            Err(e) => Err(e),
+        };
 
         // Record state execution duration
         let state_duration = state_start_time.elapsed();
+        self.report_progress(
+            run,
+            WorkflowProgressEvent::StateFinished {
+                state: current_state_id.clone(),
+                elapsed: state_duration,
+                succeeded: action_result.is_ok(),
+            },
+        );
+        let action_executed = action_result?;
         self.metrics
             .record_state_execution(&run.id, current_state_id.clone(), state_duration);
 
@@ -403,6 +659,12 @@ impl WorkflowExecutor {
 
         // Record transition in metrics
         self.metrics.record_transition(&run.id);
+        event_bus().publish(Event::WorkflowStateChanged {
+            run_id: run.id,
+            workflow: run.workflow.name.clone(),
+            from: run.current_state.clone(),
+            to: next_state.clone(),
+        });
 
         // Update the run
         run.transition_to(next_state);
@@ -474,6 +736,10 @@ impl WorkflowExecutor {
         run: &mut WorkflowRun,
         action: Box<dyn crate::workflow::Action>,
     ) -> Result<Value, ActionError> {
+        run.context.insert(
+            STATE_ID_KEY.to_string(),
+            Value::String(run.current_state.to_string()),
+        );
         action.execute(&mut run.context).await
     }
 
@@ -619,6 +885,9 @@ impl WorkflowExecutor {
                 format!("Rate limit reached: {message}. Please wait {wait_time:?} before retrying.")
             }
             ActionError::AbortError(msg) => format!("ABORT ERROR: {msg}"),
+            ActionError::ExtractionError { message, .. } => {
+                format!("Output extraction failed: {message}")
+            }
         }
     }
 
@@ -4,6 +4,7 @@ use super::core::WorkflowExecutor;
 use super::{ExecutionEventType, ExecutorError, ExecutorResult, LAST_ACTION_RESULT_KEY};
 use crate::workflow::{
     parse_action_from_description_with_context, StateId, StateType, Workflow, WorkflowRun,
+    STATE_ID_KEY,
 };
 use serde_json::Value;
 use std::collections::HashMap;
@@ -338,11 +339,19 @@ impl WorkflowExecutor {
                 &current_state.description,
                 &branch.context,
             )? {
+                let holder = format!("{} branch state {}", workflow.name, branch.current_state);
+                let _locks = self
+                    .acquire_state_locks(workflow, &branch.current_state, &holder)
+                    .await?;
                 self.log_event(
                     ExecutionEventType::StateExecution,
                     format!("Branch executing action: {}", action.description()),
                 );
 
+                branch.context.insert(
+                    STATE_ID_KEY.to_string(),
+                    Value::String(branch.current_state.to_string()),
+                );
                 match action.execute(&mut branch.context).await {
                     Ok(_result) => {
                         // Mark action as successful
@@ -42,6 +42,9 @@ pub enum ExecutorError {
     /// Manual intervention required to continue workflow
     #[error("Manual intervention required: {0}")]
     ManualInterventionRequired(String),
+    /// An invariant of the workflow did not hold when a state was entered
+    #[error("Invariant violated: {0}")]
+    InvariantViolated(String),
 }
 
 /// Result type for executor operations
@@ -4,10 +4,12 @@ use super::*;
 use crate::workflow::test_helpers::*;
 use crate::workflow::{
     ConditionType, ErrorContext, StateId, StateType, Transition, TransitionCondition, Workflow,
-    WorkflowName, WorkflowRun, WorkflowRunStatus,
+    WorkflowHooks, WorkflowName, WorkflowRun, WorkflowRunStatus, RUN_ID_KEY, STATE_ID_KEY,
+    WORKFLOW_NAME_KEY,
 };
 use serde_json::Value;
 use std::collections::HashMap;
+use std::time::Duration;
 
 fn create_test_workflow() -> Workflow {
     let mut workflow = Workflow::new(
@@ -49,6 +51,25 @@ async fn test_start_workflow() {
     assert!(!executor.get_history().is_empty());
 }
 
+#[tokio::test]
+async fn test_actions_know_their_run_and_state() {
+    let mut executor = WorkflowExecutor::new();
+    let mut workflow = Workflow::new(
+        WorkflowName::new("Attributed Workflow"),
+        "A workflow whose actions are attributed".to_string(),
+        StateId::new("start"),
+    );
+    workflow.add_state(create_state("start", r#"Log "Starting""#, false));
+    workflow.add_state(create_state("end", "End state", true));
+    workflow.add_transition(create_transition("start", "end", ConditionType::Always));
+
+    let run = executor.start_and_execute_workflow(workflow).await.unwrap();
+
+    assert_eq!(run.context[RUN_ID_KEY], Value::String(run.id.to_string()));
+    assert_eq!(run.context[WORKFLOW_NAME_KEY], "Attributed Workflow");
+    assert_eq!(run.context[STATE_ID_KEY], "start");
+}
+
 #[tokio::test]
 async fn test_workflow_execution_to_completion() {
     let mut executor = WorkflowExecutor::new();
@@ -531,6 +552,39 @@ fn test_cel_expression_with_variables() {
     assert!(result);
 }
 
+#[test]
+fn test_output_guard_conditions() {
+    let mut executor = WorkflowExecutor::new();
+    let mut context = HashMap::new();
+    context.insert(
+        "result".to_string(),
+        Value::String("Wrote the file.\n```json\n{\"done\": true}\n```".to_string()),
+    );
+    let guard = |expression: &str| TransitionCondition {
+        condition_type: ConditionType::Custom,
+        expression: Some(expression.to_string()),
+    };
+
+    // Guards are not CEL, so words CEL forbids can be matched
+    assert!(executor
+        .evaluate_condition(&guard(r#"output.contains("the file")"#), &context)
+        .unwrap());
+    assert!(executor
+        .evaluate_condition(&guard("output.json.done == true"), &context)
+        .unwrap());
+    assert!(!executor
+        .evaluate_condition(&guard(r#"output.matches("^DONE$")"#), &context)
+        .unwrap());
+    assert!(executor
+        .evaluate_condition(&guard("output.json.done == yes"), &context)
+        .is_err());
+
+    // Without a result there is no output to match
+    assert!(!executor
+        .evaluate_condition(&guard(r#"output.contains("done")"#), &HashMap::new())
+        .unwrap());
+}
+
 #[test]
 fn test_cel_expression_invalid_syntax() {
     let mut executor = WorkflowExecutor::new();
@@ -1538,3 +1592,213 @@ async fn test_say_hello_workflow() {
         .details
         .contains("Hello from Swiss Army Hammer! The workflow system is working correctly.")));
 }
+
+#[tokio::test]
+async fn test_hooks_run_on_start_and_state_change() {
+    let mut executor = WorkflowExecutor::new();
+    let mut workflow = create_test_workflow();
+    workflow.hooks = WorkflowHooks {
+        on_start: Some("Log \"Starting ${workflow_name}\"".to_string()),
+        on_state_change: Some("Log \"${previous_state} -> ${current_state}\"".to_string()),
+        on_failure: Some("Log \"Failed: ${error}\"".to_string()),
+        on_cancel: None,
+    };
+
+    let run = executor.start_and_execute_workflow(workflow).await.unwrap();
+    assert_eq!(run.status, WorkflowRunStatus::Completed);
+
+    let history = executor.get_history();
+    let count = |text: &str| history.iter().filter(|e| e.details.contains(text)).count();
+    assert_eq!(count("Ran on_start hook"), 1);
+    assert_eq!(count("Ran on_state_change hook"), 2);
+    assert_eq!(count("Ran on_failure hook"), 0);
+
+    // Hooks run on a copy of the context
+    assert!(!run.context.contains_key("hook_event"));
+}
+
+#[tokio::test]
+async fn test_failure_hook_runs_when_workflow_fails() {
+    let mut executor = WorkflowExecutor::new();
+    let mut workflow = Workflow::new(
+        WorkflowName::new("Failing Hook Test"),
+        "Workflow that aborts".to_string(),
+        StateId::new("start"),
+    );
+    workflow.add_state(create_state("start", "Abort \"stop here\"", false));
+    workflow.add_state(create_state("end", "End state", true));
+    workflow.add_transition(create_transition("start", "end", ConditionType::OnSuccess));
+    workflow.hooks.on_failure = Some("Log \"Failed: ${error}\"".to_string());
+
+    let run = executor.start_and_execute_workflow(workflow).await.unwrap();
+    assert_eq!(run.status, WorkflowRunStatus::Failed);
+
+    let history = executor.get_history();
+    assert!(history
+        .iter()
+        .any(|e| e.details.contains("Ran on_failure hook")));
+}
+
+#[tokio::test]
+async fn test_cancelled_run_stops_and_runs_cancel_hook() {
+    let temp = tempfile::TempDir::new().unwrap();
+    let cancellation = crate::workflow::RunCancellation::new(temp.path());
+    let mut workflow = create_test_workflow();
+    workflow
+        .states
+        .get_mut(&StateId::new("processing"))
+        .unwrap()
+        .description = "Wait 30 seconds".to_string();
+    workflow.hooks.on_cancel = Some("Log \"Cancelled in ${current_state}\"".to_string());
+
+    let mut executor = WorkflowExecutor::new();
+    executor.set_cancellation(cancellation.clone());
+    let mut run = executor.start_workflow(workflow).unwrap();
+    let run_id = run.id;
+    let requester = cancellation.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        requester.request(&run_id).unwrap();
+    });
+
+    // The wait is abandoned rather than run to the end
+    tokio::time::timeout(Duration::from_secs(5), executor.execute_state(&mut run))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(run.status, WorkflowRunStatus::Cancelled);
+    assert_eq!(run.current_state, StateId::new("processing"));
+    assert!(run.completed_at.is_some());
+    assert_eq!(
+        crate::workflow::recorded_failure(&run),
+        Some(crate::workflow::FailureKind::UserAbort)
+    );
+    assert!(!cancellation.is_requested(&run.id));
+    assert!(executor
+        .get_history()
+        .iter()
+        .any(|e| e.details.contains("Ran on_cancel hook")));
+}
+
+#[tokio::test]
+async fn test_failing_hook_does_not_fail_workflow() {
+    let mut executor = WorkflowExecutor::new();
+    let mut workflow = create_test_workflow();
+    workflow.hooks.on_start = Some("Abort \"hook exploded\"".to_string());
+
+    let run = executor.start_and_execute_workflow(workflow).await.unwrap();
+    assert_eq!(run.status, WorkflowRunStatus::Completed);
+
+    let history = executor.get_history();
+    assert!(history
+        .iter()
+        .any(|e| e.details.contains("on_start hook failed")));
+}
+
+#[tokio::test]
+async fn test_invariants_are_checked_after_transitions() {
+    let mut workflow = create_test_workflow();
+    workflow.invariants = vec![crate::workflow::WorkflowInvariant {
+        not_empty: Some("ticket".to_string()),
+        states: vec!["processing".to_string()],
+        ..Default::default()
+    }];
+    workflow.hooks.on_failure = Some("Log \"Failed: ${error}\"".to_string());
+
+    let mut executor = WorkflowExecutor::new();
+    let result = executor.start_and_execute_workflow(workflow.clone()).await;
+    assert!(matches!(
+        result,
+        Err(ExecutorError::InvariantViolated(msg))
+            if msg.contains("entering state 'processing'") && msg.contains("'ticket'")
+    ));
+    assert!(executor
+        .get_history()
+        .iter()
+        .any(|e| e.details.contains("Ran on_failure hook")));
+
+    // The invariant holds once a state before it sets the variable
+    workflow
+        .states
+        .get_mut(&StateId::new("start"))
+        .unwrap()
+        .description = "Set ticket=\"T-1\"".to_string();
+    let run = WorkflowExecutor::new()
+        .start_and_execute_workflow(workflow)
+        .await
+        .unwrap();
+    assert_eq!(run.status, WorkflowRunStatus::Completed);
+}
+
+#[tokio::test]
+async fn test_state_waits_for_declared_locks() {
+    let temp = tempfile::TempDir::new().unwrap();
+    let locks =
+        crate::workflow::StateLocks::new(temp.path()).with_timeout(Duration::from_millis(200));
+    let mut workflow = create_test_workflow();
+    workflow
+        .states
+        .get_mut(&StateId::new("processing"))
+        .unwrap()
+        .metadata
+        .insert(
+            crate::workflow::LOCK_METADATA_KEY.to_string(),
+            "git".to_string(),
+        );
+
+    let mut executor = WorkflowExecutor::new();
+    executor.set_state_locks(locks.clone());
+    let mut run = WorkflowRun::new(workflow);
+    run.current_state = StateId::new("processing");
+
+    // Another run holding the lock keeps the state from running
+    let held = locks.acquire(&["git"], "other run").await.unwrap();
+    let error = executor.execute_single_state(&mut run).await.unwrap_err();
+    assert!(matches!(error, ExecutorError::ExecutionFailed(_)));
+    assert!(error.to_string().contains("held by other run"));
+
+    drop(held);
+    executor.execute_single_state(&mut run).await.unwrap();
+    assert!(!locks.lock_path("git").exists());
+}
+
+#[tokio::test]
+async fn test_progress_reports_each_state() {
+    #[derive(Default)]
+    struct Recorder(std::sync::Mutex<Vec<crate::workflow::WorkflowProgressEvent>>);
+
+    impl crate::workflow::WorkflowProgress for Recorder {
+        fn on_progress(&self, _run: &WorkflowRun, event: &crate::workflow::WorkflowProgressEvent) {
+            self.0.lock().unwrap().push(event.clone());
+        }
+    }
+
+    let recorder = std::sync::Arc::new(Recorder::default());
+    let mut executor = WorkflowExecutor::new();
+    executor.add_progress(recorder.clone());
+    let run = executor
+        .start_and_execute_workflow(create_test_workflow())
+        .await
+        .unwrap();
+    assert_eq!(run.status, WorkflowRunStatus::Completed);
+
+    let events = recorder.0.lock().unwrap();
+    let started: Vec<String> = events
+        .iter()
+        .filter_map(|event| match event {
+            crate::workflow::WorkflowProgressEvent::StateStarted { state, .. } => {
+                Some(state.to_string())
+            }
+            _ => None,
+        })
+        .collect();
+    assert_eq!(started, vec!["start", "processing", "end"]);
+    assert_eq!(events.len(), 2 * started.len());
+    assert!(matches!(
+        events.last(),
+        Some(crate::workflow::WorkflowProgressEvent::StateFinished {
+            succeeded: true,
+            ..
+        })
+    ));
+}
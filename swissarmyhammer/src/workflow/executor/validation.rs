@@ -20,6 +20,10 @@
 //! - `OnSuccess`: Evaluates based on last action success
 //! - `OnFailure`: Evaluates based on last action failure
 //!
+//! ## Output Guards
+//! - `Custom` expressions starting with `output.`, such as `output.contains("DONE")`,
+//!   are [`OutputGuard`]s checked against the `result` of the last action
+//!
 //! ## Custom CEL Expressions
 //! - `Custom`: Evaluates user-provided CEL expressions
 //! - Supports complex boolean logic, variable access, and text processing
@@ -102,7 +106,7 @@
 
 use super::core::WorkflowExecutor;
 use super::{ExecutionEventType, ExecutorError, ExecutorResult, LAST_ACTION_RESULT_KEY};
-use crate::workflow::{ConditionType, StateId, TransitionCondition, WorkflowRun};
+use crate::workflow::{ConditionType, OutputGuard, StateId, TransitionCondition, WorkflowRun};
 use cel_interpreter::{Context, Value as CelValue};
 use serde_json::Value;
 use std::collections::HashMap;
@@ -251,6 +255,12 @@ impl WorkflowExecutor {
             ConditionType::OnFailure => Ok(self.evaluate_action_condition(context, false, false)),
             ConditionType::Custom => {
                 if let Some(expression) = &condition.expression {
+                    if OutputGuard::is_guard(expression) {
+                        let guard = OutputGuard::parse(expression)
+                            .map_err(ExecutorError::ExpressionError)?;
+                        let output = context.get(RESULT_VARIABLE_NAME).unwrap_or(&Value::Null);
+                        return Ok(guard.evaluate(output));
+                    }
                     self.evaluate_cel_expression(expression, context)
                 } else {
                     Err(ExecutorError::ExpressionError(
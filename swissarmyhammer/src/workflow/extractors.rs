@@ -0,0 +1,318 @@
+//! Typed values extracted from Claude output
+//!
+//! Prompt actions can pull values out of Claude's freeform response and store
+//! them in workflow variables, so transitions branch on a parsed value rather
+//! than on the raw text. Each extractor is written as `<kind>:<expression>`,
+//! optionally followed by ` as <type>`:
+//!
+//! - `regex:<pattern>` takes the first capture group, or the whole match
+//! - `json:<path>` reads a JSON document from the response and follows a path
+//!   such as `$.review.issues[0].line`
+//! - `code` or `code:<language>` takes the body of the first fenced code block,
+//!   optionally the first one tagged with a language
+
+use crate::workflow::actions::{ActionError, ActionResult};
+use regex::Regex;
+use serde_json::Value;
+use std::fmt;
+
+/// How an extractor finds its value in the output
+#[derive(Debug, Clone)]
+pub enum ExtractorKind {
+    /// First capture group, or the whole match, of a regular expression
+    Regex(Regex),
+    /// Path into a JSON document found in the output
+    JsonPath(String),
+    /// Body of the first fenced code block, optionally of one language
+    CodeBlock(Option<String>),
+}
+
+/// Type an extracted value is converted to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtractedType {
+    /// Text
+    String,
+    /// Floating point or integer number
+    Number,
+    /// Whole number
+    Integer,
+    /// `true` or `false`
+    Boolean,
+    /// Any JSON value
+    Json,
+}
+
+impl ExtractedType {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "string" => Some(Self::String),
+            "number" => Some(Self::Number),
+            "integer" => Some(Self::Integer),
+            "boolean" => Some(Self::Boolean),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+
+    /// Convert a value to this type
+    fn convert(self, value: Value) -> Result<Value, String> {
+        let text = match &value {
+            Value::String(text) => text.trim().to_string(),
+            other => other.to_string(),
+        };
+        let converted = match self {
+            Self::String => Some(match value {
+                Value::String(text) => Value::String(text),
+                _ => Value::String(text.clone()),
+            }),
+            Self::Number => match value {
+                Value::Number(_) => Some(value),
+                _ => text
+                    .parse::<f64>()
+                    .ok()
+                    .and_then(serde_json::Number::from_f64)
+                    .map(Value::Number),
+            },
+            Self::Integer => value
+                .as_i64()
+                .or_else(|| text.parse::<i64>().ok())
+                .map(Value::from),
+            Self::Boolean => value
+                .as_bool()
+                .or_else(|| text.to_lowercase().parse::<bool>().ok())
+                .map(Value::Bool),
+            Self::Json => match value {
+                Value::String(_) => serde_json::from_str(&text).ok(),
+                _ => Some(value),
+            },
+        };
+        converted.ok_or_else(|| format!("'{text}' is not a valid {self}"))
+    }
+}
+
+impl fmt::Display for ExtractedType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::String => "string",
+            Self::Number => "number",
+            Self::Integer => "integer",
+            Self::Boolean => "boolean",
+            Self::Json => "json",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Extracts one typed value from Claude output into a workflow variable
+#[derive(Debug, Clone)]
+pub struct OutputExtractor {
+    /// Variable the value is stored in
+    pub variable: String,
+    /// How the value is found
+    pub kind: ExtractorKind,
+    /// Type the value is converted to; without one, values from JSON keep
+    /// their type and other values are strings
+    pub value_type: Option<ExtractedType>,
+}
+
+impl OutputExtractor {
+    /// Parse an extractor written as `<kind>:<expression>[ as <type>]`
+    pub fn parse(variable: &str, spec: &str) -> ActionResult<Self> {
+        let (spec, value_type) = match spec.rsplit_once(" as ") {
+            Some((rest, name)) => match ExtractedType::parse(name.trim()) {
+                Some(value_type) => (rest, Some(value_type)),
+                None => (spec, None),
+            },
+            None => (spec, None),
+        };
+        let (kind, expression) = spec.split_once(':').unwrap_or((spec, ""));
+
+        let kind = match kind.trim() {
+            "regex" => ExtractorKind::Regex(Regex::new(expression).map_err(|e| {
+                ActionError::ParseError(format!("Invalid regex for extracting '{variable}': {e}"))
+            })?),
+            "json" => ExtractorKind::JsonPath(expression.trim().to_string()),
+            "code" => ExtractorKind::CodeBlock(
+                Some(expression.trim().to_string()).filter(|language| !language.is_empty()),
+            ),
+            other => {
+                return Err(ActionError::ParseError(format!(
+                    "Invalid extractor '{other}' for '{variable}': must be regex, json or code"
+                )))
+            }
+        };
+        Ok(Self {
+            variable: variable.to_string(),
+            kind,
+            value_type,
+        })
+    }
+
+    /// Extract the value from the output, describing why when it cannot
+    pub fn extract(&self, output: &str) -> Result<Value, String> {
+        let value = match &self.kind {
+            ExtractorKind::Regex(regex) => regex
+                .captures(output)
+                .and_then(|captures| captures.get(1).or_else(|| captures.get(0)))
+                .map(|found| Value::String(found.as_str().to_string()))
+                .ok_or_else(|| format!("no match for /{}/", regex.as_str())),
+            ExtractorKind::JsonPath(path) => find_json(output)
+                .ok_or_else(|| "no JSON document found".to_string())
+                .and_then(|document| {
+                    select_json_path(&document, path)
+                        .cloned()
+                        .ok_or_else(|| format!("nothing at JSON path '{path}'"))
+                }),
+            ExtractorKind::CodeBlock(language) => fenced_code_blocks(output)
+                .into_iter()
+                .find(|(info, _)| language.as_ref().map_or(true, |language| info == language))
+                .map(|(_, body)| Value::String(body))
+                .ok_or_else(|| match language {
+                    Some(language) => format!("no {language} code block found"),
+                    None => "no code block found".to_string(),
+                }),
+        };
+        value
+            .and_then(|value| match self.value_type {
+                Some(value_type) => value_type.convert(value),
+                None => Ok(value),
+            })
+            .map_err(|reason| format!("Could not extract '{}': {reason}", self.variable))
+    }
+}
+
+/// Fenced code blocks in markdown as (language, body) pairs
+fn fenced_code_blocks(text: &str) -> Vec<(String, String)> {
+    let mut blocks = Vec::new();
+    let mut current: Option<(String, Vec<&str>)> = None;
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        match current.take() {
+            None => {
+                if let Some(info) = trimmed.strip_prefix("```") {
+                    let language = info.split_whitespace().next().unwrap_or("");
+                    current = Some((language.to_string(), Vec::new()));
+                }
+            }
+            Some((language, body)) if trimmed.starts_with("```") => {
+                blocks.push((language, body.join("\n")));
+            }
+            Some((language, mut body)) => {
+                body.push(line);
+                current = Some((language, body));
+            }
+        }
+    }
+    blocks
+}
+
+/// The whole output as JSON, or the first fenced code block holding JSON
+pub(crate) fn find_json(output: &str) -> Option<Value> {
+    serde_json::from_str(output.trim()).ok().or_else(|| {
+        fenced_code_blocks(output)
+            .into_iter()
+            .find_map(|(_, body)| serde_json::from_str(&body).ok())
+    })
+}
+
+/// Follow a path such as `$.items[0].name` into a JSON value
+pub(crate) fn select_json_path<'a>(document: &'a Value, path: &str) -> Option<&'a Value> {
+    let path = path.strip_prefix('$').unwrap_or(path);
+    let mut current = document;
+    for segment in path.split('.').filter(|segment| !segment.is_empty()) {
+        let (key, indexes) = segment.split_once('[').unwrap_or((segment, ""));
+        if !key.is_empty() {
+            current = current.get(key)?;
+        }
+        if !indexes.is_empty() {
+            for index in indexes.trim_end_matches(']').split("][") {
+                current = current.get(index.parse::<usize>().ok()?)?;
+            }
+        }
+    }
+    Some(current)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const OUTPUT: &str = r#"Reviewed the change. Found 3 issues.
+
+```json
+{"verdict": "changes_requested", "issues": [{"line": 12, "blocking": true}]}
+```
+
+```rust
+fn main() {}
+```
+"#;
+
+    fn extract(spec: &str, output: &str) -> Result<Value, String> {
+        OutputExtractor::parse("value", spec)
+            .unwrap()
+            .extract(output)
+    }
+
+    #[test]
+    fn test_regex_extractor() {
+        assert_eq!(
+            extract(r"regex:Found (\d+) issues as integer", OUTPUT),
+            Ok(json!(3))
+        );
+        assert_eq!(extract("regex:Reviewed", OUTPUT), Ok(json!("Reviewed")));
+        assert_eq!(
+            extract("regex:(Found) as number", OUTPUT),
+            Err("Could not extract 'value': 'Found' is not a valid number".to_string())
+        );
+        assert!(extract("regex:Approved", OUTPUT).is_err());
+    }
+
+    #[test]
+    fn test_json_path_extractor() {
+        assert_eq!(
+            extract("json:$.verdict", OUTPUT),
+            Ok(json!("changes_requested"))
+        );
+        assert_eq!(extract("json:$.issues[0].line", OUTPUT), Ok(json!(12)));
+        assert_eq!(
+            extract("json:issues[0].blocking as boolean", OUTPUT),
+            Ok(json!(true))
+        );
+        assert_eq!(
+            extract("json:$.issues[0].line as string", OUTPUT),
+            Ok(json!("12"))
+        );
+        assert_eq!(
+            extract("json:$", r#"{"a": [1, 2]}"#),
+            Ok(json!({"a": [1, 2]}))
+        );
+        assert!(extract("json:$.missing", OUTPUT).is_err());
+        assert!(extract("json:$.verdict", "no json here").is_err());
+    }
+
+    #[test]
+    fn test_code_block_extractor() {
+        assert_eq!(extract("code:rust", OUTPUT), Ok(json!("fn main() {}")));
+        assert_eq!(
+            extract("code as json", OUTPUT).unwrap()["verdict"],
+            json!("changes_requested")
+        );
+        assert_eq!(
+            extract("code:python", OUTPUT),
+            Err("Could not extract 'value': no python code block found".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_extractor_errors() {
+        assert!(OutputExtractor::parse("value", "xpath://a").is_err());
+        assert!(OutputExtractor::parse("value", "regex:(unclosed").is_err());
+
+        // An unknown type is part of the expression
+        let extractor = OutputExtractor::parse("value", "regex:a as b").unwrap();
+        assert_eq!(extractor.value_type, None);
+        assert_eq!(extractor.extract("xa as by"), Ok(json!("a as b")));
+    }
+}
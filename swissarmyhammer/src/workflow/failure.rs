@@ -0,0 +1,314 @@
+//! Classification of failed workflow runs and retries of whole runs
+//!
+//! Actions already retry rate limited Claude calls, but a run can still fail
+//! on an error that would pass if the run were simply started again, such as a
+//! dropped connection. When a run fails, its error is classified:
+//!
+//! - [`FailureKind::Transient`]: Claude, network and timeout errors
+//! - [`FailureKind::Validation`]: invalid workflows, actions, variables and output
+//! - [`FailureKind::GitConflict`]: merge and rebase conflicts
+//! - [`FailureKind::UserAbort`]: `ABORT ERROR` output and interrupted runs
+//! - [`FailureKind::Other`]: anything else
+//!
+//! With a [`RunRetryPolicy`] allowing retries, `flow run` starts transient
+//! failures again after a backoff that doubles with each retry. The kind and
+//! attempt number are kept in the metadata of each stored run, which also
+//! names the run it retries.
+
+use crate::config::Config;
+use crate::workflow::{ActionError, ExecutorError, WorkflowRun, WorkflowRunId};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::Duration;
+
+/// Run metadata key holding the [`FailureKind`] of a failed run
+pub const FAILURE_KIND_METADATA_KEY: &str = "failure_kind";
+
+/// Run metadata key holding the error a run failed with
+pub const FAILURE_MESSAGE_METADATA_KEY: &str = "failure_message";
+
+/// Run metadata key holding the attempt number, starting at 1
+pub const ATTEMPT_METADATA_KEY: &str = "attempt";
+
+/// Run metadata key holding the ID of the failed run a run retries
+pub const RETRY_OF_METADATA_KEY: &str = "retry_of";
+
+/// Why a workflow run failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailureKind {
+    /// A Claude, network or timeout error that may pass on another attempt
+    Transient,
+    /// The workflow, an action or its output is invalid
+    Validation,
+    /// A git merge or rebase stopped on conflicts
+    GitConflict,
+    /// The run was aborted or interrupted
+    UserAbort,
+    /// Any other failure
+    Other,
+}
+
+impl FailureKind {
+    /// Name used in run metadata and output
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FailureKind::Transient => "transient",
+            FailureKind::Validation => "validation",
+            FailureKind::GitConflict => "git_conflict",
+            FailureKind::UserAbort => "user_abort",
+            FailureKind::Other => "other",
+        }
+    }
+
+    /// Whether starting the run again may succeed
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, FailureKind::Transient)
+    }
+
+    /// Classify the error a run failed with
+    pub fn of_error(error: &ExecutorError) -> Self {
+        match error {
+            ExecutorError::ActionError(action_error) => match action_error {
+                ActionError::ClaudeError(message) => match Self::of_message(message) {
+                    FailureKind::Other => FailureKind::Transient,
+                    kind => kind,
+                },
+                ActionError::Timeout { .. } | ActionError::RateLimit { .. } => {
+                    FailureKind::Transient
+                }
+                ActionError::AbortError(_) => FailureKind::UserAbort,
+                ActionError::VariableError(_)
+                | ActionError::ParseError(_)
+                | ActionError::JsonError(_)
+                | ActionError::ExtractionError { .. } => FailureKind::Validation,
+                ActionError::ExecutionError(_) | ActionError::IoError(_) => {
+                    Self::of_message(&action_error.to_string())
+                }
+            },
+            ExecutorError::StateNotFound(_)
+            | ExecutorError::InvalidTransition(_)
+            | ExecutorError::ValidationFailed(_)
+            | ExecutorError::ExpressionError(_)
+            | ExecutorError::InvariantViolated(_) => FailureKind::Validation,
+            ExecutorError::ManualInterventionRequired(_) => FailureKind::UserAbort,
+            ExecutorError::TransitionLimitExceeded { .. }
+            | ExecutorError::ExecutionFailed(_)
+            | ExecutorError::WorkflowCompleted => Self::of_message(&error.to_string()),
+        }
+    }
+
+    /// Classify a failure from its error message
+    ///
+    /// Used where only the message of an error is left, such as errors
+    /// reported by the command line.
+    pub fn of_message(message: &str) -> Self {
+        let message = message.to_lowercase();
+        let mentions = |patterns: &[&str]| patterns.iter().any(|p| message.contains(p));
+
+        if mentions(&["abort error", "interrupted", "cancelled by user"]) {
+            FailureKind::UserAbort
+        } else if mentions(&[
+            "merge conflict",
+            "conflict (",
+            "automatic merge failed",
+            "could not apply",
+            "unmerged files",
+        ]) {
+            FailureKind::GitConflict
+        } else if mentions(&[
+            "claude execution failed",
+            "rate limit",
+            "timed out",
+            "timeout",
+            "overloaded",
+            "connection",
+            "network",
+            "temporarily unavailable",
+            "service unavailable",
+            "502",
+            "503",
+            "529",
+        ]) {
+            FailureKind::Transient
+        } else if mentions(&[
+            "validation failed",
+            "parsing failed",
+            "parse error",
+            "variable operation failed",
+            "output extraction failed",
+            "expression evaluation failed",
+            "invariant violated",
+            "state not found",
+            "invalid",
+            "required argument",
+        ]) {
+            FailureKind::Validation
+        } else {
+            FailureKind::Other
+        }
+    }
+}
+
+impl fmt::Display for FailureKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for FailureKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "transient" => Ok(FailureKind::Transient),
+            "validation" => Ok(FailureKind::Validation),
+            "git_conflict" => Ok(FailureKind::GitConflict),
+            "user_abort" => Ok(FailureKind::UserAbort),
+            "other" => Ok(FailureKind::Other),
+            _ => Err(format!("Unknown failure kind '{s}'")),
+        }
+    }
+}
+
+/// How often and how soon failed runs are started again
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RunRetryPolicy {
+    /// Retries after the first attempt; no retries when zero
+    pub max_retries: u32,
+    /// Wait before the first retry, doubled for each retry after it
+    pub backoff: Duration,
+    /// Longest wait between retries
+    pub max_backoff: Duration,
+}
+
+impl Default for RunRetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 0,
+            backoff: Duration::from_secs(30),
+            max_backoff: Duration::from_secs(600),
+        }
+    }
+}
+
+impl RunRetryPolicy {
+    /// The policy set in the configuration
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            max_retries: config.workflow_retry_attempts,
+            backoff: Duration::from_secs(config.workflow_retry_backoff_seconds),
+            max_backoff: Duration::from_secs(config.workflow_retry_max_backoff_seconds),
+        }
+    }
+
+    /// Whether a run that failed with `kind` after `retries` retries is retried
+    pub fn should_retry(&self, kind: FailureKind, retries: u32) -> bool {
+        kind.is_retryable() && retries < self.max_retries
+    }
+
+    /// Wait before retry number `retry`, counting from 1
+    pub fn backoff_for(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        self.backoff
+            .checked_mul(factor)
+            .map_or(self.max_backoff, |backoff| backoff.min(self.max_backoff))
+    }
+}
+
+/// Record in a run's metadata which attempt it is and which run it retries
+pub fn record_attempt(run: &mut WorkflowRun, attempt: u32, retry_of: Option<WorkflowRunId>) {
+    run.metadata
+        .insert(ATTEMPT_METADATA_KEY.to_string(), attempt.to_string());
+    if let Some(retry_of) = retry_of {
+        run.metadata
+            .insert(RETRY_OF_METADATA_KEY.to_string(), retry_of.to_string());
+    }
+}
+
+/// Record in a run's metadata why it failed
+pub fn record_failure(run: &mut WorkflowRun, kind: FailureKind, message: &str) {
+    run.metadata
+        .insert(FAILURE_KIND_METADATA_KEY.to_string(), kind.to_string());
+    run.metadata.insert(
+        FAILURE_MESSAGE_METADATA_KEY.to_string(),
+        message.to_string(),
+    );
+}
+
+/// The failure kind recorded for a run, if it failed
+pub fn recorded_failure(run: &WorkflowRun) -> Option<FailureKind> {
+    run.metadata
+        .get(FAILURE_KIND_METADATA_KEY)
+        .and_then(|kind| kind.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workflow::test_helpers::*;
+
+    #[test]
+    fn test_classify_errors() {
+        let claude = ExecutorError::ActionError(ActionError::ClaudeError(
+            "connection reset by peer".to_string(),
+        ));
+        assert_eq!(FailureKind::of_error(&claude), FailureKind::Transient);
+        let abort = ExecutorError::ActionError(ActionError::AbortError("stop".to_string()));
+        assert_eq!(FailureKind::of_error(&abort), FailureKind::UserAbort);
+        let parse = ExecutorError::ActionError(ActionError::ParseError("bad".to_string()));
+        assert_eq!(FailureKind::of_error(&parse), FailureKind::Validation);
+        let conflict = ExecutorError::ActionError(ActionError::ExecutionError(
+            "git command failed: CONFLICT (content): Merge conflict in src/lib.rs".to_string(),
+        ));
+        assert_eq!(FailureKind::of_error(&conflict), FailureKind::GitConflict);
+
+        for (message, kind) in [
+            (
+                "Failed to execute workflow 'ci' at state 'build': Action execution failed: Claude execution failed: API overloaded",
+                FailureKind::Transient,
+            ),
+            ("Workflow validation failed: no initial state", FailureKind::Validation),
+            ("ABORT ERROR: tests are red", FailureKind::UserAbort),
+            ("Automatic merge failed; fix conflicts", FailureKind::GitConflict),
+            ("disk full", FailureKind::Other),
+        ] {
+            assert_eq!(FailureKind::of_message(message), kind, "{message}");
+        }
+        assert_eq!("git_conflict".parse(), Ok(FailureKind::GitConflict));
+    }
+
+    #[test]
+    fn test_retry_policy() {
+        let policy = RunRetryPolicy {
+            max_retries: 2,
+            backoff: Duration::from_secs(10),
+            max_backoff: Duration::from_secs(25),
+        };
+        assert!(policy.should_retry(FailureKind::Transient, 0));
+        assert!(policy.should_retry(FailureKind::Transient, 1));
+        assert!(!policy.should_retry(FailureKind::Transient, 2));
+        assert!(!policy.should_retry(FailureKind::Validation, 0));
+        assert!(!RunRetryPolicy::default().should_retry(FailureKind::Transient, 0));
+
+        assert_eq!(policy.backoff_for(1), Duration::from_secs(10));
+        assert_eq!(policy.backoff_for(2), Duration::from_secs(20));
+        assert_eq!(policy.backoff_for(3), Duration::from_secs(25));
+        assert_eq!(policy.backoff_for(40), Duration::from_secs(25));
+    }
+
+    #[test]
+    fn test_record_failure_and_attempt() {
+        let mut first = WorkflowRun::new(create_basic_workflow());
+        record_attempt(&mut first, 1, None);
+        record_failure(&mut first, FailureKind::Transient, "API overloaded");
+        assert_eq!(recorded_failure(&first), Some(FailureKind::Transient));
+        assert!(!first.metadata.contains_key(RETRY_OF_METADATA_KEY));
+
+        let mut second = WorkflowRun::new(create_basic_workflow());
+        record_attempt(&mut second, 2, Some(first.id));
+        assert_eq!(second.metadata[ATTEMPT_METADATA_KEY], "2");
+        assert_eq!(second.metadata[RETRY_OF_METADATA_KEY], first.id.to_string());
+        assert_eq!(recorded_failure(&second), None);
+    }
+}
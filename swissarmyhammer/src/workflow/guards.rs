@@ -0,0 +1,228 @@
+//! Transition guards on the output of the last action
+//!
+//! "Is Claude finished?" checks are written as transition conditions on the
+//! result of the action that just ran:
+//!
+//! - `output.contains("DONE")` is true when the output contains the text
+//! - `output.matches("^All \\d+ tests pass")` is true when the regular
+//!   expression matches somewhere in the output
+//! - `output.json.status == "done"` reads a JSON document from the output, as
+//!   the `json:` extractor does, and compares the value at a path such as
+//!   `output.json.issues[0].line` with a JSON literal; `!=` is also allowed
+//!
+//! A leading `!` negates a guard. Guards are checked when a workflow is
+//! loaded, so a bad regular expression or a malformed literal is reported
+//! before the workflow runs rather than when the transition is reached.
+
+use crate::workflow::extractors::{find_json, select_json_path};
+use regex::Regex;
+use serde_json::Value;
+
+/// Prefix that marks a transition condition as an output guard
+const OUTPUT_PREFIX: &str = "output.";
+
+/// A check against the output of the last action
+#[derive(Debug, Clone)]
+pub enum OutputGuard {
+    /// The output contains the text
+    Contains(String),
+    /// The regular expression matches somewhere in the output
+    Matches(Regex),
+    /// The value at a path in the output's JSON document equals a literal
+    JsonEquals {
+        /// Path into the document, such as `review.issues[0].line`
+        path: String,
+        /// Value the path must hold
+        value: Value,
+    },
+    /// The guard does not hold
+    Not(Box<OutputGuard>),
+}
+
+impl OutputGuard {
+    /// Whether a transition condition is written as an output guard
+    pub fn is_guard(expression: &str) -> bool {
+        let expression = expression.trim();
+        expression
+            .strip_prefix('!')
+            .unwrap_or(expression)
+            .trim_start()
+            .starts_with(OUTPUT_PREFIX)
+    }
+
+    /// Parse a guard such as `output.contains("DONE")`
+    pub fn parse(expression: &str) -> Result<Self, String> {
+        let expression = expression.trim();
+        if let Some(negated) = expression.strip_prefix('!') {
+            return Ok(Self::Not(Box::new(Self::parse(negated)?)));
+        }
+        let invalid = |reason: &str| format!("Invalid output guard '{expression}': {reason}");
+
+        let guard = expression
+            .strip_prefix(OUTPUT_PREFIX)
+            .ok_or_else(|| invalid("must start with 'output.'"))?;
+        if let Some(argument) = call_argument(guard, "contains") {
+            let text = string_literal(argument).ok_or_else(|| invalid("expected a string"))?;
+            return Ok(Self::Contains(text));
+        }
+        if let Some(argument) = call_argument(guard, "matches") {
+            let pattern = string_literal(argument).ok_or_else(|| invalid("expected a string"))?;
+            let regex = Regex::new(&pattern).map_err(|e| invalid(&e.to_string()))?;
+            return Ok(Self::Matches(regex));
+        }
+        if let Some(comparison) = guard
+            .strip_prefix("json")
+            .filter(|rest| rest.is_empty() || rest.starts_with(['.', '[', ' ', '=', '!']))
+        {
+            let no_comparison = || invalid("expected a comparison with == or !=");
+            let (path, operator) =
+                comparison.split_at(comparison.find(['=', '!']).ok_or_else(no_comparison)?);
+            let (negated, literal) = match operator.split_at(operator.len().min(2)) {
+                ("==", literal) => (false, literal),
+                ("!=", literal) => (true, literal),
+                _ => return Err(no_comparison()),
+            };
+            let value = string_literal(literal)
+                .map(Value::String)
+                .or_else(|| serde_json::from_str(literal.trim()).ok())
+                .ok_or_else(|| invalid(&format!("'{}' is not a JSON value", literal.trim())))?;
+            let guard = Self::JsonEquals {
+                path: path.trim().to_string(),
+                value,
+            };
+            return Ok(if negated {
+                Self::Not(Box::new(guard))
+            } else {
+                guard
+            });
+        }
+        Err(invalid(
+            "expected output.contains(..), output.matches(..) or output.json",
+        ))
+    }
+
+    /// Check the guard against an action result
+    pub fn evaluate(&self, output: &Value) -> bool {
+        match self {
+            Self::Contains(text) => output_text(output).contains(text.as_str()),
+            Self::Matches(regex) => regex.is_match(&output_text(output)),
+            Self::JsonEquals { path, value } => {
+                let document = match output {
+                    Value::String(text) => find_json(text),
+                    other => Some(other.clone()),
+                };
+                document
+                    .as_ref()
+                    .and_then(|document| select_json_path(document, path))
+                    .is_some_and(|found| json_equals(found, value))
+            }
+            Self::Not(guard) => !guard.evaluate(output),
+        }
+    }
+}
+
+/// The text of an action result, strings as they are and other values as JSON
+fn output_text(output: &Value) -> String {
+    match output {
+        Value::String(text) => text.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+/// Values compare as JSON, except that `1` and `1.0` are equal
+fn json_equals(found: &Value, expected: &Value) -> bool {
+    match (found.as_f64(), expected.as_f64()) {
+        (Some(found), Some(expected)) => found == expected,
+        _ => found == expected,
+    }
+}
+
+/// The argument of `name(..)`, when the guard is a call to `name`
+fn call_argument<'a>(guard: &'a str, name: &str) -> Option<&'a str> {
+    guard
+        .strip_prefix(name)?
+        .trim_start()
+        .strip_prefix('(')?
+        .trim_end()
+        .strip_suffix(')')
+}
+
+/// A double quoted JSON string, or a single quoted string without escapes
+fn string_literal(literal: &str) -> Option<String> {
+    let literal = literal.trim();
+    if literal.starts_with('"') {
+        return serde_json::from_str(literal).ok();
+    }
+    literal
+        .strip_prefix('\'')?
+        .strip_suffix('\'')
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn check(expression: &str, output: Value) -> bool {
+        OutputGuard::parse(expression).unwrap().evaluate(&output)
+    }
+
+    #[test]
+    fn test_text_guards() {
+        assert!(check(r#"output.contains("DONE")"#, json!("All DONE.")));
+        assert!(!check(r#"output.contains("DONE")"#, json!("done")));
+        assert!(check("!output.contains('DONE')", json!("not yet")));
+        assert!(check(
+            r#"output.matches("^All \\d+ tests pass")"#,
+            json!("All 12 tests pass")
+        ));
+        assert!(!check(r#"output.matches("^All \\d+")"#, json!("Not all")));
+        // Non-text results are matched as JSON
+        assert!(check(r#"output.contains("true")"#, json!({"done": true})));
+        assert!(!check(r#"output.contains("x")"#, Value::Null));
+    }
+
+    #[test]
+    fn test_json_guards() {
+        let output = json!(
+            "Review finished.\n\n```json\n{\"verdict\": \"approved\", \"issues\": [{\"line\": 12}]}\n```\n"
+        );
+        assert!(check(
+            r#"output.json.verdict == "approved""#,
+            output.clone()
+        ));
+        assert!(check("output.json.verdict == 'approved'", output.clone()));
+        assert!(check("output.json.issues[0].line == 12.0", output.clone()));
+        assert!(check("output.json.issues[0].line != 13", output.clone()));
+        assert!(!check("output.json.missing == null", output.clone()));
+        assert!(!check(
+            r#"output.json.verdict == "approved""#,
+            json!("no json")
+        ));
+        assert!(check("output.json.done == true", json!({"done": true})));
+        assert!(check(
+            r#"output.json.note == "a!=b""#,
+            json!({"note": "a!=b"})
+        ));
+    }
+
+    #[test]
+    fn test_invalid_guards() {
+        assert!(OutputGuard::is_guard(" !output.contains('x')"));
+        assert!(!OutputGuard::is_guard("result.contains(\"x\")"));
+
+        for expression in [
+            "output.contains(DONE)",
+            "output.matches(\"(unclosed\")",
+            "output.json.status == done",
+            "output.json.status",
+            "output.json.a == 1 != 2",
+            "output.json.a = 1",
+            "output.length > 3",
+        ] {
+            assert!(OutputGuard::parse(expression).is_err(), "{expression}");
+        }
+    }
+}
@@ -0,0 +1,241 @@
+//! Lifecycle hooks for workflow runs
+//!
+//! Hooks are configured in a workflow's YAML front matter and run an ordinary
+//! action (usually `Shell` or `Execute prompt`) when a run starts, changes
+//! state, fails, or is cancelled:
+//!
+//! ```yaml
+//! hooks:
+//!   on_start: Log "Starting ${workflow_name}"
+//!   on_state_change: Log "${previous_state} -> ${current_state}"
+//!   on_failure: Shell "notify-slack '${workflow_name} failed: ${error}'"
+//!   on_cancel: Shell "git checkout main"
+//! ```
+//!
+//! Hook actions see a copy of the run context extended with details about the
+//! run, so they can report on it without changing the workflow body. A failing
+//! hook is logged and never changes the outcome of the run.
+
+use crate::workflow::{parse_action_from_description, ActionError, ActionResult, WorkflowRun};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Context key holding the name of the hook being run
+pub const HOOK_EVENT_KEY: &str = "hook_event";
+
+/// Lifecycle points at which a hook can run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkflowHookEvent {
+    /// A new run is about to execute its initial state
+    Start,
+    /// The run moved from one state to another
+    StateChange,
+    /// The run stopped with an error
+    Failure,
+    /// The run was cancelled before it finished
+    Cancel,
+}
+
+impl WorkflowHookEvent {
+    /// Every lifecycle event
+    pub const ALL: [WorkflowHookEvent; 4] = [
+        WorkflowHookEvent::Start,
+        WorkflowHookEvent::StateChange,
+        WorkflowHookEvent::Failure,
+        WorkflowHookEvent::Cancel,
+    ];
+
+    /// Front matter key that configures the hook for this event
+    pub fn key(&self) -> &'static str {
+        match self {
+            WorkflowHookEvent::Start => "on_start",
+            WorkflowHookEvent::StateChange => "on_state_change",
+            WorkflowHookEvent::Failure => "on_failure",
+            WorkflowHookEvent::Cancel => "on_cancel",
+        }
+    }
+}
+
+impl fmt::Display for WorkflowHookEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.key())
+    }
+}
+
+/// Hook actions configured for a workflow
+///
+/// Each hook is an action description in the same syntax as a state action.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WorkflowHooks {
+    /// Action run when a run starts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_start: Option<String>,
+    /// Action run after every state transition
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_state_change: Option<String>,
+    /// Action run when a run fails
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_failure: Option<String>,
+    /// Action run when a run is cancelled, to clean up what it left behind
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_cancel: Option<String>,
+}
+
+impl WorkflowHooks {
+    /// Action description configured for an event
+    pub fn get(&self, event: WorkflowHookEvent) -> Option<&str> {
+        match event {
+            WorkflowHookEvent::Start => self.on_start.as_deref(),
+            WorkflowHookEvent::StateChange => self.on_state_change.as_deref(),
+            WorkflowHookEvent::Failure => self.on_failure.as_deref(),
+            WorkflowHookEvent::Cancel => self.on_cancel.as_deref(),
+        }
+        .map(str::trim)
+        .filter(|hook| !hook.is_empty())
+    }
+
+    /// Whether no hooks are configured
+    pub fn is_empty(&self) -> bool {
+        WorkflowHookEvent::ALL
+            .into_iter()
+            .all(|event| self.get(event).is_none())
+    }
+
+    /// Check that every configured hook parses as an action
+    pub fn validate(&self) -> ActionResult<()> {
+        for event in WorkflowHookEvent::ALL {
+            if let Some(hook) = self.get(event) {
+                if parse_action_from_description(hook)?.is_none() {
+                    return Err(ActionError::ParseError(format!(
+                        "Hook '{event}' is not a valid action: {hook}"
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Details about the run passed to a hook
+#[derive(Debug, Clone, Default)]
+pub struct HookDetails {
+    /// State the run left, for state change hooks
+    pub previous_state: Option<String>,
+    /// Error that stopped the run, for failure hooks
+    pub error: Option<String>,
+}
+
+/// Build the context a hook action runs with
+///
+/// The run context is copied and extended with `hook_event`, `workflow_name`,
+/// `run_id`, `current_state`, `status`, and when available `previous_state` and
+/// `error`. The same values are added to `_template_vars` so liquid templates
+/// in the hook description can use them.
+pub fn hook_context(
+    run: &WorkflowRun,
+    event: WorkflowHookEvent,
+    details: &HookDetails,
+) -> HashMap<String, Value> {
+    let mut values = serde_json::Map::new();
+    values.insert(HOOK_EVENT_KEY.to_string(), Value::String(event.to_string()));
+    values.insert(
+        "workflow_name".to_string(),
+        Value::String(run.workflow.name.to_string()),
+    );
+    values.insert("run_id".to_string(), Value::String(run.id.to_string()));
+    values.insert(
+        "current_state".to_string(),
+        Value::String(run.current_state.to_string()),
+    );
+    values.insert(
+        "status".to_string(),
+        serde_json::to_value(run.status).unwrap_or(Value::Null),
+    );
+    if let Some(previous_state) = &details.previous_state {
+        values.insert(
+            "previous_state".to_string(),
+            Value::String(previous_state.clone()),
+        );
+    }
+    if let Some(error) = &details.error {
+        values.insert("error".to_string(), Value::String(error.clone()));
+    }
+
+    let mut context = run.context.clone();
+    let mut template_vars = context
+        .get("_template_vars")
+        .and_then(|vars| vars.as_object().cloned())
+        .unwrap_or_default();
+    for (key, value) in &values {
+        template_vars.insert(key.clone(), value.clone());
+    }
+    context.extend(values);
+    context.insert("_template_vars".to_string(), Value::Object(template_vars));
+    context
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workflow::test_helpers::*;
+
+    #[test]
+    fn test_hooks_deserialize_from_yaml() {
+        let hooks: WorkflowHooks = serde_yaml::from_str(
+            "on_start: Log \"starting\"\non_failure: Shell \"echo ${error}\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            hooks.get(WorkflowHookEvent::Start),
+            Some("Log \"starting\"")
+        );
+        assert_eq!(hooks.get(WorkflowHookEvent::StateChange), None);
+        assert_eq!(
+            hooks.get(WorkflowHookEvent::Failure),
+            Some("Shell \"echo ${error}\"")
+        );
+        assert!(!hooks.is_empty());
+        assert!(hooks.validate().is_ok());
+        assert!(WorkflowHooks::default().is_empty());
+    }
+
+    #[test]
+    fn test_hooks_reject_unknown_events_and_invalid_actions() {
+        assert!(serde_yaml::from_str::<WorkflowHooks>("on_finish: Log \"done\"").is_err());
+
+        let hooks = WorkflowHooks {
+            on_failure: Some("post to slack".to_string()),
+            ..Default::default()
+        };
+        assert!(hooks.validate().is_err());
+    }
+
+    #[test]
+    fn test_hook_context_includes_run_details() {
+        let workflow = create_basic_workflow();
+        let mut run = WorkflowRun::new(workflow);
+        run.context
+            .insert("branch".to_string(), Value::String("main".to_string()));
+
+        let context = hook_context(
+            &run,
+            WorkflowHookEvent::Failure,
+            &HookDetails {
+                previous_state: Some("start".to_string()),
+                error: Some("boom".to_string()),
+            },
+        );
+
+        assert_eq!(context[HOOK_EVENT_KEY], "on_failure");
+        assert_eq!(context["workflow_name"], run.workflow.name.to_string());
+        assert_eq!(context["previous_state"], "start");
+        assert_eq!(context["error"], "boom");
+        assert_eq!(context["branch"], "main");
+        assert_eq!(context["_template_vars"]["error"], "boom");
+        assert!(!run.context.contains_key("error"));
+    }
+}
@@ -0,0 +1,228 @@
+//! Invariants checked between the states of a workflow run
+//!
+//! A workflow that expects the repository to be in a certain shape can say so
+//! in its YAML front matter. The executor checks the invariants after every
+//! transition, before the state the run moved to executes, and fails the run
+//! naming the broken invariant as soon as one no longer holds, instead of
+//! letting later states fail in confusing ways:
+//!
+//! ```yaml
+//! invariants:
+//!   - not_empty: issue_name
+//!     states: [work, review]
+//!     message: No issue was selected
+//!   - file_exists: ./issues/${issue_name}.md
+//!     states: [work, review]
+//!   - branch_matches: "^issue/"
+//!     states: [review]
+//! ```
+//!
+//! Each invariant makes exactly one check:
+//!
+//! - `file_exists` holds when the path exists, after `${variable}` references
+//!   are substituted; relative paths are relative to the run's directory
+//! - `branch_matches` holds when the regular expression matches the current
+//!   git branch
+//! - `not_empty` holds when the variable is set to a non-empty value
+//!
+//! An invariant with `states` is only checked when the run enters one of them.
+//! Invariants are validated when the workflow is loaded, so an invalid regular
+//! expression or an unknown state is reported before the workflow runs.
+
+use crate::git::GitOperations;
+use crate::workflow::action_parser::ActionParser;
+use crate::workflow::{StateId, WORKING_DIR_KEY};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// A condition the run must meet when it enters a state
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WorkflowInvariant {
+    /// Path that must exist
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_exists: Option<String>,
+    /// Regular expression the current git branch must match
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch_matches: Option<String>,
+    /// Variable that must be set to a non-empty value
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub not_empty: Option<String>,
+    /// States entering which checks the invariant, every state when empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub states: Vec<String>,
+    /// Message reported when the invariant does not hold
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl WorkflowInvariant {
+    /// The invariant as written, such as `not_empty: issue_name`
+    pub fn describe(&self) -> String {
+        let checks = [
+            ("file_exists", &self.file_exists),
+            ("branch_matches", &self.branch_matches),
+            ("not_empty", &self.not_empty),
+        ];
+        checks
+            .iter()
+            .filter_map(|(key, value)| value.as_ref().map(|value| format!("{key}: {value}")))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Check that the invariant makes exactly one valid check
+    pub fn validate(&self) -> Result<(), String> {
+        let checks = [&self.file_exists, &self.branch_matches, &self.not_empty]
+            .into_iter()
+            .filter(|check| check.is_some())
+            .count();
+        if checks != 1 {
+            return Err(format!(
+                "Invariant '{}' must have exactly one of file_exists, branch_matches or not_empty",
+                self.describe()
+            ));
+        }
+        if let Some(pattern) = &self.branch_matches {
+            Regex::new(pattern).map_err(|e| format!("Invalid invariant branch pattern: {e}"))?;
+        }
+        Ok(())
+    }
+
+    /// Whether the invariant is checked when the run enters `state`
+    pub fn applies_to(&self, state: &StateId) -> bool {
+        self.states.is_empty() || self.states.iter().any(|name| name == state.as_str())
+    }
+
+    /// Check the invariant against the run context, returning why it does not
+    /// hold
+    pub fn check(&self, context: &HashMap<String, Value>) -> Result<(), String> {
+        if let Some(path) = &self.file_exists {
+            let path = ActionParser::new()
+                .and_then(|parser| parser.substitute_variables_safe(path, context))
+                .map_err(|e| e.to_string())?;
+            if path.contains("${") {
+                return Err(format!("'{path}' uses a variable that is not set"));
+            }
+            if !work_dir(context).join(&path).exists() {
+                return Err(format!("'{path}' does not exist"));
+            }
+        }
+
+        if let Some(pattern) = &self.branch_matches {
+            let regex = Regex::new(pattern).map_err(|e| e.to_string())?;
+            let branch = GitOperations::with_work_dir(work_dir(context))
+                .and_then(|git| git.current_branch())
+                .map_err(|e| format!("the current branch can't be read: {e}"))?;
+            if !regex.is_match(&branch) {
+                return Err(format!("branch '{branch}' does not match '{pattern}'"));
+            }
+        }
+
+        if let Some(variable) = &self.not_empty {
+            let empty = match context.get(variable) {
+                None | Some(Value::Null) => true,
+                Some(Value::String(value)) => value.trim().is_empty(),
+                Some(Value::Array(values)) => values.is_empty(),
+                Some(Value::Object(values)) => values.is_empty(),
+                Some(_) => false,
+            };
+            if empty {
+                return Err(format!("variable '{variable}' is not set"));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Directory the run works in, where relative paths are resolved
+fn work_dir(context: &HashMap<String, Value>) -> PathBuf {
+    context
+        .get(WORKING_DIR_KEY)
+        .and_then(Value::as_str)
+        .map(PathBuf::from)
+        .or_else(|| std::env::current_dir().ok())
+        .unwrap_or_default()
+}
+
+/// Check the invariants that apply to the state the run entered
+///
+/// Returns a message naming the first invariant that does not hold.
+pub fn check_invariants(
+    invariants: &[WorkflowInvariant],
+    state: &StateId,
+    context: &HashMap<String, Value>,
+) -> Result<(), String> {
+    for invariant in invariants.iter().filter(|i| i.applies_to(state)) {
+        if let Err(reason) = invariant.check(context) {
+            let reason = match &invariant.message {
+                Some(message) => format!("{message} ({reason})"),
+                None => reason,
+            };
+            return Err(format!(
+                "'{}' does not hold entering state '{state}': {reason}",
+                invariant.describe()
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn invariant(yaml: &str) -> WorkflowInvariant {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn test_validate_invariants() {
+        assert!(invariant("not_empty: issue_name").validate().is_ok());
+        assert!(invariant("branch_matches: '^issue/'").validate().is_ok());
+        assert!(WorkflowInvariant::default().validate().is_err());
+        let error = invariant("not_empty: a\nfile_exists: b")
+            .validate()
+            .unwrap_err();
+        assert!(error.contains("exactly one"));
+        assert!(invariant("branch_matches: '[unclosed'").validate().is_err());
+        assert!(serde_yaml::from_str::<WorkflowInvariant>("exists: a").is_err());
+    }
+
+    #[test]
+    fn test_check_invariants() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(temp.path().join("login.md"), "# Login").unwrap();
+        let mut context = HashMap::new();
+        context.insert(
+            WORKING_DIR_KEY.to_string(),
+            Value::String(temp.path().display().to_string()),
+        );
+        let invariants = vec![
+            invariant("not_empty: issue_name\nstates: [work]\nmessage: No issue was selected"),
+            invariant("file_exists: ./${issue_name}.md"),
+        ];
+        let work = StateId::new("work");
+
+        // Only the invariants of the state entered are checked
+        let error = check_invariants(&invariants, &StateId::new("start"), &context).unwrap_err();
+        assert!(error.contains("uses a variable that is not set"));
+        let error = check_invariants(&invariants, &work, &context).unwrap_err();
+        assert_eq!(
+            error,
+            "'not_empty: issue_name' does not hold entering state 'work': \
+             No issue was selected (variable 'issue_name' is not set)"
+        );
+
+        context.insert("issue_name".to_string(), Value::String("login".into()));
+        assert!(check_invariants(&invariants, &work, &context).is_ok());
+        context.insert("issue_name".to_string(), Value::String("logout".into()));
+        let error = check_invariants(&invariants, &work, &context).unwrap_err();
+        assert!(error.contains("'./logout.md' does not exist"));
+    }
+}
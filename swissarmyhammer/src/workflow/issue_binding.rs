@@ -0,0 +1,425 @@
+//! Workflow runs started by creating issues
+//!
+//! An issue whose front matter names a workflow gets a run of it without
+//! anyone starting one:
+//!
+//! ```markdown
+//! ---
+//! workflow: work_issue
+//! ---
+//! # Fix the login bug
+//! ```
+//!
+//! When issue storage publishes [`Event::IssueCreated`], the binding reads
+//! the issue and adds a run of its workflow to the [`IssueRunQueue`], a
+//! directory of pending runs kept under `.swissarmyhammer/issue-queue` so runs
+//! queued by a short lived command are not lost. The binding only queues:
+//! `issue queue --run` takes the runs off the queue with
+//! [`IssueWorkflowBinding::next_queued`] and runs them the way `flow run`
+//! does, with the issue's name in the `issue_name` variable.
+//!
+//! When a run ends, [`IssueWorkflowBinding::settle`] moves its issue on. When
+//! a run completes, the issue moves to
+//! `SWISSARMYHAMMER_ISSUE_WORKFLOW_COMPLETE_STATE`, `complete` by default.
+//! A failed or cancelled run moves it to
+//! `SWISSARMYHAMMER_ISSUE_WORKFLOW_FAILED_STATE` when that is set, and leaves
+//! it where it is otherwise. Moves follow the configured issue transitions.
+
+use crate::config::Config;
+use crate::events::{event_bus, Event, EventSubscriber};
+use crate::issues::{
+    issue_storage_for_work_dir, move_issue, IssueMetadata, IssueStateMachine, IssueStorage,
+    COMPLETE_STATE,
+};
+use crate::workflow::{WorkflowRunId, WorkflowRunStatus};
+use crate::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+
+/// Directory, relative to the working directory, queued issue runs are kept in
+pub const DEFAULT_ISSUE_QUEUE_DIR: &str = ".swissarmyhammer/issue-queue";
+
+/// A workflow run waiting to be started for an issue
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueuedIssueRun {
+    /// Issue the run is for
+    pub issue: String,
+    /// Workflow to run
+    pub workflow: String,
+    /// When the run was queued
+    pub queued_at: DateTime<Utc>,
+}
+
+/// Workflow runs waiting to be started for issues, one file per issue
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IssueRunQueue {
+    dir: PathBuf,
+}
+
+impl IssueRunQueue {
+    /// Create a queue kept in `dir`
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Directory the queue is kept in
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Queue file of an issue
+    fn entry_path(&self, issue: &str) -> PathBuf {
+        self.dir.join(format!("{issue}.json"))
+    }
+
+    /// Queue a run, returning `false` when the issue already has one waiting
+    pub fn push(&self, entry: &QueuedIssueRun) -> Result<bool> {
+        fs::create_dir_all(&self.dir)?;
+        let path = self.entry_path(&entry.issue);
+        if path.exists() {
+            return Ok(false);
+        }
+        // Written aside and renamed, so a worker never reads half an entry
+        let temp_path = path.with_extension("json.tmp");
+        fs::write(&temp_path, serde_json::to_string_pretty(entry)?)?;
+        fs::rename(&temp_path, &path)?;
+        Ok(true)
+    }
+
+    /// Every queued run, oldest first
+    ///
+    /// Files that can't be read are skipped.
+    pub fn list(&self) -> Result<Vec<QueuedIssueRun>> {
+        if !self.dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut entries = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            let queued = fs::read_to_string(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|json| serde_json::from_str(&json).map_err(|e| e.to_string()));
+            match queued {
+                Ok(queued) => entries.push(queued),
+                Err(e) => tracing::debug!("Skipping queued issue run {}: {}", path.display(), e),
+            }
+        }
+        entries.sort_by(|a: &QueuedIssueRun, b| a.queued_at.cmp(&b.queued_at));
+        Ok(entries)
+    }
+
+    /// Take an issue's run off the queue, returning `false` when it is no
+    /// longer queued, such as when another worker took it first
+    pub fn take(&self, issue: &str) -> Result<bool> {
+        match fs::remove_file(self.entry_path(issue)) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+impl Default for IssueRunQueue {
+    fn default() -> Self {
+        Self::new(DEFAULT_ISSUE_QUEUE_DIR)
+    }
+}
+
+/// What happened to one queued issue run
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct IssueWorkflowOutcome {
+    /// Issue the run was for
+    pub issue: String,
+    /// Workflow that was run
+    pub workflow: String,
+    /// The run, once it started
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub run_id: Option<WorkflowRunId>,
+    /// How the run ended, once it started
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<WorkflowRunStatus>,
+    /// State the issue was moved to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    /// Why the run could not start or the issue could not be moved
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// Queues a workflow run for each issue created with a `workflow` field,
+/// and moves the issue on when its run ends
+#[derive(Debug, Clone)]
+pub struct IssueWorkflowBinding {
+    queue: IssueRunQueue,
+    states: IssueStateMachine,
+    complete_state: String,
+    failed_state: Option<String>,
+}
+
+impl IssueWorkflowBinding {
+    /// Create a binding keeping runs in `queue`, completing issues whose run
+    /// completed and leaving the others
+    pub fn new(queue: IssueRunQueue) -> Self {
+        Self {
+            queue,
+            states: IssueStateMachine::default(),
+            complete_state: COMPLETE_STATE.to_string(),
+            failed_state: None,
+        }
+    }
+
+    /// Build the binding from configuration settings
+    pub fn from_config(config: &Config) -> Result<Self> {
+        Ok(Self::new(IssueRunQueue::default())
+            .with_states(IssueStateMachine::from_config(config)?)
+            .with_complete_state(&config.issue_workflow_complete_state)
+            .with_failed_state(config.issue_workflow_failed_state.clone()))
+    }
+
+    /// Move issues between the states of `states`
+    pub fn with_states(mut self, states: IssueStateMachine) -> Self {
+        self.states = states;
+        self
+    }
+
+    /// Move issues whose run completed to `state`
+    pub fn with_complete_state(mut self, state: impl Into<String>) -> Self {
+        self.complete_state = state.into();
+        self
+    }
+
+    /// Move issues whose run failed or was cancelled to `state`, or leave
+    /// them where they are when `None`
+    pub fn with_failed_state(mut self, state: Option<String>) -> Self {
+        self.failed_state = state;
+        self
+    }
+
+    /// The queue runs wait in
+    pub fn queue(&self) -> &IssueRunQueue {
+        &self.queue
+    }
+
+    /// Queue a run for issue `name` when its front matter names a workflow
+    ///
+    /// Returns the queued run, or `None` when the issue names no workflow or
+    /// already has a run waiting.
+    pub async fn enqueue(
+        &self,
+        issues: &dyn IssueStorage,
+        name: &str,
+    ) -> Result<Option<QueuedIssueRun>> {
+        let issue = issues.get_issue(name).await?;
+        let Some(workflow) = IssueMetadata::from_content(&issue.content).workflow else {
+            return Ok(None);
+        };
+        let entry = QueuedIssueRun {
+            issue: issue.name,
+            workflow,
+            queued_at: Utc::now(),
+        };
+        if !self.queue.push(&entry)? {
+            return Ok(None);
+        }
+        tracing::info!(
+            "Queued workflow '{}' for issue '{}'",
+            entry.workflow,
+            entry.issue
+        );
+        Ok(Some(entry))
+    }
+
+    /// Take the oldest queued run off the queue, or `None` when it is empty
+    pub fn next_queued(&self) -> Result<Option<QueuedIssueRun>> {
+        for entry in self.queue.list()? {
+            if self.queue.take(&entry.issue)? {
+                return Ok(Some(entry));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Move issue `name` on now that its run ended with `status`
+    ///
+    /// Returns the state the issue is in afterwards, or `None` when runs
+    /// ending that way leave the issue where it is.
+    pub async fn settle(
+        &self,
+        issues: &dyn IssueStorage,
+        name: &str,
+        status: WorkflowRunStatus,
+    ) -> Result<Option<String>> {
+        let target = match status {
+            WorkflowRunStatus::Completed => Some(self.complete_state.clone()),
+            WorkflowRunStatus::Failed | WorkflowRunStatus::Cancelled => self.failed_state.clone(),
+            WorkflowRunStatus::Running | WorkflowRunStatus::Paused => None,
+        };
+        let Some(target) = target else {
+            return Ok(None);
+        };
+        let issue = issues.get_issue(name).await?;
+        if self.states.state_of(&issue) != target {
+            move_issue(issues, &self.states, name, &target).await?;
+        }
+        Ok(Some(target))
+    }
+
+    /// Queue runs for the issues created from now on until the returned task
+    /// is finished
+    pub fn spawn(self) -> IssueWorkflowTask {
+        let events = event_bus().subscribe();
+        let stop = Arc::new(Notify::new());
+        let handle = tokio::spawn(self.run(events, Arc::clone(&stop)));
+        IssueWorkflowTask { handle, stop }
+    }
+
+    /// Spawn the binding built from the configuration, or `None` when the
+    /// configured issue states are invalid
+    pub fn spawn_configured() -> Option<IssueWorkflowTask> {
+        match Self::from_config(Config::global()) {
+            Ok(binding) => Some(binding.spawn()),
+            Err(e) => {
+                tracing::warn!("Not queueing workflows for new issues: {}", e);
+                None
+            }
+        }
+    }
+
+    async fn run(self, mut events: EventSubscriber, stop: Arc<Notify>) {
+        loop {
+            tokio::select! {
+                event = events.recv() => match event {
+                    Some(Event::IssueCreated { name }) => self.enqueue_logged(&name).await,
+                    Some(_) => {}
+                    None => return,
+                },
+                _ = stop.notified() => {
+                    while let Some(event) = events.try_recv() {
+                        if let Event::IssueCreated { name } = event {
+                            self.enqueue_logged(&name).await;
+                        }
+                    }
+                    return;
+                }
+            }
+        }
+    }
+
+    async fn enqueue_logged(&self, name: &str) {
+        let result = match current_issue_storage() {
+            Ok(issues) => self.enqueue(issues.as_ref(), name).await.map(|_| ()),
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            tracing::warn!("Failed to queue a workflow for issue '{}': {}", name, e);
+        }
+    }
+}
+
+/// Issue storage of the working directory
+fn current_issue_storage() -> Result<Box<dyn IssueStorage>> {
+    issue_storage_for_work_dir(&std::env::current_dir()?)
+}
+
+/// Issue workflow binding running in the background
+#[derive(Debug)]
+pub struct IssueWorkflowTask {
+    handle: JoinHandle<()>,
+    stop: Arc<Notify>,
+}
+
+impl IssueWorkflowTask {
+    /// Queue runs for the issues already created and stop, waiting at most
+    /// `wait` for the queue to be written
+    pub async fn finish(self, wait: Duration) {
+        self.stop.notify_one();
+        let abort = self.handle.abort_handle();
+        if tokio::time::timeout(wait, self.handle).await.is_err() {
+            tracing::warn!("Gave up on queueing issue workflows after {:?}", wait);
+            abort.abort();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::issues::FileSystemIssueStorage;
+    use tempfile::TempDir;
+
+    fn binding(temp_dir: &TempDir) -> IssueWorkflowBinding {
+        IssueWorkflowBinding::new(IssueRunQueue::new(temp_dir.path().join("queue")))
+    }
+
+    #[test]
+    fn test_queue_keeps_one_run_per_issue() {
+        let temp_dir = TempDir::new().unwrap();
+        let queue = IssueRunQueue::new(temp_dir.path());
+        let entry = |issue: &str, seconds| QueuedIssueRun {
+            issue: issue.to_string(),
+            workflow: "work_issue".to_string(),
+            queued_at: DateTime::from_timestamp(seconds, 0).unwrap(),
+        };
+
+        assert!(queue.push(&entry("second", 20)).unwrap());
+        assert!(queue.push(&entry("first", 10)).unwrap());
+        assert!(!queue.push(&entry("first", 30)).unwrap());
+        let issues: Vec<String> = queue.list().unwrap().into_iter().map(|e| e.issue).collect();
+        assert_eq!(issues, vec!["first", "second"]);
+
+        assert!(queue.take("first").unwrap());
+        assert!(!queue.take("first").unwrap());
+        assert_eq!(queue.list().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_created_issue_is_queued_and_settled() {
+        let temp_dir = TempDir::new().unwrap();
+        let issues = FileSystemIssueStorage::new(temp_dir.path().join("issues")).unwrap();
+        let binding = binding(&temp_dir)
+            .with_states(IssueStateMachine::new(&["blocked".to_string()], &[]).unwrap())
+            .with_failed_state(Some("blocked".to_string()));
+        issues
+            .create_issue(
+                "bound".to_string(),
+                "---\nworkflow: issue_test\n---\n# Bound".to_string(),
+            )
+            .await
+            .unwrap();
+        issues
+            .create_issue("plain".to_string(), "# Plain".to_string())
+            .await
+            .unwrap();
+
+        let queued = binding.enqueue(&issues, "bound").await.unwrap().unwrap();
+        assert_eq!(queued.workflow, "issue_test");
+        assert!(binding.enqueue(&issues, "bound").await.unwrap().is_none());
+        assert!(binding.enqueue(&issues, "plain").await.unwrap().is_none());
+
+        assert_eq!(binding.next_queued().unwrap(), Some(queued));
+        assert_eq!(binding.next_queued().unwrap(), None);
+
+        let state = binding
+            .settle(&issues, "bound", WorkflowRunStatus::Failed)
+            .await
+            .unwrap();
+        assert_eq!(state.as_deref(), Some("blocked"));
+        let issue = issues.get_issue("bound").await.unwrap();
+        assert_eq!(binding.states.state_of(&issue), "blocked");
+        let state = binding
+            .settle(&issues, "bound", WorkflowRunStatus::Completed)
+            .await
+            .unwrap();
+        assert_eq!(state.as_deref(), Some(COMPLETE_STATE));
+        assert!(issues.get_issue("bound").await.unwrap().completed);
+    }
+}
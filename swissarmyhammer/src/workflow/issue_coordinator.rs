@@ -0,0 +1,398 @@
+//! Working several issues at once
+//!
+//! Issues worked one after another leave a large backlog running for days.
+//! The coordinator checks each issue branch out in a git worktree of its own
+//! under `.swissarmyhammer/worktrees`, runs a workflow for up to N issues at a
+//! time with every action running in the issue's worktree, and merges the
+//! finished branches into the main branch one at a time as their runs complete.
+//!
+//! A branch that does not merge cleanly is left for a person: the merge is
+//! aborted, the issue is reported as conflicted and its worktree is kept, as
+//! are the worktrees of runs that did not complete.
+
+use crate::git::GitOperations;
+use crate::issues::Issue;
+use crate::workflow::{
+    Workflow, WorkflowExecutor, WorkflowName, WorkflowRun, WorkflowRunId, WorkflowRunStatus,
+    WorkflowStorage, WORKING_DIR_KEY,
+};
+use crate::{Result, SwissArmyHammerError};
+use futures_util::stream::{self, StreamExt};
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Workflow run for each issue when none is named
+pub const DEFAULT_ISSUE_WORKFLOW: &str = "work_issue";
+
+/// Directory, relative to the repository, issue worktrees are created in
+pub const DEFAULT_WORKTREE_DIR: &str = ".swissarmyhammer/worktrees";
+
+/// Where an issue is in parallel processing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueRunStatus {
+    /// Waiting for a free slot
+    Queued,
+    /// The workflow is running in the issue's worktree
+    Running,
+    /// The workflow completed and the branch waits to be merged
+    Completed,
+    /// The workflow failed, was cancelled, or the worktree could not be created
+    Failed,
+    /// The branch was merged into the main branch
+    Merged,
+    /// The branch did not merge cleanly and needs resolving by hand
+    Conflicted,
+}
+
+impl fmt::Display for IssueRunStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = match self {
+            IssueRunStatus::Queued => "queued",
+            IssueRunStatus::Running => "running",
+            IssueRunStatus::Completed => "completed",
+            IssueRunStatus::Failed => "failed",
+            IssueRunStatus::Merged => "merged",
+            IssueRunStatus::Conflicted => "conflicted",
+        };
+        write!(f, "{status}")
+    }
+}
+
+/// Outcome of working one issue
+#[derive(Debug, Clone, Serialize)]
+pub struct IssueRun {
+    /// Issue name
+    pub issue: String,
+    /// Issue branch
+    pub branch: String,
+    /// Worktree the issue was worked in
+    pub worktree: PathBuf,
+    /// Workflow run, once the workflow has started
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub run_id: Option<WorkflowRunId>,
+    /// Where the issue is
+    pub status: IssueRunStatus,
+    /// Why the issue failed or conflicted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// Listener notified whenever an issue's status changes
+pub trait IssueRunListener: Send + Sync {
+    /// Called with the issue as it is after the change
+    fn on_update(&self, run: &IssueRun);
+}
+
+/// Works several issues concurrently, each in its own worktree
+pub struct IssueCoordinator {
+    git: GitOperations,
+    workflow: WorkflowName,
+    concurrency: usize,
+    worktree_dir: PathBuf,
+    listener: Option<Arc<dyn IssueRunListener>>,
+}
+
+impl IssueCoordinator {
+    /// Create a coordinator running up to `concurrency` issues at a time
+    pub fn new(git: GitOperations, concurrency: usize) -> Self {
+        Self {
+            git,
+            workflow: WorkflowName::new(DEFAULT_ISSUE_WORKFLOW),
+            concurrency,
+            worktree_dir: PathBuf::from(DEFAULT_WORKTREE_DIR),
+            listener: None,
+        }
+    }
+
+    /// Run another workflow for each issue
+    ///
+    /// The workflow should leave its work committed on the issue branch and
+    /// must not merge it, since the coordinator does the merging.
+    pub fn with_workflow(mut self, workflow: impl Into<WorkflowName>) -> Self {
+        self.workflow = workflow.into();
+        self
+    }
+
+    /// Create worktrees in another directory, relative to the repository
+    pub fn with_worktree_dir(mut self, worktree_dir: impl Into<PathBuf>) -> Self {
+        self.worktree_dir = worktree_dir.into();
+        self
+    }
+
+    /// Report every status change to a listener
+    pub fn with_listener(mut self, listener: Arc<dyn IssueRunListener>) -> Self {
+        self.listener = Some(listener);
+        self
+    }
+
+    fn notify(&self, run: &IssueRun) {
+        if let Some(listener) = &self.listener {
+            listener.on_update(run);
+        }
+    }
+
+    /// Work the issues, storing every run, and report each issue in the given order
+    ///
+    /// Worktrees start from the last commit of the main branch, so the
+    /// repositories involved must not have uncommitted changes.
+    pub async fn run(
+        &self,
+        storage: &mut WorkflowStorage,
+        issues: &[Issue],
+    ) -> Result<Vec<IssueRun>> {
+        if self.concurrency == 0 {
+            return Err(SwissArmyHammerError::Other(
+                "Parallel issue processing needs at least one job".to_string(),
+            ));
+        }
+        let workflow = storage.get_workflow(&self.workflow)?;
+
+        let mut repos = Vec::with_capacity(issues.len());
+        for issue in issues {
+            let git = self.git.for_issue(issue)?;
+            if git.has_uncommitted_changes()? {
+                return Err(SwissArmyHammerError::Other(format!(
+                    "{} has uncommitted changes; commit or stash them first, issue worktrees start from the last commit",
+                    git.work_dir().display()
+                )));
+            }
+            repos.push(git);
+        }
+
+        let mut reports = Vec::with_capacity(issues.len());
+        let mut queued = Vec::new();
+        for (index, (issue, git)) in issues.iter().zip(&repos).enumerate() {
+            let worktree = git.work_dir().join(&self.worktree_dir).join(&issue.name);
+            let mut report = IssueRun {
+                issue: issue.name.clone(),
+                branch: git.issue_branch(&issue.name),
+                worktree: worktree.clone(),
+                run_id: None,
+                status: IssueRunStatus::Queued,
+                message: None,
+            };
+            match self.add_worktree(git, &issue.name, &worktree) {
+                Ok(branch) => {
+                    report.branch = branch;
+                    queued.push((index, report.clone()));
+                }
+                Err(e) => {
+                    report.status = IssueRunStatus::Failed;
+                    report.message = Some(e.to_string());
+                }
+            }
+            self.notify(&report);
+            reports.push(report);
+        }
+
+        let mut runs = stream::iter(queued.into_iter().map(|(index, mut running)| {
+            let workflow = workflow.clone();
+            let issue = &issues[index];
+            async move {
+                running.status = IssueRunStatus::Running;
+                self.notify(&running);
+                let run = Self::work_issue(workflow, issue, &running.worktree).await;
+                (index, run)
+            }
+        }))
+        .buffer_unordered(self.concurrency);
+
+        // Branches merge one at a time, in the order their runs finish
+        while let Some((index, run)) = runs.next().await {
+            let report = &mut reports[index];
+            let run = match run {
+                Ok(run) => run,
+                Err(e) => {
+                    report.status = IssueRunStatus::Failed;
+                    report.message = Some(e.to_string());
+                    self.notify(report);
+                    continue;
+                }
+            };
+            report.run_id = Some(run.id);
+            storage.store_run(&run)?;
+
+            if run.status != WorkflowRunStatus::Completed {
+                report.status = IssueRunStatus::Failed;
+                report.message = Some(format!("Workflow run did not complete ({:?})", run.status));
+                self.notify(report);
+                continue;
+            }
+            report.status = IssueRunStatus::Completed;
+            self.notify(report);
+            self.merge(&repos[index], &issues[index].name, report);
+            self.notify(report);
+        }
+
+        Ok(reports)
+    }
+
+    /// Add the issue's worktree, keeping it out of the repository's status
+    fn add_worktree(
+        &self,
+        git: &GitOperations,
+        issue_name: &str,
+        worktree: &Path,
+    ) -> Result<String> {
+        let worktree_dir = git.work_dir().join(&self.worktree_dir);
+        std::fs::create_dir_all(&worktree_dir)?;
+        let gitignore = worktree_dir.join(".gitignore");
+        if !gitignore.exists() {
+            std::fs::write(gitignore, "*\n")?;
+        }
+        git.add_issue_worktree(issue_name, worktree)
+    }
+
+    /// Run the workflow for one issue with its actions running in the worktree
+    async fn work_issue(workflow: Workflow, issue: &Issue, worktree: &Path) -> Result<WorkflowRun> {
+        let mut executor = WorkflowExecutor::new();
+        let mut run = executor.start_workflow(workflow).map_err(|e| {
+            SwissArmyHammerError::Other(format!(
+                "Failed to start workflow for issue '{}': {e}",
+                issue.name
+            ))
+        })?;
+        run.context.insert(
+            WORKING_DIR_KEY.to_string(),
+            Value::String(worktree.display().to_string()),
+        );
+        run.context
+            .insert("issue_name".to_string(), Value::String(issue.name.clone()));
+
+        tracing::info!("Working issue '{}' in {}", issue.name, worktree.display());
+        if let Err(e) = executor.execute_state(&mut run).await {
+            tracing::error!("Workflow for issue '{}' failed: {e}", issue.name);
+            run.fail();
+        }
+        Ok(run)
+    }
+
+    /// Merge a completed issue branch, aborting the merge on conflicts
+    fn merge(&self, git: &GitOperations, issue_name: &str, report: &mut IssueRun) {
+        if let Err(e) = git.merge_issue_branch(issue_name) {
+            if let Err(abort) = git.abort_merge() {
+                tracing::debug!("Nothing to abort after failed merge: {abort}");
+            }
+            report.status = IssueRunStatus::Conflicted;
+            report.message = Some(e.to_string());
+            return;
+        }
+        report.status = IssueRunStatus::Merged;
+
+        if let Err(e) = git.remove_worktree(&report.worktree) {
+            tracing::warn!(
+                "Failed to remove worktree {}: {e}",
+                report.worktree.display()
+            );
+        } else if let Err(e) = git.delete_branch(&report.branch) {
+            tracing::warn!("Failed to delete branch '{}': {e}", report.branch);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workflow::MermaidParser;
+    use chrono::Utc;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let output = Command::new("git")
+            .current_dir(dir)
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {args:?} failed");
+    }
+
+    fn issue(name: &str) -> Issue {
+        Issue {
+            name: name.to_string(),
+            content: format!("# {name}"),
+            completed: false,
+            file_path: PathBuf::from(format!("issues/{name}.md")),
+            created_at: Utc::now(),
+            source: None,
+            priority: None,
+            estimate: None,
+            related_memos: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_issues_worked_in_worktrees_and_merged_in_turn() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = temp_dir.path();
+        git(repo, &["init"]);
+        git(repo, &["config", "user.name", "Test User"]);
+        git(repo, &["config", "user.email", "test@example.com"]);
+        std::fs::write(repo.join("README.md"), "# Test Repository").unwrap();
+        git(repo, &["add", "README.md"]);
+        git(repo, &["commit", "-m", "Initial commit"]);
+
+        // Every issue writes its own file and the same shared file
+        let workflow = MermaidParser::parse(
+            r#"```mermaid
+stateDiagram-v2
+    [*] --> own
+    own --> shared
+    shared --> add
+    add --> commit
+    commit --> [*]
+```
+
+## Actions
+
+- own: Shell "touch ${issue_name}.txt"
+- shared: Shell "echo ${issue_name} > shared.txt"
+- add: Shell "git add ."
+- commit: Shell "git commit -m work"
+"#,
+            "parallel_test",
+        )
+        .unwrap();
+        let mut storage = WorkflowStorage::memory();
+        storage.store_workflow(workflow).unwrap();
+
+        let git_ops = GitOperations::with_work_dir(repo.to_path_buf()).unwrap();
+        let coordinator = IssueCoordinator::new(git_ops.clone(), 2).with_workflow("parallel_test");
+        let reports = coordinator
+            .run(&mut storage, &[issue("first"), issue("second")])
+            .await
+            .unwrap();
+
+        let names: Vec<&str> = reports.iter().map(|r| r.issue.as_str()).collect();
+        assert_eq!(names, vec!["first", "second"]);
+        assert!(reports.iter().all(|r| r.run_id.is_some()));
+
+        // Whichever branch merged first, the other conflicts on the shared file
+        let merged: Vec<&IssueRun> = reports
+            .iter()
+            .filter(|r| r.status == IssueRunStatus::Merged)
+            .collect();
+        let conflicted: Vec<&IssueRun> = reports
+            .iter()
+            .filter(|r| r.status == IssueRunStatus::Conflicted)
+            .collect();
+        assert_eq!((merged.len(), conflicted.len()), (1, 1));
+        assert!(repo.join(format!("{}.txt", merged[0].issue)).exists());
+        assert!(!merged[0].worktree.exists());
+        assert!(conflicted[0].worktree.exists());
+        assert!(!git_ops.has_uncommitted_changes().unwrap());
+        assert_eq!(
+            git_ops.current_branch().unwrap(),
+            git_ops.main_branch().unwrap()
+        );
+
+        assert!(IssueCoordinator::new(git_ops, 0)
+            .run(&mut storage, &[])
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,233 @@
+//! Named locks that serialize states sharing a resource
+//!
+//! A state that touches a shared resource, such as the git working tree,
+//! declares a lock on it in the workflow's front matter:
+//!
+//! ```yaml
+//! states:
+//!   checkout:
+//!     lock: git
+//!   merge:
+//!     lock: [git, issues]
+//! ```
+//!
+//! The executor holds every lock a state declares while the state's action
+//! runs. Locks are lock files in `.swissarmyhammer/locks`, so they serialize
+//! states across runs in one process and across separate `flow run`
+//! processes. A run waiting for a lock polls until the holder releases it,
+//! the holder's process exits, or [`DEFAULT_LOCK_TIMEOUT`] passes.
+
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Directory lock files are created in, relative to the project root
+pub const DEFAULT_LOCKS_DIR: &str = ".swissarmyhammer/locks";
+
+/// State metadata key holding the comma separated locks of a state
+pub const LOCK_METADATA_KEY: &str = "lock";
+
+/// How long a state waits for its locks before failing
+pub const DEFAULT_LOCK_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+
+/// How often a waiting state checks whether a lock was released
+const LOCK_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Check that a lock name can be used as a lock file name
+pub fn validate_lock_name(name: &str) -> Result<(), String> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid lock name '{name}': use letters, digits, '-' and '_'"
+        ))
+    }
+}
+
+/// Acquires the named locks of states from a lock directory
+#[derive(Debug, Clone)]
+pub struct StateLocks {
+    dir: PathBuf,
+    timeout: Duration,
+}
+
+impl StateLocks {
+    /// Create locks kept in `dir`
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            timeout: DEFAULT_LOCK_TIMEOUT,
+        }
+    }
+
+    /// Set how long to wait for a lock before failing
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Lock file of a named lock
+    pub fn lock_path(&self, name: &str) -> PathBuf {
+        self.dir.join(format!("{name}.lock"))
+    }
+
+    /// Acquire every named lock, waiting while other runs hold them
+    ///
+    /// Locks are taken in name order so two states locking the same set of
+    /// resources cannot deadlock. `holder` is written to the lock files to
+    /// tell waiting runs who holds them.
+    pub async fn acquire(&self, names: &[&str], holder: &str) -> io::Result<StateLockGuard> {
+        let mut names = names.to_vec();
+        names.sort_unstable();
+        names.dedup();
+
+        let mut guard = StateLockGuard { paths: Vec::new() };
+        if names.is_empty() {
+            return Ok(guard);
+        }
+        fs::create_dir_all(&self.dir)?;
+
+        for name in names {
+            validate_lock_name(name)
+                .map_err(|message| io::Error::new(io::ErrorKind::InvalidInput, message))?;
+            let path = self.lock_path(name);
+            self.acquire_one(name, &path, holder).await?;
+            guard.paths.push(path);
+        }
+        Ok(guard)
+    }
+
+    async fn acquire_one(&self, name: &str, path: &Path, holder: &str) -> io::Result<()> {
+        let started = Instant::now();
+        let mut waiting = false;
+        loop {
+            match OpenOptions::new().write(true).create_new(true).open(path) {
+                Ok(mut file) => {
+                    writeln!(file, "{}", std::process::id())?;
+                    writeln!(file, "{holder}")?;
+                    return Ok(());
+                }
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {}
+                Err(e) => return Err(e),
+            }
+
+            let current = fs::read_to_string(path).unwrap_or_default();
+            if holder_has_exited(&current) {
+                tracing::warn!(
+                    "Removing lock '{name}' left by exited process: {}",
+                    path.display()
+                );
+                let _ = fs::remove_file(path);
+                continue;
+            }
+
+            if started.elapsed() >= self.timeout {
+                return Err(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    format!(
+                        "Timed out after {}s waiting for lock '{name}' held by {}. Remove {} if that run is no longer active",
+                        self.timeout.as_secs(),
+                        current.lines().nth(1).unwrap_or("another run"),
+                        path.display()
+                    ),
+                ));
+            }
+            if !waiting {
+                tracing::info!(
+                    "Waiting for lock '{name}' held by {}",
+                    current.lines().nth(1).unwrap_or("another run")
+                );
+                waiting = true;
+            }
+            tokio::time::sleep(LOCK_POLL_INTERVAL).await;
+        }
+    }
+}
+
+impl Default for StateLocks {
+    fn default() -> Self {
+        Self::new(DEFAULT_LOCKS_DIR)
+    }
+}
+
+/// Locks held by a running state, released when dropped
+#[derive(Debug)]
+pub struct StateLockGuard {
+    paths: Vec<PathBuf>,
+}
+
+impl StateLockGuard {
+    /// Lock files held by this guard
+    pub fn paths(&self) -> &[PathBuf] {
+        &self.paths
+    }
+}
+
+impl Drop for StateLockGuard {
+    fn drop(&mut self) {
+        for path in self.paths.iter().rev() {
+            if let Err(e) = fs::remove_file(path) {
+                tracing::warn!("Failed to release lock {}: {}", path.display(), e);
+            }
+        }
+    }
+}
+
+/// Whether the process that wrote a lock file is known to have exited
+///
+/// Liveness can only be checked where `/proc` is available. Elsewhere, and
+/// for lock files still being written, the holder is assumed to be running.
+fn holder_has_exited(contents: &str) -> bool {
+    let Some(pid) = contents
+        .lines()
+        .next()
+        .and_then(|line| line.trim().parse::<u32>().ok())
+    else {
+        return false;
+    };
+    let proc = Path::new("/proc");
+    proc.is_dir() && !proc.join(pid.to_string()).exists()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_acquire_waits_for_release() {
+        let temp = TempDir::new().unwrap();
+        let locks = StateLocks::new(temp.path()).with_timeout(Duration::from_millis(300));
+
+        let guard = locks.acquire(&["git", "git"], "first").await.unwrap();
+        assert_eq!(guard.paths(), [locks.lock_path("git")]);
+        let contents = fs::read_to_string(locks.lock_path("git")).unwrap();
+        assert_eq!(contents, format!("{}\nfirst\n", std::process::id()));
+
+        let error = locks.acquire(&["git"], "second").await.unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+        assert!(error.to_string().contains("held by first"));
+
+        drop(guard);
+        assert!(!locks.lock_path("git").exists());
+        let guard = locks.acquire(&["issues", "git"], "second").await.unwrap();
+        assert_eq!(
+            guard.paths(),
+            [locks.lock_path("git"), locks.lock_path("issues")]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_invalid_lock_name() {
+        let temp = TempDir::new().unwrap();
+        let locks = StateLocks::new(temp.path());
+        let error = locks.acquire(&["../git"], "run").await.unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        assert!(validate_lock_name("git_tree-1").is_ok());
+    }
+}
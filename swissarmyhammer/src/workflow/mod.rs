@@ -7,18 +7,37 @@ mod action_parser;
 mod actions;
 #[cfg(test)]
 mod actions_tests;
+mod artifacts;
 mod cache;
+mod cancellation;
+mod comparison;
+mod context_window;
 mod definition;
 mod error_utils;
+mod estimate;
 #[cfg(test)]
 mod examples_tests;
 mod executor;
+mod extractors;
+mod failure;
 mod graph;
 #[cfg(test)]
 mod graph_tests;
+mod guards;
+mod hooks;
+mod invariants;
+mod issue_binding;
+mod issue_coordinator;
+mod locks;
 mod metrics;
 mod parser;
+mod pinning;
+mod progress;
+mod providers;
 mod run;
+mod sampling;
+mod scaffold;
+mod snapshot;
 mod state;
 mod storage;
 #[cfg(test)]
@@ -33,28 +52,76 @@ mod visualization_tests;
 
 pub use actions::{
     parse_action_from_description, parse_action_from_description_with_context, Action, ActionError,
-    ActionResult, LogAction, LogLevel, PromptAction, SetVariableAction, ShellAction,
-    SubWorkflowAction, WaitAction,
+    ActionResult, ClaudeSettings, CreateIssueAction, CreateMemoAction, LogAction, LogLevel,
+    PromptAction, SaveArtifactAction, SetVariableAction, ShellAction, SubWorkflowAction,
+    UpdateIssueAction, WaitAction, RUN_ID_KEY, STATE_ID_KEY, WORKFLOW_NAME_KEY, WORKING_DIR_KEY,
 };
+pub use artifacts::{Artifact, ArtifactKind, RunArtifacts, DEFAULT_RUNS_DIR};
 pub use cache::{
     CacheStats, CelProgramCache, TransitionCache, TransitionPath, WorkflowCache,
     WorkflowCacheManager,
 };
-pub use definition::{Workflow, WorkflowError, WorkflowName, WorkflowResult};
+pub use cancellation::{RunCancellation, DEFAULT_CANCEL_DIR};
+pub use comparison::{PromptRecord, RunComparison, RunSummary, StateVisits, PROMPT_HISTORY_KEY};
+pub use context_window::{
+    CompactionStrategy, ContextWindowSettings, CONTEXT_TOKENS_KEY, CONTEXT_WINDOW_KEY,
+    DEFAULT_COMPACT_AT_PERCENT, DEFAULT_CONTEXT_WINDOW_TOKENS, DEFAULT_SUMMARY_PROMPT,
+};
+pub use definition::{Workflow, WorkflowError, WorkflowName, WorkflowResult, WorkflowVariable};
 pub use error_utils::{
     command_succeeded, extract_stderr, extract_stdout, handle_claude_command_error,
     handle_command_error, handle_command_error_with_mapper,
 };
+pub use estimate::{
+    CostEstimate, CostRange, PathEstimate, StateEstimate, WorkflowEstimate, CHARS_PER_TOKEN,
+};
 pub use executor::{
     ExecutionEvent, ExecutionEventType, ExecutorError, ExecutorResult, WorkflowExecutor,
 };
+pub use extractors::{ExtractedType, ExtractorKind, OutputExtractor};
+pub use failure::{
+    record_attempt, record_failure, recorded_failure, FailureKind, RunRetryPolicy,
+    ATTEMPT_METADATA_KEY, FAILURE_KIND_METADATA_KEY, FAILURE_MESSAGE_METADATA_KEY,
+    RETRY_OF_METADATA_KEY,
+};
 pub use graph::{GraphError, GraphResult, WorkflowGraphAnalyzer};
+pub use guards::OutputGuard;
+pub use hooks::{hook_context, HookDetails, WorkflowHookEvent, WorkflowHooks, HOOK_EVENT_KEY};
+pub use invariants::{check_invariants, WorkflowInvariant};
+pub use issue_binding::{
+    IssueRunQueue, IssueWorkflowBinding, IssueWorkflowOutcome, IssueWorkflowTask, QueuedIssueRun,
+    DEFAULT_ISSUE_QUEUE_DIR,
+};
+pub use issue_coordinator::{
+    IssueCoordinator, IssueRun, IssueRunListener, IssueRunStatus, DEFAULT_ISSUE_WORKFLOW,
+    DEFAULT_WORKTREE_DIR,
+};
+pub use locks::{
+    validate_lock_name, StateLockGuard, StateLocks, DEFAULT_LOCKS_DIR, DEFAULT_LOCK_TIMEOUT,
+    LOCK_METADATA_KEY,
+};
 pub use metrics::{
     GlobalMetrics, MemoryMetrics, ResourceTrends, RunMetrics, StateExecutionCount, WorkflowMetrics,
     WorkflowSummaryMetrics,
 };
 pub use parser::{MermaidParser, ParseError, ParseResult};
+pub use pinning::{pin_dir_for, pinned_prompts_dir, RunPins, PINNED_PROMPTS_ENV, PIN_DIR_KEY};
+pub use progress::{
+    RunProgress, RunProgressBoard, RunProgressReporter, RunUsage, WorkflowProgress,
+    WorkflowProgressEvent, DEFAULT_PROGRESS_DIR, OUTPUT_SUMMARY_CHARS,
+};
+pub use providers::{
+    AnthropicProvider, ClaudeCliProvider, ModelProvider, ModelRequest, ModelResponse,
+    OpenAiProvider, PromptBackend, SamplingProvider, ANTHROPIC_API_KEY_ENV, OPENAI_API_KEY_ENV,
+    PROMPT_BACKEND_KEY,
+};
 pub use run::{WorkflowRun, WorkflowRunId, WorkflowRunStatus};
+pub use sampling::{
+    prompt_sampler, set_prompt_sampler, PromptSampler, SamplingRequest, SamplingResponse,
+    DEFAULT_SAMPLING_MAX_TOKENS,
+};
+pub use scaffold::{WorkflowTemplate, WorkflowTemplates, DEFAULT_WORKFLOW_TEMPLATE};
+pub use snapshot::{SnapshotMismatch, WorkflowSnapshot, DEFAULT_SNAPSHOT_DIR};
 pub use state::{
     CompensationKey, ErrorContext, State, StateError, StateId, StateResult, StateType,
 };
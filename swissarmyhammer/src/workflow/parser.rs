@@ -4,13 +4,15 @@
 //! and convert them to our internal Workflow types.
 
 use crate::workflow::{
-    ConditionType, State, StateId, StateType, Transition, TransitionCondition, Workflow,
-    WorkflowName,
+    validate_lock_name, ConditionType, ContextWindowSettings, PromptBackend, State, StateId,
+    StateType, Transition, TransitionCondition, Workflow, WorkflowHooks, WorkflowInvariant,
+    WorkflowName, WorkflowVariable, LOCK_METADATA_KEY,
 };
 use mermaid_parser::{
     common::ast::{DiagramType, StateDiagram, StateTransition},
     parse_diagram,
 };
+use serde::Deserialize;
 use std::collections::HashMap;
 use thiserror::Error;
 
@@ -47,6 +49,56 @@ pub enum ParseError {
 /// Result type for parsing operations
 pub type ParseResult<T> = Result<T, ParseError>;
 
+/// Self-describing metadata from the front matter
+#[derive(Debug, Default, Deserialize)]
+struct FrontMatterMetadata {
+    /// Human-readable title
+    #[serde(default)]
+    title: Option<String>,
+    /// Tags for organization
+    #[serde(default)]
+    tags: Vec<String>,
+    /// Template variables the workflow uses
+    #[serde(default)]
+    variables: Vec<WorkflowVariable>,
+    /// Limits on the run's Claude conversation
+    #[serde(default)]
+    context_window: ContextWindowSettings,
+    /// Backend the workflow's prompts are sent to
+    #[serde(default)]
+    backend: Option<PromptBackend>,
+    /// Conditions checked every time the run enters a state
+    #[serde(default)]
+    invariants: Vec<WorkflowInvariant>,
+}
+
+/// Settings of one state from the `states` key of the front matter
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StateSettings {
+    /// Locks held while the state runs
+    #[serde(default)]
+    lock: Option<LockNames>,
+}
+
+/// One lock name, a comma separated list of names, or a YAML list of names
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum LockNames {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl LockNames {
+    fn names(&self) -> Vec<&str> {
+        let names: Vec<&str> = match self {
+            LockNames::One(names) => names.split(',').collect(),
+            LockNames::Many(names) => names.iter().map(String::as_str).collect(),
+        };
+        names.into_iter().map(str::trim).collect()
+    }
+}
+
 /// Mermaid parser for converting state diagrams to workflows
 pub struct MermaidParser;
 
@@ -91,6 +143,15 @@ impl MermaidParser {
         // Extract actions from the markdown content
         let actions = Self::extract_actions_from_markdown(input);
 
+        // Extract lifecycle hooks from the front matter
+        let hooks = Self::extract_hooks_from_front_matter(input)?;
+
+        // Extract per state settings from the front matter
+        let state_settings = Self::extract_state_settings_from_front_matter(input)?;
+
+        // Extract title, tags and variables from the front matter
+        let metadata = Self::extract_metadata_from_front_matter(input)?;
+
         // Attempt to parse the diagram
         match parse_diagram(&mermaid_content) {
             Ok(diagram) => match diagram {
@@ -98,7 +159,20 @@ impl MermaidParser {
                     state_diagram,
                     workflow_name.into(),
                     actions,
-                ),
+                )
+                .and_then(|workflow| {
+                    let workflow = Workflow {
+                        hooks,
+                        title: metadata.title,
+                        tags: metadata.tags,
+                        variables: metadata.variables,
+                        context_window: metadata.context_window,
+                        backend: metadata.backend,
+                        invariants: metadata.invariants,
+                        ..workflow
+                    };
+                    Self::apply_state_settings(workflow, state_settings)
+                }),
                 _ => Err(ParseError::WrongDiagramType {
                     diagram_type: format!("{diagram:?}"),
                 }),
@@ -120,6 +194,15 @@ impl MermaidParser {
         // Extract actions from the markdown content
         let actions = Self::extract_actions_from_markdown(input);
 
+        // Extract lifecycle hooks from the front matter
+        let hooks = Self::extract_hooks_from_front_matter(input)?;
+
+        // Extract per state settings from the front matter
+        let state_settings = Self::extract_state_settings_from_front_matter(input)?;
+
+        // Extract tags and variables from the front matter
+        let metadata = Self::extract_metadata_from_front_matter(input)?;
+
         // Attempt to parse the diagram
         match parse_diagram(&mermaid_content) {
             Ok(diagram) => match diagram {
@@ -128,9 +211,22 @@ impl MermaidParser {
                         state_diagram,
                         workflow_name.into(),
                         actions,
-                        title,
+                        title.clone(),
                         description,
                     )
+                    .and_then(|workflow| {
+                        let workflow = Workflow {
+                            hooks,
+                            title: title.or(metadata.title),
+                            tags: metadata.tags,
+                            variables: metadata.variables,
+                            context_window: metadata.context_window,
+                            backend: metadata.backend,
+                            invariants: metadata.invariants,
+                            ..workflow
+                        };
+                        Self::apply_state_settings(workflow, state_settings)
+                    })
                 }
                 _ => Err(ParseError::WrongDiagramType {
                     diagram_type: format!("{diagram:?}"),
@@ -190,6 +286,109 @@ impl MermaidParser {
         Ok(mermaid_lines.join("\n"))
     }
 
+    /// Parse the YAML front matter, if the input has any
+    fn front_matter(input: &str) -> Option<serde_yaml::Value> {
+        if !input.starts_with("---\n") {
+            return None;
+        }
+
+        let parts: Vec<&str> = input.splitn(3, "---\n").collect();
+        if parts.len() < 3 {
+            return None;
+        }
+
+        // Front matter that is not valid YAML is reported by the workflow loader
+        serde_yaml::from_str(parts[1]).ok()
+    }
+
+    /// Extract lifecycle hooks from the `hooks` key of the YAML front matter
+    fn extract_hooks_from_front_matter(input: &str) -> ParseResult<WorkflowHooks> {
+        let Some(front_matter) = Self::front_matter(input) else {
+            return Ok(WorkflowHooks::default());
+        };
+
+        let Some(hooks) = front_matter.get("hooks") else {
+            return Ok(WorkflowHooks::default());
+        };
+
+        let hooks: WorkflowHooks =
+            serde_yaml::from_value(hooks.clone()).map_err(|e| ParseError::InvalidStructure {
+                message: format!("Invalid hooks in front matter: {e}"),
+            })?;
+
+        hooks.validate().map_err(|e| ParseError::InvalidStructure {
+            message: e.to_string(),
+        })?;
+
+        Ok(hooks)
+    }
+
+    /// Extract the title, tags, declared variables, context window limits,
+    /// prompt backend and invariants from the YAML front matter
+    fn extract_metadata_from_front_matter(input: &str) -> ParseResult<FrontMatterMetadata> {
+        let Some(front_matter) = Self::front_matter(input).filter(serde_yaml::Value::is_mapping)
+        else {
+            return Ok(FrontMatterMetadata::default());
+        };
+
+        let metadata: FrontMatterMetadata =
+            serde_yaml::from_value(front_matter).map_err(|e| ParseError::InvalidStructure {
+                message: format!("Invalid metadata in front matter: {e}"),
+            })?;
+        metadata
+            .context_window
+            .validate()
+            .map_err(|message| ParseError::InvalidStructure { message })?;
+        for invariant in &metadata.invariants {
+            invariant
+                .validate()
+                .map_err(|message| ParseError::InvalidStructure { message })?;
+        }
+        Ok(metadata)
+    }
+
+    /// Extract per state settings from the `states` key of the YAML front matter
+    fn extract_state_settings_from_front_matter(
+        input: &str,
+    ) -> ParseResult<HashMap<String, StateSettings>> {
+        let Some(states) = Self::front_matter(input).and_then(|front| front.get("states").cloned())
+        else {
+            return Ok(HashMap::new());
+        };
+
+        serde_yaml::from_value(states).map_err(|e| ParseError::InvalidStructure {
+            message: format!("Invalid states in front matter: {e}"),
+        })
+    }
+
+    /// Apply per state settings from the front matter to the parsed states
+    fn apply_state_settings(
+        mut workflow: Workflow,
+        settings: HashMap<String, StateSettings>,
+    ) -> ParseResult<Workflow> {
+        for (state_id, settings) in settings {
+            let state = workflow
+                .states
+                .get_mut(&StateId::new(&state_id))
+                .ok_or_else(|| ParseError::InvalidStructure {
+                    message: format!("Front matter configures unknown state '{state_id}'"),
+                })?;
+
+            if let Some(lock) = &settings.lock {
+                let names = lock.names();
+                for name in &names {
+                    validate_lock_name(name).map_err(|message| ParseError::InvalidStructure {
+                        message: format!("State '{state_id}': {message}"),
+                    })?;
+                }
+                state
+                    .metadata
+                    .insert(LOCK_METADATA_KEY.to_string(), names.join(","));
+            }
+        }
+        Ok(workflow)
+    }
+
     /// Extract actions from markdown content
     fn extract_actions_from_markdown(input: &str) -> HashMap<String, String> {
         let mut actions = HashMap::new();
@@ -980,4 +1179,200 @@ stateDiagram-v2
         assert!(workflow.states.contains_key(&StateId::new("InnerFork")));
         assert!(workflow.states.contains_key(&StateId::new("InnerJoin")));
     }
+
+    #[test]
+    fn test_parse_hooks_from_front_matter() {
+        let input = r#"---
+name: hooked-workflow
+hooks:
+  on_start: Log "Starting ${workflow_name}"
+  on_failure: Shell "echo '${workflow_name} failed: ${error}'"
+---
+
+```mermaid
+stateDiagram-v2
+    [*] --> Start
+    Start --> [*]
+```
+"#;
+
+        let workflow = MermaidParser::parse(input, "hooked_workflow").unwrap();
+        assert_eq!(
+            workflow.hooks.on_start.as_deref(),
+            Some("Log \"Starting ${workflow_name}\"")
+        );
+        assert!(workflow.hooks.on_state_change.is_none());
+        assert!(workflow.hooks.on_failure.is_some());
+    }
+
+    #[test]
+    fn test_parse_invariants_from_front_matter() {
+        let input = r#"---
+name: checked-workflow
+invariants:
+  - not_empty: issue_name
+    states: [Work]
+  - branch_matches: "^issue/"
+---
+
+```mermaid
+stateDiagram-v2
+    [*] --> Start
+    Start --> Work
+    Work --> [*]
+```
+"#;
+
+        let workflow = MermaidParser::parse(input, "checked_workflow").unwrap();
+        assert_eq!(workflow.invariants.len(), 2);
+        assert_eq!(
+            workflow.invariants[0].not_empty.as_deref(),
+            Some("issue_name")
+        );
+        assert_eq!(workflow.invariants[0].states, ["Work"]);
+        assert!(workflow.validate_structure().is_ok());
+
+        let unknown_state = input.replace("states: [Work]", "states: [Review]");
+        let workflow = MermaidParser::parse(&unknown_state, "checked_workflow").unwrap();
+        assert!(workflow.validate_structure().is_err());
+        let bad_pattern = input.replace("\"^issue/\"", "\"[issue\"");
+        assert!(matches!(
+            MermaidParser::parse(&bad_pattern, "checked_workflow"),
+            Err(ParseError::InvalidStructure { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_hooks() {
+        let input = r#"---
+name: hooked-workflow
+hooks:
+  on_failure: post the error to slack
+---
+
+```mermaid
+stateDiagram-v2
+    [*] --> Start
+    Start --> [*]
+```
+"#;
+
+        let result = MermaidParser::parse(input, "hooked_workflow");
+        assert!(matches!(result, Err(ParseError::InvalidStructure { .. })));
+    }
+
+    #[test]
+    fn test_parse_state_locks_from_front_matter() {
+        let input = r#"---
+name: locked-workflow
+states:
+  Checkout:
+    lock: git
+  Merge:
+    lock: [git, issues]
+---
+
+```mermaid
+stateDiagram-v2
+    [*] --> Checkout
+    Checkout --> Merge
+    Merge --> [*]
+```
+"#;
+
+        let workflow = MermaidParser::parse(input, "locked_workflow").unwrap();
+        assert_eq!(workflow.states[&StateId::new("Checkout")].locks(), ["git"]);
+        assert_eq!(
+            workflow.states[&StateId::new("Merge")].locks(),
+            ["git", "issues"]
+        );
+
+        let unknown = input.replace("Merge:\n    lock", "Missing:\n    lock");
+        assert!(matches!(
+            MermaidParser::parse(&unknown, "locked_workflow"),
+            Err(ParseError::InvalidStructure { .. })
+        ));
+        let invalid = input.replace("lock: git", "lock: ../git");
+        assert!(matches!(
+            MermaidParser::parse(&invalid, "locked_workflow"),
+            Err(ParseError::InvalidStructure { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_metadata_from_front_matter() {
+        let input = r#"---
+title: Greeting
+description: Greets someone
+tags: [example, greeting]
+variables:
+  - name: name
+    description: Who to greet
+    required: true
+  - name: language
+    default: English
+---
+
+```mermaid
+stateDiagram-v2
+    [*] --> greet
+    greet --> [*]
+```
+"#;
+
+        let workflow = MermaidParser::parse(input, "greeting").unwrap();
+        assert_eq!(workflow.title.as_deref(), Some("Greeting"));
+        assert_eq!(workflow.tags, ["example", "greeting"]);
+        assert_eq!(workflow.variables.len(), 2);
+        assert_eq!(
+            workflow.variables[0].description.as_deref(),
+            Some("Who to greet")
+        );
+        assert!(workflow.variables[0].required);
+        assert_eq!(
+            workflow.variables[1].default,
+            Some(serde_json::json!("English"))
+        );
+
+        let workflow = MermaidParser::parse_with_metadata(
+            input,
+            "greeting",
+            None,
+            Some("Greets someone".to_string()),
+        )
+        .unwrap();
+        assert_eq!(workflow.title.as_deref(), Some("Greeting"));
+        assert_eq!(workflow.description, "Greets someone");
+        assert_eq!(workflow.variables.len(), 2);
+
+        assert_eq!(workflow.context_window, ContextWindowSettings::default());
+        let limited = input.replace(
+            "variables:",
+            "context_window:\n  max_tokens: 100000\n  strategy: truncate\nvariables:",
+        );
+        let workflow = MermaidParser::parse(&limited, "greeting").unwrap();
+        assert_eq!(workflow.context_window.max_tokens, 100_000);
+        assert_eq!(
+            workflow.context_window.strategy,
+            crate::workflow::CompactionStrategy::Truncate
+        );
+        let invalid = limited.replace("max_tokens: 100000", "max_tokens: 0");
+        assert!(matches!(
+            MermaidParser::parse(&invalid, "greeting"),
+            Err(ParseError::InvalidStructure { .. })
+        ));
+
+        assert_eq!(workflow.backend, None);
+        let openai = input.replace("variables:", "backend: openai\nvariables:");
+        let workflow = MermaidParser::parse(&openai, "greeting").unwrap();
+        assert_eq!(workflow.backend, Some(PromptBackend::OpenAi));
+        let unknown = input.replace("variables:", "backend: gemini\nvariables:");
+        assert!(MermaidParser::parse(&unknown, "greeting").is_err());
+
+        let invalid = input.replace("required: true", "required: maybe");
+        assert!(matches!(
+            MermaidParser::parse(&invalid, "greeting"),
+            Err(ParseError::InvalidStructure { .. })
+        ));
+    }
 }
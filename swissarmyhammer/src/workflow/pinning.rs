@@ -0,0 +1,286 @@
+//! Pinning the prompts and workflows a run uses
+//!
+//! Prompts are rendered from disk each time a state executes them, and
+//! sub-workflows are loaded when a run reaches them, so editing a file while a
+//! workflow runs used to change the run halfway through, or break it on a
+//! half-saved template. A run instead copies the prompt library the first time
+//! it renders a prompt, and a sub-workflow the first time it loads it, into its
+//! pin directory, and reads those copies for the rest of the run. Files that
+//! change on disk afterwards are reported with a warning, once each.
+//!
+//! The workflow a run executes needs no pinning: the run holds its own copy.
+//! Sub-workflows share the pins of the run that started them, and the pin
+//! directory is removed when that run finishes.
+
+use crate::file_loader::{FileEntry, FileSource, SourceFilter, VirtualFileSystem};
+use crate::prompt_integrity::PromptIntegrity;
+use crate::workflow::{Workflow, WorkflowRun, WorkflowRunId};
+use crate::{Result, SwissArmyHammerError};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Context key holding the directory a run pins its prompts and workflows in
+pub const PIN_DIR_KEY: &str = "_pin_dir";
+
+/// Environment variable pointing prompt rendering at the prompts a run pinned
+pub const PINNED_PROMPTS_ENV: &str = "SWISSARMYHAMMER_PINNED_PROMPTS_DIR";
+
+/// File recording what a run pinned
+const MANIFEST_FILE: &str = "manifest.json";
+
+/// The pin directory of a run
+pub fn pin_dir_for(run_id: &WorkflowRunId) -> PathBuf {
+    std::env::temp_dir()
+        .join("swissarmyhammer-pins")
+        .join(run_id.to_string())
+}
+
+/// The directory of pinned prompts to render from instead of the prompt
+/// directories, when rendering for a workflow run
+pub fn pinned_prompts_dir() -> Option<PathBuf> {
+    std::env::var_os(PINNED_PROMPTS_ENV)
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+}
+
+/// What a run has pinned so far
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PinManifest {
+    /// Files prompts were copied from, by prompt name
+    prompts: BTreeMap<String, PathBuf>,
+    /// Prompts and workflows already reported as changed
+    warned: BTreeSet<String>,
+}
+
+/// The prompts and workflows pinned for one run
+#[derive(Debug, Clone)]
+pub struct RunPins {
+    dir: PathBuf,
+}
+
+impl RunPins {
+    /// Pins kept in `dir`
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Pins of the run a context belongs to, if it pins anything
+    pub fn from_context(context: &HashMap<String, Value>) -> Option<Self> {
+        context
+            .get(PIN_DIR_KEY)
+            .and_then(Value::as_str)
+            .map(Self::new)
+    }
+
+    /// Directory the pins are kept in
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn manifest_path(&self) -> PathBuf {
+        self.dir.join(MANIFEST_FILE)
+    }
+
+    fn load_manifest(&self) -> Result<Option<PinManifest>> {
+        let path = self.manifest_path();
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_str(&fs::read_to_string(path)?)?))
+    }
+
+    fn save_manifest(&self, manifest: &PinManifest) -> Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        // Written aside and renamed, so parallel branches never read half a manifest
+        let temp = self.dir.join(format!("{MANIFEST_FILE}.tmp"));
+        fs::write(&temp, serde_json::to_string_pretty(manifest)?)?;
+        fs::rename(temp, self.manifest_path())?;
+        Ok(())
+    }
+
+    /// Pin the user and local prompts on first use, and warn about pinned
+    /// prompts whose files changed since
+    ///
+    /// Returns the prompts newly found changed.
+    pub fn pin_prompts(&self) -> Result<Vec<String>> {
+        if let Some(manifest) = self.load_manifest()? {
+            return self.check_prompts(manifest);
+        }
+        let mut vfs = VirtualFileSystem::new("prompts")
+            .with_source_filter(SourceFilter::global())
+            .with_integrity(PromptIntegrity::global().clone());
+        vfs.load_all()?;
+        let files: Vec<FileEntry> = vfs.files.into_values().collect();
+        self.pin_prompt_files(&files)?;
+        Ok(Vec::new())
+    }
+
+    /// Copy prompt files into the pin directory, where
+    /// [`PromptResolver`](crate::PromptResolver) loads them from when
+    /// [`PINNED_PROMPTS_ENV`] names it
+    fn pin_prompt_files(&self, files: &[FileEntry]) -> Result<()> {
+        let mut manifest = PinManifest::default();
+        for file in files
+            .iter()
+            .filter(|file| file.source != FileSource::Builtin)
+        {
+            let Some(file_name) = file.path.file_name() else {
+                continue;
+            };
+            // Keep the file name, so the copy loads under the same prompt name
+            let relative = Path::new(&file.name).with_file_name(file_name);
+            let pinned = self.dir.join("prompts").join(relative);
+            if let Some(parent) = pinned.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&pinned, &file.content)?;
+            manifest
+                .prompts
+                .insert(file.name.clone(), file.path.clone());
+        }
+        self.save_manifest(&manifest)?;
+        tracing::debug!(
+            "Pinned {} prompts in {}",
+            manifest.prompts.len(),
+            self.dir.display()
+        );
+        Ok(())
+    }
+
+    /// Warn about pinned prompts whose files changed, once each
+    fn check_prompts(&self, mut manifest: PinManifest) -> Result<Vec<String>> {
+        let mut changed = Vec::new();
+        for (name, original) in &manifest.prompts {
+            let key = format!("prompt:{name}");
+            if manifest.warned.contains(&key) {
+                continue;
+            }
+            let file_name = original.file_name().unwrap_or_default();
+            let pinned = self
+                .dir
+                .join("prompts")
+                .join(Path::new(name).with_file_name(file_name));
+            if fs::read_to_string(original).ok() != fs::read_to_string(pinned).ok() {
+                tracing::warn!(
+                    "Prompt '{name}' changed in {} during the run; the run keeps the version it loaded",
+                    original.display()
+                );
+                changed.push(name.clone());
+            }
+        }
+        if !changed.is_empty() {
+            manifest
+                .warned
+                .extend(changed.iter().map(|name| format!("prompt:{name}")));
+            self.save_manifest(&manifest)?;
+        }
+        Ok(changed)
+    }
+
+    /// The version of a sub-workflow the run uses
+    ///
+    /// The first load is pinned and returned. Later loads return the pinned
+    /// version, warning once when the workflow changed or no longer loads.
+    pub fn pin_workflow(&self, name: &str, loaded: Result<Workflow>) -> Result<Workflow> {
+        let path = self.dir.join("workflows").join(format!("{name}.json"));
+        if !path.exists() {
+            let workflow = loaded?;
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&path, serde_json::to_string(&workflow)?)?;
+            return Ok(workflow);
+        }
+
+        let pinned: Workflow = serde_json::from_str(&fs::read_to_string(&path)?).map_err(|e| {
+            SwissArmyHammerError::Other(format!("Invalid pinned workflow '{name}': {e}"))
+        })?;
+        if loaded.as_ref().ok() != Some(&pinned) {
+            let mut manifest = self.load_manifest()?.unwrap_or_default();
+            if manifest.warned.insert(format!("workflow:{name}")) {
+                tracing::warn!(
+                    "Workflow '{name}' changed on disk during the run; the run keeps the version it loaded"
+                );
+                self.save_manifest(&manifest)?;
+            }
+        }
+        Ok(pinned)
+    }
+
+    /// Remove the pins of a run that finished, leaving pins it shares with
+    /// the run that started it
+    pub fn release(run: &WorkflowRun) {
+        let Some(pins) = Self::from_context(&run.context) else {
+            return;
+        };
+        if pins.dir != pin_dir_for(&run.id) || !pins.dir.exists() {
+            return;
+        }
+        if let Err(e) = fs::remove_dir_all(&pins.dir) {
+            tracing::debug!("Failed to remove pins in {}: {}", pins.dir.display(), e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workflow::test_helpers::create_basic_workflow;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_pinned_prompts_report_changes_once() {
+        let temp = TempDir::new().unwrap();
+        let source = temp.path().join("source/prompts/review");
+        fs::create_dir_all(&source).unwrap();
+        let original = source.join("code.md.liquid");
+        fs::write(&original, "Review {{ file }}").unwrap();
+
+        let pins = RunPins::new(temp.path().join("pins"));
+        let files = vec![FileEntry::from_path_and_content(
+            original.clone(),
+            "Review {{ file }}".to_string(),
+            FileSource::Local,
+        )];
+        pins.pin_prompt_files(&files).unwrap();
+        let pinned = temp.path().join("pins/prompts/review/code.md.liquid");
+        assert_eq!(fs::read_to_string(&pinned).unwrap(), "Review {{ file }}");
+        assert!(pins.pin_prompts().unwrap().is_empty());
+
+        fs::write(&original, "Review {{ fi").unwrap();
+        assert_eq!(pins.pin_prompts().unwrap(), vec!["review/code".to_string()]);
+        assert!(pins.pin_prompts().unwrap().is_empty());
+        assert_eq!(fs::read_to_string(&pinned).unwrap(), "Review {{ file }}");
+    }
+
+    #[test]
+    fn test_pinned_workflow_outlives_changes() {
+        let temp = TempDir::new().unwrap();
+        let pins = RunPins::new(temp.path());
+        let first = create_basic_workflow();
+        assert_eq!(
+            pins.pin_workflow("basic", Ok(first.clone())).unwrap(),
+            first
+        );
+
+        let mut edited = first.clone();
+        edited.description = "Edited during the run".to_string();
+        assert_eq!(pins.pin_workflow("basic", Ok(edited)).unwrap(), first);
+        let broken = Err(SwissArmyHammerError::Other("parse error".to_string()));
+        assert_eq!(pins.pin_workflow("basic", broken).unwrap(), first);
+
+        let manifest = pins.load_manifest().unwrap().unwrap();
+        assert!(manifest.warned.contains("workflow:basic"));
+
+        let mut context = HashMap::new();
+        assert!(RunPins::from_context(&context).is_none());
+        context.insert(
+            PIN_DIR_KEY.to_string(),
+            Value::String(temp.path().display().to_string()),
+        );
+        assert_eq!(RunPins::from_context(&context).unwrap().dir(), temp.path());
+    }
+}
@@ -0,0 +1,399 @@
+//! Progress reporting for running workflows
+//!
+//! A long workflow run spends minutes in a single prompt state. To show what
+//! such a run is doing, the executor reports every state it starts and
+//! finishes to the [`WorkflowProgress`] listeners added with
+//! [`WorkflowExecutor::add_progress`](crate::workflow::WorkflowExecutor::add_progress).
+//! [`RunUsage`] totals the tokens and cost of the prompts a run has executed.
+//!
+//! A run is only stored with the other workflow runs once it stops, so
+//! [`RunProgressReporter`] keeps a [`RunProgress`] snapshot of each run it
+//! follows in `.swissarmyhammer/progress`: the state executing, a summary of
+//! the last action's output and what the run has spent so far. Snapshots are
+//! files so runs in other processes can be followed by polling the
+//! `workflow_status` MCP tool, and each update is also published as
+//! [`Event::WorkflowRunProgress`] for subscribers in the same process, such as
+//! the MCP server's client notifications.
+
+use crate::error::Result;
+use crate::events::{event_bus, Event};
+use crate::workflow::{
+    PromptRecord, StateId, WorkflowName, WorkflowRun, WorkflowRunId, WorkflowRunStatus,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Directory run progress snapshots are written to, relative to the project
+/// root
+pub const DEFAULT_PROGRESS_DIR: &str = ".swissarmyhammer/progress";
+
+/// Characters of the last action's output kept in a snapshot
+pub const OUTPUT_SUMMARY_CHARS: usize = 400;
+
+/// Hours the snapshot of a stopped run is kept
+const STOPPED_RUN_RETENTION_HOURS: i64 = 24;
+
+/// A step in the execution of a workflow run
+#[derive(Debug, Clone, PartialEq)]
+pub enum WorkflowProgressEvent {
+    /// A state started executing its action
+    StateStarted {
+        /// The state being executed
+        state: StateId,
+        /// Description of the state
+        description: String,
+    },
+    /// A state finished executing its action
+    StateFinished {
+        /// The state that was executed
+        state: StateId,
+        /// Time spent in the state, including waiting for its locks
+        elapsed: Duration,
+        /// Whether the state's action succeeded
+        succeeded: bool,
+    },
+}
+
+/// Listener notified as a workflow run progresses
+pub trait WorkflowProgress: Send + Sync {
+    /// Called for each progress event, with the run as it is at that point
+    fn on_progress(&self, run: &WorkflowRun, event: &WorkflowProgressEvent);
+}
+
+/// Tokens and cost of the prompts executed during a run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct RunUsage {
+    /// Number of prompts executed
+    pub prompts: usize,
+    /// Input and output tokens reported for the prompts
+    pub tokens: u64,
+    /// Cost reported for the prompts in US dollars
+    pub cost_usd: f64,
+}
+
+impl RunUsage {
+    /// Total the prompt history recorded in a run context
+    pub fn from_context(context: &HashMap<String, Value>) -> Self {
+        PromptRecord::history(context)
+            .iter()
+            .fold(Self::default(), |usage, record| Self {
+                prompts: usage.prompts + 1,
+                tokens: usage.tokens + record.tokens.unwrap_or(0),
+                cost_usd: usage.cost_usd + record.cost_usd.unwrap_or(0.0),
+            })
+    }
+}
+
+impl fmt::Display for RunUsage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} prompts, {} tokens, ${:.4}",
+            self.prompts, self.tokens, self.cost_usd
+        )
+    }
+}
+
+/// What a workflow run is doing and has spent, as of its last progress event
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunProgress {
+    /// The run
+    pub run_id: WorkflowRunId,
+    /// Workflow the run executes
+    pub workflow: WorkflowName,
+    /// Status of the run
+    pub status: WorkflowRunStatus,
+    /// State the run is in
+    pub current_state: StateId,
+    /// Whether the current state's action is executing
+    pub executing: bool,
+    /// Number of states the run has entered
+    pub states_entered: usize,
+    /// When the run started
+    pub started_at: DateTime<Utc>,
+    /// When the snapshot was taken
+    pub updated_at: DateTime<Utc>,
+    /// Start of the output of the last action
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_output: Option<String>,
+    /// Whether the last action succeeded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_succeeded: Option<bool>,
+    /// Prompts executed, with their tokens and cost
+    pub usage: RunUsage,
+}
+
+impl RunProgress {
+    /// Snapshot a run as it is now
+    pub fn of(run: &WorkflowRun) -> Self {
+        Self {
+            run_id: run.id,
+            workflow: run.workflow.name.clone(),
+            status: run.status,
+            current_state: run.current_state.clone(),
+            executing: false,
+            states_entered: run.history.len(),
+            started_at: run.started_at,
+            updated_at: Utc::now(),
+            last_output: run.context.get("result").and_then(summarize_output),
+            last_succeeded: run.context.get("success").and_then(Value::as_bool),
+            usage: RunUsage::from_context(&run.context),
+        }
+    }
+
+    /// Whether the run completed, failed or was cancelled
+    pub fn is_stopped(&self) -> bool {
+        matches!(
+            self.status,
+            WorkflowRunStatus::Completed | WorkflowRunStatus::Failed | WorkflowRunStatus::Cancelled
+        )
+    }
+}
+
+impl fmt::Display for RunProgress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Workflow {} run {} {:?} in state {} ({})",
+            self.workflow, self.run_id, self.status, self.current_state, self.usage
+        )
+    }
+}
+
+/// The start of an action's output, or `None` when it has no output
+fn summarize_output(output: &Value) -> Option<String> {
+    let text = match output {
+        Value::Null => return None,
+        Value::String(text) => text.trim().to_string(),
+        other => other.to_string(),
+    };
+    if text.is_empty() {
+        return None;
+    }
+    match text.char_indices().nth(OUTPUT_SUMMARY_CHARS) {
+        Some((end, _)) => Some(format!("{}…", &text[..end])),
+        None => Some(text),
+    }
+}
+
+/// Progress snapshots of workflow runs, one JSON file per run
+#[derive(Debug, Clone)]
+pub struct RunProgressBoard {
+    dir: PathBuf,
+}
+
+impl RunProgressBoard {
+    /// Create a board keeping snapshots in `dir`
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Directory the snapshots are kept in
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Snapshot file of a run
+    pub fn progress_path(&self, run_id: &WorkflowRunId) -> PathBuf {
+        self.dir.join(format!("{run_id}.json"))
+    }
+
+    /// Replace the snapshot of a run
+    ///
+    /// The snapshot is written next to its file and renamed over it, so a
+    /// reader never sees half of one.
+    pub fn update(&self, progress: &RunProgress) -> Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let path = self.progress_path(&progress.run_id);
+        let temp_path = path.with_extension("json.tmp");
+        fs::write(&temp_path, serde_json::to_string_pretty(progress)?)?;
+        fs::rename(&temp_path, &path)?;
+        Ok(())
+    }
+
+    /// The snapshot of a run, if there is one
+    pub fn get(&self, run_id: &WorkflowRunId) -> Result<Option<RunProgress>> {
+        let path = self.progress_path(run_id);
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_str(&fs::read_to_string(path)?)?))
+    }
+
+    /// Every snapshot, most recently updated first
+    ///
+    /// Files that can't be read are skipped.
+    pub fn list(&self) -> Result<Vec<RunProgress>> {
+        if !self.dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut snapshots = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            let snapshot = fs::read_to_string(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|json| serde_json::from_str(&json).map_err(|e| e.to_string()));
+            match snapshot {
+                Ok(snapshot) => snapshots.push(snapshot),
+                Err(e) => tracing::debug!("Skipping run progress {}: {}", path.display(), e),
+            }
+        }
+        snapshots.sort_by(|a: &RunProgress, b| b.updated_at.cmp(&a.updated_at));
+        Ok(snapshots)
+    }
+
+    /// Remove the snapshots of runs that stopped more than a day before
+    /// `now`, returning how many were removed
+    pub fn prune(&self, now: DateTime<Utc>) -> Result<usize> {
+        let cutoff = now - chrono::Duration::hours(STOPPED_RUN_RETENTION_HOURS);
+        let mut removed = 0;
+        for snapshot in self.list()? {
+            if snapshot.is_stopped() && snapshot.updated_at < cutoff {
+                fs::remove_file(self.progress_path(&snapshot.run_id))?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+}
+
+impl Default for RunProgressBoard {
+    fn default() -> Self {
+        Self::new(DEFAULT_PROGRESS_DIR)
+    }
+}
+
+/// Progress listener that keeps a snapshot of each run on a
+/// [`RunProgressBoard`] and publishes every update on the event bus
+#[derive(Debug, Clone, Default)]
+pub struct RunProgressReporter {
+    board: RunProgressBoard,
+}
+
+impl RunProgressReporter {
+    /// Report to a board
+    pub fn new(board: RunProgressBoard) -> Self {
+        Self { board }
+    }
+
+    /// The board snapshots are kept on
+    pub fn board(&self) -> &RunProgressBoard {
+        &self.board
+    }
+
+    /// Record a snapshot and announce it, logging instead of failing when it
+    /// can't be written
+    fn report(&self, progress: RunProgress) {
+        if let Err(e) = self.board.update(&progress) {
+            tracing::warn!(
+                "Failed to record progress of run {}: {}",
+                progress.run_id,
+                e
+            );
+        }
+        event_bus().publish(Event::WorkflowRunProgress(progress));
+    }
+
+    /// Record the status a run stopped or paused in, and remove the snapshots
+    /// of runs that stopped long ago
+    pub fn finish(&self, run: &WorkflowRun) {
+        self.report(RunProgress::of(run));
+        if let Err(e) = self.board.prune(Utc::now()) {
+            tracing::debug!("Failed to prune run progress: {}", e);
+        }
+    }
+}
+
+impl WorkflowProgress for RunProgressReporter {
+    fn on_progress(&self, run: &WorkflowRun, event: &WorkflowProgressEvent) {
+        let mut progress = RunProgress::of(run);
+        progress.executing = matches!(event, WorkflowProgressEvent::StateStarted { .. });
+        self.report(progress);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn record(tokens: Option<u64>, cost_usd: Option<f64>) -> PromptRecord {
+        PromptRecord {
+            prompt: "review".to_string(),
+            arguments: BTreeMap::new(),
+            response: "ok".to_string(),
+            cost_usd,
+            tokens,
+            hash: None,
+        }
+    }
+
+    #[test]
+    fn test_run_usage_totals_prompt_history() {
+        let mut context = HashMap::new();
+        assert_eq!(RunUsage::from_context(&context), RunUsage::default());
+
+        record(Some(1200), Some(0.01)).record(&mut context);
+        record(None, Some(0.02)).record(&mut context);
+        record(Some(300), None).record(&mut context);
+
+        let usage = RunUsage::from_context(&context);
+        assert_eq!(usage.prompts, 3);
+        assert_eq!(usage.tokens, 1500);
+        assert_eq!(usage.to_string(), "3 prompts, 1500 tokens, $0.0300");
+    }
+
+    #[test]
+    fn test_run_progress_board() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let board = RunProgressBoard::new(temp.path().join("progress"));
+        assert!(board.list().unwrap().is_empty());
+
+        let mut run = WorkflowRun::new(crate::workflow::test_helpers::create_basic_workflow());
+        run.context
+            .insert("result".to_string(), Value::String("x".repeat(500)));
+        run.context.insert("success".to_string(), Value::Bool(true));
+        record(Some(100), Some(0.25)).record(&mut run.context);
+
+        let reporter = RunProgressReporter::new(board.clone());
+        let mut events = event_bus().subscribe();
+        reporter.on_progress(
+            &run,
+            &WorkflowProgressEvent::StateStarted {
+                state: run.current_state.clone(),
+                description: "Start".to_string(),
+            },
+        );
+
+        let progress = board.get(&run.id).unwrap().unwrap();
+        assert!(progress.executing);
+        assert_eq!(progress.status, WorkflowRunStatus::Running);
+        assert_eq!(progress.last_succeeded, Some(true));
+        assert!((progress.usage.cost_usd - 0.25).abs() < 1e-9);
+        let output = progress.last_output.as_deref().unwrap();
+        assert_eq!(output.chars().count(), OUTPUT_SUMMARY_CHARS + 1);
+        assert!(output.ends_with('…'));
+        assert!(std::iter::from_fn(|| events.try_recv())
+            .any(|event| event == Event::WorkflowRunProgress(progress.clone())));
+
+        // A stopped run keeps its snapshot for a day
+        run.complete();
+        reporter.finish(&run);
+        let progress = board.get(&run.id).unwrap().unwrap();
+        assert!(progress.is_stopped() && !progress.executing);
+        assert_eq!(board.list().unwrap(), vec![progress]);
+        assert_eq!(board.prune(Utc::now()).unwrap(), 0);
+        let later = Utc::now() + chrono::Duration::hours(STOPPED_RUN_RETENTION_HOURS + 1);
+        assert_eq!(board.prune(later).unwrap(), 1);
+        assert!(board.get(&run.id).unwrap().is_none());
+    }
+}
@@ -0,0 +1,817 @@
+//! Model providers prompt actions send rendered prompts to
+//!
+//! A prompt action renders its prompt and hands it to a [`ModelProvider`],
+//! chosen by the action's [`PromptBackend`]:
+//!
+//! - `claude` pipes the prompt to the local Claude CLI, the default
+//! - `sampling` asks the model of the MCP client the workflow was started from
+//! - `anthropic` calls the Anthropic Messages API with `ANTHROPIC_API_KEY`
+//! - `openai` calls the chat completions endpoint of any OpenAI-compatible
+//!   server, such as OpenAI itself, a local Ollama or vLLM, with
+//!   `OPENAI_API_KEY` when the server needs one
+//!
+//! The backend is picked per action with `backend="<name>"`, per workflow
+//! with a `backend` key in the front matter, or for every workflow with
+//! `SWISSARMYHAMMER_PROMPT_BACKEND`, in that order, so the same workflow can
+//! run against another model by changing one setting:
+//!
+//! ```text
+//! Execute prompt "review" with backend="openai" model="llama3.1"
+//! ```
+//!
+//! Only the Claude CLI can continue a conversation; the other providers start
+//! a new one for every prompt.
+
+use crate::config::Config;
+use crate::cost::CostRates;
+use crate::error::{Result, SwissArmyHammerError};
+use crate::workflow::actions::{format_claude_output_as_yaml, ActionError, ActionResult};
+use crate::workflow::sampling::{prompt_sampler, SamplingRequest, DEFAULT_SAMPLING_MAX_TOKENS};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::process::Command;
+use tokio::time::timeout;
+
+/// Context key holding the backend the run's workflow sends prompts to
+pub const PROMPT_BACKEND_KEY: &str = "_prompt_backend";
+
+/// Environment variable read by the Claude CLI to cap output tokens
+const CLAUDE_MAX_OUTPUT_TOKENS_ENV: &str = "CLAUDE_CODE_MAX_OUTPUT_TOKENS";
+
+/// Environment variable holding the Anthropic API key
+pub const ANTHROPIC_API_KEY_ENV: &str = "ANTHROPIC_API_KEY";
+
+/// Environment variable holding the key for OpenAI-compatible endpoints
+pub const OPENAI_API_KEY_ENV: &str = "OPENAI_API_KEY";
+
+/// Version of the Anthropic Messages API requests are written for
+const ANTHROPIC_API_VERSION: &str = "2023-06-01";
+
+/// Wait before retrying a rate limited API request that names no wait
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// Where a prompt action sends its rendered prompt
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PromptBackend {
+    /// The local Claude CLI
+    #[default]
+    Claude,
+    /// The connected MCP client's model, through a sampling request
+    Sampling,
+    /// The Anthropic Messages API
+    Anthropic,
+    /// An OpenAI-compatible chat completions endpoint
+    #[serde(rename = "openai")]
+    OpenAi,
+}
+
+impl PromptBackend {
+    /// Every backend, in the order they are documented
+    pub const ALL: [PromptBackend; 4] = [
+        PromptBackend::Claude,
+        PromptBackend::Sampling,
+        PromptBackend::Anthropic,
+        PromptBackend::OpenAi,
+    ];
+
+    /// Name used in actions, front matter and configuration
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PromptBackend::Claude => "claude",
+            PromptBackend::Sampling => "sampling",
+            PromptBackend::Anthropic => "anthropic",
+            PromptBackend::OpenAi => "openai",
+        }
+    }
+
+    /// Model used when neither the action nor the workflow names one
+    pub fn default_model(&self, config: &Config) -> Option<String> {
+        match self {
+            PromptBackend::OpenAi => config.openai_model.clone(),
+            _ => config.claude_model.clone(),
+        }
+    }
+
+    /// The backend configured for every workflow
+    pub fn from_config(config: &Config) -> Result<Self> {
+        config.prompt_backend.parse()
+    }
+
+    /// The backend a run's workflow chose, if it chose one
+    pub fn from_context(context: &HashMap<String, Value>) -> Option<Self> {
+        context
+            .get(PROMPT_BACKEND_KEY)
+            .and_then(|backend| serde_json::from_value(backend.clone()).ok())
+    }
+
+    /// Store the backend in a run context for its prompt actions
+    pub fn store(&self, context: &mut HashMap<String, Value>) {
+        context.insert(
+            PROMPT_BACKEND_KEY.to_string(),
+            Value::String(self.as_str().to_string()),
+        );
+    }
+
+    /// The provider that generates responses for this backend
+    pub fn provider(&self, config: &Config) -> Result<Arc<dyn ModelProvider>> {
+        Ok(match self {
+            PromptBackend::Claude => Arc::new(ClaudeCliProvider),
+            PromptBackend::Sampling => Arc::new(SamplingProvider),
+            PromptBackend::Anthropic => Arc::new(AnthropicProvider::from_config(config)?),
+            PromptBackend::OpenAi => Arc::new(OpenAiProvider::from_config(config)),
+        })
+    }
+}
+
+impl fmt::Display for PromptBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for PromptBackend {
+    type Err = SwissArmyHammerError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let name = s.trim();
+        PromptBackend::ALL
+            .into_iter()
+            .find(|backend| backend.as_str().eq_ignore_ascii_case(name))
+            .ok_or_else(|| {
+                SwissArmyHammerError::Other(format!(
+                    "Invalid backend '{s}': must be one of {}",
+                    PromptBackend::ALL
+                        .map(|backend| backend.as_str())
+                        .join(", ")
+                ))
+            })
+    }
+}
+
+/// A rendered prompt to generate a response for
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelRequest {
+    /// The rendered prompt, sent as the user message
+    pub prompt: String,
+    /// System prompt for the model
+    pub system_prompt: Option<String>,
+    /// Model name or alias
+    pub model: Option<String>,
+    /// Sampling temperature between 0.0 and 1.0
+    pub temperature: Option<f32>,
+    /// Maximum number of output tokens
+    pub max_tokens: Option<u32>,
+    /// Conversation to continue, for providers that keep conversations
+    pub session_id: Option<String>,
+    /// Directory the model works in, for providers that run locally
+    pub working_dir: Option<PathBuf>,
+    /// Longest wait for the response
+    pub timeout: Duration,
+}
+
+/// A response generated for a [`ModelRequest`], with what the provider
+/// reported about it
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ModelResponse {
+    /// The generated text
+    pub text: String,
+    /// Cost of the request in US dollars
+    pub cost_usd: Option<f64>,
+    /// Input and output tokens of the request
+    pub tokens: Option<u64>,
+    /// Conversation the response belongs to, when it can be continued
+    pub session_id: Option<String>,
+}
+
+/// Generates responses to rendered prompts with a model
+#[async_trait::async_trait]
+pub trait ModelProvider: Send + Sync {
+    /// Name of the provider in logs
+    fn name(&self) -> &'static str;
+
+    /// Generate a response to a prompt
+    async fn generate(&self, request: ModelRequest) -> ActionResult<ModelResponse>;
+}
+
+/// Runs prompts with the local Claude CLI
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ClaudeCliProvider;
+
+impl ClaudeCliProvider {
+    /// Find claude in PATH or in its common install locations
+    fn claude_path() -> ActionResult<PathBuf> {
+        which::which("claude")
+            .or_else(|_| {
+                let home = std::env::var("HOME").unwrap_or_default();
+                let possible_paths = vec![
+                    format!("{}/.claude/local/claude", home),
+                    "/usr/local/bin/claude".to_string(),
+                    "/opt/claude/claude".to_string(),
+                ];
+
+                for path in possible_paths {
+                    if std::path::Path::new(&path).exists() {
+                        return Ok(PathBuf::from(path));
+                    }
+                }
+
+                Err(which::Error::CannotFindBinaryPath)
+            })
+            .map_err(|e| {
+                ActionError::ClaudeError(format!(
+                    "Claude CLI not found. Make sure 'claude' is installed and available in your PATH. Error: {e}"
+                ))
+            })
+    }
+}
+
+#[async_trait::async_trait]
+impl ModelProvider for ClaudeCliProvider {
+    fn name(&self) -> &'static str {
+        "Claude CLI"
+    }
+
+    async fn generate(&self, request: ModelRequest) -> ActionResult<ModelResponse> {
+        let claude_path = Self::claude_path()?;
+        let mut cmd = Command::new(&claude_path);
+        if let Some(working_dir) = &request.working_dir {
+            cmd.current_dir(working_dir);
+        }
+
+        // Claude CLI arguments
+        cmd.arg("--dangerously-skip-permissions")
+            .arg("--print")
+            .arg("--output-format")
+            .arg("stream-json")
+            .arg("--verbose");
+        if let Some(model) = &request.model {
+            cmd.arg("--model").arg(model);
+        }
+        if let Some(system_prompt) = &request.system_prompt {
+            cmd.arg("--system-prompt").arg(system_prompt);
+        }
+        if let Some(max_tokens) = request.max_tokens {
+            cmd.env(CLAUDE_MAX_OUTPUT_TOKENS_ENV, max_tokens.to_string());
+        }
+        if let Some(temperature) = request.temperature {
+            // The Claude CLI has no temperature flag, so the value cannot be forwarded
+            tracing::warn!(
+                "Ignoring temperature {} for Claude: the Claude CLI does not support setting it",
+                temperature
+            );
+        }
+        if let Some(session_id) = &request.session_id {
+            tracing::debug!("Resuming Claude session {}", session_id);
+            cmd.arg("--resume").arg(session_id);
+        }
+
+        // Set up the command to pipe prompt via stdin, killing Claude if the
+        // run is cancelled while it works
+        cmd.stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .kill_on_drop(true);
+        tracing::debug!("Running Claude at: {}", claude_path.display());
+
+        // Spawn the Claude process
+        let mut child = cmd.spawn().map_err(|e| {
+            ActionError::ClaudeError(format!("Failed to spawn Claude command: {e}"))
+        })?;
+
+        // Write the prompt to Claude's stdin
+        if let Some(mut stdin) = child.stdin.take() {
+            use tokio::io::AsyncWriteExt;
+            stdin
+                .write_all(request.prompt.as_bytes())
+                .await
+                .map_err(|e| {
+                    ActionError::ClaudeError(format!("Failed to write prompt to Claude: {e}"))
+                })?;
+            stdin.shutdown().await.map_err(|e| {
+                ActionError::ClaudeError(format!("Failed to close Claude stdin: {e}"))
+            })?;
+        }
+
+        // Get stdout for streaming
+        let stdout = child.stdout.take().ok_or_else(|| {
+            ActionError::ClaudeError("Failed to capture Claude stdout".to_string())
+        })?;
+
+        // Read stdout line by line for streaming JSON
+        use tokio::io::{AsyncBufReadExt, BufReader};
+        let reader = BufReader::new(stdout);
+        let mut lines = reader.lines();
+
+        let mut response = ModelResponse::default();
+        let line_timeout = request.timeout;
+        tracing::debug!("Using line timeout: {:?}", line_timeout);
+
+        // Read lines with timeout
+        loop {
+            match timeout(line_timeout, lines.next_line()).await {
+                Ok(Ok(Some(line))) => {
+                    // Format JSON as YAML for better readability
+                    let formatted_output = format_claude_output_as_yaml(&line);
+                    if formatted_output != line {
+                        // If it was formatted as YAML, log it with proper structure
+                        tracing::debug!("Claude output line:\n{}", formatted_output);
+                    } else {
+                        // If not JSON, log as is
+                        tracing::debug!("Claude output line: {}", line);
+                    }
+
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+
+                    if let Ok(json) = serde_json::from_str::<Value>(&line) {
+                        if let Some(id) = json.get("session_id").and_then(|s| s.as_str()) {
+                            response.session_id = Some(id.to_string());
+                        }
+                        // Look for the final result
+                        if let Some(result) = json.get("result").and_then(|r| r.as_str()) {
+                            response.text = result.to_string();
+                            response.cost_usd = json
+                                .get("total_cost_usd")
+                                .or_else(|| json.get("cost_usd"))
+                                .and_then(|c| c.as_f64());
+                            response.tokens = json.get("usage").map(|usage| {
+                                ["input_tokens", "output_tokens"]
+                                    .iter()
+                                    .filter_map(|key| usage.get(key).and_then(|t| t.as_u64()))
+                                    .sum::<u64>()
+                            });
+                            break;
+                        }
+                        // Also check for assistant messages with text content
+                        if json.get("type").and_then(|t| t.as_str()) == Some("assistant") {
+                            if let Some(message) = json.get("message").and_then(|m| m.as_object()) {
+                                if let Some(content_array) =
+                                    message.get("content").and_then(|c| c.as_array())
+                                {
+                                    for content_item in content_array {
+                                        if let Some(text) =
+                                            content_item.get("text").and_then(|t| t.as_str())
+                                        {
+                                            response.text.push_str(text);
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                Ok(Ok(None)) => {
+                    // End of stream
+                    break;
+                }
+                Ok(Err(e)) => {
+                    tracing::error!("Error reading Claude output: {}", e);
+                    break;
+                }
+                Err(_) => {
+                    // Timeout - kill the process and return error
+                    tracing::error!("Timeout reading Claude output after {:?}", line_timeout);
+                    tracing::error!("Response so far: {} characters", response.text.len());
+                    let _ = child.kill().await;
+                    // If we have some response, use it rather than erroring
+                    if !response.text.is_empty() {
+                        break;
+                    }
+                    return Err(ActionError::Timeout {
+                        timeout: line_timeout,
+                    });
+                }
+            }
+        }
+
+        // Wait for process to complete with a short timeout
+        let wait_result = timeout(Duration::from_secs(5), child.wait()).await;
+        let status = match wait_result {
+            Ok(Ok(status)) => status,
+            Ok(Err(e)) => {
+                return Err(ActionError::ClaudeError(format!(
+                    "Failed to wait for Claude: {e}"
+                )))
+            }
+            Err(_) => {
+                // Process didn't exit cleanly, kill it
+                let _ = child.kill().await;
+                return Err(ActionError::ClaudeError(
+                    "Claude process failed to exit cleanly".to_string(),
+                ));
+            }
+        };
+
+        if !status.success() {
+            return Err(ActionError::ClaudeError(
+                "Claude execution failed".to_string(),
+            ));
+        }
+
+        Ok(response)
+    }
+}
+
+/// Sends prompts to the connected MCP client's model
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SamplingProvider;
+
+#[async_trait::async_trait]
+impl ModelProvider for SamplingProvider {
+    fn name(&self) -> &'static str {
+        "MCP client's model"
+    }
+
+    async fn generate(&self, request: ModelRequest) -> ActionResult<ModelResponse> {
+        let sampler = prompt_sampler().ok_or_else(|| {
+            ActionError::ClaudeError(
+                "Sampling is not available: the prompt must run in a workflow started through an MCP client that supports sampling".to_string(),
+            )
+        })?;
+        if request.session_id.is_some() {
+            tracing::debug!("Sampling requests start a new conversation for each prompt");
+        }
+
+        let sampling_request = SamplingRequest {
+            prompt: request.prompt,
+            system_prompt: request.system_prompt,
+            model: request.model,
+            temperature: request.temperature,
+            max_tokens: request.max_tokens.unwrap_or(DEFAULT_SAMPLING_MAX_TOKENS),
+        };
+        let response = timeout(request.timeout, sampler.create_message(sampling_request))
+            .await
+            .map_err(|_| ActionError::Timeout {
+                timeout: request.timeout,
+            })?
+            .map_err(|e| ActionError::ClaudeError(format!("Sampling request failed: {e}")))?;
+        tracing::debug!("Sampled response from model {}", response.model);
+
+        Ok(ModelResponse {
+            text: response.text,
+            ..Default::default()
+        })
+    }
+}
+
+/// Calls the Anthropic Messages API
+#[derive(Debug, Clone)]
+pub struct AnthropicProvider {
+    base_url: String,
+    api_key: String,
+    rates: CostRates,
+}
+
+impl AnthropicProvider {
+    /// Create a provider calling the API at `base_url` with `api_key`
+    pub fn new(base_url: impl Into<String>, api_key: impl Into<String>, rates: CostRates) -> Self {
+        Self {
+            base_url: base_url.into(),
+            api_key: api_key.into(),
+            rates,
+        }
+    }
+
+    /// Create a provider from the configured base URL, cost rates and the
+    /// key in `ANTHROPIC_API_KEY`
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let api_key = std::env::var(ANTHROPIC_API_KEY_ENV)
+            .ok()
+            .filter(|key| !key.trim().is_empty())
+            .ok_or_else(|| {
+                SwissArmyHammerError::Other(format!(
+                    "The anthropic backend needs an API key: set {ANTHROPIC_API_KEY_ENV}"
+                ))
+            })?;
+        Ok(Self::new(
+            config.anthropic_base_url.clone(),
+            api_key,
+            CostRates::from_config(config),
+        ))
+    }
+
+    /// Body of a Messages API request
+    fn request_body(request: &ModelRequest, model: &str) -> Value {
+        let mut body = json!({
+            "model": model,
+            "max_tokens": request.max_tokens.unwrap_or(DEFAULT_SAMPLING_MAX_TOKENS),
+            "messages": [{"role": "user", "content": request.prompt}],
+        });
+        if let Some(system_prompt) = &request.system_prompt {
+            body["system"] = json!(system_prompt);
+        }
+        if let Some(temperature) = request.temperature {
+            body["temperature"] = json!(temperature);
+        }
+        body
+    }
+
+    /// The response to a Messages API request, with its cost at the
+    /// configured rates
+    fn parse_response(&self, body: &Value) -> ActionResult<ModelResponse> {
+        let content = body
+            .get("content")
+            .and_then(Value::as_array)
+            .ok_or_else(|| {
+                ActionError::ClaudeError(format!("Anthropic API response has no content: {body}"))
+            })?;
+        let text = content
+            .iter()
+            .filter(|block| block.get("type").and_then(Value::as_str) == Some("text"))
+            .filter_map(|block| block.get("text").and_then(Value::as_str))
+            .collect::<String>();
+        let usage = |key: &str| {
+            body.pointer(&format!("/usage/{key}"))
+                .and_then(Value::as_u64)
+        };
+        let (input_tokens, output_tokens) = (usage("input_tokens"), usage("output_tokens"));
+
+        Ok(ModelResponse {
+            text,
+            cost_usd: input_tokens
+                .zip(output_tokens)
+                .map(|(input, output)| self.rates.cost_usd(input, output)),
+            tokens: input_tokens
+                .zip(output_tokens)
+                .map(|(input, output)| input + output),
+            session_id: None,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl ModelProvider for AnthropicProvider {
+    fn name(&self) -> &'static str {
+        "Anthropic API"
+    }
+
+    async fn generate(&self, request: ModelRequest) -> ActionResult<ModelResponse> {
+        let model = request.model.clone().ok_or_else(|| {
+            ActionError::ClaudeError(
+                "The anthropic backend needs a model: set model=\"...\" on the action or SWISSARMYHAMMER_CLAUDE_MODEL".to_string(),
+            )
+        })?;
+        let http = reqwest::Client::new()
+            .post(format!(
+                "{}/v1/messages",
+                self.base_url.trim_end_matches('/')
+            ))
+            .header("x-api-key", &self.api_key)
+            .header("anthropic-version", ANTHROPIC_API_VERSION)
+            .json(&Self::request_body(&request, &model));
+        let body = send_json(self.name(), http, request.timeout).await?;
+        self.parse_response(&body)
+    }
+}
+
+/// Calls the chat completions endpoint of an OpenAI-compatible server
+#[derive(Debug, Clone)]
+pub struct OpenAiProvider {
+    base_url: String,
+    api_key: Option<String>,
+}
+
+impl OpenAiProvider {
+    /// Create a provider calling the endpoint under `base_url`, with
+    /// `api_key` when the server needs one
+    pub fn new(base_url: impl Into<String>, api_key: Option<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            api_key,
+        }
+    }
+
+    /// Create a provider from the configured base URL and the key in
+    /// `OPENAI_API_KEY`, if set
+    pub fn from_config(config: &Config) -> Self {
+        Self::new(
+            config.openai_base_url.clone(),
+            std::env::var(OPENAI_API_KEY_ENV)
+                .ok()
+                .filter(|key| !key.trim().is_empty()),
+        )
+    }
+
+    /// Body of a chat completions request
+    fn request_body(request: &ModelRequest, model: &str) -> Value {
+        let mut messages = Vec::new();
+        if let Some(system_prompt) = &request.system_prompt {
+            messages.push(json!({"role": "system", "content": system_prompt}));
+        }
+        messages.push(json!({"role": "user", "content": request.prompt}));
+        let mut body = json!({"model": model, "messages": messages});
+        if let Some(max_tokens) = request.max_tokens {
+            body["max_tokens"] = json!(max_tokens);
+        }
+        if let Some(temperature) = request.temperature {
+            body["temperature"] = json!(temperature);
+        }
+        body
+    }
+
+    /// The response to a chat completions request
+    ///
+    /// OpenAI-compatible servers charge differently, so no cost is reported.
+    fn parse_response(body: &Value) -> ActionResult<ModelResponse> {
+        let text = body
+            .pointer("/choices/0/message/content")
+            .and_then(Value::as_str)
+            .ok_or_else(|| {
+                ActionError::ClaudeError(format!(
+                    "OpenAI-compatible response has no message: {body}"
+                ))
+            })?;
+        Ok(ModelResponse {
+            text: text.to_string(),
+            tokens: body.pointer("/usage/total_tokens").and_then(Value::as_u64),
+            ..Default::default()
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl ModelProvider for OpenAiProvider {
+    fn name(&self) -> &'static str {
+        "OpenAI-compatible endpoint"
+    }
+
+    async fn generate(&self, request: ModelRequest) -> ActionResult<ModelResponse> {
+        let model = request.model.clone().ok_or_else(|| {
+            ActionError::ClaudeError(
+                "The openai backend needs a model: set model=\"...\" on the action or SWISSARMYHAMMER_OPENAI_MODEL".to_string(),
+            )
+        })?;
+        let mut http = reqwest::Client::new()
+            .post(format!(
+                "{}/chat/completions",
+                self.base_url.trim_end_matches('/')
+            ))
+            .json(&Self::request_body(&request, &model));
+        if let Some(api_key) = &self.api_key {
+            http = http.bearer_auth(api_key);
+        }
+        let body = send_json(self.name(), http, request.timeout).await?;
+        Self::parse_response(&body)
+    }
+}
+
+/// Send an API request and read its JSON response, reporting rate limits so
+/// the action can be retried
+async fn send_json(
+    provider: &str,
+    request: reqwest::RequestBuilder,
+    wait: Duration,
+) -> ActionResult<Value> {
+    let response = timeout(wait, request.send())
+        .await
+        .map_err(|_| ActionError::Timeout { timeout: wait })?
+        .map_err(|e| ActionError::ClaudeError(format!("{provider} request failed: {e}")))?;
+    let status = response.status();
+    let retry_after = response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok())
+        .map(Duration::from_secs);
+    let text = timeout(wait, response.text())
+        .await
+        .map_err(|_| ActionError::Timeout { timeout: wait })?
+        .map_err(|e| ActionError::ClaudeError(format!("{provider} response failed: {e}")))?;
+
+    if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        return Err(ActionError::RateLimit {
+            message: format!("{provider}: {text}"),
+            wait_time: retry_after.unwrap_or(DEFAULT_RETRY_AFTER),
+        });
+    }
+    if !status.is_success() {
+        return Err(ActionError::ClaudeError(format!(
+            "{provider} request failed with {status}: {text}"
+        )));
+    }
+    serde_json::from_str(&text).map_err(|e| {
+        ActionError::ClaudeError(format!("{provider} returned invalid JSON: {e}: {text}"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request() -> ModelRequest {
+        ModelRequest {
+            prompt: "Review the diff".to_string(),
+            system_prompt: Some("You review code".to_string()),
+            model: Some("test-model".to_string()),
+            temperature: Some(0.5),
+            max_tokens: None,
+            session_id: None,
+            working_dir: None,
+            timeout: Duration::from_secs(60),
+        }
+    }
+
+    #[test]
+    fn test_prompt_backend_names() {
+        assert_eq!(
+            "sampling".parse::<PromptBackend>().unwrap(),
+            PromptBackend::Sampling
+        );
+        assert_eq!(
+            " OpenAI ".parse::<PromptBackend>().unwrap(),
+            PromptBackend::OpenAi
+        );
+        assert!("gemini"
+            .parse::<PromptBackend>()
+            .unwrap_err()
+            .to_string()
+            .contains("claude, sampling, anthropic, openai"));
+        assert_eq!(PromptBackend::default(), PromptBackend::Claude);
+
+        // The workflow's backend travels with the run context
+        let mut context = HashMap::new();
+        assert_eq!(PromptBackend::from_context(&context), None);
+        PromptBackend::Anthropic.store(&mut context);
+        assert_eq!(
+            PromptBackend::from_context(&context),
+            Some(PromptBackend::Anthropic)
+        );
+        assert_eq!(
+            serde_yaml::from_str::<PromptBackend>("openai").unwrap(),
+            PromptBackend::OpenAi
+        );
+    }
+
+    #[test]
+    fn test_default_models() {
+        let config = Config {
+            claude_model: Some("claude-sonnet".to_string()),
+            openai_model: Some("llama3.1".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            PromptBackend::Anthropic.default_model(&config).as_deref(),
+            Some("claude-sonnet")
+        );
+        assert_eq!(
+            PromptBackend::OpenAi.default_model(&config).as_deref(),
+            Some("llama3.1")
+        );
+    }
+
+    #[test]
+    fn test_anthropic_request_and_response() {
+        let body = AnthropicProvider::request_body(&request(), "test-model");
+        assert_eq!(body["model"], "test-model");
+        assert_eq!(body["system"], "You review code");
+        assert_eq!(body["max_tokens"], DEFAULT_SAMPLING_MAX_TOKENS);
+        assert_eq!(body["messages"][0]["content"], "Review the diff");
+
+        let provider = AnthropicProvider::new(
+            "https://api.anthropic.com",
+            "key",
+            CostRates::from_config(&Config::default()),
+        );
+        let response = provider
+            .parse_response(&json!({
+                "content": [
+                    {"type": "text", "text": "Looks "},
+                    {"type": "tool_use", "name": "ignored"},
+                    {"type": "text", "text": "good"}
+                ],
+                "usage": {"input_tokens": 1_000_000, "output_tokens": 0}
+            }))
+            .unwrap();
+        assert_eq!(response.text, "Looks good");
+        assert_eq!(response.tokens, Some(1_000_000));
+        assert_eq!(response.cost_usd, Some(3.0));
+        assert!(provider.parse_response(&json!({"error": "bad"})).is_err());
+    }
+
+    #[test]
+    fn test_openai_request_and_response() {
+        let body = OpenAiProvider::request_body(&request(), "llama3.1");
+        assert_eq!(body["messages"][0]["role"], "system");
+        assert_eq!(body["messages"][1]["content"], "Review the diff");
+        assert_eq!(body["temperature"], 0.5);
+        assert!(body.get("max_tokens").is_none());
+
+        let response = OpenAiProvider::parse_response(&json!({
+            "choices": [{"message": {"role": "assistant", "content": "Looks good"}}],
+            "usage": {"prompt_tokens": 10, "completion_tokens": 5, "total_tokens": 15}
+        }))
+        .unwrap();
+        assert_eq!(response.text, "Looks good");
+        assert_eq!(response.tokens, Some(15));
+        assert_eq!(response.cost_usd, None);
+        assert!(OpenAiProvider::parse_response(&json!({"choices": []})).is_err());
+    }
+}
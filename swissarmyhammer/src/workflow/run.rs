@@ -110,6 +110,12 @@ impl WorkflowRun {
         self.status = WorkflowRunStatus::Failed;
         self.completed_at = Some(chrono::Utc::now());
     }
+
+    /// Mark the run as cancelled
+    pub fn cancel(&mut self) {
+        self.status = WorkflowRunStatus::Cancelled;
+        self.completed_at = Some(chrono::Utc::now());
+    }
 }
 
 #[cfg(test)]
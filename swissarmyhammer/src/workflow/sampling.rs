@@ -0,0 +1,73 @@
+//! Prompt execution through the connected MCP client's model
+//!
+//! An action written with `backend="sampling"` sends its rendered prompt to
+//! the model of the MCP client the workflow was started from, using an MCP
+//! sampling (`sampling/createMessage`) request, through the
+//! [`SamplingProvider`](crate::workflow::SamplingProvider):
+//!
+//! ```text
+//! Execute prompt "summarize" with file="notes.md" backend="sampling"
+//! ```
+//!
+//! The MCP server registers a [`PromptSampler`] with [`set_prompt_sampler`]
+//! when a client that supports sampling connects. Sampling actions fail when
+//! no sampler is registered, for example in workflows run from the command
+//! line.
+
+use crate::error::Result;
+use std::sync::{Arc, RwLock};
+
+/// Output token limit sent with sampling and Anthropic API requests when the
+/// action sets none
+///
+/// These requests must name a limit, unlike the Claude CLI.
+pub const DEFAULT_SAMPLING_MAX_TOKENS: u32 = 4096;
+
+/// The sampler sampling actions use, if a client that supports it is connected
+static PROMPT_SAMPLER: RwLock<Option<Arc<dyn PromptSampler>>> = RwLock::new(None);
+
+/// A rendered prompt to generate a response for
+#[derive(Debug, Clone, PartialEq)]
+pub struct SamplingRequest {
+    /// The rendered prompt, sent as the user message
+    pub prompt: String,
+    /// System prompt for the model
+    pub system_prompt: Option<String>,
+    /// Model name, sent to the client as a hint it may ignore
+    pub model: Option<String>,
+    /// Sampling temperature between 0.0 and 1.0
+    pub temperature: Option<f32>,
+    /// Maximum number of output tokens
+    pub max_tokens: u32,
+}
+
+/// A response generated for a [`SamplingRequest`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SamplingResponse {
+    /// The generated text
+    pub text: String,
+    /// The model the client used
+    pub model: String,
+}
+
+/// Generates responses with a model the server does not run itself
+#[async_trait::async_trait]
+pub trait PromptSampler: Send + Sync {
+    /// Generate a response to a prompt
+    async fn create_message(&self, request: SamplingRequest) -> Result<SamplingResponse>;
+}
+
+/// Register the sampler sampling actions use, or remove it with `None`
+pub fn set_prompt_sampler(sampler: Option<Arc<dyn PromptSampler>>) {
+    *PROMPT_SAMPLER
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = sampler;
+}
+
+/// The registered sampler, if any
+pub fn prompt_sampler() -> Option<Arc<dyn PromptSampler>> {
+    PROMPT_SAMPLER
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()
+}
@@ -0,0 +1,215 @@
+//! Workflow templates for scaffolding new workflows
+//!
+//! A workflow template is a markdown file whose front matter declares
+//! `arguments` like a prompt, and whose body is a complete workflow file,
+//! front matter and mermaid diagram included, written as a Liquid template.
+//! Rendering fills in the arguments plus `name`, the name of the new workflow.
+//!
+//! Templates are resolved like workflows: builtin templates first, then
+//! `~/.swissarmyhammer/workflow-templates`, then local
+//! `.swissarmyhammer/workflow-templates` directories. Liquid the new workflow
+//! should keep for run time, such as `{{ file }}` in an action, is wrapped in
+//! `{% raw %}...{% endraw %}`.
+
+use crate::file_loader::{FileSource, VirtualFileSystem};
+use crate::workflow::MermaidParser;
+use crate::{ArgumentSpec, Prompt, PromptLoader, Result, SwissArmyHammerError};
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+
+// Include the generated builtin workflow templates
+include!(concat!(env!("OUT_DIR"), "/builtin_workflow_templates.rs"));
+
+/// Template used by `flow new` when none is named
+pub const DEFAULT_WORKFLOW_TEMPLATE: &str = "basic";
+
+/// Template a new workflow file is generated from
+#[derive(Debug, Clone)]
+pub struct WorkflowTemplate {
+    /// Where the template was loaded from
+    pub source: FileSource,
+    /// File the template was loaded from
+    pub path: PathBuf,
+    prompt: Prompt,
+}
+
+impl WorkflowTemplate {
+    /// Parse a template from its file content
+    pub fn parse(name: &str, content: &str, source: FileSource, path: PathBuf) -> Result<Self> {
+        let prompt = PromptLoader::new().load_from_string(name, content)?;
+        Ok(Self {
+            source,
+            path,
+            prompt,
+        })
+    }
+
+    /// Name of the template
+    pub fn name(&self) -> &str {
+        &self.prompt.name
+    }
+
+    /// Description of the kind of workflow the template generates
+    pub fn description(&self) -> Option<&str> {
+        self.prompt.description.as_deref()
+    }
+
+    /// Values the template asks for, besides the workflow name
+    pub fn arguments(&self) -> impl Iterator<Item = &ArgumentSpec> {
+        self.prompt
+            .arguments
+            .iter()
+            .filter(|argument| argument.name != "name")
+    }
+
+    /// Render the workflow file for a new workflow
+    ///
+    /// Arguments not given take their defaults. The result is parsed as a
+    /// workflow, so a template that generates a broken state machine is an
+    /// error here rather than when the workflow first runs.
+    pub fn render(&self, workflow_name: &str, args: &HashMap<String, String>) -> Result<String> {
+        let mut args = args.clone();
+        args.insert("name".to_string(), workflow_name.to_string());
+        let content = self.prompt.render(&args)?;
+
+        MermaidParser::parse(&content, workflow_name).map_err(|e| {
+            SwissArmyHammerError::Other(format!(
+                "Template '{}' generated an invalid workflow: {e}",
+                self.name()
+            ))
+        })?;
+        Ok(content)
+    }
+}
+
+/// Workflow templates resolved from builtin, user and local directories
+#[derive(Debug, Clone, Default)]
+pub struct WorkflowTemplates {
+    templates: BTreeMap<String, WorkflowTemplate>,
+}
+
+impl WorkflowTemplates {
+    /// Load every template, later sources overriding earlier ones
+    pub fn load() -> Result<Self> {
+        let mut vfs = VirtualFileSystem::new("workflow-templates");
+        for (name, content) in get_builtin_workflow_templates() {
+            vfs.add_builtin(name, content);
+        }
+        vfs.load_all()?;
+
+        let mut templates = Self::default();
+        for file in vfs.list() {
+            match WorkflowTemplate::parse(
+                &file.name,
+                &file.content,
+                file.source.clone(),
+                file.path.clone(),
+            ) {
+                Ok(template) => templates.add(template),
+                Err(e) => tracing::warn!("Skipping workflow template '{}': {e}", file.name),
+            }
+        }
+        Ok(templates)
+    }
+
+    /// Add a template, replacing any template with the same name
+    pub fn add(&mut self, template: WorkflowTemplate) {
+        self.templates.insert(template.name().to_string(), template);
+    }
+
+    /// Get a template by name
+    pub fn get(&self, name: &str) -> Result<&WorkflowTemplate> {
+        self.templates.get(name).ok_or_else(|| {
+            let available: Vec<&str> = self.templates.keys().map(String::as_str).collect();
+            SwissArmyHammerError::Other(format!(
+                "Workflow template '{name}' not found (available: {})",
+                available.join(", ")
+            ))
+        })
+    }
+
+    /// All templates, ordered by name
+    pub fn list(&self) -> impl Iterator<Item = &WorkflowTemplate> {
+        self.templates.values()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn builtin(name: &str) -> WorkflowTemplate {
+        let (_, content) = get_builtin_workflow_templates()
+            .into_iter()
+            .find(|(builtin, _)| *builtin == name)
+            .unwrap();
+        WorkflowTemplate::parse(name, content, FileSource::Builtin, PathBuf::new()).unwrap()
+    }
+
+    #[test]
+    fn test_builtin_templates_render_valid_workflows() {
+        for (name, _) in get_builtin_workflow_templates() {
+            let content = builtin(name).render("generated", &HashMap::new()).unwrap();
+            assert!(content.starts_with("---\n"), "{name} has no front matter");
+        }
+
+        let args = HashMap::from([("review_prompt".to_string(), "review/code".to_string())]);
+        let content = builtin("review-loop").render("code-loop", &args).unwrap();
+        assert!(content.contains("title: Review Loop"));
+        assert!(content.contains("- review: execute prompt \"review/code\""));
+        assert!(content.contains("log \"Starting code-loop\""));
+    }
+
+    #[test]
+    fn test_template_arguments_and_errors() {
+        let template = WorkflowTemplate::parse(
+            "custom",
+            r#"---
+description: Custom
+arguments:
+  - name: name
+  - name: greeting
+    required: true
+---
+## States
+
+```mermaid
+stateDiagram-v2
+    [*] --> {{ greeting }}
+    {{ greeting }} --> [*]
+```
+
+## Actions
+
+- {{ greeting }}: log "{% raw %}{{ who }}{% endraw %} from {{ name }}"
+"#,
+            FileSource::Local,
+            PathBuf::from("custom.md"),
+        )
+        .unwrap();
+        let names: Vec<&str> = template.arguments().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, vec!["greeting"]);
+
+        assert!(template.render("hi", &HashMap::new()).is_err());
+        let args = HashMap::from([("greeting".to_string(), "hello".to_string())]);
+        let content = template.render("hi", &args).unwrap();
+        assert!(content.contains("- hello: log \"{{ who }} from hi\""));
+
+        // A template that generates no state machine is rejected
+        let notes = WorkflowTemplate::parse(
+            "notes",
+            "# Notes for {{ name }}",
+            FileSource::User,
+            PathBuf::from("notes.md"),
+        )
+        .unwrap();
+        assert!(notes.render("hi", &HashMap::new()).is_err());
+
+        let mut templates = WorkflowTemplates::default();
+        templates.add(template);
+        templates.add(notes);
+        assert!(templates.get("custom").is_ok());
+        let error = templates.get("missing").unwrap_err().to_string();
+        assert!(error.contains("available: custom, notes"));
+    }
+}
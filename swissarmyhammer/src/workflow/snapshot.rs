@@ -0,0 +1,302 @@
+//! Snapshots of the prompts a workflow renders
+//!
+//! A snapshot holds the text every prompt in a workflow renders to for a given
+//! set of variables. Recording snapshots and checking them later catches
+//! template changes that alter prompts unintentionally, for example while
+//! refactoring a shared partial.
+//!
+//! Snapshots are stored one file per state, as `<dir>/<workflow>/<state>.md`,
+//! so they read and diff like the prompts themselves. Every state that
+//! executes a prompt is rendered, not only the states one run would visit.
+//! Prompts run by sub-workflows belong to the sub-workflow's own snapshot.
+
+use crate::workflow::{parse_action_from_description_with_context, PromptAction, Workflow};
+use crate::{PromptLibrary, Result, SwissArmyHammerError};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directory snapshots are stored in, relative to the project root
+pub const DEFAULT_SNAPSHOT_DIR: &str = ".swissarmyhammer/snapshots";
+
+/// Extension of snapshot files
+const SNAPSHOT_EXTENSION: &str = "md";
+
+/// The rendered prompts of one workflow, by state
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkflowSnapshot {
+    /// Workflow name
+    pub workflow: String,
+    /// Rendered prompt text for each state that executes a prompt
+    pub prompts: BTreeMap<String, String>,
+}
+
+/// A difference between a stored snapshot and the current renders
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SnapshotMismatch {
+    /// The state renders different text than stored
+    Changed {
+        /// State ID
+        state: String,
+        /// Stored text
+        expected: String,
+        /// Text rendered now
+        actual: String,
+    },
+    /// The state renders a prompt but none is stored
+    Added {
+        /// State ID
+        state: String,
+    },
+    /// A prompt is stored for a state that no longer renders one
+    Removed {
+        /// State ID
+        state: String,
+    },
+}
+
+impl WorkflowSnapshot {
+    /// Render every prompt the workflow executes
+    ///
+    /// Action descriptions and prompt arguments are resolved against
+    /// `context` the same way they are when the workflow runs.
+    pub fn render(
+        workflow: &Workflow,
+        context: &HashMap<String, Value>,
+        library: &PromptLibrary,
+    ) -> Result<Self> {
+        let mut prompts = BTreeMap::new();
+        for (state_id, state) in &workflow.states {
+            let action = parse_action_from_description_with_context(&state.description, context)
+                .map_err(|e| {
+                    SwissArmyHammerError::Other(format!(
+                        "Failed to parse action of state '{state_id}': {e}"
+                    ))
+                })?;
+            let Some(action) = action else {
+                continue;
+            };
+            let Some(prompt) = action.as_any().downcast_ref::<PromptAction>() else {
+                continue;
+            };
+
+            let arguments = prompt.substitute_variables(context);
+            let rendered = library
+                .render_prompt(&prompt.prompt_name, &arguments)
+                .map_err(|e| {
+                    SwissArmyHammerError::Other(format!(
+                        "Failed to render prompt '{}' of state '{state_id}': {e}",
+                        prompt.prompt_name
+                    ))
+                })?;
+            prompts.insert(state_id.to_string(), rendered);
+        }
+
+        Ok(Self {
+            workflow: workflow.name.to_string(),
+            prompts,
+        })
+    }
+
+    /// Directory holding the snapshot of a workflow
+    pub fn directory(dir: &Path, workflow: &str) -> PathBuf {
+        dir.join(workflow)
+    }
+
+    /// Load the stored snapshot of a workflow, if one was recorded
+    pub fn load(dir: &Path, workflow: &str) -> Result<Option<Self>> {
+        let directory = Self::directory(dir, workflow);
+        if !directory.is_dir() {
+            return Ok(None);
+        }
+
+        let mut prompts = BTreeMap::new();
+        for entry in fs::read_dir(&directory)? {
+            let path = entry?.path();
+            if !is_snapshot_file(&path) {
+                continue;
+            }
+            if let Some(state) = path.file_stem().and_then(|stem| stem.to_str()) {
+                prompts.insert(state.to_string(), fs::read_to_string(&path)?);
+            }
+        }
+
+        Ok(Some(Self {
+            workflow: workflow.to_string(),
+            prompts,
+        }))
+    }
+
+    /// Store the snapshot, replacing any earlier snapshot of the workflow
+    pub fn save(&self, dir: &Path) -> Result<PathBuf> {
+        let directory = Self::directory(dir, &self.workflow);
+        fs::create_dir_all(&directory)?;
+
+        for entry in fs::read_dir(&directory)? {
+            let path = entry?.path();
+            let stale = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .is_some_and(|state| !self.prompts.contains_key(state));
+            if is_snapshot_file(&path) && stale {
+                fs::remove_file(&path)?;
+            }
+        }
+        for (state, rendered) in &self.prompts {
+            let path = directory.join(format!("{state}.{SNAPSHOT_EXTENSION}"));
+            fs::write(path, rendered)?;
+        }
+
+        Ok(directory)
+    }
+
+    /// Differences between this snapshot and the stored `expected` one
+    pub fn compare(&self, expected: &WorkflowSnapshot) -> Vec<SnapshotMismatch> {
+        let mut mismatches = Vec::new();
+        for (state, actual) in &self.prompts {
+            match expected.prompts.get(state) {
+                Some(stored) if stored == actual => {}
+                Some(stored) => mismatches.push(SnapshotMismatch::Changed {
+                    state: state.clone(),
+                    expected: stored.clone(),
+                    actual: actual.clone(),
+                }),
+                None => mismatches.push(SnapshotMismatch::Added {
+                    state: state.clone(),
+                }),
+            }
+        }
+        for state in expected.prompts.keys() {
+            if !self.prompts.contains_key(state) {
+                mismatches.push(SnapshotMismatch::Removed {
+                    state: state.clone(),
+                });
+            }
+        }
+        mismatches
+    }
+}
+
+impl fmt::Display for SnapshotMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotMismatch::Changed {
+                state,
+                expected,
+                actual,
+            } => {
+                let expected_lines: Vec<&str> = expected.lines().collect();
+                let actual_lines: Vec<&str> = actual.lines().collect();
+                let line = expected_lines
+                    .iter()
+                    .zip(&actual_lines)
+                    .position(|(expected, actual)| expected != actual)
+                    .unwrap_or_else(|| expected_lines.len().min(actual_lines.len()));
+                writeln!(f, "{state}: prompt changed at line {}", line + 1)?;
+                writeln!(
+                    f,
+                    "  - {}",
+                    expected_lines
+                        .get(line)
+                        .copied()
+                        .unwrap_or("<end of prompt>")
+                )?;
+                write!(
+                    f,
+                    "  + {}",
+                    actual_lines.get(line).copied().unwrap_or("<end of prompt>")
+                )
+            }
+            SnapshotMismatch::Added { state } => {
+                write!(f, "{state}: renders a prompt that has no snapshot")
+            }
+            SnapshotMismatch::Removed { state } => {
+                write!(f, "{state}: has a snapshot but no longer renders a prompt")
+            }
+        }
+    }
+}
+
+fn is_snapshot_file(path: &Path) -> bool {
+    path.is_file() && path.extension().and_then(|ext| ext.to_str()) == Some(SNAPSHOT_EXTENSION)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workflow::MermaidParser;
+    use crate::Prompt;
+    use tempfile::TempDir;
+
+    fn workflow() -> Workflow {
+        MermaidParser::parse(
+            r#"```mermaid
+stateDiagram-v2
+    [*] --> greet
+    greet --> log
+    log --> [*]
+```
+
+## Actions
+
+- greet: Execute prompt "greeting" with name="${user}"
+- log: Log "done"
+"#,
+            "hello",
+        )
+        .unwrap()
+    }
+
+    fn library(template: &str) -> PromptLibrary {
+        let mut library = PromptLibrary::new();
+        library.add(Prompt::new("greeting", template)).unwrap();
+        library
+    }
+
+    fn context() -> HashMap<String, Value> {
+        HashMap::from([("user".to_string(), Value::String("Ada".to_string()))])
+    }
+
+    #[test]
+    fn test_render_only_prompt_states() {
+        let snapshot =
+            WorkflowSnapshot::render(&workflow(), &context(), &library("Hello {{ name }}!"))
+                .unwrap();
+        assert_eq!(snapshot.workflow, "hello");
+        assert_eq!(
+            snapshot.prompts,
+            BTreeMap::from([("greet".to_string(), "Hello Ada!".to_string())])
+        );
+    }
+
+    #[test]
+    fn test_save_load_and_compare() {
+        let temp = TempDir::new().unwrap();
+        let stored =
+            WorkflowSnapshot::render(&workflow(), &context(), &library("Hello {{ name }}!"))
+                .unwrap();
+        fs::create_dir_all(temp.path().join("hello")).unwrap();
+        fs::write(temp.path().join("hello").join("old.md"), "stale").unwrap();
+        stored.save(temp.path()).unwrap();
+
+        let loaded = WorkflowSnapshot::load(temp.path(), "hello")
+            .unwrap()
+            .unwrap();
+        assert_eq!(loaded, stored);
+        assert!(WorkflowSnapshot::load(temp.path(), "other")
+            .unwrap()
+            .is_none());
+
+        let current =
+            WorkflowSnapshot::render(&workflow(), &context(), &library("Hi {{ name }}!")).unwrap();
+        let mismatches = current.compare(&loaded);
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(
+            mismatches[0].to_string(),
+            "greet: prompt changed at line 1\n  - Hello Ada!\n  + Hi Ada!"
+        );
+        assert!(stored.compare(&loaded).is_empty());
+    }
+}
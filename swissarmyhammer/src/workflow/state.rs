@@ -188,6 +188,22 @@ pub struct State {
     pub metadata: HashMap<String, String>,
 }
 
+impl State {
+    /// Names of the locks held while this state runs
+    pub fn locks(&self) -> Vec<&str> {
+        self.metadata
+            .get(crate::workflow::LOCK_METADATA_KEY)
+            .map(|locks| {
+                locks
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|lock| !lock.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
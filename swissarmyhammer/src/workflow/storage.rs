@@ -1,6 +1,9 @@
 //! Storage abstractions and implementations for workflows and workflow runs
 
 use crate::file_loader::{FileSource, VirtualFileSystem};
+use crate::fs_utils::{FileSystem, StdFileSystem};
+use crate::packages::check_installed_packages;
+use crate::security::{validate_path, PathPolicy};
 use crate::workflow::{MermaidParser, Workflow, WorkflowName, WorkflowRun, WorkflowRunId};
 use crate::{Result, SwissArmyHammerError};
 use base64::{engine::general_purpose, Engine as _};
@@ -15,6 +18,8 @@ include!(concat!(env!("OUT_DIR"), "/builtin_workflows.rs"));
 pub struct WorkflowResolver {
     /// Track the source of each workflow by name
     pub workflow_sources: HashMap<WorkflowName, FileSource>,
+    /// Track the file each workflow was loaded from by name
+    pub workflow_paths: HashMap<WorkflowName, PathBuf>,
     /// Virtual file system for managing workflows
     vfs: VirtualFileSystem,
 }
@@ -24,6 +29,7 @@ impl WorkflowResolver {
     pub fn new() -> Self {
         Self {
             workflow_sources: HashMap::new(),
+            workflow_paths: HashMap::new(),
             vfs: VirtualFileSystem::new("workflows"),
         }
     }
@@ -38,7 +44,11 @@ impl WorkflowResolver {
     /// 1. Builtin workflows (least specific, embedded in binary or resource directories)
     /// 2. User workflows from ~/.swissarmyhammer/workflows
     /// 3. Local workflows from .swissarmyhammer directories (most specific)
+    ///
+    /// Fails when an installed package's dependencies are not met.
     pub fn load_all_workflows(&mut self, storage: &mut dyn WorkflowStorageBackend) -> Result<()> {
+        check_installed_packages()?;
+
         // Load builtin workflows first (least precedence)
         self.load_builtin_workflows()?;
 
@@ -78,6 +88,8 @@ impl WorkflowResolver {
                     // Track the workflow source
                     self.workflow_sources
                         .insert(workflow.name.clone(), file.source.clone());
+                    self.workflow_paths
+                        .insert(workflow.name.clone(), file.path.clone());
 
                     // Store the workflow
                     storage.store_workflow(workflow)?;
@@ -124,6 +136,7 @@ impl Default for WorkflowResolver {
 
 /// Helper function to walk a directory and load JSON files
 fn load_json_files_from_directory<T, F>(
+    fs: &dyn FileSystem,
     directory: &Path,
     filename_filter: Option<&str>,
     mut loader: F,
@@ -134,16 +147,25 @@ where
 {
     let mut items = Vec::new();
 
-    if !directory.exists() {
+    if !fs.exists(directory) {
         return Ok(items);
     }
 
-    for entry in walkdir::WalkDir::new(directory)
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
-        let path = entry.path();
-        if path.is_file() {
+    let mut pending = vec![directory.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        for path in fs.read_dir(&dir).unwrap_or_default() {
+            if fs.is_dir(&path) {
+                // Symlinked directories are not followed, so links can't loop
+                if !path.is_symlink() {
+                    pending.push(path);
+                }
+                continue;
+            }
+            if let Err(e) = validate_path(&path, directory, PathPolicy::global()) {
+                tracing::warn!("Skipping file '{}' - {}", path.display(), e);
+                continue;
+            }
+
             // Check filename filter if provided
             if let Some(filter) = filename_filter {
                 if path.file_name().and_then(|s| s.to_str()) != Some(filter) {
@@ -152,9 +174,9 @@ where
             }
 
             // Try to load and parse the JSON file
-            if let Ok(content) = std::fs::read_to_string(path) {
+            if let Ok(content) = fs.read_to_string(&path) {
                 if let Ok(item) = serde_json::from_str::<T>(&content) {
-                    if loader(item, path) {
+                    if loader(item, &path) {
                         // Loader returned true, meaning we should keep this item
                         if let Ok(item) = serde_json::from_str::<T>(&content) {
                             items.push(item);
@@ -408,15 +430,16 @@ impl FileSystemWorkflowStorage {
         // Try to find a local .swissarmyhammer directory first
         let current_dir = std::env::current_dir()?;
         let local_dir = current_dir.join(".swissarmyhammer").join("workflows");
+        let file_name = format!("{}.mermaid", name.as_str());
         if local_dir.exists() {
-            return Ok(local_dir.join(format!("{}.mermaid", name.as_str())));
+            return validate_path(Path::new(&file_name), &local_dir, PathPolicy::global());
         }
 
         // Fall back to user directory
-        if let Some(home) = dirs::home_dir() {
+        if let Some(home) = crate::directory_utils::home_dir() {
             let user_dir = home.join(".swissarmyhammer").join("workflows");
             std::fs::create_dir_all(&user_dir)?;
-            return Ok(user_dir.join(format!("{}.mermaid", name.as_str())));
+            return validate_path(Path::new(&file_name), &user_dir, PathPolicy::global());
         }
 
         Err(SwissArmyHammerError::Storage(
@@ -438,13 +461,14 @@ impl WorkflowStorageBackend for FileSystemWorkflowStorage {
         // In practice, this would serialize back to mermaid format
         let content = serde_json::to_string_pretty(&workflow)?;
         std::fs::write(&path, content)?;
+        crate::directory_utils::invalidate_cached_indexes(&[path.clone()]);
 
         // Update cache and source tracking
         self.cache.insert(workflow.name.clone(), workflow.clone());
 
         // Determine source based on storage location
         let source = if path.starts_with(
-            dirs::home_dir()
+            crate::directory_utils::home_dir()
                 .unwrap_or_default()
                 .join(".swissarmyhammer"),
         ) {
@@ -478,7 +502,8 @@ impl WorkflowStorageBackend for FileSystemWorkflowStorage {
         // Find the workflow file in the appropriate directory
         let path = self.workflow_storage_path(name)?;
         if path.exists() {
-            std::fs::remove_file(path)?;
+            std::fs::remove_file(&path)?;
+            crate::directory_utils::invalidate_cached_indexes(&[path]);
         }
 
         // Remove from cache and source tracking
@@ -512,20 +537,30 @@ impl WorkflowStorageBackend for FileSystemWorkflowStorage {
 pub struct FileSystemWorkflowRunStorage {
     base_path: PathBuf,
     cache: dashmap::DashMap<WorkflowRunId, WorkflowRun>,
+    fs: Arc<dyn FileSystem>,
 }
 
 impl FileSystemWorkflowRunStorage {
     /// Create a new file system workflow run storage
     pub fn new(base_path: impl AsRef<Path>) -> Result<Self> {
+        Self::with_fs(base_path, Arc::new(StdFileSystem))
+    }
+
+    /// Create a workflow run storage on a specific file system
+    ///
+    /// Pass an [`OverlayFileSystem`](crate::fs_utils::OverlayFileSystem) to
+    /// keep runs in memory instead of writing them to disk.
+    pub fn with_fs(base_path: impl AsRef<Path>, fs: Arc<dyn FileSystem>) -> Result<Self> {
         let base_path = base_path.as_ref().to_path_buf();
 
-        if !base_path.exists() {
-            std::fs::create_dir_all(&base_path)?;
+        if !fs.exists(&base_path) {
+            fs.create_dir_all(&base_path)?;
         }
 
         let storage = Self {
             base_path,
             cache: dashmap::DashMap::new(),
+            fs,
         };
 
         // Load existing runs into cache
@@ -539,13 +574,14 @@ impl FileSystemWorkflowRunStorage {
         self.cache.clear();
 
         let runs_dir = self.base_path.join("runs");
-        if !runs_dir.exists() {
-            std::fs::create_dir_all(&runs_dir)?;
+        if !self.fs.exists(&runs_dir) {
+            self.fs.create_dir_all(&runs_dir)?;
         }
 
         // Use the helper function to load workflow runs
         let cache_ref = &self.cache;
         load_json_files_from_directory::<WorkflowRun, _>(
+            &*self.fs,
             &runs_dir,
             Some("run.json"),
             |run, _path| {
@@ -572,13 +608,13 @@ impl FileSystemWorkflowRunStorage {
 impl WorkflowRunStorageBackend for FileSystemWorkflowRunStorage {
     fn store_run(&mut self, run: &WorkflowRun) -> Result<()> {
         let run_dir = self.run_dir(&run.id);
-        if !run_dir.exists() {
-            std::fs::create_dir_all(&run_dir)?;
+        if !self.fs.exists(&run_dir) {
+            self.fs.create_dir_all(&run_dir)?;
         }
 
         let path = self.run_path(&run.id);
         let content = serde_json::to_string_pretty(run)?;
-        std::fs::write(&path, content)?;
+        self.fs.write(&path, &content)?;
 
         self.cache.insert(run.id, run.clone());
         Ok(())
@@ -590,11 +626,12 @@ impl WorkflowRunStorageBackend for FileSystemWorkflowRunStorage {
         }
 
         let path = self.run_path(id);
-        if !path.exists() {
+        if !self.fs.exists(&path) {
             return Err(SwissArmyHammerError::WorkflowRunNotFound(format!("{id:?}")));
         }
 
-        let content = std::fs::read_to_string(&path)?;
+        validate_path(&path, &self.base_path, PathPolicy::global())?;
+        let content = self.fs.read_to_string(&path)?;
         let run: WorkflowRun = serde_json::from_str(&content)?;
         self.cache.insert(*id, run.clone());
 
@@ -611,11 +648,11 @@ impl WorkflowRunStorageBackend for FileSystemWorkflowRunStorage {
 
     fn remove_run(&mut self, id: &WorkflowRunId) -> Result<()> {
         let run_dir = self.run_dir(id);
-        if !run_dir.exists() {
+        if !self.fs.exists(&run_dir) {
             return Err(SwissArmyHammerError::WorkflowRunNotFound(format!("{id:?}")));
         }
 
-        std::fs::remove_dir_all(run_dir)?;
+        self.fs.remove_dir_all(&run_dir)?;
         self.cache.remove(id);
         Ok(())
     }
@@ -650,6 +687,7 @@ impl WorkflowRunStorageBackend for FileSystemWorkflowRunStorage {
         Box::new(FileSystemWorkflowRunStorage {
             base_path: self.base_path.clone(),
             cache: self.cache.clone(),
+            fs: self.fs.clone(),
         })
     }
 }
@@ -683,7 +721,7 @@ impl WorkflowStorage {
     /// Create with file system backends using hierarchical loading
     pub fn file_system() -> Result<Self> {
         // Use a user directory as base path for workflow runs
-        let base_path = dirs::home_dir()
+        let base_path = crate::directory_utils::home_dir()
             .ok_or_else(|| {
                 SwissArmyHammerError::Storage(
                     "Cannot find home directory. Please ensure HOME environment variable is set"
@@ -698,6 +736,11 @@ impl WorkflowStorage {
         ))
     }
 
+    /// Replace the backend workflows are read from, keeping the run backend
+    pub fn set_workflow_backend(&mut self, workflow_backend: Arc<dyn WorkflowStorageBackend>) {
+        self.workflow_backend = workflow_backend;
+    }
+
     /// Store a workflow
     pub fn store_workflow(&mut self, workflow: Workflow) -> Result<()> {
         Arc::get_mut(&mut self.workflow_backend)
@@ -897,7 +940,7 @@ impl WorkflowStorageBackend for CompressedWorkflowStorage {
 impl WorkflowStorage {
     /// Create with compressed file system backends
     pub fn compressed_file_system() -> Result<Self> {
-        let base_path = dirs::home_dir()
+        let base_path = crate::directory_utils::home_dir()
             .ok_or_else(|| {
                 SwissArmyHammerError::Storage(
                     "Cannot find home directory. Please ensure HOME environment variable is set"
@@ -1000,6 +1043,28 @@ mod tests {
         assert!(storage.get_run(&run.id).is_err());
     }
 
+    #[test]
+    fn test_filesystem_run_storage_on_overlay() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let overlay = Arc::new(crate::fs_utils::OverlayFileSystem::default());
+        let mut storage =
+            FileSystemWorkflowRunStorage::with_fs(temp_dir.path(), overlay.clone()).unwrap();
+        let run = WorkflowRun::new(create_test_workflow());
+
+        storage.store_run(&run).unwrap();
+        assert!(!temp_dir.path().join("runs").exists());
+        assert!(overlay.is_file(&storage.run_path(&run.id)));
+
+        // A fresh storage on the same overlay loads the simulated run
+        let reloaded =
+            FileSystemWorkflowRunStorage::with_fs(temp_dir.path(), overlay.clone()).unwrap();
+        assert_eq!(reloaded.get_run(&run.id).unwrap().id, run.id);
+
+        storage.remove_run(&run.id).unwrap();
+        assert!(!overlay.exists(&storage.run_dir(&run.id)));
+        assert!(overlay.changes().len() >= 3);
+    }
+
     #[test]
     fn test_cleanup_old_runs() {
         let mut storage = MemoryWorkflowRunStorage::new();
@@ -36,6 +36,9 @@ fn test_prompt_with_arguments() {
             required: true,
             default: None,
             type_hint: Some("string".to_string()),
+            choices: None,
+            completion: None,
+            source: None,
         })
         .add_argument(ArgumentSpec {
             name: "name".to_string(),
@@ -43,6 +46,9 @@ fn test_prompt_with_arguments() {
             required: false,
             default: Some("Friend".to_string()),
             type_hint: Some("string".to_string()),
+            choices: None,
+            completion: None,
+            source: None,
         });
 
     // Test with all arguments provided
@@ -69,6 +75,9 @@ fn test_missing_required_argument() {
         required: true,
         default: None,
         type_hint: None,
+        choices: None,
+        completion: None,
+        source: None,
     });
 
     let args = HashMap::new();
@@ -293,6 +302,9 @@ fn test_example_usage() {
             required: true,
             default: None,
             type_hint: Some("string".to_string()),
+            choices: None,
+            completion: None,
+            source: None,
         })
         .add_argument(ArgumentSpec {
             name: "place".to_string(),
@@ -300,6 +312,9 @@ fn test_example_usage() {
             required: false,
             default: Some("our application".to_string()),
             type_hint: Some("string".to_string()),
+            choices: None,
+            completion: None,
+            source: None,
         });
 
     // Add to library